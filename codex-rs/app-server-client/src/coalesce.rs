@@ -0,0 +1,250 @@
+//! Merging of queued server events for consumers that fall behind.
+//!
+//! When a surface drains events more slowly than the server produces them, for example a TUI
+//! redrawing a slow terminal under fast model output, events pile up in the client queue. Before
+//! handing out the next event, the clients fold everything already queued into a shorter
+//! sequence: adjacent text deltas for the same item are concatenated, and state snapshots (thread
+//! status, token usage, the turn diff) replace earlier ones for the same subject. The consumer then
+//! catches up to the latest state in a few events instead of replaying every intermediate one.
+//! Rolling rate-limit updates are sparse, so they are always delivered individually.
+
+use std::collections::VecDeque;
+
+use codex_app_server::in_process::InProcessServerEvent;
+use codex_app_server_protocol::ServerNotification;
+
+use crate::AppServerEvent;
+
+/// Upper bound on events folded per refill, so a producer that keeps up with the drain cannot
+/// stall the consumer indefinitely.
+pub(crate) const MAX_COALESCED_EVENTS: usize = 1024;
+
+pub(crate) trait QueuedEvent {
+    fn notification(&self) -> Option<&ServerNotification>;
+    fn notification_mut(&mut self) -> Option<&mut ServerNotification>;
+}
+
+impl QueuedEvent for InProcessServerEvent {
+    fn notification(&self) -> Option<&ServerNotification> {
+        match self {
+            Self::ServerNotification(notification) => Some(notification),
+            _ => None,
+        }
+    }
+
+    fn notification_mut(&mut self) -> Option<&mut ServerNotification> {
+        match self {
+            Self::ServerNotification(notification) => Some(notification),
+            _ => None,
+        }
+    }
+}
+
+impl QueuedEvent for AppServerEvent {
+    fn notification(&self) -> Option<&ServerNotification> {
+        match self {
+            Self::ServerNotification(notification) => Some(notification),
+            _ => None,
+        }
+    }
+
+    fn notification_mut(&mut self) -> Option<&mut ServerNotification> {
+        match self {
+            Self::ServerNotification(notification) => Some(notification),
+            _ => None,
+        }
+    }
+}
+
+/// Appends `event` to `queue`, merging it into the queued events where that loses nothing the
+/// consumer would show.
+pub(crate) fn push_coalesced<E: QueuedEvent>(queue: &mut VecDeque<E>, mut event: E) {
+    let Some(notification) = event.notification_mut() else {
+        queue.push_back(event);
+        return;
+    };
+    if let Some(last) = queue.back_mut().and_then(QueuedEvent::notification_mut)
+        && append_delta(last, notification)
+    {
+        return;
+    }
+    if let Some(key) = snapshot_key(notification) {
+        queue.retain(|queued| queued.notification().and_then(snapshot_key).as_ref() != Some(&key));
+    }
+    queue.push_back(event);
+}
+
+/// Appends `next`'s text to `last` when both are deltas of the same stream.
+fn append_delta(last: &mut ServerNotification, next: &mut ServerNotification) -> bool {
+    let (text, delta) = match (last, next) {
+        (
+            ServerNotification::AgentMessageDelta(last),
+            ServerNotification::AgentMessageDelta(next),
+        ) if last.thread_id == next.thread_id && last.item_id == next.item_id => {
+            (&mut last.delta, &mut next.delta)
+        }
+        (ServerNotification::PlanDelta(last), ServerNotification::PlanDelta(next))
+            if last.thread_id == next.thread_id && last.item_id == next.item_id =>
+        {
+            (&mut last.delta, &mut next.delta)
+        }
+        (
+            ServerNotification::ReasoningSummaryTextDelta(last),
+            ServerNotification::ReasoningSummaryTextDelta(next),
+        ) if last.thread_id == next.thread_id
+            && last.item_id == next.item_id
+            && last.summary_index == next.summary_index =>
+        {
+            (&mut last.delta, &mut next.delta)
+        }
+        (
+            ServerNotification::ReasoningTextDelta(last),
+            ServerNotification::ReasoningTextDelta(next),
+        ) if last.thread_id == next.thread_id
+            && last.item_id == next.item_id
+            && last.content_index == next.content_index =>
+        {
+            (&mut last.delta, &mut next.delta)
+        }
+        (
+            ServerNotification::CommandExecutionOutputDelta(last),
+            ServerNotification::CommandExecutionOutputDelta(next),
+        ) if last.thread_id == next.thread_id && last.item_id == next.item_id => {
+            (&mut last.delta, &mut next.delta)
+        }
+        _ => return false,
+    };
+    text.push_str(delta);
+    true
+}
+
+/// Subject of a notification that carries complete state, so a newer one makes older ones moot.
+#[derive(Debug, PartialEq, Eq)]
+enum SnapshotKey {
+    ThreadStatus(String),
+    TokenUsage(String),
+    TurnDiff { thread_id: String, turn_id: String },
+}
+
+fn snapshot_key(notification: &ServerNotification) -> Option<SnapshotKey> {
+    Some(match notification {
+        ServerNotification::ThreadStatusChanged(notification) => {
+            SnapshotKey::ThreadStatus(notification.thread_id.clone())
+        }
+        ServerNotification::ThreadTokenUsageUpdated(notification) => {
+            SnapshotKey::TokenUsage(notification.thread_id.clone())
+        }
+        ServerNotification::TurnDiffUpdated(notification) => SnapshotKey::TurnDiff {
+            thread_id: notification.thread_id.clone(),
+            turn_id: notification.turn_id.clone(),
+        },
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_app_server_protocol::AgentMessageDeltaNotification;
+    use codex_app_server_protocol::ThreadStatus;
+    use codex_app_server_protocol::ThreadStatusChangedNotification;
+    use codex_app_server_protocol::TurnDiffUpdatedNotification;
+    use pretty_assertions::assert_eq;
+
+    fn agent_delta(item_id: &str, delta: &str) -> AppServerEvent {
+        AppServerEvent::ServerNotification(ServerNotification::AgentMessageDelta(
+            AgentMessageDeltaNotification {
+                thread_id: "thread-1".to_string(),
+                turn_id: "turn-1".to_string(),
+                item_id: item_id.to_string(),
+                delta: delta.to_string(),
+            },
+        ))
+    }
+
+    fn turn_diff(diff: &str) -> AppServerEvent {
+        AppServerEvent::ServerNotification(ServerNotification::TurnDiffUpdated(
+            TurnDiffUpdatedNotification {
+                thread_id: "thread-1".to_string(),
+                turn_id: "turn-1".to_string(),
+                diff: diff.to_string(),
+            },
+        ))
+    }
+
+    fn delta_texts(queue: &VecDeque<AppServerEvent>) -> Vec<String> {
+        queue
+            .iter()
+            .map(|event| match event.notification() {
+                Some(ServerNotification::AgentMessageDelta(notification)) => {
+                    format!("{}:{}", notification.item_id, notification.delta)
+                }
+                Some(ServerNotification::TurnDiffUpdated(notification)) => {
+                    format!("diff:{}", notification.diff)
+                }
+                _ => "other".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn adjacent_deltas_for_the_same_item_are_concatenated() {
+        let mut queue = VecDeque::new();
+        for event in [
+            agent_delta("msg-1", "Hel"),
+            agent_delta("msg-1", "lo"),
+            agent_delta("msg-2", "Bye"),
+            agent_delta("msg-1", "!"),
+        ] {
+            push_coalesced(&mut queue, event);
+        }
+
+        assert_eq!(
+            delta_texts(&queue),
+            vec!["msg-1:Hello", "msg-2:Bye", "msg-1:!"]
+        );
+    }
+
+    #[test]
+    fn newer_snapshots_replace_older_ones_and_move_to_the_end() {
+        let mut queue = VecDeque::new();
+        for event in [
+            turn_diff("a"),
+            agent_delta("msg-1", "x"),
+            turn_diff("b"),
+            AppServerEvent::Lagged { skipped: 2 },
+            turn_diff("c"),
+        ] {
+            push_coalesced(&mut queue, event);
+        }
+
+        assert_eq!(delta_texts(&queue), vec!["msg-1:x", "other", "diff:c"]);
+    }
+
+    #[test]
+    fn snapshots_for_different_threads_are_kept() {
+        let status = |thread_id: &str| {
+            AppServerEvent::ServerNotification(ServerNotification::ThreadStatusChanged(
+                ThreadStatusChangedNotification {
+                    thread_id: thread_id.to_string(),
+                    status: ThreadStatus::Idle,
+                },
+            ))
+        };
+        let mut queue = VecDeque::new();
+        push_coalesced(&mut queue, status("thread-1"));
+        push_coalesced(&mut queue, status("thread-2"));
+        push_coalesced(&mut queue, status("thread-1"));
+
+        let threads: Vec<&str> = queue
+            .iter()
+            .filter_map(|event| match event.notification() {
+                Some(ServerNotification::ThreadStatusChanged(notification)) => {
+                    Some(notification.thread_id.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(threads, vec!["thread-2", "thread-1"]);
+    }
+}
@@ -7,7 +7,8 @@
 //! - Typed caller-provided startup identity (`SessionSource` + client name).
 //! - Typed and raw request/notification dispatch.
 //! - Server request resolution and rejection.
-//! - Event consumption with backpressure signaling ([`InProcessServerEvent::Lagged`]).
+//! - Event consumption with backpressure signaling ([`InProcessServerEvent::Lagged`])
+//!   and coalescing of queued deltas and state updates for slow consumers.
 //! - Bounded graceful shutdown with abort fallback.
 //!
 //! The facade interposes a worker task between the caller and the underlying
@@ -15,9 +16,11 @@
 //! bridging async `mpsc` channels on both sides. Queues are bounded so overload
 //! surfaces as channel-full errors rather than unbounded memory growth.
 
+mod coalesce;
 mod path;
 mod remote;
 
+use std::collections::VecDeque;
 use std::error::Error;
 use std::fmt;
 use std::io::Error as IoError;
@@ -32,6 +35,7 @@ pub use codex_app_server::in_process::InProcessServerEvent;
 use codex_app_server::in_process::InProcessStartArgs;
 use codex_app_server::in_process::LogDbLayer;
 pub use codex_app_server::in_process::StateDbHandle;
+use codex_app_server_protocol::APP_SERVER_PROTOCOL_VERSION;
 use codex_app_server_protocol::ClientInfo;
 use codex_app_server_protocol::ClientNotification;
 use codex_app_server_protocol::ClientRequest;
@@ -353,6 +357,8 @@ impl InProcessClientStartArgs {
                 Some(self.opt_out_notification_methods.clone())
             },
             mcp_server_openai_form_elicitation: self.mcp_server_openai_form_elicitation,
+            notification_verbosity: None,
+            protocol_version: Some(APP_SERVER_PROTOCOL_VERSION),
         };
 
         InitializeParams {
@@ -431,6 +437,8 @@ enum ClientCommand {
 pub struct InProcessAppServerClient {
     command_tx: mpsc::Sender<ClientCommand>,
     event_rx: mpsc::Receiver<InProcessServerEvent>,
+    /// Events already drained from `event_rx` and merged by [`coalesce::push_coalesced`].
+    pending_events: VecDeque<InProcessServerEvent>,
     worker_handle: tokio::task::JoinHandle<()>,
 }
 
@@ -571,6 +579,7 @@ impl InProcessAppServerClient {
         Ok(Self {
             command_tx,
             event_rx,
+            pending_events: VecDeque::new(),
             worker_handle,
         })
     }
@@ -722,8 +731,22 @@ impl InProcessAppServerClient {
     /// Callers are expected to drain this stream promptly. If they fall behind,
     /// the worker emits [`InProcessServerEvent::Lagged`] markers and may reject
     /// pending server requests rather than letting approval flows hang.
+    ///
+    /// Events that have already queued up are coalesced before the first one is
+    /// returned: adjacent text deltas for the same item are concatenated and
+    /// newer state updates replace older ones, so a slow consumer catches up
+    /// without replaying every intermediate event.
     pub async fn next_event(&mut self) -> Option<InProcessServerEvent> {
-        self.event_rx.recv().await
+        if self.pending_events.is_empty() {
+            let event = self.event_rx.recv().await?;
+            coalesce::push_coalesced(&mut self.pending_events, event);
+            while self.pending_events.len() < coalesce::MAX_COALESCED_EVENTS
+                && let Ok(event) = self.event_rx.try_recv()
+            {
+                coalesce::push_coalesced(&mut self.pending_events, event);
+            }
+        }
+        self.pending_events.pop_front()
     }
 
     /// Shuts down worker and in-process runtime with bounded wait.
@@ -734,6 +757,7 @@ impl InProcessAppServerClient {
         let Self {
             command_tx,
             event_rx,
+            pending_events: _pending_events,
             worker_handle,
         } = self;
         let mut worker_handle = worker_handle;
@@ -2084,6 +2108,7 @@ mod tests {
         let mut client = InProcessAppServerClient {
             command_tx,
             event_rx,
+            pending_events: VecDeque::new(),
             worker_handle,
         };
 
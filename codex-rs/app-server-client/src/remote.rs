@@ -21,6 +21,9 @@ use crate::AppServerEvent;
 use crate::RequestResult;
 use crate::SHUTDOWN_TIMEOUT;
 use crate::TypedRequestError;
+use crate::coalesce::MAX_COALESCED_EVENTS;
+use crate::coalesce::push_coalesced;
+use codex_app_server_protocol::APP_SERVER_PROTOCOL_VERSION;
 use codex_app_server_protocol::ClientInfo;
 use codex_app_server_protocol::ClientNotification;
 use codex_app_server_protocol::ClientRequest;
@@ -100,6 +103,8 @@ impl RemoteAppServerConnectArgs {
                 Some(self.opt_out_notification_methods.clone())
             },
             mcp_server_openai_form_elicitation: self.mcp_server_openai_form_elicitation,
+            notification_verbosity: None,
+            protocol_version: Some(APP_SERVER_PROTOCOL_VERSION),
         };
 
         InitializeParams {
@@ -592,10 +597,16 @@ impl RemoteAppServerClient {
     }
 
     pub async fn next_event(&mut self) -> Option<AppServerEvent> {
-        if let Some(event) = self.pending_events.pop_front() {
-            return Some(event);
+        if self.pending_events.is_empty() {
+            let event = self.event_rx.recv().await?;
+            push_coalesced(&mut self.pending_events, event);
+            while self.pending_events.len() < MAX_COALESCED_EVENTS
+                && let Ok(event) = self.event_rx.try_recv()
+            {
+                push_coalesced(&mut self.pending_events, event);
+            }
         }
-        self.event_rx.recv().await
+        self.pending_events.pop_front()
     }
 
     pub async fn shutdown(self) -> IoResult<()> {
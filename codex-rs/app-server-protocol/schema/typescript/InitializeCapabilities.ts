@@ -1,6 +1,7 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NotificationVerbosity } from "./NotificationVerbosity";
 
 /**
  * Client-declared capabilities negotiated during initialize.
@@ -22,4 +23,14 @@ mcpServerOpenaiFormElicitation?: boolean,
  * Exact notification method names that should be suppressed for this
  * connection (for example `thread/started`).
  */
-optOutNotificationMethods?: Array<string> | null, };
+optOutNotificationMethods?: Array<string> | null,
+/**
+ * How much of each notification category this connection receives. Applied on top of
+ * `opt_out_notification_methods`.
+ */
+notificationVerbosity?: NotificationVerbosity | null,
+/**
+ * Newest app-server protocol version the client understands. Clients that omit it are
+ * treated as version 1.
+ */
+protocolVersion?: number | null, };
@@ -17,4 +17,9 @@ platformFamily: string,
  * Operating system for the running app-server target, for example
  * `"macos"`, `"linux"`, or `"windows"`.
  */
-platformOs: string, };
+platformOs: string,
+/**
+ * Protocol version used for this connection: the lower of the client's and the server's.
+ * Servers that predate versioning omit it, which means version 1.
+ */
+protocolVersion: number, };
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How much of a notification category a connection receives. `summary` drops the category's
+ * high-frequency notifications, such as per-token deltas, and keeps the rest; `off` drops them
+ * all. Items still arrive through `item/started` and `item/completed` at every level.
+ */
+export type NotificationLevel = "full" | "summary" | "off";
@@ -0,0 +1,26 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NotificationLevel } from "./NotificationLevel";
+
+/**
+ * Per-category notification levels. Omitted categories are delivered in full.
+ */
+export type NotificationVerbosity = {
+/**
+ * Streamed agent message and plan text.
+ */
+deltas?: NotificationLevel | null,
+/**
+ * Streamed command output and terminal input, file change output and patch updates, and
+ * MCP tool call progress.
+ */
+toolOutput?: NotificationLevel | null,
+/**
+ * Streamed reasoning text and reasoning summaries.
+ */
+reasoning?: NotificationLevel | null,
+/**
+ * Token usage and rate limit updates.
+ */
+metrics?: NotificationLevel | null, };
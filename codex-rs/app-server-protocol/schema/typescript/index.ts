@@ -54,6 +54,8 @@ export type { ModeKind } from "./ModeKind";
 export type { MultiAgentMode } from "./MultiAgentMode";
 export type { NetworkPolicyAmendment } from "./NetworkPolicyAmendment";
 export type { NetworkPolicyRuleAction } from "./NetworkPolicyRuleAction";
+export type { NotificationLevel } from "./NotificationLevel";
+export type { NotificationVerbosity } from "./NotificationVerbosity";
 export type { ParsedCommand } from "./ParsedCommand";
 export type { PathUri } from "./PathUri";
 export type { Personality } from "./Personality";
@@ -18,7 +18,11 @@ id: string,
  * diagnostics so users know which cloud layer needs administrator
  * attention.
  */
-name: string, } | { "type": "user",
+name: string, } | { "type": "team", file: AbsolutePathBuf,
+/**
+ * Whether this layer holds the keys the team config locks.
+ */
+locked: boolean, } | { "type": "user",
 /**
  * This is the path to the user's config.toml file, though it is not
  * guaranteed to exist.
@@ -2,8 +2,8 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type ReviewTarget = { "type": "uncommittedChanges" } | { "type": "baseBranch", branch: string, } | { "type": "commit", sha: string,
+export type ReviewTarget = { "type": "uncommittedChanges" } | { "type": "stagedChanges" } | { "type": "baseBranch", branch: string, } | { "type": "commit", sha: string,
 /**
  * Optional human-readable label (e.g., commit subject) for UIs.
  */
-title: string | null, } | { "type": "custom", instructions: string, };
+title: string | null, } | { "type": "commitRange", range: string, } | { "type": "pullRequest", url: string, } | { "type": "custom", instructions: string, };
@@ -2213,6 +2213,7 @@ mod tests {
                 .is_some_and(|stem| {
                     stem.ends_with("Params")
                         || stem == "InitializeCapabilities"
+                        || stem == "NotificationVerbosity"
                         || matches!(
                             stem,
                             "CollabAgentRef"
@@ -17,6 +17,7 @@ pub use protocol::event_mapping::*;
 pub use protocol::item_builders::*;
 pub use protocol::thread_history::*;
 pub use protocol::thread_history_projection::*;
+pub use protocol::v1::APP_SERVER_PROTOCOL_VERSION;
 pub use protocol::v1::ApplyPatchApprovalParams;
 pub use protocol::v1::ApplyPatchApprovalResponse;
 pub use protocol::v1::ClientInfo;
@@ -36,9 +37,14 @@ pub use protocol::v1::InitializeParams;
 pub use protocol::v1::InitializeResponse;
 pub use protocol::v1::InterruptConversationResponse;
 pub use protocol::v1::LoginApiKeyParams;
+pub use protocol::v1::NOTIFICATIONS_ADDED_IN_VERSION;
+pub use protocol::v1::NotificationLevel;
+pub use protocol::v1::NotificationVerbosity;
 pub use protocol::v1::SandboxSettings;
 pub use protocol::v1::Tools;
 pub use protocol::v1::UserSavedConfig;
+pub use protocol::v1::negotiate_protocol_version;
+pub use protocol::v1::notifications_unsupported_by;
 pub use protocol::v2::*;
 pub use rpc::*;
 pub use schema_fixtures::SchemaFixtureOptions;
@@ -2348,6 +2348,8 @@ mod tests {
                         "thread/started".to_string(),
                         "item/agentMessage/delta".to_string(),
                     ]),
+                    notification_verbosity: None,
+                    protocol_version: Some(1),
                 }),
             },
         };
@@ -2369,7 +2371,8 @@ mod tests {
                         "optOutNotificationMethods": [
                             "thread/started",
                             "item/agentMessage/delta"
-                        ]
+                        ],
+                        "protocolVersion": 1
                     }
                 }
             }),
@@ -2396,7 +2399,8 @@ mod tests {
                     "optOutNotificationMethods": [
                         "thread/started",
                         "item/agentMessage/delta"
-                    ]
+                    ],
+                    "protocolVersion": 1
                 }
             }
         }))?;
@@ -2419,6 +2423,8 @@ mod tests {
                             "thread/started".to_string(),
                             "item/agentMessage/delta".to_string(),
                         ]),
+                        notification_verbosity: None,
+                        protocol_version: Some(1),
                     }),
                 },
             }
@@ -2426,6 +2432,74 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn protocol_version_negotiates_down_to_the_older_side() {
+        assert_eq!(v1::negotiate_protocol_version(/*client_version*/ None), 1);
+        assert_eq!(v1::negotiate_protocol_version(Some(0)), 1);
+        assert_eq!(
+            v1::negotiate_protocol_version(Some(v1::APP_SERVER_PROTOCOL_VERSION + 1)),
+            v1::APP_SERVER_PROTOCOL_VERSION
+        );
+    }
+
+    #[test]
+    fn notifications_newer_than_the_negotiated_version_are_withheld() {
+        let added_in_version: &[(u32, &[&str])] = &[
+            (2, &["thread/pinned"]),
+            (3, &["thread/archived/bulk", "item/reasoning/summary"]),
+        ];
+
+        assert_eq!(
+            v1::notifications_added_after(added_in_version, /*version*/ 1),
+            vec![
+                "thread/pinned",
+                "thread/archived/bulk",
+                "item/reasoning/summary"
+            ]
+        );
+        assert_eq!(
+            v1::notifications_added_after(added_in_version, /*version*/ 2),
+            vec!["thread/archived/bulk", "item/reasoning/summary"]
+        );
+        assert_eq!(
+            v1::notifications_added_after(added_in_version, /*version*/ 3),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn notification_verbosity_suppresses_by_category_and_level() -> Result<()> {
+        let verbosity: v1::NotificationVerbosity = serde_json::from_value(json!({
+            "deltas": "off",
+            "toolOutput": "summary",
+            "reasoning": "full",
+        }))?;
+
+        assert_eq!(
+            verbosity.suppressed_notification_methods(),
+            vec![
+                "item/agentMessage/delta",
+                "item/plan/delta",
+                "item/commandExecution/outputDelta",
+                "item/fileChange/outputDelta",
+                "item/mcpToolCall/progress",
+            ]
+        );
+        assert_eq!(
+            v1::NotificationVerbosity {
+                metrics: Some(v1::NotificationLevel::Off),
+                ..Default::default()
+            }
+            .suppressed_notification_methods(),
+            vec!["thread/tokenUsage/updated", "account/rateLimits/updated"]
+        );
+        assert_eq!(
+            v1::NotificationVerbosity::default().suppressed_notification_methods(),
+            Vec::<&str>::new()
+        );
+        Ok(())
+    }
+
     #[test]
     fn conversation_id_serializes_as_plain_string() -> Result<()> {
         let id = ThreadId::from_string("67e55044-10b1-426f-9247-bb680e5fe0c8")?;
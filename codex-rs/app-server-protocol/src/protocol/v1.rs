@@ -57,6 +57,105 @@ pub struct InitializeCapabilities {
     /// connection (for example `thread/started`).
     #[ts(optional = nullable)]
     pub opt_out_notification_methods: Option<Vec<String>>,
+    /// How much of each notification category this connection receives. Applied on top of
+    /// `opt_out_notification_methods`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional = nullable)]
+    pub notification_verbosity: Option<NotificationVerbosity>,
+    /// Newest app-server protocol version the client understands. Clients that omit it are
+    /// treated as version 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional = nullable)]
+    pub protocol_version: Option<u32>,
+}
+
+/// Per-category notification levels. Omitted categories are delivered in full.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationVerbosity {
+    /// Streamed agent message and plan text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional = nullable)]
+    pub deltas: Option<NotificationLevel>,
+    /// Streamed command output and terminal input, file change output and patch updates, and
+    /// MCP tool call progress.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional = nullable)]
+    pub tool_output: Option<NotificationLevel>,
+    /// Streamed reasoning text and reasoning summaries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional = nullable)]
+    pub reasoning: Option<NotificationLevel>,
+    /// Token usage and rate limit updates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional = nullable)]
+    pub metrics: Option<NotificationLevel>,
+}
+
+/// How much of a notification category a connection receives. `summary` drops the category's
+/// high-frequency notifications, such as per-token deltas, and keeps the rest; `off` drops them
+/// all. Items still arrive through `item/started` and `item/completed` at every level.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationLevel {
+    Full,
+    Summary,
+    Off,
+}
+
+/// Notification methods in one category, split by how often they are sent.
+struct NotificationCategory {
+    frequent: &'static [&'static str],
+    occasional: &'static [&'static str],
+}
+
+const DELTA_NOTIFICATIONS: NotificationCategory = NotificationCategory {
+    frequent: &["item/agentMessage/delta", "item/plan/delta"],
+    occasional: &[],
+};
+
+const TOOL_OUTPUT_NOTIFICATIONS: NotificationCategory = NotificationCategory {
+    frequent: &[
+        "item/commandExecution/outputDelta",
+        "item/fileChange/outputDelta",
+        "item/mcpToolCall/progress",
+    ],
+    occasional: &[
+        "item/commandExecution/terminalInteraction",
+        "item/fileChange/patchUpdated",
+    ],
+};
+
+const REASONING_NOTIFICATIONS: NotificationCategory = NotificationCategory {
+    frequent: &[
+        "item/reasoning/textDelta",
+        "item/reasoning/summaryTextDelta",
+    ],
+    occasional: &["item/reasoning/summaryPartAdded"],
+};
+
+const METRICS_NOTIFICATIONS: NotificationCategory = NotificationCategory {
+    frequent: &["thread/tokenUsage/updated"],
+    occasional: &["account/rateLimits/updated"],
+};
+
+impl NotificationVerbosity {
+    /// Notification methods that the chosen levels suppress.
+    pub fn suppressed_notification_methods(&self) -> Vec<&'static str> {
+        [
+            (self.deltas, &DELTA_NOTIFICATIONS),
+            (self.tool_output, &TOOL_OUTPUT_NOTIFICATIONS),
+            (self.reasoning, &REASONING_NOTIFICATIONS),
+            (self.metrics, &METRICS_NOTIFICATIONS),
+        ]
+        .into_iter()
+        .flat_map(|(level, category)| match level {
+            None | Some(NotificationLevel::Full) => Vec::new(),
+            Some(NotificationLevel::Summary) => category.frequent.to_vec(),
+            Some(NotificationLevel::Off) => [category.frequent, category.occasional].concat(),
+        })
+        .collect()
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -71,6 +170,47 @@ pub struct InitializeResponse {
     /// Operating system for the running app-server target, for example
     /// `"macos"`, `"linux"`, or `"windows"`.
     pub platform_os: String,
+    /// Protocol version used for this connection: the lower of the client's and the server's.
+    /// Servers that predate versioning omit it, which means version 1.
+    #[serde(default = "first_protocol_version")]
+    pub protocol_version: u32,
+}
+
+/// Newest app-server protocol version this build speaks.
+///
+/// Bump it when adding server notifications that existing clients may not handle, and list the
+/// new methods under the new version in [`NOTIFICATIONS_ADDED_IN_VERSION`].
+pub const APP_SERVER_PROTOCOL_VERSION: u32 = 1;
+
+/// Server notification methods added after version 1, keyed by the version that added them.
+/// Connections that negotiate an older version do not receive them.
+pub const NOTIFICATIONS_ADDED_IN_VERSION: &[(u32, &[&str])] = &[];
+
+fn first_protocol_version() -> u32 {
+    1
+}
+
+/// Picks the protocol version for a connection from the version the client declared.
+pub fn negotiate_protocol_version(client_version: Option<u32>) -> u32 {
+    client_version
+        .unwrap_or(1)
+        .clamp(1, APP_SERVER_PROTOCOL_VERSION)
+}
+
+/// Notification methods that a connection on `version` must not receive.
+pub fn notifications_unsupported_by(version: u32) -> Vec<&'static str> {
+    notifications_added_after(NOTIFICATIONS_ADDED_IN_VERSION, version)
+}
+
+pub(crate) fn notifications_added_after(
+    added_in_version: &[(u32, &[&'static str])],
+    version: u32,
+) -> Vec<&'static str> {
+    added_in_version
+        .iter()
+        .filter(|(added_in, _)| *added_in > version)
+        .flat_map(|(_, methods)| methods.iter().copied())
+        .collect()
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
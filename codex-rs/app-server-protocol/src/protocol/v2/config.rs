@@ -57,6 +57,18 @@ pub enum ConfigLayerSource {
         name: String,
     },
 
+    /// Organization-managed team config from $CODEX_HOME/team_config.toml.
+    /// The keys it locks are reported as a separate layer that takes
+    /// precedence over user, project, and session settings.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Team {
+        file: AbsolutePathBuf,
+
+        /// Whether this layer holds the keys the team config locks.
+        locked: bool,
+    },
+
     /// User config layer from $CODEX_HOME/config.toml. This layer is special
     /// in that it is expected to be:
     /// - writable by the user
@@ -104,6 +116,7 @@ impl ConfigLayerSource {
         match self {
             ConfigLayerSource::Mdm { .. } => 0,
             ConfigLayerSource::System { .. } => 10,
+            ConfigLayerSource::Team { locked: false, .. } => 12,
             ConfigLayerSource::EnterpriseManaged { .. } => 15,
             ConfigLayerSource::User { profile, .. } => {
                 if profile.is_some() {
@@ -114,6 +127,7 @@ impl ConfigLayerSource {
             }
             ConfigLayerSource::Project { .. } => 25,
             ConfigLayerSource::SessionFlags => 30,
+            ConfigLayerSource::Team { locked: true, .. } => 35,
             ConfigLayerSource::LegacyManagedConfigTomlFromFile { .. } => 40,
             ConfigLayerSource::LegacyManagedConfigTomlFromMdm => 50,
         }
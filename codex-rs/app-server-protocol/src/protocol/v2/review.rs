@@ -44,6 +44,9 @@ pub enum ReviewTarget {
     /// Review the working tree: staged, unstaged, and untracked files.
     UncommittedChanges,
 
+    /// Review only the changes staged in the index.
+    StagedChanges,
+
     /// Review changes between the current branch and the given base branch.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -58,6 +61,16 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the changes in a git revision range such as `main..feature`.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { range: String },
+
+    /// Review the changes in a GitHub pull request.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    PullRequest { url: String },
+
     /// Arbitrary instructions, equivalent to the old free-form prompt.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -395,6 +395,16 @@ pub struct ThreadResumeParams {
     #[experimental("thread/resume.initialTurnsPage")]
     #[ts(optional = nullable)]
     pub initial_turns_page: Option<ThreadResumeInitialTurnsPageParams>,
+    /// When true, attach to a running thread as a read-only observer. The
+    /// connection receives the history and live events but no approval
+    /// requests, and app-server rejects its requests for the thread other than
+    /// observing resumes, fork, unsubscribe, and reads, as well as its requests
+    /// outside a thread that are not reads. Resuming without `observe` takes
+    /// control only after every controlling connection has unsubscribed.
+    /// Fails if the thread is not running. Configuration overrides are ignored.
+    #[experimental("thread/resume.observe")]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub observe: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS, ExperimentalApi)]
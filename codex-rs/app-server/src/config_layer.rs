@@ -18,6 +18,7 @@ pub(crate) fn config_layer_source_to_api(source: ConfigLayerSource) -> ApiConfig
         ConfigLayerSource::EnterpriseManaged { id, name } => {
             ApiConfigLayerSource::EnterpriseManaged { id, name }
         }
+        ConfigLayerSource::Team { file, locked } => ApiConfigLayerSource::Team { file, locked },
         ConfigLayerSource::User { file, profile } => ApiConfigLayerSource::User { file, profile },
         ConfigLayerSource::Project { dot_codex_folder } => {
             ApiConfigLayerSource::Project { dot_codex_folder }
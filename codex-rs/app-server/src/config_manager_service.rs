@@ -754,6 +754,15 @@ fn override_message(layer: &ConfigLayerSource) -> String {
         ConfigLayerSource::EnterpriseManaged { id: _, name } => {
             format!("Overridden by enterprise-managed config: {name}")
         }
+        ConfigLayerSource::Team { file, locked: true } => {
+            format!("Locked by team config: {}", file.display())
+        }
+        ConfigLayerSource::Team {
+            file,
+            locked: false,
+        } => {
+            format!("Overridden by team config: {}", file.display())
+        }
         ConfigLayerSource::Project { dot_codex_folder } => format!(
             "Overridden by project config: {}/{CONFIG_TOML_FILE}",
             dot_codex_folder.display(),
@@ -60,7 +60,9 @@ where
         thread_store: _thread_store,
     } = dependencies;
     let mut builder = ExtensionRegistryBuilder::<Config>::with_event_sink(event_sink);
+    codex_run_limits_extension::install(&mut builder, state_db.clone(), thread_manager.clone());
     if let Some(state_db) = state_db {
+        codex_post_mortem_extension::install(&mut builder, state_db.clone());
         codex_goal_extension::install_with_backend(
             &mut builder,
             state_db,
@@ -68,11 +70,28 @@ where
             codex_otel::global(),
             thread_manager,
             goal_service,
-            |config: &Config| config.features.enabled(codex_features::Feature::Goals),
+            |config: &Config| codex_goal_extension::GoalExtensionConfig {
+                enabled: config.features.enabled(codex_features::Feature::Goals),
+                checkpoint_interval: config.goals.checkpoint_interval,
+                max_consecutive_tool_failures: config.goals.max_consecutive_tool_failures,
+            },
         );
     }
     codex_guardian::install(&mut builder, guardian_agent_spawner);
     codex_memories_extension::install(&mut builder, codex_otel::global());
+    codex_git_history_extension::install(&mut builder);
+    codex_related_repos_extension::install(&mut builder);
+    codex_refactor_extension::install(&mut builder);
+    codex_notebook_extension::install(&mut builder);
+    codex_log_digest_extension::install(&mut builder);
+    codex_database_extension::install(&mut builder);
+    codex_http_request_extension::install(&mut builder);
+    codex_env_snapshot_extension::install(&mut builder);
+    codex_dependencies_extension::install(&mut builder);
+    codex_bench_extension::install(&mut builder);
+    codex_profiling_extension::install(&mut builder);
+    codex_session_artifacts_extension::install(&mut builder);
+    codex_usage_export_extension::install(&mut builder);
     codex_mcp_extension::install(&mut builder);
     codex_mcp_extension::install_executor_plugins(&mut builder, environment_manager);
     codex_web_search_extension::install(&mut builder, auth_manager.clone());
@@ -52,6 +52,7 @@ use codex_analytics::AnalyticsEventsClient;
 use codex_analytics::AppServerRpcTransport;
 use codex_app_server_protocol::ClientNotification;
 use codex_app_server_protocol::ClientRequest;
+use codex_app_server_protocol::ClientRequestSerializationScope;
 use codex_app_server_protocol::ClientResponsePayload;
 use codex_app_server_protocol::ConfigWarningNotification;
 use codex_app_server_protocol::ExperimentalApi;
@@ -60,6 +61,7 @@ use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::JSONRPCNotification;
 use codex_app_server_protocol::JSONRPCRequest;
 use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::experimental_required_message;
 use codex_arg0::Arg0DispatchPaths;
 use codex_chatgpt::workspace_settings;
@@ -93,6 +95,73 @@ fn deserialize_client_request(request: JSONRPCRequest) -> Result<ClientRequest,
         .map_err(|err| invalid_request(format!("Invalid request: {err}")))
 }
 
+/// The thread a request acts on, unless the request only reads it. Connections that attached
+/// to that thread as observers may not send it. Read-only requests are listed explicitly so a
+/// new thread method is refused to observers until someone decides it is safe.
+fn thread_controlled_by_request(request: &ClientRequest) -> Option<String> {
+    let Some(ClientRequestSerializationScope::Thread { thread_id }) = request.serialization_scope()
+    else {
+        return None;
+    };
+    match request {
+        // Observing again leaves control where it is, and forking or unsubscribing leaves the
+        // thread as it is. Resuming without `observe` takes control of the thread, which an
+        // observer may only do once no other connection controls it.
+        ClientRequest::ThreadResume { params, .. } if params.observe => None,
+        ClientRequest::ThreadFork { .. }
+        | ClientRequest::ThreadUnsubscribe { .. }
+        | ClientRequest::ThreadRead { .. }
+        | ClientRequest::ThreadGoalGet { .. }
+        | ClientRequest::ThreadBackgroundTerminalsList { .. } => None,
+        _ => Some(thread_id),
+    }
+}
+
+/// Whether a request that is not tied to one thread only reads state. A connection that
+/// observes a thread is read-only everywhere else too, so it cannot run commands, write files,
+/// or change configuration on the host of the thread it watches. Like the thread methods above,
+/// read-only requests are listed explicitly.
+fn request_is_read_only(request: &ClientRequest) -> bool {
+    matches!(
+        request,
+        ClientRequest::ThreadList { .. }
+            | ClientRequest::ThreadSearch { .. }
+            | ClientRequest::ThreadSearchOccurrences { .. }
+            | ClientRequest::ThreadLoadedList { .. }
+            | ClientRequest::SkillsList { .. }
+            | ClientRequest::HooksList { .. }
+            | ClientRequest::PluginList { .. }
+            | ClientRequest::PluginInstalled { .. }
+            | ClientRequest::PluginRead { .. }
+            | ClientRequest::PluginSkillRead { .. }
+            | ClientRequest::PluginShareList { .. }
+            | ClientRequest::AppsRead { .. }
+            | ClientRequest::AppsList { .. }
+            | ClientRequest::AppsInstalled { .. }
+            | ClientRequest::FsReadFile { .. }
+            | ClientRequest::FsGetMetadata { .. }
+            | ClientRequest::FsReadDirectory { .. }
+            | ClientRequest::ModelList { .. }
+            | ClientRequest::ModelProviderCapabilitiesRead { .. }
+            | ClientRequest::ExperimentalFeatureList { .. }
+            | ClientRequest::PermissionProfileList { .. }
+            | ClientRequest::RemoteControlStatusRead { .. }
+            | ClientRequest::CollaborationModeList { .. }
+            | ClientRequest::EnvironmentInfo { .. }
+            | ClientRequest::EnvironmentStatus { .. }
+            | ClientRequest::McpServerStatusList { .. }
+            | ClientRequest::WindowsSandboxReadiness { .. }
+            | ClientRequest::GetAccountRateLimits { .. }
+            | ClientRequest::GetAccountTokenUsage { .. }
+            | ClientRequest::GetAccount { .. }
+            | ClientRequest::GetAuthStatus { .. }
+            | ClientRequest::ConfigRead { .. }
+            | ClientRequest::ConfigRequirementsRead { .. }
+            | ClientRequest::GetConversationSummary { .. }
+            | ClientRequest::FuzzyFileSearch { .. }
+    )
+}
+
 pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
     models_refresh_worker: ModelsRefreshWorker,
@@ -119,6 +188,7 @@ pub(crate) struct MessageProcessor {
     turn_processor: TurnRequestProcessor,
     windows_sandbox_processor: WindowsSandboxRequestProcessor,
     request_serialization_queues: RequestSerializationQueues,
+    thread_state_manager: ThreadStateManager,
 }
 
 #[derive(Debug)]
@@ -432,7 +502,7 @@ impl MessageProcessor {
             Arc::clone(&config),
             config_manager.clone(),
             pending_thread_unloads,
-            thread_state_manager,
+            thread_state_manager.clone(),
             thread_watch_manager,
             thread_list_state_permit,
             Arc::clone(&skills_watcher),
@@ -499,6 +569,7 @@ impl MessageProcessor {
             turn_processor,
             windows_sandbox_processor,
             request_serialization_queues,
+            thread_state_manager,
         }
     }
 
@@ -733,18 +804,106 @@ impl MessageProcessor {
     }
 
     /// Handle a standalone JSON-RPC response originating from the peer.
-    pub(crate) async fn process_response(&self, response: JSONRPCResponse) {
+    pub(crate) async fn process_response(
+        &self,
+        connection_id: ConnectionId,
+        response: JSONRPCResponse,
+    ) {
         tracing::info!("<- response: {:?}", response);
         let JSONRPCResponse { id, result, .. } = response;
+        if self.is_observer_response(connection_id, &id).await {
+            return;
+        }
         self.outgoing.notify_client_response(id, result).await
     }
 
     /// Handle an error object received from the peer.
-    pub(crate) async fn process_error(&self, err: JSONRPCError) {
+    pub(crate) async fn process_error(&self, connection_id: ConnectionId, err: JSONRPCError) {
         tracing::error!("<- error: {:?}", err);
+        if self.is_observer_response(connection_id, &err.id).await {
+            return;
+        }
         self.outgoing.notify_client_error(err.id, err.error).await;
     }
 
+    /// Observers never receive a thread's server requests, so they must not resolve them either.
+    /// Requests that belong to no thread, such as auth refreshes, go to every connection, but
+    /// only connections that observe nothing may answer them.
+    async fn is_observer_response(&self, connection_id: ConnectionId, id: &RequestId) -> bool {
+        let is_observer = match self.outgoing.pending_request_thread_id(id).await {
+            Some(thread_id) => {
+                self.thread_state_manager
+                    .is_observer(thread_id, connection_id)
+                    .await
+            }
+            None => {
+                self.thread_state_manager
+                    .is_observing_any_thread(connection_id)
+                    .await
+            }
+        };
+        if is_observer {
+            tracing::warn!(
+                "dropping response to {id:?} from observer connection {connection_id:?}"
+            );
+        }
+        is_observer
+    }
+
+    /// Observers only read: they may not change a thread they observe, take it over while
+    /// another connection controls it, or act on the host through requests outside a thread.
+    async fn reject_observer_request(
+        &self,
+        connection_id: ConnectionId,
+        request: &ClientRequest,
+    ) -> Result<(), JSONRPCErrorError> {
+        let method = request.method_name();
+        if matches!(
+            request.serialization_scope(),
+            Some(ClientRequestSerializationScope::Thread { .. })
+        ) {
+            let Some(thread_id) = thread_controlled_by_request(request)
+                .and_then(|thread_id| ThreadId::from_string(&thread_id).ok())
+            else {
+                return Ok(());
+            };
+            if !self
+                .thread_state_manager
+                .is_observer(thread_id, connection_id)
+                .await
+            {
+                return Ok(());
+            }
+            if !matches!(request, ClientRequest::ThreadResume { .. }) {
+                return Err(invalid_request(format!(
+                    "{method} is not allowed while observing thread {thread_id}"
+                )));
+            }
+            // The owner hands the thread off by unsubscribing; until then it stays in control.
+            if self
+                .thread_state_manager
+                .has_controlling_connection(thread_id)
+                .await
+            {
+                return Err(invalid_request(format!(
+                    "thread {thread_id} is controlled by another connection; an observer can resume it once that connection unsubscribes"
+                )));
+            }
+            return Ok(());
+        }
+        if !request_is_read_only(request)
+            && self
+                .thread_state_manager
+                .is_observing_any_thread(connection_id)
+                .await
+        {
+            return Err(invalid_request(format!(
+                "{method} is not allowed while observing a thread"
+            )));
+        }
+        Ok(())
+    }
+
     async fn handle_client_request(
         self: &Arc<Self>,
         connection_request_id: ConnectionRequestId,
@@ -807,6 +966,8 @@ impl MessageProcessor {
             return Err(invalid_request(experimental_required_message(reason)));
         }
         let connection_id = connection_request_id.connection_id;
+        self.reject_observer_request(connection_id, &codex_request)
+            .await?;
         self.initialize_processor.track_initialized_request(
             connection_id,
             connection_request_id.request_id.clone(),
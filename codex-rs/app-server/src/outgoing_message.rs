@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
@@ -109,6 +110,8 @@ pub(crate) struct OutgoingMessageSender {
 pub(crate) struct ThreadScopedOutgoingMessageSender {
     outgoing: Arc<OutgoingMessageSender>,
     connection_ids: Arc<Vec<ConnectionId>>,
+    /// Subscribers that receive notifications but not server requests.
+    observer_connection_ids: Arc<HashSet<ConnectionId>>,
     thread_id: ThreadId,
 }
 
@@ -127,17 +130,30 @@ impl ThreadScopedOutgoingMessageSender {
         Self {
             outgoing,
             connection_ids: Arc::new(connection_ids),
+            observer_connection_ids: Arc::new(HashSet::new()),
             thread_id,
         }
     }
 
+    /// Keeps server requests such as approvals away from `observer_connection_ids`.
+    pub(crate) fn with_observers(mut self, observer_connection_ids: HashSet<ConnectionId>) -> Self {
+        self.observer_connection_ids = Arc::new(observer_connection_ids);
+        self
+    }
+
     pub(crate) async fn send_request(
         &self,
         payload: ServerRequestPayload,
     ) -> (RequestId, oneshot::Receiver<ClientRequestResult>) {
+        let connection_ids: Vec<ConnectionId> = self
+            .connection_ids
+            .iter()
+            .filter(|connection_id| !self.observer_connection_ids.contains(connection_id))
+            .copied()
+            .collect();
         self.outgoing
             .send_request_to_connections(
-                Some(self.connection_ids.as_slice()),
+                Some(connection_ids.as_slice()),
                 payload,
                 Some(self.thread_id),
             )
@@ -451,6 +467,15 @@ impl OutgoingMessageSender {
         request_id_to_callback.remove_entry(id)
     }
 
+    /// The thread a pending server request belongs to, if any.
+    pub(crate) async fn pending_request_thread_id(&self, id: &RequestId) -> Option<ThreadId> {
+        self.request_id_to_callback
+            .lock()
+            .await
+            .get(id)
+            .and_then(|entry| entry.thread_id)
+    }
+
     pub(crate) async fn pending_requests_for_thread(
         &self,
         thread_id: ThreadId,
@@ -1437,4 +1462,43 @@ mod tests {
                 .is_empty()
         );
     }
+
+    #[tokio::test]
+    async fn thread_scoped_requests_skip_observers() {
+        let (tx, mut rx) = mpsc::channel::<OutgoingEnvelope>(8);
+        let outgoing = Arc::new(OutgoingMessageSender::new(
+            tx,
+            codex_analytics::AnalyticsEventsClient::disabled(),
+        ));
+        let thread_id = ThreadId::new();
+        let thread_outgoing = ThreadScopedOutgoingMessageSender::new(
+            outgoing.clone(),
+            vec![ConnectionId(1), ConnectionId(2)],
+            thread_id,
+        )
+        .with_observers(HashSet::from([ConnectionId(2)]));
+
+        let (_request_id, _waiter) = thread_outgoing
+            .send_request(ServerRequestPayload::FileChangeRequestApproval(
+                FileChangeRequestApprovalParams {
+                    thread_id: thread_id.to_string(),
+                    turn_id: "turn-1".to_string(),
+                    item_id: "call-observed".to_string(),
+                    started_at_ms: 0,
+                    reason: None,
+                    grant_root: None,
+                },
+            ))
+            .await;
+        drop(thread_outgoing);
+        drop(outgoing);
+
+        let mut recipients = Vec::new();
+        while let Some(envelope) = rx.recv().await {
+            if let OutgoingEnvelope::ToConnection { connection_id, .. } = envelope {
+                recipients.push(connection_id);
+            }
+        }
+        assert_eq!(recipients, vec![ConnectionId(1)]);
+    }
 }
@@ -3,6 +3,8 @@ use std::sync::atomic::Ordering;
 
 use axum::http::HeaderValue;
 use codex_analytics::AppServerRpcTransport;
+use codex_app_server_protocol::negotiate_protocol_version;
+use codex_app_server_protocol::notifications_unsupported_by;
 use codex_login::default_client::SetOriginatorError;
 use codex_login::default_client::USER_AGENT_SUFFIX;
 use codex_login::default_client::get_codex_user_agent;
@@ -71,9 +73,24 @@ impl InitializeRequestProcessor {
         let experimental_api_enabled = capabilities.experimental_api;
         let request_attestation = capabilities.request_attestation;
         let supports_openai_form_elicitation = capabilities.mcp_server_openai_form_elicitation;
-        let opt_out_notification_methods = capabilities
+        let protocol_version = negotiate_protocol_version(capabilities.protocol_version);
+        let mut opt_out_notification_methods = capabilities
             .opt_out_notification_methods
             .unwrap_or_default();
+        if let Some(notification_verbosity) = &capabilities.notification_verbosity {
+            opt_out_notification_methods.extend(
+                notification_verbosity
+                    .suppressed_notification_methods()
+                    .into_iter()
+                    .map(str::to_string),
+            );
+        }
+        // Older clients may not handle notifications added after the version they speak.
+        opt_out_notification_methods.extend(
+            notifications_unsupported_by(protocol_version)
+                .into_iter()
+                .map(str::to_string),
+        );
         let ClientInfo {
             name,
             title: _title,
@@ -140,6 +157,7 @@ impl InitializeRequestProcessor {
             codex_home,
             platform_family: std::env::consts::FAMILY.to_string(),
             platform_os: std::env::consts::OS.to_string(),
+            protocol_version,
         };
 
         self.outgoing
@@ -326,11 +326,15 @@ pub(super) async fn ensure_listener_task_running(
                     let subscribed_connection_ids = thread_state_manager
                         .subscribed_connection_ids(conversation_id)
                         .await;
+                    let observer_connection_ids = thread_state_manager
+                        .observer_connection_ids(conversation_id)
+                        .await;
                     let thread_outgoing = ThreadScopedOutgoingMessageSender::new(
                         outgoing_for_task.clone(),
                         subscribed_connection_ids,
                         conversation_id,
-                    );
+                    )
+                    .with_observers(observer_connection_ids);
 
                     apply_bespoke_event_handling(
                         event.clone(),
@@ -640,10 +644,16 @@ pub(super) async fn handle_pending_thread_resume_request(
                 .await;
             return;
         }
-        if !thread_state_manager
-            .try_add_connection_to_thread(conversation_id, connection_id)
-            .await
-        {
+        let attached = if pending.observe {
+            thread_state_manager
+                .try_add_observer_to_thread(conversation_id, connection_id)
+                .await
+        } else {
+            thread_state_manager
+                .try_add_connection_to_thread(conversation_id, connection_id)
+                .await
+        };
+        if !attached {
             tracing::debug!(
                 thread_id = %conversation_id,
                 connection_id = ?connection_id,
@@ -738,9 +748,12 @@ pub(super) async fn handle_pending_thread_resume_request(
             );
         }
     }
-    outgoing
-        .replay_requests_to_connection_for_thread(connection_id, conversation_id)
-        .await;
+    // Observers watch the run but never answer its approvals.
+    if !pending.observe {
+        outgoing
+            .replay_requests_to_connection_for_thread(connection_id, conversation_id)
+            .await;
+    }
     // App-server owns resume response and snapshot ordering, so wait until
     // replay completes before letting extensions react to the idle thread.
     if pending.emit_thread_goal_update {
@@ -3058,6 +3058,18 @@ impl ThreadRequestProcessor {
             .await
         {
             Ok(RunningThreadResumeResult::Handled) => return Ok(()),
+            Ok(RunningThreadResumeResult::NotRunning(_)) if params.observe => {
+                self.outgoing
+                    .send_error(
+                        request_id,
+                        invalid_request(format!(
+                            "thread {} is not running; observers can only attach to a running thread",
+                            params.thread_id
+                        )),
+                    )
+                    .await;
+                return Ok(());
+            }
             Ok(RunningThreadResumeResult::NotRunning(stored_thread)) => stored_thread,
             Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
@@ -3084,6 +3096,7 @@ impl ThreadRequestProcessor {
             personality,
             exclude_turns,
             initial_turns_page,
+            observe: _,
         } = params;
         let include_turns = !exclude_turns;
 
@@ -3485,7 +3498,8 @@ impl ThreadRequestProcessor {
             }
             let config_snapshot = existing_thread.config_snapshot().await;
             let mismatch_details = collect_resume_override_mismatches(params, &config_snapshot);
-            if !mismatch_details.is_empty() {
+            // Observers never reconfigure the thread, so their overrides are simply ignored.
+            if !mismatch_details.is_empty() && !params.observe {
                 let has_subscribers = !self
                     .thread_state_manager
                     .subscribed_connection_ids(existing_thread_id)
@@ -3567,12 +3581,14 @@ impl ThreadRequestProcessor {
                 thread_state.clone(),
             )
             .await?;
-            Self::set_app_server_client_info(
-                existing_thread.as_ref(),
-                app_server_client_name,
-                app_server_client_version,
-            )
-            .await?;
+            if !params.observe {
+                Self::set_app_server_client_info(
+                    existing_thread.as_ref(),
+                    app_server_client_name,
+                    app_server_client_version,
+                )
+                .await?;
+            }
 
             let mut thread_summary = self.stored_thread_to_api_thread(
                 source_thread,
@@ -3665,6 +3681,7 @@ impl ThreadRequestProcessor {
                     paginated_initial_turns_page_with_active_slot,
                     resume_cursor_store,
                     redact_resume_payloads,
+                    observe: params.observe,
                 }),
             );
             if listener_command_tx.send(command).is_err() {
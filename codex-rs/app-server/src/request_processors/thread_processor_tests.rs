@@ -755,6 +755,7 @@ mod thread_processor_behavior_tests {
             personality: None,
             exclude_turns: false,
             initial_turns_page: None,
+            observe: false,
         };
         let config_snapshot = ThreadConfigSnapshot {
             model: "gpt-5".to_string(),
@@ -1392,6 +1393,58 @@ mod thread_processor_behavior_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn observers_stay_subscribed_until_they_take_control_or_disconnect() -> Result<()> {
+        let manager = ThreadStateManager::new();
+        let thread_id = ThreadId::from_string("5d0e9cf4-95a7-4b55-9f0c-3e2a6f4d8b11")?;
+        let driver = ConnectionId(1);
+        let observer = ConnectionId(2);
+        for connection in [driver, observer] {
+            manager
+                .connection_initialized(connection, ConnectionCapabilities::default())
+                .await;
+        }
+
+        assert!(
+            manager
+                .try_add_connection_to_thread(thread_id, driver)
+                .await
+        );
+        assert!(
+            manager
+                .try_add_observer_to_thread(thread_id, observer)
+                .await
+        );
+        let mut subscribed = manager.subscribed_connection_ids(thread_id).await;
+        subscribed.sort_by_key(|connection| connection.0);
+        assert_eq!(subscribed, vec![driver, observer]);
+        assert_eq!(
+            manager.observer_connection_ids(thread_id).await,
+            HashSet::from([observer])
+        );
+        assert!(!manager.is_observer(thread_id, driver).await);
+
+        assert!(
+            manager
+                .try_add_connection_to_thread(thread_id, observer)
+                .await
+        );
+        assert!(!manager.is_observer(thread_id, observer).await);
+
+        assert!(
+            manager
+                .try_add_observer_to_thread(thread_id, observer)
+                .await
+        );
+        manager.remove_connection(observer).await;
+        assert!(manager.observer_connection_ids(thread_id).await.is_empty());
+        assert_eq!(
+            manager.subscribed_connection_ids(thread_id).await,
+            vec![driver]
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn wait_for_thread_subscriber_unblocks_after_connection_attaches() -> Result<()> {
         let manager = ThreadStateManager::new();
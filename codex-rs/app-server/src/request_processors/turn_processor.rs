@@ -2,6 +2,8 @@ use super::*;
 use codex_agent_extension::AgentInvocation;
 use codex_agent_extension::AgentRun;
 use codex_agent_extension::AgentRunner;
+use codex_prompts::validate_commit_range;
+use codex_prompts::validate_pull_request_url;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::FunctionCallOutputContentItem;
 use codex_protocol::models::PermissionProfile;
@@ -373,6 +375,7 @@ impl TurnRequestProcessor {
     ) -> Result<(ReviewRequest, String, String), JSONRPCErrorError> {
         let cleaned_target = match target {
             ApiReviewTarget::UncommittedChanges => ApiReviewTarget::UncommittedChanges,
+            ApiReviewTarget::StagedChanges => ApiReviewTarget::StagedChanges,
             ApiReviewTarget::BaseBranch { branch } => {
                 let branch = branch.trim().to_string();
                 if branch.is_empty() {
@@ -390,6 +393,22 @@ impl TurnRequestProcessor {
                     .filter(|t| !t.is_empty());
                 ApiReviewTarget::Commit { sha, title }
             }
+            ApiReviewTarget::CommitRange { range } => {
+                let range = range.trim().to_string();
+                if range.is_empty() {
+                    return Err(invalid_request("range must not be empty".to_string()));
+                }
+                validate_commit_range(&range).map_err(|err| invalid_request(err.to_string()))?;
+                ApiReviewTarget::CommitRange { range }
+            }
+            ApiReviewTarget::PullRequest { url } => {
+                let url = url.trim().to_string();
+                if url.is_empty() {
+                    return Err(invalid_request("url must not be empty".to_string()));
+                }
+                validate_pull_request_url(&url).map_err(|err| invalid_request(err.to_string()))?;
+                ApiReviewTarget::PullRequest { url }
+            }
             ApiReviewTarget::Custom { instructions } => {
                 let trimmed = instructions.trim().to_string();
                 if trimmed.is_empty() {
@@ -405,8 +424,11 @@ impl TurnRequestProcessor {
 
         let core_target = match cleaned_target {
             ApiReviewTarget::UncommittedChanges => CoreReviewTarget::UncommittedChanges,
+            ApiReviewTarget::StagedChanges => CoreReviewTarget::StagedChanges,
             ApiReviewTarget::BaseBranch { branch } => CoreReviewTarget::BaseBranch { branch },
             ApiReviewTarget::Commit { sha, title } => CoreReviewTarget::Commit { sha, title },
+            ApiReviewTarget::CommitRange { range } => CoreReviewTarget::CommitRange { range },
+            ApiReviewTarget::PullRequest { url } => CoreReviewTarget::PullRequest { url },
             ApiReviewTarget::Custom { instructions } => CoreReviewTarget::Custom { instructions },
         };
         let target_prompt = match &core_target {
@@ -414,12 +436,19 @@ impl TurnRequestProcessor {
                 "Review the current code changes (staged, unstaged, and untracked files)."
                     .to_string()
             }
+            CoreReviewTarget::StagedChanges => "Review the staged code changes.".to_string(),
             CoreReviewTarget::BaseBranch { branch } => {
                 format!("Review the code changes against the base branch {branch:?}.")
             }
             CoreReviewTarget::Commit { sha, .. } => {
                 format!("Review the changes introduced by commit {sha:?}.")
             }
+            CoreReviewTarget::CommitRange { range } => {
+                format!("Review the code changes in the revision range {range:?}.")
+            }
+            CoreReviewTarget::PullRequest { url } => {
+                format!("Review the code changes in pull request {url}.")
+            }
             CoreReviewTarget::Custom { instructions } => instructions.clone(),
         };
 
@@ -50,6 +50,7 @@ pub(crate) struct PendingThreadResumeRequest {
         Option<codex_app_server_protocol::TurnsPage>,
     pub(crate) resume_cursor_store: Option<Arc<dyn codex_thread_store::ThreadStore>>,
     pub(crate) redact_resume_payloads: bool,
+    pub(crate) observe: bool,
 }
 
 // ThreadListenerCommand is used to perform operations in the context of the thread listener, for serialization purposes.
@@ -272,6 +273,8 @@ mod tests {
 struct ThreadEntry {
     state: Arc<Mutex<ThreadState>>,
     connection_ids: HashSet<ConnectionId>,
+    /// Subscribed connections that attached with `thread/resume.observe`.
+    observer_connection_ids: HashSet<ConnectionId>,
     has_connections_watcher: watch::Sender<bool>,
 }
 
@@ -280,6 +283,7 @@ impl Default for ThreadEntry {
         Self {
             state: Arc::new(Mutex::new(ThreadState::default())),
             connection_ids: HashSet::new(),
+            observer_connection_ids: HashSet::new(),
             has_connections_watcher: watch::channel(false).0,
         }
     }
@@ -379,6 +383,59 @@ impl ThreadStateManager {
             .unwrap_or_default()
     }
 
+    /// Subscribed connections that observe the thread without controlling it.
+    pub(crate) async fn observer_connection_ids(
+        &self,
+        thread_id: ThreadId,
+    ) -> HashSet<ConnectionId> {
+        let state = self.state.lock().await;
+        state
+            .threads
+            .get(&thread_id)
+            .map(|thread_entry| thread_entry.observer_connection_ids.clone())
+            .unwrap_or_default()
+    }
+
+    pub(crate) async fn is_observer(
+        &self,
+        thread_id: ThreadId,
+        connection_id: ConnectionId,
+    ) -> bool {
+        let state = self.state.lock().await;
+        state.threads.get(&thread_id).is_some_and(|thread_entry| {
+            thread_entry
+                .observer_connection_ids
+                .contains(&connection_id)
+        })
+    }
+
+    /// Whether a subscribed connection other than an observer is driving the thread.
+    pub(crate) async fn has_controlling_connection(&self, thread_id: ThreadId) -> bool {
+        let state = self.state.lock().await;
+        state.threads.get(&thread_id).is_some_and(|thread_entry| {
+            thread_entry
+                .connection_ids
+                .iter()
+                .any(|connection_id| !thread_entry.observer_connection_ids.contains(connection_id))
+        })
+    }
+
+    pub(crate) async fn is_observing_any_thread(&self, connection_id: ConnectionId) -> bool {
+        let state = self.state.lock().await;
+        state
+            .thread_ids_by_connection
+            .get(&connection_id)
+            .is_some_and(|thread_ids| {
+                thread_ids.iter().any(|thread_id| {
+                    state.threads.get(thread_id).is_some_and(|thread_entry| {
+                        thread_entry
+                            .observer_connection_ids
+                            .contains(&connection_id)
+                    })
+                })
+            })
+    }
+
     pub(crate) async fn thread_state(&self, thread_id: ThreadId) -> Arc<Mutex<ThreadState>> {
         let mut state = self.state.lock().await;
         state.threads.entry(thread_id).or_default().state.clone()
@@ -492,6 +549,7 @@ impl ThreadStateManager {
             }
             if let Some(thread_entry) = state.threads.get_mut(&thread_id) {
                 thread_entry.connection_ids.remove(&connection_id);
+                thread_entry.observer_connection_ids.remove(&connection_id);
                 thread_entry.update_has_connections();
             }
         };
@@ -539,10 +597,32 @@ impl ThreadStateManager {
         Some(thread_state)
     }
 
+    /// Subscribes `connection_id` to the thread. A connection that was observing the thread
+    /// becomes a regular subscriber.
     pub(crate) async fn try_add_connection_to_thread(
         &self,
         thread_id: ThreadId,
         connection_id: ConnectionId,
+    ) -> bool {
+        self.attach_connection_to_thread(thread_id, connection_id, /*observe*/ false)
+            .await
+    }
+
+    /// Subscribes `connection_id` to the thread as a read-only observer.
+    pub(crate) async fn try_add_observer_to_thread(
+        &self,
+        thread_id: ThreadId,
+        connection_id: ConnectionId,
+    ) -> bool {
+        self.attach_connection_to_thread(thread_id, connection_id, /*observe*/ true)
+            .await
+    }
+
+    async fn attach_connection_to_thread(
+        &self,
+        thread_id: ThreadId,
+        connection_id: ConnectionId,
+        observe: bool,
     ) -> bool {
         let mut state = self.state.lock().await;
         if !state.live_connections.contains_key(&connection_id) {
@@ -555,6 +635,11 @@ impl ThreadStateManager {
             .insert(thread_id);
         let thread_entry = state.threads.entry(thread_id).or_default();
         thread_entry.connection_ids.insert(connection_id);
+        if observe {
+            thread_entry.observer_connection_ids.insert(connection_id);
+        } else {
+            thread_entry.observer_connection_ids.remove(&connection_id);
+        }
         thread_entry.update_has_connections();
         true
     }
@@ -570,6 +655,7 @@ impl ThreadStateManager {
             for thread_id in &thread_ids {
                 if let Some(thread_entry) = state.threads.get_mut(thread_id) {
                     thread_entry.connection_ids.remove(&connection_id);
+                    thread_entry.observer_connection_ids.remove(&connection_id);
                     thread_entry.update_has_connections();
                 }
             }
@@ -85,6 +85,8 @@ async fn attestation_generate_round_trip_adds_header_to_responses_websocket_hand
                 request_attestation: true,
                 opt_out_notification_methods: None,
                 mcp_server_openai_form_elicitation: false,
+                notification_verbosity: None,
+                protocol_version: None,
             }),
         ),
     )
@@ -44,6 +44,8 @@ async fn mock_experimental_method_requires_experimental_api_capability() -> Resu
                 request_attestation: false,
                 opt_out_notification_methods: None,
                 mcp_server_openai_form_elicitation: false,
+                notification_verbosity: None,
+                protocol_version: None,
             }),
         )
         .await?;
@@ -80,6 +82,8 @@ async fn realtime_conversation_start_requires_experimental_api_capability() -> R
                 request_attestation: false,
                 opt_out_notification_methods: None,
                 mcp_server_openai_form_elicitation: false,
+                notification_verbosity: None,
+                protocol_version: None,
             }),
         )
         .await?;
@@ -132,6 +136,8 @@ async fn thread_memory_mode_set_requires_experimental_api_capability() -> Result
                 request_attestation: false,
                 opt_out_notification_methods: None,
                 mcp_server_openai_form_elicitation: false,
+                notification_verbosity: None,
+                protocol_version: None,
             }),
         )
         .await?;
@@ -171,6 +177,8 @@ async fn thread_settings_update_requires_experimental_api_capability() -> Result
                 request_attestation: false,
                 opt_out_notification_methods: None,
                 mcp_server_openai_form_elicitation: false,
+                notification_verbosity: None,
+                protocol_version: None,
             }),
         )
         .await?;
@@ -210,6 +218,8 @@ async fn realtime_webrtc_start_requires_experimental_api_capability() -> Result<
                 request_attestation: false,
                 opt_out_notification_methods: None,
                 mcp_server_openai_form_elicitation: false,
+                notification_verbosity: None,
+                protocol_version: None,
             }),
         )
         .await?;
@@ -265,6 +275,8 @@ async fn thread_start_mock_field_requires_experimental_api_capability() -> Resul
                 request_attestation: false,
                 opt_out_notification_methods: None,
                 mcp_server_openai_form_elicitation: false,
+                notification_verbosity: None,
+                protocol_version: None,
             }),
         )
         .await?;
@@ -307,6 +319,8 @@ async fn thread_start_without_dynamic_tools_allows_without_experimental_api_capa
                 request_attestation: false,
                 opt_out_notification_methods: None,
                 mcp_server_openai_form_elicitation: false,
+                notification_verbosity: None,
+                protocol_version: None,
             }),
         )
         .await?;
@@ -348,6 +362,8 @@ async fn thread_start_granular_approval_policy_requires_experimental_api_capabil
                 request_attestation: false,
                 opt_out_notification_methods: None,
                 mcp_server_openai_form_elicitation: false,
+                notification_verbosity: None,
+                protocol_version: None,
             }),
         )
         .await?;
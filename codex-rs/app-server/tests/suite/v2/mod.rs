@@ -78,6 +78,7 @@ mod thread_loaded_list;
 mod thread_memory_mode_set;
 mod thread_metadata_update;
 mod thread_name_websocket;
+mod thread_observe;
 mod thread_read;
 mod thread_resume;
 mod thread_rollback;
@@ -0,0 +1,301 @@
+use super::connection_handling_websocket::DEFAULT_READ_TIMEOUT;
+use super::connection_handling_websocket::WsClient;
+use super::connection_handling_websocket::connect_websocket;
+use super::connection_handling_websocket::create_config_toml;
+use super::connection_handling_websocket::read_error_for_id;
+use super::connection_handling_websocket::read_response_for_id;
+use super::connection_handling_websocket::send_request;
+use super::connection_handling_websocket::spawn_websocket_server;
+use anyhow::Context;
+use anyhow::Result;
+use app_test_support::create_fake_rollout_with_text_elements;
+use app_test_support::create_mock_responses_server_repeating_assistant;
+use app_test_support::to_response;
+use codex_app_server_protocol::ClientInfo;
+use codex_app_server_protocol::InitializeCapabilities;
+use codex_app_server_protocol::InitializeParams;
+use codex_app_server_protocol::ThreadArchiveParams;
+use codex_app_server_protocol::ThreadDeleteParams;
+use codex_app_server_protocol::ThreadGoalClearParams;
+use codex_app_server_protocol::ThreadReadParams;
+use codex_app_server_protocol::ThreadReadResponse;
+use codex_app_server_protocol::ThreadResumeParams;
+use codex_app_server_protocol::ThreadResumeResponse;
+use codex_app_server_protocol::ThreadSetNameParams;
+use codex_app_server_protocol::ThreadUnsubscribeParams;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn observers_cannot_send_requests_that_change_the_thread() -> Result<()> {
+    let server = create_mock_responses_server_repeating_assistant("Done").await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+    let thread_id = create_fake_rollout_with_text_elements(
+        codex_home.path(),
+        "2025-01-05T12-00-00",
+        "2025-01-05T12:00:00Z",
+        "Saved user message",
+        Vec::new(),
+        Some("mock_provider"),
+        /*git_info*/ None,
+    )?;
+
+    let (mut process, bind_addr) = spawn_websocket_server(codex_home.path()).await?;
+
+    let result = async {
+        let mut driver = connect_websocket(bind_addr).await?;
+        let mut observer = connect_websocket(bind_addr).await?;
+        initialize_experimental(&mut driver, /*id*/ 1, "driver").await?;
+        initialize_experimental(&mut observer, /*id*/ 2, "observer").await?;
+
+        resume(
+            &mut driver,
+            /*id*/ 10,
+            &thread_id,
+            /*observe*/ false,
+        )
+        .await?;
+        resume(
+            &mut observer,
+            /*id*/ 11,
+            &thread_id,
+            /*observe*/ true,
+        )
+        .await?;
+
+        let mutating_requests = [
+            (
+                "thread/name/set",
+                serde_json::to_value(ThreadSetNameParams {
+                    thread_id: thread_id.clone(),
+                    name: "Renamed by an observer".to_string(),
+                })?,
+            ),
+            (
+                "thread/archive",
+                serde_json::to_value(ThreadArchiveParams {
+                    thread_id: thread_id.clone(),
+                })?,
+            ),
+            (
+                "thread/goal/clear",
+                serde_json::to_value(ThreadGoalClearParams {
+                    thread_id: thread_id.clone(),
+                })?,
+            ),
+            (
+                "thread/delete",
+                serde_json::to_value(ThreadDeleteParams {
+                    thread_id: thread_id.clone(),
+                })?,
+            ),
+        ];
+        for (id, (method, params)) in (20..).zip(mutating_requests) {
+            send_request(&mut observer, method, id, Some(params)).await?;
+            let error =
+                timeout(DEFAULT_READ_TIMEOUT, read_error_for_id(&mut observer, id)).await??;
+            assert_eq!(
+                error.error.message,
+                format!("{method} is not allowed while observing thread {thread_id}")
+            );
+        }
+
+        // Reads are still allowed, and the thread is untouched.
+        send_request(
+            &mut observer,
+            "thread/read",
+            /*id*/ 30,
+            Some(serde_json::to_value(ThreadReadParams {
+                thread_id: thread_id.clone(),
+                include_turns: false,
+            })?),
+        )
+        .await?;
+        let response = timeout(
+            DEFAULT_READ_TIMEOUT,
+            read_response_for_id(&mut observer, /*id*/ 30),
+        )
+        .await??;
+        let read = to_response::<ThreadReadResponse>(response)?;
+        assert_eq!(read.thread.id, thread_id);
+        assert_eq!(read.thread.name, None);
+        Ok(())
+    }
+    .await;
+
+    process
+        .kill()
+        .await
+        .context("failed to stop websocket app-server process")?;
+    result
+}
+
+#[tokio::test]
+async fn observers_cannot_take_control_or_run_commands_until_the_owner_leaves() -> Result<()> {
+    let server = create_mock_responses_server_repeating_assistant("Done").await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+    let thread_id = create_fake_rollout_with_text_elements(
+        codex_home.path(),
+        "2025-01-05T12-00-00",
+        "2025-01-05T12:00:00Z",
+        "Saved user message",
+        Vec::new(),
+        Some("mock_provider"),
+        /*git_info*/ None,
+    )?;
+
+    let (mut process, bind_addr) = spawn_websocket_server(codex_home.path()).await?;
+
+    let result = async {
+        let mut driver = connect_websocket(bind_addr).await?;
+        let mut observer = connect_websocket(bind_addr).await?;
+        initialize_experimental(&mut driver, /*id*/ 1, "driver").await?;
+        initialize_experimental(&mut observer, /*id*/ 2, "observer").await?;
+
+        resume(
+            &mut driver,
+            /*id*/ 10,
+            &thread_id,
+            /*observe*/ false,
+        )
+        .await?;
+        resume(
+            &mut observer,
+            /*id*/ 11,
+            &thread_id,
+            /*observe*/ true,
+        )
+        .await?;
+
+        send_request(
+            &mut observer,
+            "thread/resume",
+            /*id*/ 20,
+            Some(serde_json::to_value(ThreadResumeParams {
+                thread_id: thread_id.clone(),
+                ..Default::default()
+            })?),
+        )
+        .await?;
+        let error = timeout(
+            DEFAULT_READ_TIMEOUT,
+            read_error_for_id(&mut observer, /*id*/ 20),
+        )
+        .await??;
+        assert_eq!(
+            error.error.message,
+            format!(
+                "thread {thread_id} is controlled by another connection; an observer can resume it once that connection unsubscribes"
+            )
+        );
+
+        send_request(
+            &mut observer,
+            "command/exec",
+            /*id*/ 21,
+            Some(json!({ "command": ["touch", "observer-was-here"] })),
+        )
+        .await?;
+        let error = timeout(
+            DEFAULT_READ_TIMEOUT,
+            read_error_for_id(&mut observer, /*id*/ 21),
+        )
+        .await??;
+        assert_eq!(
+            error.error.message,
+            "command/exec is not allowed while observing a thread"
+        );
+
+        // Once the owner unsubscribes, the observer may take the thread over.
+        send_request(
+            &mut driver,
+            "thread/unsubscribe",
+            /*id*/ 30,
+            Some(serde_json::to_value(ThreadUnsubscribeParams {
+                thread_id: thread_id.clone(),
+            })?),
+        )
+        .await?;
+        timeout(
+            DEFAULT_READ_TIMEOUT,
+            read_response_for_id(&mut driver, /*id*/ 30),
+        )
+        .await??;
+        resume(
+            &mut observer,
+            /*id*/ 31,
+            &thread_id,
+            /*observe*/ false,
+        )
+        .await?;
+        send_request(
+            &mut observer,
+            "thread/name/set",
+            /*id*/ 32,
+            Some(serde_json::to_value(ThreadSetNameParams {
+                thread_id: thread_id.clone(),
+                name: "Handed off".to_string(),
+            })?),
+        )
+        .await?;
+        timeout(
+            DEFAULT_READ_TIMEOUT,
+            read_response_for_id(&mut observer, /*id*/ 32),
+        )
+        .await??;
+        Ok(())
+    }
+    .await;
+
+    process
+        .kill()
+        .await
+        .context("failed to stop websocket app-server process")?;
+    result
+}
+
+async fn initialize_experimental(stream: &mut WsClient, id: i64, client_name: &str) -> Result<()> {
+    let params = InitializeParams {
+        client_info: ClientInfo {
+            name: client_name.to_string(),
+            title: None,
+            version: "0.1.0".to_string(),
+        },
+        capabilities: Some(InitializeCapabilities {
+            experimental_api: true,
+            ..Default::default()
+        }),
+    };
+    send_request(
+        stream,
+        "initialize",
+        id,
+        Some(serde_json::to_value(params)?),
+    )
+    .await?;
+    timeout(DEFAULT_READ_TIMEOUT, read_response_for_id(stream, id)).await??;
+    Ok(())
+}
+
+async fn resume(stream: &mut WsClient, id: i64, thread_id: &str, observe: bool) -> Result<()> {
+    let params = ThreadResumeParams {
+        thread_id: thread_id.to_string(),
+        observe,
+        ..Default::default()
+    };
+    send_request(
+        stream,
+        "thread/resume",
+        id,
+        Some(serde_json::to_value(params)?),
+    )
+    .await?;
+    let response = timeout(DEFAULT_READ_TIMEOUT, read_response_for_id(stream, id)).await??;
+    let resume = to_response::<ThreadResumeResponse>(response)?;
+    assert_eq!(resume.thread.id, thread_id);
+    Ok(())
+}
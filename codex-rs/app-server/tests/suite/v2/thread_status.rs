@@ -156,6 +156,8 @@ async fn thread_status_changed_can_be_opted_out() -> Result<()> {
                 request_attestation: false,
                 opt_out_notification_methods: Some(vec!["thread/status/changed".to_string()]),
                 mcp_server_openai_form_elicitation: false,
+                notification_verbosity: None,
+                protocol_version: None,
             }),
         ),
     )
@@ -0,0 +1,80 @@
+//! Implements `codex bench`, which times the `[bench]` command at a baseline revision and in the
+//! working tree and reports whether the difference is significant.
+
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_core::config::Config;
+use codex_utils_bench::BenchPlan;
+use codex_utils_cli::CliConfigOverrides;
+
+/// Options for `codex bench`. Flags override the `[bench]` table for this run.
+#[derive(Debug, clap::Parser)]
+pub struct BenchCommand {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Timed runs of the baseline and of the working tree each.
+    #[arg(long, value_name = "N")]
+    runs: Option<usize>,
+
+    /// Untimed runs on each side before timing starts.
+    #[arg(long, value_name = "N")]
+    warmup: Option<usize>,
+
+    /// Git revision to compare the working tree against.
+    #[arg(long, value_name = "REV")]
+    baseline: Option<String>,
+
+    /// Print the comparison as JSON.
+    #[arg(long, default_value_t = false)]
+    json: bool,
+
+    /// Command to time, run by the shell. Defaults to `bench.command`.
+    #[arg(trailing_var_arg = true, value_name = "COMMAND")]
+    command: Vec<String>,
+}
+
+impl BenchCommand {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let bench = config.bench;
+
+        let command = if self.command.is_empty() {
+            bench.command.context(
+                "no benchmark command: pass one after `codex bench` or set `bench.command`",
+            )?
+        } else {
+            self.command.join(" ")
+        };
+        let plan = BenchPlan {
+            command,
+            runs: self.runs.unwrap_or(bench.runs),
+            warmup: self.warmup.unwrap_or(bench.warmup),
+            timeout: Duration::from_secs(bench.timeout_seconds),
+        };
+        let baseline = self.baseline.unwrap_or(bench.baseline);
+        let cwd = std::env::current_dir().context("failed to read the current directory")?;
+
+        eprintln!(
+            "Timing `{}` {} times at {baseline} and in the working tree...",
+            plan.command, plan.runs
+        );
+        let comparison = codex_utils_bench::compare(&cwd, &baseline, &plan)
+            .await
+            .map_err(anyhow::Error::msg)?;
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&comparison)?);
+        } else {
+            print!("{}", comparison.render());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,415 @@
+//! Implements `codex bugreport`, which bundles local diagnostics into one zip archive.
+//!
+//! The archive is meant to be attached to a public issue, so every entry is sanitized before it is
+//! written: the doctor report is already redacted, `config.toml` has secret-looking values
+//! replaced, log tails have credentials and API keys masked, and the session rollout is reduced
+//! to an event timeline that records when each event happened and what kind it was, never its
+//! content. `environment.json` records the OS, toolchain versions, and an allowlist of
+//! build-related environment variables.
+
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+use clap::Parser;
+use codex_arg0::Arg0DispatchPaths;
+use codex_core::config::find_codex_home;
+use codex_tui::Cli as TuiCli;
+use codex_utils_cli::CliConfigOverrides;
+use codex_utils_env_snapshot::EnvSnapshot;
+use serde_json::Value as JsonValue;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+use crate::doctor;
+
+/// Largest suffix of each log file copied into the bundle.
+const MAX_LOG_TAIL_BYTES: u64 = 2 * 1024 * 1024;
+const LOG_FILE_NAMES: &[&str] = &["codex-tui.log", "codex-tui.jsonl"];
+const REDACTED: &str = "<redacted>";
+/// Config keys whose values are replaced when their name ends with one of these fragments, so
+/// `api_key` is masked but `model_auto_compact_token_limit` is not.
+const SECRET_KEY_FRAGMENTS: &[&str] = &[
+    "key",
+    "token",
+    "secret",
+    "password",
+    "auth",
+    "cookie",
+    "credential",
+];
+/// Config tables whose values are all replaced, because any entry may carry a credential.
+const SECRET_TABLES: &[&str] = &["env", "http_headers", "env_http_headers"];
+/// Prefixes of API keys and tokens issued by common providers, masked wherever they appear.
+const SECRET_VALUE_PREFIXES: &[&str] = &[
+    "sk-",
+    "sk_live_",
+    "sk_test_",
+    "rk_live_",
+    "ghp_",
+    "gho_",
+    "ghs_",
+    "ghu_",
+    "ghr_",
+    "github_pat_",
+    "glpat-",
+    "xoxb-",
+    "xoxp-",
+    "xapp-",
+    "AKIA",
+    "AIza",
+];
+
+/// Options for `codex bugreport`.
+#[derive(Debug, Parser)]
+pub struct BugreportCommand {
+    /// Session id whose event timeline should be included. Defaults to the most recent session.
+    #[arg(long = "session", value_name = "SESSION_ID")]
+    session_id: Option<String>,
+
+    /// Where to write the archive. Defaults to `codex-bugreport-<unix-time>.zip` in the current
+    /// directory.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+/// Collects diagnostics, writes the archive, and prints its path.
+pub async fn run_bugreport(
+    command: BugreportCommand,
+    root_config_overrides: CliConfigOverrides,
+    interactive: &TuiCli,
+    arg0_paths: &Arg0DispatchPaths,
+) -> anyhow::Result<()> {
+    let doctor_report =
+        doctor::redacted_json_report_text(root_config_overrides.clone(), interactive, arg0_paths)
+            .await?;
+    // The doctor report already explains config errors, so fall back to the default locations
+    // instead of failing the whole bundle.
+    let (codex_home, log_dir) =
+        match doctor::load_config(root_config_overrides, interactive, arg0_paths).await {
+            Ok(config) => (config.codex_home.to_path_buf(), config.log_dir),
+            Err(_) => {
+                let codex_home = find_codex_home()?.to_path_buf();
+                let log_dir = codex_home.join("log");
+                (codex_home, log_dir)
+            }
+        };
+
+    let mut entries = vec![("doctor.json".to_string(), doctor_report)];
+    let environment = EnvSnapshot::capture(&std::env::current_dir()?).await;
+    entries.push((
+        "environment.json".to_string(),
+        serde_json::to_string_pretty(&environment)?,
+    ));
+    if let Some(config) = read_optional(&codex_home.join("config.toml"))? {
+        entries.push(("config.toml".to_string(), sanitize_config_toml(&config)));
+    }
+    for name in LOG_FILE_NAMES {
+        if let Some(tail) = read_log_tail(&log_dir.join(name), MAX_LOG_TAIL_BYTES)? {
+            entries.push((format!("logs/{name}"), redact_log_text(&tail)));
+        }
+    }
+    if let Some(rollout_path) = find_rollout(&codex_home, command.session_id.as_deref()).await? {
+        let mut reader = codex_rollout::open_rollout_line_reader(&rollout_path)
+            .await
+            .with_context(|| format!("failed to open {}", rollout_path.display()))?;
+        let mut timeline = String::new();
+        while let Some(line) = reader.next_line().await? {
+            if let Some(entry) = session_timeline_entry(&line) {
+                timeline.push_str(&entry);
+                timeline.push('\n');
+            }
+        }
+        entries.push(("session-timeline.jsonl".to_string(), timeline));
+    }
+    entries.insert(0, ("README.txt".to_string(), readme(&entries)));
+
+    let output = command.output.unwrap_or_else(|| {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        PathBuf::from(format!("codex-bugreport-{timestamp}.zip"))
+    });
+    write_archive(&output, &entries)?;
+    println!(
+        "Wrote {}. Review it, then attach it to your issue.",
+        output.display()
+    );
+    Ok(())
+}
+
+fn readme(entries: &[(String, String)]) -> String {
+    let mut readme = format!(
+        "Codex {} bug report bundle.\n\n\
+         Secrets in config.toml and the logs are replaced with {REDACTED}, and the session\n\
+         timeline lists event types and times only. Review the files before sharing.\n\nContents:\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    for (name, _) in entries {
+        readme.push_str(&format!("  {name}\n"));
+    }
+    readme
+}
+
+fn write_archive(path: &Path, entries: &[(String, String)]) -> anyhow::Result<()> {
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    let mut archive = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in entries {
+        archive.start_file(name.as_str(), options)?;
+        archive.write_all(contents.as_bytes())?;
+    }
+    archive.finish()?;
+    Ok(())
+}
+
+fn read_optional(path: &Path) -> anyhow::Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+/// Reads at most `max_bytes` from the end of `path`, starting at a line boundary.
+fn read_log_tail(path: &Path, max_bytes: u64) -> anyhow::Result<Option<String>> {
+    let mut file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(err).with_context(|| format!("failed to open {}", path.display()));
+        }
+    };
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes);
+    let text = if start > 0 {
+        text.split_once('\n').map_or("", |(_, rest)| rest)
+    } else {
+        &text
+    };
+    Ok(Some(text.to_string()))
+}
+
+/// Returns the rollout recorded for `session_id`, or the most recent one when no id is given.
+pub(crate) async fn find_rollout(
+    codex_home: &Path,
+    session_id: Option<&str>,
+) -> anyhow::Result<Option<PathBuf>> {
+    match session_id {
+        Some(session_id) => Ok(Some(
+            codex_rollout::find_thread_path_by_id_str(
+                codex_home, session_id, /*state_db_ctx*/ None,
+            )
+            .await?
+            .with_context(|| format!("no recorded session found for id {session_id}"))?,
+        )),
+        None => newest_rollout(&codex_home.join("sessions")),
+    }
+}
+
+/// Returns the newest rollout under `sessions_dir`.
+///
+/// Rollouts live in `YYYY/MM/DD/rollout-<timestamp>-<id>.jsonl`, so the lexically greatest path
+/// is also the most recent one.
+fn newest_rollout(sessions_dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+    let mut dir = sessions_dir.to_path_buf();
+    for _ in 0..3 {
+        match newest_child(&dir, |path| path.is_dir())? {
+            Some(child) => dir = child,
+            None => return Ok(None),
+        }
+    }
+    newest_child(&dir, |path| {
+        path.is_file()
+            && path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with("rollout-"))
+    })
+}
+
+fn newest_child(dir: &Path, keep: impl Fn(&Path) -> bool) -> anyhow::Result<Option<PathBuf>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("failed to list {}", dir.display())),
+    };
+    let mut newest: Option<PathBuf> = None;
+    for entry in entries {
+        let path = entry?.path();
+        if keep(&path) && newest.as_ref().is_none_or(|newest| path > *newest) {
+            newest = Some(path);
+        }
+    }
+    Ok(newest)
+}
+
+/// Replaces secret-looking values in `config.toml` contents with a placeholder.
+///
+/// Unparseable config is omitted entirely rather than copied, since there is no safe way to find
+/// the secrets in it.
+pub(crate) fn sanitize_config_toml(contents: &str) -> String {
+    let Ok(mut table) = contents.parse::<toml::Table>() else {
+        return "# config.toml could not be parsed and was omitted\n".to_string();
+    };
+    sanitize_table(&mut table);
+    toml::to_string_pretty(&table)
+        .unwrap_or_else(|err| format!("# config.toml could not be re-serialized: {err}\n"))
+}
+
+fn sanitize_table(table: &mut toml::Table) {
+    for (key, value) in table.iter_mut() {
+        let key = key.to_ascii_lowercase();
+        if SECRET_TABLES.contains(&key.as_str()) {
+            redact_all(value);
+        } else if let toml::Value::Table(child) = value {
+            sanitize_table(child);
+        } else if is_secret_name(&key) {
+            redact_all(value);
+        } else if let toml::Value::Array(items) = value {
+            sanitize_array(items);
+        } else if let toml::Value::String(text) = value {
+            *text = redact_log_text(text);
+        }
+    }
+}
+
+/// Masks secrets in array elements, such as MCP server `args`. A string after a flag like
+/// `--api-key` is replaced whole, since it is that flag's value.
+fn sanitize_array(items: &mut [toml::Value]) {
+    let mut after_secret_flag = false;
+    for item in items {
+        match item {
+            toml::Value::Table(child) => sanitize_table(child),
+            toml::Value::Array(nested) => sanitize_array(nested),
+            toml::Value::String(text) if after_secret_flag => *text = REDACTED.to_string(),
+            toml::Value::String(text) => *text = redact_log_text(text),
+            toml::Value::Integer(_)
+            | toml::Value::Float(_)
+            | toml::Value::Boolean(_)
+            | toml::Value::Datetime(_) => {}
+        }
+        after_secret_flag = matches!(
+            item,
+            toml::Value::String(arg) if arg.starts_with('-') && is_secret_name(arg)
+        );
+    }
+}
+
+/// Whether a key or flag name, ignoring surrounding punctuation and case, ends with one of
+/// [`SECRET_KEY_FRAGMENTS`].
+fn is_secret_name(name: &str) -> bool {
+    let name = name
+        .trim_matches(|c: char| !c.is_ascii_alphanumeric())
+        .to_ascii_lowercase();
+    SECRET_KEY_FRAGMENTS
+        .iter()
+        .any(|fragment| name.ends_with(fragment))
+}
+
+fn looks_like_secret(value: &str) -> bool {
+    SECRET_VALUE_PREFIXES
+        .iter()
+        .any(|prefix| value.starts_with(prefix))
+}
+
+fn redact_all(value: &mut toml::Value) {
+    match value {
+        toml::Value::Table(table) => table.iter_mut().for_each(|(_, value)| redact_all(value)),
+        toml::Value::Array(items) => items.iter_mut().for_each(redact_all),
+        toml::Value::String(_)
+        | toml::Value::Integer(_)
+        | toml::Value::Float(_)
+        | toml::Value::Boolean(_)
+        | toml::Value::Datetime(_) => *value = toml::Value::String(REDACTED.to_string()),
+    }
+}
+
+/// Masks credentials that may appear in debug log lines: bearer tokens, `Authorization: Basic`
+/// values, provider API keys, and the values of secret-looking names such as `api_key=...`.
+///
+/// Quotes and punctuation around a masked value are kept so the line still reads as written.
+pub(crate) fn redact_log_text(text: &str) -> String {
+    let is_delimiter = |c: char| matches!(c, '"' | '\'' | ',' | ';');
+    let mut redacted = String::with_capacity(text.len());
+    let mut redact_next = false;
+    let mut after_authorization = false;
+    for token in text.split_inclusive(char::is_whitespace) {
+        let word = token.trim_end();
+        let trailing = &token[word.len()..];
+        let bare_start = word.len() - word.trim_start_matches(is_delimiter).len();
+        let bare = word[bare_start..].trim_end_matches(is_delimiter);
+        let (leading, closing) = (&word[..bare_start], &word[bare_start + bare.len()..]);
+        // Keys often appear as `name=value` or `name: value`; keep the name so the line still
+        // reads sensibly.
+        let secret = if redact_next && !bare.is_empty() {
+            Some(bare)
+        } else {
+            bare.match_indices(['=', ':'])
+                .find_map(|(index, _)| {
+                    let value = bare[index + 1..].trim_start_matches(is_delimiter);
+                    let secret = is_secret_name(&bare[..index]) || looks_like_secret(value);
+                    (secret && !value.is_empty()).then_some(value)
+                })
+                .or_else(|| Some(bare).filter(|bare| looks_like_secret(bare)))
+        };
+        match secret {
+            Some(secret) => {
+                redacted.push_str(leading);
+                redacted.push_str(&bare[..bare.len() - secret.len()]);
+                redacted.push_str(REDACTED);
+                redacted.push_str(closing);
+            }
+            None => redacted.push_str(word),
+        }
+        redacted.push_str(trailing);
+        if !word.is_empty() {
+            let lower = bare.to_ascii_lowercase();
+            let names_secret = bare.strip_suffix(['=', ':']).is_some_and(is_secret_name);
+            redact_next = secret.is_none()
+                && (lower.ends_with("bearer")
+                    || (after_authorization && lower == "basic")
+                    || names_secret);
+            after_authorization = lower.contains("authorization");
+        }
+    }
+    redacted
+}
+
+/// Reduces one rollout line to a JSON object with its timestamp and event type.
+///
+/// Tool calls keep their tool name because it is usually the most useful clue in a bug report;
+/// arguments, outputs, and messages are dropped.
+pub(crate) fn session_timeline_entry(line: &str) -> Option<String> {
+    let item = serde_json::from_str::<JsonValue>(line).ok()?;
+    let payload = item.get("payload");
+    let mut entry = serde_json::Map::new();
+    for (field, value) in [
+        ("timestamp", item.get("timestamp")),
+        ("type", item.get("type")),
+        (
+            "payloadType",
+            payload.and_then(|payload| payload.get("type")),
+        ),
+        ("name", payload.and_then(|payload| payload.get("name"))),
+    ] {
+        if let Some(value) = value.filter(|value| value.is_string()) {
+            entry.insert(field.to_string(), value.clone());
+        }
+    }
+    Some(JsonValue::Object(entry).to_string())
+}
+
+#[cfg(test)]
+#[path = "bugreport_tests.rs"]
+mod tests;
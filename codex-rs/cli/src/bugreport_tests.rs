@@ -0,0 +1,177 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn sanitize_config_toml_redacts_secret_keys_and_tables() {
+    let sanitized = sanitize_config_toml(
+        r#"
+model = "gpt-5"
+model_auto_compact_token_limit = 200000
+experimental_bearer_token = "abc"
+
+[model_providers.corp]
+base_url = "https://example.com"
+http_headers = { "X-Api" = "secret-header" }
+
+[mcp_servers.docs]
+command = "docs-server"
+env = { DOCS_API_KEY = "sk-123", REGION = "us" }
+"#,
+    );
+
+    assert_eq!(
+        sanitized
+            .parse::<toml::Table>()
+            .expect("sanitized config parses"),
+        r#"
+model = "gpt-5"
+model_auto_compact_token_limit = 200000
+experimental_bearer_token = "<redacted>"
+
+[model_providers.corp]
+base_url = "https://example.com"
+http_headers = { "X-Api" = "<redacted>" }
+
+[mcp_servers.docs]
+command = "docs-server"
+env = { DOCS_API_KEY = "<redacted>", REGION = "<redacted>" }
+"#
+        .parse::<toml::Table>()
+        .expect("expected config parses"),
+    );
+}
+
+#[test]
+fn sanitize_config_toml_redacts_secrets_in_arrays() {
+    let sanitized = sanitize_config_toml(
+        r#"
+[mcp_servers.search]
+command = "search-server"
+args = ["serve", "--api-key", "abc123", "--region=us", "--token=xyz", ["ghp_nested"]]
+api_key = ["one", "two"]
+
+[mcp_servers.github]
+command = "github-mcp"
+args = ["--pat", "ghp_abcdef"]
+url = "https://example.com/mcp?key=sk-123"
+"#,
+    );
+
+    assert_eq!(
+        sanitized
+            .parse::<toml::Table>()
+            .expect("sanitized config parses"),
+        r#"
+[mcp_servers.search]
+command = "search-server"
+args = ["serve", "--api-key", "<redacted>", "--region=us", "--token=<redacted>", ["<redacted>"]]
+api_key = ["<redacted>", "<redacted>"]
+
+[mcp_servers.github]
+command = "github-mcp"
+args = ["--pat", "<redacted>"]
+url = "https://example.com/mcp?key=<redacted>"
+"#
+        .parse::<toml::Table>()
+        .expect("expected config parses"),
+    );
+}
+
+#[test]
+fn sanitize_config_toml_omits_unparseable_config() {
+    assert_eq!(
+        sanitize_config_toml("api_key = \"sk-123"),
+        "# config.toml could not be parsed and was omitted\n"
+    );
+}
+
+#[test]
+fn redact_log_text_masks_bearer_tokens_and_api_keys() {
+    assert_eq!(
+        redact_log_text("auth header=\"Bearer abc.def\" key=sk-live-1\nok done\n"),
+        "auth header=\"Bearer <redacted>\" key=<redacted>\nok done\n"
+    );
+    assert_eq!(
+        redact_log_text("using sk-proj-123, then Bearer xyz"),
+        "using <redacted>, then Bearer <redacted>"
+    );
+    assert_eq!(
+        redact_log_text("api_key=\"sk-123\"; retrying"),
+        "api_key=\"<redacted>\"; retrying"
+    );
+}
+
+#[test]
+fn redact_log_text_masks_basic_auth_provider_keys_and_named_secrets() {
+    assert_eq!(
+        redact_log_text("Authorization: Basic dXNlcjpwYXNz\n\"authorization\": \"Basic Zm9v\"\n"),
+        "Authorization: Basic <redacted>\n\"authorization\": \"Basic <redacted>\"\n"
+    );
+    assert_eq!(
+        redact_log_text("api_key=abc123 client_secret: xyz token=a:b ok"),
+        "api_key=<redacted> client_secret: <redacted> token=<redacted> ok"
+    );
+    assert_eq!(
+        redact_log_text("keys AIzaSyA-123, ghp_abc and xoxb-1-2"),
+        "keys <redacted>, <redacted> and <redacted>"
+    );
+    assert_eq!(
+        redact_log_text("a basic test of model_auto_compact_token_limit=200000"),
+        "a basic test of model_auto_compact_token_limit=200000"
+    );
+}
+
+#[test]
+fn session_timeline_entry_drops_content() {
+    let line = r#"{"timestamp":"2025-01-02T03:04:05.000Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"cat\",\"secrets.txt\"]}"}}"#;
+
+    let entry = session_timeline_entry(line).expect("timeline entry");
+    assert_eq!(
+        serde_json::from_str::<JsonValue>(&entry).expect("entry is json"),
+        serde_json::json!({
+            "timestamp": "2025-01-02T03:04:05.000Z",
+            "type": "response_item",
+            "payloadType": "function_call",
+            "name": "shell",
+        })
+    );
+    assert_eq!(session_timeline_entry("not json"), None);
+}
+
+#[test]
+fn read_log_tail_starts_at_a_line_boundary() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("codex-tui.log");
+    std::fs::write(&path, "first line\nsecond line\nthird\n").expect("write log");
+
+    assert_eq!(
+        read_log_tail(&path, /*max_bytes*/ 14).expect("read tail"),
+        Some("third\n".to_string())
+    );
+    assert_eq!(
+        read_log_tail(&dir.path().join("missing.log"), /*max_bytes*/ 14).expect("read tail"),
+        None
+    );
+}
+
+#[test]
+fn newest_rollout_picks_latest_day_and_file() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    for relative in [
+        "2025/01/31/rollout-2025-01-31T10-00-00-a.jsonl",
+        "2025/02/01/rollout-2025-02-01T09-00-00-b.jsonl",
+        "2025/02/01/rollout-2025-02-01T11-00-00-c.jsonl",
+    ] {
+        let path = dir.path().join(relative);
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("create dirs");
+        std::fs::write(&path, "").expect("write rollout");
+    }
+
+    assert_eq!(
+        newest_rollout(dir.path()).expect("scan sessions"),
+        Some(
+            dir.path()
+                .join("2025/02/01/rollout-2025-02-01T11-00-00-c.jsonl")
+        )
+    );
+}
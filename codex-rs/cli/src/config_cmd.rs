@@ -0,0 +1,191 @@
+//! Implements `codex config`, which shows the effective configuration and installs team config.
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_config::CONFIG_TOML_FILE;
+use codex_config::ConfigLayerSource;
+use codex_config::ConfigLayerStack;
+use codex_config::TEAM_CONFIG_TOML_FILE;
+use codex_config::format_config_layer_source;
+use codex_config::team_config_layers;
+use codex_core::config::Config;
+use codex_core::config::find_codex_home;
+use codex_http_client::ClientRouteClass;
+use codex_http_client::HttpClientFactory;
+use codex_http_client::OutboundProxyPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_cli::CliConfigOverrides;
+use toml::Value as TomlValue;
+
+/// Replaces secret values in `config show`.
+const REDACTED: &str = "<redacted>";
+
+/// Subcommands:
+/// - `show`      — print every effective setting and the layer it comes from
+/// - `sync-team` — install a team config from a URL or file
+#[derive(Debug, clap::Parser)]
+pub struct ConfigCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: ConfigSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigSubcommand {
+    /// Print every effective setting and the layer it comes from. Settings locked by the team
+    /// config are marked as locked.
+    Show,
+
+    /// Install a team config from a URL or a checked-in file into `$CODEX_HOME/team_config.toml`.
+    SyncTeam(SyncTeamArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SyncTeamArgs {
+    /// `https://` URL or path of the team config to install.
+    #[arg(value_name = "URL_OR_PATH")]
+    pub source: String,
+}
+
+impl ConfigCli {
+    pub async fn run(self) -> Result<()> {
+        let ConfigCli {
+            config_overrides,
+            subcommand,
+        } = self;
+        match subcommand {
+            ConfigSubcommand::Show => {
+                let overrides = config_overrides
+                    .parse_overrides()
+                    .map_err(anyhow::Error::msg)?;
+                let config = Config::load_with_cli_overrides(overrides)
+                    .await
+                    .context("failed to load configuration")?;
+                print!("{}", render_config_show(&config.config_layer_stack));
+            }
+            // Syncing must not depend on the current config loading: a stale or broken team file
+            // is exactly what the sync is meant to replace.
+            ConfigSubcommand::SyncTeam(args) => run_sync_team(args).await?,
+        }
+        Ok(())
+    }
+}
+
+async fn run_sync_team(args: SyncTeamArgs) -> Result<()> {
+    let contents = if args.source.starts_with("https://") {
+        let client = HttpClientFactory::new(OutboundProxyPolicy::ReqwestDefault)
+            .build_client(&args.source, ClientRouteClass::Other)?;
+        client
+            .get(args.source.as_str())
+            .send()
+            .await
+            .and_then(codex_http_client::HttpResponse::error_for_status)
+            .with_context(|| format!("failed to download {}", args.source))?
+            .text()
+            .await?
+    } else if args.source.contains("://") {
+        anyhow::bail!("team config URLs must use https://, got {}", args.source);
+    } else {
+        std::fs::read_to_string(&args.source)
+            .with_context(|| format!("failed to read {}", args.source))?
+    };
+
+    let codex_home = find_codex_home().context("failed to locate the Codex home directory")?;
+    let destination =
+        AbsolutePathBuf::resolve_path_against_base(TEAM_CONFIG_TOML_FILE, codex_home.as_path());
+    let team_config = toml::from_str::<TomlValue>(&contents)
+        .with_context(|| format!("{} is not valid TOML", args.source))?;
+    let layers = team_config_layers(&destination, team_config)?;
+    std::fs::write(&destination, contents)
+        .with_context(|| format!("failed to write {}", destination.as_path().display()))?;
+
+    let locked_keys = layers
+        .iter()
+        .filter(|layer| matches!(layer.name, ConfigLayerSource::Team { locked: true, .. }))
+        .map(|layer| leaf_values(&layer.config).len())
+        .sum::<usize>();
+    println!(
+        "Installed team config at {} ({locked_keys} locked settings).",
+        destination.as_path().display()
+    );
+    Ok(())
+}
+
+/// Renders one `key = value` line per effective setting, annotated with its source layer.
+pub(crate) fn render_config_show(stack: &ConfigLayerStack) -> String {
+    let origins = stack.origins();
+    let mut rendered = String::new();
+    for (key, value) in leaf_values(&stack.effective_config()) {
+        // Arrays are shown whole; their origin is recorded per element.
+        let origin = origins
+            .get(&key)
+            .or_else(|| origins.get(&format!("{key}.0")))
+            .map(|origin| &origin.name);
+        let annotation = match origin {
+            Some(ConfigLayerSource::Team { file, locked: true }) => {
+                format!("locked by team config ({})", file.as_path().display())
+            }
+            Some(source) => format_config_layer_source(source, CONFIG_TOML_FILE),
+            None => "default".to_string(),
+        };
+        if is_secret_key(&key) {
+            rendered.push_str(&format!("{key} = \"{REDACTED}\"  # {annotation}\n"));
+        } else {
+            rendered.push_str(&format!("{key} = {value}  # {annotation}\n"));
+        }
+    }
+    rendered
+}
+
+/// True for settings whose values are credentials: tokens, passwords and API keys, plus every
+/// entry of the maps that carry them (environment variables and HTTP headers). Settings that
+/// only name an environment variable, such as `bearer_token_env_var`, are shown as-is.
+fn is_secret_key(key: &str) -> bool {
+    let mut segments = key.split('.').collect::<Vec<_>>();
+    let Some(last) = segments.pop() else {
+        return false;
+    };
+    let secret_map = segments.iter().enumerate().any(|(index, segment)| {
+        matches!(*segment, "env" | "http_headers" | "headers")
+            || (*segment == "set" && index > 0 && segments[index - 1] == "shell_environment_policy")
+    });
+    secret_map
+        || ["token", "password", "secret", "api_key"]
+            .iter()
+            .any(|suffix| last == *suffix || last.ends_with(&format!("_{suffix}")))
+}
+
+/// Flattens tables into sorted dotted keys, keeping arrays and scalars as values.
+fn leaf_values(value: &TomlValue) -> Vec<(String, TomlValue)> {
+    let mut leaves = Vec::new();
+    push_leaf_values(value, "", &mut leaves);
+    leaves.sort_by(|(left, _), (right, _)| left.cmp(right));
+    leaves
+}
+
+fn push_leaf_values(value: &TomlValue, prefix: &str, leaves: &mut Vec<(String, TomlValue)>) {
+    match value {
+        TomlValue::Table(table) => {
+            for (key, child) in table {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                push_leaf_values(child, &key, leaves);
+            }
+        }
+        TomlValue::String(_)
+        | TomlValue::Integer(_)
+        | TomlValue::Float(_)
+        | TomlValue::Boolean(_)
+        | TomlValue::Datetime(_)
+        | TomlValue::Array(_) => leaves.push((prefix.to_string(), value.clone())),
+    }
+}
+
+#[cfg(test)]
+#[path = "config_cmd_tests.rs"]
+mod tests;
@@ -0,0 +1,111 @@
+use super::*;
+use codex_config::ConfigLayerEntry;
+use codex_config::ConfigRequirements;
+use codex_config::ConfigRequirementsToml;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn config_show_marks_locked_settings() {
+    let codex_home = std::env::temp_dir();
+    let user_file = AbsolutePathBuf::resolve_path_against_base(CONFIG_TOML_FILE, &codex_home);
+    let team_file = AbsolutePathBuf::resolve_path_against_base(TEAM_CONFIG_TOML_FILE, &codex_home);
+    let team_config = toml::from_str(
+        r#"
+model = "gpt-team"
+sandbox_mode = "read-only"
+locked = ["sandbox_mode"]
+"#,
+    )
+    .expect("parse team config");
+    let mut layers = team_config_layers(&team_file, team_config).expect("valid team config");
+    let locked = layers.pop().expect("locked layer");
+    layers.push(ConfigLayerEntry::new(
+        ConfigLayerSource::User {
+            file: user_file.clone(),
+            profile: None,
+        },
+        toml::from_str(
+            r#"
+model = "gpt-user"
+sandbox_mode = "danger-full-access"
+notify = ["say", "done"]
+"#,
+        )
+        .expect("parse user config"),
+    ));
+    layers.push(locked);
+    let stack = ConfigLayerStack::new(
+        layers,
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .expect("stack should be ordered");
+
+    let user = user_file.as_path().display();
+    let team = team_file.as_path().display();
+    assert_eq!(
+        render_config_show(&stack),
+        format!(
+            "\
+model = \"gpt-user\"  # user ({user})
+notify = [\"say\", \"done\"]  # user ({user})
+sandbox_mode = \"read-only\"  # locked by team config ({team})
+"
+        )
+    );
+}
+
+#[test]
+fn config_show_redacts_secrets() {
+    let codex_home = std::env::temp_dir();
+    let user_file = AbsolutePathBuf::resolve_path_against_base(CONFIG_TOML_FILE, &codex_home);
+    let stack = ConfigLayerStack::new(
+        vec![ConfigLayerEntry::new(
+            ConfigLayerSource::User {
+                file: user_file.clone(),
+                profile: None,
+            },
+            toml::from_str(
+                r#"
+model_auto_compact_token_limit = 1000
+
+[mcp_servers.docs]
+bearer_token = "sk-bearer"
+bearer_token_env_var = "DOCS_TOKEN"
+env = { API_KEY = "sk-env" }
+"#,
+            )
+            .expect("parse user config"),
+        )],
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .expect("stack should be ordered");
+
+    let user = user_file.as_path().display();
+    assert_eq!(
+        render_config_show(&stack),
+        format!(
+            "\
+mcp_servers.docs.bearer_token = \"<redacted>\"  # user ({user})
+mcp_servers.docs.bearer_token_env_var = \"DOCS_TOKEN\"  # user ({user})
+mcp_servers.docs.env.API_KEY = \"<redacted>\"  # user ({user})
+model_auto_compact_token_limit = 1000  # user ({user})
+"
+        )
+    );
+}
+
+#[tokio::test]
+async fn sync_team_rejects_plain_http() {
+    let err = run_sync_team(SyncTeamArgs {
+        source: "http://example.com/team_config.toml".to_string(),
+    })
+    .await
+    .expect_err("http:// should be rejected");
+
+    assert_eq!(
+        err.to_string(),
+        "team config URLs must use https://, got http://example.com/team_config.toml"
+    );
+}
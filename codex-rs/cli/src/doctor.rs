@@ -331,6 +331,28 @@ pub async fn run_doctor(
     Ok(())
 }
 
+/// Builds the redacted JSON support report without printing it or exiting.
+///
+/// `codex bugreport` embeds this report in its archive so the bundle carries
+/// the same versions, terminal metadata, and config checks as `doctor --json`.
+pub(crate) async fn redacted_json_report_text(
+    root_config_overrides: CliConfigOverrides,
+    interactive: &TuiCli,
+    arg0_paths: &Arg0DispatchPaths,
+) -> anyhow::Result<String> {
+    let command = DoctorCommand {
+        json: true,
+        summary: false,
+        all: false,
+        no_color: true,
+        ascii: false,
+    };
+    let report = build_report(&command, root_config_overrides, interactive, arg0_paths).await;
+    Ok(serde_json::to_string_pretty(&redacted_json_report(
+        &report,
+    ))?)
+}
+
 async fn build_report(
     command: &DoctorCommand,
     root_config_overrides: CliConfigOverrides,
@@ -492,7 +514,7 @@ async fn build_report(
     }
 }
 
-async fn load_config(
+pub(crate) async fn load_config(
     root_config_overrides: CliConfigOverrides,
     interactive: &TuiCli,
     arg0_paths: &Arg0DispatchPaths,
@@ -46,6 +46,9 @@ use supports_color::Stream;
 
 #[cfg(any(target_os = "macos", target_os = "windows"))]
 mod app_cmd;
+mod bench_cmd;
+mod bugreport;
+mod config_cmd;
 #[cfg(any(target_os = "macos", target_os = "windows"))]
 mod desktop_app;
 mod doctor;
@@ -54,18 +57,27 @@ mod marketplace_cmd;
 mod mcp_cmd;
 mod plugin_cmd;
 mod remote_control_cmd;
+mod run_limits;
 #[cfg(target_os = "windows")]
 mod sandbox_setup;
+mod share;
 mod state_db_recovery;
+mod tutorial;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::bench_cmd::BenchCommand;
+use crate::config_cmd::ConfigCli;
 use crate::mcp_cmd::McpCli;
 use crate::plugin_cmd::PluginCli;
 use crate::plugin_cmd::PluginSubcommand;
 use crate::remote_control_cmd::RemoteControlCommand;
+use crate::run_limits::RunLimitOptions;
+use bugreport::BugreportCommand;
 use doctor::DoctorCommand;
+use share::ShareCommand;
 use state_db_recovery as local_state_db;
+use tutorial::TutorialCommand;
 
 use codex_config::LoaderOverrides;
 use codex_core::build_models_manager;
@@ -111,6 +123,9 @@ struct MultitoolCli {
     #[clap(flatten)]
     pub feature_toggles: FeatureToggles,
 
+    #[clap(flatten)]
+    run_limits: RunLimitOptions,
+
     #[clap(flatten)]
     remote: InteractiveRemoteOptions,
 
@@ -142,6 +157,9 @@ enum Subcommand {
     /// Manage Codex plugins.
     Plugin(PluginCli),
 
+    /// Show the effective configuration or install a team config.
+    Config(ConfigCli),
+
     /// Start Codex as an MCP server (stdio).
     McpServer(McpServerCommand),
 
@@ -164,6 +182,15 @@ enum Subcommand {
     /// Diagnose local Codex installation, config, auth, and runtime health.
     Doctor(DoctorCommand),
 
+    /// Bundle sanitized diagnostics, config, logs, and a session timeline into a zip for bug reports.
+    Bugreport(BugreportCommand),
+
+    /// Export a session as a redacted, self-contained HTML page for sharing.
+    Share(ShareCommand),
+
+    /// Time a benchmark command at a baseline revision and in the working tree, and compare.
+    Bench(BenchCommand),
+
     /// Run commands within a Codex-provided sandbox.
     Sandbox(HostSandboxArgs),
 
@@ -193,6 +220,12 @@ enum Subcommand {
     /// Fork a previous interactive session (picker by default; use --last to fork the most recent).
     Fork(ForkCommand),
 
+    /// Start a new interactive session, optionally from a workflow template (bugfix, refactor, review, or your own).
+    New(NewCommand),
+
+    /// Learn Codex in a demo project with a scripted model that uses no tokens.
+    Tutorial(TutorialCommand),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -371,6 +404,21 @@ struct DeleteCommand {
     force: bool,
 }
 
+#[derive(Debug, Parser)]
+struct NewCommand {
+    /// Workflow template to start from: `bugfix`, `refactor`, `review`, or a template in
+    /// `$CODEX_HOME/workflows/<NAME>.md`. The prompt becomes the template's task and the rendered
+    /// template becomes the session goal.
+    #[arg(long = "template", value_name = "NAME", requires = "prompt")]
+    template: Option<String>,
+
+    #[clap(flatten)]
+    remote: InteractiveRemoteOptions,
+
+    #[clap(flatten)]
+    config_overrides: TuiCli,
+}
+
 #[derive(Debug, Parser)]
 struct ForkCommand {
     /// Conversation/session id (UUID). When provided, forks this session.
@@ -969,6 +1017,7 @@ async fn cli_main(
     let MultitoolCli {
         config_overrides: mut root_config_overrides,
         feature_toggles,
+        run_limits,
         remote,
         mut interactive,
         subcommand,
@@ -977,6 +1026,9 @@ async fn cli_main(
     // Fold --enable/--disable into config overrides so they flow to all subcommands.
     let toggle_overrides = feature_toggles.to_overrides()?;
     root_config_overrides.raw_overrides.extend(toggle_overrides);
+    root_config_overrides
+        .raw_overrides
+        .extend(run_limits.to_overrides());
     let root_remote = remote.remote;
     let root_remote_auth_token_env = remote.remote_auth_token_env;
     let root_strict_config = interactive.strict_config;
@@ -1062,6 +1114,19 @@ async fn cli_main(
                 loader_overrides_for_profile(interactive.config_profile_v2.as_ref())?;
             mcp_cli.run(loader_overrides).await?;
         }
+        Some(Subcommand::Config(mut config_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "config",
+            )?;
+            // Propagate any root-level config overrides (e.g. `-c key=value`).
+            prepend_config_flags(
+                &mut config_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            config_cli.run().await?;
+        }
         Some(Subcommand::Plugin(plugin_cli)) => {
             reject_remote_mode_for_subcommand(
                 root_remote.as_deref(),
@@ -1346,6 +1411,50 @@ async fn cli_main(
             .await?;
             handle_app_exit(exit_info)?;
         }
+        Some(Subcommand::New(NewCommand {
+            template,
+            remote,
+            config_overrides,
+        })) => {
+            interactive = finalize_new_interactive(
+                interactive,
+                root_config_overrides.clone(),
+                template,
+                config_overrides,
+            );
+            let exit_info = run_interactive_tui(
+                interactive,
+                remote.remote.or(root_remote.clone()),
+                remote
+                    .remote_auth_token_env
+                    .or(root_remote_auth_token_env.clone()),
+                arg0_paths.clone(),
+            )
+            .await?;
+            handle_app_exit(exit_info)?;
+        }
+        Some(Subcommand::Tutorial(tutorial_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "tutorial",
+            )?;
+            let tutorial = tutorial::Tutorial::start(&tutorial_cli)?;
+            prepend_config_flags(
+                &mut interactive.config_overrides,
+                root_config_overrides.clone(),
+            );
+            tutorial.configure(&mut interactive)?;
+            let exit_info = run_interactive_tui(
+                interactive,
+                /*remote*/ None,
+                /*remote_auth_token_env*/ None,
+                arg0_paths.clone(),
+            )
+            .await?;
+            tutorial.finish();
+            handle_app_exit(exit_info)?;
+        }
         Some(Subcommand::Login(mut login_cli)) => {
             reject_remote_mode_for_subcommand(
                 root_remote.as_deref(),
@@ -1432,6 +1541,46 @@ async fn cli_main(
             )
             .await?;
         }
+        Some(Subcommand::Bugreport(bugreport_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "bugreport",
+            )?;
+            bugreport::run_bugreport(
+                bugreport_cli,
+                root_config_overrides.clone(),
+                &interactive,
+                &arg0_paths,
+            )
+            .await?;
+        }
+        Some(Subcommand::Share(share_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "share",
+            )?;
+            share::run_share(
+                share_cli,
+                root_config_overrides.clone(),
+                &interactive,
+                &arg0_paths,
+            )
+            .await?;
+        }
+        Some(Subcommand::Bench(mut bench_cli)) => {
+            reject_remote_mode_for_subcommand(
+                root_remote.as_deref(),
+                root_remote_auth_token_env.as_deref(),
+                "bench",
+            )?;
+            prepend_config_flags(
+                &mut bench_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            bench_cli.run().await?;
+        }
         Some(Subcommand::Cloud(mut cloud_cli)) => {
             reject_remote_mode_for_subcommand(
                 root_remote.as_deref(),
@@ -1677,13 +1826,14 @@ fn profile_v2_for_subcommand<'a>(
         | Subcommand::Delete(_)
         | Subcommand::Unarchive(_)
         | Subcommand::Fork(_)
+        | Subcommand::New(_)
         | Subcommand::Mcp(_)
         | Subcommand::Sandbox(_)
         | Subcommand::Debug(DebugCommand {
             subcommand: DebugSubcommand::PromptInput(_),
         }) => Ok(Some(profile_v2)),
         _ => anyhow::bail!(
-            "--profile only applies to runtime commands and `codex mcp`: `codex`, `codex exec`, `codex review`, `codex resume`, `codex archive`, `codex delete`, `codex unarchive`, `codex fork`, `codex mcp`, `codex sandbox`, and `codex debug prompt-input`."
+            "--profile only applies to runtime commands and `codex mcp`: `codex`, `codex exec`, `codex review`, `codex resume`, `codex archive`, `codex delete`, `codex unarchive`, `codex fork`, `codex new`, `codex mcp`, `codex sandbox`, and `codex debug prompt-input`."
         ),
     }
 }
@@ -2143,13 +2293,19 @@ fn unsupported_subcommand_name_for_strict_config(
         | Some(Subcommand::Delete(_))
         | Some(Subcommand::Unarchive(_))
         | Some(Subcommand::Fork(_))
-        | Some(Subcommand::Doctor(_)) => None,
+        | Some(Subcommand::New(_))
+        | Some(Subcommand::Tutorial(_))
+        | Some(Subcommand::Doctor(_))
+        | Some(Subcommand::Bugreport(_))
+        | Some(Subcommand::Share(_)) => None,
         Some(Subcommand::AppServer(app_server)) if app_server.subcommand.is_none() => None,
         Some(Subcommand::AppServer(app_server)) => {
             Some(app_server_subcommand_name(app_server.subcommand.as_ref()))
         }
         Some(Subcommand::RemoteControl(remote_control)) => Some(remote_control.subcommand_name()),
         Some(Subcommand::Mcp(_)) => Some("mcp"),
+        Some(Subcommand::Config(_)) => Some("config"),
+        Some(Subcommand::Bench(_)) => Some("bench"),
         Some(Subcommand::Plugin(_)) => Some("plugin"),
         #[cfg(any(target_os = "macos", target_os = "windows"))]
         Some(Subcommand::App(_)) => Some("app"),
@@ -2470,6 +2626,19 @@ fn finalize_fork_interactive(
     interactive
 }
 
+/// Build the final `TuiCli` for a `codex new` invocation.
+fn finalize_new_interactive(
+    mut interactive: TuiCli,
+    root_config_overrides: CliConfigOverrides,
+    template: Option<String>,
+    new_cli: TuiCli,
+) -> TuiCli {
+    interactive.workflow_template = template;
+    merge_interactive_cli_flags(&mut interactive, new_cli);
+    prepend_config_flags(&mut interactive.config_overrides, root_config_overrides);
+    interactive
+}
+
 fn finalize_session_archive_interactive(
     mut interactive: TuiCli,
     root_config_overrides: CliConfigOverrides,
@@ -2501,6 +2670,8 @@ fn merge_interactive_cli_flags(interactive: &mut TuiCli, subcommand_cli: TuiCli)
         strict_config,
         approval_policy,
         web_search,
+        log_json,
+        safe_mode,
         prompt,
         config_overrides,
         ..
@@ -2514,6 +2685,12 @@ fn merge_interactive_cli_flags(interactive: &mut TuiCli, subcommand_cli: TuiCli)
     if web_search {
         interactive.web_search = true;
     }
+    if log_json {
+        interactive.log_json = true;
+    }
+    if safe_mode {
+        interactive.safe_mode = true;
+    }
     if strict_config {
         interactive.strict_config = true;
     }
@@ -2634,6 +2811,7 @@ mod tests {
             config_overrides: root_overrides,
             subcommand,
             feature_toggles: _,
+            run_limits: _,
             remote: _,
         } = cli;
 
@@ -2668,6 +2846,7 @@ mod tests {
             config_overrides: root_overrides,
             subcommand,
             feature_toggles: _,
+            run_limits: _,
             remote: _,
         } = cli;
 
@@ -2686,6 +2865,29 @@ mod tests {
         finalize_fork_interactive(interactive, root_overrides, session_id, last, all, fork_cli)
     }
 
+    fn finalize_new_from_args(args: &[&str]) -> TuiCli {
+        let cli = MultitoolCli::try_parse_from(args).expect("parse");
+        let MultitoolCli {
+            interactive,
+            config_overrides: root_overrides,
+            subcommand,
+            feature_toggles: _,
+            run_limits: _,
+            remote: _,
+        } = cli;
+
+        let Subcommand::New(NewCommand {
+            template,
+            remote: _,
+            config_overrides: new_cli,
+        }) = subcommand.expect("new present")
+        else {
+            unreachable!()
+        };
+
+        finalize_new_interactive(interactive, root_overrides, template, new_cli)
+    }
+
     fn finalize_archive_from_args(args: &[&str]) -> (String, TuiCli, InteractiveRemoteOptions) {
         let cli = MultitoolCli::try_parse_from(args).expect("parse");
         let MultitoolCli {
@@ -2693,6 +2895,7 @@ mod tests {
             config_overrides: root_overrides,
             subcommand,
             feature_toggles: _,
+            run_limits: _,
             remote: _,
         } = cli;
 
@@ -3544,6 +3747,31 @@ mod tests {
         assert!(interactive.fork_show_all);
     }
 
+    #[test]
+    fn new_template_sets_workflow_template_and_prompt() {
+        let interactive = finalize_new_from_args(
+            [
+                "codex",
+                "new",
+                "--template",
+                "bugfix",
+                "login fails on Safari",
+            ]
+            .as_ref(),
+        );
+
+        assert_eq!(interactive.workflow_template.as_deref(), Some("bugfix"));
+        assert_eq!(interactive.prompt.as_deref(), Some("login fails on Safari"));
+    }
+
+    #[test]
+    fn new_template_requires_prompt() {
+        let err = MultitoolCli::try_parse_from(["codex", "new", "--template", "bugfix"])
+            .expect_err("--template without a prompt should be rejected");
+
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
     #[test]
     fn app_server_analytics_default_disabled_without_flag() {
         let app_server = app_server_from_args(["codex", "app-server"].as_ref());
@@ -0,0 +1,72 @@
+use clap::Args;
+
+/// `--max-*` flags that time-box a session. Each folds into a `-c run_limits.*` override.
+#[derive(Debug, Default, Args, Clone)]
+pub(crate) struct RunLimitOptions {
+    /// Wrap up once the session has run this long, such as `90s`, `30m`, or `2h`.
+    /// Equivalent to `-c run_limits.max_duration_seconds=<SECONDS>`.
+    #[arg(long = "max-duration", value_name = "DURATION", value_parser = parse_duration_seconds, global = true)]
+    max_duration_seconds: Option<u64>,
+
+    /// Wrap up on this turn. Equivalent to `-c run_limits.max_turns=<N>`.
+    #[arg(long = "max-turns", value_name = "N", value_parser = clap::value_parser!(u32).range(1..), global = true)]
+    max_turns: Option<u32>,
+
+    /// Wrap up once the session has spent this many tokens, such as `200000` or `500k`.
+    /// Equivalent to `-c run_limits.max_tokens=<TOKENS>`.
+    #[arg(long = "max-tokens", value_name = "TOKENS", value_parser = parse_token_count, global = true)]
+    max_tokens: Option<u64>,
+}
+
+impl RunLimitOptions {
+    pub(crate) fn to_overrides(&self) -> Vec<String> {
+        [
+            ("max_duration_seconds", self.max_duration_seconds),
+            ("max_turns", self.max_turns.map(u64::from)),
+            ("max_tokens", self.max_tokens),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| format!("run_limits.{key}={value}")))
+        .collect()
+    }
+}
+
+fn parse_duration_seconds(value: &str) -> Result<u64, String> {
+    let trimmed = value.trim();
+    let (number, unit_seconds) = if let Some(number) = trimmed.strip_suffix('s') {
+        (number, 1)
+    } else if let Some(number) = trimmed.strip_suffix('m') {
+        (number, 60)
+    } else if let Some(number) = trimmed.strip_suffix('h') {
+        (number, 60 * 60)
+    } else {
+        (trimmed, 1)
+    };
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(unit_seconds))
+        .filter(|seconds| *seconds > 0)
+        .ok_or_else(|| format!("invalid duration `{value}`; use a value like 90s, 30m, or 2h"))
+}
+
+fn parse_token_count(value: &str) -> Result<u64, String> {
+    let lowercase = value.trim().to_ascii_lowercase();
+    let (number, multiplier) = if let Some(number) = lowercase.strip_suffix('k') {
+        (number, 1_000)
+    } else if let Some(number) = lowercase.strip_suffix('m') {
+        (number, 1_000_000)
+    } else {
+        (lowercase.as_str(), 1)
+    };
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .filter(|tokens| *tokens > 0)
+        .ok_or_else(|| format!("invalid token count `{value}`; use a value like 200000 or 500k"))
+}
+
+#[cfg(test)]
+#[path = "run_limits_tests.rs"]
+mod tests;
@@ -0,0 +1,64 @@
+use clap::Parser;
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+#[derive(Debug, Parser)]
+struct TestCli {
+    #[clap(flatten)]
+    run_limits: RunLimitOptions,
+}
+
+fn overrides(args: &[&str]) -> Vec<String> {
+    TestCli::try_parse_from(std::iter::once("codex").chain(args.iter().copied()))
+        .expect("parse should succeed")
+        .run_limits
+        .to_overrides()
+}
+
+#[test]
+fn run_limit_flags_generate_overrides() {
+    assert_eq!(
+        overrides(&[
+            "--max-duration",
+            "30m",
+            "--max-turns",
+            "5",
+            "--max-tokens",
+            "500k"
+        ]),
+        vec![
+            "run_limits.max_duration_seconds=1800".to_string(),
+            "run_limits.max_turns=5".to_string(),
+            "run_limits.max_tokens=500000".to_string(),
+        ]
+    );
+    assert_eq!(overrides(&[]), Vec::<String>::new());
+}
+
+#[test]
+fn duration_accepts_units_and_bare_seconds() {
+    assert_eq!(parse_duration_seconds("90"), Ok(90));
+    assert_eq!(parse_duration_seconds("90s"), Ok(90));
+    assert_eq!(parse_duration_seconds("2h"), Ok(7200));
+    assert_eq!(
+        parse_duration_seconds("0m"),
+        Err("invalid duration `0m`; use a value like 90s, 30m, or 2h".to_string())
+    );
+    assert!(parse_duration_seconds("soon").is_err());
+}
+
+#[test]
+fn token_count_accepts_suffixes() {
+    assert_eq!(parse_token_count("200000"), Ok(200_000));
+    assert_eq!(parse_token_count("2M"), Ok(2_000_000));
+    assert_eq!(
+        parse_token_count("lots"),
+        Err("invalid token count `lots`; use a value like 200000 or 500k".to_string())
+    );
+}
+
+#[test]
+fn max_turns_rejects_zero() {
+    assert!(TestCli::try_parse_from(["codex", "--max-turns", "0"]).is_err());
+}
@@ -0,0 +1,408 @@
+//! Implements `codex share`, which exports a recorded session as a self-contained HTML page.
+//!
+//! The page is meant to be pasted into a gist or wiki, so every piece of text is passed through
+//! secret redaction and the user's home directory is shown as `~`. Output depends only on the
+//! rollout: there is no export timestamp, styles are inlined, and each transcript entry starts on
+//! its own line, so exporting the same session twice produces identical files and re-exporting a
+//! continued session produces a readable diff.
+//!
+//! Files registered as session artifacts are appended after the transcript. Text artifacts are
+//! embedded, redacted like everything else; binary and oversized ones are listed by path only.
+//! Only artifacts inside the session's working directory that the configured sandbox would let a
+//! session read, or inside the scratch and artifact directories Codex keeps for the session, are
+//! included, and the user confirms the list before the page is written.
+
+use std::io::IsTerminal;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use codex_arg0::Arg0DispatchPaths;
+use codex_protocol::permissions::FileSystemSandboxPolicy;
+use codex_secrets::redact_secrets;
+use codex_tui::Cli as TuiCli;
+use codex_utils_cli::CliConfigOverrides;
+use codex_utils_session_artifacts::SessionArtifact;
+use codex_utils_session_artifacts::load_artifacts;
+use codex_utils_session_artifacts::managed_dirs;
+use serde_json::Value as JsonValue;
+
+use crate::bugreport::find_rollout;
+use crate::doctor;
+
+/// Artifacts larger than this are listed without their contents.
+const MAX_EMBEDDED_ARTIFACT_BYTES: u64 = 256 * 1024;
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; }
+section { border-left: 3px solid #ccc; margin: 1rem 0; padding: 0.25rem 0.75rem; }
+section.user { border-color: #0a7cff; }
+section.assistant { border-color: #10a37f; }
+section.tool, section.output { border-color: #999; }
+section.artifact { border-color: #b06ab3; }
+h2 { font-size: 0.8rem; margin: 0.25rem 0; text-transform: uppercase; color: #666; }
+pre { white-space: pre-wrap; word-break: break-word; margin: 0.25rem 0; }
+section.output pre { color: #555; }";
+
+/// Options for `codex share`.
+#[derive(Debug, Parser)]
+pub struct ShareCommand {
+    /// Session id to export. Defaults to the most recent session.
+    #[arg(long = "session", value_name = "SESSION_ID")]
+    session_id: Option<String>,
+
+    /// Where to write the page. Defaults to `codex-share-<session-id>.html` in the current
+    /// directory.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Replace tool output and patch bodies with a line count, so file contents stay out of the
+    /// page.
+    #[arg(long, default_value_t = false)]
+    elide_file_contents: bool,
+
+    /// Include the session's artifacts without asking for confirmation.
+    #[arg(long, short = 'y', default_value_t = false)]
+    yes: bool,
+}
+
+/// Options that change how a session is rendered.
+#[derive(Debug)]
+pub(crate) struct ShareOptions<'a> {
+    /// Shown as `~` wherever it appears.
+    pub(crate) home_dir: Option<&'a Path>,
+    pub(crate) elide_file_contents: bool,
+}
+
+/// A session artifact with the contents to embed, when it is small enough and text.
+#[derive(Debug)]
+pub(crate) struct SharedArtifact {
+    pub(crate) artifact: SessionArtifact,
+    pub(crate) contents: Option<String>,
+}
+
+impl SharedArtifact {
+    fn read(artifact: SessionArtifact) -> Self {
+        let contents = std::fs::metadata(&artifact.path)
+            .ok()
+            .filter(|metadata| metadata.is_file() && metadata.len() <= MAX_EMBEDDED_ARTIFACT_BYTES)
+            .and_then(|_| std::fs::read_to_string(&artifact.path).ok());
+        Self { artifact, contents }
+    }
+}
+
+/// An artifact left out of the page and why.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ExcludedArtifact {
+    pub(crate) artifact: SessionArtifact,
+    pub(crate) reason: &'static str,
+}
+
+/// Splits the registered artifacts into those that may be shared and those that may not. An
+/// artifact is shared only when it resolves, after following symlinks, to a file inside one of
+/// `managed_dirs`, the directories Codex keeps for the session, or to a file inside `workspace`
+/// that `read_policy` lets a session read.
+pub(crate) fn select_artifacts(
+    artifacts: Vec<SessionArtifact>,
+    workspace: Option<&Path>,
+    managed_dirs: &[PathBuf],
+    read_policy: &FileSystemSandboxPolicy,
+) -> (Vec<SharedArtifact>, Vec<ExcludedArtifact>) {
+    let workspace = workspace.and_then(|workspace| std::fs::canonicalize(workspace).ok());
+    let managed_dirs = managed_dirs
+        .iter()
+        .filter_map(|dir| std::fs::canonicalize(dir).ok())
+        .collect::<Vec<_>>();
+    let mut shared = Vec::new();
+    let mut excluded = Vec::new();
+    for artifact in artifacts {
+        let path = std::fs::canonicalize(&artifact.path);
+        let in_managed_dir = path
+            .as_ref()
+            .is_ok_and(|path| managed_dirs.iter().any(|dir| path.starts_with(dir)));
+        let reason = match (&workspace, path) {
+            _ if in_managed_dir => None,
+            (None, _) => Some("the session's working directory is unknown"),
+            (Some(_), Err(_)) => Some("the file no longer exists"),
+            (Some(workspace), Ok(path)) if !path.starts_with(workspace) => {
+                Some("outside the session's working directory")
+            }
+            (Some(workspace), Ok(path))
+                if !read_policy.can_read_path_with_cwd(&path, workspace) =>
+            {
+                Some("the sandbox does not allow reading it")
+            }
+            (Some(_), Ok(_)) => None,
+        };
+        match reason {
+            Some(reason) => excluded.push(ExcludedArtifact { artifact, reason }),
+            None => shared.push(SharedArtifact::read(artifact)),
+        }
+    }
+    (shared, excluded)
+}
+
+/// Renders the session, writes the page, and prints its path.
+pub async fn run_share(
+    command: ShareCommand,
+    root_config_overrides: CliConfigOverrides,
+    interactive: &TuiCli,
+    arg0_paths: &Arg0DispatchPaths,
+) -> anyhow::Result<()> {
+    let config = doctor::load_config(root_config_overrides, interactive, arg0_paths).await?;
+    let codex_home = config.codex_home.to_path_buf();
+    let rollout_path = find_rollout(&codex_home, command.session_id.as_deref())
+        .await?
+        .context("no recorded sessions found")?;
+    let mut reader = codex_rollout::open_rollout_line_reader(&rollout_path)
+        .await
+        .with_context(|| format!("failed to open {}", rollout_path.display()))?;
+    let mut lines = Vec::new();
+    while let Some(line) = reader.next_line().await? {
+        lines.push(line);
+    }
+
+    let session_id = session_id(&lines);
+    let registered = match &session_id {
+        Some(session_id) => load_artifacts(&codex_home, session_id)
+            .with_context(|| format!("failed to read the artifacts of session {session_id}"))?,
+        None => Vec::new(),
+    };
+    let workspace = session_cwd(&lines);
+    let managed_dirs = session_id
+        .as_deref()
+        .map(|session_id| managed_dirs(&codex_home, session_id).to_vec())
+        .unwrap_or_default();
+    let (artifacts, excluded) = select_artifacts(
+        registered,
+        workspace.as_deref(),
+        &managed_dirs,
+        &config.permissions.file_system_sandbox_policy(),
+    );
+    if !artifacts.is_empty() || !excluded.is_empty() {
+        eprint!("{}", describe_artifacts(&artifacts, &excluded));
+    }
+    if !artifacts.is_empty() && !command.yes {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("pass --yes to include session artifacts when stdin is not a terminal");
+        }
+        if !confirm("Include these artifacts in the page? [y/N]: ")? {
+            anyhow::bail!("nothing was written");
+        }
+    }
+
+    let home_dir = std::env::home_dir();
+    let options = ShareOptions {
+        home_dir: home_dir.as_deref(),
+        elide_file_contents: command.elide_file_contents,
+    };
+    let page = render_share_html(&lines, &artifacts, &options);
+    let output = command.output.unwrap_or_else(|| {
+        let session_id = session_id.unwrap_or_else(|| "session".to_string());
+        PathBuf::from(format!("codex-share-{session_id}.html"))
+    });
+    std::fs::write(&output, page)
+        .with_context(|| format!("failed to write {}", output.display()))?;
+    println!(
+        "Wrote {}. Review it before sharing; redaction is best effort.",
+        output.display()
+    );
+    Ok(())
+}
+
+fn session_id(lines: &[String]) -> Option<String> {
+    session_meta_field(lines, "id")
+}
+
+fn session_cwd(lines: &[String]) -> Option<PathBuf> {
+    session_meta_field(lines, "cwd").map(PathBuf::from)
+}
+
+fn session_meta_field(lines: &[String], name: &str) -> Option<String> {
+    lines.iter().find_map(|line| {
+        let item = serde_json::from_str::<JsonValue>(line).ok()?;
+        if item.get("type")?.as_str()? != "session_meta" {
+            return None;
+        }
+        Some(item.get("payload")?.get(name)?.as_str()?.to_string())
+    })
+}
+
+/// The artifact list shown before the page is written.
+pub(crate) fn describe_artifacts(
+    artifacts: &[SharedArtifact],
+    excluded: &[ExcludedArtifact],
+) -> String {
+    let mut out = String::new();
+    if !artifacts.is_empty() {
+        out.push_str("Artifacts to include:\n");
+        for SharedArtifact { artifact, .. } in artifacts {
+            out.push_str(&format!(
+                "  {} ({})\n",
+                artifact.name,
+                artifact.path.display()
+            ));
+        }
+    }
+    if !excluded.is_empty() {
+        out.push_str("Artifacts left out:\n");
+        for ExcludedArtifact { artifact, reason } in excluded {
+            out.push_str(&format!(
+                "  {} ({}): {reason}\n",
+                artifact.name,
+                artifact.path.display()
+            ));
+        }
+    }
+    out
+}
+
+fn confirm(prompt: &str) -> std::io::Result<bool> {
+    eprint!("{prompt}");
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    let answer = input.trim();
+    Ok(answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes"))
+}
+
+/// Renders rollout lines as an HTML page with one section per message or tool call, followed by
+/// one section per artifact.
+pub(crate) fn render_share_html(
+    lines: &[String],
+    artifacts: &[SharedArtifact],
+    options: &ShareOptions<'_>,
+) -> String {
+    let mut title = "Codex session".to_string();
+    let mut body = String::new();
+    for line in lines {
+        let Ok(item) = serde_json::from_str::<JsonValue>(line) else {
+            continue;
+        };
+        let Some(payload) = item.get("payload") else {
+            continue;
+        };
+        let field = |name: &str| payload.get(name).and_then(JsonValue::as_str);
+        let item_type = item.get("type").and_then(JsonValue::as_str);
+        match (item_type, field("type")) {
+            (Some("session_meta"), _) => {
+                if let Some(timestamp) = field("timestamp") {
+                    title = format!("Codex session, {timestamp}");
+                }
+            }
+            (Some("event_msg"), Some("user_message")) => {
+                push_section(&mut body, "user", "User", field("message"), options);
+            }
+            (Some("event_msg"), Some("agent_message")) => {
+                push_section(&mut body, "assistant", "Codex", field("message"), options);
+            }
+            (Some("response_item"), Some("function_call")) => {
+                let heading = format!("Tool call: {}", field("name").unwrap_or("unknown"));
+                push_section(&mut body, "tool", &heading, field("arguments"), options);
+            }
+            (Some("response_item"), Some("custom_tool_call")) => {
+                let heading = format!("Tool call: {}", field("name").unwrap_or("unknown"));
+                // Custom tool input is usually a patch, which carries file contents.
+                let input = field("input").map(|input| elide(input, options));
+                push_section(&mut body, "tool", &heading, input.as_deref(), options);
+            }
+            (Some("response_item"), Some("function_call_output" | "custom_tool_call_output")) => {
+                let output = payload.get("output").map(output_text).unwrap_or_default();
+                let output = elide(&output, options);
+                push_section(&mut body, "output", "Tool output", Some(&output), options);
+            }
+            _ => {}
+        }
+    }
+    for SharedArtifact { artifact, contents } in artifacts {
+        let heading = format!("Artifact: {}", artifact.name);
+        let mut text = artifact.path.display().to_string();
+        if let Some(description) = &artifact.description {
+            text = format!("{text}\n{description}");
+        }
+        match contents {
+            Some(contents) => text = format!("{text}\n\n{}", elide(contents, options)),
+            None => text.push_str("\n\n[contents not included]"),
+        }
+        push_section(&mut body, "artifact", &heading, Some(&text), options);
+    }
+
+    let title = escape_html(&sanitize(&title, options));
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n{body}</body>\n</html>\n"
+    )
+}
+
+fn push_section(
+    body: &mut String,
+    class: &str,
+    heading: &str,
+    text: Option<&str>,
+    options: &ShareOptions<'_>,
+) {
+    body.push_str(&format!(
+        "<section class=\"{class}\">\n<h2>{}</h2>\n",
+        escape_html(heading)
+    ));
+    if let Some(text) = text {
+        body.push_str(&format!(
+            "<pre>{}</pre>\n",
+            escape_html(&sanitize(text, options))
+        ));
+    }
+    body.push_str("</section>\n");
+}
+
+/// Tool output is either a plain string or a list of content items.
+fn output_text(output: &JsonValue) -> String {
+    match output {
+        JsonValue::String(text) => text.clone(),
+        JsonValue::Array(items) => items
+            .iter()
+            .filter_map(|item| item.get("text").and_then(JsonValue::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        JsonValue::Null | JsonValue::Bool(_) | JsonValue::Number(_) | JsonValue::Object(_) => {
+            output.to_string()
+        }
+    }
+}
+
+fn elide(text: &str, options: &ShareOptions<'_>) -> String {
+    if !options.elide_file_contents || text.is_empty() {
+        return text.to_string();
+    }
+    match text.lines().count() {
+        1 => "[1 line elided]".to_string(),
+        count => format!("[{count} lines elided]"),
+    }
+}
+
+fn sanitize(text: &str, options: &ShareOptions<'_>) -> String {
+    let redacted = redact_secrets(text.to_string());
+    match options.home_dir.and_then(Path::to_str) {
+        Some(home) if !home.is_empty() && home != "/" => redacted.replace(home, "~"),
+        Some(_) | None => redacted,
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+#[path = "share_tests.rs"]
+mod tests;
@@ -0,0 +1,230 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn rollout() -> Vec<String> {
+    [
+        r#"{"timestamp":"t0","type":"session_meta","payload":{"id":"abc","timestamp":"2026-01-02T03:04:05Z","cwd":"/home/dev/repo"}}"#,
+        r#"{"timestamp":"t1","type":"event_msg","payload":{"type":"user_message","message":"Why does <main> fail? key=sk-abcdefghijklmnopqrstuvwxyz"}}"#,
+        r#"{"timestamp":"t2","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"cat\",\"/home/dev/repo/src/main.rs\"]}","call_id":"c1"}}"#,
+        r#"{"timestamp":"t3","type":"response_item","payload":{"type":"function_call_output","call_id":"c1","output":"fn main() {\n    panic!();\n}"}}"#,
+        r#"{"timestamp":"t4","type":"event_msg","payload":{"type":"agent_message","message":"It panics & exits."}}"#,
+        r#"{"timestamp":"t5","type":"event_msg","payload":{"type":"token_count"}}"#,
+        "not json",
+    ]
+    .into_iter()
+    .map(str::to_string)
+    .collect()
+}
+
+fn body(page: &str) -> &str {
+    let start = page.find("<h1>").expect("page has a heading");
+    let end = page.find("</body>").expect("page has a body");
+    &page[start..end]
+}
+
+#[test]
+fn renders_redacted_transcript() {
+    let options = ShareOptions {
+        home_dir: Some(Path::new("/home/dev")),
+        elide_file_contents: false,
+    };
+    let page = render_share_html(&rollout(), &[], &options);
+
+    assert_eq!(
+        body(&page),
+        "\
+<h1>Codex session, 2026-01-02T03:04:05Z</h1>
+<section class=\"user\">
+<h2>User</h2>
+<pre>Why does &lt;main&gt; fail? key=[REDACTED_SECRET]</pre>
+</section>
+<section class=\"tool\">
+<h2>Tool call: shell</h2>
+<pre>{&quot;command&quot;:[&quot;cat&quot;,&quot;~/repo/src/main.rs&quot;]}</pre>
+</section>
+<section class=\"output\">
+<h2>Tool output</h2>
+<pre>fn main() {
+    panic!();
+}</pre>
+</section>
+<section class=\"assistant\">
+<h2>Codex</h2>
+<pre>It panics &amp; exits.</pre>
+</section>
+"
+    );
+    assert_eq!(render_share_html(&rollout(), &[], &options), page);
+}
+
+#[test]
+fn elides_tool_output_when_requested() {
+    let options = ShareOptions {
+        home_dir: None,
+        elide_file_contents: true,
+    };
+    let page = render_share_html(&rollout(), &[], &options);
+
+    assert!(page.contains("<pre>[3 lines elided]</pre>"));
+    assert!(!page.contains("panic!"));
+}
+
+#[test]
+fn appends_artifacts_after_the_transcript() {
+    let options = ShareOptions {
+        home_dir: Some(Path::new("/home/dev")),
+        elide_file_contents: false,
+    };
+    let artifacts = [
+        SharedArtifact {
+            artifact: SessionArtifact {
+                name: "report".to_string(),
+                path: PathBuf::from("/home/dev/repo/out/report.md"),
+                description: Some("Latency <p99>".to_string()),
+            },
+            contents: Some("token sk-abcdefghijklmnopqrstuvwxyz".to_string()),
+        },
+        SharedArtifact {
+            artifact: SessionArtifact {
+                name: "plot".to_string(),
+                path: PathBuf::from("/tmp/plot.png"),
+                description: None,
+            },
+            contents: None,
+        },
+    ];
+    let page = render_share_html(&rollout(), &artifacts, &options);
+
+    assert!(
+        body(&page).ends_with(
+            "\
+<section class=\"artifact\">
+<h2>Artifact: report</h2>
+<pre>~/repo/out/report.md
+Latency &lt;p99&gt;
+
+token [REDACTED_SECRET]</pre>
+</section>
+<section class=\"artifact\">
+<h2>Artifact: plot</h2>
+<pre>/tmp/plot.png
+
+[contents not included]</pre>
+</section>
+"
+        ),
+        "{page}"
+    );
+}
+
+#[test]
+fn finds_session_id_for_default_file_name() {
+    assert_eq!(session_id(&rollout()), Some("abc".to_string()));
+}
+
+#[test]
+fn shares_only_readable_artifacts_inside_the_session_workspace() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let dir = std::fs::canonicalize(dir.path()).expect("canonical dir");
+    let workspace = dir.join("repo");
+    std::fs::create_dir(&workspace).expect("create repo");
+    std::fs::write(workspace.join("report.md"), "# Report").expect("write report");
+    std::fs::write(dir.join("id_ed25519"), "private key").expect("write key");
+    let artifact = |name: &str, path: PathBuf| SessionArtifact {
+        name: name.to_string(),
+        path,
+        description: None,
+    };
+    let registered = || {
+        vec![
+            artifact("report", workspace.join("report.md")),
+            artifact("key", workspace.join("../id_ed25519")),
+            artifact("gone", workspace.join("gone.md")),
+        ]
+    };
+
+    let (shared, excluded) = select_artifacts(
+        registered(),
+        Some(&workspace),
+        /*managed_dirs*/ &[],
+        &FileSystemSandboxPolicy::unrestricted(),
+    );
+    assert_eq!(
+        shared
+            .iter()
+            .map(|shared| (shared.artifact.name.as_str(), shared.contents.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![("report", Some("# Report"))]
+    );
+    assert_eq!(
+        excluded
+            .iter()
+            .map(|excluded| (excluded.artifact.name.as_str(), excluded.reason))
+            .collect::<Vec<_>>(),
+        vec![
+            ("key", "outside the session's working directory"),
+            ("gone", "the file no longer exists"),
+        ]
+    );
+    assert_eq!(
+        describe_artifacts(&shared, &excluded),
+        format!(
+            "Artifacts to include:\n  report ({})\nArtifacts left out:\n  key ({}): outside the session's working directory\n  gone ({}): the file no longer exists\n",
+            workspace.join("report.md").display(),
+            workspace.join("../id_ed25519").display(),
+            workspace.join("gone.md").display(),
+        )
+    );
+
+    let (shared, excluded) = select_artifacts(
+        registered(),
+        Some(&workspace),
+        /*managed_dirs*/ &[],
+        &FileSystemSandboxPolicy::restricted(Vec::new()),
+    );
+    assert!(shared.is_empty());
+    assert_eq!(excluded[0].reason, "the sandbox does not allow reading it");
+
+    let (shared, excluded) = select_artifacts(
+        registered(),
+        /*workspace*/ None,
+        /*managed_dirs*/ &[],
+        &FileSystemSandboxPolicy::unrestricted(),
+    );
+    assert!(shared.is_empty());
+    assert_eq!(excluded.len(), 3);
+}
+
+#[test]
+fn shares_artifacts_in_the_directories_codex_manages_for_the_session() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let dir = std::fs::canonicalize(dir.path()).expect("canonical dir");
+    let codex_home = dir.join("codex-home");
+    let workspace = dir.join("repo");
+    std::fs::create_dir(&workspace).expect("create repo");
+    let managed = managed_dirs(&codex_home, "abc");
+    let profile = managed[0].join("profile-1.folded");
+    std::fs::create_dir_all(&managed[0]).expect("create artifact dir");
+    std::fs::write(&profile, "main;run 3\n").expect("write profile");
+    let registered = vec![SessionArtifact {
+        name: "profile".to_string(),
+        path: profile.clone(),
+        description: None,
+    }];
+
+    let (shared, excluded) = select_artifacts(
+        registered,
+        Some(&workspace),
+        &managed,
+        &FileSystemSandboxPolicy::restricted(Vec::new()),
+    );
+
+    assert_eq!(
+        shared
+            .iter()
+            .map(|shared| (shared.artifact.path.clone(), shared.contents.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![(profile, Some("main;run 3\n"))]
+    );
+    assert!(excluded.is_empty());
+}
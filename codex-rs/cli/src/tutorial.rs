@@ -0,0 +1,503 @@
+//! Implements `codex tutorial`, a guided first session that costs no tokens.
+//!
+//! The tutorial starts the regular TUI in a throwaway demo project, pointed at a model provider
+//! served from this process by `codex-mock-model-server`. The tutorial script answers each
+//! Responses API request with canned messages and tool calls that walk the user through
+//! approvals, diffs, background terminals, and compaction. Everything the user sees around those
+//! calls (approval prompts, patch rendering, `/ps`, `/compact`) is the real TUI and agent loop.
+//!
+//! The session is configured through `-c` style overrides layered over the user's config, like
+//! `--safe-mode`, so `config.toml` is left untouched. The session still runs under the user's
+//! `CODEX_HOME`, so its rollout, message history, and logs are recorded there like any other
+//! session.
+//!
+//! Each user turn starts the next lesson, unless the message names one (`diffs`, `compaction`,
+//! ...). Within a lesson, the number of tool outputs sent back since the user's message decides
+//! the next step, so the script does not depend on how the agent loop batches requests.
+
+use std::path::Path;
+
+use anyhow::Context;
+use clap::Parser;
+use codex_mock_model_server::MockModelServer;
+use codex_tui::Cli as TuiCli;
+use codex_utils_cli::ApprovalModeCliArg;
+use codex_utils_cli::SandboxModeCliArg;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tempfile::TempDir;
+
+/// Provider id the tutorial session is pointed at.
+const PROVIDER_ID: &str = "codex-tutorial";
+
+/// First message of the session, submitted on the user's behalf.
+const START_PROMPT: &str = "Start the tutorial.";
+
+/// Replaces the summarization prompt so compaction requests can be told apart from user turns.
+const COMPACT_PROMPT: &str = "Summarize the tutorial so far so it can continue from the summary.";
+
+/// Files of the demo project, relative to its root.
+const DEMO_FILES: &[(&str, &str)] = &[
+    ("README.md", DEMO_README),
+    ("greet.sh", DEMO_GREET),
+    ("test.sh", DEMO_TEST),
+];
+
+const DEMO_README: &str = "# Demo project
+
+A tiny project for `codex tutorial`: `greet.sh` prints a greeting and `test.sh` checks it.
+";
+
+const DEMO_GREET: &str = r#"#!/bin/sh
+# Prints a greeting for the name given as the first argument.
+echo "Helo, ${1:-world}!"
+"#;
+
+const DEMO_TEST: &str = r#"#!/bin/sh
+# Checks the greeting printed by greet.sh.
+expected="Hello, Codex!"
+actual="$(sh greet.sh Codex)"
+if [ "$actual" = "$expected" ]; then
+  echo "PASS: $actual"
+else
+  echo "FAIL: expected '$expected', got '$actual'"
+  exit 1
+fi
+"#;
+
+const TEST_COMMAND: &str = "sh test.sh";
+
+const FIX_PATCH: &str = "*** Begin Patch\n*** Update File: greet.sh\n@@\n-echo \"Helo, ${1:-world}!\"\n+echo \"Hello, ${1:-world}!\"\n*** End Patch";
+
+const BACKGROUND_COMMAND: &str =
+    "for i in 1 2 3 4 5 6 7 8 9 10; do echo \"tick $i\"; sleep 3; done";
+
+const WELCOME: &str = "Welcome to Codex! This tutorial runs in a throwaway demo project, and the \
+model you are talking to is a script running on your machine, so nothing is sent to OpenAI and no \
+tokens are used. The agent around it is the real one: the prompts, diffs, and commands you will \
+see are exactly what a normal session shows.
+
+There are four lessons:
+
+1. **approvals**: deciding whether a command may run
+2. **diffs**: reviewing a change to a file
+3. **background terminals**: commands that keep running while you work
+4. **compaction**: keeping long sessions within the context window
+
+Send any message to start the next lesson, or name one (for example `diffs`) to jump to it.";
+
+const APPROVALS_INTRO: &str = "**Lesson 1: approvals.** I will run the demo project's tests. This \
+session uses the `untrusted` approval policy, so Codex asks before running anything that is not a \
+known read-only command. Approve the command to let it run, or decline it to see what happens \
+instead.";
+
+const APPROVALS_RAN: &str = "You approved, so the command ran in the sandbox and its output came \
+back to me. The test fails: `greet.sh` prints `Helo` instead of `Hello`.
+
+The prompt can also approve a command for the rest of the session, and `/permissions` changes the \
+policy, from asking about everything to asking only when a command needs to leave the sandbox.
+
+Send a message for lesson 2, where we fix the typo.";
+
+const APPROVALS_DECLINED: &str = "You declined, so the command never ran; I was told it was \
+rejected and can choose another approach. Declining is always safe, and you can also interrupt a \
+turn at any time with `esc`.
+
+The prompt can also approve a command for the rest of the session, and `/permissions` changes the \
+policy, from asking about everything to asking only when a command needs to leave the sandbox.
+
+Send a message for lesson 2, where we fix a typo in `greet.sh`.";
+
+const DIFFS_INTRO: &str = "**Lesson 2: diffs.** Codex edits files with patches and shows each \
+one as a diff. Under this approval policy you review the diff before it is applied, just like a \
+command.";
+
+const DIFFS_DONE: &str = "The change stays in the transcript as a diff. Press `ctrl+t` to open \
+the full transcript and scroll back through every change and command of the session.
+
+Send a message for lesson 3: background terminals.";
+
+const BACKGROUND_INTRO: &str = "**Lesson 3: background terminals.** I will start a command that \
+takes about 30 seconds. Codex hands control back after a short wait and keeps the command running \
+in a background terminal, so neither of us has to wait for it.";
+
+const BACKGROUND_DONE: &str = "The command is still running. Type `/ps` to list background \
+terminals with their latest output, and `/stop` to stop them all. Left alone, it finishes by \
+itself.
+
+Send a message for the last lesson: compaction.";
+
+const BACKGROUND_UNAVAILABLE: &str = "**Lesson 3: background terminals.** Background terminals \
+need the `unified_exec` feature, which is off in this setup, so this lesson is skipped. With it \
+on, long-running commands keep going after Codex gets control back, and `/ps` lists them.
+
+Send a message for the last lesson: compaction.";
+
+const COMPACTION_INTRO: &str = "**Lesson 4: compaction.** Everything in this conversation is sent \
+to the model on every turn, and long sessions eventually fill its context window. Codex then \
+compacts: it asks the model for a summary and continues from it. It does so automatically, and \
+you can do it yourself at any time.
+
+Type `/compact` now, then send one more message.";
+
+const COMPACTION_SUMMARY: &str = "Summary of the tutorial so far: we ran the demo project's \
+tests through an approval prompt, fixed the typo in `greet.sh` with a patch, started a \
+background terminal, and compacted the conversation into this summary.";
+
+const FINISHED: &str = "That is the whole tutorial.
+
+To start for real, exit with `/quit` and run `codex` in a project of your own. `/` lists every \
+command, and `codex tutorial` is always there to run again.";
+
+/// Options for `codex tutorial`.
+#[derive(Debug, Parser)]
+pub struct TutorialCommand {
+    /// Keep the demo project after the tutorial exits instead of deleting it.
+    #[arg(long, default_value_t = false)]
+    keep_project: bool,
+}
+
+/// A running tutorial: the demo project and the scripted model serving the session.
+pub(crate) struct Tutorial {
+    project: TempDir,
+    server: MockModelServer,
+    keep_project: bool,
+}
+
+impl Tutorial {
+    /// Creates the demo project and starts the scripted model on a local port.
+    pub(crate) fn start(command: &TutorialCommand) -> anyhow::Result<Self> {
+        let project = tempfile::Builder::new()
+            .prefix("codex-tutorial-")
+            .tempdir()
+            .context("failed to create the tutorial demo project")?;
+        write_demo_project(project.path())?;
+
+        let mut script = TutorialScript::default();
+        let server =
+            MockModelServer::start_with_responder(Box::new(move |request| script.respond(request)))
+                .context("failed to start the tutorial model")?;
+
+        Ok(Self {
+            project,
+            server,
+            keep_project: command.keep_project,
+        })
+    }
+
+    /// Points `interactive` at the demo project and the scripted model.
+    pub(crate) fn configure(&self, interactive: &mut TuiCli) -> anyhow::Result<()> {
+        // Trust is looked up by the resolved path, which differs from the temp path on macOS.
+        let project_dir = std::fs::canonicalize(self.project.path())?;
+        interactive.prompt = Some(START_PROMPT.to_string());
+        interactive.cwd = Some(project_dir.clone());
+        interactive.approval_policy = Some(ApprovalModeCliArg::Untrusted);
+        interactive.sandbox_mode = Some(SandboxModeCliArg::WorkspaceWrite);
+        interactive.dangerously_bypass_approvals_and_sandbox = false;
+        interactive.safe_mode = true;
+        interactive
+            .config_overrides
+            .raw_overrides
+            .extend(tutorial_overrides(&self.server.base_url(), &project_dir));
+        Ok(())
+    }
+
+    /// Stops the scripted model and removes the demo project unless it should be kept.
+    pub(crate) fn finish(self) {
+        drop(self.server);
+        if self.keep_project {
+            let path = self.project.keep();
+            println!("Tutorial demo project kept at {}", path.display());
+        }
+    }
+}
+
+fn write_demo_project(root: &Path) -> anyhow::Result<()> {
+    for (name, contents) in DEMO_FILES {
+        std::fs::write(root.join(name), contents)
+            .with_context(|| format!("failed to write demo file {name}"))?;
+    }
+    Ok(())
+}
+
+/// Overrides that route the session to the scripted model and keep it self-contained.
+fn tutorial_overrides(base_url: &str, project_dir: &Path) -> Vec<String> {
+    // JSON string literals are valid TOML basic strings, which keeps Windows paths intact.
+    let project_key = JsonValue::from(project_dir.to_string_lossy().into_owned());
+    vec![
+        format!(
+            "model_providers.{PROVIDER_ID}={{ name = \"Codex tutorial\", base_url = {}, \
+             wire_api = \"responses\", request_max_retries = 0, stream_max_retries = 0 }}",
+            JsonValue::from(base_url)
+        ),
+        format!("model_provider=\"{PROVIDER_ID}\""),
+        format!("projects={{ {project_key} = {{ trust_level = \"trusted\" }} }}"),
+        format!("compact_prompt={}", JsonValue::from(COMPACT_PROMPT)),
+        "approvals_reviewer=\"user\"".to_string(),
+        "features.memories=false".to_string(),
+    ]
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Lesson {
+    #[default]
+    Welcome,
+    Approvals,
+    Diffs,
+    BackgroundTerminals,
+    Compaction,
+    Finished,
+}
+
+impl Lesson {
+    fn next(self) -> Self {
+        match self {
+            Self::Welcome => Self::Approvals,
+            Self::Approvals => Self::Diffs,
+            Self::Diffs => Self::BackgroundTerminals,
+            Self::BackgroundTerminals => Self::Compaction,
+            Self::Compaction | Self::Finished => Self::Finished,
+        }
+    }
+
+    /// The lesson a user message asks for by name, if any.
+    fn named_in(text: &str) -> Option<Self> {
+        let text = text.to_lowercase();
+        [
+            ("approval", Self::Approvals),
+            ("diff", Self::Diffs),
+            ("terminal", Self::BackgroundTerminals),
+            ("compaction", Self::Compaction),
+        ]
+        .into_iter()
+        .find_map(|(name, lesson)| text.contains(name).then_some(lesson))
+    }
+}
+
+/// The scripted model: turns each Responses API request into the events of its reply.
+#[derive(Debug, Default)]
+struct TutorialScript {
+    current: Lesson,
+    upcoming: Lesson,
+    responses: u64,
+}
+
+impl TutorialScript {
+    fn respond(&mut self, request: &JsonValue) -> Vec<JsonValue> {
+        let input = request["input"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or(&[]);
+        let user_index = input.iter().rposition(is_user_message);
+        let user_text = user_index.map(|index| message_text(&input[index]));
+        let outputs = &input[user_index.map_or(0, |index| index + 1)..];
+        let outputs = outputs
+            .iter()
+            .filter(|item| is_tool_output(item))
+            .collect::<Vec<_>>();
+
+        self.responses += 1;
+        let id = format!("resp-tutorial-{}", self.responses);
+        let call_id = format!("call-tutorial-{}", self.responses);
+        let tools = request["tools"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or(&[]);
+
+        let mut items = Vec::new();
+        if user_text.as_deref() == Some(COMPACT_PROMPT) {
+            items.push(assistant_message(&id, COMPACTION_SUMMARY));
+        } else {
+            if outputs.is_empty() {
+                self.current = user_text
+                    .as_deref()
+                    .and_then(Lesson::named_in)
+                    .unwrap_or(self.upcoming);
+                self.upcoming = self.current.next();
+            }
+            self.script_step(&id, &call_id, tools, &outputs, &mut items);
+        }
+
+        let mut events = vec![json!({ "type": "response.created", "response": { "id": id } })];
+        events.extend(
+            items
+                .into_iter()
+                .map(|item| json!({ "type": "response.output_item.done", "item": item })),
+        );
+        events.push(json!({
+            "type": "response.completed",
+            "response": {
+                "id": id,
+                "usage": {
+                    "input_tokens": 0,
+                    "input_tokens_details": null,
+                    "output_tokens": 0,
+                    "output_tokens_details": null,
+                    "total_tokens": 0
+                }
+            }
+        }));
+        events
+    }
+
+    /// Output items for the current lesson, given the tool outputs of the turn so far.
+    fn script_step(
+        &self,
+        id: &str,
+        call_id: &str,
+        tools: &[JsonValue],
+        outputs: &[&JsonValue],
+        items: &mut Vec<JsonValue>,
+    ) {
+        let first_step = outputs.is_empty();
+        match self.current {
+            Lesson::Welcome => items.push(assistant_message(id, WELCOME)),
+            Lesson::Approvals if first_step => {
+                items.push(assistant_message(id, APPROVALS_INTRO));
+                items.extend(command_call(call_id, tools, TEST_COMMAND));
+            }
+            Lesson::Approvals => {
+                let declined = outputs
+                    .last()
+                    .is_some_and(|output| tool_output_text(output).contains("rejected"));
+                let text = if declined {
+                    APPROVALS_DECLINED
+                } else {
+                    APPROVALS_RAN
+                };
+                items.push(assistant_message(id, text));
+            }
+            Lesson::Diffs if first_step => {
+                items.push(assistant_message(id, DIFFS_INTRO));
+                items.extend(patch_call(call_id, tools));
+            }
+            Lesson::Diffs => items.push(assistant_message(id, DIFFS_DONE)),
+            Lesson::BackgroundTerminals if !offers_tool(tools, "exec_command") => {
+                items.push(assistant_message(id, BACKGROUND_UNAVAILABLE));
+            }
+            Lesson::BackgroundTerminals if first_step => {
+                items.push(assistant_message(id, BACKGROUND_INTRO));
+                let arguments = json!({ "cmd": BACKGROUND_COMMAND, "yield_time_ms": 1000 });
+                items.push(function_call(call_id, "exec_command", &arguments));
+            }
+            Lesson::BackgroundTerminals => items.push(assistant_message(id, BACKGROUND_DONE)),
+            Lesson::Compaction => items.push(assistant_message(id, COMPACTION_INTRO)),
+            Lesson::Finished => items.push(assistant_message(id, FINISHED)),
+        }
+    }
+}
+
+/// A message typed by the user, as opposed to context Codex injects in a user-role message.
+fn is_user_message(item: &JsonValue) -> bool {
+    item["type"] == "message"
+        && item["role"] == "user"
+        && !message_text(item).trim_start().starts_with('<')
+}
+
+fn is_tool_output(item: &JsonValue) -> bool {
+    item["type"]
+        .as_str()
+        .is_some_and(|kind| kind.ends_with("_call_output"))
+}
+
+fn message_text(item: &JsonValue) -> String {
+    item["content"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|part| part["text"].as_str())
+        .collect()
+}
+
+fn tool_output_text(item: &JsonValue) -> String {
+    match &item["output"] {
+        JsonValue::String(text) => text.clone(),
+        JsonValue::Array(parts) => parts
+            .iter()
+            .filter_map(|part| part["text"].as_str())
+            .collect(),
+        _ => String::new(),
+    }
+}
+
+fn offers_tool(tools: &[JsonValue], name: &str) -> bool {
+    tool_kind(tools, name).is_some()
+}
+
+/// The `type` of the tool called `name` in the request, such as `function` or `custom`.
+fn tool_kind<'a>(tools: &'a [JsonValue], name: &str) -> Option<&'a str> {
+    tools
+        .iter()
+        .find(|tool| tool["name"] == name)
+        .and_then(|tool| tool["type"].as_str())
+}
+
+/// A call that runs `command` with whichever shell tool the request offers.
+fn command_call(call_id: &str, tools: &[JsonValue], command: &str) -> Option<JsonValue> {
+    if offers_tool(tools, "exec_command") {
+        Some(function_call(
+            call_id,
+            "exec_command",
+            &json!({ "cmd": command }),
+        ))
+    } else if offers_tool(tools, "shell_command") {
+        Some(function_call(
+            call_id,
+            "shell_command",
+            &json!({ "command": command }),
+        ))
+    } else if offers_tool(tools, "shell") {
+        Some(function_call(
+            call_id,
+            "shell",
+            &json!({ "command": ["sh", "-c", command] }),
+        ))
+    } else {
+        None
+    }
+}
+
+/// A call that applies [`FIX_PATCH`] with the `apply_patch` tool, or through the shell when the
+/// request does not offer one.
+fn patch_call(call_id: &str, tools: &[JsonValue]) -> Option<JsonValue> {
+    match tool_kind(tools, "apply_patch") {
+        Some("custom") => Some(json!({
+            "type": "custom_tool_call",
+            "call_id": call_id,
+            "name": "apply_patch",
+            "input": FIX_PATCH,
+        })),
+        Some(_) => Some(function_call(
+            call_id,
+            "apply_patch",
+            &json!({ "input": FIX_PATCH }),
+        )),
+        None => command_call(
+            call_id,
+            tools,
+            &format!("apply_patch <<'EOF'\n{FIX_PATCH}\nEOF\n"),
+        ),
+    }
+}
+
+fn assistant_message(id: &str, text: &str) -> JsonValue {
+    json!({
+        "type": "message",
+        "role": "assistant",
+        "id": format!("msg-{id}"),
+        "content": [{ "type": "output_text", "text": text }],
+    })
+}
+
+fn function_call(call_id: &str, name: &str, arguments: &JsonValue) -> JsonValue {
+    json!({
+        "type": "function_call",
+        "call_id": call_id,
+        "name": name,
+        "arguments": arguments.to_string(),
+    })
+}
+
+#[cfg(test)]
+#[path = "tutorial_tests.rs"]
+mod tests;
@@ -0,0 +1,182 @@
+use codex_utils_cli::CliConfigOverrides;
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+fn user(text: &str) -> JsonValue {
+    json!({
+        "type": "message",
+        "role": "user",
+        "content": [{ "type": "input_text", "text": text }],
+    })
+}
+
+fn tool_output(call_id: &str, output: &str) -> JsonValue {
+    json!({ "type": "function_call_output", "call_id": call_id, "output": output })
+}
+
+fn tools(offered: &[(&str, &str)]) -> Vec<JsonValue> {
+    offered
+        .iter()
+        .map(|(kind, name)| json!({ "type": kind, "name": name }))
+        .collect()
+}
+
+/// Output items of the reply to a request with `input` and `tools`.
+fn reply(script: &mut TutorialScript, input: &[JsonValue], tools: &[JsonValue]) -> Vec<JsonValue> {
+    script
+        .respond(&json!({ "input": input, "tools": tools }))
+        .into_iter()
+        .filter(|event| event["type"] == "response.output_item.done")
+        .map(|event| event["item"].clone())
+        .collect()
+}
+
+fn text_of(item: &JsonValue) -> &str {
+    item["content"][0]["text"].as_str().unwrap_or_default()
+}
+
+#[test]
+fn lessons_follow_each_other_and_react_to_tool_outputs() {
+    let tools = tools(&[
+        ("function", "exec_command"),
+        ("function", "shell_command"),
+        ("custom", "apply_patch"),
+    ]);
+    let mut script = TutorialScript::default();
+    let mut input = vec![
+        user("<environment_context>\n</environment_context>"),
+        user(START_PROMPT),
+    ];
+
+    let items = reply(&mut script, &input, &tools);
+    assert_eq!(items.len(), 1);
+    assert_eq!(text_of(&items[0]), WELCOME);
+
+    input.push(user("next"));
+    let items = reply(&mut script, &input, &tools);
+    assert_eq!(text_of(&items[0]), APPROVALS_INTRO);
+    assert_eq!(items[1]["name"], "exec_command");
+    assert_eq!(
+        serde_json::from_str::<JsonValue>(items[1]["arguments"].as_str().unwrap_or_default()).ok(),
+        Some(json!({ "cmd": TEST_COMMAND }))
+    );
+
+    input.push(items[1].clone());
+    input.push(tool_output(
+        "call-tutorial-2",
+        "exec command rejected by user",
+    ));
+    let items = reply(&mut script, &input, &tools);
+    assert_eq!(items.len(), 1);
+    assert_eq!(text_of(&items[0]), APPROVALS_DECLINED);
+
+    input.push(user("ok"));
+    let items = reply(&mut script, &input, &tools);
+    assert_eq!(text_of(&items[0]), DIFFS_INTRO);
+    assert_eq!(
+        items[1],
+        json!({
+            "type": "custom_tool_call",
+            "call_id": "call-tutorial-4",
+            "name": "apply_patch",
+            "input": FIX_PATCH,
+        })
+    );
+
+    input.push(items[1].clone());
+    input.push(json!({
+        "type": "custom_tool_call_output",
+        "call_id": "call-tutorial-4",
+        "output": "Success. Updated the following files:\nM greet.sh",
+    }));
+    let items = reply(&mut script, &input, &tools);
+    assert_eq!(text_of(&items[0]), DIFFS_DONE);
+}
+
+#[test]
+fn named_lessons_and_compaction_keep_the_lesson_order() {
+    let tools = tools(&[("function", "exec_command"), ("custom", "apply_patch")]);
+    let mut script = TutorialScript::default();
+
+    let items = reply(&mut script, &[user("Show me background terminals")], &tools);
+    assert_eq!(text_of(&items[0]), BACKGROUND_INTRO);
+    assert_eq!(items[1]["name"], "exec_command");
+
+    let items = reply(&mut script, &[user(COMPACT_PROMPT)], &tools);
+    assert_eq!(items.len(), 1);
+    assert_eq!(text_of(&items[0]), COMPACTION_SUMMARY);
+
+    let items = reply(&mut script, &[user("next")], &tools);
+    assert_eq!(text_of(&items[0]), COMPACTION_INTRO);
+
+    let items = reply(&mut script, &[user("next")], &tools);
+    assert_eq!(text_of(&items[0]), FINISHED);
+    let items = reply(&mut script, &[user("and now?")], &tools);
+    assert_eq!(text_of(&items[0]), FINISHED);
+}
+
+#[test]
+fn tool_calls_use_the_tools_the_request_offers() {
+    assert_eq!(
+        patch_call("call", &tools(&[("function", "apply_patch")])),
+        Some(function_call(
+            "call",
+            "apply_patch",
+            &json!({ "input": FIX_PATCH })
+        ))
+    );
+    assert_eq!(
+        patch_call("call", &tools(&[("function", "shell_command")])),
+        Some(function_call(
+            "call",
+            "shell_command",
+            &json!({ "command": format!("apply_patch <<'EOF'\n{FIX_PATCH}\nEOF\n") })
+        ))
+    );
+    assert_eq!(
+        command_call("call", &tools(&[("function", "shell")]), TEST_COMMAND),
+        Some(function_call(
+            "call",
+            "shell",
+            &json!({ "command": ["sh", "-c", TEST_COMMAND] })
+        ))
+    );
+    assert_eq!(command_call("call", &[], TEST_COMMAND), None);
+
+    let mut script = TutorialScript::default();
+    let items = reply(&mut script, &[user("terminals")], &tools(&[]));
+    assert_eq!(items.len(), 1);
+    assert_eq!(text_of(&items[0]), BACKGROUND_UNAVAILABLE);
+}
+
+#[test]
+fn overrides_parse_as_config_values() {
+    let project_dir = Path::new(r"C:\Users\me\codex-tutorial-x");
+    let overrides = CliConfigOverrides {
+        raw_overrides: tutorial_overrides("http://127.0.0.1:4000/v1", project_dir),
+    };
+    let parsed = overrides.parse_overrides().expect("overrides should parse");
+    let value = |key: &str| {
+        parsed
+            .iter()
+            .find(|(path, _)| path == key)
+            .map(|(_, value)| value.clone())
+            .expect("override should be present")
+    };
+
+    assert_eq!(
+        value("model_providers.codex-tutorial")
+            .get("base_url")
+            .and_then(|url| url.as_str()),
+        Some("http://127.0.0.1:4000/v1")
+    );
+    assert_eq!(
+        value("projects")
+            .get(r"C:\Users\me\codex-tutorial-x")
+            .and_then(|project| project.get("trust_level"))
+            .and_then(|trust| trust.as_str()),
+        Some("trusted")
+    );
+    assert_eq!(value("compact_prompt").as_str(), Some(COMPACT_PROMPT));
+}
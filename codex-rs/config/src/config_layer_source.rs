@@ -10,6 +10,9 @@ pub enum ConfigLayerSource {
     System { file: AbsolutePathBuf },
     /// Configuration delivered by an enterprise cloud bundle.
     EnterpriseManaged { id: String, name: String },
+    /// Organization-managed team configuration. The keys it locks form a separate layer that
+    /// takes precedence over user, project, and session settings.
+    Team { file: AbsolutePathBuf, locked: bool },
     /// User configuration, optionally augmented by a selected profile.
     User {
         file: AbsolutePathBuf,
@@ -32,6 +35,7 @@ impl ConfigLayerSource {
         match self {
             ConfigLayerSource::Mdm { .. } => 0,
             ConfigLayerSource::System { .. } => 10,
+            ConfigLayerSource::Team { locked: false, .. } => 12,
             ConfigLayerSource::EnterpriseManaged { .. } => 15,
             ConfigLayerSource::User { profile, .. } => {
                 if profile.is_some() {
@@ -42,6 +46,7 @@ impl ConfigLayerSource {
             }
             ConfigLayerSource::Project { .. } => 25,
             ConfigLayerSource::SessionFlags => 30,
+            ConfigLayerSource::Team { locked: true, .. } => 35,
             ConfigLayerSource::LegacyManagedConfigTomlFromFile { .. } => 40,
             ConfigLayerSource::LegacyManagedConfigTomlFromMdm => 50,
         }
@@ -83,6 +88,10 @@ pub fn format_config_layer_source(source: &ConfigLayerSource, config_toml_file:
         ConfigLayerSource::EnterpriseManaged { id, name } => {
             format!("enterprise-managed ({name}, {id})")
         }
+        ConfigLayerSource::Team { file, locked } => {
+            let kind = if *locked { "team, locked" } else { "team" };
+            format!("{kind} ({})", file.as_path().display())
+        }
         ConfigLayerSource::User { file, .. } => {
             format!("user ({})", file.as_path().display())
         }
@@ -52,6 +52,10 @@ pub enum RequirementSource {
         file: AbsolutePathBuf,
     },
     LegacyManagedConfigTomlFromMdm,
+    /// Keys listed under `locked` in a team `config.toml`.
+    TeamConfig {
+        file: AbsolutePathBuf,
+    },
 }
 
 impl RequirementSource {
@@ -113,6 +117,9 @@ impl fmt::Display for RequirementSource {
             RequirementSource::LegacyManagedConfigTomlFromMdm => {
                 write!(f, "MDM managed_config.toml (legacy)")
             }
+            RequirementSource::TeamConfig { file } => {
+                write!(f, "team config {} (locked)", file.as_path().display())
+            }
         }
     }
 }
@@ -153,6 +160,7 @@ pub struct ConfigRequirements {
     pub check_for_update_on_startup: Option<Sourced<bool>>,
     pub allow_login_shell: Option<Sourced<bool>>,
     pub feedback: Option<Sourced<FeedbackConfigToml>>,
+    pub model: ConstrainedWithSource<Option<String>>,
     pub approval_policy: ConstrainedWithSource<AskForApproval>,
     pub approvals_reviewer: ConstrainedWithSource<ApprovalsReviewer>,
     pub permission_profile: ConstrainedWithSource<PermissionProfile>,
@@ -187,6 +195,10 @@ impl Default for ConfigRequirements {
             check_for_update_on_startup: None,
             allow_login_shell: None,
             feedback: None,
+            model: ConstrainedWithSource::new(
+                Constrained::allow_any(/*initial_value*/ None),
+                /*source*/ None,
+            ),
             approval_policy: ConstrainedWithSource::new(
                 Constrained::allow_any_from_default(),
                 /*source*/ None,
@@ -868,6 +880,7 @@ pub struct ConfigRequirementsToml {
     pub check_for_update_on_startup: Option<bool>,
     pub allow_login_shell: Option<bool>,
     pub feedback: Option<FeedbackConfigToml>,
+    pub allowed_models: Option<Vec<String>>,
     pub allowed_approval_policies: Option<Vec<AskForApproval>>,
     pub allowed_approvals_reviewers: Option<Vec<ApprovalsReviewer>>,
     pub allowed_sandbox_modes: Option<Vec<SandboxModeRequirement>>,
@@ -958,6 +971,7 @@ pub struct ConfigRequirementsWithSources {
     pub check_for_update_on_startup: Option<Sourced<bool>>,
     pub allow_login_shell: Option<Sourced<bool>>,
     pub feedback: Option<Sourced<FeedbackConfigToml>>,
+    pub allowed_models: Option<Sourced<Vec<String>>>,
     pub allowed_approval_policies: Option<Sourced<Vec<AskForApproval>>>,
     pub allowed_approvals_reviewers: Option<Sourced<Vec<ApprovalsReviewer>>>,
     pub allowed_sandbox_modes: Option<Sourced<Vec<SandboxModeRequirement>>>,
@@ -1008,6 +1022,7 @@ impl ConfigRequirementsWithSources {
             check_for_update_on_startup: _,
             allow_login_shell: _,
             feedback: _,
+            allowed_models: _,
             allowed_approval_policies: _,
             allowed_approvals_reviewers: _,
             allowed_sandbox_modes: _,
@@ -1053,6 +1068,7 @@ impl ConfigRequirementsWithSources {
                 check_for_update_on_startup,
                 allow_login_shell,
                 feedback,
+                allowed_models,
                 allowed_approval_policies,
                 allowed_approvals_reviewers,
                 allowed_sandbox_modes,
@@ -1095,6 +1111,7 @@ impl ConfigRequirementsWithSources {
             check_for_update_on_startup,
             allow_login_shell,
             feedback,
+            allowed_models,
             allowed_approval_policies,
             allowed_approvals_reviewers,
             allowed_sandbox_modes,
@@ -1126,6 +1143,7 @@ impl ConfigRequirementsWithSources {
             check_for_update_on_startup: check_for_update_on_startup.map(|sourced| sourced.value),
             allow_login_shell: allow_login_shell.map(|sourced| sourced.value),
             feedback: feedback.map(|sourced| sourced.value),
+            allowed_models: allowed_models.map(|sourced| sourced.value),
             allowed_approval_policies: allowed_approval_policies.map(|sourced| sourced.value),
             allowed_approvals_reviewers: allowed_approvals_reviewers.map(|sourced| sourced.value),
             allowed_sandbox_modes: allowed_sandbox_modes.map(|sourced| sourced.value),
@@ -1227,6 +1245,7 @@ impl ConfigRequirementsToml {
                 .feedback
                 .as_ref()
                 .is_none_or(|feedback| feedback == &FeedbackConfigToml::default())
+            && self.allowed_models.is_none()
             && self.allowed_approval_policies.is_none()
             && self.allowed_approvals_reviewers.is_none()
             && self.allowed_sandbox_modes.is_none()
@@ -1401,6 +1420,7 @@ impl TryFrom<ConfigRequirementsWithSources> for ConfigRequirements {
             check_for_update_on_startup,
             allow_login_shell,
             feedback,
+            allowed_models,
             allowed_approval_policies,
             allowed_approvals_reviewers,
             allowed_sandbox_modes,
@@ -1445,6 +1465,41 @@ impl TryFrom<ConfigRequirementsWithSources> for ConfigRequirements {
             }
         }
 
+        let model = match allowed_models {
+            Some(Sourced {
+                value: models,
+                source: requirement_source,
+            }) => {
+                let Some(initial_value) = models.first().cloned() else {
+                    return Err(ConstraintError::empty_field("allowed_models"));
+                };
+
+                let requirement_source_for_error = requirement_source.clone();
+                let constrained = Constrained::new(Some(initial_value), move |candidate| {
+                    // An unset model resolves to the provider default, which a
+                    // locked model list does not cover either.
+                    if candidate
+                        .as_ref()
+                        .is_some_and(|candidate| models.contains(candidate))
+                    {
+                        Ok(())
+                    } else {
+                        Err(ConstraintError::InvalidValue {
+                            field_name: "model",
+                            candidate: format!("{candidate:?}"),
+                            allowed: format!("{models:?}"),
+                            requirement_source: requirement_source_for_error.clone(),
+                        })
+                    }
+                })?;
+                ConstrainedWithSource::new(constrained, Some(requirement_source))
+            }
+            None => ConstrainedWithSource::new(
+                Constrained::allow_any(/*initial_value*/ None),
+                /*source*/ None,
+            ),
+        };
+
         let approval_policy = match allowed_approval_policies {
             Some(Sourced {
                 value: policies,
@@ -1728,6 +1783,7 @@ impl TryFrom<ConfigRequirementsWithSources> for ConfigRequirements {
             check_for_update_on_startup,
             allow_login_shell,
             feedback,
+            model,
             approval_policy,
             approvals_reviewer,
             permission_profile,
@@ -1882,6 +1938,7 @@ mod tests {
             check_for_update_on_startup,
             allow_login_shell,
             feedback,
+            allowed_models,
             allowed_approval_policies,
             allowed_approvals_reviewers,
             allowed_sandbox_modes,
@@ -1917,6 +1974,8 @@ mod tests {
             allow_login_shell: allow_login_shell
                 .map(|value| Sourced::new(value, RequirementSource::Unknown)),
             feedback: feedback.map(|value| Sourced::new(value, RequirementSource::Unknown)),
+            allowed_models: allowed_models
+                .map(|value| Sourced::new(value, RequirementSource::Unknown)),
             allowed_approval_policies: allowed_approval_policies
                 .map(|value| Sourced::new(value, RequirementSource::Unknown)),
             allowed_approvals_reviewers: allowed_approvals_reviewers
@@ -2132,6 +2191,7 @@ mod tests {
         let mut target = ConfigRequirementsWithSources::default();
         let source = RequirementSource::LegacyManagedConfigTomlFromMdm;
 
+        let allowed_models = vec!["managed-model".to_string()];
         let allowed_approval_policies = vec![AskForApproval::UnlessTrusted, AskForApproval::Never];
         let allowed_approvals_reviewers =
             vec![ApprovalsReviewer::AutoReview, ApprovalsReviewer::User];
@@ -2183,6 +2243,7 @@ mod tests {
             check_for_update_on_startup: Some(false),
             allow_login_shell: Some(false),
             feedback: Some(feedback.clone()),
+            allowed_models: Some(allowed_models.clone()),
             allowed_approval_policies: Some(allowed_approval_policies.clone()),
             allowed_approvals_reviewers: Some(allowed_approvals_reviewers.clone()),
             allowed_sandbox_modes: Some(allowed_sandbox_modes.clone()),
@@ -2223,6 +2284,7 @@ mod tests {
                 )),
                 allow_login_shell: Some(Sourced::new(/*value*/ false, source.clone())),
                 feedback: Some(Sourced::new(feedback, source.clone())),
+                allowed_models: Some(Sourced::new(allowed_models, source.clone())),
                 allowed_approval_policies: Some(Sourced::new(
                     allowed_approval_policies,
                     source.clone()
@@ -3321,6 +3383,41 @@ allowed_approvals_reviewers = ["user"]
         Ok(())
     }
 
+    #[test]
+    fn deserialize_allowed_models() -> Result<()> {
+        let toml_str = r#"
+            allowed_models = ["gpt-team", "gpt-team-mini"]
+        "#;
+        let config: ConfigRequirementsToml = from_str(toml_str)?;
+        let requirements: ConfigRequirements = with_unknown_source(config).try_into()?;
+
+        assert_eq!(requirements.model.get(), &Some("gpt-team".to_string()));
+        assert!(
+            requirements
+                .model
+                .can_set(&Some("gpt-team-mini".to_string()))
+                .is_ok()
+        );
+        assert_eq!(
+            requirements.model.can_set(&Some("other".to_string())),
+            Err(ConstraintError::InvalidValue {
+                field_name: "model",
+                candidate: "Some(\"other\")".into(),
+                allowed: "[\"gpt-team\", \"gpt-team-mini\"]".into(),
+                requirement_source: RequirementSource::Unknown,
+            })
+        );
+        assert!(requirements.model.can_set(&None).is_err());
+
+        let empty: ConfigRequirementsToml = from_str("allowed_models = []")?;
+        assert_eq!(
+            ConfigRequirements::try_from(with_unknown_source(empty)),
+            Err(ConstraintError::empty_field("allowed_models"))
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn deserialize_allowed_web_search_modes() -> Result<()> {
         let toml_str = r#"
@@ -12,7 +12,11 @@ use crate::types::ApprovalsReviewer;
 use crate::types::ApprovalsToml;
 use crate::types::AppsConfigToml;
 use crate::types::AuthCredentialsStoreMode;
+use crate::types::BenchToml;
+use crate::types::DatabaseToml;
 use crate::types::FeedbackConfigToml;
+use crate::types::GoalsToml;
+use crate::types::GuardrailsToml;
 use crate::types::History;
 use crate::types::MarketplaceConfig;
 use crate::types::McpServerConfig;
@@ -21,12 +25,20 @@ use crate::types::Notice;
 use crate::types::OAuthCredentialsStoreMode;
 use crate::types::OtelConfigToml;
 use crate::types::PluginConfig;
+use crate::types::ProfilingToml;
+use crate::types::RefactorToml;
+use crate::types::RelatedRepoToml;
+use crate::types::RunLimitsToml;
 use crate::types::SandboxWorkspaceWrite;
+use crate::types::ScratchpadToml;
 use crate::types::ShellEnvironmentPolicyToml;
 use crate::types::SkillsConfig;
+use crate::types::SystemPromptToml;
 use crate::types::ToolSuggestConfig;
 use crate::types::Tui;
 use crate::types::UriBasedFileOpener;
+use crate::types::UsageExportToml;
+use crate::types::WebhookToml;
 use crate::types::WindowsToml;
 use codex_features::FeaturesToml;
 use codex_model_provider_info::AMAZON_BEDROCK_PROVIDER_ID;
@@ -363,6 +375,10 @@ pub struct ConfigToml {
     /// Optionally specify a personality for the model
     pub personality: Option<Personality>,
 
+    /// Language the assistant should reply in, such as `French` or `pt-BR`. A
+    /// `response_language` set for the active project takes precedence.
+    pub response_language: Option<String>,
+
     /// Optional explicit service tier request id for new turns (for example
     /// `default`, `priority`, or `flex`; legacy `fast` also works).
     pub service_tier: Option<String>,
@@ -440,6 +456,47 @@ pub struct ConfigToml {
     /// Memories subsystem settings.
     pub memories: Option<MemoriesToml>,
 
+    /// Supervised autonomy settings for thread goals.
+    pub goals: Option<GoalsToml>,
+
+    /// Session duration, turn, and token limits.
+    pub run_limits: Option<RunLimitsToml>,
+
+    /// Per-session scratch directory settings for the `scratchpad` feature.
+    pub scratchpad: Option<ScratchpadToml>,
+
+    /// Project additions to, and opt-in replacements of, the built-in system prompt.
+    pub system_prompt: Option<SystemPromptToml>,
+
+    /// Rules that code the agent writes must follow, such as license headers and banned APIs.
+    pub guardrails: Option<GuardrailsToml>,
+
+    /// Benchmark command that `codex bench` times against a baseline revision.
+    pub bench: Option<BenchToml>,
+
+    /// Command that the `profiling` tool captures a sampling profile of.
+    pub profiling: Option<ProfilingToml>,
+
+    /// Language servers that the `refactor` tools can delegate renames to.
+    pub refactor: Option<RefactorToml>,
+
+    /// Periodic export of anonymized usage records for platform teams.
+    pub usage_export: Option<UsageExportToml>,
+
+    /// Repositories related to this workspace, keyed by name, such as the client for an API.
+    #[serde(default)]
+    pub related_repos: BTreeMap<String, RelatedRepoToml>,
+
+    /// Databases the model may inspect with read-only queries when the `database_tools` feature
+    /// is enabled, keyed by name.
+    #[serde(default)]
+    pub databases: BTreeMap<String, DatabaseToml>,
+
+    /// URLs that receive session events such as finished turns and approval requests, keyed by
+    /// name.
+    #[serde(default)]
+    pub webhooks: BTreeMap<String, WebhookToml>,
+
     /// User-level skill config entries keyed by SKILL.md path.
     pub skills: Option<SkillsConfig>,
 
@@ -568,6 +625,8 @@ pub struct AutoReviewToml {
 #[schemars(deny_unknown_fields)]
 pub struct ProjectConfig {
     pub trust_level: Option<TrustLevel>,
+    /// Language the assistant should reply in when working in this project.
+    pub response_language: Option<String>,
 }
 
 impl ProjectConfig {
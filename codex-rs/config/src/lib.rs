@@ -27,6 +27,7 @@ mod shell_environment_policy;
 mod skills_config;
 mod state;
 mod strict_config;
+mod team_config;
 pub mod test_support;
 mod thread_config;
 mod tui_keymap;
@@ -157,6 +158,8 @@ pub use state::ConfigLayerStackOrdering;
 pub use state::ConfigLoadOptions;
 pub use state::LoaderOverrides;
 pub use strict_config::config_error_from_ignored_toml_fields;
+pub use team_config::TEAM_CONFIG_TOML_FILE;
+pub use team_config::team_config_layers;
 pub use thread_config::NoopThreadConfigLoader;
 pub use thread_config::RemoteThreadConfigLoader;
 pub use thread_config::SessionThreadConfig;
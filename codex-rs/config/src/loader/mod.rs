@@ -32,6 +32,8 @@ use crate::state::validate_enabled_config_layers;
 use crate::strict_config::config_error_from_ignored_toml_value_fields;
 use crate::strict_config::ignored_toml_value_field;
 use crate::strict_config::unknown_feature_toml_value_field;
+use crate::team_config::TEAM_CONFIG_TOML_FILE;
+use crate::team_config::team_config_layers;
 use crate::thread_config::ThreadConfigContext;
 use crate::thread_config::ThreadConfigLoader;
 use codex_file_system::ExecutorFileSystem;
@@ -73,6 +75,10 @@ const PROJECT_LOCAL_CONFIG_DENYLIST: &[&str] = &[
     "experimental_realtime_webrtc_call_base_url",
     "experimental_realtime_ws_base_url",
     "otel",
+    "bench",
+    "profiling",
+    "webhooks",
+    "usage_export",
 ];
 
 async fn first_layer_config_error_from_entries(layers: &[ConfigLayerEntry]) -> Option<ConfigError> {
@@ -98,6 +104,7 @@ async fn first_layer_config_error_from_entries(layers: &[ConfigLayerEntry]) -> O
 /// - admin:    managed preferences (*)
 /// - system    `/etc/codex/config.toml` (Unix) or
 ///   `%ProgramData%\OpenAI\Codex\config.toml` (Windows)
+/// - team      `${CODEX_HOME}/team_config.toml`, minus the keys it locks
 /// - cloud     enterprise-managed cloud config bundle fragments
 /// - user      `${CODEX_HOME}/config.toml`
 /// - profile   `${CODEX_HOME}/<name>.config.toml`, when selected
@@ -105,6 +112,7 @@ async fn first_layer_config_error_from_entries(layers: &[ConfigLayerEntry]) -> O
 /// - tree      parent directories up to root looking for `./.codex/config.toml` (loaded but disabled when untrusted)
 /// - repo      `$(git rev-parse --show-toplevel)/.codex/config.toml` (loaded but disabled when untrusted)
 /// - runtime   e.g., --config flags, model selector in UI
+/// - locked    keys locked by `${CODEX_HOME}/team_config.toml`
 ///
 /// (*) Only available on macOS via managed device profiles.
 ///
@@ -181,7 +189,44 @@ pub async fn load_config_layers_state(
     let loaded_config_layers =
         layer_io::load_config_layers_internal(fs, codex_home, overrides.clone(), strict_config)
             .await?;
+
+    // The team config contributes defaults below user config and, when it
+    // locks keys, a second layer that is inserted above session flags below.
+    // Locked keys are also mirrored into requirements so that values which
+    // bypass config layers, such as `--model` or `--sandbox`, cannot change
+    // them either.
+    let mut team_default_layers = Vec::new();
+    let mut locked_team_layers = Vec::new();
     if !ignore_managed_requirements {
+        let team_config_file =
+            AbsolutePathBuf::resolve_path_against_base(TEAM_CONFIG_TOML_FILE, codex_home);
+        let team_config = load_config_toml_for_required_layer(
+            fs,
+            &team_config_file,
+            /*strict_config*/ false,
+            |config| {
+                ConfigLayerEntry::new(
+                    ConfigLayerSource::Team {
+                        file: team_config_file.clone(),
+                        locked: false,
+                    },
+                    config,
+                )
+            },
+        )
+        .await?;
+        for layer in team_config_layers(&team_config_file, team_config.config)? {
+            if matches!(layer.name, ConfigLayerSource::Team { locked: true, .. }) {
+                requirements_layers.extend(team_lock_requirements_layer(
+                    &team_config_file,
+                    &layer.config,
+                )?);
+                locked_team_layers.push(layer);
+            } else {
+                team_default_layers.push(layer);
+            }
+        }
+
         requirements_layers.extend(system_requirements_layer);
         requirements_layers.extend(bundle_requirements_layers);
         // Continue to support the legacy `managed_config.toml` locations as
@@ -240,6 +285,8 @@ pub async fn load_config_layers_state(
     )
     .await?;
     layers.push(system_layer);
+
+    layers.extend(team_default_layers);
     layers.extend(cloud_config_layers);
 
     // Add the base user config layer. When profile-v2 is selected, add the
@@ -362,8 +409,8 @@ pub async fn load_config_layers_state(
         ));
     }
 
-    for thread_config_layer in thread_config_layers {
-        insert_layer_by_precedence(&mut layers, thread_config_layer);
+    for layer in thread_config_layers.into_iter().chain(locked_team_layers) {
+        insert_layer_by_precedence(&mut layers, layer);
     }
 
     // Make a best-effort to support the legacy `managed_config.toml` as a
@@ -826,6 +873,45 @@ fn legacy_requirements_to_toml_value(legacy: LegacyManagedConfigToml) -> io::Res
     Ok(TomlValue::Table(table))
 }
 
+/// Maps the keys a team config locks onto the requirements that enforce them.
+///
+/// Only keys with a matching allow-list requirement are mirrored; every other
+/// locked key is still pinned by the locked config layer.
+fn team_lock_requirements_layer(
+    file: &AbsolutePathBuf,
+    locked: &TomlValue,
+) -> io::Result<Option<RequirementsLayerEntry>> {
+    let locked_policy: LegacyManagedConfigToml =
+        locked.clone().try_into().map_err(|err: toml::de::Error| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid team config {}: {err}", file.as_path().display()),
+            )
+        })?;
+    let mut requirements = legacy_requirements_to_toml_value(locked_policy)?;
+    let Some(table) = requirements.as_table_mut() else {
+        return Ok(None);
+    };
+    for (locked_key, requirement_key) in [
+        ("model", "allowed_models"),
+        ("web_search", "allowed_web_search_modes"),
+    ] {
+        if let Some(value) = locked.get(locked_key) {
+            table.insert(
+                requirement_key.to_string(),
+                TomlValue::Array(vec![value.clone()]),
+            );
+        }
+    }
+    if table.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(RequirementsLayerEntry::from_toml_value(
+        RequirementSource::TeamConfig { file: file.clone() },
+        requirements,
+    )))
+}
+
 fn toml_value_from_serializable<T: serde::Serialize>(value: T) -> io::Result<TomlValue> {
     TomlValue::try_from(value).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
 }
@@ -8,6 +8,7 @@ use codex_file_system::FileSystemReadStream;
 use codex_file_system::FileSystemSandboxContext;
 use codex_file_system::ReadDirectoryEntry;
 use codex_file_system::RemoveOptions;
+use codex_protocol::models::PermissionProfile;
 use codex_utils_path_uri::PathUri;
 use pretty_assertions::assert_eq;
 use tempfile::tempdir;
@@ -288,6 +289,127 @@ model = "gpt-dev"
     .expect("profile-v2 should allow unrelated legacy profiles in base user config");
 }
 
+#[tokio::test]
+async fn team_config_locked_keys_override_user_config_and_session_flags() {
+    let tmp = tempdir().expect("tempdir");
+    std::fs::write(
+        tmp.path().join(TEAM_CONFIG_TOML_FILE),
+        r#"
+model = "gpt-team"
+sandbox_mode = "read-only"
+locked = ["sandbox_mode"]
+"#,
+    )
+    .expect("write team config");
+    std::fs::write(
+        tmp.path().join(CONFIG_TOML_FILE),
+        r#"
+model = "gpt-user"
+sandbox_mode = "danger-full-access"
+"#,
+    )
+    .expect("write user config");
+
+    let stack = load_config_layers_state(
+        &TestFileSystem,
+        tmp.path(),
+        /*cwd*/ None,
+        &[(
+            "sandbox_mode".to_string(),
+            TomlValue::String("workspace-write".to_string()),
+        )],
+        LoaderOverrides::without_managed_config_for_tests(),
+        &crate::NoopThreadConfigLoader,
+    )
+    .await
+    .expect("load config layers");
+
+    let effective = stack.effective_config();
+    assert_eq!(effective.get("model"), Some(&TomlValue::from("gpt-user")));
+    assert_eq!(
+        effective.get("sandbox_mode"),
+        Some(&TomlValue::from("read-only"))
+    );
+    let team_file = AbsolutePathBuf::resolve_path_against_base(TEAM_CONFIG_TOML_FILE, tmp.path());
+    assert_eq!(
+        stack
+            .origins()
+            .get("sandbox_mode")
+            .map(|origin| &origin.name),
+        Some(&ConfigLayerSource::Team {
+            file: team_file,
+            locked: true,
+        })
+    );
+}
+
+#[tokio::test]
+async fn team_config_locked_keys_become_requirements() {
+    let tmp = tempdir().expect("tempdir");
+    std::fs::write(
+        tmp.path().join(TEAM_CONFIG_TOML_FILE),
+        r#"
+model = "gpt-team"
+sandbox_mode = "workspace-write"
+approval_policy = "on-request"
+locked = ["model", "sandbox_mode", "approval_policy"]
+"#,
+    )
+    .expect("write team config");
+
+    let stack = load_config_layers_state(
+        &TestFileSystem,
+        tmp.path(),
+        /*cwd*/ None,
+        &[],
+        LoaderOverrides::without_managed_config_for_tests(),
+        &crate::NoopThreadConfigLoader,
+    )
+    .await
+    .expect("load config layers");
+
+    let team_file = AbsolutePathBuf::resolve_path_against_base(TEAM_CONFIG_TOML_FILE, tmp.path());
+    let source = RequirementSource::TeamConfig { file: team_file };
+    let requirements = stack.requirements();
+    assert_eq!(requirements.model.source.as_ref(), Some(&source));
+    assert!(
+        requirements
+            .model
+            .can_set(&Some("gpt-team".to_string()))
+            .is_ok()
+    );
+    assert!(
+        requirements
+            .model
+            .can_set(&Some("gpt-cli".to_string()))
+            .is_err()
+    );
+    assert_eq!(requirements.approval_policy.source.as_ref(), Some(&source));
+    assert!(
+        requirements
+            .approval_policy
+            .can_set(&AskForApproval::Never)
+            .is_err()
+    );
+    assert_eq!(
+        requirements.permission_profile.source.as_ref(),
+        Some(&source)
+    );
+    assert!(
+        requirements
+            .permission_profile
+            .can_set(&PermissionProfile::Disabled)
+            .is_err()
+    );
+    assert_eq!(
+        stack.requirements_toml().allowed_sandbox_modes,
+        Some(vec![
+            SandboxModeRequirement::ReadOnly,
+            SandboxModeRequirement::WorkspaceWrite,
+        ])
+    );
+}
+
 #[tokio::test]
 async fn project_layer_loading_scans_claude_and_codex_config_dirs() {
     let tmp = tempdir().expect("tempdir");
@@ -17,7 +17,8 @@ fn apply_toml_override(root: &mut TomlValue, path: &str, value: TomlValue) {
     use toml::value::Table;
 
     let mut current = root;
-    let mut segments_iter = path.split('.').peekable();
+    let segments = path_segments(path);
+    let mut segments_iter = segments.iter().map(String::as_str).peekable();
 
     while let Some(segment) = segments_iter.next() {
         let is_last = segments_iter.peek().is_none();
@@ -53,3 +54,26 @@ fn apply_toml_override(root: &mut TomlValue, path: &str, value: TomlValue) {
         }
     }
 }
+
+/// Splits an override path on `.`, except inside a double-quoted segment, so keys that contain
+/// dots can be addressed as in TOML: `mcp_servers."team.docs".enabled`.
+fn path_segments(path: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut segment = String::new();
+    let mut quoted = false;
+    let mut chars = path.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => quoted = !quoted,
+            '\\' if quoted => segment.extend(chars.next()),
+            '.' if !quoted => segments.push(std::mem::take(&mut segment)),
+            _ => segment.push(ch),
+        }
+    }
+    segments.push(segment);
+    segments
+}
+
+#[cfg(test)]
+#[path = "overrides_tests.rs"]
+mod tests;
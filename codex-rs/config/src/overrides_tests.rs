@@ -0,0 +1,28 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn quoted_segments_address_keys_containing_dots() {
+    let layer = build_cli_overrides_layer(&[
+        (
+            "mcp_servers.\"team.docs\".enabled".to_string(),
+            TomlValue::Boolean(false),
+        ),
+        (
+            "mcp_servers.search.enabled".to_string(),
+            TomlValue::Boolean(false),
+        ),
+    ]);
+
+    let expected: TomlValue = toml::from_str(
+        r#"
+[mcp_servers."team.docs"]
+enabled = false
+
+[mcp_servers.search]
+enabled = false
+"#,
+    )
+    .expect("valid TOML");
+    assert_eq!(layer, expected);
+}
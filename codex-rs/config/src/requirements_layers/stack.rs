@@ -212,6 +212,7 @@ fn populate_merged_regular_fields_with_sources(
         check_for_update_on_startup,
         allow_login_shell,
         feedback,
+        allowed_models,
         allowed_approval_policies,
         allowed_approvals_reviewers,
         allowed_sandbox_modes,
@@ -247,6 +248,7 @@ fn populate_merged_regular_fields_with_sources(
     );
     set_sourced!(allow_login_shell, &["allow_login_shell"]);
     set_sourced!(feedback, &["feedback"]);
+    set_sourced!(allowed_models, &["allowed_models"]);
     set_sourced!(allowed_approval_policies, &["allowed_approval_policies"]);
     set_sourced!(
         allowed_approvals_reviewers,
@@ -0,0 +1,128 @@
+//! Organization-managed team configuration.
+//!
+//! A team distributes one `team_config.toml`, which `codex config sync-team` copies or downloads
+//! into `$CODEX_HOME`. It uses the same keys as `config.toml` plus a top-level `locked` array of
+//! dotted key paths:
+//!
+//! ```toml
+//! model = "gpt-5.2"
+//! sandbox_mode = "workspace-write"
+//! locked = ["sandbox_mode"]
+//! ```
+//!
+//! Unlocked keys become a low-precedence layer that user and project config may override. Locked
+//! keys become a second layer above session flags, so nothing the user configures can change them.
+//! Locked `model`, `approval_policy`, `approvals_reviewer`, `sandbox_mode` and `web_search` are
+//! also loaded as requirements, which reject command-line flags such as `--model` and `--sandbox`
+//! that are applied after the config layers are merged.
+
+use std::io;
+
+use codex_utils_absolute_path::AbsolutePathBuf;
+use toml::Value as TomlValue;
+
+use crate::ConfigLayerEntry;
+use crate::ConfigLayerSource;
+use crate::overrides::build_cli_overrides_layer;
+
+/// File name of the team config inside `$CODEX_HOME`.
+pub const TEAM_CONFIG_TOML_FILE: &str = "team_config.toml";
+
+/// Top-level key listing the dotted key paths the team config locks.
+const LOCKED_KEYS_FIELD: &str = "locked";
+
+/// Splits a parsed team config into its default layer and its locked layer.
+///
+/// Either layer is omitted when it would be empty. Locking a key that the file does not set is an
+/// error, since the team almost certainly meant to enforce a value.
+pub fn team_config_layers(
+    file: &AbsolutePathBuf,
+    config: TomlValue,
+) -> io::Result<Vec<ConfigLayerEntry>> {
+    let TomlValue::Table(mut table) = config else {
+        return Err(invalid_team_config(file, "expected a table"));
+    };
+    let locked_keys = match table.remove(LOCKED_KEYS_FIELD) {
+        None => Vec::new(),
+        Some(TomlValue::Array(items)) => items
+            .into_iter()
+            .map(|item| match item {
+                TomlValue::String(key) => Ok(key),
+                _ => Err(invalid_team_config(
+                    file,
+                    "`locked` entries must be dotted config keys",
+                )),
+            })
+            .collect::<io::Result<Vec<_>>>()?,
+        Some(_) => {
+            return Err(invalid_team_config(
+                file,
+                "`locked` must be an array of dotted config keys",
+            ));
+        }
+    };
+
+    let mut defaults = TomlValue::Table(table);
+    let mut locked_values = Vec::with_capacity(locked_keys.len());
+    for key in locked_keys {
+        let Some(value) = remove_dotted_key(&mut defaults, &key) else {
+            return Err(invalid_team_config(
+                file,
+                &format!("locks `{key}` but does not set it"),
+            ));
+        };
+        locked_values.push((key, value));
+    }
+
+    let mut layers = Vec::new();
+    if defaults.as_table().is_some_and(|table| !table.is_empty()) {
+        layers.push(ConfigLayerEntry::new(
+            ConfigLayerSource::Team {
+                file: file.clone(),
+                locked: false,
+            },
+            defaults,
+        ));
+    }
+    if !locked_values.is_empty() {
+        layers.push(ConfigLayerEntry::new(
+            ConfigLayerSource::Team {
+                file: file.clone(),
+                locked: true,
+            },
+            build_cli_overrides_layer(&locked_values),
+        ));
+    }
+    Ok(layers)
+}
+
+/// Removes the value at `key` and any tables the removal leaves empty.
+fn remove_dotted_key(root: &mut TomlValue, key: &str) -> Option<TomlValue> {
+    let table = root.as_table_mut()?;
+    let value = match key.split_once('.') {
+        Some((head, rest)) => {
+            let child = table.get_mut(head)?;
+            let value = remove_dotted_key(child, rest)?;
+            if child.as_table().is_some_and(toml::Table::is_empty) {
+                table.remove(head);
+            }
+            value
+        }
+        None => table.remove(key)?,
+    };
+    Some(value)
+}
+
+fn invalid_team_config(file: &AbsolutePathBuf, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "Invalid team config {}: {message}",
+            file.as_path().display()
+        ),
+    )
+}
+
+#[cfg(test)]
+#[path = "team_config_tests.rs"]
+mod tests;
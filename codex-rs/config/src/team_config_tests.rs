@@ -0,0 +1,82 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn team_file() -> AbsolutePathBuf {
+    AbsolutePathBuf::resolve_path_against_base(TEAM_CONFIG_TOML_FILE, std::env::temp_dir())
+}
+
+fn layers(contents: &str) -> io::Result<Vec<(ConfigLayerSource, TomlValue)>> {
+    let config = toml::from_str(contents).expect("parse team config");
+    Ok(team_config_layers(&team_file(), config)?
+        .into_iter()
+        .map(|layer| (layer.name, layer.config))
+        .collect())
+}
+
+#[test]
+fn locked_keys_move_to_their_own_layer() {
+    assert_eq!(
+        layers(
+            r#"
+model = "gpt-team"
+locked = ["analytics.enabled", "sandbox_mode"]
+sandbox_mode = "read-only"
+
+[analytics]
+enabled = false
+"#,
+        )
+        .expect("valid team config"),
+        vec![
+            (
+                ConfigLayerSource::Team {
+                    file: team_file(),
+                    locked: false,
+                },
+                toml::from_str(r#"model = "gpt-team""#).expect("defaults"),
+            ),
+            (
+                ConfigLayerSource::Team {
+                    file: team_file(),
+                    locked: true,
+                },
+                toml::from_str(
+                    r#"
+sandbox_mode = "read-only"
+
+[analytics]
+enabled = false
+"#,
+                )
+                .expect("locked"),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn team_config_without_locks_is_one_layer() {
+    assert_eq!(
+        layers(r#"model = "gpt-team""#)
+            .expect("valid team config")
+            .into_iter()
+            .map(|(source, _)| source)
+            .collect::<Vec<_>>(),
+        vec![ConfigLayerSource::Team {
+            file: team_file(),
+            locked: false,
+        }]
+    );
+}
+
+#[test]
+fn locking_an_unset_key_is_an_error() {
+    let err = layers(r#"locked = ["model"]"#).expect_err("lock without value");
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(
+        err.to_string()
+            .contains("locks `model` but does not set it"),
+        "unexpected error: {err}"
+    );
+}
@@ -111,6 +111,10 @@ pub struct TuiGlobalKeymap {
     pub toggle_fast_mode: Option<KeybindingsSpec>,
     /// Toggle raw scrollback mode for copy-friendly transcript selection.
     pub toggle_raw_output: Option<KeybindingsSpec>,
+    /// Expand or collapse the latest turn's reasoning summaries.
+    pub toggle_reasoning: Option<KeybindingsSpec>,
+    /// Cycle the transcript density between compact, normal, and verbose tool output.
+    pub cycle_transcript_density: Option<KeybindingsSpec>,
 }
 
 /// Chat context keybindings.
@@ -335,6 +339,8 @@ pub struct TuiPagerKeymap {
     pub close: Option<KeybindingsSpec>,
     /// Close the transcript overlay via its dedicated toggle key.
     pub close_transcript: Option<KeybindingsSpec>,
+    /// Cycle the transcript time gutter between off, relative, and absolute times.
+    pub toggle_timestamps: Option<KeybindingsSpec>,
 }
 
 /// List selection context keybindings for popup-style selectable lists.
@@ -373,6 +379,8 @@ pub struct TuiApprovalKeymap {
     pub open_fullscreen: Option<KeybindingsSpec>,
     /// Open the thread that requested approval when shown from another thread.
     pub open_thread: Option<KeybindingsSpec>,
+    /// Explain what the pending command does and why it was requested.
+    pub explain: Option<KeybindingsSpec>,
     /// Approve the primary option.
     pub approve: Option<KeybindingsSpec>,
     /// Approve for session when that option exists.
@@ -425,8 +433,9 @@ pub struct TuiKeymap {
 
 /// Normalize one user-entered key spec into canonical storage format.
 ///
-/// The output always orders modifiers as `ctrl-alt-shift-<key>` when present
-/// and applies accepted aliases (`escape` -> `esc`, `pageup` -> `page-up`).
+/// The output always orders modifiers as `ctrl-alt-shift-super-<key>` when
+/// present and applies accepted aliases (`escape` -> `esc`, `pageup` -> `page-up`,
+/// `cmd` -> `super`).
 /// Inputs that cannot be represented unambiguously are rejected.
 ///
 /// Normalization happens at config-deserialization time so downstream runtime
@@ -453,8 +462,12 @@ See the Codex keymap documentation for supported actions and examples."
         ));
     }
 
-    let mut modifiers =
-        BTreeMap::<&str, bool>::from([("ctrl", false), ("alt", false), ("shift", false)]);
+    let mut modifiers = BTreeMap::<&str, bool>::from([
+        ("ctrl", false),
+        ("alt", false),
+        ("shift", false),
+        ("super", false),
+    ]);
     let mut key_segments = Vec::new();
     let mut saw_key = false;
 
@@ -463,6 +476,7 @@ See the Codex keymap documentation for supported actions and examples."
             "ctrl" | "control" => Some("ctrl"),
             "alt" | "option" => Some("alt"),
             "shift" => Some("shift"),
+            "super" | "cmd" | "command" | "win" => Some("super"),
             _ => None,
         };
 
@@ -486,10 +500,12 @@ See the Codex keymap documentation for supported actions and examples."
         ));
     }
 
-    if key_segments
-        .iter()
-        .any(|segment| matches!(*segment, "ctrl" | "control" | "alt" | "option" | "shift"))
-    {
+    if key_segments.iter().any(|segment| {
+        matches!(
+            *segment,
+            "ctrl" | "control" | "alt" | "option" | "shift" | "super" | "cmd" | "command" | "win"
+        )
+    }) {
         return Err(format!(
             "invalid keybinding `{raw}`: modifiers must come before the key (for example `ctrl-a`)."
         ));
@@ -506,6 +522,9 @@ See the Codex keymap documentation for supported actions and examples."
     if modifiers.get("shift").copied().unwrap_or(false) {
         normalized.push("shift".to_string());
     }
+    if modifiers.get("super").copied().unwrap_or(false) {
+        normalized.push("super".to_string());
+    }
     normalized.push(key);
     Ok(normalized.join("-"))
 }
@@ -684,4 +703,18 @@ mod tests {
         assert_eq!(normalize_keybinding_spec("f24"), Ok("f24".to_string()));
         assert!(normalize_keybinding_spec("f25").is_err());
     }
+
+    #[test]
+    fn super_modifier_aliases_normalize_after_shift() {
+        assert_eq!(
+            normalize_keybinding_spec("cmd-shift-k"),
+            Ok("shift-super-k".to_string())
+        );
+        assert_eq!(
+            normalize_keybinding_spec("Win-Enter"),
+            Ok("super-enter".to_string())
+        );
+        assert!(normalize_keybinding_spec("super-cmd-k").is_err());
+        assert!(normalize_keybinding_spec("k-super").is_err());
+    }
 }
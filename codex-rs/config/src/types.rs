@@ -23,6 +23,9 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
+use std::num::NonZeroU32;
+use std::num::NonZeroU64;
+use std::time::Duration;
 
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -198,6 +201,19 @@ pub enum UriBasedFileOpener {
     None,
 }
 
+impl UriBasedFileOpener {
+    /// URI scheme the editor registers for opening files, or `None` when disabled.
+    pub fn get_scheme(self) -> Option<&'static str> {
+        match self {
+            UriBasedFileOpener::VsCode => Some("vscode"),
+            UriBasedFileOpener::VsCodeInsiders => Some("vscode-insiders"),
+            UriBasedFileOpener::Windsurf => Some("windsurf"),
+            UriBasedFileOpener::Cursor => Some("cursor"),
+            UriBasedFileOpener::None => None,
+        }
+    }
+}
+
 /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 #[serde(default)]
@@ -410,6 +426,529 @@ impl From<MemoriesToml> for MemoriesConfig {
     }
 }
 
+/// Supervised autonomy settings for thread goals, loaded from the `[goals]` table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct GoalsToml {
+    /// Number of consecutive automatic goal continuations after which Codex stops, summarizes
+    /// progress, and waits for the user to continue. Also stops automatic continuation when a
+    /// tool call is blocked at an approval boundary. Unset continues until the goal ends.
+    #[schemars(range(min = 1, max = 1000))]
+    pub checkpoint_interval: Option<u32>,
+    /// Number of consecutive failed tool calls after which automatic goal continuation stops for
+    /// a checkpoint review. Unset disables the failure threshold.
+    #[schemars(range(min = 1, max = 1000))]
+    pub max_consecutive_tool_failures: Option<u32>,
+}
+
+/// Effective supervised autonomy settings for thread goals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct GoalsConfig {
+    pub checkpoint_interval: Option<NonZeroU32>,
+    pub max_consecutive_tool_failures: Option<NonZeroU32>,
+}
+
+impl From<GoalsToml> for GoalsConfig {
+    fn from(toml: GoalsToml) -> Self {
+        Self {
+            checkpoint_interval: toml.checkpoint_interval.and_then(NonZeroU32::new),
+            max_consecutive_tool_failures: toml
+                .max_consecutive_tool_failures
+                .and_then(NonZeroU32::new),
+        }
+    }
+}
+
+/// Run limits loaded from the `[run_limits]` table or the `--max-*` CLI flags.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct RunLimitsToml {
+    /// Wall-clock seconds a session may run before Codex wraps up.
+    #[schemars(range(min = 1))]
+    pub max_duration_seconds: Option<u64>,
+    /// Number of turns a session may run before Codex wraps up.
+    #[schemars(range(min = 1))]
+    pub max_turns: Option<u32>,
+    /// Tokens a session may spend before Codex wraps up, counted as uncached input plus output.
+    #[schemars(range(min = 1))]
+    pub max_tokens: Option<u64>,
+}
+
+/// Per-session scratch directory settings, loaded from the `[scratchpad]` table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ScratchpadToml {
+    /// Keep each session's scratch directory when the session ends instead of deleting it.
+    pub keep_artifacts: Option<bool>,
+}
+
+/// Effective per-session scratch directory settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct ScratchpadConfig {
+    pub keep_artifacts: bool,
+}
+
+impl From<ScratchpadToml> for ScratchpadConfig {
+    fn from(toml: ScratchpadToml) -> Self {
+        Self {
+            keep_artifacts: toml.keep_artifacts.unwrap_or(false),
+        }
+    }
+}
+
+/// Token budget for `[system_prompt]` additions when `max_tokens` is unset.
+pub const DEFAULT_SYSTEM_PROMPT_MAX_TOKENS: usize = 2_000;
+
+/// Additions to the built-in system prompt, loaded from the `[system_prompt]` table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SystemPromptToml {
+    /// Text appended to the end of the built-in system prompt.
+    pub append: Option<String>,
+    /// Replacement text for sections of the built-in system prompt, keyed by section heading
+    /// without the leading `#`s. Only applied when `allow_replace` is true.
+    pub replace: Option<BTreeMap<String, String>>,
+    /// Apply the `replace` entries. Replacing built-in guidance can make the model behave worse,
+    /// so it has to be turned on explicitly.
+    pub allow_replace: Option<bool>,
+    /// Approximate token budget shared by `append` and `replace`. Defaults to 2000.
+    #[schemars(range(min = 1))]
+    pub max_tokens: Option<usize>,
+}
+
+/// Effective additions to the built-in system prompt, within the token budget.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct SystemPromptConfig {
+    pub append: Option<String>,
+    /// Replacement text keyed by section heading. Empty unless replacing was allowed.
+    pub replace: BTreeMap<String, String>,
+    pub max_tokens: usize,
+}
+
+/// Follow-up rounds per turn for guardrail violations when `max_rounds` is unset.
+pub const DEFAULT_GUARDRAILS_MAX_ROUNDS: u32 = 2;
+
+/// Checks on code the agent writes, loaded from the `[guardrails]` table.
+///
+/// Paths are globs relative to the workspace root, such as `src/**/*.rs`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct GuardrailsToml {
+    /// Times per turn violations are sent back to the model before the turn may end anyway.
+    /// Defaults to 2.
+    #[schemars(range(min = 1))]
+    pub max_rounds: Option<u32>,
+    /// Headers that new files must start with.
+    #[serde(default)]
+    pub license_headers: Vec<GuardrailLicenseHeaderToml>,
+    /// Patterns, such as banned APIs or dependencies, that lines the agent adds must not match.
+    #[serde(default)]
+    pub banned: Vec<GuardrailBannedPatternToml>,
+    /// Directories that new files of a kind must be placed in.
+    #[serde(default)]
+    pub placement: Vec<GuardrailPlacementToml>,
+}
+
+/// A header required at the top of new files, from `[[guardrails.license_headers]]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct GuardrailLicenseHeaderToml {
+    /// Globs of files that need the header.
+    pub paths: Vec<String>,
+    /// Header text, including comment markers, that must appear near the top of the file.
+    pub header: String,
+}
+
+/// A pattern that added lines must not match, from `[[guardrails.banned]]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct GuardrailBannedPatternToml {
+    /// Regular expression matched against each added line.
+    pub pattern: String,
+    /// Globs of files the rule applies to. Defaults to all files.
+    pub paths: Option<Vec<String>>,
+    /// Explanation or alternative shown to the model with each match.
+    pub message: Option<String>,
+}
+
+/// Where new files of a kind belong, from `[[guardrails.placement]]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct GuardrailPlacementToml {
+    /// Globs of files the rule applies to, such as `**/*.sql`.
+    pub paths: Vec<String>,
+    /// Globs that matching new files must also match, such as `migrations/**`.
+    pub allowed: Vec<String>,
+    /// Explanation shown to the model when a file is misplaced.
+    pub message: Option<String>,
+}
+
+/// Effective guardrail rules, with invalid rules dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct GuardrailsConfig {
+    pub max_rounds: u32,
+    pub license_headers: Vec<GuardrailLicenseHeaderToml>,
+    pub banned: Vec<GuardrailBannedPatternToml>,
+    pub placement: Vec<GuardrailPlacementToml>,
+}
+
+impl GuardrailsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.license_headers.is_empty() && self.banned.is_empty() && self.placement.is_empty()
+    }
+}
+
+/// Timed runs per side for `[bench]` when `runs` is unset.
+pub const DEFAULT_BENCH_RUNS: usize = 10;
+/// Untimed runs per side for `[bench]` when `warmup` is unset.
+pub const DEFAULT_BENCH_WARMUP: usize = 1;
+/// Revision `[bench]` compares against when `baseline` is unset.
+pub const DEFAULT_BENCH_BASELINE: &str = "HEAD";
+/// Per-run limit for `[bench]` when `timeout_seconds` is unset.
+pub const DEFAULT_BENCH_TIMEOUT_SECONDS: u64 = 600;
+
+/// Benchmark compared by `codex bench` and the `bench` tool, loaded from the `[bench]` table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct BenchToml {
+    /// Shell command to time, such as `cargo bench --bench parse`. It runs outside the sandbox.
+    pub command: Option<String>,
+    /// Timed runs of the baseline and of the working tree each. Defaults to 10.
+    #[schemars(range(min = 2))]
+    pub runs: Option<usize>,
+    /// Untimed runs on each side before timing starts. Defaults to 1.
+    pub warmup: Option<usize>,
+    /// Git revision to compare the working tree against. Defaults to `HEAD`.
+    pub baseline: Option<String>,
+    /// Seconds after which a single run is abandoned. Defaults to 600.
+    #[schemars(range(min = 1))]
+    pub timeout_seconds: Option<u64>,
+}
+
+/// Effective `[bench]` settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BenchConfig {
+    pub command: Option<String>,
+    pub runs: usize,
+    pub warmup: usize,
+    pub baseline: String,
+    pub timeout_seconds: u64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchToml::default().into()
+    }
+}
+
+impl From<BenchToml> for BenchConfig {
+    fn from(toml: BenchToml) -> Self {
+        Self {
+            command: toml
+                .command
+                .map(|command| command.trim().to_string())
+                .filter(|command| !command.is_empty()),
+            runs: toml.runs.unwrap_or(DEFAULT_BENCH_RUNS).max(2),
+            warmup: toml.warmup.unwrap_or(DEFAULT_BENCH_WARMUP),
+            baseline: toml
+                .baseline
+                .unwrap_or_else(|| DEFAULT_BENCH_BASELINE.to_string()),
+            timeout_seconds: toml
+                .timeout_seconds
+                .unwrap_or(DEFAULT_BENCH_TIMEOUT_SECONDS)
+                .max(1),
+        }
+    }
+}
+
+/// Sampling rate for `[profiling]` when `frequency_hz` is unset. An odd rate avoids sampling in
+/// lockstep with timers.
+pub const DEFAULT_PROFILING_FREQUENCY_HZ: u32 = 99;
+/// Limit for a profiled run when `[profiling]` leaves `timeout_seconds` unset.
+pub const DEFAULT_PROFILING_TIMEOUT_SECONDS: u64 = 600;
+
+/// Sampling profiler the `profiling` tool runs the command under.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProfilerKind {
+    Perf,
+    Dtrace,
+    PySpy,
+}
+
+/// Command profiled by the `profiling` tool, loaded from the `[profiling]` table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ProfilingToml {
+    /// Shell command to profile, such as `cargo run --release -- parse big.json`. It runs outside
+    /// the sandbox.
+    pub command: Option<String>,
+    /// Profiler to use. Defaults to `perf` on Linux and `dtrace` on macOS; use `py-spy` for
+    /// Python programs.
+    pub profiler: Option<ProfilerKind>,
+    /// Stack samples taken per second. Defaults to 99.
+    #[schemars(range(min = 1))]
+    pub frequency_hz: Option<u32>,
+    /// Seconds after which the profiled run is abandoned. Defaults to 600.
+    #[schemars(range(min = 1))]
+    pub timeout_seconds: Option<u64>,
+}
+
+/// Effective `[profiling]` settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProfilingConfig {
+    pub command: Option<String>,
+    pub profiler: Option<ProfilerKind>,
+    pub frequency_hz: u32,
+    pub timeout_seconds: u64,
+}
+
+impl Default for ProfilingConfig {
+    fn default() -> Self {
+        ProfilingToml::default().into()
+    }
+}
+
+impl From<ProfilingToml> for ProfilingConfig {
+    fn from(toml: ProfilingToml) -> Self {
+        Self {
+            command: toml
+                .command
+                .map(|command| command.trim().to_string())
+                .filter(|command| !command.is_empty()),
+            profiler: toml.profiler,
+            frequency_hz: toml
+                .frequency_hz
+                .unwrap_or(DEFAULT_PROFILING_FREQUENCY_HZ)
+                .max(1),
+            timeout_seconds: toml
+                .timeout_seconds
+                .unwrap_or(DEFAULT_PROFILING_TIMEOUT_SECONDS)
+                .max(1),
+        }
+    }
+}
+
+/// Settings for the `refactor` tools, loaded from the `[refactor]` table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct RefactorToml {
+    /// Language servers that `rename_symbol` asks first, keyed by the extension of the file that
+    /// defines the symbol, such as `rs = ["rust-analyzer"]`. Each one runs outside the sandbox
+    /// after the user approves it.
+    pub language_servers: Option<BTreeMap<String, Vec<String>>>,
+}
+
+/// Effective `[refactor]` settings.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct RefactorConfig {
+    /// Language server commands by file extension; entries without a program are dropped.
+    pub language_servers: BTreeMap<String, Vec<String>>,
+}
+
+impl From<RefactorToml> for RefactorConfig {
+    fn from(toml: RefactorToml) -> Self {
+        Self {
+            language_servers: toml
+                .language_servers
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(_, command)| command.first().is_some_and(|program| !program.is_empty()))
+                .map(|(extension, command)| {
+                    (extension.trim_start_matches('.').to_string(), command)
+                })
+                .collect(),
+        }
+    }
+}
+
+pub const DEFAULT_USAGE_EXPORT_FLUSH_INTERVAL_SECONDS: u64 = 300;
+pub const DEFAULT_USAGE_EXPORT_PREFIX: &str = "codex-usage/";
+
+/// Where exported usage records go.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageExportSink {
+    /// Append JSON Lines to a local file.
+    File,
+    /// Upload one JSON Lines object per flush to an S3-compatible bucket.
+    S3,
+    /// Send OTLP/HTTP log records to a collector.
+    Otlp,
+}
+
+/// Anonymized usage export, loaded from the `[usage_export]` table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct UsageExportToml {
+    /// Where records go. Usage is not exported when unset.
+    pub sink: Option<UsageExportSink>,
+    /// File that the `file` sink appends to.
+    pub path: Option<AbsolutePathBuf>,
+    /// Base URL of the S3-compatible service for the `s3` sink, such as
+    /// `https://s3.us-east-1.amazonaws.com`, or of the collector for the `otlp` sink, such as
+    /// `http://localhost:4318`.
+    pub endpoint: Option<String>,
+    /// Bucket that the `s3` sink uploads to.
+    pub bucket: Option<String>,
+    /// Key prefix of the objects the `s3` sink uploads. Defaults to `codex-usage/`.
+    pub prefix: Option<String>,
+    /// AWS region that signs `s3` uploads. Defaults to the region of the AWS environment.
+    pub region: Option<String>,
+    /// AWS profile whose credentials sign `s3` uploads.
+    pub profile: Option<String>,
+    /// Extra headers sent to the `otlp` collector, such as an API key.
+    pub headers: Option<BTreeMap<String, String>>,
+    /// Seconds between flushes of buffered records. Defaults to 300.
+    #[schemars(range(min = 1))]
+    pub flush_interval_seconds: Option<u64>,
+    /// Salt mixed into repository hashes, so they cannot be matched against hashes of known
+    /// repository URLs. Required when `sink` is set.
+    pub hash_salt: Option<String>,
+}
+
+/// Effective `[usage_export]` settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UsageExportConfig {
+    pub sink: Option<UsageExportSink>,
+    pub path: Option<AbsolutePathBuf>,
+    pub endpoint: Option<String>,
+    pub bucket: Option<String>,
+    pub prefix: String,
+    pub region: Option<String>,
+    pub profile: Option<String>,
+    pub headers: BTreeMap<String, String>,
+    pub flush_interval: Duration,
+    pub hash_salt: String,
+}
+
+impl Default for UsageExportConfig {
+    fn default() -> Self {
+        UsageExportToml::default().into()
+    }
+}
+
+impl From<UsageExportToml> for UsageExportConfig {
+    fn from(toml: UsageExportToml) -> Self {
+        Self {
+            sink: toml.sink,
+            path: toml.path,
+            endpoint: toml
+                .endpoint
+                .map(|endpoint| endpoint.trim_end_matches('/').to_string()),
+            bucket: toml.bucket,
+            prefix: toml
+                .prefix
+                .unwrap_or_else(|| DEFAULT_USAGE_EXPORT_PREFIX.to_string()),
+            region: toml.region,
+            profile: toml.profile,
+            headers: toml.headers.unwrap_or_default(),
+            flush_interval: Duration::from_secs(
+                toml.flush_interval_seconds
+                    .unwrap_or(DEFAULT_USAGE_EXPORT_FLUSH_INTERVAL_SECONDS)
+                    .max(1),
+            ),
+            hash_salt: toml.hash_salt.unwrap_or_default(),
+        }
+    }
+}
+
+/// A repository related to the workspace, from a `[related_repos.<name>]` table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct RelatedRepoToml {
+    /// Path to the repository's checkout. Relative paths resolve against the directory of the
+    /// config file that sets them.
+    pub path: AbsolutePathBuf,
+}
+
+/// A related repository the agent may read and diff alongside the workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RelatedRepo {
+    pub name: String,
+    pub path: AbsolutePathBuf,
+}
+
+/// A database connection from a `[databases.<name>]` table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct DatabaseToml {
+    /// SQLite file path, `sqlite:` URL, or `postgres://` connection string. Relative SQLite
+    /// paths resolve against the working directory.
+    pub url: String,
+}
+
+/// A database the agent may inspect with read-only queries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DatabaseConnection {
+    pub name: String,
+    pub url: String,
+}
+
+/// Retries a webhook delivery gets after its first attempt fails, unless `max_retries` is set.
+pub const DEFAULT_WEBHOOK_MAX_RETRIES: u32 = 3;
+
+/// A session event that can trigger a webhook.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// A turn finished.
+    TurnComplete,
+    /// The agent is waiting for a command, patch, or permission approval.
+    ApprovalRequested,
+    /// A turn failed with an error.
+    RunFailed,
+    /// The session used up its rollout token budget.
+    BudgetExceeded,
+}
+
+/// A webhook from a `[webhooks.<name>]` table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct WebhookToml {
+    /// URL that receives a JSON `POST` for each selected event.
+    pub url: String,
+    /// Events to send. Defaults to every event.
+    pub events: Option<Vec<WebhookEvent>>,
+    /// Environment variable holding the secret that signs each body with HMAC-SHA256.
+    pub secret_env_var: Option<String>,
+    /// Retries after a failed delivery, with exponential backoff. Defaults to 3.
+    pub max_retries: Option<u32>,
+}
+
+/// A webhook that receives session events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Webhook {
+    pub name: String,
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    pub secret_env_var: Option<String>,
+    pub max_retries: u32,
+}
+
+/// Effective run limits. Unset limits do not apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct RunLimitsConfig {
+    pub max_duration: Option<Duration>,
+    pub max_turns: Option<NonZeroU32>,
+    pub max_tokens: Option<NonZeroU64>,
+}
+
+impl From<RunLimitsToml> for RunLimitsConfig {
+    fn from(toml: RunLimitsToml) -> Self {
+        Self {
+            max_duration: toml
+                .max_duration_seconds
+                .filter(|seconds| *seconds > 0)
+                .map(Duration::from_secs),
+            max_turns: toml.max_turns.and_then(NonZeroU32::new),
+            max_tokens: toml.max_tokens.and_then(NonZeroU64::new),
+        }
+    }
+}
+
 /// Default settings that apply to all apps.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
 #[schemars(deny_unknown_fields)]
@@ -666,6 +1205,93 @@ pub enum TuiPetAnchor {
     ScreenBottom,
 }
 
+/// What the TUI does with an approval prompt nobody answers in time.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApprovalTimeoutAction {
+    /// Decline the request so the agent can continue without it.
+    #[default]
+    Deny,
+    /// Approve commands known to only read state, and decline everything else.
+    ApproveSafeReads,
+}
+
+/// How much of each tool call the TUI transcript shows.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum TranscriptDensity {
+    /// One line per tool call, without its output.
+    Compact,
+    /// The first and last few lines of tool output.
+    #[default]
+    Normal,
+    /// All of the tool output.
+    Verbose,
+}
+
+/// How the TUI lays out a markdown table that is wider than the terminal.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum TableOverflow {
+    /// Wrap cells in narrower columns, and show each row as key/value lines when values would split.
+    #[default]
+    Wrap,
+    /// Keep the leading columns as a grid and list the others as `key: value` lines under each row.
+    Fold,
+    /// Keep every column at full width; the transcript overlay scrolls the table sideways.
+    Scroll,
+}
+
+/// Where the TUI shows the destination of a markdown link. A link whose text already is its
+/// destination is never repeated.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkDestinations {
+    /// After the link text, as `text (url)`.
+    #[default]
+    Inline,
+    /// After the link text when the destination is short, otherwise as a numbered footnote.
+    InlineIfShort,
+    /// As numbered footnotes listed below the paragraph, list item, or table that uses them.
+    Footnotes,
+    /// Only as a terminal hyperlink on the link text, falling back to `inline` when the
+    /// terminal does not support hyperlinks.
+    HyperlinkOnly,
+}
+
+/// Overrides for terminal feature detection. Unset keys keep the detected value.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TerminalCapabilitiesToml {
+    /// 24-bit color. When off, colors are mapped to the nearest 256-color palette entry.
+    pub truecolor: Option<bool>,
+    /// OSC 8 hyperlinks for URLs and file references.
+    pub hyperlinks: Option<bool>,
+    /// Copying through OSC 52 when no system clipboard is reachable, such as over SSH.
+    pub osc52_clipboard: Option<bool>,
+    /// The kitty keyboard protocol, which distinguishes keys such as Shift+Enter.
+    pub kitty_keyboard: Option<bool>,
+    /// The Kitty graphics protocol for inline images.
+    pub kitty_graphics: Option<bool>,
+    /// Sixel graphics for inline images.
+    pub sixel: Option<bool>,
+    /// The iTerm2 inline image protocol.
+    pub iterm2_images: Option<bool>,
+}
+
+/// How the TUI transcript overlay labels each entry with the time it was added.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum TranscriptTimestamps {
+    /// No time gutter.
+    #[default]
+    Off,
+    /// Time elapsed since the entry, such as `5m` or `2h`.
+    Relative,
+    /// Local wall-clock time, such as `14:05`.
+    Absolute,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 pub struct TuiNotificationSettings {
@@ -759,6 +1385,50 @@ pub struct Tui {
     #[serde(default)]
     pub theme: Option<String>,
 
+    /// Syntax colors layered on top of the theme, keyed by TextMate scope selector (for example
+    /// `comment = "#7f848e"` or `"keyword.control" = "magenta"`). Values are `#rrggbb`, an ANSI
+    /// color name, or `default`.
+    #[serde(default)]
+    pub syntax_colors: BTreeMap<String, String>,
+
+    /// Color brackets in highlighted code by nesting depth.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub rainbow_brackets: bool,
+
+    /// Emphasize the changed words within modified lines of rendered diffs.
+    /// Defaults to `true`.
+    #[serde(default = "default_true")]
+    pub diff_word_emphasis: bool,
+
+    /// Columns between tab stops when rendering code blocks and diffs.
+    /// Defaults to `4`.
+    #[serde(default = "default_tab_width")]
+    #[schemars(range(min = 1))]
+    pub tab_width: usize,
+
+    /// Per-language tab widths, keyed by code fence language or file extension
+    /// (for example `go = 8`). Languages not listed use `tab_width`.
+    #[serde(default)]
+    pub tab_width_overrides: BTreeMap<String, usize>,
+
+    /// Mark tabs and trailing spaces in rendered diffs.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub diff_show_whitespace: bool,
+
+    /// Make `path:line` references in agent output clickable, opening the file with
+    /// `file_opener`. Defaults to `false`.
+    #[serde(default)]
+    pub file_hyperlinks: bool,
+
+    /// Language for TUI messages, as a tag such as `fr` or `pt-BR`.
+    ///
+    /// When unset, follows `LC_ALL`, `LC_MESSAGES`, or `LANG`. Translations are read from
+    /// `$CODEX_HOME/locales/<language>.toml`; untranslated messages are shown in English.
+    #[serde(default)]
+    pub language: Option<String>,
+
     /// Pet id to preselect in the terminal pet picker.
     ///
     /// Custom pet ids resolve against CODEX_HOME/pets/<pet-id>/pet.json.
@@ -780,6 +1450,84 @@ pub struct Tui {
     #[serde(default)]
     pub resume_cwd: Option<ResumeCwdMode>,
 
+    /// Seconds an approval prompt may wait unanswered before `approval_timeout_action` applies.
+    /// When unset, approval prompts wait indefinitely.
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub approval_timeout_seconds: Option<u64>,
+
+    /// What to do with an approval prompt once `approval_timeout_seconds` elapses.
+    /// Defaults to `deny`.
+    #[serde(default)]
+    pub approval_timeout_action: ApprovalTimeoutAction,
+
+    /// Seconds without keyboard input after which the TUI pauses background work such as
+    /// animations, draft autosave, and status polling. Any input resumes it.
+    /// When unset, background work never pauses.
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub idle_pause_seconds: Option<u64>,
+
+    /// Show each turn's reasoning summaries expanded instead of as a collapsed "Reasoning" row.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub expand_reasoning_summaries: bool,
+
+    /// Strike through checked task list items (`- [x]`) in rendered markdown, in addition to
+    /// dimming them. Defaults to `false`.
+    #[serde(default)]
+    pub strike_completed_tasks: bool,
+
+    /// How markdown tables wider than the terminal are laid out: `wrap`, `fold`, or `scroll`.
+    /// Defaults to `wrap`.
+    #[serde(default)]
+    pub table_overflow: TableOverflow,
+
+    /// Render `$...$` and `$$...$$` math in markdown, converting common LaTeX to Unicode and
+    /// boxing display formulas. When `false`, dollar signs are shown as written.
+    /// Defaults to `true`.
+    #[serde(default = "default_true")]
+    pub render_math: bool,
+
+    /// Where markdown link destinations are shown: `inline`, `inline-if-short`, `footnotes`, or
+    /// `hyperlink-only`. Defaults to `inline`.
+    #[serde(default)]
+    pub link_destinations: LinkDestinations,
+
+    /// Fenced code blocks in markdown longer than this many lines show only their first and last
+    /// halves around a `… N lines hidden …` row, which `e` expands in the transcript overlay.
+    /// `0` never collapses code blocks. Defaults to `200`.
+    #[serde(default = "default_code_block_max_lines")]
+    #[schemars(range(min = 0))]
+    pub code_block_max_lines: usize,
+
+    /// Draw local images referenced from markdown below their `[image: ...]` placeholder, using
+    /// the iTerm2, Kitty, or Sixel graphics protocol when the terminal supports one.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub inline_images: bool,
+
+    /// How much of each tool call the transcript shows: `compact`, `normal`, or `verbose`.
+    /// Defaults to `normal`.
+    #[serde(default)]
+    pub transcript_density: TranscriptDensity,
+
+    /// Time gutter in the transcript overlay: `off`, `relative`, or `absolute`.
+    /// Defaults to `off`.
+    #[serde(default)]
+    pub transcript_timestamps: TranscriptTimestamps,
+
+    /// Keep the transcript overlay pinned to new activity while it is scrolled to the bottom.
+    /// Scrolling up always pauses following until the jump-to-bottom key is pressed.
+    /// Defaults to `true`.
+    #[serde(default = "default_true")]
+    pub transcript_auto_follow: bool,
+
+    /// When running inside tmux, open `/diff` and the `/ps` background-terminal list in a new tmux
+    /// split instead of the full-screen overlay or transcript. Defaults to `false`.
+    #[serde(default)]
+    pub tmux_split_views: bool,
+
     /// Keybinding overrides for the TUI.
     ///
     /// This supports rebinding selected actions globally and by context.
@@ -791,6 +1539,10 @@ pub struct Tui {
     #[serde(default)]
     pub model_availability_nux: ModelAvailabilityNuxConfig,
 
+    /// Force terminal features on or off when detection guesses wrong for your terminal.
+    #[serde(default)]
+    pub terminal_capabilities: TerminalCapabilitiesToml,
+
     /// Trim terminal resize-reflow replay to the most recent rendered terminal rows when the
     /// transcript exceeds this cap. Omit to use Codex's terminal-specific default. Set to `0` to
     /// keep all rendered rows.
@@ -803,6 +1555,14 @@ const fn default_true() -> bool {
     true
 }
 
+const fn default_tab_width() -> usize {
+    4
+}
+
+const fn default_code_block_max_lines() -> usize {
+    200
+}
+
 /// Settings for notices we display to users via the tui and app-server clients
 /// (primarily the Codex IDE extension). NOTE: these are different from
 /// notifications - notices are warnings, NUX screens, acknowledgements, etc.
@@ -11,20 +11,39 @@ pub use codex_config::ConfigLayerStack;
 pub use codex_config::config_toml::ProjectConfig;
 pub use codex_config::config_toml::RealtimeAudioConfig;
 pub use codex_config::config_toml::RealtimeConfig;
+pub use codex_config::types::ApprovalTimeoutAction;
 pub use codex_config::types::AuthCredentialsStoreMode;
 pub use codex_config::types::AuthKeyringBackendKind;
+pub use codex_config::types::BenchConfig;
+pub use codex_config::types::DatabaseConnection;
+pub use codex_config::types::GoalsConfig;
+pub use codex_config::types::GuardrailsConfig;
 pub use codex_config::types::History;
+pub use codex_config::types::LinkDestinations;
 pub use codex_config::types::MemoriesConfig;
 pub use codex_config::types::ModelAvailabilityNuxConfig;
 pub use codex_config::types::Notice;
 pub use codex_config::types::OAuthCredentialsStoreMode;
 pub use codex_config::types::OtelConfig;
+pub use codex_config::types::ProfilingConfig;
+pub use codex_config::types::RefactorConfig;
+pub use codex_config::types::RelatedRepo;
+pub use codex_config::types::RunLimitsConfig;
+pub use codex_config::types::ScratchpadConfig;
 pub use codex_config::types::SessionPickerViewMode;
+pub use codex_config::types::SystemPromptConfig;
+pub use codex_config::types::TableOverflow;
+pub use codex_config::types::TerminalCapabilitiesToml;
 pub use codex_config::types::ToolSuggestConfig;
+pub use codex_config::types::TranscriptDensity;
+pub use codex_config::types::TranscriptTimestamps;
 pub use codex_config::types::TuiKeymap;
 pub use codex_config::types::TuiNotificationSettings;
 pub use codex_config::types::TuiPetAnchor;
 pub use codex_config::types::UriBasedFileOpener;
+pub use codex_config::types::UsageExportConfig;
+pub use codex_config::types::Webhook;
+pub use codex_config::types::WebhookEvent;
 pub use codex_core::CodexAppsToolsCache;
 pub use codex_core::CodexThread;
 pub use codex_core::ForkSnapshot;
@@ -37,6 +56,8 @@ pub use codex_core::ThreadManager;
 pub use codex_core::ThreadShutdownReport;
 pub use codex_core::build_models_manager;
 pub use codex_core::config::Config;
+pub use codex_core::config::ConfigBuilder;
+pub use codex_core::config::ConfigOverrides;
 pub use codex_core::config::Constrained;
 pub use codex_core::config::ExtraConfig;
 pub use codex_core::config::GhostSnapshotConfig;
@@ -92,18 +113,24 @@ pub use codex_protocol::config_types::AutoCompactTokenLimitScope;
 pub use codex_protocol::config_types::CollaborationModeMask;
 pub use codex_protocol::config_types::ShellEnvironmentPolicy;
 pub use codex_protocol::config_types::WebSearchMode;
+pub use codex_protocol::dynamic_tools::DynamicToolCallOutputContentItem;
+pub use codex_protocol::dynamic_tools::DynamicToolCallRequest;
 pub use codex_protocol::dynamic_tools::DynamicToolFunctionSpec;
 pub use codex_protocol::dynamic_tools::DynamicToolNamespaceSpec;
 pub use codex_protocol::dynamic_tools::DynamicToolNamespaceTool;
+pub use codex_protocol::dynamic_tools::DynamicToolResponse;
 pub use codex_protocol::dynamic_tools::DynamicToolSpec;
+pub use codex_protocol::error::CodexErr;
 pub use codex_protocol::error::Result as CodexResult;
 pub use codex_protocol::models::PermissionProfile;
 pub use codex_protocol::openai_models::ModelPreset;
 pub use codex_protocol::protocol::AskForApproval;
+pub use codex_protocol::protocol::Event;
 pub use codex_protocol::protocol::EventMsg;
 pub use codex_protocol::protocol::InitialHistory;
 pub use codex_protocol::protocol::McpServerRefreshConfig;
 pub use codex_protocol::protocol::Op;
+pub use codex_protocol::protocol::ReviewDecision;
 pub use codex_protocol::protocol::SessionConfiguredEvent;
 pub use codex_protocol::protocol::SessionSource;
 pub use codex_protocol::protocol::TurnEnvironmentSelection;
@@ -38,11 +38,14 @@ use codex_config::permissions_toml::PermissionProfileToml;
 use codex_config::permissions_toml::PermissionsToml;
 use codex_config::permissions_toml::WorkspaceRootsToml;
 use codex_config::types::AppToolApproval;
+use codex_config::types::ApprovalTimeoutAction;
 use codex_config::types::ApprovalsReviewer;
 use codex_config::types::ApprovalsToml;
 use codex_config::types::BundledSkillsConfig;
 use codex_config::types::FeedbackConfigToml;
+use codex_config::types::GoalsConfig;
 use codex_config::types::HistoryPersistence;
+use codex_config::types::LinkDestinations;
 use codex_config::types::McpServerEnvVar;
 use codex_config::types::McpServerOAuthConfig;
 use codex_config::types::McpServerToolConfig;
@@ -60,8 +63,12 @@ use codex_config::types::ResumeCwdMode;
 use codex_config::types::SandboxWorkspaceWrite;
 use codex_config::types::SessionPickerViewMode;
 use codex_config::types::SkillsConfig;
+use codex_config::types::TableOverflow;
+use codex_config::types::TerminalCapabilitiesToml;
 use codex_config::types::ToolSuggestDisabledTool;
 use codex_config::types::ToolSuggestDiscoverableType;
+use codex_config::types::TranscriptDensity;
+use codex_config::types::TranscriptTimestamps;
 use codex_config::types::Tui;
 use codex_config::types::TuiKeymap;
 use codex_config::types::TuiNotificationSettings;
@@ -364,6 +371,33 @@ always_prompt_regex = ["^git push$", "^rm -rf"]
     );
 }
 
+#[tokio::test]
+async fn goals_toml_loads_checkpoint_settings() {
+    let cfg: ConfigToml = toml::from_str(
+        r#"
+[goals]
+checkpoint_interval = 5
+max_consecutive_tool_failures = 0
+"#,
+    )
+    .expect("TOML deserialization should succeed");
+
+    let config = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").abs(),
+    )
+    .await
+    .expect("load config from goals settings");
+    assert_eq!(
+        config.goals,
+        GoalsConfig {
+            checkpoint_interval: std::num::NonZeroU32::new(5),
+            max_consecutive_tool_failures: None,
+        }
+    );
+}
+
 #[test]
 fn approvals_always_prompt_regex_toml_parses() {
     let cfg: ConfigToml = toml::from_str(
@@ -1003,10 +1037,33 @@ fn config_toml_deserializes_model_availability_nux() {
             status_line_use_colors: true,
             terminal_title: None,
             theme: None,
+            syntax_colors: BTreeMap::new(),
+            rainbow_brackets: false,
+            diff_word_emphasis: true,
+            tab_width: 4,
+            tab_width_overrides: BTreeMap::new(),
+            diff_show_whitespace: false,
+            file_hyperlinks: false,
+            language: None,
             pet: None,
             pet_anchor: TuiPetAnchor::Composer,
             session_picker_view: None,
             resume_cwd: None,
+            approval_timeout_seconds: None,
+            approval_timeout_action: ApprovalTimeoutAction::Deny,
+            idle_pause_seconds: None,
+            expand_reasoning_summaries: false,
+            strike_completed_tasks: false,
+            table_overflow: TableOverflow::Wrap,
+            render_math: true,
+            link_destinations: LinkDestinations::Inline,
+            code_block_max_lines: 200,
+            inline_images: false,
+            terminal_capabilities: TerminalCapabilitiesToml::default(),
+            transcript_density: TranscriptDensity::Normal,
+            transcript_timestamps: TranscriptTimestamps::Off,
+            transcript_auto_follow: true,
+            tmux_split_views: false,
             keymap: TuiKeymap::default(),
             model_availability_nux: ModelAvailabilityNuxConfig {
                 shown_count: HashMap::from([
@@ -2985,6 +3042,38 @@ async fn default_permissions_profile_can_extend_builtin_read_only() -> std::io::
     Ok(())
 }
 
+#[tokio::test]
+async fn project_response_language_overrides_top_level() -> std::io::Result<()> {
+    let codex_home = TempDir::new()?;
+    let cwd = TempDir::new()?;
+    let load = |project_language: Option<&str>| {
+        Config::load_from_base_config_with_overrides(
+            ConfigToml {
+                response_language: Some(" German ".to_string()),
+                projects: Some(HashMap::from([(
+                    cwd.path().to_string_lossy().to_string(),
+                    ProjectConfig {
+                        trust_level: None,
+                        response_language: project_language.map(str::to_string),
+                    },
+                )])),
+                ..Default::default()
+            },
+            ConfigOverrides {
+                cwd: Some(cwd.path().to_path_buf()),
+                ..Default::default()
+            },
+            codex_home.abs(),
+        )
+    };
+
+    let config = load(Some("pt-BR")).await?;
+    assert_eq!(config.response_language.as_deref(), Some("pt-BR"));
+    let config = load(None).await?;
+    assert_eq!(config.response_language.as_deref(), Some("German"));
+    Ok(())
+}
+
 #[tokio::test]
 async fn empty_config_defaults_to_builtin_profile_for_trusted_project() -> std::io::Result<()> {
     let codex_home = TempDir::new()?;
@@ -2997,6 +3086,7 @@ async fn empty_config_defaults_to_builtin_profile_for_trusted_project() -> std::
                 project_key,
                 ProjectConfig {
                     trust_level: Some(TrustLevel::Trusted),
+                    response_language: None,
                 },
             )])),
             ..Default::default()
@@ -3052,6 +3142,7 @@ async fn empty_config_defaults_to_builtin_profile_for_untrusted_project() -> std
                 project_key,
                 ProjectConfig {
                     trust_level: Some(TrustLevel::Untrusted),
+                    response_language: None,
                 },
             )])),
             ..Default::default()
@@ -3114,6 +3205,7 @@ async fn implicit_builtin_workspace_profile_preserves_sandbox_workspace_write_se
                 project_key,
                 ProjectConfig {
                     trust_level: Some(TrustLevel::Trusted),
+                    response_language: None,
                 },
             )])),
             sandbox_workspace_write: Some(SandboxWorkspaceWrite {
@@ -3185,6 +3277,7 @@ async fn implicit_builtin_workspace_profile_preserves_add_dir_metadata_carveouts
                 project_key,
                 ProjectConfig {
                     trust_level: Some(TrustLevel::Trusted),
+                    response_language: None,
                 },
             )])),
             windows: Some(WindowsToml {
@@ -3771,6 +3864,23 @@ fn tui_theme_defaults_to_none() {
     assert_eq!(parsed.tui.as_ref().and_then(|t| t.theme.as_deref()), None);
 }
 
+#[test]
+fn tui_syntax_colors_deserialize_from_toml() {
+    let cfg = r##"
+[tui.syntax_colors]
+comment = "#7f848e"
+"keyword.control" = "magenta"
+"##;
+    let parsed = toml::from_str::<ConfigToml>(cfg).expect("TOML deserialization should succeed");
+    assert_eq!(
+        parsed.tui.map(|t| t.syntax_colors),
+        Some(BTreeMap::from([
+            ("comment".to_string(), "#7f848e".to_string()),
+            ("keyword.control".to_string(), "magenta".to_string()),
+        ])),
+    );
+}
+
 #[test]
 fn tui_session_picker_view_deserializes_from_toml() {
     let cfg = r#"
@@ -3856,6 +3966,75 @@ fn tui_pet_anchor_defaults_to_composer() {
     );
 }
 
+#[test]
+fn tui_approval_timeout_deserializes_from_toml() {
+    let cfg = r#"
+[tui]
+approval_timeout_seconds = 600
+approval_timeout_action = "approve-safe-reads"
+"#;
+    let parsed = toml::from_str::<ConfigToml>(cfg).expect("TOML deserialization should succeed");
+    assert_eq!(
+        parsed
+            .tui
+            .as_ref()
+            .map(|t| (t.approval_timeout_seconds, t.approval_timeout_action)),
+        Some((Some(600), ApprovalTimeoutAction::ApproveSafeReads)),
+    );
+}
+
+#[test]
+fn tui_idle_pause_seconds_deserializes_from_toml() {
+    let cfg = r#"
+[tui]
+idle_pause_seconds = 300
+"#;
+    let parsed = toml::from_str::<ConfigToml>(cfg).expect("TOML deserialization should succeed");
+    assert_eq!(
+        parsed.tui.as_ref().map(|t| t.idle_pause_seconds),
+        Some(Some(300)),
+    );
+}
+
+#[test]
+fn tui_expand_reasoning_summaries_deserializes_from_toml() {
+    let cfg = r#"
+[tui]
+expand_reasoning_summaries = true
+"#;
+    let parsed = toml::from_str::<ConfigToml>(cfg).expect("TOML deserialization should succeed");
+    assert_eq!(
+        parsed.tui.as_ref().map(|t| t.expand_reasoning_summaries),
+        Some(true),
+    );
+}
+
+#[test]
+fn tui_transcript_density_deserializes_from_toml() {
+    let cfg = r#"
+[tui]
+transcript_density = "compact"
+"#;
+    let parsed = toml::from_str::<ConfigToml>(cfg).expect("TOML deserialization should succeed");
+    assert_eq!(
+        parsed.tui.as_ref().map(|t| t.transcript_density),
+        Some(TranscriptDensity::Compact),
+    );
+}
+
+#[test]
+fn tui_transcript_timestamps_deserializes_from_toml() {
+    let cfg = r#"
+[tui]
+transcript_timestamps = "relative"
+"#;
+    let parsed = toml::from_str::<ConfigToml>(cfg).expect("TOML deserialization should succeed");
+    assert_eq!(
+        parsed.tui.as_ref().map(|t| t.transcript_timestamps),
+        Some(TranscriptTimestamps::Relative),
+    );
+}
+
 #[test]
 fn tui_pet_anchor_rejects_unknown_value() {
     let cfg = r#"
@@ -3895,10 +4074,33 @@ fn tui_config_missing_notifications_field_defaults_to_enabled() {
             status_line_use_colors: true,
             terminal_title: None,
             theme: None,
+            syntax_colors: BTreeMap::new(),
+            rainbow_brackets: false,
+            diff_word_emphasis: true,
+            tab_width: 4,
+            tab_width_overrides: BTreeMap::new(),
+            diff_show_whitespace: false,
+            file_hyperlinks: false,
+            language: None,
             pet: None,
             pet_anchor: TuiPetAnchor::Composer,
             session_picker_view: None,
             resume_cwd: None,
+            approval_timeout_seconds: None,
+            approval_timeout_action: ApprovalTimeoutAction::Deny,
+            idle_pause_seconds: None,
+            expand_reasoning_summaries: false,
+            strike_completed_tasks: false,
+            table_overflow: TableOverflow::Wrap,
+            render_math: true,
+            link_destinations: LinkDestinations::Inline,
+            code_block_max_lines: 200,
+            inline_images: false,
+            terminal_capabilities: TerminalCapabilitiesToml::default(),
+            transcript_density: TranscriptDensity::Normal,
+            transcript_timestamps: TranscriptTimestamps::Off,
+            transcript_auto_follow: true,
+            tmux_split_views: false,
             keymap: TuiKeymap::default(),
             model_availability_nux: ModelAvailabilityNuxConfig::default(),
             terminal_resize_reflow_max_rows: None,
@@ -5952,6 +6154,49 @@ model = "gpt-project-local"
     Ok(())
 }
 
+#[tokio::test]
+async fn team_config_locks_are_not_bypassed_by_cli_overrides() -> std::io::Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join(codex_config::TEAM_CONFIG_TOML_FILE),
+        r#"
+model = "gpt-team"
+sandbox_mode = "workspace-write"
+locked = ["model", "sandbox_mode"]
+"#,
+    )?;
+
+    let config = ConfigBuilder::without_managed_config_for_tests()
+        .codex_home(codex_home.path().to_path_buf())
+        .harness_overrides(ConfigOverrides {
+            model: Some("gpt-cli".to_string()),
+            sandbox_mode: Some(SandboxMode::DangerFullAccess),
+            ..Default::default()
+        })
+        .build()
+        .await?;
+
+    assert_eq!(config.model.as_deref(), Some("gpt-team"));
+    assert_ne!(
+        config.permissions.permission_profile(),
+        &PermissionProfile::Disabled
+    );
+    for field in ["model", "permission_profile"] {
+        assert!(
+            config
+                .startup_warnings
+                .iter()
+                .any(|warning| warning.contains(&format!(
+                    "Configured value for `{field}` is disallowed by requirements"
+                ))),
+            "expected a requirements warning for {field}: {:?}",
+            config.startup_warnings
+        );
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn feature_table_overrides_legacy_flags() -> std::io::Result<()> {
     let codex_home = TempDir::new()?;
@@ -9308,6 +9553,7 @@ async fn test_requirements_web_search_mode_allowlist_does_not_warn_when_unset()
         check_for_update_on_startup: None,
         allow_login_shell: None,
         feedback: None,
+        allowed_models: None,
         allowed_approval_policies: None,
         allowed_approvals_reviewers: None,
         allowed_sandbox_modes: None,
@@ -9496,6 +9742,7 @@ async fn active_project_does_not_match_configured_alias_for_canonical_cwd() -> a
             alias_root.to_string_lossy().to_string(),
             ProjectConfig {
                 trust_level: Some(TrustLevel::Trusted),
+                response_language: None,
             },
         )])),
         ..Default::default()
@@ -9600,6 +9847,7 @@ trust_level = "untrusted"
         .expect("TOML deserialization should succeed");
     let active_project = ProjectConfig {
         trust_level: Some(TrustLevel::Untrusted),
+        response_language: None,
     };
 
     let resolution = derive_legacy_sandbox_policy_for_test(
@@ -9638,12 +9886,14 @@ async fn derive_sandbox_policy_falls_back_to_read_only_for_implicit_defaults() -
             project_key,
             ProjectConfig {
                 trust_level: Some(TrustLevel::Trusted),
+                response_language: None,
             },
         )])),
         ..Default::default()
     };
     let active_project = ProjectConfig {
         trust_level: Some(TrustLevel::Trusted),
+        response_language: None,
     };
     let constrained = Constrained::new(PermissionProfile::read_only(), |candidate| {
         if candidate == &PermissionProfile::read_only() {
@@ -9682,12 +9932,14 @@ async fn derive_sandbox_policy_preserves_windows_downgrade_for_unsupported_fallb
             project_key,
             ProjectConfig {
                 trust_level: Some(TrustLevel::Trusted),
+                response_language: None,
             },
         )])),
         ..Default::default()
     };
     let active_project = ProjectConfig {
         trust_level: Some(TrustLevel::Trusted),
+        response_language: None,
     };
     let constrained = Constrained::new(PermissionProfile::workspace_write(), |candidate| {
         if matches!(
@@ -9915,6 +10167,7 @@ async fn test_untrusted_project_gets_unless_trusted_approval_policy() -> anyhow:
                 test_path.to_string_lossy().to_string(),
                 ProjectConfig {
                     trust_level: Some(TrustLevel::Untrusted),
+                    response_language: None,
                 },
             )])),
             ..Default::default()
@@ -33,25 +33,45 @@ use codex_config::loader::load_config_layers_state;
 use codex_config::loader::project_trust_key;
 use codex_config::permissions_toml::PermissionsToml;
 use codex_config::sandbox_mode_requirement_for_permission_profile;
+use codex_config::types::ApprovalTimeoutAction;
 use codex_config::types::ApprovalsReviewer;
 use codex_config::types::AuthCredentialsStoreMode;
 use codex_config::types::AuthKeyringBackendKind;
+use codex_config::types::BenchConfig;
+use codex_config::types::DEFAULT_WEBHOOK_MAX_RETRIES;
+use codex_config::types::DatabaseConnection;
+use codex_config::types::GoalsConfig;
+use codex_config::types::GuardrailsConfig;
 use codex_config::types::History;
+use codex_config::types::LinkDestinations;
 use codex_config::types::McpServerConfig;
 use codex_config::types::McpServerDisabledReason;
 use codex_config::types::MemoriesConfig;
 use codex_config::types::ModelAvailabilityNuxConfig;
 use codex_config::types::Notice;
 use codex_config::types::OAuthCredentialsStoreMode;
+use codex_config::types::ProfilingConfig;
+use codex_config::types::RefactorConfig;
+use codex_config::types::RelatedRepo;
 use codex_config::types::ResumeCwdMode;
+use codex_config::types::RunLimitsConfig;
+use codex_config::types::ScratchpadConfig;
 use codex_config::types::SessionPickerViewMode;
+use codex_config::types::SystemPromptConfig;
+use codex_config::types::TableOverflow;
+use codex_config::types::TerminalCapabilitiesToml;
 use codex_config::types::ToolSuggestConfig;
 use codex_config::types::ToolSuggestDisabledTool;
 use codex_config::types::ToolSuggestDiscoverable;
+use codex_config::types::TranscriptDensity;
+use codex_config::types::TranscriptTimestamps;
 use codex_config::types::TuiKeymap;
 use codex_config::types::TuiNotificationSettings;
 use codex_config::types::TuiPetAnchor;
 use codex_config::types::UriBasedFileOpener;
+use codex_config::types::UsageExportConfig;
+use codex_config::types::Webhook;
+use codex_config::types::WebhookEvent;
 use codex_config::types::WindowsSandboxModeToml;
 use codex_core_plugins::PluginLoadOutcome;
 use codex_core_plugins::PluginsConfigInput;
@@ -163,6 +183,8 @@ pub use codex_config::Constrained;
 pub use codex_config::ConstraintError;
 pub use codex_config::ConstraintResult;
 pub use codex_config::LoaderOverrides;
+pub use codex_network_proxy::HostBlockDecision;
+pub use codex_network_proxy::HostBlockReason;
 pub use codex_network_proxy::NetworkProxyAuditMetadata;
 use codex_sandboxing::compatibility_sandbox_policy_for_permission_profile;
 pub use codex_sandboxing::system_bwrap_warning;
@@ -589,6 +611,27 @@ impl Permissions {
             .can_set_legacy_permission_profile(permission_profile)
     }
 
+    /// Grant write access to Codex-owned directories, such as the session scratchpad, on top of
+    /// whatever the current profile allows. The roots are not reported as workspace roots.
+    pub(crate) fn add_runtime_writable_roots(
+        &mut self,
+        cwd: &Path,
+        writable_roots: &[AbsolutePathBuf],
+    ) -> ConstraintResult<()> {
+        let permission_profile = self.permission_profile();
+        let (file_system_sandbox_policy, network_sandbox_policy) =
+            permission_profile.to_runtime_permissions();
+        let file_system_sandbox_policy =
+            file_system_sandbox_policy.with_additional_writable_roots(cwd, writable_roots);
+        let permission_profile = PermissionProfile::from_runtime_permissions_with_enforcement(
+            permission_profile.enforcement(),
+            &file_system_sandbox_policy,
+            network_sandbox_policy,
+        );
+        self.permission_profile_state
+            .set_permission_profile_keeping_identity(permission_profile)
+    }
+
     pub fn set_workspace_roots(&mut self, workspace_roots: Vec<AbsolutePathBuf>) {
         self.workspace_roots = workspace_roots;
     }
@@ -806,6 +849,10 @@ pub struct Config {
     /// Optionally specify the personality of the model
     pub personality: Option<Personality>,
 
+    /// Language the assistant replies in, from the active project's
+    /// `response_language` or the top-level one.
+    pub response_language: Option<String>,
+
     /// Effective permission configuration for shell tool execution.
     pub permissions: Permissions,
 
@@ -938,6 +985,30 @@ pub struct Config {
     /// Syntax highlighting theme override (kebab-case name).
     pub tui_theme: Option<String>,
 
+    /// Syntax colors keyed by TextMate scope selector, applied on top of the theme.
+    pub tui_syntax_colors: BTreeMap<String, String>,
+
+    /// Whether highlighted code colors brackets by nesting depth.
+    pub tui_rainbow_brackets: bool,
+
+    /// Whether diffs emphasize the changed words within modified lines.
+    pub tui_diff_word_emphasis: bool,
+
+    /// Columns between tab stops in rendered code blocks and diffs.
+    pub tui_tab_width: usize,
+
+    /// Tab widths keyed by code fence language or file extension.
+    pub tui_tab_width_overrides: BTreeMap<String, usize>,
+
+    /// Whether diffs mark tabs and trailing spaces.
+    pub tui_diff_show_whitespace: bool,
+
+    /// Whether `path:line` references become terminal hyperlinks.
+    pub tui_file_hyperlinks: bool,
+
+    /// Language tag for TUI messages; `None` follows the locale environment.
+    pub tui_language: Option<String>,
+
     /// Pet id preselected by the terminal pet picker.
     pub tui_pet: Option<String>,
 
@@ -951,6 +1022,51 @@ pub struct Config {
     /// When unset, prompt if the current and session directories differ.
     pub tui_resume_cwd: Option<ResumeCwdMode>,
 
+    /// Seconds an approval prompt may wait unanswered; `None` waits indefinitely.
+    pub tui_approval_timeout_seconds: Option<u64>,
+
+    /// What to do with an approval prompt once `tui_approval_timeout_seconds` elapses.
+    pub tui_approval_timeout_action: ApprovalTimeoutAction,
+
+    /// Seconds without input before the TUI pauses background work; `None` never pauses.
+    pub tui_idle_pause_seconds: Option<u64>,
+
+    /// Whether each turn's reasoning summaries start expanded in the transcript.
+    pub tui_expand_reasoning_summaries: bool,
+
+    /// Whether checked markdown task list items are struck through as well as dimmed.
+    pub tui_strike_completed_tasks: bool,
+
+    /// How markdown tables wider than the terminal are laid out.
+    pub tui_table_overflow: TableOverflow,
+
+    /// Whether `$...$` and `$$...$$` math in markdown is rendered instead of shown verbatim.
+    pub tui_render_math: bool,
+
+    /// Where markdown link destinations are shown.
+    pub tui_link_destinations: LinkDestinations,
+
+    /// Longest fenced code block shown in full in markdown; `0` never collapses.
+    pub tui_code_block_max_lines: usize,
+
+    /// Whether local markdown images are drawn with a terminal graphics protocol.
+    pub tui_inline_images: bool,
+
+    /// Terminal features forced on or off instead of detected.
+    pub tui_terminal_capabilities: TerminalCapabilitiesToml,
+
+    /// How much of each tool call the transcript shows at startup.
+    pub tui_transcript_density: TranscriptDensity,
+
+    /// How the transcript overlay labels entries with their time at startup.
+    pub tui_transcript_timestamps: TranscriptTimestamps,
+
+    /// Whether the transcript overlay follows new activity while scrolled to the bottom.
+    pub tui_transcript_auto_follow: bool,
+
+    /// Whether `/diff` and `/ps` open in a tmux split when running inside tmux.
+    pub tui_tmux_split_views: bool,
+
     /// Terminal resize-reflow tuning knobs.
     pub terminal_resize_reflow: TerminalResizeReflowConfig,
 
@@ -1043,6 +1159,42 @@ pub struct Config {
     /// Memories subsystem settings.
     pub memories: MemoriesConfig,
 
+    /// Supervised autonomy settings for thread goals.
+    pub goals: GoalsConfig,
+
+    /// Session duration, turn, and token limits.
+    pub run_limits: RunLimitsConfig,
+
+    /// Repositories related to the workspace, from `[related_repos]`.
+    pub related_repos: Vec<RelatedRepo>,
+
+    /// Databases available to the read-only database tools, from `[databases]`.
+    pub databases: Vec<DatabaseConnection>,
+
+    /// URLs that receive session events, from `[webhooks]`.
+    pub webhooks: Vec<Webhook>,
+
+    /// Per-session scratch directory settings, from `[scratchpad]`.
+    pub scratchpad: ScratchpadConfig,
+
+    /// Additions to the built-in system prompt, from `[system_prompt]`.
+    pub system_prompt: SystemPromptConfig,
+
+    /// Rules checked against code the agent writes, from `[guardrails]`.
+    pub guardrails: GuardrailsConfig,
+
+    /// Benchmark compared by `codex bench`, from `[bench]`.
+    pub bench: BenchConfig,
+
+    /// Command profiled by the `profiling` tool, from `[profiling]`.
+    pub profiling: ProfilingConfig,
+
+    /// Language servers used by the `refactor` tools, from `[refactor]`.
+    pub refactor: RefactorConfig,
+
+    /// Anonymized usage export, from `[usage_export]`.
+    pub usage_export: UsageExportConfig,
+
     /// Directory containing all Codex state (defaults to `~/.codex` but can be
     /// overridden by the `CODEX_HOME` environment variable).
     pub codex_home: AbsolutePathBuf,
@@ -3250,6 +3402,7 @@ impl Config {
             check_for_update_on_startup: _,
             allow_login_shell: _,
             feedback: _,
+            model: mut constrained_model,
             approval_policy: mut constrained_approval_policy,
             approvals_reviewer: mut constrained_approvals_reviewer,
             permission_profile: mut constrained_permission_profile,
@@ -3408,7 +3561,10 @@ impl Config {
                 resolved_cwd.as_path(),
                 repo_root.as_ref().map(AbsolutePathBuf::as_path),
             )
-            .unwrap_or(ProjectConfig { trust_level: None });
+            .unwrap_or(ProjectConfig {
+            trust_level: None,
+            response_language: None,
+        });
         let permission_config_syntax = resolve_permission_config_syntax(
             &config_layer_stack,
             &cfg,
@@ -3858,7 +4014,13 @@ impl Config {
 
         let forced_login_method = cfg.forced_login_method;
 
-        let model = model.or(cfg.model);
+        apply_requirement_constrained_value(
+            "model",
+            model.or(cfg.model),
+            &mut constrained_model,
+            &mut startup_warnings,
+        )?;
+        let model = constrained_model.get().clone();
         let notices = cfg.notice.unwrap_or_default();
         let service_tier = match service_tier_override {
             Some(Some(service_tier)) => Some(service_tier),
@@ -4072,6 +4234,14 @@ impl Config {
         )
         .map_err(std::io::Error::from)?;
         let otel = otel::resolve_config(cfg.otel.unwrap_or_default(), &mut startup_warnings);
+        let system_prompt = crate::system_prompt::resolve_config(
+            cfg.system_prompt.clone().unwrap_or_default(),
+            &mut startup_warnings,
+        );
+        let guardrails = crate::guardrails::resolve_config(
+            cfg.guardrails.clone().unwrap_or_default(),
+            &mut startup_warnings,
+        );
         let config = Self {
             model,
             service_tier,
@@ -4104,6 +4274,12 @@ impl Config {
             notify: cfg.notify,
             base_instructions,
             personality,
+            response_language: active_project
+                .response_language
+                .clone()
+                .or_else(|| cfg.response_language.clone())
+                .map(|language| language.trim().to_string())
+                .filter(|language| !language.is_empty()),
             developer_instructions,
             compact_prompt,
             include_permissions_instructions,
@@ -4151,6 +4327,49 @@ impl Config {
             agent_max_depth,
             agent_roles,
             memories: memories_config,
+            goals: cfg.goals.clone().unwrap_or_default().into(),
+            run_limits: cfg.run_limits.clone().unwrap_or_default().into(),
+            related_repos: cfg
+                .related_repos
+                .iter()
+                .map(|(name, repo)| RelatedRepo {
+                    name: name.clone(),
+                    path: repo.path.clone(),
+                })
+                .collect(),
+            databases: cfg
+                .databases
+                .iter()
+                .map(|(name, database)| DatabaseConnection {
+                    name: name.clone(),
+                    url: database.url.clone(),
+                })
+                .collect(),
+            webhooks: cfg
+                .webhooks
+                .iter()
+                .map(|(name, webhook)| Webhook {
+                    name: name.clone(),
+                    url: webhook.url.clone(),
+                    events: webhook.events.clone().unwrap_or_else(|| {
+                        vec![
+                            WebhookEvent::TurnComplete,
+                            WebhookEvent::ApprovalRequested,
+                            WebhookEvent::RunFailed,
+                            WebhookEvent::BudgetExceeded,
+                        ]
+                    }),
+                    secret_env_var: webhook.secret_env_var.clone(),
+                    max_retries: webhook.max_retries.unwrap_or(DEFAULT_WEBHOOK_MAX_RETRIES),
+                })
+                .collect(),
+            scratchpad: cfg.scratchpad.clone().unwrap_or_default().into(),
+            system_prompt,
+            guardrails,
+            bench: cfg.bench.clone().unwrap_or_default().into(),
+            profiling: cfg.profiling.clone().unwrap_or_default().into(),
+            refactor: cfg.refactor.clone().unwrap_or_default().into(),
+            usage_export: cfg.usage_export.clone().unwrap_or_default().into(),
             agent_interrupt_message_enabled,
             codex_home,
             state_home,
@@ -4290,6 +4509,29 @@ impl Config {
                 .unwrap_or(true),
             tui_terminal_title: cfg.tui.as_ref().and_then(|t| t.terminal_title.clone()),
             tui_theme: cfg.tui.as_ref().and_then(|t| t.theme.clone()),
+            tui_syntax_colors: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.syntax_colors.clone())
+                .unwrap_or_default(),
+            tui_rainbow_brackets: cfg.tui.as_ref().is_some_and(|t| t.rainbow_brackets),
+            tui_diff_word_emphasis: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.diff_word_emphasis)
+                .unwrap_or(true),
+            tui_tab_width: cfg.tui.as_ref().map(|t| t.tab_width).unwrap_or(4),
+            tui_tab_width_overrides: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.tab_width_overrides.clone())
+                .unwrap_or_default(),
+            tui_diff_show_whitespace: cfg
+                .tui
+                .as_ref()
+                .is_some_and(|t| t.diff_show_whitespace),
+            tui_file_hyperlinks: cfg.tui.as_ref().is_some_and(|t| t.file_hyperlinks),
+            tui_language: cfg.tui.as_ref().and_then(|t| t.language.clone()),
             tui_pet: cfg.tui.as_ref().and_then(|t| t.pet.clone()),
             tui_pet_anchor: cfg
                 .tui
@@ -4302,6 +4544,57 @@ impl Config {
                 .and_then(|t| t.session_picker_view)
                 .unwrap_or_default(),
             tui_resume_cwd: cfg.tui.as_ref().and_then(|t| t.resume_cwd),
+            tui_approval_timeout_seconds: cfg.tui.as_ref().and_then(|t| t.approval_timeout_seconds),
+            tui_approval_timeout_action: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.approval_timeout_action)
+                .unwrap_or_default(),
+            tui_idle_pause_seconds: cfg.tui.as_ref().and_then(|t| t.idle_pause_seconds),
+            tui_expand_reasoning_summaries: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.expand_reasoning_summaries)
+                .unwrap_or(false),
+            tui_strike_completed_tasks: cfg.tui.as_ref().is_some_and(|t| t.strike_completed_tasks),
+            tui_table_overflow: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.table_overflow)
+                .unwrap_or_default(),
+            tui_render_math: cfg.tui.as_ref().map(|t| t.render_math).unwrap_or(true),
+            tui_link_destinations: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.link_destinations)
+                .unwrap_or_default(),
+            tui_code_block_max_lines: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.code_block_max_lines)
+                .unwrap_or(200),
+            tui_inline_images: cfg.tui.as_ref().is_some_and(|t| t.inline_images),
+            tui_terminal_capabilities: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.terminal_capabilities)
+                .unwrap_or_default(),
+            tui_transcript_density: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.transcript_density)
+                .unwrap_or_default(),
+            tui_transcript_timestamps: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.transcript_timestamps)
+                .unwrap_or_default(),
+            tui_transcript_auto_follow: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.transcript_auto_follow)
+                .unwrap_or(true),
+            tui_tmux_split_views: cfg.tui.as_ref().is_some_and(|t| t.tmux_split_views),
             terminal_resize_reflow,
             tui_keymap: cfg
                 .tui
@@ -4,6 +4,7 @@ use codex_network_proxy::BlockedRequestObserver;
 use codex_network_proxy::ConfigReloader;
 use codex_network_proxy::ConfigReloaderFuture;
 use codex_network_proxy::ConfigState;
+use codex_network_proxy::HostBlockDecision;
 use codex_network_proxy::NetworkDecision;
 use codex_network_proxy::NetworkPolicyDecider;
 use codex_network_proxy::NetworkProxy;
@@ -14,12 +15,15 @@ use codex_network_proxy::NetworkProxyHandle;
 use codex_network_proxy::NetworkProxyState;
 use codex_network_proxy::build_config_state;
 use codex_network_proxy::host_and_port_from_network_addr;
+use codex_network_proxy::is_non_public_ip;
 #[cfg(any(target_os = "windows", test))]
 use codex_network_proxy::managed_proxy_ports;
 use codex_network_proxy::normalize_host;
 use codex_network_proxy::validate_policy_against_constraints;
 use codex_protocol::models::PermissionProfile;
 use std::collections::HashSet;
+use std::net::IpAddr;
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -162,6 +166,60 @@ impl NetworkProxySpec {
         Ok(StartedNetworkProxy::new(proxy, handle))
     }
 
+    /// Checks `host` against the allow and deny lists the proxy would apply to a sandboxed
+    /// command. Every host is allowed when the proxy is disabled.
+    pub async fn host_block_decision(
+        &self,
+        host: &str,
+        port: u16,
+    ) -> std::io::Result<HostBlockDecision> {
+        if !self.enabled() {
+            return Ok(HostBlockDecision::Allowed);
+        }
+        let state = self.build_state_with_audit_metadata(NetworkProxyAuditMetadata::default())?;
+        state
+            .host_blocked(host, port)
+            .await
+            .map_err(|err| std::io::Error::other(format!("failed to check network policy: {err}")))
+    }
+
+    /// Whether the network mode lets HTTP requests with `method` through, as the proxy decides
+    /// for sandboxed commands. Limited mode only allows GET, HEAD, and OPTIONS.
+    pub fn allows_method(&self, method: &str) -> bool {
+        !self.enabled() || self.config.mode.allows_method(method)
+    }
+
+    /// Resolves `domain` for a connection made without going through the proxy. The addresses
+    /// are checked the way the proxy checks them, so a caller that connects only to them cannot
+    /// be sent elsewhere by a later DNS answer. A domain that resolves to a non-public address is
+    /// refused unless local binding is allowed or it is `localhost`, which the allow list gates.
+    pub async fn resolve_for_direct_connection(
+        &self,
+        domain: &str,
+        port: u16,
+    ) -> std::io::Result<Vec<SocketAddr>> {
+        let addrs = tokio::net::lookup_host((domain, port))
+            .await?
+            .collect::<Vec<_>>();
+        if addrs.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{domain} did not resolve to any address"),
+            ));
+        }
+        let local_allowed = !self.enabled()
+            || self.config.allow_local_binding
+            || domain.eq_ignore_ascii_case("localhost")
+            || domain.parse::<IpAddr>().is_ok();
+        if !local_allowed && addrs.iter().any(|addr| is_non_public_ip(addr.ip())) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!("{domain} resolves to a non-public address"),
+            ));
+        }
+        Ok(addrs)
+    }
+
     pub(crate) fn recompute_for_permission_profile(
         &self,
         permission_profile: &PermissionProfile,
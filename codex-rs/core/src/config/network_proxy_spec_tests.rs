@@ -441,3 +441,57 @@ fn requirements_denylist_expansion_keeps_user_entries_mutable() {
     validate_policy_against_constraints(&candidate, &spec.constraints)
         .expect("user denylist entries should not become managed constraints");
 }
+
+fn spec_with(config: NetworkProxyConfig) -> NetworkProxySpec {
+    NetworkProxySpec {
+        base_config: config.clone(),
+        requirements: None,
+        config,
+        constraints: NetworkProxyConstraints::default(),
+        hard_deny_allowlist_misses: false,
+    }
+}
+
+#[test]
+fn limited_mode_only_allows_read_methods() {
+    let mut config = NetworkProxyConfig {
+        enabled: true,
+        mode: codex_network_proxy::NetworkMode::Limited,
+        ..NetworkProxyConfig::default()
+    };
+    let limited = spec_with(config.clone());
+    assert_eq!(
+        ["GET", "HEAD", "OPTIONS", "POST", "PUT", "DELETE"]
+            .map(|method| limited.allows_method(method)),
+        [true, true, true, false, false, false]
+    );
+
+    config.enabled = false;
+    assert!(spec_with(config).allows_method("POST"));
+}
+
+#[tokio::test]
+async fn direct_connections_refuse_domains_that_resolve_to_private_addresses() {
+    let mut config = NetworkProxyConfig {
+        enabled: true,
+        ..NetworkProxyConfig::default()
+    };
+    // `localhost` is gated by the allow list rather than by resolution, so use a name that
+    // resolves to loopback without being spelled `localhost`.
+    let spec = spec_with(config.clone());
+    let err = spec
+        .resolve_for_direct_connection("localhost.", 80)
+        .await
+        .expect_err("loopback resolution should be refused");
+    assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+
+    config.allow_local_binding = true;
+    let addrs = spec_with(config)
+        .resolve_for_direct_connection("localhost.", 80)
+        .await
+        .expect("local binding allows loopback");
+    assert!(
+        addrs.iter().all(|addr| addr.ip().is_loopback()),
+        "{addrs:?}"
+    );
+}
@@ -299,6 +299,21 @@ impl PermissionProfileState {
             .set(ResolvedPermissionProfile::legacy(permission_profile))
     }
 
+    /// Replace the concrete profile while keeping the active profile id and profile workspace
+    /// roots, for runtime adjustments that do not change which profile is selected.
+    pub(crate) fn set_permission_profile_keeping_identity(
+        &mut self,
+        permission_profile: PermissionProfile,
+    ) -> ConstraintResult<()> {
+        let current = self.resolved_permission_profile.get();
+        let candidate = ResolvedPermissionProfile::from_active_profile(
+            permission_profile,
+            current.active_permission_profile(),
+            current.profile_workspace_roots().to_vec(),
+        );
+        self.resolved_permission_profile.set(candidate)
+    }
+
     pub(crate) fn set_permission_profile_snapshot(
         &mut self,
         snapshot: PermissionProfileSnapshot,
@@ -0,0 +1,27 @@
+use super::ContextualUserFragment;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GuardrailViolations {
+    pub(crate) violations: String,
+}
+
+impl ContextualUserFragment for GuardrailViolations {
+    fn role(&self) -> &'static str {
+        "developer"
+    }
+
+    fn markers(&self) -> (&'static str, &'static str) {
+        Self::type_markers()
+    }
+
+    fn type_markers() -> (&'static str, &'static str) {
+        ("<guardrail_violations>\n", "\n</guardrail_violations>")
+    }
+
+    fn body(&self) -> String {
+        format!(
+            "Files you changed this turn break the project's guardrail rules. Fix each violation below before finishing; do not work around a rule by disabling the check.\n\n{}",
+            self.violations.trim_end()
+        )
+    }
+}
@@ -9,6 +9,7 @@ mod current_time_reminder;
 mod environment_context;
 mod environments_instructions;
 mod guardian_followup_review_reminder;
+mod guardrail_violations;
 mod hook_additional_context;
 mod inter_agent_completion_message;
 mod internal_model_context;
@@ -26,12 +27,15 @@ mod realtime_end_instructions;
 mod realtime_start_instructions;
 mod realtime_start_with_instructions;
 mod recommended_plugins_instructions;
+mod response_language_instructions;
 mod rollout_budget;
+mod scratchpad_instructions;
 mod subagent_notification;
 mod token_budget_context;
 mod turn_aborted;
 mod user_instructions;
 mod user_shell_command;
+mod workspace_changes;
 pub(crate) mod world_state;
 
 pub(crate) use approved_command_prefix_saved::ApprovedCommandPrefixSaved;
@@ -49,6 +53,7 @@ pub(crate) use contextual_user_message::parse_visible_hook_prompt_message;
 pub(crate) use current_time_reminder::CurrentTimeReminder;
 pub(crate) use environments_instructions::EnvironmentsInstructions;
 pub(crate) use guardian_followup_review_reminder::GuardianFollowupReviewReminder;
+pub(crate) use guardrail_violations::GuardrailViolations;
 pub(crate) use hook_additional_context::HookAdditionalContext;
 pub(crate) use inter_agent_completion_message::InterAgentCompletionMessage;
 pub use internal_model_context::InternalContextSource;
@@ -70,7 +75,9 @@ pub(crate) use realtime_end_instructions::RealtimeEndInstructions;
 pub(crate) use realtime_start_instructions::RealtimeStartInstructions;
 pub(crate) use realtime_start_with_instructions::RealtimeStartWithInstructions;
 pub(crate) use recommended_plugins_instructions::RecommendedPluginsInstructions;
+pub(crate) use response_language_instructions::ResponseLanguageInstructions;
 pub(crate) use rollout_budget::RolloutBudgetContext;
+pub(crate) use scratchpad_instructions::ScratchpadInstructions;
 pub(crate) use subagent_notification::SubagentNotification;
 pub(crate) use token_budget_context::AutoCompactFallbackPrompt;
 pub(crate) use token_budget_context::ContextWindowGuidance;
@@ -80,3 +87,4 @@ pub(crate) use token_budget_context::TokenBudgetReminder;
 pub(crate) use turn_aborted::TurnAborted;
 pub(crate) use user_instructions::UserInstructions;
 pub(crate) use user_shell_command::UserShellCommand;
+pub(crate) use workspace_changes::WorkspaceChanges;
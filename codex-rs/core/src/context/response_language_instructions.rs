@@ -0,0 +1,35 @@
+use super::ContextualUserFragment;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ResponseLanguageInstructions {
+    language: String,
+}
+
+impl ResponseLanguageInstructions {
+    pub(crate) fn new(language: impl Into<String>) -> Self {
+        Self {
+            language: language.into(),
+        }
+    }
+}
+
+impl ContextualUserFragment for ResponseLanguageInstructions {
+    fn role(&self) -> &'static str {
+        "developer"
+    }
+
+    fn markers(&self) -> (&'static str, &'static str) {
+        Self::type_markers()
+    }
+
+    fn type_markers() -> (&'static str, &'static str) {
+        ("<response_language>\n", "\n</response_language>")
+    }
+
+    fn body(&self) -> String {
+        format!(
+            "The user prefers replies in {}. Write your messages to the user, including plans and final answers, in that language even when they write in another one. Keep code, identifiers, commands, file paths, and quoted output as they are.",
+            self.language
+        )
+    }
+}
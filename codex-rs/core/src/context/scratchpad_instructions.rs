@@ -0,0 +1,44 @@
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use super::ContextualUserFragment;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ScratchpadInstructions {
+    path: AbsolutePathBuf,
+    keep_artifacts: bool,
+}
+
+impl ScratchpadInstructions {
+    pub(crate) fn new(path: AbsolutePathBuf, keep_artifacts: bool) -> Self {
+        Self {
+            path,
+            keep_artifacts,
+        }
+    }
+}
+
+impl ContextualUserFragment for ScratchpadInstructions {
+    fn role(&self) -> &'static str {
+        "developer"
+    }
+
+    fn markers(&self) -> (&'static str, &'static str) {
+        Self::type_markers()
+    }
+
+    fn type_markers() -> (&'static str, &'static str) {
+        ("<scratchpad>\n", "\n</scratchpad>")
+    }
+
+    fn body(&self) -> String {
+        let lifetime = if self.keep_artifacts {
+            "It is kept after the session ends."
+        } else {
+            "It is deleted when the session ends."
+        };
+        format!(
+            "This session has a scratch directory at `{}`, also available in shell commands as `$CODEX_SCRATCH_DIR`. Commands can always write there, whatever the sandbox policy. Put throwaway scripts, experiment output, and other temporary files there instead of in the workspace. {lifetime}",
+            self.path.display()
+        )
+    }
+}
@@ -0,0 +1,27 @@
+use super::ContextualUserFragment;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WorkspaceChanges {
+    pub(crate) changes: String,
+}
+
+impl ContextualUserFragment for WorkspaceChanges {
+    fn role(&self) -> &'static str {
+        "developer"
+    }
+
+    fn markers(&self) -> (&'static str, &'static str) {
+        Self::type_markers()
+    }
+
+    fn type_markers() -> (&'static str, &'static str) {
+        ("<workspace_changes>\n", "\n</workspace_changes>")
+    }
+
+    fn body(&self) -> String {
+        format!(
+            "This session was resumed after the workspace changed. File contents and command output earlier in this conversation may be out of date; trust the current state below and re-read files before editing them.\n\n{}",
+            self.changes.trim_end()
+        )
+    }
+}
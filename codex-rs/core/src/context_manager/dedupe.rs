@@ -0,0 +1,124 @@
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ResponseItem;
+use codex_utils_cache::sha1_digest;
+use codex_utils_output_truncation::approx_token_count;
+use std::collections::HashMap;
+
+/// Outputs smaller than this are cheaper to keep than to replace with a stub.
+const MIN_DEDUPE_OUTPUT_TOKENS: usize = 64;
+
+/// Header lines that differ between otherwise identical reruns.
+const VOLATILE_LINE_PREFIXES: [&str; 2] = ["Wall time", "Chunk ID: "];
+
+/// Outputs replaced by stubs in one deduplication pass over prompt history.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct DedupedToolOutputs {
+    pub(crate) removed: Vec<DedupedToolOutput>,
+}
+
+/// One tool output replaced by a stub.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DedupedToolOutput {
+    pub(crate) call_id: String,
+    pub(crate) tokens_saved: i64,
+}
+
+/// Replaces earlier copies of repeated tool outputs with a stub pointing at the latest copy.
+///
+/// Two outputs are repeats when the same tool was called with the same arguments and returned the
+/// same text, ignoring timing headers. The latest copy is always kept in full.
+pub(crate) fn dedupe_repeated_tool_outputs(items: &mut [ResponseItem]) -> DedupedToolOutputs {
+    let call_signatures = items
+        .iter()
+        .filter_map(|item| match item {
+            ResponseItem::FunctionCall {
+                name,
+                namespace,
+                arguments,
+                call_id,
+                ..
+            } => Some((
+                call_id.clone(),
+                tool_signature(namespace.as_deref(), name, arguments),
+            )),
+            ResponseItem::CustomToolCall {
+                name,
+                namespace,
+                input,
+                call_id,
+                ..
+            } => Some((
+                call_id.clone(),
+                tool_signature(namespace.as_deref(), name, input),
+            )),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut latest_copies: HashMap<(String, [u8; 20]), String> = HashMap::new();
+    let mut deduped = DedupedToolOutputs::default();
+    for item in items.iter_mut().rev() {
+        let (call_id, output) = match item {
+            ResponseItem::FunctionCallOutput {
+                call_id, output, ..
+            }
+            | ResponseItem::CustomToolCallOutput {
+                call_id, output, ..
+            } => (call_id, output),
+            _ => continue,
+        };
+        let Some(signature) = call_signatures.get(call_id.as_str()) else {
+            continue;
+        };
+        let FunctionCallOutputBody::Text(text) = &output.body else {
+            continue;
+        };
+        let original_tokens = approx_token_count(text);
+        if original_tokens < MIN_DEDUPE_OUTPUT_TOKENS {
+            continue;
+        }
+
+        let key = (
+            signature.clone(),
+            sha1_digest(stable_output(text).as_bytes()),
+        );
+        let Some(latest_call_id) = latest_copies.get(&key) else {
+            latest_copies.insert(key, call_id.clone());
+            continue;
+        };
+        let stub = format!(
+            "[Duplicate output omitted: identical to the later output of call `{latest_call_id}`.]"
+        );
+        deduped.removed.push(DedupedToolOutput {
+            call_id: call_id.clone(),
+            tokens_saved: i64::try_from(original_tokens.saturating_sub(approx_token_count(&stub)))
+                .unwrap_or(i64::MAX),
+        });
+        *output = FunctionCallOutputPayload {
+            body: FunctionCallOutputBody::Text(stub),
+            success: output.success,
+        };
+    }
+    deduped
+}
+
+fn tool_signature(namespace: Option<&str>, name: &str, arguments: &str) -> String {
+    let namespace = namespace.unwrap_or_default();
+    format!("{namespace}\u{0}{name}\u{0}{arguments}")
+}
+
+fn stable_output(text: &str) -> String {
+    text.lines()
+        .filter(|line| {
+            !VOLATILE_LINE_PREFIXES
+                .iter()
+                .any(|prefix| line.starts_with(prefix))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+#[path = "dedupe_tests.rs"]
+mod tests;
@@ -0,0 +1,106 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn function_call(call_id: &str, arguments: &str) -> ResponseItem {
+    ResponseItem::FunctionCall {
+        id: None,
+        name: "exec_command".to_string(),
+        namespace: None,
+        arguments: arguments.to_string(),
+        call_id: call_id.to_string(),
+        internal_chat_message_metadata_passthrough: None,
+    }
+}
+
+fn function_call_output(call_id: &str, text: &str) -> ResponseItem {
+    ResponseItem::FunctionCallOutput {
+        id: None,
+        call_id: call_id.to_string(),
+        output: FunctionCallOutputPayload {
+            body: FunctionCallOutputBody::Text(text.to_string()),
+            success: Some(false),
+        },
+        internal_chat_message_metadata_passthrough: None,
+    }
+}
+
+fn exec_output(wall_time: &str, body: &str) -> String {
+    format!("Wall time: {wall_time} seconds\nProcess exited with code 1\nOutput:\n{body}")
+}
+
+#[test]
+fn replaces_earlier_repeats_with_stub_to_latest_copy() {
+    let failure = "error[E0308]: mismatched types in src/lib.rs\n".repeat(20);
+    let mut items = vec![
+        function_call("call-1", r#"{"cmd":"cargo test"}"#),
+        function_call_output("call-1", &exec_output("1.2000", &failure)),
+        function_call("call-2", r#"{"cmd":"cargo test"}"#),
+        function_call_output("call-2", &exec_output("0.9000", &failure)),
+        function_call("call-3", r#"{"cmd":"cargo test"}"#),
+        function_call_output("call-3", &exec_output("1.1000", &failure)),
+    ];
+    let latest = items[5].clone();
+
+    let deduped = dedupe_repeated_tool_outputs(&mut items);
+
+    assert_eq!(
+        vec!["call-2", "call-1"],
+        deduped
+            .removed
+            .iter()
+            .map(|output| output.call_id.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert!(deduped.removed.iter().all(|output| output.tokens_saved > 0));
+    let stub = function_call_output(
+        "call-1",
+        "[Duplicate output omitted: identical to the later output of call `call-3`.]",
+    );
+    assert_eq!(stub, items[1]);
+    assert_eq!(
+        function_call_output(
+            "call-2",
+            "[Duplicate output omitted: identical to the later output of call `call-3`.]",
+        ),
+        items[3]
+    );
+    assert_eq!(latest, items[5]);
+}
+
+#[test]
+fn keeps_outputs_that_differ_or_come_from_different_calls() {
+    let before = "fn main() {}\n".repeat(40);
+    let after = "fn main() { run(); }\n".repeat(40);
+    let mut items = vec![
+        function_call("call-1", r#"{"cmd":"cat src/main.rs"}"#),
+        function_call_output("call-1", &before),
+        function_call("call-2", r#"{"cmd":"cat src/main.rs"}"#),
+        function_call_output("call-2", &after),
+        function_call("call-3", r#"{"cmd":"cat src/other.rs"}"#),
+        function_call_output("call-3", &after),
+    ];
+    let expected = items.clone();
+
+    assert_eq!(
+        DedupedToolOutputs::default(),
+        dedupe_repeated_tool_outputs(&mut items)
+    );
+    assert_eq!(expected, items);
+}
+
+#[test]
+fn keeps_small_repeated_outputs() {
+    let mut items = vec![
+        function_call("call-1", r#"{"cmd":"git status"}"#),
+        function_call_output("call-1", "nothing to commit"),
+        function_call("call-2", r#"{"cmd":"git status"}"#),
+        function_call_output("call-2", "nothing to commit"),
+    ];
+    let expected = items.clone();
+
+    assert_eq!(
+        DedupedToolOutputs::default(),
+        dedupe_repeated_tool_outputs(&mut items)
+    );
+    assert_eq!(expected, items);
+}
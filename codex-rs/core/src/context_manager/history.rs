@@ -165,7 +165,10 @@ impl ContextManager {
         let model_info = &turn_context.model_info;
         let personality = turn_context.personality.or(turn_context.config.personality);
         let base_instructions = BaseInstructions {
-            text: model_info.get_model_instructions(personality),
+            text: crate::system_prompt::apply(
+                &model_info.get_model_instructions(personality),
+                &turn_context.config.system_prompt,
+            ),
         };
         self.estimate_token_count_with_base_instructions(&base_instructions)
     }
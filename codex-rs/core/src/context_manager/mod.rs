@@ -1,7 +1,10 @@
+mod dedupe;
 mod history;
 mod normalize;
 pub(crate) mod updates;
 
+pub(crate) use dedupe::DedupedToolOutput;
+pub(crate) use dedupe::dedupe_repeated_tool_outputs;
 pub(crate) use history::ContextManager;
 pub(crate) use history::estimate_item_token_count;
 pub(crate) use history::is_user_turn_boundary;
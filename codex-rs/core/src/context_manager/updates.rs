@@ -76,7 +76,10 @@ pub(crate) fn build_model_instructions_update_item(
         return None;
     }
 
-    let model_instructions = next.model_info.get_model_instructions(next.personality);
+    let model_instructions = crate::system_prompt::apply(
+        &next.model_info.get_model_instructions(next.personality),
+        &next.config.system_prompt,
+    );
     if model_instructions.is_empty() {
         return None;
     }
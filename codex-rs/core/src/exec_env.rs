@@ -12,6 +12,9 @@ pub use codex_protocol::shell_environment::CODEX_THREAD_ID_ENV_VAR;
 /// overwrite this value, so it must not be treated as proof of enforcement.
 pub const CODEX_PERMISSION_PROFILE_ENV_VAR: &str = "CODEX_PERMISSION_PROFILE";
 
+/// Path of the session's scratch directory, set when the `scratchpad` feature is enabled.
+pub const CODEX_SCRATCH_DIR_ENV_VAR: &str = "CODEX_SCRATCH_DIR";
+
 /// Construct an environment map based on the rules in the specified policy. The
 /// resulting map can be passed directly to `Command::envs()` after calling
 /// `env_clear()` to ensure no unintended variables are leaked to the spawned
@@ -13,6 +13,7 @@ use codex_git_utils::GitInfo;
 use codex_git_utils::GitSha;
 use codex_git_utils::collect_git_info;
 use codex_git_utils::get_has_changes;
+use codex_git_utils::get_status_entries;
 use codex_git_utils::git_diff_to_remote;
 use codex_git_utils::recent_commits;
 use codex_git_utils::resolve_root_git_project_for_trust;
@@ -453,6 +454,23 @@ async fn test_get_has_changes_with_untracked_change_returns_true() {
     assert_eq!(get_has_changes(&repo_path).await, Some(true));
 }
 
+#[tokio::test]
+async fn test_get_status_entries_lists_tracked_and_untracked_changes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let repo_path = create_test_git_repo(&temp_dir).await;
+    assert_eq!(get_status_entries(&repo_path).await, Some(Vec::new()));
+
+    fs::write(repo_path.join("test.txt"), "updated tracked file").expect("write tracked file");
+    fs::write(repo_path.join("new_file.txt"), "untracked").expect("write untracked file");
+    assert_eq!(
+        get_status_entries(&repo_path).await,
+        Some(vec![
+            " M test.txt".to_string(),
+            "?? new_file.txt".to_string(),
+        ])
+    );
+}
+
 #[cfg(unix)]
 #[tokio::test]
 async fn test_get_has_changes_ignores_configured_hooks_path() {
@@ -1021,6 +1021,7 @@ pub(crate) fn build_guardian_review_session_config(
         policy_template,
     ));
     guardian_config.notify = None;
+    guardian_config.webhooks.clear();
     guardian_config.developer_instructions = None;
     guardian_config.permissions.approval_policy = Constrained::allow_only(AskForApproval::Never);
     guardian_config
@@ -0,0 +1,238 @@
+//! Checks on code the agent writes, from `[guardrails]`.
+//!
+//! When the model is about to end a turn, the files `apply_patch` changed
+//! during the turn are checked: new files for required license headers and
+//! placement, and added lines for banned patterns.  Violations go back to the
+//! model so it can fix them before the turn completes.
+
+use std::collections::HashSet;
+
+use codex_config::types::DEFAULT_GUARDRAILS_MAX_ROUNDS;
+use codex_config::types::GuardrailsConfig;
+use codex_config::types::GuardrailsToml;
+use globset::GlobBuilder;
+use globset::GlobSet;
+use globset::GlobSetBuilder;
+use regex_lite::Regex;
+
+use crate::turn_diff_tracker::ChangedFile;
+
+/// Lines after the header's own length that may precede it, such as a
+/// shebang or an encoding declaration.
+const HEADER_SEARCH_SLACK_LINES: usize = 5;
+
+/// Violations listed for the model at once; the rest are counted.
+const MAX_REPORTED_VIOLATIONS: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Violation {
+    pub(crate) rule: GuardrailRule,
+    pub(crate) path: String,
+    /// 1-based line in the file's current content.
+    pub(crate) line: Option<usize>,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GuardrailRule {
+    LicenseHeader,
+    Banned,
+    Placement,
+}
+
+impl GuardrailRule {
+    fn name(self) -> &'static str {
+        match self {
+            GuardrailRule::LicenseHeader => "license_header",
+            GuardrailRule::Banned => "banned",
+            GuardrailRule::Placement => "placement",
+        }
+    }
+}
+
+pub(crate) fn resolve_config(
+    config: GuardrailsToml,
+    startup_warnings: &mut Vec<String>,
+) -> GuardrailsConfig {
+    let mut warn = |rule: &str, index: usize, err: String| {
+        startup_warnings.push(format!(
+            "Ignoring `guardrails.{rule}` rule {}: {err}",
+            index + 1
+        ));
+    };
+
+    let mut license_headers = Vec::new();
+    for (index, rule) in config.license_headers.into_iter().enumerate() {
+        if rule.header.trim().is_empty() {
+            warn("license_headers", index, "`header` is empty".to_string());
+        } else if let Err(err) = glob_set(&rule.paths) {
+            warn("license_headers", index, err);
+        } else {
+            license_headers.push(rule);
+        }
+    }
+
+    let mut banned = Vec::new();
+    for (index, rule) in config.banned.into_iter().enumerate() {
+        if let Err(err) = Regex::new(&rule.pattern) {
+            warn("banned", index, format!("invalid `pattern`: {err}"));
+        } else if let Some(Err(err)) = rule.paths.as_deref().map(glob_set) {
+            warn("banned", index, err);
+        } else {
+            banned.push(rule);
+        }
+    }
+
+    let mut placement = Vec::new();
+    for (index, rule) in config.placement.into_iter().enumerate() {
+        if let Err(err) = glob_set(&rule.paths).and_then(|_| glob_set(&rule.allowed)) {
+            warn("placement", index, err);
+        } else {
+            placement.push(rule);
+        }
+    }
+
+    GuardrailsConfig {
+        max_rounds: config.max_rounds.unwrap_or(DEFAULT_GUARDRAILS_MAX_ROUNDS),
+        license_headers,
+        banned,
+        placement,
+    }
+}
+
+/// Checks the files changed in a turn against the configured rules.
+pub(crate) fn check(config: &GuardrailsConfig, files: &[ChangedFile<'_>]) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    for rule in &config.license_headers {
+        let Ok(paths) = glob_set(&rule.paths) else {
+            continue;
+        };
+        let header = rule.header.trim();
+        let search_lines = header.lines().count() + HEADER_SEARCH_SLACK_LINES;
+        for file in files {
+            // Only files the agent created; existing files keep whatever header they had.
+            if file.previous.is_some() || !paths.is_match(&file.path) {
+                continue;
+            }
+            let head = file
+                .content
+                .lines()
+                .take(search_lines)
+                .collect::<Vec<_>>()
+                .join("\n");
+            if !head.contains(header) {
+                violations.push(Violation {
+                    rule: GuardrailRule::LicenseHeader,
+                    path: file.path.clone(),
+                    line: None,
+                    message: format!(
+                        "new file is missing the required header:\n```\n{header}\n```"
+                    ),
+                });
+            }
+        }
+    }
+
+    for rule in &config.banned {
+        let Ok(pattern) = Regex::new(&rule.pattern) else {
+            continue;
+        };
+        let paths = match rule.paths.as_deref().map(glob_set) {
+            Some(Ok(paths)) => Some(paths),
+            Some(Err(_)) => continue,
+            None => None,
+        };
+        for file in files {
+            if paths
+                .as_ref()
+                .is_some_and(|paths| !paths.is_match(&file.path))
+            {
+                continue;
+            }
+            let previous_lines = file
+                .previous
+                .map(|previous| previous.lines().collect::<HashSet<_>>())
+                .unwrap_or_default();
+            for (index, line) in file.content.lines().enumerate() {
+                if previous_lines.contains(line) || !pattern.is_match(line) {
+                    continue;
+                }
+                let mut message = format!("added line matches banned pattern `{}`", rule.pattern);
+                if let Some(explanation) = &rule.message {
+                    message.push_str(": ");
+                    message.push_str(explanation);
+                }
+                violations.push(Violation {
+                    rule: GuardrailRule::Banned,
+                    path: file.path.clone(),
+                    line: Some(index + 1),
+                    message,
+                });
+            }
+        }
+    }
+
+    for rule in &config.placement {
+        let (Ok(paths), Ok(allowed)) = (glob_set(&rule.paths), glob_set(&rule.allowed)) else {
+            continue;
+        };
+        for file in files {
+            if !file.is_new_path || !paths.is_match(&file.path) || allowed.is_match(&file.path) {
+                continue;
+            }
+            let message = rule.message.clone().unwrap_or_else(|| {
+                format!("files like this belong under {}", rule.allowed.join(", "))
+            });
+            violations.push(Violation {
+                rule: GuardrailRule::Placement,
+                path: file.path.clone(),
+                line: None,
+                message,
+            });
+        }
+    }
+
+    violations
+}
+
+/// Renders violations as the list the model is asked to fix.
+pub(crate) fn render_violations(violations: &[Violation]) -> String {
+    let mut text = String::new();
+    for violation in violations.iter().take(MAX_REPORTED_VIOLATIONS) {
+        let location = match violation.line {
+            Some(line) => format!("{}:{line}", violation.path),
+            None => violation.path.clone(),
+        };
+        text.push_str(&format!(
+            "- [{}] {location}: {}\n",
+            violation.rule.name(),
+            violation.message
+        ));
+    }
+    if violations.len() > MAX_REPORTED_VIOLATIONS {
+        text.push_str(&format!(
+            "- ...and {} more\n",
+            violations.len() - MAX_REPORTED_VIOLATIONS
+        ));
+    }
+    text
+}
+
+/// Globs match paths relative to the workspace root; `*` stays within one
+/// directory and `**` spans any number.
+fn glob_set(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|err| format!("invalid glob `{pattern}`: {err}"))?;
+        builder.add(glob);
+    }
+    builder.build().map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+#[path = "guardrails_tests.rs"]
+mod tests;
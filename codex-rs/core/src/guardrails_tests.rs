@@ -0,0 +1,174 @@
+use super::*;
+use codex_config::types::GuardrailBannedPatternToml;
+use codex_config::types::GuardrailLicenseHeaderToml;
+use codex_config::types::GuardrailPlacementToml;
+use pretty_assertions::assert_eq;
+
+const HEADER: &str = "// Copyright Example Corp.\n// SPDX-License-Identifier: MIT";
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn file<'a>(path: &str, previous: Option<&'a str>, content: &'a str) -> ChangedFile<'a> {
+    ChangedFile {
+        path: path.to_string(),
+        previous,
+        content,
+        is_new_path: previous.is_none(),
+    }
+}
+
+fn config() -> GuardrailsConfig {
+    GuardrailsConfig {
+        max_rounds: DEFAULT_GUARDRAILS_MAX_ROUNDS,
+        license_headers: vec![GuardrailLicenseHeaderToml {
+            paths: strings(&["src/**/*.rs"]),
+            header: HEADER.to_string(),
+        }],
+        banned: vec![GuardrailBannedPatternToml {
+            pattern: r"\.unwrap\(\)".to_string(),
+            paths: Some(strings(&["**/*.rs"])),
+            message: Some("return an error instead".to_string()),
+        }],
+        placement: vec![GuardrailPlacementToml {
+            paths: strings(&["**/*.sql"]),
+            allowed: strings(&["migrations/**"]),
+            message: None,
+        }],
+    }
+}
+
+#[test]
+fn new_file_without_header_is_reported() {
+    let with_header = format!("{HEADER}\n\nfn ok() {{}}\n");
+    let files = [
+        file("src/lib.rs", None, "fn main() {}\n"),
+        file("src/ok.rs", None, &with_header),
+    ];
+
+    assert_eq!(
+        check(&config(), &files),
+        vec![Violation {
+            rule: GuardrailRule::LicenseHeader,
+            path: "src/lib.rs".to_string(),
+            line: None,
+            message: format!("new file is missing the required header:\n```\n{HEADER}\n```"),
+        }]
+    );
+}
+
+#[test]
+fn header_may_follow_a_shebang() {
+    let content = format!("#!/usr/bin/env run-rust\n{HEADER}\nfn main() {{}}\n");
+    let files = [file("src/bin/tool.rs", None, &content)];
+
+    assert_eq!(check(&config(), &files), Vec::new());
+}
+
+#[test]
+fn modified_files_are_not_checked_for_headers() {
+    let files = [file("src/lib.rs", Some("fn old() {}\n"), "fn new() {}\n")];
+
+    assert_eq!(check(&config(), &files), Vec::new());
+}
+
+#[test]
+fn banned_pattern_only_matches_added_lines() {
+    let previous = "let a = x.unwrap();\n";
+    let content = "let a = x.unwrap();\nlet b = y.unwrap();\n";
+    let files = [file("tests/io.rs", Some(previous), content)];
+
+    assert_eq!(
+        check(&config(), &files),
+        vec![Violation {
+            rule: GuardrailRule::Banned,
+            path: "tests/io.rs".to_string(),
+            line: Some(2),
+            message: r"added line matches banned pattern `\.unwrap\(\)`: return an error instead"
+                .to_string(),
+        }]
+    );
+}
+
+#[test]
+fn new_file_outside_allowed_directories_is_misplaced() {
+    let files = [
+        file("db/001_init.sql", None, "create table t();\n"),
+        file("migrations/001_init.sql", None, "create table t();\n"),
+    ];
+
+    assert_eq!(
+        check(&config(), &files),
+        vec![Violation {
+            rule: GuardrailRule::Placement,
+            path: "db/001_init.sql".to_string(),
+            line: None,
+            message: "files like this belong under migrations/**".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn resolve_config_drops_invalid_rules_with_warnings() {
+    let mut warnings = Vec::new();
+    let resolved = resolve_config(
+        GuardrailsToml {
+            max_rounds: None,
+            license_headers: vec![GuardrailLicenseHeaderToml {
+                paths: strings(&["src/**"]),
+                header: "  ".to_string(),
+            }],
+            banned: vec![
+                GuardrailBannedPatternToml {
+                    pattern: "(".to_string(),
+                    paths: None,
+                    message: None,
+                },
+                GuardrailBannedPatternToml {
+                    pattern: "todo!".to_string(),
+                    paths: None,
+                    message: None,
+                },
+            ],
+            placement: vec![GuardrailPlacementToml {
+                paths: strings(&["a[b"]),
+                allowed: strings(&["x/**"]),
+                message: None,
+            }],
+        },
+        &mut warnings,
+    );
+
+    assert_eq!(resolved.max_rounds, DEFAULT_GUARDRAILS_MAX_ROUNDS);
+    assert!(resolved.license_headers.is_empty());
+    assert_eq!(resolved.banned.len(), 1);
+    assert!(resolved.placement.is_empty());
+    assert_eq!(warnings.len(), 3, "{warnings:?}");
+    assert!(warnings[0].starts_with("Ignoring `guardrails.license_headers` rule 1"));
+    assert!(warnings[1].starts_with("Ignoring `guardrails.banned` rule 1"));
+    assert!(warnings[2].starts_with("Ignoring `guardrails.placement` rule 1"));
+}
+
+#[test]
+fn render_violations_lists_locations_and_rules() {
+    let violations = vec![
+        Violation {
+            rule: GuardrailRule::Banned,
+            path: "src/io.rs".to_string(),
+            line: Some(4),
+            message: "no unwrap".to_string(),
+        },
+        Violation {
+            rule: GuardrailRule::Placement,
+            path: "db/init.sql".to_string(),
+            line: None,
+            message: "use migrations/".to_string(),
+        },
+    ];
+
+    assert_eq!(
+        render_violations(&violations),
+        "- [banned] src/io.rs:4: no unwrap\n- [placement] db/init.sql: use migrations/\n"
+    );
+}
@@ -50,6 +50,7 @@ mod exec_policy;
 #[cfg(test)]
 mod git_info_tests;
 mod guardian;
+mod guardrails;
 mod hook_runtime;
 mod image_preparation;
 mod installation_id;
@@ -107,7 +108,9 @@ mod event_mapping;
 pub use codex_prompts as review_prompts;
 mod thread_manager;
 pub(crate) mod web_search;
+mod webhooks;
 pub(crate) mod windows_sandbox_read_grants;
+mod workspace_reconciliation;
 pub use thread_manager::ForkSnapshot;
 pub use thread_manager::NewThread;
 pub use thread_manager::StartThreadOptions;
@@ -132,11 +135,13 @@ pub use agents_md::LoadedAgentsMd;
 mod rollout;
 mod rollout_budget;
 pub(crate) mod safety;
+mod scratchpad;
 mod session_rollout_init_error;
 pub mod shell;
 pub(crate) mod shell_snapshot;
 pub mod spawn;
 pub(crate) mod state_db_bridge;
+mod system_prompt;
 pub use state_db_bridge::StateDbHandle;
 pub use state_db_bridge::init_state_db;
 mod thread_rollout_truncation;
@@ -0,0 +1,90 @@
+//! Per-session scratch directory for throwaway files, so experiments stay out of the workspace.
+//!
+//! The directory lives under `$CODEX_HOME/scratch/<thread_id>`. Every turn grants sandboxed
+//! commands write access to it, whatever the permission profile, and shell tools see its path in
+//! `CODEX_SCRATCH_DIR`. It is deleted when the session ends unless `[scratchpad] keep_artifacts`
+//! is set.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use codex_protocol::ThreadId;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_session_artifacts::scratchpad_dir;
+
+use crate::config::Permissions;
+use crate::exec_env::CODEX_SCRATCH_DIR_ENV_VAR;
+
+pub(crate) struct Scratchpad {
+    path: AbsolutePathBuf,
+    keep_artifacts: bool,
+}
+
+impl Scratchpad {
+    /// Creates the session's directory, or returns `None` when it cannot be created.
+    pub(crate) fn create(
+        codex_home: &AbsolutePathBuf,
+        thread_id: ThreadId,
+        keep_artifacts: bool,
+    ) -> Option<Self> {
+        let path = scratchpad_dir(codex_home.as_path(), &thread_id.to_string());
+        if let Err(err) = std::fs::create_dir_all(&path) {
+            tracing::warn!("Failed to create scratchpad at {}: {err}", path.display());
+            return None;
+        }
+        // `codex_home` is absolute, so this cannot fail.
+        let path = AbsolutePathBuf::try_from(path).ok()?;
+        Some(Self {
+            path,
+            keep_artifacts,
+        })
+    }
+
+    pub(crate) fn path(&self) -> &AbsolutePathBuf {
+        &self.path
+    }
+
+    pub(crate) fn keep_artifacts(&self) -> bool {
+        self.keep_artifacts
+    }
+
+    /// Adds the directory to the writable roots of a turn's permissions.
+    pub(crate) fn grant_write_access(&self, permissions: &mut Permissions, cwd: &Path) {
+        if let Err(err) =
+            permissions.add_runtime_writable_roots(cwd, std::slice::from_ref(&self.path))
+        {
+            tracing::warn!(
+                "Scratchpad at {} is not writable under the current requirements: {err}",
+                self.path.display()
+            );
+        }
+    }
+
+    pub(crate) fn inject_env(&self, env: &mut HashMap<String, String>) {
+        env.insert(
+            CODEX_SCRATCH_DIR_ENV_VAR.to_string(),
+            self.path.to_string_lossy().into_owned(),
+        );
+    }
+}
+
+impl Drop for Scratchpad {
+    fn drop(&mut self) {
+        if self.keep_artifacts {
+            return;
+        }
+        match std::fs::remove_dir_all(&self.path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => tracing::warn!(
+                "Failed to delete scratchpad at {}: {err}",
+                self.path.display()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "scratchpad_tests.rs"]
+mod tests;
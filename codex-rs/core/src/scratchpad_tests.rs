@@ -0,0 +1,92 @@
+use super::*;
+use codex_config::Constrained;
+use codex_protocol::models::PermissionProfile;
+use codex_protocol::protocol::AskForApproval;
+use core_test_support::PathExt;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+#[test]
+fn scratchpad_is_created_per_thread_and_removed_on_drop() {
+    let codex_home = tempdir().expect("tempdir");
+    let thread_id = ThreadId::new();
+
+    let scratchpad = Scratchpad::create(
+        &codex_home.path().abs(),
+        thread_id,
+        /*keep_artifacts*/ false,
+    )
+    .expect("scratchpad should be created");
+    let path = scratchpad.path().clone();
+    assert_eq!(
+        path,
+        codex_home
+            .path()
+            .abs()
+            .join("scratch")
+            .join(thread_id.to_string())
+    );
+    std::fs::write(path.join("experiment.txt"), "output").expect("write scratch file");
+
+    drop(scratchpad);
+    assert!(!path.exists());
+}
+
+#[test]
+fn scratchpad_keeps_artifacts_when_configured() {
+    let codex_home = tempdir().expect("tempdir");
+
+    let scratchpad = Scratchpad::create(
+        &codex_home.path().abs(),
+        ThreadId::new(),
+        /*keep_artifacts*/ true,
+    )
+    .expect("scratchpad should be created");
+    let artifact = scratchpad.path().join("result.json");
+    std::fs::write(&artifact, "{}").expect("write scratch file");
+
+    drop(scratchpad);
+    assert!(artifact.exists());
+}
+
+#[test]
+fn scratchpad_is_writable_under_read_only_permissions() {
+    let codex_home = tempdir().expect("tempdir");
+    let cwd = tempdir().expect("tempdir");
+    let scratchpad = Scratchpad::create(
+        &codex_home.path().abs(),
+        ThreadId::new(),
+        /*keep_artifacts*/ false,
+    )
+    .expect("scratchpad should be created");
+    let mut permissions = Permissions::from_approval_and_profile(
+        Constrained::allow_any(AskForApproval::Never),
+        Constrained::allow_any(PermissionProfile::read_only()),
+    )
+    .expect("permissions");
+
+    scratchpad.grant_write_access(&mut permissions, cwd.path());
+
+    let policy = permissions.file_system_sandbox_policy();
+    assert!(policy.can_write_path_with_cwd(scratchpad.path().as_path(), cwd.path()));
+    assert!(!policy.can_write_path_with_cwd(cwd.path(), cwd.path()));
+}
+
+#[test]
+fn scratchpad_env_var_points_at_directory() {
+    let codex_home = tempdir().expect("tempdir");
+    let scratchpad = Scratchpad::create(
+        &codex_home.path().abs(),
+        ThreadId::new(),
+        /*keep_artifacts*/ false,
+    )
+    .expect("scratchpad should be created");
+    let mut env = HashMap::new();
+
+    scratchpad.inject_env(&mut env);
+
+    assert_eq!(
+        env.get(CODEX_SCRATCH_DIR_ENV_VAR),
+        Some(&scratchpad.path().to_string_lossy().into_owned())
+    );
+}
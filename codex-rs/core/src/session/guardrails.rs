@@ -0,0 +1,63 @@
+use super::session::Session;
+use super::turn_context::TurnContext;
+use crate::context::ContextualUserFragment;
+use crate::context::GuardrailViolations;
+use crate::guardrails;
+use crate::tools::context::SharedTurnDiffTracker;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::WarningEvent;
+
+/// Checks the files changed this turn against `[guardrails]` and, while
+/// follow-up rounds remain, records the violations for the model.  Returns
+/// whether the turn should sample again instead of ending.
+pub(super) async fn maybe_request_fixes(
+    sess: &Session,
+    turn_context: &TurnContext,
+    turn_diff_tracker: &SharedTurnDiffTracker,
+    rounds: &mut u32,
+) -> bool {
+    let config = &turn_context.config.guardrails;
+    if config.is_empty() {
+        return false;
+    }
+    let violations = {
+        let tracker = turn_diff_tracker.lock().await;
+        guardrails::check(config, &tracker.changed_files())
+    };
+    if violations.is_empty() {
+        return false;
+    }
+
+    let count = violations.len();
+    if *rounds >= config.max_rounds {
+        sess.send_event(
+            turn_context,
+            EventMsg::Warning(WarningEvent {
+                message: format!(
+                    "Guardrails still report {count} violation(s) after {} follow-up round(s); \
+                     ending the turn.",
+                    config.max_rounds
+                ),
+            }),
+        )
+        .await;
+        return false;
+    }
+    *rounds += 1;
+
+    let response_item = ContextualUserFragment::into(GuardrailViolations {
+        violations: guardrails::render_violations(&violations),
+    });
+    sess.record_conversation_items(turn_context, std::slice::from_ref(&response_item))
+        .await;
+    sess.send_event(
+        turn_context,
+        EventMsg::Warning(WarningEvent {
+            message: format!(
+                "Guardrails found {count} violation(s); asking the agent to fix them."
+            ),
+        }),
+    )
+    .await;
+    true
+}
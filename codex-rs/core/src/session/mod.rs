@@ -30,6 +30,8 @@ use crate::context::MultiAgentModeInstructions;
 use crate::context::NetworkRuleSaved;
 use crate::context::PersonalitySpecInstructions;
 use crate::context::RecommendedPluginsInstructions;
+use crate::context::ResponseLanguageInstructions;
+use crate::context::ScratchpadInstructions;
 use crate::context::world_state::WorldState;
 use crate::current_time::TimeProvider;
 use crate::default_skill_metadata_budget;
@@ -191,6 +193,7 @@ use crate::config::PermissionProfileState;
 use crate::config::StartedNetworkProxy;
 use crate::config::resolve_web_search_mode_for_turn;
 use crate::context_manager::ContextManager;
+use crate::context_manager::DedupedToolOutput;
 use crate::thread_rollout_truncation::initial_history_has_prior_user_turns;
 use codex_config::CONFIG_TOML_FILE;
 use codex_config::ConfigLayerSource;
@@ -205,6 +208,7 @@ use codex_protocol::exec_output::StreamOutput;
 mod code_mode_warning;
 mod config_lock;
 pub(crate) mod context_window;
+mod guardrails;
 mod handlers;
 mod inject;
 mod input_queue;
@@ -221,6 +225,7 @@ pub(crate) mod time_reminder;
 mod token_budget;
 pub(crate) mod turn;
 pub(crate) mod turn_context;
+mod workspace_reconciliation;
 mod world_state;
 use self::code_mode_warning::unsupported_code_mode_warning;
 use self::config_lock::export_config_lock_if_configured;
@@ -623,7 +628,7 @@ impl Session {
         // Resolve base instructions for the session. Priority order:
         // 1. config.base_instructions override
         // 2. conversation history => session_meta.base_instructions
-        // 3. base_instructions for current model
+        // 3. base_instructions for current model, with `[system_prompt]` additions
         let model_info = models_manager
             .get_model_info(model.as_str(), &config.to_models_manager_config())
             .await;
@@ -637,7 +642,12 @@ impl Session {
             .base_instructions
             .clone()
             .or_else(|| conversation_history.get_base_instructions().map(|s| s.text))
-            .unwrap_or_else(|| model_info.get_model_instructions(config.personality));
+            .unwrap_or_else(|| {
+                crate::system_prompt::apply(
+                    &model_info.get_model_instructions(config.personality),
+                    &config.system_prompt,
+                )
+            });
 
         // Dynamic tools are defined at thread start and persisted in rollout session metadata.
         let dynamic_tools = if dynamic_tools.is_empty() {
@@ -1325,6 +1335,16 @@ impl Session {
                     state.set_token_info(Some(info));
                 }
 
+                if !is_subagent {
+                    workspace_reconciliation::reconcile_resumed_workspace(
+                        self,
+                        &turn_context,
+                        Arc::clone(&rollout_items),
+                        resumed_history.rollout_path.as_deref(),
+                    )
+                    .await;
+                }
+
                 // Defer seeding the session's initial context until the first turn starts so
                 // turn/start overrides can be merged before we write to the rollout.
                 if !is_subagent {
@@ -2006,6 +2026,9 @@ impl Session {
         self.services
             .rollout_thread_trace
             .record_protocol_event(&event.msg);
+        if let Some(webhooks) = self.services.webhooks.as_ref() {
+            webhooks.observe(&event.msg);
+        }
         self.deliver_event_raw(event).await;
     }
 
@@ -3268,7 +3291,11 @@ impl Session {
         {
             let model_info = turn_context.model_info.clone();
             let has_baked_personality = model_info.supports_personality()
-                && base_instructions == model_info.get_model_instructions(Some(personality));
+                && base_instructions
+                    == crate::system_prompt::apply(
+                        &model_info.get_model_instructions(Some(personality)),
+                        &turn_context.config.system_prompt,
+                    );
             if !has_baked_personality
                 && let Some(personality_message) =
                     crate::context_manager::updates::personality_message_for(
@@ -3280,6 +3307,15 @@ impl Session {
                     .push(PersonalitySpecInstructions::new(personality_message).render());
             }
         }
+        if let Some(language) = turn_context.config.response_language.as_deref() {
+            developer_sections.push(ResponseLanguageInstructions::new(language).render());
+        }
+        if let Some(scratchpad) = self.services.scratchpad.as_ref() {
+            developer_sections.push(
+                ScratchpadInstructions::new(scratchpad.path().clone(), scratchpad.keep_artifacts())
+                    .render(),
+            );
+        }
         if turn_context.config.include_skill_instructions {
             let host_catalog_in_world_state = turn_context
                 .extension_data
@@ -3632,6 +3668,11 @@ impl Session {
                     .await,
             );
         }
+        // What changed in the workspace while a resumed session sat idle goes after the context
+        // it corrects.
+        if let Some(item) = self.state.lock().await.take_pending_workspace_changes() {
+            context_items.push(item);
+        }
         // A snapshot can change without producing model-visible or TurnContext updates.
         let only_world_state_changed = !turn_context_changed && context_items.is_empty();
         if only_world_state_changed && world_state_item.is_none() {
@@ -3778,6 +3819,14 @@ impl Session {
         state.record_mcp_dependency_prompted(names);
     }
 
+    pub(crate) async fn newly_deduped_tool_outputs(
+        &self,
+        removed: Vec<DedupedToolOutput>,
+    ) -> Vec<DedupedToolOutput> {
+        let mut state = self.state.lock().await;
+        state.newly_deduped_tool_outputs(removed)
+    }
+
     pub(crate) async fn set_server_reasoning_included(&self, included: bool) {
         let mut state = self.state.lock().await;
         state.set_server_reasoning_included(included);
@@ -4,9 +4,11 @@ use crate::agents_md_manager::AgentsMdManager;
 use crate::config::ConstraintError;
 use crate::environment_selection::ThreadEnvironments;
 use crate::environment_selection::TurnEnvironmentSnapshot;
+use crate::scratchpad::Scratchpad;
 use crate::shell_snapshot::ShellSnapshot;
 use crate::skills::SkillError;
 use crate::state::ActiveTurn;
+use crate::webhooks::Webhooks;
 use codex_extension_api::ExtensionDataInit;
 use codex_login::auth::AgentIdentityAuthPolicy;
 use codex_protocol::SessionId;
@@ -1135,6 +1137,18 @@ impl Session {
                 ),
                 tool_search_handler_cache: Default::default(),
                 turn_environments: Arc::clone(&turn_environments),
+                scratchpad: config
+                    .features
+                    .enabled(Feature::Scratchpad)
+                    .then(|| {
+                        Scratchpad::create(
+                            &config.codex_home,
+                            thread_id,
+                            config.scratchpad.keep_artifacts,
+                        )
+                    })
+                    .flatten(),
+                webhooks: Webhooks::new(thread_id, &config.webhooks),
             };
             let sess = Arc::new(Session {
                 thread_id,
@@ -614,6 +614,7 @@ async fn write_project_trust_config(
                             project_trust_key(project),
                             ProjectConfig {
                                 trust_level: Some(*trust_level),
+                                response_language: None,
                             },
                         )
                     })
@@ -5482,6 +5483,8 @@ pub(crate) async fn make_session_and_context() -> (Session, TurnContext) {
         ),
         tool_search_handler_cache: Default::default(),
         turn_environments: Arc::clone(&turn_environments),
+        scratchpad: None,
+        webhooks: None,
     };
 
     let plugins_input = per_turn_config.plugins_config_input();
@@ -7644,6 +7647,8 @@ where
         ),
         tool_search_handler_cache: Default::default(),
         turn_environments: Arc::clone(&turn_environments),
+        scratchpad: None,
+        webhooks: None,
     };
 
     let plugins_input = per_turn_config.plugins_config_input();
@@ -8634,6 +8639,28 @@ async fn build_initial_context_omits_multi_agent_v2_usage_hints_when_hint_is_emp
     );
 }
 
+#[tokio::test]
+async fn build_initial_context_adds_response_language_as_developer_message() {
+    let (session, turn_context, _rx_event) = make_session_and_context_with_auth_and_config_and_rx(
+        CodexAuth::from_api_key("Test API Key"),
+        Vec::new(),
+        |config| {
+            config.response_language = Some("French".to_string());
+        },
+    )
+    .await;
+
+    let initial_context = build_initial_context(&session, &turn_context).await;
+
+    let developer_messages = developer_message_texts(&initial_context);
+    assert!(
+        developer_messages.iter().flatten().any(|text| {
+            text.starts_with("<response_language>\nThe user prefers replies in French.")
+        }),
+        "expected response language developer text, got {developer_messages:?}"
+    );
+}
+
 #[tokio::test]
 async fn build_initial_context_trims_skill_metadata_from_context_window_budget() {
     let (session, mut turn_context) = make_session_and_context().await;
@@ -16,6 +16,7 @@ use crate::compact_remote::run_inline_remote_auto_compact_task;
 use crate::compact_remote_v2::run_inline_remote_auto_compact_task as run_inline_remote_auto_compact_task_v2;
 use crate::connectors;
 use crate::context::ContextualUserFragment;
+use crate::context_manager::dedupe_repeated_tool_outputs;
 use crate::feedback_tags;
 use crate::hook_runtime::inspect_pending_input;
 use crate::hook_runtime::record_additional_contexts;
@@ -128,6 +129,9 @@ use tracing::trace;
 use tracing::trace_span;
 use tracing::warn;
 
+const HISTORY_DEDUPE_OUTPUTS_METRIC: &str = "codex.history.dedupe.outputs";
+const HISTORY_DEDUPE_TOKENS_SAVED_METRIC: &str = "codex.history.dedupe.tokens_saved";
+
 /// Takes initial turn input and runs a loop where, at each sampling request,
 /// the model replies with either:
 ///
@@ -211,6 +215,7 @@ pub(crate) async fn run_turn(
 
     let mut last_agent_message: Option<String> = None;
     let mut stop_hook_active = false;
+    let mut guardrail_rounds = 0;
     // Although from the perspective of codex.rs, TurnDiffTracker has the lifecycle of a Task which contains
     // many turns, from the perspective of the user, it is a single turn.
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(
@@ -265,13 +270,10 @@ pub(crate) async fn run_turn(
                 .await;
 
             // Construct the input that we will send to the model.
-            let sampling_request_input: Vec<ResponseItem> = async {
-                sess.clone_history()
-                    .await
-                    .for_prompt(&turn_context.model_info.input_modalities)
-            }
-            .instrument(trace_span!("run_turn.prepare_sampling_request_input"))
-            .await;
+            let sampling_request_input: Vec<ResponseItem> =
+                sampling_request_history(sess.as_ref(), turn_context.as_ref())
+                    .instrument(trace_span!("run_turn.prepare_sampling_request_input"))
+                    .await;
 
             let responses_metadata = turn_context.turn_metadata_state.to_responses_metadata(
                 sess.installation_id.clone(),
@@ -384,6 +386,16 @@ pub(crate) async fn run_turn(
 
                 if !needs_follow_up {
                     last_agent_message = sampling_request_last_agent_message;
+                    if super::guardrails::maybe_request_fixes(
+                        sess.as_ref(),
+                        turn_context.as_ref(),
+                        &turn_diff_tracker,
+                        &mut guardrail_rounds,
+                    )
+                    .await
+                    {
+                        continue;
+                    }
                     let stop_outcome = run_turn_stop_hooks(
                         &sess,
                         &turn_context,
@@ -1093,6 +1105,45 @@ pub(super) fn collect_explicit_app_ids_from_skill_items(
     connector_ids
 }
 
+/// Returns history prepared for a sampling request, with repeated tool outputs collapsed into
+/// stubs when `dedupe_tool_outputs` is enabled.
+async fn sampling_request_history(sess: &Session, turn_context: &TurnContext) -> Vec<ResponseItem> {
+    let mut items = sess
+        .clone_history()
+        .await
+        .for_prompt(&turn_context.model_info.input_modalities);
+    if turn_context
+        .config
+        .features
+        .enabled(Feature::DedupeToolOutputs)
+    {
+        let deduped = dedupe_repeated_tool_outputs(&mut items);
+        // Earlier sampling requests already counted the outputs they collapsed.
+        let newly_deduped = sess.newly_deduped_tool_outputs(deduped.removed).await;
+        if !newly_deduped.is_empty() {
+            let tokens_saved = newly_deduped
+                .iter()
+                .map(|output| output.tokens_saved)
+                .sum::<i64>();
+            trace!(
+                outputs = newly_deduped.len(),
+                tokens_saved, "collapsed repeated tool outputs in sampling request history"
+            );
+            turn_context.session_telemetry.counter(
+                HISTORY_DEDUPE_OUTPUTS_METRIC,
+                i64::try_from(newly_deduped.len()).unwrap_or(i64::MAX),
+                &[],
+            );
+            turn_context.session_telemetry.counter(
+                HISTORY_DEDUPE_TOKENS_SAVED_METRIC,
+                tokens_saved,
+                &[],
+            );
+        }
+    }
+    items
+}
+
 #[instrument(level = "trace", skip_all)]
 pub(crate) fn build_prompt(
     input: Vec<ResponseItem>,
@@ -1157,9 +1208,7 @@ async fn run_sampling_request(
         let prompt_input = if let Some(input) = initial_input.take() {
             input
         } else {
-            sess.clone_history()
-                .await
-                .for_prompt(&turn_context.model_info.input_modalities)
+            sampling_request_history(sess.as_ref(), turn_context.as_ref()).await
         };
         let prompt = build_prompt(
             prompt_input,
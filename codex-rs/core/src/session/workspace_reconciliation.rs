@@ -0,0 +1,75 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use super::session::Session;
+use super::turn_context::TurnContext;
+use crate::context::ContextualUserFragment;
+use crate::context::WorkspaceChanges;
+use crate::workspace_reconciliation;
+use crate::workspace_reconciliation::Workspace;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::WarningEvent;
+
+/// Compares a resumed session's history with its workspace and, when the
+/// workspace moved on, queues a note for the model on the next turn and tells
+/// the user.  The rollout file's modification time marks when the session was
+/// last active.  Only the local environment is checked, since new commits are
+/// listed by running git on this host.
+pub(super) async fn reconcile_resumed_workspace(
+    sess: &Session,
+    turn_context: &TurnContext,
+    rollout_items: Arc<Vec<RolloutItem>>,
+    rollout_path: Option<&Path>,
+) {
+    let Some(rollout_path) = rollout_path else {
+        return;
+    };
+    let Ok(last_active) = tokio::fs::metadata(rollout_path)
+        .await
+        .and_then(|metadata| metadata.modified())
+    else {
+        return;
+    };
+    let Some(turn_environment) = turn_context.environments.local() else {
+        return;
+    };
+    let Ok(cwd) = turn_environment.cwd().to_abs_path() else {
+        return;
+    };
+    let workspace = Workspace {
+        cwd,
+        roots: turn_environment
+            .workspace_roots()
+            .iter()
+            .filter_map(|root| root.to_abs_path().ok())
+            .collect(),
+        file_system: turn_environment.environment.get_filesystem(),
+        sandbox: turn_context
+            .file_system_sandbox_context(/*additional_permissions*/ None, turn_environment),
+    };
+    let Some(changes) =
+        workspace_reconciliation::detect(&workspace, &rollout_items, last_active).await
+    else {
+        return;
+    };
+
+    let response_item = ContextualUserFragment::into(WorkspaceChanges {
+        changes: changes.render(),
+    });
+    sess.state
+        .lock()
+        .await
+        .set_pending_workspace_changes(response_item);
+    sess.send_event(
+        turn_context,
+        EventMsg::Warning(WarningEvent {
+            message: format!(
+                "The workspace changed since this session was last active ({}). Codex will be \
+                 told what changed before your next message.",
+                changes.summary()
+            ),
+        }),
+    )
+    .await;
+}
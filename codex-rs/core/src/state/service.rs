@@ -14,12 +14,14 @@ use crate::environment_selection::ThreadEnvironments;
 use crate::exec_policy::ExecPolicyManager;
 use crate::guardian::GuardianRejectionCircuitBreaker;
 use crate::mcp::McpManager;
+use crate::scratchpad::Scratchpad;
 use crate::session::McpRuntimeSnapshot;
 use crate::tools::code_mode::CodeModeService;
 use crate::tools::handlers::ToolSearchHandlerCache;
 use crate::tools::network_approval::NetworkApprovalService;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecProcessManager;
+use crate::webhooks::Webhooks;
 use anyhow::Result;
 use arc_swap::ArcSwap;
 use arc_swap::ArcSwapOption;
@@ -99,6 +101,10 @@ pub(crate) struct SessionServices {
     pub(crate) code_mode_service: CodeModeService,
     pub(crate) tool_search_handler_cache: ToolSearchHandlerCache,
     pub(crate) turn_environments: Arc<ThreadEnvironments>,
+    /// Scratch directory for this session, when the `scratchpad` feature is enabled.
+    pub(crate) scratchpad: Option<Scratchpad>,
+    /// Webhooks that receive this session's events, from `[webhooks]`.
+    pub(crate) webhooks: Option<Webhooks>,
 }
 
 impl SessionServices {
@@ -12,6 +12,7 @@ use super::auto_compact_window::AutoCompactWindow;
 use super::auto_compact_window::AutoCompactWindowIds;
 use super::auto_compact_window::AutoCompactWindowSnapshot;
 use crate::context_manager::ContextManager;
+use crate::context_manager::DedupedToolOutput;
 use crate::session::PreviousTurnSettings;
 use crate::session::session::SessionConfiguration;
 use crate::session::time_reminder::CurrentTimeReminderState;
@@ -29,6 +30,9 @@ pub(crate) struct SessionState {
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
     pub(crate) server_reasoning_included: bool,
     pub(crate) mcp_dependency_prompted: HashSet<String>,
+    /// Call ids of tool outputs already counted as deduplicated. History is deduplicated again for
+    /// every sampling request, so this keeps each removed duplicate in the metrics once.
+    deduped_tool_output_call_ids: HashSet<String>,
     pub(crate) additional_context: AdditionalContextStore,
     /// Settings used by the latest regular user turn, used for turn-to-turn
     /// model/realtime handling on subsequent regular turns (including full-context
@@ -43,6 +47,8 @@ pub(crate) struct SessionState {
     pub(crate) pending_session_start_sources: VecDeque<codex_hooks::SessionStartSource>,
     granted_permissions_by_environment_id: HashMap<String, AdditionalPermissionProfile>,
     next_turn_is_first: bool,
+    /// Note about workspace changes found on resume, recorded with the next turn's context.
+    pending_workspace_changes: Option<ResponseItem>,
 }
 
 impl SessionState {
@@ -66,6 +72,7 @@ impl SessionState {
             latest_rate_limits: None,
             server_reasoning_included: false,
             mcp_dependency_prompted: HashSet::new(),
+            deduped_tool_output_call_ids: HashSet::new(),
             additional_context: AdditionalContextStore::default(),
             previous_turn_settings: None,
             auto_compact_window: AutoCompactWindow::new_with_ids(auto_compact_window_ids),
@@ -75,6 +82,7 @@ impl SessionState {
             pending_session_start_sources: VecDeque::new(),
             granted_permissions_by_environment_id: HashMap::new(),
             next_turn_is_first: true,
+            pending_workspace_changes: None,
         }
     }
 
@@ -107,6 +115,14 @@ impl SessionState {
         is_first_turn
     }
 
+    pub(crate) fn set_pending_workspace_changes(&mut self, item: ResponseItem) {
+        self.pending_workspace_changes = Some(item);
+    }
+
+    pub(crate) fn take_pending_workspace_changes(&mut self) -> Option<ResponseItem> {
+        self.pending_workspace_changes.take()
+    }
+
     pub(crate) fn clone_history(&self) -> ContextManager {
         self.history.clone()
     }
@@ -246,6 +262,20 @@ impl SessionState {
         self.mcp_dependency_prompted.clone()
     }
 
+    /// Returns the outputs in `removed` that were not deduplicated by an earlier sampling request.
+    pub(crate) fn newly_deduped_tool_outputs(
+        &mut self,
+        removed: Vec<DedupedToolOutput>,
+    ) -> Vec<DedupedToolOutput> {
+        removed
+            .into_iter()
+            .filter(|output| {
+                self.deduped_tool_output_call_ids
+                    .insert(output.call_id.clone())
+            })
+            .collect()
+    }
+
     pub(crate) fn set_session_startup_prewarm(
         &mut self,
         startup_prewarm: SessionStartupPrewarmHandle,
@@ -35,6 +35,26 @@ async fn clear_connector_selection_removes_entries() {
     assert_eq!(state.get_connector_selection(), HashSet::new());
 }
 
+#[tokio::test]
+// Verifies a deduplicated tool output is reported once across sampling requests.
+async fn newly_deduped_tool_outputs_skips_outputs_already_counted() {
+    let session_configuration = make_session_configuration_for_tests().await;
+    let mut state = SessionState::new(session_configuration);
+    let output = |call_id: &str| DedupedToolOutput {
+        call_id: call_id.to_string(),
+        tokens_saved: 100,
+    };
+
+    assert_eq!(
+        state.newly_deduped_tool_outputs(vec![output("call-1")]),
+        vec![output("call-1")]
+    );
+    assert_eq!(
+        state.newly_deduped_tool_outputs(vec![output("call-2"), output("call-1")]),
+        vec![output("call-2")]
+    );
+}
+
 #[tokio::test]
 async fn set_rate_limits_defaults_limit_id_to_codex_when_missing() {
     let session_configuration = make_session_configuration_for_tests().await;
@@ -0,0 +1,151 @@
+//! Project additions to the built-in system prompt, from `[system_prompt]`.
+//!
+//! `append` text goes after the model's base instructions.  `replace` entries
+//! swap the body of a base-instruction section, found by its Markdown heading,
+//! for project text; they only apply when `allow_replace` is set.  Both share
+//! a token budget so house conventions cannot crowd out the conversation.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use codex_config::types::DEFAULT_SYSTEM_PROMPT_MAX_TOKENS;
+use codex_config::types::SystemPromptConfig;
+use codex_config::types::SystemPromptToml;
+use codex_utils_string::approx_token_count;
+use codex_utils_string::truncate_middle_with_token_budget;
+
+pub(crate) fn resolve_config(
+    config: SystemPromptToml,
+    startup_warnings: &mut Vec<String>,
+) -> SystemPromptConfig {
+    let max_tokens = config
+        .max_tokens
+        .unwrap_or(DEFAULT_SYSTEM_PROMPT_MAX_TOKENS);
+    let mut remaining = max_tokens;
+
+    let mut replace = BTreeMap::new();
+    let entries = config.replace.unwrap_or_default();
+    if !entries.is_empty() && !config.allow_replace.unwrap_or(false) {
+        startup_warnings.push(
+            "Ignoring `system_prompt.replace` because `system_prompt.allow_replace` is not true."
+                .to_string(),
+        );
+    } else {
+        // Replacements are applied whole or not at all; a truncated section
+        // would read as a broken instruction.
+        for (heading, text) in entries {
+            let tokens = approx_token_count(&text);
+            if tokens > remaining {
+                startup_warnings.push(format!(
+                    "Ignoring `system_prompt.replace` for section `{heading}`: ~{tokens} tokens \
+                     exceeds the remaining `system_prompt.max_tokens` budget of {remaining}."
+                ));
+                continue;
+            }
+            remaining -= tokens;
+            replace.insert(heading.trim().to_string(), text);
+        }
+    }
+
+    let append = config
+        .append
+        .filter(|text| !text.trim().is_empty())
+        .and_then(|text| {
+            let tokens = approx_token_count(&text);
+            if tokens <= remaining {
+                return Some(text);
+            }
+            if remaining == 0 {
+                startup_warnings.push(
+                    "Ignoring `system_prompt.append`: the `system_prompt.max_tokens` budget is \
+                     used up by `system_prompt.replace`."
+                        .to_string(),
+                );
+                return None;
+            }
+            startup_warnings.push(format!(
+                "Truncated `system_prompt.append` from ~{tokens} tokens to the remaining \
+                 `system_prompt.max_tokens` budget of {remaining}."
+            ));
+            Some(truncate_middle_with_token_budget(&text, remaining).0)
+        });
+
+    SystemPromptConfig {
+        append,
+        replace,
+        max_tokens,
+    }
+}
+
+/// Returns `base` with the configured section replacements and appended text.
+pub(crate) fn apply(base: &str, config: &SystemPromptConfig) -> String {
+    if config.append.is_none() && config.replace.is_empty() {
+        return base.to_string();
+    }
+
+    let mut prompt = base.to_string();
+    for (heading, text) in &config.replace {
+        let Some(body) = find_section_body(&prompt, heading) else {
+            tracing::warn!(
+                "`system_prompt.replace` section `{heading}` is not in the base instructions"
+            );
+            continue;
+        };
+        let separator = if body.end < prompt.len() {
+            "\n\n"
+        } else {
+            "\n"
+        };
+        prompt.replace_range(body, &format!("\n{}{separator}", text.trim()));
+    }
+    if let Some(append) = &config.append {
+        prompt = format!("{}\n\n{}\n", prompt.trim_end(), append.trim());
+    }
+    prompt
+}
+
+/// Byte range of the text under `heading`, up to the next heading of the same
+/// or a higher level.  Headings inside fenced code blocks are not sections.
+fn find_section_body(prompt: &str, heading: &str) -> Option<Range<usize>> {
+    let mut in_fence = false;
+    let mut body: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for line in prompt.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let Some((level, title)) = parse_heading(line) else {
+            continue;
+        };
+        match body {
+            Some((start, section_level)) if level <= section_level => {
+                return Some(start..line_start);
+            }
+            None if title == heading => body = Some((offset, level)),
+            _ => {}
+        }
+    }
+    body.map(|(start, _)| start..prompt.len())
+}
+
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.bytes().take_while(|byte| *byte == b'#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    Some((level, rest.trim()))
+}
+
+#[cfg(test)]
+#[path = "system_prompt_tests.rs"]
+mod tests;
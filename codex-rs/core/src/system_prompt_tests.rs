@@ -0,0 +1,127 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+const BASE: &str = "You are Codex.
+
+## Style
+
+Be terse.
+
+### Lists
+
+Use dashes.
+
+```md
+## Style
+not a heading
+```
+
+## Testing
+
+Run the tests.
+";
+
+fn config(append: Option<&str>, replace: &[(&str, &str)]) -> SystemPromptConfig {
+    SystemPromptConfig {
+        append: append.map(str::to_string),
+        replace: replace
+            .iter()
+            .map(|(heading, text)| (heading.to_string(), text.to_string()))
+            .collect(),
+        max_tokens: DEFAULT_SYSTEM_PROMPT_MAX_TOKENS,
+    }
+}
+
+#[test]
+fn apply_without_customizations_returns_base() {
+    assert_eq!(apply(BASE, &SystemPromptConfig::default()), BASE);
+}
+
+#[test]
+fn apply_appends_and_replaces_sections() {
+    let prompt = apply(
+        BASE,
+        &config(
+            Some("Follow the house style guide."),
+            &[
+                ("Style", "Write full sentences."),
+                ("Testing", "Run `just test`."),
+            ],
+        ),
+    );
+
+    assert_eq!(
+        prompt,
+        "You are Codex.
+
+## Style
+
+Write full sentences.
+
+## Testing
+
+Run `just test`.
+
+Follow the house style guide.
+"
+    );
+}
+
+#[test]
+fn apply_replaces_only_the_subsection() {
+    let prompt = apply(BASE, &config(None, &[("Lists", "Use numbers.")]));
+
+    // The fenced `## Style` belongs to the subsection and goes with it.
+    assert!(prompt.contains("### Lists\n\nUse numbers.\n\n## Testing\n"));
+    assert!(prompt.contains("Be terse."));
+}
+
+#[test]
+fn apply_skips_unknown_sections() {
+    assert_eq!(apply(BASE, &config(None, &[("Missing", "ignored")])), BASE);
+}
+
+#[test]
+fn replace_requires_allow_replace() {
+    let mut warnings = Vec::new();
+    let resolved = resolve_config(
+        SystemPromptToml {
+            replace: Some([("Style".to_string(), "Loud.".to_string())].into()),
+            ..Default::default()
+        },
+        &mut warnings,
+    );
+
+    assert!(resolved.replace.is_empty());
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+}
+
+#[test]
+fn additions_are_token_budgeted() {
+    let mut warnings = Vec::new();
+    let resolved = resolve_config(
+        SystemPromptToml {
+            append: Some("word ".repeat(100)),
+            replace: Some(
+                [
+                    ("Style".to_string(), "short".to_string()),
+                    ("Testing".to_string(), "long ".repeat(100)),
+                ]
+                .into(),
+            ),
+            allow_replace: Some(true),
+            max_tokens: Some(20),
+        },
+        &mut warnings,
+    );
+
+    assert_eq!(
+        resolved.replace,
+        [("Style".to_string(), "short".to_string())].into()
+    );
+    let append = resolved
+        .append
+        .expect("append should be truncated, not dropped");
+    assert!(approx_token_count(&append) < 40, "{append}");
+    assert_eq!(warnings.len(), 2, "{warnings:?}");
+}
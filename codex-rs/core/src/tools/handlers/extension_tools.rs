@@ -2,8 +2,13 @@ use std::sync::Arc;
 use std::sync::Weak;
 
 use codex_protocol::items::TurnItem;
+use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ReviewDecision;
+use codex_tools::CommandApprovalFuture;
+use codex_tools::CommandApprovalRequest;
+use codex_tools::CommandApprover;
 use codex_tools::ConversationHistory;
 use codex_tools::ExtensionTurnItem;
 use codex_tools::ToolCall as ExtensionToolCall;
@@ -113,6 +118,53 @@ impl TurnItemEmitter for CoreTurnItemEmitter {
     }
 }
 
+/// Asks the user before an extension tool runs a command outside the sandbox. Only a one-time
+/// approval is offered, and nothing is asked when the approval policy forbids sandbox prompts.
+struct CoreCommandApprover {
+    session: Weak<Session>,
+    turn: Weak<TurnContext>,
+    call_id: String,
+}
+
+impl CommandApprover for CoreCommandApprover {
+    fn request_approval<'a>(
+        &'a self,
+        request: CommandApprovalRequest,
+    ) -> CommandApprovalFuture<'a> {
+        Box::pin(async move {
+            let (Some(session), Some(turn)) = (self.session.upgrade(), self.turn.upgrade()) else {
+                return false;
+            };
+            let may_ask = match turn.approval_policy.value() {
+                AskForApproval::Never => false,
+                AskForApproval::Granular(granular_config) => {
+                    granular_config.allows_sandbox_approval()
+                }
+                AskForApproval::OnRequest | AskForApproval::UnlessTrusted => true,
+            };
+            if !may_ask {
+                return false;
+            }
+            let decision = session
+                .request_command_approval(
+                    turn.as_ref(),
+                    self.call_id.clone(),
+                    /*approval_id*/ None,
+                    /*environment_id*/ None,
+                    request.command,
+                    request.cwd,
+                    Some(request.reason),
+                    /*network_approval_context*/ None,
+                    /*proposed_execpolicy_amendment*/ None,
+                    /*additional_permissions*/ None,
+                    Some(vec![ReviewDecision::Approved, ReviewDecision::Abort]),
+                )
+                .await;
+            matches!(decision, ReviewDecision::Approved)
+        })
+    }
+}
+
 async fn to_extension_call(invocation: &ToolInvocation) -> ExtensionToolCall {
     let conversation_history =
         ConversationHistory::new(invocation.session.clone_history().await.into_raw_items());
@@ -162,6 +214,11 @@ async fn to_extension_call(invocation: &ToolInvocation) -> ExtensionToolCall {
             session: Arc::downgrade(&invocation.session),
             turn: Arc::downgrade(&invocation.turn),
         }),
+        command_approver: Arc::new(CoreCommandApprover {
+            session: Arc::downgrade(&invocation.session),
+            turn: Arc::downgrade(&invocation.turn),
+            call_id: invocation.call_id.clone(),
+        }),
         environments,
         payload: invocation.payload.clone(),
     }
@@ -177,9 +234,14 @@ mod tests {
     use codex_protocol::items::TurnItem;
     use codex_protocol::models::ContentItem;
     use codex_protocol::models::ResponseItem;
+    use codex_protocol::protocol::AskForApproval;
     use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::ImageGenerationBeginEvent;
     use codex_protocol::protocol::ImageGenerationEndEvent;
+    use codex_protocol::protocol::ReviewDecision;
+    use codex_tools::CommandApprovalFuture;
+    use codex_tools::CommandApprovalRequest;
+    use codex_tools::CommandApprover;
     use codex_tools::ExtensionTurnItem;
     use codex_utils_absolute_path::test_support::PathExt;
     use codex_utils_absolute_path::test_support::test_path_buf;
@@ -106,6 +106,11 @@ impl ShellCommandHandler {
         );
         let active_permission_profile = turn_context.config.permissions.active_permission_profile();
         inject_permission_profile_env(&mut env, active_permission_profile.as_ref());
+        if !turn_environment.environment.is_remote()
+            && let Some(scratchpad) = session.services.scratchpad.as_ref()
+        {
+            scratchpad.inject_env(&mut env);
+        }
 
         Ok(ExecParams {
             command,
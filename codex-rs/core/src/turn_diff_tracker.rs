@@ -44,6 +44,17 @@ struct DiffCacheKey {
     right_revision: Option<u64>,
 }
 
+/// A file whose content apply_patch changed during the turn.
+pub(crate) struct ChangedFile<'a> {
+    /// Path relative to the display root, with `/` separators.
+    pub(crate) path: String,
+    /// Content before the turn, following renames; `None` for files created this turn.
+    pub(crate) previous: Option<&'a str>,
+    pub(crate) content: &'a str,
+    /// Whether nothing existed at `path` before the turn, as for created or moved files.
+    pub(crate) is_new_path: bool,
+}
+
 /// Tracks the net text diff for the current turn from committed apply_patch
 /// mutations, without rereading the workspace filesystem.
 pub struct TurnDiffTracker {
@@ -119,6 +130,34 @@ impl TurnDiffTracker {
         self.unified_diff.is_some()
     }
 
+    /// Files created, moved, or modified this turn that still exist, sorted by path.  Empty once
+    /// the tracker has been invalidated.
+    pub(crate) fn changed_files(&self) -> Vec<ChangedFile<'_>> {
+        if !self.valid {
+            return Vec::new();
+        }
+        let mut files = self
+            .current_by_path
+            .iter()
+            .filter_map(|(path, current)| {
+                let origin = self.origin_by_current_path.get(path).unwrap_or(path);
+                let previous = self
+                    .baseline_by_path
+                    .get(origin)
+                    .map(|content| content.content.as_str());
+                let is_new_path = !self.baseline_by_path.contains_key(path);
+                (is_new_path || previous != Some(current.content.as_str())).then(|| ChangedFile {
+                    path: self.display_path(path).replace('\\', "/"),
+                    previous,
+                    content: current.content.as_str(),
+                    is_new_path,
+                })
+            })
+            .collect::<Vec<_>>();
+        files.sort_by(|left, right| left.path.cmp(&right.path));
+        files
+    }
+
     fn refresh_unified_diff(&mut self) {
         let rename_pairs = self.rename_pairs();
         let paired_destinations = rename_pairs.values().cloned().collect::<HashSet<_>>();
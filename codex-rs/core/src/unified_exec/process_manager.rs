@@ -1134,6 +1134,11 @@ impl UnifiedExecProcessManager {
         );
         let active_permission_profile = context.turn.config.permissions.active_permission_profile();
         inject_permission_profile_env(&mut env, active_permission_profile.as_ref());
+        if !request.turn_environment.environment.is_remote()
+            && let Some(scratchpad) = context.session.services.scratchpad.as_ref()
+        {
+            scratchpad.inject_env(&mut env);
+        }
         let env = apply_unified_exec_env(env);
         let exec_server_env_config = ExecServerEnvConfig {
             policy: exec_env_policy_from_shell_policy(
@@ -0,0 +1,266 @@
+//! Session event webhooks from `[webhooks.<name>]`.
+//!
+//! Each event a webhook selects is sent as a JSON `POST` in the background, so a slow or failing
+//! endpoint never holds up the session. When the webhook names a secret, the body is signed with
+//! HMAC-SHA256 in the `X-Codex-Signature: sha256=<hex>` header. Deliveries that fail or get a
+//! response other than 2xx are retried with exponential backoff, except for client errors that a
+//! retry cannot fix.
+
+use std::time::Duration;
+
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_config::types::Webhook;
+use codex_config::types::WebhookEvent;
+use codex_http_client::build_reqwest_client_with_custom_ca;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::EventMsg;
+use hmac::Hmac;
+use hmac::Mac;
+use serde_json::Value;
+use serde_json::json;
+use sha2::Sha256;
+
+pub(crate) const SIGNATURE_HEADER: &str = "X-Codex-Signature";
+pub(crate) const EVENT_HEADER: &str = "X-Codex-Event";
+
+type HmacSha256 = Hmac<Sha256>;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The webhooks of one session.
+pub(crate) struct Webhooks {
+    thread_id: ThreadId,
+    client: reqwest::Client,
+    targets: Vec<WebhookTarget>,
+}
+
+#[derive(Clone)]
+struct WebhookTarget {
+    name: String,
+    url: String,
+    events: Vec<WebhookEvent>,
+    secret: Option<String>,
+    max_retries: u32,
+}
+
+impl Webhooks {
+    /// Resolves each webhook's secret from its environment variable. Returns `None` when no
+    /// webhook is configured.
+    pub(crate) fn new(thread_id: ThreadId, webhooks: &[Webhook]) -> Option<Self> {
+        if webhooks.is_empty() {
+            return None;
+        }
+        let client = match build_reqwest_client_with_custom_ca(
+            reqwest::Client::builder().timeout(REQUEST_TIMEOUT),
+        ) {
+            Ok(client) => client,
+            Err(err) => {
+                tracing::warn!("webhooks disabled: failed to build HTTP client: {err}");
+                return None;
+            }
+        };
+        let targets = webhooks
+            .iter()
+            .map(|webhook| WebhookTarget {
+                name: webhook.name.clone(),
+                url: webhook.url.clone(),
+                events: webhook.events.clone(),
+                secret: webhook.secret_env_var.as_ref().and_then(|var| {
+                    let secret = std::env::var(var).ok().filter(|secret| !secret.is_empty());
+                    if secret.is_none() {
+                        tracing::warn!(
+                            "webhook `{}` sends unsigned requests: `{var}` is not set",
+                            webhook.name
+                        );
+                    }
+                    secret
+                }),
+                max_retries: webhook.max_retries,
+            })
+            .collect();
+        Some(Self {
+            thread_id,
+            client,
+            targets,
+        })
+    }
+
+    /// Sends `msg` to every webhook that selects its event.
+    pub(crate) fn observe(&self, msg: &EventMsg) {
+        let Some((event, data)) = webhook_event(msg) else {
+            return;
+        };
+        let targets: Vec<WebhookTarget> = self
+            .targets
+            .iter()
+            .filter(|target| target.events.contains(&event))
+            .cloned()
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+        let body = payload(self.thread_id, event, data).to_string();
+        for target in targets {
+            let client = self.client.clone();
+            let body = body.clone();
+            tokio::spawn(async move {
+                deliver(&client, &target, event, &body, FIRST_RETRY_DELAY).await;
+            });
+        }
+    }
+}
+
+/// The webhook event `msg` triggers, with the event-specific fields of its payload.
+pub(crate) fn webhook_event(msg: &EventMsg) -> Option<(WebhookEvent, Value)> {
+    match msg {
+        EventMsg::TurnComplete(event) => Some((
+            WebhookEvent::TurnComplete,
+            json!({
+                "turn_id": event.turn_id,
+                "last_agent_message": event.last_agent_message,
+            }),
+        )),
+        EventMsg::ExecApprovalRequest(event) => Some((
+            WebhookEvent::ApprovalRequested,
+            json!({
+                "kind": "exec",
+                "turn_id": event.turn_id,
+                "call_id": event.call_id,
+                "command": event.command,
+                "cwd": event.cwd,
+                "reason": event.reason,
+            }),
+        )),
+        EventMsg::ApplyPatchApprovalRequest(event) => {
+            let mut files: Vec<_> = event.changes.keys().collect();
+            files.sort();
+            Some((
+                WebhookEvent::ApprovalRequested,
+                json!({
+                    "kind": "patch",
+                    "turn_id": event.turn_id,
+                    "call_id": event.call_id,
+                    "files": files,
+                    "reason": event.reason,
+                }),
+            ))
+        }
+        EventMsg::RequestPermissions(event) => Some((
+            WebhookEvent::ApprovalRequested,
+            json!({
+                "kind": "permissions",
+                "turn_id": event.turn_id,
+                "call_id": event.call_id,
+                "reason": event.reason,
+            }),
+        )),
+        EventMsg::Error(error)
+            if error.codex_error_info == Some(CodexErrorInfo::SessionBudgetExceeded) =>
+        {
+            Some((
+                WebhookEvent::BudgetExceeded,
+                json!({ "message": error.message }),
+            ))
+        }
+        EventMsg::Error(error) if error.affects_turn_status() => Some((
+            WebhookEvent::RunFailed,
+            json!({
+                "message": error.message,
+                "error_info": error.codex_error_info,
+            }),
+        )),
+        _ => None,
+    }
+}
+
+fn payload(thread_id: ThreadId, event: WebhookEvent, data: Value) -> Value {
+    json!({
+        "event": event,
+        "thread_id": thread_id.to_string(),
+        "timestamp": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, /*use_z*/ true),
+        "data": data,
+    })
+}
+
+/// The `X-Codex-Signature` value for `body`: `sha256=` and the hex HMAC-SHA256 of the body.
+pub(crate) fn signature(secret: &str, body: &str) -> Option<String> {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).ok()?;
+    mac.update(body.as_bytes());
+    let digest: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    Some(format!("sha256={digest}"))
+}
+
+/// Posts `body` to `target`, retrying failures after `retry_delay`, doubled on each attempt.
+/// Returns whether the endpoint accepted it.
+async fn deliver(
+    client: &reqwest::Client,
+    target: &WebhookTarget,
+    event: WebhookEvent,
+    body: &str,
+    retry_delay: Duration,
+) -> bool {
+    let event_name = json!(event);
+    let event_name = event_name.as_str().unwrap_or_default();
+    let mut delay = retry_delay;
+    for attempt in 0..=target.max_retries {
+        if attempt > 0 {
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+        let mut request = client
+            .post(&target.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, event_name)
+            .body(body.to_string());
+        if let Some(signature) = target
+            .secret
+            .as_deref()
+            .and_then(|secret| signature(secret, body))
+        {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+        match request.send().await {
+            Ok(response) if response.status().is_success() => return true,
+            Ok(response) if !is_retryable(response.status()) => {
+                tracing::warn!(
+                    "webhook `{}` rejected {event_name} with {}",
+                    target.name,
+                    response.status()
+                );
+                return false;
+            }
+            Ok(response) => tracing::debug!(
+                "webhook `{}` answered {event_name} with {}",
+                target.name,
+                response.status()
+            ),
+            Err(err) => tracing::debug!("webhook `{}` failed: {err}", target.name),
+        }
+    }
+    tracing::warn!(
+        "webhook `{}` dropped {event_name} after {} attempts",
+        target.name,
+        target.max_retries + 1
+    );
+    false
+}
+
+/// Server errors, timeouts, and rate limits may pass on a later attempt; other client errors
+/// will not.
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.is_server_error()
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+#[cfg(test)]
+#[path = "webhooks_tests.rs"]
+mod tests;
@@ -0,0 +1,108 @@
+use super::*;
+use codex_protocol::protocol::ErrorEvent;
+use pretty_assertions::assert_eq;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+
+fn target(url: String, secret: Option<&str>) -> WebhookTarget {
+    WebhookTarget {
+        name: "ci".to_string(),
+        url,
+        events: vec![WebhookEvent::TurnComplete],
+        secret: secret.map(str::to_string),
+        max_retries: 2,
+    }
+}
+
+fn error(codex_error_info: Option<CodexErrorInfo>) -> EventMsg {
+    EventMsg::Error(ErrorEvent {
+        message: "boom".to_string(),
+        codex_error_info,
+    })
+}
+
+#[test]
+fn signature_is_hex_hmac_sha256_of_the_body() {
+    assert_eq!(
+        Some("sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8".to_string()),
+        signature("key", "The quick brown fox jumps over the lazy dog")
+    );
+}
+
+#[test]
+fn errors_map_to_budget_and_failure_events() {
+    let event = |msg: EventMsg| webhook_event(&msg).map(|(event, _)| event);
+
+    assert_eq!(
+        Some(WebhookEvent::BudgetExceeded),
+        event(error(Some(CodexErrorInfo::SessionBudgetExceeded)))
+    );
+    assert_eq!(
+        Some(WebhookEvent::RunFailed),
+        event(error(Some(CodexErrorInfo::UsageLimitExceeded)))
+    );
+    assert_eq!(Some(WebhookEvent::RunFailed), event(error(None)));
+    assert_eq!(
+        None,
+        event(error(Some(CodexErrorInfo::ThreadRollbackFailed)))
+    );
+}
+
+#[tokio::test]
+async fn deliver_retries_server_errors_with_a_signed_body() {
+    let server = MockServer::start().await;
+    let body = r#"{"event":"turn_complete"}"#;
+    let expected_signature = signature("secret", body).unwrap_or_default();
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(header(SIGNATURE_HEADER, expected_signature.as_str()))
+        .and(header(EVENT_HEADER, "turn_complete"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    let delivered = deliver(
+        &reqwest::Client::new(),
+        &target(server.uri(), Some("secret")),
+        WebhookEvent::TurnComplete,
+        body,
+        Duration::ZERO,
+    )
+    .await;
+
+    assert!(delivered);
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert_eq!(2, requests.len());
+    assert_eq!(body.as_bytes(), requests[1].body.as_slice());
+}
+
+#[tokio::test]
+async fn deliver_gives_up_on_client_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let delivered = deliver(
+        &reqwest::Client::new(),
+        &target(server.uri(), /*secret*/ None),
+        WebhookEvent::TurnComplete,
+        "{}",
+        Duration::ZERO,
+    )
+    .await;
+
+    assert!(!delivered);
+    assert_eq!(
+        1,
+        server.received_requests().await.unwrap_or_default().len()
+    );
+}
@@ -0,0 +1,433 @@
+//! Notices when a resumed session's workspace moved on without it.
+//!
+//! A session can sit for days before it is resumed.  By then the branch may
+//! have new commits and the files the conversation read or patched may have
+//! been edited, so the copies in the transcript are stale.  On resume the
+//! recorded history is compared with the workspace, and what changed since the
+//! session was last active goes to the model along with the current text of
+//! the changed files.  Files are read through the environment's file system
+//! under the session's sandbox, and only files inside the workspace roots are
+//! considered.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use codex_apply_patch::Hunk;
+use codex_apply_patch::parse_patch;
+use codex_exec_server::ExecutorFileSystem;
+use codex_exec_server::FileSystemSandboxContext;
+use codex_git_utils::GitLogRequest;
+use codex_git_utils::git_log;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::parse_command::ParsedCommand;
+use codex_protocol::protocol::RolloutItem;
+use codex_shell_command::parse_command::parse_command;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_path_uri::PathUri;
+use serde_json::Value;
+
+/// New commits listed for the model; the rest are counted.
+const MAX_REPORTED_COMMITS: usize = 20;
+
+/// Changed files listed for the model; the rest are counted.
+const MAX_REPORTED_FILES: usize = 50;
+
+/// Changed files larger than this are listed without their contents.
+const MAX_REFRESHED_FILE_BYTES: u64 = 16 * 1024;
+
+/// Contents attached across all changed files.
+const MAX_REFRESHED_TOTAL_BYTES: u64 = 64 * 1024;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct WorkspaceChanges {
+    pub(crate) commits: Vec<NewCommit>,
+    /// True when more commits than [`MAX_REPORTED_COMMITS`] were found.
+    pub(crate) commits_truncated: bool,
+    pub(crate) files: Vec<StaleFile>,
+    /// Changed files beyond [`MAX_REPORTED_FILES`].
+    pub(crate) omitted_files: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NewCommit {
+    pub(crate) short_sha: String,
+    pub(crate) subject: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct StaleFile {
+    /// Path relative to the session cwd when it is inside it.
+    pub(crate) path: String,
+    pub(crate) change: StaleFileChange,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum StaleFileChange {
+    /// Edited since the session was last active; `contents` is `None` when the
+    /// file is too large or not text.
+    Modified {
+        contents: Option<String>,
+    },
+    Deleted,
+}
+
+/// The local workspace a resumed session ran in and how its files may be read.
+pub(crate) struct Workspace {
+    pub(crate) cwd: AbsolutePathBuf,
+    /// Files outside these roots are never checked.  Empty means just `cwd`.
+    pub(crate) roots: Vec<AbsolutePathBuf>,
+    pub(crate) file_system: Arc<dyn ExecutorFileSystem>,
+    pub(crate) sandbox: FileSystemSandboxContext,
+}
+
+impl Workspace {
+    fn contains(&self, path: &AbsolutePathBuf) -> bool {
+        if self.roots.is_empty() {
+            return path.as_path().starts_with(&self.cwd);
+        }
+        self.roots
+            .iter()
+            .any(|root| path.as_path().starts_with(root))
+    }
+}
+
+/// Compares `workspace` with the recorded `history` of a session last active
+/// at `last_active`.
+pub(crate) async fn detect(
+    workspace: &Workspace,
+    history: &[RolloutItem],
+    last_active: SystemTime,
+) -> Option<WorkspaceChanges> {
+    let mut changes = WorkspaceChanges::default();
+    if let Some(session) = recorded_session(history) {
+        let cwd = workspace.cwd.to_path_buf();
+        match tokio::task::spawn_blocking(move || new_commits(&cwd, &session, last_active)).await {
+            Ok((commits, truncated)) => {
+                changes.commits = commits;
+                changes.commits_truncated = truncated;
+            }
+            Err(err) => tracing::warn!(error = %err, "listing new commits failed"),
+        }
+    }
+    let context = context_files(workspace.cwd.as_path(), history);
+    let mut files = stale_files(workspace, &context, last_active).await;
+    if files.len() > MAX_REPORTED_FILES {
+        changes.omitted_files = files.len() - MAX_REPORTED_FILES;
+        files.truncate(MAX_REPORTED_FILES);
+    }
+    changes.files = files;
+    if changes.commits.is_empty() && changes.files.is_empty() {
+        return None;
+    }
+    Some(changes)
+}
+
+impl WorkspaceChanges {
+    /// One-line summary for the user.
+    pub(crate) fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.commits.is_empty() {
+            let more = if self.commits_truncated { "+" } else { "" };
+            parts.push(format!("{}{more} new commit(s)", self.commits.len()));
+        }
+        let files = self.files.len() + self.omitted_files;
+        if files > 0 {
+            parts.push(format!("{files} changed file(s) from this conversation"));
+        }
+        parts.join(", ")
+    }
+
+    /// Text for the model: what changed, then the current contents of the
+    /// changed files that fit the budget.
+    pub(crate) fn render(&self) -> String {
+        let mut out = String::new();
+        if !self.commits.is_empty() {
+            out.push_str("New commits:\n");
+            for commit in &self.commits {
+                let _ = writeln!(out, "- {} {}", commit.short_sha, commit.subject);
+            }
+            if self.commits_truncated {
+                out.push_str("- ... and older ones; run `git log` to see them\n");
+            }
+        }
+        if !self.files.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str("Changed files you have seen in this conversation:\n");
+            for file in &self.files {
+                let note = match &file.change {
+                    StaleFileChange::Modified { contents: Some(_) } => {
+                        "modified, current contents below"
+                    }
+                    StaleFileChange::Modified { contents: None } => "modified, re-read it",
+                    StaleFileChange::Deleted => "deleted",
+                };
+                let _ = writeln!(out, "- {} ({note})", file.path);
+            }
+            if self.omitted_files > 0 {
+                let _ = writeln!(out, "- ... and {} more", self.omitted_files);
+            }
+        }
+        for file in &self.files {
+            if let StaleFileChange::Modified {
+                contents: Some(contents),
+            } = &file.change
+            {
+                let _ = write!(
+                    out,
+                    "\n<file path=\"{}\">\n{}\n</file>\n",
+                    file.path,
+                    contents.trim_end_matches('\n')
+                );
+            }
+        }
+        out.trim_end().to_string()
+    }
+}
+
+/// What the session recorded about itself when it started.
+struct RecordedSession {
+    head: String,
+    /// Unix timestamp of the session start.
+    started_at: Option<i64>,
+}
+
+fn recorded_session(history: &[RolloutItem]) -> Option<RecordedSession> {
+    history.iter().find_map(|item| match item {
+        RolloutItem::SessionMeta(meta_line) => Some(RecordedSession {
+            head: meta_line.git.as_ref()?.commit_hash.as_ref()?.0.clone(),
+            started_at: chrono::DateTime::parse_from_rfc3339(&meta_line.meta.timestamp)
+                .ok()
+                .map(|timestamp| timestamp.timestamp()),
+        }),
+        _ => None,
+    })
+}
+
+/// Commits reachable from HEAD but not from the session's starting commit.
+/// Commits authored while the session was running are its own work, which the
+/// model already knows about, so they are left out.
+fn new_commits(
+    cwd: &Path,
+    session: &RecordedSession,
+    last_active: SystemTime,
+) -> (Vec<NewCommit>, bool) {
+    let request = GitLogRequest {
+        revision_range: Some(format!("{}..HEAD", session.head)),
+        paths: Vec::new(),
+        max_commits: MAX_REPORTED_COMMITS,
+        max_files_per_commit: 0,
+    };
+    let log = match git_log(cwd, &request) {
+        Ok(log) => log,
+        Err(err) => {
+            tracing::debug!(error = %err, "could not list commits since the session started");
+            return (Vec::new(), false);
+        }
+    };
+    let last_active = unix_seconds(last_active);
+    let during_session = |author_time: i64| {
+        session
+            .started_at
+            .is_some_and(|started_at| (started_at..=last_active).contains(&author_time))
+    };
+    let commits = log
+        .commits
+        .into_iter()
+        .filter(|commit| !during_session(commit.author_time))
+        .map(|commit| NewCommit {
+            short_sha: commit.sha.chars().take(7).collect(),
+            subject: commit.subject,
+        })
+        .collect();
+    (commits, log.truncated)
+}
+
+/// Files the conversation read or patched, resolved against the directory the
+/// tool ran in.  The flag is true for files the session wrote, which are known
+/// to have existed when it ended.
+fn context_files(cwd: &Path, history: &[RolloutItem]) -> BTreeMap<PathBuf, bool> {
+    let mut files = BTreeMap::new();
+    let mut add = |base: &Path, path: &Path, written: bool| {
+        let entry = files.entry(base.join(path)).or_insert(false);
+        *entry |= written;
+    };
+    for item in history {
+        let RolloutItem::ResponseItem(item) = item else {
+            continue;
+        };
+        match item {
+            ResponseItem::CustomToolCall { name, input, .. } if name == "apply_patch" => {
+                for path in patched_paths(input) {
+                    add(cwd, &path, true);
+                }
+            }
+            ResponseItem::FunctionCall {
+                name, arguments, ..
+            } => {
+                let Ok(arguments) = serde_json::from_str::<Value>(arguments) else {
+                    continue;
+                };
+                let base = arguments
+                    .get("workdir")
+                    .and_then(Value::as_str)
+                    .map_or_else(|| cwd.to_path_buf(), |workdir| cwd.join(workdir));
+                if name == "apply_patch"
+                    && let Some(input) = arguments.get("input").and_then(Value::as_str)
+                {
+                    for path in patched_paths(input) {
+                        add(&base, &path, true);
+                    }
+                }
+                let command = match arguments.get("cmd").or_else(|| arguments.get("command")) {
+                    Some(Value::String(script)) => {
+                        vec!["bash".to_string(), "-lc".to_string(), script.clone()]
+                    }
+                    Some(Value::Array(argv)) => argv
+                        .iter()
+                        .filter_map(|arg| arg.as_str().map(str::to_string))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                for path in read_paths(&command) {
+                    add(&base, &path, false);
+                }
+                if let Some(path) = arguments.get("path").and_then(Value::as_str) {
+                    add(&base, Path::new(path), false);
+                }
+            }
+            ResponseItem::LocalShellCall {
+                action: LocalShellAction::Exec(exec),
+                ..
+            } => {
+                let base = exec
+                    .working_directory
+                    .as_deref()
+                    .map_or_else(|| cwd.to_path_buf(), |workdir| cwd.join(workdir));
+                for path in read_paths(&exec.command) {
+                    add(&base, &path, false);
+                }
+            }
+            _ => {}
+        }
+    }
+    files
+}
+
+/// Files an `apply_patch` input added or updated, at their final paths.
+fn patched_paths(input: &str) -> Vec<PathBuf> {
+    let Ok(patch) = parse_patch(input) else {
+        return Vec::new();
+    };
+    patch
+        .hunks
+        .into_iter()
+        .filter_map(|hunk| match hunk {
+            Hunk::AddFile { path, .. } => Some(path),
+            Hunk::UpdateFile {
+                path, move_path, ..
+            } => Some(move_path.unwrap_or(path)),
+            Hunk::DeleteFile { .. } => None,
+        })
+        .collect()
+}
+
+fn read_paths(command: &[String]) -> Vec<PathBuf> {
+    if command.is_empty() {
+        return Vec::new();
+    }
+    parse_command(command)
+        .into_iter()
+        .filter_map(|parsed| match parsed {
+            ParsedCommand::Read { path, .. } => Some(path),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Context files inside the workspace edited after `last_active`, and files
+/// the session wrote that no longer exist.  A read path that is missing is
+/// skipped, since the read may have failed in the first place; so is any file
+/// the sandbox does not let the session read.
+async fn stale_files(
+    workspace: &Workspace,
+    files: &BTreeMap<PathBuf, bool>,
+    last_active: SystemTime,
+) -> Vec<StaleFile> {
+    let last_active_ms = unix_millis(last_active);
+    let mut budget = MAX_REFRESHED_TOTAL_BYTES;
+    let mut stale = Vec::new();
+    for (path, written) in files {
+        let Ok(path) = AbsolutePathBuf::from_absolute_path(path) else {
+            continue;
+        };
+        if !workspace.contains(&path) {
+            continue;
+        }
+        let display = path
+            .as_path()
+            .strip_prefix(&workspace.cwd)
+            .unwrap_or(path.as_path())
+            .to_string_lossy()
+            .into_owned();
+        let uri = PathUri::from_abs_path(&path);
+        let metadata = match workspace
+            .file_system
+            .get_metadata(&uri, Some(&workspace.sandbox))
+            .await
+        {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound && *written => {
+                stale.push(StaleFile {
+                    path: display,
+                    change: StaleFileChange::Deleted,
+                });
+                continue;
+            }
+            Err(_) => continue,
+        };
+        if !metadata.is_file || metadata.modified_at_ms <= last_active_ms {
+            continue;
+        }
+        let contents = if metadata.size <= MAX_REFRESHED_FILE_BYTES && metadata.size <= budget {
+            workspace
+                .file_system
+                .read_file_text(&uri, Some(&workspace.sandbox))
+                .await
+                .ok()
+        } else {
+            None
+        };
+        if let Some(contents) = &contents {
+            budget = budget.saturating_sub(contents.len() as u64);
+        }
+        stale.push(StaleFile {
+            path: display,
+            change: StaleFileChange::Modified { contents },
+        });
+    }
+    stale
+}
+
+fn unix_millis(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |duration| {
+        i64::try_from(duration.as_millis()).unwrap_or(i64::MAX)
+    })
+}
+
+fn unix_seconds(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |duration| {
+        i64::try_from(duration.as_secs()).unwrap_or(i64::MAX)
+    })
+}
+
+#[cfg(test)]
+#[path = "workspace_reconciliation_tests.rs"]
+mod tests;
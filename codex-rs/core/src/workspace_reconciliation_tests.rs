@@ -0,0 +1,225 @@
+use std::time::Duration;
+
+use codex_exec_server::LOCAL_FS;
+use codex_protocol::models::PermissionProfile;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use super::*;
+
+fn workspace(cwd: &Path, roots: &[&Path], permissions: PermissionProfile) -> Workspace {
+    let cwd = AbsolutePathBuf::from_absolute_path(cwd).expect("absolute cwd");
+    Workspace {
+        sandbox: FileSystemSandboxContext::from_permission_profile_with_cwd(
+            permissions,
+            PathUri::from_abs_path(&cwd),
+        ),
+        cwd,
+        roots: roots
+            .iter()
+            .map(|root| AbsolutePathBuf::from_absolute_path(root).expect("absolute root"))
+            .collect(),
+        file_system: LOCAL_FS.clone(),
+    }
+}
+
+fn unsandboxed(cwd: &Path) -> Workspace {
+    workspace(cwd, &[cwd], PermissionProfile::Disabled)
+}
+
+fn response_item(value: Value) -> RolloutItem {
+    RolloutItem::ResponseItem(
+        serde_json::from_value(value).expect("response item should deserialize"),
+    )
+}
+
+fn function_call(name: &str, arguments: Value) -> RolloutItem {
+    response_item(json!({
+        "type": "function_call",
+        "name": name,
+        "arguments": arguments.to_string(),
+        "call_id": "call",
+    }))
+}
+
+#[test]
+fn context_files_come_from_patches_reads_and_path_arguments() {
+    let cwd = Path::new("/repo");
+    let history = vec![
+        response_item(json!({
+            "type": "custom_tool_call",
+            "call_id": "call",
+            "name": "apply_patch",
+            "input": "*** Begin Patch\n*** Add File: src/new.rs\n+fn main() {}\n*** Update File: src/old.rs\n*** Move to: src/moved.rs\n@@\n-a\n+b\n*** Delete File: src/gone.rs\n*** End Patch",
+        })),
+        function_call("exec_command", json!({ "cmd": "cat README.md" })),
+        function_call(
+            "shell",
+            json!({ "command": ["cat", "lib.rs"], "workdir": "crates/core" }),
+        ),
+        function_call("view_image", json!({ "path": "docs/shot.png" })),
+        function_call("exec_command", json!({ "cmd": "cargo test" })),
+    ];
+
+    assert_eq!(
+        context_files(cwd, &history),
+        BTreeMap::from([
+            (PathBuf::from("/repo/README.md"), false),
+            (PathBuf::from("/repo/crates/core/lib.rs"), false),
+            (PathBuf::from("/repo/docs/shot.png"), false),
+            (PathBuf::from("/repo/src/moved.rs"), true),
+            (PathBuf::from("/repo/src/new.rs"), true),
+        ])
+    );
+}
+
+#[tokio::test]
+async fn stale_files_report_later_edits_and_deleted_writes() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let cwd = dir.path();
+    std::fs::write(cwd.join("edited.rs"), "fn edited() {}\n").expect("write edited.rs");
+    std::fs::write(
+        cwd.join("big.txt"),
+        "x".repeat(MAX_REFRESHED_FILE_BYTES as usize + 1),
+    )
+    .expect("write big.txt");
+    let files = BTreeMap::from([
+        (cwd.join("big.txt"), false),
+        (cwd.join("edited.rs"), true),
+        (cwd.join("removed.rs"), true),
+        (cwd.join("never-existed.rs"), false),
+    ]);
+
+    assert_eq!(
+        stale_files(
+            &unsandboxed(cwd),
+            &files,
+            UNIX_EPOCH + Duration::from_secs(1)
+        )
+        .await,
+        vec![
+            StaleFile {
+                path: "big.txt".to_string(),
+                change: StaleFileChange::Modified { contents: None },
+            },
+            StaleFile {
+                path: "edited.rs".to_string(),
+                change: StaleFileChange::Modified {
+                    contents: Some("fn edited() {}\n".to_string()),
+                },
+            },
+            StaleFile {
+                path: "removed.rs".to_string(),
+                change: StaleFileChange::Deleted,
+            },
+        ]
+    );
+
+    let later = SystemTime::now() + Duration::from_secs(3600);
+    assert_eq!(
+        stale_files(&unsandboxed(cwd), &files, later).await,
+        vec![StaleFile {
+            path: "removed.rs".to_string(),
+            change: StaleFileChange::Deleted,
+        }]
+    );
+}
+
+#[test]
+fn render_lists_commits_and_files_then_refreshed_contents() {
+    let changes = WorkspaceChanges {
+        commits: vec![NewCommit {
+            short_sha: "abc1234".to_string(),
+            subject: "Rename the config loader".to_string(),
+        }],
+        commits_truncated: true,
+        files: vec![
+            StaleFile {
+                path: "src/config.rs".to_string(),
+                change: StaleFileChange::Modified {
+                    contents: Some("pub fn load() {}\n".to_string()),
+                },
+            },
+            StaleFile {
+                path: "src/old.rs".to_string(),
+                change: StaleFileChange::Deleted,
+            },
+        ],
+        omitted_files: 2,
+    };
+
+    assert_eq!(
+        changes.render(),
+        "New commits:\n\
+         - abc1234 Rename the config loader\n\
+         - ... and older ones; run `git log` to see them\n\
+         \n\
+         Changed files you have seen in this conversation:\n\
+         - src/config.rs (modified, current contents below)\n\
+         - src/old.rs (deleted)\n\
+         - ... and 2 more\n\
+         \n\
+         <file path=\"src/config.rs\">\n\
+         pub fn load() {}\n\
+         </file>"
+    );
+    assert_eq!(
+        changes.summary(),
+        "1+ new commit(s), 4 changed file(s) from this conversation"
+    );
+}
+
+#[tokio::test]
+async fn detect_reports_only_files_changed_after_last_active() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("notes.md"), "notes\n").expect("write notes.md");
+    let history = vec![function_call(
+        "exec_command",
+        json!({ "cmd": "cat notes.md" }),
+    )];
+
+    let later = SystemTime::now() + Duration::from_secs(3600);
+    let workspace = unsandboxed(dir.path());
+    assert_eq!(detect(&workspace, &history, later).await, None);
+    assert_eq!(
+        detect(&workspace, &history, UNIX_EPOCH + Duration::from_secs(1))
+            .await
+            .map(|changes| changes.files),
+        Some(vec![StaleFile {
+            path: "notes.md".to_string(),
+            change: StaleFileChange::Modified {
+                contents: Some("notes\n".to_string()),
+            },
+        }])
+    );
+}
+
+#[tokio::test]
+async fn stale_files_skip_paths_outside_the_workspace_and_unreadable_files() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path().join("repo");
+    std::fs::create_dir_all(&root).expect("create repo");
+    std::fs::write(root.join("inside.rs"), "fn inside() {}\n").expect("write inside.rs");
+    std::fs::write(dir.path().join("secret.txt"), "secret\n").expect("write secret.txt");
+    let files = BTreeMap::from([
+        (root.join("inside.rs"), false),
+        (root.join("../secret.txt"), false),
+    ]);
+    let long_ago = UNIX_EPOCH + Duration::from_secs(1);
+
+    assert_eq!(
+        stale_files(&unsandboxed(&root), &files, long_ago).await,
+        vec![StaleFile {
+            path: "inside.rs".to_string(),
+            change: StaleFileChange::Modified {
+                contents: Some("fn inside() {}\n".to_string()),
+            },
+        }]
+    );
+
+    // Unit tests do not configure the sandboxed file system helper, so a
+    // restricted profile cannot read anything; the files must be skipped
+    // rather than read directly.
+    let sandboxed = workspace(&root, &[&root], PermissionProfile::read_only());
+    assert_eq!(stale_files(&sandboxed, &files, long_ago).await, Vec::new());
+}
@@ -69,6 +69,17 @@ pub struct Cli {
     )]
     pub json: bool,
 
+    /// Suppress streamed output for CI: print a heartbeat line to stderr every 30 seconds and a
+    /// JSON summary line to stdout at the end. The exit code reflects the class of failure.
+    #[arg(
+        long = "quiet",
+        short = 'q',
+        default_value_t = false,
+        global = true,
+        conflicts_with = "json"
+    )]
+    pub quiet: bool,
+
     /// Specifies file where the last message from the agent should be written.
     #[arg(
         long = "output-last-message",
@@ -78,6 +89,19 @@ pub struct Cli {
     )]
     pub last_message_file: Option<PathBuf>,
 
+    /// Write every change the agent makes as a `git apply`-able patch to FILE.
+    #[arg(long = "emit-patch", value_name = "FILE", global = true)]
+    pub emit_patch: Option<PathBuf>,
+
+    /// With --emit-patch, work in a temporary git worktree and leave the checkout untouched.
+    #[arg(
+        long = "patch-only",
+        default_value_t = false,
+        global = true,
+        requires = "emit_patch"
+    )]
+    pub patch_only: bool,
+
     /// Initial instructions for the agent. If not provided as an argument (or
     /// if `-` is used), instructions are read from stdin. If stdin is piped and
     /// a prompt is also provided, stdin is appended as a `<stdin>` block.
@@ -268,15 +292,39 @@ pub struct ReviewArgs {
     #[arg(
         long = "uncommitted",
         default_value_t = false,
-        conflicts_with_all = ["base", "commit", "prompt"]
+        conflicts_with_all = ["staged", "range", "pr", "base", "commit", "prompt"]
     )]
     pub uncommitted: bool,
 
+    /// Review only the changes staged in the index.
+    #[arg(
+        long = "staged",
+        default_value_t = false,
+        conflicts_with_all = ["uncommitted", "range", "pr", "base", "commit", "prompt"]
+    )]
+    pub staged: bool,
+
+    /// Review the changes in a git revision range, such as `main..feature`.
+    #[arg(
+        long = "range",
+        value_name = "A..B",
+        conflicts_with_all = ["uncommitted", "staged", "pr", "base", "commit", "prompt"]
+    )]
+    pub range: Option<String>,
+
+    /// Review a GitHub pull request. Uses the GitHub CLI (`gh`) to fetch it when available.
+    #[arg(
+        long = "pr",
+        value_name = "URL",
+        conflicts_with_all = ["uncommitted", "staged", "range", "base", "commit", "prompt"]
+    )]
+    pub pr: Option<String>,
+
     /// Review changes against the given base branch.
     #[arg(
         long = "base",
         value_name = "BRANCH",
-        conflicts_with_all = ["uncommitted", "commit", "prompt"]
+        conflicts_with_all = ["uncommitted", "staged", "range", "pr", "commit", "prompt"]
     )]
     pub base: Option<String>,
 
@@ -284,7 +332,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "commit",
         value_name = "SHA",
-        conflicts_with_all = ["uncommitted", "base", "prompt"]
+        conflicts_with_all = ["uncommitted", "staged", "range", "pr", "base", "prompt"]
     )]
     pub commit: Option<String>,
 
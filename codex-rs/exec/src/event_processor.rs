@@ -25,7 +25,15 @@ pub(crate) trait EventProcessor {
     /// Handle a local exec warning that is not represented as an app-server notification.
     fn process_warning(&mut self, message: String) -> CodexStatus;
 
+    /// Called periodically while the turn runs; only `--quiet` output prints anything.
+    fn heartbeat(&mut self) {}
+
     fn print_final_output(&mut self) {}
+
+    /// Process exit code for the finished run, or `None` to exit successfully.
+    fn exit_code(&self, error_seen: bool) -> Option<i32> {
+        error_seen.then_some(1)
+    }
 }
 
 pub(crate) fn handle_last_message(last_agent_message: Option<&str>, output_file: &Path) {
@@ -482,7 +482,7 @@ fn reasoning_text(
     }
 }
 
-fn final_message_from_turn_items(items: &[ThreadItem]) -> Option<String> {
+pub(crate) fn final_message_from_turn_items(items: &[ThreadItem]) -> Option<String> {
     items
         .iter()
         .rev()
@@ -498,7 +498,7 @@ fn final_message_from_turn_items(items: &[ThreadItem]) -> Option<String> {
         })
 }
 
-fn blended_total(usage: &ThreadTokenUsage) -> i64 {
+pub(crate) fn blended_total(usage: &ThreadTokenUsage) -> i64 {
     let cached_input = usage.total.cached_input_tokens.max(0);
     let non_cached_input = (usage.total.input_tokens - cached_input).max(0);
     (non_cached_input + usage.total.output_tokens.max(0)).max(0)
@@ -0,0 +1,287 @@
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_app_server_protocol::CodexErrorInfo;
+use codex_app_server_protocol::ServerNotification;
+use codex_app_server_protocol::ThreadItem;
+use codex_app_server_protocol::ThreadTokenUsage;
+use codex_app_server_protocol::TurnError;
+use codex_app_server_protocol::TurnStatus;
+use codex_core::config::Config;
+use codex_protocol::num_format::format_with_separators;
+use codex_protocol::protocol::SessionConfiguredEvent;
+use serde::Serialize;
+
+use crate::event_processor::CodexStatus;
+use crate::event_processor::EventProcessor;
+use crate::event_processor::handle_last_message;
+use crate::event_processor_with_human_output::blended_total;
+use crate::event_processor_with_human_output::final_message_from_turn_items;
+
+/// How often `--quiet` prints a heartbeat line while a turn is running.
+pub(crate) const QUIET_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Exit status for `--quiet` runs, grouped so CI can react to the class of failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum QuietOutcome {
+    Completed,
+    /// The turn failed for a reason not covered by a more specific class.
+    Failed,
+    /// A usage, budget, or context-window limit stopped the turn.
+    LimitExceeded,
+    /// The model provider rejected the credentials.
+    Unauthorized,
+    /// The provider could not be reached or kept failing.
+    Network,
+    Interrupted,
+}
+
+impl QuietOutcome {
+    pub(crate) fn exit_code(self) -> i32 {
+        match self {
+            QuietOutcome::Completed => 0,
+            QuietOutcome::Failed => 1,
+            QuietOutcome::LimitExceeded => 3,
+            QuietOutcome::Unauthorized => 4,
+            QuietOutcome::Network => 5,
+            QuietOutcome::Interrupted => 130,
+        }
+    }
+
+    fn from_error(error: &TurnError) -> Self {
+        match error.codex_error_info {
+            Some(
+                CodexErrorInfo::ContextWindowExceeded
+                | CodexErrorInfo::SessionBudgetExceeded
+                | CodexErrorInfo::UsageLimitExceeded,
+            ) => QuietOutcome::LimitExceeded,
+            Some(CodexErrorInfo::Unauthorized) => QuietOutcome::Unauthorized,
+            Some(
+                CodexErrorInfo::ServerOverloaded
+                | CodexErrorInfo::HttpConnectionFailed { .. }
+                | CodexErrorInfo::ResponseStreamConnectionFailed { .. }
+                | CodexErrorInfo::ResponseStreamDisconnected { .. }
+                | CodexErrorInfo::ResponseTooManyFailedAttempts { .. }
+                | CodexErrorInfo::InternalServerError,
+            ) => QuietOutcome::Network,
+            _ => QuietOutcome::Failed,
+        }
+    }
+}
+
+/// Final `--quiet` summary, printed to stdout as a single JSON line.
+#[derive(Debug, Serialize, PartialEq)]
+pub(crate) struct QuietSummary {
+    pub status: QuietOutcome,
+    pub exit_code: i32,
+    pub elapsed_secs: u64,
+    pub tokens_used: i64,
+    pub commands_run: usize,
+    pub files_changed: usize,
+    pub error: Option<String>,
+    pub last_message: Option<String>,
+}
+
+/// Output for `codex exec --quiet`: no streamed text, a heartbeat line on stderr every
+/// [`QUIET_HEARTBEAT_INTERVAL`], and one JSON summary line on stdout at the end.
+pub(crate) struct EventProcessorWithQuietOutput {
+    started_at: Instant,
+    last_message_path: Option<PathBuf>,
+    current_activity: Option<String>,
+    last_total_token_usage: Option<ThreadTokenUsage>,
+    commands_run: usize,
+    files_changed: usize,
+    last_error: Option<TurnError>,
+    outcome: Option<QuietOutcome>,
+    final_message: Option<String>,
+}
+
+impl EventProcessorWithQuietOutput {
+    pub(crate) fn new(last_message_path: Option<PathBuf>) -> Self {
+        Self {
+            started_at: Instant::now(),
+            last_message_path,
+            current_activity: None,
+            last_total_token_usage: None,
+            commands_run: 0,
+            files_changed: 0,
+            last_error: None,
+            outcome: None,
+            final_message: None,
+        }
+    }
+
+    fn heartbeat_line(&self, elapsed: Duration) -> String {
+        let activity = self.current_activity.as_deref().unwrap_or("thinking");
+        format!(
+            "[codex] {} · {activity} · {} tokens",
+            format_elapsed(elapsed),
+            format_with_separators(self.tokens_used())
+        )
+    }
+
+    fn tokens_used(&self) -> i64 {
+        self.last_total_token_usage
+            .as_ref()
+            .map(blended_total)
+            .unwrap_or(0)
+    }
+
+    fn outcome(&self) -> QuietOutcome {
+        match self.outcome {
+            Some(outcome) => outcome,
+            // The stream ended without a terminal turn status; treat it as a failure.
+            None => self
+                .last_error
+                .as_ref()
+                .map_or(QuietOutcome::Failed, QuietOutcome::from_error),
+        }
+    }
+
+    fn summary(&self, elapsed: Duration) -> QuietSummary {
+        let status = self.outcome();
+        QuietSummary {
+            status,
+            exit_code: status.exit_code(),
+            elapsed_secs: elapsed.as_secs(),
+            tokens_used: self.tokens_used(),
+            commands_run: self.commands_run,
+            files_changed: self.files_changed,
+            error: (status != QuietOutcome::Completed)
+                .then(|| self.last_error.as_ref().map(|error| error.message.clone()))
+                .flatten(),
+            last_message: self.final_message.clone(),
+        }
+    }
+}
+
+impl EventProcessor for EventProcessorWithQuietOutput {
+    fn print_config_summary(
+        &mut self,
+        _: &Config,
+        _: &str,
+        session_configured: &SessionConfiguredEvent,
+    ) {
+        eprintln!(
+            "[codex] session {} · model {}",
+            session_configured.session_id, session_configured.model
+        );
+    }
+
+    fn process_server_notification(&mut self, notification: ServerNotification) -> CodexStatus {
+        match notification {
+            ServerNotification::ItemStarted(notification) => {
+                self.current_activity = activity_label(&notification.item);
+                CodexStatus::Running
+            }
+            ServerNotification::ItemCompleted(notification) => {
+                match &notification.item {
+                    ThreadItem::CommandExecution { .. } => self.commands_run += 1,
+                    ThreadItem::FileChange { changes, .. } => self.files_changed += changes.len(),
+                    ThreadItem::AgentMessage { text, .. } => {
+                        self.final_message = Some(text.clone());
+                    }
+                    _ => {}
+                }
+                self.current_activity = None;
+                CodexStatus::Running
+            }
+            ServerNotification::ThreadTokenUsageUpdated(notification) => {
+                self.last_total_token_usage = Some(notification.token_usage);
+                CodexStatus::Running
+            }
+            ServerNotification::Error(notification) => {
+                if !notification.will_retry {
+                    self.last_error = Some(notification.error);
+                }
+                CodexStatus::Running
+            }
+            ServerNotification::TurnCompleted(notification) => {
+                let outcome = match notification.turn.status {
+                    TurnStatus::Completed => QuietOutcome::Completed,
+                    TurnStatus::Failed => {
+                        if let Some(error) = notification.turn.error {
+                            self.last_error = Some(error);
+                        }
+                        self.last_error
+                            .as_ref()
+                            .map_or(QuietOutcome::Failed, QuietOutcome::from_error)
+                    }
+                    TurnStatus::Interrupted => QuietOutcome::Interrupted,
+                    TurnStatus::InProgress => return CodexStatus::Running,
+                };
+                if outcome == QuietOutcome::Completed {
+                    if let Some(message) =
+                        final_message_from_turn_items(notification.turn.items.as_slice())
+                    {
+                        self.final_message = Some(message);
+                    }
+                } else {
+                    self.final_message = None;
+                }
+                self.outcome = Some(outcome);
+                CodexStatus::InitiateShutdown
+            }
+            _ => CodexStatus::Running,
+        }
+    }
+
+    fn process_warning(&mut self, message: String) -> CodexStatus {
+        eprintln!("[codex] warning: {message}");
+        CodexStatus::Running
+    }
+
+    fn heartbeat(&mut self) {
+        eprintln!("{}", self.heartbeat_line(self.started_at.elapsed()));
+    }
+
+    #[allow(clippy::print_stdout)]
+    fn print_final_output(&mut self) {
+        if self.outcome == Some(QuietOutcome::Completed)
+            && let Some(path) = self.last_message_path.as_deref()
+        {
+            handle_last_message(self.final_message.as_deref(), path);
+        }
+
+        let summary = self.summary(self.started_at.elapsed());
+        match serde_json::to_string(&summary) {
+            Ok(line) => println!("{line}"),
+            Err(err) => eprintln!("Failed to serialize summary: {err}"),
+        }
+    }
+
+    fn exit_code(&self, _error_seen: bool) -> Option<i32> {
+        match self.outcome() {
+            QuietOutcome::Completed => None,
+            outcome => Some(outcome.exit_code()),
+        }
+    }
+}
+
+fn activity_label(item: &ThreadItem) -> Option<String> {
+    match item {
+        ThreadItem::CommandExecution { command, .. } => Some(format!("running `{command}`")),
+        ThreadItem::McpToolCall { server, tool, .. } => Some(format!("calling {server}/{tool}")),
+        ThreadItem::WebSearch(item) => Some(format!("searching \"{}\"", item.query)),
+        ThreadItem::FileChange { .. } => Some("applying patch".to_string()),
+        ThreadItem::CollabAgentToolCall { .. } => Some("coordinating agents".to_string()),
+        _ => None,
+    }
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3600 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+#[cfg(test)]
+#[path = "event_processor_with_quiet_output_tests.rs"]
+mod tests;
@@ -0,0 +1,98 @@
+use super::*;
+use codex_app_server_protocol::ItemCompletedNotification;
+use codex_app_server_protocol::Turn;
+use codex_app_server_protocol::TurnCompletedNotification;
+use codex_app_server_protocol::TurnItemsView;
+use pretty_assertions::assert_eq;
+
+fn turn_completed(status: TurnStatus, error: Option<TurnError>) -> ServerNotification {
+    ServerNotification::TurnCompleted(TurnCompletedNotification {
+        thread_id: "thread-1".to_string(),
+        turn: Turn {
+            id: "turn-1".to_string(),
+            items_view: TurnItemsView::Full,
+            items: Vec::new(),
+            status,
+            error,
+            started_at: None,
+            completed_at: Some(0),
+            duration_ms: None,
+        },
+    })
+}
+
+#[test]
+fn completed_turn_reports_the_last_message_and_exits_successfully() {
+    let mut processor = EventProcessorWithQuietOutput::new(/*last_message_path*/ None);
+    processor.process_server_notification(ServerNotification::ItemCompleted(
+        ItemCompletedNotification {
+            item: ThreadItem::AgentMessage {
+                id: "msg-1".to_string(),
+                text: "all tests pass".to_string(),
+                phase: None,
+                memory_citation: None,
+            },
+            thread_id: "thread-1".to_string(),
+            turn_id: "turn-1".to_string(),
+            completed_at_ms: 0,
+        },
+    ));
+
+    let status = processor
+        .process_server_notification(turn_completed(TurnStatus::Completed, /*error*/ None));
+
+    assert_eq!(status, CodexStatus::InitiateShutdown);
+    assert_eq!(processor.exit_code(/*error_seen*/ false), None);
+    assert_eq!(
+        processor.summary(Duration::from_secs(75)),
+        QuietSummary {
+            status: QuietOutcome::Completed,
+            exit_code: 0,
+            elapsed_secs: 75,
+            tokens_used: 0,
+            commands_run: 0,
+            files_changed: 0,
+            error: None,
+            last_message: Some("all tests pass".to_string()),
+        }
+    );
+}
+
+#[test]
+fn failed_turn_exit_code_reflects_the_error_class() {
+    let mut processor = EventProcessorWithQuietOutput::new(/*last_message_path*/ None);
+
+    processor.process_server_notification(turn_completed(
+        TurnStatus::Failed,
+        Some(TurnError {
+            message: "usage limit reached".to_string(),
+            codex_error_info: Some(CodexErrorInfo::UsageLimitExceeded),
+            additional_details: None,
+        }),
+    ));
+
+    assert_eq!(processor.exit_code(/*error_seen*/ true), Some(3));
+    let summary = processor.summary(Duration::from_secs(1));
+    assert_eq!(summary.status, QuietOutcome::LimitExceeded);
+    assert_eq!(summary.error.as_deref(), Some("usage limit reached"));
+}
+
+#[test]
+fn interrupted_turn_exits_with_130() {
+    let mut processor = EventProcessorWithQuietOutput::new(/*last_message_path*/ None);
+
+    processor
+        .process_server_notification(turn_completed(TurnStatus::Interrupted, /*error*/ None));
+
+    assert_eq!(processor.exit_code(/*error_seen*/ true), Some(130));
+}
+
+#[test]
+fn heartbeat_line_shows_elapsed_activity_and_tokens() {
+    let processor = EventProcessorWithQuietOutput::new(/*last_message_path*/ None);
+
+    assert_eq!(
+        processor.heartbeat_line(Duration::from_secs(65)),
+        "[codex] 1m05s · thinking · 0 tokens"
+    );
+}
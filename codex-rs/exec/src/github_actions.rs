@@ -0,0 +1,246 @@
+//! GitHub Actions integration for `codex exec`.
+//!
+//! When `GITHUB_ACTIONS=true`, exec reports the run to the workflow without extra flags:
+//! - errors and warnings become workflow-command annotations, and changed files are annotated at
+//!   their first changed line;
+//! - `status`, `changed-files`, and `summary` are appended to `$GITHUB_OUTPUT`;
+//! - a markdown job summary is appended to `$GITHUB_STEP_SUMMARY`.
+//!
+//! Workflow commands go to stderr because stdout is reserved for the final message or JSONL. The
+//! runner reads workflow commands from both streams.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs::OpenOptions;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_app_server_protocol::PatchApplyStatus;
+use codex_app_server_protocol::ServerNotification;
+use codex_app_server_protocol::ThreadItem;
+use codex_app_server_protocol::TurnStatus;
+
+pub(crate) struct GithubActionsReporter {
+    cwd: PathBuf,
+    output_path: Option<PathBuf>,
+    step_summary_path: Option<PathBuf>,
+    changed_files: BTreeSet<String>,
+    final_message: Option<String>,
+    status: Option<TurnStatus>,
+}
+
+impl GithubActionsReporter {
+    /// Returns a reporter when running inside a GitHub Actions job.
+    pub(crate) fn from_env(cwd: &Path) -> Option<Self> {
+        Self::from_vars(cwd, |name| std::env::var(name).ok())
+    }
+
+    fn from_vars(cwd: &Path, var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        if var("GITHUB_ACTIONS").as_deref() != Some("true") {
+            return None;
+        }
+        let path_var = |name| {
+            var(name)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+        Some(Self {
+            cwd: cwd.to_path_buf(),
+            output_path: path_var("GITHUB_OUTPUT"),
+            step_summary_path: path_var("GITHUB_STEP_SUMMARY"),
+            changed_files: BTreeSet::new(),
+            final_message: None,
+            status: None,
+        })
+    }
+
+    /// Emits annotations for `notification` and records what the final outputs need.
+    pub(crate) fn observe(&mut self, notification: &ServerNotification) {
+        for annotation in self.annotations_for(notification) {
+            eprintln!("{annotation}");
+        }
+    }
+
+    fn annotations_for(&mut self, notification: &ServerNotification) -> Vec<String> {
+        match notification {
+            ServerNotification::Error(notification) if !notification.will_retry => {
+                vec![workflow_command("error", &[], &notification.error.message)]
+            }
+            ServerNotification::Warning(notification) => {
+                vec![workflow_command("warning", &[], &notification.message)]
+            }
+            ServerNotification::ConfigWarning(notification) => {
+                vec![workflow_command("warning", &[], &notification.summary)]
+            }
+            ServerNotification::ItemCompleted(notification) => match &notification.item {
+                ThreadItem::FileChange {
+                    changes, status, ..
+                } => changes
+                    .iter()
+                    .filter_map(|change| {
+                        let path = self.relative_path(&change.path);
+                        match status {
+                            PatchApplyStatus::Completed => {
+                                self.changed_files.insert(path.clone());
+                                let line = first_changed_line(&change.diff).unwrap_or(1);
+                                Some(workflow_command(
+                                    "notice",
+                                    &[("file", path), ("line", line.to_string())],
+                                    "Changed by Codex",
+                                ))
+                            }
+                            PatchApplyStatus::Failed => Some(workflow_command(
+                                "error",
+                                &[("file", path)],
+                                "Codex failed to apply a patch to this file",
+                            )),
+                            PatchApplyStatus::Declined | PatchApplyStatus::InProgress => None,
+                        }
+                    })
+                    .collect(),
+                ThreadItem::AgentMessage { text, .. } => {
+                    self.final_message = Some(text.clone());
+                    Vec::new()
+                }
+                _ => Vec::new(),
+            },
+            ServerNotification::TurnCompleted(notification) => {
+                self.status = Some(notification.turn.status.clone());
+                match (&notification.turn.status, &notification.turn.error) {
+                    (TurnStatus::Failed, Some(error)) => {
+                        vec![workflow_command("error", &[], &error.message)]
+                    }
+                    _ => Vec::new(),
+                }
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Writes step outputs and the job summary once the run has finished.
+    pub(crate) fn finish(&self) {
+        if let Some(path) = &self.output_path
+            && let Err(err) = append(path, &self.step_outputs())
+        {
+            eprintln!(
+                "Failed to write GitHub Actions outputs to {}: {err}",
+                path.display()
+            );
+        }
+        if let Some(path) = &self.step_summary_path
+            && let Err(err) = append(path, &self.job_summary())
+        {
+            eprintln!(
+                "Failed to write GitHub Actions job summary to {}: {err}",
+                path.display()
+            );
+        }
+    }
+
+    fn status_label(&self) -> &'static str {
+        match self.status {
+            Some(TurnStatus::Completed) => "completed",
+            Some(TurnStatus::Interrupted) => "interrupted",
+            Some(TurnStatus::Failed) | Some(TurnStatus::InProgress) | None => "failed",
+        }
+    }
+
+    fn step_outputs(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "status={}", self.status_label());
+        let changed_files = self
+            .changed_files
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n");
+        push_multiline_output(&mut out, "changed-files", &changed_files);
+        push_multiline_output(
+            &mut out,
+            "summary",
+            self.final_message.as_deref().unwrap_or_default(),
+        );
+        out
+    }
+
+    fn job_summary(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "## Codex\n");
+        let _ = writeln!(out, "**Status:** {}\n", self.status_label());
+        if !self.changed_files.is_empty() {
+            let _ = writeln!(out, "**Changed files**\n");
+            for path in &self.changed_files {
+                let _ = writeln!(out, "- `{path}`");
+            }
+            out.push('\n');
+        }
+        if let Some(message) = &self.final_message {
+            let _ = writeln!(out, "{message}\n");
+        }
+        out
+    }
+
+    /// Annotations need paths relative to the repository checkout.
+    fn relative_path(&self, path: &str) -> String {
+        Path::new(path)
+            .strip_prefix(&self.cwd)
+            .map(|relative| relative.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| path.to_string())
+    }
+}
+
+fn append(path: &Path, contents: &str) -> std::io::Result<()> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(contents.as_bytes())
+}
+
+/// Appends `name` using the `name<<DELIMITER` form so values may span lines.
+fn push_multiline_output(out: &mut String, name: &str, value: &str) {
+    let mut delimiter = "CODEX_OUTPUT_EOF".to_string();
+    while value.lines().any(|line| line == delimiter) {
+        delimiter.push('_');
+    }
+    let _ = writeln!(out, "{name}<<{delimiter}\n{value}\n{delimiter}");
+}
+
+/// Formats a `::command key=value,...::message` workflow command with GitHub's escaping rules.
+fn workflow_command(command: &str, properties: &[(&str, String)], message: &str) -> String {
+    let properties = properties
+        .iter()
+        .map(|(key, value)| format!("{key}={}", escape_property(value)))
+        .collect::<Vec<_>>()
+        .join(",");
+    let separator = if properties.is_empty() { "" } else { " " };
+    format!(
+        "::{command}{separator}{properties}::{}",
+        escape_data(message)
+    )
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Returns the new-file line number of the first hunk in a unified diff.
+fn first_changed_line(diff: &str) -> Option<usize> {
+    diff.lines().find_map(|line| {
+        let new_range = line.strip_prefix("@@ ")?.split_whitespace().nth(1)?;
+        let start = new_range.strip_prefix('+')?.split(',').next()?;
+        start.parse::<usize>().ok().map(|line| line.max(1))
+    })
+}
+
+#[cfg(test)]
+#[path = "github_actions_tests.rs"]
+mod tests;
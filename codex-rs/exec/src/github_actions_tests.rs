@@ -0,0 +1,107 @@
+use super::*;
+use codex_app_server_protocol::FileUpdateChange;
+use codex_app_server_protocol::ItemCompletedNotification;
+use codex_app_server_protocol::PatchChangeKind;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn reporter(vars: &[(&str, &str)]) -> Option<GithubActionsReporter> {
+    GithubActionsReporter::from_vars(Path::new("/work/repo"), |name| {
+        vars.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.to_string())
+    })
+}
+
+fn file_change(path: &str, diff: &str, status: PatchApplyStatus) -> ServerNotification {
+    ServerNotification::ItemCompleted(ItemCompletedNotification {
+        item: ThreadItem::FileChange {
+            id: "patch-1".to_string(),
+            changes: vec![FileUpdateChange {
+                path: path.to_string(),
+                kind: PatchChangeKind::Update { move_path: None },
+                diff: diff.to_string(),
+            }],
+            status,
+        },
+        thread_id: "thread-1".to_string(),
+        turn_id: "turn-1".to_string(),
+        completed_at_ms: 0,
+    })
+}
+
+#[test]
+fn reporter_is_only_enabled_inside_github_actions() {
+    assert!(reporter(&[]).is_none());
+    assert!(reporter(&[("GITHUB_ACTIONS", "false")]).is_none());
+    assert!(reporter(&[("GITHUB_ACTIONS", "true")]).is_some());
+}
+
+#[test]
+fn workflow_commands_escape_messages_and_properties() {
+    assert_eq!(
+        workflow_command(
+            "error",
+            &[("file", "src/a,b:c.rs".to_string())],
+            "50% done\nnext"
+        ),
+        "::error file=src/a%2Cb%3Ac.rs::50%25 done%0Anext"
+    );
+    assert_eq!(
+        workflow_command("warning", &[], "careful"),
+        "::warning::careful"
+    );
+}
+
+#[test]
+fn applied_patches_are_annotated_at_their_first_changed_line() {
+    let mut reporter = reporter(&[("GITHUB_ACTIONS", "true")]).expect("reporter");
+
+    let annotations = reporter.annotations_for(&file_change(
+        "/work/repo/src/lib.rs",
+        "@@ -10,3 +12,4 @@ fn main() {\n-old\n+new\n",
+        PatchApplyStatus::Completed,
+    ));
+
+    assert_eq!(
+        annotations,
+        vec!["::notice file=src/lib.rs,line=12::Changed by Codex".to_string()]
+    );
+    assert_eq!(
+        reporter.changed_files,
+        BTreeSet::from(["src/lib.rs".to_string()])
+    );
+}
+
+#[test]
+fn finish_writes_step_outputs_and_job_summary() {
+    let dir = tempdir().expect("tempdir");
+    let output = dir.path().join("output");
+    let summary = dir.path().join("summary");
+    let mut reporter = reporter(&[
+        ("GITHUB_ACTIONS", "true"),
+        ("GITHUB_OUTPUT", output.to_str().expect("utf-8 path")),
+        ("GITHUB_STEP_SUMMARY", summary.to_str().expect("utf-8 path")),
+    ])
+    .expect("reporter");
+    reporter.observe(&file_change(
+        "/work/repo/README.md",
+        "@@ -1 +1 @@\n-a\n+b\n",
+        PatchApplyStatus::Completed,
+    ));
+    reporter.final_message = Some("Updated the README.".to_string());
+    reporter.status = Some(TurnStatus::Completed);
+
+    reporter.finish();
+
+    assert_eq!(
+        std::fs::read_to_string(&output).expect("read outputs"),
+        "status=completed\n\
+         changed-files<<CODEX_OUTPUT_EOF\nREADME.md\nCODEX_OUTPUT_EOF\n\
+         summary<<CODEX_OUTPUT_EOF\nUpdated the README.\nCODEX_OUTPUT_EOF\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(&summary).expect("read summary"),
+        "## Codex\n\n**Status:** completed\n\n**Changed files**\n\n- `README.md`\n\nUpdated the README.\n\n"
+    );
+}
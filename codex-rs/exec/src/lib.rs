@@ -1,6 +1,7 @@
 // - In the default output mode, it is paramount that the only thing written to
 //   stdout is the final message (if any).
 // - In --json mode, stdout must be valid JSONL, one event per line.
+// - In --quiet mode, stdout holds only the final JSON summary line.
 // For both modes, any other output must be written to stderr.
 #![deny(clippy::print_stdout)]
 
@@ -8,7 +9,10 @@ mod cli;
 mod event_processor;
 mod event_processor_with_human_output;
 pub(crate) mod event_processor_with_jsonl_output;
+mod event_processor_with_quiet_output;
 pub(crate) mod exec_events;
+mod github_actions;
+mod patch_artifact;
 
 pub use cli::Cli;
 pub use cli::Command;
@@ -105,6 +109,8 @@ use event_processor_with_human_output::EventProcessorWithHumanOutput;
 pub use event_processor_with_jsonl_output::CodexStatus;
 pub use event_processor_with_jsonl_output::CollectedThreadEvents;
 pub use event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+use event_processor_with_quiet_output::EventProcessorWithQuietOutput;
+use event_processor_with_quiet_output::QUIET_HEARTBEAT_INTERVAL;
 pub use exec_events::AgentMessageItem;
 pub use exec_events::CollabAgentState;
 pub use exec_events::CollabAgentStatus;
@@ -138,6 +144,8 @@ pub use exec_events::TurnFailedEvent;
 pub use exec_events::TurnStartedEvent;
 pub use exec_events::Usage;
 pub use exec_events::WebSearchItem;
+use github_actions::GithubActionsReporter;
+use patch_artifact::PatchArtifact;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::future::Future;
@@ -215,7 +223,9 @@ struct ExecRunArgs {
     model_provider: Option<String>,
     oss: bool,
     output_schema_path: Option<PathBuf>,
+    patch_artifact: Option<PatchArtifact>,
     prompt: Option<String>,
+    quiet_mode: bool,
     skip_git_repo_check: bool,
     stderr_with_ansi: bool,
 }
@@ -258,7 +268,10 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
         removed_full_auto,
         color,
         last_message_file,
+        emit_patch,
+        patch_only,
         json: json_mode,
+        quiet: quiet_mode,
         prompt,
         output_schema: output_schema_path,
         config_overrides,
@@ -419,6 +432,19 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
         None // No model specified, will use the default.
     };
 
+    let patch_artifact = match emit_patch {
+        Some(output) => Some(PatchArtifact::start(
+            output,
+            config_cwd.as_path(),
+            patch_only,
+        )?),
+        None => None,
+    };
+    let resolved_cwd = patch_artifact
+        .as_ref()
+        .and_then(PatchArtifact::scratch_cwd)
+        .or(resolved_cwd);
+
     let overrides = ConfigOverrides {
         model,
         review_model: None,
@@ -590,7 +616,9 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
         model_provider,
         oss,
         output_schema_path,
+        patch_artifact,
         prompt,
+        quiet_mode,
         skip_git_repo_check,
         stderr_with_ansi,
     })
@@ -688,18 +716,25 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
         model_provider,
         oss,
         output_schema_path,
+        patch_artifact,
         prompt,
+        quiet_mode,
         skip_git_repo_check,
         stderr_with_ansi,
     } = args;
 
-    let mut event_processor: Box<dyn EventProcessor> = match json_mode {
-        true => Box::new(EventProcessorWithJsonOutput::new(last_message_file.clone())),
-        _ => Box::new(EventProcessorWithHumanOutput::create_with_ansi(
+    let mut event_processor: Box<dyn EventProcessor> = if json_mode {
+        Box::new(EventProcessorWithJsonOutput::new(last_message_file.clone()))
+    } else if quiet_mode {
+        Box::new(EventProcessorWithQuietOutput::new(
+            last_message_file.clone(),
+        ))
+    } else {
+        Box::new(EventProcessorWithHumanOutput::create_with_ansi(
             stderr_with_ansi,
             &config,
             last_message_file.clone(),
-        )),
+        ))
     };
     if oss {
         // We're in the oss section, so provider_id should be Some
@@ -719,6 +754,7 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
     }
 
     let default_cwd = config.cwd.to_path_buf();
+    let mut github_actions = GithubActionsReporter::from_env(&default_cwd);
     let default_approval_policy = config.permissions.approval_policy.value();
     let default_effort = config.model_reasoning_effort.clone();
 
@@ -869,6 +905,7 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
     // is using.
     event_processor.print_config_summary(&config, &prompt_summary, &session_configured);
     if !json_mode
+        && !quiet_mode
         && let Some(message) =
             codex_core::config::system_bwrap_warning(config.permissions.permission_profile())
     {
@@ -959,8 +996,16 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
     let mut error_seen = false;
     let mut interrupt_channel_open = true;
     let primary_thread_id_for_requests = primary_thread_id.to_string();
+    let mut heartbeat = tokio::time::interval_at(
+        tokio::time::Instant::now() + QUIET_HEARTBEAT_INTERVAL,
+        QUIET_HEARTBEAT_INTERVAL,
+    );
     loop {
         let server_event = tokio::select! {
+            _ = heartbeat.tick(), if quiet_mode => {
+                event_processor.heartbeat();
+                continue;
+            }
             maybe_interrupt = interrupt_rx.recv(), if interrupt_channel_open => {
                 if maybe_interrupt.is_none() {
                     interrupt_channel_open = false;
@@ -1027,6 +1072,9 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
                     )
                     .await;
 
+                    if let Some(github_actions) = github_actions.as_mut() {
+                        github_actions.observe(&notification);
+                    }
                     match event_processor.process_server_notification(notification) {
                         CodexStatus::Running => {}
                         CodexStatus::InitiateShutdown => {
@@ -1056,8 +1104,17 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
         warn!("in-process app-server shutdown failed: {err}");
     }
     event_processor.print_final_output();
-    if error_seen {
-        std::process::exit(1);
+    if let Some(github_actions) = &github_actions {
+        github_actions.finish();
+    }
+    if let Some(patch_artifact) = patch_artifact
+        && let Err(err) = patch_artifact.finish()
+    {
+        eprintln!("Failed to write --emit-patch output: {err:#}");
+        error_seen = true;
+    }
+    if let Some(exit_code) = event_processor.exit_code(error_seen) {
+        std::process::exit(exit_code);
     }
 
     Ok(())
@@ -1222,8 +1279,11 @@ fn session_configured_from_thread_resume_response(
 fn review_target_to_api(target: ReviewTarget) -> ApiReviewTarget {
     match target {
         ReviewTarget::UncommittedChanges => ApiReviewTarget::UncommittedChanges,
+        ReviewTarget::StagedChanges => ApiReviewTarget::StagedChanges,
         ReviewTarget::BaseBranch { branch } => ApiReviewTarget::BaseBranch { branch },
         ReviewTarget::Commit { sha, title } => ApiReviewTarget::Commit { sha, title },
+        ReviewTarget::CommitRange { range } => ApiReviewTarget::CommitRange { range },
+        ReviewTarget::PullRequest { url } => ApiReviewTarget::PullRequest { url },
         ReviewTarget::Custom { instructions } => ApiReviewTarget::Custom { instructions },
     }
 }
@@ -1983,6 +2043,12 @@ fn resolve_root_prompt(prompt_arg: Option<String>) -> String {
 fn build_review_request(args: &ReviewArgs) -> anyhow::Result<ReviewRequest> {
     let target = if args.uncommitted {
         ReviewTarget::UncommittedChanges
+    } else if args.staged {
+        ReviewTarget::StagedChanges
+    } else if let Some(range) = args.range.clone() {
+        ReviewTarget::CommitRange { range }
+    } else if let Some(url) = args.pr.clone() {
+        ReviewTarget::PullRequest { url }
     } else if let Some(branch) = args.base.clone() {
         ReviewTarget::BaseBranch { branch }
     } else if let Some(sha) = args.commit.clone() {
@@ -2000,7 +2066,7 @@ fn build_review_request(args: &ReviewArgs) -> anyhow::Result<ReviewRequest> {
         }
     } else {
         anyhow::bail!(
-            "Specify --uncommitted, --base, --commit, or provide custom review instructions"
+            "Specify --uncommitted, --staged, --range, --pr, --base, --commit, or provide custom review instructions"
         );
     };
 
@@ -101,6 +101,9 @@ fn exec_root_span_can_be_parented_from_trace_context() {
 fn builds_uncommitted_review_request() {
     let args = ReviewArgs {
         uncommitted: true,
+        staged: false,
+        range: None,
+        pr: None,
         base: None,
         commit: None,
         commit_title: None,
@@ -120,6 +123,9 @@ fn builds_uncommitted_review_request() {
 fn builds_commit_review_request_with_title() {
     let args = ReviewArgs {
         uncommitted: false,
+        staged: false,
+        range: None,
+        pr: None,
         base: None,
         commit: Some("123456789".to_string()),
         commit_title: Some("Add review command".to_string()),
@@ -138,10 +144,54 @@ fn builds_commit_review_request_with_title() {
     assert_eq!(request, expected);
 }
 
+#[test]
+fn builds_range_and_pull_request_review_requests() {
+    let range_args = ReviewArgs {
+        uncommitted: false,
+        staged: false,
+        range: Some("main..feature".to_string()),
+        pr: None,
+        base: None,
+        commit: None,
+        commit_title: None,
+        prompt: None,
+    };
+    let pr_args = ReviewArgs {
+        uncommitted: false,
+        staged: false,
+        range: None,
+        pr: Some("https://github.com/o/r/pull/7".to_string()),
+        base: None,
+        commit: None,
+        commit_title: None,
+        prompt: None,
+    };
+
+    assert_eq!(
+        build_review_request(&range_args)
+            .expect("builds range review request")
+            .target,
+        ReviewTarget::CommitRange {
+            range: "main..feature".to_string(),
+        }
+    );
+    assert_eq!(
+        build_review_request(&pr_args)
+            .expect("builds pull request review request")
+            .target,
+        ReviewTarget::PullRequest {
+            url: "https://github.com/o/r/pull/7".to_string(),
+        }
+    );
+}
+
 #[test]
 fn builds_custom_review_request_trims_prompt() {
     let args = ReviewArgs {
         uncommitted: false,
+        staged: false,
+        range: None,
+        pr: None,
         base: None,
         commit: None,
         commit_title: None,
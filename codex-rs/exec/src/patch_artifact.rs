@@ -0,0 +1,199 @@
+//! `codex exec --emit-patch` support.
+//!
+//! The starting state of the repository (tracked and untracked, non-ignored files) is recorded as a
+//! git tree using a throwaway index, so neither the working tree nor the real index is touched.
+//! When the run ends, the same snapshot is taken again and the difference between the two trees is
+//! written as a binary-safe patch that `git apply` accepts.
+//!
+//! With `--patch-only`, the agent works in a temporary detached worktree seeded with that starting
+//! state instead of the user's checkout. The worktree is removed once the patch is written.
+
+use std::ffi::OsStr;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::Context;
+use tempfile::TempDir;
+
+pub(crate) struct PatchArtifact {
+    output: PathBuf,
+    start_tree: String,
+    /// Repository root the agent edits: the user's checkout, or the scratch worktree.
+    work_root: PathBuf,
+    scratch: Option<ScratchWorktree>,
+    /// Working directory inside the scratch worktree that mirrors the requested cwd.
+    scratch_cwd: Option<PathBuf>,
+}
+
+impl PatchArtifact {
+    /// Records the starting state of the repository containing `cwd`, and with `patch_only`
+    /// prepares a scratch worktree for the agent to work in.
+    pub(crate) fn start(output: PathBuf, cwd: &Path, patch_only: bool) -> anyhow::Result<Self> {
+        let repo_root = PathBuf::from(
+            git(cwd, ["rev-parse", "--show-toplevel"])
+                .context("--emit-patch requires a git repository")?
+                .trim(),
+        );
+        let start_tree = snapshot_tree(&repo_root)?;
+        let (work_root, scratch, scratch_cwd) = if patch_only {
+            let scratch = ScratchWorktree::create(&repo_root, &start_tree)?;
+            let relative_cwd = cwd.strip_prefix(&repo_root).unwrap_or(Path::new(""));
+            let scratch_cwd = scratch.path().join(relative_cwd);
+            (
+                scratch.path().to_path_buf(),
+                Some(scratch),
+                Some(scratch_cwd),
+            )
+        } else {
+            (repo_root, None, None)
+        };
+        Ok(Self {
+            output,
+            start_tree,
+            work_root,
+            scratch,
+            scratch_cwd,
+        })
+    }
+
+    /// The directory the session should run in when `--patch-only` is set.
+    pub(crate) fn scratch_cwd(&self) -> Option<PathBuf> {
+        self.scratch_cwd.clone()
+    }
+
+    /// Writes the patch of everything that changed since [`PatchArtifact::start`] and removes the
+    /// scratch worktree, if any.
+    pub(crate) fn finish(self) -> anyhow::Result<()> {
+        let end_tree = snapshot_tree(&self.work_root)?;
+        // Raw bytes: a patch can hold text in any encoding, which must reach the file unchanged.
+        let patch = git_bytes(
+            &self.work_root,
+            [
+                "diff",
+                "--binary",
+                "--no-color",
+                "--no-ext-diff",
+                self.start_tree.as_str(),
+                end_tree.as_str(),
+            ],
+        )?;
+        std::fs::write(&self.output, patch)
+            .with_context(|| format!("failed to write patch to {}", self.output.display()))?;
+        drop(self.scratch);
+        Ok(())
+    }
+}
+
+/// A detached `git worktree` in a temporary directory, removed on drop.
+struct ScratchWorktree {
+    repo_root: PathBuf,
+    dir: TempDir,
+}
+
+impl ScratchWorktree {
+    fn create(repo_root: &Path, start_tree: &str) -> anyhow::Result<Self> {
+        let dir = tempfile::Builder::new()
+            .prefix("codex-exec-worktree-")
+            .tempdir()?;
+        git(
+            repo_root,
+            [
+                OsStr::new("worktree"),
+                OsStr::new("add"),
+                OsStr::new("--detach"),
+                dir.path().as_os_str(),
+                OsStr::new("HEAD"),
+            ],
+        )
+        .context("failed to create a scratch worktree for --patch-only")?;
+        let worktree = Self {
+            repo_root: repo_root.to_path_buf(),
+            dir,
+        };
+        // Bring over uncommitted and untracked changes so the agent starts from the same state.
+        git(worktree.path(), ["checkout", start_tree, "--", "."])?;
+        Ok(worktree)
+    }
+
+    fn path(&self) -> &Path {
+        self.dir.path()
+    }
+}
+
+impl Drop for ScratchWorktree {
+    fn drop(&mut self) {
+        let _ = git(
+            &self.repo_root,
+            [
+                OsStr::new("worktree"),
+                OsStr::new("remove"),
+                OsStr::new("--force"),
+                self.dir.path().as_os_str(),
+            ],
+        );
+    }
+}
+
+/// Writes the current working tree of `repo_root` as a git tree object and returns its id.
+fn snapshot_tree(repo_root: &Path) -> anyhow::Result<String> {
+    let index_dir = TempDir::new()?;
+    let index_path = index_dir.path().join("index");
+    // Seed the throwaway index from the real one so unchanged files are not re-hashed.
+    let real_index = git(
+        repo_root,
+        ["rev-parse", "--path-format=absolute", "--git-path", "index"],
+    )?;
+    let _ = std::fs::copy(real_index.trim(), &index_path);
+
+    git_with_index(repo_root, &index_path, &["add", "--all", "."])?;
+    Ok(git_with_index(repo_root, &index_path, &["write-tree"])?
+        .trim()
+        .to_string())
+}
+
+fn git_with_index(dir: &Path, index_path: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .env("GIT_INDEX_FILE", index_path)
+        .args(args)
+        .output()
+        .context("failed to run git")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "git {} failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn git<I, S>(dir: &Path, args: I) -> anyhow::Result<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    Ok(String::from_utf8_lossy(&git_bytes(dir, args)?).into_owned())
+}
+
+fn git_bytes<I, S>(dir: &Path, args: I) -> anyhow::Result<Vec<u8>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .context("failed to run git")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "git failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+#[path = "patch_artifact_tests.rs"]
+mod tests;
@@ -0,0 +1,80 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn init_repo() -> TempDir {
+    let repo = tempdir().expect("tempdir");
+    for args in [
+        vec!["init", "--quiet"],
+        vec!["config", "user.email", "codex@example.com"],
+        vec!["config", "user.name", "Codex"],
+    ] {
+        git(repo.path(), args).expect("git setup");
+    }
+    std::fs::write(repo.path().join("README.md"), "hello\n").expect("write readme");
+    git(repo.path(), ["add", "README.md"]).expect("git add");
+    git(repo.path(), ["commit", "--quiet", "-m", "init"]).expect("git commit");
+    repo
+}
+
+#[test]
+fn patch_contains_only_changes_made_after_start() {
+    let repo = init_repo();
+    // Pre-existing uncommitted work is part of the starting state, not the patch.
+    std::fs::write(repo.path().join("README.md"), "hello\nwip\n").expect("dirty readme");
+    let output = repo.path().join("out.patch");
+
+    let artifact =
+        PatchArtifact::start(output.clone(), repo.path(), /*patch_only*/ false).expect("start");
+    std::fs::write(repo.path().join("new.txt"), "added\n").expect("write new file");
+    artifact.finish().expect("finish");
+
+    let patch = std::fs::read_to_string(&output).expect("read patch");
+    assert!(patch.contains("+++ b/new.txt"), "{patch}");
+    assert!(!patch.contains("README.md"), "{patch}");
+}
+
+#[test]
+fn patch_only_leaves_the_checkout_untouched() {
+    let repo = init_repo();
+    std::fs::write(repo.path().join("README.md"), "hello\nwip\n").expect("dirty readme");
+    let output = repo.path().join("out.patch");
+
+    let artifact =
+        PatchArtifact::start(output.clone(), repo.path(), /*patch_only*/ true).expect("start");
+    let scratch = artifact.scratch_cwd().expect("scratch cwd");
+    assert_eq!(
+        std::fs::read_to_string(scratch.join("README.md")).expect("read scratch readme"),
+        "hello\nwip\n"
+    );
+    std::fs::write(scratch.join("README.md"), "hello\nwip\ndone\n").expect("edit scratch");
+    artifact.finish().expect("finish");
+
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("README.md")).expect("read readme"),
+        "hello\nwip\n"
+    );
+    assert!(!scratch.exists());
+    let patch = std::fs::read_to_string(&output).expect("read patch");
+    assert!(patch.contains("+done"), "{patch}");
+    git(repo.path(), ["apply", "--check", "out.patch"]).expect("patch applies");
+}
+
+#[test]
+fn patch_keeps_text_that_is_not_utf8() {
+    let repo = init_repo();
+    let output = repo.path().join("out.patch");
+
+    let artifact =
+        PatchArtifact::start(output.clone(), repo.path(), /*patch_only*/ true).expect("start");
+    let scratch = artifact.scratch_cwd().expect("scratch cwd");
+    // "café" in Latin-1.
+    std::fs::write(scratch.join("latin1.txt"), b"caf\xe9\n").expect("write latin-1 file");
+    artifact.finish().expect("finish");
+
+    git(repo.path(), ["apply", "out.patch"]).expect("patch applies");
+    assert_eq!(
+        std::fs::read(repo.path().join("latin1.txt")).expect("read applied file"),
+        b"caf\xe9\n"
+    );
+}
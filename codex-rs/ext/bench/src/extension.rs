@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use codex_config::types::BenchConfig;
+use codex_core::config::Config;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ToolContributor;
+use codex_features::Feature;
+
+use crate::tools;
+
+/// Contributes a tool that times the `[bench]` command against a baseline revision, so
+/// performance work is judged on repeated runs instead of a single timing.
+#[derive(Clone, Default)]
+pub(crate) struct BenchExtension;
+
+#[derive(Clone, Debug)]
+pub(crate) struct BenchExtensionConfig {
+    pub(crate) enabled: bool,
+    pub(crate) bench: BenchConfig,
+}
+
+impl BenchExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::Bench),
+            bench: config.bench.clone(),
+        }
+    }
+}
+
+impl ToolContributor for BenchExtension {
+    fn tools(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+    ) -> Vec<Arc<dyn codex_extension_api::ToolExecutor<codex_extension_api::ToolCall>>> {
+        let Some(config) = thread_store.get::<BenchExtensionConfig>() else {
+            return Vec::new();
+        };
+        // The model picks the baseline and run count, but the command comes from config and each
+        // run still needs the user's approval, since it executes outside the sandbox.
+        if !config.enabled || config.bench.command.is_none() {
+            return Vec::new();
+        }
+
+        tools::bench_tools(config.bench.clone())
+    }
+}
+
+/// Installs the benchmark extension contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    registry.thread_config(BenchExtensionConfig::from_config);
+    registry.tool_contributor(Arc::new(BenchExtension));
+}
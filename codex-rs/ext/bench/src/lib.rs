@@ -0,0 +1,12 @@
+mod extension;
+mod tools;
+
+pub use extension::install;
+
+pub(crate) const BENCH_NAMESPACE: &str = "bench";
+pub(crate) const COMPARE_TOOL_NAME: &str = "compare";
+/// Most timed runs per side a single tool call may ask for.
+pub(crate) const MAX_RUNS: usize = 100;
+
+#[cfg(test)]
+mod tests;
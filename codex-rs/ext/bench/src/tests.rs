@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use codex_config::types::BenchConfig;
+use codex_extension_api::DenyCommandApprover;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::NoopTurnItemEmitter;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolContributor;
+use codex_extension_api::ToolPayload;
+use codex_extension_api::new_local_environment;
+use codex_protocol::models::PermissionProfile;
+use codex_utils_output_truncation::TruncationPolicy;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::extension::BenchExtension;
+use crate::extension::BenchExtensionConfig;
+use crate::tools::bench_tool_name;
+
+fn thread_store(enabled: bool, command: Option<&str>) -> ExtensionData {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(BenchExtensionConfig {
+        enabled,
+        bench: BenchConfig {
+            command: command.map(str::to_string),
+            ..BenchConfig::default()
+        },
+    });
+    thread_store
+}
+
+fn compare_call(arguments: serde_json::Value) -> ToolCall {
+    ToolCall {
+        turn_id: "turn-1".to_string(),
+        call_id: "call-1".to_string(),
+        tool_name: bench_tool_name(crate::COMPARE_TOOL_NAME),
+        model: "gpt-test".to_string(),
+        codex_turn_metadata: None,
+        truncation_policy: TruncationPolicy::Bytes(1024),
+        conversation_history: codex_extension_api::ConversationHistory::default(),
+        turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+        command_approver: Arc::new(DenyCommandApprover),
+        environments: Vec::new(),
+        payload: ToolPayload::Function {
+            arguments: arguments.to_string(),
+        },
+    }
+}
+
+#[test]
+fn install_registers_the_compare_tool() {
+    let mut builder = ExtensionRegistryBuilder::<codex_core::config::Config>::new();
+    crate::install(&mut builder);
+    let registry = builder.build();
+    let thread_store = thread_store(/*enabled*/ true, Some("make bench"));
+
+    let tool_names = registry
+        .tool_contributors()
+        .iter()
+        .flat_map(|contributor| contributor.tools(&ExtensionData::new("session"), &thread_store))
+        .map(|tool| tool.tool_name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(tool_names, vec![bench_tool_name(crate::COMPARE_TOOL_NAME)]);
+}
+
+#[test]
+fn tools_need_the_feature_and_a_configured_command() {
+    for thread_store in [
+        thread_store(/*enabled*/ false, Some("make bench")),
+        thread_store(/*enabled*/ true, /*command*/ None),
+    ] {
+        assert!(
+            BenchExtension
+                .tools(&ExtensionData::new("session"), &thread_store)
+                .is_empty()
+        );
+    }
+}
+
+#[tokio::test]
+async fn compare_requires_a_local_environment() {
+    let tools = BenchExtension.tools(
+        &ExtensionData::new("session"),
+        &thread_store(/*enabled*/ true, Some("make bench")),
+    );
+
+    let Err(err) = tools[0].handle(compare_call(json!({}))).await else {
+        panic!("compare without a local environment should fail");
+    };
+
+    assert_eq!(
+        err,
+        FunctionCallError::RespondToModel(
+            "benchmarks are only available for local workspaces".to_string()
+        )
+    );
+}
+
+#[tokio::test]
+async fn compare_rejects_unknown_arguments() {
+    let tools = BenchExtension.tools(
+        &ExtensionData::new("session"),
+        &thread_store(/*enabled*/ true, Some("make bench")),
+    );
+
+    let Err(FunctionCallError::RespondToModel(message)) = tools[0]
+        .handle(compare_call(json!({ "command": "rm -rf /" })))
+        .await
+    else {
+        panic!("compare should not accept a command from the model");
+    };
+
+    assert!(message.contains("unknown field `command`"), "{message}");
+}
+
+#[tokio::test]
+async fn compare_needs_the_users_approval() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let tools = BenchExtension.tools(
+        &ExtensionData::new("session"),
+        &thread_store(/*enabled*/ true, Some("make bench")),
+    );
+    let mut call = compare_call(json!({}));
+    call.environments = vec![
+        new_local_environment(dir.path(), PermissionProfile::workspace_write())
+            .expect("local environment"),
+    ];
+
+    let Err(err) = tools[0].handle(call).await else {
+        panic!("compare without approval should fail");
+    };
+
+    assert_eq!(
+        err,
+        FunctionCallError::RespondToModel(
+            "the user did not approve running the benchmark".to_string()
+        )
+    );
+}
@@ -0,0 +1,123 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_config::types::BenchConfig;
+use codex_extension_api::CommandApprovalRequest;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::JsonToolOutput;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolExecutorFuture;
+use codex_extension_api::ToolName;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolSpec;
+use codex_extension_api::local_environment;
+use codex_extension_api::namespaced_function_tool;
+use codex_extension_api::parse_function_arguments;
+use codex_utils_bench::BenchComparison;
+use codex_utils_bench::BenchPlan;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::BENCH_NAMESPACE;
+use crate::COMPARE_TOOL_NAME;
+use crate::MAX_RUNS;
+
+/// The benchmark runs on this host, so the tool only works against the local checkout.
+const LOCAL_WORKSPACE_REQUIRED: &str = "benchmarks are only available for local workspaces";
+
+pub(crate) fn bench_tools(config: BenchConfig) -> Vec<Arc<dyn ToolExecutor<ToolCall>>> {
+    vec![Arc::new(CompareTool { config })]
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct CompareArgs {
+    /// Timed runs of the baseline and of the working tree each, from 2 to 100. Defaults to
+    /// `bench.runs`. Use more runs when the previous comparison was inconclusive.
+    runs: Option<usize>,
+    /// Git revision to compare the working tree against. Defaults to `bench.baseline`, usually
+    /// `HEAD`.
+    baseline: Option<String>,
+}
+
+struct CompareTool {
+    config: BenchConfig,
+}
+
+impl ToolExecutor<ToolCall> for CompareTool {
+    fn tool_name(&self) -> ToolName {
+        bench_tool_name(COMPARE_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        let command = self.config.command.as_deref().unwrap_or_default();
+        namespaced_function_tool::<CompareArgs, BenchComparison>(
+            BENCH_NAMESPACE,
+            COMPARE_TOOL_NAME,
+            &format!(
+                "Time the project's benchmark command `{command}` repeatedly at a baseline revision and in the working tree, alternating between them, and report the mean, median, spread, and whether the working tree is faster or slower at 95% confidence. The user approves each comparison before it runs. Use it to judge performance changes instead of timing a single run yourself."
+            ),
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: CompareArgs = parse_function_arguments(&call)?;
+            let cwd = local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?
+                .cwd
+                .clone();
+            let Some(command) = self.config.command.clone() else {
+                return Err(FunctionCallError::RespondToModel(
+                    "no benchmark command is configured".to_string(),
+                ));
+            };
+            let runs = args.runs.unwrap_or(self.config.runs);
+            if !(2..=MAX_RUNS).contains(&runs) {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "runs must be between 2 and {MAX_RUNS}"
+                )));
+            }
+            let baseline = args
+                .baseline
+                .unwrap_or_else(|| self.config.baseline.clone());
+            let plan = BenchPlan {
+                command,
+                runs,
+                warmup: self.config.warmup,
+                timeout: Duration::from_secs(self.config.timeout_seconds),
+            };
+
+            // The command runs on the host outside the sandbox, so every comparison is approved
+            // by the user first.
+            let approved = call
+                .command_approver
+                .request_approval(CommandApprovalRequest {
+                    command: codex_utils_bench::shell_argv(&plan.command),
+                    cwd: cwd.clone(),
+                    reason: format!(
+                        "Benchmark `{}` {} times at `{baseline}` and in the working tree",
+                        plan.command,
+                        plan.warmup + plan.runs
+                    ),
+                })
+                .await;
+            if !approved {
+                return Err(FunctionCallError::RespondToModel(
+                    "the user did not approve running the benchmark".to_string(),
+                ));
+            }
+
+            let comparison = codex_utils_bench::compare(cwd.as_path(), &baseline, &plan)
+                .await
+                .map_err(FunctionCallError::RespondToModel)?;
+            let output: Box<dyn ToolOutput> = Box::new(JsonToolOutput::new(json!(comparison)));
+            Ok(output)
+        })
+    }
+}
+
+pub(crate) fn bench_tool_name(name: &str) -> ToolName {
+    ToolName::namespaced(BENCH_NAMESPACE, name)
+}
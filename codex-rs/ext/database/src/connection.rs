@@ -0,0 +1,390 @@
+//! Read-only connections to the configured databases.
+//!
+//! Read-only access is enforced by the connection itself: SQLite files are opened read-only with
+//! `query_only` set, and PostgreSQL sessions default to read-only transactions. The statement
+//! check in [`crate::statement`] only exists to give the model a clearer error up front.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use futures::Stream;
+use futures::TryStreamExt;
+use schemars::JsonSchema;
+use serde::Serialize;
+use sqlx::AssertSqlSafe;
+use sqlx::Column;
+use sqlx::ConnectOptions;
+use sqlx::Connection;
+use sqlx::Row;
+use sqlx::TypeInfo;
+use sqlx::ValueRef;
+use sqlx::postgres::PgConnectOptions;
+use sqlx::postgres::PgConnection;
+use sqlx::postgres::PgRow;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::sqlite::SqliteRow;
+
+use crate::MAX_SCHEMA_COLUMNS;
+use crate::QUERY_TIMEOUT;
+use crate::table::markdown_table;
+
+/// Where a configured database lives, resolved from its `url`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Target {
+    Sqlite(PathBuf),
+    Postgres(String),
+}
+
+impl Target {
+    /// Resolves a configured `url`. Relative SQLite paths are taken from `cwd`; PostgreSQL is
+    /// reached over the network, so it is refused unless the active permissions allow that.
+    pub(crate) fn resolve(url: &str, cwd: &Path, network_access: bool) -> Result<Self, String> {
+        if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+            if !network_access {
+                return Err(
+                    "this database is reached over the network, which the current permissions do not allow"
+                        .to_string(),
+                );
+            }
+            return Ok(Self::Postgres(url.to_string()));
+        }
+
+        let path = match url.strip_prefix("sqlite:") {
+            Some(rest) => {
+                let rest = rest.strip_prefix("//").unwrap_or(rest);
+                rest.split_once('?').map_or(rest, |(path, _params)| path)
+            }
+            None if url.contains("://") => {
+                return Err(format!(
+                    "unsupported database url `{url}`; use a SQLite path or a postgres:// url"
+                ));
+            }
+            None => url,
+        };
+        if path.is_empty() || path == ":memory:" {
+            return Err(format!("`{url}` does not name a SQLite file"));
+        }
+        Ok(Self::Sqlite(cwd.join(path)))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct QueryResult {
+    /// Result rows as a Markdown table.
+    pub(crate) table: String,
+    /// Number of rows in the table.
+    pub(crate) row_count: usize,
+    /// True when the query returned more rows than were kept.
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct DatabaseSchema {
+    pub(crate) tables: Vec<TableSchema>,
+    /// True when the listing stopped before every column was included.
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct TableSchema {
+    /// Table or view name, qualified with its schema outside PostgreSQL's `public` schema.
+    pub(crate) name: String,
+    pub(crate) columns: Vec<ColumnSchema>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct ColumnSchema {
+    pub(crate) name: String,
+    pub(crate) data_type: String,
+    pub(crate) nullable: bool,
+    pub(crate) primary_key: bool,
+}
+
+/// One row of a schema listing, before rows are grouped by table.
+struct ColumnRow {
+    table: String,
+    column: ColumnSchema,
+}
+
+const SQLITE_SCHEMA_QUERY: &str = r#"
+SELECT m.name AS table_name, p.name AS column_name, p.type AS data_type,
+       p."notnull" AS not_null, p.pk AS primary_key
+FROM sqlite_schema AS m
+JOIN pragma_table_info(m.name) AS p
+WHERE m.type IN ('table', 'view') AND m.name NOT LIKE 'sqlite_%'
+ORDER BY m.name, p.cid
+"#;
+
+const POSTGRES_SCHEMA_QUERY: &str = r#"
+SELECT c.table_schema::text AS table_schema, c.table_name::text AS table_name,
+       c.column_name::text AS column_name, c.data_type::text AS data_type,
+       c.is_nullable = 'YES' AS nullable,
+       EXISTS (
+           SELECT 1
+           FROM information_schema.table_constraints AS tc
+           JOIN information_schema.key_column_usage AS k
+             ON k.constraint_schema = tc.constraint_schema
+            AND k.constraint_name = tc.constraint_name
+           WHERE tc.constraint_type = 'PRIMARY KEY'
+             AND tc.table_schema = c.table_schema
+             AND tc.table_name = c.table_name
+             AND k.column_name = c.column_name
+       ) AS primary_key
+FROM information_schema.columns AS c
+WHERE c.table_schema NOT IN ('pg_catalog', 'information_schema')
+ORDER BY c.table_schema, c.table_name, c.ordinal_position
+"#;
+
+/// Runs `sql` and keeps at most `max_rows` rows.
+pub(crate) async fn run_query(
+    target: &Target,
+    sql: &str,
+    max_rows: usize,
+) -> Result<QueryResult, String> {
+    // The statement comes from the model; the read-only connection is what keeps it harmless.
+    with_timeout(async {
+        let (columns, rows, truncated) = match target {
+            Target::Sqlite(path) => {
+                let mut conn = open_sqlite(path).await?;
+                let stream = sqlx::query(AssertSqlSafe(sql.to_string())).fetch(&mut conn);
+                let result = collect_rows(stream, max_rows, sqlite_value).await;
+                let _ = conn.close().await;
+                result?
+            }
+            Target::Postgres(url) => {
+                let mut conn = open_postgres(url).await?;
+                let stream = sqlx::query(AssertSqlSafe(sql.to_string())).fetch(&mut conn);
+                let result = collect_rows(stream, max_rows, postgres_value).await;
+                let _ = conn.close().await;
+                result?
+            }
+        };
+        Ok(QueryResult {
+            table: markdown_table(&columns, &rows),
+            row_count: rows.len(),
+            truncated,
+        })
+    })
+    .await
+}
+
+/// Lists tables and views with their columns, optionally only those whose name contains `filter`.
+pub(crate) async fn load_schema(
+    target: &Target,
+    filter: Option<&str>,
+) -> Result<DatabaseSchema, String> {
+    let rows = with_timeout(async {
+        match target {
+            Target::Sqlite(path) => {
+                let mut conn = open_sqlite(path).await?;
+                let rows = sqlx::query(SQLITE_SCHEMA_QUERY)
+                    .fetch_all(&mut conn)
+                    .await
+                    .map_err(|err| err.to_string());
+                let _ = conn.close().await;
+                rows?.iter().map(sqlite_column_row).collect()
+            }
+            Target::Postgres(url) => {
+                let mut conn = open_postgres(url).await?;
+                let rows = sqlx::query(POSTGRES_SCHEMA_QUERY)
+                    .fetch_all(&mut conn)
+                    .await
+                    .map_err(|err| err.to_string());
+                let _ = conn.close().await;
+                rows?.iter().map(postgres_column_row).collect()
+            }
+        }
+    })
+    .await?;
+
+    Ok(group_columns(rows, filter))
+}
+
+async fn with_timeout<T>(task: impl Future<Output = Result<T, String>>) -> Result<T, String> {
+    tokio::time::timeout(QUERY_TIMEOUT, task)
+        .await
+        .map_err(|_| format!("query timed out after {}s", QUERY_TIMEOUT.as_secs()))?
+}
+
+async fn open_sqlite(path: &Path) -> Result<SqliteConnection, String> {
+    if !path.is_file() {
+        return Err(format!("{} does not exist", path.display()));
+    }
+    let mut conn = SqliteConnectOptions::new()
+        .filename(path)
+        .read_only(true)
+        .create_if_missing(false)
+        .connect()
+        .await
+        .map_err(|err| format!("failed to open {}: {err}", path.display()))?;
+    sqlx::query("PRAGMA query_only = ON")
+        .execute(&mut conn)
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(conn)
+}
+
+async fn open_postgres(url: &str) -> Result<PgConnection, String> {
+    let mut conn = PgConnectOptions::from_str(url)
+        .map_err(|err| format!("invalid database url: {err}"))?
+        .connect()
+        .await
+        .map_err(|err| format!("failed to connect: {err}"))?;
+    for statement in [
+        "SET SESSION CHARACTERISTICS AS TRANSACTION READ ONLY",
+        "SET statement_timeout = '30s'",
+    ] {
+        sqlx::query(statement)
+            .execute(&mut conn)
+            .await
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(conn)
+}
+
+type Rows = (Vec<String>, Vec<Vec<Option<String>>>, bool);
+
+/// Reads at most `max_rows` rows from `stream`, formatting each cell with `value`.
+async fn collect_rows<R: Row>(
+    mut stream: impl Stream<Item = Result<R, sqlx::Error>> + Unpin,
+    max_rows: usize,
+    value: fn(&R, usize) -> Option<String>,
+) -> Result<Rows, String> {
+    let mut columns = Vec::new();
+    let mut rows = Vec::new();
+    while let Some(row) = stream.try_next().await.map_err(|err| err.to_string())? {
+        if rows.len() == max_rows {
+            return Ok((columns, rows, true));
+        }
+        if columns.is_empty() {
+            columns = row
+                .columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect();
+        }
+        rows.push((0..row.len()).map(|index| value(&row, index)).collect());
+    }
+    Ok((columns, rows, false))
+}
+
+/// Formats a SQLite value by its storage class, since declared column types are only hints.
+fn sqlite_value(row: &SqliteRow, index: usize) -> Option<String> {
+    let raw = row.try_get_raw(index).ok()?;
+    if raw.is_null() {
+        return None;
+    }
+    let type_name = raw.type_info().name().to_string();
+    let value = match type_name.as_str() {
+        "INTEGER" => row.try_get::<i64, _>(index).map(|value| value.to_string()),
+        "REAL" => row.try_get::<f64, _>(index).map(|value| value.to_string()),
+        "BLOB" => row
+            .try_get::<Vec<u8>, _>(index)
+            .map(|value| format!("<{} bytes>", value.len())),
+        _ => row.try_get::<String, _>(index),
+    };
+    Some(value.unwrap_or_else(|_| format!("<{type_name}>")))
+}
+
+fn postgres_value(row: &PgRow, index: usize) -> Option<String> {
+    use sqlx::types::chrono;
+
+    let raw = row.try_get_raw(index).ok()?;
+    if raw.is_null() {
+        return None;
+    }
+    let type_name = raw.type_info().name().to_string();
+    let value = match type_name.as_str() {
+        "BOOL" => row.try_get::<bool, _>(index).map(|value| value.to_string()),
+        "INT2" => row.try_get::<i16, _>(index).map(|value| value.to_string()),
+        "INT4" => row.try_get::<i32, _>(index).map(|value| value.to_string()),
+        "INT8" => row.try_get::<i64, _>(index).map(|value| value.to_string()),
+        "FLOAT4" => row.try_get::<f32, _>(index).map(|value| value.to_string()),
+        "FLOAT8" => row.try_get::<f64, _>(index).map(|value| value.to_string()),
+        "UUID" => row
+            .try_get::<sqlx::types::Uuid, _>(index)
+            .map(|value| value.to_string()),
+        "JSON" | "JSONB" => row
+            .try_get::<serde_json::Value, _>(index)
+            .map(|value| value.to_string()),
+        "DATE" => row
+            .try_get::<chrono::NaiveDate, _>(index)
+            .map(|value| value.to_string()),
+        "TIME" => row
+            .try_get::<chrono::NaiveTime, _>(index)
+            .map(|value| value.to_string()),
+        "TIMESTAMP" => row
+            .try_get::<chrono::NaiveDateTime, _>(index)
+            .map(|value| value.to_string()),
+        "TIMESTAMPTZ" => row
+            .try_get::<chrono::DateTime<chrono::Utc>, _>(index)
+            .map(|value| value.to_rfc3339()),
+        _ => row.try_get::<String, _>(index),
+    };
+    Some(value.unwrap_or_else(|_| format!("<{type_name}; cast to text to see it>")))
+}
+
+fn sqlite_column_row(row: &SqliteRow) -> Result<ColumnRow, String> {
+    let not_null: i64 = row.try_get("not_null").map_err(|err| err.to_string())?;
+    let primary_key: i64 = row.try_get("primary_key").map_err(|err| err.to_string())?;
+    Ok(ColumnRow {
+        table: row.try_get("table_name").map_err(|err| err.to_string())?,
+        column: ColumnSchema {
+            name: row.try_get("column_name").map_err(|err| err.to_string())?,
+            data_type: row.try_get("data_type").map_err(|err| err.to_string())?,
+            nullable: not_null == 0,
+            primary_key: primary_key > 0,
+        },
+    })
+}
+
+fn postgres_column_row(row: &PgRow) -> Result<ColumnRow, String> {
+    let schema: String = row.try_get("table_schema").map_err(|err| err.to_string())?;
+    let table: String = row.try_get("table_name").map_err(|err| err.to_string())?;
+    Ok(ColumnRow {
+        table: if schema == "public" {
+            table
+        } else {
+            format!("{schema}.{table}")
+        },
+        column: ColumnSchema {
+            name: row.try_get("column_name").map_err(|err| err.to_string())?,
+            data_type: row.try_get("data_type").map_err(|err| err.to_string())?,
+            nullable: row.try_get("nullable").map_err(|err| err.to_string())?,
+            primary_key: row.try_get("primary_key").map_err(|err| err.to_string())?,
+        },
+    })
+}
+
+fn group_columns(rows: Vec<ColumnRow>, filter: Option<&str>) -> DatabaseSchema {
+    let filter = filter.map(str::to_lowercase);
+    let mut tables: Vec<TableSchema> = Vec::new();
+    let mut column_count = 0;
+    for row in rows {
+        if let Some(filter) = &filter
+            && !row.table.to_lowercase().contains(filter.as_str())
+        {
+            continue;
+        }
+        if column_count == MAX_SCHEMA_COLUMNS {
+            return DatabaseSchema {
+                tables,
+                truncated: true,
+            };
+        }
+        column_count += 1;
+        match tables.last_mut() {
+            Some(table) if table.name == row.table => table.columns.push(row.column),
+            _ => tables.push(TableSchema {
+                name: row.table,
+                columns: vec![row.column],
+            }),
+        }
+    }
+    DatabaseSchema {
+        tables,
+        truncated: false,
+    }
+}
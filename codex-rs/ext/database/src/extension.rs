@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use codex_core::config::Config;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ToolContributor;
+use codex_features::Feature;
+
+use crate::tools;
+
+/// Contributes read-only query and schema tools for the databases listed under `[databases]`.
+#[derive(Clone, Default)]
+pub(crate) struct DatabaseExtension;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DatabaseEntry {
+    pub(crate) name: String,
+    pub(crate) url: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DatabaseExtensionConfig {
+    pub(crate) enabled: bool,
+    /// Whether the active permissions allow network access; network databases need it.
+    pub(crate) network_access: bool,
+    pub(crate) databases: Vec<DatabaseEntry>,
+}
+
+impl DatabaseExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::DatabaseTools),
+            network_access: config.network_sandbox_policy().is_enabled(),
+            databases: config
+                .databases
+                .iter()
+                .map(|database| DatabaseEntry {
+                    name: database.name.clone(),
+                    url: database.url.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ToolContributor for DatabaseExtension {
+    fn tools(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+    ) -> Vec<Arc<dyn codex_extension_api::ToolExecutor<codex_extension_api::ToolCall>>> {
+        let Some(config) = thread_store.get::<DatabaseExtensionConfig>() else {
+            return Vec::new();
+        };
+        if !config.enabled || config.databases.is_empty() {
+            return Vec::new();
+        }
+
+        tools::database_tools(config.databases.clone(), config.network_access)
+    }
+}
+
+/// Installs the database extension contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    registry.thread_config(DatabaseExtensionConfig::from_config);
+    registry.tool_contributor(Arc::new(DatabaseExtension));
+}
@@ -0,0 +1,25 @@
+mod connection;
+mod extension;
+mod statement;
+mod table;
+mod tools;
+
+use std::time::Duration;
+
+pub use extension::install;
+
+pub(crate) const DEFAULT_MAX_ROWS: usize = 50;
+pub(crate) const MAX_ROWS: usize = 500;
+/// Table cells are cut to this many characters.
+pub(crate) const MAX_CELL_CHARS: usize = 200;
+/// Schema listings stop after this many columns across all tables.
+pub(crate) const MAX_SCHEMA_COLUMNS: usize = 2_000;
+/// Connecting and running one query or schema listing must finish within this time.
+pub(crate) const QUERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub(crate) const DATABASE_NAMESPACE: &str = "database";
+pub(crate) const QUERY_TOOL_NAME: &str = "query";
+pub(crate) const SCHEMA_TOOL_NAME: &str = "schema";
+
+#[cfg(test)]
+mod tests;
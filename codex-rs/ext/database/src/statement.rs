@@ -0,0 +1,111 @@
+/// Keywords a read-only statement may start with.
+const READ_ONLY_KEYWORDS: &[&str] = &[
+    "select", "with", "values", "explain", "show", "table", "pragma",
+];
+
+/// Rejects anything other than a single statement that reads data.
+///
+/// The connection is opened read-only, so this is not what keeps writes out. It turns an obvious
+/// mistake into a clear message instead of a driver error.
+pub(crate) fn check_read_only(sql: &str) -> Result<(), String> {
+    let statements = split_statements(sql);
+    let [statement] = statements.as_slice() else {
+        return Err(if statements.is_empty() {
+            "the query is empty".to_string()
+        } else {
+            "run one statement at a time".to_string()
+        });
+    };
+
+    let keyword = statement
+        .trim_start_matches(|ch: char| ch == '(' || ch.is_whitespace())
+        .chars()
+        .take_while(char::is_ascii_alphabetic)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if !READ_ONLY_KEYWORDS.contains(&keyword.as_str()) {
+        return Err(format!(
+            "only read-only statements (SELECT, WITH, VALUES, EXPLAIN, SHOW, PRAGMA) are allowed, not `{}`",
+            keyword.to_ascii_uppercase()
+        ));
+    }
+    if keyword == "pragma" && statement.contains('=') {
+        return Err("PRAGMA statements that change settings are not allowed".to_string());
+    }
+    Ok(())
+}
+
+/// Splits `sql` on semicolons outside strings, quoted identifiers, and comments. Comments are
+/// replaced with a space and empty statements are dropped.
+fn split_statements(sql: &str) -> Vec<String> {
+    let chars = sql.chars().collect::<Vec<_>>();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let ch = chars[index];
+        match ch {
+            '\'' | '"' | '`' => {
+                let end = chars[index + 1..]
+                    .iter()
+                    .position(|&next| next == ch)
+                    .map_or(chars.len(), |offset| index + 1 + offset + 1);
+                current.extend(&chars[index..end]);
+                index = end;
+            }
+            '-' if chars.get(index + 1) == Some(&'-') => {
+                index = chars[index..]
+                    .iter()
+                    .position(|&next| next == '\n')
+                    .map_or(chars.len(), |offset| index + offset);
+                current.push(' ');
+            }
+            '/' if chars.get(index + 1) == Some(&'*') => {
+                index = chars[index + 2..]
+                    .windows(2)
+                    .position(|pair| pair == ['*', '/'])
+                    .map_or(chars.len(), |offset| index + 2 + offset + 2);
+                current.push(' ');
+            }
+            '$' => {
+                let end = dollar_quote_end(&chars, index).unwrap_or(index + 1);
+                current.extend(&chars[index..end]);
+                index = end;
+            }
+            ';' => {
+                statements.push(std::mem::take(&mut current));
+                index += 1;
+            }
+            _ => {
+                current.push(ch);
+                index += 1;
+            }
+        }
+    }
+    statements.push(current);
+    statements
+        .into_iter()
+        .map(|statement| statement.trim().to_string())
+        .filter(|statement| !statement.is_empty())
+        .collect()
+}
+
+/// Returns the index just past a PostgreSQL dollar-quoted string (`$$...$$` or `$tag$...$tag$`)
+/// starting at `start`, or `None` if `start` does not open one.
+fn dollar_quote_end(chars: &[char], start: usize) -> Option<usize> {
+    let tag_len = chars[start + 1..]
+        .iter()
+        .position(|&ch| ch == '$')
+        .filter(|&len| {
+            chars[start + 1..start + 1 + len]
+                .iter()
+                .all(|&ch| ch == '_' || ch.is_alphanumeric())
+        })?;
+    let tag = &chars[start..start + tag_len + 2];
+    let body_start = start + tag.len();
+    let end = chars[body_start..]
+        .windows(tag.len())
+        .position(|window| window == tag)
+        .map_or(chars.len(), |offset| body_start + offset + tag.len());
+    Some(end)
+}
@@ -0,0 +1,41 @@
+use crate::MAX_CELL_CHARS;
+
+/// Renders rows as a GitHub-flavored Markdown table. `None` cells are shown as `NULL`.
+pub(crate) fn markdown_table(columns: &[String], rows: &[Vec<Option<String>>]) -> String {
+    if columns.is_empty() {
+        return "(no rows)".to_string();
+    }
+
+    let mut lines = Vec::with_capacity(rows.len() + 2);
+    lines.push(markdown_row(columns.iter().map(String::as_str)));
+    lines.push(markdown_row(columns.iter().map(|_| "---")));
+    for row in rows {
+        lines.push(markdown_row(
+            row.iter().map(|value| value.as_deref().unwrap_or("NULL")),
+        ));
+    }
+    lines.join("\n")
+}
+
+fn markdown_row<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    let cells = cells.map(markdown_cell).collect::<Vec<_>>();
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Keeps a value on one line and inside its cell: pipes are escaped, line breaks become spaces,
+/// and long values are cut.
+fn markdown_cell(value: &str) -> String {
+    let mut cell = String::new();
+    for (count, ch) in value.chars().enumerate() {
+        if count == MAX_CELL_CHARS {
+            cell.push('…');
+            break;
+        }
+        match ch {
+            '|' => cell.push_str("\\|"),
+            '\n' | '\r' => cell.push(' '),
+            _ => cell.push(ch),
+        }
+    }
+    cell
+}
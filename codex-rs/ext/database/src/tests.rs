@@ -0,0 +1,326 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_extension_api::DenyCommandApprover;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::NoopTurnItemEmitter;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolContributor;
+use codex_extension_api::ToolPayload;
+use codex_utils_output_truncation::TruncationPolicy;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use sqlx::ConnectOptions;
+use sqlx::Connection;
+use sqlx::sqlite::SqliteConnectOptions;
+
+use crate::connection::ColumnSchema;
+use crate::connection::DatabaseSchema;
+use crate::connection::QueryResult;
+use crate::connection::TableSchema;
+use crate::connection::Target;
+use crate::connection::load_schema;
+use crate::connection::run_query;
+use crate::extension::DatabaseEntry;
+use crate::extension::DatabaseExtension;
+use crate::extension::DatabaseExtensionConfig;
+use crate::statement::check_read_only;
+use crate::table::markdown_table;
+use crate::tools::database_tool_name;
+
+fn enabled_thread_store() -> ExtensionData {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(DatabaseExtensionConfig {
+        enabled: true,
+        network_access: false,
+        databases: vec![DatabaseEntry {
+            name: "app".to_string(),
+            url: "app.db".to_string(),
+        }],
+    });
+    thread_store
+}
+
+async fn create_sqlite_database(path: &Path) {
+    let mut conn = SqliteConnectOptions::new()
+        .filename(path)
+        .create_if_missing(true)
+        .connect()
+        .await
+        .expect("create database");
+    for statement in [
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL, bio TEXT)",
+        "INSERT INTO users (name, bio) VALUES ('ada', 'math | engines'), ('grace', NULL), ('linus', 'kernels')",
+        "CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL, total REAL)",
+    ] {
+        sqlx::query(statement)
+            .execute(&mut conn)
+            .await
+            .expect("seed database");
+    }
+    conn.close().await.expect("close database");
+}
+
+#[test]
+fn install_registers_the_schema_and_query_tools() {
+    let mut builder = ExtensionRegistryBuilder::<codex_core::config::Config>::new();
+    crate::install(&mut builder);
+    let registry = builder.build();
+    let thread_store = enabled_thread_store();
+
+    let tool_names = registry
+        .tool_contributors()
+        .iter()
+        .flat_map(|contributor| contributor.tools(&ExtensionData::new("session"), &thread_store))
+        .map(|tool| tool.tool_name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        tool_names,
+        vec![
+            database_tool_name(crate::SCHEMA_TOOL_NAME),
+            database_tool_name(crate::QUERY_TOOL_NAME),
+        ]
+    );
+}
+
+#[test]
+fn tools_are_not_contributed_when_disabled_or_unconfigured() {
+    for config in [
+        DatabaseExtensionConfig {
+            enabled: false,
+            network_access: false,
+            databases: vec![DatabaseEntry {
+                name: "app".to_string(),
+                url: "app.db".to_string(),
+            }],
+        },
+        DatabaseExtensionConfig {
+            enabled: true,
+            network_access: false,
+            databases: Vec::new(),
+        },
+    ] {
+        let thread_store = ExtensionData::new("thread");
+        thread_store.insert(config);
+
+        assert!(
+            DatabaseExtension
+                .tools(&ExtensionData::new("session"), &thread_store)
+                .is_empty()
+        );
+    }
+}
+
+#[tokio::test]
+async fn tools_require_a_local_environment() {
+    let tools = DatabaseExtension.tools(&ExtensionData::new("session"), &enabled_thread_store());
+
+    let Err(err) = tools[1]
+        .handle(ToolCall {
+            turn_id: "turn-1".to_string(),
+            call_id: "call-1".to_string(),
+            tool_name: database_tool_name(crate::QUERY_TOOL_NAME),
+            model: "gpt-test".to_string(),
+            codex_turn_metadata: None,
+            truncation_policy: TruncationPolicy::Bytes(1024),
+            conversation_history: codex_extension_api::ConversationHistory::default(),
+            turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+            command_approver: Arc::new(DenyCommandApprover),
+            environments: Vec::new(),
+            payload: ToolPayload::Function {
+                arguments: json!({ "database": "app", "sql": "SELECT 1" }).to_string(),
+            },
+        })
+        .await
+    else {
+        panic!("query without a local environment should fail");
+    };
+
+    assert_eq!(
+        err,
+        FunctionCallError::RespondToModel(
+            "database tools are only available for local workspaces".to_string()
+        )
+    );
+}
+
+#[test]
+fn check_read_only_accepts_single_reads() {
+    for sql in [
+        "SELECT * FROM users",
+        "  with recent AS (SELECT 1) SELECT * FROM recent;",
+        "(SELECT 1) UNION (SELECT 2)",
+        "EXPLAIN QUERY PLAN SELECT * FROM users",
+        "PRAGMA table_info(users)",
+        "-- newest first\nSELECT name FROM users ORDER BY id DESC",
+        "SELECT 'a;b', \"odd;name\" FROM users",
+        "SELECT $body$ ; DELETE $body$",
+    ] {
+        assert_eq!(check_read_only(sql), Ok(()), "{sql}");
+    }
+}
+
+#[test]
+fn check_read_only_rejects_writes_and_multiple_statements() {
+    assert_eq!(
+        check_read_only("DELETE FROM users"),
+        Err(
+            "only read-only statements (SELECT, WITH, VALUES, EXPLAIN, SHOW, PRAGMA) are allowed, not `DELETE`"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        check_read_only("SELECT 1; DROP TABLE users"),
+        Err("run one statement at a time".to_string())
+    );
+    assert_eq!(
+        check_read_only("PRAGMA journal_mode = DELETE"),
+        Err("PRAGMA statements that change settings are not allowed".to_string())
+    );
+    assert_eq!(
+        check_read_only(" -- nothing\n ; "),
+        Err("the query is empty".to_string())
+    );
+}
+
+#[test]
+fn markdown_table_escapes_cells() {
+    let table = markdown_table(
+        &["id".to_string(), "note".to_string()],
+        &[
+            vec![Some("1".to_string()), Some("a | b\nc".to_string())],
+            vec![Some("2".to_string()), None],
+        ],
+    );
+
+    assert_eq!(
+        table,
+        "| id | note |\n| --- | --- |\n| 1 | a \\| b c |\n| 2 | NULL |"
+    );
+    assert_eq!(markdown_table(&[], &[]), "(no rows)");
+}
+
+#[test]
+fn resolve_keeps_network_databases_behind_permissions() {
+    let cwd = PathBuf::from("/work");
+
+    assert_eq!(
+        Target::resolve(
+            "sqlite://data/app.db?mode=ro",
+            &cwd,
+            /*network_access*/ false
+        ),
+        Ok(Target::Sqlite(PathBuf::from("/work/data/app.db")))
+    );
+    assert_eq!(
+        Target::resolve(
+            "postgres://localhost/app",
+            &cwd,
+            /*network_access*/ false
+        ),
+        Err(
+            "this database is reached over the network, which the current permissions do not allow"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        Target::resolve(
+            "postgres://localhost/app",
+            &cwd,
+            /*network_access*/ true
+        ),
+        Ok(Target::Postgres("postgres://localhost/app".to_string()))
+    );
+    assert_eq!(
+        Target::resolve("mysql://localhost/app", &cwd, /*network_access*/ true),
+        Err(
+            "unsupported database url `mysql://localhost/app`; use a SQLite path or a postgres:// url"
+                .to_string()
+        )
+    );
+}
+
+#[tokio::test]
+async fn sqlite_queries_return_capped_tables() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("app.db");
+    create_sqlite_database(&path).await;
+    let target = Target::Sqlite(path);
+
+    let result = run_query(
+        &target,
+        "SELECT id, name, bio FROM users ORDER BY id",
+        /*max_rows*/ 2,
+    )
+    .await
+    .expect("query");
+
+    assert_eq!(
+        result,
+        QueryResult {
+            table: "| id | name | bio |\n| --- | --- | --- |\n| 1 | ada | math \\| engines |\n| 2 | grace | NULL |"
+                .to_string(),
+            row_count: 2,
+            truncated: true,
+        }
+    );
+}
+
+#[tokio::test]
+async fn sqlite_connections_reject_writes() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("app.db");
+    create_sqlite_database(&path).await;
+
+    let err = run_query(
+        &Target::Sqlite(path),
+        "DELETE FROM users",
+        /*max_rows*/ 10,
+    )
+    .await
+    .expect_err("writes should fail");
+
+    assert!(err.contains("readonly"), "{err}");
+}
+
+#[tokio::test]
+async fn sqlite_schema_lists_tables_and_columns() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("app.db");
+    create_sqlite_database(&path).await;
+
+    let schema = load_schema(&Target::Sqlite(path), Some("user"))
+        .await
+        .expect("schema");
+
+    let column = |name: &str, data_type: &str, nullable: bool, primary_key: bool| ColumnSchema {
+        name: name.to_string(),
+        data_type: data_type.to_string(),
+        nullable,
+        primary_key,
+    };
+    assert_eq!(
+        schema,
+        DatabaseSchema {
+            tables: vec![TableSchema {
+                name: "users".to_string(),
+                columns: vec![
+                    column(
+                        "id", "INTEGER", /*nullable*/ true, /*primary_key*/ true
+                    ),
+                    column(
+                        "name", "TEXT", /*nullable*/ false, /*primary_key*/ false
+                    ),
+                    column(
+                        "bio", "TEXT", /*nullable*/ true, /*primary_key*/ false
+                    ),
+                ],
+            }],
+            truncated: false,
+        }
+    );
+}
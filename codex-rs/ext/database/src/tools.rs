@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::JsonToolOutput;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolExecutorFuture;
+use codex_extension_api::ToolName;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolSpec;
+use codex_extension_api::local_environment;
+use codex_extension_api::namespaced_function_tool;
+use codex_extension_api::parse_function_arguments;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::DATABASE_NAMESPACE;
+use crate::DEFAULT_MAX_ROWS;
+use crate::MAX_ROWS;
+use crate::QUERY_TOOL_NAME;
+use crate::SCHEMA_TOOL_NAME;
+use crate::connection::DatabaseSchema;
+use crate::connection::QueryResult;
+use crate::connection::Target;
+use crate::connection::load_schema;
+use crate::connection::run_query;
+use crate::extension::DatabaseEntry;
+use crate::statement::check_read_only;
+
+/// SQLite paths are opened on this host, so the tools only work against the local checkout.
+const LOCAL_WORKSPACE_REQUIRED: &str = "database tools are only available for local workspaces";
+
+pub(crate) fn database_tools(
+    databases: Vec<DatabaseEntry>,
+    network_access: bool,
+) -> Vec<Arc<dyn ToolExecutor<ToolCall>>> {
+    let databases = Arc::new(Databases {
+        entries: databases,
+        network_access,
+    });
+    vec![
+        Arc::new(SchemaTool {
+            databases: Arc::clone(&databases),
+        }),
+        Arc::new(QueryTool { databases }),
+    ]
+}
+
+struct Databases {
+    entries: Vec<DatabaseEntry>,
+    network_access: bool,
+}
+
+impl Databases {
+    fn names(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| format!("`{}`", entry.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn target(&self, name: &str, call: &ToolCall) -> Result<Target, FunctionCallError> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .ok_or_else(|| {
+                FunctionCallError::RespondToModel(format!(
+                    "unknown database `{name}`; configured databases: {}",
+                    self.names()
+                ))
+            })?;
+        let cwd = &local_environment(call, LOCAL_WORKSPACE_REQUIRED)?.cwd;
+        Target::resolve(&entry.url, cwd.as_path(), self.network_access)
+            .map_err(|err| FunctionCallError::RespondToModel(format!("{name}: {err}")))
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct SchemaArgs {
+    /// Name of a configured database.
+    database: String,
+    /// Only list tables whose name contains this text.
+    table: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct QueryArgs {
+    /// Name of a configured database.
+    database: String,
+    /// A single read-only statement, such as SELECT or EXPLAIN.
+    sql: String,
+    /// Maximum number of rows to return.
+    #[schemars(range(min = 1, max = 500))]
+    max_rows: Option<usize>,
+}
+
+struct SchemaTool {
+    databases: Arc<Databases>,
+}
+
+impl ToolExecutor<ToolCall> for SchemaTool {
+    fn tool_name(&self) -> ToolName {
+        database_tool_name(SCHEMA_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<SchemaArgs, DatabaseSchema>(
+            DATABASE_NAMESPACE,
+            SCHEMA_TOOL_NAME,
+            &format!(
+                "List the tables and views of a configured database with their columns, types, nullability, and primary keys. Configured databases: {}.",
+                self.databases.names()
+            ),
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: SchemaArgs = parse_function_arguments(&call)?;
+            let target = self.databases.target(&args.database, &call)?;
+            let schema = load_schema(
+                &target,
+                args.table.as_deref().filter(|table| !table.is_empty()),
+            )
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!("{}: {err}", args.database))
+            })?;
+            Ok(json_output(schema))
+        })
+    }
+}
+
+struct QueryTool {
+    databases: Arc<Databases>,
+}
+
+impl ToolExecutor<ToolCall> for QueryTool {
+    fn tool_name(&self) -> ToolName {
+        database_tool_name(QUERY_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<QueryArgs, QueryResult>(
+            DATABASE_NAMESPACE,
+            QUERY_TOOL_NAME,
+            &format!(
+                "Run one read-only SQL statement against a configured database and get the rows back as a Markdown table. Writes are rejected; the connection is read-only. Returns at most 50 rows unless max_rows is set. Configured databases: {}.",
+                self.databases.names()
+            ),
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: QueryArgs = parse_function_arguments(&call)?;
+            check_read_only(&args.sql).map_err(FunctionCallError::RespondToModel)?;
+            let target = self.databases.target(&args.database, &call)?;
+            let max_rows = args.max_rows.unwrap_or(DEFAULT_MAX_ROWS).clamp(1, MAX_ROWS);
+            let result = run_query(&target, &args.sql, max_rows)
+                .await
+                .map_err(|err| {
+                    FunctionCallError::RespondToModel(format!("{}: {err}", args.database))
+                })?;
+            Ok(json_output(result))
+        })
+    }
+}
+
+pub(crate) fn database_tool_name(name: &str) -> ToolName {
+    ToolName::namespaced(DATABASE_NAMESPACE, name)
+}
+
+fn json_output<T: Serialize>(value: T) -> Box<dyn ToolOutput> {
+    Box::new(JsonToolOutput::new(json!(value)))
+}
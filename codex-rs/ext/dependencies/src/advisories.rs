@@ -0,0 +1,250 @@
+//! Matches dependencies against a local directory of OSV advisories, such as the OSV export of
+//! RustSec or the per-ecosystem archives from osv.dev, so vulnerable versions can be found
+//! without network access.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_extension_api::ToolEnvironment;
+use ignore::WalkBuilder;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::manifests::Ecosystem;
+use crate::versions;
+
+/// An advisory that affects a dependency's locked version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct AdvisoryMatch {
+    pub(crate) id: String,
+    /// Other identifiers for the same issue, such as CVE ids.
+    pub(crate) aliases: Vec<String>,
+    pub(crate) summary: Option<String>,
+    /// Versions that fix the issue, from the advisory's ranges.
+    pub(crate) fixed_in: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct AdvisoryDb {
+    by_package: HashMap<(Ecosystem, String), Vec<Advisory>>,
+    pub(crate) advisories: usize,
+    /// Files that were not valid OSV JSON.
+    pub(crate) skipped_files: usize,
+}
+
+#[derive(Debug)]
+struct Advisory {
+    id: String,
+    aliases: Vec<String>,
+    summary: Option<String>,
+    affected: OsvAffected,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvRecord {
+    id: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    summary: Option<String>,
+    withdrawn: Option<String>,
+    #[serde(default)]
+    affected: Vec<OsvAffected>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OsvAffected {
+    package: Option<OsvPackage>,
+    #[serde(default)]
+    ranges: Vec<OsvRange>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OsvPackage {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OsvRange {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<OsvEvent>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OsvEvent {
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+}
+
+impl AdvisoryDb {
+    /// Reads every `*.json` file under `dir` through the environment's file system. Advisories
+    /// for other ecosystems and withdrawn advisories are dropped.
+    pub(crate) async fn load(environment: &ToolEnvironment, dir: &Path) -> Result<Self, String> {
+        let is_dir = environment
+            .metadata(&environment.cwd.join(dir))
+            .await
+            .is_ok_and(|metadata| metadata.is_directory);
+        if !is_dir {
+            return Err(format!("{} is not a directory", dir.display()));
+        }
+        let walk_dir = dir.to_path_buf();
+        let files = tokio::task::spawn_blocking(move || json_files(&walk_dir))
+            .await
+            .map_err(|err| format!("failed to list advisories: {err}"))?;
+        let mut db = AdvisoryDb::default();
+        for path in files {
+            let record = environment
+                .read_file(&environment.cwd.join(&path))
+                .await
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<OsvRecord>(&bytes).ok());
+            match record {
+                Some(record) => db.insert(record),
+                None => db.skipped_files += 1,
+            }
+        }
+        Ok(db)
+    }
+
+    fn insert(&mut self, record: OsvRecord) {
+        if record.withdrawn.is_some() {
+            return;
+        }
+        let mut counted = false;
+        for affected in record.affected {
+            let Some(package) = &affected.package else {
+                continue;
+            };
+            let Some(ecosystem) = ecosystem_from_osv(&package.ecosystem) else {
+                continue;
+            };
+            let key = (ecosystem, ecosystem.normalize_name(&package.name));
+            self.by_package.entry(key).or_default().push(Advisory {
+                id: record.id.clone(),
+                aliases: record.aliases.clone(),
+                summary: record.summary.clone(),
+                affected,
+            });
+            counted = true;
+        }
+        if counted {
+            self.advisories += 1;
+        }
+    }
+
+    /// Advisories whose affected versions include `version`.
+    pub(crate) fn matches(
+        &self,
+        ecosystem: Ecosystem,
+        name: &str,
+        version: &str,
+    ) -> Vec<AdvisoryMatch> {
+        let Some(advisories) = self
+            .by_package
+            .get(&(ecosystem, ecosystem.normalize_name(name)))
+        else {
+            return Vec::new();
+        };
+        let mut matches: Vec<AdvisoryMatch> = Vec::new();
+        for advisory in advisories {
+            if !affects(&advisory.affected, version)
+                || matches.iter().any(|existing| existing.id == advisory.id)
+            {
+                continue;
+            }
+            let fixed_in = advisory
+                .affected
+                .ranges
+                .iter()
+                .flat_map(|range| &range.events)
+                .filter_map(|event| event.fixed.clone())
+                .collect();
+            matches.push(AdvisoryMatch {
+                id: advisory.id.clone(),
+                aliases: advisory.aliases.clone(),
+                summary: advisory.summary.clone(),
+                fixed_in,
+            });
+        }
+        matches
+    }
+}
+
+fn json_files(dir: &Path) -> Vec<PathBuf> {
+    WalkBuilder::new(dir)
+        .standard_filters(false)
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+                && entry
+                    .path()
+                    .extension()
+                    .is_some_and(|extension| extension == "json")
+        })
+        .map(ignore::DirEntry::into_path)
+        .collect()
+}
+
+fn ecosystem_from_osv(name: &str) -> Option<Ecosystem> {
+    match name {
+        "crates.io" => Some(Ecosystem::Cargo),
+        "npm" => Some(Ecosystem::Npm),
+        "PyPI" => Some(Ecosystem::Python),
+        _ => None,
+    }
+}
+
+/// Whether `version` is listed in the advisory or falls in one of its ranges. Range events are
+/// evaluated in the order the record lists them, which OSV requires to be sorted.
+fn affects(affected: &OsvAffected, version: &str) -> bool {
+    if affected
+        .versions
+        .iter()
+        .any(|listed| versions::compare(listed, version) == Some(Ordering::Equal))
+    {
+        return true;
+    }
+    affected
+        .ranges
+        .iter()
+        .filter(|range| matches!(range.kind.as_str(), "SEMVER" | "ECOSYSTEM"))
+        .any(|range| {
+            let mut in_range = false;
+            for event in &range.events {
+                if let Some(introduced) = &event.introduced
+                    && (introduced == "0"
+                        || versions::compare(version, introduced)
+                            .is_some_and(|ordering| ordering != Ordering::Less))
+                {
+                    in_range = true;
+                }
+                if let Some(fixed) = &event.fixed
+                    && versions::compare(version, fixed)
+                        .is_some_and(|ordering| ordering != Ordering::Less)
+                {
+                    in_range = false;
+                }
+                if let Some(last_affected) = &event.last_affected
+                    && versions::compare(version, last_affected) == Some(Ordering::Greater)
+                {
+                    in_range = false;
+                }
+            }
+            in_range
+        })
+}
+
+#[cfg(test)]
+#[path = "advisories_tests.rs"]
+mod tests;
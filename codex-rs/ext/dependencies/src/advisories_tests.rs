@@ -0,0 +1,100 @@
+use codex_extension_api::new_local_environment;
+use codex_protocol::models::PermissionProfile;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use super::*;
+
+fn unsandboxed(cwd: &Path) -> ToolEnvironment {
+    new_local_environment(cwd, PermissionProfile::Disabled).expect("local environment")
+}
+
+fn write_advisory(dir: &Path, name: &str, advisory: serde_json::Value) {
+    std::fs::write(dir.join(name), advisory.to_string()).expect("write advisory");
+}
+
+#[tokio::test]
+async fn matches_versions_inside_osv_ranges() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    write_advisory(
+        temp.path(),
+        "RUSTSEC-2024-0001.json",
+        json!({
+            "id": "RUSTSEC-2024-0001",
+            "aliases": ["CVE-2024-1234"],
+            "summary": "Out-of-bounds read",
+            "affected": [{
+                "package": { "ecosystem": "crates.io", "name": "smallvec" },
+                "ranges": [{
+                    "type": "SEMVER",
+                    "events": [
+                        { "introduced": "0" },
+                        { "fixed": "1.6.1" },
+                        { "introduced": "2.0.0" },
+                        { "last_affected": "2.0.2" }
+                    ]
+                }]
+            }]
+        }),
+    );
+    write_advisory(
+        temp.path(),
+        "PYSEC-2023-1.json",
+        json!({
+            "id": "PYSEC-2023-1",
+            "affected": [{
+                "package": { "ecosystem": "PyPI", "name": "Requests" },
+                "versions": ["2.30.0"]
+            }]
+        }),
+    );
+    write_advisory(
+        temp.path(),
+        "withdrawn.json",
+        json!({
+            "id": "GHSA-withdrawn",
+            "withdrawn": "2024-01-01T00:00:00Z",
+            "affected": [{ "package": { "ecosystem": "npm", "name": "left-pad" }, "versions": ["1.3.0"] }]
+        }),
+    );
+    std::fs::write(temp.path().join("broken.json"), "{").expect("write broken file");
+
+    let db = AdvisoryDb::load(&unsandboxed(temp.path()), temp.path())
+        .await
+        .expect("load advisories");
+
+    assert_eq!((db.advisories, db.skipped_files), (2, 1));
+    assert_eq!(
+        db.matches(Ecosystem::Cargo, "smallvec", "1.6.0"),
+        vec![AdvisoryMatch {
+            id: "RUSTSEC-2024-0001".to_string(),
+            aliases: vec!["CVE-2024-1234".to_string()],
+            summary: Some("Out-of-bounds read".to_string()),
+            fixed_in: vec!["1.6.1".to_string()],
+        }]
+    );
+    assert_eq!(db.matches(Ecosystem::Cargo, "smallvec", "2.0.1").len(), 1);
+    assert_eq!(
+        db.matches(Ecosystem::Cargo, "smallvec", "1.6.1"),
+        Vec::new()
+    );
+    assert_eq!(
+        db.matches(Ecosystem::Cargo, "smallvec", "2.0.3"),
+        Vec::new()
+    );
+    assert_eq!(db.matches(Ecosystem::Python, "requests", "2.30.0").len(), 1);
+    assert_eq!(db.matches(Ecosystem::Npm, "left-pad", "1.3.0"), Vec::new());
+}
+
+#[tokio::test]
+async fn load_requires_a_directory() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let missing = temp.path().join("missing");
+
+    assert_eq!(
+        AdvisoryDb::load(&unsandboxed(temp.path()), &missing)
+            .await
+            .map(|db| db.advisories),
+        Err(format!("{} is not a directory", missing.display()))
+    );
+}
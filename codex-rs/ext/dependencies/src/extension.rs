@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::config::Config;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ToolContributor;
+use codex_features::Feature;
+
+use crate::DEFAULT_ADVISORY_DIR;
+use crate::registry::RegistryAccess;
+use crate::tools;
+
+/// Contributes a tool that reports outdated and vulnerable dependencies, so dependency updates
+/// do not start from grepping manifests.
+#[derive(Clone, Default)]
+pub(crate) struct DependenciesExtension;
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DependenciesExtensionConfig {
+    pub(crate) enabled: bool,
+    /// Advisory directory read when the tool call names none.
+    pub(crate) default_advisory_db: Option<PathBuf>,
+    pub(crate) registry_access: RegistryAccess,
+}
+
+impl DependenciesExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::DependencyReport),
+            default_advisory_db: Some(config.codex_home.join(DEFAULT_ADVISORY_DIR).into_path_buf()),
+            registry_access: RegistryAccess {
+                network_access: config.network_sandbox_policy().is_enabled(),
+                network: config.network.clone(),
+            },
+        }
+    }
+}
+
+impl ToolContributor for DependenciesExtension {
+    fn tools(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+    ) -> Vec<Arc<dyn codex_extension_api::ToolExecutor<codex_extension_api::ToolCall>>> {
+        let Some(config) = thread_store.get::<DependenciesExtensionConfig>() else {
+            return Vec::new();
+        };
+        if !config.enabled {
+            return Vec::new();
+        }
+
+        tools::dependency_tools(config.as_ref().clone())
+    }
+}
+
+/// Installs the dependency report extension contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    registry.thread_config(DependenciesExtensionConfig::from_config);
+    registry.tool_contributor(Arc::new(DependenciesExtension));
+}
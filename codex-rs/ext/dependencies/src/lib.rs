@@ -0,0 +1,26 @@
+mod advisories;
+mod extension;
+mod manifests;
+mod registry;
+mod report;
+mod tools;
+mod versions;
+
+use std::time::Duration;
+
+pub use extension::install;
+
+/// Directories below the scanned one that are searched for manifests.
+pub(crate) const MAX_SCAN_DEPTH: usize = 6;
+pub(crate) const MAX_MANIFESTS: usize = 50;
+pub(crate) const MAX_REGISTRY_LOOKUPS: usize = 300;
+pub(crate) const REGISTRY_CONCURRENCY: usize = 8;
+pub(crate) const REGISTRY_TIMEOUT: Duration = Duration::from_secs(15);
+/// Advisory directory under the Codex home used when the tool call names none.
+pub(crate) const DEFAULT_ADVISORY_DIR: &str = "advisories";
+
+pub(crate) const DEPENDENCIES_NAMESPACE: &str = "dependencies";
+pub(crate) const REPORT_TOOL_NAME: &str = "report";
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,556 @@
+//! Finds dependency manifests under a directory and reads their direct dependencies, with the
+//! version each one resolves to in the nearest lockfile. Files are read through the
+//! environment's file system, so the sandbox read policy applies.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_extension_api::ToolEnvironment;
+use ignore::WalkBuilder;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use toml::Value as TomlValue;
+
+use crate::MAX_MANIFESTS;
+use crate::MAX_SCAN_DEPTH;
+use crate::versions;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Ecosystem {
+    Cargo,
+    Npm,
+    Python,
+}
+
+impl Ecosystem {
+    /// Ecosystem name used by OSV advisories.
+    pub(crate) fn osv_name(self) -> &'static str {
+        match self {
+            Ecosystem::Cargo => "crates.io",
+            Ecosystem::Npm => "npm",
+            Ecosystem::Python => "PyPI",
+        }
+    }
+
+    /// Names as the registry compares them; Python treats `-`, `_`, and `.` alike.
+    pub(crate) fn normalize_name(self, name: &str) -> String {
+        match self {
+            Ecosystem::Python => name.to_ascii_lowercase().replace(['_', '.'], "-"),
+            Ecosystem::Cargo | Ecosystem::Npm => name.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DependencyKind {
+    Normal,
+    Dev,
+    Build,
+    Optional,
+    /// Declared once for a Cargo workspace, in `[workspace.dependencies]`.
+    Workspace,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DependencySource {
+    Registry,
+    Path,
+    Git,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ManifestDependency {
+    pub(crate) name: String,
+    pub(crate) kind: DependencyKind,
+    pub(crate) source: DependencySource,
+    /// Version requirement as written in the manifest.
+    pub(crate) requirement: Option<String>,
+    /// Version from the lockfile, or an exact pin in the manifest when no lockfile lists it.
+    pub(crate) locked: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Manifest {
+    pub(crate) path: PathBuf,
+    pub(crate) ecosystem: Ecosystem,
+    pub(crate) lockfile: Option<PathBuf>,
+    pub(crate) dependencies: Vec<ManifestDependency>,
+}
+
+/// Reads every manifest under `root`, skipping ignored files and build output. Manifests that
+/// fail to parse or cannot be read are reported in `notes`.
+pub(crate) async fn scan(
+    environment: &ToolEnvironment,
+    root: &Path,
+    notes: &mut Vec<String>,
+) -> Vec<Manifest> {
+    let walk_root = root.to_path_buf();
+    let mut paths = match tokio::task::spawn_blocking(move || manifest_paths(&walk_root)).await {
+        Ok(paths) => paths,
+        Err(err) => {
+            notes.push(format!("Failed to list manifests: {err}"));
+            return Vec::new();
+        }
+    };
+    if paths.len() > MAX_MANIFESTS {
+        notes.push(format!(
+            "Found {} manifests; only the first {MAX_MANIFESTS} were read. Pass a subdirectory \
+             as `path` to narrow the scan.",
+            paths.len()
+        ));
+        paths.truncate(MAX_MANIFESTS);
+    }
+
+    let mut manifests = Vec::new();
+    for (path, ecosystem) in paths {
+        match read_manifest(environment, root, &path, ecosystem).await {
+            Ok(Some(manifest)) => manifests.push(manifest),
+            Ok(None) => {}
+            Err(err) => notes.push(format!("Skipped {}: {err}", path.display())),
+        }
+    }
+    manifests
+}
+
+/// Manifest paths under `root` in sorted order. Only names are listed here; contents are read
+/// through the environment.
+fn manifest_paths(root: &Path) -> Vec<(PathBuf, Ecosystem)> {
+    let mut paths = WalkBuilder::new(root)
+        .max_depth(Some(MAX_SCAN_DEPTH))
+        .filter_entry(|entry| {
+            !matches!(
+                entry.file_name().to_str(),
+                Some("node_modules" | "target" | "venv" | "site-packages")
+            )
+        })
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+        })
+        .filter_map(|entry| {
+            let ecosystem = manifest_ecosystem(entry.file_name().to_str()?)?;
+            Some((entry.into_path(), ecosystem))
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+fn manifest_ecosystem(file_name: &str) -> Option<Ecosystem> {
+    match file_name {
+        "Cargo.toml" => Some(Ecosystem::Cargo),
+        "package.json" => Some(Ecosystem::Npm),
+        "pyproject.toml" => Some(Ecosystem::Python),
+        name if name.starts_with("requirements") && name.ends_with(".txt") => {
+            Some(Ecosystem::Python)
+        }
+        _ => None,
+    }
+}
+
+/// Returns `None` for manifests without dependencies, such as a `pyproject.toml` that only
+/// configures tools.
+async fn read_manifest(
+    environment: &ToolEnvironment,
+    root: &Path,
+    path: &Path,
+    ecosystem: Ecosystem,
+) -> Result<Option<Manifest>, String> {
+    let contents = read_text(environment, path).await?;
+    let is_requirements_txt = path.extension().is_some_and(|extension| extension == "txt");
+    let mut dependencies = match ecosystem {
+        Ecosystem::Cargo => parse_cargo_manifest(&contents)?,
+        Ecosystem::Npm => parse_package_json(&contents)?,
+        Ecosystem::Python if is_requirements_txt => parse_requirements_txt(&contents),
+        Ecosystem::Python => parse_pyproject(&contents)?,
+    };
+    if dependencies.is_empty() {
+        return Ok(None);
+    }
+
+    let lockfile = if is_requirements_txt {
+        None
+    } else {
+        find_lockfile(environment, root, path, ecosystem).await
+    };
+    let locked = match &lockfile {
+        Some(lockfile) => read_lockfile(environment, lockfile, ecosystem)
+            .await
+            .map_err(|err| format!("failed to read {}: {err}", lockfile.display()))?,
+        None => BTreeMap::new(),
+    };
+    for dependency in &mut dependencies {
+        if dependency.source != DependencySource::Registry {
+            continue;
+        }
+        let candidates = locked
+            .get(&ecosystem.normalize_name(&dependency.name))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        dependency.locked =
+            versions::pick_locked(ecosystem, dependency.requirement.as_deref(), candidates)
+                .or_else(|| {
+                    dependency
+                        .requirement
+                        .as_deref()
+                        .and_then(|requirement| versions::exact_pin(ecosystem, requirement))
+                });
+    }
+
+    Ok(Some(Manifest {
+        path: path.to_path_buf(),
+        ecosystem,
+        lockfile,
+        dependencies,
+    }))
+}
+
+/// Looks for the lockfile next to the manifest, then in parent directories up to `root`, where
+/// workspaces keep a single lockfile.
+async fn find_lockfile(
+    environment: &ToolEnvironment,
+    root: &Path,
+    manifest: &Path,
+    ecosystem: Ecosystem,
+) -> Option<PathBuf> {
+    let names: &[&str] = match ecosystem {
+        Ecosystem::Cargo => &["Cargo.lock"],
+        Ecosystem::Npm => &["package-lock.json", "npm-shrinkwrap.json"],
+        Ecosystem::Python => &["uv.lock", "poetry.lock"],
+    };
+    let mut dir = manifest.parent();
+    while let Some(current) = dir {
+        for name in names {
+            let candidate = current.join(name);
+            if environment
+                .metadata(&environment.cwd.join(&candidate))
+                .await
+                .is_ok_and(|metadata| metadata.is_file)
+            {
+                return Some(candidate);
+            }
+        }
+        if current == root {
+            break;
+        }
+        dir = current.parent();
+    }
+    None
+}
+
+/// Locked versions by normalized package name.
+async fn read_lockfile(
+    environment: &ToolEnvironment,
+    path: &Path,
+    ecosystem: Ecosystem,
+) -> Result<BTreeMap<String, Vec<String>>, String> {
+    let contents = read_text(environment, path).await?;
+    match ecosystem {
+        Ecosystem::Cargo | Ecosystem::Python => parse_toml_lockfile(&contents, ecosystem),
+        Ecosystem::Npm => parse_package_lock(&contents),
+    }
+}
+
+async fn read_text(environment: &ToolEnvironment, path: &Path) -> Result<String, String> {
+    environment
+        .read_file_text(&environment.cwd.join(path))
+        .await
+        .map_err(|err| err.to_string())
+}
+
+pub(crate) fn parse_cargo_manifest(contents: &str) -> Result<Vec<ManifestDependency>, String> {
+    let manifest: TomlValue = toml::from_str(contents).map_err(|err| err.to_string())?;
+    let mut dependencies = Vec::new();
+    let mut add_table = |table: Option<&TomlValue>, kind: DependencyKind| {
+        let Some(table) = table.and_then(TomlValue::as_table) else {
+            return;
+        };
+        let mut entries = table.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(key, _)| *key);
+        for (key, value) in entries {
+            dependencies.push(cargo_dependency(key, value, kind));
+        }
+    };
+
+    add_table(manifest.get("dependencies"), DependencyKind::Normal);
+    add_table(manifest.get("dev-dependencies"), DependencyKind::Dev);
+    add_table(manifest.get("build-dependencies"), DependencyKind::Build);
+    add_table(
+        manifest
+            .get("workspace")
+            .and_then(|workspace| workspace.get("dependencies")),
+        DependencyKind::Workspace,
+    );
+    if let Some(targets) = manifest.get("target").and_then(TomlValue::as_table) {
+        for target in targets.values() {
+            add_table(target.get("dependencies"), DependencyKind::Normal);
+            add_table(target.get("dev-dependencies"), DependencyKind::Dev);
+            add_table(target.get("build-dependencies"), DependencyKind::Build);
+        }
+    }
+    Ok(dependencies)
+}
+
+fn cargo_dependency(key: &str, value: &TomlValue, kind: DependencyKind) -> ManifestDependency {
+    let table = value.as_table();
+    let field = |name: &str| table.and_then(|table| table.get(name));
+    let name = field("package")
+        .and_then(TomlValue::as_str)
+        .unwrap_or(key)
+        .to_string();
+    let source = if field("path").is_some() {
+        DependencySource::Path
+    } else if field("git").is_some() {
+        DependencySource::Git
+    } else {
+        DependencySource::Registry
+    };
+    // `workspace = true` entries inherit their requirement from the workspace root.
+    let requirement = value
+        .as_str()
+        .or_else(|| field("version").and_then(TomlValue::as_str))
+        .map(str::to_string);
+    let kind = if kind == DependencyKind::Normal
+        && field("optional").and_then(TomlValue::as_bool) == Some(true)
+    {
+        DependencyKind::Optional
+    } else {
+        kind
+    };
+    ManifestDependency {
+        name,
+        kind,
+        source,
+        requirement,
+        locked: None,
+    }
+}
+
+/// Reads `Cargo.lock`, `uv.lock`, and `poetry.lock`, which all list `[[package]]` entries.
+fn parse_toml_lockfile(
+    contents: &str,
+    ecosystem: Ecosystem,
+) -> Result<BTreeMap<String, Vec<String>>, String> {
+    let lockfile: TomlValue = toml::from_str(contents).map_err(|err| err.to_string())?;
+    let mut locked: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for package in lockfile
+        .get("package")
+        .and_then(TomlValue::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let (Some(name), Some(version)) = (
+            package.get("name").and_then(TomlValue::as_str),
+            package.get("version").and_then(TomlValue::as_str),
+        ) else {
+            continue;
+        };
+        locked
+            .entry(ecosystem.normalize_name(name))
+            .or_default()
+            .push(version.to_string());
+    }
+    Ok(locked)
+}
+
+pub(crate) fn parse_package_json(contents: &str) -> Result<Vec<ManifestDependency>, String> {
+    let manifest: JsonValue = serde_json::from_str(contents).map_err(|err| err.to_string())?;
+    let mut dependencies = Vec::new();
+    for (field, kind) in [
+        ("dependencies", DependencyKind::Normal),
+        ("devDependencies", DependencyKind::Dev),
+        ("optionalDependencies", DependencyKind::Optional),
+    ] {
+        let Some(table) = manifest.get(field).and_then(JsonValue::as_object) else {
+            continue;
+        };
+        let mut entries = table.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(name, _)| *name);
+        for (name, requirement) in entries {
+            let requirement = requirement.as_str().unwrap_or_default();
+            let source = if requirement.starts_with("file:")
+                || requirement.starts_with("link:")
+                || requirement.starts_with("workspace:")
+            {
+                DependencySource::Path
+            } else if requirement.starts_with("git")
+                || requirement.starts_with("github:")
+                || requirement.contains("://")
+            {
+                DependencySource::Git
+            } else {
+                DependencySource::Registry
+            };
+            dependencies.push(ManifestDependency {
+                name: name.clone(),
+                kind,
+                source,
+                requirement: Some(requirement.to_string()),
+                locked: None,
+            });
+        }
+    }
+    Ok(dependencies)
+}
+
+/// Reads `package-lock.json`: the `packages` map of lockfile v2 and v3, or the `dependencies`
+/// map of v1.
+fn parse_package_lock(contents: &str) -> Result<BTreeMap<String, Vec<String>>, String> {
+    let lockfile: JsonValue = serde_json::from_str(contents).map_err(|err| err.to_string())?;
+    let mut locked: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    if let Some(packages) = lockfile.get("packages").and_then(JsonValue::as_object) {
+        for (path, package) in packages {
+            // Only top-level installs; nested `node_modules` hold other packages' copies.
+            let Some(name) = path.strip_prefix("node_modules/") else {
+                continue;
+            };
+            if name.contains("/node_modules/") {
+                continue;
+            }
+            if let Some(version) = package.get("version").and_then(JsonValue::as_str) {
+                locked
+                    .entry(name.to_string())
+                    .or_default()
+                    .push(version.to_string());
+            }
+        }
+    } else if let Some(dependencies) = lockfile.get("dependencies").and_then(JsonValue::as_object) {
+        for (name, package) in dependencies {
+            if let Some(version) = package.get("version").and_then(JsonValue::as_str) {
+                locked
+                    .entry(name.clone())
+                    .or_default()
+                    .push(version.to_string());
+            }
+        }
+    }
+    Ok(locked)
+}
+
+pub(crate) fn parse_requirements_txt(contents: &str) -> Vec<ManifestDependency> {
+    contents
+        .lines()
+        .filter_map(|line| parse_python_requirement(line, DependencyKind::Normal))
+        .collect()
+}
+
+/// Reads PEP 621 `[project]` dependencies and Poetry's dependency tables.
+pub(crate) fn parse_pyproject(contents: &str) -> Result<Vec<ManifestDependency>, String> {
+    let manifest: TomlValue = toml::from_str(contents).map_err(|err| err.to_string())?;
+    let mut dependencies = Vec::new();
+    if let Some(project) = manifest.get("project") {
+        for requirement in project
+            .get("dependencies")
+            .and_then(TomlValue::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(TomlValue::as_str)
+        {
+            dependencies.extend(parse_python_requirement(
+                requirement,
+                DependencyKind::Normal,
+            ));
+        }
+        for group in project
+            .get("optional-dependencies")
+            .and_then(TomlValue::as_table)
+            .into_iter()
+            .flat_map(|groups| groups.values())
+        {
+            for requirement in group
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(TomlValue::as_str)
+            {
+                dependencies.extend(parse_python_requirement(
+                    requirement,
+                    DependencyKind::Optional,
+                ));
+            }
+        }
+    }
+
+    let poetry = manifest.get("tool").and_then(|tool| tool.get("poetry"));
+    for (table, kind) in [
+        (
+            poetry.and_then(|poetry| poetry.get("dependencies")),
+            DependencyKind::Normal,
+        ),
+        (
+            poetry.and_then(|poetry| poetry.get("dev-dependencies")),
+            DependencyKind::Dev,
+        ),
+    ] {
+        for (name, value) in table.and_then(TomlValue::as_table).into_iter().flatten() {
+            if name == "python" {
+                continue;
+            }
+            let requirement = value
+                .as_str()
+                .or_else(|| value.get("version").and_then(TomlValue::as_str))
+                .map(str::to_string);
+            let source = if value.get("path").is_some() {
+                DependencySource::Path
+            } else if value.get("git").is_some() {
+                DependencySource::Git
+            } else {
+                DependencySource::Registry
+            };
+            dependencies.push(ManifestDependency {
+                name: name.clone(),
+                kind,
+                source,
+                requirement,
+                locked: None,
+            });
+        }
+    }
+    Ok(dependencies)
+}
+
+/// Parses one PEP 508 requirement such as `requests[socks]>=2.31; python_version > "3.8"`.
+/// Options, includes, and URL requirements are skipped.
+fn parse_python_requirement(line: &str, kind: DependencyKind) -> Option<ManifestDependency> {
+    let line = line.split(" #").next().unwrap_or_default().trim();
+    if line.is_empty() || line.starts_with(['#', '-']) || line.contains("://") {
+        return None;
+    }
+    let line = line.split(';').next().unwrap_or_default().trim();
+    let name_end = line
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.')))
+        .unwrap_or(line.len());
+    let name = &line[..name_end];
+    if name.is_empty() {
+        return None;
+    }
+    let mut rest = line[name_end..].trim_start();
+    if rest.starts_with('[') {
+        rest = rest
+            .find(']')
+            .map_or("", |end| rest[end + 1..].trim_start());
+    }
+    if rest.starts_with('@') {
+        return None;
+    }
+    let requirement = rest.trim_matches(|ch: char| ch == '(' || ch == ')' || ch.is_whitespace());
+    Some(ManifestDependency {
+        name: name.to_string(),
+        kind,
+        source: DependencySource::Registry,
+        requirement: (!requirement.is_empty()).then(|| requirement.to_string()),
+        locked: None,
+    })
+}
+
+#[cfg(test)]
+#[path = "manifests_tests.rs"]
+mod tests;
@@ -0,0 +1,264 @@
+use codex_extension_api::new_local_environment;
+use codex_protocol::models::PermissionProfile;
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+fn dependency(
+    name: &str,
+    kind: DependencyKind,
+    source: DependencySource,
+    requirement: Option<&str>,
+    locked: Option<&str>,
+) -> ManifestDependency {
+    ManifestDependency {
+        name: name.to_string(),
+        kind,
+        source,
+        requirement: requirement.map(str::to_string),
+        locked: locked.map(str::to_string),
+    }
+}
+
+#[test]
+fn cargo_manifest_reads_every_dependency_table() {
+    let manifest = r#"
+[package]
+name = "app"
+
+[dependencies]
+serde = "1.0"
+rand = { version = "0.8", optional = true }
+local = { path = "../local" }
+shared = { workspace = true }
+renamed = { package = "real-name", version = "2" }
+
+[dev-dependencies]
+tempfile = "3"
+
+[target.'cfg(unix)'.build-dependencies]
+cc = "1"
+"#;
+
+    assert_eq!(
+        parse_cargo_manifest(manifest).expect("manifest parses"),
+        vec![
+            dependency(
+                "local",
+                DependencyKind::Normal,
+                DependencySource::Path,
+                None,
+                None
+            ),
+            dependency(
+                "rand",
+                DependencyKind::Optional,
+                DependencySource::Registry,
+                Some("0.8"),
+                None
+            ),
+            dependency(
+                "real-name",
+                DependencyKind::Normal,
+                DependencySource::Registry,
+                Some("2"),
+                None
+            ),
+            dependency(
+                "serde",
+                DependencyKind::Normal,
+                DependencySource::Registry,
+                Some("1.0"),
+                None
+            ),
+            dependency(
+                "shared",
+                DependencyKind::Normal,
+                DependencySource::Registry,
+                None,
+                None
+            ),
+            dependency(
+                "tempfile",
+                DependencyKind::Dev,
+                DependencySource::Registry,
+                Some("3"),
+                None
+            ),
+            dependency(
+                "cc",
+                DependencyKind::Build,
+                DependencySource::Registry,
+                Some("1"),
+                None
+            ),
+        ]
+    );
+}
+
+#[test]
+fn python_requirements_skip_options_and_urls() {
+    let requirements = "\
+# pinned
+requests[socks]==2.31.0 ; python_version > \"3.8\"
+Django>=4.2,<5  # LTS
+-r dev.txt
+--index-url https://example.com/simple
+pkg @ https://example.com/pkg.whl
+flask
+";
+
+    assert_eq!(
+        parse_requirements_txt(requirements),
+        vec![
+            dependency(
+                "requests",
+                DependencyKind::Normal,
+                DependencySource::Registry,
+                Some("==2.31.0"),
+                None
+            ),
+            dependency(
+                "Django",
+                DependencyKind::Normal,
+                DependencySource::Registry,
+                Some(">=4.2,<5"),
+                None
+            ),
+            dependency(
+                "flask",
+                DependencyKind::Normal,
+                DependencySource::Registry,
+                None,
+                None
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn scan_resolves_locked_versions_from_workspace_lockfiles() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+    std::fs::create_dir_all(root.join("crates/app")).expect("create crate dir");
+    std::fs::write(
+        root.join("crates/app/Cargo.toml"),
+        "[package]\nname = \"app\"\n\n[dependencies]\nrand = \"0.8\"\nlog = \"=0.4.20\"\n",
+    )
+    .expect("write manifest");
+    std::fs::write(
+        root.join("Cargo.lock"),
+        r#"version = 3
+
+[[package]]
+name = "rand"
+version = "0.8.5"
+
+[[package]]
+name = "rand"
+version = "0.9.1"
+"#,
+    )
+    .expect("write lockfile");
+    std::fs::create_dir_all(root.join("web/node_modules/left-pad")).expect("create web dir");
+    std::fs::write(
+        root.join("web/package.json"),
+        r#"{ "dependencies": { "left-pad": "^1.3.0" }, "devDependencies": { "local": "file:../local" } }"#,
+    )
+    .expect("write package.json");
+    std::fs::write(
+        root.join("web/node_modules/left-pad/package.json"),
+        r#"{ "dependencies": { "ignored": "1.0.0" } }"#,
+    )
+    .expect("write installed package.json");
+    std::fs::write(
+        root.join("web/package-lock.json"),
+        r#"{ "lockfileVersion": 3, "packages": { "": {}, "node_modules/left-pad": { "version": "1.3.0" } } }"#,
+    )
+    .expect("write package-lock.json");
+
+    let mut notes = Vec::new();
+    let manifests = scan(
+        &new_local_environment(root, PermissionProfile::Disabled).expect("local environment"),
+        root,
+        &mut notes,
+    )
+    .await;
+
+    assert_eq!(notes, Vec::<String>::new());
+    assert_eq!(
+        manifests,
+        vec![
+            Manifest {
+                path: root.join("crates/app/Cargo.toml"),
+                ecosystem: Ecosystem::Cargo,
+                lockfile: Some(root.join("Cargo.lock")),
+                dependencies: vec![
+                    dependency(
+                        "log",
+                        DependencyKind::Normal,
+                        DependencySource::Registry,
+                        Some("=0.4.20"),
+                        Some("0.4.20")
+                    ),
+                    dependency(
+                        "rand",
+                        DependencyKind::Normal,
+                        DependencySource::Registry,
+                        Some("0.8"),
+                        Some("0.8.5")
+                    ),
+                ],
+            },
+            Manifest {
+                path: root.join("web/package.json"),
+                ecosystem: Ecosystem::Npm,
+                lockfile: Some(root.join("web/package-lock.json")),
+                dependencies: vec![
+                    dependency(
+                        "left-pad",
+                        DependencyKind::Normal,
+                        DependencySource::Registry,
+                        Some("^1.3.0"),
+                        Some("1.3.0")
+                    ),
+                    dependency(
+                        "local",
+                        DependencyKind::Dev,
+                        DependencySource::Path,
+                        Some("file:../local"),
+                        None
+                    ),
+                ],
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn scan_reads_manifests_through_the_sandbox() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"app\"\n\n[dependencies]\nrand = \"0.8\"\n",
+    )
+    .expect("write manifest");
+
+    // Unit tests do not configure the sandboxed file system helper, so a restricted profile
+    // cannot read anything; the scan must report that instead of reading the file directly.
+    let mut notes = Vec::new();
+    let manifests = scan(
+        &new_local_environment(root, PermissionProfile::read_only()).expect("local environment"),
+        root,
+        &mut notes,
+    )
+    .await;
+
+    assert_eq!(manifests, Vec::new());
+    assert_eq!(notes.len(), 1);
+    assert!(
+        notes[0].starts_with(&format!("Skipped {}: ", root.join("Cargo.toml").display())),
+        "{notes:?}"
+    );
+}
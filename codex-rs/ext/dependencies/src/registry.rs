@@ -0,0 +1,211 @@
+//! Looks up the newest published version of packages on crates.io, the npm registry, and PyPI.
+//! Lookups face the same rules a sandboxed command would: network access must be allowed and
+//! the managed proxy must let the registry host through.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use codex_core::config::HostBlockDecision;
+use codex_core::config::NetworkProxySpec;
+use codex_http_client::build_reqwest_client_with_custom_ca;
+use futures::StreamExt;
+use serde_json::Value;
+use url::Url;
+
+use crate::MAX_REGISTRY_LOOKUPS;
+use crate::REGISTRY_CONCURRENCY;
+use crate::REGISTRY_TIMEOUT;
+use crate::manifests::Ecosystem;
+
+const USER_AGENT: &str = "codex-dependencies (https://github.com/openai/codex)";
+
+#[derive(Debug, Clone)]
+pub(crate) struct Registries {
+    pub(crate) crates_io: String,
+    pub(crate) npm: String,
+    pub(crate) pypi: String,
+}
+
+impl Default for Registries {
+    fn default() -> Self {
+        Self {
+            crates_io: "https://crates.io".to_string(),
+            npm: "https://registry.npmjs.org".to_string(),
+            pypi: "https://pypi.org".to_string(),
+        }
+    }
+}
+
+impl Registries {
+    fn base(&self, ecosystem: Ecosystem) -> &str {
+        match ecosystem {
+            Ecosystem::Cargo => &self.crates_io,
+            Ecosystem::Npm => &self.npm,
+            Ecosystem::Python => &self.pypi,
+        }
+    }
+
+    fn package_url(&self, ecosystem: Ecosystem, name: &str) -> String {
+        let base = self.base(ecosystem).trim_end_matches('/');
+        match ecosystem {
+            Ecosystem::Cargo => format!("{base}/api/v1/crates/{name}"),
+            // Scoped packages keep their `@` but escape the `/`.
+            Ecosystem::Npm => format!("{base}/{}", name.replace('/', "%2f")),
+            Ecosystem::Python => format!("{base}/pypi/{name}/json"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RegistryAccess {
+    pub(crate) network_access: bool,
+    pub(crate) network: Option<NetworkProxySpec>,
+}
+
+impl RegistryAccess {
+    async fn check_host(&self, base: &str) -> Result<(), String> {
+        if !self.network_access {
+            return Err("network access is not allowed by the current permissions".to_string());
+        }
+        let url =
+            Url::parse(base).map_err(|err| format!("invalid registry url `{base}`: {err}"))?;
+        let Some(network) = &self.network else {
+            return Ok(());
+        };
+        let host = url.host_str().unwrap_or_default();
+        let port = url.port_or_known_default().unwrap_or(443);
+        match network
+            .host_block_decision(host, port)
+            .await
+            .map_err(|err| err.to_string())?
+        {
+            HostBlockDecision::Allowed => Ok(()),
+            HostBlockDecision::Blocked(reason) => Err(format!(
+                "{host} is blocked by the network policy ({reason})"
+            )),
+        }
+    }
+}
+
+/// Newest published versions by ecosystem and name. Packages that could not be looked up are
+/// missing from the result and explained in `notes`.
+pub(crate) async fn latest_versions(
+    registries: &Registries,
+    access: &RegistryAccess,
+    packages: BTreeSet<(Ecosystem, String)>,
+    notes: &mut Vec<String>,
+) -> BTreeMap<(Ecosystem, String), String> {
+    let mut allowed = BTreeSet::new();
+    for ecosystem in packages
+        .iter()
+        .map(|(ecosystem, _)| *ecosystem)
+        .collect::<BTreeSet<_>>()
+    {
+        match access.check_host(registries.base(ecosystem)).await {
+            Ok(()) => {
+                allowed.insert(ecosystem);
+            }
+            Err(err) => notes.push(format!(
+                "Skipped latest-version lookups for {}: {err}",
+                ecosystem.osv_name()
+            )),
+        }
+    }
+
+    let mut packages = packages
+        .into_iter()
+        .filter(|(ecosystem, _)| allowed.contains(ecosystem))
+        .collect::<Vec<_>>();
+    if packages.len() > MAX_REGISTRY_LOOKUPS {
+        notes.push(format!(
+            "Looked up the latest versions of only the first {MAX_REGISTRY_LOOKUPS} of {} \
+             packages.",
+            packages.len()
+        ));
+        packages.truncate(MAX_REGISTRY_LOOKUPS);
+    }
+    if packages.is_empty() {
+        return BTreeMap::new();
+    }
+
+    let client = match build_reqwest_client_with_custom_ca(
+        reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(REGISTRY_TIMEOUT),
+    ) {
+        Ok(client) => client,
+        Err(err) => {
+            notes.push(format!("Failed to build HTTP client: {err}"));
+            return BTreeMap::new();
+        }
+    };
+
+    let results = futures::stream::iter(packages)
+        .map(|(ecosystem, name)| {
+            let client = client.clone();
+            let url = registries.package_url(ecosystem, &name);
+            async move {
+                let latest = fetch_latest(&client, ecosystem, &url).await;
+                ((ecosystem, name), latest)
+            }
+        })
+        .buffer_unordered(REGISTRY_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut latest = BTreeMap::new();
+    let mut failures = Vec::new();
+    for ((ecosystem, name), result) in results {
+        match result {
+            Ok(version) => {
+                latest.insert((ecosystem, name), version);
+            }
+            Err(err) => failures.push(format!("{name}: {err}")),
+        }
+    }
+    if !failures.is_empty() {
+        failures.sort();
+        notes.push(format!(
+            "Could not look up the latest version of {} package(s), for example {}.",
+            failures.len(),
+            failures[0]
+        ));
+    }
+    latest
+}
+
+async fn fetch_latest(
+    client: &reqwest::Client,
+    ecosystem: Ecosystem,
+    url: &str,
+) -> Result<String, String> {
+    let mut request = client.get(url);
+    if ecosystem == Ecosystem::Npm {
+        // The abbreviated document is much smaller and still carries `dist-tags`.
+        request = request.header(
+            reqwest::header::ACCEPT,
+            "application/vnd.npm.install-v1+json",
+        );
+    }
+    let response = request.send().await.map_err(|err| err.to_string())?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("registry returned {status}"));
+    }
+    let body: Value = response.json().await.map_err(|err| err.to_string())?;
+    let latest = match ecosystem {
+        Ecosystem::Cargo => body
+            .pointer("/crate/max_stable_version")
+            .and_then(Value::as_str)
+            .or_else(|| body.pointer("/crate/max_version").and_then(Value::as_str)),
+        Ecosystem::Npm => body.pointer("/dist-tags/latest").and_then(Value::as_str),
+        Ecosystem::Python => body.pointer("/info/version").and_then(Value::as_str),
+    };
+    latest
+        .map(str::to_string)
+        .ok_or_else(|| "registry response has no latest version".to_string())
+}
+
+#[cfg(test)]
+#[path = "registry_tests.rs"]
+mod tests;
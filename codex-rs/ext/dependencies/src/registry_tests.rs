@@ -0,0 +1,102 @@
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+use super::*;
+
+fn registries(server: &MockServer) -> Registries {
+    Registries {
+        crates_io: server.uri(),
+        npm: server.uri(),
+        pypi: server.uri(),
+    }
+}
+
+#[tokio::test]
+async fn looks_up_latest_versions_per_ecosystem() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/crates/serde"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "crate": { "max_stable_version": "1.0.219", "max_version": "2.0.0-alpha.1" }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/@types%2fnode"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "dist-tags": { "latest": "22.1.0" } })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/pypi/requests/json"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let mut notes = Vec::new();
+    let latest = latest_versions(
+        &registries(&server),
+        &RegistryAccess {
+            network_access: true,
+            network: None,
+        },
+        BTreeSet::from([
+            (Ecosystem::Cargo, "serde".to_string()),
+            (Ecosystem::Npm, "@types/node".to_string()),
+            (Ecosystem::Python, "requests".to_string()),
+        ]),
+        &mut notes,
+    )
+    .await;
+
+    assert_eq!(
+        latest,
+        BTreeMap::from([
+            (
+                (Ecosystem::Cargo, "serde".to_string()),
+                "1.0.219".to_string()
+            ),
+            (
+                (Ecosystem::Npm, "@types/node".to_string()),
+                "22.1.0".to_string()
+            ),
+        ])
+    );
+    assert_eq!(
+        notes,
+        vec![
+            "Could not look up the latest version of 1 package(s), for example requests: \
+             registry returned 404 Not Found."
+                .to_string()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn lookups_need_network_access() {
+    let mut notes = Vec::new();
+    let latest = latest_versions(
+        &Registries::default(),
+        &RegistryAccess::default(),
+        BTreeSet::from([(Ecosystem::Cargo, "serde".to_string())]),
+        &mut notes,
+    )
+    .await;
+
+    assert_eq!(latest, BTreeMap::new());
+    assert_eq!(
+        notes,
+        vec![
+            "Skipped latest-version lookups for crates.io: network access is not allowed by \
+             the current permissions"
+                .to_string()
+        ]
+    );
+}
@@ -0,0 +1,167 @@
+//! Combines manifests, advisories, and registry versions into the report returned to the model.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::advisories::AdvisoryDb;
+use crate::advisories::AdvisoryMatch;
+use crate::manifests::DependencyKind;
+use crate::manifests::DependencySource;
+use crate::manifests::Ecosystem;
+use crate::manifests::Manifest;
+use crate::versions;
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct DependencyReport {
+    pub(crate) summary: ReportSummary,
+    pub(crate) manifests: Vec<ManifestReport>,
+    /// What could not be read or checked.
+    pub(crate) notes: Vec<String>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct ReportSummary {
+    pub(crate) dependencies: usize,
+    /// Dependencies whose locked version is older than the newest published version.
+    pub(crate) outdated: usize,
+    /// Outdated dependencies whose newest version is a semver-incompatible upgrade.
+    pub(crate) breaking_updates: usize,
+    /// Dependencies whose locked version has a known advisory.
+    pub(crate) vulnerable: usize,
+    /// Whether registries were asked for the newest versions.
+    pub(crate) latest_checked: bool,
+    /// Whether an advisory database was read.
+    pub(crate) advisories_checked: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct ManifestReport {
+    pub(crate) path: String,
+    pub(crate) ecosystem: Ecosystem,
+    pub(crate) lockfile: Option<String>,
+    pub(crate) dependencies: Vec<DependencyEntry>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct DependencyEntry {
+    pub(crate) name: String,
+    pub(crate) kind: DependencyKind,
+    pub(crate) source: DependencySource,
+    /// Version requirement as written in the manifest.
+    pub(crate) requirement: Option<String>,
+    /// Version from the lockfile, or an exact pin in the manifest when no lockfile lists it.
+    pub(crate) locked: Option<String>,
+    /// Newest published version, when registries were checked.
+    pub(crate) latest: Option<String>,
+    pub(crate) outdated: bool,
+    /// Whether upgrading to `latest` crosses a semver compatibility boundary.
+    pub(crate) breaking_update: Option<bool>,
+    pub(crate) advisories: Vec<AdvisoryMatch>,
+}
+
+/// Registry packages whose newest version is worth looking up.
+pub(crate) fn registry_packages(manifests: &[Manifest]) -> BTreeSet<(Ecosystem, String)> {
+    manifests
+        .iter()
+        .flat_map(|manifest| {
+            manifest
+                .dependencies
+                .iter()
+                .filter(|dependency| dependency.source == DependencySource::Registry)
+                .map(|dependency| {
+                    (
+                        manifest.ecosystem,
+                        manifest.ecosystem.normalize_name(&dependency.name),
+                    )
+                })
+        })
+        .collect()
+}
+
+pub(crate) fn build(
+    root: &Path,
+    manifests: Vec<Manifest>,
+    advisories: Option<&AdvisoryDb>,
+    latest: Option<&BTreeMap<(Ecosystem, String), String>>,
+    only_findings: bool,
+    notes: Vec<String>,
+) -> DependencyReport {
+    let mut summary = ReportSummary {
+        latest_checked: latest.is_some(),
+        advisories_checked: advisories.is_some(),
+        ..ReportSummary::default()
+    };
+    let relative = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+
+    let mut reports = Vec::new();
+    for manifest in manifests {
+        let ecosystem = manifest.ecosystem;
+        let mut dependencies = Vec::new();
+        for dependency in manifest.dependencies {
+            let key = (ecosystem, ecosystem.normalize_name(&dependency.name));
+            let latest = latest.and_then(|latest| latest.get(&key)).cloned();
+            let outdated = match (&dependency.locked, &latest) {
+                (Some(locked), Some(latest)) => {
+                    versions::compare(latest, locked) == Some(Ordering::Greater)
+                }
+                _ => false,
+            };
+            let breaking_update = match (&dependency.locked, &latest) {
+                (Some(locked), Some(latest)) if outdated => {
+                    versions::is_breaking_update(locked, latest)
+                }
+                _ => None,
+            };
+            let matches = match (advisories, &dependency.locked) {
+                (Some(db), Some(locked)) if dependency.source == DependencySource::Registry => {
+                    db.matches(ecosystem, &dependency.name, locked)
+                }
+                _ => Vec::new(),
+            };
+
+            summary.dependencies += 1;
+            summary.outdated += usize::from(outdated);
+            summary.breaking_updates += usize::from(breaking_update == Some(true));
+            summary.vulnerable += usize::from(!matches.is_empty());
+            if only_findings && !outdated && matches.is_empty() {
+                continue;
+            }
+            dependencies.push(DependencyEntry {
+                name: dependency.name,
+                kind: dependency.kind,
+                source: dependency.source,
+                requirement: dependency.requirement,
+                locked: dependency.locked,
+                latest,
+                outdated,
+                breaking_update,
+                advisories: matches,
+            });
+        }
+        if only_findings && dependencies.is_empty() {
+            continue;
+        }
+        reports.push(ManifestReport {
+            path: relative(&manifest.path),
+            ecosystem,
+            lockfile: manifest.lockfile.as_deref().map(relative),
+            dependencies,
+        });
+    }
+
+    DependencyReport {
+        summary,
+        manifests: reports,
+        notes,
+    }
+}
@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::JsonToolOutput;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolExecutorFuture;
+use codex_extension_api::ToolName;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolSpec;
+use codex_extension_api::local_environment;
+use codex_extension_api::namespaced_function_tool;
+use codex_extension_api::parse_function_arguments;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::DEPENDENCIES_NAMESPACE;
+use crate::REPORT_TOOL_NAME;
+use crate::advisories::AdvisoryDb;
+use crate::extension::DependenciesExtensionConfig;
+use crate::manifests;
+use crate::registry;
+use crate::registry::Registries;
+use crate::report;
+use crate::report::DependencyReport;
+
+/// Manifests are found by walking this host's file system, so the tool only works against the
+/// local checkout.
+const LOCAL_WORKSPACE_REQUIRED: &str = "dependency tools are only available for local workspaces";
+
+pub(crate) fn dependency_tools(
+    config: DependenciesExtensionConfig,
+) -> Vec<Arc<dyn ToolExecutor<ToolCall>>> {
+    vec![Arc::new(ReportTool { config })]
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct ReportArgs {
+    /// Directory to scan for manifests, relative to the working directory. Defaults to the
+    /// working directory.
+    path: Option<String>,
+    /// Look up the newest published version of each dependency on crates.io, npm, and PyPI.
+    /// Needs network access.
+    check_latest: Option<bool>,
+    /// Directory of OSV advisory JSON files to check locked versions against. Defaults to
+    /// `advisories` under the Codex home directory when it exists.
+    advisory_db: Option<String>,
+    /// Only list dependencies that are outdated or have advisories.
+    only_findings: Option<bool>,
+}
+
+struct ReportTool {
+    config: DependenciesExtensionConfig,
+}
+
+impl ToolExecutor<ToolCall> for ReportTool {
+    fn tool_name(&self) -> ToolName {
+        dependencies_tool_name(REPORT_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<ReportArgs, DependencyReport>(
+            DEPENDENCIES_NAMESPACE,
+            REPORT_TOOL_NAME,
+            "Report the direct dependencies declared in Cargo.toml, package.json, pyproject.toml, and requirements*.txt files under a directory, with the version each is locked to. Optionally checks locked versions against a local OSV advisory database and the registries' newest versions, flagging outdated, breaking, and vulnerable ones. Use it before updating dependencies instead of reading manifests and lockfiles by hand.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: ReportArgs = parse_function_arguments(&call)?;
+            let environment = local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?;
+            let root = args.path.as_ref().map_or_else(
+                || environment.cwd.clone(),
+                |path| environment.resolve_path(path),
+            );
+            let is_dir = environment
+                .metadata(&root)
+                .await
+                .is_ok_and(|metadata| metadata.is_directory);
+            if !is_dir {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{} is not a directory",
+                    root.display()
+                )));
+            }
+            let root = root.into_path_buf();
+
+            let mut notes = Vec::new();
+            let manifests = manifests::scan(environment, &root, &mut notes).await;
+            let advisories = match args.advisory_db {
+                Some(dir) => Some(
+                    AdvisoryDb::load(environment, environment.resolve_path(&dir).as_path())
+                        .await
+                        .map_err(FunctionCallError::RespondToModel)?,
+                ),
+                None => match &self.config.default_advisory_db {
+                    // The default directory is optional, so a missing one is not an error.
+                    Some(dir) => AdvisoryDb::load(environment, dir).await.ok(),
+                    None => None,
+                },
+            };
+
+            if manifests.is_empty() {
+                notes.push(
+                    "No Cargo.toml, package.json, pyproject.toml, or requirements*.txt with \
+                     dependencies was found."
+                        .to_string(),
+                );
+            }
+            match &advisories {
+                Some(db) if db.skipped_files > 0 => notes.push(format!(
+                    "Skipped {} advisory file(s) that were not OSV JSON.",
+                    db.skipped_files
+                )),
+                Some(_) => {}
+                None => notes.push(
+                    "Vulnerabilities were not checked: no advisory database. Pass `advisory_db` \
+                     with a directory of OSV JSON advisories."
+                        .to_string(),
+                ),
+            }
+
+            let check_latest = args.check_latest.unwrap_or(false);
+            let latest = if check_latest {
+                Some(
+                    registry::latest_versions(
+                        &Registries::default(),
+                        &self.config.registry_access,
+                        report::registry_packages(&manifests),
+                        &mut notes,
+                    )
+                    .await,
+                )
+            } else {
+                None
+            };
+
+            let report = report::build(
+                &root,
+                manifests,
+                advisories.as_ref(),
+                latest.as_ref(),
+                args.only_findings.unwrap_or(false),
+                notes,
+            );
+            let output = JsonToolOutput::new(json!(report));
+            // Versions and advisory text come from outside the workspace.
+            let output: Box<dyn ToolOutput> = if check_latest || report.summary.advisories_checked {
+                Box::new(output.with_external_context())
+            } else {
+                Box::new(output)
+            };
+            Ok(output)
+        })
+    }
+}
+
+pub(crate) fn dependencies_tool_name(name: &str) -> ToolName {
+    ToolName::namespaced(DEPENDENCIES_NAMESPACE, name)
+}
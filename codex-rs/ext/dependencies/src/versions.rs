@@ -0,0 +1,117 @@
+//! Version comparison across ecosystems. Cargo and npm versions are semver; Python versions are
+//! compared by their numeric release segments, which covers the common cases of PEP 440.
+
+use std::cmp::Ordering;
+
+use semver::Version;
+use semver::VersionReq;
+
+use crate::manifests::Ecosystem;
+
+/// Orders two versions, or `None` when either has no numeric release segment.
+pub(crate) fn compare(left: &str, right: &str) -> Option<Ordering> {
+    if let (Some(left), Some(right)) = (parse_semver(left), parse_semver(right)) {
+        return Some(left.cmp(&right));
+    }
+    let left = release_segments(left)?;
+    let right = release_segments(right)?;
+    let len = left.len().max(right.len());
+    let pad = |segments: &[u64]| {
+        let mut padded = segments.to_vec();
+        padded.resize(len, 0);
+        padded
+    };
+    Some(pad(&left).cmp(&pad(&right)))
+}
+
+/// Whether moving from `from` to `to` crosses a compatibility boundary the way Cargo's caret
+/// requirements draw it: a change in the leftmost nonzero of major, minor, and patch.
+pub(crate) fn is_breaking_update(from: &str, to: &str) -> Option<bool> {
+    let from = release_segments(from)?;
+    let to = release_segments(to)?;
+    let segment = |segments: &[u64], index: usize| segments.get(index).copied().unwrap_or(0);
+    let boundary = (0..3)
+        .find(|index| segment(&from, *index) != 0)
+        .unwrap_or(2);
+    Some((0..=boundary).any(|index| segment(&from, index) != segment(&to, index)))
+}
+
+/// Chooses the locked version a requirement resolved to when a lockfile lists several copies
+/// of a package: the newest one the requirement allows, else the newest one.
+pub(crate) fn pick_locked(
+    ecosystem: Ecosystem,
+    requirement: Option<&str>,
+    candidates: &[String],
+) -> Option<String> {
+    let newest = |versions: Vec<&String>| {
+        versions
+            .into_iter()
+            .reduce(|best, version| {
+                if compare(version, best) == Some(Ordering::Greater) {
+                    version
+                } else {
+                    best
+                }
+            })
+            .cloned()
+    };
+    if ecosystem == Ecosystem::Cargo
+        && let Some(requirement) =
+            requirement.and_then(|requirement| VersionReq::parse(requirement).ok())
+    {
+        let matching = candidates
+            .iter()
+            .filter(|version| {
+                Version::parse(version).is_ok_and(|version| requirement.matches(&version))
+            })
+            .collect::<Vec<_>>();
+        if !matching.is_empty() {
+            return newest(matching);
+        }
+    }
+    newest(candidates.iter().collect())
+}
+
+/// The version a requirement pins exactly, such as `=1.2.3` in Cargo, `1.2.3` in npm, or
+/// `==1.2.3` in Python.
+pub(crate) fn exact_pin(ecosystem: Ecosystem, requirement: &str) -> Option<String> {
+    let requirement = requirement.trim();
+    let version = match ecosystem {
+        Ecosystem::Cargo => requirement.strip_prefix('=')?,
+        Ecosystem::Npm => requirement.strip_prefix('=').unwrap_or(requirement),
+        Ecosystem::Python => requirement.strip_prefix("==")?,
+    }
+    .trim();
+    let is_plain_version = !version.is_empty()
+        && version.starts_with(|ch: char| ch.is_ascii_digit())
+        && !version.contains(['*', ',', ' ', '|', '<', '>', '^', '~', 'x']);
+    is_plain_version.then(|| version.to_string())
+}
+
+fn parse_semver(version: &str) -> Option<Version> {
+    Version::parse(version.trim().trim_start_matches(['v', '='])).ok()
+}
+
+/// Leading numeric segments of a version, such as `[2, 31, 0]` for `2.31.0rc1`, up to the first
+/// non-numeric character.
+fn release_segments(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches(['v', '=']);
+    let mut segments = Vec::new();
+    for segment in version.split('.') {
+        let digits = segment
+            .find(|ch: char| !ch.is_ascii_digit())
+            .map_or(segment, |end| &segment[..end]);
+        let Ok(number) = digits.parse::<u64>() else {
+            break;
+        };
+        segments.push(number);
+        if digits.len() != segment.len() {
+            break;
+        }
+    }
+    (!segments.is_empty()).then_some(segments)
+}
+
+#[cfg(test)]
+#[path = "versions_tests.rs"]
+mod tests;
@@ -0,0 +1,58 @@
+use std::cmp::Ordering;
+
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+#[test]
+fn compares_semver_and_python_versions() {
+    assert_eq!(compare("1.10.0", "1.9.3"), Some(Ordering::Greater));
+    assert_eq!(compare("1.0.0-beta.1", "1.0.0"), Some(Ordering::Less));
+    assert_eq!(compare("2.31", "2.31.0"), Some(Ordering::Equal));
+    assert_eq!(compare("2024.1.15", "2023.12.2"), Some(Ordering::Greater));
+    assert_eq!(compare("latest", "1.0.0"), None);
+}
+
+#[test]
+fn breaking_updates_follow_caret_rules() {
+    assert_eq!(is_breaking_update("1.4.2", "1.9.0"), Some(false));
+    assert_eq!(is_breaking_update("1.4.2", "2.0.0"), Some(true));
+    assert_eq!(is_breaking_update("0.3.1", "0.3.9"), Some(false));
+    assert_eq!(is_breaking_update("0.3.1", "0.4.0"), Some(true));
+    assert_eq!(is_breaking_update("0.0.3", "0.0.4"), Some(true));
+}
+
+#[test]
+fn pick_locked_prefers_the_copy_the_requirement_allows() {
+    let candidates = vec!["0.8.5".to_string(), "0.9.1".to_string()];
+
+    assert_eq!(
+        pick_locked(Ecosystem::Cargo, Some("0.8"), &candidates),
+        Some("0.8.5".to_string())
+    );
+    assert_eq!(
+        pick_locked(Ecosystem::Cargo, None, &candidates),
+        Some("0.9.1".to_string())
+    );
+    assert_eq!(pick_locked(Ecosystem::Cargo, Some("1"), &[]), None);
+}
+
+#[test]
+fn exact_pins_per_ecosystem() {
+    assert_eq!(
+        exact_pin(Ecosystem::Cargo, "=1.2.3"),
+        Some("1.2.3".to_string())
+    );
+    assert_eq!(exact_pin(Ecosystem::Cargo, "1.2.3"), None);
+    assert_eq!(
+        exact_pin(Ecosystem::Npm, "1.2.3"),
+        Some("1.2.3".to_string())
+    );
+    assert_eq!(exact_pin(Ecosystem::Npm, "^1.2.3"), None);
+    assert_eq!(
+        exact_pin(Ecosystem::Python, "==2.31.0"),
+        Some("2.31.0".to_string())
+    );
+    assert_eq!(exact_pin(Ecosystem::Python, ">=2.31"), None);
+    assert_eq!(exact_pin(Ecosystem::Python, "==2.*"), None);
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use codex_core::config::Config;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ToolContributor;
+use codex_features::Feature;
+
+use crate::tools;
+
+/// Contributes a tool that reports the OS, toolchain versions, and build-related env vars.
+#[derive(Clone, Default)]
+pub(crate) struct EnvSnapshotExtension;
+
+#[derive(Clone, Debug)]
+pub(crate) struct EnvSnapshotExtensionConfig {
+    pub(crate) enabled: bool,
+}
+
+impl EnvSnapshotExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::EnvSnapshot),
+        }
+    }
+}
+
+impl ToolContributor for EnvSnapshotExtension {
+    fn tools(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+    ) -> Vec<Arc<dyn codex_extension_api::ToolExecutor<codex_extension_api::ToolCall>>> {
+        let Some(config) = thread_store.get::<EnvSnapshotExtensionConfig>() else {
+            return Vec::new();
+        };
+        if !config.enabled {
+            return Vec::new();
+        }
+
+        tools::env_snapshot_tools()
+    }
+}
+
+/// Installs the environment snapshot extension contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    registry.thread_config(EnvSnapshotExtensionConfig::from_config);
+    registry.tool_contributor(Arc::new(EnvSnapshotExtension));
+}
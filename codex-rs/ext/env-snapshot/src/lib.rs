@@ -0,0 +1,10 @@
+mod extension;
+mod tools;
+
+pub use extension::install;
+
+pub(crate) const ENVIRONMENT_NAMESPACE: &str = "environment";
+pub(crate) const SNAPSHOT_TOOL_NAME: &str = "snapshot";
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use codex_extension_api::DenyCommandApprover;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::NoopTurnItemEmitter;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolContributor;
+use codex_extension_api::ToolPayload;
+use codex_utils_output_truncation::TruncationPolicy;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::extension::EnvSnapshotExtension;
+use crate::extension::EnvSnapshotExtensionConfig;
+use crate::tools::environment_tool_name;
+
+fn enabled_thread_store() -> ExtensionData {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(EnvSnapshotExtensionConfig { enabled: true });
+    thread_store
+}
+
+#[test]
+fn install_registers_the_snapshot_tool() {
+    let mut builder = ExtensionRegistryBuilder::<codex_core::config::Config>::new();
+    crate::install(&mut builder);
+    let registry = builder.build();
+    let thread_store = enabled_thread_store();
+
+    let tool_names = registry
+        .tool_contributors()
+        .iter()
+        .flat_map(|contributor| contributor.tools(&ExtensionData::new("session"), &thread_store))
+        .map(|tool| tool.tool_name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        tool_names,
+        vec![environment_tool_name(crate::SNAPSHOT_TOOL_NAME)]
+    );
+}
+
+#[test]
+fn tools_are_not_contributed_when_disabled() {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(EnvSnapshotExtensionConfig { enabled: false });
+
+    assert!(
+        EnvSnapshotExtension
+            .tools(&ExtensionData::new("session"), &thread_store)
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn tools_require_a_local_environment() {
+    let tools = EnvSnapshotExtension.tools(&ExtensionData::new("session"), &enabled_thread_store());
+
+    let Err(err) = tools[0]
+        .handle(ToolCall {
+            turn_id: "turn-1".to_string(),
+            call_id: "call-1".to_string(),
+            tool_name: environment_tool_name(crate::SNAPSHOT_TOOL_NAME),
+            model: "gpt-test".to_string(),
+            codex_turn_metadata: None,
+            truncation_policy: TruncationPolicy::Bytes(1024),
+            conversation_history: codex_extension_api::ConversationHistory::default(),
+            turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+            command_approver: Arc::new(DenyCommandApprover),
+            environments: Vec::new(),
+            payload: ToolPayload::Function {
+                arguments: json!({}).to_string(),
+            },
+        })
+        .await
+    else {
+        panic!("snapshot without a local environment should fail");
+    };
+
+    assert_eq!(
+        err,
+        FunctionCallError::RespondToModel(
+            "environment snapshots are only available for local workspaces".to_string()
+        )
+    );
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use codex_extension_api::JsonToolOutput;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolExecutorFuture;
+use codex_extension_api::ToolName;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolSpec;
+use codex_extension_api::local_environment;
+use codex_extension_api::namespaced_function_tool;
+use codex_extension_api::parse_function_arguments;
+use codex_utils_env_snapshot::EnvSnapshot;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::ENVIRONMENT_NAMESPACE;
+use crate::SNAPSHOT_TOOL_NAME;
+
+/// The snapshot describes this host, so the tool only works against the local checkout.
+const LOCAL_WORKSPACE_REQUIRED: &str =
+    "environment snapshots are only available for local workspaces";
+
+pub(crate) fn env_snapshot_tools() -> Vec<Arc<dyn ToolExecutor<ToolCall>>> {
+    vec![Arc::new(SnapshotTool)]
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct SnapshotArgs {}
+
+struct SnapshotTool;
+
+impl ToolExecutor<ToolCall> for SnapshotTool {
+    fn tool_name(&self) -> ToolName {
+        environment_tool_name(SNAPSHOT_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<SnapshotArgs, EnvSnapshot>(
+            ENVIRONMENT_NAMESPACE,
+            SNAPSHOT_TOOL_NAME,
+            "Report the OS, the installed versions of rustc, cargo, node, npm, python, go, docker, and git as resolved in the working directory, and build-related environment variables such as PATH and RUSTFLAGS. Use it when a failure may depend on the local setup.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let _: SnapshotArgs = parse_function_arguments(&call)?;
+            let cwd = local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?
+                .cwd
+                .to_path_buf();
+            let snapshot = EnvSnapshot::capture(&cwd).await;
+            let output: Box<dyn ToolOutput> = Box::new(JsonToolOutput::new(json!(snapshot)));
+            Ok(output)
+        })
+    }
+}
+
+pub(crate) fn environment_tool_name(name: &str) -> ToolName {
+    ToolName::namespaced(ENVIRONMENT_NAMESPACE, name)
+}
@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use codex_exec_server::FileSystemSandboxContext;
+use codex_exec_server::LOCAL_ENVIRONMENT_ID;
+use codex_exec_server::LOCAL_FS;
+use codex_protocol::models::PermissionProfile;
+use codex_tools::FunctionCallError;
+use codex_tools::ResponsesApiNamespace;
+use codex_tools::ResponsesApiNamespaceTool;
+use codex_tools::ResponsesApiTool;
+use codex_tools::ToolCall;
+use codex_tools::ToolEnvironment;
+use codex_tools::ToolSpec;
+use codex_tools::default_namespace_description;
+use codex_tools::parse_tool_input_schema;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_path_uri::PathUri;
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
+use serde::de::DeserializeOwned;
+use serde_json::Map;
+use serde_json::Value;
+
+/// Builds the spec for one function tool in `namespace`, with input and output schemas generated
+/// from `I` and `O`.
+pub fn namespaced_function_tool<I: JsonSchema, O: JsonSchema>(
+    namespace: &str,
+    name: &str,
+    description: &str,
+) -> ToolSpec {
+    let tool = ResponsesApiTool {
+        name: name.to_string(),
+        description: description.to_string(),
+        strict: false,
+        defer_loading: None,
+        parameters: parse_tool_input_schema(&tool_input_schema_for::<I>())
+            .unwrap_or_else(|err| panic!("generated input schema for {name} should parse: {err}")),
+        output_schema: Some(tool_output_schema_for::<O>()),
+    };
+
+    ToolSpec::Namespace(ResponsesApiNamespace {
+        name: namespace.to_string(),
+        description: default_namespace_description(namespace),
+        tools: vec![ResponsesApiNamespaceTool::Function(tool)],
+    })
+}
+
+/// Returns the inlined JSON schema for a tool's arguments.
+pub fn tool_input_schema_for<T: JsonSchema>() -> Value {
+    tool_schema_for::<T>(/*option_add_null_type*/ false)
+}
+
+/// Returns the inlined JSON schema for a tool's output, where optional fields may be `null`.
+pub fn tool_output_schema_for<T: JsonSchema>() -> Value {
+    tool_schema_for::<T>(/*option_add_null_type*/ true)
+}
+
+fn tool_schema_for<T: JsonSchema>(option_add_null_type: bool) -> Value {
+    let schema = SchemaSettings::draft2019_09()
+        .with(|settings| {
+            settings.inline_subschemas = true;
+            settings.option_add_null_type = option_add_null_type;
+        })
+        .into_generator()
+        .into_root_schema_for::<T>();
+    let schema_value = serde_json::to_value(schema)
+        .unwrap_or_else(|err| panic!("generated tool schema should serialize: {err}"));
+    let Value::Object(mut schema_object) = schema_value else {
+        unreachable!("root tool schema must be an object");
+    };
+
+    let mut tool_schema = Map::new();
+    for key in [
+        "properties",
+        "required",
+        "type",
+        "additionalProperties",
+        "$defs",
+        "definitions",
+    ] {
+        if let Some(value) = schema_object.remove(key) {
+            tool_schema.insert(key.to_string(), value);
+        }
+    }
+    Value::Object(tool_schema)
+}
+
+/// Parses a function call's JSON arguments, reporting malformed input back to the model.
+pub fn parse_function_arguments<T: DeserializeOwned>(
+    call: &ToolCall,
+) -> Result<T, FunctionCallError> {
+    serde_json::from_str(call.function_arguments()?)
+        .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))
+}
+
+/// Returns the call's environment on this host, for tools that only work against a local
+/// checkout. `unavailable` is returned to the model when the turn has no local environment.
+pub fn local_environment<'a>(
+    call: &'a ToolCall,
+    unavailable: &str,
+) -> Result<&'a ToolEnvironment, FunctionCallError> {
+    call.environments
+        .iter()
+        .find(|environment| environment.environment_id == LOCAL_ENVIRONMENT_ID)
+        .ok_or_else(|| FunctionCallError::RespondToModel(unavailable.to_string()))
+}
+
+/// Builds a local environment rooted at `cwd` whose file system access is limited by
+/// `permissions`.
+pub fn new_local_environment(
+    cwd: &Path,
+    permissions: PermissionProfile,
+) -> std::io::Result<ToolEnvironment> {
+    let cwd = AbsolutePathBuf::from_absolute_path(cwd)?;
+    Ok(ToolEnvironment {
+        environment_id: LOCAL_ENVIRONMENT_ID.to_string(),
+        file_system: LOCAL_FS.clone(),
+        file_system_sandbox_context: FileSystemSandboxContext::from_permission_profile_with_cwd(
+            permissions,
+            PathUri::from_abs_path(&cwd),
+        ),
+        cwd,
+    })
+}
@@ -1,7 +1,9 @@
 mod capabilities;
 mod contributors;
+mod function_tools;
 mod registry;
 mod state;
+mod thread_config;
 mod user_instructions;
 
 pub use capabilities::AgentSpawnFuture;
@@ -13,7 +15,11 @@ pub use capabilities::ResponseItemInjectionFuture;
 pub use capabilities::ResponseItemInjector;
 pub use codex_context_fragments::ContextualUserFragment;
 pub use codex_protocol::models::ResponseItem;
+pub use codex_tools::CommandApprovalFuture;
+pub use codex_tools::CommandApprovalRequest;
+pub use codex_tools::CommandApprover;
 pub use codex_tools::ConversationHistory;
+pub use codex_tools::DenyCommandApprover;
 pub use codex_tools::ExtensionTurnItem;
 pub use codex_tools::FunctionCallError;
 pub use codex_tools::JsonToolOutput;
@@ -71,6 +77,12 @@ pub use contributors::TurnStartInput;
 pub use contributors::TurnStopInput;
 pub use contributors::WorldStateContributionInput;
 pub use contributors::WorldStateSectionContribution;
+pub use function_tools::local_environment;
+pub use function_tools::namespaced_function_tool;
+pub use function_tools::new_local_environment;
+pub use function_tools::parse_function_arguments;
+pub use function_tools::tool_input_schema_for;
+pub use function_tools::tool_output_schema_for;
 pub use registry::ExtensionRegistry;
 pub use registry::ExtensionRegistryBuilder;
 pub use registry::empty_extension_registry;
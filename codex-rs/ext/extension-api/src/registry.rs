@@ -1,3 +1,4 @@
+use std::any::Any;
 use std::sync::Arc;
 
 use codex_protocol::protocol::ReviewDecision;
@@ -17,6 +18,7 @@ use crate::ToolLifecycleContributor;
 use crate::TurnInputContributor;
 use crate::TurnItemContributor;
 use crate::TurnLifecycleContributor;
+use crate::thread_config::ThreadConfigSnapshot;
 
 /// Mutable registry used while hosts register typed runtime contributions.
 pub struct ExtensionRegistryBuilder<C: Sync> {
@@ -97,6 +99,21 @@ impl<C: Sync> ExtensionRegistryBuilder<C> {
         self.config_contributors.push(contributor);
     }
 
+    /// Keeps `from_config(config)` in each thread store, computed at thread start and again
+    /// whenever the thread configuration changes.
+    ///
+    /// Extensions use this for the slice of host config their tools and prompts read, so they
+    /// need not implement both lifecycle and config contributors to track it.
+    pub fn thread_config<S>(&mut self, from_config: fn(&C) -> S)
+    where
+        C: 'static,
+        S: Any + Send + Sync,
+    {
+        let contributor = Arc::new(ThreadConfigSnapshot::new(from_config));
+        self.thread_lifecycle_contributor(contributor.clone());
+        self.config_contributor(contributor);
+    }
+
     /// Registers one token-usage contributor.
     pub fn token_usage_contributor(&mut self, contributor: Arc<dyn TokenUsageContributor>) {
         self.token_usage_contributors.push(contributor);
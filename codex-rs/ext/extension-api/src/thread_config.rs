@@ -0,0 +1,46 @@
+use std::any::Any;
+
+use crate::ConfigContributor;
+use crate::ExtensionData;
+use crate::ExtensionFuture;
+use crate::ThreadLifecycleContributor;
+use crate::ThreadStartInput;
+
+/// Keeps a value derived from the thread configuration in the thread store.
+///
+/// Registered through [`crate::ExtensionRegistryBuilder::thread_config`].
+pub(crate) struct ThreadConfigSnapshot<C, S> {
+    from_config: fn(&C) -> S,
+}
+
+impl<C, S> ThreadConfigSnapshot<C, S> {
+    pub(crate) fn new(from_config: fn(&C) -> S) -> Self {
+        Self { from_config }
+    }
+}
+
+impl<C, S> ThreadLifecycleContributor<C> for ThreadConfigSnapshot<C, S>
+where
+    C: Sync,
+    S: Any + Send + Sync,
+{
+    fn on_thread_start<'a>(&'a self, input: ThreadStartInput<'a, C>) -> ExtensionFuture<'a, ()> {
+        input.thread_store.insert((self.from_config)(input.config));
+        Box::pin(std::future::ready(()))
+    }
+}
+
+impl<C, S> ConfigContributor<C> for ThreadConfigSnapshot<C, S>
+where
+    S: Any + Send + Sync,
+{
+    fn on_config_changed(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+        _previous_config: &C,
+        new_config: &C,
+    ) {
+        thread_store.insert((self.from_config)(new_config));
+    }
+}
@@ -151,6 +151,29 @@ async fn build_round_trips_every_contributor_category() {
     );
 }
 
+#[derive(Debug, PartialEq, Eq)]
+struct ToolsEnabled(bool);
+
+#[test]
+fn thread_config_follows_config_changes() {
+    let mut builder = ExtensionRegistryBuilder::<bool>::new();
+    builder.thread_config(|enabled: &bool| ToolsEnabled(*enabled));
+    let registry = builder.build();
+    let session_store = ExtensionData::new("session");
+    let thread_store = ExtensionData::new("thread");
+
+    for (previous, new) in [(false, true), (true, false)] {
+        for contributor in registry.config_contributors() {
+            contributor.on_config_changed(&session_store, &thread_store, &previous, &new);
+        }
+        assert_eq!(
+            thread_store.get::<ToolsEnabled>().as_deref(),
+            Some(&ToolsEnabled(new))
+        );
+    }
+    assert_eq!(registry.thread_lifecycle_contributors().len(), 1);
+}
+
 struct NamedContextContributor(&'static str);
 
 impl ContextContributor for NamedContextContributor {
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use codex_core::config::Config;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ToolContributor;
+use codex_features::Feature;
+
+use crate::tools;
+
+/// Contributes structured git history tools so the model does not have to scrape pager output.
+#[derive(Clone, Default)]
+pub(crate) struct GitHistoryExtension;
+
+#[derive(Clone, Debug)]
+pub(crate) struct GitHistoryExtensionConfig {
+    pub(crate) enabled: bool,
+}
+
+impl GitHistoryExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::GitHistoryTools),
+        }
+    }
+}
+
+impl ToolContributor for GitHistoryExtension {
+    fn tools(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+    ) -> Vec<Arc<dyn codex_extension_api::ToolExecutor<codex_extension_api::ToolCall>>> {
+        let Some(config) = thread_store.get::<GitHistoryExtensionConfig>() else {
+            return Vec::new();
+        };
+        if !config.enabled {
+            return Vec::new();
+        }
+
+        tools::git_history_tools()
+    }
+}
+
+/// Installs the git history extension contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    registry.thread_config(GitHistoryExtensionConfig::from_config);
+    registry.tool_contributor(Arc::new(GitHistoryExtension));
+}
@@ -0,0 +1,22 @@
+mod extension;
+mod tools;
+
+pub use extension::install;
+
+pub(crate) const DEFAULT_LOG_MAX_COMMITS: usize = 20;
+pub(crate) const MAX_LOG_COMMITS: usize = 100;
+pub(crate) const MAX_FILES_PER_LOG_COMMIT: usize = 50;
+pub(crate) const MAX_FILES_PER_COMMIT: usize = 500;
+pub(crate) const DEFAULT_BLAME_LINES: u32 = 100;
+pub(crate) const MAX_BLAME_LINES: u32 = 400;
+pub(crate) const DEFAULT_TODO_RESULTS: usize = 100;
+pub(crate) const MAX_TODO_RESULTS: usize = 500;
+
+pub(crate) const GIT_HISTORY_NAMESPACE: &str = "git_history";
+pub(crate) const LOG_TOOL_NAME: &str = "log";
+pub(crate) const BLAME_TOOL_NAME: &str = "blame";
+pub(crate) const SHOW_COMMIT_TOOL_NAME: &str = "show_commit";
+pub(crate) const TODOS_TOOL_NAME: &str = "todos";
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::JsonToolOutput;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolExecutorFuture;
+use codex_extension_api::ToolName;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolSpec;
+use codex_extension_api::local_environment;
+use codex_extension_api::namespaced_function_tool;
+use codex_extension_api::parse_function_arguments;
+use codex_git_utils::GitBlame;
+use codex_git_utils::GitBlameRequest;
+use codex_git_utils::GitHistoryCommit;
+use codex_git_utils::GitLog;
+use codex_git_utils::GitLogRequest;
+use codex_git_utils::GitToolingError;
+use codex_git_utils::TodoScan;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::BLAME_TOOL_NAME;
+use crate::DEFAULT_BLAME_LINES;
+use crate::DEFAULT_LOG_MAX_COMMITS;
+use crate::DEFAULT_TODO_RESULTS;
+use crate::GIT_HISTORY_NAMESPACE;
+use crate::LOG_TOOL_NAME;
+use crate::MAX_BLAME_LINES;
+use crate::MAX_FILES_PER_COMMIT;
+use crate::MAX_FILES_PER_LOG_COMMIT;
+use crate::MAX_LOG_COMMITS;
+use crate::MAX_TODO_RESULTS;
+use crate::SHOW_COMMIT_TOOL_NAME;
+use crate::TODOS_TOOL_NAME;
+
+/// Git runs on this host, so the tools only work against the local environment's checkout.
+const LOCAL_WORKSPACE_REQUIRED: &str = "git history tools are only available for local workspaces";
+
+pub(crate) fn git_history_tools() -> Vec<Arc<dyn ToolExecutor<ToolCall>>> {
+    vec![
+        Arc::new(LogTool),
+        Arc::new(BlameTool),
+        Arc::new(ShowCommitTool),
+        Arc::new(TodosTool),
+    ]
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct LogArgs {
+    /// Revision or range to list, such as `main..HEAD` or a tag. Defaults to `HEAD`.
+    revision_range: Option<String>,
+    /// Only list commits that touched these paths, relative to the working directory.
+    paths: Option<Vec<String>>,
+    /// Maximum number of commits to return.
+    #[schemars(range(min = 1, max = 100))]
+    max_commits: Option<usize>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct BlameArgs {
+    /// File to blame, relative to the working directory.
+    path: String,
+    /// First line to blame, 1-indexed. Defaults to 1.
+    #[schemars(range(min = 1))]
+    start_line: Option<u32>,
+    /// Last line to blame, inclusive. At most 400 lines are returned per call.
+    #[schemars(range(min = 1))]
+    end_line: Option<u32>,
+    /// Revision to blame at. Defaults to the working tree.
+    revision: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct ShowCommitArgs {
+    /// Commit to describe, such as a sha, `HEAD~2`, or a tag.
+    revision: String,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct TodosArgs {
+    /// Maximum number of comments to return.
+    #[schemars(range(min = 1, max = 500))]
+    max_results: Option<usize>,
+}
+
+struct LogTool;
+
+impl ToolExecutor<ToolCall> for LogTool {
+    fn tool_name(&self) -> ToolName {
+        git_history_tool_name(LOG_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<LogArgs, GitLog>(
+            GIT_HISTORY_NAMESPACE,
+            LOG_TOOL_NAME,
+            "List commits newest first with author, date, message, and per-file line counts. Use it to see how a file or revision range evolved.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: LogArgs = parse_function_arguments(&call)?;
+            let cwd = local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?
+                .cwd
+                .to_path_buf();
+            let request = GitLogRequest {
+                revision_range: args.revision_range,
+                paths: args.paths.unwrap_or_default(),
+                max_commits: args
+                    .max_commits
+                    .unwrap_or(DEFAULT_LOG_MAX_COMMITS)
+                    .clamp(1, MAX_LOG_COMMITS),
+                max_files_per_commit: MAX_FILES_PER_LOG_COMMIT,
+            };
+            run_git(move || codex_git_utils::git_log(&cwd, &request)).await
+        })
+    }
+}
+
+struct BlameTool;
+
+impl ToolExecutor<ToolCall> for BlameTool {
+    fn tool_name(&self) -> ToolName {
+        git_history_tool_name(BLAME_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<BlameArgs, GitBlame>(
+            GIT_HISTORY_NAMESPACE,
+            BLAME_TOOL_NAME,
+            "Show which commit last changed each line in a range of a file, grouped into hunks with the commit's author, date, and summary.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: BlameArgs = parse_function_arguments(&call)?;
+            let cwd = local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?
+                .cwd
+                .to_path_buf();
+            let start_line = args.start_line.unwrap_or(1);
+            let request = GitBlameRequest {
+                path: args.path,
+                revision: args.revision,
+                start_line,
+                end_line: args
+                    .end_line
+                    .unwrap_or_else(|| start_line.saturating_add(DEFAULT_BLAME_LINES - 1)),
+                max_lines: MAX_BLAME_LINES,
+            };
+            run_git(move || codex_git_utils::git_blame(&cwd, &request)).await
+        })
+    }
+}
+
+struct ShowCommitTool;
+
+impl ToolExecutor<ToolCall> for ShowCommitTool {
+    fn tool_name(&self) -> ToolName {
+        git_history_tool_name(SHOW_COMMIT_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<ShowCommitArgs, GitHistoryCommit>(
+            GIT_HISTORY_NAMESPACE,
+            SHOW_COMMIT_TOOL_NAME,
+            "Describe one commit: author, date, full message, and every file it touched with added and deleted line counts.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: ShowCommitArgs = parse_function_arguments(&call)?;
+            let cwd = local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?
+                .cwd
+                .to_path_buf();
+            run_git(move || {
+                codex_git_utils::git_show_commit(&cwd, &args.revision, MAX_FILES_PER_COMMIT)
+            })
+            .await
+        })
+    }
+}
+
+struct TodosTool;
+
+impl ToolExecutor<ToolCall> for TodosTool {
+    fn tool_name(&self) -> ToolName {
+        git_history_tool_name(TODOS_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<TodosArgs, TodoScan>(
+            GIT_HISTORY_NAMESPACE,
+            TODOS_TOOL_NAME,
+            "List TODO, FIXME, and HACK comments in tracked and untracked files under the working directory, with the enclosing declaration for each.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: TodosArgs = parse_function_arguments(&call)?;
+            let cwd = local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?
+                .cwd
+                .to_path_buf();
+            let max_results = args
+                .max_results
+                .unwrap_or(DEFAULT_TODO_RESULTS)
+                .clamp(1, MAX_TODO_RESULTS);
+            run_git(move || codex_git_utils::scan_todo_comments(&cwd, max_results)).await
+        })
+    }
+}
+
+pub(crate) fn git_history_tool_name(name: &str) -> ToolName {
+    ToolName::namespaced(GIT_HISTORY_NAMESPACE, name)
+}
+
+async fn run_git<T>(
+    query: impl FnOnce() -> Result<T, GitToolingError> + Send + 'static,
+) -> Result<Box<dyn ToolOutput>, FunctionCallError>
+where
+    T: Serialize + Send + 'static,
+{
+    let result = tokio::task::spawn_blocking(query)
+        .await
+        .map_err(|err| FunctionCallError::Fatal(format!("git history task failed: {err}")))?;
+    // Bad revisions, missing paths, and out-of-range lines are all things the model can fix.
+    let value = result.map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+    Ok(Box::new(JsonToolOutput::new(json!(value))))
+}
@@ -0,0 +1,138 @@
+use codex_extension_api::ToolCallOutcome;
+use std::num::NonZeroU32;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+/// Supervised autonomy limits for automatic goal continuation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct GoalAutonomySettings {
+    pub(crate) checkpoint_interval: Option<NonZeroU32>,
+    pub(crate) max_consecutive_tool_failures: Option<NonZeroU32>,
+}
+
+/// Why automatic goal continuation stopped for a user checkpoint review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GoalCheckpointReason {
+    Interval { automatic_turns: u32 },
+    ToolFailures { consecutive_failures: u32 },
+    ApprovalBoundary,
+}
+
+/// Tracks automatic goal turns since the user last took a turn.
+///
+/// A checkpoint turn ends with a progress summary; the runtime then defers continuation until the
+/// user starts the next turn, which resets every counter here.
+#[derive(Debug)]
+pub(crate) struct GoalAutonomyState {
+    inner: Mutex<GoalAutonomyInner>,
+}
+
+#[derive(Debug, Default)]
+struct GoalAutonomyInner {
+    settings: GoalAutonomySettings,
+    pending_automatic_turn: Option<Option<GoalCheckpointReason>>,
+    automatic_turns: u32,
+    consecutive_tool_failures: u32,
+    checkpoint: Option<GoalCheckpointReason>,
+}
+
+impl GoalAutonomyState {
+    pub(crate) fn new(settings: GoalAutonomySettings) -> Self {
+        Self {
+            inner: Mutex::new(GoalAutonomyInner {
+                settings,
+                ..Default::default()
+            }),
+        }
+    }
+
+    pub(crate) fn set_settings(&self, settings: GoalAutonomySettings) {
+        self.inner().settings = settings;
+    }
+
+    /// Records that the runtime is about to start an automatic continuation turn.
+    ///
+    /// Returns the checkpoint reason when that turn should be the last one before the user reviews.
+    pub(crate) fn request_automatic_turn(&self) -> Option<GoalCheckpointReason> {
+        let mut inner = self.inner();
+        let automatic_turns = inner.automatic_turns.saturating_add(1);
+        let checkpoint = inner
+            .settings
+            .checkpoint_interval
+            .is_some_and(|interval| automatic_turns >= interval.get())
+            .then_some(GoalCheckpointReason::Interval { automatic_turns });
+        inner.pending_automatic_turn = Some(checkpoint);
+        checkpoint
+    }
+
+    pub(crate) fn cancel_automatic_turn(&self) {
+        self.inner().pending_automatic_turn = None;
+    }
+
+    /// Starts a turn; any turn the runtime did not request came from the user.
+    pub(crate) fn start_turn(&self) {
+        let mut inner = self.inner();
+        match inner.pending_automatic_turn.take() {
+            Some(checkpoint) => {
+                inner.automatic_turns = inner.automatic_turns.saturating_add(1);
+                inner.checkpoint = checkpoint;
+            }
+            None => {
+                inner.automatic_turns = 0;
+                inner.consecutive_tool_failures = 0;
+                inner.checkpoint = None;
+            }
+        }
+    }
+
+    /// Records a goal-turn tool outcome and returns a newly reached checkpoint, if any.
+    pub(crate) fn record_tool_outcome(
+        &self,
+        outcome: ToolCallOutcome,
+    ) -> Option<GoalCheckpointReason> {
+        let mut inner = self.inner();
+        let reason = match outcome {
+            ToolCallOutcome::Completed { success: true } => {
+                inner.consecutive_tool_failures = 0;
+                None
+            }
+            ToolCallOutcome::Completed { success: false }
+            | ToolCallOutcome::Failed {
+                handler_executed: true,
+            } => {
+                inner.consecutive_tool_failures = inner.consecutive_tool_failures.saturating_add(1);
+                let consecutive_failures = inner.consecutive_tool_failures;
+                inner
+                    .settings
+                    .max_consecutive_tool_failures
+                    .is_some_and(|max| consecutive_failures >= max.get())
+                    .then_some(GoalCheckpointReason::ToolFailures {
+                        consecutive_failures,
+                    })
+            }
+            ToolCallOutcome::Blocked => inner
+                .settings
+                .checkpoint_interval
+                .is_some()
+                .then_some(GoalCheckpointReason::ApprovalBoundary),
+            ToolCallOutcome::Failed {
+                handler_executed: false,
+            }
+            | ToolCallOutcome::Aborted => None,
+        };
+        if inner.checkpoint.is_some() {
+            return None;
+        }
+        inner.checkpoint = reason;
+        reason
+    }
+
+    /// Finishes a turn and returns the checkpoint it ended on, if any.
+    pub(crate) fn finish_turn(&self) -> Option<GoalCheckpointReason> {
+        self.inner().checkpoint.take()
+    }
+
+    fn inner(&self) -> std::sync::MutexGuard<'_, GoalAutonomyInner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
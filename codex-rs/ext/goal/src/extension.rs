@@ -1,3 +1,4 @@
+use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::sync::Weak;
 
@@ -36,6 +37,7 @@ use crate::accounting::BudgetLimitedGoalDisposition;
 use crate::accounting::GoalAccountingState;
 use crate::analytics::GoalAnalytics;
 use crate::api::GoalService;
+use crate::autonomy::GoalAutonomySettings;
 use crate::events::GoalEventEmitter;
 use crate::metrics::GoalMetrics;
 use crate::runtime::ActiveGoalStopReason;
@@ -45,14 +47,22 @@ use crate::spec::UPDATE_GOAL_TOOL_NAME;
 use crate::steering::budget_limit_steering_item;
 use crate::tool::GoalToolExecutor;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct GoalExtensionConfig {
     pub enabled: bool,
+    /// Automatic continuations allowed before stopping for a user checkpoint review. Also enables
+    /// stopping when a tool call is blocked at an approval boundary.
+    pub checkpoint_interval: Option<NonZeroU32>,
+    /// Consecutive failed tool calls allowed before stopping for a user checkpoint review.
+    pub max_consecutive_tool_failures: Option<NonZeroU32>,
 }
 
 impl GoalExtensionConfig {
-    fn from_enabled(enabled: bool) -> Self {
-        Self { enabled }
+    fn autonomy_settings(&self) -> GoalAutonomySettings {
+        GoalAutonomySettings {
+            checkpoint_interval: self.checkpoint_interval,
+            max_consecutive_tool_failures: self.max_consecutive_tool_failures,
+        }
     }
 }
 
@@ -64,7 +74,7 @@ pub struct GoalExtension<C> {
     metrics: GoalMetrics,
     thread_manager: Weak<ThreadManager>,
     goal_service: Arc<GoalService>,
-    goals_enabled: Arc<dyn Fn(&C) -> bool + Send + Sync>,
+    goal_config: Arc<dyn Fn(&C) -> GoalExtensionConfig + Send + Sync>,
 }
 
 impl<C> std::fmt::Debug for GoalExtension<C> {
@@ -81,7 +91,7 @@ impl<C> GoalExtension<C> {
         metrics_client: Option<MetricsClient>,
         thread_manager: Weak<ThreadManager>,
         goal_service: Arc<GoalService>,
-        goals_enabled: impl Fn(&C) -> bool + Send + Sync + 'static,
+        goal_config: impl Fn(&C) -> GoalExtensionConfig + Send + Sync + 'static,
     ) -> Self {
         Self {
             state_dbs,
@@ -90,7 +100,7 @@ impl<C> GoalExtension<C> {
             metrics: GoalMetrics::new(metrics_client),
             thread_manager,
             goal_service,
-            goals_enabled: Arc::new(goals_enabled),
+            goal_config: Arc::new(goal_config),
         }
     }
 }
@@ -101,15 +111,15 @@ where
 {
     fn on_thread_start<'a>(&'a self, input: ThreadStartInput<'a, C>) -> ExtensionFuture<'a, ()> {
         Box::pin(async move {
-            let enabled = (self.goals_enabled)(input.config);
+            let config = (self.goal_config)(input.config);
+            let enabled = config.enabled;
             let tools_available_for_thread = input.persistent_thread_state_available
                 && !matches!(
                     input.session_source,
                     SessionSource::SubAgent(SubAgentSource::Review)
                 );
-            input
-                .thread_store
-                .insert(GoalExtensionConfig::from_enabled(enabled));
+            let autonomy = config.autonomy_settings();
+            input.thread_store.insert(config);
             let accounting_state = input
                 .thread_store
                 .get_or_init::<GoalAccountingState>(GoalAccountingState::default);
@@ -128,10 +138,12 @@ where
                         analytics: self.analytics.clone(),
                         enabled,
                         tools_available_for_thread,
+                        autonomy,
                     },
                 )
             });
             runtime.set_enabled(enabled);
+            runtime.set_autonomy_settings(autonomy);
             self.goal_service.register_runtime(&runtime);
         })
     }
@@ -186,11 +198,12 @@ where
         _previous_config: &C,
         new_config: &C,
     ) {
-        let enabled = (self.goals_enabled)(new_config);
-        thread_store.insert(GoalExtensionConfig::from_enabled(enabled));
+        let config = (self.goal_config)(new_config);
         if let Some(runtime) = goal_runtime_handle(thread_store) {
-            runtime.set_enabled(enabled);
+            runtime.set_enabled(config.enabled);
+            runtime.set_autonomy_settings(config.autonomy_settings());
         }
+        thread_store.insert(config);
     }
 }
 
@@ -216,6 +229,7 @@ where
                 tracing::warn!("failed to clear deferred goal continuation: {err}");
             }
 
+            runtime.start_autonomy_turn();
             let accounting = runtime.accounting_state();
             accounting.start_turn(
                 input.turn_id,
@@ -274,6 +288,9 @@ where
                 return;
             }
             runtime.accounting_state().finish_turn(turn_id);
+            if let Err(err) = runtime.finish_autonomy_turn().await {
+                tracing::warn!("failed to defer goal continuation for checkpoint review: {err}");
+            }
         })
     }
 
@@ -302,6 +319,9 @@ where
                 return;
             }
             runtime.accounting_state().finish_turn(turn_id);
+            if let Err(err) = runtime.finish_autonomy_turn().await {
+                tracing::warn!("failed to defer goal continuation for checkpoint review: {err}");
+            }
         })
     }
 
@@ -370,6 +390,13 @@ where
             let Some(runtime) = goal_runtime_handle(input.thread_store) else {
                 return;
             };
+            if runtime.is_enabled()
+                && let Err(err) = runtime
+                    .record_tool_outcome_for_checkpoint(input.turn_id, input.outcome)
+                    .await
+            {
+                tracing::warn!("failed to record goal checkpoint tool outcome: {err}");
+            }
             let should_count_for_goal_progress = runtime.is_enabled()
                 && tool_attempt_counts_for_goal_progress(input.outcome)
                 && !(input.tool_name.namespace.is_none()
@@ -464,7 +491,7 @@ pub fn install_with_backend<C>(
     metrics_client: Option<MetricsClient>,
     thread_manager: Weak<ThreadManager>,
     goal_service: Arc<GoalService>,
-    goals_enabled: impl Fn(&C) -> bool + Send + Sync + 'static,
+    goal_config: impl Fn(&C) -> GoalExtensionConfig + Send + Sync + 'static,
 ) where
     C: Send + Sync + 'static,
 {
@@ -475,7 +502,7 @@ pub fn install_with_backend<C>(
         metrics_client,
         thread_manager,
         Arc::clone(&goal_service),
-        goals_enabled,
+        goal_config,
     ));
     registry.thread_lifecycle_contributor(extension.clone());
     registry.config_contributor(extension.clone());
@@ -4,6 +4,7 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
 use codex_core::ThreadManager;
+use codex_extension_api::ToolCallOutcome;
 use codex_protocol::ThreadId;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::ThreadGoal;
@@ -12,8 +13,11 @@ use crate::accounting::BudgetLimitedGoalDisposition;
 use crate::accounting::GoalAccountingState;
 use crate::analytics::GoalAnalytics;
 use crate::analytics::GoalEventAttribution;
+use crate::autonomy::GoalAutonomySettings;
+use crate::autonomy::GoalAutonomyState;
 use crate::events::GoalEventEmitter;
 use crate::metrics::GoalMetrics;
+use crate::steering::checkpoint_steering_item;
 use crate::steering::continuation_steering_item;
 use crate::steering::objective_updated_steering_item;
 use crate::tool::protocol_goal_from_state;
@@ -29,6 +33,7 @@ pub(crate) struct GoalRuntimeConfig {
     pub(crate) analytics: GoalAnalytics,
     pub(crate) enabled: bool,
     pub(crate) tools_available_for_thread: bool,
+    pub(crate) autonomy: GoalAutonomySettings,
 }
 
 pub(crate) enum ActiveGoalStopReason {
@@ -44,6 +49,7 @@ struct GoalRuntimeInner {
     metrics: GoalMetrics,
     thread_manager: Weak<ThreadManager>,
     accounting_state: Arc<GoalAccountingState>,
+    autonomy: GoalAutonomyState,
     enabled: AtomicBool,
     tools_available_for_thread: bool,
     goal_state_lock: Semaphore,
@@ -96,6 +102,7 @@ impl GoalRuntimeHandle {
                 metrics,
                 thread_manager,
                 accounting_state,
+                autonomy: GoalAutonomyState::new(config.autonomy),
                 enabled: AtomicBool::new(config.enabled),
                 tools_available_for_thread: config.tools_available_for_thread,
                 goal_state_lock: Semaphore::new(/*permits*/ 1),
@@ -107,6 +114,10 @@ impl GoalRuntimeHandle {
         self.inner.enabled.store(enabled, Ordering::Relaxed);
     }
 
+    pub(crate) fn set_autonomy_settings(&self, settings: GoalAutonomySettings) {
+        self.inner.autonomy.set_settings(settings);
+    }
+
     pub(crate) fn is_enabled(&self) -> bool {
         self.inner.enabled.load(Ordering::Relaxed)
     }
@@ -400,9 +411,14 @@ impl GoalRuntimeHandle {
             self.inner.accounting_state.clear_active_goal();
             return Ok(());
         }
-        let item = continuation_steering_item(&protocol_goal_from_state(goal));
+        let goal = protocol_goal_from_state(goal);
+        let item = match self.inner.autonomy.request_automatic_turn() {
+            Some(reason) => checkpoint_steering_item(&goal, reason),
+            None => continuation_steering_item(&goal),
+        };
 
         if let Err(err) = thread.try_start_turn_if_idle(vec![item]).await {
+            self.inner.autonomy.cancel_automatic_turn();
             let reason = err.reason();
             tracing::debug!(
                 ?reason,
@@ -425,6 +441,54 @@ impl GoalRuntimeHandle {
         Ok(())
     }
 
+    pub(crate) fn start_autonomy_turn(&self) {
+        self.inner.autonomy.start_turn();
+    }
+
+    /// Steers the active goal turn toward a progress summary once a checkpoint is reached.
+    pub(crate) async fn record_tool_outcome_for_checkpoint(
+        &self,
+        turn_id: &str,
+        outcome: ToolCallOutcome,
+    ) -> Result<(), String> {
+        if !self
+            .inner
+            .accounting_state
+            .turn_is_current_active_goal(turn_id)
+        {
+            return Ok(());
+        }
+        let Some(reason) = self.inner.autonomy.record_tool_outcome(outcome) else {
+            return Ok(());
+        };
+        let Some(goal) = self
+            .inner
+            .state_dbs
+            .thread_goals()
+            .get_thread_goal(self.thread_id())
+            .await
+            .map_err(|err| err.to_string())?
+        else {
+            return Ok(());
+        };
+        let item = checkpoint_steering_item(&protocol_goal_from_state(goal), reason);
+        self.inject_active_turn_steering(item).await;
+        Ok(())
+    }
+
+    /// Holds automatic continuation until the user's next turn when this turn was a checkpoint.
+    pub(crate) async fn finish_autonomy_turn(&self) -> Result<(), String> {
+        if self.inner.autonomy.finish_turn().is_none() {
+            return Ok(());
+        }
+        self.inner
+            .state_dbs
+            .thread_goals()
+            .defer_thread_goal_continuation(self.thread_id())
+            .await
+            .map_err(|err| err.to_string())
+    }
+
     pub(crate) async fn inject_active_turn_steering(&self, item: ResponseItem) {
         let Some(thread_manager) = self.inner.thread_manager.upgrade() else {
             tracing::debug!("skipping goal steering because thread manager is unavailable");
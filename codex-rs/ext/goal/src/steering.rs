@@ -1,3 +1,4 @@
+use crate::autonomy::GoalCheckpointReason;
 use codex_core::context::ContextualUserFragment;
 use codex_core::context::InternalContextSource;
 use codex_core::context::InternalModelContextFragment;
@@ -20,6 +21,13 @@ static BUDGET_LIMIT_PROMPT_TEMPLATE: LazyLock<Template> = LazyLock::new(|| {
     )
 });
 
+static CHECKPOINT_PROMPT_TEMPLATE: LazyLock<Template> = LazyLock::new(|| {
+    parse_embedded_template(
+        include_str!("../templates/goals/checkpoint.md"),
+        "goals/checkpoint.md",
+    )
+});
+
 static OBJECTIVE_UPDATED_PROMPT_TEMPLATE: LazyLock<Template> = LazyLock::new(|| {
     parse_embedded_template(
         include_str!("../templates/goals/objective_updated.md"),
@@ -46,6 +54,13 @@ pub(crate) fn continuation_steering_item(goal: &ThreadGoal) -> ResponseItem {
     goal_context_input_item(continuation_prompt(goal))
 }
 
+pub(crate) fn checkpoint_steering_item(
+    goal: &ThreadGoal,
+    reason: GoalCheckpointReason,
+) -> ResponseItem {
+    goal_context_input_item(checkpoint_prompt(goal, reason))
+}
+
 fn goal_context_input_item(prompt: String) -> ResponseItem {
     ContextualUserFragment::into(InternalModelContextFragment::new(
         InternalContextSource::from_static("goal"),
@@ -98,6 +113,39 @@ fn budget_limit_prompt(goal: &ThreadGoal) -> String {
         })
 }
 
+fn checkpoint_prompt(goal: &ThreadGoal, reason: GoalCheckpointReason) -> String {
+    let objective = escape_xml_text(&goal.objective);
+    let reason = match reason {
+        GoalCheckpointReason::Interval { automatic_turns } => {
+            format!(
+                "{automatic_turns} consecutive automatic turns reached the checkpoint interval."
+            )
+        }
+        GoalCheckpointReason::ToolFailures {
+            consecutive_failures,
+        } => format!("{consecutive_failures} consecutive tool calls failed."),
+        GoalCheckpointReason::ApprovalBoundary => {
+            "A tool call was blocked at an approval boundary.".to_string()
+        }
+    };
+    let tokens_used = goal.tokens_used.to_string();
+    let token_budget = goal
+        .token_budget
+        .map(|budget| budget.to_string())
+        .unwrap_or_else(|| "none".to_string());
+
+    CHECKPOINT_PROMPT_TEMPLATE
+        .render([
+            ("objective", objective.as_str()),
+            ("reason", reason.as_str()),
+            ("tokens_used", tokens_used.as_str()),
+            ("token_budget", token_budget.as_str()),
+        ])
+        .unwrap_or_else(|err| {
+            panic!("embedded goals/checkpoint.md template failed to render: {err}")
+        })
+}
+
 fn objective_updated_prompt(goal: &ThreadGoal) -> String {
     let objective = escape_xml_text(&goal.objective);
     let tokens_used = goal.tokens_used.to_string();
@@ -0,0 +1,110 @@
+#![allow(dead_code)]
+
+#[path = "../src/autonomy.rs"]
+mod autonomy;
+
+use std::num::NonZeroU32;
+
+use autonomy::GoalAutonomySettings;
+use autonomy::GoalAutonomyState;
+use autonomy::GoalCheckpointReason;
+use codex_extension_api::ToolCallOutcome;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn checkpoint_interval_marks_the_last_automatic_turn() {
+    let state = GoalAutonomyState::new(GoalAutonomySettings {
+        checkpoint_interval: NonZeroU32::new(2),
+        max_consecutive_tool_failures: None,
+    });
+    state.start_turn();
+    state.finish_turn();
+
+    assert_eq!(None, state.request_automatic_turn());
+    state.start_turn();
+    assert_eq!(None, state.finish_turn());
+
+    assert_eq!(
+        Some(GoalCheckpointReason::Interval { automatic_turns: 2 }),
+        state.request_automatic_turn()
+    );
+    state.start_turn();
+    assert_eq!(
+        Some(GoalCheckpointReason::Interval { automatic_turns: 2 }),
+        state.finish_turn()
+    );
+
+    // A user turn resets the count.
+    state.start_turn();
+    state.finish_turn();
+    assert_eq!(None, state.request_automatic_turn());
+}
+
+#[test]
+fn cancelled_automatic_turn_counts_next_turn_as_user_turn() {
+    let state = GoalAutonomyState::new(GoalAutonomySettings {
+        checkpoint_interval: NonZeroU32::new(1),
+        max_consecutive_tool_failures: None,
+    });
+
+    assert_eq!(
+        Some(GoalCheckpointReason::Interval { automatic_turns: 1 }),
+        state.request_automatic_turn()
+    );
+    state.cancel_automatic_turn();
+    state.start_turn();
+
+    assert_eq!(None, state.finish_turn());
+}
+
+#[test]
+fn consecutive_tool_failures_reach_checkpoint_once() {
+    let state = GoalAutonomyState::new(GoalAutonomySettings {
+        checkpoint_interval: None,
+        max_consecutive_tool_failures: NonZeroU32::new(2),
+    });
+    state.start_turn();
+
+    let failed = ToolCallOutcome::Completed { success: false };
+    assert_eq!(None, state.record_tool_outcome(failed));
+    assert_eq!(
+        None,
+        state.record_tool_outcome(ToolCallOutcome::Completed { success: true })
+    );
+    assert_eq!(None, state.record_tool_outcome(failed));
+    assert_eq!(
+        Some(GoalCheckpointReason::ToolFailures {
+            consecutive_failures: 2
+        }),
+        state.record_tool_outcome(ToolCallOutcome::Failed {
+            handler_executed: true
+        })
+    );
+    assert_eq!(None, state.record_tool_outcome(failed));
+    assert_eq!(
+        Some(GoalCheckpointReason::ToolFailures {
+            consecutive_failures: 2
+        }),
+        state.finish_turn()
+    );
+}
+
+#[test]
+fn blocked_tools_reach_checkpoint_only_with_checkpoint_interval() {
+    let unsupervised = GoalAutonomyState::new(GoalAutonomySettings::default());
+    unsupervised.start_turn();
+    assert_eq!(
+        None,
+        unsupervised.record_tool_outcome(ToolCallOutcome::Blocked)
+    );
+
+    let supervised = GoalAutonomyState::new(GoalAutonomySettings {
+        checkpoint_interval: NonZeroU32::new(10),
+        max_consecutive_tool_failures: None,
+    });
+    supervised.start_turn();
+    assert_eq!(
+        Some(GoalCheckpointReason::ApprovalBoundary),
+        supervised.record_tool_outcome(ToolCallOutcome::Blocked)
+    );
+}
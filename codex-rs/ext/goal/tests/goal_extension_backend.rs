@@ -1,6 +1,7 @@
 #![recursion_limit = "256"]
 #![allow(clippy::expect_used)]
 
+use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::PoisonError;
@@ -8,6 +9,7 @@ use std::sync::Weak;
 use std::time::Duration;
 
 use codex_analytics::AnalyticsEventsClient;
+use codex_extension_api::DenyCommandApprover;
 use codex_extension_api::ExtensionData;
 use codex_extension_api::ExtensionEventSink;
 use codex_extension_api::ExtensionRegistryBuilder;
@@ -25,6 +27,7 @@ use codex_extension_api::ToolPayload;
 use codex_extension_api::TurnErrorInput;
 use codex_extension_api::TurnStartInput;
 use codex_extension_api::TurnStopInput;
+use codex_goal_extension::GoalExtensionConfig;
 use codex_goal_extension::GoalObjectiveUpdate;
 use codex_goal_extension::GoalRuntimeHandle;
 use codex_goal_extension::GoalService;
@@ -294,6 +297,59 @@ async fn tool_finish_accounts_active_goal_progress_and_emits_event() -> anyhow::
     Ok(())
 }
 
+#[tokio::test]
+async fn tool_failure_threshold_defers_continuation_until_user_turn() -> anyhow::Result<()> {
+    let runtime = test_runtime().await?;
+    let thread_id = test_thread_id()?;
+    seed_thread_metadata(runtime.as_ref(), thread_id).await?;
+    let harness = GoalExtensionHarness::new_with_config(
+        runtime.clone(),
+        thread_id,
+        GoalExtensionConfig {
+            enabled: true,
+            checkpoint_interval: None,
+            max_consecutive_tool_failures: NonZeroU32::new(2),
+        },
+    )
+    .await?;
+    harness.start_turn("turn-1", &TokenUsage::default()).await;
+    let tools = harness.tools();
+    tool_by_name(&tools, "create_goal")
+        .handle(tool_call(
+            "create_goal",
+            "call-create-goal",
+            json!({ "objective": "ship goal extension backend" }),
+        ))
+        .await?;
+
+    for call_id in ["call-1", "call-2"] {
+        harness
+            .notify_tool_finish_with_outcome(
+                "turn-1",
+                call_id,
+                "shell",
+                ToolCallOutcome::Completed { success: false },
+            )
+            .await;
+    }
+    harness.stop_turn("turn-1").await;
+    assert!(
+        runtime
+            .thread_goals()
+            .has_thread_goal_continuation_deferral(thread_id)
+            .await?
+    );
+
+    harness.start_turn("turn-2", &TokenUsage::default()).await;
+    assert!(
+        !runtime
+            .thread_goals()
+            .has_thread_goal_continuation_deferral(thread_id)
+            .await?
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn parallel_tool_finish_accounts_active_goal_progress_once() -> anyhow::Result<()> {
     let runtime = test_runtime().await?;
@@ -1122,7 +1178,7 @@ async fn installed_tools_with_start(
         /*metrics_client*/ None,
         Weak::new(),
         goal_service,
-        |_| true,
+        |_| enabled_goal_config(),
     );
     let registry = builder.build();
     let session_store = ExtensionData::new("session-1");
@@ -1164,6 +1220,14 @@ impl GoalExtensionHarness {
     async fn new(
         runtime: Arc<codex_state::StateRuntime>,
         thread_id: ThreadId,
+    ) -> anyhow::Result<Self> {
+        Self::new_with_config(runtime, thread_id, enabled_goal_config()).await
+    }
+
+    async fn new_with_config(
+        runtime: Arc<codex_state::StateRuntime>,
+        thread_id: ThreadId,
+        config: GoalExtensionConfig,
     ) -> anyhow::Result<Self> {
         let sink = Arc::new(RecordingEventSink::default());
         let mut builder = ExtensionRegistryBuilder::<()>::with_event_sink(sink.clone());
@@ -1175,7 +1239,7 @@ impl GoalExtensionHarness {
             /*metrics_client*/ None,
             Weak::new(),
             Arc::clone(&goal_service),
-            |_| true,
+            move |_| config.clone(),
         );
         let registry = builder.build();
         let session_store = ExtensionData::new("session-1");
@@ -1289,6 +1353,22 @@ impl GoalExtensionHarness {
     }
 
     async fn notify_tool_finish(&self, turn_id: &str, call_id: &str, tool_name: &str) {
+        self.notify_tool_finish_with_outcome(
+            turn_id,
+            call_id,
+            tool_name,
+            ToolCallOutcome::Completed { success: true },
+        )
+        .await;
+    }
+
+    async fn notify_tool_finish_with_outcome(
+        &self,
+        turn_id: &str,
+        call_id: &str,
+        tool_name: &str,
+        outcome: ToolCallOutcome,
+    ) {
         let turn_store = ExtensionData::new(turn_id);
         let tool_name = codex_extension_api::ToolName::plain(tool_name);
         for contributor in self.registry.tool_lifecycle_contributors() {
@@ -1301,7 +1381,7 @@ impl GoalExtensionHarness {
                     call_id,
                     tool_name: &tool_name,
                     source: ToolCallSource::Direct,
-                    outcome: ToolCallOutcome::Completed { success: true },
+                    outcome,
                 })
                 .await;
         }
@@ -1329,6 +1409,13 @@ impl GoalExtensionHarness {
     }
 }
 
+fn enabled_goal_config() -> GoalExtensionConfig {
+    GoalExtensionConfig {
+        enabled: true,
+        ..Default::default()
+    }
+}
+
 fn tool_by_name<'a>(
     tools: &'a [Arc<dyn ToolExecutor<ToolCall>>],
     name: &str,
@@ -1349,6 +1436,7 @@ fn tool_call(tool_name: &str, call_id: &str, arguments: serde_json::Value) -> To
         truncation_policy: TruncationPolicy::Bytes(1024),
         conversation_history: codex_extension_api::ConversationHistory::default(),
         turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+        command_approver: Arc::new(DenyCommandApprover),
         environments: Vec::new(),
         payload: ToolPayload::Function {
             arguments: arguments.to_string(),
@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use url::Url;
+
+use crate::MAX_BODY_CHARS;
+use crate::MAX_RECORDED_REQUEST_BODY_CHARS;
+
+/// Header, query parameter, and JSON field names containing any of these never have their values
+/// recorded. `auth` covers `Authorization` and `cookie` covers `Set-Cookie`.
+const SENSITIVE_NAME_PARTS: &[&str] = &[
+    "auth",
+    "cookie",
+    "credential",
+    "key",
+    "passw",
+    "secret",
+    "session",
+    "signature",
+    "token",
+];
+const REDACTED: &str = "<redacted>";
+
+/// One request and its response, as returned to the model and kept in the session history.
+#[derive(Debug, PartialEq, Serialize, JsonSchema)]
+pub(crate) struct HttpExchange {
+    pub(crate) request: RecordedRequest,
+    pub(crate) response: RecordedResponse,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct RecordedRequest {
+    pub(crate) method: String,
+    /// Request URL, with credential query parameters and any password redacted.
+    pub(crate) url: String,
+    /// Request headers, with credentials redacted.
+    pub(crate) headers: BTreeMap<String, String>,
+    /// Body sent, with credential fields of JSON and form bodies redacted, cut to 2,000
+    /// characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) body: Option<String>,
+    pub(crate) body_bytes: usize,
+    /// True when `body` was cut.
+    pub(crate) body_truncated: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, JsonSchema)]
+pub(crate) struct RecordedResponse {
+    pub(crate) status: u16,
+    /// Response headers, with cookies redacted. Repeated headers are joined with `, `.
+    pub(crate) headers: BTreeMap<String, String>,
+    /// Body text, or a placeholder for binary content. Absent when `json` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) body: Option<String>,
+    /// Parsed body of a complete JSON response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) json: Option<serde_json::Value>,
+    /// Number of body bytes read.
+    pub(crate) body_bytes: usize,
+    /// True when the body was longer than what was read or returned.
+    pub(crate) truncated: bool,
+}
+
+/// Returns `headers` keyed by lowercase name, with credential values replaced.
+pub(crate) fn recorded_headers<'a>(
+    headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> BTreeMap<String, String> {
+    let mut recorded: BTreeMap<String, String> = BTreeMap::new();
+    for (name, value) in headers {
+        let name = name.to_ascii_lowercase();
+        let value = if is_sensitive_name(&name) {
+            REDACTED
+        } else {
+            value
+        };
+        recorded
+            .entry(name)
+            .and_modify(|existing| {
+                if existing != REDACTED {
+                    existing.push_str(", ");
+                    existing.push_str(value);
+                }
+            })
+            .or_insert_with(|| value.to_string());
+    }
+    recorded
+}
+
+fn is_sensitive_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name == "sig" || SENSITIVE_NAME_PARTS.iter().any(|part| name.contains(part))
+}
+
+/// Returns `url` with the values of credential query parameters and any password replaced.
+pub(crate) fn recorded_url(url: &Url) -> String {
+    let mut base = url.clone();
+    if base.password().is_some() {
+        // `<` and `>` would be percent-encoded in the userinfo, so the marker is spelled plainly.
+        let _ = base.set_password(Some("redacted"));
+    }
+    let Some(query) = url.query() else {
+        return base.to_string();
+    };
+    base.set_query(None);
+    base.set_fragment(None);
+    let fragment = url
+        .fragment()
+        .map(|fragment| format!("#{fragment}"))
+        .unwrap_or_default();
+    format!("{base}?{}{fragment}", redact_pairs(query))
+}
+
+/// Returns the request body to record: credential fields of a JSON or form body are replaced,
+/// and the text is cut to [`MAX_RECORDED_REQUEST_BODY_CHARS`]. The flag is true when it was cut.
+pub(crate) fn recorded_body(content_type: Option<&str>, body: &str) -> (String, bool) {
+    let is_form =
+        content_type.is_some_and(|content_type| content_type.contains("x-www-form-urlencoded"));
+    let redacted = if is_form {
+        redact_pairs(body)
+    } else if let Ok(mut json) = serde_json::from_str::<Value>(body) {
+        redact_json(&mut json);
+        json.to_string()
+    } else {
+        body.to_string()
+    };
+    match redacted.char_indices().nth(MAX_RECORDED_REQUEST_BODY_CHARS) {
+        Some((cut, _)) => (redacted[..cut].to_string(), true),
+        None => (redacted, false),
+    }
+}
+
+/// Replaces the values of credential pairs in a query string or form body, leaving the rest of
+/// its encoding untouched.
+fn redact_pairs(pairs: &str) -> String {
+    pairs
+        .split('&')
+        .map(|pair| {
+            let Some((name, _)) = pair.split_once('=') else {
+                return pair.to_string();
+            };
+            let decoded = url::form_urlencoded::parse(name.as_bytes())
+                .next()
+                .map(|(name, _)| name.into_owned())
+                .unwrap_or_default();
+            if is_sensitive_name(&decoded) {
+                format!("{name}={REDACTED}")
+            } else {
+                pair.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                if is_sensitive_name(name) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+/// The body fields of a [`RecordedResponse`].
+#[derive(Debug, PartialEq)]
+pub(crate) struct ResponseBody {
+    pub(crate) body: Option<String>,
+    pub(crate) json: Option<serde_json::Value>,
+    pub(crate) truncated: bool,
+}
+
+/// Turns the bytes read from a response into text or parsed JSON for the model. `complete` is
+/// false when reading stopped before the end of the body.
+pub(crate) fn response_body(
+    content_type: Option<&str>,
+    bytes: &[u8],
+    complete: bool,
+) -> ResponseBody {
+    let content_type = content_type.unwrap_or_default().to_ascii_lowercase();
+    if bytes.is_empty() {
+        return ResponseBody {
+            body: None,
+            json: None,
+            truncated: !complete,
+        };
+    }
+    if complete
+        && content_type.contains("json")
+        && let Ok(json) = serde_json::from_slice(bytes)
+    {
+        return ResponseBody {
+            body: None,
+            json: Some(json),
+            truncated: false,
+        };
+    }
+
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        // A body cut at the read limit may end inside a character.
+        Err(err) if !complete && err.error_len().is_none() => {
+            String::from_utf8_lossy(&bytes[..err.valid_up_to()]).into_owned()
+        }
+        Err(_) if !is_text_content_type(&content_type) => {
+            let content_type = if content_type.is_empty() {
+                "binary data"
+            } else {
+                content_type.as_str()
+            };
+            return ResponseBody {
+                body: Some(format!("<{} bytes of {content_type}>", bytes.len())),
+                json: None,
+                truncated: !complete,
+            };
+        }
+        Err(_) => String::from_utf8_lossy(bytes).into_owned(),
+    };
+
+    let mut chars = text.char_indices();
+    match chars.nth(MAX_BODY_CHARS) {
+        Some((cut, _)) => ResponseBody {
+            body: Some(text[..cut].to_string()),
+            json: None,
+            truncated: true,
+        },
+        None => ResponseBody {
+            body: Some(text),
+            json: None,
+            truncated: !complete,
+        },
+    }
+}
+
+fn is_text_content_type(content_type: &str) -> bool {
+    content_type.starts_with("text/")
+        || ["json", "xml", "javascript", "x-www-form-urlencoded"]
+            .iter()
+            .any(|part| content_type.contains(part))
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use codex_core::config::Config;
+use codex_core::config::NetworkProxySpec;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ToolContributor;
+use codex_features::Feature;
+
+use crate::tools;
+
+/// Contributes a structured HTTP request tool, so the model does not have to improvise curl.
+#[derive(Clone, Default)]
+pub(crate) struct HttpRequestExtension;
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct HttpRequestExtensionConfig {
+    pub(crate) enabled: bool,
+    /// Whether the active permissions allow network access at all.
+    pub(crate) network_access: bool,
+    /// Managed network proxy settings whose host allow and deny lists requests must pass.
+    pub(crate) network: Option<NetworkProxySpec>,
+}
+
+impl HttpRequestExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::HttpRequest),
+            network_access: config.network_sandbox_policy().is_enabled(),
+            network: config.network.clone(),
+        }
+    }
+}
+
+impl ToolContributor for HttpRequestExtension {
+    fn tools(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+    ) -> Vec<Arc<dyn codex_extension_api::ToolExecutor<codex_extension_api::ToolCall>>> {
+        let Some(config) = thread_store.get::<HttpRequestExtensionConfig>() else {
+            return Vec::new();
+        };
+        if !config.enabled {
+            return Vec::new();
+        }
+
+        tools::http_request_tools(config.network_access, config.network.clone())
+    }
+}
+
+/// Installs the HTTP request extension contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    registry.thread_config(HttpRequestExtensionConfig::from_config);
+    registry.tool_contributor(Arc::new(HttpRequestExtension));
+}
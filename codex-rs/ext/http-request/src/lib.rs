@@ -0,0 +1,21 @@
+mod exchange;
+mod extension;
+mod tools;
+
+use std::time::Duration;
+
+pub use extension::install;
+
+/// Response bodies are read up to this many bytes.
+pub(crate) const MAX_RESPONSE_BYTES: usize = 1024 * 1024;
+/// Text bodies returned to the model are cut to this many characters.
+pub(crate) const MAX_BODY_CHARS: usize = 20_000;
+/// Request bodies kept in the recorded exchange are cut to this many characters.
+pub(crate) const MAX_RECORDED_REQUEST_BODY_CHARS: usize = 2_000;
+pub(crate) const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub(crate) const HTTP_NAMESPACE: &str = "http";
+pub(crate) const REQUEST_TOOL_NAME: &str = "request";
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,288 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use codex_core::config::HostBlockDecision;
+use codex_core::config::NetworkProxySpec;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::JsonToolOutput;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolExecutorFuture;
+use codex_extension_api::ToolName;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolSpec;
+use codex_extension_api::namespaced_function_tool;
+use codex_extension_api::parse_function_arguments;
+use codex_http_client::build_reqwest_client_with_custom_ca;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use url::Host;
+use url::Url;
+
+use crate::HTTP_NAMESPACE;
+use crate::MAX_RESPONSE_BYTES;
+use crate::REQUEST_TIMEOUT;
+use crate::REQUEST_TOOL_NAME;
+use crate::exchange::HttpExchange;
+use crate::exchange::RecordedRequest;
+use crate::exchange::RecordedResponse;
+use crate::exchange::recorded_body;
+use crate::exchange::recorded_headers;
+use crate::exchange::recorded_url;
+use crate::exchange::response_body;
+
+pub(crate) fn http_request_tools(
+    network_access: bool,
+    network: Option<NetworkProxySpec>,
+) -> Vec<Arc<dyn ToolExecutor<ToolCall>>> {
+    vec![Arc::new(RequestTool {
+        network_access,
+        network,
+    })]
+}
+
+#[derive(Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "UPPERCASE")]
+enum HttpMethod {
+    #[default]
+    Get,
+    Head,
+    Post,
+    Put,
+    Patch,
+    Delete,
+    Options,
+}
+
+impl HttpMethod {
+    fn as_reqwest(self) -> reqwest::Method {
+        match self {
+            Self::Get => reqwest::Method::GET,
+            Self::Head => reqwest::Method::HEAD,
+            Self::Post => reqwest::Method::POST,
+            Self::Put => reqwest::Method::PUT,
+            Self::Patch => reqwest::Method::PATCH,
+            Self::Delete => reqwest::Method::DELETE,
+            Self::Options => reqwest::Method::OPTIONS,
+        }
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct RequestArgs {
+    /// HTTP method. Defaults to GET.
+    method: Option<HttpMethod>,
+    /// Absolute http:// or https:// URL, including any query string.
+    url: String,
+    /// Request headers by name.
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    /// Request body, sent as-is. Set a Content-Type header to match.
+    body: Option<String>,
+}
+
+struct RequestTool {
+    network_access: bool,
+    network: Option<NetworkProxySpec>,
+}
+
+impl RequestTool {
+    /// Applies the same rules a sandboxed `curl` would face: network access must be allowed,
+    /// the managed proxy's host allow and deny lists must let the host through, and the network
+    /// mode must allow the method. Without the managed proxy there are no lists to check the host
+    /// against, so every request is refused. Returns the addresses a domain was checked against
+    /// so the request connects to them rather than to whatever a second DNS lookup returns.
+    async fn check_host(
+        &self,
+        method: &reqwest::Method,
+        url: &Url,
+    ) -> Result<Option<PinnedHost>, FunctionCallError> {
+        if !self.network_access {
+            return Err(FunctionCallError::RespondToModel(
+                "network access is not allowed by the current permissions".to_string(),
+            ));
+        }
+        let Some(network) = &self.network else {
+            return Err(FunctionCallError::RespondToModel(
+                "requests are refused because the managed network proxy is not enabled, so there is no allow list to check the host against".to_string(),
+            ));
+        };
+        let host = url.host_str().unwrap_or_default();
+        let port = url.port_or_known_default().unwrap_or(80);
+        match network
+            .host_block_decision(host, port)
+            .await
+            .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?
+        {
+            HostBlockDecision::Allowed => {}
+            HostBlockDecision::Blocked(reason) => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "requests to {host} are blocked by the network policy ({reason})"
+                )));
+            }
+        }
+        if !network.allows_method(method.as_str()) {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "{method} requests are blocked by the network policy; only GET, HEAD, and OPTIONS are allowed"
+            )));
+        }
+        let Some(Host::Domain(domain)) = url.host() else {
+            return Ok(None);
+        };
+        let addrs = network
+            .resolve_for_direct_connection(domain, port)
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!(
+                    "requests to {host} are blocked by the network policy ({err})"
+                ))
+            })?;
+        Ok(Some(PinnedHost {
+            domain: domain.to_string(),
+            addrs,
+        }))
+    }
+}
+
+/// A domain and the addresses it resolved to when the network policy checked it.
+pub(crate) struct PinnedHost {
+    domain: String,
+    addrs: Vec<SocketAddr>,
+}
+
+impl ToolExecutor<ToolCall> for RequestTool {
+    fn tool_name(&self) -> ToolName {
+        http_tool_name(REQUEST_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<RequestArgs, HttpExchange>(
+            HTTP_NAMESPACE,
+            REQUEST_TOOL_NAME,
+            "Send an HTTP request and get back the status, headers, and body; JSON responses are parsed. Use it instead of curl to call APIs or fetch pages. Redirects are not followed, bodies over 20,000 characters are cut, and hosts and methods must be allowed by the managed network proxy's policy; without the proxy every request is refused. Each exchange is recorded in the session with credentials redacted.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: RequestArgs = parse_function_arguments(&call)?;
+            let url = Url::parse(&args.url).map_err(|err| {
+                FunctionCallError::RespondToModel(format!("invalid url `{}`: {err}", args.url))
+            })?;
+            if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "only http:// and https:// urls are supported, not `{url}`"
+                )));
+            }
+            let method = args.method.unwrap_or_default().as_reqwest();
+            let pinned = self.check_host(&method, &url).await?;
+
+            let exchange = send(method, url, pinned, args.headers, args.body)
+                .await
+                .map_err(FunctionCallError::RespondToModel)?;
+            // Response bodies come from outside the workspace.
+            let output: Box<dyn ToolOutput> =
+                Box::new(JsonToolOutput::new(json!(exchange)).with_external_context());
+            Ok(output)
+        })
+    }
+}
+
+pub(crate) async fn send(
+    method: reqwest::Method,
+    url: Url,
+    pinned: Option<PinnedHost>,
+    headers: BTreeMap<String, String>,
+    body: Option<String>,
+) -> Result<HttpExchange, String> {
+    // Redirects could lead to a host the policy never saw, so they are returned instead.
+    let mut builder = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .timeout(REQUEST_TIMEOUT);
+    if let Some(pinned) = &pinned {
+        builder = builder.resolve_to_addrs(&pinned.domain, &pinned.addrs);
+    }
+    let client = build_reqwest_client_with_custom_ca(builder)
+        .map_err(|err| format!("failed to build HTTP client: {err}"))?;
+
+    let content_type = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| value.to_ascii_lowercase());
+    let (recorded_body, body_truncated) = body
+        .as_deref()
+        .map(|body| recorded_body(content_type.as_deref(), body))
+        .unzip();
+    let recorded_request = RecordedRequest {
+        method: method.to_string(),
+        url: recorded_url(&url),
+        headers: recorded_headers(
+            headers
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str())),
+        ),
+        body: recorded_body,
+        body_bytes: body.as_ref().map_or(0, String::len),
+        body_truncated: body_truncated.unwrap_or(false),
+    };
+    let mut request = client.request(method, url);
+    for (name, value) in &headers {
+        request = request.header(name, value);
+    }
+    if let Some(body) = body {
+        request = request.body(body);
+    }
+
+    let mut response = request
+        .send()
+        .await
+        .map_err(|err| format!("request failed: {err}"))?;
+    let status = response.status().as_u16();
+    let response_headers = recorded_headers(
+        response
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.to_str().unwrap_or("<binary>"))),
+    );
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let mut bytes = Vec::new();
+    let mut complete = true;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|err| format!("failed to read response body: {err}"))?
+    {
+        let remaining = MAX_RESPONSE_BYTES - bytes.len();
+        if chunk.len() > remaining {
+            bytes.extend_from_slice(&chunk[..remaining]);
+            complete = false;
+            break;
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    let body = response_body(content_type.as_deref(), &bytes, complete);
+    Ok(HttpExchange {
+        request: recorded_request,
+        response: RecordedResponse {
+            status,
+            headers: response_headers,
+            body: body.body,
+            json: body.json,
+            body_bytes: bytes.len(),
+            truncated: body.truncated,
+        },
+    })
+}
+
+pub(crate) fn http_tool_name(name: &str) -> ToolName {
+    ToolName::namespaced(HTTP_NAMESPACE, name)
+}
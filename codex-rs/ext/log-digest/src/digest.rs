@@ -0,0 +1,401 @@
+//! Summarizes a log file: lines are reduced to templates (numbers, ids, and
+//! timestamps replaced by placeholders), counted, and grouped into clusters by level.
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::FULL_READ_MAX_BYTES;
+use crate::MAX_EXAMPLE_CHARS;
+use crate::MAX_TEMPLATES;
+use crate::SAMPLE_WINDOW_BYTES;
+use crate::SAMPLE_WINDOWS;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum LogLevel {
+    Error,
+    Warning,
+    Info,
+    Debug,
+    Unknown,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct LevelCounts {
+    pub(crate) error: u64,
+    pub(crate) warning: u64,
+    pub(crate) info: u64,
+    pub(crate) debug: u64,
+    pub(crate) unknown: u64,
+}
+
+/// Lines that reduce to the same template.
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct LogCluster {
+    /// The line with numbers, ids, and timestamps replaced by `<n>`, `<hex>`, `<uuid>`, `<ts>`,
+    /// `<date>`, and `<time>`.
+    pub(crate) template: String,
+    pub(crate) level: LogLevel,
+    pub(crate) count: u64,
+    /// First matching line, cut to a few hundred characters.
+    pub(crate) example: String,
+    /// 1-indexed line number of the example; `None` when the file was sampled.
+    pub(crate) first_line: Option<u64>,
+    pub(crate) first_timestamp: Option<String>,
+    pub(crate) last_timestamp: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct LogDigest {
+    pub(crate) bytes: u64,
+    /// True when the file was too large to read in full and evenly spaced windows were read.
+    pub(crate) sampled: bool,
+    pub(crate) lines_read: u64,
+    pub(crate) distinct_templates: usize,
+    /// Lines not grouped because the template limit was reached.
+    pub(crate) ungrouped_lines: u64,
+    pub(crate) first_timestamp: Option<String>,
+    pub(crate) last_timestamp: Option<String>,
+    pub(crate) levels: LevelCounts,
+    /// Error and warning clusters, most frequent first.
+    pub(crate) error_clusters: Vec<LogCluster>,
+    /// The most frequent clusters of any level.
+    pub(crate) top_clusters: Vec<LogCluster>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct DigestRequest {
+    pub(crate) max_clusters: usize,
+    /// Only consider lines containing this text.
+    pub(crate) filter: Option<String>,
+}
+
+/// Digests the contents of a log file. Contents over [`FULL_READ_MAX_BYTES`] are sampled in
+/// evenly spaced windows.
+pub(crate) fn digest_bytes(contents: &[u8], request: &DigestRequest) -> LogDigest {
+    let bytes = contents.len() as u64;
+    let mut digester = Digester::new(request.filter.clone());
+    let sampled = bytes > FULL_READ_MAX_BYTES;
+    if sampled {
+        for window in 0..SAMPLE_WINDOWS {
+            let offset = window * (bytes - SAMPLE_WINDOW_BYTES) / (SAMPLE_WINDOWS - 1);
+            let start = usize::try_from(offset).unwrap_or(contents.len());
+            let end = usize::try_from(offset + SAMPLE_WINDOW_BYTES)
+                .unwrap_or(contents.len())
+                .min(contents.len());
+            let text = String::from_utf8_lossy(&contents[start..end]);
+            let mut lines = text.lines().collect::<Vec<_>>();
+            // Windows start and end mid-line; drop the partial lines at either edge.
+            if offset > 0 && !lines.is_empty() {
+                lines.remove(0);
+            }
+            lines.pop();
+            for line in lines {
+                digester.ingest(line, /*line_number*/ None);
+            }
+        }
+    } else {
+        for (index, line) in contents.split_inclusive(|byte| *byte == b'\n').enumerate() {
+            let line = String::from_utf8_lossy(line);
+            digester.ingest(line.trim_end_matches(['\n', '\r']), Some(index as u64 + 1));
+        }
+    }
+    digester.finish(bytes, sampled, request.max_clusters)
+}
+
+struct ClusterStats {
+    level: LogLevel,
+    count: u64,
+    example: String,
+    first_line: Option<u64>,
+    /// Order in which the template was first seen, used to break ties.
+    order: usize,
+    first_timestamp: Option<String>,
+    last_timestamp: Option<String>,
+}
+
+pub(crate) struct Digester {
+    filter: Option<String>,
+    clusters: HashMap<String, ClusterStats>,
+    lines_read: u64,
+    ungrouped_lines: u64,
+    levels: LevelCounts,
+    first_timestamp: Option<String>,
+    last_timestamp: Option<String>,
+}
+
+impl Digester {
+    pub(crate) fn new(filter: Option<String>) -> Self {
+        Self {
+            filter,
+            clusters: HashMap::new(),
+            lines_read: 0,
+            ungrouped_lines: 0,
+            levels: LevelCounts::default(),
+            first_timestamp: None,
+            last_timestamp: None,
+        }
+    }
+
+    pub(crate) fn ingest(&mut self, line: &str, line_number: Option<u64>) {
+        if line.trim().is_empty()
+            || self
+                .filter
+                .as_deref()
+                .is_some_and(|filter| !line.contains(filter))
+        {
+            return;
+        }
+        self.lines_read += 1;
+        let level = level_for(line);
+        match level {
+            LogLevel::Error => self.levels.error += 1,
+            LogLevel::Warning => self.levels.warning += 1,
+            LogLevel::Info => self.levels.info += 1,
+            LogLevel::Debug => self.levels.debug += 1,
+            LogLevel::Unknown => self.levels.unknown += 1,
+        }
+        let timestamp = find_timestamp(line).map(str::to_string);
+        if let Some(timestamp) = &timestamp {
+            self.first_timestamp
+                .get_or_insert_with(|| timestamp.clone());
+            self.last_timestamp = Some(timestamp.clone());
+        }
+
+        let template = template_for(line);
+        let order = self.clusters.len();
+        if let Some(stats) = self.clusters.get_mut(&template) {
+            stats.count += 1;
+            if timestamp.is_some() {
+                if stats.first_timestamp.is_none() {
+                    stats.first_timestamp = timestamp.clone();
+                }
+                stats.last_timestamp = timestamp;
+            }
+        } else if order < MAX_TEMPLATES {
+            self.clusters.insert(
+                template,
+                ClusterStats {
+                    level,
+                    count: 1,
+                    example: line.chars().take(MAX_EXAMPLE_CHARS).collect(),
+                    first_line: line_number,
+                    order,
+                    first_timestamp: timestamp.clone(),
+                    last_timestamp: timestamp,
+                },
+            );
+        } else {
+            self.ungrouped_lines += 1;
+        }
+    }
+
+    pub(crate) fn finish(self, bytes: u64, sampled: bool, max_clusters: usize) -> LogDigest {
+        let distinct_templates = self.clusters.len();
+        let mut clusters = self.clusters.into_iter().collect::<Vec<_>>();
+        clusters.sort_by(|(_, left), (_, right)| {
+            right
+                .count
+                .cmp(&left.count)
+                .then(left.order.cmp(&right.order))
+        });
+        let to_cluster = |(template, stats): &(String, ClusterStats)| LogCluster {
+            template: template.clone(),
+            level: stats.level,
+            count: stats.count,
+            example: stats.example.clone(),
+            first_line: stats.first_line,
+            first_timestamp: stats.first_timestamp.clone(),
+            last_timestamp: stats.last_timestamp.clone(),
+        };
+        LogDigest {
+            bytes,
+            sampled,
+            lines_read: self.lines_read,
+            distinct_templates,
+            ungrouped_lines: self.ungrouped_lines,
+            first_timestamp: self.first_timestamp,
+            last_timestamp: self.last_timestamp,
+            levels: self.levels,
+            error_clusters: clusters
+                .iter()
+                .filter(|(_, stats)| matches!(stats.level, LogLevel::Error | LogLevel::Warning))
+                .take(max_clusters)
+                .map(to_cluster)
+                .collect(),
+            top_clusters: clusters.iter().take(max_clusters).map(to_cluster).collect(),
+        }
+    }
+}
+
+/// Classifies a line by the first level keyword it contains, such as `ERROR` or `level=warn`.
+pub(crate) fn level_for(line: &str) -> LogLevel {
+    for word in line.split(|ch: char| !ch.is_ascii_alphanumeric()) {
+        let level = match word.to_ascii_lowercase().as_str() {
+            "error" | "err" | "fatal" | "critical" | "crit" | "panic" | "severe" | "traceback" => {
+                LogLevel::Error
+            }
+            "warn" | "warning" => LogLevel::Warning,
+            "info" | "notice" => LogLevel::Info,
+            "debug" | "trace" => LogLevel::Debug,
+            _ if word.ends_with("Exception") => LogLevel::Error,
+            _ => continue,
+        };
+        return level;
+    }
+    LogLevel::Unknown
+}
+
+/// Replaces the variable parts of a line so repeated messages share one template.
+pub(crate) fn template_for(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(ch) = rest.chars().next() {
+        let (placeholder, len) = if let Some(len) = timestamp_len(rest) {
+            ("<ts>", len)
+        } else if let Some(len) = date_len(rest) {
+            ("<date>", len)
+        } else if let Some(len) = time_len(rest) {
+            ("<time>", len)
+        } else if let Some(len) = uuid_len(rest) {
+            ("<uuid>", len)
+        } else if ch.is_ascii_alphanumeric() {
+            let len = rest
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+                .unwrap_or(rest.len());
+            out.push_str(&word_template(&rest[..len]));
+            rest = &rest[len..];
+            continue;
+        } else {
+            out.push(ch);
+            rest = &rest[ch.len_utf8()..];
+            continue;
+        };
+        out.push_str(placeholder);
+        rest = &rest[len..];
+    }
+    // Dotted numbers such as versions and IP addresses collapse to one placeholder.
+    while out.contains("<n>.<n>") {
+        out = out.replace("<n>.<n>", "<n>");
+    }
+    out
+}
+
+/// `0x1f`, long hex ids, and words with digits are reduced; plain words are kept.
+fn word_template(word: &str) -> String {
+    let is_hex = |text: &str| !text.is_empty() && text.chars().all(|ch| ch.is_ascii_hexdigit());
+    if word.strip_prefix("0x").is_some_and(is_hex)
+        || (word.len() >= 8 && is_hex(word) && word.chars().any(|ch| ch.is_ascii_digit()))
+    {
+        return "<hex>".to_string();
+    }
+    let mut out = String::with_capacity(word.len());
+    let mut in_number = false;
+    for ch in word.chars() {
+        if ch.is_ascii_digit() {
+            if !in_number {
+                out.push_str("<n>");
+                in_number = true;
+            }
+        } else {
+            in_number = false;
+            out.push(ch);
+        }
+    }
+    out
+}
+
+/// Returns the first full timestamp in `line`, such as `2024-05-01T12:00:00Z`.
+pub(crate) fn find_timestamp(line: &str) -> Option<&str> {
+    line.char_indices()
+        .find_map(|(index, _)| timestamp_len(&line[index..]).map(|len| &line[index..index + len]))
+}
+
+/// `YYYY-MM-DD[T ]HH:MM:SS[.fff][Z|±HH:MM]`.
+fn timestamp_len(text: &str) -> Option<usize> {
+    let date = date_len(text)?;
+    let separator = *text.as_bytes().get(date)?;
+    if separator != b'T' && separator != b' ' {
+        return None;
+    }
+    let time = time_len(&text[date + 1..])?;
+    let mut len = date + 1 + time;
+    let bytes = text.as_bytes();
+    match bytes.get(len) {
+        Some(b'Z') => len += 1,
+        Some(b'+' | b'-') if digits(&bytes[len + 1..]) >= 2 => {
+            len += 3;
+            if bytes.get(len) == Some(&b':') {
+                len += 1;
+            }
+            len += digits(&bytes[len..]).min(2);
+        }
+        _ => {}
+    }
+    Some(len)
+}
+
+/// `YYYY-MM-DD` or `YYYY/MM/DD`.
+fn date_len(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let separator = *bytes.get(4)?;
+    (digits(bytes) == 4
+        && (separator == b'-' || separator == b'/')
+        && digits(&bytes[5..]) == 2
+        && bytes.get(7) == Some(&separator)
+        && digits(&bytes[8..]) == 2)
+        .then_some(10)
+}
+
+/// `HH:MM:SS` with an optional `.fff` or `,fff` fraction.
+fn time_len(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    if !(digits(bytes) == 2
+        && bytes.get(2) == Some(&b':')
+        && digits(&bytes[3..]) == 2
+        && bytes.get(5) == Some(&b':')
+        && digits(&bytes[6..]) == 2)
+    {
+        return None;
+    }
+    match bytes.get(8) {
+        Some(b'.' | b',') if digits(&bytes[9..]) > 0 => Some(9 + digits(&bytes[9..])),
+        _ => Some(8),
+    }
+}
+
+/// `8-4-4-4-12` hex digits.
+fn uuid_len(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut index = 0;
+    for (group, len) in [8, 4, 4, 4, 12].into_iter().enumerate() {
+        if group > 0 {
+            if bytes.get(index) != Some(&b'-') {
+                return None;
+            }
+            index += 1;
+        }
+        let hex = bytes[index.min(bytes.len())..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_hexdigit())
+            .count();
+        if hex != len {
+            return None;
+        }
+        index += len;
+    }
+    Some(index)
+}
+
+fn digits(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .take_while(|byte| byte.is_ascii_digit())
+        .count()
+}
+
+#[cfg(test)]
+#[path = "digest_tests.rs"]
+mod tests;
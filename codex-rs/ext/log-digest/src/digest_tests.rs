@@ -0,0 +1,145 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn digest_lines(lines: &[&str], filter: Option<&str>) -> LogDigest {
+    let mut digester = Digester::new(filter.map(str::to_string));
+    for (index, line) in lines.iter().enumerate() {
+        digester.ingest(line, Some(index as u64 + 1));
+    }
+    digester.finish(
+        /*bytes*/ 0, /*sampled*/ false, /*max_clusters*/ 10,
+    )
+}
+
+#[test]
+fn templates_replace_variable_parts() {
+    assert_eq!(
+        template_for("2024-05-01T12:00:03.120Z ERROR request 4821 failed after 30ms"),
+        "<ts> ERROR request <n> failed after <n>ms"
+    );
+    assert_eq!(
+        template_for("[12:00:03] conn from 10.0.0.12 id=0x7ffe trace=deadbeef01"),
+        "[<time>] conn from <n> id=<hex> trace=<hex>"
+    );
+    assert_eq!(
+        template_for("job 123e4567-e89b-12d3-a456-426614174000 done on 2024/05/01"),
+        "job <uuid> done on <date>"
+    );
+    assert_eq!(template_for("café opened"), "café opened");
+}
+
+#[test]
+fn levels_come_from_the_first_level_keyword() {
+    assert_eq!(
+        level_for("2024-05-01 12:00:00 [WARN] disk low"),
+        LogLevel::Warning
+    );
+    assert_eq!(level_for("level=error msg=\"timeout\""), LogLevel::Error);
+    assert_eq!(
+        level_for("java.lang.NullPointerException: boom"),
+        LogLevel::Error
+    );
+    assert_eq!(level_for("INFO error budget at 3%"), LogLevel::Info);
+    assert_eq!(level_for("0 errors found"), LogLevel::Unknown);
+}
+
+#[test]
+fn timestamps_include_fractions_and_offsets() {
+    assert_eq!(
+        find_timestamp("at 2024-05-01 12:00:03,120+02:00 started"),
+        Some("2024-05-01 12:00:03,120+02:00")
+    );
+    assert_eq!(find_timestamp("started at 12:00:03"), None);
+}
+
+#[test]
+fn repeated_lines_are_grouped_with_errors_first() {
+    let digest = digest_lines(
+        &[
+            "2024-05-01T12:00:00Z INFO started worker 1",
+            "2024-05-01T12:00:01Z ERROR db timeout after 30ms",
+            "2024-05-01T12:00:02Z INFO started worker 2",
+            "",
+            "2024-05-01T12:00:03Z ERROR db timeout after 45ms",
+            "2024-05-01T12:00:04Z INFO started worker 3",
+        ],
+        /*filter*/ None,
+    );
+
+    assert_eq!(
+        digest,
+        LogDigest {
+            bytes: 0,
+            sampled: false,
+            lines_read: 5,
+            distinct_templates: 2,
+            ungrouped_lines: 0,
+            first_timestamp: Some("2024-05-01T12:00:00Z".to_string()),
+            last_timestamp: Some("2024-05-01T12:00:04Z".to_string()),
+            levels: LevelCounts {
+                error: 2,
+                info: 3,
+                ..LevelCounts::default()
+            },
+            error_clusters: vec![LogCluster {
+                template: "<ts> ERROR db timeout after <n>ms".to_string(),
+                level: LogLevel::Error,
+                count: 2,
+                example: "2024-05-01T12:00:01Z ERROR db timeout after 30ms".to_string(),
+                first_line: Some(2),
+                first_timestamp: Some("2024-05-01T12:00:01Z".to_string()),
+                last_timestamp: Some("2024-05-01T12:00:03Z".to_string()),
+            }],
+            top_clusters: vec![
+                LogCluster {
+                    template: "<ts> INFO started worker <n>".to_string(),
+                    level: LogLevel::Info,
+                    count: 3,
+                    example: "2024-05-01T12:00:00Z INFO started worker 1".to_string(),
+                    first_line: Some(1),
+                    first_timestamp: Some("2024-05-01T12:00:00Z".to_string()),
+                    last_timestamp: Some("2024-05-01T12:00:04Z".to_string()),
+                },
+                LogCluster {
+                    template: "<ts> ERROR db timeout after <n>ms".to_string(),
+                    level: LogLevel::Error,
+                    count: 2,
+                    example: "2024-05-01T12:00:01Z ERROR db timeout after 30ms".to_string(),
+                    first_line: Some(2),
+                    first_timestamp: Some("2024-05-01T12:00:01Z".to_string()),
+                    last_timestamp: Some("2024-05-01T12:00:03Z".to_string()),
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn filter_keeps_only_matching_lines() {
+    let digest = digest_lines(
+        &["req=a ERROR boom", "req=b ERROR boom", "req=a INFO ok"],
+        Some("req=a"),
+    );
+
+    assert_eq!(digest.lines_read, 2);
+    assert_eq!(digest.levels.error, 1);
+    assert_eq!(digest.levels.info, 1);
+}
+
+#[test]
+fn contents_are_read_line_by_line_with_line_numbers() {
+    let digest = digest_bytes(
+        b"WARN retrying 1\r\nWARN retrying 2\n\xffWARN bad bytes\n",
+        &DigestRequest {
+            max_clusters: 1,
+            filter: None,
+        },
+    );
+
+    assert_eq!(digest.bytes, 49);
+    assert_eq!(digest.lines_read, 3);
+    assert_eq!(digest.levels.warning, 3);
+    assert_eq!(digest.error_clusters.len(), 1);
+    assert_eq!(digest.error_clusters[0].count, 2);
+    assert_eq!(digest.error_clusters[0].example, "WARN retrying 1");
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use codex_core::config::Config;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ToolContributor;
+use codex_features::Feature;
+
+use crate::tools;
+
+/// Contributes a tool that summarizes large log files instead of reading them into context.
+#[derive(Clone, Default)]
+pub(crate) struct LogDigestExtension;
+
+#[derive(Clone, Debug)]
+pub(crate) struct LogDigestExtensionConfig {
+    pub(crate) enabled: bool,
+}
+
+impl LogDigestExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::LogDigest),
+        }
+    }
+}
+
+impl ToolContributor for LogDigestExtension {
+    fn tools(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+    ) -> Vec<Arc<dyn codex_extension_api::ToolExecutor<codex_extension_api::ToolCall>>> {
+        let Some(config) = thread_store.get::<LogDigestExtensionConfig>() else {
+            return Vec::new();
+        };
+        if !config.enabled {
+            return Vec::new();
+        }
+
+        tools::log_digest_tools()
+    }
+}
+
+/// Installs the log digest extension contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    registry.thread_config(LogDigestExtensionConfig::from_config);
+    registry.tool_contributor(Arc::new(LogDigestExtension));
+}
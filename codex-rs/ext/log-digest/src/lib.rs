@@ -0,0 +1,23 @@
+mod digest;
+mod extension;
+mod tools;
+
+pub use extension::install;
+
+/// Files up to this size are read in full; larger ones are sampled.
+pub(crate) const FULL_READ_MAX_BYTES: u64 = 64 * 1024 * 1024;
+/// Number of evenly spaced windows read from a sampled file.
+pub(crate) const SAMPLE_WINDOWS: u64 = 64;
+pub(crate) const SAMPLE_WINDOW_BYTES: u64 = 512 * 1024;
+/// Distinct line templates tracked; lines with new templates past this are only counted.
+pub(crate) const MAX_TEMPLATES: usize = 20_000;
+pub(crate) const DEFAULT_MAX_CLUSTERS: usize = 20;
+pub(crate) const MAX_CLUSTERS: usize = 100;
+/// Example lines are cut to this many characters.
+pub(crate) const MAX_EXAMPLE_CHARS: usize = 400;
+
+pub(crate) const LOGS_NAMESPACE: &str = "logs";
+pub(crate) const DIGEST_TOOL_NAME: &str = "digest";
+
+#[cfg(test)]
+mod tests;
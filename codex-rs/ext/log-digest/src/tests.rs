@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use codex_extension_api::DenyCommandApprover;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::NoopTurnItemEmitter;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolContributor;
+use codex_extension_api::ToolEnvironment;
+use codex_extension_api::ToolPayload;
+use codex_extension_api::new_local_environment;
+use codex_protocol::models::PermissionProfile;
+use codex_utils_output_truncation::TruncationPolicy;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::extension::LogDigestExtension;
+use crate::extension::LogDigestExtensionConfig;
+use crate::tools::logs_tool_name;
+
+fn enabled_thread_store() -> ExtensionData {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(LogDigestExtensionConfig { enabled: true });
+    thread_store
+}
+
+fn digest_call(environments: Vec<ToolEnvironment>) -> ToolCall {
+    ToolCall {
+        turn_id: "turn-1".to_string(),
+        call_id: "call-1".to_string(),
+        tool_name: logs_tool_name(crate::DIGEST_TOOL_NAME),
+        model: "gpt-test".to_string(),
+        codex_turn_metadata: None,
+        truncation_policy: TruncationPolicy::Bytes(1024),
+        conversation_history: codex_extension_api::ConversationHistory::default(),
+        turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+        command_approver: Arc::new(DenyCommandApprover),
+        environments,
+        payload: ToolPayload::Function {
+            arguments: json!({ "path": "app.log" }).to_string(),
+        },
+    }
+}
+
+#[test]
+fn install_registers_the_digest_tool() {
+    let mut builder = ExtensionRegistryBuilder::<codex_core::config::Config>::new();
+    crate::install(&mut builder);
+    let registry = builder.build();
+    let thread_store = enabled_thread_store();
+
+    let tool_names = registry
+        .tool_contributors()
+        .iter()
+        .flat_map(|contributor| contributor.tools(&ExtensionData::new("session"), &thread_store))
+        .map(|tool| tool.tool_name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(tool_names, vec![logs_tool_name(crate::DIGEST_TOOL_NAME)]);
+}
+
+#[test]
+fn tools_are_not_contributed_when_disabled() {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(LogDigestExtensionConfig { enabled: false });
+
+    assert!(
+        LogDigestExtension
+            .tools(&ExtensionData::new("session"), &thread_store)
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn tools_require_a_local_environment() {
+    let tools = LogDigestExtension.tools(&ExtensionData::new("session"), &enabled_thread_store());
+
+    let Err(err) = tools[0].handle(digest_call(Vec::new())).await else {
+        panic!("digest without a local environment should fail");
+    };
+
+    assert_eq!(
+        err,
+        FunctionCallError::RespondToModel(
+            "log tools are only available for local workspaces".to_string()
+        )
+    );
+}
+
+#[tokio::test]
+async fn digest_reads_through_the_environment_file_system() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("app.log"), "ERROR boom 1\nERROR boom 2\n").expect("write log");
+    let tools = LogDigestExtension.tools(&ExtensionData::new("session"), &enabled_thread_store());
+
+    let output = tools[0]
+        .handle(digest_call(vec![
+            new_local_environment(dir.path(), PermissionProfile::Disabled)
+                .expect("local environment"),
+        ]))
+        .await
+        .expect("digest log");
+    let digest = output.code_mode_result(&ToolPayload::Function {
+        arguments: String::new(),
+    });
+    assert_eq!(digest["levels"]["error"], json!(2));
+
+    // A restricted sandbox needs the sandboxed file system helper, which unit tests do not
+    // configure; the read must fail rather than fall back to reading the file directly.
+    let Err(FunctionCallError::RespondToModel(message)) = tools[0]
+        .handle(digest_call(vec![
+            new_local_environment(dir.path(), PermissionProfile::read_only())
+                .expect("local environment"),
+        ]))
+        .await
+    else {
+        panic!("a sandboxed read should not bypass the sandbox");
+    };
+    assert!(
+        message.contains("sandboxed filesystem operations require configured runtime paths"),
+        "{message}"
+    );
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::JsonToolOutput;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolExecutorFuture;
+use codex_extension_api::ToolName;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolSpec;
+use codex_extension_api::local_environment;
+use codex_extension_api::namespaced_function_tool;
+use codex_extension_api::parse_function_arguments;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::DEFAULT_MAX_CLUSTERS;
+use crate::DIGEST_TOOL_NAME;
+use crate::LOGS_NAMESPACE;
+use crate::MAX_CLUSTERS;
+use crate::digest::DigestRequest;
+use crate::digest::LogDigest;
+use crate::digest::digest_bytes;
+
+/// Logs are read on this host, so the tool only works against the local checkout.
+const LOCAL_WORKSPACE_REQUIRED: &str = "log tools are only available for local workspaces";
+
+pub(crate) fn log_digest_tools() -> Vec<Arc<dyn ToolExecutor<ToolCall>>> {
+    vec![Arc::new(DigestTool)]
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct DigestArgs {
+    /// Log file to digest, relative to the working directory.
+    path: String,
+    /// Only consider lines containing this text, such as a request id or module name.
+    filter: Option<String>,
+    /// Maximum number of clusters to return in each list.
+    #[schemars(range(min = 1, max = 100))]
+    max_clusters: Option<usize>,
+}
+
+struct DigestTool;
+
+impl ToolExecutor<ToolCall> for DigestTool {
+    fn tool_name(&self) -> ToolName {
+        logs_tool_name(DIGEST_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<DigestArgs, LogDigest>(
+            LOGS_NAMESPACE,
+            DIGEST_TOOL_NAME,
+            "Summarize a log file of any size: level counts, the time range covered, and repeated lines grouped into clusters with a count and an example, errors first. Files over 64 MiB are sampled. Use it before reading a large log directly.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: DigestArgs = parse_function_arguments(&call)?;
+            let environment = local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?;
+            let request = DigestRequest {
+                max_clusters: args
+                    .max_clusters
+                    .unwrap_or(DEFAULT_MAX_CLUSTERS)
+                    .clamp(1, MAX_CLUSTERS),
+                filter: args.filter.filter(|filter| !filter.is_empty()),
+            };
+            // Read through the environment's file system so the sandbox read policy applies.
+            let contents = environment
+                .read_file(&environment.resolve_path(&args.path))
+                .await
+                .map_err(|err| {
+                    FunctionCallError::RespondToModel(format!(
+                        "failed to read {}: {err}",
+                        args.path
+                    ))
+                })?;
+            let digest = tokio::task::spawn_blocking(move || digest_bytes(&contents, &request))
+                .await
+                .map_err(|err| {
+                    FunctionCallError::Fatal(format!("log digest task failed: {err}"))
+                })?;
+            let output: Box<dyn ToolOutput> = Box::new(JsonToolOutput::new(json!(digest)));
+            Ok(output)
+        })
+    }
+}
+
+pub(crate) fn logs_tool_name(name: &str) -> ToolName {
+    ToolName::namespaced(LOGS_NAMESPACE, name)
+}
@@ -2,6 +2,7 @@ use std::path::Path;
 use std::sync::Arc;
 
 use codex_extension_api::ContextContributor;
+use codex_extension_api::DenyCommandApprover;
 use codex_extension_api::ExtensionData;
 use codex_extension_api::ExtensionRegistryBuilder;
 use codex_extension_api::NoopTurnItemEmitter;
@@ -216,6 +217,7 @@ async fn add_ad_hoc_note_tool_creates_note_file() {
             truncation_policy: TruncationPolicy::Bytes(1024),
             conversation_history: codex_extension_api::ConversationHistory::default(),
             turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+            command_approver: Arc::new(DenyCommandApprover),
             environments: Vec::new(),
             payload: payload.clone(),
         })
@@ -261,6 +263,7 @@ async fn add_ad_hoc_note_tool_rejects_paths_as_filenames() {
             truncation_policy: TruncationPolicy::Bytes(1024),
             conversation_history: codex_extension_api::ConversationHistory::default(),
             turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+            command_approver: Arc::new(DenyCommandApprover),
             environments: Vec::new(),
             payload,
         })
@@ -307,6 +310,7 @@ async fn read_tool_reads_memory_file() {
             truncation_policy: TruncationPolicy::Bytes(1024),
             conversation_history: codex_extension_api::ConversationHistory::default(),
             turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+            command_approver: Arc::new(DenyCommandApprover),
             environments: Vec::new(),
             payload: payload.clone(),
         })
@@ -356,6 +360,7 @@ async fn search_tool_accepts_multiple_queries() {
             truncation_policy: TruncationPolicy::Bytes(1024),
             conversation_history: codex_extension_api::ConversationHistory::default(),
             turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+            command_approver: Arc::new(DenyCommandApprover),
             environments: Vec::new(),
             payload: payload.clone(),
         })
@@ -431,6 +436,7 @@ async fn search_tool_accepts_windowed_all_match_mode() {
             truncation_policy: TruncationPolicy::Bytes(1024),
             conversation_history: codex_extension_api::ConversationHistory::default(),
             turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+            command_approver: Arc::new(DenyCommandApprover),
             environments: Vec::new(),
             payload: payload.clone(),
         })
@@ -486,6 +492,7 @@ async fn search_tool_rejects_legacy_single_query() {
             truncation_policy: TruncationPolicy::Bytes(1024),
             conversation_history: codex_extension_api::ConversationHistory::default(),
             turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+            command_approver: Arc::new(DenyCommandApprover),
             environments: Vec::new(),
             payload,
         })
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use codex_core::config::Config;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ToolContributor;
+use codex_features::Feature;
+
+use crate::tools;
+
+/// Contributes tools that read and edit Jupyter notebooks as cells instead of raw JSON.
+#[derive(Clone, Default)]
+pub(crate) struct NotebookExtension;
+
+#[derive(Clone, Debug)]
+pub(crate) struct NotebookExtensionConfig {
+    pub(crate) enabled: bool,
+}
+
+impl NotebookExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::NotebookTools),
+        }
+    }
+}
+
+impl ToolContributor for NotebookExtension {
+    fn tools(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+    ) -> Vec<Arc<dyn codex_extension_api::ToolExecutor<codex_extension_api::ToolCall>>> {
+        let Some(config) = thread_store.get::<NotebookExtensionConfig>() else {
+            return Vec::new();
+        };
+        if !config.enabled {
+            return Vec::new();
+        }
+
+        tools::notebook_tools()
+    }
+}
+
+/// Installs the notebook extension contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    registry.thread_config(NotebookExtensionConfig::from_config);
+    registry.tool_contributor(Arc::new(NotebookExtension));
+}
@@ -0,0 +1,15 @@
+mod extension;
+mod patch;
+mod tools;
+
+pub use extension::install;
+
+pub(crate) const DEFAULT_MAX_CELLS: usize = 50;
+pub(crate) const MAX_CELLS: usize = 200;
+
+pub(crate) const NOTEBOOK_NAMESPACE: &str = "notebook";
+pub(crate) const READ_TOOL_NAME: &str = "read";
+pub(crate) const EDIT_TOOL_NAME: &str = "edit";
+
+#[cfg(test)]
+mod tests;
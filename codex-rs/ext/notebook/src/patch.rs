@@ -0,0 +1,29 @@
+use similar::ChangeTag;
+use similar::TextDiff;
+
+/// Unchanged lines shown around each change so hunks anchor unambiguously.
+const CONTEXT_LINES: usize = 3;
+
+/// Renders the change from `old` to `new` as an `apply_patch` update of `path`.
+pub(crate) fn update_patch(path: &str, old: &str, new: &str) -> String {
+    let diff = TextDiff::from_lines(old, new);
+    let mut patch = format!("*** Begin Patch\n*** Update File: {path}\n");
+    for group in diff.grouped_ops(CONTEXT_LINES) {
+        patch.push_str("@@\n");
+        for op in group {
+            for change in diff.iter_changes(&op) {
+                let sign = match change.tag() {
+                    ChangeTag::Equal => ' ',
+                    ChangeTag::Delete => '-',
+                    ChangeTag::Insert => '+',
+                };
+                let line = change.value();
+                patch.push(sign);
+                patch.push_str(line.strip_suffix('\n').unwrap_or(line));
+                patch.push('\n');
+            }
+        }
+    }
+    patch.push_str("*** End Patch\n");
+    patch
+}
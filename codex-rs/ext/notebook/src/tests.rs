@@ -0,0 +1,214 @@
+use std::sync::Arc;
+
+use codex_extension_api::DenyCommandApprover;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::NoopTurnItemEmitter;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolContributor;
+use codex_extension_api::ToolEnvironment;
+use codex_extension_api::ToolPayload;
+use codex_extension_api::new_local_environment;
+use codex_protocol::models::PermissionProfile;
+use codex_utils_output_truncation::TruncationPolicy;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::extension::NotebookExtension;
+use crate::extension::NotebookExtensionConfig;
+use crate::tools::CellEdit;
+use crate::tools::NotebookEditPlan;
+use crate::tools::notebook_tool_name;
+use crate::tools::plan_edits;
+
+const NOTEBOOK: &str = r##"{
+ "cells": [
+  {
+   "cell_type": "code",
+   "execution_count": null,
+   "metadata": {},
+   "outputs": [],
+   "source": [
+    "x = 1\n",
+    "print(x)"
+   ]
+  }
+ ],
+ "metadata": {},
+ "nbformat": 4,
+ "nbformat_minor": 4
+}
+"##;
+
+fn enabled_thread_store() -> ExtensionData {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(NotebookExtensionConfig { enabled: true });
+    thread_store
+}
+
+fn read_call(environments: Vec<ToolEnvironment>) -> ToolCall {
+    ToolCall {
+        turn_id: "turn-1".to_string(),
+        call_id: "call-1".to_string(),
+        tool_name: notebook_tool_name(crate::READ_TOOL_NAME),
+        model: "gpt-test".to_string(),
+        codex_turn_metadata: None,
+        truncation_policy: TruncationPolicy::Bytes(1024),
+        conversation_history: codex_extension_api::ConversationHistory::default(),
+        turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+        command_approver: Arc::new(DenyCommandApprover),
+        environments,
+        payload: ToolPayload::Function {
+            arguments: json!({ "path": "nb.ipynb" }).to_string(),
+        },
+    }
+}
+
+fn edits(value: serde_json::Value) -> Vec<CellEdit> {
+    serde_json::from_value(value).expect("edits")
+}
+
+#[test]
+fn install_registers_notebook_tools() {
+    let mut builder = ExtensionRegistryBuilder::<codex_core::config::Config>::new();
+    crate::install(&mut builder);
+    let registry = builder.build();
+    let thread_store = enabled_thread_store();
+
+    let tool_names = registry
+        .tool_contributors()
+        .iter()
+        .flat_map(|contributor| contributor.tools(&ExtensionData::new("session"), &thread_store))
+        .map(|tool| tool.tool_name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        tool_names,
+        vec![
+            notebook_tool_name(crate::READ_TOOL_NAME),
+            notebook_tool_name(crate::EDIT_TOOL_NAME),
+        ]
+    );
+}
+
+#[test]
+fn tools_are_not_contributed_when_disabled() {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(NotebookExtensionConfig { enabled: false });
+
+    assert!(
+        NotebookExtension
+            .tools(&ExtensionData::new("session"), &thread_store)
+            .is_empty()
+    );
+}
+
+#[test]
+fn cell_edits_become_a_patch_of_the_notebook_json() {
+    let plan = plan_edits(
+        NOTEBOOK,
+        "nb.ipynb",
+        &edits(json!([{ "op": "replace", "index": 0, "source": "x = 2\nprint(x)" }])),
+    )
+    .expect("plan");
+
+    let patch = [
+        "*** Begin Patch",
+        "*** Update File: nb.ipynb",
+        "@@",
+        r#"    "metadata": {},"#,
+        r#"    "outputs": [],"#,
+        r#"    "source": ["#,
+        r#"-    "x = 1\n","#,
+        r#"+    "x = 2\n","#,
+        r#"     "print(x)""#,
+        "    ]",
+        "   }",
+        "*** End Patch",
+        "",
+    ]
+    .join("\n");
+    assert_eq!(
+        plan,
+        NotebookEditPlan {
+            patch,
+            cell_count: 1,
+        }
+    );
+}
+
+#[test]
+fn edits_that_change_nothing_produce_no_patch() {
+    let plan = plan_edits(
+        NOTEBOOK,
+        "nb.ipynb",
+        &edits(json!([{ "op": "replace", "index": 0, "source": "x = 1\nprint(x)" }])),
+    )
+    .expect("plan");
+
+    assert_eq!(plan.patch, "");
+}
+
+#[test]
+fn edits_with_bad_indices_are_reported() {
+    assert_eq!(
+        plan_edits(
+            NOTEBOOK,
+            "nb.ipynb",
+            &edits(json!([{ "op": "delete", "index": 3 }]))
+        ),
+        Err("cell index 3 is out of range; the notebook has 1 cells".to_string())
+    );
+}
+
+#[tokio::test]
+async fn tools_require_a_local_environment() {
+    let tools = NotebookExtension.tools(&ExtensionData::new("session"), &enabled_thread_store());
+
+    let Err(err) = tools[0].handle(read_call(Vec::new())).await else {
+        panic!("read without a local environment should fail");
+    };
+
+    assert_eq!(
+        err,
+        FunctionCallError::RespondToModel(
+            "notebook tools are only available for local workspaces".to_string()
+        )
+    );
+}
+
+#[tokio::test]
+async fn read_goes_through_the_environment_file_system() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("nb.ipynb"), NOTEBOOK).expect("write notebook");
+    let tools = NotebookExtension.tools(&ExtensionData::new("session"), &enabled_thread_store());
+
+    let output = tools[0]
+        .handle(read_call(vec![
+            new_local_environment(dir.path(), PermissionProfile::Disabled)
+                .expect("local environment"),
+        ]))
+        .await
+        .expect("read notebook");
+    let view = output.code_mode_result(&ToolPayload::Function {
+        arguments: String::new(),
+    });
+    assert_eq!(view["cell_count"], json!(1));
+
+    // A restricted sandbox needs the sandboxed file system helper, which unit tests do not
+    // configure; the read must fail rather than fall back to reading the file directly.
+    let Err(FunctionCallError::RespondToModel(message)) = tools[0]
+        .handle(read_call(vec![
+            new_local_environment(dir.path(), PermissionProfile::read_only())
+                .expect("local environment"),
+        ]))
+        .await
+    else {
+        panic!("a sandboxed read should not bypass the sandbox");
+    };
+    assert!(
+        message.contains("sandboxed filesystem operations require configured runtime paths"),
+        "{message}"
+    );
+}
@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::JsonToolOutput;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolEnvironment;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolExecutorFuture;
+use codex_extension_api::ToolName;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolSpec;
+use codex_extension_api::local_environment;
+use codex_extension_api::namespaced_function_tool;
+use codex_extension_api::parse_function_arguments;
+use codex_utils_notebook::CellType;
+use codex_utils_notebook::Notebook;
+use codex_utils_notebook::NotebookCell;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::DEFAULT_MAX_CELLS;
+use crate::EDIT_TOOL_NAME;
+use crate::MAX_CELLS;
+use crate::NOTEBOOK_NAMESPACE;
+use crate::READ_TOOL_NAME;
+use crate::patch::update_patch;
+
+/// Patches are applied by `apply_patch` in the local checkout, so the tools only work there.
+const LOCAL_WORKSPACE_REQUIRED: &str = "notebook tools are only available for local workspaces";
+
+pub(crate) fn notebook_tools() -> Vec<Arc<dyn ToolExecutor<ToolCall>>> {
+    vec![Arc::new(ReadTool), Arc::new(EditTool)]
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct ReadArgs {
+    /// Notebook to read, relative to the working directory.
+    path: String,
+    /// First cell to return, 0-indexed. Defaults to 0.
+    start_cell: Option<usize>,
+    /// Maximum number of cells to return.
+    #[schemars(range(min = 1, max = 200))]
+    max_cells: Option<usize>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct EditArgs {
+    /// Notebook to edit, relative to the working directory.
+    path: String,
+    /// Edits applied in order; each index refers to the notebook as left by the previous edit.
+    edits: Vec<CellEdit>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct CellEdit {
+    op: CellEditOp,
+    /// Cell to replace or delete, or the position to insert at.
+    index: usize,
+    /// New cell source. Required for `replace` and `insert`.
+    source: Option<String>,
+    /// Cell type for `insert` (defaults to `code`), or a new type for `replace`.
+    cell_type: Option<CellType>,
+}
+
+#[derive(Clone, Copy, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum CellEditOp {
+    Replace,
+    Insert,
+    Delete,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct NotebookView {
+    language: Option<String>,
+    cell_count: usize,
+    cells: Vec<NotebookCell>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct NotebookEditPlan {
+    /// Patch in the `apply_patch` format, or empty when the edits change nothing.
+    pub(crate) patch: String,
+    /// Number of cells after the edits.
+    pub(crate) cell_count: usize,
+}
+
+struct ReadTool;
+
+impl ToolExecutor<ToolCall> for ReadTool {
+    fn tool_name(&self) -> ToolName {
+        notebook_tool_name(READ_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<ReadArgs, NotebookView>(
+            NOTEBOOK_NAMESPACE,
+            READ_TOOL_NAME,
+            "Read a Jupyter notebook (.ipynb) as cells: index, type, source, and a trimmed text summary of each cell's outputs. Prefer this over reading the raw JSON.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: ReadArgs = parse_function_arguments(&call)?;
+            let original = read(
+                local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?,
+                &args.path,
+            )
+            .await?;
+            let notebook = Notebook::parse(&original)
+                .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+            let cells = notebook.cells();
+            let cell_count = cells.len();
+            let max_cells = args
+                .max_cells
+                .unwrap_or(DEFAULT_MAX_CELLS)
+                .clamp(1, MAX_CELLS);
+            json_output(NotebookView {
+                language: notebook.language().map(str::to_string),
+                cell_count,
+                cells: cells
+                    .into_iter()
+                    .skip(args.start_cell.unwrap_or(0))
+                    .take(max_cells)
+                    .collect(),
+            })
+        })
+    }
+}
+
+struct EditTool;
+
+impl ToolExecutor<ToolCall> for EditTool {
+    fn tool_name(&self) -> ToolName {
+        notebook_tool_name(EDIT_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<EditArgs, NotebookEditPlan>(
+            NOTEBOOK_NAMESPACE,
+            EDIT_TOOL_NAME,
+            "Plan cell-level edits to a Jupyter notebook: replace, insert, or delete cells. The notebook JSON stays valid and keeps Jupyter's formatting. Returns an apply_patch patch without writing anything; apply it with apply_patch.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: EditArgs = parse_function_arguments(&call)?;
+            let original = read(
+                local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?,
+                &args.path,
+            )
+            .await?;
+            let plan = plan_edits(&original, &args.path, &args.edits)
+                .map_err(FunctionCallError::RespondToModel)?;
+            json_output(plan)
+        })
+    }
+}
+
+/// Applies `edits` to the notebook text `original` and returns them as a patch of `patch_path`.
+pub(crate) fn plan_edits(
+    original: &str,
+    patch_path: &str,
+    edits: &[CellEdit],
+) -> Result<NotebookEditPlan, String> {
+    let mut notebook = Notebook::parse(original).map_err(|err| err.to_string())?;
+    for edit in edits {
+        let source = || {
+            edit.source
+                .as_deref()
+                .ok_or_else(|| format!("cell {}: `source` is required", edit.index))
+        };
+        let result = match edit.op {
+            CellEditOp::Replace => {
+                notebook
+                    .set_source(edit.index, source()?)
+                    .and_then(|()| match edit.cell_type {
+                        Some(cell_type) => notebook.set_cell_type(edit.index, cell_type),
+                        None => Ok(()),
+                    })
+            }
+            CellEditOp::Insert => notebook.insert_cell(
+                edit.index,
+                edit.cell_type.unwrap_or(CellType::Code),
+                source()?,
+            ),
+            CellEditOp::Delete => notebook.delete_cell(edit.index),
+        };
+        result.map_err(|err| err.to_string())?;
+    }
+
+    let updated = notebook.to_ipynb();
+    Ok(NotebookEditPlan {
+        patch: if updated == original {
+            String::new()
+        } else {
+            update_patch(patch_path, original, &updated)
+        },
+        cell_count: notebook.cells().len(),
+    })
+}
+
+/// Reads the notebook through the environment's file system, so the sandbox read policy applies.
+async fn read(environment: &ToolEnvironment, path: &str) -> Result<String, FunctionCallError> {
+    environment
+        .read_file_text(&environment.resolve_path(path))
+        .await
+        .map_err(|err| FunctionCallError::RespondToModel(format!("failed to read {path}: {err}")))
+}
+
+pub(crate) fn notebook_tool_name(name: &str) -> ToolName {
+    ToolName::namespaced(NOTEBOOK_NAMESPACE, name)
+}
+
+fn json_output<T: Serialize>(value: T) -> Result<Box<dyn ToolOutput>, FunctionCallError> {
+    Ok(Box::new(JsonToolOutput::new(json!(value))))
+}
@@ -0,0 +1,266 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use codex_core::config::Config;
+use codex_core::context::ContextualUserFragment;
+use codex_core::context::InternalContextSource;
+use codex_core::context::InternalModelContextFragment;
+use codex_extension_api::ConfigContributor;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionFuture;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ThreadLifecycleContributor;
+use codex_extension_api::ThreadResumeInput;
+use codex_extension_api::ThreadStartInput;
+use codex_extension_api::ToolFinishInput;
+use codex_extension_api::ToolLifecycleContributor;
+use codex_extension_api::ToolLifecycleFuture;
+use codex_extension_api::ToolStartInput;
+use codex_extension_api::TurnAbortInput;
+use codex_extension_api::TurnErrorInput;
+use codex_extension_api::TurnInputContext;
+use codex_extension_api::TurnInputContributor;
+use codex_extension_api::TurnLifecycleContributor;
+use codex_features::Feature;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::TurnAbortReason;
+
+use crate::report::StopReason;
+use crate::report::TurnActivity;
+use crate::report::render_post_mortem;
+
+/// Records a post-mortem when a turn errors out or is interrupted, and offers it as starting
+/// context on the first turn after the thread is resumed.
+pub(crate) struct PostMortemExtension {
+    state_db: Arc<codex_state::StateRuntime>,
+}
+
+#[derive(Clone, Debug)]
+struct PostMortemExtensionConfig {
+    enabled: bool,
+}
+
+impl PostMortemExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::PostMortems),
+        }
+    }
+}
+
+/// What the next turn should do with a stored post-mortem.
+#[derive(Debug, Default)]
+enum PendingPostMortem {
+    #[default]
+    None,
+    /// The thread was resumed; offer any stored post-mortem to the model.
+    Offer,
+    /// This runtime recorded a post-mortem and the user carried on; it is stale now.
+    Discard,
+}
+
+#[derive(Debug, Default)]
+struct PostMortemThreadState {
+    pending: Mutex<PendingPostMortem>,
+}
+
+impl PostMortemThreadState {
+    fn set(&self, pending: PendingPostMortem) {
+        *self.pending.lock().unwrap_or_else(PoisonError::into_inner) = pending;
+    }
+
+    fn take(&self) -> PendingPostMortem {
+        std::mem::take(&mut *self.pending.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl PostMortemExtension {
+    async fn record(
+        &self,
+        thread_store: &ExtensionData,
+        turn_store: &ExtensionData,
+        stop_reason: StopReason,
+    ) {
+        if !thread_store
+            .get::<PostMortemExtensionConfig>()
+            .is_some_and(|config| config.enabled)
+        {
+            return;
+        }
+        let Some(activity) = turn_store.get::<TurnActivity>() else {
+            return;
+        };
+        let Ok(thread_id) = ThreadId::from_string(thread_store.level_id()) else {
+            return;
+        };
+        let changed_paths = match activity.cwd() {
+            Some(cwd) => codex_git_utils::get_status_entries(&cwd)
+                .await
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
+        let recorded_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| i64::try_from(elapsed.as_millis()).unwrap_or(i64::MAX))
+            .unwrap_or_default();
+        let post_mortem = activity.post_mortem(
+            turn_store.level_id(),
+            &stop_reason,
+            changed_paths,
+            recorded_at_ms,
+        );
+        if let Err(err) = self
+            .state_db
+            .record_thread_post_mortem(thread_id, &post_mortem)
+            .await
+        {
+            tracing::warn!("failed to record post-mortem for {thread_id}: {err}");
+            return;
+        }
+        thread_store
+            .get_or_init::<PostMortemThreadState>(Default::default)
+            .set(PendingPostMortem::Discard);
+    }
+}
+
+impl ThreadLifecycleContributor<Config> for PostMortemExtension {
+    fn on_thread_start<'a>(
+        &'a self,
+        input: ThreadStartInput<'a, Config>,
+    ) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            input
+                .thread_store
+                .insert(PostMortemExtensionConfig::from_config(input.config));
+        })
+    }
+
+    fn on_thread_resume<'a>(&'a self, input: ThreadResumeInput<'a>) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            input
+                .thread_store
+                .get_or_init::<PostMortemThreadState>(Default::default)
+                .set(PendingPostMortem::Offer);
+        })
+    }
+}
+
+impl ConfigContributor<Config> for PostMortemExtension {
+    fn on_config_changed(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+        _previous_config: &Config,
+        new_config: &Config,
+    ) {
+        thread_store.insert(PostMortemExtensionConfig::from_config(new_config));
+    }
+}
+
+impl TurnInputContributor for PostMortemExtension {
+    fn contribute<'a>(
+        &'a self,
+        input: TurnInputContext,
+        _session_store: &'a ExtensionData,
+        thread_store: &'a ExtensionData,
+        turn_store: &'a ExtensionData,
+    ) -> ExtensionFuture<'a, Vec<Box<dyn ContextualUserFragment + Send>>> {
+        Box::pin(async move {
+            let Some(config) = thread_store.get::<PostMortemExtensionConfig>() else {
+                return Vec::new();
+            };
+            if !config.enabled {
+                return Vec::new();
+            }
+            let cwd = input
+                .environments
+                .iter()
+                .find(|environment| environment.is_primary)
+                .map(|environment| environment.cwd.clone());
+            turn_store.insert(TurnActivity::new(&input.user_input, cwd));
+
+            let offer = match thread_store
+                .get::<PostMortemThreadState>()
+                .map(|state| state.take())
+                .unwrap_or_default()
+            {
+                PendingPostMortem::None => return Vec::new(),
+                PendingPostMortem::Offer => true,
+                PendingPostMortem::Discard => false,
+            };
+            let Ok(thread_id) = ThreadId::from_string(thread_store.level_id()) else {
+                return Vec::new();
+            };
+            let post_mortem = match self.state_db.take_thread_post_mortem(thread_id).await {
+                Ok(Some(post_mortem)) if offer => post_mortem,
+                Ok(_) => return Vec::new(),
+                Err(err) => {
+                    tracing::warn!("failed to load post-mortem for {thread_id}: {err}");
+                    return Vec::new();
+                }
+            };
+            vec![Box::new(InternalModelContextFragment::new(
+                InternalContextSource::from_static("post_mortem"),
+                render_post_mortem(&post_mortem),
+            ))]
+        })
+    }
+}
+
+impl TurnLifecycleContributor for PostMortemExtension {
+    fn on_turn_abort<'a>(&'a self, input: TurnAbortInput<'a>) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            let stop_reason = match input.reason {
+                TurnAbortReason::Interrupted => StopReason::Interrupted,
+                TurnAbortReason::BudgetLimited => StopReason::BudgetLimited,
+                // The user already moved on to another turn or review.
+                TurnAbortReason::Replaced | TurnAbortReason::ReviewEnded => return,
+            };
+            self.record(input.thread_store, input.turn_store, stop_reason)
+                .await;
+        })
+    }
+
+    fn on_turn_error<'a>(&'a self, input: TurnErrorInput<'a>) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            self.record(
+                input.thread_store,
+                input.turn_store,
+                StopReason::Error(input.error),
+            )
+            .await;
+        })
+    }
+}
+
+impl ToolLifecycleContributor for PostMortemExtension {
+    fn on_tool_start<'a>(&'a self, input: ToolStartInput<'a>) -> ToolLifecycleFuture<'a> {
+        if let Some(activity) = input.turn_store.get::<TurnActivity>() {
+            activity.start_tool(input.call_id, input.tool_name.to_string());
+        }
+        Box::pin(std::future::ready(()))
+    }
+
+    fn on_tool_finish<'a>(&'a self, input: ToolFinishInput<'a>) -> ToolLifecycleFuture<'a> {
+        if let Some(activity) = input.turn_store.get::<TurnActivity>() {
+            activity.finish_tool(input.call_id, input.tool_name.to_string(), input.outcome);
+        }
+        Box::pin(std::future::ready(()))
+    }
+}
+
+/// Installs the post-mortem extension contributors into the extension registry.
+pub fn install(
+    registry: &mut ExtensionRegistryBuilder<Config>,
+    state_db: Arc<codex_state::StateRuntime>,
+) {
+    let extension = Arc::new(PostMortemExtension { state_db });
+    registry.thread_lifecycle_contributor(extension.clone());
+    registry.config_contributor(extension.clone());
+    registry.turn_input_contributor(extension.clone());
+    registry.turn_lifecycle_contributor(extension.clone());
+    registry.tool_lifecycle_contributor(extension);
+}
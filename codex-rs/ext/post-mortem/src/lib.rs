@@ -0,0 +1,6 @@
+//! Extension crate for post-mortems of runs that error out or are interrupted.
+
+mod extension;
+mod report;
+
+pub use extension::install;
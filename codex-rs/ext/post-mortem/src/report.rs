@@ -0,0 +1,201 @@
+use codex_extension_api::ToolCallOutcome;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::user_input::UserInput;
+use codex_state::ThreadPostMortemRecord;
+use codex_utils_string::take_bytes_at_char_boundary;
+use codex_utils_template::Template;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+const MAX_ATTEMPTED_BYTES: usize = 4_000;
+const MAX_TOOL_CALLS: usize = 30;
+const MAX_CHANGED_PATHS: usize = 50;
+
+static POST_MORTEM_PROMPT_TEMPLATE: LazyLock<Template> = LazyLock::new(|| {
+    Template::parse(include_str!("../templates/post_mortem.md"))
+        .unwrap_or_else(|err| panic!("embedded template post_mortem.md is invalid: {err}"))
+});
+
+/// Why a turn stopped before finishing.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum StopReason {
+    Interrupted,
+    BudgetLimited,
+    Error(CodexErrorInfo),
+}
+
+impl StopReason {
+    fn describe(&self) -> String {
+        match self {
+            StopReason::Interrupted => "interrupted by the user".to_string(),
+            StopReason::BudgetLimited => "token budget exhausted".to_string(),
+            StopReason::Error(error) => format!("error ({error:?})"),
+        }
+    }
+}
+
+/// What a turn has attempted so far, kept in the turn store until the turn ends.
+#[derive(Debug)]
+pub(crate) struct TurnActivity {
+    inner: Mutex<TurnActivityInner>,
+}
+
+#[derive(Debug)]
+struct TurnActivityInner {
+    attempted: String,
+    cwd: Option<PathBuf>,
+    tool_calls: Vec<String>,
+    running_tools: Vec<(String, String)>,
+    failed_tools: Vec<String>,
+}
+
+impl TurnActivity {
+    pub(crate) fn new(user_input: &[UserInput], cwd: Option<PathBuf>) -> Self {
+        let attempted = user_input
+            .iter()
+            .filter_map(|input| match input {
+                UserInput::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let attempted = take_bytes_at_char_boundary(&attempted, MAX_ATTEMPTED_BYTES).to_string();
+        Self {
+            inner: Mutex::new(TurnActivityInner {
+                attempted,
+                cwd,
+                tool_calls: Vec::new(),
+                running_tools: Vec::new(),
+                failed_tools: Vec::new(),
+            }),
+        }
+    }
+
+    pub(crate) fn cwd(&self) -> Option<PathBuf> {
+        self.inner().cwd.clone()
+    }
+
+    pub(crate) fn start_tool(&self, call_id: &str, tool_name: String) {
+        let mut inner = self.inner();
+        if inner.tool_calls.len() == MAX_TOOL_CALLS {
+            inner.tool_calls.remove(0);
+        }
+        inner.tool_calls.push(tool_name.clone());
+        inner.running_tools.push((call_id.to_string(), tool_name));
+    }
+
+    pub(crate) fn finish_tool(&self, call_id: &str, tool_name: String, outcome: ToolCallOutcome) {
+        let mut inner = self.inner();
+        inner
+            .running_tools
+            .retain(|(running_id, _)| running_id != call_id);
+        match outcome {
+            ToolCallOutcome::Completed { success: false }
+            | ToolCallOutcome::Failed {
+                handler_executed: true,
+            } => inner.failed_tools.push(tool_name),
+            ToolCallOutcome::Completed { success: true }
+            | ToolCallOutcome::Blocked
+            | ToolCallOutcome::Failed {
+                handler_executed: false,
+            }
+            | ToolCallOutcome::Aborted => {}
+        }
+    }
+
+    /// Builds the post-mortem for a turn that stopped with the working tree in `changed_paths`.
+    pub(crate) fn post_mortem(
+        &self,
+        turn_id: &str,
+        stop_reason: &StopReason,
+        mut changed_paths: Vec<String>,
+        recorded_at_ms: i64,
+    ) -> ThreadPostMortemRecord {
+        let inner = self.inner();
+        let mut open_questions = inner
+            .running_tools
+            .iter()
+            .map(|(_, tool_name)| {
+                format!(
+                    "`{tool_name}` was still running when the turn stopped; \
+                     its effects may be partial."
+                )
+            })
+            .collect::<Vec<_>>();
+        open_questions.extend(
+            inner
+                .failed_tools
+                .iter()
+                .map(|tool_name| format!("`{tool_name}` failed and the failure was not resolved.")),
+        );
+        if !changed_paths.is_empty() {
+            open_questions.push(
+                "Some uncommitted changes may be incomplete; review them before building on them."
+                    .to_string(),
+            );
+        }
+        if matches!(stop_reason, StopReason::Interrupted) {
+            open_questions.push(
+                "The user interrupted the run; confirm whether the approach should change."
+                    .to_string(),
+            );
+        }
+        changed_paths.truncate(MAX_CHANGED_PATHS);
+
+        ThreadPostMortemRecord {
+            turn_id: turn_id.to_string(),
+            recorded_at_ms,
+            stop_reason: stop_reason.describe(),
+            attempted: inner.attempted.clone(),
+            tool_calls: inner.tool_calls.clone(),
+            changed_paths,
+            open_questions,
+        }
+    }
+
+    fn inner(&self) -> MutexGuard<'_, TurnActivityInner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Renders a recorded post-mortem as starting context for the first turn after resume.
+pub(crate) fn render_post_mortem(post_mortem: &ThreadPostMortemRecord) -> String {
+    let attempted = escape_xml_text(&post_mortem.attempted);
+    let tool_calls = bullet_list(&post_mortem.tool_calls);
+    let changed_paths = bullet_list(&post_mortem.changed_paths);
+    let open_questions = bullet_list(&post_mortem.open_questions);
+    POST_MORTEM_PROMPT_TEMPLATE
+        .render([
+            ("stop_reason", post_mortem.stop_reason.as_str()),
+            ("attempted", attempted.as_str()),
+            ("tool_calls", tool_calls.as_str()),
+            ("changed_paths", changed_paths.as_str()),
+            ("open_questions", open_questions.as_str()),
+        ])
+        .unwrap_or_else(|err| panic!("embedded post_mortem.md template failed to render: {err}"))
+}
+
+fn bullet_list(entries: &[String]) -> String {
+    if entries.is_empty() {
+        return "- none".to_string();
+    }
+    entries
+        .iter()
+        .map(|entry| format!("- {entry}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn escape_xml_text(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+#[path = "report_tests.rs"]
+mod tests;
@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_config::types::ProfilingConfig;
+use codex_core::config::Config;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ToolContributor;
+use codex_features::Feature;
+
+use crate::tools;
+
+/// Contributes a tool that profiles the `[profiling]` command and summarizes where its samples
+/// were spent, saving the collapsed stacks as a session artifact.
+#[derive(Clone, Default)]
+pub(crate) struct ProfilingExtension;
+
+#[derive(Clone, Debug)]
+pub(crate) struct ProfilingExtensionConfig {
+    pub(crate) enabled: bool,
+    pub(crate) profiling: ProfilingConfig,
+    /// Profiles are saved and registered under this directory, next to other session artifacts.
+    pub(crate) codex_home: PathBuf,
+}
+
+impl ProfilingExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::Profiling),
+            profiling: config.profiling.clone(),
+            codex_home: config.codex_home.to_path_buf(),
+        }
+    }
+}
+
+impl ToolContributor for ProfilingExtension {
+    fn tools(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+    ) -> Vec<Arc<dyn codex_extension_api::ToolExecutor<codex_extension_api::ToolCall>>> {
+        let Some(config) = thread_store.get::<ProfilingExtensionConfig>() else {
+            return Vec::new();
+        };
+        // The command and profiler come from config, and each capture still needs the user's
+        // approval, since both run outside the sandbox.
+        if !config.enabled || config.profiling.command.is_none() {
+            return Vec::new();
+        }
+
+        tools::profiling_tools(
+            config.profiling.clone(),
+            config.codex_home.clone(),
+            thread_store.level_id().to_string(),
+        )
+    }
+}
+
+/// Installs the profiling extension contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    registry.thread_config(ProfilingExtensionConfig::from_config);
+    registry.tool_contributor(Arc::new(ProfilingExtension));
+}
@@ -0,0 +1,243 @@
+//! Collapsed stacks: one line per distinct stack, frames from root to leaf joined by `;`, followed
+//! by the number of samples that hit it. This is the input format of flamegraph renderers, so the
+//! same text is summarized for the model and saved for the user.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Leaf-most frames kept when a hot stack is reported to the model.
+const MAX_STACK_FRAMES: usize = 12;
+/// Hot stacks reported to the model, independent of how many frames are listed.
+const MAX_HOT_STACKS: usize = 10;
+
+/// Folds `perf script` output, where each sample is a header line naming the command followed by
+/// one indented line per frame, leaf first.
+pub(crate) fn fold_perf_script(script: &str) -> String {
+    let mut stacks = BTreeMap::new();
+    let mut comm = None;
+    let mut frames = Vec::new();
+    for line in script.lines().chain(std::iter::once("")) {
+        if line.trim().is_empty() {
+            if let Some(comm) = comm.take() {
+                add_stack(&mut stacks, comm, &mut frames, /*count*/ 1);
+            }
+            frames.clear();
+        } else if line.starts_with(char::is_whitespace) {
+            if comm.is_some() {
+                frames.push(perf_frame(line.trim()));
+            }
+        } else if !line.starts_with('#') {
+            comm = Some(perf_comm(line));
+        }
+    }
+    render(&stacks)
+}
+
+/// Folds the output of a DTrace `ustack()` aggregation, where each stack is a block of frames,
+/// leaf first, followed by its sample count.
+pub(crate) fn fold_dtrace(output: &str) -> String {
+    let mut stacks = BTreeMap::new();
+    let mut frames = Vec::new();
+    for line in output.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            frames.clear();
+        } else if let Ok(count) = line.parse::<u64>() {
+            if !frames.is_empty() {
+                add_stack(&mut stacks, String::new(), &mut frames, count);
+            }
+            frames.clear();
+        } else {
+            frames.push(strip_offset(line).to_string());
+        }
+    }
+    render(&stacks)
+}
+
+fn add_stack(
+    stacks: &mut BTreeMap<String, u64>,
+    root: String,
+    frames: &mut Vec<String>,
+    count: u64,
+) {
+    let mut path = Vec::with_capacity(frames.len() + 1);
+    if !root.is_empty() {
+        path.push(root);
+    }
+    path.extend(frames.drain(..).rev());
+    if path.is_empty() {
+        return;
+    }
+    *stacks.entry(path.join(";")).or_default() += count;
+}
+
+fn render(stacks: &BTreeMap<String, u64>) -> String {
+    stacks
+        .iter()
+        .map(|(stack, count)| format!("{stack} {count}\n"))
+        .collect()
+}
+
+/// The command name is everything before the pid, and may itself contain spaces.
+fn perf_comm(header: &str) -> String {
+    let tokens = header.split_whitespace().collect::<Vec<_>>();
+    let pid_index = tokens
+        .iter()
+        .position(|token| {
+            token
+                .split('/')
+                .all(|part| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit()))
+        })
+        .unwrap_or(tokens.len().min(1));
+    tokens[..pid_index].join(" ")
+}
+
+/// A frame line is `<address> <symbol>+<offset> (<object>)`. Unresolved symbols are named after
+/// the object they were sampled in.
+fn perf_frame(line: &str) -> String {
+    let rest = line
+        .split_once(char::is_whitespace)
+        .map_or("", |(_address, rest)| rest.trim());
+    let (symbol, object) = rest
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once(" ("))
+        .unwrap_or((rest, ""));
+    let symbol = strip_offset(symbol.trim());
+    if !symbol.is_empty() && symbol != "[unknown]" {
+        return symbol.to_string();
+    }
+    let object = object.rsplit('/').next().unwrap_or(object);
+    if object.is_empty() {
+        "[unknown]".to_string()
+    } else {
+        format!("[{object}]")
+    }
+}
+
+fn strip_offset(symbol: &str) -> &str {
+    match symbol.rfind("+0x") {
+        Some(index)
+            if symbol[index + 3..]
+                .bytes()
+                .all(|byte| byte.is_ascii_hexdigit()) =>
+        {
+            &symbol[..index]
+        }
+        _ => symbol,
+    }
+}
+
+/// Where the samples of a profile were spent.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub(crate) struct StackSummary {
+    /// Samples in the profile.
+    pub(crate) total_samples: u64,
+    /// Frames that were executing when sampled, by samples. High self time is where the CPU
+    /// actually spent its time.
+    pub(crate) self_time: Vec<FrameShare>,
+    /// Frames that were on the stack when sampled, counting recursive frames once per sample.
+    /// High total time with low self time points at a caller of the expensive work.
+    pub(crate) total_time: Vec<FrameShare>,
+    /// The most sampled stacks, root to leaf, in collapsed form. Long stacks keep only their
+    /// leaf-most frames, after a leading `...`.
+    pub(crate) hot_stacks: Vec<StackShare>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub(crate) struct FrameShare {
+    pub(crate) frame: String,
+    pub(crate) samples: u64,
+    /// Share of all samples, in percent.
+    pub(crate) percent: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub(crate) struct StackShare {
+    pub(crate) stack: String,
+    pub(crate) samples: u64,
+    /// Share of all samples, in percent.
+    pub(crate) percent: f64,
+}
+
+/// Summarizes collapsed stacks, listing at most `top` frames in each ranking. Malformed lines are
+/// skipped.
+pub(crate) fn summarize(folded: &str, top: usize) -> StackSummary {
+    let mut total_samples = 0;
+    let mut self_samples = HashMap::<&str, u64>::new();
+    let mut total_samples_by_frame = HashMap::<&str, u64>::new();
+    let mut stacks = Vec::new();
+    for line in folded.lines() {
+        let Some((stack, count)) = line.rsplit_once(' ') else {
+            continue;
+        };
+        let Ok(count) = count.parse::<u64>() else {
+            continue;
+        };
+        let frames = stack.split(';').collect::<Vec<_>>();
+        let Some(leaf) = frames.last() else {
+            continue;
+        };
+        total_samples += count;
+        *self_samples.entry(*leaf).or_default() += count;
+        let mut seen = HashSet::new();
+        for frame in &frames {
+            if seen.insert(*frame) {
+                *total_samples_by_frame.entry(*frame).or_default() += count;
+            }
+        }
+        stacks.push((frames, count));
+    }
+
+    let percent = |samples: u64| {
+        if total_samples == 0 {
+            0.0
+        } else {
+            (samples as f64 * 1000.0 / total_samples as f64).round() / 10.0
+        }
+    };
+    let ranked = |samples_by_frame: HashMap<&str, u64>| {
+        let mut frames = samples_by_frame.into_iter().collect::<Vec<_>>();
+        frames.sort_by(|(a_frame, a), (b_frame, b)| b.cmp(a).then(a_frame.cmp(b_frame)));
+        frames
+            .into_iter()
+            .take(top)
+            .map(|(frame, samples)| FrameShare {
+                frame: frame.to_string(),
+                samples,
+                percent: percent(samples),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    stacks.sort_by(|(a_frames, a), (b_frames, b)| b.cmp(a).then(a_frames.cmp(b_frames)));
+    let hot_stacks = stacks
+        .into_iter()
+        .take(top.min(MAX_HOT_STACKS))
+        .map(|(frames, samples)| {
+            let stack = if frames.len() > MAX_STACK_FRAMES {
+                format!(
+                    "...;{}",
+                    frames[frames.len() - MAX_STACK_FRAMES..].join(";")
+                )
+            } else {
+                frames.join(";")
+            };
+            StackShare {
+                stack,
+                samples,
+                percent: percent(samples),
+            }
+        })
+        .collect();
+
+    StackSummary {
+        total_samples,
+        self_time: ranked(self_samples),
+        total_time: ranked(total_samples_by_frame),
+        hot_stacks,
+    }
+}
@@ -0,0 +1,16 @@
+mod extension;
+mod folded;
+mod profiler;
+mod tools;
+
+pub use extension::install;
+
+pub(crate) const PROFILING_NAMESPACE: &str = "profiling";
+pub(crate) const CAPTURE_TOOL_NAME: &str = "capture";
+/// Frames listed per ranking when the model does not ask for a number.
+pub(crate) const DEFAULT_TOP_FRAMES: usize = 20;
+/// Most frames per ranking a single tool call may ask for.
+pub(crate) const MAX_TOP_FRAMES: usize = 100;
+
+#[cfg(test)]
+mod tests;
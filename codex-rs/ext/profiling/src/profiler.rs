@@ -0,0 +1,214 @@
+use std::ffi::OsString;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::Output;
+use std::process::Stdio;
+use std::time::Duration;
+
+use codex_config::types::ProfilerKind;
+use codex_extension_api::ToolEnvironment;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_path_uri::PathUri;
+use codex_utils_pty::process_group::KillProcessGroupOnDrop;
+use tokio::process::Command;
+
+use crate::folded;
+
+/// Lines of profiler stderr kept when a capture fails.
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Files the profilers write into the capture's work dir.
+const PERF_DATA_FILE: &str = "perf.data";
+const DTRACE_SCRIPT_FILE: &str = "command.sh";
+const DTRACE_OUTPUT_FILE: &str = "dtrace.out";
+const PY_SPY_OUTPUT_FILE: &str = "py-spy.folded";
+
+/// A single profiled run of a shell command.
+pub(crate) struct CapturePlan {
+    pub(crate) command: String,
+    pub(crate) profiler: ProfilerKind,
+    pub(crate) frequency_hz: u32,
+    pub(crate) timeout: Duration,
+}
+
+/// Stacks recorded by a capture.
+pub(crate) struct Capture {
+    /// Collapsed stacks, one line per distinct stack.
+    pub(crate) folded: String,
+    /// Set when the profiler exited unsuccessfully, which usually means the command failed.
+    pub(crate) warning: Option<String>,
+}
+
+/// The profiler used when neither the config nor the model picks one.
+pub(crate) fn default_profiler() -> ProfilerKind {
+    if cfg!(target_os = "macos") {
+        ProfilerKind::Dtrace
+    } else {
+        ProfilerKind::Perf
+    }
+}
+
+pub(crate) fn profiler_name(profiler: ProfilerKind) -> &'static str {
+    match profiler {
+        ProfilerKind::Perf => "perf",
+        ProfilerKind::Dtrace => "dtrace",
+        ProfilerKind::PySpy => "py-spy",
+    }
+}
+
+/// The full profiler invocation that records `plan.command` into `work_dir`, program first.
+/// This is what the user approves, and exactly what [`capture`] runs.
+pub(crate) fn record_invocation(plan: &CapturePlan, work_dir: &Path) -> Vec<String> {
+    std::iter::once(profiler_name(plan.profiler).to_string())
+        .chain(
+            record_args(plan, work_dir)
+                .into_iter()
+                .map(|arg| arg.to_string_lossy().into_owned()),
+        )
+        .collect()
+}
+
+fn record_args(plan: &CapturePlan, work_dir: &Path) -> Vec<OsString> {
+    let frequency = plan.frequency_hz.to_string();
+    match plan.profiler {
+        ProfilerKind::Perf => vec![
+            "record".into(),
+            "--quiet".into(),
+            "-g".into(),
+            "-F".into(),
+            frequency.into(),
+            "-o".into(),
+            work_dir.join(PERF_DATA_FILE).into(),
+            "--".into(),
+            "sh".into(),
+            "-c".into(),
+            plan.command.clone().into(),
+        ],
+        // `dtrace -c` splits its argument on whitespace, so the command goes through a script.
+        ProfilerKind::Dtrace => vec![
+            "-q".into(),
+            "-x".into(),
+            "ustackframes=100".into(),
+            "-o".into(),
+            work_dir.join(DTRACE_OUTPUT_FILE).into(),
+            "-n".into(),
+            format!(
+                "profile-{frequency} /pid == $target || progenyof($target)/ {{ @[ustack()] = count(); }}"
+            )
+            .into(),
+            "-c".into(),
+            format!("/bin/sh {}", work_dir.join(DTRACE_SCRIPT_FILE).display()).into(),
+        ],
+        ProfilerKind::PySpy => vec![
+            "record".into(),
+            "--format".into(),
+            "raw".into(),
+            "--subprocesses".into(),
+            "--rate".into(),
+            frequency.into(),
+            "--output".into(),
+            work_dir.join(PY_SPY_OUTPUT_FILE).into(),
+            "--".into(),
+            "sh".into(),
+            "-c".into(),
+            plan.command.clone().into(),
+        ],
+    }
+}
+
+/// Runs `plan.command` in `cwd` under the profiler and folds what it recorded. Intermediate files
+/// are written to `work_dir`, and read back through the environment's file system so the sandbox
+/// read policy applies to them.
+pub(crate) async fn capture(
+    cwd: &Path,
+    work_dir: &Path,
+    plan: &CapturePlan,
+    environment: &ToolEnvironment,
+) -> Result<Capture, String> {
+    if plan.profiler == ProfilerKind::Dtrace {
+        let script = work_dir.join(DTRACE_SCRIPT_FILE);
+        std::fs::write(&script, &plan.command)
+            .map_err(|err| format!("failed to write {}: {err}", script.display()))?;
+    }
+    let mut command = Command::new(profiler_name(plan.profiler));
+    command
+        .args(record_args(plan, work_dir))
+        .stdout(Stdio::null());
+    let record = run(command, cwd, plan).await?;
+    let folded = match plan.profiler {
+        ProfilerKind::Perf => {
+            let mut script = Command::new("perf");
+            script
+                .args(["script", "-i"])
+                .arg(work_dir.join(PERF_DATA_FILE))
+                .stdout(Stdio::piped());
+            let script = run(script, cwd, plan).await?;
+            folded::fold_perf_script(&String::from_utf8_lossy(&script.stdout))
+        }
+        ProfilerKind::Dtrace => folded::fold_dtrace(
+            &read_output(&work_dir.join(DTRACE_OUTPUT_FILE), environment).await?,
+        ),
+        ProfilerKind::PySpy => read_output(&work_dir.join(PY_SPY_OUTPUT_FILE), environment).await?,
+    };
+
+    let warning = (!record.status.success()).then(|| {
+        format!(
+            "{} exited with {}; the command may have failed:\n{}",
+            profiler_name(plan.profiler),
+            record.status,
+            stderr_tail(&record)
+        )
+    });
+    if folded.trim().is_empty() {
+        return Err(match warning {
+            Some(warning) => warning,
+            None => "the profiler recorded no samples; the command may have finished too quickly"
+                .to_string(),
+        });
+    }
+    Ok(Capture { folded, warning })
+}
+
+async fn run(mut command: Command, cwd: &Path, plan: &CapturePlan) -> Result<Output, String> {
+    let program = profiler_name(plan.profiler);
+    command
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    // The profiler gets its own process group so that a timeout also stops the profiled command
+    // and everything it started.
+    #[cfg(unix)]
+    command.process_group(0);
+    let child = command.spawn().map_err(|err| match err.kind() {
+        ErrorKind::NotFound => format!("`{program}` is not installed or not on PATH"),
+        _ => format!("failed to start `{program}`: {err}"),
+    })?;
+    let _process_group = child.id().map(KillProcessGroupOnDrop::new);
+    tokio::time::timeout(plan.timeout, child.wait_with_output())
+        .await
+        .map_err(|_| format!("profiling timed out after {}s", plan.timeout.as_secs()))?
+        .map_err(|err| format!("`{program}` failed: {err}"))
+}
+
+async fn read_output(path: &Path, environment: &ToolEnvironment) -> Result<String, String> {
+    let path_uri = AbsolutePathBuf::from_absolute_path(path)
+        .map(|path| PathUri::from_abs_path(&path))
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    match environment
+        .file_system
+        .read_file_text(&path_uri, Some(&environment.file_system_sandbox_context))
+        .await
+    {
+        Ok(contents) => Ok(contents),
+        // The profiler writes nothing when it fails before sampling; the caller reports why.
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(format!("failed to read {}: {err}", path.display())),
+    }
+}
+
+fn stderr_tail(output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines = stderr.lines().collect::<Vec<_>>();
+    lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n")
+}
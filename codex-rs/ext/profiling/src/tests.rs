@@ -0,0 +1,358 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use codex_config::types::ProfilerKind;
+use codex_config::types::ProfilingConfig;
+use codex_extension_api::CommandApprovalFuture;
+use codex_extension_api::CommandApprovalRequest;
+use codex_extension_api::CommandApprover;
+use codex_extension_api::DenyCommandApprover;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::NoopTurnItemEmitter;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolContributor;
+use codex_extension_api::ToolPayload;
+use codex_extension_api::new_local_environment;
+use codex_protocol::models::PermissionProfile;
+use codex_utils_output_truncation::TruncationPolicy;
+use codex_utils_session_artifacts::load_artifacts;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::extension::ProfilingExtension;
+use crate::extension::ProfilingExtensionConfig;
+use crate::folded::FrameShare;
+use crate::folded::StackShare;
+use crate::folded::fold_dtrace;
+use crate::folded::fold_perf_script;
+use crate::folded::summarize;
+use crate::profiler::CapturePlan;
+use crate::tools::profiling_tool_name;
+use crate::tools::save_profile;
+
+fn thread_store(enabled: bool, command: Option<&str>) -> ExtensionData {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(ProfilingExtensionConfig {
+        enabled,
+        profiling: ProfilingConfig {
+            command: command.map(str::to_string),
+            ..ProfilingConfig::default()
+        },
+        codex_home: std::env::temp_dir(),
+    });
+    thread_store
+}
+
+/// Records every approval request and declines it.
+#[derive(Default)]
+struct RecordApprovals {
+    commands: Mutex<Vec<Vec<String>>>,
+}
+
+impl CommandApprover for RecordApprovals {
+    fn request_approval<'a>(
+        &'a self,
+        request: CommandApprovalRequest,
+    ) -> CommandApprovalFuture<'a> {
+        self.commands
+            .lock()
+            .expect("approvals lock")
+            .push(request.command);
+        Box::pin(std::future::ready(false))
+    }
+}
+
+fn capture_call(arguments: serde_json::Value) -> ToolCall {
+    ToolCall {
+        turn_id: "turn-1".to_string(),
+        call_id: "call-1".to_string(),
+        tool_name: profiling_tool_name(crate::CAPTURE_TOOL_NAME),
+        model: "gpt-test".to_string(),
+        codex_turn_metadata: None,
+        truncation_policy: TruncationPolicy::Bytes(1024),
+        conversation_history: codex_extension_api::ConversationHistory::default(),
+        turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+        command_approver: Arc::new(DenyCommandApprover),
+        environments: Vec::new(),
+        payload: ToolPayload::Function {
+            arguments: arguments.to_string(),
+        },
+    }
+}
+
+fn frame(frame: &str, samples: u64, percent: f64) -> FrameShare {
+    FrameShare {
+        frame: frame.to_string(),
+        samples,
+        percent,
+    }
+}
+
+#[test]
+fn perf_script_samples_fold_root_first() {
+    let script = "\
+# ========
+# captured on: today
+my server  4242/4243 [001] 10.000001:   10101010 cpu-clock:u:
+\t    55d4a1 parse_value+0x1f (/work/target/release/server)
+\t    55d4b2 parse_document+0x80 (/work/target/release/server)
+\t    55d4c3 main+0x12 (/work/target/release/server)
+
+my server  4242 [001] 10.010001:   10101010 cpu-clock:u:
+\t    7f0011 [unknown] (/usr/lib/libc.so.6)
+\t    55d4c3 main+0x12 (/work/target/release/server)
+
+my server  4242 [001] 10.020001:   10101010 cpu-clock:u:
+\t    55d4a1 parse_value+0x2a (/work/target/release/server)
+\t    55d4b2 parse_document+0x80 (/work/target/release/server)
+\t    55d4c3 main+0x12 (/work/target/release/server)
+";
+
+    assert_eq!(
+        fold_perf_script(script),
+        "\
+my server;main;[libc.so.6] 1
+my server;main;parse_document;parse_value 2
+"
+    );
+}
+
+#[test]
+fn dtrace_aggregation_folds_with_counts() {
+    let output = "
+
+              libsystem_kernel.dylib`write+0x8
+              server`flush+0x40
+              server`main+0x1c
+               3
+
+              server`parse_value+0x10
+              server`main+0x1c
+               17
+";
+
+    assert_eq!(
+        fold_dtrace(output),
+        "\
+server`main;server`flush;libsystem_kernel.dylib`write 3
+server`main;server`parse_value 17
+"
+    );
+}
+
+#[test]
+fn summary_ranks_self_and_total_time() {
+    let folded = "\
+main;parse;lex 6
+main;parse 2
+main;eval;eval;eval 2
+not a stack
+";
+
+    let summary = summarize(folded, /*top*/ 3);
+
+    assert_eq!(summary.total_samples, 10);
+    assert_eq!(
+        summary.self_time,
+        vec![
+            frame("lex", 6, 60.0),
+            frame("eval", 2, 20.0),
+            frame("parse", 2, 20.0),
+        ]
+    );
+    // Recursive frames count once per sample.
+    assert_eq!(
+        summary.total_time,
+        vec![
+            frame("main", 10, 100.0),
+            frame("parse", 8, 80.0),
+            frame("lex", 6, 60.0),
+        ]
+    );
+    assert_eq!(
+        summary.hot_stacks[0],
+        StackShare {
+            stack: "main;parse;lex".to_string(),
+            samples: 6,
+            percent: 60.0,
+        }
+    );
+}
+
+#[test]
+fn deep_hot_stacks_keep_their_leaf_frames() {
+    let frames = (0..20).map(|depth| format!("f{depth}")).collect::<Vec<_>>();
+    let folded = format!("{} 1\n", frames.join(";"));
+
+    let summary = summarize(&folded, /*top*/ 5);
+
+    assert_eq!(
+        summary.hot_stacks[0].stack,
+        format!("...;{}", frames[8..].join(";"))
+    );
+}
+
+#[test]
+fn saved_profiles_are_numbered_session_artifacts() {
+    let codex_home = tempfile::tempdir().unwrap();
+    let plan = CapturePlan {
+        command: "make profile".to_string(),
+        profiler: ProfilerKind::PySpy,
+        frequency_hz: 99,
+        timeout: Duration::from_secs(10),
+    };
+
+    let first = save_profile(codex_home.path(), "thread-1", &plan, "main 1\n").unwrap();
+    let second = save_profile(codex_home.path(), "thread-1", &plan, "main 2\n").unwrap();
+
+    assert_eq!(first.0, "profile-1");
+    assert_eq!(second.0, "profile-2");
+    assert_eq!(std::fs::read_to_string(&second.1).unwrap(), "main 2\n");
+    let artifacts = load_artifacts(codex_home.path(), "thread-1").unwrap();
+    assert_eq!(
+        artifacts
+            .iter()
+            .map(|artifact| (artifact.name.as_str(), artifact.path.clone()))
+            .collect::<Vec<_>>(),
+        vec![("profile-1", first.1), ("profile-2", second.1)]
+    );
+    assert_eq!(
+        artifacts[0].description.as_deref(),
+        Some(
+            "Collapsed stacks from py-spy for `make profile`; open in speedscope or render with flamegraph.pl"
+        )
+    );
+}
+
+#[test]
+fn install_registers_the_capture_tool() {
+    let mut builder = ExtensionRegistryBuilder::<codex_core::config::Config>::new();
+    crate::install(&mut builder);
+    let registry = builder.build();
+    let thread_store = thread_store(/*enabled*/ true, Some("make profile"));
+
+    let tool_names = registry
+        .tool_contributors()
+        .iter()
+        .flat_map(|contributor| contributor.tools(&ExtensionData::new("session"), &thread_store))
+        .map(|tool| tool.tool_name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        tool_names,
+        vec![profiling_tool_name(crate::CAPTURE_TOOL_NAME)]
+    );
+}
+
+#[test]
+fn tools_need_the_feature_and_a_configured_command() {
+    for thread_store in [
+        thread_store(/*enabled*/ false, Some("make profile")),
+        thread_store(/*enabled*/ true, /*command*/ None),
+    ] {
+        assert!(
+            ProfilingExtension
+                .tools(&ExtensionData::new("session"), &thread_store)
+                .is_empty()
+        );
+    }
+}
+
+#[tokio::test]
+async fn capture_requires_a_local_environment() {
+    let tools = ProfilingExtension.tools(
+        &ExtensionData::new("session"),
+        &thread_store(/*enabled*/ true, Some("make profile")),
+    );
+
+    let Err(err) = tools[0].handle(capture_call(json!({}))).await else {
+        panic!("capture without a local environment should fail");
+    };
+
+    assert_eq!(
+        err,
+        FunctionCallError::RespondToModel(
+            "profiles can only be captured in local workspaces".to_string()
+        )
+    );
+}
+
+#[tokio::test]
+async fn capture_rejects_unknown_arguments() {
+    let tools = ProfilingExtension.tools(
+        &ExtensionData::new("session"),
+        &thread_store(/*enabled*/ true, Some("make profile")),
+    );
+
+    let Err(FunctionCallError::RespondToModel(message)) = tools[0]
+        .handle(capture_call(json!({ "command": "rm -rf /" })))
+        .await
+    else {
+        panic!("capture should not accept a command from the model");
+    };
+
+    assert!(message.contains("unknown field `command`"), "{message}");
+}
+
+#[tokio::test]
+async fn capture_rejects_a_profiler_chosen_by_the_model() {
+    let tools = ProfilingExtension.tools(
+        &ExtensionData::new("session"),
+        &thread_store(/*enabled*/ true, Some("make profile")),
+    );
+
+    let Err(FunctionCallError::RespondToModel(message)) = tools[0]
+        .handle(capture_call(json!({ "profiler": "py-spy" })))
+        .await
+    else {
+        panic!("capture should not accept a profiler from the model");
+    };
+
+    assert!(message.contains("unknown field `profiler`"), "{message}");
+}
+
+#[tokio::test]
+async fn capture_needs_the_users_approval_of_the_whole_profiler_invocation() {
+    let dir = tempfile::tempdir().unwrap();
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(ProfilingExtensionConfig {
+        enabled: true,
+        profiling: ProfilingConfig {
+            command: Some("make profile".to_string()),
+            profiler: Some(ProfilerKind::Perf),
+            ..ProfilingConfig::default()
+        },
+        codex_home: std::env::temp_dir(),
+    });
+    let tools = ProfilingExtension.tools(&ExtensionData::new("session"), &thread_store);
+    let approvals = Arc::new(RecordApprovals::default());
+    let mut call = capture_call(json!({}));
+    call.command_approver = approvals.clone();
+    call.environments = vec![
+        new_local_environment(dir.path(), PermissionProfile::workspace_write())
+            .expect("local environment"),
+    ];
+
+    let Err(err) = tools[0].handle(call).await else {
+        panic!("capture without approval should fail");
+    };
+
+    assert_eq!(
+        err,
+        FunctionCallError::RespondToModel(
+            "the user did not approve running the profiler".to_string()
+        )
+    );
+    let commands = approvals.commands.lock().expect("approvals lock");
+    let [command] = commands.as_slice() else {
+        panic!("expected one approval request, got {commands:?}");
+    };
+    assert_eq!(command[..5], ["perf", "record", "--quiet", "-g", "-F"]);
+    assert_eq!(
+        command[command.len() - 4..],
+        ["--", "sh", "-c", "make profile"]
+    );
+}
@@ -0,0 +1,217 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_config::types::ProfilerKind;
+use codex_config::types::ProfilingConfig;
+use codex_extension_api::CommandApprovalRequest;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::JsonToolOutput;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolExecutorFuture;
+use codex_extension_api::ToolName;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolSpec;
+use codex_extension_api::local_environment;
+use codex_extension_api::namespaced_function_tool;
+use codex_extension_api::parse_function_arguments;
+use codex_utils_session_artifacts::SessionArtifact;
+use codex_utils_session_artifacts::artifact_dir;
+use codex_utils_session_artifacts::load_artifacts;
+use codex_utils_session_artifacts::register_artifact;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::CAPTURE_TOOL_NAME;
+use crate::DEFAULT_TOP_FRAMES;
+use crate::MAX_TOP_FRAMES;
+use crate::PROFILING_NAMESPACE;
+use crate::folded::StackSummary;
+use crate::folded::summarize;
+use crate::profiler;
+use crate::profiler::CapturePlan;
+
+/// The profiler runs on this host, so the tool only works against the local checkout.
+const LOCAL_WORKSPACE_REQUIRED: &str = "profiles can only be captured in local workspaces";
+
+pub(crate) fn profiling_tools(
+    config: ProfilingConfig,
+    codex_home: PathBuf,
+    thread_id: String,
+) -> Vec<Arc<dyn ToolExecutor<ToolCall>>> {
+    vec![Arc::new(CaptureTool {
+        config,
+        codex_home,
+        thread_id,
+    })]
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct CaptureArgs {
+    /// Frames listed in each ranking, from 1 to 100. Defaults to 20.
+    top: Option<usize>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct CaptureOutput {
+    /// Command that was profiled.
+    pub(crate) command: String,
+    /// Profiler that recorded the samples.
+    pub(crate) profiler: ProfilerKind,
+    /// Stack samples taken per second.
+    pub(crate) frequency_hz: u32,
+    #[serde(flatten)]
+    pub(crate) summary: StackSummary,
+    /// Name of the session artifact holding every collapsed stack, which the user can render as a
+    /// flamegraph.
+    pub(crate) artifact: String,
+    /// Path of the collapsed stacks file.
+    pub(crate) artifact_path: PathBuf,
+    /// Set when the profiler exited unsuccessfully, so the profile may cover only part of the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) warning: Option<String>,
+}
+
+struct CaptureTool {
+    config: ProfilingConfig,
+    codex_home: PathBuf,
+    thread_id: String,
+}
+
+impl ToolExecutor<ToolCall> for CaptureTool {
+    fn tool_name(&self) -> ToolName {
+        profiling_tool_name(CAPTURE_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        let command = self.config.command.as_deref().unwrap_or_default();
+        namespaced_function_tool::<CaptureArgs, CaptureOutput>(
+            PROFILING_NAMESPACE,
+            CAPTURE_TOOL_NAME,
+            &format!(
+                "Run the project's profiling command `{command}` under a sampling profiler and report the frames with the most self time, the frames with the most total time, and the hottest stacks. Use it to find where time goes before optimizing. The user approves each capture before it runs. The full profile is saved as a session artifact the user can open as a flamegraph."
+            ),
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: CaptureArgs = parse_function_arguments(&call)?;
+            let environment = local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?;
+            let Some(command) = self.config.command.clone() else {
+                return Err(FunctionCallError::RespondToModel(
+                    "no profiling command is configured".to_string(),
+                ));
+            };
+            let top = args.top.unwrap_or(DEFAULT_TOP_FRAMES);
+            if !(1..=MAX_TOP_FRAMES).contains(&top) {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "top must be between 1 and {MAX_TOP_FRAMES}"
+                )));
+            }
+            let plan = CapturePlan {
+                command,
+                profiler: self
+                    .config
+                    .profiler
+                    .unwrap_or_else(profiler::default_profiler),
+                frequency_hz: self.config.frequency_hz,
+                timeout: Duration::from_secs(self.config.timeout_seconds),
+            };
+
+            let work_dir = tempfile::tempdir().map_err(|err| {
+                FunctionCallError::RespondToModel(format!(
+                    "failed to create a temporary dir: {err}"
+                ))
+            })?;
+
+            // The profiler and the command run on the host outside the sandbox, so the user
+            // approves the whole profiler invocation before every capture.
+            let approved = call
+                .command_approver
+                .request_approval(CommandApprovalRequest {
+                    command: profiler::record_invocation(&plan, work_dir.path()),
+                    cwd: environment.cwd.clone(),
+                    reason: format!(
+                        "Profile `{}` with {}",
+                        plan.command,
+                        profiler::profiler_name(plan.profiler)
+                    ),
+                })
+                .await;
+            if !approved {
+                return Err(FunctionCallError::RespondToModel(
+                    "the user did not approve running the profiler".to_string(),
+                ));
+            }
+
+            let capture = profiler::capture(
+                environment.cwd.as_path(),
+                work_dir.path(),
+                &plan,
+                environment,
+            )
+            .await
+            .map_err(FunctionCallError::RespondToModel)?;
+            let (artifact, artifact_path) =
+                save_profile(&self.codex_home, &self.thread_id, &plan, &capture.folded)
+                    .map_err(FunctionCallError::RespondToModel)?;
+            let output = CaptureOutput {
+                summary: summarize(&capture.folded, top),
+                command: plan.command,
+                profiler: plan.profiler,
+                frequency_hz: plan.frequency_hz,
+                artifact,
+                artifact_path,
+                warning: capture.warning,
+            };
+            let output: Box<dyn ToolOutput> = Box::new(JsonToolOutput::new(json!(output)));
+            Ok(output)
+        })
+    }
+}
+
+/// Writes the collapsed stacks next to the session's other artifacts and registers them as
+/// `profile-<n>`, numbered after the profiles already registered. Returns the name and path.
+pub(crate) fn save_profile(
+    codex_home: &Path,
+    thread_id: &str,
+    plan: &CapturePlan,
+    folded: &str,
+) -> Result<(String, PathBuf), String> {
+    let existing = load_artifacts(codex_home, thread_id)
+        .map_err(|err| format!("failed to read the session's artifacts: {err}"))?;
+    let number = existing
+        .iter()
+        .filter(|artifact| artifact.name.starts_with("profile-"))
+        .count()
+        + 1;
+    let name = format!("profile-{number}");
+    let dir = artifact_dir(codex_home, thread_id);
+    let path = dir.join(format!("{name}.folded"));
+    std::fs::create_dir_all(&dir)
+        .and_then(|()| std::fs::write(&path, folded))
+        .map_err(|err| format!("failed to save the profile to {}: {err}", path.display()))?;
+
+    let artifact = SessionArtifact {
+        name: name.clone(),
+        path: path.clone(),
+        description: Some(format!(
+            "Collapsed stacks from {} for `{}`; open in speedscope or render with flamegraph.pl",
+            profiler::profiler_name(plan.profiler),
+            plan.command
+        )),
+    };
+    register_artifact(codex_home, thread_id, artifact)
+        .map_err(|err| format!("failed to record the profile as an artifact: {err}"))?;
+    Ok((name, path))
+}
+
+pub(crate) fn profiling_tool_name(name: &str) -> ToolName {
+    ToolName::namespaced(PROFILING_NAMESPACE, name)
+}
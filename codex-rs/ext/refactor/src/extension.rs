@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use codex_config::types::RefactorConfig;
+use codex_core::config::Config;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ToolContributor;
+use codex_features::Feature;
+
+use crate::tools;
+
+/// Contributes rename and move tools that return patches instead of running regex edits.
+#[derive(Clone, Default)]
+pub(crate) struct RefactorExtension;
+
+#[derive(Clone, Debug)]
+pub(crate) struct RefactorExtensionConfig {
+    pub(crate) enabled: bool,
+    pub(crate) refactor: RefactorConfig,
+}
+
+impl RefactorExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::RefactorTools),
+            refactor: config.refactor.clone(),
+        }
+    }
+}
+
+impl ToolContributor for RefactorExtension {
+    fn tools(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+    ) -> Vec<Arc<dyn codex_extension_api::ToolExecutor<codex_extension_api::ToolCall>>> {
+        let Some(config) = thread_store.get::<RefactorExtensionConfig>() else {
+            return Vec::new();
+        };
+        if !config.enabled {
+            return Vec::new();
+        }
+
+        tools::refactor_tools(config.refactor.clone())
+    }
+}
+
+/// Installs the refactor extension contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    registry.thread_config(RefactorExtensionConfig::from_config);
+    registry.tool_contributor(Arc::new(RefactorExtension));
+}
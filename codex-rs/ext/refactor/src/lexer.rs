@@ -0,0 +1,179 @@
+//! Finds identifiers in source text while tracking whether each one sits in code or inside a
+//! string or comment.
+//!
+//! It knows each language's comment and string delimiters, which is enough to keep renames from
+//! touching prose, log messages, or unrelated text that happens to contain the same word. It
+//! covers every supported language; [`crate::syntax_tree`] adds scopes for those with a grammar.
+
+use std::path::Path;
+
+/// Comment and string delimiters for one family of languages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Syntax {
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    /// String delimiters, longest first, paired with whether the string may span lines.
+    strings: &'static [(&'static str, bool)],
+}
+
+const C_LIKE: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &[("\"", false), ("'", false)],
+};
+
+/// Rust lifetimes (`'a`) make `'` ambiguous, so only double-quoted strings are tracked.
+const RUST: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &[("\"", true)],
+};
+
+const JAVASCRIPT: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &[("`", true), ("\"", false), ("'", false)],
+};
+
+const GO: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &[("`", true), ("\"", false), ("'", false)],
+};
+
+const PYTHON: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comment: None,
+    strings: &[("\"\"\"", true), ("'''", true), ("\"", false), ("'", false)],
+};
+
+const HASH_COMMENTS: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comment: None,
+    strings: &[("\"", false), ("'", false)],
+};
+
+/// Returns the syntax for `path` based on its extension, or `None` for unsupported files.
+pub(crate) fn syntax_for_path(path: &Path) -> Option<Syntax> {
+    let extension = path.extension()?.to_str()?;
+    match extension {
+        "rs" => Some(RUST),
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => Some(JAVASCRIPT),
+        "go" => Some(GO),
+        "py" | "pyi" => Some(PYTHON),
+        "rb" | "sh" | "bash" | "zsh" => Some(HASH_COMMENTS),
+        "c" | "h" | "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" | "cs" | "java" | "kt" | "kts"
+        | "scala" | "swift" | "dart" | "php" => Some(C_LIKE),
+        _ => None,
+    }
+}
+
+/// One identifier matching the requested name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Occurrence {
+    /// Byte offset of the identifier in the text.
+    pub(crate) start: usize,
+    /// False when the identifier is inside a string or comment.
+    pub(crate) in_code: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Code,
+    LineComment,
+    BlockComment(&'static str),
+    String {
+        close: &'static str,
+        multiline: bool,
+    },
+}
+
+/// Returns every whole-identifier occurrence of `name` in `text`.
+pub(crate) fn identifier_occurrences(text: &str, syntax: Syntax, name: &str) -> Vec<Occurrence> {
+    let mut occurrences = Vec::new();
+    let mut state = State::Code;
+    let mut index = 0;
+    while let Some(ch) = text[index..].chars().next() {
+        let rest = &text[index..];
+        if ch.is_alphanumeric() || ch == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            // Runs that start with a digit are number literals such as `0x1f`, not identifiers.
+            if !ch.is_ascii_digit() && &rest[..len] == name {
+                occurrences.push(Occurrence {
+                    start: index,
+                    in_code: state == State::Code,
+                });
+            }
+            index += len;
+            continue;
+        }
+
+        let mut advance = ch.len_utf8();
+        match state {
+            State::Code => {
+                if let Some(open) = syntax
+                    .line_comments
+                    .iter()
+                    .find(|open| rest.starts_with(**open))
+                {
+                    state = State::LineComment;
+                    advance = open.len();
+                } else if let Some((open, close)) = syntax.block_comment
+                    && rest.starts_with(open)
+                {
+                    state = State::BlockComment(close);
+                    advance = open.len();
+                } else if let Some((delimiter, multiline)) = syntax
+                    .strings
+                    .iter()
+                    .find(|(delimiter, _)| rest.starts_with(*delimiter))
+                {
+                    state = State::String {
+                        close: delimiter,
+                        multiline: *multiline,
+                    };
+                    advance = delimiter.len();
+                }
+            }
+            State::LineComment => {
+                if ch == '\n' {
+                    state = State::Code;
+                }
+            }
+            State::BlockComment(close) => {
+                if rest.starts_with(close) {
+                    state = State::Code;
+                    advance = close.len();
+                }
+            }
+            State::String { close, multiline } => {
+                if ch == '\\' {
+                    // Skip the escaped character so `\"` does not end the string.
+                    advance += rest[1..].chars().next().map_or(0, char::len_utf8);
+                } else if rest.starts_with(close) {
+                    state = State::Code;
+                    advance = close.len();
+                } else if ch == '\n' && !multiline {
+                    state = State::Code;
+                }
+            }
+        }
+        index += advance;
+    }
+    occurrences
+}
+
+/// Returns true when `name` is a plain identifier that the lexer can match.
+pub(crate) fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_alphanumeric() || ch == '_')
+}
+
+#[cfg(test)]
+#[path = "lexer_tests.rs"]
+mod tests;
@@ -0,0 +1,59 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn matches(path: &str, text: &str, name: &str) -> Vec<(usize, bool)> {
+    let syntax = syntax_for_path(Path::new(path)).expect("supported syntax");
+    identifier_occurrences(text, syntax, name)
+        .into_iter()
+        .map(|occurrence| (occurrence.start, occurrence.in_code))
+        .collect()
+}
+
+#[test]
+fn code_identifiers_are_distinguished_from_strings_and_comments() {
+    let text = "let count = 1; // count\nprintln!(\"count: {}\", count);\n";
+
+    assert_eq!(
+        matches("main.rs", text, "count"),
+        vec![(4, true), (18, false), (34, false), (46, true)]
+    );
+}
+
+#[test]
+fn only_whole_identifiers_match() {
+    assert_eq!(
+        matches(
+            "app.ts",
+            "const total = subtotal + total_tax + total;",
+            "total"
+        ),
+        vec![(6, true), (37, true)]
+    );
+}
+
+#[test]
+fn python_triple_quoted_strings_span_lines() {
+    let text = "def load():\n    \"\"\"Call load\n    first.\"\"\"\n    return load\n";
+
+    assert_eq!(
+        matches("mod.py", text, "load"),
+        vec![(4, true), (24, false), (54, true)]
+    );
+}
+
+#[test]
+fn escaped_quotes_do_not_end_strings() {
+    assert_eq!(
+        matches("app.js", r#"log("say \"name\""); name();"#, "name"),
+        vec![(11, false), (21, true)]
+    );
+}
+
+#[test]
+fn identifiers_are_validated() {
+    assert!(is_identifier("snake_case"));
+    assert!(is_identifier("_Private2"));
+    assert!(!is_identifier("2fast"));
+    assert!(!is_identifier("a-b"));
+    assert!(!is_identifier(""));
+}
@@ -0,0 +1,21 @@
+mod extension;
+mod lexer;
+mod lsp;
+mod patch;
+mod plan;
+mod scope;
+mod syntax_tree;
+mod tools;
+
+pub use extension::install;
+
+/// Files larger than this are skipped when planning a refactor.
+pub(crate) const MAX_FILE_BYTES: u64 = 1_000_000;
+pub(crate) const MAX_PATCH_BYTES: usize = 400_000;
+
+pub(crate) const REFACTOR_NAMESPACE: &str = "refactor";
+pub(crate) const RENAME_SYMBOL_TOOL_NAME: &str = "rename_symbol";
+pub(crate) const MOVE_FILE_TOOL_NAME: &str = "move_file";
+
+#[cfg(test)]
+mod tests;
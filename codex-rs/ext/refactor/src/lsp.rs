@@ -0,0 +1,405 @@
+//! Asks a language server configured in `[refactor.language_servers]` to plan a rename.
+//!
+//! A server resolves references that the syntax tree cannot, such as methods called through a
+//! trait or an interface. It runs outside the sandbox, so each rename asks the user first, and
+//! the plan reads the files it touches back through the environment before building the patch.
+
+use std::time::Duration;
+
+use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_path_uri::PathUri;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::process::Child;
+use tokio::process::ChildStdin;
+use tokio::process::ChildStdout;
+use tokio::process::Command;
+
+/// How long a server gets to start, index the project, and answer the rename.
+const LANGUAGE_SERVER_TIMEOUT: Duration = Duration::from_secs(120);
+/// Pause before asking again while the server is still indexing.
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+/// `ContentModified` and `RequestCancelled`, which servers return until indexing settles.
+const RETRYABLE_ERRORS: &[i64] = &[-32801, -32800];
+
+/// The definition a rename starts from.
+pub(crate) struct RenameTarget<'a> {
+    /// Path of the defining file, relative to the working directory.
+    pub(crate) path: &'a str,
+    pub(crate) text: &'a str,
+    /// Byte offset of the definition's name in `text`.
+    pub(crate) offset: usize,
+    pub(crate) new_name: &'a str,
+}
+
+/// One replacement from the server's workspace edit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TextEdit {
+    pub(crate) start: Position,
+    pub(crate) end: Position,
+    pub(crate) new_text: String,
+}
+
+/// A zero-based line and UTF-16 column, as the protocol counts them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+pub(crate) struct Position {
+    pub(crate) line: usize,
+    pub(crate) character: usize,
+}
+
+/// Starts `command` in `cwd` and asks it to rename the symbol at `target`. Returns the edits for
+/// each file, by path relative to `cwd`.
+pub(crate) async fn rename(
+    command: &[String],
+    cwd: &AbsolutePathBuf,
+    target: &RenameTarget<'_>,
+) -> Result<Vec<(String, Vec<TextEdit>)>, String> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| "the language server command is empty".to_string())?;
+    let mut child = Command::new(program)
+        .args(args)
+        .current_dir(cwd.as_path())
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| format!("failed to start `{program}`: {err}"))?;
+    let mut connection = Connection::new(&mut child)?;
+    let edit = tokio::time::timeout(LANGUAGE_SERVER_TIMEOUT, connection.rename(cwd, target))
+        .await
+        .map_err(|_| {
+            format!(
+                "`{program}` did not answer within {} seconds",
+                LANGUAGE_SERVER_TIMEOUT.as_secs()
+            )
+        })??;
+    connection.shut_down().await;
+    workspace_edits(&edit, cwd)
+}
+
+struct Connection {
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: i64,
+}
+
+impl Connection {
+    fn new(child: &mut Child) -> Result<Self, String> {
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err("the language server has no stdio pipes".to_string());
+        };
+        Ok(Self {
+            stdin,
+            stdout: BufReader::new(stdout),
+            next_id: 1,
+        })
+    }
+
+    async fn rename(
+        &mut self,
+        cwd: &AbsolutePathBuf,
+        target: &RenameTarget<'_>,
+    ) -> Result<Value, String> {
+        let root = PathUri::from_abs_path(cwd).to_string();
+        self.request(
+            "initialize",
+            json!({
+                "processId": std::process::id(),
+                "rootUri": root,
+                "workspaceFolders": [{ "uri": root, "name": "workspace" }],
+                "capabilities": {
+                    "workspace": { "workspaceEdit": { "documentChanges": true } },
+                    "textDocument": { "rename": { "prepareSupport": false } },
+                },
+            }),
+        )
+        .await?;
+        self.notify("initialized", json!({})).await?;
+        let uri = PathUri::from_abs_path(&cwd.join(target.path)).to_string();
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": {
+                    "uri": uri,
+                    "languageId": language_id(target.path),
+                    "version": 1,
+                    "text": target.text,
+                },
+            }),
+        )
+        .await?;
+        let edit = self
+            .request(
+                "textDocument/rename",
+                json!({
+                    "textDocument": { "uri": uri },
+                    "position": position_of(target.text, target.offset),
+                    "newName": target.new_name,
+                }),
+            )
+            .await?;
+        if edit.is_null() {
+            return Err("the language server found nothing to rename".to_string());
+        }
+        Ok(edit)
+    }
+
+    /// Best effort: the server is killed when the child is dropped anyway.
+    async fn shut_down(&mut self) {
+        let _ = tokio::time::timeout(RETRY_DELAY, self.request("shutdown", Value::Null)).await;
+        let _ = self.notify("exit", Value::Null).await;
+    }
+
+    /// Sends a request and waits for its result, answering the server's own requests meanwhile
+    /// and retrying while the server reports that it is still indexing.
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        loop {
+            let id = self.next_id;
+            self.next_id += 1;
+            self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+                .await?;
+            let response = loop {
+                let message = self.receive().await?;
+                if let Some(server_method) = message.get("method").and_then(Value::as_str) {
+                    if let Some(server_id) = message.get("id") {
+                        let result = server_request_result(server_method, &message["params"]);
+                        self.send(&json!({ "jsonrpc": "2.0", "id": server_id, "result": result }))
+                            .await?;
+                    }
+                    continue;
+                }
+                if message.get("id").and_then(Value::as_i64) == Some(id) {
+                    break message;
+                }
+            };
+            let Some(error) = response.get("error") else {
+                return Ok(response.get("result").cloned().unwrap_or(Value::Null));
+            };
+            let code = error
+                .get("code")
+                .and_then(Value::as_i64)
+                .unwrap_or_default();
+            if RETRYABLE_ERRORS.contains(&code) {
+                tokio::time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+            let message = error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(format!("{method} failed: {message}"));
+        }
+    }
+
+    async fn notify(&mut self, method: &str, params: Value) -> Result<(), String> {
+        self.send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+            .await
+    }
+
+    async fn send(&mut self, message: &Value) -> Result<(), String> {
+        let body = message.to_string();
+        let framed = format!("Content-Length: {}\r\n\r\n{body}", body.len());
+        self.stdin
+            .write_all(framed.as_bytes())
+            .await
+            .map_err(|err| format!("failed to write to the language server: {err}"))?;
+        self.stdin
+            .flush()
+            .await
+            .map_err(|err| format!("failed to write to the language server: {err}"))
+    }
+
+    async fn receive(&mut self) -> Result<Value, String> {
+        let mut length = None;
+        loop {
+            let mut header = String::new();
+            let read = self
+                .stdout
+                .read_line(&mut header)
+                .await
+                .map_err(|err| format!("failed to read from the language server: {err}"))?;
+            if read == 0 {
+                return Err("the language server exited".to_string());
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((key, value)) = header.split_once(':')
+                && key.eq_ignore_ascii_case("content-length")
+            {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+        let length = length
+            .ok_or_else(|| "the language server sent a message without a length".to_string())?;
+        let mut body = vec![0; length];
+        self.stdout
+            .read_exact(&mut body)
+            .await
+            .map_err(|err| format!("failed to read from the language server: {err}"))?;
+        serde_json::from_slice(&body)
+            .map_err(|err| format!("the language server sent invalid JSON: {err}"))
+    }
+}
+
+/// Answers requests the server sends while starting up. No settings are provided, so
+/// `workspace/configuration` gets a null for each item and everything else a null result.
+fn server_request_result(method: &str, params: &Value) -> Value {
+    match method {
+        "workspace/configuration" => {
+            let items = params
+                .get("items")
+                .and_then(Value::as_array)
+                .map_or(0, Vec::len);
+            Value::Array(vec![Value::Null; items])
+        }
+        _ => Value::Null,
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WireTextEdit {
+    range: WireRange,
+    new_text: String,
+}
+
+#[derive(Deserialize)]
+struct WireRange {
+    start: Position,
+    end: Position,
+}
+
+/// Flattens a `WorkspaceEdit` into text edits per file. File creations, renames, and deletions,
+/// and edits outside `cwd`, are refused rather than left out of the plan.
+pub(crate) fn workspace_edits(
+    edit: &Value,
+    cwd: &AbsolutePathBuf,
+) -> Result<Vec<(String, Vec<TextEdit>)>, String> {
+    let mut by_uri = Vec::<(&str, &Value)>::new();
+    if let Some(changes) = edit.get("documentChanges").and_then(Value::as_array) {
+        for change in changes {
+            if change.get("kind").is_some() {
+                return Err(
+                    "the language server wants to create, rename, or delete files".to_string(),
+                );
+            }
+            let uri = change
+                .pointer("/textDocument/uri")
+                .and_then(Value::as_str)
+                .ok_or_else(|| {
+                    "the language server sent a document change without a URI".to_string()
+                })?;
+            by_uri.push((uri, &change["edits"]));
+        }
+    } else if let Some(changes) = edit.get("changes").and_then(Value::as_object) {
+        by_uri.extend(changes.iter().map(|(uri, edits)| (uri.as_str(), edits)));
+    }
+
+    let root = PathUri::from_abs_path(cwd);
+    let mut files = Vec::<(String, Vec<TextEdit>)>::new();
+    for (uri, edits) in by_uri {
+        let path = PathUri::parse(uri)
+            .ok()
+            .and_then(|uri| uri.relative_path_from(&root))
+            .filter(|path| !path.is_empty())
+            .ok_or_else(|| {
+                format!("the language server wants to edit {uri}, outside the working directory")
+            })?;
+        let edits = Vec::<WireTextEdit>::deserialize(edits)
+            .map_err(|err| format!("the language server sent invalid edits: {err}"))?
+            .into_iter()
+            .map(|edit| TextEdit {
+                start: edit.range.start,
+                end: edit.range.end,
+                new_text: edit.new_text,
+            });
+        match files.iter_mut().find(|(existing, _)| *existing == path) {
+            Some((_, existing)) => existing.extend(edits),
+            None => files.push((path, edits.collect())),
+        }
+    }
+    if files.is_empty() {
+        return Err("the language server found nothing to rename".to_string());
+    }
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(files)
+}
+
+/// Applies `edits` to `text`, or returns `None` when one falls outside it or they overlap.
+pub(crate) fn apply_edits(text: &str, edits: &[TextEdit]) -> Option<String> {
+    let mut ranges = edits
+        .iter()
+        .map(|edit| {
+            Some((
+                offset_of(text, edit.start)?,
+                offset_of(text, edit.end)?,
+                edit,
+            ))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    ranges.sort_by_key(|(start, end, _)| (*start, *end));
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0;
+    for (start, end, edit) in ranges {
+        if start < cursor || end < start {
+            return None;
+        }
+        out.push_str(&text[cursor..start]);
+        out.push_str(&edit.new_text);
+        cursor = end;
+    }
+    out.push_str(&text[cursor..]);
+    Some(out)
+}
+
+fn position_of(text: &str, offset: usize) -> Value {
+    let line_start = text[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+    json!({
+        "line": text[..offset].matches('\n').count(),
+        "character": text[line_start..offset].encode_utf16().count(),
+    })
+}
+
+fn offset_of(text: &str, position: Position) -> Option<usize> {
+    let line_start = if position.line == 0 {
+        0
+    } else {
+        text.match_indices('\n').nth(position.line - 1)?.0 + 1
+    };
+    let line = &text[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+    let mut units = 0;
+    for (index, ch) in line.char_indices() {
+        if units == position.character {
+            return Some(line_start + index);
+        }
+        units += ch.len_utf16();
+    }
+    (units == position.character).then_some(line_start + line.len())
+}
+
+fn language_id(path: &str) -> &str {
+    let extension = path.rsplit_once('.').map_or("", |(_, extension)| extension);
+    match extension {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "javascriptreact",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "typescriptreact",
+        _ => extension,
+    }
+}
+
+#[cfg(test)]
+#[path = "lsp_tests.rs"]
+mod tests;
@@ -0,0 +1,106 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn cwd() -> AbsolutePathBuf {
+    AbsolutePathBuf::from_absolute_path_checked("/work/repo").expect("absolute path")
+}
+
+fn edit(line: usize, start: usize, end: usize, new_text: &str) -> TextEdit {
+    TextEdit {
+        start: Position {
+            line,
+            character: start,
+        },
+        end: Position {
+            line,
+            character: end,
+        },
+        new_text: new_text.to_string(),
+    }
+}
+
+#[test]
+fn workspace_edits_group_changes_by_relative_path() {
+    let edit_json = json!({
+        "documentChanges": [
+            {
+                "textDocument": { "uri": "file:///work/repo/src/main.rs", "version": 1 },
+                "edits": [{ "range": { "start": { "line": 1, "character": 12 }, "end": { "line": 1, "character": 16 } }, "newText": "fetch" }],
+            },
+            {
+                "textDocument": { "uri": "file:///work/repo/src/config.rs", "version": null },
+                "edits": [{ "range": { "start": { "line": 0, "character": 7 }, "end": { "line": 0, "character": 11 } }, "newText": "fetch" }],
+            },
+        ],
+    });
+
+    assert_eq!(
+        workspace_edits(&edit_json, &cwd()),
+        Ok(vec![
+            ("src/config.rs".to_string(), vec![edit(0, 7, 11, "fetch")]),
+            ("src/main.rs".to_string(), vec![edit(1, 12, 16, "fetch")]),
+        ])
+    );
+}
+
+#[test]
+fn workspace_edits_refuse_files_outside_the_working_directory_and_file_operations() {
+    let outside = json!({ "changes": { "file:///home/me/.cargo/registry/lib.rs": [] } });
+    assert_eq!(
+        workspace_edits(&outside, &cwd()),
+        Err(
+            "the language server wants to edit file:///home/me/.cargo/registry/lib.rs, outside the working directory"
+                .to_string()
+        )
+    );
+
+    let rename_file = json!({
+        "documentChanges": [{ "kind": "rename", "oldUri": "file:///work/repo/a.rs", "newUri": "file:///work/repo/b.rs" }],
+    });
+    assert_eq!(
+        workspace_edits(&rename_file, &cwd()),
+        Err("the language server wants to create, rename, or delete files".to_string())
+    );
+}
+
+#[test]
+fn edits_are_applied_at_utf16_columns() {
+    let text = "let é = load();\nload();\n";
+
+    assert_eq!(
+        apply_edits(text, &[edit(1, 0, 4, "fetch"), edit(0, 8, 12, "fetch")]),
+        Some("let é = fetch();\nfetch();\n".to_string())
+    );
+    assert_eq!(apply_edits(text, &[edit(5, 0, 4, "fetch")]), None);
+    assert_eq!(
+        position_of(text, text.rfind("load").expect("call")),
+        json!({ "line": 1, "character": 0 })
+    );
+    assert_eq!(
+        position_of(text, text.find("load").expect("definition")),
+        json!({ "line": 0, "character": 8 })
+    );
+}
+
+#[tokio::test]
+async fn rename_reports_a_server_that_cannot_start() {
+    let target = RenameTarget {
+        path: "src/lib.rs",
+        text: "fn load() {}\n",
+        offset: 3,
+        new_name: "fetch",
+    };
+    let cwd = AbsolutePathBuf::try_from(std::env::temp_dir()).expect("absolute temp dir");
+
+    let err = rename(
+        &["codex-missing-language-server".to_string()],
+        &cwd,
+        &target,
+    )
+    .await
+    .expect_err("missing server");
+    assert!(
+        err.starts_with("failed to start `codex-missing-language-server`"),
+        "{err}"
+    );
+}
@@ -0,0 +1,91 @@
+//! Renders planned edits in the `apply_patch` format so they are reviewed and applied through the
+//! same approval flow as any other edit.
+
+/// Unchanged lines shown around each change so hunks anchor unambiguously.
+const CONTEXT_LINES: usize = 3;
+
+pub(crate) const BEGIN_PATCH: &str = "*** Begin Patch\n";
+pub(crate) const END_PATCH: &str = "*** End Patch\n";
+
+/// Renders an `*** Update File` section for `path`. Edits must keep the line count unchanged,
+/// which holds for in-line replacements such as renames and import rewrites.
+///
+/// Returns `None` when nothing changes and the file is not moved.
+pub(crate) fn update_file_section(
+    path: &str,
+    move_to: Option<&str>,
+    old: &str,
+    new: &str,
+) -> Option<String> {
+    let old_lines = old.lines().collect::<Vec<_>>();
+    let new_lines = new.lines().collect::<Vec<_>>();
+    debug_assert_eq!(old_lines.len(), new_lines.len());
+    let changed = (0..old_lines.len().min(new_lines.len()))
+        .filter(|&index| old_lines[index] != new_lines[index])
+        .collect::<Vec<_>>();
+    if changed.is_empty() && move_to.is_none() {
+        return None;
+    }
+
+    let mut section = format!("*** Update File: {path}\n");
+    if let Some(move_to) = move_to {
+        section.push_str(&format!("*** Move to: {move_to}\n"));
+    }
+    if changed.is_empty() {
+        // `apply_patch` rejects update sections without a hunk, so anchor the move on the first
+        // line without changing it.
+        section.push_str("@@\n");
+        section.push_str(&format!(
+            " {}\n",
+            old_lines.first().copied().unwrap_or_default()
+        ));
+        return Some(section);
+    }
+
+    for group in group_changes(&changed) {
+        let first = group[0];
+        let last = group[group.len() - 1];
+        let start = first.saturating_sub(CONTEXT_LINES);
+        let end = (last + CONTEXT_LINES + 1).min(old_lines.len());
+        section.push_str("@@\n");
+        let mut index = start;
+        while index < end {
+            if old_lines[index] == new_lines[index] {
+                section.push_str(&format!(" {}\n", old_lines[index]));
+                index += 1;
+                continue;
+            }
+            let run_end = (index..end)
+                .find(|&line| old_lines[line] == new_lines[line])
+                .unwrap_or(end);
+            for line in &old_lines[index..run_end] {
+                section.push_str(&format!("-{line}\n"));
+            }
+            for line in &new_lines[index..run_end] {
+                section.push_str(&format!("+{line}\n"));
+            }
+            index = run_end;
+        }
+    }
+    Some(section)
+}
+
+/// Splits changed line indices into hunks whose context windows do not overlap.
+fn group_changes(changed: &[usize]) -> Vec<&[usize]> {
+    let mut groups = Vec::new();
+    let mut group_start = 0;
+    for index in 1..changed.len() {
+        if changed[index] - changed[index - 1] > CONTEXT_LINES * 2 {
+            groups.push(&changed[group_start..index]);
+            group_start = index;
+        }
+    }
+    if !changed.is_empty() {
+        groups.push(&changed[group_start..]);
+    }
+    groups
+}
+
+#[cfg(test)]
+#[path = "patch_tests.rs"]
+mod tests;
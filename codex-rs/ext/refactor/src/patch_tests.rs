@@ -0,0 +1,36 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn unchanged_files_produce_no_section() {
+    assert_eq!(
+        update_file_section("a.rs", /*move_to*/ None, "x\n", "x\n"),
+        None
+    );
+}
+
+#[test]
+fn distant_changes_get_separate_hunks_with_context() {
+    let old = (1..=12).map(|n| format!("line{n}\n")).collect::<String>();
+    let new = old
+        .replace("line2\n", "LINE2\n")
+        .replace("line11\n", "LINE11\n");
+
+    assert_eq!(
+        update_file_section("a.txt", /*move_to*/ None, &old, &new).as_deref(),
+        Some(
+            "*** Update File: a.txt\n\
+             @@\n line1\n-line2\n+LINE2\n line3\n line4\n line5\n\
+             @@\n line8\n line9\n line10\n-line11\n+LINE11\n line12\n"
+        )
+    );
+}
+
+#[test]
+fn moves_without_edits_anchor_on_the_first_line() {
+    assert_eq!(
+        update_file_section("src/a.ts", Some("lib/a.ts"), "export {};\n", "export {};\n")
+            .as_deref(),
+        Some("*** Update File: src/a.ts\n*** Move to: lib/a.ts\n@@\n export {};\n")
+    );
+}
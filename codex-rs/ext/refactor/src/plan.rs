@@ -0,0 +1,774 @@
+//! Plans renames and moves over the tracked files under the working directory. Files are
+//! read through the environment's file system, so the sandbox read policy applies.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use codex_extension_api::CommandApprovalRequest;
+use codex_extension_api::CommandApprover;
+use codex_extension_api::ToolEnvironment;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::MAX_FILE_BYTES;
+use crate::MAX_PATCH_BYTES;
+use crate::lexer::Occurrence;
+use crate::lexer::identifier_occurrences;
+use crate::lexer::is_identifier;
+use crate::lexer::syntax_for_path;
+use crate::lsp;
+use crate::lsp::RenameTarget;
+use crate::patch::BEGIN_PATCH;
+use crate::patch::END_PATCH;
+use crate::patch::update_file_section;
+use crate::scope::import_spans;
+use crate::scope::is_definition;
+use crate::scope::is_qualified_by;
+use crate::scope::mentions;
+use crate::scope::module_name;
+use crate::syntax_tree::Binding;
+use crate::syntax_tree::resolve;
+
+/// A refactor rendered as a patch for `apply_patch`. Nothing has been written yet.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct RefactorPlan {
+    /// Patch in the `apply_patch` format, or empty when nothing needs to change.
+    pub(crate) patch: String,
+    pub(crate) files: Vec<PlannedFile>,
+    /// Things the plan could not handle and that may need a manual follow-up.
+    pub(crate) notes: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct PlannedFile {
+    pub(crate) path: String,
+    /// Number of replacements made in this file.
+    pub(crate) edits: usize,
+    pub(crate) moved_to: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RenameRequest {
+    pub(crate) old_name: String,
+    pub(crate) new_name: String,
+    /// Limit the rename to these paths, relative to the working directory.
+    pub(crate) paths: Vec<String>,
+    /// Also rename matches inside strings and comments.
+    pub(crate) include_strings_and_comments: bool,
+    /// Language server commands by file extension, from `[refactor.language_servers]`.
+    pub(crate) language_servers: BTreeMap<String, Vec<String>>,
+}
+
+/// Plans renaming the symbol `old_name`. The symbol must be defined in exactly one of the
+/// files searched; occurrences count where that definition is in scope, as decided by
+/// [`crate::syntax_tree`] where a grammar covers the file and by [`crate::scope`] across files,
+/// and the rest are listed in a note. When a language server is configured for the defining
+/// file and the user approves starting it, its rename is used instead.
+pub(crate) async fn plan_rename(
+    environment: &ToolEnvironment,
+    request: &RenameRequest,
+    approver: &dyn CommandApprover,
+) -> Result<RefactorPlan, String> {
+    for name in [&request.old_name, &request.new_name] {
+        if !is_identifier(name) {
+            return Err(format!("`{name}` is not a valid identifier"));
+        }
+    }
+    if request.old_name == request.new_name {
+        return Err("old_name and new_name are the same".to_string());
+    }
+
+    let mut candidates = Vec::new();
+    for path in tracked_files(environment, &request.paths).await? {
+        let Some(syntax) = syntax_for_path(Path::new(&path)) else {
+            continue;
+        };
+        let Some(text) = read_text(environment, &path).await else {
+            continue;
+        };
+        let occurrences = identifier_occurrences(&text, syntax, &request.old_name);
+        if !occurrences.is_empty() {
+            let bindings = resolve(Path::new(&path), &text, &request.old_name);
+            candidates.push(Candidate {
+                path,
+                text,
+                occurrences,
+                bindings,
+            });
+        }
+    }
+
+    // Rename a module-level symbol when there is one; otherwise the name may be a method, which
+    // only the files defining or importing it can refer to unqualified.
+    let target = if candidates
+        .iter()
+        .any(|candidate| candidate.definition(Binding::Module).is_some())
+    {
+        Binding::Module
+    } else {
+        Binding::Member
+    };
+    let definitions = candidates
+        .iter()
+        .filter(|candidate| candidate.definition(target).is_some())
+        .map(|candidate| Path::new(candidate.path.as_str()))
+        .collect::<Vec<_>>();
+    let definition = match definitions.as_slice() {
+        [] => {
+            return Err(format!(
+                "found no definition of `{}` in the tracked files; a name from a dependency cannot be renamed here",
+                request.old_name
+            ));
+        }
+        [definition] => *definition,
+        // Go spreads a package over the files of one directory.
+        [first, rest @ ..]
+            if rest
+                .iter()
+                .all(|path| is_go_file(path) && path.parent() == first.parent())
+                && is_go_file(first) =>
+        {
+            *first
+        }
+        _ => {
+            let files = definitions
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(format!(
+                "`{}` is defined in more than one file ({files}); limit the rename with `paths` so it covers one definition and the files that use it",
+                request.old_name
+            ));
+        }
+    };
+
+    let mut language_server_note = None;
+    if let Some(command) = language_server_for(definition, &request.language_servers)
+        && !request.include_strings_and_comments
+        && let Some(candidate) = candidates
+            .iter()
+            .find(|candidate| Path::new(candidate.path.as_str()) == definition)
+        && let Some(offset) = candidate.definition(target)
+    {
+        let rename_target = RenameTarget {
+            path: &candidate.path,
+            text: &candidate.text,
+            offset,
+            new_name: &request.new_name,
+        };
+        match plan_with_language_server(environment, approver, request, command, &rename_target)
+            .await
+        {
+            Ok(plan) => return Ok(plan),
+            Err(err) => {
+                language_server_note = Some(format!(
+                    "The language server `{}` was not used ({err}); this plan uses the built-in scope rules instead.",
+                    command.join(" ")
+                ));
+            }
+        }
+    }
+    let modules = module_name(definition).into_iter().collect::<Vec<_>>();
+
+    let mut builder = PlanBuilder::default();
+    builder.notes.extend(language_server_note);
+    let mut skipped_files = Vec::new();
+    let mut shadowed_files = Vec::new();
+    for candidate in &candidates {
+        let old = &candidate.text;
+        let importer = Path::new(candidate.path.as_str());
+        let imports = import_spans(old);
+        let in_scope = definitions.contains(&importer)
+            || (is_go_file(importer)
+                && is_go_file(definition)
+                && importer.parent() == definition.parent())
+            || imports.iter().any(|span| {
+                let statement = &old[span.clone()];
+                mentions(statement, &request.old_name)
+                    || modules.iter().any(|module| mentions(statement, module))
+            });
+        let (renamed, skipped): (Vec<_>, Vec<_>) = candidate
+            .occurrences
+            .iter()
+            .filter(|occurrence| occurrence.in_code || request.include_strings_and_comments)
+            .partition(|occurrence| {
+                let binding = if occurrence.in_code {
+                    candidate.binding(occurrence.start)
+                } else {
+                    target
+                };
+                match binding {
+                    Binding::Local => false,
+                    binding if binding == target => {
+                        in_scope || is_qualified_by(old, occurrence.start, &modules)
+                    }
+                    _ => is_qualified_by(old, occurrence.start, &modules),
+                }
+            });
+        if !skipped.is_empty() {
+            if in_scope {
+                shadowed_files.push(candidate.path.clone());
+            } else {
+                skipped_files.push(candidate.path.clone());
+            }
+        }
+        let starts = renamed
+            .iter()
+            .map(|occurrence| occurrence.start)
+            .collect::<Vec<_>>();
+        if starts.is_empty() {
+            continue;
+        }
+        let new = replace_at(old, &starts, request.old_name.len(), &request.new_name);
+        builder.update(
+            &candidate.path,
+            /*move_to*/ None,
+            old,
+            &new,
+            starts.len(),
+        )?;
+    }
+    if !skipped_files.is_empty() {
+        builder.notes.push(format!(
+            "Left `{}` unchanged in files that neither define nor import it, such as methods of other types with the same name: {}",
+            request.old_name,
+            skipped_files.join(", ")
+        ));
+    }
+    if !shadowed_files.is_empty() {
+        builder.notes.push(format!(
+            "Left `{}` unchanged where a local variable or parameter shadows it, or where it names a member of another type: {}",
+            request.old_name,
+            shadowed_files.join(", ")
+        ));
+    }
+    Ok(builder.finish())
+}
+
+/// A tracked file that mentions the name being renamed.
+struct Candidate {
+    path: String,
+    text: String,
+    occurrences: Vec<Occurrence>,
+    /// Scope resolution from the syntax tree, when a grammar covers the file.
+    bindings: Option<HashMap<usize, Binding>>,
+}
+
+impl Candidate {
+    /// What the identifier at `start` refers to. Without a syntax tree every identifier is taken
+    /// to be the module-level name, as the lexical rules assume.
+    fn binding(&self, start: usize) -> Binding {
+        self.bindings
+            .as_ref()
+            .and_then(|bindings| bindings.get(&start))
+            .copied()
+            .unwrap_or(Binding::Module)
+    }
+
+    /// Offset of the first definition of the name with the given binding, if any. Without a
+    /// syntax tree every lexical definition counts as a module-level one.
+    fn definition(&self, target: Binding) -> Option<usize> {
+        self.occurrences
+            .iter()
+            .filter(|occurrence| occurrence.in_code && is_definition(&self.text, occurrence.start))
+            .map(|occurrence| occurrence.start)
+            .find(|start| self.binding(*start) == target)
+    }
+}
+
+fn language_server_for<'a>(
+    definition: &Path,
+    servers: &'a BTreeMap<String, Vec<String>>,
+) -> Option<&'a [String]> {
+    let extension = definition.extension()?.to_str()?;
+    servers.get(extension).map(Vec::as_slice)
+}
+
+/// Asks the user to start `command`, runs its rename, and turns the edits into a plan. Edits
+/// outside `paths` are dropped and listed in a note.
+async fn plan_with_language_server(
+    environment: &ToolEnvironment,
+    approver: &dyn CommandApprover,
+    request: &RenameRequest,
+    command: &[String],
+    target: &RenameTarget<'_>,
+) -> Result<RefactorPlan, String> {
+    let approved = approver
+        .request_approval(CommandApprovalRequest {
+            command: command.to_vec(),
+            cwd: environment.cwd.clone(),
+            reason: format!(
+                "Start this language server outside the sandbox to plan renaming `{}` to `{}`",
+                request.old_name, request.new_name
+            ),
+        })
+        .await;
+    if !approved {
+        return Err("the user declined to start it".to_string());
+    }
+    let files = lsp::rename(command, &environment.cwd, target).await?;
+
+    let limits = request
+        .paths
+        .iter()
+        .map(|path| normalize(Path::new(path)))
+        .collect::<Vec<_>>();
+    let mut builder = PlanBuilder::default();
+    let mut outside_paths = Vec::new();
+    for (path, edits) in files {
+        if !limits.is_empty()
+            && !limits
+                .iter()
+                .any(|limit| Path::new(&path).starts_with(limit))
+        {
+            outside_paths.push(path);
+            continue;
+        }
+        let old = read_text(environment, &path)
+            .await
+            .ok_or_else(|| format!("it wants to edit {path}, which cannot be read"))?;
+        let new = lsp::apply_edits(&old, &edits)
+            .ok_or_else(|| format!("its edits to {path} do not match the file"))?;
+        builder.update(&path, /*move_to*/ None, &old, &new, edits.len())?;
+    }
+    builder.notes.push(format!(
+        "Planned by the language server `{}`.",
+        command.join(" ")
+    ));
+    if !outside_paths.is_empty() {
+        builder.notes.push(format!(
+            "Left out the language server's edits outside `paths`, which may still refer to `{}`: {}",
+            request.old_name,
+            outside_paths.join(", ")
+        ));
+    }
+    Ok(builder.finish())
+}
+
+fn is_go_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "go")
+}
+
+/// Plans moving `from` to `to` and rewriting references to it: relative JavaScript and
+/// TypeScript imports, Python module imports, and verbatim mentions of the old path.
+pub(crate) async fn plan_move(
+    environment: &ToolEnvironment,
+    from: &str,
+    to: &str,
+) -> Result<RefactorPlan, String> {
+    let from_path = normalize(Path::new(from));
+    let to_path = normalize(Path::new(to));
+    if from_path == to_path {
+        return Err("from and to are the same path".to_string());
+    }
+    match environment
+        .metadata(&environment.resolve_path(&path_string(&to_path)))
+        .await
+    {
+        Ok(_) => return Err(format!("{to} already exists")),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(format!("failed to check {to}: {err}")),
+    }
+    let files = tracked_files(environment, &[]).await?;
+    let from_key = path_string(&from_path);
+    let to_key = path_string(&to_path);
+    if !files.contains(&from_key) {
+        return Err(format!("{from} is not a tracked file"));
+    }
+
+    let mut builder = PlanBuilder::default();
+    let moved = read_text(environment, &from_key)
+        .await
+        .ok_or_else(|| format!("{from} is not a text file under {MAX_FILE_BYTES} bytes"))?;
+    if moved.is_empty() {
+        return Err(format!("{from} is empty; move it with the shell instead"));
+    }
+    let (moved_new, moved_edits) = if is_javascript(&from_path) {
+        rewrite_own_imports(&moved, &from_path, &to_path)
+    } else {
+        (moved.clone(), 0)
+    };
+    builder.update(&from_key, Some(&to_key), &moved, &moved_new, moved_edits)?;
+
+    let python_modules = python_module(&from_path).zip(python_module(&to_path));
+    for path in files.iter().filter(|path| **path != from_key) {
+        let Some(old) = read_text(environment, path).await else {
+            continue;
+        };
+        let importer = Path::new(path);
+        let mut new = old.clone();
+        let mut edits = 0;
+        if is_javascript(importer) {
+            edits += rewrite_imports_of(&mut new, importer, &from_path, &to_path);
+        }
+        if importer.extension().is_some_and(|ext| ext == "py")
+            && let Some((old_module, new_module)) = &python_modules
+        {
+            edits += rewrite_python_imports(&mut new, old_module, new_module);
+        }
+        edits += replace_path_mentions(&mut new, &from_key, &to_key);
+        if edits > 0 {
+            builder.update(path, /*move_to*/ None, &old, &new, edits)?;
+        }
+    }
+
+    if from_path.extension().is_some_and(|ext| ext == "rs") {
+        builder.notes.push(
+            "Rust `mod` declarations and `use` paths are not updated; adjust the module tree by hand."
+                .to_string(),
+        );
+    }
+    Ok(builder.finish())
+}
+
+#[derive(Default)]
+struct PlanBuilder {
+    sections: String,
+    files: Vec<PlannedFile>,
+    notes: Vec<String>,
+}
+
+impl PlanBuilder {
+    fn update(
+        &mut self,
+        path: &str,
+        move_to: Option<&str>,
+        old: &str,
+        new: &str,
+        edits: usize,
+    ) -> Result<(), String> {
+        let Some(section) = update_file_section(path, move_to, old, new) else {
+            return Ok(());
+        };
+        self.sections.push_str(&section);
+        if self.sections.len() > MAX_PATCH_BYTES {
+            return Err(format!(
+                "the patch would exceed {MAX_PATCH_BYTES} bytes; narrow the refactor with `paths`"
+            ));
+        }
+        self.files.push(PlannedFile {
+            path: path.to_string(),
+            edits,
+            moved_to: move_to.map(str::to_string),
+        });
+        Ok(())
+    }
+
+    fn finish(self) -> RefactorPlan {
+        let patch = if self.sections.is_empty() {
+            String::new()
+        } else {
+            format!("{BEGIN_PATCH}{}{END_PATCH}", self.sections)
+        };
+        RefactorPlan {
+            patch,
+            files: self.files,
+            notes: self.notes,
+        }
+    }
+}
+
+fn replace_at(text: &str, starts: &[usize], len: usize, replacement: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0;
+    for &start in starts {
+        out.push_str(&text[cursor..start]);
+        out.push_str(replacement);
+        cursor = start + len;
+    }
+    out.push_str(&text[cursor..]);
+    out
+}
+
+fn is_javascript(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            matches!(
+                ext,
+                "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts"
+            )
+        })
+}
+
+/// Rewrites relative import specifiers in `text` that resolve to `from` so they point at `to`.
+fn rewrite_imports_of(text: &mut String, importer: &Path, from: &Path, to: &Path) -> usize {
+    let importer_dir = importer.parent().unwrap_or(Path::new(""));
+    rewrite_relative_specifiers(text, |specifier| {
+        let target = normalize(&importer_dir.join(specifier));
+        if target == from {
+            Some(relative_specifier(importer_dir, to))
+        } else if target == from.with_extension("") {
+            Some(relative_specifier(importer_dir, &to.with_extension("")))
+        } else {
+            None
+        }
+    })
+}
+
+/// Rewrites the moved file's own relative imports so they still resolve from its new directory.
+fn rewrite_own_imports(text: &str, from: &Path, to: &Path) -> (String, usize) {
+    let old_dir = from.parent().unwrap_or(Path::new(""));
+    let new_dir = to.parent().unwrap_or(Path::new(""));
+    let mut new = text.to_string();
+    if old_dir == new_dir {
+        return (new, 0);
+    }
+    let edits = rewrite_relative_specifiers(&mut new, |specifier| {
+        let target = normalize(&old_dir.join(specifier));
+        Some(relative_specifier(new_dir, &target))
+    });
+    (new, edits)
+}
+
+/// Calls `rewrite` for every quoted `./` or `../` specifier of an `import`, `export ... from`, or
+/// `require` and substitutes the result. Other strings that look like paths are left alone.
+fn rewrite_relative_specifiers(
+    text: &mut String,
+    mut rewrite: impl FnMut(&str) -> Option<String>,
+) -> usize {
+    let mut edits = 0;
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text.as_str();
+    while let Some(quote_at) = rest.find(['"', '\'', '`']) {
+        let quote = rest[quote_at..].chars().next().unwrap_or('"');
+        let after = &rest[quote_at + 1..];
+        let Some(len) = after.find([quote, '\n']) else {
+            break;
+        };
+        let specifier = &after[..len];
+        out.push_str(&rest[..=quote_at]);
+        let is_import = is_import_specifier(&out[..out.len() - quote.len_utf8()]);
+        match rewrite_if_relative(specifier, &mut rewrite) {
+            Some(new) if is_import && after[len..].starts_with(quote) => {
+                out.push_str(&new);
+                edits += 1;
+            }
+            _ => out.push_str(specifier),
+        }
+        rest = &after[len..];
+        if rest.starts_with(quote) {
+            out.push(quote);
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    *text = out;
+    edits
+}
+
+/// Whether a string that follows `before` is a module specifier.
+fn is_import_specifier(before: &str) -> bool {
+    let before = before.trim_end();
+    ["from", "import", "require(", "import("]
+        .iter()
+        .any(|keyword| {
+            before.strip_suffix(keyword).is_some_and(|prefix| {
+                keyword.ends_with('(')
+                    || !prefix.ends_with(|ch: char| ch.is_alphanumeric() || ch == '_')
+            })
+        })
+}
+
+fn rewrite_if_relative(
+    specifier: &str,
+    rewrite: &mut impl FnMut(&str) -> Option<String>,
+) -> Option<String> {
+    if !(specifier.starts_with("./") || specifier.starts_with("../")) {
+        return None;
+    }
+    rewrite(specifier).filter(|new| new != specifier)
+}
+
+fn relative_specifier(from_dir: &Path, target: &Path) -> String {
+    let from = from_dir.components().collect::<Vec<_>>();
+    let to = target.components().collect::<Vec<_>>();
+    let common = from
+        .iter()
+        .zip(&to)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let mut parts = vec![".."; from.len() - common];
+    let rest = to[common..]
+        .iter()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    if parts.is_empty() {
+        parts.push(".");
+    }
+    let mut specifier = parts.join("/");
+    for part in rest {
+        specifier.push('/');
+        specifier.push_str(&part);
+    }
+    specifier
+}
+
+/// `pkg/sub/mod.py` becomes `pkg.sub.mod`; packages (`__init__.py`) are not handled.
+fn python_module(path: &Path) -> Option<String> {
+    if path.extension()? != "py" || path.file_stem()? == "__init__" {
+        return None;
+    }
+    let stem = path.with_extension("");
+    let module = stem
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?;
+    module
+        .iter()
+        .all(|part| is_identifier(part))
+        .then(|| module.join("."))
+}
+
+/// Rewrites `import a.b` and `from a.b import ...` lines that name `old_module`, plus
+/// `from a import b` lines that import only the moved module.
+fn rewrite_python_imports(text: &mut String, old_module: &str, new_module: &str) -> usize {
+    let (old_parent, old_leaf) = old_module.rsplit_once('.').unwrap_or(("", old_module));
+    let (new_parent, new_leaf) = new_module.rsplit_once('.').unwrap_or(("", new_module));
+    let mut edits = 0;
+    let lines = text
+        .split_inclusive('\n')
+        .map(|line| {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let body = line.trim_start();
+            let rewritten = if let Some(rest) = body.strip_prefix("import ") {
+                rewrite_module_prefix(rest, old_module, new_module)
+                    .map(|rest| format!("{indent}import {rest}"))
+            } else if let Some(rest) = body.strip_prefix("from ") {
+                rewrite_module_prefix(rest, old_module, new_module)
+                    .map(|rest| format!("{indent}from {rest}"))
+                    .or_else(|| {
+                        let imported = rest.strip_prefix(old_parent)?.strip_prefix(" import ")?;
+                        let (name, alias) = imported
+                            .split_once(" as ")
+                            .map_or((imported.trim_end(), ""), |(name, alias)| (name, alias));
+                        if old_parent.is_empty() || new_parent.is_empty() || name != old_leaf {
+                            return None;
+                        }
+                        // Keep the old name bound with an alias so call sites keep working.
+                        let alias = match alias.trim_end() {
+                            "" if new_leaf != old_leaf => format!(" as {old_leaf}"),
+                            "" => String::new(),
+                            alias => format!(" as {alias}"),
+                        };
+                        let newline = if line.ends_with('\n') { "\n" } else { "" };
+                        Some(format!(
+                            "{indent}from {new_parent} import {new_leaf}{alias}{newline}"
+                        ))
+                    })
+            } else {
+                None
+            };
+            match rewritten {
+                Some(rewritten) => {
+                    edits += 1;
+                    rewritten
+                }
+                None => line.to_string(),
+            }
+        })
+        .collect::<String>();
+    *text = lines;
+    edits
+}
+
+/// Replaces `old_module` at the start of `rest` when it is followed by a module boundary.
+fn rewrite_module_prefix(rest: &str, old_module: &str, new_module: &str) -> Option<String> {
+    let tail = rest.strip_prefix(old_module)?;
+    if tail.starts_with(|ch: char| ch.is_alphanumeric() || ch == '_') {
+        return None;
+    }
+    Some(format!("{new_module}{tail}"))
+}
+
+/// Replaces verbatim mentions of `from` that are not part of a longer path.
+fn replace_path_mentions(text: &mut String, from: &str, to: &str) -> usize {
+    let is_path_char = |ch: char| ch.is_alphanumeric() || matches!(ch, '_' | '-' | '.' | '/');
+    let mut edits = 0;
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0;
+    for (start, _) in text.match_indices(from) {
+        let end = start + from.len();
+        let before_ok = !text[..start].ends_with(is_path_char);
+        let after_ok = !text[end..]
+            .starts_with(|ch: char| ch.is_alphanumeric() || matches!(ch, '_' | '-' | '/'));
+        if start < cursor || !before_ok || !after_ok {
+            continue;
+        }
+        out.push_str(&text[cursor..start]);
+        out.push_str(to);
+        cursor = end;
+        edits += 1;
+    }
+    out.push_str(&text[cursor..]);
+    *text = out;
+    edits
+}
+
+/// Resolves `.` and `..` lexically; paths stay relative to the working directory.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            component => out.push(component),
+        }
+    }
+    out
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+async fn tracked_files(
+    environment: &ToolEnvironment,
+    paths: &[String],
+) -> Result<Vec<String>, String> {
+    let cwd = environment.cwd.clone();
+    let paths = paths.to_vec();
+    let output = tokio::task::spawn_blocking(move || {
+        Command::new("git")
+            .current_dir(cwd)
+            .args(["ls-files", "-z", "--"])
+            .args(paths)
+            .output()
+    })
+    .await
+    .map_err(|err| format!("git task failed: {err}"))?
+    .map_err(|err| format!("failed to run git: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "refactor tools need a git repository: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Reads `path` as UTF-8 text, skipping large and binary files and files the sandbox does not
+/// let the session read.
+async fn read_text(environment: &ToolEnvironment, path: &str) -> Option<String> {
+    let path = environment.resolve_path(path);
+    let metadata = environment.metadata(&path).await.ok()?;
+    if metadata.size > MAX_FILE_BYTES {
+        return None;
+    }
+    environment.read_file_text(&path).await.ok()
+}
+
+#[cfg(test)]
+#[path = "plan_tests.rs"]
+mod tests;
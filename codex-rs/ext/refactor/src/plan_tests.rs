@@ -0,0 +1,403 @@
+use super::*;
+use codex_extension_api::DenyCommandApprover;
+use codex_extension_api::new_local_environment;
+use codex_protocol::models::PermissionProfile;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn repo(files: &[(&str, &str)]) -> TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    for (path, contents) in files {
+        let path = dir.path().join(path);
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("create dirs");
+        std::fs::write(path, contents).expect("write file");
+    }
+    for args in [&["init", "-q"][..], &["add", "."][..]] {
+        let status = Command::new("git")
+            .current_dir(dir.path())
+            .args(args)
+            .status()
+            .expect("run git");
+        assert!(status.success());
+    }
+    dir
+}
+
+fn unsandboxed(dir: &TempDir) -> ToolEnvironment {
+    new_local_environment(dir.path(), PermissionProfile::Disabled).expect("local environment")
+}
+
+fn rename(old_name: &str, new_name: &str) -> RenameRequest {
+    RenameRequest {
+        old_name: old_name.to_string(),
+        new_name: new_name.to_string(),
+        paths: Vec::new(),
+        include_strings_and_comments: false,
+        language_servers: BTreeMap::new(),
+    }
+}
+
+#[tokio::test]
+async fn rename_skips_strings_comments_and_longer_identifiers() {
+    let dir = repo(&[
+        (
+            "src/lib.rs",
+            "// parse input\nfn parse(input: &str) {}\nfn parse_all() { parse(\"parse\"); }\n",
+        ),
+        ("README.md", "Call parse first.\n"),
+    ]);
+
+    let plan = plan_rename(
+        &unsandboxed(&dir),
+        &rename("parse", "parse_line"),
+        &DenyCommandApprover,
+    )
+    .await
+    .expect("plan");
+
+    assert_eq!(
+        plan,
+        RefactorPlan {
+            patch: "*** Begin Patch\n\
+                    *** Update File: src/lib.rs\n\
+                    @@\n \
+                    // parse input\n\
+                    -fn parse(input: &str) {}\n\
+                    -fn parse_all() { parse(\"parse\"); }\n\
+                    +fn parse_line(input: &str) {}\n\
+                    +fn parse_all() { parse_line(\"parse\"); }\n\
+                    *** End Patch\n"
+                .to_string(),
+            files: vec![PlannedFile {
+                path: "src/lib.rs".to_string(),
+                edits: 2,
+                moved_to: None,
+            }],
+            notes: Vec::new(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn rename_rejects_non_identifiers() {
+    let dir = repo(&[("a.py", "x = 1\n")]);
+
+    assert_eq!(
+        plan_rename(
+            &unsandboxed(&dir),
+            &rename("x", "not-valid"),
+            &DenyCommandApprover
+        )
+        .await,
+        Err("`not-valid` is not a valid identifier".to_string())
+    );
+}
+
+#[tokio::test]
+async fn move_rewrites_relative_imports_in_both_directions() {
+    let dir = repo(&[
+        (
+            "src/util.ts",
+            "import { log } from './log';\nexport const x = 1;\n",
+        ),
+        ("src/log.ts", "export const log = 1;\n"),
+        ("src/app.ts", "import { x } from \"./util\";\n"),
+    ]);
+
+    let plan = plan_move(&unsandboxed(&dir), "src/util.ts", "src/shared/util.ts")
+        .await
+        .expect("plan");
+
+    assert_eq!(
+        plan.patch,
+        "*** Begin Patch\n\
+         *** Update File: src/util.ts\n\
+         *** Move to: src/shared/util.ts\n\
+         @@\n\
+         -import { log } from './log';\n\
+         +import { log } from '../log';\n \
+         export const x = 1;\n\
+         *** Update File: src/app.ts\n\
+         @@\n\
+         -import { x } from \"./util\";\n\
+         +import { x } from \"./shared/util\";\n\
+         *** End Patch\n"
+    );
+}
+
+#[tokio::test]
+async fn move_rewrites_python_imports_and_path_mentions() {
+    let dir = repo(&[
+        ("pkg/old.py", "VALUE = 1\n"),
+        (
+            "main.py",
+            "import pkg.old\nfrom pkg import old\nfrom pkg.old import VALUE\n",
+        ),
+        ("docs.md", "See pkg/old.py and pkg/old.pyc.\n"),
+    ]);
+
+    let plan = plan_move(&unsandboxed(&dir), "pkg/old.py", "pkg/new.py")
+        .await
+        .expect("plan");
+
+    let edited = plan
+        .files
+        .iter()
+        .map(|file| (file.path.as_str(), file.edits))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        edited,
+        vec![("pkg/old.py", 0), ("docs.md", 1), ("main.py", 3)]
+    );
+    assert!(
+        plan.patch
+            .contains("+import pkg.new\n+from pkg import new as old\n+from pkg.new import VALUE\n"),
+        "{}",
+        plan.patch
+    );
+    assert!(
+        plan.patch.contains("+See pkg/new.py and pkg/old.pyc.\n"),
+        "{}",
+        plan.patch
+    );
+}
+
+#[tokio::test]
+async fn move_refuses_to_overwrite() {
+    let dir = repo(&[("a.ts", "1\n"), ("b.ts", "2\n")]);
+
+    assert_eq!(
+        plan_move(&unsandboxed(&dir), "a.ts", "b.ts").await,
+        Err("b.ts already exists".to_string())
+    );
+}
+
+#[tokio::test]
+async fn rename_skips_files_the_sandbox_cannot_read() {
+    let dir = repo(&[("a.py", "x = 1\n")]);
+
+    // Unit tests do not configure the sandboxed file system helper, so every sandboxed read
+    // fails; the plan must not fall back to reading the files directly.
+    let plan = plan_rename(
+        &new_local_environment(dir.path(), PermissionProfile::read_only())
+            .expect("local environment"),
+        &rename("x", "y"),
+        &DenyCommandApprover,
+    )
+    .await;
+
+    assert_eq!(
+        plan,
+        Err(
+            "found no definition of `x` in the tracked files; a name from a dependency cannot be renamed here"
+                .to_string()
+        )
+    );
+}
+
+#[tokio::test]
+async fn rename_leaves_same_named_members_in_files_that_do_not_import_the_symbol() {
+    let dir = repo(&[
+        (
+            "src/config.rs",
+            "pub fn load() {}
+",
+        ),
+        (
+            "src/main.rs",
+            "use crate::config::load;
+fn main() { load(); }
+",
+        ),
+        (
+            "src/cache.rs",
+            "fn warm(store: Store) { store.load(); }
+",
+        ),
+        (
+            "src/app.rs",
+            "fn run() { config::load(); }
+",
+        ),
+    ]);
+
+    let plan = plan_rename(
+        &unsandboxed(&dir),
+        &rename("load", "load_config"),
+        &DenyCommandApprover,
+    )
+    .await
+    .expect("plan");
+
+    let edited = plan
+        .files
+        .iter()
+        .map(|file| (file.path.as_str(), file.edits))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        edited,
+        vec![("src/app.rs", 1), ("src/config.rs", 1), ("src/main.rs", 2)]
+    );
+    assert_eq!(
+        plan.notes,
+        vec![
+            "Left `load` unchanged in files that neither define nor import it, such as methods of other types with the same name: src/cache.rs"
+                .to_string()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn rename_leaves_locals_that_shadow_the_symbol() {
+    let dir = repo(&[(
+        "src/lib.rs",
+        "pub fn count() -> u32 { 1 }
+fn twice(count: u32) -> u32 { count * 2 }
+fn total() -> u32 { let count = count() + 1; count }
+",
+    )]);
+
+    let plan = plan_rename(
+        &unsandboxed(&dir),
+        &rename("count", "tally"),
+        &DenyCommandApprover,
+    )
+    .await
+    .expect("plan");
+
+    assert_eq!(
+        plan,
+        RefactorPlan {
+            patch: "*** Begin Patch\n\
+                    *** Update File: src/lib.rs\n\
+                    @@\n\
+                    -pub fn count() -> u32 { 1 }\n\
+                    +pub fn tally() -> u32 { 1 }\n \
+                    fn twice(count: u32) -> u32 { count * 2 }\n\
+                    -fn total() -> u32 { let count = count() + 1; count }\n\
+                    +fn total() -> u32 { let count = tally() + 1; count }\n\
+                    *** End Patch\n"
+                .to_string(),
+            files: vec![PlannedFile {
+                path: "src/lib.rs".to_string(),
+                edits: 2,
+                moved_to: None,
+            }],
+            notes: vec![
+                "Left `count` unchanged where a local variable or parameter shadows it, or where it names a member of another type: src/lib.rs"
+                    .to_string()
+            ],
+        }
+    );
+}
+
+#[tokio::test]
+async fn rename_tells_apart_same_named_symbols_in_different_scopes() {
+    let dir = repo(&[
+        (
+            "app/config.py",
+            "def load():\n    return {}\n\n\nclass Cache:\n    def load(self):\n        return load()\n",
+        ),
+        (
+            "app/main.py",
+            "from app import config\n\n\ndef run(cache):\n    cache.load()\n    return config.load()\n",
+        ),
+    ]);
+
+    let plan = plan_rename(
+        &unsandboxed(&dir),
+        &rename("load", "read_config"),
+        &DenyCommandApprover,
+    )
+    .await
+    .expect("plan");
+
+    assert_eq!(
+        plan.patch,
+        "*** Begin Patch\n\
+         *** Update File: app/config.py\n\
+         @@\n\
+         -def load():\n\
+         +def read_config():\n     \
+         return {}\n \n \n \
+         class Cache:\n     \
+         def load(self):\n\
+         -        return load()\n\
+         +        return read_config()\n\
+         *** Update File: app/main.py\n\
+         @@\n \n \
+         def run(cache):\n     \
+         cache.load()\n\
+         -    return config.load()\n\
+         +    return config.read_config()\n\
+         *** End Patch\n"
+    );
+    assert_eq!(
+        plan.notes,
+        vec![
+            "Left `load` unchanged where a local variable or parameter shadows it, or where it names a member of another type: app/config.py, app/main.py"
+                .to_string()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn rename_falls_back_to_the_built_in_rules_when_the_language_server_is_declined() {
+    let dir = repo(&[("src/lib.rs", "pub fn load() {}\n")]);
+    let mut request = rename("load", "fetch");
+    request
+        .language_servers
+        .insert("rs".to_string(), vec!["rust-analyzer".to_string()]);
+
+    let plan = plan_rename(&unsandboxed(&dir), &request, &DenyCommandApprover)
+        .await
+        .expect("plan");
+
+    assert_eq!(
+        plan.notes,
+        vec![
+            "The language server `rust-analyzer` was not used (the user declined to start it); this plan uses the built-in scope rules instead."
+                .to_string()
+        ]
+    );
+    assert_eq!(plan.files.len(), 1);
+}
+
+#[tokio::test]
+async fn rename_refuses_a_name_defined_in_several_files() {
+    let dir = repo(&[
+        ("a.py", "def run():\n    pass\n"),
+        ("b.py", "def run():\n    pass\n"),
+    ]);
+
+    assert_eq!(
+        plan_rename(&unsandboxed(&dir), &rename("run", "start"), &DenyCommandApprover).await,
+        Err(
+            "`run` is defined in more than one file (a.py, b.py); limit the rename with `paths` so it covers one definition and the files that use it"
+                .to_string()
+        )
+    );
+}
+
+#[tokio::test]
+async fn move_only_rewrites_import_specifiers() {
+    let dir = repo(&[
+        ("src/util.ts", "export const x = 1;\n"),
+        (
+            "src/app.ts",
+            "import { x } from './util';\nconst y = require('./util');\nconst label = './util';\n",
+        ),
+    ]);
+
+    let plan = plan_move(&unsandboxed(&dir), "src/util.ts", "src/lib/util.ts")
+        .await
+        .expect("plan");
+
+    assert!(
+        plan.patch.contains(
+            "+import { x } from './lib/util';\n+const y = require('./lib/util');\n const label = './util';\n"
+        ),
+        "{}",
+        plan.patch
+    );
+}
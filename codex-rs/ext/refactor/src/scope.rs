@@ -0,0 +1,146 @@
+//! Decides which occurrences of an identifier refer to the symbol being renamed.
+//!
+//! Without a compiler this cannot resolve every reference, so it stays conservative: the symbol
+//! is in scope in the file that defines it, in files that import it by name or import its
+//! module, and in Go files of the defining package. Elsewhere only references qualified with the
+//! defining module (`config::Name`, `config.Name`) count. Everything else, such as a method of
+//! the same name on another type, is left alone and reported. Within a file,
+//! [`crate::syntax_tree`] further sets aside shadowing locals and members where it can parse.
+
+use std::ops::Range;
+use std::path::Path;
+
+/// Keywords that introduce a named definition in the supported languages.
+const DEFINITION_KEYWORDS: &[&str] = &[
+    "fn",
+    "struct",
+    "enum",
+    "trait",
+    "type",
+    "union",
+    "mod",
+    "const",
+    "static",
+    "function",
+    "class",
+    "interface",
+    "def",
+    "func",
+    "module",
+    "namespace",
+    "let",
+    "var",
+    "val",
+];
+
+/// Keywords that start an import statement, after any `pub` or `export` prefix.
+const IMPORT_KEYWORDS: &[&str] = &["use", "import", "from", "#include", "require"];
+
+/// Whether the identifier at `start` is the name in a definition such as `fn name` or
+/// `class name`, or is assigned at the start of a line, as Python module constants are.
+pub(crate) fn is_definition(text: &str, start: usize) -> bool {
+    let line_start = text[..start].rfind('\n').map_or(0, |newline| newline + 1);
+    if line_start == start {
+        let after = text[start..]
+            .trim_start_matches(|ch: char| ch.is_alphanumeric() || ch == '_')
+            .trim_start_matches([' ', '\t']);
+        return after.starts_with(':') || (after.starts_with('=') && !after.starts_with("=="));
+    }
+    text[line_start..start]
+        .split_whitespace()
+        .next_back()
+        .is_some_and(|keyword| DEFINITION_KEYWORDS.contains(&keyword))
+}
+
+/// Byte ranges of the import statements in `text`. A statement that opens a `{` or `(` list
+/// continues until the list closes, so multi-line imports are covered.
+pub(crate) fn import_spans(text: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    let mut lines = text.split_inclusive('\n');
+    while let Some(line) = lines.next() {
+        let start = offset;
+        offset += line.len();
+        if !is_import_line(line) {
+            continue;
+        }
+        let mut depth = bracket_depth(line);
+        while depth > 0
+            && let Some(next) = lines.next()
+        {
+            offset += next.len();
+            depth += bracket_depth(next);
+        }
+        spans.push(start..offset);
+    }
+    spans
+}
+
+fn is_import_line(line: &str) -> bool {
+    let mut words = line.trim_start().split_whitespace();
+    let first = words.next().unwrap_or_default();
+    let keyword = match first {
+        "export" => return line.contains(" from "),
+        "pub" => words.next().unwrap_or_default(),
+        _ if first.starts_with("pub(") => words.next().unwrap_or_default(),
+        _ => first,
+    };
+    IMPORT_KEYWORDS.contains(&keyword)
+        || IMPORT_KEYWORDS
+            .iter()
+            .any(|import| keyword.starts_with(&format!("{import}(")))
+        || line.contains("require(")
+}
+
+fn bracket_depth(line: &str) -> i32 {
+    line.chars()
+        .map(|ch| match ch {
+            '{' | '(' => 1,
+            '}' | ')' => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// The module a file's definitions are imported through: its stem, or its directory for files
+/// such as `mod.rs`, `index.ts`, and `__init__.py` that stand for the directory. Go code refers
+/// to other packages by directory.
+pub(crate) fn module_name(path: &Path) -> Option<&str> {
+    let stem = path.file_stem()?.to_str()?;
+    let stands_for_directory = matches!(stem, "mod" | "lib" | "index" | "__init__")
+        || path.extension().is_some_and(|ext| ext == "go");
+    if stands_for_directory {
+        path.parent()?.file_name()?.to_str()
+    } else {
+        Some(stem)
+    }
+}
+
+/// Whether the identifier at `start` is qualified with `::` or with one of `modules`, as in
+/// `crate::config::Name` or `config.Name`.
+pub(crate) fn is_qualified_by(text: &str, start: usize, modules: &[&str]) -> bool {
+    let before = &text[..start];
+    if before.ends_with("::") {
+        return true;
+    }
+    let Some(before) = before.strip_suffix('.') else {
+        return false;
+    };
+    let qualifier_start = before
+        .rfind(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+        .map_or(0, |index| index + 1);
+    modules.contains(&&before[qualifier_start..])
+}
+
+/// Whether `text` mentions `word` as a whole identifier.
+pub(crate) fn mentions(text: &str, word: &str) -> bool {
+    text.match_indices(word).any(|(start, _)| {
+        let end = start + word.len();
+        !text[..start].ends_with(|ch: char| ch.is_alphanumeric() || ch == '_')
+            && !text[end..].starts_with(|ch: char| ch.is_alphanumeric() || ch == '_')
+    })
+}
+
+#[cfg(test)]
+#[path = "scope_tests.rs"]
+mod tests;
@@ -0,0 +1,58 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn definitions_are_recognized_by_their_keyword() {
+    let text = "pub fn load() {}\nlet x = load();\nexport class Loader {}\n";
+
+    assert!(is_definition(text, text.find("load").expect("fn name")));
+    assert!(!is_definition(text, text.rfind("load").expect("call")));
+    assert!(is_definition(
+        text,
+        text.find("Loader").expect("class name")
+    ));
+
+    let python = "LIMIT = 10\nprint(LIMIT)\n";
+    assert!(is_definition(python, 0));
+    assert!(!is_definition(python, python.rfind("LIMIT").expect("use")));
+}
+
+#[test]
+fn import_spans_cover_multi_line_lists() {
+    let text = "use crate::{\n    config::Config,\n    load,\n};\nfn main() {}\nimport { a } from './a';\nfrom pkg import (\n    b,\n)\nx = require('./c')\n";
+
+    let spans = import_spans(text)
+        .into_iter()
+        .map(|span| &text[span])
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        spans,
+        vec![
+            "use crate::{\n    config::Config,\n    load,\n};\n",
+            "import { a } from './a';\n",
+            "from pkg import (\n    b,\n)\n",
+            "x = require('./c')\n",
+        ]
+    );
+}
+
+#[test]
+fn modules_are_named_after_their_file_or_directory() {
+    assert_eq!(module_name(Path::new("src/config.rs")), Some("config"));
+    assert_eq!(module_name(Path::new("src/config/mod.rs")), Some("config"));
+    assert_eq!(module_name(Path::new("web/api/index.ts")), Some("api"));
+    assert_eq!(module_name(Path::new("pkg/store/store.go")), Some("store"));
+}
+
+#[test]
+fn qualified_references_need_a_path_or_a_known_module() {
+    let text = "config::load(); config.load(); client.load();";
+
+    let qualified = text
+        .match_indices("load")
+        .map(|(start, _)| is_qualified_by(text, start, &["config"]))
+        .collect::<Vec<_>>();
+
+    assert_eq!(qualified, vec![true, true, false]);
+}
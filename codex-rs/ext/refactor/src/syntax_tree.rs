@@ -0,0 +1,370 @@
+//! Resolves identifiers with tree-sitter grammars, for the languages that have one here.
+//!
+//! The lexer only knows whether an identifier sits in code. The syntax tree also tells apart a
+//! module-level name, a local variable or parameter that shadows it, and a member reached through
+//! a value or defined inside a type, so a rename can leave the latter two alone. Resolution is
+//! still syntactic: it follows scopes but not types.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::Path;
+
+use tree_sitter::Language;
+use tree_sitter::Node;
+use tree_sitter::Parser;
+use tree_sitter::Tree;
+
+/// What an identifier refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Binding {
+    /// A definition at module level, or a reference that resolves to one.
+    Module,
+    /// A local variable, parameter, or nested definition, or a reference inside its scope.
+    Local,
+    /// A field, method, or property: reached through a value, or defined inside a type.
+    Member,
+}
+
+/// Node kinds that decide scoping in one grammar.
+struct Grammar {
+    /// Nodes whose local bindings are not visible outside them, such as functions and blocks.
+    scopes: &'static [&'static str],
+    /// Nodes whose definitions are members rather than locals, such as impl blocks and classes.
+    type_bodies: &'static [&'static str],
+    /// `(node, field)` pairs whose identifiers bind a name; `None` matches any child.
+    binders: &'static [(&'static str, Option<&'static str>)],
+    /// Patterns that destructure into several bindings, with their fields that bind nothing.
+    patterns: &'static [(&'static str, &'static [&'static str])],
+    /// Identifier kinds that always name a member.
+    member_kinds: &'static [&'static str],
+    /// `(node, field)` pairs whose plain identifier names a member.
+    member_fields: &'static [(&'static str, &'static str)],
+    /// Statements that declare a name as not local to the scope they appear in.
+    global_statements: &'static [&'static str],
+    /// Whether a local binding covers its whole scope, as in Python, rather than only the code
+    /// after it.
+    hoisted: bool,
+}
+
+const RUST: Grammar = Grammar {
+    scopes: &[
+        "block",
+        "function_item",
+        "closure_expression",
+        "for_expression",
+        "if_expression",
+        "while_expression",
+    ],
+    type_bodies: &["impl_item", "trait_item"],
+    binders: &[
+        ("let_declaration", Some("pattern")),
+        ("let_condition", Some("pattern")),
+        ("parameter", Some("pattern")),
+        ("closure_parameters", None),
+        ("for_expression", Some("pattern")),
+        ("function_item", Some("name")),
+        ("function_signature_item", Some("name")),
+        ("const_item", Some("name")),
+        ("static_item", Some("name")),
+    ],
+    patterns: &[
+        ("tuple_pattern", &[]),
+        ("slice_pattern", &[]),
+        ("tuple_struct_pattern", &["type"]),
+        ("struct_pattern", &["type"]),
+        ("field_pattern", &["name"]),
+        ("ref_pattern", &[]),
+        ("mut_pattern", &[]),
+        ("reference_pattern", &[]),
+        ("or_pattern", &[]),
+        ("captured_pattern", &[]),
+    ],
+    member_kinds: &["field_identifier"],
+    member_fields: &[],
+    global_statements: &[],
+    hoisted: false,
+};
+
+const PYTHON: Grammar = Grammar {
+    scopes: &[
+        "function_definition",
+        "lambda",
+        "list_comprehension",
+        "set_comprehension",
+        "dictionary_comprehension",
+        "generator_expression",
+    ],
+    type_bodies: &["class_definition"],
+    binders: &[
+        ("parameters", None),
+        ("lambda_parameters", None),
+        ("default_parameter", Some("name")),
+        ("typed_parameter", None),
+        ("typed_default_parameter", Some("name")),
+        ("assignment", Some("left")),
+        ("augmented_assignment", Some("left")),
+        ("for_statement", Some("left")),
+        ("for_in_clause", Some("left")),
+        ("as_pattern", Some("alias")),
+        ("function_definition", Some("name")),
+        ("class_definition", Some("name")),
+    ],
+    patterns: &[
+        ("pattern_list", &[]),
+        ("tuple_pattern", &[]),
+        ("list_pattern", &[]),
+        ("list_splat_pattern", &[]),
+        ("dictionary_splat_pattern", &[]),
+        ("as_pattern_target", &[]),
+    ],
+    member_kinds: &[],
+    member_fields: &[("attribute", "attribute"), ("keyword_argument", "name")],
+    global_statements: &["global_statement", "nonlocal_statement"],
+    hoisted: true,
+};
+
+/// Shared by JavaScript and TypeScript, whose grammars use the same names for these nodes.
+const JAVASCRIPT: Grammar = Grammar {
+    scopes: &[
+        "function_declaration",
+        "generator_function_declaration",
+        "function_expression",
+        "generator_function",
+        "arrow_function",
+        "method_definition",
+        "statement_block",
+        "for_statement",
+        "for_in_statement",
+        "catch_clause",
+    ],
+    type_bodies: &[],
+    binders: &[
+        ("variable_declarator", Some("name")),
+        ("formal_parameters", None),
+        ("required_parameter", Some("pattern")),
+        ("optional_parameter", Some("pattern")),
+        ("arrow_function", Some("parameter")),
+        ("catch_clause", Some("parameter")),
+        ("for_in_statement", Some("left")),
+        ("function_declaration", Some("name")),
+        ("generator_function_declaration", Some("name")),
+        ("class_declaration", Some("name")),
+    ],
+    patterns: &[
+        ("object_pattern", &[]),
+        ("array_pattern", &[]),
+        ("pair_pattern", &["key"]),
+        ("rest_pattern", &[]),
+        ("assignment_pattern", &["right"]),
+        ("object_assignment_pattern", &["right"]),
+    ],
+    member_kinds: &["property_identifier", "private_property_identifier"],
+    member_fields: &[],
+    global_statements: &[],
+    hoisted: false,
+};
+
+const GO: Grammar = Grammar {
+    scopes: &[
+        "function_declaration",
+        "method_declaration",
+        "func_literal",
+        "block",
+        "if_statement",
+        "for_statement",
+        "expression_switch_statement",
+        "type_switch_statement",
+    ],
+    type_bodies: &[],
+    binders: &[
+        ("parameter_declaration", Some("name")),
+        ("variadic_parameter_declaration", Some("name")),
+        ("short_var_declaration", Some("left")),
+        ("var_spec", Some("name")),
+        ("const_spec", Some("name")),
+        ("range_clause", Some("left")),
+        ("function_declaration", Some("name")),
+    ],
+    patterns: &[("expression_list", &[])],
+    member_kinds: &["field_identifier"],
+    member_fields: &[],
+    global_statements: &[],
+    hoisted: false,
+};
+
+fn grammar_for_path(path: &Path) -> Option<(Language, &'static Grammar)> {
+    let extension = path.extension()?.to_str()?;
+    let grammar = match extension {
+        "rs" => (tree_sitter_rust::LANGUAGE.into(), &RUST),
+        "py" | "pyi" => (tree_sitter_python::LANGUAGE.into(), &PYTHON),
+        "js" | "jsx" | "mjs" | "cjs" => (tree_sitter_javascript::LANGUAGE.into(), &JAVASCRIPT),
+        "ts" | "mts" | "cts" => (
+            tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            &JAVASCRIPT,
+        ),
+        "tsx" => (tree_sitter_typescript::LANGUAGE_TSX.into(), &JAVASCRIPT),
+        "go" => (tree_sitter_go::LANGUAGE.into(), &GO),
+        _ => return None,
+    };
+    Some(grammar)
+}
+
+/// Resolves every identifier spelled `name` in `text`, keyed by byte offset. Returns `None` when
+/// there is no grammar for `path` or the file does not parse cleanly, so callers fall back to
+/// the lexical rules in [`crate::scope`].
+pub(crate) fn resolve(path: &Path, text: &str, name: &str) -> Option<HashMap<usize, Binding>> {
+    let (language, grammar) = grammar_for_path(path)?;
+    let mut parser = Parser::new();
+    parser.set_language(&language).ok()?;
+    let tree = parser.parse(text, /*old_tree*/ None)?;
+    if tree.root_node().has_error() {
+        return None;
+    }
+    let identifiers = identifiers_named(&tree, text, name);
+
+    // A scope that declares the name `global` assigns the module-level binding.
+    let global_scopes = identifiers
+        .iter()
+        .filter(|node| {
+            node.parent()
+                .is_some_and(|parent| grammar.global_statements.contains(&parent.kind()))
+        })
+        .filter_map(|node| enclosing(grammar, *node))
+        .filter_map(|(node, is_scope)| is_scope.then_some(node.id()))
+        .collect::<HashSet<_>>();
+
+    let mut bindings = HashMap::new();
+    let mut shadowed = Vec::<Range<usize>>::new();
+    for node in &identifiers {
+        let binding = if is_member(grammar, *node) {
+            Binding::Member
+        } else if let Some((binder, field)) = binder(grammar, *node) {
+            // A definition names itself in the surrounding scope; other binders, such as a
+            // `for` loop or a lambda, may themselves be the scope they bind in.
+            let start = if field == Some("name") {
+                binder.parent()
+            } else {
+                Some(binder)
+            };
+            match start.and_then(|start| enclosing(grammar, start)) {
+                None => Binding::Module,
+                Some((_, false)) => Binding::Member,
+                Some((scope, true)) if global_scopes.contains(&scope.id()) => Binding::Module,
+                Some((scope, true)) => {
+                    let visible_from = if grammar.hoisted {
+                        scope.start_byte()
+                    } else if scope.id() == binder.id() {
+                        node.end_byte()
+                    } else {
+                        binder.end_byte()
+                    };
+                    shadowed.push(visible_from..scope.end_byte());
+                    Binding::Local
+                }
+            }
+        } else {
+            continue;
+        };
+        bindings.insert(node.start_byte(), binding);
+    }
+    for node in &identifiers {
+        let start = node.start_byte();
+        bindings.entry(start).or_insert_with(|| {
+            if shadowed.iter().any(|range| range.contains(&start)) {
+                Binding::Local
+            } else {
+                Binding::Module
+            }
+        });
+    }
+    Some(bindings)
+}
+
+/// Leaf nodes whose text is exactly `name`.
+fn identifiers_named<'tree>(tree: &'tree Tree, text: &str, name: &str) -> Vec<Node<'tree>> {
+    let mut identifiers = Vec::new();
+    let mut cursor = tree.walk();
+    loop {
+        let node = cursor.node();
+        if node.child_count() == 0 && node.is_named() && &text[node.byte_range()] == name {
+            identifiers.push(node);
+        }
+        if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return identifiers;
+            }
+        }
+    }
+}
+
+fn is_member(grammar: &Grammar, node: Node<'_>) -> bool {
+    grammar.member_kinds.contains(&node.kind())
+        || node.parent().is_some_and(|parent| {
+            grammar
+                .member_fields
+                .iter()
+                .any(|(kind, field)| parent.kind() == *kind && is_field(parent, node, field))
+        })
+}
+
+/// The node that binds `node` as a name, such as a `let` declaration or a parameter list, and
+/// the field that holds it, or `None` when `node` is not in a binding position.
+fn binder<'tree>(
+    grammar: &Grammar,
+    node: Node<'tree>,
+) -> Option<(Node<'tree>, Option<&'static str>)> {
+    let mut child = node;
+    let mut parent = node.parent()?;
+    loop {
+        let binding_field = grammar
+            .binders
+            .iter()
+            .find(|(kind, field)| {
+                parent.kind() == *kind && field.is_none_or(|field| is_field(parent, child, field))
+            })
+            .map(|(_, field)| *field);
+        if let Some(field) = binding_field {
+            return Some((parent, field));
+        }
+        let (_, excluded) = grammar
+            .patterns
+            .iter()
+            .find(|(kind, _)| parent.kind() == *kind)?;
+        if excluded.iter().any(|field| is_field(parent, child, field)) {
+            return None;
+        }
+        child = parent;
+        parent = parent.parent()?;
+    }
+}
+
+/// The innermost scope or type body containing `node`, including `node` itself, paired with
+/// whether it is a scope. `None` means module level.
+fn enclosing<'tree>(grammar: &Grammar, node: Node<'tree>) -> Option<(Node<'tree>, bool)> {
+    let mut current = Some(node);
+    while let Some(node) = current {
+        if grammar.scopes.contains(&node.kind()) {
+            return Some((node, true));
+        }
+        if grammar.type_bodies.contains(&node.kind()) {
+            return Some((node, false));
+        }
+        current = node.parent();
+    }
+    None
+}
+
+fn is_field(parent: Node<'_>, child: Node<'_>, field: &str) -> bool {
+    let mut cursor = parent.walk();
+    parent
+        .children_by_field_name(field, &mut cursor)
+        .any(|candidate| candidate.id() == child.id())
+}
+
+#[cfg(test)]
+#[path = "syntax_tree_tests.rs"]
+mod tests;
@@ -0,0 +1,105 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+/// Bindings of every identifier spelled `name`, in source order.
+fn resolved(path: &str, text: &str, name: &str) -> Vec<Binding> {
+    let mut bindings = resolve(Path::new(path), text, name)
+        .expect("grammar")
+        .into_iter()
+        .collect::<Vec<_>>();
+    bindings.sort_by_key(|(start, _)| *start);
+    bindings.into_iter().map(|(_, binding)| binding).collect()
+}
+
+#[test]
+fn locals_shadow_the_module_level_name_only_where_visible() {
+    let text = "fn count() -> u32 { 1 }
+fn a(count: u32) -> u32 { count + 1 }
+fn b() -> u32 { let total = count(); let count = total; count }
+fn c() -> u32 { count() }
+";
+
+    assert_eq!(
+        resolved("lib.rs", text, "count"),
+        vec![
+            Binding::Module,
+            Binding::Local,
+            Binding::Local,
+            Binding::Module,
+            Binding::Local,
+            Binding::Local,
+            Binding::Module,
+        ]
+    );
+}
+
+#[test]
+fn same_named_symbols_in_different_scopes_are_told_apart() {
+    let python = "def load():
+    return 1
+
+class Loader:
+    def load(self):
+        return load()
+
+def run(loader):
+    loader.load()
+    load = 2
+    return load
+";
+    assert_eq!(
+        resolved("app.py", python, "load"),
+        vec![
+            Binding::Module,
+            Binding::Member,
+            Binding::Module,
+            Binding::Member,
+            Binding::Local,
+            Binding::Local,
+        ]
+    );
+
+    let go = "package store
+
+func Load() int { return 1 }
+
+func (s *Store) Load() int { return Load() }
+
+func run(s *Store) int {
+	Load := s.Load
+	return Load()
+}
+";
+    assert_eq!(
+        resolved("store.go", go, "Load"),
+        vec![
+            Binding::Module,
+            Binding::Member,
+            Binding::Module,
+            Binding::Local,
+            Binding::Member,
+            Binding::Local,
+        ]
+    );
+}
+
+#[test]
+fn global_statements_keep_the_module_binding() {
+    let text = "count = 0
+
+def bump():
+    global count
+    count += 1
+";
+
+    assert_eq!(
+        resolved("counter.py", text, "count"),
+        vec![Binding::Module, Binding::Module, Binding::Module]
+    );
+}
+
+#[test]
+fn files_without_a_clean_parse_fall_back_to_the_lexer() {
+    assert_eq!(resolve(Path::new("task.rb"), "def run; end\n", "run"), None);
+    assert_eq!(resolve(Path::new("lib.rs"), "fn run( {\n", "run"), None);
+}
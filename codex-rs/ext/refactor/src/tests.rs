@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use codex_config::types::RefactorConfig;
+use codex_extension_api::DenyCommandApprover;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::NoopTurnItemEmitter;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolContributor;
+use codex_extension_api::ToolPayload;
+use codex_utils_output_truncation::TruncationPolicy;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::extension::RefactorExtension;
+use crate::extension::RefactorExtensionConfig;
+use crate::tools::refactor_tool_name;
+
+fn enabled_thread_store() -> ExtensionData {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(RefactorExtensionConfig {
+        enabled: true,
+        refactor: RefactorConfig::default(),
+    });
+    thread_store
+}
+
+#[test]
+fn tools_are_not_contributed_when_disabled() {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(RefactorExtensionConfig {
+        enabled: false,
+        refactor: RefactorConfig::default(),
+    });
+
+    assert!(
+        RefactorExtension
+            .tools(&ExtensionData::new("session"), &thread_store)
+            .is_empty()
+    );
+}
+
+#[test]
+fn install_registers_refactor_tools() {
+    let mut builder = ExtensionRegistryBuilder::<codex_core::config::Config>::new();
+    crate::install(&mut builder);
+    let registry = builder.build();
+    let thread_store = enabled_thread_store();
+
+    let tool_names = registry
+        .tool_contributors()
+        .iter()
+        .flat_map(|contributor| contributor.tools(&ExtensionData::new("session"), &thread_store))
+        .map(|tool| tool.tool_name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        tool_names,
+        vec![
+            refactor_tool_name(crate::RENAME_SYMBOL_TOOL_NAME),
+            refactor_tool_name(crate::MOVE_FILE_TOOL_NAME),
+        ]
+    );
+}
+
+#[test]
+fn move_file_tool_definition_requires_both_paths() {
+    let tools = RefactorExtension.tools(&ExtensionData::new("session"), &enabled_thread_store());
+    let spec = serde_json::to_value(tools[1].spec()).expect("serialize tool spec");
+
+    assert_eq!(
+        spec.pointer("/tools/0/parameters/required"),
+        Some(&json!(["from", "to"]))
+    );
+}
+
+#[tokio::test]
+async fn tools_require_a_local_environment() {
+    let tools = RefactorExtension.tools(&ExtensionData::new("session"), &enabled_thread_store());
+
+    let Err(err) = tools[0]
+        .handle(ToolCall {
+            turn_id: "turn-1".to_string(),
+            call_id: "call-1".to_string(),
+            tool_name: refactor_tool_name(crate::RENAME_SYMBOL_TOOL_NAME),
+            model: "gpt-test".to_string(),
+            codex_turn_metadata: None,
+            truncation_policy: TruncationPolicy::Bytes(1024),
+            conversation_history: codex_extension_api::ConversationHistory::default(),
+            turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+            command_approver: Arc::new(DenyCommandApprover),
+            environments: Vec::new(),
+            payload: ToolPayload::Function {
+                arguments: json!({ "old_name": "a", "new_name": "b" }).to_string(),
+            },
+        })
+        .await
+    else {
+        panic!("rename without a local environment should fail");
+    };
+
+    assert_eq!(
+        err,
+        FunctionCallError::RespondToModel(
+            "refactor tools are only available for local workspaces".to_string()
+        )
+    );
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use codex_config::types::RefactorConfig;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::JsonToolOutput;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolExecutorFuture;
+use codex_extension_api::ToolName;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolSpec;
+use codex_extension_api::local_environment;
+use codex_extension_api::namespaced_function_tool;
+use codex_extension_api::parse_function_arguments;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::MOVE_FILE_TOOL_NAME;
+use crate::REFACTOR_NAMESPACE;
+use crate::RENAME_SYMBOL_TOOL_NAME;
+use crate::plan;
+use crate::plan::RefactorPlan;
+use crate::plan::RenameRequest;
+
+/// Planning lists files with git on this host, so the tools only work against the local checkout.
+const LOCAL_WORKSPACE_REQUIRED: &str = "refactor tools are only available for local workspaces";
+
+pub(crate) fn refactor_tools(config: RefactorConfig) -> Vec<Arc<dyn ToolExecutor<ToolCall>>> {
+    vec![
+        Arc::new(RenameSymbolTool {
+            language_servers: config.language_servers,
+        }),
+        Arc::new(MoveFileTool),
+    ]
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct RenameSymbolArgs {
+    /// Identifier to rename.
+    old_name: String,
+    /// New identifier.
+    new_name: String,
+    /// Only rename in these files or directories, relative to the working directory.
+    paths: Option<Vec<String>>,
+    /// Also rename whole-word matches inside strings and comments. Defaults to false.
+    include_strings_and_comments: Option<bool>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct MoveFileArgs {
+    /// Tracked file to move, relative to the working directory.
+    from: String,
+    /// Destination path, relative to the working directory. Must not exist.
+    to: String,
+}
+
+struct RenameSymbolTool {
+    language_servers: BTreeMap<String, Vec<String>>,
+}
+
+impl ToolExecutor<ToolCall> for RenameSymbolTool {
+    fn tool_name(&self) -> ToolName {
+        refactor_tool_name(RENAME_SYMBOL_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<RenameSymbolArgs, RefactorPlan>(
+            REFACTOR_NAMESPACE,
+            RENAME_SYMBOL_TOOL_NAME,
+            "Plan renaming an identifier across tracked source files. The identifier must be defined in exactly one searched file. Occurrences are renamed in that file, in files that import it or its module, and where that module qualifies them; others, such as same-named methods of other types and local variables that shadow it, are listed in the notes. Matches whole identifiers in code only, skipping strings, comments, and longer names that contain it. If a language server is configured for the defining file, the user is asked to start it and its rename is used instead. Returns an apply_patch patch without writing anything; review it and apply it with apply_patch.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: RenameSymbolArgs = parse_function_arguments(&call)?;
+            let environment = local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?;
+            let request = RenameRequest {
+                old_name: args.old_name,
+                new_name: args.new_name,
+                paths: args.paths.unwrap_or_default(),
+                include_strings_and_comments: args.include_strings_and_comments.unwrap_or(false),
+                language_servers: self.language_servers.clone(),
+            };
+            plan_output(
+                plan::plan_rename(environment, &request, call.command_approver.as_ref()).await,
+            )
+        })
+    }
+}
+
+struct MoveFileTool;
+
+impl ToolExecutor<ToolCall> for MoveFileTool {
+    fn tool_name(&self) -> ToolName {
+        refactor_tool_name(MOVE_FILE_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<MoveFileArgs, RefactorPlan>(
+            REFACTOR_NAMESPACE,
+            MOVE_FILE_TOOL_NAME,
+            "Plan moving a tracked file and updating what refers to it: relative JavaScript and TypeScript imports (including the moved file's own), Python module imports, and verbatim mentions of the old path. Returns an apply_patch patch without writing anything; review it and apply it with apply_patch.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: MoveFileArgs = parse_function_arguments(&call)?;
+            let environment = local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?;
+            plan_output(plan::plan_move(environment, &args.from, &args.to).await)
+        })
+    }
+}
+
+pub(crate) fn refactor_tool_name(name: &str) -> ToolName {
+    ToolName::namespaced(REFACTOR_NAMESPACE, name)
+}
+
+fn plan_output(
+    result: Result<RefactorPlan, String>,
+) -> Result<Box<dyn ToolOutput>, FunctionCallError> {
+    // Invalid names, missing files, and oversized plans are all things the model can fix.
+    let plan = result.map_err(FunctionCallError::RespondToModel)?;
+    Ok(Box::new(JsonToolOutput::new(json!(plan))))
+}
@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::config::Config;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ToolContributor;
+use codex_features::Feature;
+
+use crate::tools;
+
+/// Contributes status and diff tools spanning the repositories listed under `[related_repos]`.
+#[derive(Clone, Default)]
+pub(crate) struct RelatedReposExtension;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RelatedRepoEntry {
+    pub(crate) name: String,
+    pub(crate) path: PathBuf,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RelatedReposExtensionConfig {
+    pub(crate) enabled: bool,
+    pub(crate) repos: Vec<RelatedRepoEntry>,
+}
+
+impl RelatedReposExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::RelatedRepoTools),
+            repos: config
+                .related_repos
+                .iter()
+                .map(|repo| RelatedRepoEntry {
+                    name: repo.name.clone(),
+                    path: repo.path.to_path_buf(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ToolContributor for RelatedReposExtension {
+    fn tools(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+    ) -> Vec<Arc<dyn codex_extension_api::ToolExecutor<codex_extension_api::ToolCall>>> {
+        let Some(config) = thread_store.get::<RelatedReposExtensionConfig>() else {
+            return Vec::new();
+        };
+        if !config.enabled || config.repos.is_empty() {
+            return Vec::new();
+        }
+
+        tools::related_repos_tools(&config.repos)
+    }
+}
+
+/// Installs the related repos extension contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    registry.thread_config(RelatedReposExtensionConfig::from_config);
+    registry.tool_contributor(Arc::new(RelatedReposExtension));
+}
@@ -0,0 +1,16 @@
+mod extension;
+mod tools;
+
+pub use extension::install;
+
+/// Combined diffs longer than this are cut at a line boundary.
+pub(crate) const MAX_DIFF_BYTES: usize = 200_000;
+pub(crate) const MAX_STATUS_ENTRIES: usize = 200;
+
+pub(crate) const RELATED_REPOS_NAMESPACE: &str = "related_repos";
+pub(crate) const STATUS_TOOL_NAME: &str = "status";
+pub(crate) const DIFF_TOOL_NAME: &str = "diff";
+pub(crate) const COMMIT_TOOL_NAME: &str = "commit";
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,413 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use codex_extension_api::CommandApprovalFuture;
+use codex_extension_api::CommandApprovalRequest;
+use codex_extension_api::CommandApprover;
+use codex_extension_api::DenyCommandApprover;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::NoopTurnItemEmitter;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolContributor;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolPayload;
+use codex_utils_output_truncation::TruncationPolicy;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::extension::RelatedRepoEntry;
+use crate::extension::RelatedReposExtension;
+use crate::extension::RelatedReposExtensionConfig;
+use crate::tools::RelatedReposDiff;
+use crate::tools::combine_diffs;
+use crate::tools::related_repos_tool_name;
+use crate::tools::repo_status;
+
+fn repo(name: &str, path: &str) -> RelatedRepoEntry {
+    RelatedRepoEntry {
+        name: name.to_string(),
+        path: PathBuf::from(path),
+    }
+}
+
+fn thread_store_with(repos: Vec<RelatedRepoEntry>) -> ExtensionData {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(RelatedReposExtensionConfig {
+        enabled: true,
+        repos,
+    });
+    thread_store
+}
+
+struct ApproveCommands;
+
+/// Records every approval request and declines the one for `git <declined>`.
+struct RecordApprovals {
+    declined: &'static str,
+    commands: Mutex<Vec<Vec<String>>>,
+}
+
+impl CommandApprover for RecordApprovals {
+    fn request_approval<'a>(
+        &'a self,
+        request: CommandApprovalRequest,
+    ) -> CommandApprovalFuture<'a> {
+        let approved = request.command.get(1).map(String::as_str) != Some(self.declined);
+        self.commands
+            .lock()
+            .expect("approvals lock")
+            .push(request.command);
+        Box::pin(std::future::ready(approved))
+    }
+}
+
+impl CommandApprover for ApproveCommands {
+    fn request_approval<'a>(
+        &'a self,
+        _request: CommandApprovalRequest,
+    ) -> CommandApprovalFuture<'a> {
+        Box::pin(std::future::ready(true))
+    }
+}
+
+fn tool_call(name: &str, arguments: serde_json::Value) -> ToolCall {
+    tool_call_with_approver(name, arguments, Arc::new(DenyCommandApprover))
+}
+
+fn tool_call_with_approver(
+    name: &str,
+    arguments: serde_json::Value,
+    command_approver: Arc<dyn CommandApprover>,
+) -> ToolCall {
+    ToolCall {
+        turn_id: "turn-1".to_string(),
+        call_id: "call-1".to_string(),
+        tool_name: related_repos_tool_name(name),
+        model: "gpt-test".to_string(),
+        codex_turn_metadata: None,
+        truncation_policy: TruncationPolicy::Bytes(1024),
+        conversation_history: codex_extension_api::ConversationHistory::default(),
+        turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+        command_approver,
+        environments: Vec::new(),
+        payload: ToolPayload::Function {
+            arguments: arguments.to_string(),
+        },
+    }
+}
+
+#[test]
+fn tools_are_not_contributed_without_related_repos() {
+    assert!(
+        RelatedReposExtension
+            .tools(
+                &ExtensionData::new("session"),
+                &thread_store_with(Vec::new())
+            )
+            .is_empty()
+    );
+}
+
+#[test]
+fn tools_are_not_contributed_when_the_feature_is_disabled() {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(RelatedReposExtensionConfig {
+        enabled: false,
+        repos: vec![repo("client", "/work/client")],
+    });
+
+    assert!(
+        RelatedReposExtension
+            .tools(&ExtensionData::new("session"), &thread_store)
+            .is_empty()
+    );
+}
+
+#[test]
+fn install_registers_related_repos_tools() {
+    let mut builder = ExtensionRegistryBuilder::<codex_core::config::Config>::new();
+    crate::install(&mut builder);
+    let registry = builder.build();
+    let thread_store = thread_store_with(vec![repo("client", "/work/client")]);
+
+    let tool_names = registry
+        .tool_contributors()
+        .iter()
+        .flat_map(|contributor| contributor.tools(&ExtensionData::new("session"), &thread_store))
+        .map(|tool| tool.tool_name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        tool_names,
+        vec![
+            related_repos_tool_name(crate::STATUS_TOOL_NAME),
+            related_repos_tool_name(crate::DIFF_TOOL_NAME),
+            related_repos_tool_name(crate::COMMIT_TOOL_NAME),
+        ]
+    );
+}
+
+#[test]
+fn tool_descriptions_list_the_configured_repos() {
+    let tools = RelatedReposExtension.tools(
+        &ExtensionData::new("session"),
+        &thread_store_with(vec![repo("client", "/work/client")]),
+    );
+    let spec = serde_json::to_value(tools[0].spec()).expect("serialize tool spec");
+
+    let description = spec
+        .pointer("/tools/0/description")
+        .and_then(serde_json::Value::as_str)
+        .expect("description");
+    assert!(
+        description.contains("`client` at /work/client"),
+        "{description}"
+    );
+}
+
+#[tokio::test]
+async fn unknown_repo_names_are_reported_to_the_model() {
+    let tools = RelatedReposExtension.tools(
+        &ExtensionData::new("session"),
+        &thread_store_with(vec![
+            repo("api", "/work/api"),
+            repo("client", "/work/client"),
+        ]),
+    );
+
+    let Err(err) = tools[1]
+        .handle(tool_call(
+            crate::DIFF_TOOL_NAME,
+            json!({ "repos": ["web"] }),
+        ))
+        .await
+    else {
+        panic!("diff of an unknown repo should fail");
+    };
+
+    assert_eq!(
+        err,
+        FunctionCallError::RespondToModel(
+            "unknown related repository `web`; configured repositories: api, client".to_string()
+        )
+    );
+}
+
+#[test]
+fn combined_diff_has_a_header_per_repo_and_cuts_at_a_line_boundary() {
+    let api = repo("api", "/work/api");
+    let client = repo("client", "/work/client");
+    let sections = vec![(&api, "+one\n+two\n".to_string()), (&client, String::new())];
+
+    assert_eq!(
+        combine_diffs(&sections, /*max_bytes*/ 1_000),
+        RelatedReposDiff {
+            diff: "# api (/work/api)\n+one\n+two\n\n# client (/work/client)\n(no changes)\n\n"
+                .to_string(),
+            truncated: false,
+        }
+    );
+    assert_eq!(
+        combine_diffs(&sections, /*max_bytes*/ 25),
+        RelatedReposDiff {
+            diff: "# api (/work/api)\n+one\n".to_string(),
+            truncated: true,
+        }
+    );
+}
+
+#[tokio::test]
+async fn status_reports_missing_repositories() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let missing = RelatedRepoEntry {
+        name: "client".to_string(),
+        path: dir.path().to_path_buf(),
+    };
+
+    let call = tool_call_with_approver(
+        crate::STATUS_TOOL_NAME,
+        json!({}),
+        Arc::new(ApproveCommands),
+    );
+
+    let status = repo_status(&call, &missing).await;
+
+    assert_eq!(status.error.as_deref(), Some("not a git repository"));
+    assert!(status.changes.is_empty());
+}
+
+#[tokio::test]
+async fn status_is_not_read_without_approval() {
+    let (_dir, api) = init_repo("api");
+
+    let status = repo_status(&tool_call(crate::STATUS_TOOL_NAME, json!({})), &api).await;
+
+    assert_eq!(
+        status.error.as_deref(),
+        Some("the user did not approve running git in this repository")
+    );
+    assert_eq!(status.branch, None);
+    assert_eq!(status.head, None);
+}
+
+fn git(repo: &Path, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .current_dir(repo)
+        .args(args)
+        .output()
+        .expect("run git");
+    assert!(output.status.success(), "git {args:?}: {output:?}");
+    String::from_utf8(output.stdout).expect("utf-8 git output")
+}
+
+fn init_repo(name: &str) -> (tempfile::TempDir, RelatedRepoEntry) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    for args in [
+        &["init", "--quiet", "--initial-branch", "main"][..],
+        &["config", "user.email", "codex@example.com"],
+        &["config", "user.name", "Codex"],
+    ] {
+        git(dir.path(), args);
+    }
+    std::fs::write(dir.path().join("README.md"), "hello\n").expect("write readme");
+    git(dir.path(), &["add", "README.md"]);
+    git(dir.path(), &["commit", "--quiet", "-m", "init"]);
+    let entry = RelatedRepoEntry {
+        name: name.to_string(),
+        path: dir.path().to_path_buf(),
+    };
+    (dir, entry)
+}
+
+fn commit_tool(repos: Vec<RelatedRepoEntry>) -> Arc<dyn ToolExecutor<ToolCall>> {
+    RelatedReposExtension
+        .tools(&ExtensionData::new("session"), &thread_store_with(repos))
+        .into_iter()
+        .find(|tool| tool.tool_name() == related_repos_tool_name(crate::COMMIT_TOOL_NAME))
+        .expect("commit tool")
+}
+
+fn output_json(output: &dyn ToolOutput) -> serde_json::Value {
+    output
+        .post_tool_use_response(
+            "call-1",
+            &ToolPayload::Function {
+                arguments: String::new(),
+            },
+        )
+        .expect("json output")
+}
+
+#[tokio::test]
+async fn commit_uses_one_branch_and_message_across_changed_repos() {
+    let (api_dir, api) = init_repo("api");
+    let (client_dir, client) = init_repo("client");
+    let (_docs_dir, docs) = init_repo("docs");
+    std::fs::write(api_dir.path().join("api.rs"), "fn api() {}\n").expect("write api");
+    std::fs::write(client_dir.path().join("README.md"), "hello\nclient\n").expect("write client");
+
+    let output = commit_tool(vec![api, client, docs])
+        .handle(tool_call_with_approver(
+            crate::COMMIT_TOOL_NAME,
+            json!({ "message": "Rename the field", "branch": "rename-field" }),
+            Arc::new(ApproveCommands),
+        ))
+        .await
+        .expect("commit");
+
+    let mut expected = Vec::new();
+    for (name, dir) in [("api", &api_dir), ("client", &client_dir)] {
+        assert_eq!(
+            git(dir.path(), &["log", "-1", "--format=%s"]),
+            "Rename the field\n"
+        );
+        assert_eq!(git(dir.path(), &["status", "--porcelain"]), "");
+        expected.push(json!({
+            "name": name,
+            "branch": "rename-field",
+            "commit": git(dir.path(), &["rev-parse", "HEAD"]).trim(),
+            "pull_request": null,
+            "error": null,
+        }));
+    }
+    assert_eq!(output_json(output.as_ref()), json!({ "repos": expected }));
+}
+
+#[tokio::test]
+async fn commit_is_not_made_without_approval() {
+    let (dir, api) = init_repo("api");
+    std::fs::write(dir.path().join("README.md"), "hello\nwip\n").expect("write readme");
+
+    let output = commit_tool(vec![api])
+        .handle(tool_call(
+            crate::COMMIT_TOOL_NAME,
+            json!({ "message": "Update readme", "branch": "update-readme" }),
+        ))
+        .await
+        .expect("commit");
+
+    assert_eq!(
+        output_json(output.as_ref()),
+        json!({ "repos": [{
+            "name": "api",
+            "branch": null,
+            "commit": null,
+            "pull_request": null,
+            "error": "the user did not approve running git in this repository",
+        }] })
+    );
+    assert_eq!(git(dir.path(), &["branch", "--show-current"]), "main\n");
+    assert_eq!(git(dir.path(), &["rev-list", "--count", "HEAD"]), "1\n");
+}
+
+#[tokio::test]
+async fn commit_asks_before_every_command_and_stops_at_a_declined_push() {
+    let (dir, api) = init_repo("api");
+    std::fs::write(dir.path().join("README.md"), "hello\nwip\n").expect("write readme");
+    let approvals = Arc::new(RecordApprovals {
+        declined: "push",
+        commands: Mutex::new(Vec::new()),
+    });
+
+    let output = commit_tool(vec![api])
+        .handle(tool_call_with_approver(
+            crate::COMMIT_TOOL_NAME,
+            json!({
+                "message": "Update readme",
+                "branch": "update-readme",
+                "open_pull_requests": true,
+            }),
+            approvals.clone(),
+        ))
+        .await
+        .expect("commit");
+
+    let commands = approvals.commands.lock().expect("approvals lock").clone();
+    assert_eq!(
+        commands,
+        [
+            &["git", "switch", "--create", "update-readme"][..],
+            &["git", "add", "--all"],
+            &["git", "commit", "--message", "Update readme"],
+            &["git", "push", "--set-upstream", "origin", "update-readme"],
+        ]
+        .iter()
+        .map(|command| command.iter().map(ToString::to_string).collect::<Vec<_>>())
+        .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        output_json(output.as_ref()),
+        json!({ "repos": [{
+            "name": "api",
+            "branch": "update-readme",
+            "commit": git(dir.path(), &["rev-parse", "HEAD"]).trim(),
+            "pull_request": null,
+            "error": "the user did not approve running git in this repository",
+        }] })
+    );
+}
@@ -0,0 +1,504 @@
+use std::sync::Arc;
+
+use codex_extension_api::CommandApprovalRequest;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::JsonToolOutput;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolExecutorFuture;
+use codex_extension_api::ToolName;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolSpec;
+use codex_extension_api::namespaced_function_tool;
+use codex_extension_api::parse_function_arguments;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::COMMIT_TOOL_NAME;
+use crate::DIFF_TOOL_NAME;
+use crate::MAX_DIFF_BYTES;
+use crate::MAX_STATUS_ENTRIES;
+use crate::RELATED_REPOS_NAMESPACE;
+use crate::STATUS_TOOL_NAME;
+use crate::extension::RelatedRepoEntry;
+
+pub(crate) fn related_repos_tools(
+    repos: &[RelatedRepoEntry],
+) -> Vec<Arc<dyn ToolExecutor<ToolCall>>> {
+    let repos: Arc<[RelatedRepoEntry]> = repos.into();
+    vec![
+        Arc::new(StatusTool {
+            repos: repos.clone(),
+        }),
+        Arc::new(DiffTool {
+            repos: repos.clone(),
+        }),
+        Arc::new(CommitTool { repos }),
+    ]
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct RelatedReposArgs {
+    /// Names of the repositories to include. Defaults to every related repository.
+    repos: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct CommitArgs {
+    /// Names of the repositories to commit in. Defaults to every related repository with
+    /// uncommitted changes.
+    repos: Option<Vec<String>>,
+    /// Commit message used in every repository. Its first line titles the pull requests.
+    message: String,
+    /// Branch to create and commit on in every repository. Defaults to each repository's
+    /// current branch.
+    branch: Option<String>,
+    /// Push the branch and open a pull request in each repository with `gh`.
+    #[serde(default)]
+    open_pull_requests: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct RelatedRepoStatus {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    /// Current branch, or `None` when HEAD is detached.
+    pub(crate) branch: Option<String>,
+    pub(crate) head: Option<String>,
+    /// `git status --porcelain` entries such as ` M src/lib.rs`.
+    pub(crate) changes: Vec<String>,
+    /// True when `changes` was capped.
+    pub(crate) changes_truncated: bool,
+    /// Set when the path is missing or is not a git repository.
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+struct RelatedReposStatus {
+    repos: Vec<RelatedRepoStatus>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct RelatedReposDiff {
+    /// Unified diffs against HEAD, each preceded by a `# <name> (<path>)` header line.
+    pub(crate) diff: String,
+    /// True when the combined diff was cut short.
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct RelatedRepoCommit {
+    pub(crate) name: String,
+    /// Branch the commit was made on.
+    pub(crate) branch: Option<String>,
+    /// Hash of the new commit.
+    pub(crate) commit: Option<String>,
+    /// URL of the pull request, when one was opened.
+    pub(crate) pull_request: Option<String>,
+    /// Why the repository was not committed, or why its pull request was not opened.
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub(crate) struct RelatedReposCommit {
+    pub(crate) repos: Vec<RelatedRepoCommit>,
+}
+
+struct StatusTool {
+    repos: Arc<[RelatedRepoEntry]>,
+}
+
+impl ToolExecutor<ToolCall> for StatusTool {
+    fn tool_name(&self) -> ToolName {
+        related_repos_tool_name(STATUS_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<RelatedReposArgs, RelatedReposStatus>(
+            RELATED_REPOS_NAMESPACE,
+            STATUS_TOOL_NAME,
+            &format!(
+                "Show the branch, HEAD commit, and uncommitted changes of each related repository. {}",
+                describe_repos(&self.repos)
+            ),
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: RelatedReposArgs = parse_function_arguments(&call)?;
+            let mut repos = Vec::new();
+            for repo in select_repos(&self.repos, args.repos.as_deref())? {
+                repos.push(repo_status(&call, repo).await);
+            }
+            json_output(RelatedReposStatus { repos })
+        })
+    }
+}
+
+struct DiffTool {
+    repos: Arc<[RelatedRepoEntry]>,
+}
+
+impl ToolExecutor<ToolCall> for DiffTool {
+    fn tool_name(&self) -> ToolName {
+        related_repos_tool_name(DIFF_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<RelatedReposArgs, RelatedReposDiff>(
+            RELATED_REPOS_NAMESPACE,
+            DIFF_TOOL_NAME,
+            &format!(
+                "Show one combined diff of the uncommitted changes to tracked files across related repositories, with a header per repository. {}",
+                describe_repos(&self.repos)
+            ),
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: RelatedReposArgs = parse_function_arguments(&call)?;
+            let mut sections = Vec::new();
+            for repo in select_repos(&self.repos, args.repos.as_deref())? {
+                let command = [
+                    "diff",
+                    "--no-color",
+                    "--no-ext-diff",
+                    "--no-textconv",
+                    "HEAD",
+                ];
+                let reason = format!(
+                    "Read the uncommitted changes in related repository `{}`",
+                    repo.name
+                );
+                let diff = approved_git(&call, repo, &command, reason).await;
+                sections.push((repo, diff.unwrap_or_else(|err| format!("error: {err}\n"))));
+            }
+            json_output(combine_diffs(&sections, MAX_DIFF_BYTES))
+        })
+    }
+}
+
+struct CommitTool {
+    repos: Arc<[RelatedRepoEntry]>,
+}
+
+impl ToolExecutor<ToolCall> for CommitTool {
+    fn tool_name(&self) -> ToolName {
+        related_repos_tool_name(COMMIT_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<CommitArgs, RelatedReposCommit>(
+            RELATED_REPOS_NAMESPACE,
+            COMMIT_TOOL_NAME,
+            &format!(
+                "Commit the uncommitted changes of several related repositories together: every repository gets the same message and, if given, the same new branch, and can optionally be pushed with a pull request opened through `gh`. The user approves each `git` and `gh` command before it runs. {}",
+                describe_repos(&self.repos)
+            ),
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: CommitArgs = parse_function_arguments(&call)?;
+            if args.message.trim().is_empty() {
+                return Err(FunctionCallError::RespondToModel(
+                    "message must not be empty".to_string(),
+                ));
+            }
+            let explicit = args.repos.is_some();
+            let mut changed = Vec::new();
+            for repo in select_repos(&self.repos, args.repos.as_deref())? {
+                let has_changes = codex_git_utils::get_status_entries(&repo.path)
+                    .await
+                    .is_some_and(|entries| !entries.is_empty());
+                if has_changes || explicit {
+                    changed.push((repo, has_changes));
+                }
+            }
+            let names = changed
+                .iter()
+                .map(|(repo, _)| format!("`{}`", repo.name))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let mut repos = Vec::new();
+            for (repo, has_changes) in changed {
+                let mut result = RelatedRepoCommit {
+                    name: repo.name.clone(),
+                    branch: None,
+                    commit: None,
+                    pull_request: None,
+                    error: None,
+                };
+                if !has_changes {
+                    result.error = Some("no uncommitted changes".to_string());
+                } else if let Err(err) = commit_repo(&call, repo, &args, &names, &mut result).await
+                {
+                    result.error = Some(err);
+                }
+                repos.push(result);
+            }
+            json_output(RelatedReposCommit { repos })
+        })
+    }
+}
+
+/// Commits all changes in `repo`, then pushes and opens a pull request when asked. Every command
+/// is approved on its own right before it runs, so the user sees exactly what each step does and
+/// can stop before the push. Progress is recorded in `result` so a declined or failed push still
+/// reports the commit.
+async fn commit_repo(
+    call: &ToolCall,
+    repo: &RelatedRepoEntry,
+    args: &CommitArgs,
+    coordinated_repos: &str,
+    result: &mut RelatedRepoCommit,
+) -> Result<(), String> {
+    let name = &repo.name;
+    if let Some(branch) = &args.branch
+        && codex_git_utils::current_branch_name(&repo.path)
+            .await
+            .as_ref()
+            != Some(branch)
+    {
+        let reason =
+            format!("Create branch `{branch}` in related repository `{name}` to commit on");
+        approved_git(call, repo, &["switch", "--create", branch], reason).await?;
+    }
+    let reason = format!("Stage every change in related repository `{name}` for the commit");
+    approved_git(call, repo, &["add", "--all"], reason).await?;
+    let reason = format!("Commit the staged changes in related repository `{name}`");
+    approved_git(call, repo, &["commit", "--message", &args.message], reason).await?;
+    result.branch = codex_git_utils::current_branch_name(&repo.path).await;
+    result.commit = codex_git_utils::get_head_commit_hash(&repo.path)
+        .await
+        .map(|sha| sha.0);
+    if !args.open_pull_requests {
+        return Ok(());
+    }
+
+    let Some(branch) = result.branch.clone() else {
+        return Err("committed, but HEAD is detached so no pull request was opened".to_string());
+    };
+    let reason = format!("Push branch `{branch}` of related repository `{name}` to `origin`");
+    approved_git(
+        call,
+        repo,
+        &["push", "--set-upstream", "origin", &branch],
+        reason,
+    )
+    .await?;
+    let (title, body) = args
+        .message
+        .trim()
+        .split_once('\n')
+        .map_or((args.message.trim(), ""), |(title, body)| {
+            (title, body.trim())
+        });
+    let body = format!("{body}\n\nPart of a coordinated change across {coordinated_repos}.");
+    let reason =
+        format!("Open a pull request for branch `{branch}` of related repository `{name}`");
+    let url = approved_run(
+        call,
+        repo,
+        "gh",
+        &[
+            "pr",
+            "create",
+            "--head",
+            &branch,
+            "--title",
+            title,
+            "--body",
+            body.trim(),
+        ],
+        reason,
+    )
+    .await?;
+    result.pull_request = url.lines().last().map(str::to_string);
+    Ok(())
+}
+
+async fn approved_git(
+    call: &ToolCall,
+    repo: &RelatedRepoEntry,
+    args: &[&str],
+    reason: String,
+) -> Result<String, String> {
+    approved_run(call, repo, "git", args, reason).await
+}
+
+/// Runs `program args` in `repo` once the user approves it.
+async fn approved_run(
+    call: &ToolCall,
+    repo: &RelatedRepoEntry,
+    program: &str,
+    args: &[&str],
+    reason: String,
+) -> Result<String, String> {
+    approve(call, repo, program, args, reason).await?;
+    run(repo, program, args).await
+}
+
+/// Asks the user before running `program args` in `repo`, which happens on the host outside the
+/// sandbox. Approval covers one run, so every call asks again.
+async fn approve(
+    call: &ToolCall,
+    repo: &RelatedRepoEntry,
+    program: &str,
+    args: &[&str],
+    reason: String,
+) -> Result<(), String> {
+    let cwd = AbsolutePathBuf::from_absolute_path(&repo.path)
+        .map_err(|err| format!("invalid repository path: {err}"))?;
+    let command = std::iter::once(program)
+        .chain(args.iter().copied())
+        .map(str::to_string)
+        .collect();
+    let approved = call
+        .command_approver
+        .request_approval(CommandApprovalRequest {
+            command,
+            cwd,
+            reason,
+        })
+        .await;
+    if approved {
+        Ok(())
+    } else {
+        Err(format!(
+            "the user did not approve running {program} in this repository"
+        ))
+    }
+}
+
+pub(crate) fn related_repos_tool_name(name: &str) -> ToolName {
+    ToolName::namespaced(RELATED_REPOS_NAMESPACE, name)
+}
+
+/// Lists the configured repositories so the model knows which names and paths it can use.
+fn describe_repos(repos: &[RelatedRepoEntry]) -> String {
+    let repos = repos
+        .iter()
+        .map(|repo| format!("`{}` at {}", repo.name, repo.path.display()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "Related repositories: {repos}. Read and edit their files by path with the usual tools; use `related_repos.commit` to commit and open pull requests across them together."
+    )
+}
+
+fn select_repos<'a>(
+    repos: &'a [RelatedRepoEntry],
+    names: Option<&[String]>,
+) -> Result<Vec<&'a RelatedRepoEntry>, FunctionCallError> {
+    let Some(names) = names else {
+        return Ok(repos.iter().collect());
+    };
+    names
+        .iter()
+        .map(|name| {
+            repos.iter().find(|repo| &repo.name == name).ok_or_else(|| {
+                let known = repos
+                    .iter()
+                    .map(|repo| repo.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                FunctionCallError::RespondToModel(format!(
+                    "unknown related repository `{name}`; configured repositories: {known}"
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Reads the branch, HEAD, and `git status` of `repo` once the user approves running git there.
+pub(crate) async fn repo_status(call: &ToolCall, repo: &RelatedRepoEntry) -> RelatedRepoStatus {
+    let mut status = RelatedRepoStatus {
+        name: repo.name.clone(),
+        path: repo.path.display().to_string(),
+        branch: None,
+        head: None,
+        changes: Vec::new(),
+        changes_truncated: false,
+        error: None,
+    };
+    let reason = format!(
+        "Read the branch, HEAD commit, and uncommitted changes of related repository `{}`",
+        repo.name
+    );
+    if let Err(err) = approve(call, repo, "git", &["status", "--porcelain"], reason).await {
+        status.error = Some(err);
+        return status;
+    }
+    let Some(mut changes) = codex_git_utils::get_status_entries(&repo.path).await else {
+        status.error = Some("not a git repository".to_string());
+        return status;
+    };
+    status.changes_truncated = changes.len() > MAX_STATUS_ENTRIES;
+    changes.truncate(MAX_STATUS_ENTRIES);
+    status.changes = changes;
+    status.branch = codex_git_utils::current_branch_name(&repo.path).await;
+    status.head = codex_git_utils::get_head_commit_hash(&repo.path)
+        .await
+        .map(|sha| sha.0);
+    status
+}
+
+/// Joins per-repository diffs under header lines, cutting at a line boundary past `max_bytes`.
+pub(crate) fn combine_diffs(
+    sections: &[(&RelatedRepoEntry, String)],
+    max_bytes: usize,
+) -> RelatedReposDiff {
+    let mut diff = String::new();
+    for (repo, section) in sections {
+        diff.push_str(&format!("# {} ({})\n", repo.name, repo.path.display()));
+        if section.is_empty() {
+            diff.push_str("(no changes)\n");
+        } else {
+            diff.push_str(section);
+        }
+        diff.push('\n');
+    }
+    if diff.len() <= max_bytes {
+        return RelatedReposDiff {
+            diff,
+            truncated: false,
+        };
+    }
+    let mut end = max_bytes;
+    while !diff.is_char_boundary(end) {
+        end -= 1;
+    }
+    let end = diff[..end].rfind('\n').map_or(end, |newline| newline + 1);
+    diff.truncate(end);
+    RelatedReposDiff {
+        diff,
+        truncated: true,
+    }
+}
+
+async fn run(repo: &RelatedRepoEntry, program: &str, args: &[&str]) -> Result<String, String> {
+    let output = tokio::process::Command::new(program)
+        .current_dir(&repo.path)
+        .args(args)
+        .output()
+        .await
+        .map_err(|err| format!("failed to run {program}: {err}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn json_output(value: impl Serialize) -> Result<Box<dyn ToolOutput>, FunctionCallError> {
+    Ok(Box::new(JsonToolOutput::new(json!(value))))
+}
@@ -0,0 +1,251 @@
+use std::sync::Arc;
+use std::sync::Weak;
+use std::time::Instant;
+
+use codex_core::ThreadManager;
+use codex_core::config::Config;
+use codex_extension_api::ConfigContributor;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionFuture;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ThreadLifecycleContributor;
+use codex_extension_api::ThreadStartInput;
+use codex_extension_api::TokenUsageContributor;
+use codex_extension_api::ToolFinishInput;
+use codex_extension_api::ToolLifecycleContributor;
+use codex_extension_api::ToolLifecycleFuture;
+use codex_extension_api::ToolStartInput;
+use codex_extension_api::TurnAbortInput;
+use codex_extension_api::TurnLifecycleContributor;
+use codex_extension_api::TurnStartInput;
+use codex_extension_api::TurnStopInput;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::TokenUsageInfo;
+
+use crate::limits::RunLimitsState;
+use crate::steering::wrap_up_steering_item;
+
+/// Time-boxes a session: asks the model to wrap up as a run limit approaches, interrupts the
+/// running turn once the duration or token limit is reached, and stops automatic goal
+/// continuation once any limit is reached. Turns the user starts are never blocked.
+pub(crate) struct RunLimitsExtension {
+    state_db: Option<Arc<codex_state::StateRuntime>>,
+    thread_manager: Weak<ThreadManager>,
+}
+
+impl RunLimitsExtension {
+    /// Injects the wrap-up instructions into the running turn once a limit is near.
+    async fn maybe_wrap_up(&self, thread_store: &ExtensionData) {
+        let Some(state) = thread_store.get::<RunLimitsState>() else {
+            return;
+        };
+        let Some(limit) = state.wrap_up_due(Instant::now()) else {
+            return;
+        };
+        let Ok(thread_id) = ThreadId::from_string(thread_store.level_id()) else {
+            return;
+        };
+        let Some(thread_manager) = self.thread_manager.upgrade() else {
+            tracing::debug!("skipping run limit wrap-up because thread manager is unavailable");
+            return;
+        };
+        let Ok(thread) = thread_manager.get_thread(thread_id).await else {
+            tracing::debug!("skipping run limit wrap-up because live thread is unavailable");
+            return;
+        };
+        // Leave the wrap-up pending when no turn is active so the next usage update retries it.
+        if thread
+            .inject_if_running(vec![wrap_up_steering_item(limit)])
+            .await
+            .is_ok()
+        {
+            state.mark_wrap_up_sent();
+        }
+    }
+
+    /// Interrupts the running turn once the duration or token limit is reached. Checked on every
+    /// usage update and tool call, and by a timer for the duration limit.
+    async fn enforce(&self, thread_store: &ExtensionData) {
+        let Some(state) = thread_store.get::<RunLimitsState>() else {
+            return;
+        };
+        let Ok(thread_id) = ThreadId::from_string(thread_store.level_id()) else {
+            return;
+        };
+        interrupt_if_due(&self.thread_manager, thread_id, &state).await;
+    }
+
+    /// Checks the duration limit when it runs out, in case the turn is then inside a long tool
+    /// call or model request.
+    fn schedule_duration_check(&self, thread_store: &ExtensionData, state: Arc<RunLimitsState>) {
+        let Some(time_left) = state.time_left(Instant::now()) else {
+            return;
+        };
+        let Ok(thread_id) = ThreadId::from_string(thread_store.level_id()) else {
+            return;
+        };
+        let thread_manager = self.thread_manager.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(time_left).await;
+            interrupt_if_due(&thread_manager, thread_id, &state).await;
+        });
+    }
+
+    /// Holds automatic goal continuation until the user's next turn once a limit is reached.
+    async fn stop_if_limit_reached(&self, thread_store: &ExtensionData) {
+        let Some(state) = thread_store.get::<RunLimitsState>() else {
+            return;
+        };
+        let Some(limit) = state.limit_reached(Instant::now()) else {
+            return;
+        };
+        let Some(state_db) = self.state_db.as_ref() else {
+            return;
+        };
+        let Ok(thread_id) = ThreadId::from_string(thread_store.level_id()) else {
+            return;
+        };
+        tracing::info!(?limit, "run limit reached for thread {thread_id}");
+        if let Err(err) = state_db
+            .thread_goals()
+            .defer_thread_goal_continuation(thread_id)
+            .await
+        {
+            tracing::warn!("failed to defer goal continuation after run limit: {err}");
+        }
+    }
+}
+
+impl ThreadLifecycleContributor<Config> for RunLimitsExtension {
+    fn on_thread_start<'a>(
+        &'a self,
+        input: ThreadStartInput<'a, Config>,
+    ) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            input
+                .thread_store
+                .insert(RunLimitsState::new(input.config.run_limits, Instant::now()));
+        })
+    }
+}
+
+impl ConfigContributor<Config> for RunLimitsExtension {
+    fn on_config_changed(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+        _previous_config: &Config,
+        new_config: &Config,
+    ) {
+        if let Some(state) = thread_store.get::<RunLimitsState>() {
+            state.set_limits(new_config.run_limits);
+        }
+    }
+}
+
+impl TurnLifecycleContributor for RunLimitsExtension {
+    fn on_turn_start<'a>(&'a self, input: TurnStartInput<'a>) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            let Some(state) = input.thread_store.get::<RunLimitsState>() else {
+                return;
+            };
+            state.start_turn(input.token_usage_at_turn_start);
+            self.schedule_duration_check(input.thread_store, state);
+            self.maybe_wrap_up(input.thread_store).await;
+        })
+    }
+
+    fn on_turn_stop<'a>(&'a self, input: TurnStopInput<'a>) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            if let Some(state) = input.thread_store.get::<RunLimitsState>() {
+                state.end_turn();
+            }
+            self.stop_if_limit_reached(input.thread_store).await;
+        })
+    }
+
+    fn on_turn_abort<'a>(&'a self, input: TurnAbortInput<'a>) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            if let Some(state) = input.thread_store.get::<RunLimitsState>() {
+                state.end_turn();
+            }
+            self.stop_if_limit_reached(input.thread_store).await;
+        })
+    }
+}
+
+impl TokenUsageContributor for RunLimitsExtension {
+    fn on_token_usage<'a>(
+        &'a self,
+        _session_store: &'a ExtensionData,
+        thread_store: &'a ExtensionData,
+        _turn_store: &'a ExtensionData,
+        token_usage: &'a TokenUsageInfo,
+    ) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            let Some(state) = thread_store.get::<RunLimitsState>() else {
+                return;
+            };
+            state.record_token_usage(&token_usage.total_token_usage);
+            self.maybe_wrap_up(thread_store).await;
+            self.enforce(thread_store).await;
+        })
+    }
+}
+
+impl ToolLifecycleContributor for RunLimitsExtension {
+    fn on_tool_start<'a>(&'a self, input: ToolStartInput<'a>) -> ToolLifecycleFuture<'a> {
+        Box::pin(self.enforce(input.thread_store))
+    }
+
+    fn on_tool_finish<'a>(&'a self, input: ToolFinishInput<'a>) -> ToolLifecycleFuture<'a> {
+        Box::pin(self.enforce(input.thread_store))
+    }
+}
+
+async fn interrupt_if_due(
+    thread_manager: &Weak<ThreadManager>,
+    thread_id: ThreadId,
+    state: &RunLimitsState,
+) {
+    let Some(limit) = state.interrupt_due(Instant::now()) else {
+        return;
+    };
+    let Some(thread_manager) = thread_manager.upgrade() else {
+        tracing::debug!("skipping run limit interrupt because thread manager is unavailable");
+        return;
+    };
+    let Ok(thread) = thread_manager.get_thread(thread_id).await else {
+        tracing::debug!("skipping run limit interrupt because live thread is unavailable");
+        return;
+    };
+    tracing::info!(
+        ?limit,
+        "interrupting turn at run limit for thread {thread_id}"
+    );
+    match thread.submit(Op::Interrupt).await {
+        Ok(_) => state.mark_interrupted(),
+        Err(err) => tracing::warn!("failed to interrupt turn at run limit: {err}"),
+    }
+}
+
+/// Installs the run limits extension contributors into the extension registry.
+///
+/// Without a state DB, run limits still steer the model to wrap up but cannot hold automatic goal
+/// continuation.
+pub fn install(
+    registry: &mut ExtensionRegistryBuilder<Config>,
+    state_db: Option<Arc<codex_state::StateRuntime>>,
+    thread_manager: Weak<ThreadManager>,
+) {
+    let extension = Arc::new(RunLimitsExtension {
+        state_db,
+        thread_manager,
+    });
+    registry.thread_lifecycle_contributor(extension.clone());
+    registry.config_contributor(extension.clone());
+    registry.turn_lifecycle_contributor(extension.clone());
+    registry.tool_lifecycle_contributor(extension.clone());
+    registry.token_usage_contributor(extension);
+}
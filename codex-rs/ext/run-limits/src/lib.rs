@@ -0,0 +1,7 @@
+//! Extension crate for time-boxed runs (`--max-duration`, `--max-turns`, `--max-tokens`).
+
+mod extension;
+mod limits;
+mod steering;
+
+pub use extension::install;
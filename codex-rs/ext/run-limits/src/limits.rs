@@ -0,0 +1,173 @@
+use codex_config::types::RunLimitsConfig;
+use codex_protocol::protocol::TokenUsage;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Share of the duration or token limit after which the model is asked to wrap up.
+const WRAP_UP_THRESHOLD_PERCENT: u64 = 80;
+
+/// The limit that triggered a wrap-up or ended automatic continuation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RunLimit {
+    Duration { max: Duration },
+    Turns { max: u32 },
+    Tokens { max: u64 },
+}
+
+/// Tracks session-wide usage against the configured run limits.
+///
+/// Reaching the duration or token limit interrupts the turn that is running, or the next one if
+/// the session is idle; later turns are not interrupted again. Tokens are counted as uncached input plus output, relative to the usage snapshot taken when the
+/// first turn started, so a resumed thread gets a fresh budget.
+#[derive(Debug)]
+pub(crate) struct RunLimitsState {
+    inner: Mutex<RunLimitsInner>,
+}
+
+#[derive(Debug)]
+struct RunLimitsInner {
+    limits: RunLimitsConfig,
+    started_at: Instant,
+    turns: u32,
+    token_baseline: Option<i64>,
+    tokens_used: u64,
+    turn_active: bool,
+    wrap_up_sent: bool,
+    interrupted: bool,
+}
+
+impl RunLimitsState {
+    pub(crate) fn new(limits: RunLimitsConfig, now: Instant) -> Self {
+        Self {
+            inner: Mutex::new(RunLimitsInner {
+                limits,
+                started_at: now,
+                turns: 0,
+                token_baseline: None,
+                tokens_used: 0,
+                turn_active: false,
+                wrap_up_sent: false,
+                interrupted: false,
+            }),
+        }
+    }
+
+    pub(crate) fn set_limits(&self, limits: RunLimitsConfig) {
+        self.inner().limits = limits;
+    }
+
+    pub(crate) fn start_turn(&self, token_usage_at_turn_start: &TokenUsage) {
+        let mut inner = self.inner();
+        inner.turns = inner.turns.saturating_add(1);
+        inner.turn_active = true;
+        let baseline = *inner
+            .token_baseline
+            .get_or_insert(token_usage_at_turn_start.blended_total());
+        inner.tokens_used = tokens_since(baseline, token_usage_at_turn_start);
+    }
+
+    pub(crate) fn end_turn(&self) {
+        self.inner().turn_active = false;
+    }
+
+    pub(crate) fn record_token_usage(&self, total_token_usage: &TokenUsage) {
+        let mut inner = self.inner();
+        let Some(baseline) = inner.token_baseline else {
+            return;
+        };
+        inner.tokens_used = tokens_since(baseline, total_token_usage);
+    }
+
+    /// Returns the limit the model should start wrapping up for, unless a wrap-up was already sent.
+    ///
+    /// Duration and token limits trigger once most of the budget is spent; the turn limit triggers
+    /// when the last allowed turn starts.
+    pub(crate) fn wrap_up_due(&self, now: Instant) -> Option<RunLimit> {
+        let inner = self.inner();
+        if inner.wrap_up_sent {
+            return None;
+        }
+        inner.first_limit(now, |used, max| {
+            used.saturating_mul(100) >= max.saturating_mul(WRAP_UP_THRESHOLD_PERCENT)
+        })
+    }
+
+    pub(crate) fn mark_wrap_up_sent(&self) {
+        self.inner().wrap_up_sent = true;
+    }
+
+    /// Returns the limit the session has reached, if any.
+    pub(crate) fn limit_reached(&self, now: Instant) -> Option<RunLimit> {
+        self.inner().first_limit(now, |used, max| used >= max)
+    }
+
+    /// Returns the duration or token limit the running turn must be interrupted for, unless a
+    /// turn was already interrupted. The turn limit never interrupts: the last allowed turn runs
+    /// to completion.
+    pub(crate) fn interrupt_due(&self, now: Instant) -> Option<RunLimit> {
+        let inner = self.inner();
+        if !inner.turn_active || inner.interrupted {
+            return None;
+        }
+        inner.budget_limit(now, |used, max| used >= max)
+    }
+
+    pub(crate) fn mark_interrupted(&self) {
+        self.inner().interrupted = true;
+    }
+
+    /// Time until the duration limit is reached, if one is set.
+    pub(crate) fn time_left(&self, now: Instant) -> Option<Duration> {
+        let inner = self.inner();
+        let elapsed = now.saturating_duration_since(inner.started_at);
+        inner
+            .limits
+            .max_duration
+            .map(|max| max.saturating_sub(elapsed))
+    }
+
+    fn inner(&self) -> MutexGuard<'_, RunLimitsInner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl RunLimitsInner {
+    fn first_limit(&self, now: Instant, spent: impl Fn(u64, u64) -> bool) -> Option<RunLimit> {
+        if let Some(max) = self.limits.max_turns
+            && self.turns >= max.get()
+        {
+            return Some(RunLimit::Turns { max: max.get() });
+        }
+        self.budget_limit(now, spent)
+    }
+
+    fn budget_limit(&self, now: Instant, spent: impl Fn(u64, u64) -> bool) -> Option<RunLimit> {
+        if let Some(max) = self.limits.max_duration {
+            let elapsed = now.saturating_duration_since(self.started_at);
+            if spent(duration_millis(elapsed), duration_millis(max)) {
+                return Some(RunLimit::Duration { max });
+            }
+        }
+        if let Some(max) = self.limits.max_tokens
+            && spent(self.tokens_used, max.get())
+        {
+            return Some(RunLimit::Tokens { max: max.get() });
+        }
+        None
+    }
+}
+
+fn tokens_since(baseline: i64, total_token_usage: &TokenUsage) -> u64 {
+    u64::try_from(total_token_usage.blended_total() - baseline).unwrap_or_default()
+}
+
+fn duration_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+#[path = "limits_tests.rs"]
+mod tests;
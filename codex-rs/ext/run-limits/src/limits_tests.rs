@@ -0,0 +1,144 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use std::num::NonZeroU32;
+use std::num::NonZeroU64;
+
+fn usage(input_tokens: i64, cached_input_tokens: i64, output_tokens: i64) -> TokenUsage {
+    TokenUsage {
+        input_tokens,
+        cached_input_tokens,
+        output_tokens,
+        total_tokens: input_tokens + output_tokens,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn turn_limit_wraps_up_on_last_turn() {
+    let now = Instant::now();
+    let state = RunLimitsState::new(
+        RunLimitsConfig {
+            max_turns: NonZeroU32::new(2),
+            ..Default::default()
+        },
+        now,
+    );
+
+    state.start_turn(&TokenUsage::default());
+    assert_eq!(None, state.wrap_up_due(now));
+    assert_eq!(None, state.limit_reached(now));
+
+    state.start_turn(&TokenUsage::default());
+    assert_eq!(Some(RunLimit::Turns { max: 2 }), state.wrap_up_due(now));
+    assert_eq!(Some(RunLimit::Turns { max: 2 }), state.limit_reached(now));
+
+    state.mark_wrap_up_sent();
+    assert_eq!(None, state.wrap_up_due(now));
+}
+
+#[test]
+fn duration_limit_wraps_up_before_it_is_reached() {
+    let now = Instant::now();
+    let max = Duration::from_secs(100);
+    let state = RunLimitsState::new(
+        RunLimitsConfig {
+            max_duration: Some(max),
+            ..Default::default()
+        },
+        now,
+    );
+
+    assert_eq!(None, state.wrap_up_due(now + Duration::from_secs(79)));
+    assert_eq!(
+        Some(RunLimit::Duration { max }),
+        state.wrap_up_due(now + Duration::from_secs(80))
+    );
+    assert_eq!(None, state.limit_reached(now + Duration::from_secs(99)));
+    assert_eq!(
+        Some(RunLimit::Duration { max }),
+        state.limit_reached(now + Duration::from_secs(100))
+    );
+}
+
+#[test]
+fn token_limit_counts_uncached_input_and_output_since_first_turn() {
+    let now = Instant::now();
+    let state = RunLimitsState::new(
+        RunLimitsConfig {
+            max_tokens: NonZeroU64::new(1_000),
+            ..Default::default()
+        },
+        now,
+    );
+
+    // Usage recorded before the first turn belongs to an earlier run.
+    state.record_token_usage(&usage(
+        /*input_tokens*/ 5_000, /*cached_input_tokens*/ 0, /*output_tokens*/ 0,
+    ));
+    state.start_turn(&usage(
+        /*input_tokens*/ 5_000, /*cached_input_tokens*/ 0, /*output_tokens*/ 0,
+    ));
+    assert_eq!(None, state.wrap_up_due(now));
+
+    state.record_token_usage(&usage(
+        /*input_tokens*/ 10_000, /*cached_input_tokens*/ 4_500,
+        /*output_tokens*/ 300,
+    ));
+    assert_eq!(
+        Some(RunLimit::Tokens { max: 1_000 }),
+        state.wrap_up_due(now)
+    );
+    assert_eq!(None, state.limit_reached(now));
+
+    state.record_token_usage(&usage(
+        /*input_tokens*/ 10_000, /*cached_input_tokens*/ 4_500,
+        /*output_tokens*/ 500,
+    ));
+    assert_eq!(
+        Some(RunLimit::Tokens { max: 1_000 }),
+        state.limit_reached(now)
+    );
+}
+
+#[test]
+fn unset_limits_never_trigger() {
+    let now = Instant::now();
+    let state = RunLimitsState::new(RunLimitsConfig::default(), now);
+
+    state.start_turn(&usage(
+        /*input_tokens*/ 1_000_000, /*cached_input_tokens*/ 0, /*output_tokens*/ 0,
+    ));
+    let later = now + Duration::from_secs(24 * 60 * 60);
+    assert_eq!(None, state.wrap_up_due(later));
+    assert_eq!(None, state.limit_reached(later));
+}
+
+#[test]
+fn reaching_a_budget_interrupts_one_running_turn() {
+    let now = Instant::now();
+    let max = Duration::from_secs(100);
+    let state = RunLimitsState::new(
+        RunLimitsConfig {
+            max_duration: Some(max),
+            max_turns: NonZeroU32::new(1),
+            ..Default::default()
+        },
+        now,
+    );
+    let later = now + Duration::from_secs(100);
+
+    // The turn limit lets the last turn finish; an idle session has nothing to interrupt.
+    assert_eq!(None, state.interrupt_due(later));
+    state.start_turn(&TokenUsage::default());
+    assert_eq!(None, state.interrupt_due(now));
+    assert_eq!(
+        Some(Duration::from_secs(40)),
+        state.time_left(now + Duration::from_secs(60))
+    );
+
+    assert_eq!(Some(RunLimit::Duration { max }), state.interrupt_due(later));
+    state.mark_interrupted();
+    state.end_turn();
+    state.start_turn(&TokenUsage::default());
+    assert_eq!(None, state.interrupt_due(later));
+}
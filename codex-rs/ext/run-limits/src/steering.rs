@@ -0,0 +1,33 @@
+use crate::limits::RunLimit;
+use codex_core::context::ContextualUserFragment;
+use codex_core::context::InternalContextSource;
+use codex_core::context::InternalModelContextFragment;
+use codex_protocol::models::ResponseItem;
+use codex_utils_template::Template;
+use std::sync::LazyLock;
+
+static WRAP_UP_PROMPT_TEMPLATE: LazyLock<Template> =
+    LazyLock::new(
+        || match Template::parse(include_str!("../templates/wrap_up.md")) {
+            Ok(template) => template,
+            Err(err) => panic!("embedded template wrap_up.md is invalid: {err}"),
+        },
+    );
+
+pub(crate) fn wrap_up_steering_item(limit: RunLimit) -> ResponseItem {
+    ContextualUserFragment::into(InternalModelContextFragment::new(
+        InternalContextSource::from_static("run_limits"),
+        wrap_up_prompt(limit),
+    ))
+}
+
+fn wrap_up_prompt(limit: RunLimit) -> String {
+    let limit = match limit {
+        RunLimit::Duration { max } => format!("{} seconds of wall-clock time", max.as_secs()),
+        RunLimit::Turns { max } => format!("{max} turns; this is the last one"),
+        RunLimit::Tokens { max } => format!("{max} tokens"),
+    };
+    WRAP_UP_PROMPT_TEMPLATE
+        .render([("limit", limit.as_str())])
+        .unwrap_or_else(|err| panic!("embedded template wrap_up.md failed to render: {err}"))
+}
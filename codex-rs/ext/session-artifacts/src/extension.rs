@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::config::Config;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ToolContributor;
+use codex_features::Feature;
+
+use crate::tools;
+
+/// Contributes a tool that records generated files as named artifacts of the session.
+#[derive(Clone, Default)]
+pub(crate) struct SessionArtifactsExtension;
+
+#[derive(Clone, Debug)]
+pub(crate) struct SessionArtifactsExtensionConfig {
+    pub(crate) enabled: bool,
+    /// The registry lives under this directory, where the TUI and `codex share` look for it.
+    pub(crate) codex_home: PathBuf,
+}
+
+impl SessionArtifactsExtensionConfig {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            enabled: config.features.enabled(Feature::SessionArtifacts),
+            codex_home: config.codex_home.to_path_buf(),
+        }
+    }
+}
+
+impl ToolContributor for SessionArtifactsExtension {
+    fn tools(
+        &self,
+        _session_store: &ExtensionData,
+        thread_store: &ExtensionData,
+    ) -> Vec<Arc<dyn codex_extension_api::ToolExecutor<codex_extension_api::ToolCall>>> {
+        let Some(config) = thread_store.get::<SessionArtifactsExtensionConfig>() else {
+            return Vec::new();
+        };
+        if !config.enabled {
+            return Vec::new();
+        }
+
+        tools::session_artifact_tools(
+            config.codex_home.clone(),
+            thread_store.level_id().to_string(),
+        )
+    }
+}
+
+/// Installs the session artifacts extension contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    registry.thread_config(SessionArtifactsExtensionConfig::from_config);
+    registry.tool_contributor(Arc::new(SessionArtifactsExtension));
+}
@@ -0,0 +1,10 @@
+mod extension;
+mod tools;
+
+pub use extension::install;
+
+pub(crate) const ARTIFACTS_NAMESPACE: &str = "artifacts";
+pub(crate) const REGISTER_TOOL_NAME: &str = "register";
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,303 @@
+use std::sync::Arc;
+
+use codex_extension_api::DenyCommandApprover;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::NoopTurnItemEmitter;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolContributor;
+use codex_extension_api::ToolPayload;
+use codex_extension_api::new_local_environment;
+use codex_protocol::models::PermissionProfile;
+use codex_utils_output_truncation::TruncationPolicy;
+use codex_utils_session_artifacts::SessionArtifact;
+use codex_utils_session_artifacts::artifact_dir;
+use codex_utils_session_artifacts::load_artifacts;
+use codex_utils_session_artifacts::scratchpad_dir;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::extension::SessionArtifactsExtension;
+use crate::extension::SessionArtifactsExtensionConfig;
+use crate::tools::RegisterArgs;
+use crate::tools::artifacts_tool_name;
+use crate::tools::register;
+
+fn enabled_thread_store() -> ExtensionData {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(SessionArtifactsExtensionConfig {
+        enabled: true,
+        codex_home: std::env::temp_dir(),
+    });
+    thread_store
+}
+
+fn register_args(name: &str, path: &str, description: Option<&str>) -> RegisterArgs {
+    RegisterArgs {
+        name: name.to_string(),
+        path: path.to_string(),
+        description: description.map(str::to_string),
+    }
+}
+
+#[test]
+fn install_registers_the_register_tool() {
+    let mut builder = ExtensionRegistryBuilder::<codex_core::config::Config>::new();
+    crate::install(&mut builder);
+    let registry = builder.build();
+    let thread_store = enabled_thread_store();
+
+    let tool_names = registry
+        .tool_contributors()
+        .iter()
+        .flat_map(|contributor| contributor.tools(&ExtensionData::new("session"), &thread_store))
+        .map(|tool| tool.tool_name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        tool_names,
+        vec![artifacts_tool_name(crate::REGISTER_TOOL_NAME)]
+    );
+}
+
+#[test]
+fn tools_are_not_contributed_when_disabled() {
+    let thread_store = ExtensionData::new("thread");
+    thread_store.insert(SessionArtifactsExtensionConfig {
+        enabled: false,
+        codex_home: std::env::temp_dir(),
+    });
+
+    assert!(
+        SessionArtifactsExtension
+            .tools(&ExtensionData::new("session"), &thread_store)
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn register_resolves_relative_paths_and_replaces_by_name() {
+    let codex_home = tempfile::tempdir().expect("tempdir");
+    let workspace = tempfile::tempdir().expect("tempdir");
+    let cwd = std::fs::canonicalize(workspace.path()).expect("canonical cwd");
+    std::fs::create_dir(cwd.join("out")).expect("create out dir");
+    std::fs::write(cwd.join("out/report.md"), "# Report").expect("write report");
+    std::fs::write(cwd.join("out/report-v2.md"), "# Report v2").expect("write report");
+    let environment =
+        new_local_environment(&cwd, PermissionProfile::Disabled).expect("local environment");
+
+    register(
+        codex_home.path(),
+        "thread-1",
+        &environment,
+        register_args("report", "out/report.md", /*description*/ None),
+    )
+    .await
+    .expect("register report");
+    let output = register(
+        codex_home.path(),
+        "thread-1",
+        &environment,
+        register_args(
+            " report ",
+            "out/report-v2.md",
+            Some(" Latency by endpoint "),
+        ),
+    )
+    .await
+    .expect("replace report");
+
+    let expected = vec![SessionArtifact {
+        name: "report".to_string(),
+        path: cwd.join("out/report-v2.md"),
+        description: Some("Latency by endpoint".to_string()),
+    }];
+    assert_eq!(output.artifacts, expected);
+    assert_eq!(
+        load_artifacts(codex_home.path(), "thread-1").expect("load artifacts"),
+        expected
+    );
+}
+
+#[tokio::test]
+async fn register_rejects_missing_files_and_directories() {
+    let codex_home = tempfile::tempdir().expect("tempdir");
+    let workspace = tempfile::tempdir().expect("tempdir");
+    let environment = new_local_environment(workspace.path(), PermissionProfile::Disabled)
+        .expect("local environment");
+
+    let Err(FunctionCallError::RespondToModel(missing)) = register(
+        codex_home.path(),
+        "thread-1",
+        &environment,
+        register_args("plot", "plot.png", /*description*/ None),
+    )
+    .await
+    else {
+        panic!("registering a missing file should fail");
+    };
+    assert!(missing.starts_with("cannot register"), "{missing}");
+
+    let Err(FunctionCallError::RespondToModel(directory)) = register(
+        codex_home.path(),
+        "thread-1",
+        &environment,
+        register_args("outputs", ".", /*description*/ None),
+    )
+    .await
+    else {
+        panic!("registering a directory should fail");
+    };
+    assert!(directory.ends_with("not a file"), "{directory}");
+    assert_eq!(
+        load_artifacts(codex_home.path(), "thread-1").expect("load artifacts"),
+        Vec::new()
+    );
+}
+
+#[tokio::test]
+async fn register_rejects_files_outside_the_workspace_or_the_sandbox() {
+    let codex_home = tempfile::tempdir().expect("tempdir");
+    let dir = tempfile::tempdir().expect("tempdir");
+    let dir = std::fs::canonicalize(dir.path()).expect("canonical dir");
+    let cwd = dir.join("repo");
+    std::fs::create_dir(&cwd).expect("create repo");
+    std::fs::write(dir.join("secrets.env"), "TOKEN=1").expect("write secrets");
+    std::fs::write(cwd.join("report.md"), "# Report").expect("write report");
+
+    let Err(FunctionCallError::RespondToModel(outside)) = register(
+        codex_home.path(),
+        "thread-1",
+        &new_local_environment(&cwd, PermissionProfile::Disabled).expect("local environment"),
+        register_args("secrets", "../secrets.env", /*description*/ None),
+    )
+    .await
+    else {
+        panic!("registering a file outside the workspace should fail");
+    };
+    assert!(outside.ends_with("outside the workspace"), "{outside}");
+
+    // Unit tests do not configure the sandboxed file system helper, so a restricted profile
+    // cannot read anything; registration must fail rather than check the file directly.
+    let Err(FunctionCallError::RespondToModel(sandboxed)) = register(
+        codex_home.path(),
+        "thread-1",
+        &new_local_environment(&cwd, PermissionProfile::read_only()).expect("local environment"),
+        register_args("report", "report.md", /*description*/ None),
+    )
+    .await
+    else {
+        panic!("registering a file the sandbox cannot read should fail");
+    };
+    assert!(sandboxed.starts_with("cannot register"), "{sandboxed}");
+    assert_eq!(
+        load_artifacts(codex_home.path(), "thread-1").expect("load artifacts"),
+        Vec::new()
+    );
+}
+
+#[tokio::test]
+async fn register_accepts_files_in_the_session_scratch_and_artifact_dirs() {
+    let codex_home = tempfile::tempdir().expect("tempdir");
+    let codex_home = std::fs::canonicalize(codex_home.path()).expect("canonical codex home");
+    let workspace = tempfile::tempdir().expect("tempdir");
+    let profile = artifact_dir(&codex_home, "thread-1").join("profile-1.folded");
+    let plot = scratchpad_dir(&codex_home, "thread-1").join("plot.png");
+    for path in [&profile, &plot] {
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        std::fs::write(path, "data").expect("write file");
+    }
+    // The sandbox cannot read anything in unit tests; Codex's own directories do not need it.
+    let environment = new_local_environment(workspace.path(), PermissionProfile::read_only())
+        .expect("local environment");
+
+    register(
+        &codex_home,
+        "thread-1",
+        &environment,
+        register_args(
+            "profile",
+            &profile.display().to_string(),
+            /*description*/ None,
+        ),
+    )
+    .await
+    .expect("register profile");
+    let output = register(
+        &codex_home,
+        "thread-1",
+        &environment,
+        register_args(
+            "plot",
+            &plot.display().to_string(),
+            /*description*/ None,
+        ),
+    )
+    .await
+    .expect("register plot");
+
+    assert_eq!(
+        output
+            .artifacts
+            .into_iter()
+            .map(|artifact| (artifact.name, artifact.path))
+            .collect::<Vec<_>>(),
+        vec![("profile".to_string(), profile), ("plot".to_string(), plot)]
+    );
+    let Err(FunctionCallError::RespondToModel(other_thread)) = register(
+        &codex_home,
+        "thread-2",
+        &environment,
+        register_args(
+            "plot",
+            &scratchpad_dir(&codex_home, "thread-1")
+                .join("plot.png")
+                .display()
+                .to_string(),
+            /*description*/ None,
+        ),
+    )
+    .await
+    else {
+        panic!("registering another session's scratch file should fail");
+    };
+    assert!(
+        other_thread.ends_with("outside the workspace"),
+        "{other_thread}"
+    );
+}
+
+#[tokio::test]
+async fn tools_require_a_local_environment() {
+    let tools =
+        SessionArtifactsExtension.tools(&ExtensionData::new("session"), &enabled_thread_store());
+
+    let Err(err) = tools[0]
+        .handle(ToolCall {
+            turn_id: "turn-1".to_string(),
+            call_id: "call-1".to_string(),
+            tool_name: artifacts_tool_name(crate::REGISTER_TOOL_NAME),
+            model: "gpt-test".to_string(),
+            codex_turn_metadata: None,
+            truncation_policy: TruncationPolicy::Bytes(1024),
+            conversation_history: codex_extension_api::ConversationHistory::default(),
+            turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+            command_approver: Arc::new(DenyCommandApprover),
+            environments: Vec::new(),
+            payload: ToolPayload::Function {
+                arguments: json!({"name": "report", "path": "report.md"}).to_string(),
+            },
+        })
+        .await
+    else {
+        panic!("registering without a local environment should fail");
+    };
+
+    assert_eq!(
+        err,
+        FunctionCallError::RespondToModel(
+            "artifacts can only be registered in local workspaces".to_string()
+        )
+    );
+}
@@ -0,0 +1,164 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_extension_api::FunctionCallError;
+use codex_extension_api::JsonToolOutput;
+use codex_extension_api::ToolCall;
+use codex_extension_api::ToolEnvironment;
+use codex_extension_api::ToolExecutor;
+use codex_extension_api::ToolExecutorFuture;
+use codex_extension_api::ToolName;
+use codex_extension_api::ToolOutput;
+use codex_extension_api::ToolSpec;
+use codex_extension_api::local_environment;
+use codex_extension_api::namespaced_function_tool;
+use codex_extension_api::parse_function_arguments;
+use codex_utils_session_artifacts::SessionArtifact;
+use codex_utils_session_artifacts::managed_dirs;
+use codex_utils_session_artifacts::register_artifact;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::ARTIFACTS_NAMESPACE;
+use crate::REGISTER_TOOL_NAME;
+
+/// Artifacts are opened from the machine running Codex, so they must live on the local host.
+const LOCAL_WORKSPACE_REQUIRED: &str = "artifacts can only be registered in local workspaces";
+
+pub(crate) fn session_artifact_tools(
+    codex_home: PathBuf,
+    thread_id: String,
+) -> Vec<Arc<dyn ToolExecutor<ToolCall>>> {
+    vec![Arc::new(RegisterTool {
+        codex_home,
+        thread_id,
+    })]
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct RegisterArgs {
+    /// Short name for the artifact, such as `latency-report`. Registering the same name again
+    /// replaces the earlier entry.
+    pub(crate) name: String,
+    /// Path of the generated file inside the workspace or the session's scratch directory,
+    /// absolute or relative to the working directory.
+    pub(crate) path: String,
+    /// One line on what the file contains.
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct RegisterOutput {
+    /// Every artifact registered in this session, including the new one.
+    pub(crate) artifacts: Vec<SessionArtifact>,
+}
+
+struct RegisterTool {
+    codex_home: PathBuf,
+    thread_id: String,
+}
+
+impl ToolExecutor<ToolCall> for RegisterTool {
+    fn tool_name(&self) -> ToolName {
+        artifacts_tool_name(REGISTER_TOOL_NAME)
+    }
+
+    fn spec(&self) -> ToolSpec {
+        namespaced_function_tool::<RegisterArgs, RegisterOutput>(
+            ARTIFACTS_NAMESPACE,
+            REGISTER_TOOL_NAME,
+            "Record a file you generated in the workspace, such as a report, plot, or generated data, as a named artifact of this session. The file must be inside the workspace and readable under the sandbox, or inside the session's scratch directory. The user can list and open registered artifacts, and they are included when the session is exported. Do not register source files you edited.",
+        )
+    }
+
+    fn handle(&self, call: ToolCall) -> ToolExecutorFuture<'_> {
+        Box::pin(async move {
+            let args: RegisterArgs = parse_function_arguments(&call)?;
+            let environment = local_environment(&call, LOCAL_WORKSPACE_REQUIRED)?;
+            let output = register(&self.codex_home, &self.thread_id, environment, args).await?;
+            let output: Box<dyn ToolOutput> = Box::new(JsonToolOutput::new(json!(output)));
+            Ok(output)
+        })
+    }
+}
+
+/// Resolves the file and adds it to the session's registry. Only files inside the workspace that
+/// the sandbox lets the session read, or inside a directory Codex manages for the session (its
+/// scratch and artifact directories), can be registered, since registered files can later be
+/// exported and shared.
+pub(crate) async fn register(
+    codex_home: &Path,
+    thread_id: &str,
+    environment: &ToolEnvironment,
+    args: RegisterArgs,
+) -> Result<RegisterOutput, FunctionCallError> {
+    let name = args.name.trim();
+    if name.is_empty() {
+        return Err(FunctionCallError::RespondToModel(
+            "artifact name must not be empty".to_string(),
+        ));
+    }
+    let path = environment.resolve_path(&args.path);
+    // Resolve symlinks first so a link inside the workspace cannot point outside it.
+    let path = std::fs::canonicalize(&path).map_err(|err| {
+        FunctionCallError::RespondToModel(format!("cannot register {}: {err}", path.display()))
+    })?;
+    let canonical_root =
+        |root: &Path| std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let in_workspace = environment
+        .workspace_roots()
+        .iter()
+        .any(|root| path.starts_with(canonical_root(root)));
+    let in_managed_dir = managed_dirs(codex_home, thread_id)
+        .iter()
+        .any(|dir| path.starts_with(canonical_root(dir)));
+    if !in_workspace && !in_managed_dir {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "cannot register {}: outside the workspace",
+            path.display()
+        )));
+    }
+    // Codex writes its own directories itself, so only workspace files go through the sandbox.
+    let is_file = if in_workspace {
+        let absolute = environment.cwd.join(&path);
+        environment
+            .metadata(&absolute)
+            .await
+            .map(|metadata| metadata.is_file)
+            .map_err(|err| err.to_string())
+    } else {
+        std::fs::metadata(&path)
+            .map(|metadata| metadata.is_file())
+            .map_err(|err| err.to_string())
+    }
+    .map_err(|err| {
+        FunctionCallError::RespondToModel(format!("cannot register {}: {err}", path.display()))
+    })?;
+    if !is_file {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "cannot register {}: not a file",
+            path.display()
+        )));
+    }
+    let artifact = SessionArtifact {
+        name: name.to_string(),
+        path,
+        description: args
+            .description
+            .map(|description| description.trim().to_string())
+            .filter(|description| !description.is_empty()),
+    };
+    let artifacts = register_artifact(codex_home, thread_id, artifact).map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to record the artifact: {err}"))
+    })?;
+    Ok(RegisterOutput { artifacts })
+}
+
+pub(crate) fn artifacts_tool_name(name: &str) -> ToolName {
+    ToolName::namespaced(ARTIFACTS_NAMESPACE, name)
+}
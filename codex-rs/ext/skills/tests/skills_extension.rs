@@ -9,6 +9,7 @@ use codex_core_skills::SKILLS_INTRO_WITH_ABSOLUTE_PATHS;
 use codex_core_skills::SkillLoadOutcome;
 use codex_core_skills::injection::InjectedHostSkillPrompts;
 use codex_extension_api::ConversationHistory;
+use codex_extension_api::DenyCommandApprover;
 use codex_extension_api::ExtensionData;
 use codex_extension_api::ExtensionEventSink;
 use codex_extension_api::ExtensionRegistryBuilder;
@@ -598,6 +599,7 @@ async fn skills_list_truncates_catalog_descriptions_in_tool_output() -> TestResu
             truncation_policy: TruncationPolicy::Bytes(1_024),
             conversation_history: ConversationHistory::default(),
             turn_item_emitter: Arc::new(NoopTurnItemEmitter),
+            command_approver: Arc::new(DenyCommandApprover),
             environments: Vec::new(),
             payload: payload.clone(),
         })
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::sync::Weak;
+use std::time::Duration;
+
+use crate::record::UsageRecord;
+use crate::sink::Sink;
+
+/// Records kept while the sink is unreachable. Older ones are dropped first.
+const MAX_PENDING_RECORDS: usize = 10_000;
+
+/// Buffers usage records and writes them to one sink.
+pub(crate) struct UsageExporter {
+    sink: Sink,
+    pending: Mutex<Vec<UsageRecord>>,
+    /// Keeps the periodic and thread-stop flushes from writing the same records twice.
+    flush_lock: tokio::sync::Mutex<()>,
+}
+
+impl UsageExporter {
+    pub(crate) fn new(sink: Sink) -> Self {
+        Self {
+            sink,
+            pending: Mutex::new(Vec::new()),
+            flush_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Creates an exporter that flushes every `interval` until it is dropped.
+    pub(crate) fn start(sink: Sink, interval: Duration) -> Arc<Self> {
+        let exporter = Arc::new(Self::new(sink));
+        let weak = Arc::downgrade(&exporter);
+        tokio::spawn(flush_periodically(weak, interval));
+        exporter
+    }
+
+    pub(crate) fn record(&self, record: UsageRecord) {
+        let mut pending = self.pending();
+        pending.push(record);
+        let overflow = pending.len().saturating_sub(MAX_PENDING_RECORDS);
+        pending.drain(..overflow);
+    }
+
+    /// Writes every buffered record. Records the sink refuses stay buffered for the next flush.
+    pub(crate) async fn flush(&self) {
+        let _guard = self.flush_lock.lock().await;
+        let records = std::mem::take(&mut *self.pending());
+        if records.is_empty() {
+            return;
+        }
+        if let Err(err) = self.sink.write(&records).await {
+            tracing::warn!("failed to export {} usage records: {err}", records.len());
+            let mut pending = self.pending();
+            let newer = std::mem::replace(&mut *pending, records);
+            drop(pending);
+            for record in newer {
+                self.record(record);
+            }
+        }
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, Vec<UsageRecord>> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+async fn flush_periodically(exporter: Weak<UsageExporter>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    // The first tick completes immediately, when nothing has been recorded yet.
+    ticker.tick().await;
+    loop {
+        ticker.tick().await;
+        let Some(exporter) = exporter.upgrade() else {
+            return;
+        };
+        exporter.flush().await;
+    }
+}
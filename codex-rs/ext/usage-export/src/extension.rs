@@ -0,0 +1,153 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Instant;
+
+use chrono::Utc;
+use codex_config::types::UsageExportConfig;
+use codex_core::config::Config;
+use codex_extension_api::ExtensionData;
+use codex_extension_api::ExtensionFuture;
+use codex_extension_api::ExtensionRegistryBuilder;
+use codex_extension_api::ThreadLifecycleContributor;
+use codex_extension_api::ThreadStartInput;
+use codex_extension_api::ThreadStopInput;
+use codex_extension_api::TokenUsageContributor;
+use codex_extension_api::TurnAbortInput;
+use codex_extension_api::TurnLifecycleContributor;
+use codex_extension_api::TurnStartInput;
+use codex_extension_api::TurnStopInput;
+use codex_protocol::protocol::TokenUsageInfo;
+
+use crate::exporter::UsageExporter;
+use crate::record::TurnUsage;
+use crate::record::repo_hash;
+use crate::record::repo_identity;
+use crate::sink::Sink;
+
+/// Records the usage of every turn and hands it to the exporter for the thread's settings.
+#[derive(Default)]
+struct UsageExportExtension {
+    /// One exporter per distinct `[usage_export]` table, shared by the threads that use it.
+    exporters: Mutex<Vec<(UsageExportConfig, Arc<UsageExporter>)>>,
+}
+
+/// Export state of a thread, kept in the thread store.
+struct ThreadExport {
+    exporter: Arc<UsageExporter>,
+    repo_hash: Option<String>,
+}
+
+impl UsageExportExtension {
+    fn exporter(&self, config: &UsageExportConfig) -> Option<Arc<UsageExporter>> {
+        let mut exporters = self
+            .exporters
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some((_, exporter)) = exporters.iter().find(|(existing, _)| existing == config) {
+            return Some(Arc::clone(exporter));
+        }
+        let sink = match Sink::from_config(config) {
+            Ok(sink) => sink?,
+            Err(err) => {
+                tracing::warn!("usage export disabled: {err}");
+                return None;
+            }
+        };
+        let exporter = UsageExporter::start(sink, config.flush_interval);
+        exporters.push((config.clone(), Arc::clone(&exporter)));
+        Some(exporter)
+    }
+
+    fn finish_turn(thread_store: &ExtensionData, turn_store: &ExtensionData) {
+        let (Some(thread), Some(turn)) = (
+            thread_store.get::<ThreadExport>(),
+            turn_store.remove::<TurnUsage>(),
+        ) else {
+            return;
+        };
+        thread
+            .exporter
+            .record(turn.finish(thread.repo_hash.clone(), Instant::now(), Utc::now()));
+    }
+}
+
+impl ThreadLifecycleContributor<Config> for UsageExportExtension {
+    fn on_thread_start<'a>(
+        &'a self,
+        input: ThreadStartInput<'a, Config>,
+    ) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            let config = &input.config.usage_export;
+            let Some(exporter) = self.exporter(config) else {
+                return;
+            };
+            let repo_hash = repo_identity(input.config.cwd.as_path())
+                .await
+                .map(|identity| repo_hash(&config.hash_salt, &identity));
+            input.thread_store.insert(ThreadExport {
+                exporter,
+                repo_hash,
+            });
+        })
+    }
+
+    fn on_thread_stop<'a>(&'a self, input: ThreadStopInput<'a>) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            if let Some(thread) = input.thread_store.get::<ThreadExport>() {
+                thread.exporter.flush().await;
+            }
+        })
+    }
+}
+
+impl TurnLifecycleContributor for UsageExportExtension {
+    fn on_turn_start<'a>(&'a self, input: TurnStartInput<'a>) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            if input.thread_store.get::<ThreadExport>().is_none() {
+                return;
+            }
+            input.turn_store.insert(TurnUsage::new(
+                input.collaboration_mode.model(),
+                input.token_usage_at_turn_start,
+                Instant::now(),
+            ));
+        })
+    }
+
+    fn on_turn_stop<'a>(&'a self, input: TurnStopInput<'a>) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            Self::finish_turn(input.thread_store, input.turn_store);
+        })
+    }
+
+    fn on_turn_abort<'a>(&'a self, input: TurnAbortInput<'a>) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            Self::finish_turn(input.thread_store, input.turn_store);
+        })
+    }
+}
+
+impl TokenUsageContributor for UsageExportExtension {
+    fn on_token_usage<'a>(
+        &'a self,
+        _session_store: &'a ExtensionData,
+        _thread_store: &'a ExtensionData,
+        turn_store: &'a ExtensionData,
+        token_usage: &'a TokenUsageInfo,
+    ) -> ExtensionFuture<'a, ()> {
+        Box::pin(async move {
+            if let Some(turn) = turn_store.get::<TurnUsage>() {
+                turn.update(&token_usage.total_token_usage);
+            }
+        })
+    }
+}
+
+/// Installs the usage export contributors into the extension registry.
+pub fn install(registry: &mut ExtensionRegistryBuilder<Config>) {
+    let extension = Arc::new(UsageExportExtension::default());
+    registry.thread_lifecycle_contributor(extension.clone());
+    registry.turn_lifecycle_contributor(extension.clone());
+    registry.token_usage_contributor(extension);
+}
@@ -0,0 +1,16 @@
+//! Extension crate that exports anonymized usage records from `[usage_export]`.
+//!
+//! Each finished turn becomes one record with the model, token counts, duration, and a salted
+//! hash of the repository. Records are buffered and written to the configured sink every
+//! `flush_interval_seconds` and when a thread stops. They never include prompts, paths, or user
+//! names.
+
+mod exporter;
+mod extension;
+mod record;
+mod sink;
+
+pub use extension::install;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,107 @@
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Instant;
+
+use chrono::DateTime;
+use chrono::Utc;
+use codex_protocol::protocol::TokenUsage;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+/// Usage of one finished turn.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct UsageRecord {
+    pub(crate) timestamp: DateTime<Utc>,
+    pub(crate) model: String,
+    pub(crate) input_tokens: i64,
+    pub(crate) cached_input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) reasoning_output_tokens: i64,
+    pub(crate) duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) repo_hash: Option<String>,
+}
+
+/// Usage tracked while a turn runs, kept in the turn store.
+pub(crate) struct TurnUsage {
+    model: String,
+    started_at: Instant,
+    usage_at_start: TokenUsage,
+    latest_usage: Mutex<TokenUsage>,
+}
+
+impl TurnUsage {
+    pub(crate) fn new(model: &str, usage_at_start: &TokenUsage, now: Instant) -> Self {
+        Self {
+            model: model.to_string(),
+            started_at: now,
+            usage_at_start: usage_at_start.clone(),
+            latest_usage: Mutex::new(usage_at_start.clone()),
+        }
+    }
+
+    /// Records the thread's running total after a model response.
+    pub(crate) fn update(&self, total_usage: &TokenUsage) {
+        *self
+            .latest_usage
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = total_usage.clone();
+    }
+
+    /// The record for this turn, counting tokens used since it started.
+    pub(crate) fn finish(
+        &self,
+        repo_hash: Option<String>,
+        now: Instant,
+        timestamp: DateTime<Utc>,
+    ) -> UsageRecord {
+        let latest = self
+            .latest_usage
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let start = &self.usage_at_start;
+        let duration = now.saturating_duration_since(self.started_at);
+        UsageRecord {
+            timestamp,
+            model: self.model.clone(),
+            input_tokens: (latest.input_tokens - start.input_tokens).max(0),
+            cached_input_tokens: (latest.cached_input_tokens - start.cached_input_tokens).max(0),
+            output_tokens: (latest.output_tokens - start.output_tokens).max(0),
+            reasoning_output_tokens: (latest.reasoning_output_tokens
+                - start.reasoning_output_tokens)
+                .max(0),
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            repo_hash,
+        }
+    }
+}
+
+/// Hex SHA-256 of the salt and the repository's identity, so records from clones of one
+/// repository match without revealing which repository it is.
+pub(crate) fn repo_hash(salt: &str, repo_identity: &str) -> String {
+    let digest = Sha256::new()
+        .chain_update(salt.as_bytes())
+        .chain_update([0])
+        .chain_update(repo_identity.as_bytes())
+        .finalize();
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// The repository identity for `cwd`: its canonical `origin` URL, or any remote's, falling back
+/// to the checkout's root path. `None` outside a git repository.
+pub(crate) async fn repo_identity(cwd: &Path) -> Option<String> {
+    let root = codex_git_utils::get_git_repo_root(cwd)?;
+    let remote = codex_git_utils::get_git_remote_urls(cwd)
+        .await
+        .and_then(|remotes| {
+            remotes
+                .get("origin")
+                .or_else(|| remotes.values().next())
+                .cloned()
+        })
+        .and_then(|url| codex_git_utils::canonicalize_git_remote_url(&url));
+    Some(remote.unwrap_or_else(|| root.display().to_string()))
+}
@@ -0,0 +1,283 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::Utc;
+use codex_aws_auth::AwsAuthConfig;
+use codex_aws_auth::AwsAuthContext;
+use codex_aws_auth::AwsRequestToSign;
+use codex_config::types::UsageExportConfig;
+use codex_config::types::UsageExportSink;
+use codex_http_client::build_reqwest_client_with_custom_ca;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Method;
+use serde_json::Value;
+use serde_json::json;
+use sha2::Digest;
+use sha2::Sha256;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::OnceCell;
+
+use crate::record::UsageRecord;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const OTLP_LOGS_PATH: &str = "/v1/logs";
+const OTLP_SCOPE: &str = "codex.usage";
+
+/// Destination that flushed records are written to.
+pub(crate) enum Sink {
+    File(PathBuf),
+    S3 {
+        client: reqwest::Client,
+        endpoint: String,
+        bucket: String,
+        prefix: String,
+        auth_config: AwsAuthConfig,
+        auth: OnceCell<AwsAuthContext>,
+    },
+    Otlp {
+        client: reqwest::Client,
+        endpoint: String,
+        headers: BTreeMap<String, String>,
+    },
+}
+
+impl Sink {
+    /// The sink `config` selects. `Ok(None)` when export is off; an error names the missing or
+    /// invalid setting.
+    pub(crate) fn from_config(config: &UsageExportConfig) -> Result<Option<Self>, String> {
+        let Some(kind) = config.sink else {
+            return Ok(None);
+        };
+        // Without a private salt, anyone could hash public repository URLs and match them
+        // against the exported records.
+        if config.hash_salt.is_empty() {
+            return Err("usage_export.hash_salt is required when usage_export.sink is set".into());
+        }
+        let sink = match kind {
+            UsageExportSink::File => {
+                let path = config
+                    .path
+                    .as_ref()
+                    .ok_or("usage_export.path is required for the file sink")?;
+                Self::File(path.to_path_buf())
+            }
+            UsageExportSink::S3 => Self::S3 {
+                client: http_client()?,
+                endpoint: config
+                    .endpoint
+                    .clone()
+                    .ok_or("usage_export.endpoint is required for the s3 sink")?,
+                bucket: config
+                    .bucket
+                    .clone()
+                    .ok_or("usage_export.bucket is required for the s3 sink")?,
+                prefix: config.prefix.clone(),
+                auth_config: AwsAuthConfig {
+                    profile: config.profile.clone(),
+                    region: config.region.clone(),
+                    service: "s3".to_string(),
+                },
+                auth: OnceCell::new(),
+            },
+            UsageExportSink::Otlp => Self::Otlp {
+                client: http_client()?,
+                endpoint: config
+                    .endpoint
+                    .clone()
+                    .ok_or("usage_export.endpoint is required for the otlp sink")?,
+                headers: config.headers.clone(),
+            },
+        };
+        Ok(Some(sink))
+    }
+
+    pub(crate) async fn write(&self, records: &[UsageRecord]) -> Result<(), String> {
+        match self {
+            Self::File(path) => append_json_lines(path, records).await,
+            Self::S3 {
+                client,
+                endpoint,
+                bucket,
+                prefix,
+                auth_config,
+                auth,
+            } => {
+                let auth = auth
+                    .get_or_try_init(|| AwsAuthContext::load(auth_config.clone()))
+                    .await
+                    .map_err(|err| format!("failed to load AWS credentials: {err}"))?;
+                let url = format!("{endpoint}/{bucket}/{}", object_key(prefix));
+                put_signed_object(client, auth, url, json_lines(records)?).await
+            }
+            Self::Otlp {
+                client,
+                endpoint,
+                headers,
+            } => {
+                let mut request = client
+                    .post(format!("{endpoint}{OTLP_LOGS_PATH}"))
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(otlp_logs(records).to_string());
+                for (name, value) in headers {
+                    request = request.header(name, value);
+                }
+                let response = request
+                    .send()
+                    .await
+                    .map_err(|err| format!("OTLP export failed: {err}"))?;
+                check_status(response, "OTLP collector")
+            }
+        }
+    }
+}
+
+fn http_client() -> Result<reqwest::Client, String> {
+    build_reqwest_client_with_custom_ca(reqwest::Client::builder().timeout(REQUEST_TIMEOUT))
+        .map_err(|err| format!("failed to build HTTP client: {err}"))
+}
+
+fn check_status(response: reqwest::Response, target: &str) -> Result<(), String> {
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(format!("{target} answered {status}"))
+    }
+}
+
+/// Records as JSON Lines, one record per line.
+pub(crate) fn json_lines(records: &[UsageRecord]) -> Result<String, String> {
+    let mut out = String::new();
+    for record in records {
+        let line = serde_json::to_string(record)
+            .map_err(|err| format!("failed to serialize usage record: {err}"))?;
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+async fn append_json_lines(path: &Path, records: &[UsageRecord]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+    }
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+        .map_err(|err| format!("failed to open {}: {err}", path.display()))?;
+    file.write_all(json_lines(records)?.as_bytes())
+        .await
+        .map_err(|err| format!("failed to write {}: {err}", path.display()))
+}
+
+/// A key that sorts by upload time and never collides, such as
+/// `codex-usage/2026-10-16/1760623500000-<uuid>.jsonl`.
+fn object_key(prefix: &str) -> String {
+    let now = Utc::now();
+    format!(
+        "{prefix}{}/{}-{}.jsonl",
+        now.format("%Y-%m-%d"),
+        now.timestamp_millis(),
+        uuid::Uuid::new_v4()
+    )
+}
+
+async fn put_signed_object(
+    client: &reqwest::Client,
+    auth: &AwsAuthContext,
+    url: String,
+    body: String,
+) -> Result<(), String> {
+    let body = Bytes::from(body);
+    let payload_hash: String = Sha256::digest(&body)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-ndjson"),
+    );
+    // S3 rejects signed requests that do not declare the payload hash.
+    headers.insert(
+        "x-amz-content-sha256",
+        HeaderValue::from_str(&payload_hash).map_err(|err| err.to_string())?,
+    );
+    let signed = auth
+        .sign(AwsRequestToSign {
+            method: Method::PUT,
+            url,
+            headers,
+            body: body.clone(),
+        })
+        .await
+        .map_err(|err| format!("failed to sign S3 upload: {err}"))?;
+    let response = client
+        .put(signed.url)
+        .headers(signed.headers)
+        .body(body)
+        .send()
+        .await
+        .map_err(|err| format!("S3 upload failed: {err}"))?;
+    check_status(response, "S3 endpoint")
+}
+
+/// An OTLP/HTTP JSON logs request with one log record per usage record.
+pub(crate) fn otlp_logs(records: &[UsageRecord]) -> Value {
+    let log_records: Vec<Value> = records
+        .iter()
+        .map(|record| {
+            let mut attributes = vec![
+                string_attribute("model", &record.model),
+                int_attribute("input_tokens", record.input_tokens),
+                int_attribute("cached_input_tokens", record.cached_input_tokens),
+                int_attribute("output_tokens", record.output_tokens),
+                int_attribute("reasoning_output_tokens", record.reasoning_output_tokens),
+                int_attribute(
+                    "duration_ms",
+                    i64::try_from(record.duration_ms).unwrap_or(i64::MAX),
+                ),
+            ];
+            if let Some(repo_hash) = &record.repo_hash {
+                attributes.push(string_attribute("repo_hash", repo_hash));
+            }
+            json!({
+                "timeUnixNano": record
+                    .timestamp
+                    .timestamp_nanos_opt()
+                    .unwrap_or_default()
+                    .to_string(),
+                "body": { "stringValue": OTLP_SCOPE },
+                "attributes": attributes,
+            })
+        })
+        .collect();
+    json!({
+        "resourceLogs": [{
+            "resource": {
+                "attributes": [string_attribute("service.name", "codex")],
+            },
+            "scopeLogs": [{
+                "scope": { "name": OTLP_SCOPE },
+                "logRecords": log_records,
+            }],
+        }],
+    })
+}
+
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// OTLP JSON carries 64-bit integers as strings.
+fn int_attribute(key: &str, value: i64) -> Value {
+    json!({ "key": key, "value": { "intValue": value.to_string() } })
+}
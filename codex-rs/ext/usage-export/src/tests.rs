@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::TimeZone;
+use chrono::Utc;
+use codex_config::types::UsageExportConfig;
+use codex_config::types::UsageExportSink;
+use codex_config::types::UsageExportToml;
+use codex_protocol::protocol::TokenUsage;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+use crate::exporter::UsageExporter;
+use crate::record::TurnUsage;
+use crate::record::UsageRecord;
+use crate::record::repo_hash;
+use crate::sink::Sink;
+
+fn usage(input_tokens: i64, cached_input_tokens: i64, output_tokens: i64) -> TokenUsage {
+    TokenUsage {
+        input_tokens,
+        cached_input_tokens,
+        output_tokens,
+        total_tokens: input_tokens + output_tokens,
+        ..Default::default()
+    }
+}
+
+fn record(model: &str) -> UsageRecord {
+    UsageRecord {
+        timestamp: Utc.timestamp_opt(1_760_623_500, 0).unwrap(),
+        model: model.to_string(),
+        input_tokens: 1200,
+        cached_input_tokens: 800,
+        output_tokens: 300,
+        reasoning_output_tokens: 100,
+        duration_ms: 4_500,
+        repo_hash: Some("abc123".to_string()),
+    }
+}
+
+#[test]
+fn turn_usage_counts_tokens_since_the_turn_started() {
+    let started = Instant::now();
+    let turn = TurnUsage::new("gpt-5", &usage(1000, 400, 200), started);
+    turn.update(&usage(1500, 900, 260));
+
+    let timestamp = Utc.timestamp_opt(1_760_623_500, 0).unwrap();
+    let record = turn.finish(
+        Some("hash".to_string()),
+        started + Duration::from_millis(2_500),
+        timestamp,
+    );
+
+    assert_eq!(
+        UsageRecord {
+            timestamp,
+            model: "gpt-5".to_string(),
+            input_tokens: 500,
+            cached_input_tokens: 500,
+            output_tokens: 60,
+            reasoning_output_tokens: 0,
+            duration_ms: 2_500,
+            repo_hash: Some("hash".to_string()),
+        },
+        record
+    );
+}
+
+#[test]
+fn repo_hash_is_stable_and_salted() {
+    let remote = "github.com/example/service";
+
+    assert_eq!(repo_hash("", remote), repo_hash("", remote));
+    assert_eq!(64, repo_hash("", remote).len());
+    assert_ne!(repo_hash("", remote), repo_hash("team-salt", remote));
+    assert!(!repo_hash("", remote).contains("example"));
+}
+
+#[tokio::test]
+async fn file_sink_appends_json_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("usage").join("records.jsonl");
+    let exporter = UsageExporter::new(Sink::File(path.clone()));
+
+    exporter.record(record("gpt-5"));
+    exporter.flush().await;
+    exporter.record(record("gpt-5-mini"));
+    exporter.flush().await;
+    // Nothing is left to write, so this appends nothing.
+    exporter.flush().await;
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let models: Vec<String> = contents
+        .lines()
+        .map(|line| {
+            let value: Value = serde_json::from_str(line).unwrap();
+            value["model"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(vec!["gpt-5".to_string(), "gpt-5-mini".to_string()], models);
+    assert!(contents.contains(r#""timestamp":"2025-10-16T14:05:00Z""#));
+}
+
+#[tokio::test]
+async fn otlp_sink_posts_one_log_record_per_usage_record() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/logs"))
+        .and(header("x-api-key", "secret"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    let sink = Sink::Otlp {
+        client: reqwest::Client::new(),
+        endpoint: server.uri(),
+        headers: BTreeMap::from([("x-api-key".to_string(), "secret".to_string())]),
+    };
+
+    sink.write(&[record("gpt-5"), record("gpt-5-mini")])
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(1, requests.len());
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    let log_records = &body["resourceLogs"][0]["scopeLogs"][0]["logRecords"];
+    assert_eq!(2, log_records.as_array().unwrap().len());
+    assert_eq!(
+        "1760623500000000000",
+        log_records[0]["timeUnixNano"].as_str().unwrap()
+    );
+    assert_eq!(
+        serde_json::json!({ "key": "input_tokens", "value": { "intValue": "1200" } }),
+        log_records[0]["attributes"][1]
+    );
+}
+
+#[test]
+fn sinks_require_a_hash_salt() {
+    let toml = UsageExportToml {
+        sink: Some(UsageExportSink::Otlp),
+        endpoint: Some("http://localhost:4318".to_string()),
+        ..UsageExportToml::default()
+    };
+    assert_eq!(
+        Some("usage_export.hash_salt is required when usage_export.sink is set".to_string()),
+        Sink::from_config(&UsageExportConfig::from(toml.clone())).err()
+    );
+
+    let salted = UsageExportConfig::from(UsageExportToml {
+        hash_salt: Some("private".to_string()),
+        ..toml
+    });
+    assert!(matches!(
+        Sink::from_config(&salted),
+        Ok(Some(Sink::Otlp { .. }))
+    ));
+}
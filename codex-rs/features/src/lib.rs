@@ -226,6 +226,36 @@ pub enum Feature {
     GuardianApproval,
     /// Enable persisted thread goals and automatic goal continuation.
     Goals,
+    /// Expose structured `git log`, `git blame`, commit, and TODO comment tools to the model.
+    GitHistoryTools,
+    /// Expose tools that plan symbol renames and file moves as reviewable patches.
+    RefactorTools,
+    /// Expose tools that read and edit Jupyter notebooks cell by cell.
+    NotebookTools,
+    /// Expose a tool that summarizes large log files into level counts and line clusters.
+    LogDigest,
+    /// Expose read-only query and schema tools for the databases listed under `[databases]`.
+    DatabaseTools,
+    /// Expose a structured HTTP request tool gated by the network policy.
+    HttpRequest,
+    /// Expose a tool that reports the OS, toolchain versions, and build-related env vars.
+    EnvSnapshot,
+    /// Expose a tool that reports outdated and vulnerable dependencies from manifests and lockfiles.
+    DependencyReport,
+    /// Expose a tool that times the `[bench]` command at a baseline revision and in the working tree.
+    Bench,
+    /// Expose a tool that profiles the `[profiling]` command and summarizes its hottest stacks.
+    Profiling,
+    /// Expose status, diff, and commit tools spanning the repositories under `[related_repos]`.
+    RelatedRepoTools,
+    /// Give each session a scratch directory that sandboxed commands can always write to.
+    Scratchpad,
+    /// Let the model register generated files as named artifacts of the session.
+    SessionArtifacts,
+    /// Record a post-mortem when a turn errors out or is interrupted and offer it on resume.
+    PostMortems,
+    /// Collapse repeated tool outputs in prompt history into stubs pointing at the latest copy.
+    DedupeToolOutputs,
     /// Add current context-window metadata to model-visible context.
     TokenBudget,
     /// Track and report a shared token budget across a session's agent threads.
@@ -1269,6 +1299,96 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Stable,
         default_enabled: true,
     },
+    FeatureSpec {
+        id: Feature::GitHistoryTools,
+        key: "git_history_tools",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::RefactorTools,
+        key: "refactor_tools",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::NotebookTools,
+        key: "notebook_tools",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::LogDigest,
+        key: "log_digest",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::DatabaseTools,
+        key: "database_tools",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::HttpRequest,
+        key: "http_request",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::EnvSnapshot,
+        key: "env_snapshot",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::DependencyReport,
+        key: "dependency_report",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::Bench,
+        key: "bench",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::Profiling,
+        key: "profiling",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::RelatedRepoTools,
+        key: "related_repo_tools",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::Scratchpad,
+        key: "scratchpad",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::SessionArtifacts,
+        key: "session_artifacts",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::PostMortems,
+        key: "post_mortems",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::DedupeToolOutputs,
+        key: "dedupe_tool_outputs",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::TokenBudget,
         key: "token_budget",
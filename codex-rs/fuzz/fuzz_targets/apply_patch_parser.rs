@@ -0,0 +1,32 @@
+#![no_main]
+
+use codex_apply_patch::StreamingPatchParser;
+use codex_apply_patch::parse_patch;
+use libfuzzer_sys::fuzz_target;
+
+// The first byte picks the size of the deltas fed to the streaming parser; the rest is the patch.
+fuzz_target!(|data: &[u8]| {
+    let Some((&chunk_len, patch)) = data.split_first() else {
+        return;
+    };
+    let Ok(patch) = std::str::from_utf8(patch) else {
+        return;
+    };
+    let _ = parse_patch(patch);
+
+    let chunk_len = usize::from(chunk_len.max(1));
+    let mut parser = StreamingPatchParser::default();
+    let mut rest = patch;
+    while !rest.is_empty() {
+        let mut end = chunk_len.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end += 1;
+        }
+        let (delta, tail) = rest.split_at(end);
+        if parser.push_delta(delta).is_err() {
+            return;
+        }
+        rest = tail;
+    }
+    let _ = parser.finish();
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// The first byte picks the wrap width (0 for unwrapped); the rest is the markdown source.
+fuzz_target!(|data: &[u8]| {
+    let Some((&width, source)) = data.split_first() else {
+        return;
+    };
+    let Ok(source) = std::str::from_utf8(source) else {
+        return;
+    };
+    let width = (width != 0).then_some(usize::from(width));
+    codex_tui::fuzzing::render_markdown(source, width);
+});
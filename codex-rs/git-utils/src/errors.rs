@@ -26,6 +26,10 @@ pub enum GitToolingError {
     NonRelativePath { path: PathBuf },
     #[error("path {path:?} escapes the repository root")]
     PathEscapesRepository { path: PathBuf },
+    #[error("invalid revision {revision:?}")]
+    InvalidRevision { revision: String },
+    #[error("invalid line range {start}-{end}; lines are 1-indexed and start must not exceed end")]
+    InvalidLineRange { start: u32, end: u32 },
     #[error("failed to process path inside worktree")]
     PathPrefix(#[from] std::path::StripPrefixError),
     #[error(transparent)]
@@ -0,0 +1,303 @@
+use std::ffi::OsString;
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::GitToolingError;
+use crate::operations::ensure_git_repository;
+use crate::operations::run_git_for_stdout;
+
+/// Commit bodies longer than this are cut at a character boundary.
+const MAX_COMMIT_BODY_BYTES: usize = 4_000;
+const RECORD_SEPARATOR: char = '\u{001e}';
+const FIELD_SEPARATOR: char = '\u{001f}';
+const BODY_TERMINATOR: char = '\u{001d}';
+// <RS> sha <US> author <US> email <US> author time <US> subject <US> body <GS>, then numstat lines.
+const LOG_FORMAT: &str = "--format=%x1e%H%x1f%an%x1f%ae%x1f%at%x1f%s%x1f%b%x1d";
+
+/// Query for [`git_log`].
+#[derive(Clone, Debug, Default)]
+pub struct GitLogRequest {
+    /// Revision or range passed to `git log`, such as `main..HEAD`. Defaults to `HEAD`.
+    pub revision_range: Option<String>,
+    /// Limit history to commits touching these paths.
+    pub paths: Vec<String>,
+    pub max_commits: usize,
+    pub max_files_per_commit: usize,
+}
+
+/// A commit with the files it touched, as reported by `git log --numstat`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GitHistoryCommit {
+    pub sha: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// Unix timestamp (seconds since epoch) of the author date.
+    pub author_time: i64,
+    pub subject: String,
+    pub body: String,
+    pub files: Vec<GitFileChange>,
+    /// True when `files` was capped.
+    pub files_truncated: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GitFileChange {
+    /// Path as printed by git; renames use git's `old => new` notation.
+    pub path: String,
+    /// `None` for binary files.
+    pub additions: Option<u64>,
+    pub deletions: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GitLog {
+    pub commits: Vec<GitHistoryCommit>,
+    /// True when more commits matched than `max_commits`.
+    pub truncated: bool,
+}
+
+/// Query for [`git_blame`]. Line numbers are 1-indexed and inclusive.
+#[derive(Clone, Debug)]
+pub struct GitBlameRequest {
+    pub path: String,
+    /// Revision to blame at. Defaults to the working tree.
+    pub revision: Option<String>,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub max_lines: u32,
+}
+
+/// Consecutive lines last changed by the same commit.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GitBlameHunk {
+    pub sha: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// Unix timestamp (seconds since epoch) of the author date.
+    pub author_time: i64,
+    pub summary: String,
+    /// Line number of the first line in the blamed file.
+    pub start_line: u32,
+    pub lines: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GitBlame {
+    pub hunks: Vec<GitBlameHunk>,
+    /// True when the requested range was longer than `max_lines` and was cut short.
+    pub truncated: bool,
+}
+
+/// Returns commits from `git log`, newest first, with the files each one touched.
+pub fn git_log(cwd: &Path, request: &GitLogRequest) -> Result<GitLog, GitToolingError> {
+    ensure_git_repository(cwd)?;
+    let mut args = vec![
+        OsString::from("log"),
+        OsString::from("--no-color"),
+        OsString::from("--numstat"),
+        OsString::from(LOG_FORMAT),
+        OsString::from(format!("--max-count={}", request.max_commits + 1)),
+    ];
+    if let Some(range) = &request.revision_range {
+        validate_revision(range)?;
+        args.push(OsString::from(range));
+    }
+    args.push(OsString::from("--"));
+    args.extend(request.paths.iter().map(OsString::from));
+
+    let stdout = run_git_for_stdout(cwd, args, /*env*/ None)?;
+    let mut commits = parse_log_output(&stdout, request.max_files_per_commit);
+    let truncated = commits.len() > request.max_commits;
+    commits.truncate(request.max_commits);
+    Ok(GitLog { commits, truncated })
+}
+
+/// Returns a single commit with the files it touched.
+pub fn git_show_commit(
+    cwd: &Path,
+    revision: &str,
+    max_files: usize,
+) -> Result<GitHistoryCommit, GitToolingError> {
+    let log = git_log(
+        cwd,
+        &GitLogRequest {
+            revision_range: Some(revision.to_string()),
+            paths: Vec::new(),
+            max_commits: 1,
+            max_files_per_commit: max_files,
+        },
+    )?;
+    log.commits
+        .into_iter()
+        .next()
+        .ok_or_else(|| GitToolingError::InvalidRevision {
+            revision: revision.to_string(),
+        })
+}
+
+/// Returns who last changed each line in the requested range, grouped by commit.
+pub fn git_blame(cwd: &Path, request: &GitBlameRequest) -> Result<GitBlame, GitToolingError> {
+    ensure_git_repository(cwd)?;
+    if request.start_line == 0 || request.end_line < request.start_line {
+        return Err(GitToolingError::InvalidLineRange {
+            start: request.start_line,
+            end: request.end_line,
+        });
+    }
+    let last_allowed = request
+        .start_line
+        .saturating_add(request.max_lines.max(1) - 1);
+    let truncated = request.end_line > last_allowed;
+    let end_line = request.end_line.min(last_allowed);
+
+    let mut args = vec![
+        OsString::from("blame"),
+        OsString::from("--line-porcelain"),
+        OsString::from(format!("-L{},{end_line}", request.start_line)),
+    ];
+    if let Some(revision) = &request.revision {
+        validate_revision(revision)?;
+        args.push(OsString::from(revision));
+    }
+    args.push(OsString::from("--"));
+    args.push(OsString::from(&request.path));
+
+    let stdout = run_git_for_stdout(cwd, args, /*env*/ None)?;
+    Ok(GitBlame {
+        hunks: parse_blame_porcelain(&stdout),
+        truncated,
+    })
+}
+
+/// Rejects revisions git would parse as options.
+fn validate_revision(revision: &str) -> Result<(), GitToolingError> {
+    if revision.trim().is_empty() || revision.starts_with('-') {
+        return Err(GitToolingError::InvalidRevision {
+            revision: revision.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn parse_log_output(stdout: &str, max_files_per_commit: usize) -> Vec<GitHistoryCommit> {
+    stdout
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let (header, numstat) = record.split_once(BODY_TERMINATOR)?;
+            let mut fields = header.splitn(6, FIELD_SEPARATOR);
+            let sha = fields.next()?.trim().to_string();
+            let author_name = fields.next()?.to_string();
+            let author_email = fields.next()?.to_string();
+            let author_time = fields.next()?.parse().unwrap_or(0);
+            let subject = fields.next()?.to_string();
+            let body = truncate_at_char_boundary(fields.next()?.trim(), MAX_COMMIT_BODY_BYTES);
+
+            let mut files = numstat
+                .lines()
+                .filter_map(parse_numstat_line)
+                .collect::<Vec<_>>();
+            let files_truncated = files.len() > max_files_per_commit;
+            files.truncate(max_files_per_commit);
+            Some(GitHistoryCommit {
+                sha,
+                author_name,
+                author_email,
+                author_time,
+                subject,
+                body,
+                files,
+                files_truncated,
+            })
+        })
+        .collect()
+}
+
+fn parse_numstat_line(line: &str) -> Option<GitFileChange> {
+    let mut parts = line.splitn(3, '\t');
+    let additions = parts.next()?;
+    let deletions = parts.next()?;
+    let path = parts.next()?;
+    Some(GitFileChange {
+        path: path.to_string(),
+        // Binary files report `-` for both counts.
+        additions: additions.parse().ok(),
+        deletions: deletions.parse().ok(),
+    })
+}
+
+fn parse_blame_porcelain(stdout: &str) -> Vec<GitBlameHunk> {
+    let mut hunks: Vec<GitBlameHunk> = Vec::new();
+    let mut current: Option<GitBlameHunk> = None;
+    for line in stdout.lines() {
+        let Some(entry) = current.as_mut() else {
+            // `<sha> <original line> <final line> [<group size>]` starts each line's record.
+            let mut parts = line.split(' ');
+            let (Some(sha), Some(_), Some(final_line)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            current = Some(GitBlameHunk {
+                sha: sha.to_string(),
+                author_name: String::new(),
+                author_email: String::new(),
+                author_time: 0,
+                summary: String::new(),
+                start_line: final_line.parse().unwrap_or(0),
+                lines: Vec::new(),
+            });
+            continue;
+        };
+
+        // The tab-prefixed source line ends each record.
+        if let Some(content) = line.strip_prefix('\t') {
+            let Some(mut entry) = current.take() else {
+                continue;
+            };
+            entry.lines.push(content.to_string());
+            match hunks.last_mut() {
+                Some(previous)
+                    if previous.sha == entry.sha
+                        && previous.start_line + previous.lines.len() as u32
+                            == entry.start_line =>
+                {
+                    previous.lines.append(&mut entry.lines);
+                }
+                _ => hunks.push(entry),
+            }
+            continue;
+        }
+
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        match key {
+            "author" => entry.author_name = value.to_string(),
+            "author-mail" => {
+                entry.author_email = value
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string();
+            }
+            "author-time" => entry.author_time = value.parse().unwrap_or(0),
+            "summary" => entry.summary = value.to_string(),
+            _ => {}
+        }
+    }
+    hunks
+}
+
+fn truncate_at_char_boundary(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", &text[..end])
+}
+
+#[cfg(test)]
+#[path = "history_tests.rs"]
+mod tests;
@@ -0,0 +1,231 @@
+use std::path::Path;
+use std::process::Command;
+
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+fn git(repo: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args([
+            "-c",
+            "user.name=Ada",
+            "-c",
+            "user.email=ada@example.com",
+            "-c",
+            "commit.gpgsign=false",
+        ])
+        .args(args)
+        .current_dir(repo)
+        .status()
+        .expect("run git");
+    assert_eq!(status.code(), Some(0), "git {args:?}");
+}
+
+#[test]
+fn parses_log_records_with_numstat_and_caps_files() {
+    let stdout = "\u{1e}abc123\u{1f}Ada\u{1f}ada@example.com\u{1f}1700000000\u{1f}Fix parser\u{1f}Handle empty input.\n\nRefs #4\n\u{1d}\n\n3\t1\tsrc/lib.rs\n-\t-\tassets/logo.png\n2\t0\tREADME.md\n\
+                  \u{1e}def456\u{1f}Grace\u{1f}grace@example.com\u{1f}1690000000\u{1f}Initial commit\u{1f}\u{1d}\n\n10\t0\tsrc/{old.rs => new.rs}";
+
+    assert_eq!(
+        parse_log_output(stdout, /*max_files_per_commit*/ 2),
+        vec![
+            GitHistoryCommit {
+                sha: "abc123".to_string(),
+                author_name: "Ada".to_string(),
+                author_email: "ada@example.com".to_string(),
+                author_time: 1_700_000_000,
+                subject: "Fix parser".to_string(),
+                body: "Handle empty input.\n\nRefs #4".to_string(),
+                files: vec![
+                    GitFileChange {
+                        path: "src/lib.rs".to_string(),
+                        additions: Some(3),
+                        deletions: Some(1),
+                    },
+                    GitFileChange {
+                        path: "assets/logo.png".to_string(),
+                        additions: None,
+                        deletions: None,
+                    },
+                ],
+                files_truncated: true,
+            },
+            GitHistoryCommit {
+                sha: "def456".to_string(),
+                author_name: "Grace".to_string(),
+                author_email: "grace@example.com".to_string(),
+                author_time: 1_690_000_000,
+                subject: "Initial commit".to_string(),
+                body: String::new(),
+                files: vec![GitFileChange {
+                    path: "src/{old.rs => new.rs}".to_string(),
+                    additions: Some(10),
+                    deletions: Some(0),
+                }],
+                files_truncated: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn parses_line_porcelain_into_hunks() {
+    let stdout = "\
+aaa 1 10 2
+author Ada
+author-mail <ada@example.com>
+author-time 1700000000
+summary Add retry loop
+filename src/lib.rs
+\tfor attempt in 0..3 {
+aaa 2 11
+author Ada
+author-mail <ada@example.com>
+author-time 1700000000
+summary Add retry loop
+filename src/lib.rs
+\t    send()?;
+bbb 5 12 1
+author Grace
+author-mail <grace@example.com>
+author-time 1690000000
+summary Initial commit
+previous ccc src/lib.rs
+filename src/lib.rs
+\t}";
+
+    assert_eq!(
+        parse_blame_porcelain(stdout),
+        vec![
+            GitBlameHunk {
+                sha: "aaa".to_string(),
+                author_name: "Ada".to_string(),
+                author_email: "ada@example.com".to_string(),
+                author_time: 1_700_000_000,
+                summary: "Add retry loop".to_string(),
+                start_line: 10,
+                lines: vec![
+                    "for attempt in 0..3 {".to_string(),
+                    "    send()?;".to_string()
+                ],
+            },
+            GitBlameHunk {
+                sha: "bbb".to_string(),
+                author_name: "Grace".to_string(),
+                author_email: "grace@example.com".to_string(),
+                author_time: 1_690_000_000,
+                summary: "Initial commit".to_string(),
+                start_line: 12,
+                lines: vec!["}".to_string()],
+            },
+        ]
+    );
+}
+
+#[test]
+fn rejects_option_like_revisions_and_bad_ranges() {
+    let temp_dir = tempfile::tempdir().expect("create temp dir");
+    git(temp_dir.path(), &["init", "-q"]);
+
+    let err = git_log(
+        temp_dir.path(),
+        &GitLogRequest {
+            revision_range: Some("--output=/tmp/x".to_string()),
+            max_commits: 1,
+            ..Default::default()
+        },
+    )
+    .expect_err("option-like revision");
+    assert_eq!(err.to_string(), "invalid revision \"--output=/tmp/x\"");
+
+    let err = git_blame(
+        temp_dir.path(),
+        &GitBlameRequest {
+            path: "a.txt".to_string(),
+            revision: None,
+            start_line: 5,
+            end_line: 2,
+            max_lines: 100,
+        },
+    )
+    .expect_err("inverted range");
+    assert!(matches!(
+        err,
+        GitToolingError::InvalidLineRange { start: 5, end: 2 }
+    ));
+}
+
+#[test]
+fn reads_history_and_blame_from_repository() {
+    let temp_dir = tempfile::tempdir().expect("create temp dir");
+    let repo = temp_dir.path();
+    git(repo, &["init", "-q"]);
+    std::fs::write(repo.join("a.txt"), "one\ntwo\n").expect("write a.txt");
+    git(repo, &["add", "a.txt"]);
+    git(repo, &["commit", "-q", "-m", "Add a"]);
+    std::fs::write(repo.join("a.txt"), "one\nTWO\nthree\n").expect("rewrite a.txt");
+    std::fs::write(repo.join("b.txt"), "b\n").expect("write b.txt");
+    git(repo, &["add", "a.txt", "b.txt"]);
+    git(
+        repo,
+        &["commit", "-q", "-m", "Update a", "-m", "Body text."],
+    );
+
+    let log = git_log(
+        repo,
+        &GitLogRequest {
+            revision_range: None,
+            paths: vec!["a.txt".to_string()],
+            max_commits: 1,
+            max_files_per_commit: 10,
+        },
+    )
+    .expect("git log");
+    assert!(log.truncated);
+    let [latest] = log.commits.as_slice() else {
+        panic!("expected one commit, got {:?}", log.commits);
+    };
+    assert_eq!(
+        (
+            latest.subject.as_str(),
+            latest.body.as_str(),
+            latest.author_name.as_str()
+        ),
+        ("Update a", "Body text.", "Ada")
+    );
+
+    let commit = git_show_commit(repo, "HEAD~1", /*max_files*/ 10).expect("show commit");
+    assert_eq!(
+        commit.files,
+        vec![GitFileChange {
+            path: "a.txt".to_string(),
+            additions: Some(2),
+            deletions: Some(0),
+        }]
+    );
+
+    let blame = git_blame(
+        repo,
+        &GitBlameRequest {
+            path: "a.txt".to_string(),
+            revision: None,
+            start_line: 1,
+            end_line: 3,
+            max_lines: 2,
+        },
+    )
+    .expect("git blame");
+    assert!(blame.truncated);
+    assert_eq!(
+        blame
+            .hunks
+            .iter()
+            .map(|hunk| (hunk.summary.as_str(), hunk.start_line, hunk.lines.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("Add a", 1, vec!["one".to_string()]),
+            ("Update a", 2, vec!["TWO".to_string()]),
+        ]
+    );
+}
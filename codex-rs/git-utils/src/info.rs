@@ -266,6 +266,14 @@ fn trim_git_suffix(value: &str) -> &str {
 }
 
 pub async fn get_has_changes(cwd: &Path) -> Option<bool> {
+    get_status_entries(cwd)
+        .await
+        .map(|entries| !entries.is_empty())
+}
+
+/// Return `git status --porcelain` entries such as ` M src/lib.rs` or `?? notes.md`.
+/// Returns `None` if not in a git repo or on error/timeout.
+pub async fn get_status_entries(cwd: &Path) -> Option<Vec<String>> {
     let git = Path::new("git");
     let fsmonitor = detect_local_fsmonitor_override(git, cwd).await;
     let output =
@@ -274,7 +282,13 @@ pub async fn get_has_changes(cwd: &Path) -> Option<bool> {
         return None;
     }
 
-    Some(!output.stdout.is_empty())
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect(),
+    )
 }
 
 fn parse_git_remote_urls(stdout: &str) -> Option<BTreeMap<String, String>> {
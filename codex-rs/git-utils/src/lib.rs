@@ -3,9 +3,11 @@ mod baseline;
 mod branch;
 mod errors;
 mod fsmonitor;
+mod history;
 mod info;
 mod operations;
 mod platform;
+mod todos;
 
 pub use apply::ApplyGitRequest;
 pub use apply::ApplyGitResult;
@@ -25,6 +27,16 @@ pub use errors::GitToolingError;
 pub use fsmonitor::FsmonitorOverride;
 pub use fsmonitor::FsmonitorProbeRunner;
 pub use fsmonitor::detect_fsmonitor_override;
+pub use history::GitBlame;
+pub use history::GitBlameHunk;
+pub use history::GitBlameRequest;
+pub use history::GitFileChange;
+pub use history::GitHistoryCommit;
+pub use history::GitLog;
+pub use history::GitLogRequest;
+pub use history::git_blame;
+pub use history::git_log;
+pub use history::git_show_commit;
 pub use info::CommitLogEntry;
 pub use info::GitDiffToRemote;
 pub use info::GitInfo;
@@ -37,8 +49,14 @@ pub use info::get_git_remote_urls_assume_git_repo;
 pub use info::get_git_repo_root;
 pub use info::get_has_changes;
 pub use info::get_head_commit_hash;
+pub use info::get_status_entries;
 pub use info::git_diff_to_remote;
 pub use info::local_git_branches;
 pub use info::recent_commits;
 pub use info::resolve_root_git_project_for_trust;
 pub use platform::create_symlink;
+pub use todos::TODO_GREP_ARGS;
+pub use todos::TodoComment;
+pub use todos::TodoScan;
+pub use todos::parse_todo_grep_output;
+pub use todos::scan_todo_comments;
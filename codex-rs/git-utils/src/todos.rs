@@ -0,0 +1,195 @@
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::GitToolingError;
+use crate::operations::ensure_git_repository;
+use crate::operations::run_git_for_stdout;
+
+const TODO_MARKERS: [&str; 3] = ["TODO", "FIXME", "HACK"];
+
+/// `git grep` arguments listing marker lines in tracked and untracked files.
+///
+/// `--show-function` adds a `path=line=declaration` line ahead of each match naming the nearest
+/// preceding top-level declaration, which [`parse_todo_grep_output`] reports as context.
+pub const TODO_GREP_ARGS: &[&str] = &[
+    "grep",
+    "-n",
+    "-I",
+    "-w",
+    "--show-function",
+    "--untracked",
+    "--no-color",
+    "-e",
+    "TODO",
+    "-e",
+    "FIXME",
+    "-e",
+    "HACK",
+    "--",
+];
+
+/// A TODO/FIXME/HACK marker found inside a source comment.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TodoComment {
+    /// Path relative to the directory `git grep` ran in.
+    pub path: String,
+    pub line: u32,
+    pub marker: String,
+    /// Comment text after the marker, without an `(owner):` tag.
+    pub text: String,
+    /// Nearest preceding top-level declaration, when git found one.
+    pub context: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TodoScan {
+    pub todos: Vec<TodoComment>,
+    /// True when more markers were found than the requested maximum.
+    pub truncated: bool,
+}
+
+/// Lists TODO/FIXME/HACK comments in the repository containing `cwd`.
+pub fn scan_todo_comments(cwd: &Path, max_results: usize) -> Result<TodoScan, GitToolingError> {
+    ensure_git_repository(cwd)?;
+    match run_git_for_stdout(
+        cwd,
+        TODO_GREP_ARGS.iter().map(OsString::from),
+        /*env*/ None,
+    ) {
+        Ok(stdout) => Ok(parse_todo_grep_output(&stdout, max_results)),
+        // `git grep` exits 1 when nothing matches.
+        Err(GitToolingError::GitCommand { status, .. }) if status.code() == Some(1) => {
+            Ok(TodoScan {
+                todos: Vec::new(),
+                truncated: false,
+            })
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Parses output from `git grep` run with [`TODO_GREP_ARGS`].
+///
+/// Markers outside comments, such as inside string literals, are skipped.
+pub fn parse_todo_grep_output(stdout: &str, max_results: usize) -> TodoScan {
+    let mut todos = Vec::new();
+    let mut context: Option<(&str, &str)> = None;
+    let mut truncated = false;
+    for line in stdout.lines() {
+        let Some((path, separator, line_number, content)) = split_grep_line(line) else {
+            continue;
+        };
+        if separator == '=' {
+            context = Some((path, content.trim()));
+            continue;
+        }
+        let Some((marker, text)) = comment_marker(path, content) else {
+            continue;
+        };
+        if todos.len() == max_results {
+            truncated = true;
+            break;
+        }
+        todos.push(TodoComment {
+            path: path.to_string(),
+            line: line_number,
+            marker: marker.to_string(),
+            text,
+            context: context
+                .filter(|(context_path, _)| *context_path == path)
+                .map(|(_, declaration)| declaration.to_string()),
+        });
+    }
+    TodoScan { todos, truncated }
+}
+
+/// Splits `path:line:content` (a match) or `path=line=content` (a function context line).
+fn split_grep_line(line: &str) -> Option<(&str, char, u32, &str)> {
+    line.char_indices()
+        .filter(|(_, ch)| matches!(ch, ':' | '='))
+        .find_map(|(index, separator)| {
+            let rest = &line[index + 1..];
+            let digits = rest.len()
+                - rest
+                    .trim_start_matches(|ch: char| ch.is_ascii_digit())
+                    .len();
+            let content = rest[digits..].strip_prefix(separator)?;
+            let line_number = rest[..digits].parse().ok()?;
+            Some((&line[..index], separator, line_number, content))
+        })
+}
+
+/// Returns the marker and its text when a marker appears inside a comment on this line.
+fn comment_marker(path: &str, content: &str) -> Option<(&'static str, String)> {
+    let comment_start = comment_prefixes(path)
+        .iter()
+        .filter_map(|prefix| {
+            // `*` only continues a block comment at the start of a line.
+            if *prefix == "*" {
+                content
+                    .trim_start()
+                    .starts_with('*')
+                    .then(|| content.len() - content.trim_start().len())
+            } else {
+                content.find(prefix)
+            }
+        })
+        .min()?;
+    let comment = &content[comment_start..];
+
+    let (index, marker) = TODO_MARKERS
+        .iter()
+        .filter_map(|marker| word_position(comment, marker).map(|index| (index, *marker)))
+        .min_by_key(|(index, _)| *index)?;
+    let mut text = comment[index + marker.len()..].trim_start();
+    if let Some(owner_tagged) = text.strip_prefix('(')
+        && let Some((_, rest)) = owner_tagged.split_once(')')
+    {
+        text = rest;
+    }
+    let text = text
+        .trim_start_matches([':', '-', ' '])
+        .trim_end()
+        .trim_end_matches("*/")
+        .trim_end_matches("-->")
+        .trim_end();
+    Some((marker, text.to_string()))
+}
+
+fn word_position(haystack: &str, word: &str) -> Option<usize> {
+    let is_word_char = |ch: char| ch.is_alphanumeric() || ch == '_';
+    haystack
+        .match_indices(word)
+        .map(|(index, _)| index)
+        .find(|index| {
+            let before = haystack[..*index].chars().next_back();
+            let after = haystack[index + word.len()..].chars().next();
+            !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+        })
+}
+
+fn comment_prefixes(path: &str) -> &'static [&'static str] {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default();
+    match extension {
+        "py" | "sh" | "bash" | "zsh" | "fish" | "rb" | "pl" | "r" | "yaml" | "yml" | "toml"
+        | "ex" | "exs" | "nix" | "ps1" | "bzl" | "bazel" | "cmake" => &["#"],
+        "sql" | "lua" | "hs" | "elm" => &["--"],
+        "html" | "xml" | "md" | "svg" => &["<!--"],
+        "vue" | "svelte" => &["<!--", "//", "/*", "*"],
+        // Dockerfile, Makefile, and other extensionless files usually use `#`.
+        "" => &["#", "//"],
+        _ => &["//", "/*", "*"],
+    }
+}
+
+#[cfg(test)]
+#[path = "todos_tests.rs"]
+mod tests;
@@ -0,0 +1,78 @@
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+fn todo(path: &str, line: u32, marker: &str, text: &str, context: Option<&str>) -> TodoComment {
+    TodoComment {
+        path: path.to_string(),
+        line,
+        marker: marker.to_string(),
+        text: text.to_string(),
+        context: context.map(str::to_string),
+    }
+}
+
+#[test]
+fn parses_comment_markers_with_declaration_context() {
+    let stdout = "\
+src/main.rs=1=fn main() {
+src/main.rs:2:    let a = 1; // TODO: handle errors
+src/main.rs:3:    let s = \"TODO not a comment\";
+src/main.rs=6=impl Foo {
+src/main.rs:8:        // FIXME(alice): this leaks
+src/main.rs:12:    /* HACK - pin the version */
+scripts/run.py=1=class Runner:
+scripts/run.py:3:        # HACK: workaround for CI
+scripts/run.py:4:        value = \"FIXME\"
+docs/a:b.md:7:<!-- TODO document flags -->
+";
+
+    assert_eq!(
+        parse_todo_grep_output(stdout, /*max_results*/ 10),
+        TodoScan {
+            todos: vec![
+                todo(
+                    "src/main.rs",
+                    2,
+                    "TODO",
+                    "handle errors",
+                    Some("fn main() {")
+                ),
+                todo("src/main.rs", 8, "FIXME", "this leaks", Some("impl Foo {")),
+                todo(
+                    "src/main.rs",
+                    12,
+                    "HACK",
+                    "pin the version",
+                    Some("impl Foo {")
+                ),
+                todo(
+                    "scripts/run.py",
+                    3,
+                    "HACK",
+                    "workaround for CI",
+                    Some("class Runner:")
+                ),
+                todo("docs/a:b.md", 7, "TODO", "document flags", None),
+            ],
+            truncated: false,
+        }
+    );
+}
+
+#[test]
+fn caps_results_and_requires_whole_word_markers() {
+    let stdout = "\
+a.rs:1:// TODOS are not markers
+a.rs:2:// TODO first
+a.rs:3:// TODO second
+";
+
+    assert_eq!(
+        parse_todo_grep_output(stdout, /*max_results*/ 1),
+        TodoScan {
+            todos: vec![todo("a.rs", 2, "TODO", "first", None)],
+            truncated: true,
+        }
+    );
+}
@@ -281,7 +281,8 @@ fn fallback_managed_hooks_source_path(
 ) -> AbsolutePathBuf {
     match requirement_source {
         Some(RequirementSource::SystemRequirementsToml { file })
-        | Some(RequirementSource::LegacyManagedConfigTomlFromFile { file }) => file.clone(),
+        | Some(RequirementSource::LegacyManagedConfigTomlFromFile { file })
+        | Some(RequirementSource::TeamConfig { file }) => file.clone(),
         Some(RequirementSource::MdmManagedPreferences { domain, key }) => {
             synthetic_layer_path(&format!("<mdm:{domain}:{key}>/requirements.toml"))
         }
@@ -370,6 +371,7 @@ fn load_toml_hooks_from_layer(
 fn config_toml_source_path(layer: &ConfigLayerEntry) -> AbsolutePathBuf {
     match &layer.name {
         ConfigLayerSource::System { file }
+        | ConfigLayerSource::Team { file, .. }
         | ConfigLayerSource::User { file, .. }
         | ConfigLayerSource::LegacyManagedConfigTomlFromFile { file } => file.clone(),
         ConfigLayerSource::Project { dot_codex_folder } => layer
@@ -681,7 +683,10 @@ fn hook_trusted_hash(is_managed: bool, state: Option<&HookStateToml>) -> Option<
 fn hook_metadata_for_config_layer_source(source: &ConfigLayerSource) -> (HookSource, bool) {
     match source {
         ConfigLayerSource::System { .. } => (HookSource::System, true),
-        ConfigLayerSource::User { .. } => (HookSource::User, false),
+        // A team file is synced from a URL, so its hooks need the same review as the user's own.
+        ConfigLayerSource::User { .. } | ConfigLayerSource::Team { .. } => {
+            (HookSource::User, false)
+        }
         ConfigLayerSource::Project { .. } => (HookSource::Project, false),
         ConfigLayerSource::Mdm { .. } => (HookSource::Mdm, true),
         ConfigLayerSource::EnterpriseManaged { .. } => (HookSource::CloudManagedConfig, true),
@@ -713,7 +718,10 @@ fn hook_source_for_requirement_source(source: Option<&RequirementSource>) -> Hoo
             hook_source_for_requirement_source(sources.first())
         }
         Some(RequirementSource::EnterpriseManaged { .. }) => HookSource::CloudRequirements,
-        Some(RequirementSource::Unknown) | None => HookSource::Unknown,
+        // Team locks only carry allow-lists, never managed hooks.
+        Some(RequirementSource::TeamConfig { .. }) | Some(RequirementSource::Unknown) | None => {
+            HookSource::Unknown
+        }
     }
 }
 
@@ -1311,6 +1319,13 @@ mod tests {
             }),
             (HookSource::User, false),
         );
+        assert_eq!(
+            super::hook_metadata_for_config_layer_source(&ConfigLayerSource::Team {
+                file: config_file.clone(),
+                locked: true,
+            }),
+            (HookSource::User, false),
+        );
         assert_eq!(
             super::hook_metadata_for_config_layer_source(&ConfigLayerSource::Project {
                 dot_codex_folder
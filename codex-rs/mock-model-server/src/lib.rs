@@ -0,0 +1,365 @@
+//! A stand-in model provider for end-to-end tests.
+//!
+//! [`MockModelServer`] speaks the Responses API wire format and answers each `POST /v1/responses`
+//! with the next reply of a [`Scenario`]: streamed messages, tool calls, malformed events, rate
+//! limits, or streams that drop before completing.  Tests embed it as a library; other harnesses
+//! run the `codex-mock-model-server` binary with a scenario file and point a model provider at it,
+//! so no API key or network access is needed.  Replies that depend on the request, like those of
+//! `codex tutorial`, come from a responder passed to [`MockModelServer::start_with_responder`].
+//!
+//! Besides the model endpoint, `GET /v1/models` returns an empty list and `GET /requests` returns
+//! the JSON bodies of the model requests received so far.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::fs::{self};
+use std::io::Read;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::thread::JoinHandle;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use clap::Parser;
+use serde::Serialize;
+use serde_json::Value;
+use tiny_http::Header;
+use tiny_http::Method;
+use tiny_http::Request;
+use tiny_http::Response;
+use tiny_http::Server;
+use tiny_http::StatusCode;
+
+mod scenario;
+use scenario::Reply;
+pub use scenario::Scenario;
+pub use scenario::ScriptedResponse;
+use scenario::sse_reply;
+
+/// CLI arguments for the mock server.
+#[derive(Debug, Clone, Parser)]
+#[command(
+    name = "codex-mock-model-server",
+    about = "Scripted Responses API server for end-to-end tests"
+)]
+pub struct Args {
+    /// JSON scenario file: `{"responses": [{"type": "message", "text": "..."}, ...]}`.
+    #[arg(long, value_name = "FILE")]
+    pub scenario: PathBuf,
+
+    /// Port to listen on. If not set, an ephemeral port is used.
+    #[arg(long)]
+    pub port: Option<u16>,
+
+    /// Path to a JSON file to write startup info (single line). Includes {"port": <u16>}.
+    #[arg(long, value_name = "FILE")]
+    pub server_info: Option<PathBuf>,
+
+    /// Enable HTTP shutdown endpoint at GET /shutdown
+    #[arg(long)]
+    pub http_shutdown: bool,
+}
+
+#[derive(Serialize)]
+struct ServerInfo {
+    port: u16,
+    pid: u32,
+}
+
+/// Entry point for the library main, for parity with other crates.
+pub fn run_main(args: Args) -> Result<()> {
+    let scenario = fs::read_to_string(&args.scenario)
+        .with_context(|| format!("reading {}", args.scenario.display()))?;
+    let scenario: Scenario = serde_json::from_str(&scenario)
+        .with_context(|| format!("parsing {}", args.scenario.display()))?;
+
+    let mut server = MockModelServer::bind(args.port, scenario, args.http_shutdown)?;
+    if let Some(path) = args.server_info.as_ref() {
+        write_server_info(path, server.addr.port())?;
+    }
+    eprintln!("codex-mock-model-server listening on {}", server.addr);
+
+    if let Some(thread) = server.thread.take() {
+        let _ = thread.join();
+    }
+    Err(anyhow!("server stopped unexpectedly"))
+}
+
+/// A running mock server.  Dropping it stops the server.
+pub struct MockModelServer {
+    addr: SocketAddr,
+    server: Arc<Server>,
+    state: Arc<Mutex<ServerState>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Turns the body of a model request into the server-sent events of its reply.
+pub type Responder = Box<dyn FnMut(&Value) -> Vec<Value> + Send>;
+
+struct ServerState {
+    replies: Replies,
+    requests: Vec<Value>,
+}
+
+enum Replies {
+    Scripted(VecDeque<ScriptedResponse>),
+    Responder(Responder),
+}
+
+impl MockModelServer {
+    /// Starts serving `scenario` on an ephemeral port.
+    pub fn start(scenario: Scenario) -> Result<Self> {
+        Self::bind(/*port*/ None, scenario, /*http_shutdown*/ false)
+    }
+
+    /// Starts serving on an ephemeral port, answering each model request with the events
+    /// `responder` returns for its body.
+    pub fn start_with_responder(responder: Responder) -> Result<Self> {
+        Self::bind_replies(
+            /*port*/ None,
+            Replies::Responder(responder),
+            /*http_shutdown*/ false,
+        )
+    }
+
+    fn bind(port: Option<u16>, scenario: Scenario, http_shutdown: bool) -> Result<Self> {
+        Self::bind_replies(
+            port,
+            Replies::Scripted(scenario.responses.into()),
+            http_shutdown,
+        )
+    }
+
+    fn bind_replies(port: Option<u16>, replies: Replies, http_shutdown: bool) -> Result<Self> {
+        let addr = SocketAddr::from(([127, 0, 0, 1], port.unwrap_or(0)));
+        let listener = TcpListener::bind(addr).with_context(|| format!("failed to bind {addr}"))?;
+        let addr = listener.local_addr().context("failed to read local_addr")?;
+        let server = Arc::new(
+            Server::from_listener(listener, None)
+                .map_err(|err| anyhow!("creating HTTP server: {err}"))?,
+        );
+        let state = Arc::new(Mutex::new(ServerState {
+            replies,
+            requests: Vec::new(),
+        }));
+
+        let thread = std::thread::spawn({
+            let server = Arc::clone(&server);
+            let state = Arc::clone(&state);
+            move || {
+                for request in server.incoming_requests() {
+                    if let Err(err) = handle_request(request, &state, http_shutdown) {
+                        eprintln!("codex-mock-model-server: {err}");
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            server,
+            state,
+            thread: Some(thread),
+        })
+    }
+
+    /// Base URL for a model provider, e.g. `http://127.0.0.1:1234/v1`.
+    pub fn base_url(&self) -> String {
+        format!("http://{}/v1", self.addr)
+    }
+
+    /// Bodies of the model requests received so far, in order.
+    pub fn requests(&self) -> Vec<Value> {
+        self.lock_state().requests.clone()
+    }
+
+    /// Number of scripted replies not yet served.  Always zero with a responder.
+    pub fn remaining_responses(&self) -> usize {
+        match &self.lock_state().replies {
+            Replies::Scripted(responses) => responses.len(),
+            Replies::Responder(_) => 0,
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, ServerState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for MockModelServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn handle_request(
+    mut request: Request,
+    state: &Mutex<ServerState>,
+    http_shutdown: bool,
+) -> Result<()> {
+    let method = request.method().clone();
+    let url = request.url().to_string();
+    let reply = match (&method, url.as_str()) {
+        (Method::Post, "/v1/responses") => {
+            let mut body = Vec::new();
+            request.as_reader().read_to_end(&mut body)?;
+            let body = serde_json::from_slice(&body)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&body).into_owned()));
+            let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+            let response_id = format!("resp-{}", state.requests.len() + 1);
+            let reply = match &mut state.replies {
+                Replies::Scripted(responses) => match responses.pop_front() {
+                    Some(response) => response.reply(&response_id),
+                    None => plain_reply(500, "no scripted responses left"),
+                },
+                Replies::Responder(responder) => sse_reply(&responder(&body)),
+            };
+            state.requests.push(body);
+            reply
+        }
+        (Method::Get, "/v1/models") => json_reply(&serde_json::json!({
+            "data": [],
+            "object": "list",
+        })),
+        (Method::Get, "/requests") => {
+            let state = state.lock().unwrap_or_else(PoisonError::into_inner);
+            json_reply(&Value::Array(state.requests.clone()))
+        }
+        (Method::Get, "/shutdown") if http_shutdown => {
+            let _ = request.respond(Response::new_empty(StatusCode(200)));
+            std::process::exit(0);
+        }
+        _ => plain_reply(404, "not found"),
+    };
+
+    let headers = reply
+        .headers
+        .iter()
+        .filter_map(|(name, value)| Header::from_bytes(name.as_bytes(), value.as_bytes()).ok())
+        .collect::<Vec<_>>();
+    let body = reply.body.into_bytes();
+    let length = body.len();
+    request.respond(Response::new(
+        StatusCode(reply.status),
+        headers,
+        std::io::Cursor::new(body),
+        Some(length),
+        None,
+    ))?;
+    Ok(())
+}
+
+fn json_reply(value: &Value) -> Reply {
+    Reply {
+        status: 200,
+        headers: vec![("content-type", "application/json".to_string())],
+        body: value.to_string(),
+    }
+}
+
+fn plain_reply(status: u16, body: &str) -> Reply {
+    Reply {
+        status,
+        headers: vec![("content-type", "text/plain".to_string())],
+        body: body.to_string(),
+    }
+}
+
+fn write_server_info(path: &Path, port: u16) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+
+    let info = ServerInfo {
+        port,
+        pid: std::process::id(),
+    };
+    let mut data = serde_json::to_string(&info)?;
+    data.push('\n');
+    let mut f = File::create(path)?;
+    f.write_all(data.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::net::TcpStream;
+
+    fn post_responses(server: &MockModelServer, body: &str) -> String {
+        let mut stream = TcpStream::connect(server.addr).expect("connect");
+        write!(
+            stream,
+            "POST /v1/responses HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            server.addr,
+            body.len()
+        )
+        .expect("write request");
+        let mut response = String::new();
+        stream.read_to_string(&mut response).expect("read response");
+        response
+    }
+
+    #[test]
+    fn replies_follow_the_scenario_and_requests_are_recorded() {
+        let server = MockModelServer::start(Scenario {
+            responses: vec![
+                ScriptedResponse::Message {
+                    text: "hello".to_string(),
+                    chunk_chars: None,
+                },
+                ScriptedResponse::RateLimit {
+                    retry_after_seconds: None,
+                    usage_limit: true,
+                },
+            ],
+        })
+        .expect("start server");
+
+        let first = post_responses(&server, r#"{"model":"mock"}"#);
+        let second = post_responses(&server, r#"{"model":"mock"}"#);
+        let third = post_responses(&server, r#"{"model":"mock"}"#);
+
+        assert!(first.starts_with("HTTP/1.1 200"), "{first}");
+        assert!(first.contains("response.completed"), "{first}");
+        assert!(second.starts_with("HTTP/1.1 429"), "{second}");
+        assert!(second.contains("usage_limit_reached"), "{second}");
+        assert!(third.starts_with("HTTP/1.1 500"), "{third}");
+        assert_eq!(server.remaining_responses(), 0);
+        assert_eq!(
+            server.requests(),
+            vec![serde_json::json!({"model": "mock"}); 3]
+        );
+    }
+
+    #[test]
+    fn responder_replies_depend_on_the_request() {
+        let server = MockModelServer::start_with_responder(Box::new(|request| {
+            vec![serde_json::json!({ "type": "response.created", "model": request["model"] })]
+        }))
+        .expect("start server");
+
+        let response = post_responses(&server, r#"{"model":"tutorial"}"#);
+
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains("event: response.created"), "{response}");
+        assert!(response.contains(r#""model":"tutorial""#), "{response}");
+        assert_eq!(
+            server.requests(),
+            vec![serde_json::json!({"model": "tutorial"})]
+        );
+    }
+}
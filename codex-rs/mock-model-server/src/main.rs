@@ -0,0 +1,7 @@
+use clap::Parser;
+use codex_mock_model_server::Args as MockModelServerArgs;
+
+pub fn main() -> anyhow::Result<()> {
+    let args = MockModelServerArgs::parse();
+    codex_mock_model_server::run_main(args)
+}
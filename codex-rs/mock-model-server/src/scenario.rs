@@ -0,0 +1,306 @@
+//! Scripted model replies and their encoding in the Responses API wire format.
+
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+
+/// Replies served to successive `POST /v1/responses` requests, in order.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub responses: Vec<ScriptedResponse>,
+}
+
+/// One scripted reply to a model request.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScriptedResponse {
+    /// An assistant message, streamed as text deltas of at most `chunk_chars` characters.
+    Message {
+        text: String,
+        #[serde(default)]
+        chunk_chars: Option<usize>,
+    },
+    /// A function call.  `arguments` may be a JSON value or an already encoded string.
+    ToolCall {
+        call_id: String,
+        name: String,
+        #[serde(default)]
+        arguments: Value,
+    },
+    /// A completed response with one event whose `data` is truncated JSON.
+    MalformedJson,
+    /// `429 Too Many Requests`.  With `usage_limit`, the body reports a reached usage limit and
+    /// the rate-limit headers show the primary window as full.
+    RateLimit {
+        #[serde(default)]
+        retry_after_seconds: Option<u64>,
+        #[serde(default)]
+        usage_limit: bool,
+    },
+    /// Streams `text` as deltas, then closes the connection before `response.completed`.
+    StreamDrop {
+        #[serde(default)]
+        text: String,
+    },
+    /// Arbitrary server-sent events, sent as given.  Each needs a `type` field.
+    Events { events: Vec<Value> },
+}
+
+/// An HTTP reply ready to send.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Reply {
+    pub(crate) status: u16,
+    pub(crate) headers: Vec<(&'static str, String)>,
+    pub(crate) body: String,
+}
+
+impl ScriptedResponse {
+    pub(crate) fn reply(&self, response_id: &str) -> Reply {
+        match self {
+            Self::Message { text, chunk_chars } => {
+                let mut events = vec![created(response_id), message_added(response_id)];
+                events.extend(text_deltas(text, *chunk_chars));
+                events.push(json!({
+                    "type": "response.output_item.done",
+                    "item": message_item(response_id, text),
+                }));
+                events.push(completed(response_id));
+                sse_reply(&events)
+            }
+            Self::ToolCall {
+                call_id,
+                name,
+                arguments,
+            } => {
+                let arguments = match arguments {
+                    Value::String(arguments) => arguments.clone(),
+                    Value::Null => "{}".to_string(),
+                    arguments => arguments.to_string(),
+                };
+                sse_reply(&[
+                    created(response_id),
+                    json!({
+                        "type": "response.output_item.done",
+                        "item": {
+                            "type": "function_call",
+                            "call_id": call_id,
+                            "name": name,
+                            "arguments": arguments,
+                        },
+                    }),
+                    completed(response_id),
+                ])
+            }
+            Self::MalformedJson => {
+                let mut body = sse_body(&[created(response_id)]);
+                body.push_str(
+                    "event: response.output_text.delta\ndata: {\"type\":\"response.output_text.delta\",\"delta\":\n\n",
+                );
+                body.push_str(&sse_body(&[completed(response_id)]));
+                Reply {
+                    status: 200,
+                    headers: vec![("content-type", "text/event-stream".to_string())],
+                    body,
+                }
+            }
+            Self::RateLimit {
+                retry_after_seconds,
+                usage_limit,
+            } => {
+                let mut headers = vec![("content-type", "application/json".to_string())];
+                if let Some(seconds) = retry_after_seconds {
+                    headers.push(("retry-after", seconds.to_string()));
+                }
+                let error = if *usage_limit {
+                    headers.push(("x-codex-primary-used-percent", "100.0".to_string()));
+                    headers.push(("x-codex-primary-window-minutes", "300".to_string()));
+                    json!({
+                        "type": "usage_limit_reached",
+                        "message": "The usage limit has been reached",
+                    })
+                } else {
+                    json!({
+                        "type": "requests",
+                        "code": "rate_limit_exceeded",
+                        "message": "Rate limit reached, please try again later",
+                    })
+                };
+                Reply {
+                    status: 429,
+                    headers,
+                    body: json!({ "error": error }).to_string(),
+                }
+            }
+            Self::StreamDrop { text } => {
+                let mut events = vec![created(response_id), message_added(response_id)];
+                events.extend(text_deltas(text, /*chunk_chars*/ None));
+                sse_reply(&events)
+            }
+            Self::Events { events } => sse_reply(events),
+        }
+    }
+}
+
+pub(crate) fn sse_reply(events: &[Value]) -> Reply {
+    Reply {
+        status: 200,
+        headers: vec![("content-type", "text/event-stream".to_string())],
+        body: sse_body(events),
+    }
+}
+
+fn sse_body(events: &[Value]) -> String {
+    events
+        .iter()
+        .map(|event| {
+            let kind = event
+                .get("type")
+                .and_then(Value::as_str)
+                .unwrap_or("message");
+            format!("event: {kind}\ndata: {event}\n\n")
+        })
+        .collect()
+}
+
+fn created(response_id: &str) -> Value {
+    json!({
+        "type": "response.created",
+        "response": { "id": response_id },
+    })
+}
+
+fn completed(response_id: &str) -> Value {
+    json!({
+        "type": "response.completed",
+        "response": {
+            "id": response_id,
+            "usage": {
+                "input_tokens": 0,
+                "input_tokens_details": null,
+                "output_tokens": 0,
+                "output_tokens_details": null,
+                "total_tokens": 0,
+            },
+        },
+    })
+}
+
+fn message_item(response_id: &str, text: &str) -> Value {
+    json!({
+        "type": "message",
+        "role": "assistant",
+        "id": format!("msg-{response_id}"),
+        "content": [{ "type": "output_text", "text": text }],
+    })
+}
+
+fn message_added(response_id: &str) -> Value {
+    json!({
+        "type": "response.output_item.added",
+        "item": message_item(response_id, ""),
+    })
+}
+
+fn text_deltas(text: &str, chunk_chars: Option<usize>) -> Vec<Value> {
+    let chars: Vec<char> = text.chars().collect();
+    chars
+        .chunks(chunk_chars.unwrap_or(chars.len()).max(1))
+        .map(|chunk| {
+            json!({
+                "type": "response.output_text.delta",
+                "delta": chunk.iter().collect::<String>(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn event_types(body: &str) -> Vec<&str> {
+        body.lines()
+            .filter_map(|line| line.strip_prefix("event: "))
+            .collect()
+    }
+
+    #[test]
+    fn scenarios_parse_from_json() {
+        let scenario: Scenario = serde_json::from_str(
+            r#"{"responses": [
+                {"type": "message", "text": "hi", "chunk_chars": 1},
+                {"type": "tool_call", "call_id": "call-1", "name": "shell", "arguments": {"command": ["ls"]}},
+                {"type": "rate_limit", "retry_after_seconds": 2},
+                {"type": "stream_drop"}
+            ]}"#,
+        )
+        .expect("scenario parses");
+
+        assert_eq!(scenario.responses.len(), 4);
+    }
+
+    #[test]
+    fn messages_stream_deltas_and_complete() {
+        let reply = ScriptedResponse::Message {
+            text: "hello".to_string(),
+            chunk_chars: Some(2),
+        }
+        .reply("resp-1");
+
+        assert_eq!(reply.status, 200);
+        assert_eq!(
+            event_types(&reply.body),
+            vec![
+                "response.created",
+                "response.output_item.added",
+                "response.output_text.delta",
+                "response.output_text.delta",
+                "response.output_text.delta",
+                "response.output_item.done",
+                "response.completed",
+            ]
+        );
+    }
+
+    #[test]
+    fn tool_call_arguments_are_encoded_as_a_string() {
+        let reply = ScriptedResponse::ToolCall {
+            call_id: "call-1".to_string(),
+            name: "shell".to_string(),
+            arguments: json!({ "command": ["ls"] }),
+        }
+        .reply("resp-1");
+
+        assert!(
+            reply
+                .body
+                .contains(r#""arguments":"{\"command\":[\"ls\"]}""#),
+            "{}",
+            reply.body
+        );
+    }
+
+    #[test]
+    fn stream_drops_end_without_completion() {
+        let reply = ScriptedResponse::StreamDrop {
+            text: "partial".to_string(),
+        }
+        .reply("resp-1");
+
+        assert!(!event_types(&reply.body).contains(&"response.completed"));
+    }
+
+    #[test]
+    fn rate_limits_reply_429_with_retry_after() {
+        let reply = ScriptedResponse::RateLimit {
+            retry_after_seconds: Some(3),
+            usage_limit: false,
+        }
+        .reply("resp-1");
+
+        assert_eq!(reply.status, 429);
+        assert!(reply.headers.contains(&("retry-after", "3".to_string())));
+    }
+}
@@ -24,3 +24,5 @@ pub use review_request::ResolvedReviewRequest;
 pub use review_request::resolve_review_request;
 pub use review_request::review_prompt;
 pub use review_request::user_facing_hint;
+pub use review_request::validate_commit_range;
+pub use review_request::validate_pull_request_url;
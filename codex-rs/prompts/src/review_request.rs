@@ -17,6 +17,8 @@ pub struct ResolvedReviewRequest {
 
 const UNCOMMITTED_PROMPT: &str = "Review the current code changes (staged, unstaged, and untracked files) and provide prioritized findings.";
 
+const STAGED_PROMPT: &str = "Review the staged code changes and provide prioritized findings. Run `git diff --staged` to inspect them; ignore unstaged and untracked files.";
+
 const BASE_BRANCH_PROMPT_BACKUP: &str = "Review the code changes against the base branch '{{branch}}'. Start by finding the merge diff between the current branch and {{branch}}'s upstream e.g. (`git merge-base HEAD \"$(git rev-parse --abbrev-ref \"{{branch}}@{upstream}\")\"`), then run `git diff` against that SHA to see what changes we would merge into the {{branch}} branch. Provide prioritized, actionable findings.";
 const BASE_BRANCH_PROMPT: &str = "Review the code changes against the base branch '{{base_branch}}'. The merge base commit for this comparison is {{merge_base_sha}}. Run `git diff {{merge_base_sha}}` to inspect the changes relative to {{base_branch}}. Provide prioritized, actionable findings.";
 static BASE_BRANCH_PROMPT_BACKUP_TEMPLATE: LazyLock<Template> = LazyLock::new(|| {
@@ -39,6 +41,18 @@ static COMMIT_PROMPT_TEMPLATE: LazyLock<Template> = LazyLock::new(|| {
         .unwrap_or_else(|err| panic!("commit review prompt must parse: {err}"))
 });
 
+const COMMIT_RANGE_PROMPT: &str = "Review the code changes in the revision range {{range}}. Run `git log {{range}}` and `git diff {{range}}` to inspect them. Provide prioritized, actionable findings.";
+static COMMIT_RANGE_PROMPT_TEMPLATE: LazyLock<Template> = LazyLock::new(|| {
+    Template::parse(COMMIT_RANGE_PROMPT)
+        .unwrap_or_else(|err| panic!("commit range review prompt must parse: {err}"))
+});
+
+const PULL_REQUEST_PROMPT: &str = "Review the code changes in pull request {{url}}. Run `gh pr view {{url}}` for its description and `gh pr diff {{url}}` for its changes; if the GitHub CLI is unavailable, fetch the pull request branch and diff it against its base. Provide prioritized, actionable findings.";
+static PULL_REQUEST_PROMPT_TEMPLATE: LazyLock<Template> = LazyLock::new(|| {
+    Template::parse(PULL_REQUEST_PROMPT)
+        .unwrap_or_else(|err| panic!("pull request review prompt must parse: {err}"))
+});
+
 pub fn resolve_review_request(
     request: ReviewRequest,
     cwd: &AbsolutePathBuf,
@@ -59,6 +73,7 @@ pub fn resolve_review_request(
 pub fn review_prompt(target: &ReviewTarget, cwd: &AbsolutePathBuf) -> anyhow::Result<String> {
     match target {
         ReviewTarget::UncommittedChanges => Ok(UNCOMMITTED_PROMPT.to_string()),
+        ReviewTarget::StagedChanges => Ok(STAGED_PROMPT.to_string()),
         ReviewTarget::BaseBranch { branch } => {
             if let Some(commit) = merge_base_with_head(cwd, branch)? {
                 Ok(render_review_prompt(
@@ -88,6 +103,20 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &AbsolutePathBuf) -> anyhow::Re
                 ))
             }
         }
+        ReviewTarget::CommitRange { range } => {
+            validate_commit_range(range)?;
+            Ok(render_review_prompt(
+                &COMMIT_RANGE_PROMPT_TEMPLATE,
+                [("range", range.as_str())],
+            ))
+        }
+        ReviewTarget::PullRequest { url } => {
+            validate_pull_request_url(url)?;
+            Ok(render_review_prompt(
+                &PULL_REQUEST_PROMPT_TEMPLATE,
+                [("url", url.as_str())],
+            ))
+        }
         ReviewTarget::Custom { instructions } => {
             let prompt = instructions.trim();
             if prompt.is_empty() {
@@ -98,6 +127,47 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &AbsolutePathBuf) -> anyhow::Re
     }
 }
 
+/// Checks that `range` is a git revision range, such as `main..feature` or `v1.0...HEAD~2`.
+/// The prompt asks the model to paste it into `git log` and `git diff` commands, so anything
+/// a shell would interpret, or that git would read as an option, is refused.
+pub fn validate_commit_range(range: &str) -> anyhow::Result<()> {
+    let is_revision_char = |c: char| c.is_ascii_alphanumeric() || "._/-~^@{}:".contains(c);
+    if range.is_empty() || range.starts_with('-') || !range.chars().all(is_revision_char) {
+        anyhow::bail!("`{range}` is not a git revision range such as `main..feature`");
+    }
+    Ok(())
+}
+
+/// Checks that `url` is a pull request URL such as `https://github.com/owner/repo/pull/7`. The
+/// prompt asks the model to paste it into `gh pr` commands, so only that shape is accepted.
+pub fn validate_pull_request_url(url: &str) -> anyhow::Result<()> {
+    let is_name = |segment: &str| {
+        !segment.is_empty()
+            && !segment.starts_with('-')
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "._-".contains(c))
+    };
+    let segments = url
+        .strip_prefix("https://")
+        .map(|rest| rest.trim_end_matches('/').split('/').collect::<Vec<_>>());
+    let valid = matches!(
+        segments.as_deref(),
+        Some([host, owner, repo, "pull", number])
+            if is_name(host)
+                && is_name(owner)
+                && is_name(repo)
+                && !number.is_empty()
+                && number.chars().all(|c| c.is_ascii_digit())
+    );
+    if !valid {
+        anyhow::bail!(
+            "`{url}` is not a pull request URL such as `https://github.com/owner/repo/pull/7`"
+        );
+    }
+    Ok(())
+}
+
 fn render_review_prompt<'a, const N: usize>(
     template: &Template,
     variables: [(&'a str, &'a str); N],
@@ -110,6 +180,7 @@ fn render_review_prompt<'a, const N: usize>(
 pub fn user_facing_hint(target: &ReviewTarget) -> String {
     match target {
         ReviewTarget::UncommittedChanges => "current changes".to_string(),
+        ReviewTarget::StagedChanges => "staged changes".to_string(),
         ReviewTarget::BaseBranch { branch } => format!("changes against '{branch}'"),
         ReviewTarget::Commit { sha, title } => {
             let short_sha: String = sha.chars().take(7).collect();
@@ -119,6 +190,8 @@ pub fn user_facing_hint(target: &ReviewTarget) -> String {
                 format!("commit {short_sha}")
             }
         }
+        ReviewTarget::CommitRange { range } => format!("changes in {range}"),
+        ReviewTarget::PullRequest { url } => format!("pull request {url}"),
         ReviewTarget::Custom { instructions } => instructions.trim().to_string(),
     }
 }
@@ -49,3 +49,61 @@ fn review_prompt_template_renders_commit_variant_with_title() {
         "Review the code changes introduced by commit deadbeef (\"Fix bug\"). Provide prioritized, actionable findings."
     );
 }
+
+#[test]
+fn review_prompt_template_renders_commit_range_and_pull_request_variants() {
+    let cwd = AbsolutePathBuf::current_dir().expect("cwd");
+    assert_eq!(
+        review_prompt(
+            &ReviewTarget::CommitRange {
+                range: "main..feature".to_string(),
+            },
+            &cwd,
+        )
+        .expect("range prompt should render"),
+        "Review the code changes in the revision range main..feature. Run `git log main..feature` and `git diff main..feature` to inspect them. Provide prioritized, actionable findings."
+    );
+    assert_eq!(
+        review_prompt(
+            &ReviewTarget::PullRequest {
+                url: "https://github.com/o/r/pull/7".to_string(),
+            },
+            &cwd,
+        )
+        .expect("pull request prompt should render"),
+        "Review the code changes in pull request https://github.com/o/r/pull/7. Run `gh pr view https://github.com/o/r/pull/7` for its description and `gh pr diff https://github.com/o/r/pull/7` for its changes; if the GitHub CLI is unavailable, fetch the pull request branch and diff it against its base. Provide prioritized, actionable findings."
+    );
+}
+
+#[test]
+fn review_prompt_rejects_ranges_and_urls_a_shell_would_interpret() {
+    let cwd = AbsolutePathBuf::current_dir().expect("cwd");
+    for range in [
+        "main..feature; rm -rf ~",
+        "$(touch pwned)..HEAD",
+        "--output=/tmp/x",
+        "main ..feature",
+        "",
+    ] {
+        let target = ReviewTarget::CommitRange {
+            range: range.to_string(),
+        };
+        assert!(review_prompt(&target, &cwd).is_err(), "{range:?}");
+    }
+    for url in [
+        "https://github.com/o/r/pull/7;id",
+        "https://github.com/o/r/pull/7 --repo x",
+        "https://github.com/o/r/pull/`id`",
+        "http://github.com/o/r/pull/7",
+        "https://github.com/o/r/issues/7",
+    ] {
+        let target = ReviewTarget::PullRequest {
+            url: url.to_string(),
+        };
+        assert!(review_prompt(&target, &cwd).is_err(), "{url:?}");
+    }
+
+    assert!(validate_commit_range("v1.0...HEAD~2").is_ok());
+    assert!(validate_commit_range("origin/main@{upstream}..HEAD^").is_ok());
+    assert!(validate_pull_request_url("https://github.example.com/o/r.js/pull/12/").is_ok());
+}
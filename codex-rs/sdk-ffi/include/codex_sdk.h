@@ -0,0 +1,146 @@
+/*
+ * C interface to the Codex agent, for driving it in-process from other
+ * languages.  Link against the `codex_sdk_ffi` library built from
+ * codex-rs/sdk-ffi.
+ *
+ * Ownership:
+ *   - Handles from codex_sdk_new and codex_session_start are freed with
+ *     codex_sdk_free and codex_session_free.  Sessions keep working after
+ *     codex_sdk_free, and so does the tool callback: `tool_user_data` must
+ *     stay valid until `tool_user_data_free` is called, or, without one,
+ *     until the instance and every session started from it are freed.
+ *   - Strings written to `error_out` or returned by the schema functions are
+ *     owned by the caller and freed with codex_string_free.  Pass NULL as
+ *     `error_out` to ignore errors.
+ *   - Strings passed to callbacks are only valid during the call.
+ *
+ * Threads: callbacks run on Codex's worker threads, possibly several at once,
+ * so they and their user data must be thread-safe.  From inside a callback,
+ * only codex_session_send, codex_session_submit, codex_session_interrupt,
+ * codex_tool_reply_set_text, the schema functions, and codex_string_free may
+ * be called.  Send, submit, and interrupt are then queued and return true
+ * without waiting; codex_sdk_new and codex_session_start fail, and the free
+ * functions must not be called.
+ */
+
+#ifndef CODEX_SDK_H
+#define CODEX_SDK_H
+
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct CodexSdk CodexSdk;
+typedef struct CodexSession CodexSession;
+typedef struct CodexToolReply CodexToolReply;
+
+/*
+ * Receives each session event as a JSON object with `id` (the submission the
+ * event belongs to) and `msg` (the event, tagged by `type`, for example
+ * "agent_message" or "turn_complete").
+ */
+typedef void (*CodexEventCallback)(const char *event_json, void *user_data);
+
+/*
+ * Runs a call to one of the tools listed in the options.  Set the output with
+ * codex_tool_reply_set_text and return whether the call succeeded; the output
+ * of a failed call is shown to the model as an error.
+ */
+typedef bool (*CodexToolCallback)(const char *tool,
+                                  const char *arguments_json,
+                                  CodexToolReply *reply,
+                                  void *user_data);
+
+/* Releases `tool_user_data` once no tool callback can receive it anymore. */
+typedef void (*CodexUserDataFree)(void *user_data);
+
+/*
+ * Loads config the way the CLI does and creates a Codex instance.
+ * `options_json` may be NULL, or an object with any of:
+ *
+ *   "codex_home":      directory with config.toml and credentials
+ *   "cwd":             directory the agent works in
+ *   "model":           model to use instead of the configured one
+ *   "model_provider":  id of a provider in `model_providers`
+ *   "approval_policy": "untrusted", "on-request", or "never"
+ *   "tools":           [{"name", "description", "input_schema"}, ...]
+ *
+ * With "untrusted" or "on-request", commands and patches wait for an answer
+ * to their `exec_approval_request` or `apply_patch_approval_request` event,
+ * submitted with codex_session_submit.
+ *
+ * Tools require `tool_callback`.  `tool_user_data_free` may be NULL; when set,
+ * it is called once, on any thread, after the instance and all of its
+ * sessions are freed, or before this returns if no tool needs the data.
+ * Returns NULL on failure.
+ */
+CodexSdk *codex_sdk_new(const char *options_json,
+                        CodexToolCallback tool_callback,
+                        void *tool_user_data,
+                        CodexUserDataFree tool_user_data_free,
+                        char **error_out);
+
+void codex_sdk_free(CodexSdk *sdk);
+
+/*
+ * Starts a session.  Events are delivered to `event_callback`, which may be
+ * NULL.  Returns NULL on failure.
+ */
+CodexSession *codex_session_start(const CodexSdk *sdk,
+                                  CodexEventCallback event_callback,
+                                  void *user_data,
+                                  char **error_out);
+
+/*
+ * Starts a turn with `text` as the user message.  From a callback the turn is
+ * queued and true is returned without waiting.
+ */
+bool codex_session_send(const CodexSession *session,
+                        const char *text,
+                        char **error_out);
+
+/*
+ * Submits a JSON object tagged by `type`: {"type": "user_input", "items":
+ * [...]} starts a turn with text, image, or other input items, and
+ * {"type": "interrupt"} stops the running turn.  Approval requests are
+ * answered with {"type": "exec_approval", "id": ..., "decision": ...} or
+ * {"type": "patch_approval", "id": ..., "decision": ...}, where `decision` is
+ * "approve", "approve_for_session", "deny", or "abort".  See
+ * codex_submission_schema for the full shape.  Queued like
+ * codex_session_send from a callback.
+ */
+bool codex_session_submit(const CodexSession *session,
+                          const char *submission_json,
+                          char **error_out);
+
+/* Stops the running turn.  Queued like codex_session_send from a callback. */
+bool codex_session_interrupt(const CodexSession *session, char **error_out);
+
+/*
+ * Shuts the session down, waiting for it to finish, and frees it.  Requests
+ * still queued from callbacks fail.
+ */
+void codex_session_free(CodexSession *session);
+
+/* Sets the text a tool call returns.  Only valid inside a tool callback. */
+void codex_tool_reply_set_text(CodexToolReply *reply, const char *text);
+
+/*
+ * JSON Schemas (draft 7) of the event JSON, the options, and the submissions,
+ * for generating typed clients.  They describe the library they come from, so
+ * regenerate after upgrading it.  Return NULL if a schema cannot be
+ * serialized.  Free the results with codex_string_free.
+ */
+char *codex_event_schema(void);
+char *codex_options_schema(void);
+char *codex_submission_schema(void);
+
+void codex_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CODEX_SDK_H */
@@ -0,0 +1,834 @@
+//! C interface to `codex-sdk`, for editor plugins and scripts in other
+//! languages that drive Codex in-process.
+//!
+//! `include/codex_sdk.h` is the contract: it declares every function here and
+//! documents ownership.  In short, handles returned by `*_new`/`*_start` are
+//! freed with the matching `*_free`, strings returned through `error_out` are
+//! freed with [`codex_string_free`], and callbacks run on Codex's worker
+//! threads, so they must be thread-safe.  Only [`codex_session_send`],
+//! [`codex_session_submit`], and [`codex_session_interrupt`] may be called from
+//! a callback; they queue the request instead of waiting for it, since blocking
+//! on the runtime from one of its own threads would panic.
+
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::ffi::c_char;
+use std::ffi::c_void;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::ptr;
+use std::sync::Arc;
+
+use codex_sdk::AskForApproval;
+use codex_sdk::Codex;
+use codex_sdk::ConfigOverrides;
+use codex_sdk::DynamicToolFunctionSpec;
+use codex_sdk::Op;
+use codex_sdk::ReviewDecision;
+use codex_sdk::Session;
+use codex_sdk::Tool;
+use codex_sdk::ToolCallFuture;
+use codex_sdk::ToolOutput;
+use codex_sdk::UserInput;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::runtime::Runtime;
+use tokio::task::JoinHandle;
+
+mod schema;
+
+pub use schema::event_schema;
+pub use schema::options_schema;
+pub use schema::submission_schema;
+pub use schema::write_schemas;
+
+/// Receives each event of a session as JSON.
+pub type CodexEventCallback =
+    Option<unsafe extern "C" fn(event_json: *const c_char, user_data: *mut c_void)>;
+
+/// Runs a tool call.  Sets the output with [`codex_tool_reply_set_text`] and
+/// returns whether the call succeeded.
+pub type CodexToolCallback = Option<
+    unsafe extern "C" fn(
+        tool: *const c_char,
+        arguments_json: *const c_char,
+        reply: *mut CodexToolReply,
+        user_data: *mut c_void,
+    ) -> bool,
+>;
+
+/// Releases the tool user data once no callback can receive it anymore.
+pub type CodexUserDataFree = Option<unsafe extern "C" fn(user_data: *mut c_void)>;
+
+/// Options passed to [`codex_sdk_new`] as JSON.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+struct SdkOptions {
+    codex_home: Option<PathBuf>,
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    model_provider: Option<String>,
+    approval_policy: Option<AskForApproval>,
+    tools: Vec<ToolOptions>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct ToolOptions {
+    name: String,
+    description: String,
+    input_schema: Value,
+}
+
+/// A submission passed to [`codex_session_submit`] as JSON.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Submission {
+    /// Starts a turn with these input items.
+    UserInput { items: Vec<UserInput> },
+    /// Stops the running turn.
+    Interrupt,
+    /// Answers an `exec_approval_request` event.  `id` is the event's
+    /// `approval_id`, or its `call_id` when it has none.
+    ExecApproval {
+        id: String,
+        turn_id: Option<String>,
+        decision: ApprovalDecision,
+    },
+    /// Answers an `apply_patch_approval_request` event.  `id` is the event's
+    /// `call_id`.
+    PatchApproval {
+        id: String,
+        decision: ApprovalDecision,
+    },
+}
+
+/// How an approval request is answered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum ApprovalDecision {
+    /// Runs the command or applies the patch.
+    Approve,
+    /// Approves this request and matching ones for the rest of the session.
+    ApproveForSession,
+    /// Refuses the request; the agent continues and tries something else.
+    Deny,
+    /// Refuses the request and stops the turn.
+    Abort,
+}
+
+impl From<ApprovalDecision> for ReviewDecision {
+    fn from(decision: ApprovalDecision) -> Self {
+        match decision {
+            ApprovalDecision::Approve => ReviewDecision::Approved,
+            ApprovalDecision::ApproveForSession => ReviewDecision::ApprovedForSession,
+            ApprovalDecision::Deny => ReviewDecision::default(),
+            ApprovalDecision::Abort => ReviewDecision::Abort,
+        }
+    }
+}
+
+/// A Codex instance and the runtime its sessions run on.
+pub struct CodexSdk {
+    runtime: Arc<Runtime>,
+    codex: Codex,
+}
+
+/// A running session and the task that delivers its events.
+pub struct CodexSession {
+    runtime: Arc<Runtime>,
+    session: Arc<Session>,
+    events: JoinHandle<()>,
+}
+
+/// Output of a tool call, filled in by the tool callback.
+#[derive(Debug, Default)]
+pub struct CodexToolReply {
+    text: String,
+}
+
+/// Foreign pointer handed back to callbacks.  The header requires callbacks
+/// and their user data to be usable from any thread.
+#[derive(Clone, Copy)]
+struct UserData(*mut c_void);
+
+unsafe impl Send for UserData {}
+unsafe impl Sync for UserData {}
+
+/// The tool user data, shared by every tool of an instance.  Sessions keep the
+/// tools alive after [`codex_sdk_free`], so the data is released when the last
+/// of them goes away rather than with the instance.
+struct ToolUserData {
+    user_data: UserData,
+    free: CodexUserDataFree,
+}
+
+impl Drop for ToolUserData {
+    fn drop(&mut self) {
+        if let Some(free) = self.free {
+            // SAFETY: the caller of `codex_sdk_new` promised `free` accepts
+            // the user data from any thread, and nothing uses it afterwards.
+            unsafe { free(self.user_data.0) };
+        }
+    }
+}
+
+struct FfiTool {
+    spec: DynamicToolFunctionSpec,
+    callback: unsafe extern "C" fn(
+        *const c_char,
+        *const c_char,
+        *mut CodexToolReply,
+        *mut c_void,
+    ) -> bool,
+    user_data: Arc<ToolUserData>,
+}
+
+impl Tool for FfiTool {
+    fn spec(&self) -> DynamicToolFunctionSpec {
+        self.spec.clone()
+    }
+
+    fn call(&self, arguments: Value) -> ToolCallFuture<'_> {
+        let callback = self.callback;
+        let user_data = self.user_data.user_data;
+        let tool = c_string(self.spec.name.clone());
+        let arguments = c_string(arguments.to_string());
+        Box::pin(async move {
+            // Foreign callbacks may block, so keep them off the async workers.
+            let outcome = tokio::task::spawn_blocking(move || {
+                let mut reply = CodexToolReply::default();
+                let user_data = user_data;
+                // SAFETY: the strings outlive the call and `reply` is a valid
+                // exclusive pointer; the caller of `codex_sdk_new` promised the
+                // callback accepts these arguments from any thread.
+                let success =
+                    unsafe { callback(tool.as_ptr(), arguments.as_ptr(), &mut reply, user_data.0) };
+                (success, reply.text)
+            })
+            .await;
+            match outcome {
+                Ok((true, text)) => ToolOutput::text(text),
+                Ok((false, text)) => ToolOutput::error(text),
+                Err(err) => ToolOutput::error(format!("tool callback failed: {err}")),
+            }
+        })
+    }
+}
+
+/// Loads config and creates a Codex instance.
+///
+/// `options_json` may be null for defaults.  Returns null on failure and, when
+/// `error_out` is not null, stores an error message there.
+/// `tool_user_data_free`, if set, is called once with `tool_user_data` after
+/// the instance and every session started from it are freed, or before this
+/// function returns when no tool needs it.
+///
+/// # Safety
+///
+/// `options_json` must be null or a NUL-terminated string, and `error_out`
+/// null or valid for writes.  `tool_callback`, `tool_user_data`, and
+/// `tool_user_data_free` must be safe to use from any thread until
+/// `tool_user_data_free` is called, or until the instance and all of its
+/// sessions are freed when it is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_sdk_new(
+    options_json: *const c_char,
+    tool_callback: CodexToolCallback,
+    tool_user_data: *mut c_void,
+    tool_user_data_free: CodexUserDataFree,
+    error_out: *mut *mut c_char,
+) -> *mut CodexSdk {
+    let tool_user_data = Arc::new(ToolUserData {
+        user_data: UserData(tool_user_data),
+        free: tool_user_data_free,
+    });
+    if inside_runtime() {
+        return fail(error_out, "codex_sdk_new cannot be called from a callback");
+    }
+    // SAFETY: forwarded from this function's contract.
+    let options = match unsafe { optional_str(options_json) } {
+        Ok(None) => SdkOptions::default(),
+        Ok(Some(json)) => match serde_json::from_str(json) {
+            Ok(options) => options,
+            Err(err) => return fail(error_out, format!("invalid options: {err}")),
+        },
+        Err(err) => return fail(error_out, err),
+    };
+    let mut builder = Codex::builder().config_overrides(ConfigOverrides {
+        cwd: options.cwd,
+        model: options.model,
+        model_provider: options.model_provider,
+        approval_policy: options.approval_policy,
+        ..Default::default()
+    });
+    if let Some(codex_home) = options.codex_home {
+        builder = builder.codex_home(codex_home);
+    }
+    if !options.tools.is_empty() {
+        let Some(callback) = tool_callback else {
+            return fail(error_out, "tools need a tool callback");
+        };
+        for tool in options.tools {
+            builder = builder.tool(FfiTool {
+                spec: DynamicToolFunctionSpec {
+                    name: tool.name,
+                    description: tool.description,
+                    input_schema: tool.input_schema,
+                    defer_loading: false,
+                },
+                callback,
+                user_data: Arc::clone(&tool_user_data),
+            });
+        }
+    }
+
+    let runtime = match Runtime::new() {
+        Ok(runtime) => Arc::new(runtime),
+        Err(err) => return fail(error_out, format!("failed to start runtime: {err}")),
+    };
+    match runtime.block_on(builder.build()) {
+        Ok(codex) => Box::into_raw(Box::new(CodexSdk { runtime, codex })),
+        Err(err) => fail(error_out, err),
+    }
+}
+
+/// Frees a Codex instance.  Sessions started from it keep working, and so do
+/// its tools until the last of them is freed.
+///
+/// # Safety
+///
+/// `sdk` must be null or a pointer returned by [`codex_sdk_new`] that was not
+/// freed yet.  Must not be called from an event or tool callback.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_sdk_free(sdk: *mut CodexSdk) {
+    if !sdk.is_null() {
+        // SAFETY: forwarded from this function's contract.
+        drop(unsafe { Box::from_raw(sdk) });
+    }
+}
+
+/// Starts a session.  Its events are passed to `event_callback`, if set, as
+/// JSON objects with `id` and `msg` fields.
+///
+/// # Safety
+///
+/// `sdk` must be a live pointer from [`codex_sdk_new`] and `error_out` null or
+/// valid for writes.  `event_callback` and `user_data` must be safe to use
+/// from any thread until the session is freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_session_start(
+    sdk: *const CodexSdk,
+    event_callback: CodexEventCallback,
+    user_data: *mut c_void,
+    error_out: *mut *mut c_char,
+) -> *mut CodexSession {
+    if inside_runtime() {
+        return fail(
+            error_out,
+            "codex_session_start cannot be called from a callback",
+        );
+    }
+    // SAFETY: forwarded from this function's contract.
+    let Some(sdk) = (unsafe { sdk.as_ref() }) else {
+        return fail(error_out, "sdk is null");
+    };
+    let session = match sdk.runtime.block_on(sdk.codex.start_session()) {
+        Ok(session) => Arc::new(session),
+        Err(err) => return fail(error_out, err),
+    };
+    let user_data = UserData(user_data);
+    // Events are read even without a callback so tool calls get answered.
+    let events = sdk.runtime.spawn({
+        let session = Arc::clone(&session);
+        async move {
+            let user_data = user_data;
+            while let Ok(event) = session.next_event().await {
+                let (Some(callback), Ok(json)) = (event_callback, serde_json::to_string(&event))
+                else {
+                    continue;
+                };
+                let json = c_string(json);
+                // SAFETY: `json` outlives the call; the callback's thread
+                // safety is part of `codex_session_start`'s contract.
+                unsafe { callback(json.as_ptr(), user_data.0) };
+            }
+        }
+    });
+    Box::into_raw(Box::new(CodexSession {
+        runtime: Arc::clone(&sdk.runtime),
+        session,
+        events,
+    }))
+}
+
+/// Starts a turn with `text` as the user message.  From a callback the turn is
+/// queued and `true` returned right away; a failure to start it is then only
+/// visible as the absence of the turn's events.
+///
+/// # Safety
+///
+/// `session` must be a live pointer from [`codex_session_start`], `text` a
+/// NUL-terminated string, and `error_out` null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_session_send(
+    session: *const CodexSession,
+    text: *const c_char,
+    error_out: *mut *mut c_char,
+) -> bool {
+    // SAFETY: forwarded from this function's contract.
+    let (Some(session), Ok(Some(text))) =
+        (unsafe { session.as_ref() }, unsafe { optional_str(text) })
+    else {
+        set_error(error_out, "session and text must be valid and non-null");
+        return false;
+    };
+    if inside_runtime() {
+        let handle = Arc::clone(&session.session);
+        let text = text.to_string();
+        session.runtime.spawn(async move {
+            let _ = handle.send(text).await;
+        });
+        return true;
+    }
+    match session.runtime.block_on(session.session.send(text)) {
+        Ok(_) => true,
+        Err(err) => {
+            set_error(error_out, err);
+            false
+        }
+    }
+}
+
+/// Submits `submission_json`, a [`Submission`] such as
+/// `{"type": "user_input", "items": [{"type": "text", "text": "..."}]}` or an
+/// answer to an approval request.  From a callback the submission is queued
+/// and `true` returned right away, once it has parsed.
+///
+/// # Safety
+///
+/// `session` must be a live pointer from [`codex_session_start`],
+/// `submission_json` a NUL-terminated string, and `error_out` null or valid for
+/// writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_session_submit(
+    session: *const CodexSession,
+    submission_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> bool {
+    // SAFETY: forwarded from this function's contract.
+    let (Some(session), Ok(Some(submission_json))) = (unsafe { session.as_ref() }, unsafe {
+        optional_str(submission_json)
+    }) else {
+        set_error(
+            error_out,
+            "session and submission_json must be valid and non-null",
+        );
+        return false;
+    };
+    let submission = match serde_json::from_str::<Submission>(submission_json) {
+        Ok(submission) => submission,
+        Err(err) => {
+            set_error(error_out, format!("invalid submission: {err}"));
+            return false;
+        }
+    };
+    let handle = Arc::clone(&session.session);
+    let submit = async move {
+        match submission {
+            Submission::UserInput { items } => handle.send_input(items).await.map(drop),
+            Submission::Interrupt => handle.interrupt().await,
+            Submission::ExecApproval {
+                id,
+                turn_id,
+                decision,
+            } => handle
+                .submit(Op::ExecApproval {
+                    id,
+                    turn_id,
+                    decision: decision.into(),
+                })
+                .await
+                .map(drop),
+            Submission::PatchApproval { id, decision } => handle
+                .submit(Op::PatchApproval {
+                    id,
+                    decision: decision.into(),
+                })
+                .await
+                .map(drop),
+        }
+    };
+    if inside_runtime() {
+        session.runtime.spawn(async move {
+            let _ = submit.await;
+        });
+        return true;
+    }
+    match session.runtime.block_on(submit) {
+        Ok(()) => true,
+        Err(err) => {
+            set_error(error_out, err);
+            false
+        }
+    }
+}
+
+/// Stops the running turn.  From a callback the interrupt is queued and `true`
+/// returned right away.
+///
+/// # Safety
+///
+/// `session` must be a live pointer from [`codex_session_start`] and
+/// `error_out` null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_session_interrupt(
+    session: *const CodexSession,
+    error_out: *mut *mut c_char,
+) -> bool {
+    // SAFETY: forwarded from this function's contract.
+    let Some(session) = (unsafe { session.as_ref() }) else {
+        set_error(error_out, "session is null");
+        return false;
+    };
+    if inside_runtime() {
+        let handle = Arc::clone(&session.session);
+        session.runtime.spawn(async move {
+            let _ = handle.interrupt().await;
+        });
+        return true;
+    }
+    match session.runtime.block_on(session.session.interrupt()) {
+        Ok(()) => true,
+        Err(err) => {
+            set_error(error_out, err);
+            false
+        }
+    }
+}
+
+/// Shuts a session down and frees it.  No event callbacks run afterwards, and
+/// requests still queued from callbacks fail.
+///
+/// # Safety
+///
+/// `session` must be null or a pointer returned by [`codex_session_start`]
+/// that was not freed yet.  Must not be called from an event or tool callback.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_session_free(session: *mut CodexSession) {
+    if session.is_null() {
+        return;
+    }
+    // SAFETY: forwarded from this function's contract.
+    let CodexSession {
+        runtime,
+        session,
+        events,
+    } = *unsafe { Box::from_raw(session) };
+    events.abort();
+    // Requests queued from callbacks may still hold the session, so shut it
+    // down through the shared handle rather than waiting for the last one.
+    runtime.block_on(async move {
+        let _ = events.await;
+        let _ = session.shutdown().await;
+    });
+}
+
+/// Sets the text a tool call returns to the model.
+///
+/// # Safety
+///
+/// `reply` must be the pointer passed to the running tool callback and `text`
+/// a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_tool_reply_set_text(
+    reply: *mut CodexToolReply,
+    text: *const c_char,
+) {
+    // SAFETY: forwarded from this function's contract.
+    if let (Some(reply), Ok(Some(text))) =
+        (unsafe { reply.as_mut() }, unsafe { optional_str(text) })
+    {
+        reply.text = text.to_string();
+    }
+}
+
+/// Returns the JSON Schema of the events passed to [`CodexEventCallback`], or
+/// null if it cannot be serialized.  Free the result with [`codex_string_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn codex_event_schema() -> *mut c_char {
+    schema_string(event_schema())
+}
+
+/// Returns the JSON Schema of the options accepted by [`codex_sdk_new`], or
+/// null if it cannot be serialized.  Free the result with [`codex_string_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn codex_options_schema() -> *mut c_char {
+    schema_string(options_schema())
+}
+
+/// Returns the JSON Schema of the submissions accepted by
+/// [`codex_session_submit`], or null if it cannot be serialized.  Free the
+/// result with [`codex_string_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn codex_submission_schema() -> *mut c_char {
+    schema_string(submission_schema())
+}
+
+fn schema_string(schema: serde_json::Result<Value>) -> *mut c_char {
+    match schema {
+        Ok(schema) => c_string(schema.to_string()).into_raw(),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Frees a string returned by this library.
+///
+/// # Safety
+///
+/// `string` must be null or a string returned through `error_out` or by a
+/// schema function that was not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: forwarded from this function's contract.
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Whether the caller runs on a Codex runtime thread, as event and tool
+/// callbacks do.  `block_on` panics there, and a panic cannot unwind into C.
+fn inside_runtime() -> bool {
+    tokio::runtime::Handle::try_current().is_ok()
+}
+
+/// Reads a UTF-8 argument.  Null reads as `None`.
+unsafe fn optional_str<'a>(ptr: *const c_char) -> Result<Option<&'a str>, String> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    // SAFETY: the caller passes a NUL-terminated string.
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map(Some)
+        .map_err(|err| format!("argument is not UTF-8: {err}"))
+}
+
+/// Converts to a C string, dropping interior NULs rather than failing.
+fn c_string(text: String) -> CString {
+    CString::new(text).unwrap_or_else(|err| {
+        let mut bytes = err.into_vec();
+        bytes.retain(|byte| *byte != 0);
+        CString::new(bytes).unwrap_or_default()
+    })
+}
+
+fn set_error(error_out: *mut *mut c_char, message: impl Display) {
+    if !error_out.is_null() {
+        // SAFETY: `error_out` is null or valid for writes per every caller's
+        // contract.
+        unsafe { *error_out = c_string(message.to_string()).into_raw() };
+    }
+}
+
+fn fail<T>(error_out: *mut *mut c_char, message: impl Display) -> *mut T {
+    set_error(error_out, message);
+    ptr::null_mut()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    fn take_error(error: *mut c_char) -> String {
+        assert!(!error.is_null());
+        // SAFETY: `error` came from `set_error`.
+        let message = unsafe { CStr::from_ptr(error) }
+            .to_string_lossy()
+            .into_owned();
+        // SAFETY: as above; freed exactly once.
+        unsafe { codex_string_free(error) };
+        message
+    }
+
+    #[test]
+    fn invalid_options_are_reported_through_error_out() {
+        let options = c_string(r#"{"model": 1}"#.to_string());
+        let mut error = ptr::null_mut();
+        // SAFETY: valid string and out pointer.
+        let sdk = unsafe {
+            codex_sdk_new(
+                options.as_ptr(),
+                /*tool_callback*/ None,
+                ptr::null_mut(),
+                /*tool_user_data_free*/ None,
+                &mut error,
+            )
+        };
+
+        assert!(sdk.is_null());
+        assert!(take_error(error).starts_with("invalid options:"));
+    }
+
+    #[test]
+    fn tools_without_a_callback_are_rejected() {
+        let options = c_string(
+            r#"{"tools": [{"name": "echo", "description": "", "input_schema": {}}]}"#.to_string(),
+        );
+        let mut error = ptr::null_mut();
+        // SAFETY: valid string and out pointer.
+        let sdk = unsafe {
+            codex_sdk_new(
+                options.as_ptr(),
+                /*tool_callback*/ None,
+                ptr::null_mut(),
+                /*tool_user_data_free*/ None,
+                &mut error,
+            )
+        };
+
+        assert!(sdk.is_null());
+        assert_eq!(take_error(error), "tools need a tool callback");
+    }
+
+    #[test]
+    fn submissions_are_tagged_by_type() {
+        let submission: Submission = serde_json::from_str(
+            r#"{"type": "user_input", "items": [{"type": "text", "text": "hi"}]}"#,
+        )
+        .expect("user input");
+        let Submission::UserInput { items } = submission else {
+            panic!("expected user input, got {submission:?}");
+        };
+        assert_eq!(items.len(), 1);
+
+        assert!(matches!(
+            serde_json::from_str(r#"{"type": "interrupt"}"#),
+            Ok(Submission::Interrupt)
+        ));
+        assert!(matches!(
+            serde_json::from_str(
+                r#"{"type": "exec_approval", "id": "call-1", "decision": "approve_for_session"}"#
+            ),
+            Ok(Submission::ExecApproval {
+                turn_id: None,
+                decision: ApprovalDecision::ApproveForSession,
+                ..
+            })
+        ));
+        assert!(matches!(
+            serde_json::from_str(
+                r#"{"type": "patch_approval", "id": "call-2", "decision": "deny"}"#
+            ),
+            Ok(Submission::PatchApproval {
+                decision: ApprovalDecision::Deny,
+                ..
+            })
+        ));
+        assert!(serde_json::from_str::<Submission>(r#"{"type": "shutdown"}"#).is_err());
+    }
+
+    unsafe extern "C" fn echo_tool(
+        tool: *const c_char,
+        arguments_json: *const c_char,
+        reply: *mut CodexToolReply,
+        user_data: *mut c_void,
+    ) -> bool {
+        // SAFETY: the strings are valid for the call and `user_data` is the
+        // `u32` the test passes.
+        let (tool, arguments, suffix) = unsafe {
+            (
+                CStr::from_ptr(tool).to_string_lossy(),
+                CStr::from_ptr(arguments_json).to_string_lossy(),
+                *(user_data as *const u32),
+            )
+        };
+        let text = c_string(format!("{tool} {arguments} {suffix}"));
+        // SAFETY: `reply` is the pointer passed to this callback.
+        unsafe { codex_tool_reply_set_text(reply, text.as_ptr()) };
+        tool == "echo"
+    }
+
+    #[test]
+    fn tool_calls_reach_the_callback_and_return_its_reply() {
+        static SUFFIX: u32 = 7;
+        let tool = FfiTool {
+            spec: DynamicToolFunctionSpec {
+                name: "echo".to_string(),
+                description: String::new(),
+                input_schema: Value::Null,
+                defer_loading: false,
+            },
+            callback: echo_tool,
+            user_data: Arc::new(ToolUserData {
+                user_data: UserData(&SUFFIX as *const u32 as *mut c_void),
+                free: None,
+            }),
+        };
+        let runtime = Runtime::new().expect("runtime");
+
+        let output = runtime.block_on(tool.call(serde_json::json!({ "text": "hi" })));
+
+        assert_eq!(output, ToolOutput::text(r#"echo {"text":"hi"} 7"#));
+    }
+
+    static FREED: AtomicUsize = AtomicUsize::new(0);
+
+    unsafe extern "C" fn count_free(_user_data: *mut c_void) {
+        FREED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn tool_user_data_is_freed_once_with_the_last_tool() {
+        let user_data = Arc::new(ToolUserData {
+            user_data: UserData(ptr::null_mut()),
+            free: Some(count_free),
+        });
+        let tool = |name: &str| FfiTool {
+            spec: DynamicToolFunctionSpec {
+                name: name.to_string(),
+                description: String::new(),
+                input_schema: Value::Null,
+                defer_loading: false,
+            },
+            callback: echo_tool,
+            user_data: Arc::clone(&user_data),
+        };
+        let (first, second) = (tool("first"), tool("second"));
+        drop(user_data);
+
+        drop(first);
+        assert_eq!(FREED.load(Ordering::SeqCst), 0);
+        drop(second);
+        assert_eq!(FREED.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn calls_that_block_are_refused_from_callbacks() {
+        let runtime = Runtime::new().expect("runtime");
+        let mut error = ptr::null_mut();
+
+        let sdk = runtime.block_on(async {
+            // SAFETY: null options and a valid out pointer.
+            unsafe {
+                codex_sdk_new(
+                    ptr::null(),
+                    /*tool_callback*/ None,
+                    ptr::null_mut(),
+                    /*tool_user_data_free*/ None,
+                    &mut error,
+                )
+            }
+        });
+
+        assert!(sdk.is_null());
+        assert_eq!(
+            take_error(error),
+            "codex_sdk_new cannot be called from a callback"
+        );
+    }
+}
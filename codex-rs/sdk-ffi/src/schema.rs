@@ -0,0 +1,153 @@
+//! JSON Schemas of the JSON that crosses the C interface.
+//!
+//! The schemas are derived from the same types that produce and parse the
+//! JSON, so a frontend that generates its client types from them stops
+//! compiling when an event changes shape instead of misreading it.
+
+use std::path::Path;
+
+use codex_sdk::Event;
+use schemars::JsonSchema;
+use schemars::schema_for;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::SdkOptions;
+use crate::Submission;
+
+/// Schema of the events passed to the event callback.
+pub fn event_schema() -> serde_json::Result<Value> {
+    schema_json::<Event>()
+}
+
+/// Schema of the options accepted by `codex_sdk_new`.
+pub fn options_schema() -> serde_json::Result<Value> {
+    schema_json::<SdkOptions>()
+}
+
+/// Schema of the submissions accepted by `codex_session_submit`.
+pub fn submission_schema() -> serde_json::Result<Value> {
+    schema_json::<Submission>()
+}
+
+/// Writes `event.schema.json`, `options.schema.json`, and
+/// `submission.schema.json` to `out_dir`.  The checked-in copies under
+/// `sdk-ffi/schema` are written the same way.
+pub fn write_schemas(out_dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(out_dir)?;
+    for (name, schema) in [
+        ("event", event_schema()?),
+        ("options", options_schema()?),
+        ("submission", submission_schema()?),
+    ] {
+        let json = serde_json::to_vec_pretty(&schema)?;
+        std::fs::write(out_dir.join(format!("{name}.schema.json")), json)?;
+    }
+    Ok(())
+}
+
+fn schema_json<T: JsonSchema>() -> serde_json::Result<Value> {
+    serde_json::to_value(schema_for!(T)).map(|schema| canonicalize_json(&schema))
+}
+
+/// Sorts object keys so the output does not depend on serde_json's
+/// `preserve_order` feature.
+fn canonicalize_json(value: &Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.iter().map(canonicalize_json).collect()),
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            let mut sorted = Map::with_capacity(map.len());
+            for (key, child) in entries {
+                sorted.insert(key.clone(), canonicalize_json(child));
+            }
+            Value::Object(sorted)
+        }
+        _ => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn generated_schemas_match_fixtures() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        write_schemas(temp_dir.path()).expect("write schemas");
+
+        for (name, expected) in [
+            ("event", include_str!("../schema/event.schema.json")),
+            ("options", include_str!("../schema/options.schema.json")),
+            (
+                "submission",
+                include_str!("../schema/submission.schema.json"),
+            ),
+        ] {
+            let actual =
+                std::fs::read_to_string(temp_dir.path().join(format!("{name}.schema.json")))
+                    .expect("read generated schema");
+            assert_eq!(
+                expected.replace("\r\n", "\n"),
+                actual,
+                "{name}.schema.json is out of date; run `just write-sdk-schema`"
+            );
+        }
+    }
+
+    #[test]
+    fn event_schema_covers_every_event_type() {
+        let schema = event_schema().expect("event schema");
+        assert_eq!(schema["required"], serde_json::json!(["id", "msg"]));
+
+        let event_types = schema["definitions"]["EventMsg"]["oneOf"]
+            .as_array()
+            .expect("EventMsg is a tagged union")
+            .iter()
+            .filter_map(|variant| variant["properties"]["type"]["enum"][0].as_str())
+            .collect::<Vec<_>>();
+        for event_type in [
+            "agent_message",
+            "turn_complete",
+            "dynamic_tool_call_request",
+        ] {
+            assert!(
+                event_types.contains(&event_type),
+                "missing {event_type} in {event_types:?}"
+            );
+        }
+    }
+
+    /// The options are documented by hand in `codex_sdk.h`; keep the two in step.
+    #[test]
+    fn options_schema_matches_the_header() {
+        let schema = options_schema().expect("options schema");
+        let mut properties = schema["properties"]
+            .as_object()
+            .expect("options are an object")
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        properties.sort_unstable();
+        assert_eq!(
+            properties,
+            vec![
+                "approval_policy",
+                "codex_home",
+                "cwd",
+                "model",
+                "model_provider",
+                "tools",
+            ]
+        );
+        assert_eq!(schema["additionalProperties"], Value::Bool(false));
+
+        let header = include_str!("../include/codex_sdk.h");
+        for property in properties {
+            assert!(header.contains(&format!("\"{property}\"")), "{property}");
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use codex_core_api::CodexErr;
+use codex_core_api::ExecServerError;
+
+/// Errors returned by the SDK.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("failed to load config: {0}")]
+    Config(#[source] std::io::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to set up the execution environment: {0}")]
+    Environment(#[from] ExecServerError),
+
+    #[error(transparent)]
+    Codex(#[from] CodexErr),
+
+    /// The turn ended with an error event.
+    #[error("turn failed: {0}")]
+    TurnFailed(String),
+
+    #[error("turn was aborted")]
+    TurnAborted,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
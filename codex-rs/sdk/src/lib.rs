@@ -0,0 +1,228 @@
+//! Embed the Codex agent in a Rust application.
+//!
+//! [`Codex`] loads the user's config and owns the thread machinery that the CLI
+//! uses.  Each [`Session`] is one conversation: send input, read the typed
+//! [`Event`]s of its turns, and let the model call [`Tool`]s that the
+//! application implements.
+//!
+//! ```ignore
+//! let codex = Codex::builder().model("gpt-5.2").tool(Weather).build().await?;
+//! let session = codex.start_session().await?;
+//! let reply = session.run("What's the weather in Paris?").await?;
+//! session.shutdown().await?;
+//! ```
+//!
+//! The crate is a facade over `codex-core-api`; the types it exposes come from
+//! there, so they match the ones the CLI and app server use.
+
+#![deny(private_bounds, private_interfaces, unreachable_pub)]
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core_api::AuthManager;
+use codex_core_api::CodexAppsToolsCache;
+use codex_core_api::CodexHomeUserInstructionsProvider;
+use codex_core_api::ConfigBuilder;
+use codex_core_api::EnvironmentManager;
+use codex_core_api::ExecServerRuntimePaths;
+use codex_core_api::ExtensionRegistryBuilder;
+use codex_core_api::NewThread;
+use codex_core_api::ThreadManager;
+use codex_core_api::build_models_manager;
+use codex_core_api::init_state_db;
+use codex_core_api::install_image_generation_extension;
+use codex_core_api::local_agent_graph_store_from_state_db;
+use codex_core_api::resolve_installation_id;
+use codex_core_api::thread_store_from_config;
+
+mod error;
+mod session;
+mod tools;
+
+pub use codex_core_api::AskForApproval;
+pub use codex_core_api::Config;
+pub use codex_core_api::ConfigOverrides;
+pub use codex_core_api::DynamicToolCallOutputContentItem;
+pub use codex_core_api::DynamicToolCallRequest;
+pub use codex_core_api::DynamicToolFunctionSpec;
+pub use codex_core_api::Event;
+pub use codex_core_api::EventMsg;
+pub use codex_core_api::Op;
+pub use codex_core_api::ReviewDecision;
+pub use codex_core_api::SessionSource;
+pub use codex_core_api::ThreadId;
+pub use codex_core_api::UserInput;
+pub use error::Error;
+pub use error::Result;
+pub use session::Session;
+pub use tools::Tool;
+pub use tools::ToolCallFuture;
+pub use tools::ToolOutput;
+use tools::ToolRegistry;
+
+/// Configures a [`Codex`].
+///
+/// Config is loaded the way the CLI loads it, from `config.toml` under the
+/// Codex home, with the overrides set here applied on top.
+pub struct CodexBuilder {
+    codex_home: Option<PathBuf>,
+    overrides: ConfigOverrides,
+    session_source: SessionSource,
+    tools: ToolRegistry,
+}
+
+impl CodexBuilder {
+    /// Codex home to read config and credentials from.  Defaults to
+    /// `$CODEX_HOME`, or `~/.codex`.
+    pub fn codex_home(mut self, codex_home: PathBuf) -> Self {
+        self.codex_home = Some(codex_home);
+        self
+    }
+
+    /// Directory the agent works in.  Defaults to the current directory.
+    pub fn cwd(mut self, cwd: PathBuf) -> Self {
+        self.overrides.cwd = Some(cwd);
+        self
+    }
+
+    /// Model to use instead of the configured one.
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.overrides.model = Some(model.into());
+        self
+    }
+
+    /// Id of a provider in `model_providers` to use instead of the configured one.
+    pub fn model_provider(mut self, model_provider: impl Into<String>) -> Self {
+        self.overrides.model_provider = Some(model_provider.into());
+        self
+    }
+
+    /// Replaces all config overrides, including those set by [`Self::cwd`],
+    /// [`Self::model`], and [`Self::model_provider`].
+    pub fn config_overrides(mut self, overrides: ConfigOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// How sessions are labeled in history and analytics.  Defaults to
+    /// [`SessionSource::Exec`].
+    pub fn session_source(mut self, session_source: SessionSource) -> Self {
+        self.session_source = session_source;
+        self
+    }
+
+    /// Adds a tool the model can call in every session.
+    pub fn tool(mut self, tool: impl Tool + 'static) -> Self {
+        self.tools.register(Arc::new(tool));
+        self
+    }
+
+    pub async fn build(self) -> Result<Codex> {
+        let Self {
+            codex_home,
+            overrides,
+            session_source,
+            tools,
+        } = self;
+        let mut config_builder = ConfigBuilder::default().harness_overrides(overrides);
+        if let Some(codex_home) = codex_home {
+            config_builder = config_builder.codex_home(codex_home);
+        }
+        let config = config_builder.build().await.map_err(Error::Config)?;
+
+        let state_db = init_state_db(&config).await;
+        let auth_manager =
+            AuthManager::shared_from_config(&config, /*enable_codex_api_key_env*/ true).await;
+        // Without a Codex executable to re-enter, commands run without the
+        // helpers that need one, as in other embedders.
+        let local_runtime_paths = match config.codex_self_exe.clone() {
+            Some(codex_self_exe) => Some(ExecServerRuntimePaths::from_optional_paths(
+                Some(codex_self_exe),
+                config.codex_linux_sandbox_exe.clone(),
+            )?),
+            None => None,
+        };
+        let environment_manager = Arc::new(
+            EnvironmentManager::from_codex_home(config.codex_home.clone(), local_runtime_paths)
+                .await?,
+        );
+        let installation_id = resolve_installation_id(&config.state_home).await?;
+        let user_instructions_provider = Arc::new(CodexHomeUserInstructionsProvider::new(
+            config.codex_home.clone(),
+        ));
+        let mut extensions = ExtensionRegistryBuilder::<Config>::new();
+        install_image_generation_extension(
+            &mut extensions,
+            auth_manager.clone(),
+            |config: &Config| Some(config.codex_home.clone()),
+        );
+        let thread_store = thread_store_from_config(&config, state_db.clone());
+        let thread_manager = ThreadManager::new(
+            &config,
+            Arc::clone(&auth_manager),
+            build_models_manager(&config, auth_manager),
+            CodexAppsToolsCache::default(),
+            session_source,
+            environment_manager,
+            Arc::new(extensions.build()),
+            user_instructions_provider,
+            /*analytics_events_client*/ None,
+            thread_store,
+            local_agent_graph_store_from_state_db(state_db.as_ref()),
+            installation_id,
+            /*attestation_provider*/ None,
+            /*external_time_provider*/ None,
+        );
+
+        Ok(Codex {
+            config,
+            thread_manager: Arc::new(thread_manager),
+            tools: Arc::new(tools),
+        })
+    }
+}
+
+/// A loaded config and the machinery to run sessions with it.
+pub struct Codex {
+    config: Config,
+    thread_manager: Arc<ThreadManager>,
+    tools: Arc<ToolRegistry>,
+}
+
+impl Codex {
+    pub fn builder() -> CodexBuilder {
+        CodexBuilder {
+            codex_home: None,
+            overrides: ConfigOverrides::default(),
+            session_source: SessionSource::Exec,
+            tools: ToolRegistry::default(),
+        }
+    }
+
+    /// The resolved config that new sessions start from.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Starts a conversation with the builder's tools available to the model.
+    pub async fn start_session(&self) -> Result<Session> {
+        self.start_session_with_config(self.config.clone()).await
+    }
+
+    /// Starts a conversation with an adjusted copy of [`Self::config`].
+    pub async fn start_session_with_config(&self, config: Config) -> Result<Session> {
+        let NewThread {
+            thread_id, thread, ..
+        } = self
+            .thread_manager
+            .start_thread_with_tools(config, self.tools.specs())
+            .await?;
+        Ok(Session::new(
+            thread_id,
+            thread,
+            Arc::clone(&self.thread_manager),
+            Arc::clone(&self.tools),
+        ))
+    }
+}
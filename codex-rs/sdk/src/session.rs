@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use codex_core_api::CodexThread;
+use codex_core_api::Event;
+use codex_core_api::EventMsg;
+use codex_core_api::Op;
+use codex_core_api::ThreadId;
+use codex_core_api::ThreadManager;
+use codex_core_api::UserInput;
+
+use crate::Error;
+use crate::Result;
+use crate::tools::ToolRegistry;
+
+/// One conversation with the agent.
+///
+/// Read events with [`Self::next_event`] after sending input; a turn makes no
+/// progress on tool calls or approvals while nobody reads its events.
+pub struct Session {
+    thread_id: ThreadId,
+    thread: Arc<CodexThread>,
+    thread_manager: Arc<ThreadManager>,
+    tools: Arc<ToolRegistry>,
+}
+
+impl Session {
+    pub(crate) fn new(
+        thread_id: ThreadId,
+        thread: Arc<CodexThread>,
+        thread_manager: Arc<ThreadManager>,
+        tools: Arc<ToolRegistry>,
+    ) -> Self {
+        Self {
+            thread_id,
+            thread,
+            thread_manager,
+            tools,
+        }
+    }
+
+    pub fn id(&self) -> ThreadId {
+        self.thread_id
+    }
+
+    /// Starts a turn with `text` as the user message.  Returns the submission
+    /// id that the turn's events carry.
+    pub async fn send(&self, text: impl Into<String>) -> Result<String> {
+        self.send_input(vec![UserInput::Text {
+            text: text.into(),
+            text_elements: Vec::new(),
+        }])
+        .await
+    }
+
+    /// Starts a turn with arbitrary input items, such as images.
+    pub async fn send_input(&self, items: Vec<UserInput>) -> Result<String> {
+        self.submit(Op::UserInput {
+            items,
+            final_output_json_schema: None,
+            responsesapi_client_metadata: None,
+            additional_context: Default::default(),
+            thread_settings: Default::default(),
+        })
+        .await
+    }
+
+    /// Sends any operation, for example an approval decision.
+    pub async fn submit(&self, op: Op) -> Result<String> {
+        Ok(self.thread.submit(op).await?)
+    }
+
+    /// Stops the running turn.
+    pub async fn interrupt(&self) -> Result<()> {
+        self.submit(Op::Interrupt).await?;
+        Ok(())
+    }
+
+    /// Waits for the next event.
+    ///
+    /// Calls to the SDK's tools are answered in the background; their
+    /// `DynamicToolCallRequest` events are still returned so callers can
+    /// follow along.
+    pub async fn next_event(&self) -> Result<Event> {
+        let event = self.thread.next_event().await?;
+        if let EventMsg::DynamicToolCallRequest(request) = &event.msg {
+            let request = request.clone();
+            let thread = Arc::clone(&self.thread);
+            let tools = Arc::clone(&self.tools);
+            tokio::spawn(async move {
+                let response = tools.dispatch(&request).await;
+                if let Err(err) = thread
+                    .submit(Op::DynamicToolResponse {
+                        id: request.call_id,
+                        response,
+                    })
+                    .await
+                {
+                    tracing::warn!("failed to return output of tool {}: {err}", request.tool);
+                }
+            });
+        }
+        Ok(event)
+    }
+
+    /// Runs one turn with `prompt` and returns the agent's last message.
+    ///
+    /// Events are consumed along the way; use [`Self::send`] and
+    /// [`Self::next_event`] to observe them.
+    pub async fn run(&self, prompt: impl Into<String>) -> Result<Option<String>> {
+        self.send(prompt).await?;
+        loop {
+            match self.next_event().await?.msg {
+                EventMsg::TurnComplete(event) => match event.error {
+                    Some(error) => return Err(Error::TurnFailed(error.message)),
+                    None => return Ok(event.last_agent_message),
+                },
+                EventMsg::Error(event) => return Err(Error::TurnFailed(event.message)),
+                EventMsg::TurnAborted(_) => return Err(Error::TurnAborted),
+                _ => {}
+            }
+        }
+    }
+
+    /// Ends the conversation and waits for its background work to finish.
+    /// Anything submitted afterwards fails.
+    pub async fn shutdown(&self) -> Result<()> {
+        let result = self.thread.shutdown_and_wait().await;
+        self.thread_manager.remove_thread(&self.thread_id).await;
+        Ok(result?)
+    }
+}
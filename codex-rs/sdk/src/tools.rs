@@ -0,0 +1,176 @@
+//! Tools implemented by the embedding application.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use codex_core_api::DynamicToolCallOutputContentItem;
+use codex_core_api::DynamicToolCallRequest;
+use codex_core_api::DynamicToolFunctionSpec;
+use codex_core_api::DynamicToolResponse;
+use codex_core_api::DynamicToolSpec;
+use serde_json::Value;
+
+/// Future returned by [`Tool::call`].
+pub type ToolCallFuture<'a> = Pin<Box<dyn Future<Output = ToolOutput> + Send + 'a>>;
+
+/// A function the model can call, implemented by the embedding application.
+///
+/// Calls run on the Tokio runtime while the turn waits for their output, so
+/// several calls from one turn may run at the same time.
+pub trait Tool: Send + Sync {
+    /// Name, description, and JSON Schema of the arguments, as shown to the model.
+    fn spec(&self) -> DynamicToolFunctionSpec;
+
+    /// Runs the tool with the arguments the model sent.
+    fn call(&self, arguments: Value) -> ToolCallFuture<'_>;
+}
+
+/// What a [`Tool`] call returns to the model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolOutput {
+    pub content_items: Vec<DynamicToolCallOutputContentItem>,
+    pub success: bool,
+}
+
+impl ToolOutput {
+    /// A successful call with a text result.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            content_items: vec![DynamicToolCallOutputContentItem::InputText { text: text.into() }],
+            success: true,
+        }
+    }
+
+    /// A failed call.  The message is shown to the model so it can recover.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            ..Self::text(message)
+        }
+    }
+}
+
+impl From<ToolOutput> for DynamicToolResponse {
+    fn from(output: ToolOutput) -> Self {
+        Self {
+            content_items: output.content_items,
+            success: output.success,
+        }
+    }
+}
+
+/// The tools of a [`crate::Codex`], keyed by name.  A later tool with the same
+/// name replaces an earlier one.
+#[derive(Clone, Default)]
+pub(crate) struct ToolRegistry {
+    tools: BTreeMap<String, Arc<dyn Tool>>,
+}
+
+impl ToolRegistry {
+    pub(crate) fn register(&mut self, tool: Arc<dyn Tool>) {
+        self.tools.insert(tool.spec().name, tool);
+    }
+
+    /// Specs passed to the thread so the model sees the tools.
+    pub(crate) fn specs(&self) -> Vec<DynamicToolSpec> {
+        self.tools
+            .values()
+            .map(|tool| DynamicToolSpec::Function(tool.spec()))
+            .collect()
+    }
+
+    /// Runs the requested tool.  Requests for unknown tools fail rather than
+    /// leaving the turn waiting.
+    pub(crate) async fn dispatch(&self, request: &DynamicToolCallRequest) -> DynamicToolResponse {
+        let tool = match request.namespace {
+            Some(_) => None,
+            None => self.tools.get(&request.tool),
+        };
+        match tool {
+            Some(tool) => tool.call(request.arguments.clone()).await.into(),
+            None => ToolOutput::error(format!("unknown tool: {}", request.tool)).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    struct Echo;
+
+    impl Tool for Echo {
+        fn spec(&self) -> DynamicToolFunctionSpec {
+            DynamicToolFunctionSpec {
+                name: "echo".to_string(),
+                description: "Returns its `text` argument.".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": { "text": { "type": "string" } },
+                    "required": ["text"],
+                }),
+                defer_loading: false,
+            }
+        }
+
+        fn call(&self, arguments: Value) -> ToolCallFuture<'_> {
+            Box::pin(async move {
+                match arguments.get("text").and_then(Value::as_str) {
+                    Some(text) => ToolOutput::text(text),
+                    None => ToolOutput::error("missing `text`"),
+                }
+            })
+        }
+    }
+
+    fn request(tool: &str, arguments: Value) -> DynamicToolCallRequest {
+        DynamicToolCallRequest {
+            call_id: "call-1".to_string(),
+            turn_id: "turn-1".to_string(),
+            started_at_ms: 0,
+            namespace: None,
+            tool: tool.to_string(),
+            arguments,
+        }
+    }
+
+    fn registry() -> ToolRegistry {
+        let mut registry = ToolRegistry::default();
+        registry.register(Arc::new(Echo));
+        registry
+    }
+
+    #[test]
+    fn registered_tools_are_exposed_as_function_specs() {
+        assert_eq!(
+            registry().specs(),
+            vec![DynamicToolSpec::Function(Echo.spec())]
+        );
+    }
+
+    #[tokio::test]
+    async fn calls_are_routed_to_the_named_tool() {
+        assert_eq!(
+            registry()
+                .dispatch(&request("echo", json!({ "text": "hi" })))
+                .await,
+            DynamicToolResponse::from(ToolOutput::text("hi"))
+        );
+        assert_eq!(
+            registry().dispatch(&request("echo", json!({}))).await,
+            DynamicToolResponse::from(ToolOutput::error("missing `text`"))
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_tools_fail_instead_of_hanging_the_turn() {
+        assert_eq!(
+            registry().dispatch(&request("missing", json!({}))).await,
+            DynamicToolResponse::from(ToolOutput::error("unknown tool: missing"))
+        );
+    }
+}
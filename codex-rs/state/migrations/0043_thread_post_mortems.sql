@@ -0,0 +1,10 @@
+CREATE TABLE thread_post_mortems (
+    thread_id TEXT PRIMARY KEY,
+    turn_id TEXT NOT NULL,
+    recorded_at_ms INTEGER NOT NULL,
+    stop_reason TEXT NOT NULL,
+    attempted TEXT NOT NULL,
+    tool_calls TEXT NOT NULL,
+    changed_paths TEXT NOT NULL,
+    open_questions TEXT NOT NULL
+);
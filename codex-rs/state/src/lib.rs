@@ -65,6 +65,7 @@ pub use runtime::RemoteControlEnrollmentRecord;
 pub use runtime::RuntimeDbBackup;
 pub use runtime::RuntimeDbPath;
 pub use runtime::ThreadFilterOptions;
+pub use runtime::ThreadPostMortemRecord;
 pub use runtime::backup_runtime_db_for_fresh_start;
 pub use runtime::goals_db_filename;
 pub use runtime::goals_db_path;
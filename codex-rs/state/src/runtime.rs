@@ -51,6 +51,7 @@ mod external_agent_config_imports;
 mod goals;
 mod logs;
 mod memories;
+mod post_mortems;
 mod recovery;
 mod remote_control;
 #[cfg(test)]
@@ -66,6 +67,7 @@ pub use goals::GoalAccountingOutcome;
 pub use goals::GoalStore;
 pub use goals::GoalUpdate;
 pub use memories::MemoryStore;
+pub use post_mortems::ThreadPostMortemRecord;
 pub use recovery::RuntimeDbBackup;
 pub use recovery::backup_runtime_db_for_fresh_start;
 pub use recovery::is_sqlite_corruption_error;
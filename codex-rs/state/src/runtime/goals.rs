@@ -141,6 +141,24 @@ SELECT EXISTS(
         .map_err(Into::into)
     }
 
+    /// Holds automatic goal continuation until the next turn starts.
+    ///
+    /// Does nothing when the thread has no goal.
+    pub async fn defer_thread_goal_continuation(&self, thread_id: ThreadId) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+INSERT INTO thread_goal_continuation_deferrals (thread_id)
+SELECT thread_id FROM thread_goals WHERE thread_id = ?
+ON CONFLICT(thread_id) DO NOTHING
+            "#,
+        )
+        .bind(thread_id.to_string())
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn clear_thread_goal_continuation_deferral(
         &self,
         thread_id: ThreadId,
@@ -753,6 +771,56 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn continuation_deferral_requires_a_goal_and_clears() {
+        let runtime = test_runtime().await;
+        let thread_id = test_thread_id();
+        upsert_test_thread(&runtime, thread_id).await;
+        let goals = runtime.thread_goals();
+
+        goals
+            .defer_thread_goal_continuation(thread_id)
+            .await
+            .expect("deferral without a goal should succeed");
+        assert!(
+            !goals
+                .has_thread_goal_continuation_deferral(thread_id)
+                .await
+                .unwrap()
+        );
+
+        goals
+            .replace_thread_goal(
+                thread_id,
+                "optimize the benchmark",
+                crate::ThreadGoalStatus::Active,
+                /*token_budget*/ None,
+            )
+            .await
+            .expect("goal replacement should succeed");
+        goals
+            .defer_thread_goal_continuation(thread_id)
+            .await
+            .expect("deferral should succeed");
+        assert!(
+            goals
+                .has_thread_goal_continuation_deferral(thread_id)
+                .await
+                .unwrap()
+        );
+
+        goals
+            .clear_thread_goal_continuation_deferral(thread_id)
+            .await
+            .expect("clearing the deferral should succeed");
+        assert!(
+            !goals
+                .has_thread_goal_continuation_deferral(thread_id)
+                .await
+                .unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn replace_thread_goal_applies_budget_limit_immediately() {
         let runtime = test_runtime().await;
@@ -0,0 +1,109 @@
+use super::StateRuntime;
+use codex_protocol::ThreadId;
+use serde::Deserialize;
+use serde::Serialize;
+use sqlx::Row;
+
+/// Structured summary of a turn that errored out or was interrupted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadPostMortemRecord {
+    pub turn_id: String,
+    pub recorded_at_ms: i64,
+    /// Why the turn stopped, such as `interrupted` or an error kind.
+    pub stop_reason: String,
+    /// The request the turn was working on.
+    pub attempted: String,
+    /// Tool calls the turn issued, in order.
+    pub tool_calls: Vec<String>,
+    /// `git status --porcelain` entries in the working tree when the turn stopped.
+    pub changed_paths: Vec<String>,
+    pub open_questions: Vec<String>,
+}
+
+impl StateRuntime {
+    /// Records the post-mortem for a thread, replacing any earlier one.
+    pub async fn record_thread_post_mortem(
+        &self,
+        thread_id: ThreadId,
+        post_mortem: &ThreadPostMortemRecord,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+INSERT INTO thread_post_mortems (
+    thread_id,
+    turn_id,
+    recorded_at_ms,
+    stop_reason,
+    attempted,
+    tool_calls,
+    changed_paths,
+    open_questions
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+ON CONFLICT(thread_id) DO UPDATE SET
+    turn_id = excluded.turn_id,
+    recorded_at_ms = excluded.recorded_at_ms,
+    stop_reason = excluded.stop_reason,
+    attempted = excluded.attempted,
+    tool_calls = excluded.tool_calls,
+    changed_paths = excluded.changed_paths,
+    open_questions = excluded.open_questions
+"#,
+        )
+        .bind(thread_id.to_string())
+        .bind(post_mortem.turn_id.as_str())
+        .bind(post_mortem.recorded_at_ms)
+        .bind(post_mortem.stop_reason.as_str())
+        .bind(post_mortem.attempted.as_str())
+        .bind(serde_json::to_string(&post_mortem.tool_calls)?)
+        .bind(serde_json::to_string(&post_mortem.changed_paths)?)
+        .bind(serde_json::to_string(&post_mortem.open_questions)?)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes and returns the post-mortem recorded for a thread, if any.
+    pub async fn take_thread_post_mortem(
+        &self,
+        thread_id: ThreadId,
+    ) -> anyhow::Result<Option<ThreadPostMortemRecord>> {
+        let row = sqlx::query(
+            r#"
+DELETE FROM thread_post_mortems
+WHERE thread_id = ?
+RETURNING
+    turn_id,
+    recorded_at_ms,
+    stop_reason,
+    attempted,
+    tool_calls,
+    changed_paths,
+    open_questions
+"#,
+        )
+        .bind(thread_id.to_string())
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        row.map(|row| {
+            let tool_calls: String = row.try_get("tool_calls")?;
+            let changed_paths: String = row.try_get("changed_paths")?;
+            let open_questions: String = row.try_get("open_questions")?;
+            Ok(ThreadPostMortemRecord {
+                turn_id: row.try_get("turn_id")?,
+                recorded_at_ms: row.try_get("recorded_at_ms")?,
+                stop_reason: row.try_get("stop_reason")?,
+                attempted: row.try_get("attempted")?,
+                tool_calls: serde_json::from_str(&tool_calls)?,
+                changed_paths: serde_json::from_str(&changed_paths)?,
+                open_questions: serde_json::from_str(&open_questions)?,
+            })
+        })
+        .transpose()
+    }
+}
+
+#[cfg(test)]
+#[path = "post_mortems_tests.rs"]
+mod tests;
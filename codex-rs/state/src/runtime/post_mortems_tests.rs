@@ -0,0 +1,37 @@
+use super::*;
+use crate::runtime::test_support::unique_temp_dir;
+use pretty_assertions::assert_eq;
+
+fn post_mortem(turn_id: &str) -> ThreadPostMortemRecord {
+    ThreadPostMortemRecord {
+        turn_id: turn_id.to_string(),
+        recorded_at_ms: 1_700_000_000_000,
+        stop_reason: "interrupted".to_string(),
+        attempted: "fix the flaky login test".to_string(),
+        tool_calls: vec!["shell".to_string(), "apply_patch".to_string()],
+        changed_paths: vec![" M src/login.rs".to_string()],
+        open_questions: vec!["Tool call `shell` did not finish.".to_string()],
+    }
+}
+
+#[tokio::test]
+async fn take_returns_latest_post_mortem_once() -> anyhow::Result<()> {
+    let runtime = StateRuntime::init(unique_temp_dir(), "test-provider".to_string()).await?;
+    let thread_id = ThreadId::from_string("00000000-0000-0000-0000-000000000123")?;
+
+    assert_eq!(None, runtime.take_thread_post_mortem(thread_id).await?);
+
+    runtime
+        .record_thread_post_mortem(thread_id, &post_mortem("turn-1"))
+        .await?;
+    runtime
+        .record_thread_post_mortem(thread_id, &post_mortem("turn-2"))
+        .await?;
+
+    assert_eq!(
+        Some(post_mortem("turn-2")),
+        runtime.take_thread_post_mortem(thread_id).await?
+    );
+    assert_eq!(None, runtime.take_thread_post_mortem(thread_id).await?);
+    Ok(())
+}
@@ -10,12 +10,14 @@ use anyhow::bail;
 use clap::Parser;
 use codex_core_api::AbsolutePathBuf;
 use codex_core_api::AltScreenMode;
+use codex_core_api::ApprovalTimeoutAction;
 use codex_core_api::ApprovalsReviewer;
 use codex_core_api::Arg0DispatchPaths;
 use codex_core_api::AskForApproval;
 use codex_core_api::AuthCredentialsStoreMode;
 use codex_core_api::AuthManager;
 use codex_core_api::AutoCompactTokenLimitScope;
+use codex_core_api::BenchConfig;
 use codex_core_api::CodexAppsToolsCache;
 use codex_core_api::CodexHomeUserInstructionsProvider;
 use codex_core_api::CodexThread;
@@ -28,7 +30,10 @@ use codex_core_api::ExecServerRuntimePaths;
 use codex_core_api::ExtensionRegistryBuilder;
 use codex_core_api::Features;
 use codex_core_api::GhostSnapshotConfig;
+use codex_core_api::GoalsConfig;
+use codex_core_api::GuardrailsConfig;
 use codex_core_api::History;
+use codex_core_api::LinkDestinations;
 use codex_core_api::MemoriesConfig;
 use codex_core_api::ModelAvailabilityNuxConfig;
 use codex_core_api::MultiAgentV2Config;
@@ -40,19 +45,29 @@ use codex_core_api::Op;
 use codex_core_api::OtelConfig;
 use codex_core_api::PermissionProfile;
 use codex_core_api::Permissions;
+use codex_core_api::ProfilingConfig;
 use codex_core_api::ProjectConfig;
 use codex_core_api::RealtimeAudioConfig;
 use codex_core_api::RealtimeConfig;
+use codex_core_api::RefactorConfig;
+use codex_core_api::RunLimitsConfig;
+use codex_core_api::ScratchpadConfig;
 use codex_core_api::SessionPickerViewMode;
 use codex_core_api::SessionSource;
+use codex_core_api::SystemPromptConfig;
+use codex_core_api::TableOverflow;
+use codex_core_api::TerminalCapabilitiesToml;
 use codex_core_api::TerminalResizeReflowConfig;
 use codex_core_api::ThreadManager;
 use codex_core_api::ThreadStoreConfig;
 use codex_core_api::ToolSuggestConfig;
+use codex_core_api::TranscriptDensity;
+use codex_core_api::TranscriptTimestamps;
 use codex_core_api::TuiKeymap;
 use codex_core_api::TuiNotificationSettings;
 use codex_core_api::TuiPetAnchor;
 use codex_core_api::UriBasedFileOpener;
+use codex_core_api::UsageExportConfig;
 use codex_core_api::UserInput;
 use codex_core_api::WebSearchMode;
 use codex_core_api::arg0_dispatch_or_else;
@@ -190,6 +205,7 @@ fn new_config(model: Option<String>, arg0_paths: Arg0DispatchPaths) -> anyhow::R
         model_provider_id,
         model_provider,
         personality: None,
+        response_language: None,
         permissions: Permissions::from_approval_and_profile(
             Constrained::allow_any(AskForApproval::Never),
             Constrained::allow_any(PermissionProfile::read_only()),
@@ -221,6 +237,14 @@ fn new_config(model: Option<String>, arg0_paths: Arg0DispatchPaths) -> anyhow::R
         tui_status_line_use_colors: true,
         tui_terminal_title: None,
         tui_theme: None,
+        tui_syntax_colors: BTreeMap::new(),
+        tui_rainbow_brackets: false,
+        tui_diff_word_emphasis: true,
+        tui_tab_width: 4,
+        tui_tab_width_overrides: BTreeMap::new(),
+        tui_diff_show_whitespace: false,
+        tui_file_hyperlinks: false,
+        tui_language: None,
         tui_raw_output_mode: false,
         tui_pet: None,
         tui_pet_anchor: TuiPetAnchor::Composer,
@@ -228,6 +252,21 @@ fn new_config(model: Option<String>, arg0_paths: Arg0DispatchPaths) -> anyhow::R
         tui_keymap: TuiKeymap::default(),
         tui_session_picker_view: SessionPickerViewMode::Dense,
         tui_resume_cwd: None,
+        tui_approval_timeout_seconds: None,
+        tui_approval_timeout_action: ApprovalTimeoutAction::Deny,
+        tui_idle_pause_seconds: None,
+        tui_expand_reasoning_summaries: false,
+        tui_strike_completed_tasks: false,
+        tui_table_overflow: TableOverflow::Wrap,
+        tui_render_math: true,
+        tui_link_destinations: LinkDestinations::Inline,
+        tui_code_block_max_lines: 200,
+        tui_inline_images: false,
+        tui_terminal_capabilities: TerminalCapabilitiesToml::default(),
+        tui_transcript_density: TranscriptDensity::Normal,
+        tui_transcript_timestamps: TranscriptTimestamps::Off,
+        tui_transcript_auto_follow: true,
+        tui_tmux_split_views: false,
         tui_vim_mode_default: false,
         cwd: cwd.clone(),
         workspace_roots: vec![cwd],
@@ -249,6 +288,18 @@ fn new_config(model: Option<String>, arg0_paths: Arg0DispatchPaths) -> anyhow::R
         agent_max_depth: 1,
         agent_roles: BTreeMap::new(),
         memories: MemoriesConfig::default(),
+        goals: GoalsConfig::default(),
+        run_limits: RunLimitsConfig::default(),
+        related_repos: Vec::new(),
+        databases: Vec::new(),
+        webhooks: Vec::new(),
+        scratchpad: ScratchpadConfig::default(),
+        system_prompt: SystemPromptConfig::default(),
+        guardrails: GuardrailsConfig::default(),
+        bench: BenchConfig::default(),
+        profiling: ProfilingConfig::default(),
+        refactor: RefactorConfig::default(),
+        usage_export: UsageExportConfig::default(),
         state_home: codex_home.clone(),
         sqlite_home: codex_home.to_path_buf(),
         log_dir: codex_home.join("log").to_path_buf(),
@@ -298,7 +349,10 @@ fn new_config(model: Option<String>, arg0_paths: Arg0DispatchPaths) -> anyhow::R
         current_time_reminder: None,
         features: Default::default(),
         suppress_unstable_features_warning: false,
-        active_project: ProjectConfig { trust_level: None },
+        active_project: ProjectConfig {
+            trust_level: None,
+            response_language: None,
+        },
         notices: Notice::default(),
         check_for_update_on_startup: false,
         disable_paste_burst: false,
@@ -281,20 +281,28 @@ fn searchable_text(item: &ThreadItem) -> Option<Cow<'_, str>> {
 
 fn markdown_to_search_text(markdown: &str) -> String {
     let mut text = String::new();
+    // `Parser::new` leaves math and definition lists off, so their events (added in
+    // pulldown-cmark 0.11 and 0.12) never occur here; they are only matched to keep the
+    // match exhaustive.
     for event in Parser::new(markdown.trim()) {
         match event {
             Event::Text(value)
             | Event::Code(value)
             | Event::Html(value)
-            | Event::InlineHtml(value) => text.push_str(&value),
+            | Event::InlineHtml(value)
+            | Event::InlineMath(value)
+            | Event::DisplayMath(value) => text.push_str(&value),
             Event::SoftBreak | Event::HardBreak | Event::Rule => text.push(' '),
             Event::End(
                 TagEnd::Paragraph
                 | TagEnd::Heading(_)
-                | TagEnd::BlockQuote
+                | TagEnd::BlockQuote(_)
                 | TagEnd::CodeBlock
                 | TagEnd::List(_)
                 | TagEnd::Item
+                | TagEnd::DefinitionList
+                | TagEnd::DefinitionListTitle
+                | TagEnd::DefinitionListDefinition
                 | TagEnd::Table
                 | TagEnd::TableHead
                 | TagEnd::TableRow
@@ -62,7 +62,11 @@ pub use responses_api::dynamic_tool_to_responses_api_tool;
 pub use responses_api::mcp_tool_to_deferred_responses_api_tool;
 pub use responses_api::mcp_tool_to_responses_api_tool;
 pub use responses_api::tool_definition_to_responses_api_tool;
+pub use tool_call::CommandApprovalFuture;
+pub use tool_call::CommandApprovalRequest;
+pub use tool_call::CommandApprover;
 pub use tool_call::ConversationHistory;
+pub use tool_call::DenyCommandApprover;
 pub use tool_call::ExtensionTurnItem;
 pub use tool_call::NoopTurnItemEmitter;
 pub use tool_call::ToolCall;
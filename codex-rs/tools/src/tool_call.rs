@@ -3,11 +3,14 @@ use crate::ToolName;
 use crate::ToolPayload;
 use codex_extension_items::ExtensionItem;
 use codex_file_system::ExecutorFileSystem;
+use codex_file_system::FileMetadata;
+use codex_file_system::FileSystemResult;
 use codex_file_system::FileSystemSandboxContext;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::EventMsg;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_output_truncation::TruncationPolicy;
+use codex_utils_path_uri::PathUri;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -59,6 +62,42 @@ pub trait TurnItemEmitter: Send + Sync {
     fn emit_completed<'a>(&'a self, item: ExtensionTurnItem) -> TurnItemEmissionFuture<'a>;
 }
 
+/// Future returned when an extension tool asks the user to approve a command.
+pub type CommandApprovalFuture<'a> = Pin<Box<dyn Future<Output = bool> + Send + 'a>>;
+
+/// A command an extension tool wants to run on the host outside the sandbox.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommandApprovalRequest {
+    pub command: Vec<String>,
+    pub cwd: AbsolutePathBuf,
+    /// Why the tool runs the command, shown to the user with it.
+    pub reason: String,
+}
+
+/// Host-provided capability for extension tools to ask the user before running
+/// a command outside the sandbox.
+///
+/// Approval covers one run only; the host never offers to remember it, so tools
+/// must ask again on every call.
+pub trait CommandApprover: Send + Sync {
+    /// Resolves to true when the user approved the command.
+    fn request_approval<'a>(&'a self, request: CommandApprovalRequest)
+    -> CommandApprovalFuture<'a>;
+}
+
+/// Command approver used when a caller cannot ask the user; it denies everything.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DenyCommandApprover;
+
+impl CommandApprover for DenyCommandApprover {
+    fn request_approval<'a>(
+        &'a self,
+        _request: CommandApprovalRequest,
+    ) -> CommandApprovalFuture<'a> {
+        Box::pin(std::future::ready(false))
+    }
+}
+
 /// Host-owned turn environment summary visible to extension tools.
 #[derive(Clone)]
 pub struct ToolEnvironment {
@@ -72,6 +111,61 @@ pub struct ToolEnvironment {
     pub file_system_sandbox_context: FileSystemSandboxContext,
 }
 
+impl ToolEnvironment {
+    /// Resolves a model-supplied path against the environment's working directory.
+    pub fn resolve_path(&self, path: &str) -> AbsolutePathBuf {
+        self.cwd.join(path)
+    }
+
+    /// Roots of the workspace the sandbox context grants, or the working
+    /// directory when it names none.
+    pub fn workspace_roots(&self) -> Vec<AbsolutePathBuf> {
+        let roots = self
+            .file_system_sandbox_context
+            .workspace_roots
+            .iter()
+            .filter_map(|root| root.to_abs_path().ok())
+            .collect::<Vec<_>>();
+        if roots.is_empty() {
+            vec![self.cwd.clone()]
+        } else {
+            roots
+        }
+    }
+
+    /// Reads a file through the environment's file system, subject to the sandbox
+    /// read policy, as the built-in tools do.
+    pub async fn read_file(&self, path: &AbsolutePathBuf) -> FileSystemResult<Vec<u8>> {
+        self.file_system
+            .read_file(
+                &PathUri::from_abs_path(path),
+                Some(&self.file_system_sandbox_context),
+            )
+            .await
+    }
+
+    /// Looks up a file's metadata through the environment's file system, subject
+    /// to the sandbox read policy.
+    pub async fn metadata(&self, path: &AbsolutePathBuf) -> FileSystemResult<FileMetadata> {
+        self.file_system
+            .get_metadata(
+                &PathUri::from_abs_path(path),
+                Some(&self.file_system_sandbox_context),
+            )
+            .await
+    }
+
+    /// Like [`Self::read_file`], decoding the contents as UTF-8.
+    pub async fn read_file_text(&self, path: &AbsolutePathBuf) -> FileSystemResult<String> {
+        self.file_system
+            .read_file_text(
+                &PathUri::from_abs_path(path),
+                Some(&self.file_system_sandbox_context),
+            )
+            .await
+    }
+}
+
 /// Turn-item emitter used when a caller does not expose visible item emission.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct NoopTurnItemEmitter;
@@ -96,6 +190,7 @@ pub struct ToolCall {
     pub truncation_policy: TruncationPolicy,
     pub conversation_history: ConversationHistory,
     pub turn_item_emitter: Arc<dyn TurnItemEmitter>,
+    pub command_approver: Arc<dyn CommandApprover>,
     pub environments: Vec<ToolEnvironment>,
     pub payload: ToolPayload,
 }
@@ -114,6 +209,7 @@ impl std::fmt::Debug for ToolCall {
             .field("truncation_policy", &self.truncation_policy)
             .field("conversation_history", &self.conversation_history)
             .field("turn_item_emitter", &"<host turn item emitter>")
+            .field("command_approver", &"<host command approver>")
             .field("environment_count", &self.environments.len())
             .field("payload", &self.payload)
             .finish()
@@ -37,6 +37,7 @@ use crate::chatwidget::ChatWidget;
 use crate::chatwidget::ExternalEditorState;
 use crate::chatwidget::ReplayKind;
 use crate::chatwidget::ThreadInputState;
+use crate::composer_draft::ComposerDraftStore;
 use crate::cwd_prompt::CwdPromptAction;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::split_command_string;
@@ -190,6 +191,7 @@ use std::sync::atomic::Ordering;
 use std::thread;
 use std::time::Duration;
 use std::time::Instant;
+use std::time::SystemTime;
 use tokio::select;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc;
@@ -206,9 +208,11 @@ mod app_server_event_targets;
 mod app_server_events;
 pub(crate) mod app_server_requests;
 mod background_requests;
+mod composer_draft;
 mod config_persistence;
 mod event_dispatch;
 mod history_ui;
+mod idle_pause;
 mod input;
 mod loaded_threads;
 mod pending_interactive_replay;
@@ -524,6 +528,10 @@ pub(crate) struct App {
     pub(crate) file_search: FileSearchManager,
 
     pub(crate) transcript_cells: Vec<Arc<dyn HistoryCell>>,
+    /// When each of `transcript_cells` was committed, for the transcript time gutter.
+    pub(crate) transcript_cell_times: Vec<SystemTime>,
+    /// Time recorded for newly committed cells while a thread's turns are replayed.
+    history_clock: Option<SystemTime>,
 
     // Pager overlay state (Transcript or Static like Diff)
     pub(crate) overlay: Option<Overlay>,
@@ -591,6 +599,10 @@ pub(crate) struct App {
     // Serialize hook enablement writes per hook so stale completions cannot
     // persist an older toggle after a newer one.
     pending_hook_enabled_writes: HashMap<String, Option<bool>>,
+    /// Autosave target for the unsent composer draft; `None` until the run loop starts it.
+    composer_draft: Option<ComposerDraftStore>,
+    /// When the last key or paste arrived, for pausing background work while the user is away.
+    last_input_at: Instant,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -774,6 +786,7 @@ impl App {
         cloud_config_bundle: CloudConfigBundleLoader,
         initial_prompt: Option<String>,
         initial_images: Vec<PathBuf>,
+        initial_goal: Option<String>,
         session_selection: SessionSelection,
         feedback: codex_feedback::CodexFeedback,
         is_first_run: bool,
@@ -1011,6 +1024,9 @@ impl App {
             }
         };
         chat_widget.remote_connection = remote_connection;
+        if let Some(objective) = initial_goal {
+            chat_widget.queue_initial_goal(objective);
+        }
         let thread_and_widget_ms = thread_and_widget_started_at.elapsed().as_millis();
         chat_widget
             .maybe_prompt_windows_sandbox_enable(should_prompt_windows_sandbox_nux_at_startup);
@@ -1045,6 +1061,8 @@ See the Codex keymap documentation for supported actions and examples."
             enhanced_keys_supported,
             keymap: runtime_keymap,
             transcript_cells: Vec::new(),
+            transcript_cell_times: Vec::new(),
+            history_clock: None,
             overlay: None,
             deferred_history_lines: Vec::new(),
             has_emitted_history_lines: false,
@@ -1078,6 +1096,8 @@ See the Codex keymap documentation for supported actions and examples."
             rate_limit_hard_stop_generation: 0,
             pending_plugin_enabled_writes: HashMap::new(),
             pending_hook_enabled_writes: HashMap::new(),
+            composer_draft: None,
+            last_input_at: Instant::now(),
         };
         if let Some(entry) = startup_hooks_browser {
             app.chat_widget.open_hooks_browser(entry);
@@ -1096,6 +1116,7 @@ See the Codex keymap documentation for supported actions and examples."
             }
         }
         let initial_session_ms = initial_session_started_at.elapsed().as_millis();
+        app.start_composer_draft_autosave();
 
         // On startup, if a managed filesystem sandbox is active, warn about
         // world-writable dirs on Windows.
@@ -1131,6 +1152,11 @@ See the Codex keymap documentation for supported actions and examples."
         let tui_events = tui.event_stream();
         tokio::pin!(tui_events);
 
+        crate::render::background_highlight::enable_background_highlighting(tui.frame_requester());
+        crate::render::grammar_watcher::maybe_watch_custom_grammars(
+            app.config.codex_home.as_path(),
+            tui.frame_requester(),
+        );
         tui.frame_requester().schedule_frame();
         tracing::info!(
             duration_ms = %(startup_elapsed_before_app + startup_started_at.elapsed()).as_millis(),
@@ -1220,6 +1246,10 @@ See the Codex keymap documentation for supported actions and examples."
                             app.handle_exit_mode(&mut app_server, ExitMode::ShutdownFirst).await
                         }
                     }
+                    () = crate::idle_pause::wait_for_idle_pause(app.background_work_pause_deadline()) => {
+                        app.pause_background_work();
+                        AppRunControl::Continue
+                    }
                     app_server_event = app_server.next_event(), if listen_for_app_server_events => {
                         match app_server_event {
                             Some(event) => app.handle_app_server_event(&app_server, event).await,
@@ -1243,6 +1273,7 @@ See the Codex keymap documentation for supported actions and examples."
                 }
             }
         };
+        app.save_composer_draft();
         if let Err(err) = app_server.shutdown().await {
             tracing::warn!(error = %err, "failed to shut down embedded app server");
         }
@@ -1285,6 +1316,9 @@ See the Codex keymap documentation for supported actions and examples."
         app_server: &mut AppServerSession,
         event: TuiEvent,
     ) -> Result<AppRunControl> {
+        if matches!(event, TuiEvent::Key(_) | TuiEvent::Paste(_)) {
+            self.note_user_input(tui);
+        }
         if matches!(event, TuiEvent::Draw | TuiEvent::Resize) {
             self.handle_draw_pre_render(tui)?;
         }
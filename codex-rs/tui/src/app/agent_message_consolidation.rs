@@ -36,10 +36,10 @@ impl App {
         // source-backed cell during consolidation.
         if let Some(cell) = deferred_history_cell {
             let cell: Arc<dyn HistoryCell> = cell.into();
+            let time = self.push_transcript_cell(cell.clone());
             if let Some(Overlay::Transcript(t)) = &mut self.overlay {
-                t.insert_cell(cell.clone());
+                t.insert_cell(cell, time);
             }
-            self.transcript_cells.push(cell);
         }
 
         // Walk backward to find the contiguous run of streaming AgentMessageCells that
@@ -61,8 +61,7 @@ impl App {
                     inline_visualization_context,
                 ),
             );
-            self.transcript_cells
-                .splice(start..end, std::iter::once(consolidated.clone()));
+            self.splice_transcript_cells(start..end, consolidated.clone());
 
             if let Some(Overlay::Transcript(t)) = &mut self.overlay {
                 t.consolidate_cells(start..end, consolidated.clone());
@@ -717,8 +717,14 @@ impl App {
         };
 
         self.transcript_cells.remove(index);
+        if index < self.transcript_cell_times.len() {
+            self.transcript_cell_times.remove(index);
+        }
         if let Some(Overlay::Transcript(overlay)) = &mut self.overlay {
-            overlay.replace_cells(self.transcript_cells.clone());
+            overlay.replace_cells(
+                self.transcript_cells.clone(),
+                self.transcript_cell_times.clone(),
+            );
         }
     }
 }
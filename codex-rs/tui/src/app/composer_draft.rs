@@ -0,0 +1,46 @@
+use super::App;
+use crate::app_event::AppEvent;
+use crate::composer_draft::COMPOSER_DRAFT_AUTOSAVE_INTERVAL;
+use crate::composer_draft::ComposerDraftStore;
+use crate::idle_pause::background_work_paused;
+use tokio::time::MissedTickBehavior;
+
+impl App {
+    /// Restores the draft an interrupted run left behind and starts saving the current one.
+    pub(super) fn start_composer_draft_autosave(&mut self) {
+        let mut store =
+            ComposerDraftStore::new(self.config.codex_home.as_path(), self.config.cwd.as_path());
+        if let Some(draft) = store.recover() {
+            self.chat_widget.restore_recovered_composer_draft(draft);
+        }
+        self.composer_draft = Some(store);
+
+        let app_event_tx = self.app_event_tx.app_event_tx.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(COMPOSER_DRAFT_AUTOSAVE_INTERVAL);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes immediately; nothing has been typed yet.
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                // Nothing is typed while the user is away.
+                if background_work_paused() {
+                    continue;
+                }
+                // Sent on the raw channel so the ticks stay out of the session log.
+                if app_event_tx.send(AppEvent::AutosaveComposerDraft).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    pub(super) fn save_composer_draft(&mut self) {
+        let Some(store) = self.composer_draft.as_mut() else {
+            return;
+        };
+        if let Err(err) = store.save(self.chat_widget.composer_draft()) {
+            tracing::warn!("failed to save composer draft: {err}");
+        }
+    }
+}
@@ -6,6 +6,8 @@
 
 use super::*;
 use codex_config::ConfigLayerSource;
+use codex_exec_server::LOCAL_FS;
+use codex_git_utils::resolve_root_git_project_for_trust;
 #[cfg(target_os = "windows")]
 use codex_utils_approval_presets::ApprovalPreset;
 
@@ -241,11 +243,28 @@ impl App {
             .rebuild_config_for_cwd(self.chat_widget.config_ref().cwd.to_path_buf())
             .await?;
         self.apply_runtime_policy_overrides(&mut config);
+        let reload_syntax_theme = config.tui_theme != self.config.tui_theme
+            || config.tui_syntax_colors != self.config.tui_syntax_colors;
         self.config = config;
         self.chat_widget.sync_plugin_mentions_config(&self.config);
+        if reload_syntax_theme {
+            self.reload_syntax_theme_from_config();
+        }
         Ok(())
     }
 
+    /// Re-applies `tui.theme` and `[tui.syntax_colors]` after the config changed on disk, so
+    /// palette edits take effect without restarting.
+    fn reload_syntax_theme_from_config(&mut self) {
+        for warning in crate::render::highlight::set_syntax_colors(&self.config.tui_syntax_colors) {
+            self.chat_widget.add_error_message(warning);
+        }
+        self.restore_runtime_theme_from_config();
+        self.chat_widget
+            .set_tui_theme(self.config.tui_theme.clone());
+        self.refresh_status_line();
+    }
+
     pub(super) async fn refresh_in_memory_config_from_disk_best_effort(&mut self, action: &str) {
         if let Err(err) = self.refresh_in_memory_config_from_disk().await {
             tracing::warn!(
@@ -731,6 +750,39 @@ impl App {
         }
     }
 
+    /// Saves the `/language` choice under the current project's `[projects]` entry.
+    pub(super) async fn persist_response_language(
+        &mut self,
+        app_server: &mut AppServerSession,
+        language: Option<String>,
+    ) {
+        self.config.response_language = language.clone();
+        let project: PathBuf =
+            resolve_root_git_project_for_trust(LOCAL_FS.as_ref(), &self.config.cwd)
+                .await
+                .map(Into::into)
+                .unwrap_or_else(|| self.config.cwd.to_path_buf());
+        let edits = vec![crate::config_update::build_project_response_language_edit(
+            &project,
+            language.as_deref(),
+        )];
+        match crate::config_update::write_config_batch(app_server.request_handle(), edits).await {
+            Ok(_) => {
+                let message = if let Some(language) = language {
+                    format!("Replies will be in {language} in this project")
+                } else {
+                    "Response language cleared for this project".to_string()
+                };
+                self.chat_widget.add_info_message(message, /*hint*/ None);
+            }
+            Err(err) => {
+                tracing::error!(error = %err, "failed to persist response language");
+                self.chat_widget
+                    .add_error_message(format!("Failed to save response language: {err}"));
+            }
+        }
+    }
+
     pub(super) async fn reset_memories_with_app_server(
         &mut self,
         app_server: &mut AppServerSession,
@@ -311,6 +311,9 @@ impl App {
             AppEvent::InsertHistoryCell(cell) => {
                 self.insert_history_cell(tui, cell);
             }
+            AppEvent::SetHistoryClock(time) => {
+                self.history_clock = time;
+            }
             AppEvent::EndInitialHistoryReplayBuffer => {
                 self.finish_initial_history_replay_buffer(tui);
             }
@@ -341,8 +344,7 @@ impl App {
                     Arc::new(history_cell::new_proposed_plan(source, &self.config.cwd));
 
                 if start < end {
-                    self.transcript_cells
-                        .splice(start..end, std::iter::once(consolidated.clone()));
+                    self.splice_transcript_cells(start..end, consolidated.clone());
 
                     if let Some(Overlay::Transcript(t)) = &mut self.overlay {
                         t.consolidate_cells(start..end, consolidated.clone());
@@ -351,9 +353,9 @@ impl App {
 
                     self.finish_required_stream_reflow(tui)?;
                 } else {
-                    self.transcript_cells.push(consolidated.clone());
+                    let time = self.push_transcript_cell(consolidated.clone());
                     if let Some(Overlay::Transcript(t)) = &mut self.overlay {
-                        t.insert_cell(consolidated.clone());
+                        t.insert_cell(consolidated.clone(), time);
                         tui.frame_requester().schedule_frame();
                     }
                     self.insert_history_cell_lines(
@@ -390,6 +392,9 @@ impl App {
             AppEvent::CommitTick => {
                 self.chat_widget.on_commit_tick();
             }
+            AppEvent::AutosaveComposerDraft => {
+                self.save_composer_draft();
+            }
             AppEvent::Exit(mode) => {
                 if mode == ExitMode::ShutdownFirst {
                     self.show_shutdown_feedback(tui)?;
@@ -501,6 +506,13 @@ impl App {
             AppEvent::DiffResult(text) => {
                 // Clear the in-progress state in the bottom pane
                 self.chat_widget.on_diff_complete();
+                if crate::tmux::split_views_enabled() && !text.trim().is_empty() {
+                    let cwd = self.config.cwd.as_path();
+                    match crate::tmux::open_in_split("codex diff", &text, cwd) {
+                        Ok(()) => return Ok(AppRunControl::Continue),
+                        Err(err) => tracing::warn!("failed to open /diff in a tmux split: {err}"),
+                    }
+                }
                 // Enter alternate screen using TUI helper and build pager lines
                 let _ = tui.enter_alt_screen();
                 let pager_lines: Vec<ratatui::text::Line<'static>> = if text.trim().is_empty() {
@@ -515,6 +527,16 @@ impl App {
                 ));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::ShowLogs(filter) => {
+                let _ = tui.enter_alt_screen();
+                let records = crate::log_tail::log_tail().snapshot();
+                self.overlay = Some(Overlay::new_static_with_lines(
+                    crate::log_tail::log_lines(&records, &filter),
+                    "L O G S".to_string(),
+                    self.keymap.pager.clone(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::OpenAppLink {
                 app_id,
                 title,
@@ -1776,6 +1798,9 @@ impl App {
                     }
                 }
             }
+            AppEvent::PersistResponseLanguage { language } => {
+                self.persist_response_language(app_server, language).await;
+            }
             AppEvent::UpdateAskForApprovalPolicy(policy) => {
                 let mut config = self.config.clone();
                 if !self.try_set_approval_policy_on_config(
@@ -2126,6 +2151,21 @@ impl App {
             AppEvent::OpenReviewCustomPrompt => {
                 self.chat_widget.show_review_custom_prompt();
             }
+            AppEvent::ApplyReviewFindingFix(finding) => {
+                self.chat_widget.apply_review_finding_fix(finding);
+            }
+            AppEvent::TodoScanResult(result) => {
+                self.chat_widget.open_todos_popup(result);
+            }
+            AppEvent::EnvSnapshotResult(snapshot) => {
+                self.chat_widget.add_env_snapshot(snapshot);
+            }
+            AppEvent::SystemPromptContextResult(result) => {
+                self.chat_widget.add_system_prompt_context(result);
+            }
+            AppEvent::FixTodoComment(todo) => {
+                self.chat_widget.fix_todo_comment(todo);
+            }
             AppEvent::SubmitUserMessageWithMode {
                 text,
                 collaboration_mode,
@@ -10,11 +10,11 @@ const DESKTOP_THREAD_OPENED_MESSAGE: &str = "Opened this session in the Desktop
 impl App {
     pub(super) fn insert_history_cell(&mut self, tui: &mut tui::Tui, cell: Box<dyn HistoryCell>) {
         let cell: Arc<dyn HistoryCell> = cell.into();
+        let time = self.push_transcript_cell(cell.clone());
         if let Some(Overlay::Transcript(t)) = &mut self.overlay {
-            t.insert_cell(cell.clone());
+            t.insert_cell(cell.clone(), time);
             tui.frame_requester().schedule_frame();
         }
-        self.transcript_cells.push(cell.clone());
         if self.initial_history_replay_buffer.as_ref().is_some() {
             self.insert_history_cell_lines_with_initial_replay_buffer(
                 tui,
@@ -35,6 +35,29 @@ impl App {
         self.chat_widget.request_pending_usage_output_insertion();
     }
 
+    /// Appends a committed cell to `transcript_cells` and returns the time recorded for it.
+    pub(super) fn push_transcript_cell(&mut self, cell: Arc<dyn HistoryCell>) -> SystemTime {
+        let time = self.history_clock.unwrap_or_else(SystemTime::now);
+        self.transcript_cells.push(cell);
+        self.transcript_cell_times.push(time);
+        time
+    }
+
+    /// Replaces `range` of `transcript_cells` with `cell`, which keeps the time of the first cell
+    /// it replaces.
+    pub(super) fn splice_transcript_cells(
+        &mut self,
+        range: std::ops::Range<usize>,
+        cell: Arc<dyn HistoryCell>,
+    ) {
+        self.transcript_cells
+            .splice(range.clone(), std::iter::once(cell));
+        let times_end = range.end.min(self.transcript_cell_times.len());
+        if range.start < times_end {
+            self.transcript_cell_times.drain(range.start + 1..times_end);
+        }
+    }
+
     pub(super) fn pending_usage_output_insertion_blocked(&self) -> bool {
         self.chat_widget.usage_history_insertion_blocked()
             || self
@@ -165,6 +188,7 @@ impl App {
     pub(super) fn reset_transcript_state_after_clear(&mut self) {
         self.overlay = None;
         self.transcript_cells.clear();
+        self.transcript_cell_times.clear();
         self.deferred_history_lines.clear();
         self.has_emitted_history_lines = false;
         self.transcript_reflow.clear();
@@ -0,0 +1,29 @@
+use std::time::Instant;
+
+use super::App;
+use crate::idle_pause::idle_pause_deadline;
+use crate::idle_pause::set_background_work_paused;
+use crate::tui;
+
+impl App {
+    /// When background work should pause if the user stays away; `None` when it never will.
+    pub(super) fn background_work_pause_deadline(&self) -> Option<Instant> {
+        idle_pause_deadline(self.last_input_at, self.config.tui_idle_pause_seconds)
+    }
+
+    pub(super) fn pause_background_work(&mut self) {
+        if !set_background_work_paused(/*paused*/ true) {
+            tracing::debug!("pausing background work after idle period");
+        }
+    }
+
+    /// Records user input and resumes background work if it was paused.
+    pub(super) fn note_user_input(&mut self, tui: &mut tui::Tui) {
+        self.last_input_at = Instant::now();
+        if set_background_work_paused(/*paused*/ false) {
+            tracing::debug!("resuming background work after input");
+            // The draw reschedules animations and polling that were skipped while paused.
+            tui.frame_requester().schedule_frame();
+        }
+    }
+}
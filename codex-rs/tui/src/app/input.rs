@@ -79,8 +79,13 @@ impl App {
         } else {
             self.chat_widget.set_raw_output_mode(enabled);
         }
+        self.redraw_transcript(tui);
+    }
+
+    /// Re-renders scrollback after a change to how existing history cells display.
+    fn redraw_transcript(&mut self, tui: &mut tui::Tui) {
         if let Err(err) = self.reflow_transcript_now(tui) {
-            tracing::warn!(error = %err, "failed to reflow transcript after raw output mode toggle");
+            tracing::warn!(error = %err, "failed to reflow transcript after display change");
             self.chat_widget
                 .add_error_message(format!("Failed to redraw transcript: {err}"));
         }
@@ -162,11 +167,32 @@ impl App {
             return;
         }
 
+        if app_keymap_shortcuts_available && self.keymap.app.toggle_reasoning.is_pressed(key_event)
+        {
+            if self.chat_widget.toggle_reasoning_panel() {
+                self.redraw_transcript(tui);
+            }
+            return;
+        }
+
+        if app_keymap_shortcuts_available
+            && self
+                .keymap
+                .app
+                .cycle_transcript_density
+                .is_pressed(key_event)
+        {
+            crate::transcript_density::cycle_transcript_density();
+            self.redraw_transcript(tui);
+            return;
+        }
+
         if app_keymap_shortcuts_available && self.keymap.app.open_transcript.is_pressed(key_event) {
             // Enter alternate screen and set viewport to full size.
             let _ = tui.enter_alt_screen();
             self.overlay = Some(Overlay::new_transcript(
                 self.transcript_cells.clone(),
+                self.transcript_cell_times.clone(),
                 self.keymap.pager.clone(),
             ));
             tui.frame_requester().schedule_frame();
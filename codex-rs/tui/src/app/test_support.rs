@@ -33,6 +33,8 @@ pub(super) async fn make_test_app() -> App {
         runtime_permission_profile_override: None,
         file_search,
         transcript_cells: Vec::new(),
+        transcript_cell_times: Vec::new(),
+        history_clock: None,
         overlay: None,
         deferred_history_lines: Vec::new(),
         has_emitted_history_lines: false,
@@ -68,6 +70,8 @@ pub(super) async fn make_test_app() -> App {
         rate_limit_hard_stop_generation: 0,
         pending_plugin_enabled_writes: HashMap::new(),
         pending_hook_enabled_writes: HashMap::new(),
+        composer_draft: None,
+        last_input_at: Instant::now(),
     }
 }
 
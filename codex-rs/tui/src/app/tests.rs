@@ -4668,6 +4668,7 @@ async fn make_test_app() -> App {
         rate_limit_hard_stop_generation: 0,
         pending_plugin_enabled_writes: HashMap::new(),
         pending_hook_enabled_writes: HashMap::new(),
+        composer_draft: None,
     }
 }
 
@@ -4735,6 +4736,7 @@ async fn make_test_app_with_channels() -> (
             rate_limit_hard_stop_generation: 0,
             pending_plugin_enabled_writes: HashMap::new(),
             pending_hook_enabled_writes: HashMap::new(),
+            composer_draft: None,
         },
         rx,
         op_rx,
@@ -7259,6 +7261,7 @@ async fn clear_only_ui_reset_preserves_chat_session_state() {
     }) as Arc<dyn HistoryCell>];
     app.overlay = Some(Overlay::new_transcript(
         app.transcript_cells.clone(),
+        Vec::new(),
         crate::keymap::RuntimeKeymap::defaults().pager,
     ));
     app.deferred_history_lines = vec![Line::from("stale buffered line").into()];
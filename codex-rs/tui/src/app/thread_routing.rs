@@ -710,6 +710,7 @@ impl App {
                             collaboration_mode.clone(),
                             *personality,
                             final_output_json_schema.clone(),
+                            self.chat_widget.response_language_context(),
                         )
                         .await?;
                     if self.active_thread_id == Some(thread_id)
@@ -84,7 +84,7 @@ impl App {
     ///
     /// If backtrack preview is active, Esc / Left steps selection, Right steps forward, Enter
     /// confirms. Otherwise, Esc begins preview mode and all other events are forwarded to the
-    /// overlay.
+    /// overlay, including Esc while a file opened from the transcript is showing.
     pub(crate) async fn handle_backtrack_overlay_event(
         &mut self,
         tui: &mut tui::Tui,
@@ -134,6 +134,10 @@ impl App {
             kind: KeyEventKind::Press | KeyEventKind::Repeat,
             ..
         }) = event
+            && !matches!(
+                &self.overlay,
+                Some(Overlay::Transcript(transcript)) if transcript.is_viewing_file()
+            )
         {
             // First Esc in transcript overlay: begin backtrack preview at latest user message.
             self.begin_overlay_backtrack_preview(tui);
@@ -196,6 +200,7 @@ impl App {
         let _ = tui.enter_alt_screen();
         self.overlay = Some(Overlay::new_transcript(
             self.transcript_cells.clone(),
+            self.transcript_cell_times.clone(),
             self.keymap.pager.clone(),
         ));
         tui.frame_requester().schedule_frame();
@@ -370,6 +375,12 @@ impl App {
 
         if let Some(overlay) = &mut self.overlay {
             overlay.handle_event(tui, event)?;
+            // The chat widget keeps the clipboard owner alive after the overlay closes.
+            if let Overlay::Transcript(t) = overlay
+                && let Some(code) = t.take_code_block_to_copy()
+            {
+                self.chat_widget.copy_code_block(&code);
+            }
             if overlay.is_done() {
                 self.close_transcript_overlay(tui);
                 tui.frame_requester().schedule_frame();
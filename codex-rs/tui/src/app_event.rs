@@ -33,11 +33,14 @@ use codex_app_server_protocol::SkillsListResponse;
 use codex_app_server_protocol::ThreadGoalStatus;
 use codex_connectors::AppInfo;
 use codex_file_search::FileMatch;
+use codex_git_utils::TodoComment;
+use codex_git_utils::TodoScan;
 use codex_message_history::HistoryBatchCursor;
 use codex_protocol::ThreadId;
 use codex_protocol::openai_models::ModelPreset;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_approval_presets::ApprovalPreset;
+use codex_utils_env_snapshot::EnvSnapshot;
 
 use crate::app_command::AppCommand;
 use crate::app_server_session::AppServerStartedThread;
@@ -46,6 +49,7 @@ use crate::bottom_pane::StatusLineItem;
 use crate::bottom_pane::TerminalTitleItem;
 use crate::chatwidget::UserMessage;
 use crate::goal_files::GoalDraft;
+use crate::review_findings::ReviewFindingEntry;
 use codex_app_server_protocol::AskForApproval;
 use codex_config::types::ApprovalsReviewer;
 use codex_features::Feature;
@@ -438,6 +442,21 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Result of the `/todos` scan; `Ok(None)` when the workspace is not a git repository.
+    TodoScanResult(Result<Option<TodoScan>, String>),
+
+    /// Result of capturing the local environment for `/env`.
+    EnvSnapshotResult(EnvSnapshot),
+
+    /// Effective system prompt read for `/context`, or why it is unavailable.
+    SystemPromptContextResult(Result<String, String>),
+
+    /// Ask the agent to resolve a TODO comment picked from `/todos`.
+    FixTodoComment(TodoComment),
+
+    /// Open the `/logs` pager over the in-memory log tail.
+    ShowLogs(crate::log_tail::LogFilter),
+
     /// Open the app link view in the bottom pane.
     OpenAppLink {
         app_id: String,
@@ -721,6 +740,10 @@ pub(crate) enum AppEvent {
 
     InsertHistoryCell(Box<dyn HistoryCell>),
 
+    /// Time to record for history cells inserted after this event, used while replaying a
+    /// resumed thread's turns; `None` returns to the current time.
+    SetHistoryClock(Option<std::time::SystemTime>),
+
     /// Finish buffering initial resume replay after all replay events have been queued.
     EndInitialHistoryReplayBuffer,
 
@@ -755,6 +778,9 @@ pub(crate) enum AppEvent {
     StopCommitAnimation,
     CommitTick,
 
+    /// Periodic tick asking the app to save the unsent composer draft.
+    AutosaveComposerDraft,
+
     /// Update the current reasoning effort in the running app and widget.
     UpdateReasoningEffort(Option<ReasoningEffort>),
 
@@ -785,6 +811,11 @@ pub(crate) enum AppEvent {
         service_tier: Option<String>,
     },
 
+    /// Persist the response language picked with `/language` for the current project.
+    PersistResponseLanguage {
+        language: Option<String>,
+    },
+
     /// Open the reasoning selection popup after picking a model.
     OpenReasoningPopup {
         model: ModelPreset,
@@ -1008,6 +1039,9 @@ pub(crate) enum AppEvent {
     /// Open the custom prompt option from the review popup.
     OpenReviewCustomPrompt,
 
+    /// Ask the agent to apply the fix suggested by a review finding.
+    ApplyReviewFindingFix(ReviewFindingEntry),
+
     /// Submit a user message with an explicit collaboration mask.
     SubmitUserMessageWithMode {
         text: String,
@@ -13,6 +13,8 @@
 
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
 
 use crate::app::app_server_requests::ResolvedAppServerRequest;
 #[cfg(test)]
@@ -28,7 +30,11 @@ use crate::bottom_pane::popup_consts::accept_cancel_hint_line;
 use crate::diff_model::FileChange;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell;
+use crate::history_cell::ApprovalDecisionActor;
 use crate::history_cell::ReviewDecision;
+use crate::i18n::Msg;
+use crate::i18n::tr;
+use crate::i18n::tr_args;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::key_hint::KeyBindingListExt;
@@ -68,6 +74,11 @@ use ratatui::text::Span;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Wrap;
 
+mod command_explanation;
+mod timeout;
+
+pub(crate) use timeout::ApprovalTimeout;
+
 /// Request coming from the agent that needs user approval.
 #[derive(Clone, Debug)]
 pub(crate) enum ApprovalRequest {
@@ -165,6 +176,17 @@ impl ApprovalRequest {
             _ => false,
         }
     }
+
+    /// Whether the explain key applies: only shell commands that are shown to the user.
+    fn can_explain(&self) -> bool {
+        matches!(
+            self,
+            ApprovalRequest::Exec(ExecApprovalRequest {
+                network_approval_context: None,
+                ..
+            })
+        )
+    }
 }
 
 /// Modal overlay asking the user to approve or deny one or more requests.
@@ -175,10 +197,17 @@ pub(crate) struct ApprovalOverlay {
     list: ListSelectionView,
     options: Vec<ApprovalOption>,
     current_complete: bool,
+    /// Whether the current exec request shows its explanation under the command.
+    show_explanation: bool,
     done: bool,
     features: Features,
     approval_keymap: ApprovalKeymap,
     list_keymap: ListKeymap,
+    timeout: Option<ApprovalTimeout>,
+    /// When the current request was shown; its timeout counts from here.
+    current_shown_at: Instant,
+    /// Set once the user interacts with the current request, which stops its countdown.
+    timeout_snoozed: bool,
 }
 
 impl ApprovalOverlay {
@@ -188,6 +217,7 @@ impl ApprovalOverlay {
         features: Features,
         approval_keymap: ApprovalKeymap,
         list_keymap: ListKeymap,
+        timeout: Option<ApprovalTimeout>,
     ) -> Self {
         let mut view = Self {
             current_request: None,
@@ -196,10 +226,14 @@ impl ApprovalOverlay {
             list: ListSelectionView::new(Default::default(), app_event_tx, list_keymap.clone()),
             options: Vec::new(),
             current_complete: false,
+            show_explanation: false,
             done: false,
             features,
             approval_keymap,
             list_keymap,
+            timeout,
+            current_shown_at: Instant::now(),
+            timeout_snoozed: false,
         };
         view.set_current(request);
         view
@@ -228,15 +262,39 @@ impl ApprovalOverlay {
 
     fn set_current(&mut self, request: ApprovalRequest) {
         self.current_complete = false;
-        let header = build_header(&request);
-        let (options, params) = Self::build_options(
-            &request,
+        self.show_explanation = false;
+        self.current_shown_at = Instant::now();
+        self.timeout_snoozed = false;
+        self.current_request = Some(request);
+        self.rebuild_list(/*initial_selected_idx*/ None);
+    }
+
+    /// Rebuilds the option list for the current request, keeping `initial_selected_idx`
+    /// highlighted when the header changes underneath it.
+    fn rebuild_list(&mut self, initial_selected_idx: Option<usize>) {
+        let Some(request) = self.current_request.as_ref() else {
+            return;
+        };
+        let mut header = build_header(request);
+        if self.show_explanation
+            && let ApprovalRequest::Exec(request) = request
+        {
+            header = Box::new(ColumnRenderable::with([
+                header,
+                Box::new(
+                    Paragraph::new(command_explanation::explanation_lines(request))
+                        .wrap(Wrap { trim: false }),
+                ) as Box<dyn Renderable>,
+            ]));
+        }
+        let (options, mut params) = Self::build_options(
+            request,
             header,
             &self.features,
             &self.approval_keymap,
             &self.list_keymap,
         );
-        self.current_request = Some(request);
+        params.initial_selected_idx = initial_selected_idx;
         self.options = options;
         self.list =
             ListSelectionView::new(params, self.app_event_tx.clone(), self.list_keymap.clone());
@@ -258,26 +316,28 @@ impl ApprovalOverlay {
                     approval_keymap,
                 ),
                 request.network_approval_context.as_ref().map_or_else(
-                    || "Would you like to run the following command?".to_string(),
+                    || tr(Msg::ApprovalRunCommandTitle),
                     |network_approval_context| {
-                        format!(
-                            "Do you want to approve network access to \"{}\"?",
-                            network_approval_context.host
+                        tr_args(
+                            Msg::ApprovalNetworkAccessTitle,
+                            &[("host", network_approval_context.host.as_str())],
                         )
                     },
                 ),
             ),
             ApprovalRequest::Permissions(_) => (
                 permissions_options(approval_keymap),
-                "Would you like to grant these permissions?".to_string(),
-            ),
-            ApprovalRequest::ApplyPatch(_) => (
-                patch_options(approval_keymap),
-                "Would you like to make the following edits?".to_string(),
+                tr(Msg::ApprovalPermissionsTitle),
             ),
+            ApprovalRequest::ApplyPatch(_) => {
+                (patch_options(approval_keymap), tr(Msg::ApprovalEditsTitle))
+            }
             ApprovalRequest::McpElicitation(request) => (
                 elicitation_options(approval_keymap),
-                format!("{} needs your approval.", request.server_name),
+                tr_args(
+                    Msg::ApprovalElicitationTitle,
+                    &[("server", request.server_name.as_str())],
+                ),
             ),
         };
 
@@ -307,7 +367,7 @@ impl ApprovalOverlay {
         (options, params)
     }
 
-    fn apply_selection(&mut self, actual_idx: usize) {
+    fn apply_selection(&mut self, actual_idx: usize, actor: ApprovalDecisionActor) {
         if self.current_complete {
             return;
         }
@@ -317,7 +377,12 @@ impl ApprovalOverlay {
         if let Some(request) = self.current_request.as_ref() {
             match (request, &option.decision) {
                 (ApprovalRequest::Exec(request), ApprovalDecision::Command(decision)) => {
-                    self.handle_exec_decision(&request.id, &request.command, decision.clone());
+                    self.handle_exec_decision(
+                        &request.id,
+                        &request.command,
+                        decision.clone(),
+                        actor,
+                    );
                 }
                 (
                     ApprovalRequest::Permissions(request),
@@ -327,6 +392,7 @@ impl ApprovalOverlay {
                         &request.call_id,
                         &request.permissions,
                         *decision,
+                        actor,
                     );
                 }
                 (ApprovalRequest::ApplyPatch(request), ApprovalDecision::FileChange(decision)) => {
@@ -355,6 +421,7 @@ impl ApprovalOverlay {
         id: &str,
         command: &[String],
         decision: CommandExecutionApprovalDecision,
+        actor: ApprovalDecisionActor,
     ) {
         let Some(request) = self.current_request.as_ref() else {
             return;
@@ -380,7 +447,7 @@ impl ApprovalOverlay {
             let cell = history_cell::new_approval_decision_cell(
                 subject,
                 command_decision_to_review_decision(&decision),
-                history_cell::ApprovalDecisionActor::User,
+                actor,
             );
             self.app_event_tx.send(AppEvent::InsertHistoryCell(cell));
         }
@@ -394,6 +461,7 @@ impl ApprovalOverlay {
         call_id: &str,
         permissions: &RequestPermissionProfile,
         decision: PermissionsDecision,
+        actor: ApprovalDecisionActor,
     ) {
         let Some(request) = self.current_request.as_ref() else {
             return;
@@ -414,15 +482,16 @@ impl ApprovalOverlay {
             PermissionsDecision::GrantForTurnWithStrictAutoReview
         );
         if request.thread_label().is_none() {
-            let message = if granted_permissions.is_empty() {
-                "You did not grant additional permissions"
+            let outcome = if granted_permissions.is_empty() {
+                "did not grant additional permissions"
             } else if strict_auto_review {
-                "You granted additional permissions with strict auto review"
+                "granted additional permissions with strict auto review"
             } else if matches!(scope, PermissionGrantScope::Session) {
-                "You granted additional permissions for this session"
+                "granted additional permissions for this session"
             } else {
-                "You granted additional permissions"
+                "granted additional permissions"
             };
+            let message = format!("{}{outcome}", actor.subject());
             self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
                 crate::history_cell::PlainHistoryCell::new(vec![message.into()]),
             )));
@@ -474,6 +543,66 @@ impl ApprovalOverlay {
         );
     }
 
+    /// Time left before the current request times out, while its countdown is running.
+    fn timeout_remaining_at(&self, now: Instant) -> Option<Duration> {
+        let timeout = self.timeout?;
+        if self.timeout_snoozed || self.current_complete || self.current_request.is_none() {
+            return None;
+        }
+        let waited = now.saturating_duration_since(self.current_shown_at);
+        Some(timeout.after.saturating_sub(waited))
+    }
+
+    /// Index of the option applied when the current request times out.
+    fn timeout_option_idx(&self) -> Option<usize> {
+        let timeout = self.timeout?;
+        let request = self.current_request.as_ref()?;
+        timeout::timeout_option(request, &self.options, timeout.action)
+    }
+
+    fn countdown_line_at(&self, now: Instant) -> Option<Line<'static>> {
+        let remaining = self.timeout_remaining_at(now)?;
+        let idx = self.timeout_option_idx()?;
+        Some(timeout::countdown_line(remaining, &self.options[idx]))
+    }
+
+    /// Applies the configured timeout decision, records it, and moves on to the next request.
+    fn resolve_timed_out_request(&mut self) {
+        let (Some(timeout), Some(idx)) = (self.timeout, self.timeout_option_idx()) else {
+            self.timeout_snoozed = true;
+            return;
+        };
+        let Some(request) = self.current_request.as_ref() else {
+            return;
+        };
+        tracing::info!(
+            thread_id = %request.thread_id(),
+            waited_secs = timeout.after.as_secs(),
+            decision = %self.options[idx].label,
+            "approval prompt timed out"
+        );
+        if request.thread_label().is_none() {
+            let actor = ApprovalDecisionActor::Timeout.subject();
+            let message = match request {
+                ApprovalRequest::ApplyPatch(_) => {
+                    Some(format!("{actor}declined the proposed file changes"))
+                }
+                ApprovalRequest::McpElicitation(request) => Some(format!(
+                    "{actor}declined the request from {}",
+                    request.server_name
+                )),
+                // Exec and permissions decisions record their own history with the actor.
+                ApprovalRequest::Exec(_) | ApprovalRequest::Permissions(_) => None,
+            };
+            if let Some(message) = message {
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    crate::history_cell::PlainHistoryCell::new(vec![message.into()]),
+                )));
+            }
+        }
+        self.apply_selection(idx, ApprovalDecisionActor::Timeout);
+    }
+
     fn advance_queue(&mut self) {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
@@ -495,6 +624,7 @@ impl ApprovalOverlay {
                         &request.id,
                         &request.command,
                         CommandExecutionApprovalDecision::Cancel,
+                        ApprovalDecisionActor::User,
                     );
                 }
                 ApprovalRequest::Permissions(request) => {
@@ -502,6 +632,7 @@ impl ApprovalOverlay {
                         &request.call_id,
                         &request.permissions,
                         PermissionsDecision::Deny,
+                        ApprovalDecisionActor::User,
                     );
                 }
                 ApprovalRequest::ApplyPatch(request) => {
@@ -544,6 +675,19 @@ impl ApprovalOverlay {
             return true;
         }
 
+        if key_event.kind == KeyEventKind::Press
+            && self.approval_keymap.explain.is_pressed(*key_event)
+            && self
+                .current_request
+                .as_ref()
+                .is_some_and(ApprovalRequest::can_explain)
+        {
+            self.show_explanation = !self.show_explanation;
+            let selected = self.list.selected_actual_idx();
+            self.rebuild_list(selected);
+            return true;
+        }
+
         if self.list_keymap.cancel.is_pressed(*key_event) {
             self.cancel_current_request();
             return true;
@@ -554,7 +698,7 @@ impl ApprovalOverlay {
             .iter()
             .position(|opt| opt.shortcuts.iter().any(|s| s.is_press(*key_event)))
         {
-            self.apply_selection(idx);
+            self.apply_selection(idx, ApprovalDecisionActor::User);
             true
         } else {
             false
@@ -564,12 +708,13 @@ impl ApprovalOverlay {
 
 impl BottomPaneView for ApprovalOverlay {
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        self.timeout_snoozed = true;
         if self.try_handle_shortcut(&key_event) {
             return;
         }
         self.list.handle_key_event(key_event);
         if let Some(idx) = self.list.take_last_selected_index() {
-            self.apply_selection(idx);
+            self.apply_selection(idx, ApprovalDecisionActor::User);
         }
     }
 
@@ -597,15 +742,45 @@ impl BottomPaneView for ApprovalOverlay {
     fn terminal_title_requires_action(&self) -> bool {
         true
     }
+
+    fn pre_draw_tick(&mut self, now: Instant) -> bool {
+        if self.timeout_remaining_at(now) != Some(Duration::ZERO) {
+            return false;
+        }
+        self.resolve_timed_out_request();
+        true
+    }
+
+    fn next_frame_delay(&self) -> Option<Duration> {
+        self.timeout_remaining_at(Instant::now())
+            .map(|remaining| remaining.min(Duration::from_secs(/*secs*/ 1)))
+    }
 }
 
 impl Renderable for ApprovalOverlay {
     fn desired_height(&self, width: u16) -> u16 {
-        self.list.desired_height(width)
+        let countdown_rows = u16::from(self.countdown_line_at(Instant::now()).is_some());
+        self.list.desired_height(width) + countdown_rows
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        self.list.render(area, buf);
+        let Some(countdown) = self.countdown_line_at(Instant::now()) else {
+            self.list.render(area, buf);
+            return;
+        };
+        let list_area = Rect {
+            height: area.height.saturating_sub(1),
+            ..area
+        };
+        self.list.render(list_area, buf);
+        countdown.render(
+            Rect {
+                y: list_area.bottom(),
+                height: area.height - list_area.height,
+                ..area
+            },
+            buf,
+        );
     }
 
     fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
@@ -625,6 +800,16 @@ fn approval_footer_hint(
         "to cancel",
     )
     .spans;
+    if request.can_explain()
+        && let Some(explain) = primary_binding(&approval_keymap.explain)
+    {
+        if !spans.is_empty() {
+            spans.push(" or ".into());
+        } else {
+            spans.push("Press ".into());
+        }
+        spans.extend([explain.into(), " to explain".into()]);
+    }
     if request.thread_label().is_some()
         && let Some(open_thread) = primary_binding(&approval_keymap.open_thread)
     {
@@ -667,33 +852,41 @@ fn network_approval_command_target(command: &[String]) -> Option<&str> {
     }
 }
 
+/// A header field label such as "Reason: ", in the active language.
+fn header_label(msg: Msg) -> Span<'static> {
+    format!("{} ", tr(msg)).into()
+}
+
 fn build_header(request: &ApprovalRequest) -> Box<dyn Renderable> {
     match request {
         ApprovalRequest::Exec(request) => {
             let mut header: Vec<Line<'static>> = Vec::new();
             if let Some(thread_label) = &request.thread_label {
                 header.push(Line::from(vec![
-                    "Thread: ".into(),
+                    header_label(Msg::ApprovalHeaderThread),
                     thread_label.clone().bold(),
                 ]));
                 header.push(Line::from(""));
             }
             if let Some(environment_id) = &request.environment_id {
                 header.push(Line::from(vec![
-                    "Environment: ".into(),
+                    header_label(Msg::ApprovalHeaderEnvironment),
                     environment_id.clone().bold(),
                 ]));
                 header.push(Line::from(""));
             }
             if let Some(reason) = &request.reason {
-                header.push(Line::from(vec!["Reason: ".into(), reason.clone().italic()]));
+                header.push(Line::from(vec![
+                    header_label(Msg::ApprovalHeaderReason),
+                    reason.clone().italic(),
+                ]));
                 header.push(Line::from(""));
             }
             if let Some(additional_permissions) = &request.additional_permissions
                 && let Some(rule_line) = format_additional_permissions_rule(additional_permissions)
             {
                 header.push(Line::from(vec![
-                    "Permission rule: ".into(),
+                    header_label(Msg::ApprovalHeaderPermissionRule),
                     rule_line.cyan(),
                 ]));
                 header.push(Line::from(""));
@@ -712,25 +905,28 @@ fn build_header(request: &ApprovalRequest) -> Box<dyn Renderable> {
             let mut header: Vec<Line<'static>> = Vec::new();
             if let Some(thread_label) = &request.thread_label {
                 header.push(Line::from(vec![
-                    "Thread: ".into(),
+                    header_label(Msg::ApprovalHeaderThread),
                     thread_label.clone().bold(),
                 ]));
                 header.push(Line::from(""));
             }
             if let Some(environment_id) = &request.environment_id {
                 header.push(Line::from(vec![
-                    "Environment: ".into(),
+                    header_label(Msg::ApprovalHeaderEnvironment),
                     environment_id.clone().bold(),
                 ]));
                 header.push(Line::from(""));
             }
             if let Some(reason) = &request.reason {
-                header.push(Line::from(vec!["Reason: ".into(), reason.clone().italic()]));
+                header.push(Line::from(vec![
+                    header_label(Msg::ApprovalHeaderReason),
+                    reason.clone().italic(),
+                ]));
                 header.push(Line::from(""));
             }
             if let Some(rule_line) = format_requested_permissions_rule(&request.permissions) {
                 header.push(Line::from(vec![
-                    "Permission rule: ".into(),
+                    header_label(Msg::ApprovalHeaderPermissionRule),
                     rule_line.cyan(),
                 ]));
             }
@@ -740,7 +936,7 @@ fn build_header(request: &ApprovalRequest) -> Box<dyn Renderable> {
             let mut header: Vec<Box<dyn Renderable>> = Vec::new();
             if let Some(thread_label) = &request.thread_label {
                 header.push(Box::new(Line::from(vec![
-                    "Thread: ".into(),
+                    header_label(Msg::ApprovalHeaderThread),
                     thread_label.clone().bold(),
                 ])));
             }
@@ -752,7 +948,7 @@ fn build_header(request: &ApprovalRequest) -> Box<dyn Renderable> {
                 }
                 header.push(Box::new(
                     Paragraph::new(Line::from_iter([
-                        "Reason: ".into(),
+                        header_label(Msg::ApprovalHeaderReason),
                         reason.clone().italic(),
                     ]))
                     .wrap(Wrap { trim: false }),
@@ -764,13 +960,16 @@ fn build_header(request: &ApprovalRequest) -> Box<dyn Renderable> {
             let mut lines = Vec::new();
             if let Some(thread_label) = &request.thread_label {
                 lines.push(Line::from(vec![
-                    "Thread: ".into(),
+                    header_label(Msg::ApprovalHeaderThread),
                     thread_label.clone().bold(),
                 ]));
                 lines.push(Line::from(""));
             }
             lines.extend([
-                Line::from(vec!["Server: ".into(), request.server_name.clone().bold()]),
+                Line::from(vec![
+                    header_label(Msg::ApprovalHeaderServer),
+                    request.server_name.clone().bold(),
+                ]),
                 Line::from(""),
                 Line::from(request.message.clone()),
             ]);
@@ -835,9 +1034,9 @@ fn exec_options(
         .filter_map(|decision| match decision {
             CommandExecutionApprovalDecision::Accept => Some(ApprovalOption {
                 label: if network_approval_context.is_some() {
-                    "Yes, just this once".to_string()
+                    tr(Msg::ApprovalYesJustThisOnce)
                 } else {
-                    "Yes, proceed".to_string()
+                    tr(Msg::ApprovalYesProceed)
                 },
                 decision: ApprovalDecision::Command(CommandExecutionApprovalDecision::Accept),
                 shortcuts: keymap.approve.clone(),
@@ -851,8 +1050,9 @@ fn exec_options(
                 }
 
                 Some(ApprovalOption {
-                    label: format!(
-                        "Yes, and don't ask again for commands that start with `{rendered_prefix}`"
+                    label: tr_args(
+                        Msg::ApprovalYesCommandPrefix,
+                        &[("prefix", rendered_prefix.as_str())],
                     ),
                     decision: ApprovalDecision::Command(
                         CommandExecutionApprovalDecision::AcceptWithExecpolicyAmendment {
@@ -864,11 +1064,11 @@ fn exec_options(
             }
             CommandExecutionApprovalDecision::AcceptForSession => Some(ApprovalOption {
                 label: if network_approval_context.is_some() {
-                    "Yes, and allow this host for this conversation".to_string()
+                    tr(Msg::ApprovalYesAllowHostForConversation)
                 } else if additional_permissions.is_some() {
-                    "Yes, and allow these permissions for this session".to_string()
+                    tr(Msg::ApprovalYesAllowPermissionsForSession)
                 } else {
-                    "Yes, and don't ask again for this command in this session".to_string()
+                    tr(Msg::ApprovalYesCommandForSession)
                 },
                 decision: ApprovalDecision::Command(
                     CommandExecutionApprovalDecision::AcceptForSession,
@@ -880,13 +1080,12 @@ fn exec_options(
             } => {
                 let (label, shortcuts) = match network_policy_amendment.action {
                     NetworkPolicyRuleAction::Allow => (
-                        "Yes, and allow this host in the future".to_string(),
+                        tr(Msg::ApprovalYesAllowHostInFuture),
                         keymap.approve_for_prefix.clone(),
                     ),
-                    NetworkPolicyRuleAction::Deny => (
-                        "No, and block this host in the future".to_string(),
-                        keymap.deny.clone(),
-                    ),
+                    NetworkPolicyRuleAction::Deny => {
+                        (tr(Msg::ApprovalNoBlockHostInFuture), keymap.deny.clone())
+                    }
                 };
                 Some(ApprovalOption {
                     label,
@@ -899,12 +1098,12 @@ fn exec_options(
                 })
             }
             CommandExecutionApprovalDecision::Decline => Some(ApprovalOption {
-                label: "No, continue without running it".to_string(),
+                label: tr(Msg::ApprovalNoContinueWithoutRunning),
                 decision: ApprovalDecision::Command(CommandExecutionApprovalDecision::Decline),
                 shortcuts: keymap.deny.clone(),
             }),
             CommandExecutionApprovalDecision::Cancel => Some(ApprovalOption {
-                label: "No, and tell Codex what to do differently".to_string(),
+                label: tr(Msg::ApprovalNoTellCodex),
                 decision: ApprovalDecision::Command(CommandExecutionApprovalDecision::Cancel),
                 shortcuts: keymap.decline.clone(),
             }),
@@ -1010,17 +1209,17 @@ fn path_label(base: &str, subpath: &Option<LegacyAppPathString>) -> String {
 fn patch_options(keymap: &ApprovalKeymap) -> Vec<ApprovalOption> {
     vec![
         ApprovalOption {
-            label: "Yes, proceed".to_string(),
+            label: tr(Msg::ApprovalYesProceed),
             decision: ApprovalDecision::FileChange(FileChangeApprovalDecision::Accept),
             shortcuts: keymap.approve.clone(),
         },
         ApprovalOption {
-            label: "Yes, and don't ask again for these files".to_string(),
+            label: tr(Msg::ApprovalYesFilesForSession),
             decision: ApprovalDecision::FileChange(FileChangeApprovalDecision::AcceptForSession),
             shortcuts: keymap.approve_for_session.clone(),
         },
         ApprovalOption {
-            label: "No, and tell Codex what to do differently".to_string(),
+            label: tr(Msg::ApprovalNoTellCodex),
             decision: ApprovalDecision::FileChange(FileChangeApprovalDecision::Cancel),
             shortcuts: keymap.decline.clone(),
         },
@@ -1037,24 +1236,24 @@ fn permissions_options(keymap: &ApprovalKeymap) -> Vec<ApprovalOption> {
 
     vec![
         ApprovalOption {
-            label: "Yes, grant these permissions for this turn".to_string(),
+            label: tr(Msg::ApprovalYesGrantPermissionsForTurn),
             decision: ApprovalDecision::Permissions(PermissionsDecision::GrantForTurn),
             shortcuts: keymap.approve.clone(),
         },
         ApprovalOption {
-            label: "Yes, grant for this turn with strict auto review".to_string(),
+            label: tr(Msg::ApprovalYesGrantForTurnWithStrictAutoReview),
             decision: ApprovalDecision::Permissions(
                 PermissionsDecision::GrantForTurnWithStrictAutoReview,
             ),
             shortcuts: vec![key_hint::plain(KeyCode::Char('r'))],
         },
         ApprovalOption {
-            label: "Yes, grant these permissions for this session".to_string(),
+            label: tr(Msg::ApprovalYesGrantPermissionsForSession),
             decision: ApprovalDecision::Permissions(PermissionsDecision::GrantForSession),
             shortcuts: keymap.approve_for_session.clone(),
         },
         ApprovalOption {
-            label: "No, continue without permissions".to_string(),
+            label: tr(Msg::ApprovalNoContinueWithoutPermissions),
             decision: ApprovalDecision::Permissions(PermissionsDecision::Deny),
             shortcuts: deny_shortcuts,
         },
@@ -1085,17 +1284,17 @@ fn elicitation_options(keymap: &ApprovalKeymap) -> Vec<ApprovalOption> {
 
     vec![
         ApprovalOption {
-            label: "Yes, provide the requested info".to_string(),
+            label: tr(Msg::ApprovalYesProvideRequestedInfo),
             decision: ApprovalDecision::McpElicitation(McpServerElicitationAction::Accept),
             shortcuts: keymap.approve.clone(),
         },
         ApprovalOption {
-            label: "No, but continue without it".to_string(),
+            label: tr(Msg::ApprovalNoContinueWithoutInfo),
             decision: ApprovalDecision::McpElicitation(McpServerElicitationAction::Decline),
             shortcuts: decline_shortcuts,
         },
         ApprovalOption {
-            label: "Cancel this request".to_string(),
+            label: tr(Msg::ApprovalCancelRequest),
             decision: ApprovalDecision::McpElicitation(McpServerElicitationAction::Cancel),
             shortcuts: cancel_shortcuts,
         },
@@ -1111,12 +1310,14 @@ mod tests {
     use codex_app_server_protocol::ExecPolicyAmendment;
     use codex_app_server_protocol::NetworkApprovalProtocol;
     use codex_app_server_protocol::NetworkPolicyAmendment;
+    use codex_config::types::ApprovalTimeoutAction;
     use codex_protocol::models::FileSystemPermissions;
     use codex_protocol::models::NetworkPermissions;
     use codex_utils_absolute_path::AbsolutePathBuf;
     use crossterm::event::KeyModifiers;
     use insta::assert_snapshot;
     use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::UnboundedReceiver;
     use tokio::sync::mpsc::unbounded_channel;
 
     fn absolute_path(path: &str) -> AbsolutePathBuf {
@@ -1193,9 +1394,56 @@ mod tests {
             features,
             approval_keymap,
             list_keymap,
+            /*timeout*/ None,
+        )
+    }
+
+    fn make_overlay_with_timeout(
+        command: &[&str],
+        app_event_tx: AppEventSender,
+        action: ApprovalTimeoutAction,
+    ) -> ApprovalOverlay {
+        let keymap = crate::keymap::RuntimeKeymap::defaults();
+        ApprovalOverlay::new(
+            ApprovalRequest::Exec(ExecApprovalRequest {
+                thread_id: ThreadId::new(),
+                thread_label: None,
+                id: "test".to_string(),
+                environment_id: None,
+                command: command.iter().map(ToString::to_string).collect(),
+                reason: None,
+                available_decisions: vec![
+                    CommandExecutionApprovalDecision::Accept,
+                    CommandExecutionApprovalDecision::Decline,
+                    CommandExecutionApprovalDecision::Cancel,
+                ],
+                network_approval_context: None,
+                additional_permissions: None,
+            }),
+            app_event_tx,
+            Features::with_defaults(),
+            keymap.approval,
+            keymap.list,
+            ApprovalTimeout::from_config(Some(600), action),
         )
     }
 
+    fn exec_decisions(
+        rx: &mut UnboundedReceiver<AppEvent>,
+    ) -> Vec<CommandExecutionApprovalDecision> {
+        let mut decisions = Vec::new();
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::SubmitThreadOp {
+                op: Op::ExecApproval { decision, .. },
+                ..
+            } = ev
+            {
+                decisions.push(decision);
+            }
+        }
+        decisions
+    }
+
     fn make_exec_request() -> ApprovalRequest {
         ApprovalRequest::Exec(ExecApprovalRequest {
             thread_id: ThreadId::new(),
@@ -1555,6 +1803,130 @@ mod tests {
         );
     }
 
+    #[test]
+    fn explain_key_toggles_command_explanation() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = make_overlay(
+            ApprovalRequest::Exec(ExecApprovalRequest {
+                thread_id: ThreadId::new(),
+                thread_label: None,
+                id: "test".to_string(),
+                environment_id: None,
+                command: vec![
+                    "bash".to_string(),
+                    "-lc".to_string(),
+                    "git add . && git commit -m wip".to_string(),
+                ],
+                reason: Some("save the finished refactor".to_string()),
+                available_decisions: vec![
+                    CommandExecutionApprovalDecision::Accept,
+                    CommandExecutionApprovalDecision::Cancel,
+                ],
+                network_approval_context: None,
+                additional_permissions: None,
+            }),
+            tx,
+            Features::with_defaults(),
+        );
+        view.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE));
+        assert_eq!(view.list.selected_actual_idx(), Some(1));
+        assert_snapshot!(
+            "approval_overlay_exec_explanation",
+            render_overlay_lines(&view, /*width*/ 80)
+        );
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE));
+        assert!(!render_overlay_lines(&view, /*width*/ 80).contains("What it does:"));
+    }
+
+    #[test]
+    fn unanswered_exec_request_is_declined_after_timeout() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view =
+            make_overlay_with_timeout(&["rm", "-rf", "build"], tx, ApprovalTimeoutAction::Deny);
+        assert!(
+            render_overlay_lines(&view, /*width*/ 80)
+                .contains("No answer: declines automatically in 10m 00s")
+        );
+
+        let deadline = view.current_shown_at + Duration::from_secs(/*secs*/ 600);
+        assert!(!view.pre_draw_tick(deadline - Duration::from_secs(/*secs*/ 1)));
+        assert!(view.pre_draw_tick(deadline));
+
+        assert!(view.is_complete());
+        let mut history = Vec::new();
+        let mut decisions = Vec::new();
+        while let Ok(ev) = rx.try_recv() {
+            match ev {
+                AppEvent::InsertHistoryCell(cell) => {
+                    history.extend(render_history_cell_lines(cell.as_ref(), /*width*/ 80));
+                }
+                AppEvent::SubmitThreadOp {
+                    op: Op::ExecApproval { decision, .. },
+                    ..
+                } => decisions.push(decision),
+                _ => {}
+            }
+        }
+        assert_eq!(decisions, vec![CommandExecutionApprovalDecision::Decline]);
+        assert_eq!(
+            history,
+            vec!["✗ Timeout policy did not approve codex to run rm -rf build".to_string()]
+        );
+    }
+
+    #[test]
+    fn approve_safe_reads_only_approves_read_only_commands() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = make_overlay_with_timeout(
+            &["cat", "README.md"],
+            AppEventSender::new(tx.clone()),
+            ApprovalTimeoutAction::ApproveSafeReads,
+        );
+        assert!(
+            render_overlay_lines(&view, /*width*/ 80)
+                .contains("Read-only command: approves automatically in")
+        );
+        assert!(view.pre_draw_tick(view.current_shown_at + Duration::from_secs(/*secs*/ 600)));
+        assert_eq!(
+            exec_decisions(&mut rx),
+            vec![CommandExecutionApprovalDecision::Accept]
+        );
+
+        let mut view = make_overlay_with_timeout(
+            &["rm", "README.md"],
+            AppEventSender::new(tx),
+            ApprovalTimeoutAction::ApproveSafeReads,
+        );
+        assert!(view.pre_draw_tick(view.current_shown_at + Duration::from_secs(/*secs*/ 600)));
+        assert_eq!(
+            exec_decisions(&mut rx),
+            vec![CommandExecutionApprovalDecision::Decline]
+        );
+    }
+
+    #[test]
+    fn key_press_stops_the_timeout_countdown() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = make_overlay_with_timeout(
+            &["rm", "-rf", "build"],
+            AppEventSender::new(tx),
+            ApprovalTimeoutAction::Deny,
+        );
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+
+        assert!(!render_overlay_lines(&view, /*width*/ 80).contains("automatically"));
+        assert_eq!(view.next_frame_delay(), None);
+        assert!(!view.pre_draw_tick(view.current_shown_at + Duration::from_secs(/*secs*/ 600)));
+        assert!(!view.is_complete());
+        assert_eq!(exec_decisions(&mut rx), Vec::new());
+    }
+
     #[test]
     fn exec_prefix_option_emits_execpolicy_amendment() {
         let (tx, mut rx) = unbounded_channel::<AppEvent>();
@@ -2081,6 +2453,7 @@ mod tests {
             Features::with_defaults(),
             keymap.approval,
             keymap.list,
+            /*timeout*/ None,
         );
         let rendered = render_overlay_lines(&view, /*width*/ 120);
         assert!(rendered.contains("Thread: Banach [worker]"));
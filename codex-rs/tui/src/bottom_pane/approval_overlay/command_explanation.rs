@@ -0,0 +1,316 @@
+//! Offline, one-sentence explanations of commands awaiting approval.
+//!
+//! The approval overlay shows these when the user presses the explain key. They come from a small
+//! built-in reference of common tools rather than a model round-trip, so they appear instantly and
+//! never leave the machine. Commands outside the reference say so instead of guessing.
+
+use std::ffi::OsStr;
+use std::path::Path;
+
+use codex_shell_command::bash::extract_bash_command;
+use codex_shell_command::bash::parse_shell_lc_plain_commands;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+use super::ExecApprovalRequest;
+
+/// `(program, subcommand, what it does)`. A `None` subcommand matches any invocation, so
+/// entries with a subcommand must precede the catch-all for the same program.
+const COMMAND_REFERENCE: &[(&str, Option<&str>, &str)] = &[
+    (
+        "git",
+        Some("status"),
+        "shows which files have uncommitted changes",
+    ),
+    (
+        "git",
+        Some("diff"),
+        "shows uncommitted changes line by line",
+    ),
+    ("git", Some("log"), "lists recent commits"),
+    ("git", Some("show"), "shows the contents of a commit"),
+    (
+        "git",
+        Some("add"),
+        "stages file changes for the next commit",
+    ),
+    (
+        "git",
+        Some("commit"),
+        "records the staged changes as a new commit",
+    ),
+    (
+        "git",
+        Some("push"),
+        "uploads local commits to a remote repository",
+    ),
+    (
+        "git",
+        Some("pull"),
+        "downloads and merges commits from a remote repository",
+    ),
+    (
+        "git",
+        Some("fetch"),
+        "downloads commits from a remote repository without merging them",
+    ),
+    (
+        "git",
+        Some("checkout"),
+        "switches branches or restores files, discarding local edits to them",
+    ),
+    ("git", Some("switch"), "switches to another branch"),
+    ("git", Some("restore"), "discards local edits to files"),
+    (
+        "git",
+        Some("reset"),
+        "moves the current branch and can discard uncommitted work",
+    ),
+    ("git", Some("clean"), "deletes untracked files"),
+    ("git", Some("stash"), "sets uncommitted changes aside"),
+    (
+        "git",
+        Some("rebase"),
+        "rewrites commits on top of another branch",
+    ),
+    (
+        "git",
+        Some("merge"),
+        "merges another branch into the current one",
+    ),
+    ("git", Some("branch"), "lists, creates, or deletes branches"),
+    ("git", Some("clone"), "downloads a copy of a repository"),
+    ("git", None, "runs a git operation on the repository"),
+    ("cargo", Some("build"), "compiles the Rust project"),
+    (
+        "cargo",
+        Some("check"),
+        "type-checks the Rust project without producing binaries",
+    ),
+    ("cargo", Some("test"), "builds and runs the Rust tests"),
+    ("cargo", Some("run"), "builds and runs the Rust program"),
+    ("cargo", Some("clippy"), "runs the Rust linter"),
+    ("cargo", Some("fmt"), "reformats Rust source files"),
+    ("cargo", Some("add"), "adds a dependency to Cargo.toml"),
+    (
+        "cargo",
+        Some("install"),
+        "downloads, builds, and installs a Rust binary",
+    ),
+    ("cargo", None, "runs a Rust toolchain command"),
+    (
+        "npm",
+        Some("install"),
+        "downloads JavaScript dependencies and may run their install scripts",
+    ),
+    (
+        "npm",
+        Some("ci"),
+        "reinstalls JavaScript dependencies from the lockfile",
+    ),
+    ("npm", Some("test"), "runs the project's JavaScript tests"),
+    ("npm", Some("run"), "runs a script defined in package.json"),
+    ("npm", None, "runs a Node package manager command"),
+    ("pnpm", None, "runs a Node package manager command"),
+    ("yarn", None, "runs a Node package manager command"),
+    (
+        "npx",
+        None,
+        "downloads if needed and runs a Node package's command",
+    ),
+    (
+        "pip",
+        Some("install"),
+        "downloads and installs Python packages",
+    ),
+    (
+        "pip3",
+        Some("install"),
+        "downloads and installs Python packages",
+    ),
+    ("python", None, "runs a Python program"),
+    ("python3", None, "runs a Python program"),
+    ("node", None, "runs a JavaScript program"),
+    ("go", Some("build"), "compiles the Go packages"),
+    ("go", Some("test"), "builds and runs the Go tests"),
+    ("go", None, "runs a Go toolchain command"),
+    ("make", None, "runs build steps from the Makefile"),
+    ("pytest", None, "runs the Python tests"),
+    ("ls", None, "lists files in a directory"),
+    ("cat", None, "prints the contents of files"),
+    ("head", None, "prints the beginning of files"),
+    ("tail", None, "prints the end of files"),
+    ("grep", None, "searches files for matching text"),
+    ("rg", None, "searches files for matching text"),
+    ("find", None, "searches for files by name or attributes"),
+    ("sed", None, "rewrites text from files"),
+    ("rm", None, "deletes files"),
+    ("rmdir", None, "deletes empty directories"),
+    ("mv", None, "moves or renames files"),
+    ("cp", None, "copies files"),
+    ("mkdir", None, "creates directories"),
+    (
+        "touch",
+        None,
+        "creates empty files or updates their timestamps",
+    ),
+    ("chmod", None, "changes file permissions"),
+    ("chown", None, "changes file ownership"),
+    ("ln", None, "creates links between files"),
+    ("tar", None, "creates or extracts archives"),
+    ("unzip", None, "extracts a zip archive"),
+    ("curl", None, "makes a network request to a URL"),
+    ("wget", None, "downloads files from the network"),
+    ("ssh", None, "opens a shell on a remote machine"),
+    ("scp", None, "copies files to or from a remote machine"),
+    ("docker", None, "manages containers or images"),
+    ("kubectl", None, "manages a Kubernetes cluster"),
+    ("kill", None, "sends a signal to stop processes"),
+    ("pkill", None, "stops processes by name"),
+    ("echo", None, "prints text"),
+    ("pwd", None, "prints the current directory"),
+    ("cd", None, "changes the current directory"),
+];
+
+/// `(program, short flag, long flag, caveat)` appended when an invocation uses the flag.
+const FLAG_CAVEATS: &[(&str, Option<char>, &str, &str)] = &[
+    (
+        "rm",
+        Some('r'),
+        "--recursive",
+        "including whole directories",
+    ),
+    (
+        "rm",
+        Some('f'),
+        "--force",
+        "without asking for confirmation",
+    ),
+    ("sed", Some('i'), "--in-place", "editing the files in place"),
+    ("git", None, "--force", "overwriting existing history"),
+    (
+        "curl",
+        Some('o'),
+        "--output",
+        "saving the response to a file",
+    ),
+];
+
+/// Lines shown under the command: what it does and the reason the agent gave.
+pub(super) fn explanation_lines(request: &ExecApprovalRequest) -> Vec<Line<'static>> {
+    let why = request
+        .reason
+        .as_deref()
+        .filter(|reason| !reason.trim().is_empty())
+        .map_or_else(
+            || "Codex did not say why it needs this command.".to_string(),
+            str::to_string,
+        );
+    vec![
+        Line::from(""),
+        Line::from(vec![
+            "What it does: ".bold(),
+            explain_command(&request.command).into(),
+        ]),
+        Line::from(vec!["Why Codex wants it: ".bold(), why.into()]),
+    ]
+}
+
+/// Explains `command` in one sentence.
+pub(super) fn explain_command(command: &[String]) -> String {
+    let commands = if extract_bash_command(command).is_some() {
+        let Some(commands) = parse_shell_lc_plain_commands(command) else {
+            return "This shell script uses redirection, substitution, or control flow, so it \
+                    cannot be summarized; read it in full before approving."
+                .to_string();
+        };
+        commands
+    } else {
+        vec![command.to_vec()]
+    };
+
+    let phrases: Vec<String> = commands
+        .iter()
+        .filter_map(|words| describe(words))
+        .collect();
+    match phrases.split_first() {
+        None => "This command does nothing Codex can describe.".to_string(),
+        Some((first, [])) => format!("{first}."),
+        Some((first, rest)) => format!("{first}, then {}.", rest.join(", then ")),
+    }
+}
+
+fn describe(words: &[String]) -> Option<String> {
+    let mut words = words;
+    let mut elevated = false;
+    while let Some(word) = words.first().map(String::as_str) {
+        match word {
+            "sudo" => elevated = true,
+            "env" | "nohup" | "time" | "command" => {}
+            _ if is_env_assignment(word) => {}
+            _ => break,
+        }
+        words = &words[1..];
+    }
+
+    let (program, args) = words.split_first()?;
+    let name = Path::new(program)
+        .file_name()
+        .and_then(OsStr::to_str)
+        .unwrap_or(program);
+    let subcommand = args
+        .iter()
+        .map(String::as_str)
+        .find(|arg| !arg.starts_with('-'));
+    let invocation = match subcommand {
+        Some(subcommand)
+            if COMMAND_REFERENCE
+                .iter()
+                .any(|(entry, sub, _)| *entry == name && *sub == Some(subcommand)) =>
+        {
+            format!("{name} {subcommand}")
+        }
+        _ => name.to_string(),
+    };
+
+    let mut phrase = match COMMAND_REFERENCE
+        .iter()
+        .find(|(entry, sub, _)| *entry == name && (sub.is_none() || *sub == subcommand))
+    {
+        Some((_, _, description)) => format!("`{invocation}` {description}"),
+        None => format!("`{invocation}` is not in the built-in command reference"),
+    };
+    for (_, _, _, caveat) in FLAG_CAVEATS.iter().filter(|(entry, short, long, _)| {
+        *entry == name && args.iter().any(|arg| flag_matches(arg, *short, long))
+    }) {
+        phrase.push_str(", ");
+        phrase.push_str(caveat);
+    }
+    if elevated {
+        phrase.push_str(", with administrator privileges");
+    }
+    Some(phrase)
+}
+
+fn is_env_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+    })
+}
+
+fn flag_matches(arg: &str, short: Option<char>, long: &str) -> bool {
+    if arg == long || arg.starts_with(&format!("{long}=")) {
+        return true;
+    }
+    match (short, arg.strip_prefix('-')) {
+        (Some(short), Some(cluster)) if !cluster.starts_with('-') => cluster.contains(short),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+#[path = "command_explanation_tests.rs"]
+mod tests;
@@ -0,0 +1,67 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn words(command: &[&str]) -> Vec<String> {
+    command.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn known_subcommand_is_explained() {
+    assert_eq!(
+        explain_command(&words(&["git", "status"])),
+        "`git status` shows which files have uncommitted changes."
+    );
+    assert_eq!(
+        explain_command(&words(&["/usr/bin/git", "-c", "core.pager=cat", "remote"])),
+        "`git` runs a git operation on the repository."
+    );
+}
+
+#[test]
+fn shell_script_steps_are_joined_in_order() {
+    assert_eq!(
+        explain_command(&words(&["bash", "-lc", "git add . && git commit -m wip"])),
+        "`git add` stages file changes for the next commit, then `git commit` records the staged \
+         changes as a new commit."
+    );
+}
+
+#[test]
+fn risky_flags_and_sudo_are_called_out() {
+    assert_eq!(
+        explain_command(&words(&["sudo", "rm", "-rf", "build"])),
+        "`rm` deletes files, including whole directories, without asking for confirmation, with \
+         administrator privileges."
+    );
+    assert_eq!(
+        explain_command(&words(&[
+            "FOO=1",
+            "sed",
+            "--in-place",
+            "s/a/b/",
+            "notes.txt"
+        ])),
+        "`sed` rewrites text from files, editing the files in place."
+    );
+}
+
+#[test]
+fn unknown_programs_are_not_guessed() {
+    assert_eq!(
+        explain_command(&words(&["frobnicate", "--all"])),
+        "`frobnicate` is not in the built-in command reference."
+    );
+}
+
+#[test]
+fn complex_scripts_ask_for_a_full_read() {
+    assert_eq!(
+        explain_command(&words(&[
+            "bash",
+            "-lc",
+            "curl https://example.com > out.sh"
+        ])),
+        "This shell script uses redirection, substitution, or control flow, so it cannot be \
+         summarized; read it in full before approving."
+    );
+}
@@ -0,0 +1,111 @@
+//! Unattended handling for approval prompts (`tui.approval_timeout_seconds`).
+//!
+//! When configured, a prompt nobody answers is resolved once the timeout elapses: declined, or,
+//! with `approve-safe-reads`, approved when it only runs commands known to be read-only. The
+//! prompt shows a countdown meanwhile. Any key press on the prompt means someone is there, so it
+//! stops the countdown for that request.
+
+use std::time::Duration;
+
+use codex_app_server_protocol::CommandExecutionApprovalDecision;
+use codex_app_server_protocol::FileChangeApprovalDecision;
+use codex_app_server_protocol::McpServerElicitationAction;
+use codex_config::types::ApprovalTimeoutAction;
+use codex_shell_command::is_safe_command::is_known_safe_command;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+use super::ApprovalDecision;
+use super::ApprovalOption;
+use super::ApprovalRequest;
+use super::PermissionsDecision;
+use crate::status_indicator_widget::fmt_elapsed_compact;
+
+/// Configured handling for approval prompts nobody answers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ApprovalTimeout {
+    pub(crate) after: Duration,
+    pub(crate) action: ApprovalTimeoutAction,
+}
+
+impl ApprovalTimeout {
+    /// Returns `None` when no timeout is configured, so prompts wait indefinitely.
+    pub(crate) fn from_config(seconds: Option<u64>, action: ApprovalTimeoutAction) -> Option<Self> {
+        seconds.filter(|seconds| *seconds > 0).map(|seconds| Self {
+            after: Duration::from_secs(seconds),
+            action,
+        })
+    }
+}
+
+/// Index into `options` of the option applied when `request` times out.
+///
+/// Declining is preferred over canceling so the agent can carry on without the request. Returns
+/// `None` when the request offers neither.
+pub(super) fn timeout_option(
+    request: &ApprovalRequest,
+    options: &[ApprovalOption],
+    action: ApprovalTimeoutAction,
+) -> Option<usize> {
+    let approve = match (action, request) {
+        (ApprovalTimeoutAction::ApproveSafeReads, ApprovalRequest::Exec(request)) => {
+            request.network_approval_context.is_none()
+                && request.additional_permissions.is_none()
+                && is_known_safe_command(&request.command)
+        }
+        (ApprovalTimeoutAction::Deny | ApprovalTimeoutAction::ApproveSafeReads, _) => false,
+    };
+    if approve
+        && let Some(idx) = options.iter().position(|option| {
+            matches!(
+                option.decision,
+                ApprovalDecision::Command(CommandExecutionApprovalDecision::Accept)
+            )
+        })
+    {
+        return Some(idx);
+    }
+    options
+        .iter()
+        .position(|option| {
+            matches!(
+                option.decision,
+                ApprovalDecision::Command(CommandExecutionApprovalDecision::Decline)
+                    | ApprovalDecision::FileChange(FileChangeApprovalDecision::Decline)
+                    | ApprovalDecision::Permissions(PermissionsDecision::Deny)
+                    | ApprovalDecision::McpElicitation(McpServerElicitationAction::Decline)
+            )
+        })
+        .or_else(|| {
+            options.iter().position(|option| {
+                matches!(
+                    option.decision,
+                    ApprovalDecision::Command(CommandExecutionApprovalDecision::Cancel)
+                        | ApprovalDecision::FileChange(FileChangeApprovalDecision::Cancel)
+                        | ApprovalDecision::McpElicitation(McpServerElicitationAction::Cancel)
+                )
+            })
+        })
+}
+
+/// Countdown shown under the prompt while it waits for an answer.
+pub(super) fn countdown_line(remaining: Duration, option: &ApprovalOption) -> Line<'static> {
+    let mut seconds = remaining.as_secs();
+    if remaining.subsec_nanos() > 0 {
+        seconds = seconds.saturating_add(1);
+    }
+    let remaining = fmt_elapsed_compact(seconds);
+    let text = if matches!(
+        option.decision,
+        ApprovalDecision::Command(CommandExecutionApprovalDecision::Accept)
+    ) {
+        format!("Read-only command: approves automatically in {remaining}")
+    } else {
+        format!("No answer: declines automatically in {remaining}")
+    };
+    Line::from(vec!["  ".into(), text.red()])
+}
+
+#[cfg(test)]
+#[path = "timeout_tests.rs"]
+mod tests;
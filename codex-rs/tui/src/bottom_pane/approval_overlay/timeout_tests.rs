@@ -0,0 +1,38 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn missing_or_zero_timeout_waits_indefinitely() {
+    assert_eq!(
+        ApprovalTimeout::from_config(/*seconds*/ None, ApprovalTimeoutAction::Deny),
+        None
+    );
+    assert_eq!(
+        ApprovalTimeout::from_config(Some(0), ApprovalTimeoutAction::Deny),
+        None
+    );
+    assert_eq!(
+        ApprovalTimeout::from_config(Some(90), ApprovalTimeoutAction::ApproveSafeReads),
+        Some(ApprovalTimeout {
+            after: Duration::from_secs(/*secs*/ 90),
+            action: ApprovalTimeoutAction::ApproveSafeReads,
+        })
+    );
+}
+
+#[test]
+fn countdown_rounds_partial_seconds_up() {
+    let decline = ApprovalOption {
+        label: "No".to_string(),
+        decision: ApprovalDecision::Command(CommandExecutionApprovalDecision::Decline),
+        shortcuts: Vec::new(),
+    };
+    let line = countdown_line(Duration::from_millis(/*millis*/ 59_200), &decline);
+    assert_eq!(
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect::<String>(),
+        "  No answer: declines automatically in 1m 00s"
+    );
+}
@@ -268,6 +268,7 @@ mod footer_state;
 mod history_search;
 mod popup_state;
 mod slash_input;
+mod template_placeholders;
 
 use self::attachment_state::AttachmentState;
 use self::draft_state::ComposerMentionBinding;
@@ -280,6 +281,7 @@ use self::popup_state::PopupState;
 use self::slash_input::SlashInput;
 use self::slash_input::SlashValidation;
 use self::slash_input::SubmissionValidation;
+use self::template_placeholders::TemplatePlaceholders;
 use crate::app_event::AppEvent;
 use crate::app_event::ConnectorsSnapshot;
 use crate::app_event_sender::AppEventSender;
@@ -382,9 +384,11 @@ fn parent_owned_command_is_allowed(command: SlashCommand, args: &str) -> bool {
                 | SlashCommand::Import
                 | SlashCommand::Hooks
                 | SlashCommand::Status
+                | SlashCommand::Context
                 | SlashCommand::Usage
                 | SlashCommand::Ide
                 | SlashCommand::DebugConfig
+                | SlashCommand::Logs
                 | SlashCommand::Title
                 | SlashCommand::Statusline
                 | SlashCommand::Theme
@@ -490,6 +494,7 @@ pub(crate) struct ChatComposer {
     windows_degraded_sandbox_active: bool,
     side_conversation_active: bool,
     history_search: Option<HistorySearchSession>,
+    template_placeholders: Option<TemplatePlaceholders>,
     submit_keys: Vec<KeyBinding>,
     queue_keys: Vec<KeyBinding>,
     toggle_shortcuts_keys: Vec<KeyBinding>,
@@ -667,6 +672,7 @@ impl ChatComposer {
             windows_degraded_sandbox_active: false,
             side_conversation_active: false,
             history_search: None,
+            template_placeholders: None,
             submit_keys: vec![key_hint::plain(KeyCode::Enter)],
             queue_keys: vec![key_hint::plain(KeyCode::Tab)],
             toggle_shortcuts_keys: vec![
@@ -1082,6 +1088,7 @@ impl ChatComposer {
     pub fn handle_paste(&mut self, pasted: String) -> bool {
         let pasted = pasted.replace("\r\n", "\n").replace('\r', "\n");
         let pasted = sanitize_user_text(&pasted);
+        self.clear_selected_template_placeholder();
         let char_count = pasted.chars().count();
         if char_count > LARGE_PASTE_CHAR_THRESHOLD {
             let placeholder = self.next_large_paste_placeholder(char_count);
@@ -1437,6 +1444,7 @@ impl ChatComposer {
         mention_bindings: Vec<MentionBinding>,
     ) {
         // Clear any existing content, placeholders, and attachments first.
+        self.template_placeholders = None;
         self.draft.textarea.set_text_clearing_elements("");
         self.draft.is_bash_mode = false;
         self.draft.pending_pastes.clear();
@@ -3326,6 +3334,9 @@ impl ChatComposer {
         } else {
             self.footer.mode = reset_mode_after_activity(self.footer.mode);
         }
+        if let Some(result) = self.handle_template_placeholder_key(&key_event) {
+            return result;
+        }
         if self.queue_keys.is_pressed(key_event)
             && (self.is_task_running || self.queue_submissions || !self.is_bang_shell_command())
         {
@@ -4232,11 +4243,14 @@ fn footer_insert_newline_key(
         return Some(shift_enter);
     }
 
+    // Plain Enter submits, and chords the terminal cannot report would advertise a dead key.
     let plain_enter = key_hint::plain(KeyCode::Enter);
     bindings
         .iter()
         .copied()
-        .find(|binding| *binding != plain_enter)
+        .find(|binding| {
+            *binding != plain_enter && (enhanced_keys_supported || !binding.needs_enhanced_keys())
+        })
         .or_else(|| bindings.first().copied())
 }
 
@@ -4724,6 +4738,7 @@ impl ChatComposer {
                     .render_ref_masked(textarea_rect, buf, &mut state, mask_char);
             } else {
                 let mut highlights = self.plugin_at_mention_highlights();
+                highlights.extend(self.template_placeholder_highlights());
                 let search_highlight_style =
                     Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD);
                 highlights.extend(
@@ -4831,6 +4846,24 @@ mod tests {
         )
     }
 
+    #[test]
+    fn footer_newline_hint_skips_chords_legacy_terminals_cannot_report() {
+        let bindings = [
+            key_hint::plain(KeyCode::Enter),
+            key_hint::ctrl(KeyCode::Enter),
+            key_hint::ctrl(KeyCode::Char('j')),
+        ];
+
+        assert_eq!(
+            footer_insert_newline_key(&bindings, /*enhanced_keys_supported*/ false),
+            Some(key_hint::ctrl(KeyCode::Char('j')))
+        );
+        assert_eq!(
+            footer_insert_newline_key(&bindings, /*enhanced_keys_supported*/ true),
+            Some(key_hint::ctrl(KeyCode::Enter))
+        );
+    }
+
     #[test]
     fn parent_owned_thread_allows_bare_navigation_commands() {
         for (command, expected) in [
@@ -0,0 +1,171 @@
+//! Snippet-style placeholder fields for templates inserted into the composer.
+//!
+//! `/template` inserts markdown containing `{{ name }}` fields. While the draft still contains such
+//! fields, Tab selects the next one (wrapping around) instead of queueing the draft, and the first
+//! character typed or pasted replaces the selected field, like tab stops in an editor snippet.
+//! Fields stay highlighted while navigation is active. The session ends once no fields remain or
+//! the draft is replaced.
+
+use std::ops::Range;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+
+use super::ChatComposer;
+use super::InputResult;
+use crate::key_hint::has_ctrl_or_alt;
+
+/// Placeholder navigation state for a draft that came from a template.
+#[derive(Debug, Default)]
+pub(super) struct TemplatePlaceholders {
+    /// Field selected by the last Tab; the next edit replaces it.
+    selected: Option<Range<usize>>,
+}
+
+/// Byte ranges of `{{ name }}` fields in `text`, in order.
+///
+/// Names may contain letters, digits, spaces, `_`, and `-`, so prose such as `{{` on its own or
+/// template syntax spanning lines is left alone.
+pub(super) fn placeholder_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut search_from = 0;
+    while let Some(open) = text[search_from..].find("{{") {
+        let start = search_from + open;
+        let name_start = start + 2;
+        let Some(close) = text[name_start..].find("}}") else {
+            break;
+        };
+        let name_end = name_start + close;
+        let name = text[name_start..name_end].trim();
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|ch| ch.is_alphanumeric() || matches!(ch, ' ' | '_' | '-'))
+        {
+            ranges.push(start..name_end + 2);
+            search_from = name_end + 2;
+        } else {
+            search_from = name_start;
+        }
+    }
+    ranges
+}
+
+impl ChatComposer {
+    /// Replaces the draft with `template` and selects its first placeholder field.
+    pub(crate) fn insert_template(&mut self, template: String) {
+        self.set_text_content(template, Vec::new(), Vec::new());
+        let Some(first) = placeholder_ranges(self.draft.textarea.text())
+            .into_iter()
+            .next()
+        else {
+            self.move_cursor_to_end();
+            return;
+        };
+        self.draft.textarea.set_cursor(first.start);
+        self.template_placeholders = Some(TemplatePlaceholders {
+            selected: Some(first),
+        });
+    }
+
+    /// Handles Tab and edits of the selected field while a template draft has fields left.
+    ///
+    /// Returns `None` when the key should continue through normal composer handling, which is also
+    /// the case for typed characters after the selected field has been cleared for them.
+    pub(super) fn handle_template_placeholder_key(
+        &mut self,
+        key_event: &KeyEvent,
+    ) -> Option<(InputResult, bool)> {
+        self.template_placeholders.as_ref()?;
+        let ranges = placeholder_ranges(self.draft.textarea.text());
+        if ranges.is_empty() {
+            self.template_placeholders = None;
+            return None;
+        }
+        let selected = self.take_selected_template_placeholder();
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Tab,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                let next = match &selected {
+                    Some(selected) => ranges.iter().find(|range| range.start > selected.start),
+                    None => {
+                        let cursor = self.draft.textarea.cursor();
+                        ranges.iter().find(|range| range.start >= cursor)
+                    }
+                }
+                .or(ranges.first())
+                .cloned()?;
+                self.draft.textarea.set_cursor(next.start);
+                self.template_placeholders = Some(TemplatePlaceholders {
+                    selected: Some(next),
+                });
+                Some((InputResult::None, true))
+            }
+            _ if self.draft.textarea.is_vim_normal_mode() => None,
+            KeyEvent {
+                code: KeyCode::Char(_),
+                modifiers,
+                ..
+            } if !has_ctrl_or_alt(*modifiers) => {
+                if let Some(selected) = selected {
+                    self.draft.textarea.replace_range(selected, "");
+                }
+                None
+            }
+            KeyEvent {
+                code: KeyCode::Backspace | KeyCode::Delete,
+                ..
+            } => {
+                let selected = selected?;
+                self.draft.textarea.replace_range(selected, "");
+                Some((InputResult::None, true))
+            }
+            _ => None,
+        }
+    }
+
+    /// Clears the selected field so a paste lands in its place.
+    pub(super) fn clear_selected_template_placeholder(&mut self) {
+        if let Some(selected) = self.take_selected_template_placeholder() {
+            self.draft.textarea.replace_range(selected, "");
+        }
+    }
+
+    /// Takes the selected field if the cursor still sits on it unchanged.
+    fn take_selected_template_placeholder(&mut self) -> Option<Range<usize>> {
+        let selected = self.template_placeholders.as_mut()?.selected.take()?;
+        let text = self.draft.textarea.text();
+        (self.draft.textarea.cursor() == selected.start
+            && placeholder_ranges(text).contains(&selected))
+        .then_some(selected)
+    }
+
+    pub(super) fn template_placeholder_highlights(&self) -> Vec<(Range<usize>, Style)> {
+        let Some(placeholders) = self.template_placeholders.as_ref() else {
+            return Vec::new();
+        };
+        let field_style = Style::default().fg(Color::Cyan);
+        placeholder_ranges(self.draft.textarea.text())
+            .into_iter()
+            .map(|range| {
+                let style = if placeholders.selected.as_ref() == Some(&range) {
+                    field_style.add_modifier(Modifier::REVERSED)
+                } else {
+                    field_style
+                };
+                (range, style)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "template_placeholders_tests.rs"]
+mod tests;
@@ -0,0 +1,65 @@
+use super::*;
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use pretty_assertions::assert_eq;
+use tokio::sync::mpsc::unbounded_channel;
+
+fn composer() -> ChatComposer {
+    let (tx, _rx) = unbounded_channel::<AppEvent>();
+    ChatComposer::new(
+        /*has_input_focus*/ true,
+        AppEventSender::new(tx),
+        /*enhanced_keys_supported*/ false,
+        "Ask Codex to do anything".to_string(),
+        /*disable_paste_burst*/ true,
+    )
+}
+
+fn press(composer: &mut ChatComposer, code: KeyCode) {
+    let _ = composer.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+}
+
+#[test]
+fn finds_named_fields_and_skips_other_braces() {
+    let text = "Fix {{ task }} in {{file-name}}; keep {{}} and {{ a.b }} and {{ open";
+
+    assert_eq!(
+        vec!["{{ task }}", "{{file-name}}"],
+        placeholder_ranges(text)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn tab_cycles_fields_and_typing_replaces_the_selected_one() {
+    let mut composer = composer();
+    composer.insert_template("Fix {{ task }} in {{ file }}.".to_string());
+
+    press(&mut composer, KeyCode::Char('b'));
+    press(&mut composer, KeyCode::Char('u'));
+    press(&mut composer, KeyCode::Char('g'));
+    assert_eq!("Fix bug in {{ file }}.", composer.current_text());
+
+    press(&mut composer, KeyCode::Tab);
+    press(&mut composer, KeyCode::Char('a'));
+    assert_eq!("Fix bug in a.", composer.current_text());
+
+    // With no fields left, Tab goes back to its usual meaning.
+    assert!(composer.template_placeholders.is_some());
+    press(&mut composer, KeyCode::Tab);
+    assert!(composer.template_placeholders.is_none());
+}
+
+#[test]
+fn tab_wraps_to_the_first_field_and_backspace_clears_the_selection() {
+    let mut composer = composer();
+    composer.insert_template("{{ one }} {{ two }}".to_string());
+
+    press(&mut composer, KeyCode::Tab);
+    press(&mut composer, KeyCode::Tab);
+    press(&mut composer, KeyCode::Backspace);
+
+    assert_eq!(" {{ two }}", composer.current_text());
+}
@@ -352,6 +352,12 @@ pub(crate) fn feedback_success_cell(
                     thread_id.to_string().bold(),
                     " in an existing issue.".into(),
                 ]),
+                "".into(),
+                Line::from(vec![
+                    "  To attach local diagnostics, run ".into(),
+                    format!("codex bugreport --session {thread_id}").cyan(),
+                    ".".into(),
+                ]),
             ]);
         }
         None => {
@@ -884,7 +890,7 @@ mod tests {
         );
         assert_eq!(
             rendered,
-            "• Feedback uploaded. Please open an issue using the following URL:\n\n  https://github.com/ai-nd-co/codex/issues/new?template=3-cli.yml&steps=Uploaded%20thread:%20thread-1\n\n  Or mention your thread ID thread-1 in an existing issue."
+            "• Feedback uploaded. Please open an issue using the following URL:\n\n  https://github.com/ai-nd-co/codex/issues/new?template=3-cli.yml&steps=Uploaded%20thread:%20thread-1\n\n  Or mention your thread ID thread-1 in an existing issue.\n\n  To attach local diagnostics, run codex bugreport --session thread-1."
         );
     }
 
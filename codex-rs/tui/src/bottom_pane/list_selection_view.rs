@@ -467,7 +467,7 @@ impl ListSelectionView {
         MAX_POPUP_ROWS.min(len.max(1))
     }
 
-    fn selected_actual_idx(&self) -> Option<usize> {
+    pub(crate) fn selected_actual_idx(&self) -> Option<usize> {
         self.state
             .selected_idx
             .and_then(|visible_idx| self.filtered_indices.get(visible_idx).copied())
@@ -23,6 +23,7 @@ use crate::app_event::HistoryLookupResponse;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::pending_input_preview::PendingInputPreview;
 use crate::bottom_pane::pending_thread_approvals::PendingThreadApprovals;
+use crate::bottom_pane::plan_checklist::PlanChecklist;
 use crate::bottom_pane::unified_exec_footer::UnifiedExecFooter;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
@@ -43,6 +44,7 @@ use codex_file_search::FileMatch;
 use codex_plugin::PluginCapabilitySummary;
 use codex_protocol::ThreadId;
 use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::user_input::TextElement;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -72,6 +74,7 @@ pub(crate) use app_link_view::AppLinkViewParams;
 pub(crate) use approval_overlay::ApplyPatchApprovalRequest;
 pub(crate) use approval_overlay::ApprovalOverlay;
 pub(crate) use approval_overlay::ApprovalRequest;
+pub(crate) use approval_overlay::ApprovalTimeout;
 pub(crate) use approval_overlay::ExecApprovalRequest;
 pub(crate) use approval_overlay::McpElicitationApprovalRequest;
 pub(crate) use approval_overlay::PermissionsApprovalRequest;
@@ -149,6 +152,7 @@ pub(crate) use title_setup::preview_line_for_title_items;
 mod paste_burst;
 mod pending_input_preview;
 mod pending_thread_approvals;
+mod plan_checklist;
 pub(crate) mod popup_consts;
 mod scroll_state;
 mod selection_popup_common;
@@ -246,9 +250,13 @@ pub(crate) struct BottomPane {
     pending_input_preview: PendingInputPreview,
     /// Inactive threads with pending approval requests.
     pending_thread_approvals: PendingThreadApprovals,
+    /// Latest agent plan, shown as a checklist while the turn runs.
+    plan_checklist: PlanChecklist,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
     keymap: RuntimeKeymap,
+    /// Applied to approval prompts nobody answers; `None` waits indefinitely.
+    approval_timeout: Option<ApprovalTimeout>,
 }
 
 pub(crate) struct BottomPaneParams {
@@ -301,11 +309,13 @@ impl BottomPane {
             unified_exec_footer: UnifiedExecFooter::new(),
             pending_input_preview: PendingInputPreview::new(),
             pending_thread_approvals: PendingThreadApprovals::new(),
+            plan_checklist: PlanChecklist::new(),
             esc_backtrack_hint: false,
             animations_enabled,
             context_window_percent: None,
             context_window_used_tokens: None,
             keymap,
+            approval_timeout: None,
         }
     }
 
@@ -405,6 +415,11 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Sets how approval prompts opened from now on handle going unanswered.
+    pub(crate) fn set_approval_timeout(&mut self, timeout: Option<ApprovalTimeout>) {
+        self.approval_timeout = timeout;
+    }
+
     /// Clear pending attachments and mention bindings e.g. when a slash command doesn't submit text.
     pub(crate) fn drain_pending_submission_state(&mut self) {
         let _ = self.take_recent_submission_images_with_placeholders();
@@ -599,6 +614,7 @@ impl BottomPane {
             first.features,
             self.keymap.approval.clone(),
             self.keymap.list.clone(),
+            self.approval_timeout,
         );
         while let Some(delayed) = self.delayed_approval_requests.pop_back() {
             modal.enqueue_request(delayed.request);
@@ -807,6 +823,12 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Replace the composer text with a template and select its first `{{ ... }}` field.
+    pub(crate) fn insert_composer_template(&mut self, template: String) {
+        self.composer.insert_template(template);
+        self.request_redraw();
+    }
+
     /// Replace the composer text while preserving mention link targets.
     ///
     /// Use this when rehydrating a draft after a local validation/gating
@@ -1053,6 +1075,7 @@ impl BottomPane {
         } else {
             // Hide the status indicator when a task completes, but keep other modal views.
             self.hide_status_indicator();
+            self.plan_checklist.set_steps(Vec::new());
         }
     }
 
@@ -1294,6 +1317,12 @@ impl BottomPane {
         }
     }
 
+    /// Replace the plan checklist shown above the composer; it clears when the task stops.
+    pub(crate) fn set_plan_checklist(&mut self, steps: Vec<PlanItemArg>) {
+        self.plan_checklist.set_steps(steps);
+        self.request_redraw();
+    }
+
     #[cfg(test)]
     pub(crate) fn pending_thread_approvals(&self) -> &[String] {
         self.pending_thread_approvals.threads()
@@ -1431,6 +1460,7 @@ impl BottomPane {
                 features.clone(),
                 self.keymap.approval.clone(),
                 self.keymap.list.clone(),
+                self.approval_timeout,
             );
             self.pause_status_timer_for_modal();
             self.push_view(Box::new(modal));
@@ -1738,16 +1768,25 @@ impl BottomPane {
                     RenderableItem::Borrowed(&self.unified_exec_footer),
                 );
             }
+            let has_plan_checklist = !self.plan_checklist.is_empty();
             let has_pending_thread_approvals = !self.pending_thread_approvals.is_empty();
             let has_pending_input = !self.pending_input_preview.queued_messages.is_empty()
                 || !self.pending_input_preview.pending_steers.is_empty()
                 || !self.pending_input_preview.rejected_steers.is_empty();
             let has_status_or_footer =
                 self.status.is_some() || !self.unified_exec_footer.is_empty();
-            let has_inline_previews = has_pending_thread_approvals || has_pending_input;
+            let has_inline_previews =
+                has_plan_checklist || has_pending_thread_approvals || has_pending_input;
             if has_inline_previews && has_status_or_footer {
                 flex.push(/*flex*/ 0, RenderableItem::Owned("".into()));
             }
+            flex.push(
+                /*flex*/ 1,
+                RenderableItem::Borrowed(&self.plan_checklist),
+            );
+            if has_plan_checklist && (has_pending_thread_approvals || has_pending_input) {
+                flex.push(/*flex*/ 0, RenderableItem::Owned("".into()));
+            }
             flex.push(
                 /*flex*/ 1,
                 RenderableItem::Borrowed(&self.pending_thread_approvals),
@@ -0,0 +1,109 @@
+//! Live checklist of the agent's current plan, shown above the composer while a turn runs.
+//!
+//! Every plan update replaces the whole plan, so the widget only keeps the latest one; the
+//! transcript keeps each update as its own `PlanUpdateCell`. Long plans are windowed around the
+//! first unfinished step so the checklist never crowds out the composer.
+
+use codex_protocol::plan_tool::PlanItemArg;
+use codex_protocol::plan_tool::StepStatus;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+
+use crate::line_truncation::truncate_line_with_ellipsis_if_overflow;
+use crate::render::renderable::Renderable;
+
+/// Most steps listed at once; the rest collapse into summary rows.
+const MAX_VISIBLE_STEPS: usize = 5;
+
+/// Tracks the latest plan update and renders it as a checklist.
+pub(crate) struct PlanChecklist {
+    steps: Vec<PlanItemArg>,
+}
+
+impl PlanChecklist {
+    pub(crate) fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    pub(crate) fn set_steps(&mut self, steps: Vec<PlanItemArg>) {
+        self.steps = steps;
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    fn render_lines(&self, width: u16) -> Vec<Line<'static>> {
+        if self.steps.is_empty() || width < 4 {
+            return Vec::new();
+        }
+
+        let completed = self
+            .steps
+            .iter()
+            .filter(|item| matches!(item.status, StepStatus::Completed))
+            .count();
+        let mut lines = vec![Line::from(vec![
+            "  ".into(),
+            "Plan".bold(),
+            format!(" {completed}/{}", self.steps.len()).dim(),
+        ])];
+
+        // Keep the last finished step in view for context, then as many upcoming steps as fit.
+        let first_open = self
+            .steps
+            .iter()
+            .position(|item| !matches!(item.status, StepStatus::Completed))
+            .unwrap_or(self.steps.len());
+        let start = first_open
+            .saturating_sub(1)
+            .min(self.steps.len().saturating_sub(MAX_VISIBLE_STEPS));
+        let end = (start + MAX_VISIBLE_STEPS).min(self.steps.len());
+
+        if start > 0 {
+            lines.push(Line::from(format!("    … {start} done").dim()));
+        }
+        for PlanItemArg { step, status } in &self.steps[start..end] {
+            let line = match status {
+                StepStatus::Completed => {
+                    Line::from(vec!["    ✔ ".green(), step.clone().dim().crossed_out()])
+                }
+                StepStatus::InProgress => {
+                    Line::from(vec!["    › ".cyan().bold(), step.clone().cyan().bold()])
+                }
+                StepStatus::Pending => Line::from(vec!["    □ ".dim(), step.clone().into()]),
+            };
+            lines.push(truncate_line_with_ellipsis_if_overflow(
+                line,
+                width as usize,
+            ));
+        }
+        if end < self.steps.len() {
+            lines.push(Line::from(
+                format!("    … +{} more", self.steps.len() - end).dim(),
+            ));
+        }
+        lines
+    }
+}
+
+impl Renderable for PlanChecklist {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+
+        Paragraph::new(self.render_lines(area.width)).render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.render_lines(width).len() as u16
+    }
+}
+
+#[cfg(test)]
+#[path = "plan_checklist_tests.rs"]
+mod tests;
@@ -0,0 +1,92 @@
+use super::*;
+use insta::assert_snapshot;
+use pretty_assertions::assert_eq;
+
+fn step(step: &str, status: StepStatus) -> PlanItemArg {
+    PlanItemArg {
+        step: step.to_string(),
+        status,
+    }
+}
+
+fn render_rows(checklist: &PlanChecklist, width: u16) -> String {
+    let height = checklist.desired_height(width);
+    let area = Rect::new(0, 0, width, height);
+    let mut buf = Buffer::empty(area);
+    checklist.render(area, &mut buf);
+
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn desired_height_empty() {
+    let checklist = PlanChecklist::new();
+    assert_eq!(checklist.desired_height(/*width*/ 40), 0);
+}
+
+#[test]
+fn render_step_states() {
+    let mut checklist = PlanChecklist::new();
+    checklist.set_steps(vec![
+        step("Explore codebase", StepStatus::Completed),
+        step("Implement feature", StepStatus::InProgress),
+        step("Write tests", StepStatus::Pending),
+    ]);
+
+    assert_snapshot!(render_rows(&checklist, /*width*/ 40));
+}
+
+#[test]
+fn long_plan_is_windowed_around_first_open_step() {
+    let mut checklist = PlanChecklist::new();
+    checklist.set_steps(
+        (1..=9)
+            .map(|idx| {
+                let status = match idx {
+                    1..=4 => StepStatus::Completed,
+                    5 => StepStatus::InProgress,
+                    _ => StepStatus::Pending,
+                };
+                step(&format!("Step {idx}"), status)
+            })
+            .collect(),
+    );
+
+    assert_eq!(
+        render_rows(&checklist, /*width*/ 30),
+        [
+            "  Plan 4/9",
+            "    … 3 done",
+            "    ✔ Step 4",
+            "    › Step 5",
+            "    □ Step 6",
+            "    □ Step 7",
+            "    □ Step 8",
+            "    … +1 more",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn long_steps_are_truncated_to_one_row() {
+    let mut checklist = PlanChecklist::new();
+    checklist.set_steps(vec![step(
+        "Refactor the configuration loader",
+        StepStatus::InProgress,
+    )]);
+
+    assert_eq!(
+        render_rows(&checklist, /*width*/ 20),
+        ["  Plan 0/1", "    › Refactor the …"].join("\n")
+    );
+}
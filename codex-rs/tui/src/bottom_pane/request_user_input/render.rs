@@ -7,7 +7,6 @@ use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
 use std::borrow::Cow;
 use std::time::Instant;
-use unicode_width::UnicodeWidthChar;
 use unicode_width::UnicodeWidthStr;
 
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
@@ -18,6 +17,7 @@ use crate::bottom_pane::selection_popup_common::menu_surface_padding_height;
 use crate::bottom_pane::selection_popup_common::render_menu_surface;
 use crate::bottom_pane::selection_popup_common::render_rows;
 use crate::bottom_pane::selection_popup_common::wrap_styled_line;
+use crate::display_width::graphemes_with_width;
 use crate::render::renderable::Renderable;
 
 use super::DESIRED_SPACERS_BETWEEN_SECTIONS;
@@ -509,19 +509,18 @@ fn truncate_line_word_boundary_with_ellipsis(
 
     'outer: for (span_idx, span) in line.spans.iter().enumerate() {
         let text = span.content.as_ref();
-        for (byte_idx, ch) in text.char_indices() {
-            let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if used.saturating_add(ch_width) > limit {
+        for (byte_idx, grapheme, width) in graphemes_with_width(text) {
+            if used.saturating_add(width) > limit {
                 overflowed = true;
                 break 'outer;
             }
-            used = used.saturating_add(ch_width);
+            used = used.saturating_add(width);
             let bp = BreakPoint {
                 span_idx,
-                byte_end: byte_idx + ch.len_utf8(),
+                byte_end: byte_idx + grapheme.len(),
             };
             last_fit = Some(bp);
-            if ch.is_whitespace() {
+            if grapheme.chars().all(char::is_whitespace) {
                 last_word_break = Some(bp);
             }
         }
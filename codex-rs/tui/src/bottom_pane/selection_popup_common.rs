@@ -8,9 +8,9 @@ use ratatui::text::Span;
 use ratatui::widgets::Block;
 use ratatui::widgets::Widget;
 use std::borrow::Cow;
-use unicode_width::UnicodeWidthChar;
 use unicode_width::UnicodeWidthStr;
 
+use crate::display_width::graphemes_with_width;
 use crate::key_hint::KeyBinding;
 use crate::line_truncation::truncate_line_with_ellipsis_if_overflow;
 use crate::render::Insets;
@@ -448,33 +448,38 @@ fn build_full_line(row: &GenericDisplayRow, desc_col: usize) -> Line<'static> {
     let mut truncated = false;
 
     if let Some(idxs) = row.match_indices.as_ref() {
+        // Match indices are char offsets; a grapheme is highlighted when any of its chars matched.
         let mut idx_iter = idxs.iter().peekable();
-        for (char_idx, ch) in row.name.chars().enumerate() {
-            let ch_w = UnicodeWidthChar::width(ch).unwrap_or(0);
-            let next_width = used_width.saturating_add(ch_w);
+        let mut char_idx = 0usize;
+        for (_, grapheme, width) in graphemes_with_width(&row.name) {
+            let next_width = used_width.saturating_add(width);
             if next_width > name_limit {
                 truncated = true;
                 break;
             }
             used_width = next_width;
 
-            if idx_iter.peek().is_some_and(|next| **next == char_idx) {
-                idx_iter.next();
-                name_spans.push(ch.to_string().bold());
+            let char_end = char_idx + grapheme.chars().count();
+            let mut matched = false;
+            while idx_iter.next_if(|next| **next < char_end).is_some() {
+                matched = true;
+            }
+            char_idx = char_end;
+            if matched {
+                name_spans.push(grapheme.to_string().bold());
             } else {
-                name_spans.push(ch.to_string().into());
+                name_spans.push(grapheme.to_string().into());
             }
         }
     } else {
-        for ch in row.name.chars() {
-            let ch_w = UnicodeWidthChar::width(ch).unwrap_or(0);
-            let next_width = used_width.saturating_add(ch_w);
+        for (_, grapheme, width) in graphemes_with_width(&row.name) {
+            let next_width = used_width.saturating_add(width);
             if next_width > name_limit {
                 truncated = true;
                 break;
             }
             used_width = next_width;
-            name_spans.push(ch.to_string().into());
+            name_spans.push(grapheme.to_string().into());
         }
     }
 
@@ -10,6 +10,8 @@ use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::Paragraph;
 
+use crate::i18n::Msg;
+use crate::i18n::tr_args;
 use crate::live_wrap::take_prefix_by_width;
 use crate::render::renderable::Renderable;
 
@@ -48,10 +50,12 @@ impl UnifiedExecFooter {
         }
 
         let count = self.processes.len();
-        let plural = if count == 1 { "" } else { "s" };
-        Some(format!(
-            "{count} background terminal{plural} running · /ps to view · /stop to close"
-        ))
+        let msg = if count == 1 {
+            Msg::FooterBackgroundTerminalsOne
+        } else {
+            Msg::FooterBackgroundTerminalsOther
+        };
+        Some(tr_args(msg, &[("count", count.to_string().as_str())]))
     }
 
     fn render_lines(&self, width: u16) -> Vec<Line<'static>> {
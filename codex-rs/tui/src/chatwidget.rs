@@ -266,6 +266,7 @@ use crate::auto_review_denials;
 use crate::auto_review_denials::RecentAutoReviewDenials;
 use crate::bottom_pane::ApplyPatchApprovalRequest;
 use crate::bottom_pane::ApprovalRequest;
+use crate::bottom_pane::ApprovalTimeout;
 use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
@@ -308,11 +309,14 @@ use crate::history_cell::McpInvocation;
 use crate::history_cell::McpToolCallCell;
 use crate::history_cell::PlainHistoryCell;
 use crate::history_cell::WebSearchCell;
+use crate::i18n::Msg;
+use crate::i18n::tr;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::key_hint::KeyBindingListExt;
 use crate::keymap::ChatKeymap;
 use crate::keymap::RuntimeKeymap;
+use crate::keymap::primary_binding;
 use crate::render::Insets;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::FlexRenderable;
@@ -326,10 +330,12 @@ use crate::status_indicator_widget::STATUS_DETAILS_DEFAULT_MAX_LINES;
 use crate::status_indicator_widget::StatusDetailsCapitalization;
 use crate::text_formatting::truncate_text;
 use crate::tui::FrameRequester;
+mod activity;
 mod command_lifecycle;
 mod connectors;
 mod constructor;
 use self::connectors::ConnectorsState;
+mod env_snapshot;
 mod exec_state;
 use self::exec_state::RunningCommand;
 use self::exec_state::UnifiedExecProcessSummary;
@@ -356,6 +362,7 @@ mod keymap_picker;
 mod mcp_startup;
 use self::mcp_startup::McpStartupStatus;
 mod pets;
+mod session_artifacts;
 mod session_flow;
 mod session_header;
 use self::session_header::SessionHeader;
@@ -394,6 +401,7 @@ pub(crate) use self::rate_limits::limit_label_for_window;
 mod reasoning_shortcuts;
 mod rendering;
 mod replay;
+mod response_language;
 mod review;
 mod review_popups;
 use self::review::ReviewState;
@@ -413,7 +421,9 @@ use self::status_state::TerminalTitleStatusKind;
 mod status_controls;
 mod status_surfaces;
 mod streaming;
+mod system_prompt_context;
 use self::status_surfaces::CachedProjectRootName;
+mod todo_popups;
 mod tokens;
 pub(crate) use self::tokens::TokenActivityView;
 mod tool_lifecycle;
@@ -584,6 +594,9 @@ pub(crate) struct ChatWidget {
     pending_stream_consolidations: usize,
     /// Holds the platform clipboard lease so copied text remains available while supported.
     clipboard_lease: Option<crate::clipboard_copy::ClipboardLease>,
+    /// Whether `/language` changed the response language after the thread started, so turns
+    /// carry the new preference to the model.
+    response_language_changed: bool,
     copy_last_response_binding: Vec<KeyBinding>,
     running_commands: HashMap<String, RunningCommand>,
     collab_agent_metadata: HashMap<ThreadId, AgentMetadata>,
@@ -633,6 +646,13 @@ pub(crate) struct ChatWidget {
     reasoning_header: Option<String>,
     // Preserves reasoning-summary part boundaries for transcript-only recording.
     reasoning_summary_parts: Vec<String>,
+    // Expand/collapse flag shared by the latest turn's reasoning cells; kept after the turn ends so
+    // the toggle still reaches it.
+    reasoning_panel: Option<history_cell::ReasoningPanel>,
+    // Whether the current turn already drew its "Reasoning" row.
+    reasoning_panel_open: bool,
+    // `app.toggle_reasoning` binding named in new panels' "Reasoning" row.
+    reasoning_panel_hint_binding: Option<KeyBinding>,
     status_state: StatusState,
     review: ReviewState,
     // Active hook runs render in a dedicated live cell so they can run alongside tools.
@@ -1317,6 +1337,7 @@ impl ChatWidget {
 
         // User messages reset separator state so the next agent response doesn't add a stray break.
         self.transcript.needs_final_message_separator = false;
+        self.reasoning_panel_open = false;
     }
 
     /// Exit the UI immediately without waiting for shutdown.
@@ -1401,7 +1422,24 @@ impl ChatWidget {
                 command_display: process.command_display.clone(),
                 recent_chunks: process.recent_chunks.clone(),
             })
-            .collect();
+            .collect::<Vec<_>>();
+        if crate::tmux::split_views_enabled() && !processes.is_empty() {
+            let text = history_cell::unified_exec_processes_text(&processes);
+            match crate::tmux::open_in_split(
+                "codex background terminals",
+                &text,
+                self.config.cwd.as_path(),
+            ) {
+                Ok(()) => {
+                    self.add_info_message(
+                        "Opened background terminals in a tmux split.".to_string(),
+                        /*hint*/ None,
+                    );
+                    return;
+                }
+                Err(err) => tracing::warn!("failed to open /ps in a tmux split: {err}"),
+            }
+        }
         self.add_to_history(history_cell::new_unified_exec_processes_output(processes));
     }
 
@@ -1410,7 +1448,7 @@ impl ChatWidget {
         self.unified_exec_processes.clear();
         self.sync_unified_exec_footer();
         self.add_info_message(
-            "Stopping all background terminals.".to_string(),
+            tr(Msg::StatusStoppingBackgroundTerminals),
             /*hint*/ None,
         );
     }
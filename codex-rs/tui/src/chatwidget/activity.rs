@@ -0,0 +1,139 @@
+//! "Currently doing" headers for the status indicator.
+//!
+//! While a tool item runs, the status row names it ("Running tests in codex-core", "Editing
+//! tui/src/app.rs") instead of the generic "Working". Items can overlap, so each running item
+//! keeps its header and the most recently started one is shown until it finishes. Background
+//! terminals are left out: the unified-exec footer and wait status already describe them, and they
+//! can outlive the turn step that started them.
+
+use std::path::Path;
+
+use codex_app_server_protocol::CommandAction;
+use codex_app_server_protocol::ThreadItem;
+
+use super::exec_state::is_unified_exec_source;
+use crate::diff_render::display_path_for;
+use crate::exec_command::split_command_string;
+use crate::exec_command::strip_bash_lc_and_escape;
+
+/// Headers of the tool items currently running, in start order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct ToolActivityStatus {
+    entries: Vec<ToolActivityEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ToolActivityEntry {
+    id: String,
+    header: String,
+}
+
+impl ToolActivityStatus {
+    pub(super) fn start(&mut self, id: String, header: String) {
+        self.entries.retain(|entry| entry.id != id);
+        self.entries.push(ToolActivityEntry { id, header });
+    }
+
+    /// Returns whether `id` was running.
+    pub(super) fn finish(&mut self, id: &str) -> bool {
+        let original_len = self.entries.len();
+        self.entries.retain(|entry| entry.id != id);
+        self.entries.len() != original_len
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Header of the most recently started item that is still running.
+    pub(super) fn current(&self) -> Option<&str> {
+        self.entries.last().map(|entry| entry.header.as_str())
+    }
+}
+
+/// Status header describing `item`, or `None` for items that are not tool activity.
+pub(super) fn activity_header(item: &ThreadItem, cwd: &Path) -> Option<String> {
+    match item {
+        ThreadItem::CommandExecution { source, .. } if is_unified_exec_source(*source) => None,
+        ThreadItem::CommandExecution {
+            command,
+            command_actions,
+            ..
+        } => Some(command_header(command, command_actions)),
+        ThreadItem::FileChange { changes, .. } => match changes.as_slice() {
+            [] => None,
+            [change] => Some(format!(
+                "Editing {}",
+                display_path_for(Path::new(&change.path), cwd)
+            )),
+            changes => Some(format!("Editing {} files", changes.len())),
+        },
+        ThreadItem::McpToolCall { server, tool, .. } => Some(format!("Calling {server}.{tool}")),
+        ThreadItem::DynamicToolCall { tool, .. } => Some(format!("Calling {tool}")),
+        ThreadItem::WebSearch(_) => Some("Searching the web".to_string()),
+        ThreadItem::ImageGeneration(_) => Some("Generating image".to_string()),
+        ThreadItem::UserMessage { .. }
+        | ThreadItem::HookPrompt { .. }
+        | ThreadItem::AgentMessage { .. }
+        | ThreadItem::Plan { .. }
+        | ThreadItem::Reasoning { .. }
+        | ThreadItem::CollabAgentToolCall { .. }
+        | ThreadItem::SubAgentActivity { .. }
+        | ThreadItem::ImageView { .. }
+        | ThreadItem::Sleep(_)
+        | ThreadItem::EnteredReviewMode { .. }
+        | ThreadItem::ExitedReviewMode { .. }
+        | ThreadItem::ContextCompaction { .. } => None,
+    }
+}
+
+/// Describes a shell command by its first parsed action; pipelines are named after their head.
+fn command_header(command: &str, actions: &[CommandAction]) -> String {
+    match actions.first() {
+        Some(CommandAction::Read { name, .. }) => format!("Reading {name}"),
+        Some(CommandAction::ListFiles {
+            path: Some(path), ..
+        }) => format!("Listing files in {path}"),
+        Some(CommandAction::ListFiles { path: None, .. }) => "Listing files".to_string(),
+        Some(CommandAction::Search {
+            query: Some(query), ..
+        }) => format!("Searching for {query}"),
+        Some(CommandAction::Search { query: None, .. }) => "Searching files".to_string(),
+        Some(CommandAction::Unknown { command }) => program_header(command),
+        None => program_header(&strip_bash_lc_and_escape(&split_command_string(command))),
+    }
+}
+
+/// Names the program being run, recognizing the common test runners.
+fn program_header(command: &str) -> String {
+    let words: Vec<&str> = command.split_whitespace().collect();
+    let Some(program) = words
+        .first()
+        .map(|word| word.rsplit('/').next().unwrap_or(word))
+    else {
+        return "Running command".to_string();
+    };
+    let runs_tests = matches!(
+        (program, words.get(1).copied()),
+        ("cargo", Some("test" | "nextest"))
+            | (
+                "go" | "npm" | "pnpm" | "yarn" | "bun" | "just" | "make",
+                Some("test")
+            )
+            | ("pytest" | "jest" | "vitest", _)
+    );
+    if !runs_tests {
+        return format!("Running {program}");
+    }
+    match words
+        .windows(2)
+        .find(|pair| matches!(pair[0], "-p" | "--package"))
+    {
+        Some(pair) => format!("Running tests in {}", pair[1]),
+        None => "Running tests".to_string(),
+    }
+}
+
+#[cfg(test)]
+#[path = "activity_tests.rs"]
+mod tests;
@@ -0,0 +1,104 @@
+use super::*;
+use codex_app_server_protocol::FileUpdateChange;
+use codex_app_server_protocol::PatchApplyStatus;
+use codex_app_server_protocol::PatchChangeKind;
+use pretty_assertions::assert_eq;
+
+fn unknown(command: &str) -> CommandAction {
+    CommandAction::Unknown {
+        command: command.to_string(),
+    }
+}
+
+#[test]
+fn test_runners_name_the_package_under_test() {
+    assert_eq!(
+        command_header("", &[unknown("cargo test -p codex-core --lib")]),
+        "Running tests in codex-core"
+    );
+    assert_eq!(
+        command_header("", &[unknown("cargo nextest run")]),
+        "Running tests"
+    );
+    assert_eq!(
+        command_header("", &[unknown("/usr/bin/pytest -k slow")]),
+        "Running tests"
+    );
+    assert_eq!(
+        command_header("", &[unknown("cargo build --release")]),
+        "Running cargo"
+    );
+}
+
+#[test]
+fn parsed_actions_describe_reads_and_searches() {
+    assert_eq!(
+        command_header(
+            "",
+            &[CommandAction::Search {
+                command: "rg foo".to_string(),
+                query: Some("foo".to_string()),
+                path: None,
+            }]
+        ),
+        "Searching for foo"
+    );
+    assert_eq!(
+        command_header(
+            "",
+            &[CommandAction::ListFiles {
+                command: "ls src".to_string(),
+                path: Some("src".to_string()),
+            }]
+        ),
+        "Listing files in src"
+    );
+}
+
+#[test]
+fn unparsed_commands_fall_back_to_the_program_name() {
+    assert_eq!(
+        command_header("bash -lc 'go test ./...'", &[]),
+        "Running tests"
+    );
+    assert_eq!(command_header("", &[]), "Running command");
+}
+
+#[test]
+fn file_changes_name_the_edited_path() {
+    let change = |path: &str| FileUpdateChange {
+        path: path.to_string(),
+        kind: PatchChangeKind::Add,
+        diff: String::new(),
+    };
+    let item = |changes| ThreadItem::FileChange {
+        id: "patch-1".to_string(),
+        changes,
+        status: PatchApplyStatus::InProgress,
+    };
+    let cwd = Path::new("/repo");
+
+    assert_eq!(
+        activity_header(&item(vec![change("/repo/tui/src/app.rs")]), cwd),
+        Some("Editing tui/src/app.rs".to_string())
+    );
+    assert_eq!(
+        activity_header(&item(vec![change("/repo/a.rs"), change("/repo/b.rs")]), cwd),
+        Some("Editing 2 files".to_string())
+    );
+}
+
+#[test]
+fn latest_running_item_wins_until_it_finishes() {
+    let mut status = ToolActivityStatus::default();
+    status.start("a".to_string(), "Running tests".to_string());
+    status.start("b".to_string(), "Reading lib.rs".to_string());
+    assert_eq!(status.current(), Some("Reading lib.rs"));
+
+    assert!(status.finish("b"));
+    assert_eq!(status.current(), Some("Running tests"));
+    assert!(!status.finish("b"));
+
+    status.clear();
+    assert_eq!(status.current(), None);
+}
@@ -96,7 +96,7 @@ impl ChatWidget {
             self.status_state.terminal_title_status_kind =
                 TerminalTitleStatusKind::WaitingForBackgroundTerminal;
             self.set_status(
-                "Waiting for background terminal".to_string(),
+                tr(Msg::StatusWaitingForBackgroundTerminal),
                 command_display.clone(),
                 StatusDetailsCapitalization::Preserve,
                 /*details_max_lines*/ 1,
@@ -255,6 +255,9 @@ impl ChatWidget {
         // Ensure the status indicator is visible while the command runs.
         self.bottom_pane.ensure_status_indicator();
         let parsed_cmd = self.annotate_skill_reads_in_parsed_cmd(parsed_cmd);
+        self.transcript
+            .turn_citations
+            .record_command_reads(&parsed_cmd, self.config.cwd.as_path());
         self.running_commands.insert(
             id.clone(),
             RunningCommand {
@@ -264,6 +267,9 @@ impl ChatWidget {
             },
         );
         let is_wait_interaction = matches!(source, ExecCommandSource::UnifiedExecInteraction);
+        if !is_wait_interaction {
+            self.transcript.last_command = Some(strip_bash_lc_and_escape(&command));
+        }
         let command_display = command.join(" ");
         let should_suppress_unified_wait = is_wait_interaction
             && self
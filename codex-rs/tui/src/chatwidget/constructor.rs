@@ -77,6 +77,12 @@ impl ChatWidget {
             .as_ref()
             .map(|keymap| keymap.app.copy.clone())
             .unwrap_or_else(|| default_keymap.app.copy.clone());
+        let reasoning_panel_hint_binding = primary_binding(
+            runtime_keymap
+                .as_ref()
+                .map(|keymap| &keymap.app.toggle_reasoning)
+                .unwrap_or(&default_keymap.app.toggle_reasoning),
+        );
         let chat_keymap = runtime_keymap
             .as_ref()
             .map(|keymap| keymap.chat.clone())
@@ -149,6 +155,7 @@ impl ChatWidget {
             plan_stream_controller: None,
             pending_stream_consolidations: 0,
             clipboard_lease: None,
+            response_language_changed: false,
             copy_last_response_binding,
             running_commands: HashMap::new(),
             collab_agent_metadata: HashMap::new(),
@@ -181,6 +188,9 @@ impl ChatWidget {
             reasoning_buffer: String::new(),
             reasoning_header: None,
             reasoning_summary_parts: Vec::new(),
+            reasoning_panel: None,
+            reasoning_panel_open: false,
+            reasoning_panel_hint_binding,
             status_state: StatusState::default(),
             review: ReviewState::default(),
             active_hook_cell: None,
@@ -255,6 +265,12 @@ impl ChatWidget {
         widget
             .bottom_pane
             .set_vim_enabled(widget.config.tui_vim_mode_default);
+        widget
+            .bottom_pane
+            .set_approval_timeout(ApprovalTimeout::from_config(
+                widget.config.tui_approval_timeout_seconds,
+                widget.config.tui_approval_timeout_action,
+            ));
         widget
             .bottom_pane
             .set_status_line_enabled(!widget.configured_status_line_items().is_empty());
@@ -0,0 +1,30 @@
+//! `/env`: show the OS, toolchain versions, and build-related env vars of this machine.
+
+use codex_utils_env_snapshot::EnvSnapshot;
+
+use super::*;
+
+impl ChatWidget {
+    pub(crate) fn start_env_snapshot(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let cwd = self
+            .current_cwd
+            .clone()
+            .unwrap_or_else(|| self.config.cwd.to_path_buf());
+        tokio::spawn(async move {
+            let snapshot = EnvSnapshot::capture(&cwd).await;
+            tx.send(AppEvent::EnvSnapshotResult(snapshot));
+        });
+    }
+
+    pub(crate) fn add_env_snapshot(&mut self, snapshot: EnvSnapshot) {
+        let mut lines: Vec<Line<'static>> = vec!["/env".magenta().into()];
+        lines.extend(
+            snapshot
+                .summary_lines()
+                .into_iter()
+                .map(|line| Line::from(format!("  {line}")).dim()),
+        );
+        self.add_plain_history_lines(lines);
+    }
+}
@@ -5,6 +5,8 @@ use std::collections::VecDeque;
 
 use super::user_messages::remap_colliding_paste_placeholders;
 use super::*;
+use crate::bottom_pane::ChatComposer;
+use crate::composer_draft::ComposerDraft;
 
 impl ChatWidget {
     pub(crate) fn set_initial_user_message_submit_suppressed(&mut self, suppressed: bool) {
@@ -30,6 +32,16 @@ impl ChatWidget {
         }
     }
 
+    /// Queues `/goal <objective>` so the goal is set, and its first turn starts, once the
+    /// session is configured.
+    pub(crate) fn queue_initial_goal(&mut self, objective: String) {
+        self.queue_user_message_with_options(
+            UserMessage::from(format!("/goal {objective}")),
+            QueuedInputAction::ParseSlash,
+            Vec::new(),
+        );
+    }
+
     pub(super) fn pop_next_queued_user_message(
         &mut self,
     ) -> Option<(QueuedUserMessage, UserMessageHistoryRecord)> {
@@ -469,4 +481,36 @@ impl ChatWidget {
     pub(crate) fn set_queue_autosend_suppressed(&mut self, suppressed: bool) {
         self.input_queue.suppress_queue_autosend = suppressed;
     }
+
+    /// Unsent input worth autosaving: queued follow-ups in send order plus the composer text.
+    pub(crate) fn composer_draft(&self) -> ComposerDraft {
+        let queued_messages = self
+            .input_queue
+            .rejected_steers_queue
+            .iter()
+            .map(|user_message| user_message.text.clone())
+            .chain(self.input_queue.queued_user_messages.iter().map(|queued| {
+                let (text, _) = ChatComposer::expand_pending_pastes(
+                    &queued.text,
+                    queued.text_elements.clone(),
+                    &queued.pending_pastes,
+                );
+                text
+            }))
+            .collect();
+        ComposerDraft {
+            queued_messages,
+            composer_text: self.bottom_pane.composer_text_with_pending(),
+        }
+    }
+
+    /// Puts a draft recovered from an interrupted run back in the composer for review.
+    pub(crate) fn restore_recovered_composer_draft(&mut self, draft: ComposerDraft) {
+        self.bottom_pane
+            .set_composer_text(draft.into_composer_text(), Vec::new(), Vec::new());
+        self.add_info_message(
+            "Draft restored".to_string(),
+            Some("Unsent input from the last session is back in the composer.".to_string()),
+        );
+    }
 }
@@ -137,6 +137,9 @@ impl ChatWidget {
             );
             return (false, None);
         }
+        let now = std::time::SystemTime::now();
+        self.add_time_gap_separator_if_idle(now);
+        self.transcript.last_activity_at = Some(now);
         let UserMessage {
             text,
             local_images,
@@ -264,12 +264,98 @@ impl ChatWidget {
         &mut self,
         copy_fn: impl FnOnce(&str) -> Result<Option<crate::clipboard_copy::ClipboardLease>, String>,
     ) {
-        match self.transcript.last_agent_markdown.clone() {
-            Some(markdown) if !markdown.is_empty() => match copy_fn(&markdown) {
+        let markdown = self.transcript.last_agent_markdown.clone();
+        self.copy_text_with(
+            markdown,
+            "Copied last message to clipboard",
+            "No agent response to copy",
+            copy_fn,
+        );
+    }
+
+    /// Copy the aggregate diff of the current turn to the system clipboard.
+    pub(crate) fn copy_turn_diff(&mut self) {
+        self.copy_turn_diff_with(crate::clipboard_copy::copy_to_clipboard);
+    }
+
+    pub(super) fn copy_turn_diff_with(
+        &mut self,
+        copy_fn: impl FnOnce(&str) -> Result<Option<crate::clipboard_copy::ClipboardLease>, String>,
+    ) {
+        let diff = self.transcript.turn_diff.clone();
+        self.copy_text_with(
+            diff,
+            "Copied turn diff to clipboard",
+            "No changes in this turn to copy",
+            copy_fn,
+        );
+    }
+
+    /// Copy the most recently executed command line to the system clipboard.
+    pub(crate) fn copy_last_command(&mut self) {
+        self.copy_last_command_with(crate::clipboard_copy::copy_to_clipboard);
+    }
+
+    pub(super) fn copy_last_command_with(
+        &mut self,
+        copy_fn: impl FnOnce(&str) -> Result<Option<crate::clipboard_copy::ClipboardLease>, String>,
+    ) {
+        let command = self.transcript.last_command.clone();
+        self.copy_text_with(
+            command,
+            "Copied last command to clipboard",
+            "No command to copy",
+            copy_fn,
+        );
+    }
+
+    /// Copy the last code block of the last agent response to the system clipboard.
+    pub(crate) fn copy_last_code_block(&mut self) {
+        self.copy_last_code_block_with(crate::clipboard_copy::copy_to_clipboard);
+    }
+
+    pub(super) fn copy_last_code_block_with(
+        &mut self,
+        copy_fn: impl FnOnce(&str) -> Result<Option<crate::clipboard_copy::ClipboardLease>, String>,
+    ) {
+        let code = self
+            .transcript
+            .last_agent_markdown
+            .as_deref()
+            .and_then(crate::markdown_render::last_code_block);
+        self.copy_text_with(
+            code,
+            "Copied code block to clipboard",
+            "No code block in the last response to copy",
+            copy_fn,
+        );
+    }
+
+    /// Copy a code block chosen in the transcript overlay to the system clipboard.
+    pub(crate) fn copy_code_block(&mut self, code: &str) {
+        self.copy_text_with(
+            Some(code.to_string()),
+            "Copied code block to clipboard",
+            "Code block is empty",
+            crate::clipboard_copy::copy_to_clipboard,
+        );
+    }
+
+    /// Shared `/copy` flow: keeps the clipboard lease alive on success and reports the outcome in
+    /// history. A failed copy leaves the previous lease in place.
+    fn copy_text_with(
+        &mut self,
+        text: Option<String>,
+        copied_message: &str,
+        empty_message: &str,
+        copy_fn: impl FnOnce(&str) -> Result<Option<crate::clipboard_copy::ClipboardLease>, String>,
+    ) {
+        match text {
+            Some(text) if !text.is_empty() => match copy_fn(&text) {
                 Ok(lease) => {
                     self.clipboard_lease = lease;
                     self.add_to_history(history_cell::new_info_event(
-                        "Copied last message to clipboard".into(),
+                        copied_message.to_string(),
                         /*hint*/ None,
                     ));
                 }
@@ -277,9 +363,7 @@ impl ChatWidget {
                     "Copy failed: {error}"
                 ))),
             },
-            _ => self.add_to_history(history_cell::new_error_event(
-                "No agent response to copy".into(),
-            )),
+            _ => self.add_to_history(history_cell::new_error_event(empty_message.to_string())),
         }
         self.request_redraw();
     }
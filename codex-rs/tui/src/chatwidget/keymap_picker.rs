@@ -18,6 +18,7 @@ use super::ChatWidget;
 use super::queued_message_edit_hint_binding;
 use crate::app_event::KeymapEditIntent;
 use crate::keymap::RuntimeKeymap;
+use crate::keymap::primary_binding;
 use crate::keymap_setup;
 
 impl ChatWidget {
@@ -168,6 +169,7 @@ impl ChatWidget {
     ) {
         self.config.tui_keymap = keymap_config;
         self.copy_last_response_binding = runtime_keymap.app.copy.clone();
+        self.reasoning_panel_hint_binding = primary_binding(&runtime_keymap.app.toggle_reasoning);
         self.chat_keymap = runtime_keymap.chat.clone();
         self.queued_message_edit_hint_binding = queued_message_edit_hint_binding(
             &self.chat_keymap.edit_queued_message,
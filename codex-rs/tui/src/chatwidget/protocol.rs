@@ -241,6 +241,9 @@ impl ChatWidget {
         // this TUI already rendered locally. Once that turn ends, another
         // client can submit the same text and it still needs its own user cell.
         self.last_rendered_user_message_display = None;
+        if replay_kind.is_none() {
+            self.transcript.last_activity_at = Some(std::time::SystemTime::now());
+        }
         match notification.turn.status {
             TurnStatus::Completed => {
                 let last_agent_message =
@@ -318,6 +321,10 @@ impl ChatWidget {
         notification: ItemStartedNotification,
         from_replay: bool,
     ) {
+        let activity = (!from_replay && self.bottom_pane.is_task_running())
+            .then(|| activity::activity_header(&notification.item, &self.config.cwd))
+            .flatten()
+            .map(|header| (notification.item.id().to_string(), header));
         match notification.item {
             item @ ThreadItem::CommandExecution { .. } => self.on_command_execution_started(item),
             ThreadItem::FileChange { id: _, changes, .. } => {
@@ -357,6 +364,10 @@ impl ChatWidget {
             }
             _ => {}
         }
+        if let Some((id, header)) = activity {
+            self.status_state.tool_activity.start(id, header);
+            self.show_tool_activity_header();
+        }
     }
 
     fn handle_item_completed_notification(
@@ -364,10 +375,18 @@ impl ChatWidget {
         notification: ItemCompletedNotification,
         replay_kind: Option<ReplayKind>,
     ) {
+        let finished_activity = replay_kind.is_none()
+            && self
+                .status_state
+                .tool_activity
+                .finish(notification.item.id());
         self.handle_thread_item(
             notification.item,
             notification.turn_id,
             replay_kind.map_or(ThreadItemRenderSource::Live, ThreadItemRenderSource::Replay),
         );
+        if finished_activity && self.bottom_pane.is_task_running() {
+            self.restore_reasoning_status_header();
+        }
     }
 }
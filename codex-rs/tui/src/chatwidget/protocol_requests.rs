@@ -159,6 +159,7 @@ impl ChatWidget {
 
     pub(super) fn on_turn_diff(&mut self, unified_diff: String) {
         debug!("TurnDiffEvent: {unified_diff}");
+        self.transcript.turn_diff = Some(unified_diff);
         self.refresh_status_line();
     }
 
@@ -3,6 +3,8 @@
 //! This module rehydrates turns and items into transcript state while avoiding
 //! live-only side effects.
 
+use std::time::SystemTime;
+
 use super::*;
 
 impl ChatWidget {
@@ -28,6 +30,12 @@ impl ChatWidget {
                 completed_at,
                 duration_ms,
             } = turn;
+            let turn_time =
+                started_at.and_then(crate::transcript_timestamps::system_time_from_unix_seconds);
+            if let Some(turn_time) = turn_time {
+                self.add_time_gap_separator_if_idle(turn_time);
+            }
+            self.app_event_tx.send(AppEvent::SetHistoryClock(turn_time));
             if matches!(status, TurnStatus::InProgress) {
                 self.turn_lifecycle.last_turn_id = Some(turn_id.clone());
                 self.last_non_retry_error = None;
@@ -65,6 +73,26 @@ impl ChatWidget {
                     Some(replay_kind),
                 );
             }
+            if let Some(activity_at) = completed_at
+                .or(started_at)
+                .and_then(crate::transcript_timestamps::system_time_from_unix_seconds)
+            {
+                self.transcript.last_activity_at = Some(activity_at);
+            }
+        }
+        self.app_event_tx.send(AppEvent::SetHistoryClock(None));
+    }
+
+    /// Inserts a "2 hours later" separator when the conversation was idle for longer than
+    /// [`crate::transcript_timestamps::GAP_THRESHOLD`] before `at`.
+    pub(super) fn add_time_gap_separator_if_idle(&mut self, at: SystemTime) {
+        let Some(last_activity_at) = self.transcript.last_activity_at else {
+            return;
+        };
+        if let Ok(gap) = at.duration_since(last_activity_at)
+            && gap >= crate::transcript_timestamps::GAP_THRESHOLD
+        {
+            self.add_to_history(history_cell::TimeGapSeparator::new(gap));
         }
     }
 
@@ -183,8 +211,11 @@ impl ChatWidget {
                     self.enter_review_mode_with_hint(review, /*from_replay*/ true);
                 }
             }
-            ThreadItem::ExitedReviewMode { .. } => {
+            ThreadItem::ExitedReviewMode { review, .. } => {
                 self.exit_review_mode_after_item();
+                if !from_replay {
+                    self.open_review_findings_popup(&review);
+                }
             }
             ThreadItem::ContextCompaction { .. } => {
                 self.add_info_message("Context compacted".to_string(), /*hint*/ None);
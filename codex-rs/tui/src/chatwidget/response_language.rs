@@ -0,0 +1,75 @@
+//! `/language`: choose the language replies come back in for the current project.
+//!
+//! The choice is saved under the project's `[projects."<path>"]` table so later sessions start
+//! with it. The running thread learns about it through the `additional_context` of its next
+//! turns, and the UI switches to that language's locale catalog if there is one.
+
+use std::collections::HashMap;
+
+use codex_app_server_protocol::AdditionalContextEntry;
+use codex_app_server_protocol::AdditionalContextKind;
+
+use super::*;
+
+const LANGUAGE_USAGE: &str = "Usage: /language [<language>|clear]";
+const RESPONSE_LANGUAGE_CONTEXT_KEY: &str = "response_language";
+
+impl ChatWidget {
+    pub(crate) fn add_response_language_output(&mut self) {
+        let message = match self.config.response_language.as_deref() {
+            Some(language) => format!("Replies are in {language}"),
+            None => {
+                "No response language set; replies follow the language you write in".to_string()
+            }
+        };
+        self.add_info_message(message, Some(LANGUAGE_USAGE.to_string()));
+    }
+
+    /// Sets the response language, or clears it with `None`, for this session and the project.
+    pub(crate) fn set_response_language(&mut self, language: Option<String>) {
+        if language == self.config.response_language {
+            self.add_response_language_output();
+            return;
+        }
+        self.config.response_language = language.clone();
+        self.response_language_changed = true;
+        if self.config.tui_language.is_none() {
+            let warning = match language.as_deref() {
+                Some(language) => {
+                    crate::i18n::switch_to(language, self.config.codex_home.as_path())
+                }
+                None => {
+                    crate::i18n::init(/*configured*/ None, self.config.codex_home.as_path())
+                }
+            };
+            if let Some(warning) = warning {
+                self.add_error_message(warning);
+            }
+        }
+        self.app_event_tx
+            .send(AppEvent::PersistResponseLanguage { language });
+    }
+
+    /// Context for the next turn that tells the model about a response language chosen with
+    /// `/language` after the thread started. The app server only forwards it when it changes.
+    pub(crate) fn response_language_context(
+        &self,
+    ) -> Option<HashMap<String, AdditionalContextEntry>> {
+        if !self.response_language_changed {
+            return None;
+        }
+        let value = match self.config.response_language.as_deref() {
+            Some(language) => format!(
+                "The user now prefers replies in {language}. Write your messages to the user in that language from now on, and keep code, identifiers, commands, and file paths as they are."
+            ),
+            None => "The user cleared their response language preference. Reply in the language the user writes in from now on.".to_string(),
+        };
+        Some(HashMap::from([(
+            RESPONSE_LANGUAGE_CONTEXT_KEY.to_string(),
+            AdditionalContextEntry {
+                value,
+                kind: AdditionalContextKind::Application,
+            },
+        )]))
+    }
+}
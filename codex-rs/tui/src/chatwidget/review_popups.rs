@@ -1,6 +1,8 @@
-//! Review preset selection and custom review prompt surfaces.
+//! Review preset selection, custom review prompt, and review findings surfaces.
 
 use super::*;
+use crate::review_findings::ReviewFindingEntry;
+use crate::review_findings::parse_review_findings;
 
 impl ChatWidget {
     pub(crate) fn open_review_popup(&mut self) {
@@ -29,6 +31,15 @@ impl ChatWidget {
             ..Default::default()
         });
 
+        items.push(SelectionItem {
+            name: "Review staged changes".to_string(),
+            actions: vec![Box::new(move |tx: &AppEventSender| {
+                tx.review(ReviewTarget::StagedChanges);
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
         items.push(SelectionItem {
             name: "Review a commit".to_string(),
             actions: vec![Box::new({
@@ -125,6 +136,47 @@ impl ChatWidget {
         });
     }
 
+    /// Lists the findings of a finished review, grouped by file and ordered by priority.
+    /// Selecting a finding asks the agent to apply its suggested fix.
+    pub(crate) fn open_review_findings_popup(&mut self, review: &str) {
+        let findings = parse_review_findings(review);
+        if findings.is_empty() {
+            return;
+        }
+        let title = format!("Review findings ({})", findings.len());
+        let items = findings
+            .into_iter()
+            .map(|finding| {
+                let search_value = format!("{} {}", finding.title, finding.location());
+                SelectionItem {
+                    name: finding.title.clone(),
+                    description: Some(finding.location()),
+                    selected_description: Some(finding.body.clone()),
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::ApplyReviewFindingFix(finding.clone()));
+                    })],
+                    dismiss_on_select: true,
+                    search_value: Some(search_value),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(title),
+            subtitle: Some("Enter applies the suggested fix".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search findings".to_string()),
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn apply_review_finding_fix(&mut self, finding: ReviewFindingEntry) {
+        self.queue_user_message(UserMessage::from(finding.fix_prompt()));
+    }
+
     pub(crate) fn show_review_custom_prompt(&mut self) {
         let tx = self.app_event_tx.clone();
         let view = CustomPromptView::new(
@@ -0,0 +1,73 @@
+//! `/artifacts`: list the files the agent registered as artifacts of this session and open one.
+
+use codex_utils_session_artifacts::load_artifacts;
+use url::Url;
+
+use super::*;
+use crate::diff_render::display_path_for;
+
+impl ChatWidget {
+    pub(crate) fn open_artifacts_popup(&mut self) {
+        let artifacts = match self.thread_id {
+            Some(thread_id) => {
+                match load_artifacts(self.config.codex_home.as_path(), &thread_id.to_string()) {
+                    Ok(artifacts) => artifacts,
+                    Err(err) => {
+                        self.add_error_message(format!("Failed to read session artifacts: {err}"));
+                        return;
+                    }
+                }
+            }
+            None => Vec::new(),
+        };
+        if artifacts.is_empty() {
+            self.add_info_message(
+                "No artifacts registered in this session.".to_string(),
+                Some("Codex registers reports, plots, and other generated files here.".to_string()),
+            );
+            return;
+        }
+
+        let cwd = self
+            .current_cwd
+            .clone()
+            .unwrap_or_else(|| self.config.cwd.to_path_buf());
+        let items = artifacts
+            .into_iter()
+            .map(|artifact| {
+                let location = display_path_for(&artifact.path, &cwd);
+                let url = Url::from_file_path(&artifact.path)
+                    .ok()
+                    .filter(|_| artifact.path.is_file());
+                let is_disabled = url.is_none();
+                SelectionItem {
+                    search_value: Some(format!("{} {location}", artifact.name)),
+                    name: artifact.name,
+                    description: Some(location),
+                    selected_description: artifact.description,
+                    is_disabled,
+                    disabled_reason: is_disabled.then(|| "file no longer exists".to_string()),
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        if let Some(url) = &url {
+                            tx.send(AppEvent::OpenUrlInBrowser {
+                                url: url.to_string(),
+                            });
+                        }
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(format!("Session artifacts ({})", items.len())),
+            subtitle: Some("Enter opens the file with your default viewer".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search artifacts".to_string()),
+            ..Default::default()
+        });
+    }
+}
@@ -14,6 +14,8 @@ use crate::bottom_pane::slash_commands::SlashCommandItem;
 use crate::bottom_pane::slash_commands::find_slash_command;
 use crate::goal_display::GOAL_USAGE;
 use crate::goal_files::GoalDraft;
+use crate::log_tail::LOGS_USAGE;
+use crate::log_tail::LogFilter;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum SlashCommandDispatchSource {
@@ -35,6 +37,7 @@ const SIDE_STARTING_CONTEXT_LABEL: &str = "Side starting...";
 const SIDE_SLASH_COMMAND_UNAVAILABLE_HINT: &str =
     "Press Ctrl+C to return to the main thread first.";
 const GOAL_USAGE_HINT: &str = "Example: /goal improve benchmark coverage";
+const COPY_USAGE: &str = "Usage: /copy [last|diff|command|code]";
 const RAW_USAGE: &str = "Usage: /raw [on|off]";
 const USAGE_CHATGPT_LOGIN_REQUIRED: &str = "Sign in with ChatGPT to use /usage.";
 
@@ -280,6 +283,9 @@ impl ChatWidget {
                 self.open_personality_popup();
                 self.defer_input_until_settings_applied();
             }
+            SlashCommand::Language => {
+                self.add_response_language_output();
+            }
             SlashCommand::Plan => {
                 self.apply_plan_slash_command();
             }
@@ -401,24 +407,63 @@ impl ChatWidget {
                     .current_cwd
                     .clone()
                     .unwrap_or_else(|| self.config.cwd.to_path_buf());
+                let related_repos = self.config.related_repos.clone();
                 tokio::spawn(async move {
                     let text = match runner {
-                        Some(runner) => match get_git_diff(runner.as_ref(), &cwd).await {
-                            Ok((is_git_repo, diff_text)) => {
-                                if is_git_repo {
-                                    diff_text
-                                } else {
-                                    "`/diff` — _not inside a git repository_".to_string()
+                        Some(runner) => {
+                            let mut text = match get_git_diff(runner.as_ref(), &cwd).await {
+                                Ok((is_git_repo, diff_text)) => {
+                                    if is_git_repo {
+                                        diff_text
+                                    } else {
+                                        "`/diff` — _not inside a git repository_".to_string()
+                                    }
                                 }
+                                Err(e) => format!("Failed to compute diff: {e}"),
+                            };
+                            // Related repos follow the workspace diff so one view covers the change.
+                            for repo in related_repos {
+                                let section = match get_git_diff(runner.as_ref(), &repo.path).await
+                                {
+                                    Ok((true, diff_text)) if diff_text.is_empty() => {
+                                        "(no changes)\n".to_string()
+                                    }
+                                    Ok((true, diff_text)) => diff_text,
+                                    Ok((false, _)) => "(not a git repository)\n".to_string(),
+                                    Err(e) => format!("Failed to compute diff: {e}\n"),
+                                };
+                                text.push_str(&format!(
+                                    "\n# {} ({})\n{section}",
+                                    repo.name,
+                                    repo.path.display()
+                                ));
                             }
-                            Err(e) => format!("Failed to compute diff: {e}"),
-                        },
+                            text
+                        }
                         None => "Failed to compute diff: workspace command runner unavailable"
                             .to_string(),
                     };
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Todos => {
+                self.start_todo_scan();
+            }
+            SlashCommand::Env => {
+                self.start_env_snapshot();
+            }
+            SlashCommand::Artifacts => {
+                self.open_artifacts_popup();
+            }
+            SlashCommand::Template => {
+                let available = crate::workflow_templates::available_templates(
+                    self.config.codex_home.as_path(),
+                );
+                self.add_error_message(format!(
+                    "Usage: /template <name>. Available: {}.",
+                    available.join(", ")
+                ));
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
@@ -458,6 +503,13 @@ impl ChatWidget {
             SlashCommand::DebugConfig => {
                 self.add_debug_config_output();
             }
+            SlashCommand::Context => {
+                self.start_system_prompt_context();
+            }
+            SlashCommand::Logs => {
+                self.app_event_tx
+                    .send(AppEvent::ShowLogs(LogFilter::default()));
+            }
             SlashCommand::Title => {
                 self.open_terminal_title_setup();
             }
@@ -702,6 +754,17 @@ impl ChatWidget {
                 }
                 _ => self.add_error_message("Usage: /keymap [debug]".to_string()),
             },
+            SlashCommand::Logs => match LogFilter::parse(trimmed) {
+                Some(filter) => self.app_event_tx.send(AppEvent::ShowLogs(filter)),
+                None => self.add_error_message(LOGS_USAGE.to_string()),
+            },
+            SlashCommand::Copy => match trimmed.to_ascii_lowercase().as_str() {
+                "last" => self.copy_last_agent_markdown(),
+                "diff" => self.copy_turn_diff(),
+                "command" => self.copy_last_command(),
+                "code" => self.copy_last_code_block(),
+                _ => self.add_error_message(COPY_USAGE.to_string()),
+            },
             SlashCommand::Raw => match trimmed.to_ascii_lowercase().as_str() {
                 "on" => {
                     self.set_raw_output_mode_and_notify(/*enabled*/ true);
@@ -713,6 +776,13 @@ impl ChatWidget {
                 }
                 _ => self.add_error_message(RAW_USAGE.to_string()),
             },
+            SlashCommand::Language => {
+                if trimmed.eq_ignore_ascii_case("clear") {
+                    self.set_response_language(/*language*/ None);
+                } else {
+                    self.set_response_language(Some(trimmed.to_string()));
+                }
+            }
             SlashCommand::Rename if !trimmed.is_empty() => {
                 if !self.ensure_thread_rename_allowed() {
                     return;
@@ -905,6 +975,15 @@ impl ChatWidget {
             SlashCommand::Pets if !trimmed.is_empty() => {
                 self.select_pet_by_id(args);
             }
+            SlashCommand::Template if !trimmed.is_empty() => {
+                match crate::workflow_templates::load_workflow_template(
+                    self.config.codex_home.as_path(),
+                    trimmed,
+                ) {
+                    Ok(template) => self.bottom_pane.insert_composer_template(template),
+                    Err(err) => self.add_error_message(err.to_string()),
+                }
+            }
             _ => self.dispatch_command(cmd),
         }
         if source == SlashCommandDispatchSource::Live && cmd != SlashCommand::Goal {
@@ -1059,6 +1138,8 @@ impl ChatWidget {
             | SlashCommand::Status
             | SlashCommand::Usage
             | SlashCommand::DebugConfig
+            | SlashCommand::Context
+            | SlashCommand::Logs
             | SlashCommand::Ps
             | SlashCommand::Stop
             | SlashCommand::MemoryDrop
@@ -1071,6 +1152,10 @@ impl ChatWidget {
             | SlashCommand::Raw
             | SlashCommand::Vim
             | SlashCommand::Diff
+            | SlashCommand::Todos
+            | SlashCommand::Env
+            | SlashCommand::Artifacts
+            | SlashCommand::Template
             | SlashCommand::App
             | SlashCommand::Rename
             | SlashCommand::TestApproval => QueueDrain::Continue,
@@ -1086,6 +1171,7 @@ impl ChatWidget {
             | SlashCommand::Review
             | SlashCommand::Model
             | SlashCommand::Personality
+            | SlashCommand::Language
             | SlashCommand::Plan
             | SlashCommand::Goal
             | SlashCommand::Side
@@ -1,5 +1,8 @@
 //! Status indicator and terminal-title state for `ChatWidget`.
 
+use super::activity::ToolActivityStatus;
+use crate::i18n::Msg;
+use crate::i18n::tr;
 use crate::status_indicator_widget::STATUS_DETAILS_DEFAULT_MAX_LINES;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -12,7 +15,7 @@ pub(super) struct StatusIndicatorState {
 impl StatusIndicatorState {
     pub(super) fn working() -> Self {
         Self {
-            header: String::from("Working"),
+            header: tr(Msg::StatusWorking),
             details: None,
             details_max_lines: STATUS_DETAILS_DEFAULT_MAX_LINES,
         }
@@ -112,6 +115,7 @@ impl PendingGuardianReviewStatus {
 pub(super) struct StatusState {
     pub(super) current_status: StatusIndicatorState,
     pub(super) pending_guardian_review_status: PendingGuardianReviewStatus,
+    pub(super) tool_activity: ToolActivityStatus,
     pub(super) terminal_title_status_kind: TerminalTitleStatusKind,
     pub(super) retry_status_header: Option<String>,
     pub(super) pending_status_indicator_restore: bool,
@@ -122,6 +126,7 @@ impl Default for StatusState {
         Self {
             current_status: StatusIndicatorState::working(),
             pending_guardian_review_status: PendingGuardianReviewStatus::default(),
+            tool_activity: ToolActivityStatus::default(),
             terminal_title_status_kind: TerminalTitleStatusKind::Working,
             retry_status_header: None,
             pending_status_indicator_restore: false,
@@ -372,6 +372,10 @@ impl ChatWidget {
         {
             return Some(TERMINAL_TITLE_ACTION_REQUIRED_INTERVAL);
         }
+        // The action-required blink keeps running while idle; it is how an away user notices.
+        if crate::idle_pause::background_work_paused() {
+            return None;
+        }
 
         self.should_animate_terminal_title_spinner_with_selections(selections)
             .then_some(TERMINAL_TITLE_SPINNER_INTERVAL)
@@ -588,6 +592,7 @@ impl ChatWidget {
             .is_some()
             || self.status_line_workspace_messages_disabled
             || !self.has_codex_backend_auth
+            || crate::idle_pause::background_work_paused()
         {
             return false;
         }
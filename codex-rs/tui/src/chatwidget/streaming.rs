@@ -6,7 +6,29 @@
 use super::*;
 
 impl ChatWidget {
+    /// Names the most recent running tool item in the status row. Guardian reviews keep their
+    /// own header until they finish.
+    pub(super) fn show_tool_activity_header(&mut self) -> bool {
+        if !self.status_state.pending_guardian_review_status.is_empty() {
+            return false;
+        }
+        let Some(header) = self
+            .status_state
+            .tool_activity
+            .current()
+            .map(str::to_string)
+        else {
+            return false;
+        };
+        self.status_state.terminal_title_status_kind = TerminalTitleStatusKind::Working;
+        self.set_status_header(header);
+        true
+    }
+
     pub(super) fn restore_reasoning_status_header(&mut self) {
+        if self.show_tool_activity_header() {
+            return;
+        }
         if self.reasoning_header.is_none() {
             self.reasoning_header = extract_first_bold(&self.reasoning_buffer);
         }
@@ -15,7 +37,7 @@ impl ChatWidget {
             self.set_status_header(header);
         } else if self.bottom_pane.is_task_running() {
             self.status_state.terminal_title_status_kind = TerminalTitleStatusKind::Working;
-            self.set_status_header(String::from("Working"));
+            self.set_status_header(tr(Msg::StatusWorking));
         }
     }
 
@@ -278,8 +300,31 @@ impl ChatWidget {
         }
         if !self.reasoning_summary_parts.is_empty() {
             let reasoning_parts = std::mem::take(&mut self.reasoning_summary_parts);
-            let cell = history_cell::new_reasoning_summary_block(reasoning_parts, &self.config.cwd);
-            self.add_boxed_history(cell);
+            let (header, content) = history_cell::split_reasoning_summary_parts(&reasoning_parts);
+            if header.is_empty() {
+                let cell =
+                    history_cell::new_reasoning_summary_block(reasoning_parts, &self.config.cwd);
+                self.add_boxed_history(cell);
+            } else {
+                // Later summaries of the turn join the panel whose row the first one drew.
+                let shows_header = !self.reasoning_panel_open;
+                let panel = match &self.reasoning_panel {
+                    Some(panel) if self.reasoning_panel_open => panel.clone(),
+                    _ => history_cell::ReasoningPanel::new(
+                        self.config.tui_expand_reasoning_summaries,
+                        self.reasoning_panel_hint_binding,
+                    ),
+                };
+                self.reasoning_panel = Some(panel.clone());
+                self.reasoning_panel_open = true;
+                self.add_to_history(history_cell::ReasoningPanelCell::new(
+                    header,
+                    content,
+                    &self.config.cwd,
+                    panel,
+                    shows_header,
+                ));
+            }
         }
         self.reasoning_buffer.clear();
         self.reasoning_header = None;
@@ -287,6 +332,17 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    /// Expands or collapses the latest turn's reasoning summaries.
+    ///
+    /// Returns `false` when no turn has shown reasoning yet. Cells already in scrollback change how
+    /// they render, so the caller must reflow the transcript.
+    pub(crate) fn toggle_reasoning_panel(&self) -> bool {
+        self.reasoning_panel
+            .as_ref()
+            .map(history_cell::ReasoningPanel::toggle)
+            .is_some()
+    }
+
     pub(super) fn on_reasoning_section_break(&mut self) {
         // Start a new reasoning block for header extraction and accumulate transcript.
         if !self.reasoning_buffer.is_empty() {
@@ -327,11 +383,18 @@ impl ChatWidget {
             }
         }
         let parsed = parse_assistant_markdown(&message, self.config.cwd.as_path());
-        self.finalize_completed_assistant_message(Some(parsed.visible_markdown.as_str()));
-        if matches!(item.phase, Some(MessagePhase::FinalAnswer) | None)
-            && !parsed.visible_markdown.is_empty()
-        {
-            self.record_agent_markdown(&parsed.visible_markdown);
+        self.finalize_completed_assistant_message(Some(&parsed.visible_markdown));
+        if matches!(item.phase, Some(MessagePhase::FinalAnswer) | None) {
+            if let Some(sources) = self
+                .transcript
+                .turn_citations
+                .sources_cell(&parsed.visible_markdown, self.config.cwd.as_path())
+            {
+                self.add_to_history(sources);
+            }
+            if !parsed.visible_markdown.is_empty() {
+                self.record_agent_markdown(&parsed.visible_markdown);
+            }
         }
         if !from_replay
             && let Some(cwd) = parsed.last_created_branch_cwd()
@@ -0,0 +1,81 @@
+//! `/context`: show the system prompt the session runs with and the project
+//! additions from `[system_prompt]` that went into it.
+
+use codex_utils_string::approx_token_count;
+
+use super::*;
+
+impl ChatWidget {
+    pub(crate) fn start_system_prompt_context(&mut self) {
+        let Some(rollout_path) = self.rollout_path() else {
+            self.add_system_prompt_context(Err(
+                "The system prompt is recorded when the session starts; try again in a moment."
+                    .to_string(),
+            ));
+            return;
+        };
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            // The rollout's session metadata holds the instructions the
+            // session actually sent, including any project additions.
+            let result = match codex_rollout::read_session_meta_line(&rollout_path).await {
+                Ok(meta_line) => meta_line
+                    .meta
+                    .base_instructions
+                    .map(|instructions| instructions.text)
+                    .ok_or_else(|| "This session did not record its system prompt.".to_string()),
+                Err(err) => Err(format!("Failed to read the system prompt: {err}")),
+            };
+            tx.send(AppEvent::SystemPromptContextResult(result));
+        });
+    }
+
+    pub(crate) fn add_system_prompt_context(&mut self, result: Result<String, String>) {
+        let additions = &self.config.system_prompt;
+        let mut lines: Vec<Line<'static>> = vec!["/context".magenta().into()];
+
+        if additions.append.is_none() && additions.replace.is_empty() {
+            lines.push("  No [system_prompt] additions configured.".dim().into());
+        } else {
+            lines.push(
+                format!(
+                    "  [system_prompt] additions (budget ~{} tokens):",
+                    additions.max_tokens
+                )
+                .into(),
+            );
+            if let Some(append) = &additions.append {
+                lines.push(format!("    append: ~{} tokens", approx_token_count(append)).into());
+            }
+            for (heading, text) in &additions.replace {
+                lines.push(
+                    format!(
+                        "    replace \"{heading}\": ~{} tokens",
+                        approx_token_count(text)
+                    )
+                    .into(),
+                );
+            }
+        }
+
+        match result {
+            Ok(prompt) => {
+                lines.push(
+                    format!(
+                        "  Effective system prompt: ~{} tokens",
+                        approx_token_count(&prompt)
+                    )
+                    .into(),
+                );
+                lines.push("".into());
+                lines.extend(
+                    prompt
+                        .lines()
+                        .map(|line| Line::from(line.to_string()).dim()),
+                );
+            }
+            Err(message) => lines.push(format!("  {message}").dim().into()),
+        }
+        self.add_plain_history_lines(lines);
+    }
+}
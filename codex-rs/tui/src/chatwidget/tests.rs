@@ -237,6 +237,7 @@ mod composer_submission;
 #[path = "tests/config_errors_tests.rs"]
 mod config_errors;
 mod exec_flow;
+mod frame_harness;
 mod goal_menu;
 mod goal_validation;
 mod guardian;
@@ -1216,6 +1216,44 @@ async fn live_app_server_turn_completion_repairs_dropped_message_deltas() {
     assert_eq!(consolidations.len(), 1);
 }
 
+#[tokio::test]
+async fn live_app_server_final_answer_lists_cited_pages() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+
+    handle_turn_started(&mut chat, "turn-1");
+    chat.on_web_search_end(
+        "search-1".to_string(),
+        String::new(),
+        codex_app_server_protocol::WebSearchAction::OpenPage {
+            url: Some("https://docs.rs/tokio/".to_string()),
+        },
+    );
+    while rx.try_recv().is_ok() {}
+
+    let answer = "Spawning is covered at https://docs.rs/tokio/.\n";
+    handle_agent_message_delta(&mut chat, answer);
+    chat.run_commit_tick();
+    while rx.try_recv().is_ok() {}
+    complete_assistant_message(&mut chat, "msg-1", answer, Some(MessagePhase::FinalAnswer));
+
+    let mut consolidated = Vec::new();
+    let mut inserted = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        match event {
+            AppEvent::ConsolidateAgentMessage { source, .. } => consolidated.push(source),
+            AppEvent::InsertHistoryCell(cell) => {
+                inserted.push(lines_to_single_string(&cell.display_lines(/*width*/ 80)));
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(consolidated, vec![answer.to_string()]);
+    assert_eq!(
+        inserted.last().map(String::as_str),
+        Some("Sources\n  1. https://docs.rs/tokio/\n")
+    );
+}
+
 #[tokio::test]
 async fn live_app_server_stream_recovery_restores_previous_status_header() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
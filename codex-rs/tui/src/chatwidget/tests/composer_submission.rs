@@ -1778,3 +1778,38 @@ async fn interrupt_prepends_queued_messages_before_existing_composer_text() {
 
     let _ = drain_insert_history(&mut rx);
 }
+
+#[tokio::test]
+async fn composer_draft_round_trips_queued_messages_into_the_composer() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    chat.thread_id = Some(ThreadId::new());
+    handle_turn_started(&mut chat, "turn-1");
+    chat.bottom_pane
+        .set_composer_text("run the tests".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+    chat.bottom_pane
+        .set_composer_text("then fix the".to_string(), Vec::new(), Vec::new());
+
+    let draft = chat.composer_draft();
+    assert_eq!(
+        crate::composer_draft::ComposerDraft {
+            queued_messages: vec!["run the tests".to_string()],
+            composer_text: "then fix the".to_string(),
+        },
+        draft
+    );
+
+    let (mut restored, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    drain_insert_history(&mut rx);
+    restored.restore_recovered_composer_draft(draft);
+
+    assert_eq!(
+        "run the tests\n\nthen fix the",
+        restored.bottom_pane.composer_text()
+    );
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(rendered.contains("Draft restored"), "{rendered}");
+}
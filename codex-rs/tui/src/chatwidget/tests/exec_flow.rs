@@ -291,6 +291,22 @@ async fn unified_exec_begin_restores_working_status_snapshot() {
     );
 }
 
+#[tokio::test]
+async fn exec_begin_names_running_command_in_status_header() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    chat.on_task_started();
+
+    let begin = begin_exec(&mut chat, "call-1", "cargo test -p codex-core");
+    let status = chat
+        .bottom_pane
+        .status_widget()
+        .expect("status indicator should be visible");
+    assert_eq!(status.header(), "Running tests in codex-core");
+
+    end_exec(&mut chat, begin, "ok", "", /*exit_code*/ 0);
+    assert_eq!(chat.status_state.current_status.header, "Working");
+}
+
 #[tokio::test]
 async fn exec_history_cell_shows_working_then_completed() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
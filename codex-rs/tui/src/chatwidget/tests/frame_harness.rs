@@ -0,0 +1,228 @@
+//! Full-frame rendering driven by scripted events.
+//!
+//! [`FrameHarness`] feeds a script of server notifications, key presses, pastes, and commit ticks
+//! through the same `ChatWidget` entry points that `App` calls from its event loop, and keeps the
+//! history cells the widget emits. [`FrameHarness::render`] then draws a whole terminal screen in a
+//! headless VT100 backend: committed history in the scrollback above, the live viewport (active
+//! cell, status row, composer, footer) at the bottom. Rendering the same script at several widths
+//! with [`FrameHarness::render_widths`] catches layout regressions that per-widget snapshots miss,
+//! such as history wrapping or the footer colliding with the composer on a narrow screen.
+//!
+//! Frames taken while a turn is running include the elapsed-time status row, so snapshot scripts
+//! should finish their turns before rendering.
+
+use std::sync::Arc;
+
+use crate::history_cell::HistoryCell;
+
+use super::*;
+
+pub(super) enum ScriptStep {
+    Notification(ServerNotification),
+    Key(KeyEvent),
+    Paste(String),
+    /// Commits streamed output the way the frame loop's commit animation does.
+    CommitTick,
+}
+
+pub(super) struct FrameHarness {
+    pub(super) chat: ChatWidget,
+    app_events: tokio::sync::mpsc::UnboundedReceiver<AppEvent>,
+    _ops: tokio::sync::mpsc::UnboundedReceiver<Op>,
+    history: Vec<Arc<dyn HistoryCell>>,
+}
+
+impl FrameHarness {
+    pub(super) async fn new() -> Self {
+        let (mut chat, app_events, ops) = make_chatwidget_manual(/*model_override*/ None).await;
+        chat.thread_id = Some(ThreadId::new());
+        chat.show_welcome_banner = false;
+        Self {
+            chat,
+            app_events,
+            _ops: ops,
+            history: Vec::new(),
+        }
+    }
+
+    pub(super) fn thread_id(&self) -> String {
+        self.chat
+            .thread_id
+            .map(|id| id.to_string())
+            .unwrap_or_default()
+    }
+
+    pub(super) fn run(&mut self, script: impl IntoIterator<Item = ScriptStep>) {
+        for step in script {
+            match step {
+                ScriptStep::Notification(notification) => self
+                    .chat
+                    .handle_server_notification(notification, /*replay_kind*/ None),
+                ScriptStep::Key(key) => self.chat.handle_key_event(key),
+                ScriptStep::Paste(text) => self.chat.handle_paste(text),
+                ScriptStep::CommitTick => self.chat.on_commit_tick(),
+            }
+            self.collect_history();
+        }
+    }
+
+    /// Keeps the history cells `App` would write to scrollback.
+    fn collect_history(&mut self) {
+        while let Ok(event) = self.app_events.try_recv() {
+            match event {
+                AppEvent::InsertHistoryCell(cell) => self.history.push(cell.into()),
+                AppEvent::ConsolidateAgentMessage {
+                    deferred_history_cell,
+                    ..
+                } => {
+                    if let Some(cell) = deferred_history_cell {
+                        self.history.push(cell.into());
+                    }
+                    self.chat.note_stream_consolidation_completed();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Draws the screen of a `width` x `height` terminal after the script so far.
+    pub(super) fn render(&self, width: u16, height: u16) -> String {
+        let viewport_height = self.chat.desired_height(width).min(height);
+        let mut term =
+            crate::custom_terminal::Terminal::with_options(VT100Backend::new(width, height))
+                .expect("terminal");
+        term.set_viewport_area(Rect::new(
+            0,
+            height - viewport_height,
+            width,
+            viewport_height,
+        ));
+
+        let wrap_width = self.chat.history_wrap_width(width);
+        for (index, cell) in self.history.iter().enumerate() {
+            let mut lines = cell.display_lines(wrap_width);
+            if index > 0 && !cell.is_stream_continuation() && !lines.is_empty() {
+                lines.insert(0, "".into());
+            }
+            crate::insert_history::insert_history_lines(&mut term, lines)
+                .expect("insert history lines");
+        }
+        term.draw(|frame| self.chat.render(frame.area(), frame.buffer_mut()))
+            .expect("draw frame");
+
+        normalize_snapshot_paths(term.backend().vt100().screen().contents())
+    }
+
+    /// Renders one frame per width, each under a header naming its size.
+    pub(super) fn render_widths(&self, widths: &[u16], height: u16) -> String {
+        widths
+            .iter()
+            .map(|&width| format!("--- {width}x{height} ---\n{}", self.render(width, height)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn agent_message_script(thread_id: &str, text: &str) -> Vec<ScriptStep> {
+    let turn_id = "turn-1";
+    let mut script = vec![ScriptStep::Notification(ServerNotification::TurnStarted(
+        TurnStartedNotification {
+            thread_id: thread_id.to_string(),
+            turn: app_server_turn(
+                turn_id,
+                AppServerTurnStatus::InProgress,
+                /*duration_ms*/ None,
+                /*error*/ None,
+            ),
+        },
+    ))];
+    for word in text.split_inclusive(' ') {
+        script.push(ScriptStep::Notification(
+            ServerNotification::AgentMessageDelta(
+                codex_app_server_protocol::AgentMessageDeltaNotification {
+                    thread_id: thread_id.to_string(),
+                    turn_id: turn_id.to_string(),
+                    item_id: "msg-1".to_string(),
+                    delta: word.to_string(),
+                },
+            ),
+        ));
+        script.push(ScriptStep::CommitTick);
+    }
+    script.push(ScriptStep::Notification(ServerNotification::ItemCompleted(
+        ItemCompletedNotification {
+            thread_id: thread_id.to_string(),
+            turn_id: turn_id.to_string(),
+            completed_at_ms: 0,
+            item: AppServerThreadItem::AgentMessage {
+                id: "msg-1".to_string(),
+                text: text.to_string(),
+                phase: None,
+                memory_citation: None,
+            },
+        },
+    )));
+    script.push(ScriptStep::Notification(ServerNotification::TurnCompleted(
+        TurnCompletedNotification {
+            thread_id: thread_id.to_string(),
+            turn: app_server_turn(
+                turn_id,
+                AppServerTurnStatus::Completed,
+                /*duration_ms*/ Some(1_000),
+                /*error*/ None,
+            ),
+        },
+    )));
+    script
+}
+
+const LONG_ANSWER: &str = "The footer, the status bar, and committed history all share the \
+    bottom of a short terminal, so this answer is long enough to wrap several times on a narrow \
+    screen. END-OF-ANSWER\n";
+
+#[tokio::test]
+async fn scripted_frames_are_identical_across_runs() {
+    let mut frames = Vec::new();
+    for _ in 0..2 {
+        let mut harness = FrameHarness::new().await;
+        let script = agent_message_script(&harness.thread_id(), LONG_ANSWER);
+        harness.run(script);
+        frames.push(harness.render_widths(&[40, 80, 120], /*height*/ 24));
+    }
+
+    assert_eq!(frames[0], frames[1]);
+}
+
+#[tokio::test]
+async fn narrow_frames_keep_history_and_composer_on_screen() {
+    let mut harness = FrameHarness::new().await;
+    let script = agent_message_script(&harness.thread_id(), LONG_ANSWER);
+    harness.run(script);
+    harness.run([
+        ScriptStep::Paste("draft reply".to_string()),
+        ScriptStep::Key(KeyEvent::new(KeyCode::Char('!'), KeyModifiers::NONE)),
+    ]);
+
+    for width in [40, 80] {
+        let frame = harness.render(width, /*height*/ 24);
+        let rows: Vec<&str> = frame.lines().collect();
+        assert!(
+            rows.iter()
+                .all(|row| row.chars().count() <= usize::from(width)),
+            "rows wider than {width} columns:\n{frame}"
+        );
+        assert!(
+            frame.contains("END-OF-ANSWER"),
+            "the end of the answer should stay visible at width {width}:\n{frame}"
+        );
+        let answer_row = rows
+            .iter()
+            .position(|row| row.contains("END-OF-ANSWER"))
+            .expect("answer row");
+        let draft_row = rows
+            .iter()
+            .position(|row| row.contains("draft reply"))
+            .unwrap_or_else(|| panic!("composer draft missing at width {width}:\n{frame}"));
+        assert!(answer_row < draft_row, "history renders above the composer");
+    }
+}
@@ -584,6 +584,13 @@ pub(super) fn replay_entered_review_mode(chat: &mut ChatWidget, review: impl Int
 }
 
 pub(super) fn handle_exited_review_mode(chat: &mut ChatWidget) {
+    handle_exited_review_mode_with_review(chat, String::new());
+}
+
+pub(super) fn handle_exited_review_mode_with_review(
+    chat: &mut ChatWidget,
+    review: impl Into<String>,
+) {
     chat.handle_server_notification(
         ServerNotification::ItemCompleted(ItemCompletedNotification {
             thread_id: thread_id(chat),
@@ -595,7 +602,7 @@ pub(super) fn handle_exited_review_mode(chat: &mut ChatWidget) {
             completed_at_ms: 0,
             item: AppServerThreadItem::ExitedReviewMode {
                 id: "review-end".to_string(),
-                review: String::new(),
+                review: review.into(),
             },
         }),
         /*replay_kind*/ None,
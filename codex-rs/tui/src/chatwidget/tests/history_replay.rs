@@ -1328,6 +1328,71 @@ async fn live_reasoning_summary_drops_empty_parts_without_losing_content() {
     assert_eq!(rendered, "• done\n");
 }
 
+#[tokio::test]
+async fn live_reasoning_summaries_collapse_into_one_toggleable_row_per_turn() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    chat.show_welcome_banner = false;
+    chat.on_task_started();
+    let _ = drain_insert_history(&mut rx);
+
+    for (item_id, summary) in [
+        (
+            "reasoning-1",
+            "**Inspecting**\n\nReading the config loader.",
+        ),
+        (
+            "reasoning-2",
+            "**Planning**\n\nAdding a field to the struct.",
+        ),
+    ] {
+        chat.handle_server_notification(
+            ServerNotification::ReasoningSummaryTextDelta(ReasoningSummaryTextDeltaNotification {
+                thread_id: "thread-1".to_string(),
+                turn_id: "turn-1".to_string(),
+                item_id: item_id.to_string(),
+                delta: summary.to_string(),
+                summary_index: 0,
+            }),
+            /*replay_kind*/ None,
+        );
+        chat.handle_server_notification(
+            ServerNotification::ItemCompleted(ItemCompletedNotification {
+                thread_id: "thread-1".to_string(),
+                turn_id: "turn-1".to_string(),
+                completed_at_ms: 0,
+                item: AppServerThreadItem::Reasoning {
+                    id: item_id.to_string(),
+                    summary: vec![summary.to_string()],
+                    content: Vec::new(),
+                },
+            }),
+            /*replay_kind*/ None,
+        );
+    }
+
+    let mut cells = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::InsertHistoryCell(cell) = event {
+            cells.push(cell);
+        }
+    }
+    let visible = |cells: &[Box<dyn HistoryCell>]| {
+        cells
+            .iter()
+            .map(|cell| lines_to_single_string(&cell.display_lines(/*width*/ 80)))
+            .collect::<String>()
+    };
+    assert_eq!(visible(&cells), "• Reasoning (⌥ + t to expand)\n");
+
+    assert!(chat.toggle_reasoning_panel());
+    assert_eq!(
+        visible(&cells),
+        "• Reasoning (⌥ + t to collapse)\n\
+         • Reading the config loader.\n\
+         • Adding a field to the struct.\n"
+    );
+}
+
 #[tokio::test]
 async fn thread_snapshot_replayed_turn_started_marks_task_running() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
@@ -1439,3 +1504,45 @@ async fn stream_recovery_restores_previous_status_header() {
     assert_eq!(status.details(), None);
     assert!(chat.status_state.retry_status_header.is_none());
 }
+
+#[tokio::test]
+async fn replayed_turns_after_a_long_idle_gap_are_separated() {
+    let (mut chat, mut rx, _ops) = make_chatwidget_manual(/*model_override*/ None).await;
+    let turn = |turn_id: &str, started_at: i64, text: &str| AppServerTurn {
+        items: vec![AppServerThreadItem::AgentMessage {
+            id: format!("{turn_id}-answer"),
+            text: text.to_string(),
+            phase: Some(MessagePhase::FinalAnswer),
+            memory_citation: None,
+        }],
+        started_at: Some(started_at),
+        completed_at: Some(started_at + 60),
+        ..app_server_turn(
+            turn_id,
+            AppServerTurnStatus::Completed,
+            /*duration_ms*/ None,
+            /*error*/ None,
+        )
+    };
+
+    chat.replay_thread_turns(
+        vec![
+            turn("turn-1", 1_000, "first answer"),
+            turn("turn-2", 1_000 + 5 * 60, "second answer"),
+            turn("turn-3", 1_000 + 5 * 60 + 2 * 60 * 60 + 60, "third answer"),
+        ],
+        ReplayKind::ResumeInitialMessages,
+    );
+
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let first = rendered.find("first answer").expect("first answer");
+    let second = rendered.find("second answer").expect("second answer");
+    let gap = rendered.find("2 hours later").expect("gap separator");
+    let third = rendered.find("third answer").expect("third answer");
+    assert!(first < second && second < gap && gap < third, "{rendered}");
+    assert_eq!(rendered.matches("later").count(), 1, "{rendered}");
+}
@@ -1738,3 +1738,57 @@ async fn plan_update_renders_history_cell() {
     assert!(blob.contains("Implement feature"));
     assert!(blob.contains("Write tests"));
 }
+
+#[tokio::test]
+async fn plan_update_during_turn_shows_live_checklist() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    chat.on_task_started();
+    drain_insert_history(&mut rx);
+
+    chat.on_plan_update(UpdatePlanArgs {
+        explanation: None,
+        plan: vec![
+            PlanItemArg {
+                step: "Explore codebase".into(),
+                status: StepStatus::Completed,
+            },
+            PlanItemArg {
+                step: "Implement feature".into(),
+                status: StepStatus::InProgress,
+            },
+        ],
+    });
+
+    let mut transcript = String::new();
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::InsertHistoryCell(cell) = event {
+            assert!(
+                cell.display_lines(/*width*/ 80).is_empty(),
+                "plan update should stay out of scrollback during the turn"
+            );
+            transcript.push_str(&lines_to_single_string(
+                &cell.transcript_lines(/*width*/ 80),
+            ));
+        }
+    }
+    assert!(
+        transcript.contains("Updated Plan"),
+        "missing transcript record: {transcript:?}"
+    );
+
+    let rendered = render_bottom_popup(&chat, /*width*/ 80);
+    assert!(
+        rendered.contains("Plan 1/2"),
+        "missing checklist: {rendered:?}"
+    );
+    assert!(rendered.contains("› Implement feature"));
+
+    chat.on_task_complete(
+        /*last_agent_message*/ None, /*duration_ms*/ None, /*from_replay*/ false,
+    );
+    let rendered = render_bottom_popup(&chat, /*width*/ 80);
+    assert!(
+        !rendered.contains("Implement feature"),
+        "checklist should clear when the turn ends: {rendered:?}"
+    );
+}
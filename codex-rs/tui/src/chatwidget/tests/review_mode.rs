@@ -1051,7 +1051,8 @@ async fn review_popup_custom_prompt_action_sends_event() {
     // Open the preset selection popup
     chat.open_review_popup();
 
-    // Move selection down to the fourth item: "Custom review instructions"
+    // Move selection down to the fifth item: "Custom review instructions"
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
     chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
     chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
     chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
@@ -1069,6 +1070,37 @@ async fn review_popup_custom_prompt_action_sends_event() {
     assert!(found, "expected OpenReviewCustomPrompt event to be sent");
 }
 
+/// A finished live review lists its findings by file, and Enter asks the agent to apply the
+/// selected finding's fix.
+#[tokio::test]
+async fn review_findings_popup_applies_selected_fix() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    let review = "One crash and a typo.\n\nFull review comments:\n\n\
+- [P1] Missing bounds check — /repo/src/lib.rs:10-12\n  Index 0 is read before checking length.\n\n\
+- [P3] Typo in log message — /repo/src/a.rs:1-1\n  Spelling.";
+
+    handle_exited_review_mode_with_review(&mut chat, review);
+
+    let popup = render_bottom_popup(&chat, /*width*/ 100);
+    assert!(popup.contains("Review findings (2)"), "{popup}");
+    let typo = popup
+        .find("Typo in log message")
+        .expect("typo finding listed");
+    let bounds = popup
+        .find("Missing bounds check")
+        .expect("bounds finding listed");
+    assert!(typo < bounds, "findings should be grouped by file: {popup}");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut applied = None;
+    while let Ok(ev) = rx.try_recv() {
+        if let AppEvent::ApplyReviewFindingFix(finding) = ev {
+            applied = Some(finding.location());
+        }
+    }
+    assert_eq!(applied.as_deref(), Some("/repo/src/a.rs:1-1"));
+}
+
 /// The commit picker shows only commit subjects (no timestamps).
 #[tokio::test]
 async fn review_commit_picker_shows_subjects_without_timestamps() {
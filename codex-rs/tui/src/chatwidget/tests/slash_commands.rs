@@ -1960,6 +1960,90 @@ async fn slash_copy_stores_clipboard_lease_and_preserves_it_on_failure() {
     );
 }
 
+#[tokio::test]
+async fn slash_copy_diff_command_and_code_copy_turn_state() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    chat.transcript.last_agent_markdown =
+        Some("Run:\n\n```sh\ncargo fmt\ncargo test\n```\n".to_string());
+    chat.transcript.turn_diff = Some("diff --git a/lib.rs b/lib.rs\n".to_string());
+    begin_exec(&mut chat, "call-1", "cargo test -p codex-tui");
+    drain_insert_history(&mut rx);
+
+    chat.copy_turn_diff_with(|diff| {
+        assert_eq!(diff, "diff --git a/lib.rs b/lib.rs\n");
+        Ok(None)
+    });
+    chat.copy_last_command_with(|command| {
+        assert_eq!(command, "cargo test -p codex-tui");
+        Ok(None)
+    });
+    chat.copy_last_code_block_with(|code| {
+        assert_eq!(code, "cargo fmt\ncargo test");
+        Ok(None)
+    });
+
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(
+        rendered.contains("Copied turn diff to clipboard")
+            && rendered.contains("Copied last command to clipboard")
+            && rendered.contains("Copied code block to clipboard"),
+        "expected all success messages, got {rendered:?}"
+    );
+}
+
+#[tokio::test]
+async fn slash_language_sets_and_clears_response_language() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    assert_eq!(chat.response_language_context(), None);
+
+    chat.dispatch_command_with_args(SlashCommand::Language, "French".to_string(), Vec::new());
+    assert_eq!(chat.config.response_language.as_deref(), Some("French"));
+    let mut persisted = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::PersistResponseLanguage { language } = event {
+            persisted.push(language);
+        }
+    }
+    assert_eq!(persisted, vec![Some("French".to_string())]);
+    let context = chat
+        .response_language_context()
+        .expect("changed language should be sent with the next turn");
+    assert!(context["response_language"].value.contains("French"));
+
+    chat.dispatch_command_with_args(SlashCommand::Language, "CLEAR".to_string(), Vec::new());
+    assert_eq!(chat.config.response_language, None);
+    assert!(matches!(
+        rx.try_recv(),
+        Ok(AppEvent::PersistResponseLanguage { language: None })
+    ));
+}
+
+#[tokio::test]
+async fn slash_copy_reports_missing_sources_and_usage() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+
+    chat.dispatch_command_with_args(SlashCommand::Copy, "diff".to_string(), Vec::new());
+    chat.dispatch_command_with_args(SlashCommand::Copy, "command".to_string(), Vec::new());
+    chat.dispatch_command_with_args(SlashCommand::Copy, "all".to_string(), Vec::new());
+
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rendered.len(),
+        3,
+        "expected three messages, got {rendered:?}"
+    );
+    assert!(rendered[0].contains("No changes in this turn to copy"));
+    assert!(rendered[1].contains("No command to copy"));
+    assert!(rendered[2].contains("Usage: /copy [last|diff|command|code]"));
+}
+
 #[tokio::test]
 async fn slash_copy_state_is_preserved_during_running_task() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
@@ -2933,3 +3017,207 @@ async fn compact_queues_user_messages_snapshot() {
         normalize_snapshot_paths(term.backend().vt100().screen().contents())
     );
 }
+
+/// `/todos` results open a searchable picker, and Enter asks the agent to resolve the selected
+/// comment.
+#[tokio::test]
+async fn todos_popup_dispatches_selected_comment() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    let todo = |path: &str, line: u32, text: &str| codex_git_utils::TodoComment {
+        path: path.to_string(),
+        line,
+        marker: "TODO".to_string(),
+        text: text.to_string(),
+        context: Some("fn main() {".to_string()),
+    };
+
+    chat.open_todos_popup(Ok(Some(codex_git_utils::TodoScan {
+        todos: vec![
+            todo("src/main.rs", 2, "handle errors"),
+            todo("src/lib.rs", 9, "drop the retry"),
+        ],
+        truncated: false,
+    })));
+
+    let popup = render_bottom_popup(&chat, /*width*/ 100);
+    assert!(popup.contains("TODO comments (2)"), "{popup}");
+    assert!(popup.contains("TODO: handle errors"), "{popup}");
+    assert!(popup.contains("src/lib.rs:9"), "{popup}");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut selected = None;
+    while let Ok(ev) = rx.try_recv() {
+        if let AppEvent::FixTodoComment(todo) = ev {
+            selected = Some(format!("{}:{}", todo.path, todo.line));
+        }
+    }
+    assert_eq!(selected.as_deref(), Some("src/main.rs:2"));
+}
+
+#[tokio::test]
+async fn todos_outside_git_repository_reports_info_message() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+
+    chat.open_todos_popup(Ok(None));
+
+    let cells = drain_insert_history(&mut rx);
+    let rendered = cells
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        rendered.contains("not inside a git repository"),
+        "{rendered}"
+    );
+}
+
+#[tokio::test]
+async fn env_snapshot_is_added_to_history() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+
+    chat.add_env_snapshot(codex_utils_env_snapshot::EnvSnapshot {
+        os: codex_utils_env_snapshot::OsSnapshot {
+            name: "Ubuntu 24.04 (noble) [64-bit]".to_string(),
+            family: "linux".to_string(),
+            arch: "x86_64".to_string(),
+        },
+        toolchains: vec![
+            codex_utils_env_snapshot::ToolchainVersion {
+                name: "rustc".to_string(),
+                version: Some("rustc 1.90.0".to_string()),
+            },
+            codex_utils_env_snapshot::ToolchainVersion {
+                name: "docker".to_string(),
+                version: None,
+            },
+        ],
+        env: [("CARGO_HOME".to_string(), "/opt/cargo".to_string())].into(),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    let rendered = cells
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(rendered.contains("/env"), "{rendered}");
+    assert!(
+        rendered.contains("os: Ubuntu 24.04 (noble) [64-bit] (x86_64)"),
+        "{rendered}"
+    );
+    assert!(rendered.contains("rustc: rustc 1.90.0"), "{rendered}");
+    assert!(rendered.contains("docker: not found"), "{rendered}");
+    assert!(rendered.contains("$CARGO_HOME: /opt/cargo"), "{rendered}");
+}
+
+#[tokio::test]
+async fn system_prompt_context_shows_additions_and_prompt() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    chat.config.system_prompt = codex_config::types::SystemPromptConfig {
+        append: Some("Use the house style guide.".to_string()),
+        replace: [("Testing".to_string(), "Run `just test`.".to_string())].into(),
+        max_tokens: 2_000,
+    };
+
+    chat.add_system_prompt_context(Ok(
+        "You are Codex.\n\nUse the house style guide.".to_string()
+    ));
+
+    let cells = drain_insert_history(&mut rx);
+    let rendered = cells
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(rendered.contains("/context"), "{rendered}");
+    assert!(rendered.contains("budget ~2000 tokens"), "{rendered}");
+    assert!(rendered.contains("append: ~7 tokens"), "{rendered}");
+    assert!(rendered.contains("replace \"Testing\""), "{rendered}");
+    assert!(
+        rendered.contains("Effective system prompt: ~11 tokens"),
+        "{rendered}"
+    );
+    assert!(
+        rendered.contains("Use the house style guide."),
+        "{rendered}"
+    );
+}
+
+/// `/artifacts` lists the files registered for the current thread, and Enter opens the selected
+/// one.
+#[tokio::test]
+async fn artifacts_popup_opens_selected_artifact() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    let thread_id = ThreadId::new();
+    chat.thread_id = Some(thread_id);
+    let outputs = tempfile::tempdir().expect("tempdir");
+    let report = outputs.path().join("report.md");
+    std::fs::write(&report, "# Latency").expect("write report");
+    codex_utils_session_artifacts::register_artifact(
+        chat.config.codex_home.as_path(),
+        &thread_id.to_string(),
+        codex_utils_session_artifacts::SessionArtifact {
+            name: "latency-report".to_string(),
+            path: report.clone(),
+            description: Some("p99 latency by endpoint".to_string()),
+        },
+    )
+    .expect("register artifact");
+
+    chat.dispatch_command(SlashCommand::Artifacts);
+
+    let popup = render_bottom_popup(&chat, /*width*/ 100);
+    assert!(popup.contains("Session artifacts (1)"), "{popup}");
+    assert!(popup.contains("latency-report"), "{popup}");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut opened = None;
+    while let Ok(ev) = rx.try_recv() {
+        if let AppEvent::OpenUrlInBrowser { url } = ev {
+            opened = Some(url);
+        }
+    }
+    assert_eq!(
+        opened,
+        Some(
+            url::Url::from_file_path(&report)
+                .expect("file url")
+                .to_string()
+        )
+    );
+}
+
+#[tokio::test]
+async fn artifacts_without_registered_files_reports_info_message() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    chat.thread_id = Some(ThreadId::new());
+
+    chat.dispatch_command(SlashCommand::Artifacts);
+
+    let cells = drain_insert_history(&mut rx);
+    let rendered = cells
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        rendered.contains("No artifacts registered in this session."),
+        "{rendered}"
+    );
+}
+
+#[tokio::test]
+async fn template_command_inserts_template_with_first_field_selected() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+
+    chat.bottom_pane
+        .set_composer_text("/template bugfix".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    for ch in "login fails".chars() {
+        chat.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+    }
+
+    let composer_text = chat.bottom_pane.composer_text();
+    assert!(
+        composer_text.starts_with("Fix this bug:\n\nlogin fails\n\nApproach:"),
+        "{composer_text}"
+    );
+}
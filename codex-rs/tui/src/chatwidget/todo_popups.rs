@@ -0,0 +1,152 @@
+//! `/todos`: list TODO/FIXME/HACK comments in the workspace and dispatch a turn to fix one.
+
+use std::path::Path;
+use std::time::Duration;
+
+use codex_git_utils::TODO_GREP_ARGS;
+use codex_git_utils::TodoComment;
+use codex_git_utils::TodoScan;
+use codex_git_utils::parse_todo_grep_output;
+
+use super::*;
+use crate::workspace_command::WorkspaceCommand;
+use crate::workspace_command::WorkspaceCommandExecutor;
+
+const TODO_SCAN_TIMEOUT: Duration = Duration::from_secs(/*secs*/ 30);
+const MAX_TODO_RESULTS: usize = 500;
+
+impl ChatWidget {
+    pub(crate) fn start_todo_scan(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let runner = self.workspace_command_runner.clone();
+        let cwd = self
+            .current_cwd
+            .clone()
+            .unwrap_or_else(|| self.config.cwd.to_path_buf());
+        tokio::spawn(async move {
+            let result = match runner {
+                Some(runner) => scan_workspace_todos(runner.as_ref(), &cwd).await,
+                None => Err("workspace command runner unavailable".to_string()),
+            };
+            tx.send(AppEvent::TodoScanResult(result));
+        });
+    }
+
+    pub(crate) fn open_todos_popup(&mut self, result: Result<Option<TodoScan>, String>) {
+        let scan = match result {
+            Ok(Some(scan)) => scan,
+            Ok(None) => {
+                self.add_info_message(
+                    "`/todos` — not inside a git repository".to_string(),
+                    /*hint*/ None,
+                );
+                return;
+            }
+            Err(err) => {
+                self.add_error_message(format!("Failed to scan for TODO comments: {err}"));
+                return;
+            }
+        };
+        if scan.todos.is_empty() {
+            self.add_info_message(
+                "No TODO, FIXME, or HACK comments found.".to_string(),
+                /*hint*/ None,
+            );
+            return;
+        }
+
+        let title = if scan.truncated {
+            format!("TODO comments (first {})", scan.todos.len())
+        } else {
+            format!("TODO comments ({})", scan.todos.len())
+        };
+        let items = scan
+            .todos
+            .into_iter()
+            .map(|todo| {
+                let location = format!("{}:{}", todo.path, todo.line);
+                let name = if todo.text.is_empty() {
+                    todo.marker.clone()
+                } else {
+                    format!("{}: {}", todo.marker, todo.text)
+                };
+                SelectionItem {
+                    search_value: Some(format!("{name} {location}")),
+                    name,
+                    description: Some(location),
+                    selected_description: todo.context.clone(),
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::FixTodoComment(todo.clone()));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(title),
+            subtitle: Some("Enter asks Codex to resolve the comment".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search comments".to_string()),
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn fix_todo_comment(&mut self, todo: TodoComment) {
+        let context = todo
+            .context
+            .map(|context| format!("\nInside: {context}"))
+            .unwrap_or_default();
+        self.queue_user_message(UserMessage::from(format!(
+            "Resolve this {} comment, remove the comment once it no longer applies, and verify the change.\n\nLocation: {}:{}{context}\n\n{}",
+            todo.marker, todo.path, todo.line, todo.text
+        )));
+    }
+}
+
+/// Runs `git grep` for comment markers in the active workspace.
+///
+/// Returns `Ok(None)` outside a git repository.
+async fn scan_workspace_todos(
+    runner: &dyn WorkspaceCommandExecutor,
+    cwd: &Path,
+) -> Result<Option<TodoScan>, String> {
+    let inside_repo = runner
+        .run(
+            WorkspaceCommand::new(["git", "rev-parse", "--is-inside-work-tree"])
+                .cwd(cwd.to_path_buf()),
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+    if !inside_repo.success() {
+        return Ok(None);
+    }
+
+    let output = runner
+        .run(
+            WorkspaceCommand::new(["git"].into_iter().chain(TODO_GREP_ARGS.iter().copied()))
+                .cwd(cwd.to_path_buf())
+                .timeout(TODO_SCAN_TIMEOUT)
+                .disable_output_cap(),
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+    match output.exit_code {
+        0 => Ok(Some(parse_todo_grep_output(
+            &output.stdout,
+            MAX_TODO_RESULTS,
+        ))),
+        // `git grep` exits 1 when nothing matches.
+        1 => Ok(Some(TodoScan {
+            todos: Vec::new(),
+            truncated: false,
+        })),
+        code => Err(format!(
+            "git grep failed with status {code}: {}",
+            output.stderr.trim()
+        )),
+    }
+}
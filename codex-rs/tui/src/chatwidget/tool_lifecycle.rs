@@ -87,6 +87,7 @@ impl ChatWidget {
         action: codex_app_server_protocol::WebSearchAction,
     ) {
         self.flush_answer_stream_with_separator();
+        self.transcript.turn_citations.record_web_search(&action);
         let mut handled = false;
         if let Some(cell) = self
             .transcript
@@ -1,6 +1,9 @@
 //! Transcript and active-cell bookkeeping for `ChatWidget`.
 
+use std::time::SystemTime;
+
 use super::HistoryCell;
+use crate::citations::TurnCitations;
 
 #[derive(Default)]
 pub(super) struct TranscriptState {
@@ -29,6 +32,14 @@ pub(super) struct TranscriptState {
     pub(super) plan_delta_buffer: String,
     /// True while a plan item is streaming.
     pub(super) plan_item_active: bool,
+    /// Command line of the most recently started command, for `/copy command`.
+    pub(super) last_command: Option<String>,
+    /// Latest aggregate diff of the files changed during the current turn.
+    pub(super) turn_diff: Option<String>,
+    /// Pages and files consulted during the current turn, cited by its final answer.
+    pub(super) turn_citations: TurnCitations,
+    /// When the conversation last saw activity, used to mark long idle gaps.
+    pub(super) last_activity_at: Option<SystemTime>,
 }
 
 impl TranscriptState {
@@ -64,6 +75,8 @@ impl TranscriptState {
         self.latest_proposed_plan_markdown = None;
         self.plan_delta_buffer.clear();
         self.plan_item_active = false;
+        self.turn_diff = None;
+        self.turn_citations = TurnCitations::default();
     }
 }
 
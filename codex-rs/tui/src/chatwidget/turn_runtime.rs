@@ -71,6 +71,7 @@ impl ChatWidget {
         self.reset_safety_buffering_for_turn_start();
         self.turn_lifecycle.start(Instant::now());
         self.transcript.reset_turn_flags();
+        self.status_state.tool_activity.clear();
         self.adaptive_chunking.reset();
         if self.plan_stream_controller.take().is_some() {
             self.request_pending_usage_output_insertion_after_stream_shutdown();
@@ -93,6 +94,7 @@ impl ChatWidget {
         self.reasoning_summary_parts.clear();
         self.reasoning_buffer.clear();
         self.reasoning_header = None;
+        self.reasoning_panel_open = false;
         self.set_ambient_pet_notification(
             crate::pets::PetNotificationKind::Running,
             /*body*/ None,
@@ -142,6 +144,14 @@ impl ChatWidget {
             self.request_pending_usage_output_insertion_after_stream_shutdown();
         }
         self.flush_unified_exec_wait_streak();
+        if let Some(cell) = self
+            .transcript
+            .turn_diff
+            .take()
+            .and_then(|diff| history_cell::new_turn_diff_stat(&diff, self.config.cwd.as_path()))
+        {
+            self.add_to_history(cell);
+        }
         if !from_replay {
             self.collect_runtime_metrics_delta();
             let runtime_metrics =
@@ -506,7 +516,13 @@ impl ChatWidget {
             .count();
         self.transcript.last_plan_progress = (total > 0).then_some((completed, total));
         self.refresh_status_surfaces();
-        self.add_to_history(history_cell::new_plan_update(update));
+        // While a turn runs the bottom pane shows the plan live, so scrollback only gets updates
+        // that arrive with no turn to attach a checklist to.
+        let transcript_only = self.bottom_pane.is_task_running();
+        if transcript_only {
+            self.bottom_pane.set_plan_checklist(update.plan.clone());
+        }
+        self.add_to_history(history_cell::new_plan_update(update, transcript_only));
     }
 
     pub(super) fn interrupted_turn_message(&self, reason: TurnAbortReason) -> String {
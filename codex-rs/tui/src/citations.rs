@@ -0,0 +1,165 @@
+//! Numbered source lists for answers built on fetched pages and file reads.
+//!
+//! While a turn runs, `ChatWidget` records the pages its web searches opened and the files its
+//! commands read, taken from the structured tool items the agent reports. When the final answer
+//! completes, [`TurnCitations::sources_cell`] lists the recorded sources the answer links to or
+//! names as an inline-code path, numbered in the order the answer first references them. The
+//! answer itself is left exactly as the model wrote it.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_app_server_protocol::WebSearchAction;
+use codex_protocol::parse_command::ParsedCommand;
+use pulldown_cmark::Event;
+use pulldown_cmark::Parser;
+use pulldown_cmark::Tag;
+use pulldown_cmark::TagEnd;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+use crate::history_cell::PlainHistoryCell;
+use crate::markdown_render::parser_options;
+use crate::terminal_hyperlinks::web_destination;
+use crate::terminal_hyperlinks::web_links_in_text;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum CitationSource {
+    Web(String),
+    File(PathBuf),
+}
+
+/// Sources consulted during the current turn, in the order they were first used.
+#[derive(Debug, Default)]
+pub(crate) struct TurnCitations {
+    sources: Vec<CitationSource>,
+}
+
+impl TurnCitations {
+    /// Records the page a web search opened or searched within.
+    pub(crate) fn record_web_search(&mut self, action: &WebSearchAction) {
+        match action {
+            WebSearchAction::OpenPage { url: Some(url) }
+            | WebSearchAction::FindInPage { url: Some(url), .. } => {
+                self.record(CitationSource::Web(url.clone()));
+            }
+            WebSearchAction::OpenPage { url: None }
+            | WebSearchAction::FindInPage { url: None, .. }
+            | WebSearchAction::Search { .. }
+            | WebSearchAction::Other => {}
+        }
+    }
+
+    /// Records the files a command reads, resolving relative paths against `cwd`.
+    pub(crate) fn record_command_reads(&mut self, parsed: &[ParsedCommand], cwd: &Path) {
+        for command in parsed {
+            if let ParsedCommand::Read { path, .. } = command {
+                self.record(CitationSource::File(cwd.join(path)));
+            }
+        }
+    }
+
+    fn record(&mut self, source: CitationSource) {
+        if !self.sources.contains(&source) {
+            self.sources.push(source);
+        }
+    }
+
+    /// Returns a cell listing the recorded sources `markdown` references, or `None` when it
+    /// references none of them.
+    pub(crate) fn sources_cell(&self, markdown: &str, cwd: &Path) -> Option<PlainHistoryCell> {
+        let cited = self.cited_sources(markdown, cwd);
+        if cited.is_empty() {
+            return None;
+        }
+        let mut lines = vec![Line::from("Sources".bold())];
+        for (index, source) in cited.into_iter().enumerate() {
+            let label = match source {
+                CitationSource::Web(url) => url.clone().cyan().underlined(),
+                CitationSource::File(path) => path
+                    .strip_prefix(cwd)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+                    .cyan(),
+            };
+            lines.push(Line::from(vec![format!("  {}. ", index + 1).dim(), label]));
+        }
+        Some(PlainHistoryCell::new(lines))
+    }
+
+    /// The recorded sources `markdown` links to or names in inline code, in order of first
+    /// reference. Code blocks are skipped, and a reference must resolve to exactly the recorded
+    /// URL or path; file names and other partial mentions do not count.
+    fn cited_sources(&self, markdown: &str, cwd: &Path) -> Vec<&CitationSource> {
+        let mut cited = Vec::new();
+        let mut cite = |source: Option<&CitationSource>| {
+            if let Some(source) = source
+                && !cited.contains(&source)
+            {
+                cited.push(source);
+            }
+        };
+        let mut in_code_block = false;
+        for event in Parser::new_ext(markdown, parser_options()) {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                Event::End(TagEnd::CodeBlock) => in_code_block = false,
+                Event::Start(Tag::Link { dest_url, .. }) => match web_destination(&dest_url) {
+                    Some(url) => cite(self.web_source(&url)),
+                    None => cite(self.file_source(&dest_url, cwd)),
+                },
+                Event::Code(code) => cite(self.file_source(&code, cwd)),
+                Event::Text(text) if !in_code_block => {
+                    for link in web_links_in_text(&text) {
+                        cite(self.web_source(&link.destination));
+                    }
+                }
+                _ => {}
+            }
+        }
+        cited
+    }
+
+    fn web_source(&self, url: &str) -> Option<&CitationSource> {
+        self.sources.iter().find(|source| {
+            matches!(source, CitationSource::Web(recorded)
+                if recorded.trim_end_matches('/') == url.trim_end_matches('/'))
+        })
+    }
+
+    /// Finds the recorded file `reference` points at. The reference may carry a `:line[:col]` or
+    /// `#L<line>` suffix and is resolved against `cwd` when relative.
+    fn file_source(&self, reference: &str, cwd: &Path) -> Option<&CitationSource> {
+        let reference = reference.strip_prefix("file://").unwrap_or(reference);
+        let reference = reference
+            .split_once("#L")
+            .map_or(reference, |(path, _)| path);
+        let path = cwd.join(strip_line_suffix(reference.trim()));
+        self.sources
+            .iter()
+            .find(|source| matches!(source, CitationSource::File(recorded) if *recorded == path))
+    }
+}
+
+/// Removes up to two trailing `:<number>` segments, as in `src/lib.rs:12:4`.
+fn strip_line_suffix(reference: &str) -> &str {
+    let mut path = reference;
+    for _ in 0..2 {
+        match path.rsplit_once(':') {
+            Some((rest, number))
+                if !rest.is_empty()
+                    && !number.is_empty()
+                    && number.chars().all(|ch| ch.is_ascii_digit()) =>
+            {
+                path = rest;
+            }
+            _ => break,
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+#[path = "citations_tests.rs"]
+mod tests;
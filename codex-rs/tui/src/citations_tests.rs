@@ -0,0 +1,133 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+use crate::history_cell::HistoryCell;
+
+fn citations(cwd: &Path) -> TurnCitations {
+    let mut citations = TurnCitations::default();
+    citations.record_web_search(&WebSearchAction::OpenPage {
+        url: Some("https://docs.rs/tokio/".to_string()),
+    });
+    citations.record_web_search(&WebSearchAction::Search {
+        query: Some("tokio select".to_string()),
+        queries: None,
+    });
+    citations.record_command_reads(
+        &[
+            ParsedCommand::Read {
+                cmd: "cat src/lib.rs".to_string(),
+                name: "lib.rs".to_string(),
+                path: PathBuf::from("src/lib.rs"),
+            },
+            ParsedCommand::Read {
+                cmd: "cat Cargo.toml".to_string(),
+                name: "Cargo.toml".to_string(),
+                path: PathBuf::from("Cargo.toml"),
+            },
+        ],
+        cwd,
+    );
+    citations
+}
+
+fn labels(cited: Vec<&CitationSource>) -> Vec<String> {
+    cited
+        .into_iter()
+        .map(|source| match source {
+            CitationSource::Web(url) => url.clone(),
+            CitationSource::File(path) => path.display().to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn answers_without_references_get_no_sources() {
+    let cwd = Path::new("/repo");
+    assert!(
+        citations(cwd)
+            .sources_cell("Done; lib.rs and tokio were enough.", cwd)
+            .is_none()
+    );
+    assert!(
+        TurnCitations::default()
+            .sources_cell("See https://docs.rs/tokio/.", cwd)
+            .is_none()
+    );
+}
+
+#[test]
+fn only_referenced_sources_are_listed_in_reference_order() {
+    let cwd = Path::new("/repo");
+    let answer = "\
+The entry point is `src/lib.rs:12`, as [the docs](https://docs.rs/tokio) explain.
+```
+Cargo.toml stays uncited inside code
+```
+Nothing else counts, not even mylib.rs or lib.rs.";
+
+    assert_eq!(
+        labels(citations(cwd).cited_sources(answer, cwd)),
+        vec![
+            "/repo/src/lib.rs".to_string(),
+            "https://docs.rs/tokio/".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn bare_urls_and_absolute_paths_are_references() {
+    let cwd = Path::new("/repo");
+    let answer = "See https://docs.rs/tokio/, then `/repo/Cargo.toml` and `src/lib.rs`.";
+
+    assert_eq!(
+        labels(citations(cwd).cited_sources(answer, cwd)),
+        vec![
+            "https://docs.rs/tokio/".to_string(),
+            "/repo/Cargo.toml".to_string(),
+            "/repo/src/lib.rs".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn sources_cell_numbers_the_cited_sources() {
+    let cwd = Path::new("/repo");
+    let cell = citations(cwd)
+        .sources_cell("Read `Cargo.toml` and https://docs.rs/tokio/.", cwd)
+        .expect("sources cell");
+
+    let rendered = cell
+        .display_lines(/*width*/ 80)
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rendered,
+        vec![
+            "Sources".to_string(),
+            "  1. Cargo.toml".to_string(),
+            "  2. https://docs.rs/tokio/".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn repeated_sources_are_listed_once() {
+    let cwd = Path::new("/repo");
+    let mut citations = citations(cwd);
+    citations.record_command_reads(
+        &[ParsedCommand::Read {
+            cmd: "sed -n 1,10p /repo/src/lib.rs".to_string(),
+            name: "lib.rs".to_string(),
+            path: PathBuf::from("/repo/src/lib.rs"),
+        }],
+        cwd,
+    );
+
+    assert_eq!(citations.sources.len(), 3);
+}
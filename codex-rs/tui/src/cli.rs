@@ -54,6 +54,11 @@ pub struct Cli {
     #[clap(skip)]
     pub fork_show_all: bool,
 
+    /// Internal: workflow template set by the top-level `codex new --template` wrapper. The
+    /// rendered template, with the prompt as its task, becomes the new session's goal.
+    #[clap(skip)]
+    pub workflow_template: Option<String>,
+
     #[clap(flatten)]
     pub shared: TuiSharedCliOptions,
 
@@ -68,9 +73,29 @@ pub struct Cli {
     /// Disable alternate screen mode
     ///
     /// Runs the TUI in inline mode, preserving terminal scrollback history.
-    #[arg(long = "no-alt-screen", default_value_t = false)]
+    #[arg(
+        long = "no-alt-screen",
+        visible_alias = "inline",
+        default_value_t = false
+    )]
     pub no_alt_screen: bool,
 
+    /// Start with MCP servers, hooks, plugins, apps, and custom themes disabled.
+    ///
+    /// Use this to recover from a config or extension that breaks startup; nothing on disk changes.
+    #[arg(long = "safe-mode", default_value_t = false)]
+    pub safe_mode: bool,
+
+    /// Internal: why safe mode started without the user's config.toml, shown as a startup warning.
+    #[clap(skip)]
+    pub safe_mode_config_error: Option<String>,
+
+    /// Write structured JSON logs to `codex-tui.jsonl` in the log directory.
+    ///
+    /// Useful when attaching logs to a bug report; honors `RUST_LOG` like the plain log file.
+    #[arg(long = "log-json", default_value_t = false)]
+    pub log_json: bool,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 }
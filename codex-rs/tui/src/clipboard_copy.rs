@@ -21,6 +21,8 @@
 use base64::Engine;
 use std::io::Write;
 
+use crate::terminal_capabilities::terminal_capabilities;
+
 /// Maximum raw bytes we will base64-encode into an OSC 52 sequence.
 /// Large payloads are rejected before encoding to avoid overwhelming the terminal.
 const OSC52_MAX_RAW_BYTES: usize = 100_000;
@@ -455,7 +457,16 @@ impl SuppressStderr {
 }
 
 /// Write text to the clipboard via the OSC 52 terminal escape sequence.
+///
+/// Fails without writing when the terminal is known to drop OSC 52, so the user sees an error
+/// instead of a copy that silently went nowhere.
 fn osc52_copy(text: &str) -> Result<(), String> {
+    if !terminal_capabilities().osc52_clipboard {
+        return Err(
+            "this terminal does not support OSC 52; set `tui.terminal_capabilities.osc52_clipboard = true` to try it anyway"
+                .to_string(),
+        );
+    }
     let sequence = osc52_sequence(text, std::env::var_os("TMUX").is_some())?;
     #[cfg(unix)]
     {
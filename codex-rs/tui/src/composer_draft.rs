@@ -0,0 +1,129 @@
+//! Autosave and recovery for the unfinished composer draft.
+//!
+//! While the TUI runs, the app periodically writes the composer text and any queued messages to a
+//! per-directory file under `$CODEX_HOME/composer_drafts/`. If the TUI is interrupted or crashes,
+//! the next start in the same directory restores the draft. Submitting or clearing everything
+//! removes the file.
+
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest as _;
+use sha2::Sha256;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const COMPOSER_DRAFTS_DIR: &str = "composer_drafts";
+
+/// How often the app checks whether the draft changed and needs saving.
+pub(crate) const COMPOSER_DRAFT_AUTOSAVE_INTERVAL: Duration = Duration::from_secs(/*secs*/ 3);
+
+/// Unsent input: messages queued behind a running turn plus the text still in the composer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ComposerDraft {
+    #[serde(default)]
+    pub(crate) queued_messages: Vec<String>,
+    #[serde(default)]
+    pub(crate) composer_text: String,
+}
+
+impl ComposerDraft {
+    fn is_empty(&self) -> bool {
+        self.composer_text.trim().is_empty()
+            && self
+                .queued_messages
+                .iter()
+                .all(|message| message.trim().is_empty())
+    }
+
+    /// Joins the draft into a single composer text, queued messages first in the order they would
+    /// have been sent.
+    ///
+    /// Restored messages go back to the composer rather than the queue so nothing is sent before
+    /// the user has reviewed it.
+    pub(crate) fn into_composer_text(self) -> String {
+        self.queued_messages
+            .into_iter()
+            .chain(std::iter::once(self.composer_text))
+            .filter(|text| !text.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredComposerDraft {
+    cwd: PathBuf,
+    #[serde(flatten)]
+    draft: ComposerDraft,
+}
+
+/// Draft file for one working directory, remembering what was last written to skip no-op saves.
+#[derive(Debug)]
+pub(crate) struct ComposerDraftStore {
+    path: PathBuf,
+    cwd: PathBuf,
+    last_saved: ComposerDraft,
+}
+
+impl ComposerDraftStore {
+    pub(crate) fn new(codex_home: &Path, cwd: &Path) -> Self {
+        let digest = Sha256::digest(cwd.as_os_str().as_encoded_bytes());
+        Self {
+            path: codex_home
+                .join(COMPOSER_DRAFTS_DIR)
+                .join(format!("{digest:x}.json")),
+            cwd: cwd.to_path_buf(),
+            last_saved: ComposerDraft::default(),
+        }
+    }
+
+    /// Loads the draft a previous run left behind in this directory, if any.
+    pub(crate) fn recover(&mut self) -> Option<ComposerDraft> {
+        let contents = std::fs::read_to_string(&self.path).ok()?;
+        let stored = match serde_json::from_str::<StoredComposerDraft>(&contents) {
+            Ok(stored) => stored,
+            Err(err) => {
+                tracing::warn!(
+                    "ignoring unreadable composer draft {}: {err}",
+                    self.path.display()
+                );
+                return None;
+            }
+        };
+        if stored.cwd != self.cwd || stored.draft.is_empty() {
+            return None;
+        }
+        self.last_saved = stored.draft.clone();
+        Some(stored.draft)
+    }
+
+    /// Writes `draft` if it changed since the last save, or removes the file once it is empty.
+    pub(crate) fn save(&mut self, draft: ComposerDraft) -> std::io::Result<()> {
+        if draft == self.last_saved {
+            return Ok(());
+        }
+        if draft.is_empty() {
+            match std::fs::remove_file(&self.path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        } else {
+            let stored = StoredComposerDraft {
+                cwd: self.cwd.clone(),
+                draft: draft.clone(),
+            };
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&self.path, serde_json::to_string(&stored)?)?;
+        }
+        self.last_saved = draft;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "composer_draft_tests.rs"]
+mod tests;
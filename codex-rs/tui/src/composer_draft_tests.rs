@@ -0,0 +1,55 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn draft(queued_messages: &[&str], composer_text: &str) -> ComposerDraft {
+    ComposerDraft {
+        queued_messages: queued_messages
+            .iter()
+            .map(|message| message.to_string())
+            .collect(),
+        composer_text: composer_text.to_string(),
+    }
+}
+
+#[test]
+fn saved_draft_is_recovered_in_the_same_directory_only() {
+    let codex_home = tempdir().expect("temp codex home");
+    let saved = draft(&["run the tests"], "then fix the");
+    ComposerDraftStore::new(codex_home.path(), Path::new("/repo"))
+        .save(saved.clone())
+        .expect("save draft");
+
+    assert_eq!(
+        Some(saved),
+        ComposerDraftStore::new(codex_home.path(), Path::new("/repo")).recover()
+    );
+    assert_eq!(
+        None,
+        ComposerDraftStore::new(codex_home.path(), Path::new("/other")).recover()
+    );
+}
+
+#[test]
+fn saving_an_empty_draft_removes_the_file() {
+    let codex_home = tempdir().expect("temp codex home");
+    let mut store = ComposerDraftStore::new(codex_home.path(), Path::new("/repo"));
+    store
+        .save(draft(&[], "half a thought"))
+        .expect("save draft");
+    store.save(draft(&[], "  ")).expect("clear draft");
+
+    assert!(!store.path.exists());
+    assert_eq!(
+        None,
+        ComposerDraftStore::new(codex_home.path(), Path::new("/repo")).recover()
+    );
+}
+
+#[test]
+fn restored_text_puts_queued_messages_before_the_composer_text() {
+    assert_eq!(
+        "first queued\n\nsecond queued\n\nunfinished",
+        draft(&["first queued", "", "second queued"], "unfinished").into_composer_text()
+    );
+}
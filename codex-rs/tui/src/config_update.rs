@@ -48,16 +48,32 @@ pub(crate) fn format_config_error(err: &impl Display) -> String {
     format!("{err:#}")
 }
 
-fn trusted_project_edit(project_path: &Path) -> ConfigEdit {
+fn project_scoped_key_path(project_path: &Path, key_path: &str) -> String {
     let project_key = project_trust_key(project_path)
         .replace('\\', "\\\\")
         .replace('"', "\\\"");
+    format!("projects.\"{project_key}\".{key_path}")
+}
+
+fn trusted_project_edit(project_path: &Path) -> ConfigEdit {
     replace_config_value(
-        format!("projects.\"{project_key}\".trust_level"),
+        project_scoped_key_path(project_path, "trust_level"),
         serde_json::json!(TrustLevel::Trusted.to_string()),
     )
 }
 
+/// Sets, or clears with `None`, the language replies use in the project at `project_path`.
+pub(crate) fn build_project_response_language_edit(
+    project_path: &Path,
+    language: Option<&str>,
+) -> ConfigEdit {
+    let key_path = project_scoped_key_path(project_path, "response_language");
+    match language {
+        Some(language) => replace_config_value(key_path, serde_json::json!(language)),
+        None => clear_config_value(key_path),
+    }
+}
+
 pub(crate) fn build_model_selection_edits(
     model: &str,
     effort: Option<impl ToString>,
@@ -23,6 +23,23 @@ fn trusted_project_edit_targets_project_trust_level() {
     );
 }
 
+#[test]
+fn project_response_language_edit_sets_and_clears_the_project_key() {
+    let project = Path::new("/workspace/team.project");
+    assert_eq!(
+        build_project_response_language_edit(project, Some("French")),
+        ConfigEdit {
+            key_path: "projects.\"/workspace/team.project\".response_language".to_string(),
+            value: serde_json::json!("French"),
+            merge_strategy: MergeStrategy::Replace,
+        }
+    );
+    assert_eq!(
+        build_project_response_language_edit(project, None).value,
+        serde_json::Value::Null
+    );
+}
+
 #[test]
 fn format_config_error_preserves_server_validation_message() {
     let err = Err::<(), _>(color_eyre::eyre::eyre!(
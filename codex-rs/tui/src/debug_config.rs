@@ -218,6 +218,14 @@ fn render_debug_config_lines(
         ));
     }
 
+    if let Some(models) = requirements_toml.allowed_models.as_ref() {
+        requirement_lines.push(requirement_line(
+            "allowed_models",
+            join_or_empty(models.clone()),
+            requirements.model.source.as_ref(),
+        ));
+    }
+
     if let Some(policies) = requirements_toml.allowed_approval_policies.as_ref() {
         let value = join_or_empty(policies.iter().map(ToString::to_string).collect::<Vec<_>>());
         requirement_lines.push(requirement_line(
@@ -407,6 +415,7 @@ fn render_non_file_layer_details(layer: &ConfigLayerEntry) -> Vec<Line<'static>>
         ConfigLayerSource::SessionFlags => render_session_flag_details(&layer.config),
         ConfigLayerSource::Mdm { .. }
         | ConfigLayerSource::EnterpriseManaged { .. }
+        | ConfigLayerSource::Team { .. }
         | ConfigLayerSource::LegacyManagedConfigTomlFromMdm => render_non_file_layer_value(layer),
         ConfigLayerSource::System { .. }
         | ConfigLayerSource::User { .. }
@@ -471,6 +480,8 @@ fn non_file_layer_value_label(source: &ConfigLayerSource) -> &'static str {
             "MDM value"
         }
         ConfigLayerSource::EnterpriseManaged { .. } => "Enterprise-managed config value",
+        ConfigLayerSource::Team { locked: false, .. } => "Team config value",
+        ConfigLayerSource::Team { locked: true, .. } => "Locked team config value",
         ConfigLayerSource::SessionFlags
         | ConfigLayerSource::System { .. }
         | ConfigLayerSource::User { .. }
@@ -941,6 +952,7 @@ interrupt_message = false
             feedback: Some(FeedbackConfigToml {
                 enabled: Some(false),
             }),
+            allowed_models: None,
             allowed_approval_policies: Some(vec![AskForApproval::OnRequest.to_core()]),
             allowed_approvals_reviewers: Some(vec![ApprovalsReviewer::AutoReview]),
             allowed_sandbox_modes: Some(vec![SandboxModeRequirement::ReadOnly]),
@@ -27,6 +27,19 @@
 //! is intentionally *not* preserved because hunks are visually separated and
 //! re-synchronize at context boundaries anyway.
 //!
+//! **Word emphasis for `Update` diffs:** deleted lines immediately followed by
+//! insertions are paired, and the tokens that differ within each pair get a
+//! stronger background so small edits in long lines stand out.  See
+//! [`word_diff`]; `tui.diff_word_emphasis = false` turns it off.
+//!
+//! **Fenced diffs:** ```` ```diff ```` blocks in markdown that name their
+//! files get the same syntax-over-tint compositing, without line numbers or
+//! wrapping.  See [`fenced`].
+//!
+//! **Tabs and whitespace:** tabs expand to the tab stops configured for the
+//! file's extension before wrapping, and `tui.diff_show_whitespace = true`
+//! marks tabs and trailing spaces. See [`whitespace`].
+//!
 //! **Wrapping:** long lines are hard-wrapped at the available column width.
 //! Syntax-highlighted spans are split at character boundaries with styles
 //! preserved across the split so that no color information is lost.
@@ -42,13 +55,14 @@ use ratatui::text::Line as RtLine;
 use ratatui::text::Span as RtSpan;
 use ratatui::widgets::Paragraph;
 use std::collections::HashMap;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 
 use codex_utils_absolute_path::AbsolutePathBuf;
 use unicode_width::UnicodeWidthChar;
 
-/// Replacement for a tab character in rendered diff content.
+/// Fallback replacement for a tab that reaches wrapping unexpanded.
 const TAB_REPLACEMENT: &str = "    ";
 /// Display width of a tab character in columns.
 const TAB_WIDTH: usize = TAB_REPLACEMENT.len();
@@ -82,14 +96,22 @@ use crate::color::perceptual_distance;
 use crate::diff_model::FileChange;
 use crate::exec_command::relativize_to_home;
 use crate::render::Insets;
+use crate::render::background_highlight::Highlight;
+use crate::render::background_highlight::highlight_code_to_styled_spans_cached;
+use crate::render::background_highlight::highlight_code_to_styled_spans_in_background;
 use crate::render::highlight::DiffScopeBackgroundRgbs;
+use crate::render::highlight::content_language;
 use crate::render::highlight::diff_scope_background_rgbs;
 use crate::render::highlight::exceeds_highlight_limits;
+use crate::render::highlight::file_name_language;
+use crate::render::highlight::header_language;
 use crate::render::highlight::highlight_code_to_styled_spans;
 use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
+use crate::render::tabs::tab_width_for;
+use crate::terminal_capabilities::terminal_capabilities;
 use crate::terminal_palette::StdoutColorLevel;
 use crate::terminal_palette::XTERM_COLORS;
 use crate::terminal_palette::default_bg;
@@ -100,6 +122,17 @@ use codex_git_utils::get_git_repo_root;
 use codex_terminal_detection::TerminalName;
 use codex_terminal_detection::terminal_info;
 
+mod fenced;
+mod notebook;
+mod whitespace;
+mod word_diff;
+
+pub(crate) use fenced::diff_block_to_lines;
+pub(crate) use fenced::is_diff_language;
+pub(crate) use notebook::readable_notebook_changes;
+pub(crate) use whitespace::set_show_whitespace_enabled;
+pub(crate) use word_diff::set_word_emphasis_enabled;
+
 /// Classifies a diff line for gutter sign rendering and style selection.
 ///
 /// `Insert` renders with a `+` sign and green text, `Delete` with `-` and red
@@ -302,6 +335,7 @@ pub struct DiffSummary {
 
 impl DiffSummary {
     pub(crate) fn new(changes: HashMap<PathBuf, FileChange>, cwd: AbsolutePathBuf) -> Self {
+        let changes = readable_notebook_changes(changes, cwd.as_path());
         Self { changes, cwd }
     }
 }
@@ -309,13 +343,25 @@ impl DiffSummary {
 impl Renderable for FileChange {
     fn render(&self, area: Rect, buf: &mut Buffer) {
         let mut lines = vec![];
-        render_change(self, &mut lines, area.width as usize, /*lang*/ None);
+        render_change(
+            self,
+            &mut lines,
+            area.width as usize,
+            /*lang*/ None,
+            /*background*/ false,
+        );
         Paragraph::new(lines).render(area, buf);
     }
 
     fn desired_height(&self, width: u16) -> u16 {
         let mut lines = vec![];
-        render_change(self, &mut lines, width as usize, /*lang*/ None);
+        render_change(
+            self,
+            &mut lines,
+            width as usize,
+            /*lang*/ None,
+            /*background*/ false,
+        );
         lines.len() as u16
     }
 }
@@ -348,13 +394,25 @@ impl From<DiffSummary> for Box<dyn Renderable> {
     }
 }
 
+/// Renders `changes` fully highlighted, for output that is drawn once, such as scrollback.
 pub(crate) fn create_diff_summary(
     changes: &HashMap<PathBuf, FileChange>,
     cwd: &Path,
     wrap_cols: usize,
 ) -> Vec<RtLine<'static>> {
     let rows = collect_rows(changes);
-    render_changes_block(rows, wrap_cols, cwd)
+    render_changes_block(rows, wrap_cols, cwd, /*background*/ false)
+}
+
+/// Like [`create_diff_summary`], but large changes are highlighted on a worker thread and drawn
+/// plain until a later frame, for views that redraw on every frame such as the transcript overlay.
+pub(crate) fn create_diff_summary_in_background(
+    changes: &HashMap<PathBuf, FileChange>,
+    cwd: &Path,
+    wrap_cols: usize,
+) -> Vec<RtLine<'static>> {
+    let rows = collect_rows(changes);
+    render_changes_block(rows, wrap_cols, cwd, /*background*/ true)
 }
 
 // Shared row for per-file presentation
@@ -389,7 +447,7 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row<'_>> {
     rows
 }
 
-fn line_counts(change: &FileChange) -> (usize, usize) {
+pub(crate) fn line_counts(change: &FileChange) -> (usize, usize) {
     match change {
         FileChange::Add { content } => (content.lines().count(), 0),
         FileChange::Delete { content } => (0, content.lines().count()),
@@ -407,7 +465,12 @@ fn render_line_count_summary(added: usize, removed: usize) -> Vec<RtSpan<'static
     spans
 }
 
-fn render_changes_block(rows: Vec<Row<'_>>, wrap_cols: usize, cwd: &Path) -> Vec<RtLine<'static>> {
+fn render_changes_block(
+    rows: Vec<Row<'_>>,
+    wrap_cols: usize,
+    cwd: &Path,
+    background: bool,
+) -> Vec<RtLine<'static>> {
     let mut out: Vec<RtLine<'static>> = Vec::new();
 
     let render_path = |row: &Row<'_>| -> Vec<RtSpan<'static>> {
@@ -462,34 +525,108 @@ fn render_changes_block(rows: Vec<Row<'_>>, wrap_cols: usize, cwd: &Path) -> Vec
         // For renames, use the destination extension for highlighting — the
         // diff content reflects the new file, not the old one.
         let lang_path = r.move_path.unwrap_or(r.path);
-        let lang = detect_lang_for_path(lang_path);
+        let lang = detect_lang_for_path(lang_path, change_text(r.change));
         let mut lines = vec![];
-        render_change(r.change, &mut lines, wrap_cols - 4, lang.as_deref());
+        render_change(
+            r.change,
+            &mut lines,
+            wrap_cols - 4,
+            lang.as_deref(),
+            background,
+        );
         out.extend(prefix_lines(lines, "    ".into(), "    ".into()));
     }
 
     out
 }
 
+/// Highlights a hunk as a single block so syntect parser state is preserved across consecutive
+/// lines. When the block cannot be highlighted, each line is highlighted on its own, so one line
+/// the grammar trips on does not leave the whole hunk uncolored. Returns `None` while a
+/// background highlight is still pending.
+fn hunk_syntax_lines(
+    lines: &[diffy::Line<'_, str>],
+    language: &str,
+    background: bool,
+) -> Option<Vec<Option<Vec<RtSpan<'static>>>>> {
+    let text_of = |line: &diffy::Line<'_, str>| match line {
+        diffy::Line::Insert(text) | diffy::Line::Delete(text) | diffy::Line::Context(text) => *text,
+    };
+    let hunk_text: String = lines.iter().map(text_of).collect();
+    let block = if background {
+        match highlight_code_to_styled_spans_in_background(&hunk_text, language) {
+            Highlight::Ready(block) => block,
+            Highlight::Pending => return None,
+        }
+    } else {
+        highlight_code_to_styled_spans_cached(&hunk_text, language)
+    };
+    if let Some(block) = block.filter(|block| block.len() == lines.len()) {
+        return Some(block.into_iter().map(Some).collect());
+    }
+    Some(
+        lines
+            .iter()
+            .map(|line| {
+                highlight_code_to_styled_spans(text_of(line), language)
+                    .and_then(|spans| spans.into_iter().next())
+            })
+            .collect(),
+    )
+}
+
 /// Detect the programming language for a file path by its extension.
 /// Returns the raw extension string for `normalize_lang` / `find_syntax`
-/// to resolve downstream.
-fn detect_lang_for_path(path: &Path) -> Option<String> {
-    let ext = path.extension()?.to_str()?;
+/// to resolve downstream.  `content` is consulted for `.h` headers, which
+/// are shared by C and C++, and for files without an extension whose name
+/// does not identify them.
+fn detect_lang_for_path(path: &Path, content: &str) -> Option<String> {
+    let Some(ext) = path.extension() else {
+        return path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(file_name_language)
+            .map(str::to_string)
+            .or_else(|| content_language(content));
+    };
+    let ext = ext.to_str()?;
+    // Notebook changes are rendered as `# %%` cell text; see `notebook.rs`.
+    if ext == "ipynb" {
+        return Some("py".to_string());
+    }
+    if ext == "h" {
+        return Some(header_language(content).to_string());
+    }
     Some(ext.to_string())
 }
 
+fn change_text(change: &FileChange) -> &str {
+    match change {
+        FileChange::Add { content } | FileChange::Delete { content } => content,
+        FileChange::Update { unified_diff, .. } => unified_diff,
+    }
+}
+
 fn render_change(
     change: &FileChange,
     out: &mut Vec<RtLine<'static>>,
     width: usize,
     lang: Option<&str>,
+    background: bool,
 ) {
     let style_context = current_diff_render_style_context();
+    let highlight = |code: &str, lang: &str| {
+        if background {
+            highlight_code_to_styled_spans_in_background(code, lang).ready()
+        } else {
+            highlight_code_to_styled_spans_cached(code, lang)
+        }
+    };
+    let tab_width = tab_width_for(lang);
     match change {
         FileChange::Add { content } => {
             // Pre-highlight the entire file content as a whole.
-            let syntax_lines = lang.and_then(|l| highlight_code_to_styled_spans(content, l));
+            let syntax_lines = lang.and_then(|l| highlight(content, l));
             let line_number_width = line_number_width(content.lines().count());
             for (i, raw) in content.lines().enumerate() {
                 let syn = syntax_lines.as_ref().and_then(|sl| sl.get(i));
@@ -504,6 +641,8 @@ fn render_change(
                         style_context.theme,
                         style_context.color_level,
                         style_context.diff_backgrounds,
+                        /*emphasis*/ &[],
+                        tab_width,
                     ));
                 } else {
                     out.extend(push_wrapped_diff_line_inner_with_theme_and_color_level(
@@ -516,12 +655,14 @@ fn render_change(
                         style_context.theme,
                         style_context.color_level,
                         style_context.diff_backgrounds,
+                        /*emphasis*/ &[],
+                        tab_width,
                     ));
                 }
             }
         }
         FileChange::Delete { content } => {
-            let syntax_lines = lang.and_then(|l| highlight_code_to_styled_spans(content, l));
+            let syntax_lines = lang.and_then(|l| highlight(content, l));
             let line_number_width = line_number_width(content.lines().count());
             for (i, raw) in content.lines().enumerate() {
                 let syn = syntax_lines.as_ref().and_then(|sl| sl.get(i));
@@ -536,6 +677,8 @@ fn render_change(
                         style_context.theme,
                         style_context.color_level,
                         style_context.diff_backgrounds,
+                        /*emphasis*/ &[],
+                        tab_width,
                     ));
                 } else {
                     out.extend(push_wrapped_diff_line_inner_with_theme_and_color_level(
@@ -548,6 +691,8 @@ fn render_change(
                         style_context.theme,
                         style_context.color_level,
                         style_context.diff_backgrounds,
+                        /*emphasis*/ &[],
+                        tab_width,
                     ));
                 }
             }
@@ -612,28 +757,18 @@ fn render_change(
                     }
                     is_first_hunk = false;
 
-                    // Highlight each hunk as a single block so syntect parser
-                    // state is preserved across consecutive lines.
-                    let hunk_syntax_lines = diff_lang.and_then(|language| {
-                        let hunk_text: String = h
-                            .lines()
-                            .iter()
-                            .map(|line| match line {
-                                diffy::Line::Insert(text)
-                                | diffy::Line::Delete(text)
-                                | diffy::Line::Context(text) => *text,
-                            })
-                            .collect();
-                        let syntax_lines = highlight_code_to_styled_spans(&hunk_text, language)?;
-                        (syntax_lines.len() == h.lines().len()).then_some(syntax_lines)
-                    });
+                    let hunk_syntax_lines = diff_lang
+                        .and_then(|language| hunk_syntax_lines(h.lines(), language, background));
+
+                    let hunk_emphasis = word_diff::hunk_emphasis(h.lines());
 
                     let mut old_ln = h.old_range().start();
                     let mut new_ln = h.new_range().start();
                     for (line_idx, l) in h.lines().iter().enumerate() {
                         let syntax_spans = hunk_syntax_lines
                             .as_ref()
-                            .and_then(|syntax_lines| syntax_lines.get(line_idx));
+                            .and_then(|syntax_lines| syntax_lines.get(line_idx))
+                            .and_then(Option::as_ref);
                         match l {
                             diffy::Line::Insert(text) => {
                                 let s = text.trim_end_matches('\n');
@@ -649,6 +784,8 @@ fn render_change(
                                             style_context.theme,
                                             style_context.color_level,
                                             style_context.diff_backgrounds,
+                                            &hunk_emphasis[line_idx],
+                                            tab_width,
                                         ),
                                     );
                                 } else {
@@ -663,6 +800,8 @@ fn render_change(
                                             style_context.theme,
                                             style_context.color_level,
                                             style_context.diff_backgrounds,
+                                            &hunk_emphasis[line_idx],
+                                            tab_width,
                                         ),
                                     );
                                 }
@@ -682,6 +821,8 @@ fn render_change(
                                             style_context.theme,
                                             style_context.color_level,
                                             style_context.diff_backgrounds,
+                                            &hunk_emphasis[line_idx],
+                                            tab_width,
                                         ),
                                     );
                                 } else {
@@ -696,6 +837,8 @@ fn render_change(
                                             style_context.theme,
                                             style_context.color_level,
                                             style_context.diff_backgrounds,
+                                            &hunk_emphasis[line_idx],
+                                            tab_width,
                                         ),
                                     );
                                 }
@@ -715,6 +858,8 @@ fn render_change(
                                             style_context.theme,
                                             style_context.color_level,
                                             style_context.diff_backgrounds,
+                                            /*emphasis*/ &[],
+                                            tab_width,
                                         ),
                                     );
                                 } else {
@@ -729,6 +874,8 @@ fn render_change(
                                             style_context.theme,
                                             style_context.color_level,
                                             style_context.diff_backgrounds,
+                                            /*emphasis*/ &[],
+                                            tab_width,
                                         ),
                                     );
                                 }
@@ -810,6 +957,8 @@ pub(crate) fn push_wrapped_diff_line_with_style_context(
         style_context.theme,
         style_context.color_level,
         style_context.diff_backgrounds,
+        /*emphasis*/ &[],
+        tab_width_for(/*lang*/ None),
     )
 }
 
@@ -839,6 +988,8 @@ pub(crate) fn push_wrapped_diff_line_with_syntax_and_style_context(
         style_context.theme,
         style_context.color_level,
         style_context.diff_backgrounds,
+        /*emphasis*/ &[],
+        tab_width_for(/*lang*/ None),
     )
 }
 
@@ -853,6 +1004,8 @@ fn push_wrapped_diff_line_inner_with_theme_and_color_level(
     theme: DiffTheme,
     color_level: DiffColorLevel,
     diff_backgrounds: ResolvedDiffBackgrounds,
+    emphasis: &[Range<usize>],
+    tab_width: usize,
 ) -> Vec<RtLine<'static>> {
     let ln_str = line_number.to_string();
 
@@ -877,6 +1030,8 @@ fn push_wrapped_diff_line_inner_with_theme_and_color_level(
 
     let line_bg = style_line_bg_for(kind, diff_backgrounds);
     let gutter_style = style_gutter_for(kind, theme, color_level);
+    let emphasis_style = word_diff::style_word_emphasis(kind, theme, color_level);
+    let show_whitespace = whitespace::show_whitespace_enabled();
 
     // When we have syntax spans, compose them with the diff style for a richer
     // view. The sign character keeps the diff color; content gets syntax colors
@@ -895,6 +1050,8 @@ fn push_wrapped_diff_line_inner_with_theme_and_color_level(
                 RtSpan::styled(sp.content.clone().into_owned(), style)
             })
             .collect();
+        let styled = word_diff::emphasize_spans(styled, emphasis, emphasis_style);
+        let styled = whitespace::expand_whitespace(styled, tab_width, show_whitespace);
 
         // Determine how many display columns remain for content after the
         // gutter and sign character.
@@ -923,7 +1080,12 @@ fn push_wrapped_diff_line_inner_with_theme_and_color_level(
     }
 
     let available_content_cols = width.saturating_sub(prefix_cols + 1).max(1);
-    let styled = vec![RtSpan::styled(text.to_string(), content_style)];
+    let styled = word_diff::emphasize_spans(
+        vec![RtSpan::styled(text.to_string(), content_style)],
+        emphasis,
+        emphasis_style,
+    );
+    let styled = whitespace::expand_whitespace(styled, tab_width, show_whitespace);
     let wrapped_chunks = wrap_styled_spans(&styled, available_content_cols);
 
     let mut lines: Vec<RtLine<'static>> = Vec::new();
@@ -1061,13 +1223,19 @@ fn diff_theme() -> DiffTheme {
 ///
 /// Keeping env reads in this thin wrapper lets
 /// [`diff_color_level_for_terminal`] stay pure and easy to unit test.
+/// `tui.terminal_capabilities.truecolor` has the last word.
 fn diff_color_level() -> DiffColorLevel {
-    diff_color_level_for_terminal(
+    let level = diff_color_level_for_terminal(
         stdout_color_level(),
         terminal_info().name,
         std::env::var_os("WT_SESSION").is_some(),
         has_force_color_override(),
-    )
+    );
+    match level {
+        _ if terminal_capabilities().truecolor => DiffColorLevel::TrueColor,
+        DiffColorLevel::TrueColor => DiffColorLevel::Ansi256,
+        level => level,
+    }
 }
 
 /// Returns whether `FORCE_COLOR` is explicitly set.
@@ -1843,6 +2011,8 @@ mod tests {
             DiffTheme::Dark,
             DiffColorLevel::Ansi16,
             fallback_diff_backgrounds(DiffTheme::Dark, DiffColorLevel::Ansi16),
+            /*emphasis*/ &[],
+            TAB_WIDTH,
         );
         lines.extend(push_wrapped_diff_line_inner_with_theme_and_color_level(
             /*line_number*/ 2,
@@ -1854,6 +2024,8 @@ mod tests {
             DiffTheme::Dark,
             DiffColorLevel::Ansi16,
             fallback_diff_backgrounds(DiffTheme::Dark, DiffColorLevel::Ansi16),
+            /*emphasis*/ &[],
+            TAB_WIDTH,
         ));
 
         snapshot_lines(
@@ -2085,6 +2257,8 @@ mod tests {
             DiffTheme::Light,
             DiffColorLevel::TrueColor,
             fallback_diff_backgrounds(DiffTheme::Light, DiffColorLevel::TrueColor),
+            /*emphasis*/ &[],
+            TAB_WIDTH,
         );
 
         assert!(
@@ -2220,6 +2394,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn go_files_use_go_highlighting() {
+        assert_eq!(
+            detect_lang_for_path(Path::new("cmd/server/main.go"), "").as_deref(),
+            Some("go")
+        );
+
+        let mut changes: HashMap<PathBuf, FileChange> = HashMap::new();
+        changes.insert(
+            PathBuf::from("main.go"),
+            FileChange::Add {
+                content: "package main\n\nfunc sum(a, b int) int { return a + b }\n".to_string(),
+            },
+        );
+
+        let lines = create_diff_summary(&changes, &PathBuf::from("/"), /*wrap_cols*/ 80);
+        let has_rgb = lines.iter().any(|line| {
+            line.spans
+                .iter()
+                .any(|s| matches!(s.style.fg, Some(ratatui::style::Color::Rgb(..))))
+        });
+        assert!(
+            has_rgb,
+            "add diff for .go file should produce syntax-highlighted (RGB) spans"
+        );
+    }
+
     #[test]
     fn cpp_module_extensions_use_cpp_highlighting() {
         let highlighted_tokens = [
@@ -2300,13 +2501,77 @@ mod tests {
     #[test]
     fn detect_lang_for_common_paths() {
         // Standard extensions are detected.
-        assert!(detect_lang_for_path(Path::new("foo.rs")).is_some());
-        assert!(detect_lang_for_path(Path::new("bar.py")).is_some());
-        assert!(detect_lang_for_path(Path::new("app.tsx")).is_some());
+        assert!(detect_lang_for_path(Path::new("foo.rs"), "").is_some());
+        assert!(detect_lang_for_path(Path::new("bar.py"), "").is_some());
+        assert!(detect_lang_for_path(Path::new("app.tsx"), "").is_some());
+
+        // Extensionless files return None unless their name or content names a language.
+        assert!(detect_lang_for_path(Path::new("Makefile"), "").is_none());
+        assert!(detect_lang_for_path(Path::new("randomfile"), "").is_none());
+    }
 
-        // Extensionless files return None.
-        assert!(detect_lang_for_path(Path::new("Makefile")).is_none());
-        assert!(detect_lang_for_path(Path::new("randomfile")).is_none());
+    #[test]
+    fn detect_lang_for_ruby_and_php_paths() {
+        for (path, expected) in [
+            ("app/models/user.rb", "rb"),
+            ("lib/tasks/seed.rake", "rake"),
+            ("Gemfile", "rb"),
+            ("Rakefile", "rb"),
+            ("config.ru", "ru"),
+            ("app/Http/Controllers/UserController.php", "php"),
+        ] {
+            assert_eq!(
+                detect_lang_for_path(Path::new(path), "").as_deref(),
+                Some(expected),
+                "{path}"
+            );
+        }
+        // A PHP script without an extension, such as Laravel's `artisan`, is found by its shebang.
+        assert!(detect_lang_for_path(Path::new("artisan"), "#!/usr/bin/env php\n").is_some());
+    }
+
+    #[test]
+    fn detect_lang_for_extensionless_scripts_checks_content() {
+        assert_eq!(
+            detect_lang_for_path(
+                Path::new("bin/deploy"),
+                "#!/usr/bin/env python3\nimport sys\n"
+            )
+            .as_deref(),
+            Some("py")
+        );
+        assert_eq!(
+            detect_lang_for_path(
+                Path::new("scripts/setup"),
+                "@@ -1,2 +1,3 @@\n #!/bin/bash\n+set -euo pipefail\n echo done\n"
+            )
+            .as_deref(),
+            Some("sh")
+        );
+        assert_eq!(
+            detect_lang_for_path(Path::new("notes"), "just some text\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn detect_lang_for_headers_checks_content() {
+        assert_eq!(
+            detect_lang_for_path(Path::new("include/list.h"), "struct list;\n").as_deref(),
+            Some("c")
+        );
+        assert_eq!(
+            detect_lang_for_path(
+                Path::new("include/widget.h"),
+                "@@ -1 +1,2 @@\n+class Widget;\n #pragma once\n"
+            )
+            .as_deref(),
+            Some("cpp")
+        );
+        assert_eq!(
+            detect_lang_for_path(Path::new("include/widget.hpp"), "").as_deref(),
+            Some("hpp")
+        );
     }
 
     #[test]
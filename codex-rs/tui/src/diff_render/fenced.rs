@@ -0,0 +1,254 @@
+//! Diff-aware highlighting for ```` ```diff ```` code blocks in markdown.
+//!
+//! The `diff` grammar colors whole lines green or red, which hides the syntax of the code being
+//! changed.  When a fenced diff names its files (`+++ b/src/lib.rs`), the content lines of each
+//! hunk are instead highlighted in that file's language and composited onto the add/remove
+//! backgrounds of the patch view: inserted lines keep their syntax colors on a green tint, deleted
+//! lines are dimmed on a red tint, and paired edits get word emphasis.
+//!
+//! Unlike the patch view, the block keeps its original text (no line numbers, no wrapping), so
+//! copying it still yields a valid diff.  Blocks that name no file with a known language are left
+//! to the `diff` grammar.
+
+use std::path::Path;
+
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line as RtLine;
+use ratatui::text::Span as RtSpan;
+
+use super::DiffLineType;
+use super::DiffRenderStyleContext;
+use super::current_diff_render_style_context;
+use super::detect_lang_for_path;
+use super::style_add;
+use super::style_context;
+use super::style_del;
+use super::style_line_bg_for;
+use super::style_sign_add;
+use super::style_sign_del;
+use super::whitespace;
+use super::word_diff;
+use crate::render::highlight::exceeds_highlight_limits;
+use crate::render::highlight::highlight_code_to_styled_spans;
+use crate::render::tabs::tab_width_for;
+
+/// Whether a code fence language marks its block as a unified diff.
+pub(crate) fn is_diff_language(lang: &str) -> bool {
+    lang.eq_ignore_ascii_case("diff") || lang.eq_ignore_ascii_case("patch")
+}
+
+/// Renders a unified diff with its hunks highlighted in the language of the files they change.
+///
+/// Returns `None` when no hunk could be highlighted, so the caller can fall back to the `diff`
+/// grammar.
+pub(crate) fn diff_block_to_lines(diff: &str) -> Option<Vec<RtLine<'static>>> {
+    let lines: Vec<&str> = diff.lines().collect();
+    if exceeds_highlight_limits(diff.len(), lines.len()) {
+        return None;
+    }
+    let render_context = current_diff_render_style_context();
+    let mut out = Vec::with_capacity(lines.len());
+    let mut lang: Option<String> = None;
+    let mut highlighted_any = false;
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        index += 1;
+        if let Some(path) = line
+            .strip_prefix("+++ ")
+            .or_else(|| line.strip_prefix("--- "))
+            .and_then(header_path)
+        {
+            lang = detect_lang_for_path(Path::new(path), "");
+        }
+        out.push(RtLine::from(RtSpan::styled(
+            line.to_string(),
+            header_style(line),
+        )));
+        if !line.starts_with("@@") {
+            continue;
+        }
+
+        let mut remaining = hunk_counts(line);
+        let start = index;
+        while let Some(next) = lines.get(index)
+            && continues_hunk(next, &mut remaining)
+        {
+            index += 1;
+        }
+        let (hunk, highlighted) =
+            hunk_to_lines(&lines[start..index], lang.as_deref(), render_context);
+        highlighted_any |= highlighted;
+        out.extend(hunk);
+    }
+    highlighted_any.then_some(out)
+}
+
+/// The path named by a `---` or `+++` header, without its `a/` or `b/` prefix.
+fn header_path(header: &str) -> Option<&str> {
+    // GNU diff appends a tab and a timestamp.
+    let path = header.split('\t').next()?.trim();
+    if path.is_empty() || path == "/dev/null" {
+        return None;
+    }
+    Some(
+        path.strip_prefix("a/")
+            .or_else(|| path.strip_prefix("b/"))
+            .unwrap_or(path),
+    )
+}
+
+fn header_style(line: &str) -> Style {
+    if line.starts_with("@@") {
+        Style::default().fg(Color::Cyan)
+    } else if ["diff ", "index ", "--- ", "+++ "]
+        .iter()
+        .any(|prefix| line.starts_with(prefix))
+    {
+        Style::default().add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    }
+}
+
+/// Old and new line counts of a `@@ -l,s +l,s @@` header.  `None` for the bare `@@` headers
+/// models often write, whose hunk then runs until the first line without a diff sign.
+fn hunk_counts(header: &str) -> Option<(usize, usize)> {
+    let mut ranges = header.strip_prefix("@@ ")?.split_whitespace();
+    let old = ranges.next()?.strip_prefix('-')?;
+    let new = ranges.next()?.strip_prefix('+')?;
+    let count = |range: &str| match range.split_once(',') {
+        Some((_, count)) => count.parse().ok(),
+        None => Some(1),
+    };
+    Some((count(old)?, count(new)?))
+}
+
+/// Whether `line` belongs to the hunk, counting it against the lines the header announced.
+fn continues_hunk(line: &str, remaining: &mut Option<(usize, usize)>) -> bool {
+    let Some((old, new)) = remaining.as_mut() else {
+        return matches!(line.chars().next(), Some('+' | '-' | ' ' | '\\'))
+            && !line.starts_with("+++ ")
+            && !line.starts_with("--- ");
+    };
+    match line.chars().next() {
+        Some('\\') => {}
+        Some('+') if *new > 0 => *new -= 1,
+        Some('-') if *old > 0 => *old -= 1,
+        // Some editors strip the space of blank context lines.
+        Some(' ') | None if *old > 0 && *new > 0 => {
+            *old -= 1;
+            *new -= 1;
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Renders the lines of one hunk, highlighting them as a single block so parser state carries
+/// across lines.  Also reports whether syntax highlighting was applied.
+fn hunk_to_lines(
+    hunk: &[&str],
+    lang: Option<&str>,
+    render_context: DiffRenderStyleContext,
+) -> (Vec<RtLine<'static>>, bool) {
+    let content: Vec<(DiffLineType, &str, &str)> = hunk
+        .iter()
+        .filter_map(|line| {
+            let kind = match line.chars().next() {
+                Some('+') => DiffLineType::Insert,
+                Some('-') => DiffLineType::Delete,
+                Some(' ') | None => DiffLineType::Context,
+                _ => return None,
+            };
+            let (sign, text) = line.split_at(line.len().min(1));
+            Some((kind, sign, text))
+        })
+        .collect();
+
+    let syntax_lines = lang.and_then(|lang| {
+        let code: String = content
+            .iter()
+            .map(|(_, _, text)| format!("{text}\n"))
+            .collect();
+        let syntax_lines = highlight_code_to_styled_spans(&code, lang)?;
+        (syntax_lines.len() == content.len()).then_some(syntax_lines)
+    });
+    let diffy_lines: Vec<diffy::Line<'_, str>> = content
+        .iter()
+        .map(|&(kind, _, text)| match kind {
+            DiffLineType::Insert => diffy::Line::Insert(text),
+            DiffLineType::Delete => diffy::Line::Delete(text),
+            DiffLineType::Context => diffy::Line::Context(text),
+        })
+        .collect();
+    let emphasis = word_diff::hunk_emphasis(&diffy_lines);
+    let tab_width = tab_width_for(lang);
+
+    let DiffRenderStyleContext {
+        theme,
+        color_level,
+        diff_backgrounds,
+    } = render_context;
+    let mut content_index = 0;
+    let lines = hunk
+        .iter()
+        .map(|line| {
+            // `\ No newline at end of file` and similar markers.
+            if line.starts_with('\\') {
+                return RtLine::from(RtSpan::styled(
+                    line.to_string(),
+                    Style::default().add_modifier(Modifier::DIM),
+                ));
+            }
+            let (kind, sign, text) = content[content_index];
+            let (sign_style, plain_style) = match kind {
+                DiffLineType::Insert => (
+                    style_sign_add(theme, color_level, diff_backgrounds),
+                    style_add(theme, color_level, diff_backgrounds),
+                ),
+                DiffLineType::Delete => (
+                    style_sign_del(theme, color_level, diff_backgrounds),
+                    style_del(theme, color_level, diff_backgrounds),
+                ),
+                DiffLineType::Context => (style_context(), style_context()),
+            };
+            let spans = match syntax_lines
+                .as_ref()
+                .and_then(|lines| lines.get(content_index))
+            {
+                Some(spans) => spans
+                    .iter()
+                    .map(|span| {
+                        let style = if matches!(kind, DiffLineType::Delete) {
+                            span.style.add_modifier(Modifier::DIM)
+                        } else {
+                            span.style
+                        };
+                        RtSpan::styled(span.content.clone(), style)
+                    })
+                    .collect(),
+                None => vec![RtSpan::styled(text.to_string(), plain_style)],
+            };
+            let spans = word_diff::emphasize_spans(
+                spans,
+                &emphasis[content_index],
+                word_diff::style_word_emphasis(kind, theme, color_level),
+            );
+            let spans =
+                whitespace::expand_whitespace(spans, tab_width, /*show_whitespace*/ false);
+            content_index += 1;
+
+            let mut row = vec![RtSpan::styled(sign.to_string(), sign_style)];
+            row.extend(spans);
+            RtLine::from(row).style(style_line_bg_for(kind, diff_backgrounds))
+        })
+        .collect();
+    (lines, syntax_lines.is_some())
+}
+
+#[cfg(test)]
+#[path = "fenced_tests.rs"]
+mod tests;
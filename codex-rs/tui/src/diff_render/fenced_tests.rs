@@ -0,0 +1,68 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn line_text(line: &RtLine<'_>) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+const RUST_DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+--- a/not/a/header
++    let greeting = \"hi\";
+ }
+";
+
+#[test]
+fn named_rust_diff_keeps_text_and_composites_syntax_over_backgrounds() {
+    let lines = diff_block_to_lines(RUST_DIFF).expect("rust diff highlights");
+    let backgrounds = current_diff_render_style_context().diff_backgrounds;
+
+    assert_eq!(
+        lines.iter().map(line_text).collect::<Vec<_>>(),
+        RUST_DIFF.lines().collect::<Vec<_>>()
+    );
+    // The deleted line that looks like a file header stays inside the hunk.
+    assert_eq!(
+        lines[5].style,
+        style_line_bg_for(DiffLineType::Delete, backgrounds)
+    );
+    assert_eq!(
+        lines[6].style,
+        style_line_bg_for(DiffLineType::Insert, backgrounds)
+    );
+    assert!(
+        lines[6].spans.len() > 2,
+        "inserted line should carry syntax tokens: {:?}",
+        lines[6].spans
+    );
+}
+
+#[test]
+fn diffs_without_a_known_file_fall_back_to_the_diff_grammar() {
+    assert_eq!(diff_block_to_lines("@@\n-old\n+new\n"), None);
+    assert_eq!(
+        diff_block_to_lines("--- a/notes\n+++ b/notes\n@@ -1 +1 @@\n-old\n+new\n"),
+        None
+    );
+}
+
+#[test]
+fn bare_hunk_headers_run_until_a_line_without_a_sign() {
+    let lines = diff_block_to_lines("+++ b/main.py\n@@\n-x = 1\n+x = 2\ntrailing prose\n")
+        .expect("python diff highlights");
+    let backgrounds = current_diff_render_style_context().diff_backgrounds;
+
+    assert_eq!(
+        lines[3].style,
+        style_line_bg_for(DiffLineType::Insert, backgrounds)
+    );
+    assert_eq!(lines[4].style, Style::default());
+    assert_eq!(line_text(&lines[4]), "trailing prose");
+}
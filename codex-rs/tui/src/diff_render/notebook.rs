@@ -0,0 +1,72 @@
+//! Shows Jupyter notebook changes cell by cell instead of as raw notebook JSON.
+//!
+//! An `.ipynb` change is rewritten into a diff of each notebook's [`Notebook::render_text`] form,
+//! so the existing diff renderer shows cell headers, sources, and output summaries. Updates only
+//! carry a unified diff, so the other side is rebuilt from the file on disk, which may hold either
+//! the old notebook (approval previews) or the new one (applied patches).
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_utils_notebook::Notebook;
+
+use crate::diff_model::FileChange;
+
+pub(crate) fn readable_notebook_changes(
+    changes: HashMap<PathBuf, FileChange>,
+    cwd: &Path,
+) -> HashMap<PathBuf, FileChange> {
+    changes
+        .into_iter()
+        .map(|(path, change)| {
+            let change = if path.extension().is_some_and(|ext| ext == "ipynb") {
+                readable_change(&cwd.join(&path), &change).unwrap_or(change)
+            } else {
+                change
+            };
+            (path, change)
+        })
+        .collect()
+}
+
+/// Returns `None` when the notebook cannot be reconstructed; the raw JSON diff is shown instead.
+fn readable_change(path: &Path, change: &FileChange) -> Option<FileChange> {
+    match change {
+        FileChange::Add { content } => Some(FileChange::Add {
+            content: render(content)?,
+        }),
+        FileChange::Delete { content } => Some(FileChange::Delete {
+            content: render(content)?,
+        }),
+        FileChange::Update {
+            unified_diff,
+            move_path,
+        } => {
+            let patch = diffy::Patch::from_str(unified_diff).ok()?;
+            let on_disk = [move_path.as_deref(), Some(path)]
+                .into_iter()
+                .flatten()
+                .find_map(|path| std::fs::read_to_string(path).ok())?;
+            let (old, new) = match diffy::apply(&on_disk, &patch) {
+                Ok(new) => (on_disk, new),
+                Err(_) => (diffy::apply(&on_disk, &patch.reverse()).ok()?, on_disk),
+            };
+            let (old, new) = (render(&old)?, render(&new)?);
+            Some(FileChange::Update {
+                unified_diff: diffy::create_patch(&old, &new).to_string(),
+                move_path: move_path.clone(),
+            })
+        }
+    }
+}
+
+fn render(content: &str) -> Option<String> {
+    Notebook::parse(content)
+        .ok()
+        .map(|notebook| notebook.render_text())
+}
+
+#[cfg(test)]
+#[path = "notebook_tests.rs"]
+mod tests;
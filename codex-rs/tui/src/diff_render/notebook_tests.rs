@@ -0,0 +1,53 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn notebook(source: &str) -> String {
+    let mut notebook =
+        Notebook::parse(r#"{"cells": [], "metadata": {}, "nbformat": 4, "nbformat_minor": 4}"#)
+            .expect("parse");
+    notebook
+        .insert_cell(0, codex_utils_notebook::CellType::Code, source)
+        .expect("insert");
+    notebook.to_ipynb()
+}
+
+#[test]
+fn notebook_updates_are_shown_as_cell_diffs_from_either_side() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let old = notebook("x = 1\n");
+    let new = notebook("x = 2\n");
+    let change = FileChange::Update {
+        unified_diff: diffy::create_patch(&old, &new).to_string(),
+        move_path: None,
+    };
+    let expected = FileChange::Update {
+        unified_diff: diffy::create_patch(
+            "# %% [code] cell 0\nx = 1\n",
+            "# %% [code] cell 0\nx = 2\n",
+        )
+        .to_string(),
+        move_path: None,
+    };
+
+    for on_disk in [&old, &new] {
+        std::fs::write(dir.path().join("nb.ipynb"), on_disk).expect("write notebook");
+        let changes = readable_notebook_changes(
+            HashMap::from([(PathBuf::from("nb.ipynb"), change.clone())]),
+            dir.path(),
+        );
+        assert_eq!(changes[Path::new("nb.ipynb")], expected);
+    }
+}
+
+#[test]
+fn other_files_are_left_alone() {
+    let change = FileChange::Add {
+        content: "{}".to_string(),
+    };
+    let changes = readable_notebook_changes(
+        HashMap::from([(PathBuf::from("data.json"), change.clone())]),
+        Path::new("/"),
+    );
+
+    assert_eq!(changes[Path::new("data.json")], change);
+}
@@ -0,0 +1,106 @@
+//! Tab expansion and optional whitespace markers for diff line content.
+//!
+//! Runs before wrapping so tabs land on real tab stops for the file's language (see
+//! [`crate::render::tabs`]) rather than a fixed replacement. With `tui.diff_show_whitespace`, tabs
+//! render as `→` padded to the stop and trailing spaces as `·`, both dimmed, so whitespace-only
+//! edits are visible.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use ratatui::style::Modifier;
+use ratatui::text::Span as RtSpan;
+use unicode_width::UnicodeWidthChar;
+
+use crate::render::tabs::tab_advance;
+
+const TAB_MARKER: char = '→';
+const TRAILING_SPACE_MARKER: char = '·';
+
+static SHOW_WHITESPACE: AtomicBool = AtomicBool::new(false);
+
+/// Applies `tui.diff_show_whitespace` from config.
+pub(crate) fn set_show_whitespace_enabled(enabled: bool) {
+    SHOW_WHITESPACE.store(enabled, Ordering::Relaxed);
+}
+
+pub(super) fn show_whitespace_enabled() -> bool {
+    SHOW_WHITESPACE.load(Ordering::Relaxed)
+}
+
+/// Expands tabs in `spans` to `tab_width` stops, optionally marking tabs and trailing spaces.
+pub(super) fn expand_whitespace(
+    spans: Vec<RtSpan<'static>>,
+    tab_width: usize,
+    show_whitespace: bool,
+) -> Vec<RtSpan<'static>> {
+    if !show_whitespace && spans.iter().all(|span| !span.content.contains('\t')) {
+        return spans;
+    }
+    let trailing_start = if show_whitespace {
+        let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+        text.trim_end_matches([' ', '\t']).len()
+    } else {
+        usize::MAX
+    };
+
+    let mut out: Vec<RtSpan<'static>> = Vec::with_capacity(spans.len());
+    let mut offset = 0;
+    let mut col = 0;
+    for span in spans {
+        let mut plain = String::new();
+        let mut marked = String::new();
+        for (index, ch) in span.content.char_indices() {
+            let marker = match ch {
+                '\t' => {
+                    let advance = tab_advance(col, tab_width);
+                    col += advance;
+                    if show_whitespace {
+                        Some(format!(
+                            "{TAB_MARKER}{}",
+                            " ".repeat(advance.saturating_sub(1))
+                        ))
+                    } else {
+                        plain.extend(std::iter::repeat_n(' ', advance));
+                        None
+                    }
+                }
+                ' ' if offset + index >= trailing_start => {
+                    col += 1;
+                    Some(TRAILING_SPACE_MARKER.to_string())
+                }
+                _ => {
+                    col += ch.width().unwrap_or(0);
+                    plain.push(ch);
+                    None
+                }
+            };
+            if let Some(marker) = marker {
+                if !plain.is_empty() {
+                    out.push(RtSpan::styled(std::mem::take(&mut plain), span.style));
+                }
+                marked.push_str(&marker);
+            } else if !marked.is_empty() {
+                out.push(RtSpan::styled(
+                    std::mem::take(&mut marked),
+                    span.style.add_modifier(Modifier::DIM),
+                ));
+            }
+        }
+        if !marked.is_empty() {
+            out.push(RtSpan::styled(
+                marked,
+                span.style.add_modifier(Modifier::DIM),
+            ));
+        }
+        if !plain.is_empty() {
+            out.push(RtSpan::styled(plain, span.style));
+        }
+        offset += span.content.len();
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "whitespace_tests.rs"]
+mod tests;
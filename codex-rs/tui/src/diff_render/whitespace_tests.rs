@@ -0,0 +1,31 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use ratatui::style::Color;
+use ratatui::style::Style;
+
+#[test]
+fn tabs_expand_to_stops_across_spans() {
+    let keyword = Style::default().fg(Color::Magenta);
+    let spans = vec![RtSpan::styled("if", keyword), RtSpan::raw("\tx\ty")];
+
+    assert_eq!(
+        vec![RtSpan::styled("if", keyword), RtSpan::raw("  x   y")],
+        expand_whitespace(spans, /*tab_width*/ 4, /*show_whitespace*/ false)
+    );
+}
+
+#[test]
+fn show_whitespace_marks_tabs_and_trailing_spaces_only() {
+    let style = Style::default().fg(Color::Green);
+    let dim = style.add_modifier(Modifier::DIM);
+    let spans = vec![RtSpan::styled("\ta b  ", style)];
+
+    assert_eq!(
+        vec![
+            RtSpan::styled("→   ", dim),
+            RtSpan::styled("a b", style),
+            RtSpan::styled("··", dim),
+        ],
+        expand_whitespace(spans, /*tab_width*/ 4, /*show_whitespace*/ true)
+    );
+}
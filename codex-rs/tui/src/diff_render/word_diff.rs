@@ -0,0 +1,294 @@
+//! Intra-line emphasis for modified diff lines.
+//!
+//! A hunk that replaces lines shows up as a run of deletions immediately
+//! followed by a run of insertions.  Each deleted line is paired with the
+//! insertion at the same offset, both are split into word / whitespace /
+//! punctuation tokens on grapheme boundaries, and the tokens outside their
+//! longest common subsequence are emphasized with a stronger background
+//! (bold on ANSI-16) so a one-character edit in a long line is easy to spot.
+//!
+//! Pairs that share too little text, or are too long to compare cheaply, get
+//! no emphasis: highlighting nearly every token adds noise, not signal.
+
+use std::ops::Range;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Span as RtSpan;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use super::DiffColorLevel;
+use super::DiffLineType;
+use super::DiffTheme;
+use super::LIGHT_256_ADD_NUM_BG_IDX;
+use super::LIGHT_256_DEL_NUM_BG_IDX;
+use super::LIGHT_TC_ADD_NUM_BG_RGB;
+use super::LIGHT_TC_DEL_NUM_BG_RGB;
+use super::RichDiffColorLevel;
+use crate::terminal_palette::indexed_color;
+use crate::terminal_palette::rgb_color;
+
+// Emphasis backgrounds sit one step above the line tint.  Light themes reuse
+// the gutter pastels, matching GitHub's word highlights.
+const DARK_TC_ADD_EMPHASIS_BG_RGB: (u8, u8, u8) = (46, 99, 64); // #2E6340
+const DARK_TC_DEL_EMPHASIS_BG_RGB: (u8, u8, u8) = (122, 45, 38); // #7A2D26
+const DARK_256_ADD_EMPHASIS_BG_IDX: u8 = 28;
+const DARK_256_DEL_EMPHASIS_BG_IDX: u8 = 88;
+
+/// Upper bound on `old_tokens * new_tokens` for the LCS table of one pair.
+const MAX_LCS_CELLS: usize = 64 * 1024;
+
+static WORD_EMPHASIS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Applies `tui.diff_word_emphasis` from config.
+pub(crate) fn set_word_emphasis_enabled(enabled: bool) {
+    WORD_EMPHASIS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TokenClass {
+    Word,
+    Space,
+    Other,
+}
+
+/// Splits `text` into tokens, returned as byte ranges.
+///
+/// Runs of word characters and runs of whitespace form single tokens.
+/// Punctuation, emoji, and wide (CJK) graphemes are tokens on their own, since
+/// scripts without spaces would otherwise collapse into one giant "word".
+fn tokenize(text: &str) -> Vec<Range<usize>> {
+    let mut tokens: Vec<Range<usize>> = Vec::new();
+    let mut current: Option<(TokenClass, Range<usize>)> = None;
+    for (index, grapheme) in text.grapheme_indices(/*is_extended*/ true) {
+        let first = grapheme.chars().next().unwrap_or_default();
+        let class = if first.is_whitespace() {
+            TokenClass::Space
+        } else if (first.is_alphanumeric() || first == '_') && grapheme.width() < 2 {
+            TokenClass::Word
+        } else {
+            TokenClass::Other
+        };
+        let end = index + grapheme.len();
+        if let Some((current_class, range)) = current.as_mut()
+            && *current_class == class
+            && class != TokenClass::Other
+        {
+            range.end = end;
+            continue;
+        }
+        if let Some((_, range)) = current.replace((class, index..end)) {
+            tokens.push(range);
+        }
+    }
+    if let Some((_, range)) = current {
+        tokens.push(range);
+    }
+    tokens
+}
+
+/// Returns the byte ranges of changed tokens in `old` and `new`, or `None`
+/// when the lines are too different (or too long) for emphasis to help.
+pub(super) fn changed_ranges(
+    old: &str,
+    new: &str,
+) -> Option<(Vec<Range<usize>>, Vec<Range<usize>>)> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    if old_tokens.len().saturating_mul(new_tokens.len()) > MAX_LCS_CELLS {
+        return None;
+    }
+
+    // lcs[i][j] is the LCS length of old_tokens[i..] and new_tokens[j..].
+    let columns = new_tokens.len() + 1;
+    let mut lcs = vec![0usize; (old_tokens.len() + 1) * columns];
+    for i in (0..old_tokens.len()).rev() {
+        for j in (0..new_tokens.len()).rev() {
+            lcs[i * columns + j] = if old[old_tokens[i].clone()] == new[new_tokens[j].clone()] {
+                lcs[(i + 1) * columns + j + 1] + 1
+            } else {
+                lcs[(i + 1) * columns + j].max(lcs[i * columns + j + 1])
+            };
+        }
+    }
+
+    let mut old_changed = vec![true; old_tokens.len()];
+    let mut new_changed = vec![true; new_tokens.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < old_tokens.len() && j < new_tokens.len() {
+        if old[old_tokens[i].clone()] == new[new_tokens[j].clone()] {
+            old_changed[i] = false;
+            new_changed[j] = false;
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * columns + j] >= lcs[i * columns + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    let shared: usize = old_tokens
+        .iter()
+        .zip(&old_changed)
+        .filter(|(range, changed)| !**changed && !old[(*range).clone()].trim().is_empty())
+        .map(|(range, _)| range.len())
+        .sum();
+    let longest = old.trim().len().max(new.trim().len());
+    if shared * 3 < longest {
+        return None;
+    }
+
+    Some((
+        merge_changed(&old_tokens, &old_changed),
+        merge_changed(&new_tokens, &new_changed),
+    ))
+}
+
+/// Collapses adjacent changed tokens into contiguous byte ranges.
+fn merge_changed(tokens: &[Range<usize>], changed: &[bool]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for (token, _) in tokens.iter().zip(changed).filter(|(_, changed)| **changed) {
+        match ranges.last_mut() {
+            Some(last) if last.end == token.start => last.end = token.end,
+            _ => ranges.push(token.clone()),
+        }
+    }
+    ranges
+}
+
+/// Computes emphasis ranges for every line of a hunk, indexed like
+/// `hunk_lines`.  Context lines and unpaired insertions/deletions get none.
+pub(super) fn hunk_emphasis(hunk_lines: &[diffy::Line<'_, str>]) -> Vec<Vec<Range<usize>>> {
+    hunk_emphasis_with(hunk_lines, WORD_EMPHASIS_ENABLED.load(Ordering::Relaxed))
+}
+
+/// [`hunk_emphasis`] with word emphasis `enabled` given explicitly rather than read from config.
+fn hunk_emphasis_with(
+    hunk_lines: &[diffy::Line<'_, str>],
+    enabled: bool,
+) -> Vec<Vec<Range<usize>>> {
+    let mut emphasis = vec![Vec::new(); hunk_lines.len()];
+    if !enabled {
+        return emphasis;
+    }
+
+    let mut index = 0;
+    while index < hunk_lines.len() {
+        let deletes_start = index;
+        while matches!(hunk_lines.get(index), Some(diffy::Line::Delete(_))) {
+            index += 1;
+        }
+        let inserts_start = index;
+        while matches!(hunk_lines.get(index), Some(diffy::Line::Insert(_))) {
+            index += 1;
+        }
+        if deletes_start == inserts_start || inserts_start == index {
+            index = index.max(deletes_start + 1);
+            continue;
+        }
+        for (old_index, new_index) in (deletes_start..inserts_start).zip(inserts_start..index) {
+            let (diffy::Line::Delete(old), diffy::Line::Insert(new)) =
+                (&hunk_lines[old_index], &hunk_lines[new_index])
+            else {
+                continue;
+            };
+            if let Some((old_ranges, new_ranges)) =
+                changed_ranges(old.trim_end_matches('\n'), new.trim_end_matches('\n'))
+            {
+                emphasis[old_index] = old_ranges;
+                emphasis[new_index] = new_ranges;
+            }
+        }
+    }
+    emphasis
+}
+
+/// Style layered over the changed tokens of an insert or delete line.
+pub(super) fn style_word_emphasis(
+    kind: DiffLineType,
+    theme: DiffTheme,
+    color_level: DiffColorLevel,
+) -> Style {
+    let Some(level) = RichDiffColorLevel::from_diff_color_level(color_level) else {
+        return Style::default().add_modifier(Modifier::BOLD);
+    };
+    let bg: Color = match (kind, theme, level) {
+        (DiffLineType::Insert, DiffTheme::Dark, RichDiffColorLevel::TrueColor) => {
+            rgb_color(DARK_TC_ADD_EMPHASIS_BG_RGB)
+        }
+        (DiffLineType::Insert, DiffTheme::Dark, RichDiffColorLevel::Ansi256) => {
+            indexed_color(DARK_256_ADD_EMPHASIS_BG_IDX)
+        }
+        (DiffLineType::Insert, DiffTheme::Light, RichDiffColorLevel::TrueColor) => {
+            rgb_color(LIGHT_TC_ADD_NUM_BG_RGB)
+        }
+        (DiffLineType::Insert, DiffTheme::Light, RichDiffColorLevel::Ansi256) => {
+            indexed_color(LIGHT_256_ADD_NUM_BG_IDX)
+        }
+        (DiffLineType::Delete, DiffTheme::Dark, RichDiffColorLevel::TrueColor) => {
+            rgb_color(DARK_TC_DEL_EMPHASIS_BG_RGB)
+        }
+        (DiffLineType::Delete, DiffTheme::Dark, RichDiffColorLevel::Ansi256) => {
+            indexed_color(DARK_256_DEL_EMPHASIS_BG_IDX)
+        }
+        (DiffLineType::Delete, DiffTheme::Light, RichDiffColorLevel::TrueColor) => {
+            rgb_color(LIGHT_TC_DEL_NUM_BG_RGB)
+        }
+        (DiffLineType::Delete, DiffTheme::Light, RichDiffColorLevel::Ansi256) => {
+            indexed_color(LIGHT_256_DEL_NUM_BG_IDX)
+        }
+        (DiffLineType::Context, _, _) => return Style::default(),
+    };
+    Style::default().bg(bg)
+}
+
+/// Splits `spans` at the boundaries of `ranges` (byte offsets into the
+/// concatenated span text) and patches `emphasis` onto the covered pieces.
+pub(super) fn emphasize_spans(
+    spans: Vec<RtSpan<'static>>,
+    ranges: &[Range<usize>],
+    emphasis: Style,
+) -> Vec<RtSpan<'static>> {
+    if ranges.is_empty() {
+        return spans;
+    }
+    let mut out: Vec<RtSpan<'static>> = Vec::with_capacity(spans.len() + ranges.len() * 2);
+    let mut offset = 0;
+    for span in spans {
+        let text = span.content.as_ref();
+        let span_range = offset..offset + text.len();
+        let mut cuts = vec![0, text.len()];
+        for range in ranges {
+            for boundary in [range.start, range.end] {
+                if span_range.contains(&boundary) && text.is_char_boundary(boundary - offset) {
+                    cuts.push(boundary - offset);
+                }
+            }
+        }
+        cuts.sort_unstable();
+        cuts.dedup();
+        for piece in cuts.windows(2) {
+            let (start, end) = (piece[0], piece[1]);
+            let emphasized = ranges
+                .iter()
+                .any(|range| range.start <= offset + start && offset + end <= range.end);
+            let style = if emphasized {
+                span.style.patch(emphasis)
+            } else {
+                span.style
+            };
+            out.push(RtSpan::styled(text[start..end].to_string(), style));
+        }
+        offset = span_range.end;
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "word_diff_tests.rs"]
+mod tests;
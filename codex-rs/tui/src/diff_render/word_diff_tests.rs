@@ -0,0 +1,106 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn changed_text<'a>(text: &'a str, ranges: &[Range<usize>]) -> Vec<&'a str> {
+    ranges.iter().map(|range| &text[range.clone()]).collect()
+}
+
+fn assert_changed(old: &str, new: &str, expected_old: &[&str], expected_new: &[&str]) {
+    let (old_ranges, new_ranges) = changed_ranges(old, new).expect("lines are similar");
+    assert_eq!(changed_text(old, &old_ranges), expected_old, "{old:?}");
+    assert_eq!(changed_text(new, &new_ranges), expected_new, "{new:?}");
+}
+
+#[test]
+fn single_character_change_emphasizes_only_that_token() {
+    assert_changed(
+        "let total = compute_sum(values, offset + 1, config.limit);",
+        "let total = compute_sum(values, offset + 2, config.limit);",
+        &["1"],
+        &["2"],
+    );
+    assert_changed("a.b(c)", "a.b(c, d)", &[], &[", d"]);
+}
+
+#[test]
+fn unicode_tokens_are_compared_by_grapheme() {
+    // CJK graphemes are tokens on their own, so one changed character stays small.
+    assert_changed("日本語のテキスト", "日本語のテクスト", &["キ"], &["ク"]);
+    // A ZWJ family is a single token and is never split mid-sequence.
+    assert_changed(
+        "greet 👨\u{200d}👩\u{200d}👧 family",
+        "greet 👨\u{200d}👩\u{200d}👦 family",
+        &["👨\u{200d}👩\u{200d}👧"],
+        &["👨\u{200d}👩\u{200d}👦"],
+    );
+    // Combining accents belong to their word.
+    assert_changed(
+        "print(\"ecole\")",
+        "print(\"e\u{301}cole\")",
+        &["ecole"],
+        &["e\u{301}cole"],
+    );
+    assert_changed("flag = 🇺🇸;", "flag = 🇯🇵;", &["🇺🇸"], &["🇯🇵"]);
+}
+
+#[test]
+fn dissimilar_lines_get_no_emphasis() {
+    assert_eq!(
+        changed_ranges("return Ok(value);", "panic!(\"unreachable state\")"),
+        None
+    );
+}
+
+#[test]
+fn hunk_emphasis_pairs_deletions_with_following_insertions() {
+    let lines = [
+        diffy::Line::Context("fn main() {\n"),
+        diffy::Line::Delete("    let x = 1;\n"),
+        diffy::Line::Delete("    let y = 2;\n"),
+        diffy::Line::Insert("    let x = 10;\n"),
+        diffy::Line::Context("}\n"),
+        diffy::Line::Insert("// trailing\n"),
+    ];
+
+    let emphasis = hunk_emphasis_with(&lines, /*enabled*/ true);
+    let rendered: Vec<Vec<&str>> = lines
+        .iter()
+        .zip(&emphasis)
+        .map(|(line, ranges)| {
+            let (diffy::Line::Context(text)
+            | diffy::Line::Delete(text)
+            | diffy::Line::Insert(text)) = line;
+            changed_text(text, ranges)
+        })
+        .collect();
+    assert_eq!(
+        rendered,
+        vec![vec![], vec!["1"], vec![], vec!["10"], vec![], vec![]]
+    );
+
+    let disabled = hunk_emphasis_with(&lines, /*enabled*/ false);
+    assert!(disabled.iter().all(Vec::is_empty));
+}
+
+#[test]
+fn emphasize_spans_splits_across_span_boundaries() {
+    let keyword = Style::default().fg(Color::Magenta);
+    let plain = Style::default();
+    let emphasis = Style::default().bg(Color::Green);
+    let spans = vec![
+        RtSpan::styled("let ", keyword),
+        RtSpan::styled("café = 1", plain),
+    ];
+
+    let emphasized = emphasize_spans(spans, &[2..7], emphasis);
+
+    assert_eq!(
+        emphasized,
+        vec![
+            RtSpan::styled("le", keyword),
+            RtSpan::styled("t ", keyword.patch(emphasis)),
+            RtSpan::styled("caf", plain.patch(emphasis)),
+            RtSpan::styled("é = 1", plain),
+        ]
+    );
+}
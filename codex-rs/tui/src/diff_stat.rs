@@ -0,0 +1,218 @@
+//! `git diff --stat`-style summaries of the files a turn changed.
+//!
+//! Core tracks every patch applied during a turn and reports the aggregate as
+//! a single git-style unified diff (`turn/diffUpdated`).  [`parse_turn_diff`]
+//! splits that diff back into per-file [`FileChange`]s so the summary cell can
+//! show one compact row per file here, and the transcript overlay can expand
+//! the same changes into the full diff via
+//! [`crate::diff_render::create_diff_summary`].
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use unicode_width::UnicodeWidthStr;
+
+use crate::diff_model::FileChange;
+use crate::diff_render::display_path_for;
+use crate::diff_render::line_counts;
+use crate::text_formatting::center_truncate_path;
+use crate::ui_consts::TRANSCRIPT_HINT;
+
+const DEV_NULL: &str = "/dev/null";
+/// Columns used by the `  └ ` / `    ` row prefix.
+const ROW_PREFIX_COLS: usize = 4;
+/// The path column gives way before the bar shrinks below this many columns.
+const MIN_BAR_COLS: usize = 10;
+
+/// Splits a multi-file git-style unified diff into per-file changes keyed by
+/// the original path.  Sections without `---`/`+++` headers (mode-only or
+/// binary changes) are skipped.
+pub(crate) fn parse_turn_diff(unified_diff: &str) -> HashMap<PathBuf, FileChange> {
+    let mut changes = HashMap::new();
+    let mut sections: Vec<&str> = Vec::new();
+    let mut start = None;
+    for (offset, _) in unified_diff.match_indices("diff --git ") {
+        if offset != 0 && !unified_diff[..offset].ends_with('\n') {
+            continue;
+        }
+        if let Some(start) = start.replace(offset) {
+            sections.push(&unified_diff[start..offset]);
+        }
+    }
+    if let Some(start) = start {
+        sections.push(&unified_diff[start..]);
+    }
+
+    for section in sections {
+        let Some(body_start) = section
+            .match_indices("\n--- ")
+            .next()
+            .map(|(offset, _)| offset + 1)
+        else {
+            continue;
+        };
+        let body = &section[body_start..];
+        let mut lines = body.lines();
+        let (Some(old_header), Some(new_header)) = (
+            lines.next().and_then(|line| line.strip_prefix("--- ")),
+            lines.next().and_then(|line| line.strip_prefix("+++ ")),
+        ) else {
+            continue;
+        };
+        let old_path = header_path(old_header, "a/");
+        let new_path = header_path(new_header, "b/");
+        let hunk_text = |sign: char| -> String {
+            lines
+                .clone()
+                .filter_map(|line| line.strip_prefix(sign))
+                .map(|line| format!("{line}\n"))
+                .collect()
+        };
+        let (path, change) = match (old_path, new_path) {
+            (None, Some(new_path)) => (
+                new_path,
+                FileChange::Add {
+                    content: hunk_text('+'),
+                },
+            ),
+            (Some(old_path), None) => (
+                old_path,
+                FileChange::Delete {
+                    content: hunk_text('-'),
+                },
+            ),
+            (Some(old_path), Some(new_path)) => {
+                let move_path = (old_path != new_path).then(|| new_path.clone());
+                (
+                    old_path,
+                    FileChange::Update {
+                        unified_diff: body.to_string(),
+                        move_path,
+                    },
+                )
+            }
+            (None, None) => continue,
+        };
+        changes.insert(path, change);
+    }
+    changes
+}
+
+fn header_path(header: &str, prefix: &str) -> Option<PathBuf> {
+    let header = header.split('\t').next().unwrap_or(header).trim_end();
+    if header == DEV_NULL {
+        return None;
+    }
+    Some(PathBuf::from(header.strip_prefix(prefix).unwrap_or(header)))
+}
+
+/// Renders the summary header and one `path | count +++---` row per file,
+/// sized to `width` columns.
+pub(crate) fn diff_stat_lines(
+    changes: &HashMap<PathBuf, FileChange>,
+    cwd: &Path,
+    width: usize,
+) -> Vec<Line<'static>> {
+    let mut rows: Vec<(String, usize, usize)> = changes
+        .iter()
+        .map(|(path, change)| {
+            let mut label = display_path_for(path, cwd);
+            if let FileChange::Update {
+                move_path: Some(move_path),
+                ..
+            } = change
+            {
+                label = format!("{label} → {}", display_path_for(move_path, cwd));
+            }
+            let (added, removed) = line_counts(change);
+            (label, added, removed)
+        })
+        .collect();
+    rows.sort();
+
+    let total_added: usize = rows.iter().map(|(_, added, _)| added).sum();
+    let total_removed: usize = rows.iter().map(|(_, _, removed)| removed).sum();
+    let noun = if rows.len() == 1 { "file" } else { "files" };
+    let mut lines = vec![Line::from(vec![
+        "• ".dim(),
+        format!("{} {noun} changed", rows.len()).bold(),
+        ", ".into(),
+        format!("+{total_added}").green(),
+        " ".into(),
+        format!("-{total_removed}").red(),
+    ])];
+
+    let max_total = rows
+        .iter()
+        .map(|(_, added, removed)| added + removed)
+        .max()
+        .unwrap_or(0);
+    let count_cols = max_total.to_string().len();
+    let available = width.saturating_sub(ROW_PREFIX_COLS + " | ".len() + count_cols + 1);
+    let longest_label = rows
+        .iter()
+        .map(|(label, _, _)| label.width())
+        .max()
+        .unwrap_or(0);
+    let label_cols = longest_label
+        .min(available.saturating_sub(MIN_BAR_COLS))
+        .max(1);
+    let bar_cols = available.saturating_sub(label_cols).max(1);
+
+    for (index, (label, added, removed)) in rows.into_iter().enumerate() {
+        let label = center_truncate_path(&label, label_cols);
+        let padding = " ".repeat(label_cols.saturating_sub(label.width()));
+        let (plus, minus) = scale_bar(added, removed, max_total, bar_cols);
+        let prefix = if index == 0 { "  └ " } else { "    " };
+        let mut spans: Vec<Span<'static>> = vec![
+            prefix.dim(),
+            format!("{label}{padding} | ").into(),
+            format!("{:>count_cols$} ", added + removed).into(),
+        ];
+        if plus > 0 {
+            spans.push("+".repeat(plus).green());
+        }
+        if minus > 0 {
+            spans.push("-".repeat(minus).red());
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(vec![
+        "    ".into(),
+        format!("({TRANSCRIPT_HINT})").dim(),
+    ]));
+    lines
+}
+
+/// Scales `added`/`removed` into at most `bar_cols` bar characters the way
+/// `git diff --stat` does: unscaled when the largest file fits, otherwise
+/// proportional, never rounding a non-zero side down to nothing.
+fn scale_bar(added: usize, removed: usize, max_total: usize, bar_cols: usize) -> (usize, usize) {
+    if max_total <= bar_cols {
+        return (added, removed);
+    }
+    let scale = |count: usize| {
+        if count == 0 {
+            0
+        } else {
+            (count * bar_cols / max_total).max(1)
+        }
+    };
+    let total = added + removed;
+    let scaled_total = scale(total);
+    let plus = scale(added).min(scaled_total);
+    let minus = if removed == 0 {
+        0
+    } else {
+        scaled_total.saturating_sub(plus).max(1)
+    };
+    (plus, minus)
+}
+
+#[cfg(test)]
+#[path = "diff_stat_tests.rs"]
+mod tests;
@@ -0,0 +1,128 @@
+use super::*;
+use insta::assert_snapshot;
+use pretty_assertions::assert_eq;
+
+const TURN_DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@
+ fn a() {}
+-fn b() {}
++fn b() { 1 }
++fn c() {}
+ fn d() {}
+diff --git a/docs/new.md b/docs/new.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/docs/new.md
+@@ -0,0 +1,2 @@
++# Title
++body
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+index 4444444..0000000
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+";
+
+fn lines_to_text(lines: &[Line<'static>]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn parse_turn_diff_splits_files_by_kind() {
+    let changes = parse_turn_diff(TURN_DIFF);
+
+    assert_eq!(
+        changes,
+        HashMap::from([
+            (
+                PathBuf::from("src/lib.rs"),
+                FileChange::Update {
+                    unified_diff: "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,3 +1,4 @@\n fn a() {}\n-fn b() {}\n+fn b() { 1 }\n+fn c() {}\n fn d() {}\n".to_string(),
+                    move_path: None,
+                },
+            ),
+            (
+                PathBuf::from("docs/new.md"),
+                FileChange::Add {
+                    content: "# Title\nbody\n".to_string(),
+                },
+            ),
+            (
+                PathBuf::from("old.txt"),
+                FileChange::Delete {
+                    content: "gone\n".to_string(),
+                },
+            ),
+        ])
+    );
+}
+
+#[test]
+fn parse_turn_diff_keeps_renames_under_the_original_path() {
+    let changes =
+        parse_turn_diff("diff --git a/a.rs b/b.rs\n--- a/a.rs\n+++ b/b.rs\n@@ -1 +1 @@\n-x\n+y\n");
+
+    assert_eq!(
+        changes,
+        HashMap::from([(
+            PathBuf::from("a.rs"),
+            FileChange::Update {
+                unified_diff: "--- a/a.rs\n+++ b/b.rs\n@@ -1 +1 @@\n-x\n+y\n".to_string(),
+                move_path: Some(PathBuf::from("b.rs")),
+            },
+        )])
+    );
+    assert_eq!(parse_turn_diff(""), HashMap::new());
+}
+
+#[test]
+fn diff_stat_lines_render_per_file_bars() {
+    let changes = parse_turn_diff(TURN_DIFF);
+
+    assert_snapshot!(
+        "diff_stat_lines_render_per_file_bars",
+        lines_to_text(&diff_stat_lines(
+            &changes,
+            Path::new("/"),
+            /*width*/ 40
+        ))
+    );
+}
+
+#[test]
+fn scale_bar_fits_large_changes_into_the_bar() {
+    assert_eq!(
+        scale_bar(
+            /*added*/ 2, /*removed*/ 3, /*max_total*/ 5, /*bar_cols*/ 10
+        ),
+        (2, 3)
+    );
+    assert_eq!(
+        scale_bar(
+            /*added*/ 300, /*removed*/ 100, /*max_total*/ 400, /*bar_cols*/ 40
+        ),
+        (30, 10)
+    );
+    assert_eq!(
+        scale_bar(
+            /*added*/ 1, /*removed*/ 1, /*max_total*/ 1000, /*bar_cols*/ 10
+        ),
+        (1, 1)
+    );
+}
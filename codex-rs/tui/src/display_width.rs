@@ -0,0 +1,56 @@
+//! Grapheme-aware display width helpers for width-bounded cuts.
+//!
+//! Terminals lay out grapheme clusters, not `char`s: a ZWJ emoji family, a flag, or a letter with
+//! combining marks each occupy a single cell group. Summing [`unicode_width::UnicodeWidthChar`]
+//! over chars over-counts ZWJ sequences, and cutting between chars can split a cluster so the
+//! terminal draws something wider or narrower than we measured. Either way borders misalign and
+//! the cursor drifts. Code that truncates or breaks text at a column budget should measure and
+//! cut through these helpers so the measured width always matches what the terminal draws and
+//! what ratatui's `Span::width` reports.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Iterates `(byte_index, grapheme, display_width)` over the extended grapheme clusters of `text`.
+pub(crate) fn graphemes_with_width(text: &str) -> impl Iterator<Item = (usize, &str, usize)> {
+    text.grapheme_indices(/*is_extended*/ true)
+        .map(|(index, grapheme)| (index, grapheme, grapheme.width()))
+}
+
+/// Returns the byte length and display width of the longest prefix of `text` that fits in
+/// `max_cols`, ending on a grapheme boundary.
+///
+/// Zero-width clusters directly after the cut are included, so the prefix never strands a
+/// trailing mark or joiner at the start of the remainder.
+pub(crate) fn prefix_by_width(text: &str, max_cols: usize) -> (usize, usize) {
+    let mut cols = 0usize;
+    let mut end = 0usize;
+    for (index, grapheme, width) in graphemes_with_width(text) {
+        if cols.saturating_add(width) > max_cols {
+            break;
+        }
+        cols += width;
+        end = index + grapheme.len();
+    }
+    (end, cols)
+}
+
+/// Returns the byte index and display width of the longest suffix of `text` that fits in
+/// `max_cols`, starting on a grapheme boundary.
+pub(crate) fn suffix_by_width(text: &str, max_cols: usize) -> (usize, usize) {
+    let mut cols = 0usize;
+    let mut start = text.len();
+    for (index, grapheme) in text.grapheme_indices(/*is_extended*/ true).rev() {
+        let width = grapheme.width();
+        if cols.saturating_add(width) > max_cols {
+            break;
+        }
+        cols += width;
+        start = index;
+    }
+    (start, cols)
+}
+
+#[cfg(test)]
+#[path = "display_width_tests.rs"]
+mod tests;
@@ -0,0 +1,105 @@
+//! Width harness: every width-bounded helper must stay within its budget and cut on grapheme
+//! boundaries for each tricky fixture at every budget up to the fixture's full width.
+
+use super::*;
+use crate::line_truncation::truncate_line_with_ellipsis_if_overflow;
+use crate::live_wrap::RowBuilder;
+use crate::live_wrap::take_prefix_by_width;
+use crate::text_formatting::center_truncate_path;
+use pretty_assertions::assert_eq;
+use ratatui::text::Line;
+
+const WIDTH_FIXTURES: &[&str] = &[
+    "plain ascii text",
+    "日本語のテキストと中文",
+    "👨\u{200d}👩\u{200d}👧\u{200d}👦 family emoji",
+    "🇺🇸🇯🇵 flag pairs",
+    "e\u{301}cole with combining accent",
+    "👍🏽 skin tone modifier",
+    "❤\u{fe0f} variation selector",
+    "mixed ascii 漢字 and 🎉 party",
+    "ｆｕｌｌｗｉｄｔｈ latin",
+];
+
+fn grapheme_boundaries(text: &str) -> Vec<usize> {
+    let mut boundaries: Vec<usize> = text
+        .grapheme_indices(/*is_extended*/ true)
+        .map(|(index, _)| index)
+        .collect();
+    boundaries.push(text.len());
+    boundaries
+}
+
+#[test]
+fn prefix_and_suffix_cut_on_grapheme_boundaries_within_budget() {
+    for text in WIDTH_FIXTURES {
+        let boundaries = grapheme_boundaries(text);
+        for max_cols in 0..=text.width() + 1 {
+            let (end, cols) = prefix_by_width(text, max_cols);
+            assert!(boundaries.contains(&end), "{text:?} prefix at {max_cols}");
+            assert_eq!(cols, text[..end].width(), "{text:?} prefix at {max_cols}");
+            assert!(cols <= max_cols, "{text:?} prefix at {max_cols}");
+
+            let (start, cols) = suffix_by_width(text, max_cols);
+            assert!(boundaries.contains(&start), "{text:?} suffix at {max_cols}");
+            assert_eq!(cols, text[start..].width(), "{text:?} suffix at {max_cols}");
+            assert!(cols <= max_cols, "{text:?} suffix at {max_cols}");
+        }
+        assert_eq!(
+            prefix_by_width(text, text.width()),
+            (text.len(), text.width())
+        );
+    }
+}
+
+#[test]
+fn truncation_helpers_stay_within_budget() {
+    for text in WIDTH_FIXTURES {
+        for max_cols in 0..=text.width() + 1 {
+            let (prefix, suffix, cols) = take_prefix_by_width(text, max_cols);
+            assert_eq!(format!("{prefix}{suffix}"), *text);
+            assert_eq!(cols, prefix.width(), "{text:?} at {max_cols}");
+            assert!(cols <= max_cols, "{text:?} at {max_cols}");
+
+            let line = truncate_line_with_ellipsis_if_overflow(Line::from(*text), max_cols);
+            assert!(line.width() <= max_cols, "{text:?} ellipsis at {max_cols}");
+
+            let path = center_truncate_path(text, max_cols);
+            assert!(path.width() <= max_cols, "{text:?} path at {max_cols}");
+        }
+    }
+}
+
+#[test]
+fn row_builder_rows_fit_and_round_trip() {
+    for text in WIDTH_FIXTURES {
+        // Rows narrower than the widest grapheme necessarily overflow by design.
+        let widest = graphemes_with_width(text)
+            .map(|(_, _, width)| width)
+            .max()
+            .unwrap_or(1);
+        for width in widest.max(1)..=text.width() {
+            let mut builder = RowBuilder::new(width);
+            builder.push_fragment(text);
+            let rows = builder.display_rows();
+            assert!(
+                rows.iter().all(|row| row.width() <= width),
+                "{text:?} at {width}: {rows:?}"
+            );
+            let joined: String = rows.iter().map(|row| row.text.as_str()).collect();
+            assert_eq!(joined, *text);
+        }
+    }
+}
+
+#[test]
+fn combining_marks_stay_with_their_base() {
+    assert_eq!(
+        take_prefix_by_width("e\u{301}x", /*max_cols*/ 1),
+        ("e\u{301}".to_string(), "x", 1)
+    );
+    assert_eq!(
+        take_prefix_by_width("日本", /*max_cols*/ 3),
+        ("日".to_string(), "本", 2)
+    );
+}
@@ -12,12 +12,14 @@ use crate::motion::activity_indicator;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::line_utils::prefix_lines;
 use crate::render::line_utils::push_owned_lines;
+use crate::transcript_density::transcript_density;
 use crate::ui_consts::TRANSCRIPT_HINT;
 use crate::wrapping::RtOptions;
 use crate::wrapping::adaptive_wrap_line;
 use crate::wrapping::adaptive_wrap_lines;
 use codex_ansi_escape::ansi_escape_line;
 use codex_app_server_protocol::CommandExecutionSource as ExecCommandSource;
+use codex_config::types::TranscriptDensity;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_shell_command::bash::extract_bash_command;
 use codex_utils_elapsed::format_duration;
@@ -188,7 +190,7 @@ impl HistoryCell for ExecCell {
         if self.is_exploring_cell() {
             self.exploring_display_lines(width)
         } else {
-            self.command_display_lines(width)
+            self.command_display_lines(width, transcript_density())
         }
     }
 
@@ -349,7 +351,7 @@ impl ExecCell {
         out
     }
 
-    fn command_display_lines(&self, width: u16) -> Vec<Line<'static>> {
+    fn command_display_lines(&self, width: u16, density: TranscriptDensity) -> Vec<Line<'static>> {
         let [call] = &self.calls.as_slice() else {
             panic!("Expected exactly one call in a command display cell");
         };
@@ -414,12 +416,18 @@ impl ExecCell {
             }
         }
 
+        let truncate = match density {
+            TranscriptDensity::Compact => return vec![header_line],
+            TranscriptDensity::Normal => true,
+            TranscriptDensity::Verbose => false,
+        };
         let mut lines: Vec<Line<'static>> = vec![header_line];
 
-        let continuation_lines = Self::limit_lines_from_start(
-            &continuation_lines,
-            layout.command_continuation_max_lines,
-        );
+        let continuation_lines = if truncate {
+            Self::limit_lines_from_start(&continuation_lines, layout.command_continuation_max_lines)
+        } else {
+            continuation_lines
+        };
         if !continuation_lines.is_empty() {
             lines.extend(prefix_lines(
                 continuation_lines,
@@ -429,7 +437,9 @@ impl ExecCell {
         }
 
         if let Some(output) = call.output.as_ref() {
-            let line_limit = if call.is_user_shell_command() {
+            let line_limit = if !truncate {
+                usize::MAX
+            } else if call.is_user_shell_command() {
                 USER_SHELL_TOOL_CALL_MAX_LINES
             } else {
                 TOOL_CALL_MAX_LINES
@@ -477,15 +487,19 @@ impl ExecCell {
                     Span::from(layout.output_block.initial_prefix).dim(),
                     Span::from(layout.output_block.subsequent_prefix),
                 );
-                let trimmed_output = Self::truncate_lines_middle(
-                    &prefixed_output,
-                    display_limit,
-                    width,
-                    raw_output.omitted,
-                    Some(Line::from(
-                        Span::from(layout.output_block.subsequent_prefix).dim(),
-                    )),
-                );
+                let trimmed_output = if truncate {
+                    Self::truncate_lines_middle(
+                        &prefixed_output,
+                        display_limit,
+                        width,
+                        raw_output.omitted,
+                        Some(Line::from(
+                            Span::from(layout.output_block.subsequent_prefix).dim(),
+                        )),
+                    )
+                } else {
+                    prefixed_output
+                };
 
                 if !trimmed_output.is_empty() {
                     lines.extend(trimmed_output);
@@ -777,7 +791,7 @@ mod tests {
         let cell = ExecCell::new(call, /*animations_enabled*/ false);
 
         // Use a narrow width so each logical line wraps into many on-screen lines.
-        let lines = cell.command_display_lines(width);
+        let lines = cell.command_display_lines(width, TranscriptDensity::Normal);
         let rendered_rows = Paragraph::new(Text::from(lines.clone()))
             .wrap(Wrap { trim: false })
             .line_count(width);
@@ -1017,7 +1031,7 @@ mod tests {
 
         let cell = ExecCell::new(call, /*animations_enabled*/ false);
         let rendered: Vec<String> = cell
-            .command_display_lines(/*width*/ 36)
+            .command_display_lines(/*width*/ 36, TranscriptDensity::Normal)
             .iter()
             .map(|line| {
                 line.spans
@@ -1049,12 +1063,12 @@ mod tests {
 
         let cell = ExecCell::new(call, /*animations_enabled*/ false);
         let first: Vec<String> = cell
-            .command_display_lines(/*width*/ 80)
+            .command_display_lines(/*width*/ 80, TranscriptDensity::Normal)
             .iter()
             .map(render_line_text)
             .collect();
         let second: Vec<String> = cell
-            .command_display_lines(/*width*/ 80)
+            .command_display_lines(/*width*/ 80, TranscriptDensity::Normal)
             .iter()
             .map(render_line_text)
             .collect();
@@ -1063,6 +1077,42 @@ mod tests {
         assert_eq!(first, vec!["• Running echo done".to_string()]);
     }
 
+    #[test]
+    fn transcript_density_controls_command_output() {
+        let output = (1..=12).map(|n| format!("line {n}\n")).collect::<String>();
+        let call = ExecCall {
+            call_id: "call-id".to_string(),
+            command: vec!["bash".into(), "-lc".into(), "seq 12".into()],
+            parsed: Vec::new(),
+            output: Some(CommandOutput::new(/*exit_code*/ 0, output)),
+            source: ExecCommandSource::Agent,
+            start_time: None,
+            duration: Some(std::time::Duration::from_millis(5)),
+            interaction_input: None,
+        };
+        let cell = ExecCell::new(call, /*animations_enabled*/ false);
+        let render = |density| {
+            cell.command_display_lines(/*width*/ 80, density)
+                .iter()
+                .map(render_line_text)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            render(TranscriptDensity::Compact),
+            vec!["• Ran seq 12".to_string()]
+        );
+        assert!(
+            render(TranscriptDensity::Normal)
+                .iter()
+                .any(|line| line.contains("… +")),
+            "normal density should truncate long output"
+        );
+        let verbose = render(TranscriptDensity::Verbose);
+        assert_eq!(verbose.len(), 13, "{verbose:?}");
+        assert_eq!(verbose[12], "    line 12");
+    }
+
     #[test]
     fn exploring_display_does_not_split_long_url_like_search_query() {
         let url_like = "example.test/api/v1/projects/alpha-team/releases/2026-02-17/builds/1234567890/artifacts/reports/performance/summary/detail/with/a/very/long/path";
@@ -1120,7 +1170,7 @@ mod tests {
 
         let cell = ExecCell::new(call, /*animations_enabled*/ false);
         let rendered: Vec<String> = cell
-            .command_display_lines(/*width*/ 36)
+            .command_display_lines(/*width*/ 36, TranscriptDensity::Normal)
             .iter()
             .map(|line| {
                 line.spans
@@ -0,0 +1,268 @@
+//! `path:line` references in agent answers.
+//!
+//! Markdown rendering looks for references such as `src/lib.rs:42` in prose, inline code, and local
+//! file links. Each one is drawn like a local file link and registered on its span as a
+//! [`TerminalHyperlink`], so the transcript overlay can step through the references of rendered
+//! cells and open the referenced file at that line.  Rows of highlighted code blocks are scanned
+//! the same way, so compiler output such as `src/main.rs:3:5: error` is linked too.
+
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use regex_lite::Regex;
+use unicode_width::UnicodeWidthStr;
+
+use crate::render::highlight::content_language;
+use crate::render::highlight::file_name_language;
+use crate::render::highlight::header_language;
+use crate::render::highlight::highlight_code_to_lines;
+use crate::terminal_hyperlinks::HyperlinkLine;
+use crate::terminal_hyperlinks::TerminalHyperlink;
+use crate::terminal_hyperlinks::web_links_in_text;
+
+/// Rows of context kept above the referenced line when the viewer opens.
+const VIEWER_CONTEXT_LINES: usize = 3;
+
+// A path ending in a file extension, then `:line` and an optional `:column`. The first group
+// keeps references inside words or other tokens (such as URLs) from matching.
+static FILE_REFERENCE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    match Regex::new(
+        r#"(^|[\s(\[{<'"`])((?:[A-Za-z]:)?[A-Za-z0-9_.~/\\-]*[A-Za-z0-9_-]\.[A-Za-z][A-Za-z0-9]*):([1-9][0-9]*)(:[0-9]+)?"#,
+    ) {
+        Ok(regex) => regex,
+        Err(error) => panic!("invalid file reference regex: {error}"),
+    }
+});
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct FileReference {
+    pub(crate) path: PathBuf,
+    /// One-based line number.
+    pub(crate) line: usize,
+}
+
+impl FileReference {
+    /// Resolves a relative path against `cwd`, when one is known.
+    fn resolve(mut self, cwd: Option<&Path>) -> Self {
+        if let Some(cwd) = cwd
+            && self.path.is_relative()
+        {
+            self.path = cwd.join(&self.path);
+        }
+        self
+    }
+}
+
+/// Returns the byte range and reference of every `path:line` reference in `text`.
+pub(crate) fn find_file_references(text: &str) -> Vec<(Range<usize>, FileReference)> {
+    FILE_REFERENCE_RE
+        .captures_iter(text)
+        .filter_map(|captures| {
+            let whole = captures.get(0)?;
+            if text[whole.end()..]
+                .chars()
+                .next()
+                .is_some_and(|ch| ch.is_alphanumeric() || ch == '_')
+            {
+                return None;
+            }
+            let start = captures.get(1)?.end();
+            let path = captures.get(2)?.as_str();
+            let line = captures.get(3)?.as_str().parse().ok()?;
+            Some((
+                start..whole.end(),
+                FileReference {
+                    path: PathBuf::from(path),
+                    line,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Registers `span` as a file reference when it holds exactly one `path:line` reference and
+/// nothing else.
+///
+/// Inline code and local file links go through this so `` `src/lib.rs:42` `` is linked but a
+/// longer code snippet that happens to contain a reference is left alone.
+pub(crate) fn annotate_file_reference_span(
+    span: Span<'static>,
+    cwd: Option<&Path>,
+) -> HyperlinkLine {
+    let reference = match find_file_references(&span.content).as_slice() {
+        [(range, reference)] if *range == (0..span.content.len()) => Some(reference.clone()),
+        _ => None,
+    };
+    let columns = 0..span.content.width();
+    let mut annotated = HyperlinkLine::new(Line::from(span));
+    if let Some(reference) = reference {
+        annotated
+            .hyperlinks
+            .push(TerminalHyperlink::file(columns, &reference.resolve(cwd)));
+    }
+    annotated
+}
+
+/// Splits prose into spans, drawing `path:line` references with `reference_style`.
+///
+/// Web URLs are annotated as usual; a reference inside a URL is left to the URL.
+pub(crate) fn annotate_file_references_in_text(
+    text: &str,
+    style: Style,
+    reference_style: Style,
+    cwd: Option<&Path>,
+) -> HyperlinkLine {
+    let mut hyperlinks = web_links_in_text(text);
+    let mut annotated = HyperlinkLine::new(Line::default());
+    let mut cursor = 0;
+    for (range, reference) in find_file_references(text) {
+        let columns = text[..range.start].width()..text[..range.end].width();
+        if overlaps_any(&hyperlinks, &columns) {
+            continue;
+        }
+        if cursor < range.start {
+            annotated
+                .line
+                .push_span(Span::styled(text[cursor..range.start].to_string(), style));
+        }
+        annotated.line.push_span(Span::styled(
+            text[range.clone()].to_string(),
+            reference_style,
+        ));
+        hyperlinks.push(TerminalHyperlink::file(columns, &reference.resolve(cwd)));
+        cursor = range.end;
+    }
+    if cursor < text.len() || annotated.line.spans.is_empty() {
+        annotated
+            .line
+            .push_span(Span::styled(text[cursor..].to_string(), style));
+    }
+    hyperlinks.sort_by_key(|link| link.columns.start);
+    annotated.hyperlinks = hyperlinks;
+    annotated
+}
+
+/// Registers the web URLs and `path:line` references in an already styled line, such as a row of a
+/// highlighted code block, leaving its spans untouched.
+pub(crate) fn annotate_links_in_line(line: Line<'static>, cwd: Option<&Path>) -> HyperlinkLine {
+    let text = line
+        .spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect::<String>();
+    let mut hyperlinks = web_links_in_text(&text);
+    for (range, reference) in find_file_references(&text) {
+        let columns = text[..range.start].width()..text[..range.end].width();
+        if !overlaps_any(&hyperlinks, &columns) {
+            hyperlinks.push(TerminalHyperlink::file(columns, &reference.resolve(cwd)));
+        }
+    }
+    hyperlinks.sort_by_key(|link| link.columns.start);
+    HyperlinkLine {
+        line,
+        hyperlinks,
+        image: None,
+        scrollable: false,
+        heading_level: None,
+        hidden_code: None,
+        code_block: None,
+    }
+}
+
+fn overlaps_any(hyperlinks: &[TerminalHyperlink], columns: &Range<usize>) -> bool {
+    hyperlinks
+        .iter()
+        .any(|link| link.columns.start < columns.end && columns.start < link.columns.end)
+}
+
+/// Collects the file references registered on `lines`, in display order.
+///
+/// A reference wrapped across several rows is returned once.
+pub(crate) fn file_references_in_lines(lines: &[HyperlinkLine]) -> Vec<FileReference> {
+    let mut references = Vec::new();
+    let mut wrapped_reference: Option<FileReference> = None;
+    for line in lines {
+        let mut continued_reference = wrapped_reference.take();
+        for link in &line.hyperlinks {
+            let Some(reference) = link.file_reference() else {
+                continued_reference = None;
+                continue;
+            };
+            if continued_reference.take().as_ref() != Some(&reference) {
+                references.push(reference.clone());
+            }
+            if link.columns.end >= line.width() {
+                wrapped_reference = Some(reference);
+            }
+        }
+    }
+    references
+}
+
+/// Renders the referenced file for the viewer, with line numbers and the referenced line marked.
+///
+/// Returns the lines and the row to scroll to so the referenced line is near the top.
+pub(crate) fn file_viewer_lines(reference: &FileReference) -> (Vec<Line<'static>>, usize) {
+    let contents = match std::fs::read_to_string(&reference.path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            return (
+                vec![
+                    format!("Could not read {}: {err}", reference.path.display())
+                        .red()
+                        .into(),
+                ],
+                0,
+            );
+        }
+    };
+    let language = match reference
+        .path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+    {
+        "h" => header_language(&contents).to_string(),
+        "" => reference
+            .path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(file_name_language)
+            .map(str::to_string)
+            .or_else(|| content_language(&contents))
+            .unwrap_or_default(),
+        extension => extension.to_string(),
+    };
+    let code_lines = highlight_code_to_lines(&contents, &language);
+    let gutter_width = code_lines.len().to_string().len();
+    let lines = code_lines
+        .into_iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let number = index + 1;
+            let gutter = format!("{number:>gutter_width$} ");
+            let mut spans = if number == reference.line {
+                vec![gutter.bold(), "▌".cyan()]
+            } else {
+                vec![gutter.dim(), " ".into()]
+            };
+            spans.extend(line.spans);
+            Line::from(spans)
+        })
+        .collect();
+    let scroll_offset = reference
+        .line
+        .saturating_sub(1)
+        .saturating_sub(VIEWER_CONTEXT_LINES);
+    (lines, scroll_offset)
+}
+
+#[cfg(test)]
+#[path = "file_references_tests.rs"]
+mod tests;
@@ -0,0 +1,148 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn reference(path: &str, line: usize) -> FileReference {
+    FileReference {
+        path: PathBuf::from(path),
+        line,
+    }
+}
+
+#[test]
+fn finds_path_line_references_in_prose() {
+    let text = "See src/lib.rs:42, (core/mod.rs:7:3) and main.rs:9.";
+    let found = find_file_references(text)
+        .into_iter()
+        .map(|(range, reference)| (&text[range], reference))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        found,
+        vec![
+            ("src/lib.rs:42", reference("src/lib.rs", 42)),
+            ("core/mod.rs:7:3", reference("core/mod.rs", 7)),
+            ("main.rs:9", reference("main.rs", 9)),
+        ]
+    );
+}
+
+#[test]
+fn ignores_text_that_only_resembles_a_reference() {
+    for text in [
+        "src/lib.rs without a line",
+        "at 12:30 today",
+        "version v1.2:3",
+        "https://example.com/a.rs:10",
+        "foo.rs:0",
+        "foo.rs:12abc",
+    ] {
+        assert_eq!(find_file_references(text), Vec::new(), "{text}");
+    }
+}
+
+#[test]
+fn prose_references_are_styled_and_resolved_against_cwd() {
+    let cwd = Path::new("/repo");
+    let annotated = annotate_file_references_in_text(
+        "edit src/lib.rs:42 now",
+        Style::default(),
+        Style::new().cyan(),
+        Some(cwd),
+    );
+
+    assert_eq!(
+        annotated.line,
+        Line::from(vec!["edit ".into(), "src/lib.rs:42".cyan(), " now".into()])
+    );
+    assert_eq!(
+        annotated
+            .hyperlinks
+            .iter()
+            .map(|link| (link.columns.clone(), link.file_reference()))
+            .collect::<Vec<_>>(),
+        vec![(5..18, Some(reference("/repo/src/lib.rs", 42)))]
+    );
+}
+
+#[test]
+fn only_whole_spans_become_references() {
+    let whole = annotate_file_reference_span("src/lib.rs:42".into(), /*cwd*/ None);
+    let partial = annotate_file_reference_span("open src/lib.rs:42".into(), /*cwd*/ None);
+
+    assert_eq!(
+        whole
+            .hyperlinks
+            .iter()
+            .map(TerminalHyperlink::file_reference)
+            .collect::<Vec<_>>(),
+        vec![Some(reference("src/lib.rs", 42))]
+    );
+    assert_eq!(partial.hyperlinks, Vec::new());
+}
+
+#[test]
+fn styled_lines_keep_their_spans_and_gain_links() {
+    let line = Line::from(vec![
+        "error".red(),
+        ": see src/main.rs:3:5 and https://example.com/e0308".into(),
+    ]);
+    let annotated = annotate_links_in_line(line.clone(), Some(Path::new("/repo")));
+
+    assert_eq!(annotated.line, line);
+    assert_eq!(
+        annotated
+            .hyperlinks
+            .iter()
+            .map(|link| (link.columns.clone(), link.file_reference()))
+            .collect::<Vec<_>>(),
+        vec![
+            (11..26, Some(reference("/repo/src/main.rs", 3))),
+            (31..56, None),
+        ]
+    );
+}
+
+#[test]
+fn wrapped_reference_is_collected_once() {
+    let reference = reference("src/lib.rs", 42);
+    let mut first = HyperlinkLine::new(Line::from("see src/"));
+    first
+        .hyperlinks
+        .push(TerminalHyperlink::file(4..8, &reference));
+    let mut second = HyperlinkLine::new(Line::from("lib.rs:42 and again src/lib.rs:42"));
+    second
+        .hyperlinks
+        .push(TerminalHyperlink::file(0..9, &reference));
+    second
+        .hyperlinks
+        .push(TerminalHyperlink::file(20..33, &reference));
+
+    assert_eq!(
+        file_references_in_lines(&[first, second]),
+        vec![reference.clone(), reference]
+    );
+}
+
+#[test]
+fn viewer_marks_the_referenced_line() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("notes.txt");
+    let contents = (1..=10).map(|n| format!("line {n}\n")).collect::<String>();
+    std::fs::write(&path, contents).expect("write file");
+
+    let (lines, scroll_offset) = file_viewer_lines(&FileReference { path, line: 6 });
+    let rendered = lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(rendered.len(), 10);
+    assert_eq!(rendered[4], " 5  line 5");
+    assert_eq!(rendered[5], " 6 ▌line 6");
+    assert_eq!(scroll_offset, 2);
+}
@@ -0,0 +1,32 @@
+//! Entry points for the fuzz targets in `codex-rs/fuzz`.
+//!
+//! Compiled only with the `fuzzing` feature. Each function runs one internal parser on arbitrary
+//! input and discards the result; a fuzz run fails when one of them panics.
+
+use crate::table_detect::FenceTracker;
+use crate::table_detect::is_table_delimiter_line;
+use crate::table_detect::is_table_header_line;
+use crate::table_detect::parse_table_segments;
+
+/// Renders markdown the way history cells do, wrapped to `width` when given.
+pub fn render_markdown(input: &str, width: Option<usize>) {
+    let _ = crate::markdown_render::render_markdown_text_with_width(input, width);
+}
+
+/// Runs an agent message through table fence unwrapping, the per-line table and fence
+/// detection used while streaming, and the streaming renderer that maps block offsets back to
+/// the raw source.
+pub fn render_agent_markdown(input: &str, width: Option<usize>) {
+    let _ = crate::markdown::unwrap_markdown_fences(input);
+    let mut fences = FenceTracker::new();
+    for line in input.lines() {
+        fences.advance(line);
+        let _ = fences.kind();
+        let _ = parse_table_segments(line);
+        let _ = is_table_header_line(line);
+        let _ = is_table_delimiter_line(line);
+    }
+    let _ = crate::markdown::render_streaming_markdown_agent_with_links_and_cwd(
+        input, width, /*cwd*/ None,
+    );
+}
@@ -0,0 +1,92 @@
+//! Public access to the TUI's syntax highlighter for other workspace crates.
+//!
+//! Exec reports, MCP tool responses, and similar surfaces can render code with the same grammars,
+//! theme, and guardrails as the TUI instead of carrying their own highlighter. The internal engine
+//! in `render::highlight` stays crate-private; this module only exposes language resolution and
+//! conversion to styled ratatui lines.
+//!
+//! Outside the TUI no theme is configured, so lines use the adaptive default theme for the
+//! terminal's background. Inputs over the highlighting limits come back as plain lines.
+
+use std::path::Path;
+
+use ratatui::text::Line;
+
+use crate::render::highlight::content_language;
+use crate::render::highlight::file_name_language;
+use crate::render::highlight::has_syntax;
+use crate::render::highlight::header_language;
+use crate::render::highlight::highlight_code_to_lines;
+
+/// A language the highlighter has a grammar for.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Language(String);
+
+impl Language {
+    /// Resolves a code fence language or file extension, such as `rust`, `rs`, or `tsx`.
+    /// Returns `None` when no grammar matches.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        has_syntax(name).then(|| Self(name.to_string()))
+    }
+
+    /// Resolves the language of a file from its extension. Files without one, such as `Gemfile`
+    /// or scripts in `bin/`, are recognized by name or by a shebang or modeline in `content`.
+    pub fn from_path(path: &Path, content: &str) -> Option<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("h") => Self::from_name(header_language(content)),
+            Some(ext) => Self::from_name(ext),
+            None => path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(file_name_language)
+                .map(str::to_string)
+                .or_else(|| content_language(content))
+                .and_then(|name| Self::from_name(&name)),
+        }
+    }
+
+    /// The name this language was resolved from.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Highlights `code` as `language`, one styled line per source line. Tabs are expanded to the
+/// language's tab width.
+pub fn to_lines(code: &str, language: &Language) -> Vec<Line<'static>> {
+    highlight_code_to_lines(code, &language.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn languages_resolve_from_names_and_paths() {
+        assert_eq!(
+            Language::from_name("rust").map(|language| language.name().to_string()),
+            Some("rust".to_string())
+        );
+        assert_eq!(Language::from_name("not-a-language"), None);
+        assert_eq!(
+            Language::from_path(Path::new("src/main.rs"), ""),
+            Language::from_name("rs")
+        );
+        assert_eq!(
+            Language::from_path(Path::new("bin/tool"), "#!/usr/bin/env python3\n")
+                .map(|language| language.name().to_string()),
+            Some("py".to_string())
+        );
+    }
+
+    #[test]
+    fn to_lines_styles_each_source_line() {
+        let language = Language::from_name("rust").expect("rust grammar");
+        let lines = to_lines("fn main() {\n}\n", &language);
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].spans.len() > 1, "expected styled tokens");
+    }
+}
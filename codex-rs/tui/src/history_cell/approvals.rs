@@ -161,7 +161,7 @@ pub fn new_approval_decision_cell(
                 let summary = if let Some(snippet) = non_empty_exec_snippet(&command) {
                     let snippet = Span::from(snippet).dim();
                     match actor {
-                        ApprovalDecisionActor::User => vec![
+                        ApprovalDecisionActor::User | ApprovalDecisionActor::Timeout => vec![
                             actor.subject().into(),
                             "did not approve".bold(),
                             " codex to run ".into(),
@@ -176,7 +176,7 @@ pub fn new_approval_decision_cell(
                     }
                 } else {
                     match actor {
-                        ApprovalDecisionActor::User => vec![
+                        ApprovalDecisionActor::User | ApprovalDecisionActor::Timeout => vec![
                             actor.subject().into(),
                             "did not approve".bold(),
                             " this request".into(),
@@ -267,13 +267,16 @@ pub fn new_approval_decision_cell(
 pub enum ApprovalDecisionActor {
     User,
     Guardian,
+    /// The configured `tui.approval_timeout_action`, applied to an unanswered prompt.
+    Timeout,
 }
 
 impl ApprovalDecisionActor {
-    fn subject(self) -> &'static str {
+    pub(crate) fn subject(self) -> &'static str {
         match self {
             Self::User => "You ",
             Self::Guardian => "Auto-reviewer ",
+            Self::Timeout => "Timeout policy ",
         }
     }
 }
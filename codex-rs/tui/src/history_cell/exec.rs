@@ -233,6 +233,26 @@ impl HistoryCell for UnifiedExecProcessesCell {
     }
 }
 
+/// Plain-text listing of every background terminal with its full command and recent output, for
+/// viewers outside the transcript such as a tmux split.
+pub(crate) fn unified_exec_processes_text(processes: &[UnifiedExecProcessDetails]) -> String {
+    let mut text = String::from("Background terminals\n");
+    for process in processes {
+        text.push('\n');
+        for line in process.command_display.lines() {
+            text.push_str("$ ");
+            text.push_str(line);
+            text.push('\n');
+        }
+        for chunk in &process.recent_chunks {
+            text.push_str("  ");
+            text.push_str(chunk);
+            text.push('\n');
+        }
+    }
+    text
+}
+
 pub(crate) fn new_unified_exec_processes_output(
     processes: Vec<UnifiedExecProcessDetails>,
 ) -> CompositeHistoryCell {
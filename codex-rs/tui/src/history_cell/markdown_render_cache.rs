@@ -1,12 +1,54 @@
 //! Single-width render cache shared by finalized source-backed markdown history cells.
+//!
+//! Every cache registers its rendered lines with a process-wide [`RenderBudget`]. When the total
+//! number of retained lines exceeds the budget, the least recently used renders are spilled to
+//! disk (see [`super::render_spill`]) and read back the next time their cell is scrolled into
+//! view. Spilled renders have their own byte budget; past it, the oldest spills are dropped and
+//! their cells render again from the markdown source they still hold. This keeps memory flat in
+//! very long sessions without changing what the transcript shows.
 
+use super::render_spill::SpilledRender;
 use crate::terminal_hyperlinks::HyperlinkLine;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::LazyLock;
 use std::sync::Mutex;
 use std::sync::PoisonError;
+use std::sync::Weak;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 
-#[derive(Debug, Default)]
+/// Upper bound on rendered lines retained across all finalized markdown cells.
+const MAX_RETAINED_RENDERED_LINES: usize = 50_000;
+
+/// Most recently used caches are never evicted, even when they alone exceed the line budget.
+const MIN_RETAINED_RENDERS: usize = 64;
+
+/// Upper bound on bytes of evicted renders kept in spill files.
+const MAX_SPILLED_BYTES: u64 = 128 * 1024 * 1024;
+
+static NEXT_CACHE_ID: AtomicU64 = AtomicU64::new(0);
+static RENDER_BUDGET: LazyLock<Mutex<RenderBudget>> = LazyLock::new(|| {
+    Mutex::new(RenderBudget::new(
+        MAX_RETAINED_RENDERED_LINES,
+        MIN_RETAINED_RENDERS,
+        MAX_SPILLED_BYTES,
+    ))
+});
+
+type CachedRender = Option<(MarkdownRenderCacheKey, RetainedLines)>;
+
+/// Where the lines of a cached render currently live.
+#[derive(Debug)]
+pub(super) enum RetainedLines {
+    InMemory(Vec<HyperlinkLine>),
+    Spilled(SpilledRender),
+}
+
+#[derive(Debug)]
 pub(super) struct MarkdownRenderCache {
-    pub(super) cached: Mutex<Option<(MarkdownRenderCacheKey, Vec<HyperlinkLine>)>>,
+    id: u64,
+    pub(super) cached: Arc<Mutex<CachedRender>>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -18,11 +60,21 @@ pub(super) struct MarkdownRenderCacheKey {
     pub(super) color_level: crate::terminal_palette::StdoutColorLevel,
 }
 
+impl Default for MarkdownRenderCache {
+    fn default() -> Self {
+        Self {
+            id: NEXT_CACHE_ID.fetch_add(1, Ordering::Relaxed),
+            cached: Arc::default(),
+        }
+    }
+}
+
 impl MarkdownRenderCache {
     /// Return lines cached for this width and terminal render state, rendering on a cache miss.
     ///
     /// Only the most recent entry is retained, so changing width, syntax theme, or terminal colors
-    /// replaces the cached render.
+    /// replaces the cached render. The global render budget may spill the entry to disk, in which
+    /// case the next call reads it back, or drop it, in which case the next call renders again.
     pub(super) fn render(
         &self,
         width: u16,
@@ -33,17 +85,202 @@ impl MarkdownRenderCache {
             syntax_theme_revision: crate::render::highlight::syntax_theme_revision(),
             terminal_fg: crate::terminal_palette::default_fg(),
             terminal_bg: crate::terminal_palette::default_bg(),
-            color_level: crate::terminal_palette::stdout_color_level(),
+            color_level: crate::terminal_palette::effective_stdout_color_level(),
         };
-        let mut cached = self.cached.lock().unwrap_or_else(PoisonError::into_inner);
-        if let Some((cached_key, lines)) = cached.as_ref()
-            && *cached_key == key
-        {
-            return lines.clone();
+        let hit = {
+            let cached = self.cached.lock().unwrap_or_else(PoisonError::into_inner);
+            match cached.as_ref() {
+                Some((cached_key, RetainedLines::InMemory(lines))) if *cached_key == key => {
+                    Some((lines.clone(), /*spilled*/ false))
+                }
+                Some((cached_key, RetainedLines::Spilled(spilled))) if *cached_key == key => {
+                    spilled.read().map(|lines| (lines, /*spilled*/ true))
+                }
+                _ => None,
+            }
+        };
+        match hit {
+            Some((lines, false)) => {
+                lock_budget().touch(self.id);
+                lines
+            }
+            // Lines read back from disk count against the in-memory budget again.
+            Some((lines, true)) => self.retain(key, lines),
+            None => self.retain(key, render()),
         }
+    }
 
-        let lines = render();
-        *cached = Some((key, lines.clone()));
+    /// Keep `lines` in memory for `key`, replacing any spilled copy, and spill whatever that
+    /// pushes out of the line budget.
+    fn retain(&self, key: MarkdownRenderCacheKey, lines: Vec<HyperlinkLine>) -> Vec<HyperlinkLine> {
+        *self.cached.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some((key, RetainedLines::InMemory(lines.clone())));
+        // Never hold a cache slot lock while taking the budget lock; eviction locks slots after
+        // releasing the budget so the two lock orders cannot interleave.
+        let evicted = lock_budget().retain(self.id, Arc::downgrade(&self.cached), lines.len());
+        for (id, slot) in evicted {
+            spill(id, &slot);
+        }
         lines
     }
 }
+
+/// Move the in-memory lines of evicted cache `id` to disk, or drop them when they cannot be
+/// spilled. Spills pushed out of the byte budget are dropped along the way.
+fn spill(id: u64, slot: &Weak<Mutex<CachedRender>>) {
+    let Some(slot) = slot.upgrade() else {
+        return;
+    };
+    let bytes = {
+        let mut cached = slot.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some((key, RetainedLines::InMemory(lines))) = cached.take() else {
+            return;
+        };
+        let Some(spilled) = SpilledRender::write(id, &lines) else {
+            return;
+        };
+        let bytes = spilled.bytes;
+        *cached = Some((key, RetainedLines::Spilled(spilled)));
+        bytes
+    };
+    let dropped = lock_budget().record_spill(id, Arc::downgrade(&slot), bytes);
+    for slot in dropped {
+        if let Some(slot) = slot.upgrade() {
+            let mut cached = slot.lock().unwrap_or_else(PoisonError::into_inner);
+            if matches!(cached.as_ref(), Some((_, RetainedLines::Spilled(_)))) {
+                *cached = None;
+            }
+        }
+    }
+}
+
+impl Drop for MarkdownRenderCache {
+    fn drop(&mut self) {
+        lock_budget().forget(self.id);
+    }
+}
+
+fn lock_budget() -> std::sync::MutexGuard<'static, RenderBudget> {
+    RENDER_BUDGET.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Least-recently-used accounting of rendered lines held by markdown render caches, and of the
+/// bytes their spilled renders keep on disk.
+#[derive(Debug)]
+pub(super) struct RenderBudget {
+    max_lines: usize,
+    min_entries: usize,
+    total_lines: usize,
+    entries: VecDeque<RetainedRender>,
+    max_spilled_bytes: u64,
+    spilled_bytes: u64,
+    spilled: VecDeque<SpilledEntry>,
+}
+
+#[derive(Debug)]
+struct RetainedRender {
+    id: u64,
+    slot: Weak<Mutex<CachedRender>>,
+    lines: usize,
+}
+
+#[derive(Debug)]
+struct SpilledEntry {
+    id: u64,
+    slot: Weak<Mutex<CachedRender>>,
+    bytes: u64,
+}
+
+impl RenderBudget {
+    pub(super) fn new(max_lines: usize, min_entries: usize, max_spilled_bytes: u64) -> Self {
+        Self {
+            max_lines,
+            min_entries,
+            total_lines: 0,
+            entries: VecDeque::new(),
+            max_spilled_bytes,
+            spilled_bytes: 0,
+            spilled: VecDeque::new(),
+        }
+    }
+
+    /// Record a fresh render for cache `id` and return the caches whose lines must leave memory
+    /// to get back under budget. Callers spill them after releasing the budget lock.
+    pub(super) fn retain(
+        &mut self,
+        id: u64,
+        slot: Weak<Mutex<CachedRender>>,
+        lines: usize,
+    ) -> Vec<(u64, Weak<Mutex<CachedRender>>)> {
+        self.forget(id);
+        self.total_lines += lines;
+        self.entries.push_back(RetainedRender { id, slot, lines });
+
+        let mut evicted = Vec::new();
+        while self.total_lines > self.max_lines && self.entries.len() > self.min_entries {
+            let Some(entry) = self.entries.pop_front() else {
+                break;
+            };
+            self.total_lines -= entry.lines;
+            evicted.push((entry.id, entry.slot));
+        }
+        evicted
+    }
+
+    /// Record that cache `id` spilled `bytes` to disk and return the slots whose spills must be
+    /// dropped to get back under the byte budget, oldest first.
+    pub(super) fn record_spill(
+        &mut self,
+        id: u64,
+        slot: Weak<Mutex<CachedRender>>,
+        bytes: u64,
+    ) -> Vec<Weak<Mutex<CachedRender>>> {
+        self.forget_spill(id);
+        self.spilled_bytes += bytes;
+        self.spilled.push_back(SpilledEntry { id, slot, bytes });
+
+        let mut dropped = Vec::new();
+        while self.spilled_bytes > self.max_spilled_bytes {
+            let Some(entry) = self.spilled.pop_front() else {
+                break;
+            };
+            self.spilled_bytes -= entry.bytes;
+            dropped.push(entry.slot);
+        }
+        dropped
+    }
+
+    /// Mark cache `id` as most recently used.
+    pub(super) fn touch(&mut self, id: u64) {
+        if self.entries.back().is_some_and(|entry| entry.id == id) {
+            return;
+        }
+        if let Some(index) = self.entries.iter().position(|entry| entry.id == id)
+            && let Some(entry) = self.entries.remove(index)
+        {
+            self.entries.push_back(entry);
+        }
+    }
+
+    /// Stop accounting for cache `id`, e.g. because its cell was dropped.
+    pub(super) fn forget(&mut self, id: u64) {
+        if let Some(index) = self.entries.iter().position(|entry| entry.id == id)
+            && let Some(entry) = self.entries.remove(index)
+        {
+            self.total_lines -= entry.lines;
+        }
+        self.forget_spill(id);
+    }
+
+    fn forget_spill(&mut self, id: u64) {
+        if let Some(index) = self.spilled.iter().position(|entry| entry.id == id)
+            && let Some(entry) = self.spilled.remove(index)
+        {
+            self.spilled_bytes -= entry.bytes;
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "markdown_render_cache_tests.rs"]
+mod tests;
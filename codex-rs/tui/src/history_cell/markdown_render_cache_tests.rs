@@ -0,0 +1,178 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+fn slot() -> Arc<Mutex<CachedRender>> {
+    Arc::new(Mutex::new(None))
+}
+
+fn evicted_slots(
+    evicted: Vec<Weak<Mutex<CachedRender>>>,
+    candidates: &[&Arc<Mutex<CachedRender>>],
+) -> Vec<usize> {
+    evicted
+        .iter()
+        .filter_map(|weak| {
+            let evicted = weak.upgrade()?;
+            candidates
+                .iter()
+                .position(|candidate| Arc::ptr_eq(candidate, &evicted))
+        })
+        .collect()
+}
+
+#[test]
+fn budget_evicts_least_recently_used_renders() {
+    let mut budget = RenderBudget::new(
+        /*max_lines*/ 10, /*min_entries*/ 1, /*max_spilled_bytes*/ 0,
+    );
+    let (a, b, c) = (slot(), slot(), slot());
+
+    assert!(
+        budget
+            .retain(/*id*/ 0, Arc::downgrade(&a), /*lines*/ 4)
+            .is_empty()
+    );
+    assert!(
+        budget
+            .retain(/*id*/ 1, Arc::downgrade(&b), /*lines*/ 4)
+            .is_empty()
+    );
+    budget.touch(/*id*/ 0);
+
+    let evicted = budget
+        .retain(/*id*/ 2, Arc::downgrade(&c), /*lines*/ 4)
+        .into_iter()
+        .map(|(_, slot)| slot)
+        .collect();
+
+    assert_eq!(evicted_slots(evicted, &[&a, &b, &c]), vec![1]);
+}
+
+#[test]
+fn budget_keeps_minimum_entries_even_when_over_budget() {
+    let mut budget = RenderBudget::new(
+        /*max_lines*/ 1, /*min_entries*/ 2, /*max_spilled_bytes*/ 0,
+    );
+    let (a, b) = (slot(), slot());
+
+    assert!(
+        budget
+            .retain(/*id*/ 0, Arc::downgrade(&a), /*lines*/ 100)
+            .is_empty()
+    );
+    assert!(
+        budget
+            .retain(/*id*/ 1, Arc::downgrade(&b), /*lines*/ 100)
+            .is_empty()
+    );
+}
+
+#[test]
+fn forgotten_renders_release_their_lines() {
+    let mut budget = RenderBudget::new(
+        /*max_lines*/ 10, /*min_entries*/ 0, /*max_spilled_bytes*/ 0,
+    );
+    let (a, b) = (slot(), slot());
+
+    assert!(
+        budget
+            .retain(/*id*/ 0, Arc::downgrade(&a), /*lines*/ 8)
+            .is_empty()
+    );
+    budget.forget(/*id*/ 0);
+
+    assert!(
+        budget
+            .retain(/*id*/ 1, Arc::downgrade(&b), /*lines*/ 8)
+            .is_empty()
+    );
+}
+
+#[test]
+fn rerendering_replaces_previous_line_count() {
+    let mut budget = RenderBudget::new(
+        /*max_lines*/ 10, /*min_entries*/ 0, /*max_spilled_bytes*/ 0,
+    );
+    let a = slot();
+
+    assert!(
+        budget
+            .retain(/*id*/ 0, Arc::downgrade(&a), /*lines*/ 8)
+            .is_empty()
+    );
+    assert!(
+        budget
+            .retain(/*id*/ 0, Arc::downgrade(&a), /*lines*/ 8)
+            .is_empty()
+    );
+}
+
+#[test]
+fn evicted_cache_renders_again_from_source() {
+    let cache = MarkdownRenderCache::default();
+    let width = 40;
+    cache.render(width, || vec![HyperlinkLine::from("first")]);
+
+    *cache.cached.lock().expect("render cache lock") = None;
+
+    assert_eq!(
+        cache.render(width, || vec![HyperlinkLine::from("second")]),
+        vec![HyperlinkLine::from("second")]
+    );
+}
+
+#[test]
+fn budget_drops_oldest_spills_over_the_byte_budget() {
+    let mut budget = RenderBudget::new(
+        /*max_lines*/ 10, /*min_entries*/ 0, /*max_spilled_bytes*/ 100,
+    );
+    let (a, b, c) = (slot(), slot(), slot());
+
+    assert!(
+        budget
+            .record_spill(/*id*/ 0, Arc::downgrade(&a), /*bytes*/ 60)
+            .is_empty()
+    );
+    assert!(
+        budget
+            .record_spill(/*id*/ 1, Arc::downgrade(&b), /*bytes*/ 30)
+            .is_empty()
+    );
+    let dropped = budget.record_spill(/*id*/ 2, Arc::downgrade(&c), /*bytes*/ 30);
+    assert_eq!(evicted_slots(dropped, &[&a, &b, &c]), vec![0]);
+
+    budget.forget(/*id*/ 1);
+    assert!(
+        budget
+            .record_spill(/*id*/ 3, Arc::downgrade(&a), /*bytes*/ 60)
+            .is_empty()
+    );
+}
+
+#[test]
+fn evicted_render_is_read_back_from_disk() {
+    let cache = MarkdownRenderCache::default();
+    let width = 40;
+    let lines = vec![HyperlinkLine::from(Line::from(vec![
+        "plain ".into(),
+        "bold".bold(),
+    ]))];
+    cache.render(width, || lines.clone());
+
+    spill(cache.id, &Arc::downgrade(&cache.cached));
+    assert!(matches!(
+        *cache.cached.lock().expect("render cache lock"),
+        Some((_, RetainedLines::Spilled(_)))
+    ));
+
+    assert_eq!(
+        cache.render(width, || panic!("spilled lines should be read back")),
+        lines
+    );
+    assert!(matches!(
+        *cache.cached.lock().expect("render cache lock"),
+        Some((_, RetainedLines::InMemory(_)))
+    ));
+}
@@ -1,6 +1,9 @@
 //! MCP tool-call, inventory, and output history cells.
 
 use super::*;
+use crate::text_formatting::format_json_compact;
+use crate::transcript_density::transcript_density;
+use codex_config::types::TranscriptDensity;
 
 #[derive(Debug)]
 struct McpImageOutputCell;
@@ -85,22 +88,19 @@ impl McpToolCallCell {
         self.result = Some(Err("interrupted".to_string()));
     }
 
-    fn render_content_block(block: &serde_json::Value, width: usize) -> String {
+    /// Renders one result block, keeping at most `max_lines` rows of text when a limit is given.
+    fn render_content_block(
+        block: &serde_json::Value,
+        max_lines: Option<usize>,
+        width: usize,
+    ) -> String {
         let content = match serde_json::from_value::<rmcp::model::Content>(block.clone()) {
             Ok(content) => content,
-            Err(_) => {
-                return format_and_truncate_tool_result(
-                    &block.to_string(),
-                    TOOL_CALL_MAX_LINES,
-                    width,
-                );
-            }
+            Err(_) => return format_tool_result(&block.to_string(), max_lines, width),
         };
 
         match content.raw {
-            rmcp::model::RawContent::Text(text) => {
-                format_and_truncate_tool_result(&text.text, TOOL_CALL_MAX_LINES, width)
-            }
+            rmcp::model::RawContent::Text(text) => format_tool_result(&text.text, max_lines, width),
             rmcp::model::RawContent::Image(_) => "<image content>".to_string(),
             rmcp::model::RawContent::Audio(_) => "<audio content>".to_string(),
             rmcp::model::RawContent::Resource(resource) => {
@@ -115,8 +115,8 @@ impl McpToolCallCell {
     }
 }
 
-impl HistoryCell for McpToolCallCell {
-    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+impl McpToolCallCell {
+    fn density_lines(&self, width: u16, density: TranscriptDensity) -> Vec<Line<'static>> {
         let mut lines: Vec<Line<'static>> = Vec::new();
         let status = self.success();
         let bullet = match status {
@@ -158,6 +158,11 @@ impl HistoryCell for McpToolCallCell {
             lines.extend(prefix_lines(body_lines, "  └ ".dim(), "    ".into()));
         }
 
+        let max_lines = match density {
+            TranscriptDensity::Compact => return lines,
+            TranscriptDensity::Normal => Some(TOOL_CALL_MAX_LINES),
+            TranscriptDensity::Verbose => None,
+        };
         let mut detail_lines: Vec<Line<'static>> = Vec::new();
         // Reserve four columns for the tree prefix ("  └ "/"    ") and ensure the wrapper still has at least one cell to work with.
         let detail_wrap_width = (width as usize).saturating_sub(4).max(1);
@@ -167,7 +172,8 @@ impl HistoryCell for McpToolCallCell {
                 Ok(codex_protocol::mcp::CallToolResult { content, .. }) => {
                     if !content.is_empty() {
                         for block in content {
-                            let text = Self::render_content_block(block, detail_wrap_width);
+                            let text =
+                                Self::render_content_block(block, max_lines, detail_wrap_width);
                             for segment in text.split('\n') {
                                 let line = Line::from(segment.to_string().dim());
                                 let wrapped = adaptive_wrap_line(
@@ -182,11 +188,8 @@ impl HistoryCell for McpToolCallCell {
                     }
                 }
                 Err(err) => {
-                    let err_text = format_and_truncate_tool_result(
-                        &format!("Error: {err}"),
-                        TOOL_CALL_MAX_LINES,
-                        width as usize,
-                    );
+                    let err_text =
+                        format_tool_result(&format!("Error: {err}"), max_lines, width as usize);
                     let err_line = Line::from(err_text.dim());
                     let wrapped = adaptive_wrap_line(
                         &err_line,
@@ -210,6 +213,12 @@ impl HistoryCell for McpToolCallCell {
 
         lines
     }
+}
+
+impl HistoryCell for McpToolCallCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.density_lines(width, transcript_density())
+    }
 
     fn raw_lines(&self) -> Vec<Line<'static>> {
         let header_text = if self.success().is_some() {
@@ -226,7 +235,11 @@ impl HistoryCell for McpToolCallCell {
             match result {
                 Ok(codex_protocol::mcp::CallToolResult { content, .. }) => {
                     for block in content {
-                        let text = Self::render_content_block(block, RAW_TOOL_OUTPUT_WIDTH);
+                        let text = Self::render_content_block(
+                            block,
+                            Some(TOOL_CALL_MAX_LINES),
+                            RAW_TOOL_OUTPUT_WIDTH,
+                        );
                         lines.extend(raw_lines_from_source(&text));
                     }
                 }
@@ -245,6 +258,14 @@ impl HistoryCell for McpToolCallCell {
     }
 }
 
+/// Formats a tool result, truncating it to `max_lines` rows when a limit is given.
+fn format_tool_result(text: &str, max_lines: Option<usize>, width: usize) -> String {
+    match max_lines {
+        Some(max_lines) => format_and_truncate_tool_result(text, max_lines, width),
+        None => format_json_compact(text).unwrap_or_else(|| text.to_string()),
+    }
+}
+
 pub(crate) fn new_active_mcp_tool_call(
     call_id: String,
     invocation: McpInvocation,
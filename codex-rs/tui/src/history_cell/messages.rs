@@ -213,6 +213,9 @@ impl HistoryCell for UserHistoryCell {
         }
         lines
     }
+    fn transcript_marker(&self) -> Option<TranscriptMarker> {
+        Some(TranscriptMarker::UserMessage)
+    }
 }
 
 #[derive(Debug)]
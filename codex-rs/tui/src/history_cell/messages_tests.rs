@@ -1,5 +1,6 @@
 use super::*;
 use crate::history_cell::markdown_render_cache::MarkdownRenderCacheKey;
+use crate::history_cell::markdown_render_cache::RetainedLines;
 use pretty_assertions::assert_eq;
 
 fn replace_cached_lines(
@@ -14,7 +15,7 @@ fn replace_cached_lines(
     let (key, lines) = rendered_lines
         .as_mut()
         .expect("render cache should be populated");
-    *lines = vec![HyperlinkLine::from("cached")];
+    *lines = RetainedLines::InMemory(vec![HyperlinkLine::from("cached")]);
     update_key(key);
 }
 
@@ -33,6 +33,7 @@ use crate::session_state::ThreadSessionState;
 use crate::style::proposed_plan_style;
 use crate::style::user_message_style;
 use crate::terminal_hyperlinks::HyperlinkLine;
+use crate::terminal_hyperlinks::clip_scrollable_lines;
 use crate::terminal_hyperlinks::mark_buffer_hyperlinks;
 use crate::terminal_hyperlinks::plain_hyperlink_lines;
 use crate::terminal_hyperlinks::prefix_hyperlink_lines;
@@ -119,6 +120,8 @@ mod messages;
 mod notices;
 mod patches;
 mod plans;
+mod reasoning_panel;
+mod render_spill;
 mod request_user_input;
 mod search;
 mod separators;
@@ -135,6 +138,8 @@ pub(crate) use messages::*;
 pub(crate) use notices::*;
 pub(crate) use patches::*;
 pub(crate) use plans::*;
+pub(crate) use reasoning_panel::*;
+pub(crate) use render_spill::remove_spilled_renders;
 pub(crate) use request_user_input::*;
 pub(crate) use search::*;
 pub(crate) use separators::*;
@@ -149,6 +154,14 @@ pub(crate) enum HistoryRenderMode {
     Raw,
 }
 
+/// Kind of cell flagged in the transcript overlay's scrollbar so long sessions are easy to scan.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub(crate) enum TranscriptMarker {
+    UserMessage,
+    Patch,
+    Error,
+}
+
 pub(crate) fn raw_lines_from_source(source: &str) -> Vec<Line<'static>> {
     if source.is_empty() {
         return Vec::new();
@@ -258,10 +271,15 @@ pub(crate) trait HistoryCell: std::fmt::Debug + Send + Sync + Any {
     /// Returns the number of viewport rows for the transcript overlay.
     ///
     /// Uses the same `Paragraph::line_count` measurement as
-    /// `desired_height`. Contains a workaround for a ratatui bug where
-    /// a single whitespace-only line reports 2 rows instead of 1.
+    /// `desired_height`, with full-width table rows taking one row each.
+    /// Contains a workaround for a ratatui bug where a single
+    /// whitespace-only line reports 2 rows instead of 1.
     fn desired_transcript_height(&self, width: u16) -> u16 {
-        let lines = visible_lines(self.transcript_hyperlink_lines(width));
+        let lines = visible_lines(clip_scrollable_lines(
+            self.transcript_hyperlink_lines(width),
+            /*column*/ 0,
+            usize::from(width),
+        ));
         // Workaround: ratatui's line_count returns 2 for a single
         // whitespace-only line. Clamp to 1 in that case.
         if let [line] = &lines[..]
@@ -292,6 +310,11 @@ pub(crate) trait HistoryCell: std::fmt::Debug + Send + Sync + Any {
         false
     }
 
+    /// Scrollbar marker for this cell in the transcript overlay, if it is a landmark.
+    fn transcript_marker(&self) -> Option<TranscriptMarker> {
+        None
+    }
+
     /// Returns a coarse "animation tick" when transcript output is time-dependent.
     ///
     /// The transcript overlay caches the rendered output of the in-flight active cell, so cells
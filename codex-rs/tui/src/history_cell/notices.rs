@@ -210,10 +210,30 @@ pub(crate) fn new_info_event(message: String, hint: Option<String>) -> PlainHist
     PlainHistoryCell { lines }
 }
 
-pub(crate) fn new_error_event(message: String) -> PlainHistoryCell {
+/// Error notice; kept apart from `PlainHistoryCell` so the transcript can mark it.
+#[derive(Debug)]
+pub(crate) struct ErrorHistoryCell {
+    pub(super) lines: Vec<Line<'static>>,
+}
+
+impl HistoryCell for ErrorHistoryCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        self.lines.clone()
+    }
+
+    fn raw_lines(&self) -> Vec<Line<'static>> {
+        plain_lines(self.lines.clone())
+    }
+
+    fn transcript_marker(&self) -> Option<TranscriptMarker> {
+        Some(TranscriptMarker::Error)
+    }
+}
+
+pub(crate) fn new_error_event(message: String) -> ErrorHistoryCell {
     // Use a hair space (U+200A) to create a subtle, near-invisible separation
     // before the text. VS16 is intentionally omitted to keep spacing tighter
     // in terminals like Ghostty.
     let lines: Vec<Line<'static>> = vec![vec![format!("■ {message}").red()].into()];
-    PlainHistoryCell { lines }
+    ErrorHistoryCell { lines }
 }
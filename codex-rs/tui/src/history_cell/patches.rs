@@ -1,6 +1,9 @@
 //! Patch summaries and image-tool transcript helpers.
 
 use super::*;
+use crate::diff_render::create_diff_summary_in_background;
+use crate::diff_stat::diff_stat_lines;
+use crate::diff_stat::parse_turn_diff;
 use codex_utils_path_uri::LegacyAppPathString;
 
 #[derive(Debug)]
@@ -21,6 +24,14 @@ impl HistoryCell for PatchHistoryCell {
             RAW_DIFF_SUMMARY_WIDTH,
         ))
     }
+
+    fn transcript_lines(&self, width: u16) -> Vec<Line<'static>> {
+        create_diff_summary_in_background(&self.changes, &self.cwd, width as usize)
+    }
+
+    fn transcript_marker(&self) -> Option<TranscriptMarker> {
+        Some(TranscriptMarker::Patch)
+    }
 }
 /// Create a new `PendingPatch` cell that lists the file‑level summary of
 /// a proposed patch. The summary lines should already be formatted (e.g.
@@ -30,12 +41,49 @@ pub(crate) fn new_patch_event(
     cwd: &Path,
 ) -> PatchHistoryCell {
     PatchHistoryCell {
-        changes,
+        changes: crate::diff_render::readable_notebook_changes(changes, cwd),
         cwd: cwd.to_path_buf(),
     }
 }
 
-pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
+/// End-of-turn `git diff --stat`-style summary of every file the turn changed.
+///
+/// The transcript overlay expands it into the full diff.
+#[derive(Debug)]
+pub(crate) struct TurnDiffStatCell {
+    changes: HashMap<PathBuf, FileChange>,
+    cwd: PathBuf,
+}
+
+impl HistoryCell for TurnDiffStatCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        diff_stat_lines(&self.changes, &self.cwd, width as usize)
+    }
+
+    fn raw_lines(&self) -> Vec<Line<'static>> {
+        plain_lines(diff_stat_lines(
+            &self.changes,
+            &self.cwd,
+            RAW_DIFF_SUMMARY_WIDTH,
+        ))
+    }
+
+    fn transcript_lines(&self, width: u16) -> Vec<Line<'static>> {
+        create_diff_summary_in_background(&self.changes, &self.cwd, width as usize)
+    }
+}
+
+/// Builds the turn summary from the aggregate unified diff reported for the
+/// turn, or `None` when no file changed.
+pub(crate) fn new_turn_diff_stat(unified_diff: &str, cwd: &Path) -> Option<TurnDiffStatCell> {
+    let changes = parse_turn_diff(unified_diff);
+    (!changes.is_empty()).then(|| TurnDiffStatCell {
+        changes,
+        cwd: cwd.to_path_buf(),
+    })
+}
+
+pub(crate) fn new_patch_apply_failure(stderr: String) -> ErrorHistoryCell {
     let mut lines: Vec<Line<'static>> = Vec::new();
 
     // Failure title
@@ -54,7 +102,7 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
         lines.extend(output.lines);
     }
 
-    PlainHistoryCell { lines }
+    ErrorHistoryCell { lines }
 }
 
 pub(crate) fn new_view_image_tool_call(path: LegacyAppPathString, cwd: &Path) -> PlainHistoryCell {
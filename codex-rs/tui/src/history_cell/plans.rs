@@ -45,9 +45,16 @@ impl HistoryCell for StreamingPlanTailCell {
     }
 }
 /// Render a user‑friendly plan update styled like a checkbox todo list.
-pub(crate) fn new_plan_update(update: UpdatePlanArgs) -> PlanUpdateCell {
+///
+/// `transcript_only` cells stay out of scrollback while the bottom pane shows the plan as a live
+/// checklist, but still record each update in the transcript.
+pub(crate) fn new_plan_update(update: UpdatePlanArgs, transcript_only: bool) -> PlanUpdateCell {
     let UpdatePlanArgs { explanation, plan } = update;
-    PlanUpdateCell { explanation, plan }
+    PlanUpdateCell {
+        explanation,
+        plan,
+        transcript_only,
+    }
 }
 
 /// Create a proposed-plan cell that snapshots the session cwd for later markdown rendering.
@@ -170,10 +177,11 @@ impl HistoryCell for ProposedPlanStreamCell {
 pub(crate) struct PlanUpdateCell {
     explanation: Option<String>,
     plan: Vec<PlanItemArg>,
+    transcript_only: bool,
 }
 
-impl HistoryCell for PlanUpdateCell {
-    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+impl PlanUpdateCell {
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
         let render_note = |text: &str| -> Vec<Line<'static>> {
             let wrap_width = width.saturating_sub(4).max(1) as usize;
             let note = Line::from(text.to_string().dim().italic());
@@ -224,8 +232,25 @@ impl HistoryCell for PlanUpdateCell {
 
         lines
     }
+}
+
+impl HistoryCell for PlanUpdateCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        if self.transcript_only {
+            Vec::new()
+        } else {
+            self.lines(width)
+        }
+    }
+
+    fn transcript_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.lines(width)
+    }
 
     fn raw_lines(&self) -> Vec<Line<'static>> {
+        if self.transcript_only {
+            return Vec::new();
+        }
         let mut lines = vec![Line::from("Updated Plan")];
         if let Some(explanation) = self
             .explanation
@@ -1,4 +1,5 @@
 use super::*;
+use crate::history_cell::markdown_render_cache::RetainedLines;
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -13,7 +14,7 @@ fn finalized_plan_reuses_lines_primed_by_transcript_height() {
         .expect("render cache lock")
         .as_mut()
         .expect("render cache should be populated")
-        .1 = vec![HyperlinkLine::from("cached")];
+        .1 = RetainedLines::InMemory(vec![HyperlinkLine::from("cached")]);
 
     assert_eq!(
         visible_lines(cell.transcript_hyperlink_lines(width)),
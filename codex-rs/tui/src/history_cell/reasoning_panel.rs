@@ -0,0 +1,116 @@
+//! Collapsible reasoning summaries.
+//!
+//! Each turn's reasoning summaries collapse into a single "Reasoning" row so they do not double the
+//! length of the transcript. Every summary is still its own cell, inserted where it arrived, but the
+//! cells of one turn share a [`ReasoningPanel`] flag: the first draws the row, and all of them show
+//! their text only while the flag is expanded. Flipping the flag changes how cells already in
+//! scrollback render, so callers reflow the transcript afterwards. The transcript overlay always
+//! shows the full text. The row's hint names the `tui.keymap.app.toggle_reasoning` binding the
+//! panel was created with, and is left off when that action is unbound.
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use super::*;
+use crate::key_hint::KeyBinding;
+
+/// Expanded/collapsed state shared by the reasoning cells of one turn.
+#[derive(Clone, Debug)]
+pub(crate) struct ReasoningPanel {
+    expanded: Arc<AtomicBool>,
+    /// Binding named in the "Reasoning" row's hint.
+    toggle_key: Option<KeyBinding>,
+}
+
+impl ReasoningPanel {
+    pub(crate) fn new(expanded: bool, toggle_key: Option<KeyBinding>) -> Self {
+        Self {
+            expanded: Arc::new(AtomicBool::new(expanded)),
+            toggle_key,
+        }
+    }
+
+    pub(crate) fn is_expanded(&self) -> bool {
+        self.expanded.load(Ordering::Relaxed)
+    }
+
+    /// Flips the panel and returns whether it is now expanded.
+    pub(crate) fn toggle(&self) -> bool {
+        !self.expanded.fetch_xor(true, Ordering::Relaxed)
+    }
+}
+
+/// A reasoning summary whose visibility follows its turn's [`ReasoningPanel`].
+#[derive(Debug)]
+pub(crate) struct ReasoningPanelCell {
+    summary: ReasoningSummaryCell,
+    panel: ReasoningPanel,
+    /// Whether this is the turn's first summary, which draws the "Reasoning" row.
+    shows_header: bool,
+}
+
+impl ReasoningPanelCell {
+    pub(crate) fn new(
+        header: String,
+        content: String,
+        cwd: &Path,
+        panel: ReasoningPanel,
+        shows_header: bool,
+    ) -> Self {
+        Self {
+            summary: ReasoningSummaryCell::new(
+                header, content, cwd, /*transcript_only*/ false,
+            ),
+            panel,
+            shows_header,
+        }
+    }
+
+    fn header_line(&self) -> Line<'static> {
+        let mut spans = vec!["• ".dim(), "Reasoning".dim().italic()];
+        if let Some(key) = self.panel.toggle_key {
+            let action = if self.panel.is_expanded() {
+                "collapse"
+            } else {
+                "expand"
+            };
+            spans.push(" (".dim());
+            spans.push(key.into());
+            spans.push(format!(" to {action})").dim());
+        }
+        Line::from(spans)
+    }
+}
+
+impl HistoryCell for ReasoningPanelCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        if self.shows_header {
+            lines.push(self.header_line());
+        }
+        if self.panel.is_expanded() {
+            lines.extend(self.summary.display_lines(width));
+        }
+        lines
+    }
+
+    fn transcript_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.summary.transcript_lines(width)
+    }
+
+    fn raw_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        if self.shows_header {
+            lines.extend(plain_lines([self.header_line()]));
+        }
+        if self.panel.is_expanded() {
+            lines.extend(self.summary.raw_lines());
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+#[path = "reasoning_panel_tests.rs"]
+mod tests;
@@ -0,0 +1,93 @@
+use super::*;
+use crate::key_hint;
+use crossterm::event::KeyCode;
+use pretty_assertions::assert_eq;
+
+fn text(lines: Vec<Line<'static>>) -> Vec<String> {
+    plain_lines(lines)
+        .into_iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+fn turn_cells(panel: &ReasoningPanel) -> (ReasoningPanelCell, ReasoningPanelCell) {
+    let cwd = Path::new("/tmp");
+    (
+        ReasoningPanelCell::new(
+            "**Inspecting**".to_string(),
+            "Reading the config loader.".to_string(),
+            cwd,
+            panel.clone(),
+            /*shows_header*/ true,
+        ),
+        ReasoningPanelCell::new(
+            "**Planning**".to_string(),
+            "Adding a field to the struct.".to_string(),
+            cwd,
+            panel.clone(),
+            /*shows_header*/ false,
+        ),
+    )
+}
+
+#[test]
+fn collapsed_panel_shows_only_the_reasoning_row() {
+    let panel = ReasoningPanel::new(
+        /*expanded*/ false,
+        Some(key_hint::alt(KeyCode::Char('t'))),
+    );
+    let (first, second) = turn_cells(&panel);
+
+    assert_eq!(
+        text(first.display_lines(/*width*/ 80)),
+        vec!["• Reasoning (⌥ + t to expand)"]
+    );
+    assert_eq!(
+        text(second.display_lines(/*width*/ 80)),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        text(second.transcript_lines(/*width*/ 80)),
+        vec!["• Adding a field to the struct."]
+    );
+}
+
+#[test]
+fn toggling_the_panel_expands_every_cell_of_the_turn() {
+    let panel = ReasoningPanel::new(
+        /*expanded*/ false,
+        Some(key_hint::alt(KeyCode::Char('t'))),
+    );
+    let (first, second) = turn_cells(&panel);
+
+    assert!(panel.toggle());
+
+    assert_eq!(
+        text(first.display_lines(/*width*/ 80)),
+        vec![
+            "• Reasoning (⌥ + t to collapse)",
+            "• Reading the config loader.",
+        ]
+    );
+    assert_eq!(
+        text(second.display_lines(/*width*/ 80)),
+        vec!["• Adding a field to the struct."]
+    );
+    assert_eq!(
+        text(second.raw_lines()),
+        vec!["Adding a field to the struct."]
+    );
+    assert!(!panel.toggle());
+    assert_eq!(
+        text(second.display_lines(/*width*/ 80)),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn unbound_toggle_leaves_the_hint_off() {
+    let panel = ReasoningPanel::new(/*expanded*/ false, /*toggle_key*/ None);
+    let (first, _second) = turn_cells(&panel);
+
+    assert_eq!(text(first.display_lines(/*width*/ 80)), vec!["• Reasoning"]);
+}
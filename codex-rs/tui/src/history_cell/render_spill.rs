@@ -0,0 +1,252 @@
+//! On-disk spill for markdown renders evicted from memory by the render budget.
+//!
+//! Evicted lines are written as JSON to a per-process directory under the system temp dir, so
+//! scrolling back to an old cell reads its lines instead of parsing and highlighting the markdown
+//! again. A spill file is removed when its cell is dropped, renders again, or is pushed out by the
+//! spilled-bytes budget, and the whole directory is removed when the TUI exits. Directories left
+//! behind by sessions that did not exit cleanly are removed by the next session once they are a
+//! day old. Renders that carry inline images are never spilled; they render again from source.
+
+use crate::markdown_render::HiddenCode;
+use crate::terminal_hyperlinks::HyperlinkLine;
+use crate::terminal_hyperlinks::TerminalHyperlink;
+use ratatui::layout::Alignment;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::SystemTime;
+
+/// Directory under the system temp dir that holds one spill directory per TUI process.
+const SPILL_ROOT: &str = "codex-tui-renders";
+
+/// Spill directories of other processes older than this are assumed abandoned.
+const STALE_SPILL_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+static SPILL_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+fn spill_dir() -> Option<&'static Path> {
+    SPILL_DIR
+        .get_or_init(|| {
+            let root = std::env::temp_dir().join(SPILL_ROOT);
+            let name = std::process::id().to_string();
+            remove_stale_spill_dirs(&root, &name, STALE_SPILL_AGE);
+            let dir = root.join(name);
+            fs::create_dir_all(&dir).ok()?;
+            Some(dir)
+        })
+        .as_deref()
+}
+
+/// Remove this process's spill directory. Called once the TUI has exited.
+pub(crate) fn remove_spilled_renders() {
+    if let Some(Some(dir)) = SPILL_DIR.get() {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
+
+/// Remove spill directories under `root`, other than `keep`, untouched for at least `max_age`.
+fn remove_stale_spill_dirs(root: &Path, keep: &str, max_age: Duration) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    let now = SystemTime::now();
+    for entry in entries.flatten() {
+        if entry.file_name() == keep {
+            continue;
+        }
+        let stale = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age >= max_age);
+        if stale {
+            let _ = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+/// Lines of one evicted render, stored on disk. Dropping it removes the file.
+#[derive(Debug)]
+pub(super) struct SpilledRender {
+    path: PathBuf,
+    pub(super) bytes: u64,
+}
+
+impl SpilledRender {
+    /// Write `lines` for cache `id`. Returns `None` when the render has an inline image or the
+    /// spill directory cannot be written; the caller then drops the lines as before.
+    pub(super) fn write(id: u64, lines: &[HyperlinkLine]) -> Option<Self> {
+        Self::write_in(spill_dir()?, id, lines)
+    }
+
+    fn write_in(dir: &Path, id: u64, lines: &[HyperlinkLine]) -> Option<Self> {
+        let lines = lines
+            .iter()
+            .map(SpilledLine::from_line)
+            .collect::<Option<Vec<_>>>()?;
+        let json = serde_json::to_vec(&lines).ok()?;
+        let path = dir.join(format!("{id}.json"));
+        fs::write(&path, &json).ok()?;
+        Some(Self {
+            path,
+            bytes: json.len() as u64,
+        })
+    }
+
+    /// Read the spilled lines back, or `None` when the file is gone or unreadable.
+    pub(super) fn read(&self) -> Option<Vec<HyperlinkLine>> {
+        let json = fs::read(&self.path).ok()?;
+        let lines: Vec<SpilledLine> = serde_json::from_slice(&json).ok()?;
+        let mut code_block = None;
+        Some(
+            lines
+                .into_iter()
+                .map(|line| line.into_line(&mut code_block))
+                .collect(),
+        )
+    }
+}
+
+impl Drop for SpilledRender {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SpilledLine {
+    style: SpilledStyle,
+    alignment: Option<SpilledAlignment>,
+    spans: Vec<(String, SpilledStyle)>,
+    hyperlinks: Vec<TerminalHyperlink>,
+    scrollable: bool,
+    heading_level: Option<u8>,
+    hidden_code: Option<HiddenCode>,
+    code_block: Option<String>,
+}
+
+impl SpilledLine {
+    fn from_line(line: &HyperlinkLine) -> Option<Self> {
+        if line.image.is_some() {
+            return None;
+        }
+        Some(Self {
+            style: line.line.style.into(),
+            alignment: line.line.alignment.map(Into::into),
+            spans: line
+                .line
+                .spans
+                .iter()
+                .map(|span| (span.content.to_string(), span.style.into()))
+                .collect(),
+            hyperlinks: line.hyperlinks.clone(),
+            scrollable: line.scrollable,
+            heading_level: line.heading_level,
+            hidden_code: line.hidden_code.as_deref().cloned(),
+            code_block: line.code_block.as_deref().map(str::to_string),
+        })
+    }
+
+    /// Rebuild the line. Consecutive rows of one code block share its source again through
+    /// `previous_code_block`.
+    fn into_line(self, previous_code_block: &mut Option<Arc<str>>) -> HyperlinkLine {
+        let code_block = self.code_block.map(|source| match previous_code_block {
+            Some(previous) if **previous == *source => Arc::clone(previous),
+            _ => Arc::from(source),
+        });
+        previous_code_block.clone_from(&code_block);
+        let mut line = Line::from(
+            self.spans
+                .into_iter()
+                .map(|(content, style)| Span::styled(content, Style::from(style)))
+                .collect::<Vec<_>>(),
+        )
+        .style(Style::from(self.style));
+        line.alignment = self.alignment.map(Into::into);
+        HyperlinkLine {
+            line,
+            hyperlinks: self.hyperlinks,
+            image: None,
+            scrollable: self.scrollable,
+            heading_level: self.heading_level,
+            hidden_code: self.hidden_code.map(Arc::new),
+            code_block,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SpilledStyle {
+    fg: Option<String>,
+    bg: Option<String>,
+    underline_color: Option<String>,
+    add_modifier: u16,
+    sub_modifier: u16,
+}
+
+impl From<Style> for SpilledStyle {
+    fn from(style: Style) -> Self {
+        Self {
+            fg: style.fg.map(|color| color.to_string()),
+            bg: style.bg.map(|color| color.to_string()),
+            underline_color: style.underline_color.map(|color| color.to_string()),
+            add_modifier: style.add_modifier.bits(),
+            sub_modifier: style.sub_modifier.bits(),
+        }
+    }
+}
+
+impl From<SpilledStyle> for Style {
+    fn from(style: SpilledStyle) -> Self {
+        let color = |color: Option<String>| color.and_then(|color| color.parse::<Color>().ok());
+        Style {
+            fg: color(style.fg),
+            bg: color(style.bg),
+            underline_color: color(style.underline_color),
+            add_modifier: Modifier::from_bits_truncate(style.add_modifier),
+            sub_modifier: Modifier::from_bits_truncate(style.sub_modifier),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum SpilledAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+impl From<Alignment> for SpilledAlignment {
+    fn from(alignment: Alignment) -> Self {
+        match alignment {
+            Alignment::Left => Self::Left,
+            Alignment::Center => Self::Center,
+            Alignment::Right => Self::Right,
+        }
+    }
+}
+
+impl From<SpilledAlignment> for Alignment {
+    fn from(alignment: SpilledAlignment) -> Self {
+        match alignment {
+            SpilledAlignment::Left => Self::Left,
+            SpilledAlignment::Center => Self::Center,
+            SpilledAlignment::Right => Self::Right,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "render_spill_tests.rs"]
+mod tests;
@@ -0,0 +1,76 @@
+use super::*;
+use crate::inline_images::test_inline_image;
+use pretty_assertions::assert_eq;
+use ratatui::style::Stylize;
+
+#[test]
+fn spilled_lines_read_back_unchanged() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let code_block: Arc<str> = Arc::from("let a = 1;\nlet b = 2;\n");
+    let mut heading = HyperlinkLine::from(Line::from(vec![
+        "# ".magenta(),
+        Span::styled(
+            "Title",
+            Style::new().bold().underline_color(Color::Rgb(1, 2, 3)),
+        ),
+    ]));
+    heading.heading_level = Some(1);
+    let mut first_row = HyperlinkLine::from(Line::from("let a = 1;".cyan()).centered());
+    first_row.code_block = Some(Arc::clone(&code_block));
+    let mut second_row = HyperlinkLine::from(Line::from("let b = 2;".on_black().not_dim()));
+    second_row.code_block = Some(code_block);
+    second_row.scrollable = true;
+    let mut link = HyperlinkLine::default();
+    link.push_span("docs".into(), Some("https://example.com/docs"));
+    let lines = vec![heading, first_row, second_row, link];
+
+    let spilled = SpilledRender::write_in(dir.path(), /*id*/ 7, &lines).expect("spill");
+    let restored = spilled.read().expect("read spill");
+
+    assert_eq!(restored, lines);
+    let (Some(first), Some(second)) = (&restored[1].code_block, &restored[2].code_block) else {
+        panic!("code block rows lost their source");
+    };
+    assert!(Arc::ptr_eq(first, second));
+}
+
+#[test]
+fn renders_with_images_are_not_spilled() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let mut line = HyperlinkLine::from("chart");
+    line.image = Some(test_inline_image(
+        PathBuf::from("chart.png"),
+        /*column*/ 0,
+        /*rows*/ 1,
+    ));
+
+    assert!(SpilledRender::write_in(dir.path(), /*id*/ 0, &[line]).is_none());
+}
+
+#[test]
+fn dropping_a_spill_removes_its_file() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let spilled =
+        SpilledRender::write_in(dir.path(), /*id*/ 3, &[HyperlinkLine::from("a")]).expect("spill");
+    let path = dir.path().join("3.json");
+    assert!(path.exists());
+
+    drop(spilled);
+
+    assert!(!path.exists());
+}
+
+#[test]
+fn stale_spill_dirs_of_other_sessions_are_removed() {
+    let root = tempfile::tempdir().expect("temp dir");
+    for name in ["1", "2"] {
+        fs::create_dir(root.path().join(name)).expect("spill dir");
+    }
+
+    remove_stale_spill_dirs(root.path(), "1", Duration::from_secs(3600));
+    assert!(root.path().join("2").exists());
+
+    remove_stale_spill_dirs(root.path(), "1", Duration::ZERO);
+    assert!(root.path().join("1").exists());
+    assert!(!root.path().join("2").exists());
+}
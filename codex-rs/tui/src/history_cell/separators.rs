@@ -73,6 +73,34 @@ impl HistoryCell for FinalMessageSeparator {
     }
 }
 
+/// Separator marking a stretch of time in which the conversation was idle, such as between a
+/// resumed session's last turn and the next one.
+#[derive(Debug)]
+pub(crate) struct TimeGapSeparator {
+    label: String,
+}
+
+impl TimeGapSeparator {
+    pub(crate) fn new(gap: Duration) -> Self {
+        Self {
+            label: crate::transcript_timestamps::gap_label(gap),
+        }
+    }
+}
+
+impl HistoryCell for TimeGapSeparator {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let label = format!(" {} ", self.label);
+        let (label, _suffix, label_width) = take_prefix_by_width(&label, width as usize);
+        let fill = (width as usize).saturating_sub(label_width);
+        vec![Line::from_iter(["─".repeat(fill / 2), label, "─".repeat(fill - fill / 2)]).dim()]
+    }
+
+    fn raw_lines(&self) -> Vec<Line<'static>> {
+        vec![Line::from(format!("— {} —", self.label))]
+    }
+}
+
 pub(crate) fn runtime_metrics_label(summary: RuntimeMetricsSummary) -> Option<String> {
     let mut parts = Vec::new();
     if summary.tool_calls.count > 0 {
@@ -613,6 +613,25 @@ fn ps_output_empty_snapshot() {
     insta::assert_snapshot!(rendered);
 }
 
+#[test]
+fn ps_text_lists_full_commands_and_recent_output() {
+    let text = unified_exec_processes_text(&[
+        UnifiedExecProcessDetails {
+            command_display: "echo hello\nand then some extra text".to_string(),
+            recent_chunks: vec!["hello".to_string(), "done".to_string()],
+        },
+        UnifiedExecProcessDetails {
+            command_display: "cargo watch".to_string(),
+            recent_chunks: Vec::new(),
+        },
+    ]);
+
+    assert_eq!(
+        text,
+        "Background terminals\n\n$ echo hello\n$ and then some extra text\n  hello\n  done\n\n$ cargo watch\n"
+    );
+}
+
 #[tokio::test]
 async fn session_info_uses_availability_nux_tooltip_override() {
     let config = test_config().await;
@@ -2201,7 +2220,7 @@ fn plan_update_with_note_and_wrapping_snapshot() {
             ],
         };
 
-    let cell = new_plan_update(update);
+    let cell = new_plan_update(update, /*transcript_only*/ false);
     // Narrow width to force wrapping for both the note and steps
     let lines = cell.display_lines(/*width*/ 32);
     let rendered = render_lines(&lines).join("\n");
@@ -2224,7 +2243,7 @@ fn plan_update_without_note_snapshot() {
         ],
     };
 
-    let cell = new_plan_update(update);
+    let cell = new_plan_update(update, /*transcript_only*/ false);
     let lines = cell.display_lines(/*width*/ 40);
     let rendered = render_lines(&lines).join("\n");
     insta::assert_snapshot!(rendered);
@@ -2244,7 +2263,7 @@ fn plan_update_does_not_split_url_like_tokens_in_note_or_step() {
         }],
     };
 
-    let cell = new_plan_update(update);
+    let cell = new_plan_update(update, /*transcript_only*/ false);
     let rendered = render_lines(&cell.display_lines(/*width*/ 30));
 
     assert_eq!(
@@ -0,0 +1,254 @@
+//! Locale catalog for user-facing TUI strings.
+//!
+//! Each translatable string is a [`Msg`] variant with a stable catalog key and an English default
+//! compiled into the binary. At startup [`init`] picks a language from `tui.language`, falling
+//! back to `LC_ALL`, `LC_MESSAGES`, and `LANG`, and loads `$CODEX_HOME/locales/<language>.toml`:
+//! a flat table mapping catalog keys to translated templates. Keys missing from that file render
+//! in English, so community translations can be added or completed without code changes.
+//! `/language` switches to another language's catalog for the rest of the session.
+//!
+//! Templates may reference `{name}` placeholders, which [`tr_args`] fills in.
+//!
+//! The catalog covers the status line, the background terminal footer, and the approval overlay's
+//! titles, header labels, and options. Key hints such as "Press enter to confirm" are shared with
+//! every popup and are not in it yet.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+const LOCALES_DIR: &str = "locales";
+const LANGUAGE_ENV_VARS: &[&str] = &["LC_ALL", "LC_MESSAGES", "LANG"];
+
+static CATALOG: LazyLock<RwLock<Catalog>> = LazyLock::new(|| RwLock::new(Catalog::default()));
+
+/// Defines [`Msg`] with its catalog key and English default side by side.
+macro_rules! messages {
+    ($($variant:ident => $key:literal, $english:literal;)*) => {
+        /// A translatable TUI string.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub(crate) enum Msg {
+            $($variant,)*
+        }
+
+        impl Msg {
+            /// Every message, in catalog order.
+            pub(crate) const ALL: &[Msg] = &[$(Msg::$variant,)*];
+
+            /// Key used for this message in locale files.
+            pub(crate) fn key(self) -> &'static str {
+                match self {
+                    $(Msg::$variant => $key,)*
+                }
+            }
+
+            /// Built-in English text, used when the active locale has no translation.
+            pub(crate) fn english(self) -> &'static str {
+                match self {
+                    $(Msg::$variant => $english,)*
+                }
+            }
+        }
+    };
+}
+
+messages! {
+    StatusWorking => "status.working", "Working";
+    StatusWaitingForBackgroundTerminal =>
+        "status.waiting_for_background_terminal", "Waiting for background terminal";
+    StatusStoppingBackgroundTerminals =>
+        "status.stopping_background_terminals", "Stopping all background terminals.";
+    FooterBackgroundTerminalsOne =>
+        "footer.background_terminals.one",
+        "{count} background terminal running · /ps to view · /stop to close";
+    FooterBackgroundTerminalsOther =>
+        "footer.background_terminals.other",
+        "{count} background terminals running · /ps to view · /stop to close";
+    ApprovalRunCommandTitle =>
+        "approval.run_command.title", "Would you like to run the following command?";
+    ApprovalNetworkAccessTitle =>
+        "approval.network_access.title", "Do you want to approve network access to \"{host}\"?";
+    ApprovalPermissionsTitle =>
+        "approval.permissions.title", "Would you like to grant these permissions?";
+    ApprovalEditsTitle => "approval.edits.title", "Would you like to make the following edits?";
+    ApprovalElicitationTitle => "approval.elicitation.title", "{server} needs your approval.";
+    ApprovalHeaderThread => "approval.header.thread", "Thread:";
+    ApprovalHeaderEnvironment => "approval.header.environment", "Environment:";
+    ApprovalHeaderReason => "approval.header.reason", "Reason:";
+    ApprovalHeaderPermissionRule => "approval.header.permission_rule", "Permission rule:";
+    ApprovalHeaderServer => "approval.header.server", "Server:";
+    ApprovalYesProceed => "approval.option.yes_proceed", "Yes, proceed";
+    ApprovalYesJustThisOnce => "approval.option.yes_just_this_once", "Yes, just this once";
+    ApprovalYesAllowHostForConversation =>
+        "approval.option.yes_allow_host_for_conversation",
+        "Yes, and allow this host for this conversation";
+    ApprovalYesAllowPermissionsForSession =>
+        "approval.option.yes_allow_permissions_for_session",
+        "Yes, and allow these permissions for this session";
+    ApprovalYesCommandForSession =>
+        "approval.option.yes_command_for_session",
+        "Yes, and don't ask again for this command in this session";
+    ApprovalYesFilesForSession =>
+        "approval.option.yes_files_for_session", "Yes, and don't ask again for these files";
+    ApprovalYesCommandPrefix =>
+        "approval.option.yes_command_prefix",
+        "Yes, and don't ask again for commands that start with `{prefix}`";
+    ApprovalYesAllowHostInFuture =>
+        "approval.option.yes_allow_host_in_future", "Yes, and allow this host in the future";
+    ApprovalNoBlockHostInFuture =>
+        "approval.option.no_block_host_in_future", "No, and block this host in the future";
+    ApprovalYesGrantPermissionsForTurn =>
+        "approval.option.yes_grant_permissions_for_turn",
+        "Yes, grant these permissions for this turn";
+    ApprovalYesGrantForTurnWithStrictAutoReview =>
+        "approval.option.yes_grant_for_turn_with_strict_auto_review",
+        "Yes, grant for this turn with strict auto review";
+    ApprovalYesGrantPermissionsForSession =>
+        "approval.option.yes_grant_permissions_for_session",
+        "Yes, grant these permissions for this session";
+    ApprovalNoContinueWithoutPermissions =>
+        "approval.option.no_continue_without_permissions", "No, continue without permissions";
+    ApprovalYesProvideRequestedInfo =>
+        "approval.option.yes_provide_requested_info", "Yes, provide the requested info";
+    ApprovalNoContinueWithoutInfo =>
+        "approval.option.no_continue_without_info", "No, but continue without it";
+    ApprovalCancelRequest => "approval.option.cancel_request", "Cancel this request";
+    ApprovalNoContinueWithoutRunning =>
+        "approval.option.no_continue_without_running", "No, continue without running it";
+    ApprovalNoTellCodex =>
+        "approval.option.no_tell_codex", "No, and tell Codex what to do differently";
+}
+
+/// Translations for one language, keyed by [`Msg::key`].
+#[derive(Debug, Default)]
+pub(crate) struct Catalog {
+    translations: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Parses a locale file. Non-string values and unknown keys are ignored with a warning so a
+    /// catalog written for a newer Codex still loads.
+    pub(crate) fn parse(contents: &str) -> Result<Self, toml::de::Error> {
+        let table: toml::Table = toml::from_str(contents)?;
+        let mut translations = HashMap::new();
+        for (key, value) in table {
+            match value {
+                toml::Value::String(text) if Msg::ALL.iter().any(|msg| msg.key() == key) => {
+                    translations.insert(key, text);
+                }
+                _ => tracing::warn!("ignoring locale entry `{key}`"),
+            }
+        }
+        Ok(Self { translations })
+    }
+
+    pub(crate) fn get(&self, msg: Msg) -> &str {
+        self.translations
+            .get(msg.key())
+            .map_or(msg.english(), String::as_str)
+    }
+}
+
+/// Selects the UI language and loads its catalog, returning a warning for unreadable files.
+///
+/// Looks for the full tag first (`pt-BR.toml`) and then the primary subtag (`pt.toml`). A
+/// language without a locale file silently uses English.
+pub(crate) fn init(configured: Option<&str>, codex_home: &Path) -> Option<String> {
+    let path = resolve_language(configured, |name| std::env::var(name).ok())
+        .and_then(|language| find_locale_file(&language, codex_home));
+    let (catalog, warning) = match path {
+        Some(path) => read_catalog(&path),
+        None => (Catalog::default(), None),
+    };
+    *CATALOG.write().unwrap_or_else(PoisonError::into_inner) = catalog;
+    warning
+}
+
+/// Switches the UI to `language` for the rest of the session if it has a locale file, returning a
+/// warning for an unreadable one. Without a locale file the current catalog stays.
+pub(crate) fn switch_to(language: &str, codex_home: &Path) -> Option<String> {
+    let path = resolve_language(Some(language), |_| None)
+        .and_then(|language| find_locale_file(&language, codex_home))?;
+    let (catalog, warning) = read_catalog(&path);
+    *CATALOG.write().unwrap_or_else(PoisonError::into_inner) = catalog;
+    warning
+}
+
+fn find_locale_file(language: &str, codex_home: &Path) -> Option<PathBuf> {
+    let locales = codex_home.join(LOCALES_DIR);
+    let primary = language.split('-').next().unwrap_or(language);
+    [language, primary]
+        .into_iter()
+        .map(|tag| locales.join(format!("{tag}.toml")))
+        .find(|path| path.is_file())
+}
+
+/// Reads a locale file, falling back to English with a warning if it cannot be used.
+fn read_catalog(path: &Path) -> (Catalog, Option<String>) {
+    let catalog = match std::fs::read_to_string(path) {
+        Ok(contents) => Catalog::parse(&contents).map_err(|err| err.to_string()),
+        Err(err) => Err(err.to_string()),
+    };
+    match catalog {
+        Ok(catalog) => (catalog, None),
+        Err(err) => (
+            Catalog::default(),
+            Some(format!(
+                "Failed to load locale file {}: {err}. Using English.",
+                path.display()
+            )),
+        ),
+    }
+}
+
+/// Returns the normalized language tag from config or the locale environment.
+///
+/// `C` and `POSIX` locales mean "no preference". Encodings and modifiers are dropped and
+/// underscores become hyphens, so `pt_BR.UTF-8` resolves to `pt-BR`.
+pub(crate) fn resolve_language(
+    configured: Option<&str>,
+    env: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    let raw = match configured {
+        Some(language) => language.to_string(),
+        None => LANGUAGE_ENV_VARS
+            .iter()
+            .filter_map(|name| env(name))
+            .find(|value| !value.trim().is_empty())?,
+    };
+    let tag = raw
+        .trim()
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-");
+    (!tag.is_empty() && tag != "C" && tag != "POSIX").then_some(tag)
+}
+
+/// Returns `msg` in the active language.
+pub(crate) fn tr(msg: Msg) -> String {
+    CATALOG
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(msg)
+        .to_string()
+}
+
+/// Returns `msg` in the active language with `{name}` placeholders replaced.
+pub(crate) fn tr_args(msg: Msg, args: &[(&str, &str)]) -> String {
+    fill(&tr(msg), args)
+}
+
+fn fill(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+#[cfg(test)]
+#[path = "i18n_tests.rs"]
+mod tests;
@@ -0,0 +1,82 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use std::collections::HashSet;
+
+fn env_from(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    move |name| vars.get(name).cloned()
+}
+
+#[test]
+fn catalog_keys_are_unique() {
+    let keys: HashSet<&str> = Msg::ALL.iter().map(|msg| msg.key()).collect();
+    assert_eq!(keys.len(), Msg::ALL.len());
+}
+
+#[test]
+fn resolve_language_prefers_config_then_environment() {
+    assert_eq!(
+        resolve_language(Some("fr"), env_from(&[("LANG", "de_DE.UTF-8")])),
+        Some("fr".to_string())
+    );
+    assert_eq!(
+        resolve_language(
+            /*configured*/ None,
+            env_from(&[("LC_MESSAGES", "pt_BR.UTF-8"), ("LANG", "de_DE.UTF-8")])
+        ),
+        Some("pt-BR".to_string())
+    );
+    assert_eq!(
+        resolve_language(/*configured*/ None, env_from(&[("LC_ALL", "C.UTF-8")])),
+        None
+    );
+    assert_eq!(resolve_language(/*configured*/ None, env_from(&[])), None);
+}
+
+#[test]
+fn catalog_falls_back_to_english_for_missing_keys() {
+    let catalog = Catalog::parse(
+        r#"
+"status.working" = "Travail en cours"
+"not.a.real.key" = "ignored"
+"#,
+    )
+    .expect("valid locale file");
+
+    assert_eq!(catalog.get(Msg::StatusWorking), "Travail en cours");
+    assert_eq!(catalog.get(Msg::ApprovalYesProceed), "Yes, proceed");
+}
+
+#[test]
+fn fill_replaces_named_placeholders() {
+    assert_eq!(
+        fill(
+            Msg::FooterBackgroundTerminalsOther.english(),
+            &[("count", "3")]
+        ),
+        "3 background terminals running · /ps to view · /stop to close"
+    );
+}
+
+#[test]
+fn locale_files_are_found_by_full_tag_then_primary_subtag() {
+    let codex_home = tempfile::TempDir::new().expect("temp dir");
+    let locales = codex_home.path().join(LOCALES_DIR);
+    std::fs::create_dir_all(&locales).expect("create locales dir");
+    std::fs::write(
+        locales.join("fr.toml"),
+        r#""status.working" = "Travail en cours""#,
+    )
+    .expect("write locale file");
+
+    let path = find_locale_file("fr-CA", codex_home.path()).expect("fr.toml should match");
+    assert_eq!(path, locales.join("fr.toml"));
+    let (catalog, warning) = read_catalog(&path);
+    assert_eq!(catalog.get(Msg::StatusWorking), "Travail en cours");
+    assert_eq!(warning, None);
+
+    assert_eq!(find_locale_file("Japanese", codex_home.path()), None);
+}
@@ -0,0 +1,47 @@
+//! Pausing background work while the user is away (`tui.idle_pause_seconds`).
+//!
+//! `App` remembers when the last key or paste arrived. Once the configured period passes without
+//! input, it marks background work paused. Animation frames (the status spinner, animated terminal
+//! titles, and the ambient pet), composer draft autosave, and workspace headline polling check
+//! [`background_work_paused`] before scheduling more work. The next input clears the flag and
+//! requests a frame, and that draw restarts whatever was skipped.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn background_work_paused() -> bool {
+    PAUSED.load(Ordering::Relaxed)
+}
+
+/// Sets whether background work is paused and returns whether it was paused before.
+pub(crate) fn set_background_work_paused(paused: bool) -> bool {
+    PAUSED.swap(paused, Ordering::Relaxed)
+}
+
+/// When background work should pause if no input arrives after `last_input_at`, or `None` when
+/// idle pausing is off or work is already paused.
+pub(crate) fn idle_pause_deadline(
+    last_input_at: Instant,
+    idle_pause_seconds: Option<u64>,
+) -> Option<Instant> {
+    if background_work_paused() {
+        return None;
+    }
+    last_input_at.checked_add(Duration::from_secs(idle_pause_seconds?))
+}
+
+/// Completes at `deadline`, or never when there is none.
+pub(crate) async fn wait_for_idle_pause(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+#[path = "idle_pause_tests.rs"]
+mod tests;
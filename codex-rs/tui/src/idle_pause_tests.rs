@@ -0,0 +1,16 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn deadline_follows_the_last_input() {
+    let last_input_at = Instant::now();
+
+    assert_eq!(
+        idle_pause_deadline(last_input_at, /*idle_pause_seconds*/ None),
+        None
+    );
+    assert_eq!(
+        idle_pause_deadline(last_input_at, Some(300)),
+        Some(last_input_at + Duration::from_secs(300))
+    );
+}
@@ -0,0 +1,276 @@
+//! Inline images drawn into scrollback with a terminal graphics protocol.
+//!
+//! Markdown images always render as an `[image: alt] (url)` placeholder. When `tui.inline_images`
+//! is on and the terminal speaks the iTerm2, Kitty, or Sixel protocol, a local image file is also
+//! drawn below the placeholder. The markdown renderer reserves blank rows for it and attaches an
+//! [`InlineImage`] to the last one; the image is only drawn when that row reaches scrollback, so
+//! it never affects layout and degrades to blank rows anywhere else (the transcript overlay, for
+//! example).
+
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+use anyhow::Context;
+use anyhow::Result;
+use base64::Engine as _;
+use base64::engine::general_purpose;
+use image::imageops::FilterType;
+use url::Url;
+
+use crate::pets::encode_sixel;
+use crate::terminal_capabilities::TerminalCapabilities;
+use crate::terminal_capabilities::terminal_capabilities;
+
+const ESC: &str = "\x1b";
+const ST: &str = "\x1b\\";
+const BEL: &str = "\x07";
+const KITTY_CHUNK_SIZE: usize = 4096;
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+/// Tallest image drawn, in rows, so one screenshot cannot push a whole answer out of view.
+const MAX_IMAGE_ROWS: u16 = 20;
+/// Cell size assumed when the terminal does not report its pixel size.
+const DEFAULT_CELL_SIZE: CellSize = CellSize {
+    width_px: 10,
+    height_px: 20,
+};
+
+static INLINE_IMAGE_TERMINAL: RwLock<Option<InlineImageTerminal>> = RwLock::new(None);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum InlineImageProtocol {
+    /// iTerm2's `OSC 1337 File=` sequence, also understood by WezTerm.
+    Iterm2,
+    Kitty,
+    Sixel,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct CellSize {
+    width_px: u16,
+    height_px: u16,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct InlineImageTerminal {
+    protocol: InlineImageProtocol,
+    cell_size: CellSize,
+}
+
+/// A local image to draw over the rows reserved for it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct InlineImage {
+    pub(crate) path: PathBuf,
+    pub(crate) protocol: InlineImageProtocol,
+    /// Column of the image's left edge; shifted like hyperlink ranges when lines are prefixed.
+    pub(crate) column: usize,
+    pub(crate) columns: u16,
+    /// Rows the image covers, ending at the row of the line that carries it.
+    pub(crate) rows: u16,
+    cell_size: CellSize,
+}
+
+/// Applies `tui.inline_images` from config. Detects the terminal's graphics protocol once, so set
+/// it before the first markdown is rendered.
+pub(crate) fn set_inline_images(enabled: bool) {
+    let terminal = if enabled {
+        detect_protocol().map(|protocol| InlineImageTerminal {
+            protocol,
+            cell_size: detect_cell_size(),
+        })
+    } else {
+        None
+    };
+    *INLINE_IMAGE_TERMINAL
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = terminal;
+}
+
+fn inline_image_terminal() -> Option<InlineImageTerminal> {
+    *INLINE_IMAGE_TERMINAL
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Resolves a markdown image destination to a drawable image at most `max_columns` wide.
+/// Returns `None` when inline images are off, the destination is not a readable local image, or
+/// it is a remote URL; the placeholder line is all that renders then.
+pub(crate) fn load(
+    destination: &str,
+    cwd: Option<&Path>,
+    max_columns: usize,
+) -> Option<InlineImage> {
+    let terminal = inline_image_terminal()?;
+    let path = local_image_path(destination, cwd)?;
+    let (width_px, height_px) = image::image_dimensions(&path).ok()?;
+    let (columns, rows) = fit_cells(width_px, height_px, max_columns, terminal.cell_size)?;
+    Some(InlineImage {
+        path,
+        protocol: terminal.protocol,
+        column: 0,
+        columns,
+        rows,
+        cell_size: terminal.cell_size,
+    })
+}
+
+#[cfg(test)]
+pub(crate) fn test_inline_image(path: PathBuf, column: usize, rows: u16) -> InlineImage {
+    InlineImage {
+        path,
+        protocol: InlineImageProtocol::Iterm2,
+        column,
+        columns: 4,
+        rows,
+        cell_size: DEFAULT_CELL_SIZE,
+    }
+}
+
+fn local_image_path(destination: &str, cwd: Option<&Path>) -> Option<PathBuf> {
+    let path = if destination.starts_with("file://") {
+        Url::parse(destination).ok()?.to_file_path().ok()?
+    } else if destination.contains("://") || destination.starts_with("data:") {
+        return None;
+    } else {
+        let path = PathBuf::from(destination);
+        match cwd {
+            Some(cwd) if path.is_relative() => cwd.join(path),
+            _ => path,
+        }
+    };
+    path.is_file().then_some(path)
+}
+
+/// Columns and rows that fit the image in `max_columns` and `MAX_IMAGE_ROWS` without
+/// upscaling it or changing its aspect ratio.
+fn fit_cells(
+    width_px: u32,
+    height_px: u32,
+    max_columns: usize,
+    cell_size: CellSize,
+) -> Option<(u16, u16)> {
+    if width_px == 0 || height_px == 0 || max_columns == 0 {
+        return None;
+    }
+    let cell_width = f64::from(cell_size.width_px);
+    let cell_height = f64::from(cell_size.height_px);
+    let aspect = f64::from(height_px) / f64::from(width_px);
+    let max_columns = max_columns.min(usize::from(u16::MAX)) as f64;
+    let mut columns = (f64::from(width_px) / cell_width)
+        .ceil()
+        .clamp(1.0, max_columns);
+    let mut rows = (columns * cell_width * aspect / cell_height)
+        .ceil()
+        .max(1.0);
+    let max_rows = f64::from(MAX_IMAGE_ROWS);
+    if rows > max_rows {
+        rows = max_rows;
+        columns = (rows * cell_height / aspect / cell_width).floor().max(1.0);
+    }
+    Some((columns as u16, rows as u16))
+}
+
+/// The escape sequence that draws `image` with its top-left corner at the cursor. Kitty and
+/// iTerm2 leave the cursor where it was; Sixel terminals move it below the image.
+pub(crate) fn escape_sequence(image: &InlineImage) -> Result<String> {
+    let bytes = fs::read(&image.path).with_context(|| format!("read {}", image.path.display()))?;
+    let columns = image.columns;
+    let rows = image.rows;
+    match image.protocol {
+        InlineImageProtocol::Iterm2 => {
+            let size = bytes.len();
+            let payload = general_purpose::STANDARD.encode(&bytes);
+            Ok(format!(
+                "{ESC}]1337;File=inline=1;size={size};width={columns};height={rows};preserveAspectRatio=1:{payload}{BEL}"
+            ))
+        }
+        InlineImageProtocol::Kitty => {
+            let png = if bytes.starts_with(PNG_SIGNATURE) {
+                bytes
+            } else {
+                let mut png = Cursor::new(Vec::new());
+                image::load_from_memory(&bytes)
+                    .with_context(|| format!("decode {}", image.path.display()))?
+                    .write_to(&mut png, image::ImageFormat::Png)
+                    .context("encode png")?;
+                png.into_inner()
+            };
+            Ok(kitty_transmit_png(&png, columns, rows))
+        }
+        InlineImageProtocol::Sixel => {
+            let decoded = image::load_from_memory(&bytes)
+                .with_context(|| format!("decode {}", image.path.display()))?;
+            let rgba = decoded
+                .resize(
+                    u32::from(columns) * u32::from(image.cell_size.width_px),
+                    u32::from(rows) * u32::from(image.cell_size.height_px),
+                    FilterType::Triangle,
+                )
+                .to_rgba8();
+            let (width, height) = rgba.dimensions();
+            let sixel = encode_sixel(&rgba.into_raw(), width, height)?;
+            String::from_utf8(sixel).context("sixel output is not valid UTF-8")
+        }
+    }
+}
+
+fn kitty_transmit_png(png: &[u8], columns: u16, rows: u16) -> String {
+    let payload = general_purpose::STANDARD.encode(png);
+    let chunks = payload
+        .as_bytes()
+        .chunks(KITTY_CHUNK_SIZE)
+        .collect::<Vec<_>>();
+    let mut command = String::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        // Base64 output is ASCII, so every chunk boundary is a char boundary.
+        let chunk = String::from_utf8_lossy(chunk);
+        let more = u8::from(index + 1 < chunks.len());
+        if index == 0 {
+            command.push_str(&format!(
+                "{ESC}_Ga=T,f=100,c={columns},r={rows},C=1,q=2,m={more};{chunk}{ST}"
+            ));
+        } else {
+            command.push_str(&format!("{ESC}_Gm={more};{chunk}{ST}"));
+        }
+    }
+    command
+}
+
+fn detect_protocol() -> Option<InlineImageProtocol> {
+    protocol_for_capabilities(terminal_capabilities())
+}
+
+/// Picks the terminal's native protocol first: iTerm2 and WezTerm also speak Kitty graphics, but
+/// draw their own protocol more reliably.
+fn protocol_for_capabilities(capabilities: TerminalCapabilities) -> Option<InlineImageProtocol> {
+    if capabilities.iterm2_images {
+        Some(InlineImageProtocol::Iterm2)
+    } else if capabilities.kitty_graphics {
+        Some(InlineImageProtocol::Kitty)
+    } else if capabilities.sixel {
+        Some(InlineImageProtocol::Sixel)
+    } else {
+        None
+    }
+}
+
+fn detect_cell_size() -> CellSize {
+    crossterm::terminal::window_size()
+        .ok()
+        .and_then(|size| {
+            (size.columns > 0 && size.rows > 0 && size.width > 0 && size.height > 0).then(|| {
+                CellSize {
+                    width_px: (size.width / size.columns).max(1),
+                    height_px: (size.height / size.rows).max(1),
+                }
+            })
+        })
+        .unwrap_or(DEFAULT_CELL_SIZE)
+}
+
+#[cfg(test)]
+#[path = "inline_images_tests.rs"]
+mod tests;
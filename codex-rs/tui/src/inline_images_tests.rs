@@ -0,0 +1,224 @@
+use codex_config::types::TerminalCapabilitiesToml;
+use codex_terminal_detection::Multiplexer;
+use codex_terminal_detection::TerminalInfo;
+use codex_terminal_detection::TerminalName;
+use pretty_assertions::assert_eq;
+
+use super::*;
+use crate::terminal_palette::StdoutColorLevel;
+
+const CELL: CellSize = CellSize {
+    width_px: 10,
+    height_px: 20,
+};
+
+fn write_png(dir: &Path, name: &str, width: u32, height: u32) -> PathBuf {
+    let path = dir.join(name);
+    image::RgbaImage::from_pixel(width, height, image::Rgba([0, 128, 255, 255]))
+        .save(&path)
+        .unwrap();
+    path
+}
+
+fn test_image(path: PathBuf, protocol: InlineImageProtocol) -> InlineImage {
+    InlineImage {
+        path,
+        protocol,
+        column: 0,
+        columns: 4,
+        rows: 2,
+        cell_size: CELL,
+    }
+}
+
+fn terminal(name: TerminalName, term_program: Option<&str>, term: Option<&str>) -> TerminalInfo {
+    TerminalInfo {
+        name,
+        term_program: term_program.map(str::to_string),
+        version: None,
+        term: term.map(str::to_string),
+        multiplexer: None,
+    }
+}
+
+fn protocol_for_terminal(info: &TerminalInfo) -> Option<InlineImageProtocol> {
+    protocol_for_capabilities(TerminalCapabilities::detect(
+        info,
+        StdoutColorLevel::TrueColor,
+    ))
+}
+
+#[test]
+fn fit_cells_keeps_small_images_at_natural_size() {
+    assert_eq!(fit_cells(200, 100, /*max_columns*/ 80, CELL), Some((20, 5)));
+}
+
+#[test]
+fn fit_cells_shrinks_wide_images_to_max_columns() {
+    assert_eq!(
+        fit_cells(2000, 1000, /*max_columns*/ 40, CELL),
+        Some((40, 10))
+    );
+}
+
+#[test]
+fn fit_cells_caps_tall_images_and_narrows_them() {
+    assert_eq!(
+        fit_cells(400, 2000, /*max_columns*/ 80, CELL),
+        Some((8, 20))
+    );
+}
+
+#[test]
+fn fit_cells_rejects_empty_images() {
+    assert_eq!(fit_cells(0, 10, /*max_columns*/ 80, CELL), None);
+    assert_eq!(fit_cells(10, 10, /*max_columns*/ 0, CELL), None);
+}
+
+#[test]
+fn local_image_path_resolves_relative_and_file_urls() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_png(dir.path(), "plot.png", 1, 1);
+    let file_url = Url::from_file_path(&path).unwrap().to_string();
+
+    assert_eq!(
+        local_image_path("plot.png", Some(dir.path())),
+        Some(path.clone())
+    );
+    assert_eq!(local_image_path(&file_url, /*cwd*/ None), Some(path));
+    assert_eq!(local_image_path("missing.png", Some(dir.path())), None);
+    assert_eq!(
+        local_image_path("https://example.com/plot.png", Some(dir.path())),
+        None
+    );
+}
+
+#[test]
+fn protocol_detection_prefers_each_terminal_native_protocol() {
+    for (info, expected) in [
+        (
+            terminal(TerminalName::Iterm2, Some("iTerm.app"), /*term*/ None),
+            Some(InlineImageProtocol::Iterm2),
+        ),
+        (
+            terminal(TerminalName::WezTerm, Some("WezTerm"), /*term*/ None),
+            Some(InlineImageProtocol::Iterm2),
+        ),
+        (
+            terminal(TerminalName::Ghostty, Some("ghostty"), /*term*/ None),
+            Some(InlineImageProtocol::Kitty),
+        ),
+        (
+            terminal(
+                TerminalName::Unknown,
+                /*term_program*/ None,
+                Some("xterm-kitty"),
+            ),
+            Some(InlineImageProtocol::Kitty),
+        ),
+        (
+            terminal(
+                TerminalName::Unknown,
+                /*term_program*/ None,
+                Some("foot"),
+            ),
+            Some(InlineImageProtocol::Sixel),
+        ),
+        (
+            terminal(
+                TerminalName::Unknown,
+                /*term_program*/ None,
+                Some("xterm-256color"),
+            ),
+            None,
+        ),
+    ] {
+        assert_eq!(protocol_for_terminal(&info), expected, "{info:?}");
+    }
+}
+
+#[test]
+fn protocol_detection_is_off_inside_multiplexers() {
+    let mut info = terminal(TerminalName::Kitty, Some("kitty"), /*term*/ None);
+    info.multiplexer = Some(Multiplexer::Tmux { version: None });
+
+    assert_eq!(protocol_for_terminal(&info), None);
+}
+
+#[test]
+fn protocol_overrides_fall_back_to_the_next_protocol() {
+    let wezterm = terminal(TerminalName::WezTerm, Some("WezTerm"), /*term*/ None);
+    let capabilities = TerminalCapabilities::detect(&wezterm, StdoutColorLevel::TrueColor)
+        .with_overrides(&TerminalCapabilitiesToml {
+            iterm2_images: Some(false),
+            ..TerminalCapabilitiesToml::default()
+        });
+    assert_eq!(
+        protocol_for_capabilities(capabilities),
+        Some(InlineImageProtocol::Kitty)
+    );
+
+    let unknown = terminal(
+        TerminalName::Unknown,
+        /*term_program*/ None,
+        Some("xterm-256color"),
+    );
+    let capabilities = TerminalCapabilities::detect(&unknown, StdoutColorLevel::TrueColor)
+        .with_overrides(&TerminalCapabilitiesToml {
+            sixel: Some(true),
+            ..TerminalCapabilitiesToml::default()
+        });
+    assert_eq!(
+        protocol_for_capabilities(capabilities),
+        Some(InlineImageProtocol::Sixel)
+    );
+}
+
+#[test]
+fn iterm2_sequence_inlines_file_with_cell_size() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_png(dir.path(), "plot.png", 2, 2);
+    let bytes = fs::read(&path).unwrap();
+
+    let sequence = escape_sequence(&test_image(path, InlineImageProtocol::Iterm2)).unwrap();
+
+    assert_eq!(
+        sequence,
+        format!(
+            "\x1b]1337;File=inline=1;size={};width=4;height=2;preserveAspectRatio=1:{}\x07",
+            bytes.len(),
+            general_purpose::STANDARD.encode(&bytes)
+        )
+    );
+}
+
+#[test]
+fn kitty_sequence_keeps_cursor_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_png(dir.path(), "plot.png", 2, 2);
+
+    let sequence = escape_sequence(&test_image(path, InlineImageProtocol::Kitty)).unwrap();
+
+    assert!(sequence.starts_with("\x1b_Ga=T,f=100,c=4,r=2,C=1,q=2,m=0;"));
+    assert!(sequence.ends_with("\x1b\\"));
+}
+
+#[test]
+fn kitty_transmission_is_chunked() {
+    let command = kitty_transmit_png(&[0; KITTY_CHUNK_SIZE], /*columns*/ 1, /*rows*/ 1);
+
+    assert_eq!(command.matches("\x1b_G").count(), 2);
+    assert!(command.contains(",m=1;"));
+    assert!(command.contains("\x1b_Gm=0;"));
+}
+
+#[test]
+fn sixel_sequence_encodes_decoded_pixels() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_png(dir.path(), "plot.png", 4, 4);
+
+    let sequence = escape_sequence(&test_image(path, InlineImageProtocol::Sixel)).unwrap();
+
+    assert!(sequence.starts_with("\x1bP"));
+    assert!(sequence.ends_with("\x1b\\"));
+}
@@ -289,7 +289,11 @@ fn transcript_overlay_remeasures_visualization_when_artifact_becomes_available()
         Path::new("/workspace"),
         Some(context.clone()),
     );
-    let mut overlay = TranscriptOverlay::new(vec![Arc::new(cell)], RuntimeKeymap::defaults().pager);
+    let mut overlay = TranscriptOverlay::new(
+        vec![Arc::new(cell)],
+        Vec::new(),
+        RuntimeKeymap::defaults().pager,
+    );
     let area = Rect::new(
         /*x*/ 0, /*y*/ 0, /*width*/ 240, /*height*/ 12,
     );
@@ -301,10 +305,13 @@ fn transcript_overlay_remeasures_visualization_when_artifact_becomes_available()
 
     fs::write(context.thread_dir.join("chart.html"), "<div>chart</div>")
         .expect("write visualization fragment");
-    overlay.insert_cell(Arc::new(AgentMarkdownCell::new(
-        "next message".to_string(),
-        Path::new("/workspace"),
-    )));
+    overlay.insert_cell(
+        Arc::new(AgentMarkdownCell::new(
+            "next message".to_string(),
+            Path::new("/workspace"),
+        )),
+        std::time::SystemTime::now(),
+    );
     buffer = Buffer::empty(area);
     overlay.render(area, &mut buffer);
 
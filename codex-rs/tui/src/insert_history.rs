@@ -12,6 +12,7 @@ use crate::terminal_hyperlinks::HyperlinkLine;
 use crate::terminal_hyperlinks::decorate_spans;
 use crate::terminal_hyperlinks::plain_hyperlink_lines;
 use crate::terminal_hyperlinks::remap_wrapped_line;
+use crate::terminal_hyperlinks::window_line;
 use crate::wrapping::RtOptions;
 use crate::wrapping::adaptive_wrap_line;
 use crate::wrapping::line_contains_url_like;
@@ -36,6 +37,7 @@ use ratatui::layout::Size;
 use ratatui::prelude::Backend;
 use ratatui::style::Color;
 use ratatui::style::Modifier;
+use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
 
@@ -134,6 +136,9 @@ where
     for line in lines {
         let line_wrapped = match wrap_policy {
             HistoryLineWrapPolicy::Terminal => vec![line.clone()],
+            HistoryLineWrapPolicy::PreWrap if line.scrollable => {
+                vec![clip_table_row(line, wrap_width)]
+            }
             HistoryLineWrapPolicy::PreWrap
                 if line_contains_url_like(&line.line)
                     && !line_has_mixed_url_and_non_url_tokens(&line.line) =>
@@ -239,6 +244,9 @@ where
                 queue!(writer, Print("\r\n"))?;
                 write_history_line(writer, line, wrap_width)?;
             }
+            if area.top() > 0 {
+                write_inline_images(writer, &wrapped, wrap_width, area.top() - 1)?;
+            }
 
             queue!(writer, ResetScrollRegion)?;
             queue!(writer, MoveTo(last_cursor_pos.x, last_cursor_pos.y))?;
@@ -255,6 +263,21 @@ where
     Ok(())
 }
 
+/// Cuts a full-width table row to the viewport and marks the cut with a dim `›`; the transcript
+/// overlay still shows the whole row.
+fn clip_table_row(line: &HyperlinkLine, wrap_width: usize) -> HyperlinkLine {
+    if line.width() <= wrap_width {
+        return line.clone();
+    }
+    let mut clipped = window_line(
+        line.clone(),
+        /*column*/ 0,
+        wrap_width.saturating_sub(1),
+    );
+    clipped.line.push_span(Span::from("›").dim());
+    clipped
+}
+
 pub(crate) fn leading_whitespace_prefix(line: &Line<'_>) -> Line<'static> {
     let mut spans = Vec::new();
     for span in &line.spans {
@@ -323,11 +346,46 @@ fn write_history_line<W: Write>(
     let merged_line = HyperlinkLine {
         line: Line::from(merged_spans),
         hyperlinks: line.hyperlinks.clone(),
+        image: None,
+        scrollable: false,
+        heading_level: None,
+        hidden_code: None,
+        code_block: None,
     };
     let decorated = decorate_spans(&merged_line);
     write_spans(writer, decorated.iter())
 }
 
+/// Draw the images carried by `lines`, which were just written so that the last one ends on
+/// `bottom_row`. An image covers the rows of its line and the blank rows reserved above it, so
+/// images whose top has already scrolled off the screen are skipped rather than drawn clipped.
+fn write_inline_images<W: Write>(
+    writer: &mut W,
+    lines: &[HyperlinkLine],
+    wrap_width: usize,
+    bottom_row: u16,
+) -> io::Result<()> {
+    let mut rows_below = 0u16;
+    for line in lines.iter().rev() {
+        let line_bottom = bottom_row.checked_sub(rows_below);
+        rows_below = rows_below.saturating_add(line.width().max(1).div_ceil(wrap_width) as u16);
+        let (Some(image), Some(line_bottom)) = (&line.image, line_bottom) else {
+            continue;
+        };
+        let Some(top) = line_bottom.checked_sub(image.rows.saturating_sub(1)) else {
+            continue;
+        };
+        match crate::inline_images::escape_sequence(image) {
+            Ok(sequence) => {
+                let column = u16::try_from(image.column).unwrap_or(u16::MAX);
+                queue!(writer, MoveTo(column, top), Print(sequence))?;
+            }
+            Err(err) => tracing::debug!("skipping inline image {}: {err:#}", image.path.display()),
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SetScrollRegion(pub std::ops::Range<u16>);
 
@@ -483,6 +541,7 @@ mod tests {
     use crate::test_backend::VT100Backend;
     use ratatui::layout::Rect;
     use ratatui::style::Color;
+    use std::path::PathBuf;
 
     #[test]
     fn writes_bold_then_regular_spans() {
@@ -512,6 +571,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inline_image_is_drawn_from_the_top_of_its_reserved_rows() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("plot.png");
+        image::RgbaImage::from_pixel(1, 1, image::Rgba([0, 0, 0, 255]))
+            .save(&path)
+            .expect("write png");
+        let mut image_line = HyperlinkLine::new(Line::from("  "));
+        image_line.image = Some(crate::inline_images::test_inline_image(
+            path, /*column*/ 2, /*rows*/ 3,
+        ));
+        let lines = vec![
+            HyperlinkLine::new(Line::from("  ")),
+            HyperlinkLine::new(Line::from("  ")),
+            image_line,
+            HyperlinkLine::new(Line::from("after")),
+        ];
+        let mut actual = Vec::new();
+
+        write_inline_images(
+            &mut actual,
+            &lines,
+            /*wrap_width*/ 80,
+            /*bottom_row*/ 10,
+        )
+        .expect("write images");
+
+        // The image line ends one row above the bottom, so its three rows start at row 7.
+        let output = String::from_utf8(actual).expect("UTF-8 terminal output");
+        assert!(output.starts_with("\x1b[8;3H\x1b]1337;File=inline=1;"));
+    }
+
+    #[test]
+    fn inline_image_whose_top_scrolled_away_is_skipped() {
+        let mut image_line = HyperlinkLine::new(Line::default());
+        image_line.image = Some(crate::inline_images::test_inline_image(
+            PathBuf::from("missing.png"),
+            /*column*/ 0,
+            /*rows*/ 5,
+        ));
+        let mut actual = Vec::new();
+
+        write_inline_images(
+            &mut actual,
+            &[image_line],
+            /*wrap_width*/ 80,
+            /*bottom_row*/ 2,
+        )
+        .expect("write images");
+
+        assert!(actual.is_empty());
+    }
+
     #[test]
     fn writes_semantic_web_link_without_changing_visible_text() {
         let destination = "https://example.com/long/path";
@@ -30,6 +30,12 @@ const ALT_PREFIX: &str = "⌥ + ";
 const ALT_PREFIX: &str = "alt + ";
 const CTRL_PREFIX: &str = "ctrl + ";
 const SHIFT_PREFIX: &str = "shift + ";
+#[cfg(test)]
+const SUPER_PREFIX: &str = "⌘ + ";
+#[cfg(all(not(test), target_os = "macos"))]
+const SUPER_PREFIX: &str = "⌘ + ";
+#[cfg(all(not(test), not(target_os = "macos")))]
+const SUPER_PREFIX: &str = "super + ";
 
 /// One concrete key event that can trigger a TUI action.
 ///
@@ -66,6 +72,20 @@ impl KeyBinding {
         (self.key, self.modifiers)
     }
 
+    /// Whether only terminals speaking the kitty keyboard protocol can report this binding.
+    ///
+    /// Legacy encodings never carry Super, send Ctrl/Shift+Enter as a plain carriage return, and
+    /// have no Ctrl variant of Tab, Backspace, or Esc, so such a binding silently never fires.
+    pub(crate) fn needs_enhanced_keys(&self) -> bool {
+        self.modifiers.contains(KeyModifiers::SUPER)
+            || (self.key == KeyCode::Enter
+                && self
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::SHIFT))
+            || (matches!(self.key, KeyCode::Tab | KeyCode::Backspace | KeyCode::Esc)
+                && self.modifiers.contains(KeyModifiers::CONTROL))
+    }
+
     pub(crate) fn display_label(&self) -> String {
         let modifiers = modifiers_to_string(self.modifiers);
         let key = match self.key {
@@ -146,6 +166,7 @@ pub(crate) fn is_plain_text_key_event(event: KeyEvent) -> bool {
         } if !ch.is_ascii_control()
             && !modifiers.contains(KeyModifiers::CONTROL)
             && !modifiers.contains(KeyModifiers::ALT)
+            && !modifiers.contains(KeyModifiers::SUPER)
     )
 }
 
@@ -180,6 +201,9 @@ fn modifiers_to_string(modifiers: KeyModifiers) -> String {
     if modifiers.contains(KeyModifiers::ALT) {
         result.push_str(ALT_PREFIX);
     }
+    if modifiers.contains(KeyModifiers::SUPER) {
+        result.push_str(SUPER_PREFIX);
+    }
     result
 }
 
@@ -356,6 +380,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn super_bindings_render_and_need_enhanced_keys() {
+        let binding = KeyBinding::new(KeyCode::Char('k'), KeyModifiers::SUPER);
+
+        assert_eq!(binding.display_label(), "⌘ + k");
+        assert!(binding.needs_enhanced_keys());
+        assert!(!is_plain_text_key_event(KeyEvent::new(
+            KeyCode::Char('k'),
+            KeyModifiers::SUPER
+        )));
+    }
+
+    #[test]
+    fn only_chords_legacy_terminals_cannot_encode_need_enhanced_keys() {
+        for binding in [
+            ctrl(KeyCode::Enter),
+            shift(KeyCode::Enter),
+            ctrl(KeyCode::Tab),
+            ctrl(KeyCode::Backspace),
+        ] {
+            assert!(binding.needs_enhanced_keys(), "{binding:?}");
+        }
+        for binding in [
+            plain(KeyCode::Enter),
+            alt(KeyCode::Enter),
+            ctrl(KeyCode::Char('j')),
+            shift(KeyCode::Tab),
+        ] {
+            assert!(!binding.needs_enhanced_keys(), "{binding:?}");
+        }
+    }
+
     #[test]
     fn ctrl_alt_sets_both_modifiers() {
         assert_eq!(
@@ -70,6 +70,10 @@ pub(crate) struct AppKeymap {
     pub(crate) toggle_fast_mode: Vec<KeyBinding>,
     /// Toggle raw scrollback mode for copy-friendly transcript selection.
     pub(crate) toggle_raw_output: Vec<KeyBinding>,
+    /// Expand or collapse the latest turn's reasoning summaries.
+    pub(crate) toggle_reasoning: Vec<KeyBinding>,
+    /// Cycle the transcript density between compact, normal, and verbose tool output.
+    pub(crate) cycle_transcript_density: Vec<KeyBinding>,
 }
 
 /// Chat-level keybindings evaluated at the app event layer.
@@ -222,6 +226,7 @@ pub(crate) struct PagerKeymap {
     pub(crate) jump_bottom: Vec<KeyBinding>,
     pub(crate) close: Vec<KeyBinding>,
     pub(crate) close_transcript: Vec<KeyBinding>,
+    pub(crate) toggle_timestamps: Vec<KeyBinding>,
 }
 
 /// Generic list picker keybindings shared across popup list views.
@@ -255,6 +260,7 @@ pub(crate) struct ListKeymap {
 pub(crate) struct ApprovalKeymap {
     pub(crate) open_fullscreen: Vec<KeyBinding>,
     pub(crate) open_thread: Vec<KeyBinding>,
+    pub(crate) explain: Vec<KeyBinding>,
     pub(crate) approve: Vec<KeyBinding>,
     pub(crate) approve_for_session: Vec<KeyBinding>,
     pub(crate) approve_for_prefix: Vec<KeyBinding>,
@@ -422,6 +428,16 @@ impl RuntimeKeymap {
                 &defaults.app.toggle_raw_output,
                 "tui.keymap.global.toggle_raw_output",
             )?,
+            toggle_reasoning: resolve_bindings(
+                keymap.global.toggle_reasoning.as_ref(),
+                &defaults.app.toggle_reasoning,
+                "tui.keymap.global.toggle_reasoning",
+            )?,
+            cycle_transcript_density: resolve_bindings(
+                keymap.global.cycle_transcript_density.as_ref(),
+                &defaults.app.cycle_transcript_density,
+                "tui.keymap.global.cycle_transcript_density",
+            )?,
         };
 
         let mut chat = ChatKeymap {
@@ -766,11 +782,13 @@ impl RuntimeKeymap {
             jump_bottom: resolve_local!(keymap, defaults, pager, jump_bottom),
             close: resolve_local!(keymap, defaults, pager, close),
             close_transcript: resolve_local!(keymap, defaults, pager, close_transcript),
+            toggle_timestamps: resolve_local!(keymap, defaults, pager, toggle_timestamps),
         };
 
         let approval = ApprovalKeymap {
             open_fullscreen: resolve_local!(keymap, defaults, approval, open_fullscreen),
             open_thread: resolve_local!(keymap, defaults, approval, open_thread),
+            explain: resolve_local!(keymap, defaults, approval, explain),
             approve: resolve_local!(keymap, defaults, approval, approve),
             approve_for_session: resolve_local!(keymap, defaults, approval, approve_for_session),
             approve_for_prefix: resolve_local!(keymap, defaults, approval, approve_for_prefix),
@@ -809,6 +827,14 @@ impl RuntimeKeymap {
                 keymap.global.toggle_raw_output.as_ref(),
                 app.toggle_raw_output.as_slice(),
             ),
+            (
+                keymap.global.toggle_reasoning.as_ref(),
+                app.toggle_reasoning.as_slice(),
+            ),
+            (
+                keymap.global.cycle_transcript_density.as_ref(),
+                app.cycle_transcript_density.as_slice(),
+            ),
             (keymap.list.move_up.as_ref(), list_move_up.as_slice()),
             (keymap.list.move_down.as_ref(), list_move_down.as_slice()),
             (keymap.list.accept.as_ref(), list_accept.as_slice()),
@@ -821,6 +847,10 @@ impl RuntimeKeymap {
                 keymap.approval.open_thread.as_ref(),
                 approval.open_thread.as_slice(),
             ),
+            (
+                keymap.approval.explain.as_ref(),
+                approval.explain.as_slice(),
+            ),
             (
                 keymap.approval.approve.as_ref(),
                 approval.approve.as_slice(),
@@ -916,6 +946,8 @@ impl RuntimeKeymap {
                 toggle_vim_mode: default_bindings![],
                 toggle_fast_mode: default_bindings![],
                 toggle_raw_output: default_bindings![alt(KeyCode::Char('r'))],
+                toggle_reasoning: default_bindings![alt(KeyCode::Char('t'))],
+                cycle_transcript_density: default_bindings![alt(KeyCode::Char('o'))],
             },
             chat: ChatKeymap {
                 interrupt_turn: default_bindings![plain(KeyCode::Esc)],
@@ -1110,6 +1142,7 @@ impl RuntimeKeymap {
                 jump_bottom: default_bindings![plain(KeyCode::End)],
                 close: default_bindings![plain(KeyCode::Char('q')), ctrl(KeyCode::Char('c'))],
                 close_transcript: default_bindings![ctrl(KeyCode::Char('t'))],
+                toggle_timestamps: default_bindings![plain(KeyCode::Char('t'))],
             },
             list: ListKeymap {
                 move_up: default_bindings![
@@ -1142,6 +1175,7 @@ impl RuntimeKeymap {
                     ))
                 ],
                 open_thread: default_bindings![plain(KeyCode::Char('o'))],
+                explain: default_bindings![plain(KeyCode::Char('e'))],
                 approve: default_bindings![plain(KeyCode::Char('y'))],
                 approve_for_session: default_bindings![plain(KeyCode::Char('a'))],
                 approve_for_prefix: default_bindings![plain(KeyCode::Char('p'))],
@@ -1175,6 +1209,11 @@ impl RuntimeKeymap {
                 ("toggle_vim_mode", self.app.toggle_vim_mode.as_slice()),
                 ("toggle_fast_mode", self.app.toggle_fast_mode.as_slice()),
                 ("toggle_raw_output", self.app.toggle_raw_output.as_slice()),
+                ("toggle_reasoning", self.app.toggle_reasoning.as_slice()),
+                (
+                    "cycle_transcript_density",
+                    self.app.cycle_transcript_density.as_slice(),
+                ),
                 ("chat.interrupt_turn", self.chat.interrupt_turn.as_slice()),
                 (
                     "chat.decrease_reasoning_effort",
@@ -1218,6 +1257,11 @@ impl RuntimeKeymap {
                 ("toggle_vim_mode", self.app.toggle_vim_mode.as_slice()),
                 ("toggle_fast_mode", self.app.toggle_fast_mode.as_slice()),
                 ("toggle_raw_output", self.app.toggle_raw_output.as_slice()),
+                ("toggle_reasoning", self.app.toggle_reasoning.as_slice()),
+                (
+                    "cycle_transcript_density",
+                    self.app.cycle_transcript_density.as_slice(),
+                ),
                 ("chat.interrupt_turn", self.chat.interrupt_turn.as_slice()),
                 (
                     "chat.decrease_reasoning_effort",
@@ -1267,6 +1311,11 @@ impl RuntimeKeymap {
                 ("toggle_vim_mode", self.app.toggle_vim_mode.as_slice()),
                 ("toggle_fast_mode", self.app.toggle_fast_mode.as_slice()),
                 ("toggle_raw_output", self.app.toggle_raw_output.as_slice()),
+                ("toggle_reasoning", self.app.toggle_reasoning.as_slice()),
+                (
+                    "cycle_transcript_density",
+                    self.app.cycle_transcript_density.as_slice(),
+                ),
             ],
             [
                 ("list.move_up", self.list.move_up.as_slice()),
@@ -1284,6 +1333,7 @@ impl RuntimeKeymap {
                     self.approval.open_fullscreen.as_slice(),
                 ),
                 ("approval.open_thread", self.approval.open_thread.as_slice()),
+                ("approval.explain", self.approval.explain.as_slice()),
                 ("approval.approve", self.approval.approve.as_slice()),
                 (
                     "approval.approve_for_session",
@@ -1341,6 +1391,11 @@ impl RuntimeKeymap {
                 ("toggle_vim_mode", self.app.toggle_vim_mode.as_slice()),
                 ("toggle_fast_mode", self.app.toggle_fast_mode.as_slice()),
                 ("toggle_raw_output", self.app.toggle_raw_output.as_slice()),
+                ("toggle_reasoning", self.app.toggle_reasoning.as_slice()),
+                (
+                    "cycle_transcript_density",
+                    self.app.cycle_transcript_density.as_slice(),
+                ),
                 (
                     "composer.history_search_previous",
                     self.composer.history_search_previous.as_slice(),
@@ -1576,6 +1631,7 @@ impl RuntimeKeymap {
                 ("jump_bottom", self.pager.jump_bottom.as_slice()),
                 ("close", self.pager.close.as_slice()),
                 ("close_transcript", self.pager.close_transcript.as_slice()),
+                ("toggle_timestamps", self.pager.toggle_timestamps.as_slice()),
             ],
         )?;
 
@@ -1592,8 +1648,9 @@ impl RuntimeKeymap {
                 ("jump_bottom", self.pager.jump_bottom.as_slice()),
                 ("close", self.pager.close.as_slice()),
                 ("close_transcript", self.pager.close_transcript.as_slice()),
+                ("toggle_timestamps", self.pager.toggle_timestamps.as_slice()),
             ],
-            TRANSCRIPT_BACKTRACK_RESERVED_BINDINGS,
+            TRANSCRIPT_RESERVED_BINDINGS,
             [],
         )?;
 
@@ -1618,6 +1675,7 @@ impl RuntimeKeymap {
             [
                 ("open_fullscreen", self.approval.open_fullscreen.as_slice()),
                 ("open_thread", self.approval.open_thread.as_slice()),
+                ("explain", self.approval.explain.as_slice()),
                 ("approve", self.approval.approve.as_slice()),
                 (
                     "approve_for_session",
@@ -1650,6 +1708,7 @@ impl RuntimeKeymap {
                 self.approval.open_fullscreen.as_slice(),
             ),
             ("approval.open_thread", self.approval.open_thread.as_slice()),
+            ("approval.explain", self.approval.explain.as_slice()),
             ("approval.approve", self.approval.approve.as_slice()),
             (
                 "approval.approve_for_session",
@@ -1805,7 +1864,7 @@ const MAIN_RESERVED_BINDINGS: &[(&str, KeyBinding)] = &[
     ),
 ];
 
-const TRANSCRIPT_BACKTRACK_RESERVED_BINDINGS: &[(&str, KeyBinding)] = &[
+const TRANSCRIPT_RESERVED_BINDINGS: &[(&str, KeyBinding)] = &[
     (
         "fixed.transcript_edit_previous",
         key_hint::plain(KeyCode::Esc),
@@ -1822,6 +1881,14 @@ const TRANSCRIPT_BACKTRACK_RESERVED_BINDINGS: &[(&str, KeyBinding)] = &[
         "fixed.transcript_confirm_edit",
         key_hint::plain(KeyCode::Enter),
     ),
+    (
+        "fixed.transcript_next_file_reference",
+        key_hint::plain(KeyCode::Tab),
+    ),
+    (
+        "fixed.transcript_previous_file_reference",
+        key_hint::shift(KeyCode::Tab),
+    ),
 ];
 
 /// Resolve one action with context -> global -> default precedence.
@@ -1925,6 +1992,50 @@ fn keymap_value_contains_alias(value: &serde_json::Value, alias: &str) -> bool {
     }
 }
 
+/// Warn about configured chords that only reach the app through the kitty keyboard protocol.
+///
+/// Legacy terminals report `shift-enter` as plain Enter and drop `super` entirely, so these
+/// bindings silently never fire there. Returns `None` when every configured binding is usable.
+pub(crate) fn enhanced_keys_warning(keymap: &TuiKeymap) -> Option<String> {
+    let Ok(value) = serde_json::to_value(keymap) else {
+        return None;
+    };
+    let mut specs = Vec::new();
+    collect_keymap_specs(&value, &mut specs);
+    specs
+        .retain(|spec| parse_keybinding(spec).is_some_and(|binding| binding.needs_enhanced_keys()));
+    specs.sort_unstable();
+    specs.dedup();
+    if specs.is_empty() {
+        return None;
+    }
+    let specs = specs
+        .iter()
+        .map(|spec| format!("`{spec}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "This terminal does not support the kitty keyboard protocol, so `tui.keymap` bindings {specs} will not work. Rebind them, or set `tui.terminal_capabilities.kitty_keyboard = true` if the terminal does support it."
+    ))
+}
+
+fn collect_keymap_specs<'a>(value: &'a serde_json::Value, specs: &mut Vec<&'a str>) {
+    match value {
+        serde_json::Value::String(value) => specs.push(value),
+        serde_json::Value::Array(values) => {
+            for value in values {
+                collect_keymap_specs(value, specs);
+            }
+        }
+        serde_json::Value::Object(values) => {
+            for value in values.values() {
+                collect_keymap_specs(value, specs);
+            }
+        }
+        serde_json::Value::Bool(_) | serde_json::Value::Number(_) | serde_json::Value::Null => {}
+    }
+}
+
 fn resolve_new_default_bindings(
     configured: Option<&KeybindingsSpec>,
     fallback: &[KeyBinding],
@@ -1977,6 +2088,7 @@ fn parse_keybinding(spec: &str) -> Option<KeyBinding> {
             "ctrl" => modifiers |= KeyModifiers::CONTROL,
             "alt" => modifiers |= KeyModifiers::ALT,
             "shift" => modifiers |= KeyModifiers::SHIFT,
+            "super" => modifiers |= KeyModifiers::SUPER,
             other => {
                 key_name = Some(other.to_string());
                 break;
@@ -2046,6 +2158,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parses_super_binding() {
+        let binding = parse_keybinding("shift-super-enter").expect("binding should parse");
+        assert_eq!(
+            binding.parts(),
+            (KeyCode::Enter, KeyModifiers::SHIFT | KeyModifiers::SUPER)
+        );
+    }
+
+    #[test]
+    fn enhanced_keys_warning_lists_only_kitty_only_chords() {
+        assert_eq!(enhanced_keys_warning(&TuiKeymap::default()), None);
+
+        let mut keymap = TuiKeymap::default();
+        keymap.composer.submit = Some(one("ctrl-enter"));
+        keymap.global.open_transcript = Some(one("super-t"));
+        keymap.chat.edit_queued_message = Some(one("ctrl-e"));
+
+        let warning = enhanced_keys_warning(&keymap).expect("expected warning");
+        assert!(warning.contains("`ctrl-enter`, `super-t`"));
+        assert!(!warning.contains("ctrl-e`"));
+    }
+
     #[test]
     fn rejects_shadowing_composer_binding_in_app_scope() {
         let mut keymap = TuiKeymap::default();
@@ -717,10 +717,11 @@ fn key_parts_to_config_key_spec(
     let (code, normalized_modifiers) = crate::key_hint::normalize_key_parts(code, modifiers);
     modifiers = normalized_modifiers;
 
-    let supported_modifiers = KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT;
+    let supported_modifiers =
+        KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT | KeyModifiers::SUPER;
     if !modifiers.difference(supported_modifiers).is_empty() {
         return Err(
-            "Only ctrl, alt, and shift modifiers can be stored in `tui.keymap`.".to_string(),
+            "Only ctrl, alt, shift, and super modifiers can be stored in `tui.keymap`.".to_string(),
         );
     }
 
@@ -777,6 +778,9 @@ fn format_key_spec(modifiers: KeyModifiers, key: &str) -> String {
     if modifiers.contains(KeyModifiers::SHIFT) {
         parts.push("shift");
     }
+    if modifiers.contains(KeyModifiers::SUPER) {
+        parts.push("super");
+    }
     parts.push(key);
     parts.join("-")
 }
@@ -1057,6 +1061,7 @@ mod tests {
             vec![
                 "Approval.open_fullscreen",
                 "Approval.open_thread",
+                "Approval.explain",
                 "Approval.approve",
                 "Approval.approve_for_session",
                 "Approval.approve_for_prefix",
@@ -1929,4 +1934,19 @@ mod tests {
 
         assert!(err.contains("composer.nope"));
     }
+
+    #[test]
+    fn captured_super_chords_are_stored_as_super() {
+        assert_eq!(
+            key_event_to_config_key_spec(KeyEvent::new(
+                KeyCode::Enter,
+                KeyModifiers::SHIFT | KeyModifiers::SUPER
+            )),
+            Ok("shift-super-enter".to_string())
+        );
+        assert!(
+            key_event_to_config_key_spec(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::HYPER))
+                .is_err()
+        );
+    }
 }
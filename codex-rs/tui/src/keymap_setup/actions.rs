@@ -93,6 +93,8 @@ pub(super) const KEYMAP_ACTIONS: &[KeymapActionDescriptor] = &[
     action("global", "Global", "toggle_vim_mode", "Turn Vim composer mode on or off."),
     gated_action("global", "Global", "toggle_fast_mode", "Turn Fast mode on or off.", KeymapActionFeature::FastMode),
     action("global", "Global", "toggle_raw_output", "Toggle raw scrollback mode."),
+    action("global", "Global", "toggle_reasoning", "Expand or collapse reasoning summaries."),
+    action("global", "Global", "cycle_transcript_density", "Cycle compact, normal, and verbose tool output."),
     action("chat", "Chat", "interrupt_turn", "Interrupt the active turn."),
     action("chat", "Chat", "decrease_reasoning_effort", "Decrease reasoning effort."),
     action("chat", "Chat", "increase_reasoning_effort", "Increase reasoning effort."),
@@ -177,6 +179,7 @@ pub(super) const KEYMAP_ACTIONS: &[KeymapActionDescriptor] = &[
     action("pager", "Pager", "jump_bottom", "Jump to the end."),
     action("pager", "Pager", "close", "Close the pager overlay."),
     action("pager", "Pager", "close_transcript", "Close the transcript overlay."),
+    action("pager", "Pager", "toggle_timestamps", "Cycle the transcript time gutter."),
     action("list", "List", "move_up", "Move list selection up."),
     action("list", "List", "move_down", "Move list selection down."),
     action("list", "List", "move_left", "Move horizontally left in list pickers."),
@@ -189,6 +192,7 @@ pub(super) const KEYMAP_ACTIONS: &[KeymapActionDescriptor] = &[
     action("list", "List", "cancel", "Cancel and close selection views."),
     action("approval", "Approval", "open_fullscreen", "Open approval details fullscreen."),
     action("approval", "Approval", "open_thread", "Open the approval source thread when available."),
+    action("approval", "Approval", "explain", "Explain the pending command in exec approvals."),
     action("approval", "Approval", "approve", "Approve the primary option."),
     action("approval", "Approval", "approve_for_session", "Approve for the session when available."),
     action("approval", "Approval", "approve_for_prefix", "Approve with an exec-policy prefix when available."),
@@ -236,6 +240,8 @@ pub(super) fn binding_slot<'a>(
         ("global", "toggle_vim_mode") => Some(&mut keymap.global.toggle_vim_mode),
         ("global", "toggle_fast_mode") => Some(&mut keymap.global.toggle_fast_mode),
         ("global", "toggle_raw_output") => Some(&mut keymap.global.toggle_raw_output),
+        ("global", "toggle_reasoning") => Some(&mut keymap.global.toggle_reasoning),
+        ("global", "cycle_transcript_density") => Some(&mut keymap.global.cycle_transcript_density),
         ("chat", "interrupt_turn") => Some(&mut keymap.chat.interrupt_turn),
         ("chat", "decrease_reasoning_effort") => Some(&mut keymap.chat.decrease_reasoning_effort),
         ("chat", "increase_reasoning_effort") => Some(&mut keymap.chat.increase_reasoning_effort),
@@ -320,6 +326,7 @@ pub(super) fn binding_slot<'a>(
         ("pager", "jump_bottom") => Some(&mut keymap.pager.jump_bottom),
         ("pager", "close") => Some(&mut keymap.pager.close),
         ("pager", "close_transcript") => Some(&mut keymap.pager.close_transcript),
+        ("pager", "toggle_timestamps") => Some(&mut keymap.pager.toggle_timestamps),
         ("list", "move_up") => Some(&mut keymap.list.move_up),
         ("list", "move_down") => Some(&mut keymap.list.move_down),
         ("list", "move_left") => Some(&mut keymap.list.move_left),
@@ -332,6 +339,7 @@ pub(super) fn binding_slot<'a>(
         ("list", "cancel") => Some(&mut keymap.list.cancel),
         ("approval", "open_fullscreen") => Some(&mut keymap.approval.open_fullscreen),
         ("approval", "open_thread") => Some(&mut keymap.approval.open_thread),
+        ("approval", "explain") => Some(&mut keymap.approval.explain),
         ("approval", "approve") => Some(&mut keymap.approval.approve),
         ("approval", "approve_for_session") => Some(&mut keymap.approval.approve_for_session),
         ("approval", "approve_for_prefix") => Some(&mut keymap.approval.approve_for_prefix),
@@ -361,6 +369,8 @@ pub(super) fn bindings_for_action<'a>(
         ("global", "toggle_vim_mode") => Some(runtime_keymap.app.toggle_vim_mode.as_slice()),
         ("global", "toggle_fast_mode") => Some(runtime_keymap.app.toggle_fast_mode.as_slice()),
         ("global", "toggle_raw_output") => Some(runtime_keymap.app.toggle_raw_output.as_slice()),
+        ("global", "toggle_reasoning") => Some(runtime_keymap.app.toggle_reasoning.as_slice()),
+        ("global", "cycle_transcript_density") => Some(runtime_keymap.app.cycle_transcript_density.as_slice()),
         ("chat", "interrupt_turn") => Some(runtime_keymap.chat.interrupt_turn.as_slice()),
         ("chat", "decrease_reasoning_effort") => Some(runtime_keymap.chat.decrease_reasoning_effort.as_slice()),
         ("chat", "increase_reasoning_effort") => Some(runtime_keymap.chat.increase_reasoning_effort.as_slice()),
@@ -445,6 +455,7 @@ pub(super) fn bindings_for_action<'a>(
         ("pager", "jump_bottom") => Some(runtime_keymap.pager.jump_bottom.as_slice()),
         ("pager", "close") => Some(runtime_keymap.pager.close.as_slice()),
         ("pager", "close_transcript") => Some(runtime_keymap.pager.close_transcript.as_slice()),
+        ("pager", "toggle_timestamps") => Some(runtime_keymap.pager.toggle_timestamps.as_slice()),
         ("list", "move_up") => Some(runtime_keymap.list.move_up.as_slice()),
         ("list", "move_down") => Some(runtime_keymap.list.move_down.as_slice()),
         ("list", "move_left") => Some(runtime_keymap.list.move_left.as_slice()),
@@ -457,6 +468,7 @@ pub(super) fn bindings_for_action<'a>(
         ("list", "cancel") => Some(runtime_keymap.list.cancel.as_slice()),
         ("approval", "open_fullscreen") => Some(runtime_keymap.approval.open_fullscreen.as_slice()),
         ("approval", "open_thread") => Some(runtime_keymap.approval.open_thread.as_slice()),
+        ("approval", "explain") => Some(runtime_keymap.approval.explain.as_slice()),
         ("approval", "approve") => Some(runtime_keymap.approval.approve.as_slice()),
         ("approval", "approve_for_session") => Some(runtime_keymap.approval.approve_for_session.as_slice()),
         ("approval", "approve_for_prefix") => Some(runtime_keymap.approval.approve_for_prefix.as_slice()),
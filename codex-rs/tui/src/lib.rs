@@ -46,6 +46,7 @@ use codex_config::format_config_error_with_source;
 use codex_config::types::ResumeCwdMode;
 use codex_exec_server::EnvironmentManager;
 use codex_exec_server::ExecServerRuntimePaths;
+use codex_features::Feature;
 use codex_login::AuthConfig;
 use codex_login::default_client::originator;
 use codex_login::default_client::set_default_client_residency_requirement;
@@ -100,11 +101,13 @@ mod ascii_animation;
 mod bottom_pane;
 mod branch_summary;
 mod chatwidget;
+mod citations;
 mod cli;
 mod clipboard_copy;
 mod clipboard_paste;
 mod collaboration_modes;
 mod color;
+mod composer_draft;
 mod config_update;
 pub(crate) mod custom_terminal;
 mod pets;
@@ -114,6 +117,8 @@ mod cwd_prompt;
 mod debug_config;
 mod diff_model;
 mod diff_render;
+mod diff_stat;
+mod display_width;
 mod exec_cell;
 mod exec_command;
 mod external_agent_config_migration;
@@ -121,15 +126,23 @@ mod external_agent_config_migration_flow;
 mod external_agent_config_migration_model;
 mod external_agent_config_migration_source;
 mod external_editor;
+mod file_references;
 mod file_search;
 mod frames;
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub mod fuzzing;
 mod get_git_diff;
 mod git_action_directives;
 mod goal_display;
 mod goal_files;
+pub mod highlight;
 mod history_cell;
 mod hooks_rpc;
+mod i18n;
 mod ide_context;
+mod idle_pause;
+mod inline_images;
 mod inline_visualization;
 pub(crate) mod insert_history;
 pub use insert_history::insert_history_lines;
@@ -140,6 +153,7 @@ mod line_truncation;
 pub(crate) mod live_wrap;
 pub use live_wrap::RowBuilder;
 mod local_chatgpt_auth;
+mod log_tail;
 mod managed_new_thread_defaults;
 mod markdown;
 mod markdown_render;
@@ -161,6 +175,8 @@ pub(crate) mod public_widgets;
 mod render;
 mod resize_reflow_cap;
 mod resume_picker;
+mod review_findings;
+mod safe_mode;
 mod selection_list;
 mod service_tier_resolution;
 mod session_archive_commands;
@@ -176,6 +192,7 @@ mod status;
 mod status_indicator_widget;
 mod streaming;
 mod style;
+mod terminal_capabilities;
 mod terminal_hyperlinks;
 mod terminal_palette;
 mod terminal_probe;
@@ -184,9 +201,12 @@ mod terminal_visualization_instructions;
 mod text_formatting;
 mod theme_picker;
 mod thread_transcript;
+mod tmux;
 mod token_usage;
 mod tooltips;
+mod transcript_density;
 mod transcript_reflow;
+mod transcript_timestamps;
 mod tui;
 mod ui_consts;
 pub(crate) mod update_action;
@@ -203,6 +223,7 @@ mod version;
 mod width;
 #[cfg(any(target_os = "windows", test))]
 mod windows_sandbox;
+mod workflow_templates;
 mod workspace_command;
 mod workspace_messages;
 
@@ -228,6 +249,10 @@ pub use public_widgets::composer_input::ComposerInput;
 // (tests access modules directly within the crate)
 
 const TUI_LOG_FILE_NAME: &str = "codex-tui.log";
+const TUI_JSON_LOG_FILE_NAME: &str = "codex-tui.jsonl";
+
+type TuiFileLogLayer =
+    Box<dyn tracing_subscriber::Layer<tracing_subscriber::Registry> + Send + Sync>;
 
 #[cfg(unix)]
 const AUTO_CONNECT_DAEMON_CONNECT_TIMEOUT: std::time::Duration =
@@ -908,7 +933,7 @@ fn can_reuse_implicit_local_daemon(
 pub async fn run_main(
     mut cli: Cli,
     arg0_paths: Arg0DispatchPaths,
-    loader_overrides: LoaderOverrides,
+    mut loader_overrides: LoaderOverrides,
     explicit_remote_endpoint: Option<RemoteAppServerEndpoint>,
 ) -> std::io::Result<AppExitInfo> {
     let strict_config = cli.strict_config;
@@ -930,13 +955,20 @@ pub async fn run_main(
             .raw_overrides
             .push("web_search=\"live\"".to_string());
     }
+    if cli.safe_mode {
+        cli.config_overrides.raw_overrides.extend(
+            crate::safe_mode::SAFE_MODE_RAW_OVERRIDES
+                .iter()
+                .map(ToString::to_string),
+        );
+    }
 
     // When using `--oss`, let the bootstrapper pick the model (defaulting to
     // gpt-oss:20b) and ensure it is present locally. Also, force the built‑in
     let raw_overrides = cli.config_overrides.raw_overrides.clone();
     // `oss` model provider.
     let overrides_cli = codex_utils_cli::CliConfigOverrides { raw_overrides };
-    let cli_kv_overrides = match overrides_cli.parse_overrides() {
+    let mut cli_kv_overrides = match overrides_cli.parse_overrides() {
         // Parse `-c` overrides from the CLI.
         Ok(v) => v,
         #[allow(clippy::print_stderr)]
@@ -962,6 +994,21 @@ pub async fn run_main(
         launch_loader_overrides.user_config_path = Some(user_config_path);
         launch_loader_overrides.user_config_profile = Some(profile_v2.clone());
     }
+    // Safe mode is how users recover from a broken config, so a user config that fails to load
+    // is ignored for the session instead of stopping startup.
+    if cli.safe_mode
+        && let Some(error) = crate::safe_mode::user_config_load_error(
+            &codex_home,
+            &cli_kv_overrides,
+            &launch_loader_overrides,
+            strict_config,
+        )
+        .await
+    {
+        loader_overrides.ignore_user_config = true;
+        launch_loader_overrides.ignore_user_config = true;
+        cli.safe_mode_config_error = Some(error);
+    }
     let reuse_implicit_local_daemon = can_reuse_implicit_local_daemon(
         &cli_kv_overrides,
         &launch_loader_overrides,
@@ -998,7 +1045,6 @@ pub async fn run_main(
     let cwd = cli.cwd.clone();
     let config_cwd =
         config_cwd_for_app_server_target(cwd.as_deref(), &app_server_target, &environment_manager)?;
-    let mut loader_overrides = loader_overrides;
     if let Some(profile_v2) = cli.config_profile_v2.as_ref() {
         let user_config_path = resolve_profile_v2_config_path(&codex_home, profile_v2);
         loader_overrides.user_config_path = Some(user_config_path);
@@ -1015,6 +1061,11 @@ pub async fn run_main(
     )
     .await;
     let bootstrap_config_toml = &bootstrap_config.config_toml;
+    if cli.safe_mode {
+        cli_kv_overrides.extend(crate::safe_mode::disable_mcp_server_overrides(
+            &bootstrap_config_toml.mcp_servers,
+        ));
+    }
 
     let chatgpt_base_url = bootstrap_config_toml
         .chatgpt_base_url
@@ -1206,7 +1257,7 @@ pub async fn run_main(
         }
     }
 
-    let (tui_file_layer, _tui_file_log_guard) = if config_toml_log_dir_configured {
+    let (tui_file_layer, _tui_file_log_guard) = if config_toml_log_dir_configured || cli.log_json {
         let log_dir = config.log_dir.clone();
         std::fs::create_dir_all(&log_dir)?;
         let mut log_file_opts = OpenOptions::new();
@@ -1221,20 +1272,34 @@ pub async fn run_main(
             log_file_opts.mode(0o600);
         }
 
-        let log_file = log_file_opts.open(log_dir.join(TUI_LOG_FILE_NAME))?;
+        let log_file_name = if cli.log_json {
+            TUI_JSON_LOG_FILE_NAME
+        } else {
+            TUI_LOG_FILE_NAME
+        };
+        let log_file = log_file_opts.open(log_dir.join(log_file_name))?;
         let (non_blocking, guard) = non_blocking(log_file);
         let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
             EnvFilter::new("codex_core=info,codex_tui=info,codex_rmcp_client=info")
         });
-        let file_layer = tracing_subscriber::fmt::layer()
-            .with_writer(non_blocking)
-            .with_target(true)
-            .with_ansi(false)
-            .with_span_events(
-                tracing_subscriber::fmt::format::FmtSpan::NEW
-                    | tracing_subscriber::fmt::format::FmtSpan::CLOSE,
-            )
-            .with_filter(env_filter);
+        let span_events = tracing_subscriber::fmt::format::FmtSpan::NEW
+            | tracing_subscriber::fmt::format::FmtSpan::CLOSE;
+        let file_layer: TuiFileLogLayer = if cli.log_json {
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_writer(non_blocking)
+                .with_span_events(span_events)
+                .with_filter(env_filter)
+                .boxed()
+        } else {
+            tracing_subscriber::fmt::layer()
+                .with_writer(non_blocking)
+                .with_target(true)
+                .with_ansi(false)
+                .with_span_events(span_events)
+                .with_filter(env_filter)
+                .boxed()
+        };
         (Some(file_layer), Some(guard))
     } else {
         (None, None)
@@ -1270,6 +1335,7 @@ pub async fn run_main(
 
     let _ = tracing_subscriber::registry()
         .with(tui_file_layer)
+        .with(log_tail::log_tail().layer())
         .with(feedback_layer)
         .with(feedback_metadata_layer)
         .with(log_db_layer)
@@ -1330,6 +1396,10 @@ async fn run_ratatui_app(
         tracing::error!("panic: {info}");
         prev_hook(info);
     }));
+    // Keyboard setup in `tui::init` already consults the capability overrides.
+    crate::terminal_capabilities::set_terminal_capability_overrides(
+        initial_config.tui_terminal_capabilities,
+    );
     let mut initialized_terminal = tui::init()?;
     initialized_terminal.terminal.clear()?;
 
@@ -1691,10 +1761,62 @@ async fn run_ratatui_app(
     // Configure syntax highlighting theme from the final config — onboarding
     // and resume/fork can both reload config with a different tui_theme, so
     // this must happen after the last possible reload.
-    if let Some(w) = crate::render::highlight::set_theme_override(
-        config.tui_theme.clone(),
-        find_codex_home().ok().map(AbsolutePathBuf::into_path_buf),
-    ) {
+    // Safe mode leaves out custom themes, syntax colors, and grammars, which are all found
+    // through CODEX_HOME.
+    if cli.safe_mode {
+        crate::render::highlight::set_theme_override(/*name*/ None, /*codex_home*/ None);
+        config
+            .startup_warnings
+            .push(crate::safe_mode::SAFE_MODE_WARNING.to_string());
+        if let Some(error) = cli.safe_mode_config_error.as_deref() {
+            config
+                .startup_warnings
+                .push(crate::safe_mode::skipped_user_config_warning(error));
+        }
+    } else {
+        let syntax_color_warnings =
+            crate::render::highlight::set_syntax_colors(&config.tui_syntax_colors);
+        config.startup_warnings.extend(syntax_color_warnings);
+        if let Some(w) = crate::render::highlight::set_theme_override(
+            config.tui_theme.clone(),
+            find_codex_home().ok().map(AbsolutePathBuf::into_path_buf),
+        ) {
+            config.startup_warnings.push(w);
+        }
+    }
+    crate::terminal_capabilities::set_terminal_capability_overrides(
+        config.tui_terminal_capabilities,
+    );
+    crate::render::highlight::set_rainbow_brackets(config.tui_rainbow_brackets);
+    crate::markdown_render::set_strike_completed_tasks(config.tui_strike_completed_tasks);
+    crate::markdown_render::set_table_overflow(config.tui_table_overflow);
+    crate::markdown_render::set_render_math(config.tui_render_math);
+    crate::markdown_render::set_link_destinations(config.tui_link_destinations);
+    crate::markdown_render::set_code_block_max_lines(config.tui_code_block_max_lines);
+    crate::inline_images::set_inline_images(config.tui_inline_images);
+    crate::render::highlight::set_highlight_color_level(
+        crate::terminal_palette::effective_stdout_color_level(),
+    );
+    crate::terminal_hyperlinks::set_file_link_target(config.tui_file_hyperlinks.then(|| {
+        config.file_opener.get_scheme().map_or(
+            crate::terminal_hyperlinks::FileLinkTarget::File,
+            crate::terminal_hyperlinks::FileLinkTarget::Editor,
+        )
+    }));
+    crate::diff_render::set_word_emphasis_enabled(config.tui_diff_word_emphasis);
+    crate::diff_render::set_show_whitespace_enabled(config.tui_diff_show_whitespace);
+    crate::render::tabs::set_tab_widths(config.tui_tab_width, &config.tui_tab_width_overrides);
+    crate::transcript_density::set_transcript_density(config.tui_transcript_density);
+    crate::transcript_timestamps::set_transcript_timestamps(config.tui_transcript_timestamps);
+    crate::pager_overlay::set_transcript_auto_follow(config.tui_transcript_auto_follow);
+    crate::tmux::set_split_views_enabled(config.tui_tmux_split_views);
+    if !tui.enhanced_keys_supported()
+        && let Some(w) = crate::keymap::enhanced_keys_warning(&config.tui_keymap)
+    {
+        config.startup_warnings.push(w);
+    }
+    if let Some(w) = crate::i18n::init(config.tui_language.as_deref(), config.codex_home.as_path())
+    {
         config.startup_warnings.push(w);
     }
 
@@ -1723,9 +1845,36 @@ async fn run_ratatui_app(
         prompt,
         shared,
         no_alt_screen,
+        workflow_template,
         ..
     } = cli;
     let images = shared.into_inner().images;
+    // A workflow template turns the prompt into the session goal instead of a first message.
+    let (prompt, initial_goal) = match workflow_template {
+        Some(name) => {
+            let goal = if config.features.enabled(Feature::Goals) {
+                workflow_templates::render_workflow_goal(
+                    config.codex_home.as_path(),
+                    &name,
+                    prompt.as_deref().unwrap_or_default(),
+                )
+                .map_err(color_eyre::Report::from)
+            } else {
+                Err(color_eyre::eyre::eyre!(
+                    "Workflow templates require the `goals` feature."
+                ))
+            };
+            match goal {
+                Ok(goal) => (None, Some(goal)),
+                Err(err) => {
+                    terminal_restore_guard.restore_silently();
+                    session_log::log_session_end();
+                    return Err(err);
+                }
+            }
+        }
+        None => (prompt, None),
+    };
 
     let use_alt_screen = determine_alt_screen_mode(no_alt_screen, config.tui_alternate_screen);
     tui.set_alt_screen_enabled(use_alt_screen);
@@ -1799,6 +1948,7 @@ async fn run_ratatui_app(
         cloud_config_bundle,
         prompt,
         images,
+        initial_goal,
         session_selection,
         feedback,
         should_show_trust_screen, // Proxy to: is it a first run in this directory?
@@ -1813,6 +1963,7 @@ async fn run_ratatui_app(
     .await;
 
     terminal_restore_guard.restore_silently();
+    history_cell::remove_spilled_renders();
     // Mark the end of the recorded session.
     session_log::log_session_end();
     // ignore error when collecting usage – report underlying error instead
@@ -1972,23 +2123,27 @@ async fn load_bootstrap_config_or_exit(
     {
         Ok(config_toml) => config_toml,
         Err(err) => {
-            let config_error = err
-                .get_ref()
-                .and_then(|err| err.downcast_ref::<ConfigLoadError>())
-                .map(ConfigLoadError::config_error);
-            if let Some(config_error) = config_error {
-                eprintln!(
-                    "Error loading config.toml:\n{}",
-                    format_config_error_with_source(config_error)
-                );
-            } else {
-                eprintln!("Error loading config.toml: {err}");
-            }
+            eprintln!("{}", describe_config_load_error(&err));
             std::process::exit(1);
         }
     }
 }
 
+/// Describes a config load failure, quoting the offending source when the error points into it.
+fn describe_config_load_error(err: &std::io::Error) -> String {
+    match err
+        .get_ref()
+        .and_then(|err| err.downcast_ref::<ConfigLoadError>())
+        .map(ConfigLoadError::config_error)
+    {
+        Some(config_error) => format!(
+            "Error loading config.toml:\n{}",
+            format_config_error_with_source(config_error)
+        ),
+        None => format!("Error loading config.toml: {err}"),
+    }
+}
+
 /// Determine if the user has decided whether to trust the current directory.
 fn should_show_trust_screen(config: &Config) -> bool {
     config.active_project.trust_level.is_none()
@@ -2966,7 +3121,10 @@ mod tests {
     async fn windows_shows_trust_prompt_without_sandbox() -> std::io::Result<()> {
         let temp_dir = TempDir::new()?;
         let mut config = build_config(&temp_dir).await?;
-        config.active_project = ProjectConfig { trust_level: None };
+        config.active_project = ProjectConfig {
+            trust_level: None,
+            response_language: None,
+        };
         config.set_windows_sandbox_enabled(/*value*/ false);
 
         let should_show = should_show_trust_screen(&config);
@@ -3160,7 +3318,10 @@ mod tests {
     async fn windows_shows_trust_prompt_with_sandbox() -> std::io::Result<()> {
         let temp_dir = TempDir::new()?;
         let mut config = build_config(&temp_dir).await?;
-        config.active_project = ProjectConfig { trust_level: None };
+        config.active_project = ProjectConfig {
+            trust_level: None,
+            response_language: None,
+        };
         config.set_windows_sandbox_enabled(/*value*/ true);
 
         let should_show = should_show_trust_screen(&config);
@@ -3184,6 +3345,7 @@ mod tests {
         let mut config = build_config(&temp_dir).await?;
         config.active_project = ProjectConfig {
             trust_level: Some(TrustLevel::Untrusted),
+            response_language: None,
         };
 
         let should_show = should_show_trust_screen(&config);
@@ -1,8 +1,10 @@
 use ratatui::text::Line;
 use ratatui::text::Span;
-use unicode_width::UnicodeWidthChar;
 use unicode_width::UnicodeWidthStr;
 
+use crate::display_width::graphemes_with_width;
+use crate::display_width::prefix_by_width;
+
 pub(crate) fn line_width(line: &Line<'_>) -> usize {
     line.iter()
         .map(|span| UnicodeWidthStr::width(span.content.as_ref()))
@@ -42,15 +44,7 @@ pub(crate) fn truncate_line_to_width(line: Line<'static>, max_width: usize) -> L
 
         let style = span.style;
         let text = span.content.as_ref();
-        let mut end_idx = 0usize;
-        for (idx, ch) in text.char_indices() {
-            let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if used + ch_width > max_width {
-                break;
-            }
-            end_idx = idx + ch.len_utf8();
-            used += ch_width;
-        }
+        let (end_idx, _) = prefix_by_width(text, max_width - used);
 
         if end_idx > 0 {
             spans_out.push(Span::styled(text[..end_idx].to_string(), style));
@@ -72,6 +66,53 @@ pub(crate) fn truncate_line_to_width(line: Line<'static>, max_width: usize) -> L
 /// pre-scan + return original line unchanged) and uses `truncate_line_to_width`
 /// for the overflow case.
 /// Performance should be reevaluated if using this method in loops/over larger content in the future.
+/// Drops the first `columns` display columns of `line`. A wide grapheme cut in half becomes blank
+/// padding so the rest of the line keeps its position.
+pub(crate) fn skip_line_columns(line: Line<'static>, columns: usize) -> Line<'static> {
+    let Line {
+        style,
+        alignment,
+        spans,
+    } = line;
+    let mut skipped = 0usize;
+    let mut spans_out: Vec<Span<'static>> = Vec::with_capacity(spans.len());
+
+    for span in spans {
+        if skipped >= columns {
+            spans_out.push(span);
+            continue;
+        }
+
+        let span_width = UnicodeWidthStr::width(span.content.as_ref());
+        if skipped + span_width <= columns {
+            skipped += span_width;
+            continue;
+        }
+
+        let text = span.content.as_ref();
+        let mut start = text.len();
+        for (index, _, width) in graphemes_with_width(text) {
+            if skipped >= columns {
+                start = index;
+                break;
+            }
+            skipped += width;
+        }
+        let padding = " ".repeat(skipped - columns);
+        skipped = columns;
+        spans_out.push(Span::styled(
+            format!("{padding}{}", &text[start..]),
+            span.style,
+        ));
+    }
+
+    Line {
+        style,
+        alignment,
+        spans: spans_out,
+    }
+}
+
 pub(crate) fn truncate_line_with_ellipsis_if_overflow(
     line: Line<'static>,
     max_width: usize,
@@ -98,3 +139,81 @@ pub(crate) fn truncate_line_with_ellipsis_if_overflow(
         spans,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use proptest::prelude::*;
+    use ratatui::style::Stylize;
+
+    fn styled_line() -> impl Strategy<Value = Line<'static>> {
+        prop::collection::vec(("[a-z ]{1,8}|→|界面", any::<bool>()), 0..8).prop_map(|spans| {
+            Line::from(
+                spans
+                    .into_iter()
+                    .map(|(text, bold)| {
+                        if bold {
+                            Span::from(text).bold()
+                        } else {
+                            Span::from(text)
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+    }
+
+    fn line_text(line: &Line<'_>) -> String {
+        line.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn overflowing_line_ends_with_ellipsis() {
+        let line = Line::from(vec!["status ".bold(), "line".into()]);
+
+        let truncated = truncate_line_with_ellipsis_if_overflow(line, /*max_width*/ 8);
+
+        assert_eq!(line_text(&truncated), "status …");
+        assert_eq!(line_width(&truncated), 8);
+    }
+
+    #[test]
+    fn skipping_into_a_wide_grapheme_pads_the_remainder() {
+        let line = Line::from(vec!["ab".bold(), "界面x".into()]);
+
+        let skipped = skip_line_columns(line, /*columns*/ 3);
+
+        assert_eq!(skipped, Line::from(vec![Span::from(" 面x")]));
+    }
+
+    proptest! {
+        #[test]
+        fn truncated_lines_fit_and_keep_a_prefix(line in styled_line(), max_width in 0usize..40) {
+            let text = line_text(&line);
+            let truncated = truncate_line_with_ellipsis_if_overflow(line.clone(), max_width);
+            let truncated_text = line_text(&truncated);
+
+            prop_assert!(line_width(&truncated) <= max_width);
+            if max_width > 0 && line_width(&line) <= max_width {
+                prop_assert_eq!(truncated, line);
+            } else {
+                let kept = truncated_text
+                    .strip_suffix('…')
+                    .unwrap_or(&truncated_text);
+                prop_assert!(text.starts_with(kept), "{:?} is not a prefix of {:?}", kept, text);
+            }
+        }
+
+        #[test]
+        fn truncation_keeps_span_styles(line in styled_line(), max_width in 1usize..40) {
+            let truncated = truncate_line_to_width(line.clone(), max_width);
+
+            prop_assert!(truncated.spans.len() <= line.spans.len());
+            for (kept, original) in truncated.spans.iter().zip(&line.spans) {
+                prop_assert_eq!(kept.style, original.style);
+                prop_assert!(original.content.starts_with(kept.content.as_ref()));
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
-use unicode_width::UnicodeWidthChar;
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
+use crate::display_width::prefix_by_width;
+
 /// A single visual row produced by RowBuilder.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Row {
@@ -140,9 +142,9 @@ impl RowBuilder {
             let (prefix, suffix, taken) =
                 take_prefix_by_width(&self.current_line, self.target_width);
             if taken == 0 {
-                // Avoid infinite loop on pathological inputs; take one scalar and continue.
-                if let Some((i, ch)) = self.current_line.char_indices().next() {
-                    let len = i + ch.len_utf8();
+                // Avoid infinite loop on pathological inputs; take one grapheme and continue.
+                if let Some(grapheme) = self.current_line.graphemes(/*is_extended*/ true).next() {
+                    let len = grapheme.len();
                     let p = self.current_line[..len].to_string();
                     self.rows.push(Row {
                         text: p,
@@ -170,23 +172,14 @@ impl RowBuilder {
 
 /// Take a prefix of `text` whose visible width is at most `max_cols`.
 /// Returns (prefix, suffix, prefix_width).
+///
+/// The cut always lands on a grapheme boundary, so emoji sequences and combining marks are never
+/// split across the prefix and suffix.
 pub fn take_prefix_by_width(text: &str, max_cols: usize) -> (String, &str, usize) {
     if max_cols == 0 || text.is_empty() {
         return (String::new(), text, 0);
     }
-    let mut cols = 0usize;
-    let mut end_idx = 0usize;
-    for (i, ch) in text.char_indices() {
-        let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-        if cols.saturating_add(ch_width) > max_cols {
-            break;
-        }
-        cols += ch_width;
-        end_idx = i + ch.len_utf8();
-        if cols == max_cols {
-            break;
-        }
-    }
+    let (end_idx, cols) = prefix_by_width(text, max_cols);
     let prefix = text[..end_idx].to_string();
     let suffix = &text[end_idx..];
     (prefix, suffix, cols)
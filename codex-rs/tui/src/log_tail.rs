@@ -0,0 +1,256 @@
+//! In-memory tail of Codex's own tracing output for the `/logs` overlay.
+//!
+//! [`LogTailLayer`] is installed next to the file and feedback layers at startup and keeps the most
+//! recent events, with their level and target, in a bounded ring. `/logs` snapshots that ring into
+//! a static pager so users can inspect what Codex has been doing without locating the log file.
+//! Filtering happens at display time, so narrowing or widening a filter never loses records.
+
+use chrono::DateTime;
+use chrono::Local;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use tracing::Event;
+use tracing::Level;
+use tracing::Subscriber;
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing_subscriber::Layer;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Number of records retained for `/logs`; older records are dropped first.
+const LOG_TAIL_CAPACITY: usize = 2_000;
+
+pub(crate) const LOGS_USAGE: &str = "Usage: /logs [error|warn|info|debug|trace] [target]";
+
+static LOG_TAIL: LazyLock<LogTail> = LazyLock::new(|| LogTail::new(LOG_TAIL_CAPACITY));
+
+/// Process-wide log tail shared by the tracing layer and the `/logs` command.
+pub(crate) fn log_tail() -> &'static LogTail {
+    &LOG_TAIL
+}
+
+/// One captured tracing event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct LogRecord {
+    pub(crate) timestamp: DateTime<Local>,
+    pub(crate) level: Level,
+    pub(crate) target: String,
+    pub(crate) message: String,
+}
+
+/// Bounded ring of recent log records.
+#[derive(Debug)]
+pub(crate) struct LogTail {
+    capacity: usize,
+    records: Mutex<VecDeque<LogRecord>>,
+}
+
+impl LogTail {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    fn push(&self, record: LogRecord) {
+        let mut records = self.records.lock().unwrap_or_else(PoisonError::into_inner);
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// Copy the retained records, oldest first.
+    pub(crate) fn snapshot(&self) -> Vec<LogRecord> {
+        self.records
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Returns a layer that records Codex debug events and third-party warnings into this tail.
+    pub(crate) fn layer<S>(&'static self) -> impl Layer<S> + Send + Sync + 'static
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        LogTailLayer { tail: self }.with_filter(
+            Targets::new()
+                .with_default(Level::WARN)
+                .with_target("codex", Level::DEBUG),
+        )
+    }
+}
+
+struct LogTailLayer {
+    tail: &'static LogTail,
+}
+
+impl<S> Layer<S> for LogTailLayer
+where
+    S: Subscriber,
+{
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.tail.push(LogRecord {
+            timestamp: Local::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.finish(),
+        });
+    }
+}
+
+/// Formats the `message` field first, followed by the remaining fields as `key=value` pairs.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        match (self.message.is_empty(), self.fields.is_empty()) {
+            (_, true) => self.message,
+            (true, false) => self.fields,
+            (false, false) => format!("{} {}", self.message, self.fields),
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+            return;
+        }
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        let _ = write!(self.fields, "{}={value:?}", field.name());
+    }
+}
+
+/// Display-time filter parsed from `/logs` arguments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct LogFilter {
+    /// Least severe level to show; `Level::INFO` shows info, warn, and error records.
+    pub(crate) level: Level,
+    /// Case-insensitive substring that the record target must contain.
+    pub(crate) target: Option<String>,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            level: Level::INFO,
+            target: None,
+        }
+    }
+}
+
+impl LogFilter {
+    /// Parse `/logs` arguments: an optional level and an optional target substring, in any order.
+    pub(crate) fn parse(args: &str) -> Option<Self> {
+        let mut filter = Self::default();
+        let mut saw_level = false;
+        for token in args.split_whitespace() {
+            if let Ok(level) = token.parse::<Level>() {
+                if saw_level {
+                    return None;
+                }
+                filter.level = level;
+                saw_level = true;
+            } else if filter.target.is_none() {
+                filter.target = Some(token.to_ascii_lowercase());
+            } else {
+                return None;
+            }
+        }
+        Some(filter)
+    }
+
+    fn matches(&self, record: &LogRecord) -> bool {
+        record.level <= self.level
+            && self
+                .target
+                .as_ref()
+                .is_none_or(|target| record.target.to_ascii_lowercase().contains(target))
+    }
+
+    fn describe(&self) -> String {
+        let level = self.level.as_str().to_ascii_lowercase();
+        match &self.target {
+            Some(target) => format!("{level} and above from targets matching \"{target}\""),
+            None => format!("{level} and above"),
+        }
+    }
+}
+
+/// Render the records that pass `filter` as pager lines, newest last.
+pub(crate) fn log_lines(records: &[LogRecord], filter: &LogFilter) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = vec![
+        vec![
+            "Showing ".dim(),
+            filter.describe().into(),
+            ". Use /logs <level> <target> to filter.".dim(),
+        ]
+        .into(),
+        "".into(),
+    ];
+    let matching: Vec<&LogRecord> = records
+        .iter()
+        .filter(|record| filter.matches(record))
+        .collect();
+    if matching.is_empty() {
+        lines.push("No log records match.".italic().into());
+        return lines;
+    }
+    lines.extend(matching.into_iter().map(log_line));
+    lines
+}
+
+fn log_line(record: &LogRecord) -> Line<'static> {
+    let level = format!("{:<5}", record.level.as_str());
+    let level: Span<'static> = match record.level {
+        Level::ERROR => level.red().bold(),
+        Level::WARN => level.yellow().bold(),
+        Level::INFO => level.green(),
+        Level::DEBUG => level.cyan(),
+        _ => level.dim(),
+    };
+    vec![
+        record.timestamp.format("%H:%M:%S%.3f").to_string().dim(),
+        " ".into(),
+        level,
+        " ".into(),
+        format!("{}:", record.target).dim(),
+        " ".into(),
+        record.message.clone().into(),
+    ]
+    .into()
+}
+
+#[cfg(test)]
+#[path = "log_tail_tests.rs"]
+mod tests;
@@ -0,0 +1,90 @@
+use super::*;
+use chrono::TimeZone;
+use pretty_assertions::assert_eq;
+
+fn record(level: Level, target: &str, message: &str) -> LogRecord {
+    LogRecord {
+        timestamp: Local
+            .with_ymd_and_hms(2025, 1, 2, 3, 4, 5)
+            .single()
+            .expect("valid timestamp"),
+        level,
+        target: target.to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn rendered(lines: &[Line<'static>]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect()
+}
+
+#[test]
+fn parses_level_and_target_in_any_order() {
+    assert_eq!(LogFilter::parse(""), Some(LogFilter::default()));
+    assert_eq!(
+        LogFilter::parse("codex_core DEBUG"),
+        Some(LogFilter {
+            level: Level::DEBUG,
+            target: Some("codex_core".to_string()),
+        })
+    );
+    assert_eq!(LogFilter::parse("warn error"), None);
+    assert_eq!(LogFilter::parse("codex_core codex_tui"), None);
+}
+
+#[test]
+fn tail_drops_oldest_records_at_capacity() {
+    let tail = LogTail::new(/*capacity*/ 2);
+    tail.push(record(Level::INFO, "codex_tui", "first"));
+    tail.push(record(Level::INFO, "codex_tui", "second"));
+    tail.push(record(Level::INFO, "codex_tui", "third"));
+
+    assert_eq!(
+        tail.snapshot(),
+        vec![
+            record(Level::INFO, "codex_tui", "second"),
+            record(Level::INFO, "codex_tui", "third"),
+        ]
+    );
+}
+
+#[test]
+fn log_lines_filter_by_level_and_target() {
+    let records = vec![
+        record(Level::DEBUG, "codex_core::client", "sending request"),
+        record(Level::WARN, "codex_core::exec", "command timed out"),
+        record(Level::ERROR, "codex_tui::app", "render failed"),
+    ];
+    let filter = LogFilter::parse("warn core").expect("valid filter");
+
+    assert_eq!(
+        rendered(&log_lines(&records, &filter)),
+        vec![
+            "Showing warn and above from targets matching \"core\". Use /logs <level> <target> to filter.",
+            "",
+            "03:04:05.000 WARN  codex_core::exec: command timed out",
+        ]
+    );
+}
+
+#[test]
+fn log_lines_report_when_nothing_matches() {
+    let records = vec![record(Level::DEBUG, "codex_core", "noise")];
+
+    assert_eq!(
+        rendered(&log_lines(&records, &LogFilter::default())),
+        vec![
+            "Showing info and above. Use /logs <level> <target> to filter.",
+            "",
+            "No log records match.",
+        ]
+    );
+}
@@ -39,22 +39,36 @@
 //! body rows, or even 3-char-wide columns cannot fit, body rows render as
 //! key/value records.
 
+use crate::diff_render::diff_block_to_lines;
+use crate::diff_render::is_diff_language;
+use crate::display_width::graphemes_with_width;
+use crate::file_references::annotate_file_reference_span;
+use crate::file_references::annotate_file_references_in_text;
+use crate::file_references::annotate_links_in_line;
+use crate::inline_images;
+use crate::inline_images::InlineImage;
 use crate::markdown_text_merge::DecodedTextMerge;
 use crate::render::highlight::foreground_style_for_scopes;
-use crate::render::highlight::highlight_code_to_lines;
+use crate::render::highlight::highlight_streamed_code_to_lines;
 use crate::render::line_utils::line_to_static;
+use crate::render::tabs::expand_tabs;
+use crate::render::tabs::tab_width_for;
 use crate::style::table_separator_style;
+use crate::terminal_capabilities::terminal_capabilities;
 use crate::terminal_hyperlinks::HyperlinkLine;
-use crate::terminal_hyperlinks::annotate_web_urls_in_line;
 use crate::terminal_hyperlinks::remap_wrapped_line;
 use crate::terminal_hyperlinks::visible_lines;
 use crate::terminal_hyperlinks::web_destination;
 use crate::wrapping::RtOptions;
 use crate::wrapping::adaptive_wrap_line;
 use crate::wrapping::word_wrap_line;
+use codex_config::types::LinkDestinations;
+use codex_config::types::TableOverflow;
+use codex_protocol::num_format::format_with_separators;
 use codex_utils_string::normalize_markdown_hash_location_suffix;
 use dirs::home_dir;
 use pulldown_cmark::Alignment;
+use pulldown_cmark::BlockQuoteKind;
 use pulldown_cmark::CodeBlockKind;
 use pulldown_cmark::CowStr;
 use pulldown_cmark::Event;
@@ -69,14 +83,22 @@ use ratatui::text::Line;
 use ratatui::text::Span;
 use ratatui::text::Text;
 use regex_lite::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use std::cmp::Reverse;
 use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::sync::LazyLock;
-use unicode_width::UnicodeWidthChar;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use unicode_width::UnicodeWidthStr;
 use url::Url;
 
+mod math;
 mod streaming;
 mod table_key_value;
 
@@ -87,6 +109,75 @@ const TABLE_COLUMN_GAP: usize = 2;
 const TABLE_CELL_PADDING: usize = 1;
 const TABLE_HEADER_SEPARATOR_CHAR: char = '━';
 const TABLE_BODY_SEPARATOR_CHAR: char = '─';
+const TASK_OPEN_MARKER: &str = "☐";
+const TASK_DONE_MARKER: &str = "☑";
+const DEFINITION_INDENT: &str = "    ";
+/// Widest inline image when rendering without a wrap width.
+const UNWRAPPED_IMAGE_MAX_COLUMNS: usize = 80;
+/// Widest link destination that `tui.link_destinations = "inline-if-short"` keeps inline.
+const SHORT_LINK_DESTINATION_MAX_COLUMNS: usize = 40;
+
+static STRIKE_COMPLETED_TASKS: AtomicBool = AtomicBool::new(false);
+
+/// Applies `tui.strike_completed_tasks` from config.  Read when a render
+/// starts, so set it before the first markdown is rendered.
+pub(crate) fn set_strike_completed_tasks(enabled: bool) {
+    STRIKE_COMPLETED_TASKS.store(enabled, Ordering::Relaxed);
+}
+
+static TABLE_OVERFLOW: AtomicU8 = AtomicU8::new(TableOverflow::Wrap as u8);
+
+/// Applies `tui.table_overflow` from config.  Read when a render starts, like
+/// [`set_strike_completed_tasks`].
+pub(crate) fn set_table_overflow(overflow: TableOverflow) {
+    TABLE_OVERFLOW.store(overflow as u8, Ordering::Relaxed);
+}
+
+static RENDER_MATH: AtomicBool = AtomicBool::new(true);
+
+/// Applies `tui.render_math` from config.  Read when a render starts, like
+/// [`set_strike_completed_tasks`].
+pub(crate) fn set_render_math(enabled: bool) {
+    RENDER_MATH.store(enabled, Ordering::Relaxed);
+}
+
+static LINK_DESTINATIONS: AtomicU8 = AtomicU8::new(LinkDestinations::Inline as u8);
+
+/// Applies `tui.link_destinations` from config.  Read when a render starts, like
+/// [`set_strike_completed_tasks`].
+pub(crate) fn set_link_destinations(mode: LinkDestinations) {
+    LINK_DESTINATIONS.store(mode as u8, Ordering::Relaxed);
+}
+
+static CODE_BLOCK_MAX_LINES: AtomicUsize = AtomicUsize::new(200);
+
+/// Applies `tui.code_block_max_lines` from config.  Read when a render starts, like
+/// [`set_strike_completed_tasks`].
+pub(crate) fn set_code_block_max_lines(max_lines: usize) {
+    CODE_BLOCK_MAX_LINES.store(max_lines, Ordering::Relaxed);
+}
+
+/// Longest fenced code block rendered in full; `0` never collapses.
+pub(crate) fn code_block_max_lines() -> usize {
+    CODE_BLOCK_MAX_LINES.load(Ordering::Relaxed)
+}
+
+fn link_destinations() -> LinkDestinations {
+    match LINK_DESTINATIONS.load(Ordering::Relaxed) {
+        value if value == LinkDestinations::InlineIfShort as u8 => LinkDestinations::InlineIfShort,
+        value if value == LinkDestinations::Footnotes as u8 => LinkDestinations::Footnotes,
+        value if value == LinkDestinations::HyperlinkOnly as u8 => LinkDestinations::HyperlinkOnly,
+        _ => LinkDestinations::Inline,
+    }
+}
+
+fn table_overflow() -> TableOverflow {
+    match TABLE_OVERFLOW.load(Ordering::Relaxed) {
+        value if value == TableOverflow::Fold as u8 => TableOverflow::Fold,
+        value if value == TableOverflow::Scroll as u8 => TableOverflow::Scroll,
+        _ => TableOverflow::Wrap,
+    }
+}
 
 struct MarkdownStyles {
     h1: Style,
@@ -96,13 +187,24 @@ struct MarkdownStyles {
     h5: Style,
     h6: Style,
     code: Style,
+    math: Style,
     emphasis: Style,
     strong: Style,
     strikethrough: Style,
+    definition_term: Style,
     ordered_list_marker: Style,
     unordered_list_marker: Style,
+    task_marker: Style,
+    task_done_marker: Style,
+    task_done: Style,
     link: Style,
+    image: Style,
     blockquote: Style,
+    alert_note: Style,
+    alert_tip: Style,
+    alert_important: Style,
+    alert_warning: Style,
+    alert_caution: Style,
 }
 
 impl Default for MarkdownStyles {
@@ -115,13 +217,28 @@ impl Default for MarkdownStyles {
             h5: Style::new().italic(),
             h6: Style::new().italic(),
             code: Style::new().cyan(),
+            math: Style::new().cyan().italic(),
             emphasis: Style::new().italic(),
             strong: Style::new().bold(),
             strikethrough: Style::new().crossed_out(),
+            definition_term: Style::new().bold(),
             ordered_list_marker: Style::new().light_blue(),
             unordered_list_marker: Style::new(),
+            task_marker: Style::new().cyan(),
+            task_done_marker: Style::new().green(),
+            task_done: if STRIKE_COMPLETED_TASKS.load(Ordering::Relaxed) {
+                Style::new().dim().crossed_out()
+            } else {
+                Style::new().dim()
+            },
             link: Style::new().cyan().underlined(),
+            image: Style::new().dim(),
             blockquote: Style::new().green(),
+            alert_note: Style::new().blue(),
+            alert_tip: Style::new().green(),
+            alert_important: Style::new().magenta(),
+            alert_warning: Style::new().yellow(),
+            alert_caution: Style::new().red(),
         }
     }
 }
@@ -188,6 +305,24 @@ impl TableCell {
         self.lines.push(HyperlinkLine::new(Line::default()));
     }
 
+    /// The cell's spans on one line, each patched over `base`, for places such as record labels
+    /// that show a cell without wrapping it.
+    fn styled_spans(&self, base: Style) -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" ", base));
+            }
+            spans.extend(
+                line.line
+                    .spans
+                    .iter()
+                    .map(|span| Span::styled(span.content.clone(), base.patch(span.style))),
+            );
+        }
+        spans
+    }
+
     fn plain_text(&self) -> String {
         use std::fmt::Write;
         let mut buf = String::new();
@@ -266,6 +401,18 @@ enum TableColumnKind {
     Compact,
 }
 
+impl TableColumnKind {
+    /// Order in which `tui.table_overflow = "fold"` moves columns out of the grid: prose first,
+    /// then long tokens, so short values stay in the grid longest.
+    fn fold_priority(self) -> u8 {
+        match self {
+            TableColumnKind::Narrative => 0,
+            TableColumnKind::TokenHeavy => 1,
+            TableColumnKind::Compact => 2,
+        }
+    }
+}
+
 /// Per-column statistics used to drive the width-allocation algorithm.
 ///
 /// Collected in a single pass over the header and body rows before any
@@ -342,18 +489,117 @@ pub(crate) fn render_markdown_lines_with_width_cwd_and_hidden_link_destinations(
     cwd: Option<&Path>,
     is_hidden_link_destination: &dyn Fn(&str) -> bool,
 ) -> Vec<HyperlinkLine> {
-    let mut options = Options::empty();
-    options.insert(Options::ENABLE_STRIKETHROUGH);
-    options.insert(Options::ENABLE_TABLES);
-    let parser = DecodedTextMerge::new(Parser::new_ext(input, options).into_offset_iter());
+    let parser = DecodedTextMerge::new(Parser::new_ext(input, parser_options()).into_offset_iter());
     let mut w = Writer::new(input, parser, width, cwd, is_hidden_link_destination);
     w.run();
     w.text
 }
 
+/// Unstyled source of the last code block in `markdown`, without its final line break, as
+/// rendered rows carry it in [`HyperlinkLine::code_block`].
+pub(crate) fn last_code_block(markdown: &str) -> Option<String> {
+    let mut last = None;
+    let mut open: Option<String> = None;
+    for event in Parser::new_ext(markdown, parser_options()) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => open = Some(String::new()),
+            Event::Text(text) => {
+                if let Some(code) = open.as_mut() {
+                    code.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => last = open.take(),
+            _ => {}
+        }
+    }
+    last.map(|code| match code.strip_suffix('\n') {
+        Some(code) => code.to_string(),
+        None => code,
+    })
+}
+
+/// Markdown extensions enabled for every render, streamed or not, so both agree on block
+/// boundaries.
+pub(crate) fn parser_options() -> Options {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_DEFINITION_LIST);
+    // Strips `{#id .class}` from headings instead of rendering it.
+    options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
+    // Reports `> [!NOTE]` and the other GitHub alert markers as the blockquote's kind.
+    options.insert(Options::ENABLE_GFM);
+    // Without it, `$` is ordinary text and formulas show as written.
+    if RENDER_MATH.load(Ordering::Relaxed) {
+        options.insert(Options::ENABLE_MATH);
+    }
+    options
+}
+
+/// Source lines a long fenced code block leaves out of its render, kept on the row that says how
+/// many are hidden so the transcript overlay can draw them on request.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct HiddenCode {
+    lang: Option<String>,
+    code: String,
+}
+
+impl HiddenCode {
+    /// The hidden lines, highlighted like the rest of their block.
+    pub(crate) fn lines(&self) -> Vec<Line<'static>> {
+        match self.lang.as_deref() {
+            Some(lang) => highlight_code_lines(&self.code, lang),
+            None => {
+                let tab_width = tab_width_for(self.lang.as_deref());
+                self.code
+                    .lines()
+                    .map(|line| Line::from(expand_tabs(line, tab_width).into_owned()))
+                    .collect()
+            }
+        }
+    }
+
+    fn line_count(&self) -> usize {
+        self.code.split_inclusive('\n').count()
+    }
+}
+
+fn highlight_code_lines(code: &str, lang: &str) -> Vec<Line<'static>> {
+    is_diff_language(lang)
+        .then(|| diff_block_to_lines(code))
+        .flatten()
+        .unwrap_or_else(|| highlight_streamed_code_to_lines(code, lang))
+}
+
+/// Splits a code block longer than `max_lines` into the lines kept above its collapse row, the
+/// hidden lines, and the lines kept below it. Half of `max_lines` is kept on each side.
+fn split_long_code(code: &str, max_lines: usize) -> Option<[&str; 3]> {
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(code.split_inclusive('\n').scan(0, |end, line| {
+            *end += line.len();
+            Some(*end)
+        }))
+        .collect();
+    let line_count = line_starts.len() - 1;
+    if max_lines == 0 || line_count <= max_lines {
+        return None;
+    }
+    let kept = max_lines / 2;
+    let hidden_start = line_starts[kept];
+    let hidden_end = line_starts[line_count - kept];
+    Some([
+        &code[..hidden_start],
+        &code[hidden_start..hidden_end],
+        &code[hidden_end..],
+    ])
+}
+
 #[derive(Clone, Debug)]
 struct LinkState {
     destination: String,
+    /// Plain text of the label, to skip destinations that only repeat it.
+    label: String,
     show_destination: bool,
     style_label: bool,
     /// Pre-rendered display text for local file links.
@@ -363,10 +609,41 @@ struct LinkState {
     local_target_display: Option<String>,
 }
 
+#[derive(Clone, Copy, Debug)]
+struct DefinitionListState {
+    /// Whether definitions follow their term on the same line instead of indented below it.
+    compact: bool,
+    /// Definitions seen since the current term, so compact ones after the first are separated
+    /// with `; `.
+    definitions_in_entry: usize,
+}
+
+#[derive(Clone, Debug)]
+struct ImageState {
+    destination: String,
+    /// Whether alt text has started, so the `: ` separator after `[image` is written once.
+    has_alt: bool,
+}
+
 fn should_render_link_destination(dest_url: &str) -> bool {
     !is_local_path_like_link(dest_url)
 }
 
+/// Whether a link's label already shows its destination, as in `<https://x>`, `[x.com](https://x.com/)`,
+/// or `<mailto:me@x.com>`, so rendering the destination again would only repeat it.
+fn label_repeats_destination(label: &str, destination: &str) -> bool {
+    fn normalized(text: &str) -> String {
+        let text = text.trim();
+        let text = ["https://", "http://", "mailto:"]
+            .iter()
+            .find_map(|scheme| text.strip_prefix(scheme))
+            .unwrap_or(text);
+        text.trim_end_matches('/').to_ascii_lowercase()
+    }
+    let label = normalized(label);
+    !label.is_empty() && label == normalized(destination)
+}
+
 static COLON_LOCATION_SUFFIX_RE: LazyLock<Regex> =
     LazyLock::new(
         || match Regex::new(r":\d+(?::\d+)?(?:[-–]\d+(?::\d+)?)?$") {
@@ -401,13 +678,27 @@ where
     list_indices: Vec<Option<u64>>,
     list_needs_blank_before_next_item: Vec<bool>,
     list_item_start_line_counts: Vec<usize>,
+    /// Per open list item, whether it is a checked task whose done style is
+    /// pushed onto `inline_styles`.
+    list_item_task_done: Vec<bool>,
+    /// Open definition lists, innermost last.
+    definition_lists: Vec<DefinitionListState>,
     link: Option<LinkState>,
+    image: Option<ImageState>,
+    /// Inline images closed in the current block, drawn below it once the block ends.
+    pending_images: Vec<InlineImage>,
     needs_newline: bool,
     pending_marker_line: bool,
     in_paragraph: bool,
     in_code_block: bool,
     code_block_lang: Option<String>,
     code_block_buffer: String,
+    /// Whether the open code block is fenced; only fenced blocks collapse.
+    code_block_fenced: bool,
+    /// Index in `text` of the open code block's first row.
+    code_block_start: usize,
+    /// Fenced code blocks longer than this collapse; `0` never collapses.
+    code_block_max_lines: usize,
     wrap_width: Option<usize>,
     cwd: Option<PathBuf>,
     is_hidden_link_destination: &'policy dyn Fn(&str) -> bool,
@@ -419,6 +710,12 @@ where
     current_line_style: Style,
     current_line_in_code_block: bool,
     table_state: Option<TableState>,
+    /// How tables wider than `wrap_width` are laid out.
+    table_overflow: TableOverflow,
+    /// Where link destinations are shown.
+    link_destinations: LinkDestinations,
+    /// Destinations of the current block's footnoted links; a link's number is its index plus one.
+    pending_link_footnotes: Vec<String>,
 }
 
 impl<'a, 'policy, I> Writer<'a, 'policy, I>
@@ -442,13 +739,20 @@ where
             list_indices: Vec::new(),
             list_needs_blank_before_next_item: Vec::new(),
             list_item_start_line_counts: Vec::new(),
+            list_item_task_done: Vec::new(),
+            definition_lists: Vec::new(),
             link: None,
+            image: None,
+            pending_images: Vec::new(),
             needs_newline: false,
             pending_marker_line: false,
             in_paragraph: false,
             in_code_block: false,
             code_block_lang: None,
             code_block_buffer: String::new(),
+            code_block_fenced: false,
+            code_block_start: 0,
+            code_block_max_lines: code_block_max_lines(),
             wrap_width,
             cwd: cwd.map(Path::to_path_buf),
             is_hidden_link_destination,
@@ -460,6 +764,9 @@ where
             current_line_style: Style::default(),
             current_line_in_code_block: false,
             table_state: None,
+            table_overflow: table_overflow(),
+            link_destinations: link_destinations(),
+            pending_link_footnotes: Vec::new(),
         }
     }
 
@@ -468,10 +775,16 @@ where
             self.handle_event(ev, range);
         }
         self.flush_current_line();
+        self.flush_block_trailers();
     }
 
     fn handle_event(&mut self, event: Event<'a>, range: Range<usize>) {
         self.prepare_for_event(&event);
+        if let Some(link) = self.link.as_mut()
+            && let Event::Text(text) | Event::Code(text) = &event
+        {
+            link.label.push_str(text);
+        }
         match event {
             Event::Start(tag) => self.start_tag(tag, range),
             Event::End(tag) => self.end_tag(tag),
@@ -489,8 +802,10 @@ where
             }
             Event::Html(html) => self.html(html, /*inline*/ false),
             Event::InlineHtml(html) => self.html(html, /*inline*/ true),
+            Event::InlineMath(math) => self.inline_math(math),
+            Event::DisplayMath(math) => self.display_math(math),
             Event::FootnoteReference(_) => {}
-            Event::TaskListMarker(_) => {}
+            Event::TaskListMarker(checked) => self.task_list_marker(checked),
         }
     }
 
@@ -515,7 +830,7 @@ where
         match tag {
             Tag::Paragraph => self.start_paragraph(),
             Tag::Heading { level, .. } => self.start_heading(level),
-            Tag::BlockQuote => self.start_blockquote(),
+            Tag::BlockQuote(kind) => self.start_blockquote(kind),
             Tag::CodeBlock(kind) => {
                 let indent = match kind {
                     CodeBlockKind::Fenced(_) => None,
@@ -529,18 +844,19 @@ where
             }
             Tag::List(start) => self.start_list(start),
             Tag::Item => self.start_item(),
+            Tag::DefinitionList => self.start_definition_list(),
+            Tag::DefinitionListTitle => self.start_definition_title(),
+            Tag::DefinitionListDefinition => self.start_definition(),
             Tag::Emphasis => self.push_inline_style(self.styles.emphasis),
             Tag::Strong => self.push_inline_style(self.styles.strong),
             Tag::Strikethrough => self.push_inline_style(self.styles.strikethrough),
             Tag::Link { dest_url, .. } => self.push_link(dest_url.to_string()),
+            Tag::Image { dest_url, .. } => self.start_image(dest_url.to_string()),
             Tag::Table(alignments) => self.start_table(alignments),
             Tag::TableHead => self.start_table_head(),
             Tag::TableRow => self.start_table_row(range),
             Tag::TableCell => self.start_table_cell(),
-            Tag::HtmlBlock
-            | Tag::FootnoteDefinition(_)
-            | Tag::Image { .. }
-            | Tag::MetadataBlock(_) => {}
+            Tag::HtmlBlock | Tag::FootnoteDefinition(_) | Tag::MetadataBlock(_) => {}
         }
     }
 
@@ -548,30 +864,35 @@ where
         match tag {
             TagEnd::Paragraph => self.end_paragraph(),
             TagEnd::Heading(_) => self.end_heading(),
-            TagEnd::BlockQuote => self.end_blockquote(),
+            TagEnd::BlockQuote(_) => self.end_blockquote(),
             TagEnd::CodeBlock => self.end_codeblock(),
             TagEnd::List(_) => self.end_list(),
             TagEnd::Item => {
                 self.flush_current_line();
+                self.flush_block_trailers();
                 let start_line_count = self.list_item_start_line_counts.pop().unwrap_or_default();
                 if self.text.len().saturating_sub(start_line_count) > 1
                     && let Some(needs_blank) = self.list_needs_blank_before_next_item.last_mut()
                 {
                     *needs_blank = true;
                 }
+                if self.list_item_task_done.pop().unwrap_or(false) {
+                    self.pop_inline_style();
+                }
                 self.indent_stack.pop();
                 self.pending_marker_line = false;
             }
+            TagEnd::DefinitionList => self.end_definition_list(),
+            TagEnd::DefinitionListTitle => self.end_definition_title(),
+            TagEnd::DefinitionListDefinition => self.end_definition(),
             TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => self.pop_inline_style(),
             TagEnd::Link => self.pop_link(),
+            TagEnd::Image => self.end_image(),
             TagEnd::Table => self.end_table(),
             TagEnd::TableHead => self.end_table_head(),
             TagEnd::TableRow => self.end_table_row(),
             TagEnd::TableCell => self.end_table_cell(),
-            TagEnd::HtmlBlock
-            | TagEnd::FootnoteDefinition
-            | TagEnd::Image
-            | TagEnd::MetadataBlock(_) => {}
+            TagEnd::HtmlBlock | TagEnd::FootnoteDefinition | TagEnd::MetadataBlock(_) => {}
         }
     }
 
@@ -591,6 +912,7 @@ where
         if self.in_table_cell() {
             return;
         }
+        self.flush_block_trailers();
         self.needs_newline = true;
         self.in_paragraph = false;
         self.pending_marker_line = false;
@@ -614,6 +936,9 @@ where
         };
         let content = format!("{} ", "#".repeat(level as usize));
         self.push_line(Line::from(vec![Span::styled(content, heading_style)]));
+        if let Some(line) = self.current_line_content.as_mut() {
+            line.heading_level = Some(level as u8);
+        }
         self.push_inline_style(heading_style);
         self.needs_newline = false;
     }
@@ -622,11 +947,12 @@ where
         if self.in_table_cell() {
             return;
         }
+        self.flush_block_trailers();
         self.needs_newline = true;
         self.pop_inline_style();
     }
 
-    fn start_blockquote(&mut self) {
+    fn start_blockquote(&mut self, kind: Option<BlockQuoteKind>) {
         if self.in_table_cell() {
             return;
         }
@@ -634,11 +960,36 @@ where
             self.push_blank_line();
             self.needs_newline = false;
         }
+        let Some(kind) = kind else {
+            self.indent_stack.push(IndentContext::new(
+                vec![Span::from("> ")],
+                /*marker*/ None,
+                /*is_list*/ false,
+            ));
+            return;
+        };
+        // GitHub alerts get a border and label in the alert's color, and their text keeps its
+        // own style instead of the blockquote green.
+        let (icon, label, style) = self.alert_heading(kind);
         self.indent_stack.push(IndentContext::new(
-            vec![Span::from("> ")],
+            vec![Span::styled("│ ", style)],
             /*marker*/ None,
             /*is_list*/ false,
         ));
+        self.push_line(Line::from(Span::styled(
+            format!("{icon} {label}"),
+            style.bold(),
+        )));
+    }
+
+    fn alert_heading(&self, kind: BlockQuoteKind) -> (&'static str, &'static str, Style) {
+        match kind {
+            BlockQuoteKind::Note => ("ⓘ", "Note", self.styles.alert_note),
+            BlockQuoteKind::Tip => ("★", "Tip", self.styles.alert_tip),
+            BlockQuoteKind::Important => ("!", "Important", self.styles.alert_important),
+            BlockQuoteKind::Warning => ("⚠", "Warning", self.styles.alert_warning),
+            BlockQuoteKind::Caution => ("✗", "Caution", self.styles.alert_caution),
+        }
     }
 
     fn end_blockquote(&mut self) {
@@ -653,6 +1004,17 @@ where
         if self.suppressing_local_link_label() {
             return;
         }
+        let text = match self.image.as_mut() {
+            Some(image) if !image.has_alt => {
+                image.has_alt = true;
+                CowStr::from(format!(": {text}"))
+            }
+            // Text resuming a paragraph below a display formula starts a fresh row.
+            _ if self.needs_newline && self.in_paragraph => {
+                CowStr::from(text.trim_start().to_string())
+            }
+            _ => text,
+        };
         self.line_ends_with_local_link_target = false;
         if self.in_table_cell() {
             self.push_text_to_table_cell(&text);
@@ -664,13 +1026,16 @@ where
         }
         self.pending_marker_line = false;
 
-        // When inside a fenced code block with a known language, accumulate
-        // text into the buffer for batch highlighting in end_codeblock().
-        // Append verbatim — pulldown-cmark text events already contain the
-        // original line breaks, so inserting separators would double them.
-        if self.in_code_block && self.code_block_lang.is_some() {
+        // Inside a code block, accumulate text into the buffer: blocks with a
+        // known language are highlighted as a batch in end_codeblock(), and
+        // long blocks keep their hidden lines from it. Append verbatim —
+        // pulldown-cmark text events already contain the original line
+        // breaks, so inserting separators would double them.
+        if self.in_code_block {
             self.code_block_buffer.push_str(&text);
-            return;
+            if self.code_block_lang.is_some() {
+                return;
+            }
         }
 
         if self.in_code_block && !self.needs_newline {
@@ -696,7 +1061,11 @@ where
             if i > 0 {
                 self.push_line(Line::default());
             }
-            let content = line.to_string();
+            let content = if self.in_code_block {
+                expand_tabs(line, tab_width_for(self.code_block_lang.as_deref())).into_owned()
+            } else {
+                line.to_string()
+            };
             let style = self.inline_styles.last().copied().unwrap_or_default();
             self.push_text_spans(&content, style);
         }
@@ -708,8 +1077,30 @@ where
             return;
         }
         self.line_ends_with_local_link_target = false;
+        let code = annotate_file_reference_span(
+            Span::from(code.into_string()).style(self.styles.code),
+            self.cwd.as_deref(),
+        );
         if self.in_table_cell() {
-            self.push_span_to_table_cell(Span::from(code.into_string()).style(self.styles.code));
+            self.push_annotated_to_table_cell(code);
+            return;
+        }
+
+        if self.pending_marker_line {
+            self.push_line(Line::default());
+            self.pending_marker_line = false;
+        }
+        self.push_annotated(code);
+    }
+
+    fn inline_math(&mut self, math: CowStr<'a>) {
+        if self.suppressing_local_link_label() {
+            return;
+        }
+        self.line_ends_with_local_link_target = false;
+        let span = Span::styled(math::latex_to_unicode(&math).join(" "), self.styles.math);
+        if self.in_table_cell() {
+            self.push_span_to_table_cell(span);
             return;
         }
 
@@ -717,10 +1108,86 @@ where
             self.push_line(Line::default());
             self.pending_marker_line = false;
         }
-        let span = Span::from(code.into_string()).style(self.styles.code);
         self.push_span(span);
     }
 
+    /// Draws `$$...$$` as a bordered block on its own rows, or as barred rows when the box
+    /// would not fit the wrap width.
+    fn display_math(&mut self, math: CowStr<'a>) {
+        if self.in_table_cell() || self.suppressing_local_link_label() {
+            self.inline_math(math);
+            return;
+        }
+        let rows = math::latex_to_unicode(&math);
+        if rows.is_empty() {
+            return;
+        }
+        self.line_ends_with_local_link_target = false;
+
+        // Drop the empty row a paragraph or soft break started for the formula, keeping any
+        // list marker it carried, then separate the box from text above it.
+        let mut pending_marker_line = self.pending_marker_line;
+        if let Some(last) = self
+            .current_line_content
+            .as_mut()
+            .and_then(|line| line.line.spans.last_mut())
+        {
+            last.content = last.content.trim_end().to_string().into();
+        }
+        if self
+            .current_line_content
+            .as_ref()
+            .is_some_and(|line| line.width() == 0)
+        {
+            pending_marker_line |= self.current_initial_indent != self.current_subsequent_indent;
+            self.current_line_content = None;
+            self.current_initial_indent.clear();
+            self.current_subsequent_indent.clear();
+        }
+        self.flush_current_line();
+        let prefix_width = self.prefix_width();
+        if self
+            .text
+            .last()
+            .is_some_and(|line| line.line.width() > prefix_width)
+        {
+            self.push_blank_line();
+        }
+
+        let border = table_separator_style();
+        let width = rows.iter().map(|row| row.width()).max().unwrap_or(0);
+        let fits = self
+            .wrap_width
+            .is_none_or(|wrap_width| prefix_width + width + 4 <= wrap_width);
+        if fits {
+            let rule = "─".repeat(width + 2);
+            let mut lines = vec![Line::from(Span::styled(format!("┌{rule}┐"), border))];
+            for row in rows {
+                let padding = " ".repeat(width.saturating_sub(row.width()));
+                lines.push(Line::from(vec![
+                    Span::styled("│ ", border),
+                    Span::styled(row, self.styles.math),
+                    Span::styled(format!("{padding} │"), border),
+                ]));
+            }
+            lines.push(Line::from(Span::styled(format!("└{rule}┘"), border)));
+            for line in lines {
+                self.push_prewrapped_line(HyperlinkLine::new(line), pending_marker_line);
+                pending_marker_line = false;
+            }
+        } else {
+            self.pending_marker_line = pending_marker_line;
+            for row in rows {
+                self.push_line(Line::default());
+                self.push_span(Span::styled("│ ", border));
+                self.push_span(Span::styled(row, self.styles.math));
+            }
+            self.flush_current_line();
+        }
+        self.pending_marker_line = false;
+        self.needs_newline = true;
+    }
+
     fn html(&mut self, html: CowStr<'a>, inline: bool) {
         if self.suppressing_local_link_label() {
             return;
@@ -781,6 +1248,10 @@ where
             return;
         }
         self.line_ends_with_local_link_target = false;
+        // A display formula already ended the row.
+        if self.needs_newline && self.in_paragraph {
+            return;
+        }
         self.push_line(Line::default());
     }
 
@@ -809,6 +1280,7 @@ where
         }
         self.flush_current_line();
         self.list_item_start_line_counts.push(self.text.len());
+        self.list_item_task_done.push(false);
         self.pending_marker_line = true;
         let depth = self.list_indices.len();
         let is_ordered = self
@@ -848,12 +1320,125 @@ where
         self.needs_newline = false;
     }
 
+    /// Top-level definition lists put each term on its own line with its definitions indented
+    /// below. Inside list items there is little room for that, so they stay compact as
+    /// `term: definition` on the item's line, which is also how `- [file](path)\n  : note`
+    /// keeps rendering.
+    fn start_definition_list(&mut self) {
+        self.flush_current_line();
+        let compact = !self.list_indices.is_empty();
+        if self.needs_newline && !compact {
+            self.push_blank_line();
+        }
+        self.definition_lists.push(DefinitionListState {
+            compact,
+            definitions_in_entry: 0,
+        });
+        self.needs_newline = false;
+    }
+
+    fn end_definition_list(&mut self) {
+        self.flush_current_line();
+        self.definition_lists.pop();
+        self.needs_newline = true;
+    }
+
+    fn start_definition_title(&mut self) {
+        self.flush_current_line();
+        if let Some(list) = self.definition_lists.last_mut() {
+            list.definitions_in_entry = 0;
+        }
+        if self.needs_newline && !self.in_compact_definition_list() {
+            self.push_blank_line();
+        }
+        self.needs_newline = false;
+        self.push_line(Line::default());
+        self.push_inline_style(self.styles.definition_term);
+    }
+
+    fn end_definition_title(&mut self) {
+        self.pop_inline_style();
+        if !self.in_compact_definition_list() {
+            self.flush_current_line();
+            self.flush_block_trailers();
+        }
+        self.needs_newline = false;
+    }
+
+    /// Consecutive definitions of one term are not separated by blank lines.
+    fn start_definition(&mut self) {
+        let Some(list) = self.definition_lists.last_mut() else {
+            return;
+        };
+        list.definitions_in_entry += 1;
+        if list.compact {
+            let separator = if list.definitions_in_entry == 1 {
+                ": "
+            } else {
+                "; "
+            };
+            self.push_span(Span::from(separator));
+        } else {
+            self.flush_current_line();
+            self.indent_stack.push(IndentContext::new(
+                vec![Span::from(DEFINITION_INDENT)],
+                /*marker*/ None,
+                /*is_list*/ false,
+            ));
+        }
+        self.needs_newline = false;
+    }
+
+    fn end_definition(&mut self) {
+        if self.in_compact_definition_list() {
+            self.needs_newline = false;
+            return;
+        }
+        self.flush_current_line();
+        self.flush_block_trailers();
+        self.indent_stack.pop();
+        self.needs_newline = true;
+    }
+
+    fn in_compact_definition_list(&self) -> bool {
+        self.definition_lists
+            .last()
+            .is_some_and(|list| list.compact)
+    }
+
+    /// Renders a `[ ]` / `[x]` task marker, which pulldown-cmark emits right
+    /// after the item starts.  Bullets swap their dash for the checkbox;
+    /// numbered items keep the number and get the checkbox after it.
+    fn task_list_marker(&mut self, checked: bool) {
+        if self.pending_marker_line {
+            self.push_line(Line::default());
+        }
+        let (symbol, style) = if checked {
+            (TASK_DONE_MARKER, self.styles.task_done_marker)
+        } else {
+            (TASK_OPEN_MARKER, self.styles.task_marker)
+        };
+        match self.current_initial_indent.last_mut() {
+            Some(marker) if marker.content.ends_with("- ") => {
+                let indent = &marker.content[..marker.content.len() - "- ".len()];
+                *marker = Span::styled(format!("{indent}{symbol} "), style);
+            }
+            _ => self.push_span(Span::styled(format!("{symbol} "), style)),
+        }
+        if checked && let Some(done) = self.list_item_task_done.last_mut() {
+            *done = true;
+            self.push_inline_style(self.styles.task_done);
+        }
+    }
+
     fn start_codeblock(&mut self, lang: Option<String>, indent: Option<Span<'static>>) {
         self.flush_current_line();
         if !self.text.is_empty() {
             self.push_blank_line();
         }
         self.in_code_block = true;
+        self.code_block_fenced = indent.is_none();
+        self.code_block_start = self.text.len();
 
         // Extract the language token from the info string.  CommonMark info
         // strings can contain metadata after the language, separated by commas,
@@ -876,25 +1461,86 @@ where
     }
 
     fn end_codeblock(&mut self) {
-        // If we buffered code for a known language, syntax-highlight it now.
+        let code = std::mem::take(&mut self.code_block_buffer);
+        let max_lines = if self.code_block_fenced {
+            self.code_block_max_lines
+        } else {
+            0
+        };
+        // If we buffered code for a known language, syntax-highlight it now. Lines hidden by
+        // collapsing are only highlighted if the transcript overlay asks for them.
         if let Some(lang) = self.code_block_lang.take() {
-            let code = std::mem::take(&mut self.code_block_buffer);
-            if !code.is_empty() {
-                let highlighted = highlight_code_to_lines(&code, &lang);
-                for hl_line in highlighted {
-                    self.push_line(Line::default());
-                    for span in hl_line.spans {
-                        self.push_span(span);
-                    }
-                }
+            if let Some([above, hidden, below]) = split_long_code(&code, max_lines) {
+                self.push_highlighted_code(above, &lang);
+                self.push_hidden_code_row(HiddenCode {
+                    lang: Some(lang.clone()),
+                    code: hidden.to_string(),
+                });
+                self.push_highlighted_code(below, &lang);
+            } else if !code.is_empty() {
+                self.push_highlighted_code(&code, &lang);
             }
+        } else if let Some([_, hidden, _]) = split_long_code(&code, max_lines) {
+            self.collapse_rendered_code(HiddenCode {
+                lang: None,
+                code: hidden.to_string(),
+            });
         }
+        self.tag_code_block_rows(&code);
 
         self.needs_newline = true;
         self.in_code_block = false;
         self.indent_stack.pop();
     }
 
+    fn push_highlighted_code(&mut self, code: &str, lang: &str) {
+        for hl_line in highlight_code_lines(code, lang) {
+            // Diff lines carry their add/remove background on the line.
+            self.push_line(Line::default().style(hl_line.style));
+            self.push_annotated(annotate_links_in_line(
+                Line::from(hl_line.spans),
+                self.cwd.as_deref(),
+            ));
+        }
+    }
+
+    /// Marks every row of the code block that just ended with its unstyled `code`, without the
+    /// final line break, so the whole block can be copied from any of its rows.
+    fn tag_code_block_rows(&mut self, code: &str) {
+        self.flush_current_line();
+        let source: Arc<str> = Arc::from(code.strip_suffix('\n').unwrap_or(code));
+        for line in &mut self.text[self.code_block_start..] {
+            line.code_block = Some(Arc::clone(&source));
+        }
+    }
+
+    /// Adds the `… N lines hidden …` row of a collapsed code block.
+    fn push_hidden_code_row(&mut self, hidden: HiddenCode) {
+        let hidden_lines = i64::try_from(hidden.line_count()).unwrap_or(i64::MAX);
+        self.push_line(Line::default());
+        self.push_span(format!("… {} lines hidden …", format_with_separators(hidden_lines)).dim());
+        if let Some(line) = self.current_line_content.as_mut() {
+            line.hidden_code = Some(Arc::new(hidden));
+        }
+        self.flush_current_line();
+    }
+
+    /// Collapses a code block without a language, whose rows were rendered as its text arrived,
+    /// by replacing the rows of its `hidden` lines.
+    fn collapse_rendered_code(&mut self, hidden: HiddenCode) {
+        self.flush_current_line();
+        let kept = self.code_block_max_lines / 2;
+        let rows = self.text.len() - self.code_block_start;
+        // Each source line renders as one unwrapped row; anything else is left in full.
+        if rows != 2 * kept + hidden.line_count() {
+            return;
+        }
+        let below = self.text.split_off(self.text.len() - kept);
+        self.text.truncate(self.code_block_start + kept);
+        self.push_hidden_code_row(hidden);
+        self.text.extend(below);
+    }
+
     fn start_table(&mut self, alignments: Vec<Alignment>) {
         self.flush_current_line();
         if self.needs_newline {
@@ -929,6 +1575,7 @@ where
             self.push_hyperlink_line(spillover_line);
             self.flush_current_line();
         }
+        self.flush_link_footnotes();
         self.needs_newline = true;
     }
 
@@ -1056,19 +1703,28 @@ where
             .link
             .as_ref()
             .and_then(|link| web_destination(&link.destination));
-        let mut annotated = if let Some(destination) = destination {
+        let annotated = if let Some(destination) = destination {
             let mut annotated = HyperlinkLine::new(Line::default());
             annotated.push_span(span, Some(&destination));
             annotated
         } else if self.link.is_some() || self.in_code_block {
             HyperlinkLine::new(Line::from(span))
         } else {
-            annotate_web_urls_in_line(Line::from(span))
+            annotate_file_references_in_text(
+                text,
+                style,
+                style.patch(self.styles.code),
+                self.cwd.as_deref(),
+            )
         };
+        self.push_annotated_to_table_cell(annotated);
+    }
+
+    fn push_annotated_to_table_cell(&mut self, annotated: HyperlinkLine) {
         if let Some(table_state) = self.table_state.as_mut()
             && let Some(cell) = table_state.current_cell.as_mut()
         {
-            cell.push_annotated(std::mem::take(&mut annotated));
+            cell.push_annotated(annotated);
         }
     }
 
@@ -1131,6 +1787,52 @@ where
                 .bold();
         let separator_style = table_separator_style();
 
+        if self.table_overflow == TableOverflow::Scroll
+            && let Some(natural_widths) =
+                Self::compute_column_widths(&metrics, /*available_width*/ None)
+            && available_width.is_some_and(|width| natural_widths.iter().sum::<usize>() > width)
+        {
+            let mut table_lines = self.render_table_grid(
+                &header,
+                &rows,
+                &natural_widths,
+                &table_state.alignments,
+                &[],
+                header_style,
+                separator_style,
+            );
+            for line in &mut table_lines {
+                line.scrollable = true;
+            }
+            return RenderedTableLines {
+                table_lines,
+                table_lines_prewrapped: true,
+                spillover_lines,
+            };
+        }
+
+        let grid_is_readable = widths.as_ref().is_some_and(|column_widths| {
+            !table_key_value::should_render_records(&rows, column_widths, &metrics)
+        });
+        if self.table_overflow == TableOverflow::Fold
+            && !rows.is_empty()
+            && !grid_is_readable
+            && let Some(table_lines) = self.render_folded_table(
+                &header,
+                &rows,
+                &metrics,
+                &table_state.alignments,
+                header_style,
+                separator_style,
+            )
+        {
+            return RenderedTableLines {
+                table_lines,
+                table_lines_prewrapped: true,
+                spillover_lines,
+            };
+        }
+
         let Some(column_widths) = widths else {
             if !rows.is_empty() {
                 return RenderedTableLines {
@@ -1157,7 +1859,7 @@ where
             };
         };
 
-        if table_key_value::should_render_records(&rows, &column_widths, &metrics) {
+        if !grid_is_readable {
             return RenderedTableLines {
                 table_lines: table_key_value::render_records(
                     &header,
@@ -1172,38 +1874,130 @@ where
             };
         }
 
+        RenderedTableLines {
+            table_lines: self.render_table_grid(
+                &header,
+                &rows,
+                &column_widths,
+                &table_state.alignments,
+                &[],
+                header_style,
+                separator_style,
+            ),
+            table_lines_prewrapped: true,
+            spillover_lines,
+        }
+    }
+
+    /// Render the header, separator, and body rows of an aligned grid.
+    ///
+    /// `row_details` holds lines drawn under each body row, before the row
+    /// separator; rows without an entry get none.
+    #[allow(clippy::too_many_arguments)]
+    fn render_table_grid(
+        &self,
+        header: &[TableCell],
+        rows: &[Vec<TableCell>],
+        column_widths: &[usize],
+        alignments: &[Alignment],
+        row_details: &[Vec<HyperlinkLine>],
+        header_style: Style,
+        separator_style: Style,
+    ) -> Vec<HyperlinkLine> {
         let mut out = Vec::with_capacity(2 + rows.len() * 2);
-        out.extend(self.render_table_row(
-            &header,
-            &column_widths,
-            &table_state.alignments,
-            header_style,
-        ));
+        out.extend(self.render_table_row(header, column_widths, alignments, header_style));
         out.push(Self::render_table_separator(
-            &column_widths,
+            column_widths,
             TABLE_HEADER_SEPARATOR_CHAR,
             separator_style,
         ));
         for (row_idx, row) in rows.iter().enumerate() {
-            out.extend(self.render_table_row(
-                row,
-                &column_widths,
-                &table_state.alignments,
-                Style::default(),
-            ));
+            out.extend(self.render_table_row(row, column_widths, alignments, Style::default()));
+            if let Some(details) = row_details.get(row_idx) {
+                out.extend(details.iter().cloned());
+            }
             if row_idx + 1 < rows.len() {
                 out.push(Self::render_table_separator(
-                    &column_widths,
+                    column_widths,
                     TABLE_BODY_SEPARATOR_CHAR,
                     separator_style,
                 ));
             }
         }
-        RenderedTableLines {
-            table_lines: out,
-            table_lines_prewrapped: true,
-            spillover_lines,
+        out
+    }
+
+    /// Render a table that does not fit as a grid by folding columns out of it.
+    ///
+    /// Columns leave the grid least important first (see
+    /// [`TableColumnKind::fold_priority`]), rightmost first within a kind,
+    /// until the remaining grid fits readably; the folded columns are listed
+    /// as `key: value` lines under each row. The first column always stays.
+    /// Returns `None` when even the first column alone does not fit.
+    fn render_folded_table(
+        &self,
+        header: &[TableCell],
+        rows: &[Vec<TableCell>],
+        metrics: &[TableColumnMetrics],
+        alignments: &[Alignment],
+        header_style: Style,
+        separator_style: Style,
+    ) -> Option<Vec<HyperlinkLine>> {
+        let mut fold_order: Vec<usize> = (1..metrics.len()).collect();
+        fold_order.sort_by_key(|&column| (metrics[column].kind.fold_priority(), Reverse(column)));
+
+        for folded_count in 1..=fold_order.len() {
+            let (kept, folded): (Vec<usize>, Vec<usize>) =
+                (0..metrics.len()).partition(|column| !fold_order[..folded_count].contains(column));
+            let pick = |cells: &[TableCell], columns: &[usize]| -> Vec<TableCell> {
+                columns
+                    .iter()
+                    .map(|&column| cells[column].clone())
+                    .collect()
+            };
+            let kept_rows: Vec<Vec<TableCell>> = rows.iter().map(|row| pick(row, &kept)).collect();
+            let kept_metrics: Vec<TableColumnMetrics> =
+                kept.iter().map(|&column| metrics[column].clone()).collect();
+            let Some(column_widths) =
+                Self::compute_column_widths(&kept_metrics, self.available_table_width(kept.len()))
+            else {
+                continue;
+            };
+            if table_key_value::should_render_records(&kept_rows, &column_widths, &kept_metrics) {
+                continue;
+            }
+
+            let folded_header = pick(header, &folded);
+            let folded_metrics: Vec<TableColumnMetrics> = folded
+                .iter()
+                .map(|&column| metrics[column].clone())
+                .collect();
+            let row_details: Vec<Vec<HyperlinkLine>> = rows
+                .iter()
+                .map(|row| {
+                    table_key_value::render_records(
+                        &folded_header,
+                        &[pick(row, &folded)],
+                        &folded_metrics,
+                        self.available_record_width(),
+                        header_style,
+                        separator_style,
+                    )
+                })
+                .collect();
+            let kept_alignments: Vec<Alignment> =
+                kept.iter().map(|&column| alignments[column]).collect();
+            return Some(self.render_table_grid(
+                &pick(header, &kept),
+                &kept_rows,
+                &column_widths,
+                &kept_alignments,
+                &row_details,
+                header_style,
+                separator_style,
+            ));
         }
+        None
     }
 
     fn normalize_row(row: &mut Vec<TableCell>, column_count: usize) {
@@ -1577,40 +2371,41 @@ where
                 if index > 0 {
                     out.push_span(" ".into(), /*destination*/ None);
                 }
-                let text = line
-                    .line
-                    .spans
-                    .iter()
-                    .map(|span| span.content.as_ref())
-                    .collect::<String>();
-                let mut column = 0usize;
-                let mut current_destination = None;
-                let mut current_text = String::new();
                 let flush = |out: &mut HyperlinkLine,
                              current_text: &mut String,
+                             style: Style,
                              destination: Option<&str>| {
                     if !current_text.is_empty() {
-                        out.push_span(Span::raw(std::mem::take(current_text)), destination);
+                        out.push_span(
+                            Span::styled(std::mem::take(current_text), style),
+                            destination,
+                        );
                     }
                 };
-                for ch in text.chars() {
-                    let destination = line
-                        .hyperlinks
-                        .iter()
-                        .find(|link| link.columns.contains(&column))
-                        .map(|link| link.destination.as_str());
-                    if destination != current_destination {
-                        flush(&mut out, &mut current_text, current_destination);
-                        current_destination = destination;
-                    }
-                    if ch == '|' {
-                        current_text.push_str("\\|");
-                    } else {
-                        current_text.push(ch);
+                // Split at span and link boundaries so each piece keeps its style and destination.
+                let mut column = 0usize;
+                for span in &line.line.spans {
+                    let mut current_destination = None;
+                    let mut current_text = String::new();
+                    for (_, grapheme, width) in graphemes_with_width(&span.content) {
+                        let destination = line
+                            .hyperlinks
+                            .iter()
+                            .find(|link| link.columns.contains(&column))
+                            .map(|link| link.destination.as_str());
+                        if destination != current_destination {
+                            flush(&mut out, &mut current_text, span.style, current_destination);
+                            current_destination = destination;
+                        }
+                        if grapheme == "|" {
+                            current_text.push_str("\\|");
+                        } else {
+                            current_text.push_str(grapheme);
+                        }
+                        column += width;
                     }
-                    column += UnicodeWidthChar::width(ch).unwrap_or(/*default*/ 0);
+                    flush(&mut out, &mut current_text, span.style, current_destination);
                 }
-                flush(&mut out, &mut current_text, current_destination);
             }
             out.push_span(" |".into(), /*destination*/ None);
         }
@@ -1804,6 +2599,7 @@ where
                 None
             },
             destination: dest_url,
+            label: String::new(),
         });
     }
 
@@ -1813,32 +2609,7 @@ where
                 self.pop_inline_style();
             }
             if link.show_destination {
-                // Link destinations are rendered as " (url)" suffixes. When parsing table cells,
-                // append the suffix into the active cell buffer rather than the outer paragraph
-                // line to avoid detached url lines.
-                if self.in_table_cell() {
-                    self.push_span_to_table_cell(" (".into());
-                    let mut destination = HyperlinkLine::new(Line::default());
-                    destination.push_span(
-                        Span::styled(link.destination.clone(), self.styles.link),
-                        web_destination(&link.destination).as_deref(),
-                    );
-                    if let Some(table_state) = self.table_state.as_mut()
-                        && let Some(cell) = table_state.current_cell.as_mut()
-                    {
-                        cell.push_annotated(destination);
-                    }
-                    self.push_span_to_table_cell(")".into());
-                } else {
-                    self.push_span(" (".into());
-                    let mut destination = HyperlinkLine::new(Line::default());
-                    destination.push_span(
-                        Span::styled(link.destination.clone(), self.styles.link),
-                        web_destination(&link.destination).as_deref(),
-                    );
-                    self.push_annotated(destination);
-                    self.push_span(")".into());
-                }
+                self.push_link_destination(&link.label, link.destination);
             } else if let Some(local_target_display) = link.local_target_display {
                 // Local file links are rendered as code-like path text so the transcript shows the
                 // resolved target instead of arbitrary caller-provided label text.
@@ -1848,20 +2619,177 @@ where
                     .copied()
                     .unwrap_or_default()
                     .patch(self.styles.code);
-                let span = Span::styled(local_target_display, style);
+                let target = annotate_file_reference_span(
+                    Span::styled(local_target_display, style),
+                    self.cwd.as_deref(),
+                );
                 if self.in_table_cell() {
-                    self.push_span_to_table_cell(span);
+                    self.push_annotated_to_table_cell(target);
                 } else {
                     if self.pending_marker_line {
                         self.push_line(Line::default());
                     }
-                    self.push_span(span);
+                    self.push_annotated(target);
                     self.line_ends_with_local_link_target = true;
                 }
             }
         }
     }
 
+    /// Shows a link's destination where `tui.link_destinations` asks for it.
+    fn push_link_destination(&mut self, label: &str, destination: String) {
+        if label_repeats_destination(label, &destination) {
+            return;
+        }
+        let as_footnote = match self.link_destinations {
+            LinkDestinations::Inline => false,
+            LinkDestinations::InlineIfShort => {
+                destination.width() > SHORT_LINK_DESTINATION_MAX_COLUMNS
+            }
+            LinkDestinations::Footnotes => true,
+            LinkDestinations::HyperlinkOnly => {
+                // The label carries the hyperlink; without terminal support the destination
+                // would be lost, so it stays inline.
+                if web_destination(&destination).is_some() && terminal_capabilities().hyperlinks {
+                    return;
+                }
+                false
+            }
+        };
+        if as_footnote {
+            self.push_footnote_marker(destination);
+        } else {
+            self.push_destination_suffix(&destination);
+        }
+    }
+
+    /// Marks a link with its `[n]` footnote number; the footnote itself is listed when the block
+    /// ends. Links to the same destination within a block share a number.
+    fn push_footnote_marker(&mut self, destination: String) {
+        let index = match self
+            .pending_link_footnotes
+            .iter()
+            .position(|pending| *pending == destination)
+        {
+            Some(index) => index,
+            None => {
+                self.pending_link_footnotes.push(destination);
+                self.pending_link_footnotes.len() - 1
+            }
+        };
+        let marker = Span::styled(format!("[{}]", index + 1), self.styles.link);
+        if self.in_table_cell() {
+            self.push_span_to_table_cell(marker);
+        } else {
+            self.push_span(marker);
+        }
+    }
+
+    /// Lists the current block's link footnotes as `[n]: url` lines below it.
+    fn flush_link_footnotes(&mut self) {
+        for (index, destination) in std::mem::take(&mut self.pending_link_footnotes)
+            .into_iter()
+            .enumerate()
+        {
+            self.flush_current_line();
+            self.push_line(Line::from(Span::styled(
+                format!("[{}]: ", index + 1),
+                self.styles.link,
+            )));
+            let mut annotated = HyperlinkLine::new(Line::default());
+            annotated.push_span(
+                Span::styled(destination.clone(), self.styles.link),
+                web_destination(&destination).as_deref(),
+            );
+            self.push_annotated(annotated);
+            self.flush_current_line();
+        }
+    }
+
+    /// Renders a link or image destination as a " (url)" suffix. When parsing table cells, the
+    /// suffix goes into the active cell buffer rather than the outer paragraph line to avoid
+    /// detached url lines.
+    fn push_destination_suffix(&mut self, destination: &str) {
+        let mut annotated = HyperlinkLine::new(Line::default());
+        annotated.push_span(
+            Span::styled(destination.to_string(), self.styles.link),
+            web_destination(destination).as_deref(),
+        );
+        if self.in_table_cell() {
+            self.push_span_to_table_cell(" (".into());
+            self.push_annotated_to_table_cell(annotated);
+            self.push_span_to_table_cell(")".into());
+        } else {
+            self.push_span(" (".into());
+            self.push_annotated(annotated);
+            self.push_span(")".into());
+        }
+    }
+
+    /// Images render as an `[image: alt]` placeholder followed by their destination, since the
+    /// transcript is text. Alt text arrives as ordinary text events between the image tags.
+    fn start_image(&mut self, dest_url: String) {
+        self.push_inline_style(self.styles.image);
+        self.text(CowStr::Borrowed("[image"));
+        self.image = Some(ImageState {
+            destination: dest_url,
+            has_alt: false,
+        });
+    }
+
+    fn end_image(&mut self) {
+        let Some(image) = self.image.take() else {
+            return;
+        };
+        self.text(CowStr::Borrowed("]"));
+        self.pop_inline_style();
+        if image.destination.is_empty() || self.suppressing_local_link_label() {
+            return;
+        }
+        self.push_destination_suffix(&image.destination);
+        if self.in_table_cell() {
+            return;
+        }
+        let max_columns = self
+            .wrap_width
+            .unwrap_or(UNWRAPPED_IMAGE_MAX_COLUMNS)
+            .saturating_sub(self.prefix_width());
+        if let Some(inline_image) =
+            inline_images::load(&image.destination, self.cwd.as_deref(), max_columns)
+        {
+            self.pending_images.push(inline_image);
+        }
+    }
+
+    /// Ends a block: lists its link footnotes, then draws its inline images.
+    fn flush_block_trailers(&mut self) {
+        self.flush_link_footnotes();
+        self.flush_pending_images();
+    }
+
+    /// Reserves blank rows below the block that just ended for each of its inline images. The
+    /// image rides on the last reserved row and is drawn when that row reaches scrollback.
+    fn flush_pending_images(&mut self) {
+        for mut image in std::mem::take(&mut self.pending_images) {
+            self.flush_current_line();
+            image.column = self.prefix_width();
+            for _ in 0..image.rows {
+                self.push_line(Line::default());
+                self.flush_current_line();
+            }
+            if let Some(line) = self.text.last_mut() {
+                line.image = Some(image);
+            }
+        }
+    }
+
+    fn prefix_width(&self) -> usize {
+        self.prefix_spans(/*pending_marker_line*/ false)
+            .iter()
+            .map(|span| span.content.width())
+            .sum()
+    }
+
     fn suppressing_local_link_label(&self) -> bool {
         self.link
             .as_ref()
@@ -1997,10 +2925,17 @@ where
             let mut annotated = HyperlinkLine::new(Line::default());
             annotated.push_span(span, Some(&destination));
             annotated
-        } else if self.link.is_some() || self.in_code_block {
+        } else if self.link.is_some() {
             HyperlinkLine::new(Line::from(span))
+        } else if self.in_code_block {
+            annotate_links_in_line(Line::from(span), self.cwd.as_deref())
         } else {
-            annotate_web_urls_in_line(Line::from(span))
+            annotate_file_references_in_text(
+                text,
+                style,
+                style.patch(self.styles.code),
+                self.cwd.as_deref(),
+            )
         };
         self.push_annotated(annotated);
     }
@@ -2281,6 +3216,9 @@ mod markdown_render_tests {
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
+    use proptest::prelude::*;
+    use ratatui::style::Color;
+    use ratatui::style::Modifier;
     use ratatui::text::Text;
 
     fn lines_to_strings(text: &Text<'_>) -> Vec<String> {
@@ -2900,4 +3838,88 @@ mod tests {
         assert!(!destinations.contains(&code_url));
         assert!(!destinations.contains(&"https://shown.example"));
     }
+
+    fn output_spans(lines: &[HyperlinkLine]) -> Vec<Span<'static>> {
+        lines
+            .iter()
+            .flat_map(|line| line.line.spans.iter().cloned())
+            .collect()
+    }
+
+    #[test]
+    fn pipe_table_fallback_keeps_cell_styles() {
+        let lines = render_markdown_lines_with_width_and_cwd(
+            "| **Name** | `id` |\n| --- | --- |\n",
+            /*width*/ Some(5),
+            /*cwd*/ None,
+        );
+        let spans = output_spans(&lines);
+
+        assert!(
+            spans
+                .iter()
+                .any(|span| span.content == "Name"
+                    && span.style.add_modifier.contains(Modifier::BOLD))
+        );
+        assert!(
+            spans
+                .iter()
+                .any(|span| span.content == "id" && span.style.fg == Some(Color::Cyan))
+        );
+    }
+
+    #[test]
+    fn key_value_table_labels_keep_header_styles() {
+        let markdown = "| `c1` | c2 | c3 | c4 | c5 | c6 |\n| --- | --- | --- | --- | --- | --- |\n| https://example.com/a/very/long/path | 2 | 3 | 4 | 5 | 6 |\n";
+        let lines = render_markdown_lines_with_width_and_cwd(
+            markdown,
+            /*width*/ Some(20),
+            /*cwd*/ None,
+        );
+        let spans = output_spans(&lines);
+
+        assert!(
+            spans
+                .iter()
+                .any(|span| span.content == "c1" && span.style.fg == Some(Color::Cyan))
+        );
+    }
+
+    fn prose() -> impl Strategy<Value = String> {
+        prop::collection::vec("[a-z]{1,12}", 1..60).prop_map(|words| words.join(" "))
+    }
+
+    fn words(lines: &[String]) -> Vec<String> {
+        lines
+            .iter()
+            .flat_map(|line| line.split_whitespace())
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    // Widths start at the longest generated word (plus the list marker), so words are never split.
+    proptest! {
+        #[test]
+        fn wrapped_paragraphs_fit_width_and_keep_words(text in prose(), width in 12usize..80) {
+            let lines = lines_to_strings(&render_markdown_text_with_width(&text, Some(width)));
+
+            for line in &lines {
+                prop_assert!(line.width() <= width, "{:?} is wider than {}", line, width);
+            }
+            prop_assert_eq!(words(&lines), words(&[text]));
+        }
+
+        #[test]
+        fn wrapped_list_items_keep_marker_and_hanging_indent(text in prose(), width in 14usize..80) {
+            let lines =
+                lines_to_strings(&render_markdown_text_with_width(&format!("- {text}"), Some(width)));
+
+            for (index, line) in lines.iter().enumerate() {
+                let prefix = if index == 0 { "- " } else { "  " };
+                prop_assert!(line.starts_with(prefix), "line {} is {:?}", index, line);
+                prop_assert!(line.width() <= width, "{:?} is wider than {}", line, width);
+            }
+            prop_assert_eq!(words(&lines), words(&[format!("- {text}")]));
+        }
+    }
 }
@@ -0,0 +1,511 @@
+//! LaTeX-to-Unicode conversion for `$...$` and `$$...$$` math in markdown.
+//!
+//! Covers what model answers commonly use: Greek letters, operators and relations,
+//! super/subscripts, fractions, roots, accents, and font commands. Anything unrecognized is
+//! kept as written so the formula stays readable instead of silently losing terms.
+
+/// Converts a LaTeX math source to Unicode text, one entry per `\\`-separated row.
+pub(super) fn latex_to_unicode(source: &str) -> Vec<String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut converter = Converter {
+        chars: &chars,
+        pos: 0,
+    };
+    let converted = converter.sequence(/*in_group*/ false);
+    converted
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Upright operator names such as `\sin`, which render as their spelling.
+const FUNCTION_NAMES: &[&str] = &[
+    "arccos", "arcsin", "arctan", "arg", "cos", "cosh", "cot", "csc", "deg", "det", "dim", "exp",
+    "gcd", "inf", "ker", "lg", "lim", "ln", "log", "max", "min", "Pr", "sec", "sin", "sinh", "sup",
+    "tan", "tanh",
+];
+
+struct Converter<'a> {
+    chars: &'a [char],
+    pos: usize,
+}
+
+impl Converter<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += 1;
+        Some(ch)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Converts until the end of input, or through the closing `}` when inside a group.
+    fn sequence(&mut self, in_group: bool) -> String {
+        let mut out = String::new();
+        while let Some(ch) = self.bump() {
+            match ch {
+                '}' if in_group => break,
+                '}' => {}
+                '{' => out.push_str(&self.sequence(/*in_group*/ true)),
+                '\\' => out.push_str(&self.command()),
+                '^' => {
+                    let argument = self.argument();
+                    out.push_str(&script(&argument, superscript_char, '^'));
+                }
+                '_' => {
+                    let argument = self.argument();
+                    out.push_str(&script(&argument, subscript_char, '_'));
+                }
+                '&' | '~' | '\t' => out.push(' '),
+                ch => out.push(ch),
+            }
+        }
+        out
+    }
+
+    /// Reads one command argument: a `{...}` group, a command, or a single character.
+    fn argument(&mut self) -> String {
+        self.skip_whitespace();
+        match self.bump() {
+            Some('{') => self.sequence(/*in_group*/ true),
+            Some('\\') => self.command(),
+            Some(ch) => ch.to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// Reads a `[...]` optional argument if one follows.
+    fn optional_argument(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        if self.peek() != Some('[') {
+            return None;
+        }
+        self.pos += 1;
+        let mut out = String::new();
+        while let Some(ch) = self.bump() {
+            match ch {
+                ']' => break,
+                '{' => out.push_str(&self.sequence(/*in_group*/ true)),
+                '\\' => out.push_str(&self.command()),
+                ch => out.push(ch),
+            }
+        }
+        Some(out)
+    }
+
+    /// Converts the command after a `\`.
+    fn command(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(|ch| ch.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return match self.bump() {
+                Some('\\') => "\n".to_string(),
+                Some(',' | ':' | ';' | ' ' | '>') => " ".to_string(),
+                Some('!') | None => String::new(),
+                Some('|') => "‖".to_string(),
+                Some(ch) => ch.to_string(),
+            };
+        }
+        let name: String = self.chars[start..self.pos].iter().collect();
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let numerator = self.argument();
+                let denominator = self.argument();
+                fraction(numerator.trim(), denominator.trim())
+            }
+            "sqrt" => {
+                let index = self.optional_argument();
+                let radicand = self.argument();
+                root(index.as_deref().map(str::trim), radicand.trim())
+            }
+            "text" | "textrm" | "textit" | "textbf" | "textsf" | "texttt" | "mbox" | "mathrm"
+            | "mathit" | "mathbf" | "mathsf" | "mathtt" | "mathcal" | "mathfrak" | "mathscr"
+            | "boldsymbol" | "operatorname" => self.argument(),
+            "mathbb" => self.argument().chars().map(double_struck_char).collect(),
+            "hat" | "widehat" => accent(&self.argument(), '\u{0302}'),
+            "bar" | "overline" => accent(&self.argument(), '\u{0304}'),
+            "vec" => accent(&self.argument(), '\u{20D7}'),
+            "dot" => accent(&self.argument(), '\u{0307}'),
+            "ddot" => accent(&self.argument(), '\u{0308}'),
+            "tilde" | "widetilde" => accent(&self.argument(), '\u{0303}'),
+            "begin" | "end" => {
+                self.argument();
+                String::new()
+            }
+            "left" | "right" | "bigl" | "bigr" | "Bigl" | "Bigr" | "big" | "Big" | "bigg"
+            | "Bigg" => {
+                // `\left.` is an invisible delimiter.
+                self.skip_whitespace();
+                if self.peek() == Some('.') {
+                    self.pos += 1;
+                }
+                String::new()
+            }
+            "displaystyle" | "textstyle" | "limits" | "nolimits" => String::new(),
+            "quad" | "qquad" => " ".to_string(),
+            "limsup" => "lim sup".to_string(),
+            "liminf" => "lim inf".to_string(),
+            "mod" | "bmod" => " mod ".to_string(),
+            name if FUNCTION_NAMES.contains(&name) => name.to_string(),
+            name => match symbol(name) {
+                Some(symbol) => symbol.to_string(),
+                None => self.unknown_command(name),
+            },
+        }
+    }
+
+    /// Keeps an unrecognized command as written, including a directly following `{...}`
+    /// argument, so its input does not run into the command name.
+    fn unknown_command(&mut self, name: &str) -> String {
+        if self.peek() != Some('{') {
+            return format!("\\{name}");
+        }
+        self.pos += 1;
+        let argument = self.sequence(/*in_group*/ true);
+        format!("\\{name}{{{argument}}}")
+    }
+}
+
+/// Applies `^`/`_` to `text`, falling back to the LaTeX marker when a character has no
+/// Unicode script form.
+fn script(text: &str, map: fn(char) -> Option<char>, marker: char) -> String {
+    let compact: String = text.chars().filter(|ch| !ch.is_whitespace()).collect();
+    if let Some(mapped) = compact.chars().map(map).collect::<Option<String>>()
+        && !mapped.is_empty()
+    {
+        return mapped;
+    }
+    if compact.chars().count() == 1 {
+        format!("{marker}{compact}")
+    } else {
+        format!("{marker}({})", text.trim())
+    }
+}
+
+fn fraction(numerator: &str, denominator: &str) -> String {
+    if let Some(vulgar) = vulgar_fraction(numerator, denominator) {
+        return vulgar.to_string();
+    }
+    format!("{}/{}", group(numerator), group(denominator))
+}
+
+fn root(index: Option<&str>, radicand: &str) -> String {
+    let sign = match index {
+        None | Some("" | "2") => "√".to_string(),
+        Some("3") => "∛".to_string(),
+        Some("4") => "∜".to_string(),
+        Some(index) => format!("{}√", script(index, superscript_char, '^')),
+    };
+    format!("{sign}{}", group(radicand))
+}
+
+/// Wraps compound expressions in parentheses so `/` and `√` bind to the whole term.
+fn group(text: &str) -> String {
+    let compound = text
+        .chars()
+        .any(|ch| ch.is_whitespace() || "+-−±∓×÷·*/=<>≤≥≠,".contains(ch));
+    if compound {
+        format!("({text})")
+    } else {
+        text.to_string()
+    }
+}
+
+fn accent(text: &str, combining: char) -> String {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => format!("{ch}{combining}"),
+        _ => text.to_string(),
+    }
+}
+
+fn vulgar_fraction(numerator: &str, denominator: &str) -> Option<char> {
+    Some(match (numerator, denominator) {
+        ("1", "2") => '½',
+        ("1", "3") => '⅓',
+        ("2", "3") => '⅔',
+        ("1", "4") => '¼',
+        ("3", "4") => '¾',
+        ("1", "5") => '⅕',
+        ("2", "5") => '⅖',
+        ("3", "5") => '⅗',
+        ("4", "5") => '⅘',
+        ("1", "6") => '⅙',
+        ("5", "6") => '⅚',
+        ("1", "7") => '⅐',
+        ("1", "8") => '⅛',
+        ("3", "8") => '⅜',
+        ("5", "8") => '⅝',
+        ("7", "8") => '⅞',
+        ("1", "9") => '⅑',
+        ("1", "10") => '⅒',
+        _ => return None,
+    })
+}
+
+fn superscript_char(ch: char) -> Option<char> {
+    Some(match ch {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        '+' => '⁺',
+        '-' | '−' => '⁻',
+        '=' => '⁼',
+        '(' => '⁽',
+        ')' => '⁾',
+        'a' => 'ᵃ',
+        'b' => 'ᵇ',
+        'c' => 'ᶜ',
+        'd' => 'ᵈ',
+        'e' => 'ᵉ',
+        'f' => 'ᶠ',
+        'g' => 'ᵍ',
+        'h' => 'ʰ',
+        'i' => 'ⁱ',
+        'j' => 'ʲ',
+        'k' => 'ᵏ',
+        'l' => 'ˡ',
+        'm' => 'ᵐ',
+        'n' => 'ⁿ',
+        'o' => 'ᵒ',
+        'p' => 'ᵖ',
+        'r' => 'ʳ',
+        's' => 'ˢ',
+        't' => 'ᵗ',
+        'u' => 'ᵘ',
+        'v' => 'ᵛ',
+        'w' => 'ʷ',
+        'x' => 'ˣ',
+        'y' => 'ʸ',
+        'z' => 'ᶻ',
+        'A' => 'ᴬ',
+        'B' => 'ᴮ',
+        'D' => 'ᴰ',
+        'E' => 'ᴱ',
+        'G' => 'ᴳ',
+        'H' => 'ᴴ',
+        'I' => 'ᴵ',
+        'J' => 'ᴶ',
+        'K' => 'ᴷ',
+        'L' => 'ᴸ',
+        'M' => 'ᴹ',
+        'N' => 'ᴺ',
+        'O' => 'ᴼ',
+        'P' => 'ᴾ',
+        'R' => 'ᴿ',
+        'T' => 'ᵀ',
+        'U' => 'ᵁ',
+        'V' => 'ⱽ',
+        'W' => 'ᵂ',
+        '′' | '*' | '∗' | '∘' => ch,
+        _ => return None,
+    })
+}
+
+fn subscript_char(ch: char) -> Option<char> {
+    Some(match ch {
+        '0' => '₀',
+        '1' => '₁',
+        '2' => '₂',
+        '3' => '₃',
+        '4' => '₄',
+        '5' => '₅',
+        '6' => '₆',
+        '7' => '₇',
+        '8' => '₈',
+        '9' => '₉',
+        '+' => '₊',
+        '-' | '−' => '₋',
+        '=' => '₌',
+        '(' => '₍',
+        ')' => '₎',
+        'a' => 'ₐ',
+        'e' => 'ₑ',
+        'h' => 'ₕ',
+        'i' => 'ᵢ',
+        'j' => 'ⱼ',
+        'k' => 'ₖ',
+        'l' => 'ₗ',
+        'm' => 'ₘ',
+        'n' => 'ₙ',
+        'o' => 'ₒ',
+        'p' => 'ₚ',
+        'r' => 'ᵣ',
+        's' => 'ₛ',
+        't' => 'ₜ',
+        'u' => 'ᵤ',
+        'v' => 'ᵥ',
+        'x' => 'ₓ',
+        'β' => 'ᵦ',
+        'γ' => 'ᵧ',
+        'ρ' => 'ᵨ',
+        'φ' => 'ᵩ',
+        'χ' => 'ᵪ',
+        _ => return None,
+    })
+}
+
+fn double_struck_char(ch: char) -> char {
+    match ch {
+        'C' => 'ℂ',
+        'H' => 'ℍ',
+        'N' => 'ℕ',
+        'P' => 'ℙ',
+        'Q' => 'ℚ',
+        'R' => 'ℝ',
+        'Z' => 'ℤ',
+        ch => ch,
+    }
+}
+
+fn symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" => "ϵ",
+        "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "vartheta" => "ϑ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "varpi" => "ϖ",
+        "rho" => "ρ",
+        "varrho" => "ϱ",
+        "sigma" => "σ",
+        "varsigma" => "ς",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" => "ϕ",
+        "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Upsilon" => "Υ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        "times" => "×",
+        "cdot" => "·",
+        "ast" => "∗",
+        "star" => "⋆",
+        "circ" => "∘",
+        "bullet" => "∙",
+        "div" => "÷",
+        "pm" => "±",
+        "mp" => "∓",
+        "oplus" => "⊕",
+        "otimes" => "⊗",
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "ll" => "≪",
+        "gg" => "≫",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "simeq" => "≃",
+        "cong" => "≅",
+        "propto" => "∝",
+        "perp" => "⊥",
+        "parallel" => "∥",
+        "mid" => "∣",
+        "in" => "∈",
+        "notin" => "∉",
+        "ni" => "∋",
+        "subset" => "⊂",
+        "subseteq" => "⊆",
+        "supset" => "⊃",
+        "supseteq" => "⊇",
+        "cup" => "∪",
+        "cap" => "∩",
+        "setminus" => "∖",
+        "emptyset" | "varnothing" => "∅",
+        "forall" => "∀",
+        "exists" => "∃",
+        "nexists" => "∄",
+        "neg" | "lnot" => "¬",
+        "land" | "wedge" => "∧",
+        "lor" | "vee" => "∨",
+        "to" | "rightarrow" => "→",
+        "leftarrow" | "gets" => "←",
+        "leftrightarrow" => "↔",
+        "Rightarrow" | "implies" => "⇒",
+        "Leftarrow" => "⇐",
+        "Leftrightarrow" | "iff" => "⇔",
+        "mapsto" => "↦",
+        "uparrow" => "↑",
+        "downarrow" => "↓",
+        "infty" => "∞",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "sum" => "∑",
+        "prod" => "∏",
+        "coprod" => "∐",
+        "int" => "∫",
+        "iint" => "∬",
+        "iiint" => "∭",
+        "oint" => "∮",
+        "bigcup" => "⋃",
+        "bigcap" => "⋂",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "vdots" => "⋮",
+        "ddots" => "⋱",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "lbrace" => "{",
+        "rbrace" => "}",
+        "vert" => "|",
+        "Vert" => "‖",
+        "angle" => "∠",
+        "triangle" => "△",
+        "degree" => "°",
+        "prime" => "′",
+        "hbar" => "ℏ",
+        "ell" => "ℓ",
+        "Re" => "ℜ",
+        "Im" => "ℑ",
+        "aleph" => "ℵ",
+        "therefore" => "∴",
+        "because" => "∵",
+        _ => return None,
+    })
+}
@@ -6,11 +6,11 @@
 use super::DecodedTextMerge;
 use super::Event;
 use super::HyperlinkLine;
-use super::Options;
 use super::Parser;
 use super::Tag;
 use super::Writer;
 use super::never_hide_link_destination;
+use super::parser_options;
 use std::ops::Range;
 use std::path::Path;
 
@@ -35,10 +35,7 @@ pub(crate) fn render_streaming_markdown_lines_with_width_and_cwd(
     width: Option<usize>,
     cwd: Option<&Path>,
 ) -> StreamingMarkdownRender {
-    let mut options = Options::empty();
-    options.insert(Options::ENABLE_STRIKETHROUGH);
-    options.insert(Options::ENABLE_TABLES);
-    let parser = Parser::new_ext(input, options);
+    let parser = Parser::new_ext(input, parser_options());
     let has_reference_link_definition = parser.reference_definitions().iter().next().is_some();
     let parser = TopLevelBlockTracker {
         iter: DecodedTextMerge::new(parser.into_offset_iter()),
@@ -164,12 +164,11 @@ fn render_aligned_field(
     for (line_index, value_line) in wrapped_value.into_iter().enumerate() {
         let mut spans = Vec::new();
         if line_index == 0 {
-            let label = header.plain_text();
             spans.push(Span::raw(" ".repeat(FIELD_LEADING_PADDING)));
-            spans.push(Span::styled(label.clone(), label_style));
-            spans.push(Span::raw(
-                " ".repeat(label_width.saturating_sub(label.width()) + FIELD_GAP),
-            ));
+            spans.extend(header.styled_spans(label_style));
+            spans.push(Span::raw(" ".repeat(
+                label_width.saturating_sub(header.plain_text().width()) + FIELD_GAP,
+            )));
         } else {
             spans.push(Span::raw(" ".repeat(value_indent)));
         }
@@ -187,7 +186,7 @@ fn render_stacked_field(
     let label_width = available_width
         .map(|width| width.saturating_sub(FIELD_LEADING_PADDING).max(1))
         .unwrap_or_else(|| header.plain_text().width().max(1));
-    let label = Line::from(Span::styled(header.plain_text(), label_style));
+    let label = Line::from(header.styled_spans(label_style));
     let mut wrapped_labels = Vec::new();
     push_owned_lines(
         &word_wrap_line(&label, RtOptions::new(label_width)),
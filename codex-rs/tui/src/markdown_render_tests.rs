@@ -1,4 +1,5 @@
 use pretty_assertions::assert_eq;
+use ratatui::style::Color;
 use ratatui::style::Modifier;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
@@ -7,11 +8,21 @@ use ratatui::text::Text;
 use std::path::Path;
 
 use crate::markdown_render::COLON_LOCATION_SUFFIX_RE;
+use crate::markdown_render::DecodedTextMerge;
 use crate::markdown_render::HASH_LOCATION_SUFFIX_RE;
+use crate::markdown_render::HyperlinkLine;
+use crate::markdown_render::LinkDestinations;
+use crate::markdown_render::Parser;
+use crate::markdown_render::TableOverflow;
+use crate::markdown_render::Writer;
+use crate::markdown_render::last_code_block;
+use crate::markdown_render::never_hide_link_destination;
+use crate::markdown_render::parser_options;
 use crate::markdown_render::render_markdown_lines_with_width_and_cwd;
 use crate::markdown_render::render_markdown_text;
 use crate::markdown_render::render_markdown_text_with_width;
 use crate::markdown_render::render_markdown_text_with_width_and_cwd;
+use crate::markdown_render::visible_lines;
 use insta::assert_debug_snapshot;
 use insta::assert_snapshot;
 
@@ -233,6 +244,40 @@ fn blockquote_nested_two_levels() {
     assert_eq!(text, expected);
 }
 
+#[test]
+fn github_alert_renders_label_and_colored_border() {
+    let text = render_markdown_text("> [!WARNING]\n> Back up first.\n");
+    let expected = Text::from_iter([
+        Line::from_iter(["│ ".yellow(), "⚠ Warning".yellow().bold()]),
+        Line::from_iter(["│ ".yellow(), Span::from("Back up first.")]),
+    ]);
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn github_alerts_keep_border_across_paragraphs_and_kinds() {
+    let md = "> [!NOTE]\n> one\n>\n> two\n\n> [!CAUTION]\n> careful\n\n> [!UNKNOWN]\n> plain\n";
+    let text = render_markdown_text(md);
+    assert_eq!(
+        plain_lines(&text),
+        vec![
+            "│ ⓘ Note",
+            "│ one",
+            "│ ",
+            "│ two",
+            "",
+            "│ ✗ Caution",
+            "│ careful",
+            "",
+            "> [!UNKNOWN]",
+            "> plain",
+        ]
+    );
+    assert_eq!(text.lines[0].spans[0].style.fg, Some(Color::Blue));
+    assert_eq!(text.lines[5].spans[0].style.fg, Some(Color::Red));
+    assert_eq!(text.lines[8].style.fg, Some(Color::Green));
+}
+
 #[test]
 fn blockquote_with_list_items() {
     let md = "> - item 1\n> - item 2\n";
@@ -612,6 +657,66 @@ fn list_ordered_custom_start() {
     assert_eq!(text, expected);
 }
 
+#[test]
+fn task_list_items_render_checkboxes() {
+    let text = render_markdown_text("- [ ] Open\n- [x] Done\n  - [x] Nested\n");
+    let expected = Text::from_iter([
+        Line::from_iter(["☐ ".cyan(), "Open".into()]),
+        Line::from_iter(["☑ ".green(), "Done".dim()]),
+        Line::from_iter(["    ☑ ".green(), "Nested".dim()]),
+    ]);
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn ordered_task_list_keeps_numbers() {
+    let text = render_markdown_text("1. [x] Ship it\n2. [ ] Announce\n");
+    let expected = Text::from_iter([
+        Line::from_iter(["1. ".light_blue(), "☑ ".green(), "Ship it".dim()]),
+        Line::from_iter(["2. ".light_blue(), "☐ ".cyan(), "Announce".into()]),
+    ]);
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn definition_list_renders_bold_terms_with_indented_definitions() {
+    let text = render_markdown_text(
+        "GET\n: Reads a resource\n: Never has a body\n\nPOST\n: Creates a resource\n\nAfter\n",
+    );
+    let expected = Text::from_iter([
+        Line::from("GET".bold()),
+        Line::from_iter(["    ", "Reads a resource"]),
+        Line::from_iter(["    ", "Never has a body"]),
+        Line::default(),
+        Line::from("POST".bold()),
+        Line::from_iter(["    ", "Creates a resource"]),
+        Line::default(),
+        Line::from("After"),
+    ]);
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn definition_list_inside_list_item_stays_on_the_item_line() {
+    let text = render_markdown_text("- GET\n  : Reads a resource\n  : Never has a body\n");
+    let expected = Text::from(Line::from_iter([
+        "- ".into(),
+        "GET".bold(),
+        ": ".into(),
+        "Reads a resource".into(),
+        "; ".into(),
+        "Never has a body".into(),
+    ]));
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn heading_attributes_are_not_rendered() {
+    let text = render_markdown_text("## Setup {#setup .note}\n");
+    let expected = Text::from(Line::from_iter(["## ".bold(), "Setup".bold()]));
+    assert_eq!(text, expected);
+}
+
 #[test]
 fn nested_unordered_in_ordered() {
     let md = "1. Outer\n    - Inner A\n    - Inner B\n2. Next\n";
@@ -806,6 +911,63 @@ fn file_link_appends_line_number_when_label_lacks_it() {
     assert_eq!(text, expected);
 }
 
+#[test]
+fn file_references_register_open_actions() {
+    let lines = render_markdown_lines_with_width_and_cwd(
+        "Fix src/lib.rs:42, `core/mod.rs:7`, and [mod.rs](/repo/core/mod.rs:9) but not `run foo.rs:1`.",
+        /*width*/ None,
+        Some(Path::new("/repo")),
+    );
+    let references = lines
+        .iter()
+        .flat_map(|line| line.hyperlinks.iter())
+        .filter_map(crate::terminal_hyperlinks::TerminalHyperlink::file_reference)
+        .map(|reference| (reference.path.display().to_string(), reference.line))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        references,
+        vec![
+            ("/repo/src/lib.rs".to_string(), 42),
+            ("/repo/core/mod.rs".to_string(), 7),
+            ("/repo/core/mod.rs".to_string(), 9),
+        ]
+    );
+    assert_eq!(
+        lines[0].line.spans[1],
+        Span::from("src/lib.rs:42").cyan(),
+        "bare references are drawn like local file links"
+    );
+}
+
+#[test]
+fn code_block_references_and_urls_are_linked() {
+    let lines = render_markdown_lines_with_width_and_cwd(
+        "```rust\n// see src/lib.rs:42 and https://example.com/docs\n```\n\n```\nsrc/main.rs:3:5: error\n```\n",
+        /*width*/ None,
+        Some(Path::new("/repo")),
+    );
+    let links = lines
+        .iter()
+        .flat_map(|line| line.hyperlinks.iter())
+        .map(|link| {
+            link.file_reference().map_or_else(
+                || link.destination.clone(),
+                |reference| format!("{}:{}", reference.path.display(), reference.line),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        links,
+        vec![
+            "/repo/src/lib.rs:42".to_string(),
+            "https://example.com/docs".to_string(),
+            "/repo/src/main.rs:3".to_string(),
+        ]
+    );
+}
+
 #[test]
 fn file_link_keeps_absolute_paths_outside_cwd() {
     let text = render_markdown_text_for_cwd(
@@ -920,6 +1082,39 @@ fn url_link_shows_destination() {
     assert_eq!(text, expected);
 }
 
+#[test]
+fn image_renders_alt_text_and_destination() {
+    let text = render_markdown_text("![Latency plot](https://example.com/plot.png)");
+    let expected = Text::from(Line::from_iter([
+        "[image".dim(),
+        ": Latency plot".dim(),
+        "]".dim(),
+        " (".into(),
+        "https://example.com/plot.png".cyan().underlined(),
+        ")".into(),
+    ]));
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn image_without_alt_text_keeps_local_destination() {
+    let text = render_markdown_text("Before\n\n![](screenshots/login.png)\n\nAfter");
+    let expected = Text::from_iter([
+        Line::from("Before"),
+        Line::default(),
+        Line::from_iter([
+            "[image".dim(),
+            "]".dim(),
+            " (".into(),
+            "screenshots/login.png".cyan().underlined(),
+            ")".into(),
+        ]),
+        Line::default(),
+        Line::from("After"),
+    ]);
+    assert_eq!(text, expected);
+}
+
 #[test]
 fn markdown_render_file_link_snapshot() {
     let text = render_markdown_text_for_cwd(
@@ -1770,6 +1965,72 @@ fn table_falls_back_to_key_value_records_if_grid_cannot_fit() {
     );
 }
 
+fn render_lines_with_table_overflow(
+    md: &str,
+    width: usize,
+    overflow: TableOverflow,
+) -> Vec<HyperlinkLine> {
+    let parser = DecodedTextMerge::new(Parser::new_ext(md, parser_options()).into_offset_iter());
+    let mut writer = Writer::new(
+        md,
+        parser,
+        Some(width),
+        /*cwd*/ None,
+        &never_hide_link_destination,
+    );
+    writer.table_overflow = overflow;
+    writer.run();
+    writer.text
+}
+
+const TEN_COLUMN_TABLE: &str = "| c1 | c2 | c3 | c4 | c5 | c6 | c7 | c8 | c9 | c10 |\n|---|---|---|---|---|---|---|---|---|---|\n| 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10 |\n| a | b | c | d | e | f | g | h | i | j |\n";
+
+#[test]
+fn table_fold_keeps_leading_columns_and_lists_the_rest_under_each_row() {
+    let lines =
+        render_lines_with_table_overflow(TEN_COLUMN_TABLE, /*width*/ 20, TableOverflow::Fold);
+
+    assert_eq!(
+        plain_lines(&Text::from(visible_lines(lines))),
+        vec![
+            " c1     c2     c3",
+            "━━━━━  ━━━━━  ━━━━━",
+            " 1      2      3",
+            " c4   4",
+            " c5   5",
+            " c6   6",
+            " c7   7",
+            " c8   8",
+            " c9   9",
+            " c10  10",
+            "─────  ─────  ─────",
+            " a      b      c",
+            " c4   d",
+            " c5   e",
+            " c6   f",
+            " c7   g",
+            " c8   h",
+            " c9   i",
+            " c10  j",
+        ]
+    );
+}
+
+#[test]
+fn table_scroll_keeps_every_column_at_full_width() {
+    let lines = render_lines_with_table_overflow(
+        TEN_COLUMN_TABLE,
+        /*width*/ 20,
+        TableOverflow::Scroll,
+    );
+
+    assert_eq!(lines.len(), 5);
+    assert!(lines.iter().all(|line| line.scrollable));
+    let text = plain_lines(&Text::from(visible_lines(lines)));
+    assert!(text[0].ends_with("c9     c10"), "{text:?}");
+    assert!(text[2].ends_with("9      10"), "{text:?}");
+}
+
 #[test]
 fn table_key_value_fallback_preserves_rich_values_and_themed_labels() {
     let md = "| Key | Content | Extra | More |\n|---|---|---|---|\n| item | [link](https://example.com) | **bold** | `code` |\n";
@@ -1795,3 +2056,225 @@ fn table_key_value_fallback_preserves_rich_values_and_themed_labels() {
             .any(|span| span.style.add_modifier.contains(Modifier::UNDERLINED))
     }));
 }
+
+#[test]
+fn inline_math_converts_latex_to_unicode() {
+    let text = render_markdown_text(
+        r"Energy $E = mc^2$, angles $\alpha + \beta \leq \pi$, half $\frac{1}{2}$, next $x_{i+1}$, root $\sqrt{a + b}$, ratio $\frac{n}{k+1}$.",
+    );
+    assert_eq!(
+        plain_lines(&text),
+        vec!["Energy E = mc², angles α + β ≤ π, half ½, next xᵢ₊₁, root √(a + b), ratio n/(k+1)."]
+    );
+    let math_span = text.lines[0]
+        .spans
+        .iter()
+        .find(|span| span.content == "E = mc²")
+        .expect("math span");
+    assert!(math_span.style.add_modifier.contains(Modifier::ITALIC));
+}
+
+#[test]
+fn inline_math_keeps_unknown_commands_and_unscriptable_exponents() {
+    let text =
+        render_markdown_text(r"$\mathbb{R}^n$, $e^{i\theta}$, $\lim_{x \to 0} f$, $\weird{x}$");
+    assert_eq!(
+        plain_lines(&text),
+        vec![r"ℝⁿ, e^(iθ), lim_(x → 0) f, \weird{x}"]
+    );
+}
+
+#[test]
+fn display_math_renders_in_a_bordered_block() {
+    let text = render_markdown_text("Area:\n\n$$\\pi r^2 \\\\ \\frac{a}{b}$$\n\nDone.");
+    assert_eq!(
+        plain_lines(&text),
+        vec![
+            "Area:",
+            "",
+            "┌──────┐",
+            "│ π r² │",
+            "│ a/b  │",
+            "└──────┘",
+            "",
+            "Done.",
+        ]
+    );
+}
+
+#[test]
+fn display_math_inside_a_paragraph_gets_its_own_rows() {
+    for markdown in [
+        "We get:\n$$x^2$$\nwhere x > 0",
+        "We get: $$x^2$$ where x > 0",
+    ] {
+        let text = render_markdown_text(markdown);
+        assert_eq!(
+            plain_lines(&text),
+            vec!["We get:", "", "┌────┐", "│ x² │", "└────┘", "where x > 0"],
+            "{markdown:?}"
+        );
+    }
+}
+
+#[test]
+fn display_math_too_wide_for_a_box_falls_back_to_barred_rows() {
+    let text = render_markdown_text_with_width(r"$$a + b + c + d$$", Some(12));
+    assert_eq!(plain_lines(&text), vec!["│ a + b + c", "+ d"]);
+}
+
+#[test]
+fn dollar_amounts_stay_plain_text() {
+    let text = render_markdown_text("It costs $5 and $10 today.");
+    assert_eq!(plain_lines(&text), vec!["It costs $5 and $10 today."]);
+}
+
+fn render_lines_with_link_destinations(md: &str, mode: LinkDestinations) -> Vec<String> {
+    let parser = DecodedTextMerge::new(Parser::new_ext(md, parser_options()).into_offset_iter());
+    let mut writer = Writer::new(
+        md,
+        parser,
+        /*wrap_width*/ None,
+        /*cwd*/ None,
+        &never_hide_link_destination,
+    );
+    writer.link_destinations = mode;
+    writer.run();
+    plain_lines(&Text::from(visible_lines(writer.text)))
+}
+
+#[test]
+fn link_destination_is_skipped_when_the_label_already_shows_it() {
+    let md = "See <https://example.com>, [example.com](https://example.com/), and <mailto:me@example.com>.\n";
+
+    assert_eq!(
+        render_lines_with_link_destinations(md, LinkDestinations::Inline),
+        vec!["See https://example.com, example.com, and mailto:me@example.com."]
+    );
+}
+
+#[test]
+fn footnoted_links_are_listed_below_their_block() {
+    let md = "Read [the docs](https://example.com/docs) and [the guide](https://example.com/guide), then [the docs](https://example.com/docs) again.\n\nNext paragraph with [a link](https://example.org).\n";
+
+    assert_eq!(
+        render_lines_with_link_destinations(md, LinkDestinations::Footnotes),
+        vec![
+            "Read the docs[1] and the guide[2], then the docs[1] again.",
+            "[1]: https://example.com/docs",
+            "[2]: https://example.com/guide",
+            "",
+            "Next paragraph with a link[1].",
+            "[1]: https://example.org",
+        ]
+    );
+}
+
+#[test]
+fn inline_if_short_footnotes_only_long_destinations() {
+    let md = "[short](https://example.com) and [long](https://example.com/a/very/long/path/to/some/page.html)\n";
+
+    assert_eq!(
+        render_lines_with_link_destinations(md, LinkDestinations::InlineIfShort),
+        vec![
+            "short (https://example.com) and long[1]",
+            "[1]: https://example.com/a/very/long/path/to/some/page.html",
+        ]
+    );
+}
+
+fn render_with_code_block_max_lines(md: &str, max_lines: usize) -> Vec<HyperlinkLine> {
+    let parser = DecodedTextMerge::new(Parser::new_ext(md, parser_options()).into_offset_iter());
+    let mut writer = Writer::new(
+        md,
+        parser,
+        /*wrap_width*/ None,
+        /*cwd*/ None,
+        &never_hide_link_destination,
+    );
+    writer.code_block_max_lines = max_lines;
+    writer.run();
+    writer.text
+}
+
+fn numbered_code_block(lang: &str, lines: usize) -> String {
+    let body: String = (1..=lines).map(|line| format!("line {line}\n")).collect();
+    format!("```{lang}\n{body}```\n")
+}
+
+#[test]
+fn long_code_blocks_keep_their_first_and_last_lines() {
+    for lang in ["", "rust"] {
+        let lines = render_with_code_block_max_lines(&numbered_code_block(lang, 10), 4);
+
+        assert_eq!(
+            plain_lines(&Text::from(visible_lines(lines.clone()))),
+            vec![
+                "line 1",
+                "line 2",
+                "… 6 lines hidden …",
+                "line 9",
+                "line 10",
+            ],
+            "lang {lang:?}"
+        );
+        let hidden = lines[2].hidden_code.as_ref().expect("hidden code");
+        assert_eq!(
+            plain_lines(&Text::from(hidden.lines())),
+            (3..=8)
+                .map(|line| format!("line {line}"))
+                .collect::<Vec<_>>(),
+            "lang {lang:?}"
+        );
+    }
+}
+
+#[test]
+fn code_blocks_within_the_limit_render_in_full() {
+    let lines = render_with_code_block_max_lines(&numbered_code_block("rust", 4), 4);
+    assert_eq!(lines.len(), 4);
+    assert!(lines.iter().all(|line| line.hidden_code.is_none()));
+
+    let lines = render_with_code_block_max_lines(&numbered_code_block("rust", 10), 0);
+    assert_eq!(lines.len(), 10);
+}
+
+#[test]
+fn code_block_rows_carry_the_unstyled_block_source() {
+    let md = "Intro\n\n```rust\nfn main() {\n\tprintln!(\"hi\");\n}\n```\n\n    indented code\n\nAfter\n";
+    let lines = render_with_code_block_max_lines(md, 200);
+    let sources: Vec<Option<&str>> = lines
+        .iter()
+        .map(|line| line.code_block.as_deref())
+        .collect();
+    let rust = Some("fn main() {\n\tprintln!(\"hi\");\n}");
+    assert_eq!(
+        sources,
+        vec![
+            None,
+            None,
+            rust,
+            rust,
+            rust,
+            None,
+            Some("indented code"),
+            None,
+            None,
+        ]
+    );
+
+    // A collapsed block's rows, hidden-lines row included, carry the whole block.
+    let md = numbered_code_block("", 10);
+    let lines = render_with_code_block_max_lines(&md, 4);
+    assert!(lines.iter().all(|line| line.code_block.as_deref()
+        == Some(md.trim_start_matches("```\n").trim_end_matches("\n```\n"))));
+}
+
+#[test]
+fn last_code_block_returns_the_source_of_the_final_block() {
+    assert_eq!(
+        last_code_block("```sh\nfirst\n```\n\ntext\n\n```\n  second\n\n  block\n```\n"),
+        Some("  second\n\n  block".to_string())
+    );
+    assert_eq!(last_code_block("no code here, only `inline`"), None);
+}
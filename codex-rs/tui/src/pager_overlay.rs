@@ -14,28 +14,66 @@
 //! `TranscriptOverlay::sync_live_tail` uses the key to decide when the cached tail must be
 //! recomputed. `ChatWidget` is responsible for producing a key that changes when the active cell
 //! mutates in place or when its transcript output is time-dependent.
+//!
+//! The transcript follows new activity while it is scrolled to the bottom. Scrolling up locks the
+//! scroll position; cells that arrive while locked are counted in a "N new messages ↓" pill, and
+//! the jump-to-bottom key resumes following. With `tui.transcript_auto_follow = false` the
+//! transcript never moves on its own.
+//!
+//! Long transcripts get a minimap scrollbar (see [`minimap`]) marking user messages, patches, and
+//! errors; `[` and `]` jump between those landmarks.
+//!
+//! `t` (`tui.keymap.pager.toggle_timestamps`) cycles the time gutter (see
+//! [`crate::transcript_timestamps`]) between off, relative, and absolute times. `App` passes the
+//! time each committed cell was added alongside the cells.
+//!
+//! Tables kept at full width by `tui.table_overflow = "scroll"` show one window of each row; `<`
+//! and `>` move every such table sideways together.
+//!
+//! Markdown headings open collapsible sections (see [`section_folds`]): `z` folds the section at
+//! the top of the view and `Z` folds every section.
+//!
+//! `e` shows or hides the lines that collapsed long code blocks leave out (see [`hidden_code`]).
 
+use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::io::Result;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::SystemTime;
 
 use crate::chatwidget::ActiveCellTranscriptKey;
+use crate::file_references::FileReference;
+use crate::file_references::file_references_in_lines;
+use crate::file_references::file_viewer_lines;
 use crate::history_cell::HistoryCell;
+use crate::history_cell::TranscriptMarker;
 use crate::history_cell::UserHistoryCell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::key_hint::KeyBindingListExt;
 use crate::keymap::PagerKeymap;
+use crate::keymap::primary_binding;
 use crate::render::Insets;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
 use crate::terminal_hyperlinks::HyperlinkLine;
+use crate::terminal_hyperlinks::clip_scrollable_lines;
 use crate::terminal_hyperlinks::mark_buffer_hyperlinks;
 use crate::terminal_hyperlinks::visible_lines_ref;
+use crate::transcript_timestamps::GUTTER_WIDTH;
+use crate::transcript_timestamps::cycle_transcript_timestamps;
+use crate::transcript_timestamps::gutter_label;
+use crate::transcript_timestamps::transcript_timestamps;
 use crate::tui;
 use crate::tui::TuiEvent;
+use codex_config::types::TranscriptTimestamps;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
 use ratatui::buffer::Buffer;
 use ratatui::buffer::Cell;
 use ratatui::layout::Rect;
@@ -50,14 +88,32 @@ use ratatui::widgets::Widget;
 use ratatui::widgets::WidgetRef;
 use ratatui::widgets::Wrap;
 
+mod hidden_code;
+mod minimap;
+mod section_folds;
+
+static TRANSCRIPT_AUTO_FOLLOW: AtomicBool = AtomicBool::new(true);
+
+/// Applies `tui.transcript_auto_follow` from config.
+pub(crate) fn set_transcript_auto_follow(enabled: bool) {
+    TRANSCRIPT_AUTO_FOLLOW.store(enabled, Ordering::Relaxed);
+}
+
+/// Columns that `<` and `>` move full-width tables by.
+const TABLE_SCROLL_STEP: usize = 8;
+
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
 }
 
 impl Overlay {
-    pub(crate) fn new_transcript(cells: Vec<Arc<dyn HistoryCell>>, keymap: PagerKeymap) -> Self {
-        Self::Transcript(TranscriptOverlay::new(cells, keymap))
+    pub(crate) fn new_transcript(
+        cells: Vec<Arc<dyn HistoryCell>>,
+        cell_times: Vec<SystemTime>,
+        keymap: PagerKeymap,
+    ) -> Self {
+        Self::Transcript(TranscriptOverlay::new(cells, cell_times, keymap))
     }
 
     pub(crate) fn new_static_with_lines(
@@ -124,8 +180,13 @@ struct PagerView {
     keymap: PagerKeymap,
     last_content_height: Option<usize>,
     last_rendered_height: Option<usize>,
+    /// Width of the content column in the last frame, after any minimap column.
+    last_content_width: Option<u16>,
     /// If set, on next render ensure this chunk is visible.
     pending_scroll_chunk: Option<usize>,
+    /// Renderable index and kind of each landmark; when set, overflowing content gets a minimap
+    /// scrollbar in the rightmost column.
+    landmarks: Option<Vec<(usize, TranscriptMarker)>>,
 }
 
 impl PagerView {
@@ -142,7 +203,9 @@ impl PagerView {
             keymap,
             last_content_height: None,
             last_rendered_height: None,
+            last_content_width: None,
             pending_scroll_chunk: None,
+            landmarks: None,
         }
     }
 
@@ -156,10 +219,19 @@ impl PagerView {
     fn render(&mut self, area: Rect, buf: &mut Buffer) {
         Clear.render(area, buf);
         self.render_header(area, buf);
-        let content_area = self.content_area(area);
+        let mut content_area = self.content_area(area);
         self.update_last_content_height(content_area.height);
-        let content_height = self.content_height(content_area.width);
+        let mut content_height = self.content_height(content_area.width);
+        let minimap_area = (self.landmarks.is_some()
+            && content_height > content_area.height as usize
+            && content_area.width > 1)
+            .then(|| {
+                content_area.width -= 1;
+                content_height = self.content_height(content_area.width);
+                Rect::new(content_area.right(), content_area.y, 1, content_area.height)
+            });
         self.last_rendered_height = Some(content_height);
+        self.last_content_width = Some(content_area.width);
         // If there is a pending request to scroll a specific chunk into view,
         // satisfy it now that wrapping is up to date for this width.
         if let Some(idx) = self.pending_scroll_chunk.take() {
@@ -170,10 +242,36 @@ impl PagerView {
             .min(content_height.saturating_sub(content_area.height as usize));
 
         self.render_content(content_area, buf);
+        if let Some(minimap_area) = minimap_area {
+            self.render_minimap(minimap_area, buf, content_area.width, content_height);
+        }
 
         self.render_bottom_bar(area, content_area, buf, content_height);
     }
 
+    fn render_minimap(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        content_width: u16,
+        content_height: usize,
+    ) {
+        let Some(landmarks) = self.landmarks.as_ref() else {
+            return;
+        };
+        let mut chunk_tops = Vec::with_capacity(self.renderables.len());
+        let mut top = 0;
+        for renderable in &self.renderables {
+            chunk_tops.push(top);
+            top += renderable.desired_height(content_width) as usize;
+        }
+        let marks: Vec<(usize, TranscriptMarker)> = landmarks
+            .iter()
+            .filter_map(|&(chunk, marker)| Some((*chunk_tops.get(chunk)?, marker)))
+            .collect();
+        minimap::render_minimap(area, buf, content_height, self.scroll_offset, &marks);
+    }
+
     fn render_header(&self, area: Rect, buf: &mut Buffer) {
         Span::from("/ ".repeat(area.width as usize / 2))
             .dim()
@@ -334,6 +432,24 @@ impl PagerView {
         self.scroll_offset >= max_scroll
     }
 
+    /// The chunk drawn at the top of the last frame: its index, the content row it starts at, and
+    /// how many of its rows were scrolled above the view.
+    fn top_chunk(&self) -> Option<(usize, usize, usize)> {
+        let width = self.last_content_width?;
+        let height = self.last_content_height?;
+        let total_height = self.last_rendered_height?;
+        let top = self.scroll_offset.min(total_height.saturating_sub(height));
+        let mut chunk_top = 0;
+        for (index, renderable) in self.renderables.iter().enumerate() {
+            let chunk_height = renderable.desired_height(width) as usize;
+            if top < chunk_top + chunk_height {
+                return Some((index, chunk_top, top - chunk_top));
+            }
+            chunk_top += chunk_height;
+        }
+        None
+    }
+
     /// Request that the given text chunk index be scrolled into view on next render.
     fn scroll_chunk_into_view(&mut self, chunk_index: usize) {
         self.pending_scroll_chunk = Some(chunk_index);
@@ -393,12 +509,65 @@ impl Renderable for CachedRenderable {
 
 struct CellRenderable {
     cell: Arc<dyn HistoryCell>,
+    /// When the cell was added to the transcript, shown in the time gutter.
+    time: Option<SystemTime>,
     highlighted: bool,
+    table_scroll: Arc<TableScroll>,
+    /// Sections of this cell folded with `z` / `Z`, by heading order.
+    folded_sections: BTreeSet<usize>,
+    /// Whether `e` showed the lines hidden by this cell's collapsed code blocks.
+    show_hidden_code: bool,
+}
+
+/// Sideways position of full-width table rows, shared by every cell so all tables scroll together.
+#[derive(Debug, Default)]
+struct TableScroll {
+    column: AtomicUsize,
+    /// How far the widest table row drawn in the current frame overflows its cell.
+    overflow: AtomicUsize,
+}
+
+/// Columns reserved for the time gutter at `width`; none when it is off or the view is too narrow.
+fn time_gutter_width(width: u16) -> u16 {
+    if transcript_timestamps() == TranscriptTimestamps::Off || width <= GUTTER_WIDTH * 2 {
+        0
+    } else {
+        GUTTER_WIDTH
+    }
 }
 
 impl Renderable for CellRenderable {
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        let hyperlink_lines = self.cell.transcript_hyperlink_lines(area.width);
+        let gutter = time_gutter_width(area.width);
+        if gutter > 0
+            && !self.cell.is_stream_continuation()
+            && let Some(label) = self
+                .time
+                .and_then(|time| gutter_label(transcript_timestamps(), time, SystemTime::now()))
+        {
+            Span::from(label).dim().render_ref(
+                Rect::new(area.x, area.y, gutter - 1, 1.min(area.height)),
+                buf,
+            );
+        }
+        let area = Rect::new(area.x + gutter, area.y, area.width - gutter, area.height);
+        let hyperlink_lines = self.transcript_lines(area.width);
+        let width = usize::from(area.width);
+        let overflow = hyperlink_lines
+            .iter()
+            .filter(|line| line.scrollable)
+            .map(|line| line.width().saturating_sub(width))
+            .max()
+            .unwrap_or(0);
+        self.table_scroll
+            .overflow
+            .fetch_max(overflow, Ordering::Relaxed);
+        let column = self
+            .table_scroll
+            .column
+            .load(Ordering::Relaxed)
+            .min(overflow);
+        let hyperlink_lines = clip_scrollable_lines(hyperlink_lines, column, width);
         let style = if self.cell.as_any().is::<UserHistoryCell>() {
             if self.highlighted {
                 user_message_style().reversed()
@@ -416,10 +585,77 @@ impl Renderable for CellRenderable {
     }
 
     fn desired_height(&self, width: u16) -> u16 {
-        self.cell.desired_transcript_height(width)
+        let width = width - time_gutter_width(width);
+        if self.folded_sections.is_empty() && !self.show_hidden_code {
+            return self.cell.desired_transcript_height(width);
+        }
+        let lines = clip_scrollable_lines(
+            self.transcript_lines(width),
+            /*column*/ 0,
+            usize::from(width),
+        );
+        Paragraph::new(Text::from(visible_lines_ref(&lines)))
+            .wrap(Wrap { trim: false })
+            .line_count(width)
+            .try_into()
+            .unwrap_or(/*default*/ 0)
+    }
+}
+
+impl CellRenderable {
+    /// The cell's transcript lines with any hidden code shown and its folded sections collapsed.
+    fn transcript_lines(&self, width: u16) -> Vec<HyperlinkLine> {
+        displayed_transcript_lines(
+            self.cell.as_ref(),
+            width,
+            self.show_hidden_code,
+            &self.folded_sections,
+        )
+    }
+}
+
+/// `cell`'s transcript lines as the overlay draws them: with the lines its collapsed code blocks
+/// hide if `show_hidden_code`, and with its `folded_sections` collapsed.
+fn displayed_transcript_lines(
+    cell: &dyn HistoryCell,
+    width: u16,
+    show_hidden_code: bool,
+    folded_sections: &BTreeSet<usize>,
+) -> Vec<HyperlinkLine> {
+    let lines = cell_transcript_lines(cell, width, show_hidden_code);
+    if folded_sections.is_empty() {
+        return lines;
+    }
+    section_folds::fold_lines(lines, folded_sections)
+        .into_iter()
+        .map(|(_, line)| line)
+        .collect()
+}
+
+/// `cell`'s transcript lines, with the lines its collapsed code blocks hide if `show_hidden_code`.
+fn cell_transcript_lines(
+    cell: &dyn HistoryCell,
+    width: u16,
+    show_hidden_code: bool,
+) -> Vec<HyperlinkLine> {
+    let lines = cell.transcript_hyperlink_lines(width);
+    if show_hidden_code {
+        hidden_code::show_hidden_code(lines)
+    } else {
+        lines
     }
 }
 
+/// Rows `line` takes in the transcript at `width`; full-width table rows are clipped to one.
+fn transcript_line_rows(line: &HyperlinkLine, width: u16) -> usize {
+    if line.scrollable {
+        return 1;
+    }
+    Paragraph::new(line.line.clone())
+        .wrap(Wrap { trim: false })
+        .line_count(width)
+}
+
 struct HyperlinkLinesRenderable {
     lines: Vec<HyperlinkLine>,
 }
@@ -441,6 +677,13 @@ impl Renderable for HyperlinkLinesRenderable {
     }
 }
 
+/// Pads or trims `cell_times` to one entry per cell; cells without a recorded time get no label.
+fn aligned_cell_times(cell_count: usize, cell_times: Vec<SystemTime>) -> Vec<Option<SystemTime>> {
+    let mut cell_times: Vec<Option<SystemTime>> = cell_times.into_iter().map(Some).collect();
+    cell_times.resize(cell_count, None);
+    cell_times
+}
+
 pub(crate) struct TranscriptOverlay {
     /// Pager UI state and the renderables currently displayed.
     ///
@@ -449,9 +692,36 @@ pub(crate) struct TranscriptOverlay {
     view: PagerView,
     /// Committed transcript cells (does not include the live tail).
     cells: Vec<Arc<dyn HistoryCell>>,
+    /// When each of `cells` was added, index-aligned with `cells`.
+    cell_times: Vec<Option<SystemTime>>,
     highlight_cell: Option<usize>,
     /// Cache key for the render-only live tail appended after committed cells.
     live_tail_key: Option<LiveTailKey>,
+    /// File reference selected with Tab / Shift+Tab, with the index of the cell it appears in.
+    focused_file_reference: Option<(usize, FileReference)>,
+    /// Viewer opened on the focused file reference; it replaces the transcript until closed.
+    file_viewer: Option<StaticOverlay>,
+    /// Cell index of the landmark last jumped to with `[` / `]`.
+    landmark_cursor: Option<usize>,
+    /// Set when the user scrolls up; new activity no longer moves the view until they jump to the
+    /// bottom.
+    scroll_locked: bool,
+    /// Committed cells inserted below the view while it was not following.
+    unseen_cells: usize,
+    table_scroll: Arc<TableScroll>,
+    /// Folded markdown sections, by cell index and then heading order within the cell.
+    folded_sections: HashMap<usize, BTreeSet<usize>>,
+    /// Whether the cell at the top of the last frame has markdown sections to fold.
+    top_cell_has_sections: bool,
+    /// Cells whose hidden code lines `e` showed.
+    shown_hidden_code: BTreeSet<usize>,
+    /// Whether the cell at the top of the last frame has collapsed code blocks, and if so whether
+    /// their hidden lines are shown.
+    top_cell_hidden_code: Option<bool>,
+    /// Whether the last frame showed a row of a code block for `y` to copy.
+    code_block_in_view: bool,
+    /// Source of the code block `y` picked, until the app copies it.
+    code_block_to_copy: Option<Arc<str>>,
     is_done: bool,
 }
 
@@ -475,41 +745,90 @@ impl TranscriptOverlay {
     ///
     /// This overlay does not own the "active cell"; callers may optionally append a live tail via
     /// `sync_live_tail` during draws to reflect in-flight activity.
-    pub(crate) fn new(transcript_cells: Vec<Arc<dyn HistoryCell>>, keymap: PagerKeymap) -> Self {
+    pub(crate) fn new(
+        transcript_cells: Vec<Arc<dyn HistoryCell>>,
+        cell_times: Vec<SystemTime>,
+        keymap: PagerKeymap,
+    ) -> Self {
+        let cell_times = aligned_cell_times(transcript_cells.len(), cell_times);
+        let table_scroll = Arc::new(TableScroll::default());
         Self {
             view: PagerView::new(
-                Self::render_cells(&transcript_cells, /*highlight_cell*/ None),
+                Self::render_cells(
+                    &transcript_cells,
+                    &cell_times,
+                    /*highlight_cell*/ None,
+                    &table_scroll,
+                    &HashMap::new(),
+                    &BTreeSet::new(),
+                ),
                 "T R A N S C R I P T".to_string(),
                 usize::MAX,
                 keymap,
             ),
             cells: transcript_cells,
+            cell_times,
             highlight_cell: None,
             live_tail_key: None,
+            focused_file_reference: None,
+            file_viewer: None,
+            landmark_cursor: None,
+            scroll_locked: !TRANSCRIPT_AUTO_FOLLOW.load(Ordering::Relaxed),
+            unseen_cells: 0,
+            table_scroll,
+            folded_sections: HashMap::new(),
+            top_cell_has_sections: false,
+            shown_hidden_code: BTreeSet::new(),
+            top_cell_hidden_code: None,
+            code_block_in_view: false,
+            code_block_to_copy: None,
             is_done: false,
         }
     }
 
     fn render_cells(
         cells: &[Arc<dyn HistoryCell>],
+        cell_times: &[Option<SystemTime>],
         highlight_cell: Option<usize>,
+        table_scroll: &Arc<TableScroll>,
+        folded_sections: &HashMap<usize, BTreeSet<usize>>,
+        shown_hidden_code: &BTreeSet<usize>,
     ) -> Vec<Box<dyn Renderable>> {
         cells
             .iter()
+            .zip(cell_times)
             .enumerate()
-            .map(|(i, cell)| Self::render_cell(cell, i, highlight_cell))
+            .map(|(i, (cell, time))| {
+                Self::render_cell(
+                    cell,
+                    *time,
+                    i,
+                    highlight_cell,
+                    table_scroll,
+                    folded_sections.get(&i).cloned().unwrap_or_default(),
+                    shown_hidden_code.contains(&i),
+                )
+            })
             .collect()
     }
 
     /// Build the renderable for a committed cell, caching its height when the cell is stable.
     fn render_cell(
         cell: &Arc<dyn HistoryCell>,
+        time: Option<SystemTime>,
         index: usize,
         highlight_cell: Option<usize>,
+        table_scroll: &Arc<TableScroll>,
+        folded_sections: BTreeSet<usize>,
+        show_hidden_code: bool,
     ) -> Box<dyn Renderable> {
         let cell_renderable = CellRenderable {
             cell: cell.clone(),
+            time,
             highlighted: highlight_cell == Some(index),
+            table_scroll: table_scroll.clone(),
+            folded_sections,
+            show_hidden_code,
         };
         let mut cell_renderable: Box<dyn Renderable> = if cell.has_stable_transcript_height() {
             Box::new(CachedRenderable::new(cell_renderable))
@@ -537,12 +856,21 @@ impl TranscriptOverlay {
     /// This expects `cell` to be a committed transcript cell (not the in-flight active cell). If
     /// the overlay was scrolled to bottom before insertion, it remains pinned to bottom after the
     /// insertion to preserve the "follow along" behavior.
-    pub(crate) fn insert_cell(&mut self, cell: Arc<dyn HistoryCell>) {
-        let follow_bottom = self.view.is_scrolled_to_bottom();
+    pub(crate) fn insert_cell(&mut self, cell: Arc<dyn HistoryCell>, time: SystemTime) {
+        let follow_bottom = self.is_following();
         let had_prior_cells = !self.cells.is_empty();
         let tail_renderable = self.take_live_tail_renderable();
-        let cell_renderable = Self::render_cell(&cell, self.cells.len(), self.highlight_cell);
+        let cell_renderable = Self::render_cell(
+            &cell,
+            Some(time),
+            self.cells.len(),
+            self.highlight_cell,
+            &self.table_scroll,
+            BTreeSet::new(),
+            /*show_hidden_code*/ false,
+        );
         self.cells.push(cell);
+        self.cell_times.push(Some(time));
         self.view.renderables.push(cell_renderable);
         if let Some(tail) = tail_renderable {
             let tail = if !had_prior_cells
@@ -565,6 +893,8 @@ impl TranscriptOverlay {
         }
         if follow_bottom {
             self.view.scroll_offset = usize::MAX;
+        } else {
+            self.unseen_cells += 1;
         }
     }
 
@@ -573,9 +903,16 @@ impl TranscriptOverlay {
     ///
     /// This is used when existing history is trimmed (for example after rollback) so the
     /// transcript overlay immediately reflects the same committed cells as the main transcript.
-    pub(crate) fn replace_cells(&mut self, cells: Vec<Arc<dyn HistoryCell>>) {
-        let follow_bottom = self.view.is_scrolled_to_bottom();
+    pub(crate) fn replace_cells(
+        &mut self,
+        cells: Vec<Arc<dyn HistoryCell>>,
+        cell_times: Vec<SystemTime>,
+    ) {
+        let follow_bottom = self.is_following();
         self.cells = cells;
+        self.cell_times = aligned_cell_times(self.cells.len(), cell_times);
+        self.folded_sections.clear();
+        self.shown_hidden_code.clear();
         if self
             .highlight_cell
             .is_some_and(|idx| idx >= self.cells.len())
@@ -600,7 +937,7 @@ impl TranscriptOverlay {
         range: std::ops::Range<usize>,
         consolidated: Arc<dyn HistoryCell>,
     ) {
-        let follow_bottom = self.view.is_scrolled_to_bottom();
+        let follow_bottom = self.is_following();
         // Clamp the range to the overlay's cell count to avoid panic if the overlay has fewer
         // cells than the main transcript (e.g. cells were inserted after the overlay has opened).
         let clamped_end = range.end.min(self.cells.len());
@@ -618,6 +955,25 @@ impl TranscriptOverlay {
             }
             self.cells
                 .splice(clamped_start..clamped_end, std::iter::once(consolidated));
+            // The consolidated cell keeps the time of the first cell it replaces.
+            self.cell_times.drain(clamped_start + 1..clamped_end);
+            // Folds inside the range referred to the streamed cells' headings.
+            self.folded_sections = std::mem::take(&mut self.folded_sections)
+                .into_iter()
+                .filter_map(|(index, folded)| match index {
+                    index if index < clamped_start => Some((index, folded)),
+                    index if index >= clamped_end => Some((index + 1 - removed, folded)),
+                    _ => None,
+                })
+                .collect();
+            self.shown_hidden_code = std::mem::take(&mut self.shown_hidden_code)
+                .into_iter()
+                .filter_map(|index| match index {
+                    index if index < clamped_start => Some(index),
+                    index if index >= clamped_end => Some(index + 1 - removed),
+                    _ => None,
+                })
+                .collect();
             if self
                 .highlight_cell
                 .is_some_and(|highlight_cell| highlight_cell >= self.cells.len())
@@ -659,7 +1015,7 @@ impl TranscriptOverlay {
         if self.live_tail_key == next_key {
             return;
         }
-        let follow_bottom = self.view.is_scrolled_to_bottom();
+        let follow_bottom = self.is_following();
 
         self.take_live_tail_renderable();
         self.live_tail_key = next_key;
@@ -695,9 +1051,55 @@ impl TranscriptOverlay {
         self.view.is_scrolled_to_bottom()
     }
 
+    /// Whether new activity should keep the view pinned to the bottom.
+    fn is_following(&self) -> bool {
+        !self.scroll_locked && self.view.is_scrolled_to_bottom()
+    }
+
+    /// Locks the scroll position when `key_event` scrolls up, and resumes following when it jumps
+    /// to the bottom.
+    fn update_scroll_lock(&mut self, key_event: KeyEvent) {
+        let keymap = &self.view.keymap;
+        if keymap.jump_bottom.is_pressed(key_event) {
+            self.scroll_locked = !TRANSCRIPT_AUTO_FOLLOW.load(Ordering::Relaxed);
+            self.unseen_cells = 0;
+        } else if keymap.scroll_up.is_pressed(key_event)
+            || keymap.page_up.is_pressed(key_event)
+            || keymap.half_page_up.is_pressed(key_event)
+            || keymap.jump_top.is_pressed(key_event)
+        {
+            self.scroll_locked = true;
+        }
+    }
+
+    /// Draws the "N new messages ↓" pill over the left end of the pager's bottom separator.
+    fn render_unseen_pill(&self, view_area: Rect, buf: &mut Buffer) {
+        if self.unseen_cells == 0 || view_area.height == 0 {
+            return;
+        }
+        let noun = if self.unseen_cells == 1 {
+            "message"
+        } else {
+            "messages"
+        };
+        let pill = format!(" {} new {noun} ↓ ", self.unseen_cells);
+        let y = view_area.bottom() - 1;
+        Span::from(pill).cyan().render_ref(
+            Rect::new(view_area.x + 1, y, view_area.width.saturating_sub(1), 1),
+            buf,
+        );
+    }
+
     fn rebuild_renderables(&mut self) {
         let tail_renderable = self.take_live_tail_renderable();
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.view.renderables = Self::render_cells(
+            &self.cells,
+            &self.cell_times,
+            self.highlight_cell,
+            &self.table_scroll,
+            &self.folded_sections,
+            &self.shown_hidden_code,
+        );
         if let Some(tail) = tail_renderable {
             self.view.renderables.push(tail);
         }
@@ -719,6 +1121,18 @@ impl TranscriptOverlay {
     ) -> Box<dyn Renderable> {
         let mut renderable: Box<dyn Renderable> =
             Box::new(CachedRenderable::new(HyperlinkLinesRenderable { lines }));
+        // Keep in-flight output aligned with committed cells when the time gutter is on.
+        if transcript_timestamps() != TranscriptTimestamps::Off {
+            renderable = Box::new(InsetRenderable::new(
+                renderable,
+                Insets::tlbr(
+                    /*top*/ 0,
+                    /*left*/ GUTTER_WIDTH,
+                    /*bottom*/ 0,
+                    /*right*/ 0,
+                ),
+            ));
+        }
         if has_prior_cells && !is_stream_continuation {
             renderable = Box::new(InsetRenderable::new(
                 renderable,
@@ -776,20 +1190,377 @@ impl TranscriptOverlay {
         } else {
             pairs.push((vec![key_hint::plain(KeyCode::Esc)], "to edit prev"));
         }
+        if self
+            .cells
+            .iter()
+            .any(|cell| cell.transcript_marker().is_some())
+        {
+            pairs.push((
+                vec![
+                    key_hint::plain(KeyCode::Char('[')),
+                    key_hint::plain(KeyCode::Char(']')),
+                ],
+                "to jump between markers",
+            ));
+        }
+        if self.table_scroll.overflow.load(Ordering::Relaxed) > 0 {
+            pairs.push((
+                vec![
+                    key_hint::plain(KeyCode::Char('<')),
+                    key_hint::plain(KeyCode::Char('>')),
+                ],
+                "to scroll tables",
+            ));
+        }
+        if self.top_cell_has_sections {
+            pairs.push((
+                vec![
+                    key_hint::plain(KeyCode::Char('z')),
+                    key_hint::plain(KeyCode::Char('Z')),
+                ],
+                "to fold sections",
+            ));
+        }
+        if let Some(shown) = self.top_cell_hidden_code {
+            pairs.push((
+                vec![key_hint::plain(KeyCode::Char('e'))],
+                if shown {
+                    "to hide long code"
+                } else {
+                    "to show hidden code"
+                },
+            ));
+        }
+        if self.code_block_in_view {
+            pairs.push((vec![key_hint::plain(KeyCode::Char('y'))], "to copy code"));
+        }
+        if let Some(toggle) = primary_binding(&self.view.keymap.toggle_timestamps) {
+            pairs.push((vec![toggle], "to toggle times"));
+        }
+        let open_hint = self.focused_file_reference.as_ref().map(|(_, reference)| {
+            format!("to open {}:{}", reference.path.display(), reference.line)
+        });
+        if let Some(open_hint) = open_hint.as_deref() {
+            pairs.push((vec![key_hint::plain(KeyCode::Enter)], open_hint));
+        }
         render_key_hints(line2, buf, &pairs);
     }
 
+    /// Moves the focus to the next or previous file reference in the transcript.
+    ///
+    /// With nothing focused yet, both directions start from the most recent reference.
+    fn focus_file_reference(&mut self, forward: bool) {
+        let references: Vec<(usize, FileReference)> = self
+            .cells
+            .iter()
+            .enumerate()
+            .flat_map(|(index, cell)| {
+                file_references_in_lines(&cell.transcript_hyperlink_lines(u16::MAX))
+                    .into_iter()
+                    .map(move |reference| (index, reference))
+            })
+            .collect();
+        let Some(last) = references.len().checked_sub(1) else {
+            return;
+        };
+        let current = self
+            .focused_file_reference
+            .as_ref()
+            .and_then(|focused| references.iter().position(|entry| entry == focused));
+        let next = match current {
+            Some(index) if forward => (index + 1) % references.len(),
+            Some(index) => index.checked_sub(1).unwrap_or(last),
+            None => last,
+        };
+        let (cell, reference) = references[next].clone();
+        self.view.scroll_chunk_into_view(cell);
+        self.focused_file_reference = Some((cell, reference));
+    }
+
+    /// Cell index and kind of every committed cell flagged in the minimap scrollbar.
+    fn landmarks(&self) -> Vec<(usize, TranscriptMarker)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| Some((index, cell.transcript_marker()?)))
+            .collect()
+    }
+
+    /// Scrolls to the next or previous landmark: a user message, applied patch, or error.
+    ///
+    /// With no landmark visited yet, both directions start from the most recent one.
+    fn jump_to_landmark(&mut self, forward: bool) {
+        let cells: Vec<usize> = self
+            .landmarks()
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
+        let (Some(&first), Some(&last)) = (cells.first(), cells.last()) else {
+            return;
+        };
+        let next = match self.landmark_cursor {
+            Some(cursor) if forward => cells
+                .iter()
+                .copied()
+                .find(|&index| index > cursor)
+                .unwrap_or(first),
+            Some(cursor) => cells
+                .iter()
+                .rev()
+                .copied()
+                .find(|&index| index < cursor)
+                .unwrap_or(last),
+            None => last,
+        };
+        self.landmark_cursor = Some(next);
+        self.view.scroll_chunk_into_view(next);
+    }
+
+    fn open_file_viewer(&mut self, reference: &FileReference) {
+        let (lines, scroll_offset) = file_viewer_lines(reference);
+        self.file_viewer = Some(StaticOverlay {
+            view: PagerView::new(
+                vec![Box::new(Paragraph::new(Text::from(lines)))],
+                format!("{}:{}", reference.path.display(), reference.line),
+                scroll_offset,
+                self.view.keymap.clone(),
+            ),
+            is_done: false,
+        });
+    }
+
+    /// Switches the time gutter to its next mode and re-lays out the transcript for it.
+    fn cycle_time_gutter(&mut self) {
+        cycle_transcript_timestamps();
+        let follow_bottom = self.is_following();
+        // Cached heights and the live tail were laid out for the previous gutter width.
+        self.take_live_tail_renderable();
+        self.live_tail_key = None;
+        self.rebuild_renderables();
+        if follow_bottom {
+            self.view.scroll_offset = usize::MAX;
+        }
+    }
+
+    /// Moves full-width tables a few columns sideways, stopping where the widest table drawn last
+    /// frame ends.
+    fn scroll_tables(&mut self, right: bool) {
+        let column = self.table_scroll.column.load(Ordering::Relaxed);
+        let column = if right {
+            (column + TABLE_SCROLL_STEP).min(self.table_scroll.overflow.load(Ordering::Relaxed))
+        } else {
+            column.saturating_sub(TABLE_SCROLL_STEP)
+        };
+        self.table_scroll.column.store(column, Ordering::Relaxed);
+    }
+
+    /// Folds or unfolds the markdown section at the top of the view, then scrolls its heading to
+    /// the top.
+    fn toggle_section_at_top(&mut self) {
+        let (Some((index, chunk_top, row)), Some(width)) =
+            (self.view.top_chunk(), self.view.last_content_width)
+        else {
+            return;
+        };
+        let Some(cell) = self.cells.get(index) else {
+            return;
+        };
+        let inset = usize::from(index > 0 && !cell.is_stream_continuation());
+        let width = width - time_gutter_width(width);
+        let lines = cell_transcript_lines(
+            cell.as_ref(),
+            width,
+            self.shown_hidden_code.contains(&index),
+        );
+        let sections = section_folds::sections(&lines);
+        let folded = self.folded_sections.entry(index).or_default();
+        let visible = section_folds::fold_lines(lines, folded);
+
+        let row = row.saturating_sub(inset);
+        let mut line_top = 0;
+        let mut top_line = visible.last().map(|(line, _)| *line);
+        for (line, hyperlink_line) in &visible {
+            line_top += transcript_line_rows(hyperlink_line, width);
+            if row < line_top {
+                top_line = Some(*line);
+                break;
+            }
+        }
+        let Some(section) =
+            top_line.and_then(|line| section_folds::section_to_toggle(&sections, line))
+        else {
+            return;
+        };
+        if !folded.remove(&section) {
+            folded.insert(section);
+        }
+        if folded.is_empty() {
+            self.folded_sections.remove(&index);
+        }
+
+        let heading_row: usize = visible
+            .iter()
+            .take_while(|(line, _)| *line < sections[section].start)
+            .map(|(_, hyperlink_line)| transcript_line_rows(hyperlink_line, width))
+            .sum();
+        self.scroll_locked = true;
+        self.rebuild_renderables();
+        self.view.scroll_offset = chunk_top + inset + heading_row;
+    }
+
+    /// Folds every markdown section in the transcript, or unfolds them all if any is folded.
+    fn toggle_all_sections(&mut self) {
+        if self.folded_sections.is_empty() {
+            let Some(width) = self.view.last_content_width else {
+                return;
+            };
+            let width = width - time_gutter_width(width);
+            for (index, cell) in self.cells.iter().enumerate() {
+                let sections = section_folds::sections(&cell.transcript_hyperlink_lines(width));
+                if !sections.is_empty() {
+                    self.folded_sections
+                        .insert(index, (0..sections.len()).collect());
+                }
+            }
+        } else {
+            self.folded_sections.clear();
+        }
+        let follow_bottom = self.is_following();
+        self.rebuild_renderables();
+        if follow_bottom {
+            self.view.scroll_offset = usize::MAX;
+        }
+    }
+
+    /// Whether the committed cell at the top of the last frame has any markdown headings.
+    fn top_cell_has_sections(&self) -> bool {
+        let (Some((index, _, _)), Some(width)) =
+            (self.view.top_chunk(), self.view.last_content_width)
+        else {
+            return false;
+        };
+        self.cells.get(index).is_some_and(|cell| {
+            cell.transcript_hyperlink_lines(width - time_gutter_width(width))
+                .iter()
+                .any(|line| line.heading_level.is_some())
+        })
+    }
+
+    /// Shows or hides the code lines hidden in the cell at the top of the view.
+    fn toggle_hidden_code_at_top(&mut self) {
+        let Some((index, _, _)) = self.view.top_chunk() else {
+            return;
+        };
+        if self.top_cell_hidden_code.is_none() {
+            return;
+        }
+        if !self.shown_hidden_code.remove(&index) {
+            self.shown_hidden_code.insert(index);
+        }
+        self.scroll_locked = true;
+        self.rebuild_renderables();
+    }
+
+    /// Whether the committed cell at the top of the last frame has collapsed code blocks, and if
+    /// so whether their hidden lines are shown.
+    fn top_cell_hidden_code(&self) -> Option<bool> {
+        let (Some((index, _, _)), Some(width)) =
+            (self.view.top_chunk(), self.view.last_content_width)
+        else {
+            return None;
+        };
+        let cell = self.cells.get(index)?;
+        hidden_code::has_hidden_code(
+            &cell.transcript_hyperlink_lines(width - time_gutter_width(width)),
+        )
+        .then(|| self.shown_hidden_code.contains(&index))
+    }
+
+    /// The code block `y` copies: the first one with a row in the last frame.
+    fn code_block_in_view(&self) -> Option<Arc<str>> {
+        let (top_index, _, scrolled) = self.view.top_chunk()?;
+        let width = self.view.last_content_width?;
+        let height = self.view.last_content_height?;
+        let width = width - time_gutter_width(width);
+        let view = scrolled..scrolled + height;
+        // Rows below the top of the chunk drawn at the top of the view.
+        let mut row = 0;
+        for (index, cell) in self.cells.iter().enumerate().skip(top_index) {
+            if index > 0 && !cell.is_stream_continuation() {
+                row += 1;
+            }
+            let lines = displayed_transcript_lines(
+                cell.as_ref(),
+                width,
+                self.shown_hidden_code.contains(&index),
+                &self
+                    .folded_sections
+                    .get(&index)
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+            for line in lines {
+                if row >= view.end {
+                    return None;
+                }
+                let rows = transcript_line_rows(&line, width);
+                if row + rows > view.start && line.code_block.is_some() {
+                    return line.code_block;
+                }
+                row += rows;
+            }
+        }
+        None
+    }
+
+    /// Takes the source of the code block picked with `y`, for the app to put on the clipboard.
+    pub(crate) fn take_code_block_to_copy(&mut self) -> Option<Arc<str>> {
+        self.code_block_to_copy.take()
+    }
+
+    /// Whether a file opened from a reference is showing in place of the transcript.
+    pub(crate) fn is_viewing_file(&self) -> bool {
+        self.file_viewer.is_some()
+    }
+
     pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        if let Some(file_viewer) = self.file_viewer.as_mut() {
+            file_viewer.render(area, buf);
+            return;
+        }
         let top_h = area.height.saturating_sub(3);
         let top = Rect::new(area.x, area.y, area.width, top_h);
         let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        self.view.landmarks = Some(self.landmarks());
+        self.table_scroll.overflow.store(0, Ordering::Relaxed);
         self.view.render(top, buf);
+        if self.view.is_scrolled_to_bottom() {
+            self.unseen_cells = 0;
+        }
+        self.top_cell_has_sections = self.top_cell_has_sections();
+        self.top_cell_hidden_code = self.top_cell_hidden_code();
+        self.code_block_in_view = self.code_block_in_view().is_some();
+        self.render_unseen_pill(top, buf);
         self.render_hints(bottom, buf);
     }
 }
 
 impl TranscriptOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        if let Some(file_viewer) = self.file_viewer.as_mut() {
+            if let TuiEvent::Key(key_event) = event
+                && key_event.code == KeyCode::Esc
+            {
+                file_viewer.is_done = true;
+            } else {
+                file_viewer.handle_event(tui, event)?;
+            }
+            if file_viewer.is_done() {
+                self.file_viewer = None;
+                tui.frame_requester().schedule_frame();
+            }
+            return Ok(());
+        }
         match event {
             TuiEvent::Key(key_event) => match key_event {
                 e if self.view.keymap.close.is_pressed(e)
@@ -798,7 +1569,85 @@ impl TranscriptOverlay {
                     self.is_done = true;
                     Ok(())
                 }
-                other => self.view.handle_key_event(tui, other),
+                KeyEvent {
+                    code: KeyCode::Tab | KeyCode::BackTab,
+                    kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                    ..
+                } => {
+                    self.scroll_locked = true;
+                    self.focus_file_reference(key_event.code == KeyCode::Tab);
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char(bracket @ ('[' | ']')),
+                    kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                    ..
+                } => {
+                    self.scroll_locked = true;
+                    self.jump_to_landmark(bracket == ']');
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char(arrow @ ('<' | '>')),
+                    kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                    ..
+                } => {
+                    self.scroll_tables(arrow == '>');
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                e if self.view.keymap.toggle_timestamps.is_pressed(e) => {
+                    self.cycle_time_gutter();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char(fold @ ('z' | 'Z')),
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    if fold == 'z' {
+                        self.toggle_section_at_top();
+                    } else {
+                        self.toggle_all_sections();
+                    }
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('e'),
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.toggle_hidden_code_at_top();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('y'),
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.code_block_to_copy = self.code_block_in_view();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Enter,
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    if let Some((_, reference)) = self.focused_file_reference.clone() {
+                        self.open_file_viewer(&reference);
+                        tui.frame_requester().schedule_frame();
+                    }
+                    Ok(())
+                }
+                other => {
+                    self.update_scroll_lock(other);
+                    self.view.handle_key_event(tui, other)
+                }
             },
             TuiEvent::Draw | TuiEvent::Resize => {
                 tui.draw(u16::MAX, |frame| {
@@ -1016,7 +1865,7 @@ mod tests {
     }
 
     fn transcript_overlay(cells: Vec<Arc<dyn HistoryCell>>) -> TranscriptOverlay {
-        TranscriptOverlay::new(cells, default_pager_keymap())
+        TranscriptOverlay::new(cells, Vec::new(), default_pager_keymap())
     }
 
     fn static_overlay(lines: Vec<Line<'static>>, title: &str) -> StaticOverlay {
@@ -1073,6 +1922,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn toggle_times_hint_follows_the_keymap() {
+        let mut keymap = default_pager_keymap();
+        keymap.toggle_timestamps = vec![key_hint::plain(KeyCode::Char('g'))];
+        let mut overlay = TranscriptOverlay::new(
+            vec![Arc::new(TestCell {
+                lines: vec![Line::from("hello")],
+            })],
+            Vec::new(),
+            keymap,
+        );
+
+        let area = Rect::new(0, 0, 200, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+
+        let s = buffer_to_text(&buf, area);
+        assert!(
+            s.contains("g to toggle times"),
+            "expected remapped toggle hint in overlay footer, got: {s:?}"
+        );
+    }
+
+    #[test]
+    fn file_references_cycle_from_latest_and_open_in_viewer() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(dir.path().join("b.rs"), "fn a() {}\nfn b() {}\n").expect("write file");
+        let mut overlay = transcript_overlay(vec![
+            Arc::new(history_cell::AgentMarkdownCell::new(
+                "See a.rs:1.".to_string(),
+                dir.path(),
+            )),
+            Arc::new(history_cell::AgentMarkdownCell::new(
+                "Then `b.rs:2`.".to_string(),
+                dir.path(),
+            )),
+        ]);
+        let focused = |overlay: &TranscriptOverlay| {
+            overlay
+                .focused_file_reference
+                .as_ref()
+                .map(|(cell, reference)| (*cell, reference.line))
+        };
+
+        overlay.focus_file_reference(/*forward*/ true);
+        assert_eq!(focused(&overlay), Some((1, 2)));
+        overlay.focus_file_reference(/*forward*/ true);
+        assert_eq!(focused(&overlay), Some((0, 1)));
+        overlay.focus_file_reference(/*forward*/ false);
+        assert_eq!(focused(&overlay), Some((1, 2)));
+
+        let area = Rect::new(0, 0, 120, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let transcript = buffer_to_text(&buf, area);
+        assert!(
+            transcript.contains("enter to open") && transcript.contains("b.rs:2"),
+            "expected open hint in overlay footer, got: {transcript:?}"
+        );
+
+        let (_, reference) = overlay.focused_file_reference.clone().expect("focused");
+        overlay.open_file_viewer(&reference);
+        assert!(overlay.is_viewing_file());
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let viewer = buffer_to_text(&buf, area);
+        assert!(
+            viewer.contains("2 ▌fn b() {}"),
+            "expected referenced line in viewer, got: {viewer:?}"
+        );
+    }
+
     #[test]
     fn transcript_overlay_snapshot_basic() {
         // Prepare a transcript overlay with a few lines
@@ -1272,6 +2193,130 @@ mod tests {
         assert_snapshot!("transcript_overlay_apply_patch_scroll_vt100", snapshot);
     }
 
+    #[test]
+    fn transcript_overlay_jumps_between_landmarks() {
+        let user_cell = |message: &str| -> Arc<dyn HistoryCell> {
+            Arc::new(UserHistoryCell {
+                message: message.to_string(),
+                text_elements: Vec::new(),
+                local_image_paths: Vec::new(),
+                remote_image_urls: Vec::new(),
+            })
+        };
+        let mut cells: Vec<Arc<dyn HistoryCell>> = vec![user_cell("first")];
+        cells.extend((0..10).map(|i| {
+            Arc::new(TestCell {
+                lines: vec![Line::from(format!("line-{i}"))],
+            }) as Arc<dyn HistoryCell>
+        }));
+        cells.push(Arc::new(history_cell::new_error_event("boom".to_string())));
+        cells.push(user_cell("last"));
+        let mut overlay = transcript_overlay(cells);
+
+        overlay.jump_to_landmark(/*forward*/ false);
+        assert_eq!(Some(12), overlay.landmark_cursor);
+        overlay.jump_to_landmark(/*forward*/ false);
+        assert_eq!(Some(11), overlay.landmark_cursor);
+        overlay.jump_to_landmark(/*forward*/ false);
+        assert_eq!(Some(0), overlay.landmark_cursor);
+        overlay.jump_to_landmark(/*forward*/ false);
+        assert_eq!(Some(12), overlay.landmark_cursor);
+        overlay.jump_to_landmark(/*forward*/ true);
+        assert_eq!(Some(0), overlay.landmark_cursor);
+
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        assert_eq!(0, overlay.view.scroll_offset);
+        assert_eq!("●", buf[(39, 1)].symbol());
+    }
+
+    #[test]
+    fn transcript_overlay_folds_markdown_sections() {
+        let mut overlay = transcript_overlay(vec![Arc::new(history_cell::AgentMarkdownCell::new(
+            "# Intro\n\nbody one\n\nbody two\n\n# Next\n\nmore".to_string(),
+            std::path::Path::new("/tmp"),
+        ))]);
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        assert!(overlay.top_cell_has_sections);
+
+        overlay.toggle_section_at_top();
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+        assert!(text.contains("Intro ⋯"), "{text}");
+        assert!(!text.contains("body one"), "{text}");
+        assert!(text.contains("more"), "{text}");
+
+        overlay.toggle_all_sections();
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+        assert!(!text.contains('⋯'), "{text}");
+        assert!(text.contains("body one"), "{text}");
+    }
+
+    #[test]
+    fn transcript_overlay_shows_hidden_code_lines() {
+        let body: String = (1..=210).map(|line| format!("line {line}\n")).collect();
+        let mut overlay = transcript_overlay(vec![Arc::new(history_cell::AgentMarkdownCell::new(
+            format!("```\n{body}```\n"),
+            std::path::Path::new("/tmp"),
+        ))]);
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        assert_eq!(overlay.top_cell_hidden_code, Some(false));
+        let collapsed_height = overlay.view.renderables[0].desired_height(area.width);
+
+        overlay.toggle_hidden_code_at_top();
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        assert_eq!(overlay.top_cell_hidden_code, Some(true));
+        // The ten hidden lines replace the row that stood for them.
+        assert_eq!(
+            overlay.view.renderables[0].desired_height(area.width),
+            collapsed_height + 9
+        );
+
+        overlay.toggle_hidden_code_at_top();
+        assert_eq!(
+            overlay.view.renderables[0].desired_height(area.width),
+            collapsed_height
+        );
+    }
+
+    #[test]
+    fn transcript_overlay_copies_the_first_code_block_in_view() {
+        let filler: String = (1..=20).map(|line| format!("filler {line}\n\n")).collect();
+        let mut overlay = transcript_overlay(vec![Arc::new(history_cell::AgentMarkdownCell::new(
+            format!(
+                "intro\n\n```\nfirst\n```\n\nmiddle\n\n```rust\nsecond\n  indented\n```\n\n{filler}"
+            ),
+            std::path::Path::new("/tmp"),
+        ))]);
+        let area = Rect::new(0, 0, 40, 12);
+        overlay.view.scroll_offset = 0;
+        overlay.render(area, &mut Buffer::empty(area));
+        assert!(overlay.code_block_in_view);
+        assert_eq!(overlay.code_block_in_view().as_deref(), Some("first"));
+
+        // Past the first block, the next one below the top of the view is picked.
+        overlay.view.scroll_offset = 3;
+        overlay.render(area, &mut Buffer::empty(area));
+        assert_eq!(
+            overlay.code_block_in_view().as_deref(),
+            Some("second\n  indented")
+        );
+
+        overlay.view.scroll_offset = 10;
+        overlay.render(area, &mut Buffer::empty(area));
+        assert!(!overlay.code_block_in_view);
+        assert_eq!(overlay.code_block_in_view(), None);
+    }
+
     #[test]
     fn transcript_overlay_keeps_scroll_pinned_at_bottom() {
         let mut overlay = transcript_overlay(
@@ -1292,9 +2337,12 @@ mod tests {
             "expected initial render to leave view at bottom"
         );
 
-        overlay.insert_cell(Arc::new(TestCell {
-            lines: vec!["tail".into()],
-        }));
+        overlay.insert_cell(
+            Arc::new(TestCell {
+                lines: vec!["tail".into()],
+            }),
+            SystemTime::now(),
+        );
 
         assert_eq!(overlay.view.scroll_offset, usize::MAX);
     }
@@ -1316,13 +2364,52 @@ mod tests {
 
         overlay.view.scroll_offset = 0;
 
-        overlay.insert_cell(Arc::new(TestCell {
-            lines: vec!["tail".into()],
-        }));
+        overlay.insert_cell(
+            Arc::new(TestCell {
+                lines: vec!["tail".into()],
+            }),
+            SystemTime::now(),
+        );
 
         assert_eq!(overlay.view.scroll_offset, 0);
     }
 
+    #[test]
+    fn transcript_overlay_scroll_lock_counts_new_cells_until_end() {
+        let test_cell = |text: &str| -> Arc<dyn HistoryCell> {
+            Arc::new(TestCell {
+                lines: vec![Line::from(text.to_string())],
+            })
+        };
+        let mut overlay =
+            transcript_overlay((0..20).map(|i| test_cell(&format!("line{i}"))).collect());
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+
+        overlay.update_scroll_lock(KeyEvent::from(KeyCode::Up));
+        overlay.view.scroll_offset = 5;
+        overlay.insert_cell(test_cell("new one"), SystemTime::now());
+        overlay.insert_cell(test_cell("new two"), SystemTime::now());
+        overlay.render(area, &mut buf);
+
+        assert_eq!(5, overlay.view.scroll_offset);
+        assert!(buffer_to_text(&buf, area).contains(" 2 new messages ↓ "));
+
+        // Scrolling to the bottom by hand shows the new cells but keeps the lock.
+        overlay.view.scroll_offset = usize::MAX;
+        overlay.render(area, &mut buf);
+        overlay.insert_cell(test_cell("new three"), SystemTime::now());
+        assert_eq!(1, overlay.unseen_cells);
+        assert_ne!(usize::MAX, overlay.view.scroll_offset);
+
+        overlay.update_scroll_lock(KeyEvent::from(KeyCode::End));
+        overlay.view.scroll_offset = usize::MAX;
+        overlay.insert_cell(test_cell("new four"), SystemTime::now());
+        assert_eq!(0, overlay.unseen_cells);
+        assert_eq!(usize::MAX, overlay.view.scroll_offset);
+    }
+
     #[test]
     fn transcript_overlay_insert_preserves_cached_cell_heights() {
         let height_calls = Arc::new(AtomicUsize::new(0));
@@ -1335,9 +2422,12 @@ mod tests {
         overlay.render(area, &mut buf);
         assert_eq!(height_calls.load(Ordering::Relaxed), 1);
 
-        overlay.insert_cell(Arc::new(TestCell {
-            lines: vec![Line::from("inserted")],
-        }));
+        overlay.insert_cell(
+            Arc::new(TestCell {
+                lines: vec![Line::from("inserted")],
+            }),
+            SystemTime::now(),
+        );
         overlay.render(area, &mut buf);
 
         assert_eq!(height_calls.load(Ordering::Relaxed), 1);
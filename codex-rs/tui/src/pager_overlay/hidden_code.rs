@@ -0,0 +1,37 @@
+//! Lines of long code blocks hidden by markdown rendering.
+//!
+//! A fenced code block longer than `tui.code_block_max_lines` keeps its first and last lines
+//! around a `… N lines hidden …` row that carries the rest of its source. `e` draws those lines
+//! in place of the row for every collapsed block in the cell at the top of the view, and hides
+//! them again. They are only highlighted once shown.
+
+use ratatui::text::Line;
+
+use crate::terminal_hyperlinks::HyperlinkLine;
+
+/// Whether any row of `lines` stands for hidden code lines.
+pub(super) fn has_hidden_code(lines: &[HyperlinkLine]) -> bool {
+    lines.iter().any(|line| line.hidden_code.is_some())
+}
+
+/// Replaces every hidden-lines row in `lines` with the lines it stands for, indented like the row
+/// and part of the same code block.
+pub(super) fn show_hidden_code(lines: Vec<HyperlinkLine>) -> Vec<HyperlinkLine> {
+    let mut shown = Vec::with_capacity(lines.len());
+    for line in lines {
+        let Some(hidden) = &line.hidden_code else {
+            shown.push(line);
+            continue;
+        };
+        // The row is its block's indent followed by the hidden-lines label.
+        let indent = &line.line.spans[..line.line.spans.len().saturating_sub(1)];
+        shown.extend(hidden.lines().into_iter().map(|code_line| {
+            let mut spans = indent.to_vec();
+            spans.extend(code_line.spans);
+            let mut shown_line = HyperlinkLine::new(Line::from(spans).style(code_line.style));
+            shown_line.code_block = line.code_block.clone();
+            shown_line
+        }));
+    }
+    shown
+}
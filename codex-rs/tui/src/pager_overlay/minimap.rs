@@ -0,0 +1,92 @@
+//! Minimap-style scrollbar for the transcript overlay.
+//!
+//! When the transcript is taller than the viewport, the overlay reserves its rightmost column for a
+//! scrollbar. The column is a scaled-down map of the whole transcript. The thumb shows the visible
+//! page, and rows that contain a user message, an applied patch, or an error get a colored dot so
+//! the user can see where the landmarks are and jump to them with `[` and `]`.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+
+use crate::history_cell::TranscriptMarker;
+
+const TRACK_SYMBOL: &str = "│";
+const THUMB_SYMBOL: &str = "┃";
+const MARKER_SYMBOL: &str = "●";
+
+/// One row of the scrollbar column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct MinimapRow {
+    /// Whether the row falls inside the visible page.
+    thumb: bool,
+    /// Most important landmark scaled onto this row; errors win over patches over messages.
+    marker: Option<TranscriptMarker>,
+}
+
+/// Scales a transcript of `total_height` rows, scrolled to `scroll_offset`, onto `height` rows.
+///
+/// The viewport is assumed to be `height` rows tall, matching the column beside it. `marks` holds
+/// the content row of each landmark.
+fn minimap_rows(
+    height: usize,
+    total_height: usize,
+    scroll_offset: usize,
+    marks: &[(usize, TranscriptMarker)],
+) -> Vec<MinimapRow> {
+    let mut rows = vec![MinimapRow::default(); height];
+    if height == 0 || total_height == 0 {
+        return rows;
+    }
+    let scale = |content_row: usize| (content_row * height / total_height).min(height - 1);
+    let thumb_len = (height * height / total_height).clamp(1, height);
+    let max_scroll = total_height.saturating_sub(height);
+    let thumb_start = if max_scroll == 0 {
+        0
+    } else {
+        scroll_offset.min(max_scroll) * (height - thumb_len) / max_scroll
+    };
+    for row in &mut rows[thumb_start..thumb_start + thumb_len] {
+        row.thumb = true;
+    }
+    for &(content_row, marker) in marks {
+        let row = &mut rows[scale(content_row)];
+        row.marker = row.marker.max(Some(marker));
+    }
+    rows
+}
+
+/// Draws the scrollbar for a transcript of `total_height` rows into the one-column `area`.
+pub(super) fn render_minimap(
+    area: Rect,
+    buf: &mut Buffer,
+    total_height: usize,
+    scroll_offset: usize,
+    marks: &[(usize, TranscriptMarker)],
+) {
+    let rows = minimap_rows(area.height as usize, total_height, scroll_offset, marks);
+    for (y, row) in (area.y..area.bottom()).zip(rows) {
+        let (symbol, style) = match row.marker {
+            Some(marker) => {
+                let style = marker_style(marker);
+                (MARKER_SYMBOL, if row.thumb { style.bold() } else { style })
+            }
+            None if row.thumb => (THUMB_SYMBOL, Style::default()),
+            None => (TRACK_SYMBOL, Style::default().dim()),
+        };
+        buf[(area.x, y)].set_symbol(symbol).set_style(style);
+    }
+}
+
+fn marker_style(marker: TranscriptMarker) -> Style {
+    match marker {
+        TranscriptMarker::UserMessage => Style::default().cyan(),
+        TranscriptMarker::Patch => Style::default().green(),
+        TranscriptMarker::Error => Style::default().red(),
+    }
+}
+
+#[cfg(test)]
+#[path = "minimap_tests.rs"]
+mod tests;
@@ -0,0 +1,53 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn thumb_rows(rows: &[MinimapRow]) -> Vec<usize> {
+    rows.iter()
+        .enumerate()
+        .filter_map(|(index, row)| row.thumb.then_some(index))
+        .collect()
+}
+
+#[test]
+fn thumb_tracks_the_visible_page() {
+    let top = minimap_rows(
+        /*height*/ 10,
+        /*total_height*/ 40,
+        /*scroll_offset*/ 0,
+        &[],
+    );
+    assert_eq!(vec![0, 1], thumb_rows(&top));
+
+    let bottom = minimap_rows(
+        /*height*/ 10,
+        /*total_height*/ 40,
+        /*scroll_offset*/ 30,
+        &[],
+    );
+    assert_eq!(vec![8, 9], thumb_rows(&bottom));
+}
+
+#[test]
+fn markers_scale_onto_rows_and_errors_win_collisions() {
+    let rows = minimap_rows(
+        /*height*/ 4,
+        /*total_height*/ 100,
+        /*scroll_offset*/ 50,
+        &[
+            (0, TranscriptMarker::UserMessage),
+            (10, TranscriptMarker::Error),
+            (5, TranscriptMarker::Patch),
+            (99, TranscriptMarker::UserMessage),
+        ],
+    );
+
+    assert_eq!(
+        vec![
+            Some(TranscriptMarker::Error),
+            None,
+            None,
+            Some(TranscriptMarker::UserMessage),
+        ],
+        rows.iter().map(|row| row.marker).collect::<Vec<_>>()
+    );
+}
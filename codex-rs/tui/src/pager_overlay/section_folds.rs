@@ -0,0 +1,82 @@
+//! Collapsible markdown sections for the transcript overlay.
+//!
+//! Every markdown heading in a cell opens a section that runs until the next heading of the same
+//! or a higher level, or the end of the cell. `z` folds or unfolds the section at the top of the
+//! view and `Z` folds or unfolds every section, so a multi-page answer can be read as an outline.
+//! A folded section keeps its heading row and says how many rows it hides.
+
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use ratatui::style::Stylize;
+use ratatui::text::Span;
+
+use crate::terminal_hyperlinks::HyperlinkLine;
+
+/// Row ranges of the sections opened by each heading in `lines`, in heading order. Each range
+/// starts at its heading row.
+pub(super) fn sections(lines: &[HyperlinkLine]) -> Vec<Range<usize>> {
+    let headings: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(row, line)| Some((row, line.heading_level?)))
+        .collect();
+    headings
+        .iter()
+        .enumerate()
+        .map(|(index, &(start, level))| {
+            let end = headings[index + 1..]
+                .iter()
+                .find(|&&(_, next_level)| next_level <= level)
+                .map_or(lines.len(), |&(row, _)| row);
+            start..end
+        })
+        .collect()
+}
+
+/// Drops the rows of each section in `folded` after its heading, pairing every kept row with its
+/// index in `lines`. Folded headings end with a dimmed count of the rows they hide.
+pub(super) fn fold_lines(
+    lines: Vec<HyperlinkLine>,
+    folded: &BTreeSet<usize>,
+) -> Vec<(usize, HyperlinkLine)> {
+    let mut hidden = vec![false; lines.len()];
+    let mut hidden_counts = vec![0; lines.len()];
+    for (index, section) in sections(&lines).into_iter().enumerate() {
+        // Sections inside an already folded one are hidden with it.
+        if !folded.contains(&index) || hidden[section.start] {
+            continue;
+        }
+        hidden_counts[section.start] = section.len() - 1;
+        hidden[section.start + 1..section.end].fill(true);
+    }
+    lines
+        .into_iter()
+        .enumerate()
+        .filter(|(row, _)| !hidden[*row])
+        .map(|(row, mut line)| {
+            if hidden_counts[row] > 0 {
+                line.line.push_span(fold_marker(hidden_counts[row]));
+            }
+            (row, line)
+        })
+        .collect()
+}
+
+/// The section `z` acts on for content row `row`: the innermost section containing it, or the
+/// first one below it when the row comes before every heading.
+pub(super) fn section_to_toggle(sections: &[Range<usize>], row: usize) -> Option<usize> {
+    sections
+        .iter()
+        .rposition(|section| section.contains(&row))
+        .or_else(|| sections.iter().position(|section| section.start > row))
+}
+
+fn fold_marker(hidden_rows: usize) -> Span<'static> {
+    let noun = if hidden_rows == 1 { "line" } else { "lines" };
+    format!(" ⋯ {hidden_rows} {noun}").dim()
+}
+
+#[cfg(test)]
+#[path = "section_folds_tests.rs"]
+mod tests;
@@ -0,0 +1,89 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use ratatui::text::Line;
+
+fn line(text: &'static str) -> HyperlinkLine {
+    let mut line = HyperlinkLine::from(text);
+    line.heading_level = text
+        .find(|ch| ch != '#')
+        .filter(|&level| level > 0 && text[level..].starts_with(' '))
+        .and_then(|level| u8::try_from(level).ok());
+    line
+}
+
+fn doc() -> Vec<HyperlinkLine> {
+    vec![
+        line("Intro"),
+        line("# Design"),
+        line("overview"),
+        line("## Goals"),
+        line("fast"),
+        line("## Plan"),
+        line("step one"),
+        line("step two"),
+        line("# Appendix"),
+        line("notes"),
+    ]
+}
+
+fn text(lines: &[(usize, HyperlinkLine)]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|(_, line)| {
+            line.line
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn sections_run_until_the_next_heading_at_the_same_or_higher_level() {
+    assert_eq!(sections(&doc()), vec![1..8, 3..5, 5..8, 8..10]);
+}
+
+#[test]
+fn folded_sections_keep_their_heading_and_count_hidden_rows() {
+    let folded = fold_lines(doc(), &BTreeSet::from([1, 3]));
+    assert_eq!(
+        text(&folded),
+        vec![
+            "Intro",
+            "# Design",
+            "overview",
+            "## Goals ⋯ 1 line",
+            "## Plan",
+            "step one",
+            "step two",
+            "# Appendix ⋯ 1 line",
+        ]
+    );
+    assert_eq!(
+        folded.iter().map(|(row, _)| *row).collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 5, 6, 7, 8]
+    );
+}
+
+#[test]
+fn folding_an_outer_section_hides_nested_ones() {
+    let folded = fold_lines(doc(), &BTreeSet::from([0, 2]));
+    assert_eq!(
+        text(&folded),
+        vec!["Intro", "# Design ⋯ 6 lines", "# Appendix", "notes"]
+    );
+}
+
+#[test]
+fn toggle_targets_the_innermost_section_or_the_next_heading() {
+    let sections = sections(&doc());
+    assert_eq!(section_to_toggle(&sections, /*row*/ 0), Some(0));
+    assert_eq!(section_to_toggle(&sections, /*row*/ 2), Some(0));
+    assert_eq!(section_to_toggle(&sections, /*row*/ 4), Some(1));
+    assert_eq!(section_to_toggle(&sections, /*row*/ 9), Some(3));
+    assert_eq!(
+        section_to_toggle(&sections(&[Line::from("plain").into()]), /*row*/ 0),
+        None
+    );
+}
@@ -200,7 +200,10 @@ impl AmbientPet {
     }
 
     fn next_frame_delay(&self) -> Option<Duration> {
-        if self.support.protocol().is_none() || !self.animations_enabled {
+        if self.support.protocol().is_none()
+            || !self.animations_enabled
+            || crate::idle_pause::background_work_paused()
+        {
             return None;
         }
 
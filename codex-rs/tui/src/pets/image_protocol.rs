@@ -11,17 +11,18 @@ use base64::Engine as _;
 use base64::engine::general_purpose;
 use codex_terminal_detection::Multiplexer;
 use codex_terminal_detection::TerminalInfo;
-use codex_terminal_detection::TerminalName;
 use codex_terminal_detection::terminal_info;
 use image::imageops::FilterType;
 
 use super::sixel;
+use crate::terminal_capabilities::TerminalCapabilities;
+use crate::terminal_capabilities::is_iterm2_terminal;
+use crate::terminal_capabilities::terminal_capabilities;
 
 const ESC: &str = "\x1b";
 const ST: &str = "\x1b\\";
 const KITTY_CHUNK_SIZE: usize = 4096;
 const SIXEL_CACHE_VERSION: &str = "v2";
-const ITERM2_KITTY_MIN_VERSION: (u64, u64, u64) = (3, 6, 0);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ImageProtocol {
@@ -121,18 +122,13 @@ pub(crate) fn detect_pet_image_support() -> PetImageSupport {
         return PetImageSupport::Unsupported(PetImageUnsupportedReason::Zellij);
     }
 
-    if env::var_os("KITTY_WINDOW_ID").is_some() {
-        return PetImageSupport::Supported(ImageProtocol::Kitty);
-    }
-
-    if env::var_os("WEZTERM_EXECUTABLE").is_some() || env::var_os("WEZTERM_VERSION").is_some() {
-        return PetImageSupport::Supported(ImageProtocol::Kitty);
-    }
-
-    pet_image_support_for_terminal(&terminal_info())
+    pet_image_support_for_terminal(&terminal_info(), terminal_capabilities())
 }
 
-fn pet_image_support_for_terminal(info: &TerminalInfo) -> PetImageSupport {
+fn pet_image_support_for_terminal(
+    info: &TerminalInfo,
+    capabilities: TerminalCapabilities,
+) -> PetImageSupport {
     match info.multiplexer {
         Some(Multiplexer::Tmux { .. }) => {
             return PetImageSupport::Unsupported(PetImageUnsupportedReason::Tmux);
@@ -143,77 +139,23 @@ fn pet_image_support_for_terminal(info: &TerminalInfo) -> PetImageSupport {
         None => {}
     }
 
-    if supports_iterm2_kitty_graphics(info) {
+    if capabilities.kitty_graphics && is_iterm2_terminal(info) {
         return PetImageSupport::Supported(ImageProtocol::KittyLocalFile);
     }
 
-    if is_iterm2_terminal(info) {
-        return PetImageSupport::Unsupported(PetImageUnsupportedReason::Iterm2TooOld);
-    }
-
-    if supports_kitty_graphics(info) {
+    if capabilities.kitty_graphics {
         return PetImageSupport::Supported(ImageProtocol::Kitty);
     }
 
-    if supports_sixel(info) {
+    if capabilities.sixel {
         return PetImageSupport::Supported(ImageProtocol::Sixel);
     }
 
-    PetImageSupport::Unsupported(PetImageUnsupportedReason::Terminal)
-}
-
-fn supports_iterm2_kitty_graphics(info: &TerminalInfo) -> bool {
-    is_iterm2_terminal(info)
-        && version_is_at_least(
-            info.version.as_deref(),
-            /*minimum*/ ITERM2_KITTY_MIN_VERSION,
-        )
-}
-
-fn is_iterm2_terminal(info: &TerminalInfo) -> bool {
-    matches!(info.name, TerminalName::Iterm2)
-        || terminal_field_contains(info.term_program.as_deref(), "iterm")
-}
-
-fn supports_kitty_graphics(info: &TerminalInfo) -> bool {
-    matches!(
-        info.name,
-        TerminalName::Ghostty | TerminalName::Kitty | TerminalName::WezTerm
-    ) || terminal_field_contains(info.term.as_deref(), "kitty")
-        || terminal_field_contains(info.term.as_deref(), "ghostty")
-        || terminal_field_contains(info.term.as_deref(), "wezterm")
-        || terminal_field_contains(info.term_program.as_deref(), "kitty")
-        || terminal_field_contains(info.term_program.as_deref(), "ghostty")
-        || terminal_field_contains(info.term_program.as_deref(), "wezterm")
-}
-
-fn supports_sixel(info: &TerminalInfo) -> bool {
-    matches!(info.name, TerminalName::WindowsTerminal)
-        || terminal_field_contains(info.term.as_deref(), "sixel")
-        || terminal_field_contains(info.term.as_deref(), "mlterm")
-        || terminal_field_contains(info.term.as_deref(), "foot")
-}
-
-fn terminal_field_contains(value: Option<&str>, needle: &str) -> bool {
-    value.is_some_and(|value| value.to_ascii_lowercase().contains(needle))
-}
-
-fn version_is_at_least(version: Option<&str>, minimum: (u64, u64, u64)) -> bool {
-    parse_dotted_version(version).is_some_and(|version| version >= minimum)
-}
-
-fn parse_dotted_version(version: Option<&str>) -> Option<(u64, u64, u64)> {
-    let version = version?;
-    let mut parts = version.split('.');
-    let major = parts.next()?.parse().ok()?;
-    let minor = parts.next().unwrap_or("0").parse().ok()?;
-    let patch = parts.next().unwrap_or("0").parse().ok()?;
-
-    if parts.next().is_some() {
-        return None;
+    if is_iterm2_terminal(info) {
+        return PetImageSupport::Unsupported(PetImageUnsupportedReason::Iterm2TooOld);
     }
 
-    Some((major, minor, patch))
+    PetImageSupport::Unsupported(PetImageUnsupportedReason::Terminal)
 }
 
 pub fn kitty_delete_image(image_id: u32) -> String {
@@ -311,9 +253,11 @@ pub fn sixel_frame(frame_path: &Path, cache_dir: &Path, height_px: u16) -> Resul
 
 #[cfg(test)]
 mod tests {
+    use codex_terminal_detection::TerminalName;
     use serial_test::serial;
 
     use super::*;
+    use crate::terminal_palette::StdoutColorLevel;
 
     struct EnvVarGuard {
         name: &'static str,
@@ -413,7 +357,7 @@ mod tests {
     #[test]
     fn pet_image_support_prefers_multiplexer_safety() {
         assert_eq!(
-            pet_image_support_for_terminal(&terminal_info_for_test(
+            support_for_terminal(&terminal_info_for_test(
                 TerminalName::Ghostty,
                 Some(Multiplexer::Tmux { version: None }),
                 Some("Ghostty"),
@@ -422,7 +366,7 @@ mod tests {
             PetImageSupport::Unsupported(PetImageUnsupportedReason::Tmux)
         );
         assert_eq!(
-            pet_image_support_for_terminal(&terminal_info_for_test(
+            support_for_terminal(&terminal_info_for_test(
                 TerminalName::Kitty,
                 Some(Multiplexer::Zellij { version: None }),
                 Some("kitty"),
@@ -451,7 +395,7 @@ mod tests {
             ),
         ] {
             assert_eq!(
-                pet_image_support_for_terminal(&info),
+                support_for_terminal(&info),
                 PetImageSupport::Supported(ImageProtocol::KittyLocalFile)
             );
         }
@@ -483,7 +427,7 @@ mod tests {
             ),
         ] {
             assert_eq!(
-                pet_image_support_for_terminal(&info),
+                support_for_terminal(&info),
                 PetImageSupport::Unsupported(PetImageUnsupportedReason::Iterm2TooOld)
             );
         }
@@ -541,7 +485,7 @@ mod tests {
             ),
         ] {
             assert_eq!(
-                pet_image_support_for_terminal(&info),
+                support_for_terminal(&info),
                 PetImageSupport::Supported(ImageProtocol::Kitty)
             );
         }
@@ -576,34 +520,16 @@ mod tests {
             ),
         ] {
             assert_eq!(
-                pet_image_support_for_terminal(&info),
+                support_for_terminal(&info),
                 PetImageSupport::Supported(ImageProtocol::Sixel)
             );
         }
     }
 
-    #[test]
-    #[serial]
-    fn wezterm_env_uses_kitty_graphics_for_ambient_pets() {
-        let _tmux = EnvVarGuard::new("TMUX", /*value*/ None);
-        let _tmux_pane = EnvVarGuard::new("TMUX_PANE", /*value*/ None);
-        let _zellij = EnvVarGuard::new("ZELLIJ", /*value*/ None);
-        let _zellij_session = EnvVarGuard::new("ZELLIJ_SESSION_NAME", /*value*/ None);
-        let _zellij_version = EnvVarGuard::new("ZELLIJ_VERSION", /*value*/ None);
-        let _kitty = EnvVarGuard::new("KITTY_WINDOW_ID", /*value*/ None);
-        let _wezterm = EnvVarGuard::new("WEZTERM_VERSION", Some("20240203"));
-        let _wezterm_executable = EnvVarGuard::new("WEZTERM_EXECUTABLE", /*value*/ None);
-
-        assert_eq!(
-            detect_pet_image_support(),
-            PetImageSupport::Supported(ImageProtocol::Kitty)
-        );
-    }
-
     #[test]
     fn pet_image_support_rejects_unknown_terminals() {
         assert_eq!(
-            pet_image_support_for_terminal(&terminal_info_for_test(
+            support_for_terminal(&terminal_info_for_test(
                 TerminalName::Unknown,
                 /*multiplexer*/ None,
                 /*term_program*/ None,
@@ -613,6 +539,13 @@ mod tests {
         );
     }
 
+    fn support_for_terminal(info: &TerminalInfo) -> PetImageSupport {
+        pet_image_support_for_terminal(
+            info,
+            TerminalCapabilities::detect(info, StdoutColorLevel::TrueColor),
+        )
+    }
+
     fn terminal_info_for_test(
         name: TerminalName,
         multiplexer: Option<Multiplexer>,
@@ -644,16 +577,6 @@ mod tests {
         }
     }
 
-    #[test]
-    fn parse_dotted_version_requires_simple_numeric_components() {
-        assert_eq!(parse_dotted_version(Some("3.6.10")), Some((3, 6, 10)));
-        assert_eq!(parse_dotted_version(Some("3.6")), Some((3, 6, 0)));
-        assert_eq!(parse_dotted_version(Some("3")), Some((3, 0, 0)));
-        assert_eq!(parse_dotted_version(Some("3.6.10.1")), None);
-        assert_eq!(parse_dotted_version(Some("3.6beta")), None);
-        assert_eq!(parse_dotted_version(/*version*/ None), None);
-    }
-
     #[test]
     fn sixel_frame_encodes_without_external_crate() {
         let dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,278 @@
+//! Off-thread syntax highlighting for large code, such as multi-thousand-line diffs.
+//!
+//! Highlighting a large file can take long enough to stall a frame, and the transcript overlay
+//! redraws its cells on every frame. [`highlight_code_to_styled_spans_in_background`] returns
+//! cached spans when a worker thread has already highlighted the same code; otherwise it queues
+//! the code and returns [`Highlight::Pending`], so the caller draws plain text. When the worker
+//! finishes it stores the spans and requests a frame, and the next draw picks them up.
+//!
+//! Lines written to terminal scrollback are drawn once and cannot be redrawn, so they use
+//! [`highlight_code_to_styled_spans_cached`] instead. It highlights inline on a miss and stores
+//! the result, and the transcript overlay (`Ctrl+T`) then finds it in the cache.
+//!
+//! Results are keyed by a hash of the code and language plus [`syntax_theme_revision`], so a
+//! theme change highlights again.
+
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::mpsc;
+
+use ratatui::text::Span;
+
+use crate::render::highlight::highlight_code_to_styled_spans;
+use crate::render::highlight::syntax_theme_revision;
+use crate::tui::FrameRequester;
+
+/// Inputs with fewer lines are highlighted inline; they are fast enough not to stall a frame.
+const BACKGROUND_HIGHLIGHT_MIN_LINES: usize = 1_000;
+
+/// Number of highlighted inputs kept; older results are dropped first.
+const CACHE_CAPACITY: usize = 32;
+
+type StyledLines = Vec<Vec<Span<'static>>>;
+
+static HIGHLIGHTER: OnceLock<BackgroundHighlighter> = OnceLock::new();
+
+/// Starts highlighting large inputs off the draw thread, requesting a frame from `frame_requester`
+/// whenever a result is ready. Until this is called, every input is highlighted inline.
+pub(crate) fn enable_background_highlighting(frame_requester: FrameRequester) {
+    let _ = HIGHLIGHTER.get_or_init(|| {
+        BackgroundHighlighter::new(Box::new(move || frame_requester.schedule_frame()))
+    });
+}
+
+/// Highlighting of an input, as far as it is known.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Highlight {
+    /// The highlighted lines, or `None` when the input cannot be highlighted.
+    Ready(Option<StyledLines>),
+    /// A worker is still highlighting the input; draw it plain until the next frame.
+    Pending,
+}
+
+impl Highlight {
+    /// The highlighted lines, treating a pending result like one that cannot be highlighted.
+    pub(crate) fn ready(self) -> Option<StyledLines> {
+        match self {
+            Self::Ready(lines) => lines,
+            Self::Pending => None,
+        }
+    }
+}
+
+/// Same as [`highlight_code_to_styled_spans`], except that large inputs are highlighted on a
+/// worker thread and are [`Highlight::Pending`] until the result is ready.
+pub(crate) fn highlight_code_to_styled_spans_in_background(code: &str, lang: &str) -> Highlight {
+    match HIGHLIGHTER.get() {
+        Some(highlighter) if code.lines().count() >= BACKGROUND_HIGHLIGHT_MIN_LINES => {
+            highlighter.highlight(code, lang)
+        }
+        _ => Highlight::Ready(highlight_code_to_styled_spans(code, lang)),
+    }
+}
+
+/// Same as [`highlight_code_to_styled_spans`], except that large inputs are looked up in and
+/// added to the background highlighter's cache. For output that is drawn only once, such as
+/// scrollback, where a plain placeholder would never be replaced.
+pub(crate) fn highlight_code_to_styled_spans_cached(code: &str, lang: &str) -> Option<StyledLines> {
+    match HIGHLIGHTER.get() {
+        Some(highlighter) if code.lines().count() >= BACKGROUND_HIGHLIGHT_MIN_LINES => {
+            highlighter.highlight_now(code, lang)
+        }
+        _ => highlight_code_to_styled_spans(code, lang),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct JobKey {
+    content_hash: u64,
+    theme_revision: u64,
+}
+
+struct Job {
+    key: JobKey,
+    code: String,
+    lang: String,
+}
+
+#[derive(Default)]
+struct Results {
+    /// Finished jobs, oldest first. `None` means the language has no grammar.
+    done: VecDeque<(JobKey, Option<Arc<StyledLines>>)>,
+    pending: HashSet<JobKey>,
+}
+
+struct BackgroundHighlighter {
+    jobs: mpsc::Sender<Job>,
+    results: Arc<Mutex<Results>>,
+}
+
+impl BackgroundHighlighter {
+    fn new(on_ready: Box<dyn Fn() + Send>) -> Self {
+        let (jobs, job_rx) = mpsc::channel::<Job>();
+        let results = Arc::new(Mutex::new(Results::default()));
+        let worker_results = Arc::clone(&results);
+        let spawned = std::thread::Builder::new()
+            .name("codex-highlight".to_string())
+            .spawn(move || {
+                while let Ok(job) = job_rx.recv() {
+                    let lines = highlight_code_to_styled_spans(&job.code, &job.lang).map(Arc::new);
+                    let Ok(mut results) = worker_results.lock() else {
+                        return;
+                    };
+                    results.pending.remove(&job.key);
+                    results.store(job.key, lines);
+                    drop(results);
+                    on_ready();
+                }
+            });
+        if let Err(err) = spawned {
+            tracing::warn!("failed to start the highlighting thread: {err}");
+        }
+        Self { jobs, results }
+    }
+
+    /// Returns the highlighted lines if they are ready, queueing the input otherwise.
+    fn highlight(&self, code: &str, lang: &str) -> Highlight {
+        let key = job_key(code, lang);
+        let Ok(mut results) = self.results.lock() else {
+            return Highlight::Ready(highlight_code_to_styled_spans(code, lang));
+        };
+        if let Some(lines) = results.get(key) {
+            return Highlight::Ready(lines);
+        }
+        if results.pending.insert(key) {
+            let job = Job {
+                key,
+                code: code.to_string(),
+                lang: lang.to_string(),
+            };
+            if self.jobs.send(job).is_err() {
+                // The worker is gone; highlight inline rather than staying plain forever.
+                results.pending.remove(&key);
+                drop(results);
+                return Highlight::Ready(highlight_code_to_styled_spans(code, lang));
+            }
+        }
+        Highlight::Pending
+    }
+
+    /// Returns the cached highlighted lines, highlighting inline and caching them on a miss.
+    fn highlight_now(&self, code: &str, lang: &str) -> Option<StyledLines> {
+        let key = job_key(code, lang);
+        if let Some(lines) = self
+            .results
+            .lock()
+            .ok()
+            .and_then(|results| results.get(key))
+        {
+            return lines;
+        }
+        let lines = highlight_code_to_styled_spans(code, lang);
+        if let Ok(mut results) = self.results.lock() {
+            results.store(key, lines.clone().map(Arc::new));
+        }
+        lines
+    }
+}
+
+impl Results {
+    /// The finished result for `key`, if any.
+    fn get(&self, key: JobKey) -> Option<Option<StyledLines>> {
+        self.done
+            .iter()
+            .find(|(done, _)| *done == key)
+            .map(|(_, lines)| lines.as_deref().cloned())
+    }
+
+    fn store(&mut self, key: JobKey, lines: Option<Arc<StyledLines>>) {
+        if self.done.iter().any(|(done, _)| *done == key) {
+            return;
+        }
+        if self.done.len() >= CACHE_CAPACITY {
+            self.done.pop_front();
+        }
+        self.done.push_back((key, lines));
+    }
+}
+
+fn job_key(code: &str, lang: &str) -> JobKey {
+    let mut hasher = DefaultHasher::new();
+    lang.hash(&mut hasher);
+    code.hash(&mut hasher);
+    JobKey {
+        content_hash: hasher.finish(),
+        theme_revision: syntax_theme_revision(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn results_arrive_after_the_worker_finishes() {
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let highlighter = BackgroundHighlighter::new(Box::new(move || {
+            let _ = ready_tx.send(());
+        }));
+        let code = "fn main() {}\n".repeat(BACKGROUND_HIGHLIGHT_MIN_LINES);
+
+        assert_eq!(highlighter.highlight(&code, "rust"), Highlight::Pending);
+        ready_rx
+            .recv_timeout(Duration::from_secs(30))
+            .expect("worker should finish");
+
+        assert_eq!(
+            highlighter.highlight(&code, "rust"),
+            Highlight::Ready(highlight_code_to_styled_spans(&code, "rust"))
+        );
+        assert!(
+            ready_rx.try_recv().is_err(),
+            "the input was highlighted once"
+        );
+    }
+
+    #[test]
+    fn unknown_languages_resolve_to_plain_text() {
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let highlighter = BackgroundHighlighter::new(Box::new(move || {
+            let _ = ready_tx.send(());
+        }));
+
+        assert_eq!(highlighter.highlight("x", "xyzlang"), Highlight::Pending);
+        ready_rx
+            .recv_timeout(Duration::from_secs(30))
+            .expect("worker should finish");
+        assert_eq!(
+            highlighter.highlight("x", "xyzlang"),
+            Highlight::Ready(None)
+        );
+        assert!(ready_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn inline_results_are_cached_for_later_frames() {
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let highlighter = BackgroundHighlighter::new(Box::new(move || {
+            let _ = ready_tx.send(());
+        }));
+        let code = "let x = 1;\n".repeat(BACKGROUND_HIGHLIGHT_MIN_LINES);
+        let expected = highlight_code_to_styled_spans(&code, "rust");
+
+        assert_eq!(highlighter.highlight_now(&code, "rust"), expected);
+        assert_eq!(
+            highlighter.highlight(&code, "rust"),
+            Highlight::Ready(expected)
+        );
+        assert!(ready_rx.try_recv().is_err(), "the worker was not needed");
+    }
+}
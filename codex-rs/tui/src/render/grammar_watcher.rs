@@ -0,0 +1,64 @@
+//! Reloads custom grammars while they are being written.
+//!
+//! Custom `.sublime-syntax` grammars under `{CODEX_HOME}/grammars/` are
+//! normally loaded once.  With `CODEX_TUI_WATCH_GRAMMARS=1`, the directory is
+//! watched and every change reloads the grammars and redraws, so a grammar
+//! author can edit scopes and see the result without restarting Codex.
+//! Scrollback already written to the terminal keeps its old colors; the
+//! transcript overlay (`Ctrl+T`) and new output use the reloaded grammars.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_file_watcher::DebouncedWatchReceiver;
+use codex_file_watcher::FileWatcher;
+use codex_file_watcher::WatchPath;
+
+use crate::render::highlight::custom_grammars_dir;
+use crate::render::highlight::reload_custom_grammars;
+use crate::tui::FrameRequester;
+
+/// Editors often save a file as several writes; reload once per burst.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Starts watching `{codex_home}/grammars/` when `CODEX_TUI_WATCH_GRAMMARS`
+/// is set.  Must be called from within the Tokio runtime.
+pub(crate) fn maybe_watch_custom_grammars(codex_home: &Path, frame_requester: FrameRequester) {
+    let enabled = std::env::var("CODEX_TUI_WATCH_GRAMMARS")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "yes" | "YES"))
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+
+    let file_watcher = match FileWatcher::new() {
+        Ok(file_watcher) => Arc::new(file_watcher),
+        Err(err) => {
+            tracing::warn!("failed to watch custom grammars: {err}");
+            return;
+        }
+    };
+    let dir = custom_grammars_dir(codex_home);
+    let (subscriber, rx) = file_watcher.add_subscriber();
+    let registration = subscriber.register_paths(vec![WatchPath {
+        path: dir.clone(),
+        recursive: true,
+    }]);
+    tracing::info!("watching custom grammars in {}", dir.display());
+
+    tokio::spawn(async move {
+        // Keep the watch alive for as long as the task runs.
+        let _watch = (file_watcher, subscriber, registration);
+        let mut rx = DebouncedWatchReceiver::new(rx, RELOAD_DEBOUNCE);
+        while rx.recv().await.is_some() {
+            // Building the grammar set takes a while; keep it off the async workers.
+            if let Err(err) = tokio::task::spawn_blocking(reload_custom_grammars).await {
+                tracing::warn!("failed to reload custom grammars: {err}");
+                continue;
+            }
+            tracing::info!("reloaded custom grammars from {}", dir.display());
+            frame_requester.schedule_frame();
+        }
+    });
+}
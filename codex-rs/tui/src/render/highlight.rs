@@ -1,46 +1,102 @@
 //! Syntax highlighting engine for the TUI.
 //!
 //! Wraps [syntect] with the [two_face] grammar and theme bundles to provide
-//! ~250-language syntax highlighting and 32 bundled color themes.  The module
-//! owns five process-global singletons:
+//! ~250-language syntax highlighting and 32 bundled color themes, plus a
+//! `high-contrast` theme defined here.  The module owns nine process-global
+//! singletons:
 //!
 //! | Singleton | Type | Purpose |
 //! |---|---|---|
-//! | `SYNTAX_SET` | `OnceLock<SyntaxSet>` | Grammar database, immutable after init |
+//! | `SYNTAX_SET` | `RwLock<Option<Arc<SyntaxSet>>>` | Grammar database, replaced only when custom grammars are reloaded |
 //! | `THEME` | `OnceLock<RwLock<Theme>>` | Active color theme, swappable at runtime |
 //! | `THEME_REVISION` | `AtomicU64` | Invalidates rendered-content caches after theme swaps |
 //! | `THEME_OVERRIDE` | `OnceLock<Option<String>>` | Persisted user preference (write-once) |
-//! | `CODEX_HOME` | `OnceLock<Option<PathBuf>>` | Root for custom `.tmTheme` discovery |
+//! | `CODEX_HOME` | `OnceLock<Option<PathBuf>>` | Root for custom `.tmTheme` and grammar discovery |
+//! | `SYNTAX_COLORS` | `RwLock<Vec<ThemeItem>>` | `[tui.syntax_colors]` overrides, replaced on config reload |
+//! | `STREAM_HIGHLIGHTER` | `Mutex<IncrementalHighlighter>` | Parse checkpoints of recently rendered code blocks |
+//! | `RAINBOW_BRACKETS` | `AtomicBool` | `tui.rainbow_brackets`, colors brackets by nesting depth |
+//! | `COLOR_LEVEL` | `RwLock<StdoutColorLevel>` | Terminal color support that RGB theme colors are quantized to |
 //!
 //! **Lifecycle:** call [`set_theme_override`] once at startup (after the final
 //! config is resolved) to persist the user preference and seed the `THEME`
 //! lock.  After that, [`set_syntax_theme`] and [`current_syntax_theme`] can
 //! swap/snapshot the theme for live preview.  All highlighting functions read
-//! the theme via `theme_lock()`.
+//! the theme via `theme_lock()`.  Every theme resolved by name has the
+//! `SYNTAX_COLORS` overrides layered on top, so call [`set_syntax_colors`]
+//! before resolving when the config changes.
+//!
+//! **Custom grammars:** `.sublime-syntax` files under `{CODEX_HOME}/grammars/`
+//! are added to the bundled set the first time it is used, so call
+//! [`set_theme_override`] before highlighting anything.  A user grammar wins
+//! over a bundled one that claims the same extension.  Grammar authors can
+//! set `CODEX_TUI_WATCH_GRAMMARS=1` to reload them on every change, see
+//! `grammar_watcher`.
+//!
+//! **Streaming:** the stream controllers re-render the whole message on every
+//! committed line, so [`highlight_streamed_code_to_lines`] keeps the parser
+//! state at the end of each recently highlighted code block.  When a block
+//! comes back with more lines appended, only the new lines are highlighted.
+//!
+//! **Injection:** languages a grammar embeds itself (`<script>` in HTML,
+//! fenced code in Markdown) are resolved by syntect.  In addition, string
+//! literals whose content opens with a capitalized SQL keyword are highlighted
+//! with the SQL grammar, see `BlockHighlightState`.
+//!
+//! **Rainbow brackets:** with `tui.rainbow_brackets`, brackets outside strings
+//! and comments cycle through [`RAINBOW_BRACKET_COLORS`] by nesting depth, see
+//! `BracketDepth`.
+//!
+//! **Rust:** macro invocations such as `println!` take the theme color of
+//! `support.macro`, and `unsafe { .. }` blocks get a faint warning background,
+//! see `RustEmphasis`.
+//!
+//! **Comment markers:** `TODO`, `FIXME`, `XXX`, and `SAFETY:` inside comments
+//! are drawn bold in a warning color, see [`comment_marker_styles`].
 //!
 //! **Guardrails:** inputs exceeding 512 KB or 10 000 lines are rejected early
 //! (returns `None`) to prevent pathological CPU/memory usage.  Callers must
 //! fall back to plain unstyled text.
 
+use crate::color::blend;
+use crate::render::tabs::expand_tabs;
+use crate::render::tabs::tab_width_for;
+use crate::terminal_palette::StdoutColorLevel;
+use crate::terminal_palette::foreground_color_for_level;
 use ratatui::style::Color as RtColor;
 use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::text::Line;
 use ratatui::text::Span;
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::OnceLock;
+use std::sync::PoisonError;
 use std::sync::RwLock;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
-use syntect::easy::HighlightLines;
 use syntect::highlighting::Color as SyntectColor;
 use syntect::highlighting::FontStyle;
+use syntect::highlighting::HighlightIterator;
+use syntect::highlighting::HighlightState;
 use syntect::highlighting::Highlighter;
+use syntect::highlighting::ScopeSelectors;
 use syntect::highlighting::Style as SyntectStyle;
+use syntect::highlighting::StyleModifier;
 use syntect::highlighting::Theme;
+use syntect::highlighting::ThemeItem;
 use syntect::highlighting::ThemeSet;
+use syntect::highlighting::ThemeSettings;
+use syntect::parsing::ParseState;
 use syntect::parsing::Scope;
+use syntect::parsing::ScopeStack;
+use syntect::parsing::ScopeStackOp;
 use syntect::parsing::SyntaxReference;
 use syntect::parsing::SyntaxSet;
 use syntect::util::LinesWithEndings;
@@ -48,11 +104,16 @@ use two_face::theme::EmbeddedThemeName;
 
 // -- Global singletons -------------------------------------------------------
 
-static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+static SYNTAX_SET: RwLock<Option<Arc<SyntaxSet>>> = RwLock::new(None);
 static THEME: OnceLock<RwLock<Theme>> = OnceLock::new();
 static THEME_REVISION: AtomicU64 = AtomicU64::new(0);
 static THEME_OVERRIDE: OnceLock<Option<String>> = OnceLock::new();
 static CODEX_HOME: OnceLock<Option<PathBuf>> = OnceLock::new();
+static SYNTAX_COLORS: RwLock<Vec<ThemeItem>> = RwLock::new(Vec::new());
+static RAINBOW_BRACKETS: AtomicBool = AtomicBool::new(false);
+static COLOR_LEVEL: RwLock<StdoutColorLevel> = RwLock::new(StdoutColorLevel::TrueColor);
+static STREAM_HIGHLIGHTER: Mutex<IncrementalHighlighter> =
+    Mutex::new(IncrementalHighlighter::new());
 
 // Syntect/bat encode ANSI palette semantics in alpha:
 // `a=0` => indexed ANSI palette via RGB payload, `a=1` => terminal default.
@@ -60,8 +121,56 @@ const ANSI_ALPHA_INDEX: u8 = 0x00;
 const ANSI_ALPHA_DEFAULT: u8 = 0x01;
 const OPAQUE_ALPHA: u8 = 0xFF;
 
-fn syntax_set() -> &'static SyntaxSet {
-    SYNTAX_SET.get_or_init(two_face::syntax::extra_newlines)
+fn syntax_set() -> Arc<SyntaxSet> {
+    if let Some(syntax_set) = SYNTAX_SET
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+    {
+        return Arc::clone(syntax_set);
+    }
+    let mut guard = SYNTAX_SET.write().unwrap_or_else(PoisonError::into_inner);
+    Arc::clone(guard.get_or_insert_with(|| Arc::new(load_syntax_set())))
+}
+
+fn load_syntax_set() -> SyntaxSet {
+    let bundled = two_face::syntax::extra_newlines();
+    match CODEX_HOME.get().and_then(Option::as_deref) {
+        Some(home) => with_custom_grammars(bundled, &custom_grammars_dir(home)),
+        None => bundled,
+    }
+}
+
+/// Load the grammars again so edits to custom grammars show up without a
+/// restart, and invalidate rendered-content caches.
+///
+/// Code blocks being highlighted keep the set they started with, so the
+/// replaced set is freed once the last of them is done.  Only the grammar
+/// watcher calls this.
+pub(crate) fn reload_custom_grammars() {
+    let syntax_set = Arc::new(load_syntax_set());
+    *SYNTAX_SET.write().unwrap_or_else(PoisonError::into_inner) = Some(syntax_set);
+    THEME_REVISION.fetch_add(1, Ordering::Release);
+}
+
+pub(crate) fn custom_grammars_dir(codex_home: &Path) -> PathBuf {
+    codex_home.join("grammars")
+}
+
+/// Add the `.sublime-syntax` grammars found under `dir` to `bundled`.
+///
+/// A grammar that fails to load is logged and the bundled set is returned
+/// unchanged, so one broken file cannot disable highlighting.
+fn with_custom_grammars(bundled: SyntaxSet, dir: &Path) -> SyntaxSet {
+    if !dir.is_dir() {
+        return bundled;
+    }
+    let mut builder = bundled.clone().into_builder();
+    if let Err(err) = builder.add_from_folder(dir, /*lines_include_newline*/ true) {
+        tracing::warn!("Ignoring custom grammars in {}: {err}", dir.display());
+        return bundled;
+    }
+    builder.build()
 }
 
 // NOTE: We intentionally do NOT emit a runtime diagnostic when an ANSI-family
@@ -112,7 +221,7 @@ pub(crate) fn validate_theme_name(name: Option<&str>, codex_home: Option<&Path>)
         .map(|home| custom_theme_path(name, home).display().to_string())
         .unwrap_or_else(|| format!("$CODEX_HOME/themes/{name}.tmTheme"));
     // Bundled themes always resolve.
-    if parse_theme_name(name).is_some() {
+    if is_bundled_theme_name(name) {
         return None;
     }
     // Custom themes must parse successfully; an unreadable/invalid file should
@@ -175,6 +284,183 @@ fn parse_theme_name(name: &str) -> Option<EmbeddedThemeName> {
     }
 }
 
+/// Kebab-case name of the theme built by [`high_contrast_theme`].
+const HIGH_CONTRAST_THEME_NAME: &str = "high-contrast";
+
+/// Scope colors of the `high-contrast` theme as `(selector, ANSI palette
+/// index, bold)`.  Only the eight base colors are used, and never yellow or
+/// white, so the theme stays legible on both light and dark terminals.
+const HIGH_CONTRAST_SCOPES: &[(&str, Option<u8>, bool)] = &[
+    ("comment", Some(0x06), false),
+    ("string, constant.character", Some(0x02), false),
+    ("constant.numeric, constant.language", Some(0x05), false),
+    ("keyword, storage", Some(0x04), true),
+    ("entity.name.function, support.function", None, true),
+    ("entity.name.type, support.type", Some(0x05), true),
+    ("invalid", Some(0x01), true),
+    ("markup.heading", Some(0x04), true),
+    ("markup.inserted", Some(0x02), false),
+    ("markup.deleted", Some(0x01), false),
+];
+
+/// Build the `high-contrast` theme.  Colors use the ANSI alpha encoding, so
+/// the terminal palette decides the exact shades and unlisted scopes keep the
+/// terminal's default foreground.
+fn high_contrast_theme() -> Theme {
+    let terminal_default = SyntectColor {
+        r: 0,
+        g: 0,
+        b: 0,
+        a: ANSI_ALPHA_DEFAULT,
+    };
+    Theme {
+        name: Some("High Contrast".to_string()),
+        settings: ThemeSettings {
+            foreground: Some(terminal_default),
+            background: Some(terminal_default),
+            ..ThemeSettings::default()
+        },
+        scopes: HIGH_CONTRAST_SCOPES
+            .iter()
+            .map(|(selector, index, bold)| ThemeItem {
+                scope: ScopeSelectors::from_str(selector).unwrap_or_else(|err| {
+                    panic!("invalid high-contrast selector {selector}: {err}")
+                }),
+                style: StyleModifier {
+                    foreground: index.map(|index| SyntectColor {
+                        r: index,
+                        g: 0,
+                        b: 0,
+                        a: ANSI_ALPHA_INDEX,
+                    }),
+                    background: None,
+                    font_style: bold.then_some(FontStyle::BOLD),
+                },
+            })
+            .collect(),
+        ..Theme::default()
+    }
+}
+
+fn is_bundled_theme_name(name: &str) -> bool {
+    name == HIGH_CONTRAST_THEME_NAME || parse_theme_name(name).is_some()
+}
+
+/// Resolve a bundled theme: a two-face theme or one defined in this module.
+fn bundled_theme(name: &str) -> Option<Theme> {
+    if name == HIGH_CONTRAST_THEME_NAME {
+        return Some(high_contrast_theme());
+    }
+    parse_theme_name(name).map(|embedded| two_face::theme::extra().get(embedded).clone())
+}
+
+/// Replace the `[tui.syntax_colors]` overrides layered onto every resolved
+/// theme.  Keys are TextMate scope selectors such as `comment` or
+/// `keyword.control`; values are `#rrggbb` or an ANSI color name.
+///
+/// This does not touch the active theme; re-resolve it afterwards.  Returns a
+/// user-facing warning for each entry that could not be parsed.
+pub(crate) fn set_syntax_colors(colors: &BTreeMap<String, String>) -> Vec<String> {
+    let (items, warnings) = parse_syntax_colors(colors);
+    let mut guard = match SYNTAX_COLORS.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *guard = items;
+    warnings
+}
+
+fn parse_syntax_colors(colors: &BTreeMap<String, String>) -> (Vec<ThemeItem>, Vec<String>) {
+    let mut items = Vec::new();
+    let mut warnings = Vec::new();
+    for (selector, value) in colors {
+        let Ok(scope) = ScopeSelectors::from_str(selector) else {
+            warnings.push(format!(
+                "Ignoring tui.syntax_colors entry \"{selector}\": not a valid scope selector."
+            ));
+            continue;
+        };
+        let Some(foreground) = parse_syntax_color(value) else {
+            warnings.push(format!(
+                "Ignoring tui.syntax_colors entry \"{selector}\": \"{value}\" is not a \
+                 #rrggbb color or an ANSI color name."
+            ));
+            continue;
+        };
+        items.push(ThemeItem {
+            scope,
+            style: StyleModifier {
+                foreground: Some(foreground),
+                background: None,
+                font_style: None,
+            },
+        });
+    }
+    (items, warnings)
+}
+
+/// Parse `#rrggbb`, an ANSI color name that follows the terminal palette, or
+/// `default` for the terminal's default foreground.
+fn parse_syntax_color(value: &str) -> Option<SyntectColor> {
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |range: std::ops::Range<usize>| u8::from_str_radix(hex.get(range)?, 16).ok();
+        return Some(SyntectColor {
+            r: channel(0..2)?,
+            g: channel(2..4)?,
+            b: channel(4..6)?,
+            a: OPAQUE_ALPHA,
+        });
+    }
+    let (index, alpha) = match value.to_ascii_lowercase().as_str() {
+        "default" => (0, ANSI_ALPHA_DEFAULT),
+        "black" => (0x00, ANSI_ALPHA_INDEX),
+        "red" => (0x01, ANSI_ALPHA_INDEX),
+        "green" => (0x02, ANSI_ALPHA_INDEX),
+        "yellow" => (0x03, ANSI_ALPHA_INDEX),
+        "blue" => (0x04, ANSI_ALPHA_INDEX),
+        "magenta" => (0x05, ANSI_ALPHA_INDEX),
+        "cyan" => (0x06, ANSI_ALPHA_INDEX),
+        "white" => (0x07, ANSI_ALPHA_INDEX),
+        _ => return None,
+    };
+    Some(SyntectColor {
+        r: index,
+        g: 0,
+        b: 0,
+        a: alpha,
+    })
+}
+
+/// Layer the configured `[tui.syntax_colors]` overrides onto `theme`.
+fn with_syntax_colors(theme: Theme) -> Theme {
+    let colors = match SYNTAX_COLORS.read() {
+        Ok(colors) => colors,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    apply_syntax_colors(theme, &colors)
+}
+
+/// Syntect keeps the first of equally specific matches, so overrides go in
+/// front; theme rules with the same selector are dropped outright.  A more
+/// specific theme rule (`keyword.control` over `keyword`) still wins.
+fn apply_syntax_colors(mut theme: Theme, colors: &[ThemeItem]) -> Theme {
+    if colors.is_empty() {
+        return theme;
+    }
+    let mut scopes = colors.to_vec();
+    scopes.extend(
+        theme
+            .scopes
+            .into_iter()
+            .filter(|item| !colors.iter().any(|color| color.scope == item.scope)),
+    );
+    theme.scopes = scopes;
+    theme
+}
+
 /// Build the expected path for a custom theme file.
 fn custom_theme_path(name: &str, codex_home: &Path) -> PathBuf {
     codex_home.join("themes").join(format!("{name}.tmTheme"))
@@ -207,24 +493,23 @@ pub(crate) fn adaptive_default_theme_name() -> &'static str {
 /// Build the theme from current override/default-theme settings.
 /// Extracted from the old `theme()` init closure so it can be reused.
 fn resolve_theme_with_override(name: Option<&str>, codex_home: Option<&Path>) -> Theme {
-    let ts = two_face::theme::extra();
-
     // Honor user-configured theme if valid.
     if let Some(name) = name {
         // 1. Try bundled theme by kebab-case name.
-        if let Some(theme_name) = parse_theme_name(name) {
-            return ts.get(theme_name).clone();
+        if let Some(theme) = bundled_theme(name) {
+            return with_syntax_colors(theme);
         }
         // 2. Try loading {CODEX_HOME}/themes/{name}.tmTheme from disk.
         if let Some(home) = codex_home
             && let Some(theme) = load_custom_theme(name, home)
         {
-            return theme;
+            return with_syntax_colors(theme);
         }
         tracing::debug!("Theme \"{name}\" not recognized; using default theme");
     }
 
-    ts.get(adaptive_default_embedded_theme_name()).clone()
+    let ts = two_face::theme::extra();
+    with_syntax_colors(ts.get(adaptive_default_embedded_theme_name()).clone())
 }
 
 /// Build the theme from current override/default-theme settings.
@@ -251,6 +536,29 @@ pub(crate) fn set_syntax_theme(theme: Theme) {
     THEME_REVISION.fetch_add(1, Ordering::Release);
 }
 
+/// Applies `tui.rainbow_brackets` from config.  Changing it invalidates
+/// rendered-content caches like a theme swap does.
+pub(crate) fn set_rainbow_brackets(enabled: bool) {
+    if RAINBOW_BRACKETS.swap(enabled, Ordering::Relaxed) != enabled {
+        THEME_REVISION.fetch_add(1, Ordering::Release);
+    }
+}
+
+/// Sets the terminal color support that RGB theme colors are quantized to.
+/// Call once at startup with the detected level; the default assumes
+/// truecolor.  Changing it invalidates rendered-content caches.
+pub(crate) fn set_highlight_color_level(level: StdoutColorLevel) {
+    let mut guard = COLOR_LEVEL.write().unwrap_or_else(PoisonError::into_inner);
+    if *guard != level {
+        *guard = level;
+        THEME_REVISION.fetch_add(1, Ordering::Release);
+    }
+}
+
+fn highlight_color_level() -> StdoutColorLevel {
+    *COLOR_LEVEL.read().unwrap_or_else(PoisonError::into_inner)
+}
+
 /// Return the revision of the active syntax theme for rendered-content caches.
 pub(crate) fn syntax_theme_revision() -> u64 {
     THEME_REVISION.load(Ordering::Acquire)
@@ -332,7 +640,7 @@ fn foreground_style_for_scopes_with_theme(theme: &Theme, scope_names: &[&str]) -
 pub(crate) fn configured_theme_name() -> String {
     // Explicit user override?
     if let Some(Some(name)) = THEME_OVERRIDE.get() {
-        if parse_theme_name(name).is_some() {
+        if is_bundled_theme_name(name) {
             return name.clone();
         }
         if let Some(Some(home)) = CODEX_HOME.get()
@@ -347,16 +655,15 @@ pub(crate) fn configured_theme_name() -> String {
 /// Resolve a theme name to a `Theme` (bundled or custom). Returns `None`
 /// when the name is unknown and no matching `.tmTheme` file exists.
 pub(crate) fn resolve_theme_by_name(name: &str, codex_home: Option<&Path>) -> Option<Theme> {
-    let ts = two_face::theme::extra();
     // Bundled theme?
-    if let Some(embedded) = parse_theme_name(name) {
-        return Some(ts.get(embedded).clone());
+    if let Some(theme) = bundled_theme(name) {
+        return Some(with_syntax_colors(theme));
     }
     // Custom .tmTheme file?
     if let Some(home) = codex_home
         && let Some(theme) = load_custom_theme(name, home)
     {
-        return Some(theme);
+        return Some(with_syntax_colors(theme));
     }
     None
 }
@@ -411,7 +718,7 @@ pub(crate) fn list_available_themes(codex_home: Option<&Path>) -> Vec<ThemeEntry
     entries
 }
 
-/// All 32 bundled theme names in kebab-case, ordered alphabetically.
+/// All 33 bundled theme names in kebab-case, ordered alphabetically.
 const BUILTIN_THEME_NAMES: &[&str] = &[
     "1337",
     "ansi",
@@ -432,6 +739,7 @@ const BUILTIN_THEME_NAMES: &[&str] = &[
     "github",
     "gruvbox-dark",
     "gruvbox-light",
+    "high-contrast",
     "inspired-github",
     "monokai-extended",
     "monokai-extended-bright",
@@ -482,22 +790,34 @@ fn ansi_palette_color(index: u8) -> RtColor {
 /// foreground", allowing the caller to omit the foreground attribute entirely.
 ///
 /// Passing a color from a standard RGB theme (alpha 0xFF) returns
-/// `Some(Rgb(..))`, so this function is backward-compatible with non-ANSI
-/// themes. Unexpected intermediate alpha values are treated as RGB.
-///
-/// `clippy::disallowed_methods` is explicitly allowed here because this helper
-/// intentionally constructs `ratatui::style::Color::Rgb`.
-#[allow(clippy::disallowed_methods)]
+/// `Some(Rgb(..))` on truecolor terminals, so this function is
+/// backward-compatible with non-ANSI themes. On terminals limited to 256 or 16
+/// colors the RGB value is quantized to the nearest palette entry (see
+/// [`set_highlight_color_level`]). Unexpected intermediate alpha values are
+/// treated as RGB.
 fn convert_syntect_color(color: SyntectColor) -> Option<RtColor> {
+    convert_syntect_color_for_level(color, highlight_color_level())
+}
+
+fn convert_syntect_color_for_level(
+    color: SyntectColor,
+    level: StdoutColorLevel,
+) -> Option<RtColor> {
     match color.a {
         // Bat-compatible encoding used by `ansi`, `base16`, and `base16-256`:
         // alpha 0x00 means `r` stores an ANSI palette index, not RGB red.
         ANSI_ALPHA_INDEX => Some(ansi_palette_color(color.r)),
         // alpha 0x01 means "use terminal default foreground/background".
         ANSI_ALPHA_DEFAULT => None,
-        OPAQUE_ALPHA => Some(RtColor::Rgb(color.r, color.g, color.b)),
+        OPAQUE_ALPHA => Some(foreground_color_for_level(
+            (color.r, color.g, color.b),
+            level,
+        )),
         // Non-ANSI alpha values appear in some bundled themes; treat as plain RGB.
-        _ => Some(RtColor::Rgb(color.r, color.g, color.b)),
+        _ => Some(foreground_color_for_level(
+            (color.r, color.g, color.b),
+            level,
+        )),
     }
 }
 
@@ -528,13 +848,12 @@ fn convert_style(syn_style: SyntectStyle) -> Style {
 
 // -- Syntax lookup ------------------------------------------------------------
 
-/// Try to find a syntect `SyntaxReference` for the given language identifier.
+/// Try to find a syntect `SyntaxReference` in `ss` for the given language
+/// identifier.
 ///
 /// two-face's extended syntax set (~250 languages) resolves most names and
 /// extensions directly.  We only patch the few aliases it cannot handle.
-fn find_syntax(lang: &str) -> Option<&'static SyntaxReference> {
-    let ss = syntax_set();
-
+fn find_syntax_in<'a>(ss: &'a SyntaxSet, lang: &str) -> Option<&'a SyntaxReference> {
     // Aliases that two-face does not resolve on its own.
     let normalized = lang.to_ascii_lowercase();
     let patched = match normalized.as_str() {
@@ -544,6 +863,8 @@ fn find_syntax(lang: &str) -> Option<&'static SyntaxReference> {
         "cppm" | "cxxm" | "ixx" => "cpp",
         "golang" => "go",
         "python3" => "python",
+        // Rack's `config.ru`.
+        "ru" => "rb",
         "shell" => "bash",
         _ => lang,
     };
@@ -572,6 +893,230 @@ fn find_syntax(lang: &str) -> Option<&'static SyntaxReference> {
     None
 }
 
+/// Whether a grammar is available for `lang`, a language name or file extension.
+pub(crate) fn has_syntax(lang: &str) -> bool {
+    find_syntax_in(&syntax_set(), lang).is_some()
+}
+
+/// Like [`find_syntax_in`], but picks the grammar variant that fits `code`.
+///
+/// The PHP grammar starts out in HTML and only highlights code inside `<?php` tags, so snippets
+/// and diff hunks of plain PHP use the tag-less "PHP Source" grammar instead.  Only the first
+/// line is checked, so the choice stays put while a code block streams in.
+fn find_syntax_for_code<'a>(
+    ss: &'a SyntaxSet,
+    lang: &str,
+    code: &str,
+) -> Option<&'a SyntaxReference> {
+    let syntax = find_syntax_in(ss, lang)?;
+    let starts_with_markup = code
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(|line| line.trim_start().starts_with('<'));
+    if syntax.name == "PHP" && !starts_with_markup {
+        return ss.find_syntax_by_name("PHP Source").or(Some(syntax));
+    }
+    Some(syntax)
+}
+
+/// Ruby DSL files that are named without an extension, such as a Rails app's `Gemfile`.
+const RUBY_FILE_NAMES: &[&str] = &[
+    "Appfile",
+    "Berksfile",
+    "Brewfile",
+    "Capfile",
+    "Dangerfile",
+    "Fastfile",
+    "Gemfile",
+    "Guardfile",
+    "Podfile",
+    "Rakefile",
+    "Thorfile",
+    "Vagrantfile",
+];
+
+/// Language of a file whose name alone identifies it, such as `Gemfile`.
+///
+/// Returns a file extension, like [`content_language`].
+pub(crate) fn file_name_language(file_name: &str) -> Option<&'static str> {
+    RUBY_FILE_NAMES.contains(&file_name).then_some("rb")
+}
+
+/// Only the start of a header is scanned when telling C from C++.
+const HEADER_SCAN_LINES: usize = 2_000;
+
+/// Line prefixes that only occur in C++ sources.
+const CPP_LINE_PREFIXES: &[&str] = &[
+    "class ",
+    "enum class ",
+    "namespace ",
+    "private:",
+    "protected:",
+    "public:",
+    "template<",
+    "template <",
+    "typename ",
+    "using ",
+    "virtual ",
+];
+
+/// Picks the language for a `.h` header, which C and C++ share: `cpp` when `content` uses
+/// C++-only constructs such as classes, templates, namespaces, or `::`, and `c` otherwise.
+///
+/// `content` may be a unified diff; leading `+`/`-` markers and indentation are ignored.
+pub(crate) fn header_language(content: &str) -> &'static str {
+    let is_cpp = content.lines().take(HEADER_SCAN_LINES).any(|line| {
+        let line = line.trim_start_matches(['+', '-', ' ', '\t']);
+        let is_comment = line.starts_with("//") || line.starts_with("/*") || line.starts_with('*');
+        !is_comment
+            && (line.contains("::")
+                || CPP_LINE_PREFIXES
+                    .iter()
+                    .any(|prefix| line.starts_with(prefix)))
+    });
+    if is_cpp { "cpp" } else { "c" }
+}
+
+/// Vim reads modelines from this many lines at the start and end of a file.
+const MODELINE_SCAN_LINES: usize = 5;
+
+/// Picks the language for a file whose name does not tell, such as an extension-less script,
+/// from its contents: a shebang (`#!/usr/bin/env python3`), a Vim or Emacs modeline, or an
+/// XML, HTML, or JSON opening.
+///
+/// Returns a file extension of the detected grammar, so the result resolves like a path
+/// extension does.  `content` may be a unified diff; hunk headers and `+`/`-` markers are
+/// ignored, so a shebang is only seen when the diff covers the first line.
+pub(crate) fn content_language(content: &str) -> Option<String> {
+    let lines = source_lines(content);
+    let first = lines.iter().position(|line| !line.trim().is_empty())?;
+    let language = lines[first]
+        .strip_prefix("#!")
+        .and_then(shebang_language)
+        .or_else(|| lines.iter().copied().take(2).find_map(emacs_mode))
+        .or_else(|| {
+            let tail = lines.len().saturating_sub(MODELINE_SCAN_LINES);
+            lines
+                .iter()
+                .copied()
+                .take(MODELINE_SCAN_LINES)
+                .chain(lines.iter().copied().skip(tail.max(MODELINE_SCAN_LINES)))
+                .find_map(vim_filetype)
+        })
+        .or_else(|| prologue_language(lines[first].trim_start()))?;
+    let syntax_set = syntax_set();
+    let syntax = find_syntax_in(&syntax_set, &language)?;
+    Some(
+        syntax
+            .file_extensions
+            .first()
+            .unwrap_or(&syntax.name)
+            .clone(),
+    )
+}
+
+/// The new-side source lines of `content`, which may be a unified diff.
+fn source_lines(content: &str) -> Vec<&str> {
+    if !content.starts_with("@@") {
+        return content.lines().collect();
+    }
+    content
+        .lines()
+        .filter(|line| !line.starts_with("@@") && !line.starts_with('-'))
+        .map(|line| line.get(1..).unwrap_or_default())
+        .collect()
+}
+
+/// Language of the interpreter named by a shebang, given the text after `#!`.
+fn shebang_language(shebang: &str) -> Option<String> {
+    let mut words = shebang.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        // `env -S deno run` and `env LANG=C python3` name the program after options.
+        program = words.find(|word| !word.starts_with('-') && !word.contains('='))?;
+    }
+    // `python3.12` and `ruby2.7` are the same languages as `python` and `ruby`.
+    let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let language = match program {
+        "sh" | "bash" | "zsh" | "dash" | "ksh" | "ash" => "bash",
+        "node" | "nodejs" | "deno" | "bun" => "javascript",
+        "tclsh" | "wish" => "tcl",
+        "gawk" | "mawk" | "nawk" => "awk",
+        "runghc" | "runhaskell" => "haskell",
+        "Rscript" => "r",
+        "pwsh" => "powershell",
+        "make" => "makefile",
+        program => program,
+    };
+    Some(language.to_string())
+}
+
+/// Mode from an Emacs `-*- mode: python -*-` or `-*- python -*-` line.
+fn emacs_mode(line: &str) -> Option<String> {
+    let (_, rest) = line.split_once("-*-")?;
+    let (variables, _) = rest.split_once("-*-")?;
+    let mode = if variables.contains(':') {
+        variables.split(';').find_map(|variable| {
+            let (name, value) = variable.split_once(':')?;
+            (name.trim().eq_ignore_ascii_case("mode")).then_some(value)
+        })?
+    } else {
+        variables
+    };
+    let mode = mode.trim().to_ascii_lowercase();
+    let mode = match mode.as_str() {
+        "c++" => "cpp".to_string(),
+        "shell-script" | "sh" => "bash".to_string(),
+        _ => mode,
+    };
+    (!mode.is_empty()).then_some(mode)
+}
+
+/// Filetype from a Vim modeline such as `# vim: set ft=python:` or `// vi: syntax=rust`.
+fn vim_filetype(line: &str) -> Option<String> {
+    let options = ["vim:", "vi:", "ex:"].iter().find_map(|marker| {
+        line.match_indices(marker)
+            .find(|(index, _)| {
+                line[..*index]
+                    .chars()
+                    .next_back()
+                    .is_none_or(char::is_whitespace)
+            })
+            .map(|(index, _)| &line[index + marker.len()..])
+    })?;
+    options
+        .split([' ', '\t', ':'])
+        .find_map(|option| {
+            ["filetype=", "ft=", "syntax=", "syn="]
+                .iter()
+                .find_map(|name| option.strip_prefix(name))
+        })
+        .filter(|filetype| !filetype.is_empty())
+        .map(str::to_string)
+}
+
+/// Language implied by how a document opens: an XML declaration, an HTML doctype, or a JSON
+/// object or array.
+fn prologue_language(first_line: &str) -> Option<String> {
+    let language = if first_line.starts_with("<?xml") {
+        "xml"
+    } else if first_line
+        .get(..14)
+        .is_some_and(|start| start.eq_ignore_ascii_case("<!doctype html"))
+    {
+        "html"
+    } else if matches!(first_line.trim_end(), "{" | "[")
+        || ["{\"", "[{", "[\""]
+            .iter()
+            .any(|start| first_line.starts_with(start))
+    {
+        "json"
+    } else {
+        return None;
+    };
+    Some(language.to_string())
+}
+
 // -- Guardrail constants ------------------------------------------------------
 
 /// Skip highlighting for inputs larger than 512 KB to avoid excessive memory
@@ -615,29 +1160,31 @@ fn highlight_to_line_spans_with_theme(
         return None;
     }
 
-    let syntax = find_syntax(lang)?;
-    let mut h = HighlightLines::new(syntax, theme);
+    let syntax_set = syntax_set();
+    let syntax = find_syntax_for_code(&syntax_set, lang, code)?;
+    let highlighter = Highlighter::new(theme);
+    let mut state = BlockHighlightState::new(Arc::clone(&syntax_set), syntax, &highlighter);
     let mut lines: Vec<Vec<Span<'static>>> = Vec::new();
+    highlight_lines_from_state(code, &highlighter, &mut state, &mut lines)?;
+    Some(lines)
+}
 
-    for line in LinesWithEndings::from(code) {
-        let ranges = h.highlight_line(line, syntax_set()).ok()?;
-        let mut spans: Vec<Span<'static>> = Vec::new();
-        for (style, text) in ranges {
-            // Strip trailing line endings (LF and CR) since we handle line
-            // breaks ourselves.  CRLF inputs would otherwise leave a stray \r.
-            let text = text.trim_end_matches(['\n', '\r']);
-            if text.is_empty() {
-                continue;
-            }
-            spans.push(Span::styled(text.to_string(), convert_style(style)));
+/// Convert one line of syntect output into spans.
+fn styled_line_spans(ranges: Vec<(SyntectStyle, &str)>) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    for (style, text) in ranges {
+        // Strip trailing line endings (LF and CR) since we handle line
+        // breaks ourselves.  CRLF inputs would otherwise leave a stray \r.
+        let text = text.trim_end_matches(['\n', '\r']);
+        if text.is_empty() {
+            continue;
         }
-        if spans.is_empty() {
-            spans.push(Span::raw(String::new()));
-        }
-        lines.push(spans);
+        spans.push(Span::styled(text.to_string(), convert_style(style)));
     }
-
-    Some(lines)
+    if spans.is_empty() {
+        spans.push(Span::raw(String::new()));
+    }
+    spans
 }
 
 /// Parse `code` using syntect for `lang` and return per-line styled spans.
@@ -651,32 +1198,797 @@ fn highlight_to_line_spans(code: &str, lang: &str) -> Option<Vec<Vec<Span<'stati
     highlight_to_line_spans_with_theme(code, lang, &theme_guard)
 }
 
+// -- Incremental highlighting -------------------------------------------------
+
+/// How many code blocks keep a checkpoint.  A streamed message re-renders every
+/// block it contains, so this bounds how many blocks per message stay cheap.
+const MAX_HIGHLIGHT_CHECKPOINTS: usize = 8;
+
+/// Parser state saved at the end of a code block's last complete line.
+struct HighlightCheckpoint {
+    lang: String,
+    theme_revision: u64,
+    /// Source highlighted so far.  Always empty or ending with a newline.
+    source: String,
+    lines: Vec<Vec<Span<'static>>>,
+    state: BlockHighlightState,
+}
+
+/// Highlights code blocks that grow by appended lines, resuming from the
+/// checkpoint of an earlier call instead of starting over.
+///
+/// A block is recognized by its content: a checkpoint applies when its source
+/// is a prefix of the new code and the language and theme revision match.
+/// The result is identical to highlighting the whole block at once.
+struct IncrementalHighlighter {
+    /// Most recently used last.
+    checkpoints: VecDeque<HighlightCheckpoint>,
+}
+
+impl IncrementalHighlighter {
+    const fn new() -> Self {
+        Self {
+            checkpoints: VecDeque::new(),
+        }
+    }
+
+    fn highlight(
+        &mut self,
+        code: &str,
+        lang: &str,
+        theme: &Theme,
+        theme_revision: u64,
+    ) -> Option<Vec<Vec<Span<'static>>>> {
+        if code.is_empty()
+            || code.len() > MAX_HIGHLIGHT_BYTES
+            || code.lines().count() > MAX_HIGHLIGHT_LINES
+        {
+            return None;
+        }
+        let syntax_set = syntax_set();
+        let syntax = find_syntax_for_code(&syntax_set, lang, code)?;
+        let highlighter = Highlighter::new(theme);
+        // Checkpoints are keyed by grammar rather than by `lang`, since one language can map to
+        // more than one grammar.
+        let lang = syntax.name.as_str();
+
+        let (mut lines, mut state, resume_at) =
+            match self.take_checkpoint(code, lang, theme_revision) {
+                Some(checkpoint) => (checkpoint.lines, checkpoint.state, checkpoint.source.len()),
+                None => (
+                    Vec::new(),
+                    BlockHighlightState::new(Arc::clone(&syntax_set), syntax, &highlighter),
+                    0,
+                ),
+            };
+        // A trailing line without a newline may still grow, so the checkpoint
+        // is taken before it.
+        let complete_len = code.rfind('\n').map_or(0, |index| index + 1).max(resume_at);
+        highlight_lines_from_state(
+            &code[resume_at..complete_len],
+            &highlighter,
+            &mut state,
+            &mut lines,
+        )?;
+
+        self.checkpoints.push_back(HighlightCheckpoint {
+            lang: lang.to_string(),
+            theme_revision,
+            source: code[..complete_len].to_string(),
+            lines: lines.clone(),
+            state: state.clone(),
+        });
+        if self.checkpoints.len() > MAX_HIGHLIGHT_CHECKPOINTS {
+            self.checkpoints.pop_front();
+        }
+
+        highlight_lines_from_state(&code[complete_len..], &highlighter, &mut state, &mut lines)?;
+        Some(lines)
+    }
+
+    /// Removes and returns the longest checkpoint that `code` continues.
+    fn take_checkpoint(
+        &mut self,
+        code: &str,
+        lang: &str,
+        theme_revision: u64,
+    ) -> Option<HighlightCheckpoint> {
+        let index = self
+            .checkpoints
+            .iter()
+            .enumerate()
+            .filter(|(_, checkpoint)| {
+                checkpoint.lang == lang
+                    && checkpoint.theme_revision == theme_revision
+                    && code.starts_with(&checkpoint.source)
+            })
+            .max_by_key(|(_, checkpoint)| checkpoint.source.len())
+            .map(|(index, _)| index)?;
+        self.checkpoints.remove(index)
+    }
+}
+
+/// Highlight `source` line by line, continuing from the given parser state.
+fn highlight_lines_from_state(
+    source: &str,
+    highlighter: &Highlighter<'_>,
+    state: &mut BlockHighlightState,
+    lines: &mut Vec<Vec<Span<'static>>>,
+) -> Option<()> {
+    for line in LinesWithEndings::from(source) {
+        lines.push(state.highlight_line(line, highlighter)?);
+    }
+    Some(())
+}
+
+// -- Language injection -------------------------------------------------------
+
+/// Keywords that mark a string literal as SQL when they open its content.
+/// They must be written in capitals, as queries usually are, so that prose
+/// such as "Update the file" is left alone.
+const SQL_INJECTION_KEYWORDS: &[&str] = &[
+    "ALTER", "CREATE", "DELETE", "DROP", "INSERT", "SELECT", "UPDATE", "WITH",
+];
+
+/// Scopes used to find string literals in the host language.
+#[derive(Clone, Copy)]
+struct InjectionScopes {
+    string: Scope,
+    string_punctuation: Scope,
+    sql: Scope,
+}
+
+impl InjectionScopes {
+    /// Returns `None` when `syntax` is SQL itself or no SQL grammar is loaded.
+    fn for_host(syntax: &SyntaxReference) -> Option<Self> {
+        let sql = Scope::new("source.sql").ok()?;
+        if sql.is_prefix_of(syntax.scope) || !has_syntax("sql") {
+            return None;
+        }
+        Some(Self {
+            string: Scope::new("string").ok()?,
+            string_punctuation: Scope::new("punctuation.definition.string").ok()?,
+            sql,
+        })
+    }
+
+    /// Whether `scopes` sit inside the content of a string literal that the
+    /// host grammar did not already hand to the SQL grammar.
+    fn in_string_content(&self, scopes: &ScopeStack) -> bool {
+        let scopes = scopes.as_slice();
+        scopes.iter().any(|scope| self.string.is_prefix_of(*scope))
+            && !scopes.iter().any(|scope| {
+                self.string_punctuation.is_prefix_of(*scope) || self.sql.is_prefix_of(*scope)
+            })
+    }
+}
+
+/// Parser state for one code block.
+///
+/// Languages a grammar embeds itself, such as `<script>` bodies in HTML or
+/// fenced code in Markdown, are resolved by syntect while parsing.  On top of
+/// that, a string literal whose content opens with one of
+/// [`SQL_INJECTION_KEYWORDS`] is highlighted with the SQL grammar, including
+/// literals that span several lines.
+#[derive(Clone)]
+struct BlockHighlightState {
+    /// The set `parse_state` belongs to, kept in case grammars are reloaded
+    /// while the block is being highlighted.
+    syntax_set: Arc<SyntaxSet>,
+    parse_state: ParseState,
+    highlight_state: HighlightState,
+    /// Host scopes at the end of the last highlighted line.
+    scopes: ScopeStack,
+    injection_scopes: Option<InjectionScopes>,
+    /// SQL parser state while inside an injected string literal.
+    injection: Option<(ParseState, HighlightState)>,
+    /// Where strings and comments start and end, for the overlays below.
+    literals: Option<LiteralScopes>,
+    /// Bracket nesting, tracked only with `tui.rainbow_brackets` on.
+    brackets: Option<BracketDepth>,
+    /// Macro and `unsafe` block tracking, for Rust code only.
+    rust: Option<RustEmphasis>,
+}
+
+impl BlockHighlightState {
+    fn new(
+        syntax_set: Arc<SyntaxSet>,
+        syntax: &SyntaxReference,
+        highlighter: &Highlighter<'_>,
+    ) -> Self {
+        Self {
+            syntax_set,
+            parse_state: ParseState::new(syntax),
+            highlight_state: HighlightState::new(highlighter, ScopeStack::new()),
+            scopes: ScopeStack::new(),
+            injection_scopes: InjectionScopes::for_host(syntax),
+            injection: None,
+            literals: LiteralScopes::new(),
+            brackets: RAINBOW_BRACKETS
+                .load(Ordering::Relaxed)
+                .then_some(BracketDepth { depth: 0 }),
+            rust: RustEmphasis::for_host(syntax),
+        }
+    }
+
+    fn highlight_line(
+        &mut self,
+        line: &str,
+        highlighter: &Highlighter<'_>,
+    ) -> Option<Vec<Span<'static>>> {
+        let ops = self.parse_state.parse_line(line, &self.syntax_set).ok()?;
+        let ranges = self.highlight_ranges(line, &ops, highlighter)?;
+        let Some(literals) = self.literals.as_mut() else {
+            return Some(styled_line_spans(ranges));
+        };
+        let segments = literals.segments(line, &ops)?;
+        let mut overlays = comment_marker_styles(line, &segments, &ranges);
+        if let Some(brackets) = self.brackets.as_mut() {
+            overlays.extend(brackets.color_line(line, &segments, &ranges));
+        }
+        let mut unsafe_ranges = Vec::new();
+        if let Some(rust) = self.rust.as_mut() {
+            let (macros, in_unsafe) = rust.scan_line(line, &segments, &ranges, highlighter);
+            overlays.extend(macros);
+            unsafe_ranges = in_unsafe;
+        }
+        let ranges = if overlays.is_empty() {
+            ranges
+        } else {
+            splice_injected_styles(line, &ranges, overlays)
+        };
+        if !unsafe_ranges.is_empty()
+            && let Some(background) = unsafe_block_background(highlighter)
+        {
+            return Some(tinted_line_spans(ranges, &unsafe_ranges, background));
+        }
+        Some(styled_line_spans(ranges))
+    }
+
+    /// Styles `line` with the host grammar and any injected SQL.
+    fn highlight_ranges<'a>(
+        &mut self,
+        line: &'a str,
+        ops: &[(usize, ScopeStackOp)],
+        highlighter: &Highlighter<'_>,
+    ) -> Option<Vec<(SyntectStyle, &'a str)>> {
+        let ranges: Vec<(SyntectStyle, &str)> =
+            HighlightIterator::new(&mut self.highlight_state, ops, line, highlighter).collect();
+        let Some(injection_scopes) = self.injection_scopes else {
+            return Some(ranges);
+        };
+
+        // Find the byte ranges of string content on this line.
+        let mut string_ranges: Vec<Range<usize>> = Vec::new();
+        let mut start = 0;
+        for (index, op) in ops
+            .iter()
+            .map(|(index, op)| (*index, Some(op)))
+            .chain(std::iter::once((line.len(), None)))
+        {
+            if index > start && injection_scopes.in_string_content(&self.scopes) {
+                match string_ranges.last_mut() {
+                    Some(last) if last.end == start => last.end = index,
+                    _ => string_ranges.push(start..index),
+                }
+            }
+            if let Some(op) = op {
+                self.scopes.apply(op).ok()?;
+            }
+            start = index;
+        }
+
+        let mut injected: Vec<(SyntectStyle, Range<usize>)> = Vec::new();
+        for range in string_ranges {
+            let content = &line[range.clone()];
+            if self.injection.is_none() && opens_with_sql_keyword(content) {
+                let sql = find_syntax_in(&self.syntax_set, "sql")?;
+                self.injection = Some((
+                    ParseState::new(sql),
+                    HighlightState::new(highlighter, ScopeStack::new()),
+                ));
+            }
+            if let Some((parse_state, highlight_state)) = self.injection.as_mut() {
+                // Grammars expect every line to end with a newline.
+                let sql_line = if content.ends_with('\n') {
+                    content.to_string()
+                } else {
+                    format!("{content}\n")
+                };
+                let sql_ops = parse_state.parse_line(&sql_line, &self.syntax_set).ok()?;
+                let mut offset = range.start;
+                for (style, text) in
+                    HighlightIterator::new(highlight_state, &sql_ops, &sql_line, highlighter)
+                {
+                    let end = (offset + text.len()).min(range.end);
+                    if end > offset {
+                        injected.push((style, offset..end));
+                    }
+                    offset += text.len();
+                }
+            }
+            // The literal ends unless it runs on to the next line.
+            if range.end < line.len() || !injection_scopes.in_string_content(&self.scopes) {
+                self.injection = None;
+            }
+        }
+        if injected.is_empty() {
+            return Some(ranges);
+        }
+        Some(splice_injected_styles(line, &ranges, injected))
+    }
+}
+
+fn opens_with_sql_keyword(content: &str) -> bool {
+    let content = content.trim_start();
+    SQL_INJECTION_KEYWORDS.iter().any(|keyword| {
+        content.strip_prefix(keyword).is_some_and(|rest| {
+            rest.chars()
+                .next()
+                .is_none_or(|next| next.is_whitespace() || next == '(')
+        })
+    })
+}
+
+/// Overlay the styles of byte ranges, such as injected SQL, comment markers, or
+/// rainbow brackets, on the host styles of `line`.
+fn splice_injected_styles<'a>(
+    line: &'a str,
+    host: &[(SyntectStyle, &str)],
+    injected: Vec<(SyntectStyle, Range<usize>)>,
+) -> Vec<(SyntectStyle, &'a str)> {
+    let mut styles: Vec<SyntectStyle> = Vec::with_capacity(line.len());
+    for (style, text) in host {
+        styles.extend(std::iter::repeat_n(*style, text.len()));
+    }
+    for (style, range) in injected {
+        styles[range].fill(style);
+    }
+    // Style changes only happen at the edges of syntect tokens, which are
+    // always character boundaries.
+    let mut spliced = Vec::new();
+    let mut start = 0;
+    for index in 1..=styles.len() {
+        if index == styles.len() || styles[index] != styles[start] {
+            spliced.push((styles[start], &line[start..index]));
+            start = index;
+        }
+    }
+    spliced
+}
+
+// -- Strings and comments ----------------------------------------------------
+
+/// Host scopes across the lines of one code block, reduced to whether each
+/// stretch of a line sits in a string or a comment.
+#[derive(Clone)]
+struct LiteralScopes {
+    /// Host scopes at the end of the last segmented line.
+    scopes: ScopeStack,
+    string: Scope,
+    comment: Scope,
+}
+
+/// A stretch of one line that is uniformly inside or outside strings and
+/// comments.
+struct LiteralSegment {
+    range: Range<usize>,
+    string: bool,
+    comment: bool,
+}
+
+impl LiteralSegment {
+    fn is_code(&self) -> bool {
+        !self.string && !self.comment
+    }
+}
+
+impl LiteralScopes {
+    fn new() -> Option<Self> {
+        Some(Self {
+            scopes: ScopeStack::new(),
+            string: Scope::new("string").ok()?,
+            comment: Scope::new("comment").ok()?,
+        })
+    }
+
+    fn in_scope(&self, selector: Scope) -> bool {
+        self.scopes
+            .as_slice()
+            .iter()
+            .any(|scope| selector.is_prefix_of(*scope))
+    }
+
+    /// Splits `line` where it enters or leaves a string or comment.
+    fn segments(
+        &mut self,
+        line: &str,
+        ops: &[(usize, ScopeStackOp)],
+    ) -> Option<Vec<LiteralSegment>> {
+        let mut segments: Vec<LiteralSegment> = Vec::new();
+        let mut start = 0;
+        for (index, op) in ops
+            .iter()
+            .map(|(index, op)| (*index, Some(op)))
+            .chain(std::iter::once((line.len(), None)))
+        {
+            if index > start {
+                let string = self.in_scope(self.string);
+                let comment = self.in_scope(self.comment);
+                match segments.last_mut() {
+                    Some(last) if last.string == string && last.comment == comment => {
+                        last.range.end = index;
+                    }
+                    _ => segments.push(LiteralSegment {
+                        range: start..index,
+                        string,
+                        comment,
+                    }),
+                }
+            }
+            if let Some(op) = op {
+                self.scopes.apply(op).ok()?;
+            }
+            start = index;
+        }
+        Some(segments)
+    }
+}
+
+// -- Comment markers ----------------------------------------------------------
+
+/// Words that flag a comment for attention.  `SAFETY:` keeps its colon so the
+/// word alone, as in "thread safety", is left alone.
+const COMMENT_MARKERS: &[&str] = &["TODO", "FIXME", "XXX", "SAFETY:"];
+
+/// Marker color, as an ANSI palette index so it follows the terminal's palette
+/// on light and dark backgrounds alike.
+const COMMENT_MARKER_COLOR: u8 = 0x03;
+
+/// Bold, warning-colored styles for the [`COMMENT_MARKERS`] that stand as whole
+/// words inside the comments of `line`.
+fn comment_marker_styles(
+    line: &str,
+    segments: &[LiteralSegment],
+    ranges: &[(SyntectStyle, &str)],
+) -> Vec<(SyntectStyle, Range<usize>)> {
+    let is_word = |ch: char| ch.is_alphanumeric() || ch == '_';
+    let mut styles = Vec::new();
+    for segment in segments.iter().filter(|segment| segment.comment) {
+        let text = &line[segment.range.clone()];
+        for marker in COMMENT_MARKERS {
+            for (offset, _) in text.match_indices(marker) {
+                let start = segment.range.start + offset;
+                let end = start + marker.len();
+                if line[..start].chars().next_back().is_some_and(is_word)
+                    || line[end..].chars().next().is_some_and(is_word)
+                {
+                    continue;
+                }
+                let Some(mut style) = style_at(ranges, start) else {
+                    continue;
+                };
+                style.foreground = SyntectColor {
+                    r: COMMENT_MARKER_COLOR,
+                    g: 0,
+                    b: 0,
+                    a: ANSI_ALPHA_INDEX,
+                };
+                style.font_style |= FontStyle::BOLD;
+                styles.push((style, start..end));
+            }
+        }
+    }
+    styles
+}
+
+// -- Rainbow brackets ---------------------------------------------------------
+
+/// Bracket colors by nesting depth, as ANSI palette indices so they follow the
+/// terminal's palette on light and dark backgrounds alike.
+const RAINBOW_BRACKET_COLORS: &[u8] = &[0x03, 0x05, 0x04];
+
+/// Bracket nesting depth across the lines of one code block.
+///
+/// Grammars disagree on how they scope brackets (`punctuation.section.*` in
+/// Rust, `meta.brace.*` in TypeScript, nothing at all in some), so every `(`,
+/// `[`, and `{` counts unless it sits in a string or comment.
+#[derive(Clone)]
+struct BracketDepth {
+    depth: usize,
+}
+
+impl BracketDepth {
+    /// Colors for the brackets of `line`, keeping the rest of each bracket's
+    /// style.
+    fn color_line(
+        &mut self,
+        line: &str,
+        segments: &[LiteralSegment],
+        ranges: &[(SyntectStyle, &str)],
+    ) -> Vec<(SyntectStyle, Range<usize>)> {
+        let mut colored = Vec::new();
+        for segment in segments.iter().filter(|segment| segment.is_code()) {
+            for (offset, ch) in line[segment.range.clone()].char_indices() {
+                let depth = match ch {
+                    '(' | '[' | '{' => {
+                        self.depth += 1;
+                        self.depth - 1
+                    }
+                    ')' | ']' | '}' => {
+                        self.depth = self.depth.saturating_sub(1);
+                        self.depth
+                    }
+                    _ => continue,
+                };
+                let offset = segment.range.start + offset;
+                let Some(mut style) = style_at(ranges, offset) else {
+                    continue;
+                };
+                style.foreground = SyntectColor {
+                    r: RAINBOW_BRACKET_COLORS[depth % RAINBOW_BRACKET_COLORS.len()],
+                    g: 0,
+                    b: 0,
+                    a: ANSI_ALPHA_INDEX,
+                };
+                colored.push((style, offset..offset + 1));
+            }
+        }
+        colored
+    }
+}
+
+// -- Rust emphasis -----------------------------------------------------------
+
+/// Scopes whose theme color marks Rust macro invocations; the first one the
+/// theme colors wins.  Set `support.macro` in `[tui.syntax_colors]` to give
+/// macros a color of their own.
+const MACRO_SCOPES: &[&str] = &[
+    "support.macro",
+    "entity.name.function.macro",
+    "entity.name.function",
+];
+
+/// Warning hue mixed into the theme background behind `unsafe` blocks.
+const UNSAFE_TINT: (u8, u8, u8) = (0xE5, 0xA5, 0x0A);
+
+/// Share of [`UNSAFE_TINT`] in the mix, low enough to keep code readable.
+const UNSAFE_TINT_ALPHA: f32 = 0.12;
+
+/// Rust macro invocations and `unsafe` blocks across the lines of one code
+/// block, so that reviewers spot them at a glance.
+///
+/// Like [`BracketDepth`], this scans the code outside strings and comments
+/// rather than relying on grammar scopes: `name!` followed by a bracket is a
+/// macro invocation, and `unsafe` directly followed by `{` opens an unsafe
+/// block that ends at the matching `}`.
+#[derive(Clone)]
+struct RustEmphasis {
+    /// `{` nesting depth.
+    depth: usize,
+    /// Depths at which the enclosing `unsafe` blocks were opened.
+    unsafe_blocks: Vec<usize>,
+    /// Where the background starts once the `{` of a pending `unsafe` arrives.
+    pending_unsafe: Option<usize>,
+}
+
+impl RustEmphasis {
+    fn for_host(syntax: &SyntaxReference) -> Option<Self> {
+        let rust = Scope::new("source.rust").ok()?;
+        rust.is_prefix_of(syntax.scope).then_some(Self {
+            depth: 0,
+            unsafe_blocks: Vec::new(),
+            pending_unsafe: None,
+        })
+    }
+
+    /// Styles for the macro invocations of `line`, and the byte ranges of
+    /// `line` that lie inside `unsafe` blocks.
+    fn scan_line(
+        &mut self,
+        line: &str,
+        segments: &[LiteralSegment],
+        ranges: &[(SyntectStyle, &str)],
+        highlighter: &Highlighter<'_>,
+    ) -> (Vec<(SyntectStyle, Range<usize>)>, Vec<Range<usize>>) {
+        let is_word = |ch: char| ch.is_alphanumeric() || ch == '_';
+        let mut macros = Vec::new();
+        let mut in_unsafe = Vec::new();
+        let mut unsafe_start = (!self.unsafe_blocks.is_empty()).then_some(0);
+        if self.pending_unsafe.is_some() {
+            // The keyword ended the previous line.
+            self.pending_unsafe = Some(0);
+        }
+        for segment in segments.iter().filter(|segment| segment.is_code()) {
+            let end = segment.range.end;
+            let mut index = segment.range.start;
+            while let Some(ch) = line[index..end].chars().next() {
+                if is_word(ch) {
+                    let word_end = line[index..end]
+                        .find(|ch: char| !is_word(ch))
+                        .map_or(end, |len| index + len);
+                    if &line[index..word_end] == "unsafe" {
+                        self.pending_unsafe = Some(index);
+                    } else {
+                        self.pending_unsafe = None;
+                        if let Some(after) = line[word_end..end].strip_prefix('!')
+                            && after.trim_start().starts_with(['(', '[', '{'])
+                            && let Some(foreground) = macro_foreground(highlighter)
+                            && let Some(mut style) = style_at(ranges, index)
+                        {
+                            style.foreground = foreground;
+                            macros.push((style, index..word_end + 1));
+                        }
+                    }
+                    index = word_end;
+                    continue;
+                }
+                match ch {
+                    '{' => {
+                        if let Some(start) = self.pending_unsafe.take() {
+                            if self.unsafe_blocks.is_empty() {
+                                unsafe_start = Some(start);
+                            }
+                            self.unsafe_blocks.push(self.depth);
+                        }
+                        self.depth += 1;
+                    }
+                    '}' => {
+                        self.pending_unsafe = None;
+                        self.depth = self.depth.saturating_sub(1);
+                        if self.unsafe_blocks.last() == Some(&self.depth) {
+                            self.unsafe_blocks.pop();
+                            if self.unsafe_blocks.is_empty()
+                                && let Some(start) = unsafe_start.take()
+                            {
+                                in_unsafe.push(start..index + 1);
+                            }
+                        }
+                    }
+                    ch if ch.is_whitespace() => {}
+                    _ => self.pending_unsafe = None,
+                }
+                index += ch.len_utf8();
+            }
+        }
+        let content_end = line.trim_end_matches(['\n', '\r']).len();
+        if let Some(start) = unsafe_start
+            && start < content_end
+        {
+            in_unsafe.push(start..content_end);
+        }
+        (macros, in_unsafe)
+    }
+}
+
+fn macro_foreground(highlighter: &Highlighter<'_>) -> Option<SyntectColor> {
+    MACRO_SCOPES.iter().find_map(|scope_name| {
+        let scope = Scope::new(scope_name).ok()?;
+        highlighter.style_mod_for_stack(&[scope]).foreground
+    })
+}
+
+/// The theme background with a little of [`UNSAFE_TINT`] mixed in.  `None`
+/// when the theme leaves the background to the terminal, as the ANSI themes
+/// do, since there is nothing to mix with.
+fn unsafe_block_background(highlighter: &Highlighter<'_>) -> Option<RtColor> {
+    let background = highlighter.get_default().background;
+    if background.a != OPAQUE_ALPHA {
+        return None;
+    }
+    let (r, g, b) = blend(
+        UNSAFE_TINT,
+        (background.r, background.g, background.b),
+        UNSAFE_TINT_ALPHA,
+    );
+    convert_syntect_color(SyntectColor {
+        r,
+        g,
+        b,
+        a: OPAQUE_ALPHA,
+    })
+}
+
+/// Like [`styled_line_spans`], with `background` behind the `tinted` byte
+/// ranges of the line.
+fn tinted_line_spans(
+    ranges: Vec<(SyntectStyle, &str)>,
+    tinted: &[Range<usize>],
+    background: RtColor,
+) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut start = 0;
+    for (style, text) in ranges {
+        let end = start + text.len();
+        let mut cuts: Vec<usize> = tinted
+            .iter()
+            .flat_map(|range| [range.start, range.end])
+            .filter(|cut| start < *cut && *cut < end)
+            .collect();
+        cuts.sort_unstable();
+        cuts.push(end);
+        let mut from = start;
+        for to in cuts {
+            let piece = text[from - start..to - start].trim_end_matches(['\n', '\r']);
+            if !piece.is_empty() {
+                let mut piece_style = convert_style(style);
+                if tinted.iter().any(|range| range.contains(&from)) {
+                    piece_style = piece_style.bg(background);
+                }
+                spans.push(Span::styled(piece.to_string(), piece_style));
+            }
+            from = to;
+        }
+        start = end;
+    }
+    if spans.is_empty() {
+        spans.push(Span::raw(String::new()));
+    }
+    spans
+}
+
+/// The style covering byte `offset` of the line split into `ranges`.
+fn style_at(ranges: &[(SyntectStyle, &str)], offset: usize) -> Option<SyntectStyle> {
+    let mut end = 0;
+    ranges.iter().find_map(|(style, text)| {
+        end += text.len();
+        (offset < end).then_some(*style)
+    })
+}
+
 // -- Public API ---------------------------------------------------------------
 
 /// Highlight code in any supported language, returning styled ratatui `Line`s.
 ///
-/// Falls back to plain unstyled text when the language is not recognized or the
-/// input exceeds safety guardrails.  Callers can always render the result
+/// Tabs are expanded to the tab width configured for `lang` first.  Falls back
+/// to plain unstyled text when the language is not recognized or the input
+/// exceeds safety guardrails.  Callers can always render the result
 /// directly -- the fallback path produces equivalent plain-text lines.
 ///
 /// Used by `markdown_render` for fenced code blocks and by `exec_cell` for bash
 /// command highlighting.
 pub(crate) fn highlight_code_to_lines(code: &str, lang: &str) -> Vec<Line<'static>> {
+    let code = expand_tabs(code, tab_width_for(Some(lang)));
+    let code = code.as_ref();
     if let Some(line_spans) = highlight_to_line_spans(code, lang) {
         line_spans.into_iter().map(Line::from).collect()
     } else {
-        // Fallback: plain text, one Line per source line.
-        // Use `lines()` instead of `split('\n')` to avoid a phantom trailing
-        // empty element when the input ends with '\n' (as pulldown-cmark emits).
-        let mut result: Vec<Line<'static>> =
-            code.lines().map(|l| Line::from(l.to_string())).collect();
-        if result.is_empty() {
-            result.push(Line::from(String::new()));
-        }
-        result
+        plain_lines(code)
     }
 }
 
+/// Like [`highlight_code_to_lines`], for code blocks in markdown that may be
+/// rendered again with more lines appended while a response streams in.
+///
+/// Only the lines added since the block was last highlighted are parsed.
+pub(crate) fn highlight_streamed_code_to_lines(code: &str, lang: &str) -> Vec<Line<'static>> {
+    let code = expand_tabs(code, tab_width_for(Some(lang)));
+    let code = code.as_ref();
+    let theme_guard = match theme_lock().read() {
+        Ok(theme_guard) => theme_guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let mut highlighter = match STREAM_HIGHLIGHTER.lock() {
+        Ok(highlighter) => highlighter,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    match highlighter.highlight(code, lang, &theme_guard, syntax_theme_revision()) {
+        Some(line_spans) => line_spans.into_iter().map(Line::from).collect(),
+        None => plain_lines(code),
+    }
+}
+
+/// Fallback: plain text, one Line per source line.
+fn plain_lines(code: &str) -> Vec<Line<'static>> {
+    // Use `lines()` instead of `split('\n')` to avoid a phantom trailing
+    // empty element when the input ends with '\n' (as pulldown-cmark emits).
+    let mut result: Vec<Line<'static>> = code.lines().map(|l| Line::from(l.to_string())).collect();
+    if result.is_empty() {
+        result.push(Line::from(String::new()));
+    }
+    result
+}
+
 /// Backward-compatible wrapper for bash highlighting used by exec cells.
 pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
     highlight_code_to_lines(script, "bash")
@@ -703,12 +2015,7 @@ mod tests {
     use super::*;
     use insta::assert_snapshot;
     use pretty_assertions::assert_eq;
-    use std::str::FromStr;
     use syntect::highlighting::Color as SyntectColor;
-    use syntect::highlighting::ScopeSelectors;
-    use syntect::highlighting::StyleModifier;
-    use syntect::highlighting::ThemeItem;
-    use syntect::highlighting::ThemeSettings;
 
     fn write_minimal_tmtheme(path: &Path) {
         // Minimal valid .tmTheme plist (enough for syntect to parse).
@@ -846,6 +2153,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn highlight_go_resolves_fence_aliases_to_go_syntax() {
+        let syntax_set = syntax_set();
+        for lang in ["go", "golang", "Go"] {
+            assert_eq!(
+                find_syntax_in(&syntax_set, lang).map(|syntax| syntax.name.as_str()),
+                Some("Go"),
+                "find_syntax_in({lang:?}) should resolve to Go"
+            );
+        }
+
+        let code = "func main() {\n\tfmt.Println(\"hi\")\n}";
+        let lines = highlight_code_to_lines(code, "golang");
+        assert_eq!(reconstructed(&lines), code);
+        let func_span = lines[0]
+            .spans
+            .iter()
+            .find(|sp| sp.content.as_ref() == "func");
+        let style = func_span.map(|s| s.style).unwrap_or_default();
+        assert!(
+            style.fg.is_some() || style.add_modifier != Modifier::empty(),
+            "expected func keyword to have non-default style, got {style:?}"
+        );
+    }
+
+    #[test]
+    fn header_language_detects_cpp_headers() {
+        for content in [
+            "#pragma once\nclass Widget {\npublic:\n  int size() const;\n};\n",
+            "template <typename T>\nT max(T a, T b);\n",
+            "#include <vector>\nstd::vector<int> ids();\n",
+            "@@ -1,2 +1,3 @@\n #pragma once\n+namespace app {\n }\n",
+        ] {
+            assert_eq!(header_language(content), "cpp", "{content}");
+        }
+        for content in [
+            "#ifndef LIST_H\n#define LIST_H\n#ifdef __cplusplus\nextern \"C\" {\n#endif\nstruct list { struct list *next; };\n",
+            "/* See class::method in the C++ wrapper. */\nint sum(int a, int b);\n",
+            "",
+        ] {
+            assert_eq!(header_language(content), "c", "{content}");
+        }
+        let syntax_set = syntax_set();
+        assert_eq!(
+            find_syntax_in(&syntax_set, "c").map(|syntax| syntax.name.as_str()),
+            Some("C")
+        );
+        assert_eq!(
+            find_syntax_in(&syntax_set, "cpp").map(|syntax| syntax.name.as_str()),
+            Some("C++")
+        );
+    }
+
     #[test]
     fn highlight_unknown_lang_falls_back() {
         let code = "some random text";
@@ -1050,6 +2410,35 @@ mod tests {
         assert!(matches!(rt.fg, Some(RtColor::Rgb(10, 20, 30))));
     }
 
+    #[test]
+    fn rgb_theme_colors_are_quantized_below_truecolor() {
+        let orange = syntect::highlighting::Color {
+            r: 255,
+            g: 135,
+            b: 0,
+            a: 0xFF,
+        };
+        let ansi_blue = syntect::highlighting::Color {
+            r: 0x04,
+            g: 0,
+            b: 0,
+            a: 0,
+        };
+
+        assert_eq!(
+            convert_syntect_color_for_level(orange, StdoutColorLevel::Ansi256),
+            Some(RtColor::Indexed(208))
+        );
+        assert_eq!(
+            convert_syntect_color_for_level(orange, StdoutColorLevel::Ansi16),
+            Some(RtColor::LightRed)
+        );
+        assert_eq!(
+            convert_syntect_color_for_level(ansi_blue, StdoutColorLevel::Ansi16),
+            Some(RtColor::Blue)
+        );
+    }
+
     #[test]
     fn ansi_palette_color_maps_ansi_white_to_gray() {
         assert_eq!(ansi_palette_color(/*index*/ 0x07), RtColor::Gray);
@@ -1057,7 +2446,7 @@ mod tests {
 
     #[test]
     fn ansi_family_themes_use_terminal_palette_colors_not_rgb() {
-        for theme_name in ["ansi", "base16", "base16-256"] {
+        for theme_name in ["ansi", "base16", "base16-256", "high-contrast"] {
             let theme = resolve_theme_by_name(theme_name, /*codex_home*/ None)
                 .unwrap_or_else(|| panic!("expected built-in theme {theme_name} to resolve"));
             let lines = highlight_to_line_spans_with_theme(
@@ -1130,6 +2519,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn highlight_markdown_styles_inline_and_block_structure() {
+        let theme = resolve_theme_by_name("dracula", /*codex_home*/ None).expect("dracula theme");
+        let code = "plain *emphasis* and [link](https://example.com)\n\n> quoted\n";
+
+        let lines = highlight_to_line_spans_with_theme(code, "markdown", &theme)
+            .expect("markdown highlights");
+        let style_of = |line: usize, text: &str| {
+            lines[line]
+                .iter()
+                .find(|span| span.content.contains(text))
+                .map(|span| span.style)
+                .unwrap_or_else(|| panic!("expected a span containing {text:?}"))
+        };
+
+        let plain = style_of(/*line*/ 0, "plain");
+        assert_ne!(style_of(/*line*/ 0, "emphasis"), plain);
+        assert_ne!(style_of(/*line*/ 0, "link"), plain);
+        assert_ne!(style_of(/*line*/ 2, "quoted"), plain);
+    }
+
     #[test]
     fn highlight_large_input_falls_back() {
         // Input exceeding MAX_HIGHLIGHT_BYTES should return None (plain text
@@ -1162,6 +2572,333 @@ mod tests {
         );
     }
 
+    const STREAMED_RUST: &str =
+        "/* a comment\n   spanning lines */\nfn main() {\n    let s = \"text\";\n}\n";
+
+    #[test]
+    fn incremental_highlighting_matches_full_highlighting() {
+        let theme = resolve_theme_by_name("dracula", /*codex_home*/ None).expect("dracula theme");
+        let mut highlighter = IncrementalHighlighter::new();
+
+        // Every prefix a streamed block passes through, including ones that
+        // end mid-line.
+        for end in 1..=STREAMED_RUST.len() {
+            let code = &STREAMED_RUST[..end];
+            assert_eq!(
+                highlighter.highlight(code, "rust", &theme, /*theme_revision*/ 0),
+                highlight_to_line_spans_with_theme(code, "rust", &theme),
+                "prefix {code:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn incremental_highlighting_resumes_from_the_last_complete_line() {
+        let theme = resolve_theme_by_name("dracula", /*codex_home*/ None).expect("dracula theme");
+        let mut highlighter = IncrementalHighlighter::new();
+
+        highlighter.highlight(
+            "fn main() {\n    let",
+            "rust",
+            &theme,
+            /*theme_revision*/ 0,
+        );
+        highlighter.highlight(
+            "fn main() {\n    let x = 1;\n}\n",
+            "rust",
+            &theme,
+            /*theme_revision*/ 0,
+        );
+        let sources = highlighter
+            .checkpoints
+            .iter()
+            .map(|checkpoint| checkpoint.source.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(sources, vec!["fn main() {\n    let x = 1;\n}\n"]);
+
+        // A theme change starts over instead of reusing stale styles.
+        highlighter.highlight(
+            "fn main() {\n    let x = 1;\n}\n",
+            "rust",
+            &theme,
+            /*theme_revision*/ 1,
+        );
+        assert_eq!(highlighter.checkpoints.len(), 2);
+    }
+
+    fn span_texts(lines: &[Vec<Span<'static>>]) -> Vec<Vec<String>> {
+        lines
+            .iter()
+            .map(|spans| spans.iter().map(|span| span.content.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn sql_in_string_literals_is_highlighted_as_sql() {
+        let theme = resolve_theme_by_name("dracula", /*codex_home*/ None).expect("dracula theme");
+        let code =
+            "const one = \"SELECT id FROM users\";\nconst many = `\nSELECT id\nFROM users`;\n";
+
+        let lines =
+            highlight_to_line_spans_with_theme(code, "js", &theme).expect("javascript highlights");
+        let texts = span_texts(&lines);
+
+        assert!(
+            texts[0].contains(&"SELECT".to_string()) && texts[0].contains(&"FROM".to_string()),
+            "expected SQL keywords as their own tokens, got {:?}",
+            texts[0]
+        );
+        // The injection carries over the lines of a multi-line literal and
+        // stops at its closing quote.
+        assert!(
+            texts[3].contains(&"FROM".to_string()),
+            "expected SQL in the continued literal, got {:?}",
+            texts[3]
+        );
+        let from = lines[3].iter().find(|span| span.content == "FROM");
+        let closing = lines[3].iter().find(|span| span.content.contains('`'));
+        assert_ne!(from.map(|span| span.style), closing.map(|span| span.style));
+    }
+
+    #[test]
+    fn prose_in_string_literals_is_not_injected() {
+        let theme = resolve_theme_by_name("dracula", /*codex_home*/ None).expect("dracula theme");
+        let code = "const label = \"Select an item\";\n";
+
+        let injected =
+            highlight_to_line_spans_with_theme(code, "js", &theme).expect("javascript highlights");
+        let syntax_set = syntax_set();
+        let mut plain_state = BlockHighlightState::new(
+            Arc::clone(&syntax_set),
+            find_syntax_in(&syntax_set, "js").expect("javascript syntax"),
+            &Highlighter::new(&theme),
+        );
+        plain_state.injection_scopes = None;
+        let mut plain = Vec::new();
+        highlight_lines_from_state(
+            code,
+            &Highlighter::new(&theme),
+            &mut plain_state,
+            &mut plain,
+        )
+        .expect("javascript highlights");
+
+        assert_eq!(injected, plain);
+        assert!(!opens_with_sql_keyword("Select an item"));
+        assert!(opens_with_sql_keyword("  WITH recent AS (SELECT 1)"));
+        assert!(!opens_with_sql_keyword("WITHDRAW"));
+    }
+
+    #[test]
+    fn rainbow_brackets_cycle_by_depth_outside_strings() {
+        let theme = resolve_theme_by_name("dracula", /*codex_home*/ None).expect("dracula theme");
+        let highlighter = Highlighter::new(&theme);
+        let syntax_set = syntax_set();
+        let mut state = BlockHighlightState::new(
+            Arc::clone(&syntax_set),
+            find_syntax_in(&syntax_set, "rust").expect("rust syntax"),
+            &highlighter,
+        );
+        state.brackets = Some(BracketDepth { depth: 0 });
+        let mut lines = Vec::new();
+        highlight_lines_from_state(
+            "fn f() {\n    g([1], \"(\");\n}\n",
+            &highlighter,
+            &mut state,
+            &mut lines,
+        )
+        .expect("rust highlights");
+
+        let mut bracket_colors = lines
+            .iter()
+            .flatten()
+            .flat_map(|span| {
+                span.content
+                    .chars()
+                    .filter(|ch| "()[]{}".contains(*ch))
+                    .map(move |ch| (ch, span.style.fg))
+            })
+            .collect::<Vec<_>>();
+        let [yellow, magenta, blue] = [RtColor::Yellow, RtColor::Magenta, RtColor::Blue].map(Some);
+        // The parenthesis inside the string literal keeps its string color.
+        let (_, in_string) = bracket_colors.remove(6);
+        assert!(![yellow, magenta, blue].contains(&in_string));
+        assert_eq!(
+            bracket_colors,
+            vec![
+                ('(', yellow),
+                (')', yellow),
+                ('{', yellow),
+                ('(', magenta),
+                ('[', blue),
+                (']', blue),
+                (')', magenta),
+                ('}', yellow),
+            ]
+        );
+    }
+
+    #[test]
+    fn comment_markers_are_emphasized_only_in_comments() {
+        let lines = highlight_code_to_lines(
+            "// TODO: fix\n// SAFETY: checked, not TODOS\nlet s = \"FIXME\";\n",
+            "rust",
+        );
+
+        let marker_spans = lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .filter(|span| {
+                span.style.fg == Some(RtColor::Yellow)
+                    && span.style.add_modifier.contains(Modifier::BOLD)
+            })
+            .map(|span| span.content.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(marker_spans, vec!["TODO", "SAFETY:"]);
+    }
+
+    #[test]
+    fn rust_macros_and_unsafe_blocks_are_emphasized() {
+        let theme = resolve_theme_by_name("dracula", /*codex_home*/ None).expect("dracula theme");
+        let code = r#"fn f(p: *const u8) -> u8 {
+    println!("unsafe {{");
+    let v = unsafe {
+        p.read()
+    };
+    v
+}
+"#;
+        let lines =
+            highlight_to_line_spans_with_theme(code, "rust", &theme).expect("rust highlights");
+
+        let macro_fg =
+            foreground_style_for_scopes_with_theme(&theme, MACRO_SCOPES).and_then(|style| style.fg);
+        let println = lines[1].iter().find(|span| span.content == "println!");
+        assert_eq!(println.map(|span| span.style.fg), Some(macro_fg));
+
+        // The `unsafe {` inside the string literal opens nothing.
+        let tinted = lines
+            .iter()
+            .map(|spans| {
+                spans
+                    .iter()
+                    .filter(|span| span.style.bg.is_some())
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tinted,
+            vec!["", "", "unsafe {", "        p.read()", "    }", "", ""]
+        );
+    }
+
+    const CUSTOM_GRAMMAR: &str = r#"%YAML 1.2
+---
+name: Codexlang
+file_extensions: [cdxl]
+scope: source.cdxl
+contexts:
+  main:
+    - match: '\b(fn|let)\b'
+      scope: keyword.control.cdxl
+"#;
+
+    #[test]
+    fn custom_grammars_are_added_to_bundled_syntaxes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("codexlang.sublime-syntax"), CUSTOM_GRAMMAR).unwrap();
+
+        let set = with_custom_grammars(two_face::syntax::extra_newlines(), dir.path());
+
+        assert_eq!(
+            set.find_syntax_by_token("cdxl")
+                .map(|syntax| syntax.name.as_str()),
+            Some("Codexlang")
+        );
+        assert!(set.find_syntax_by_name("Rust").is_some());
+    }
+
+    #[test]
+    fn replaced_syntax_set_is_freed_once_blocks_finish() {
+        let theme = resolve_theme_by_name("dracula", /*codex_home*/ None).expect("dracula theme");
+        let highlighter = Highlighter::new(&theme);
+        let replaced = Arc::new(two_face::syntax::extra_newlines());
+        let freed = Arc::downgrade(&replaced);
+        let mut state = BlockHighlightState::new(
+            Arc::clone(&replaced),
+            find_syntax_in(&replaced, "rust").expect("rust syntax"),
+            &highlighter,
+        );
+        // What `reload_custom_grammars` does to the global set while a block streams in.
+        drop(replaced);
+
+        assert!(
+            state
+                .highlight_line("fn main() {}\n", &highlighter)
+                .is_some()
+        );
+        assert!(freed.upgrade().is_some());
+        drop(state);
+        assert!(freed.upgrade().is_none());
+    }
+
+    #[test]
+    fn invalid_custom_grammar_keeps_bundled_syntaxes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("codexlang.sublime-syntax"), CUSTOM_GRAMMAR).unwrap();
+        std::fs::write(dir.path().join("broken.sublime-syntax"), "name: [unclosed").unwrap();
+        let bundled = two_face::syntax::extra_newlines();
+        let bundled_count = bundled.syntaxes().len();
+
+        let set = with_custom_grammars(bundled, dir.path());
+
+        assert_eq!(set.syntaxes().len(), bundled_count);
+        assert!(set.find_syntax_by_token("cdxl").is_none());
+    }
+
+    #[test]
+    fn content_language_reads_shebangs() {
+        let cases = [
+            ("#!/usr/bin/env python3\n", Some("py")),
+            ("#!/usr/bin/python3.12 -u\n", Some("py")),
+            ("#! /bin/sh\nset -e\n", Some("sh")),
+            ("#!/usr/bin/env -S node --no-warnings\n", Some("js")),
+            ("#!/usr/bin/env LANG=C ruby\n", Some("rb")),
+            ("#!/usr/bin/env not-a-language\n", None),
+        ];
+        for (content, expected) in cases {
+            assert_eq!(
+                content_language(content).as_deref(),
+                expected,
+                "{content:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn content_language_reads_modelines_and_prologues() {
+        let cases = [
+            ("# -*- mode: python; coding: utf-8 -*-\nx = 1\n", Some("py")),
+            ("/* -*- c++ -*- */\n", Some("cpp")),
+            ("# -*- coding: utf-8 -*-\n", None),
+            ("line\nline\n# vim: set ft=ruby ts=2:\n", Some("rb")),
+            ("// vi: syntax=rust\n", Some("rs")),
+            ("# index: ft=ruby\n", None),
+            ("<?xml version=\"1.0\"?>\n<root/>\n", Some("xml")),
+            ("<!DOCTYPE html>\n<html></html>\n", Some("html")),
+            ("{\n  \"name\": \"codex\"\n}\n", Some("json")),
+            ("[section]\nkey = 1\n", None),
+        ];
+        for (content, expected) in cases {
+            assert_eq!(
+                content_language(content).as_deref(),
+                expected,
+                "{content:?}"
+            );
+        }
+    }
+
     #[test]
     fn find_syntax_resolves_languages_and_aliases() {
         // Languages resolved directly by two-face's extended syntax set.
@@ -1200,10 +2937,7 @@ mod tests {
             "dockerfile",
         ];
         for lang in languages {
-            assert!(
-                find_syntax(lang).is_some(),
-                "find_syntax({lang:?}) returned None"
-            );
+            assert!(has_syntax(lang), "has_syntax({lang:?}) returned false");
         }
         // Common file extensions.
         let extensions = [
@@ -1211,23 +2945,56 @@ mod tests {
             "css", "html", "cs",
         ];
         for ext in extensions {
-            assert!(
-                find_syntax(ext).is_some(),
-                "find_syntax({ext:?}) returned None"
-            );
+            assert!(has_syntax(ext), "has_syntax({ext:?}) returned false");
         }
         // Patched aliases that two-face cannot resolve on its own.
         for alias in [
             "csharp", "c-sharp", "cu", "cuh", "cppm", "CPPM", "cxxm", "CxXm", "ixx", "IXX",
-            "golang", "python3", "shell",
+            "golang", "python3", "ru", "shell",
         ] {
             assert!(
-                find_syntax(alias).is_some(),
-                "find_syntax({alias:?}) returned None — patched alias broken"
+                has_syntax(alias),
+                "has_syntax({alias:?}) returned false — patched alias broken"
             );
         }
     }
 
+    #[test]
+    fn php_without_an_opening_tag_uses_the_source_grammar() {
+        let syntax_set = syntax_set();
+        let name = |code: &str| {
+            find_syntax_for_code(&syntax_set, "php", code).map(|syntax| syntax.name.as_str())
+        };
+
+        assert_eq!(name("$user = User::find($id);\n"), Some("PHP Source"));
+        assert_eq!(name("\n  return view('home');\n"), Some("PHP Source"));
+        assert_eq!(name("<?php\n$user = null;\n"), Some("PHP"));
+        // Blade and other templates open with markup and keep the HTML-based grammar.
+        assert_eq!(name("<div>{{ $user->name }}</div>\n"), Some("PHP"));
+        assert_eq!(
+            find_syntax_for_code(&syntax_set, "ruby", "$stdout.puts 1\n")
+                .map(|syntax| syntax.name.as_str()),
+            Some("Ruby")
+        );
+
+        let theme = resolve_theme_by_name("dracula", /*codex_home*/ None).expect("dracula theme");
+        let lines = highlight_to_line_spans_with_theme("$user = null;\n", "php", &theme)
+            .expect("php highlights");
+        assert!(
+            lines[0].iter().any(|span| span.style != lines[0][0].style),
+            "tag-less PHP should not render in a single style"
+        );
+    }
+
+    #[test]
+    fn ruby_build_files_are_detected_by_name() {
+        for name in ["Gemfile", "Rakefile", "Vagrantfile", "Podfile"] {
+            assert_eq!(file_name_language(name), Some("rb"), "{name}");
+        }
+        assert_eq!(file_name_language("Makefile"), None);
+        assert_eq!(file_name_language("gemfile.lock"), None);
+    }
+
     #[test]
     fn diff_scope_backgrounds_prefer_markup_scope_then_diff_fallback() {
         let theme = Theme {
@@ -1293,6 +3060,63 @@ mod tests {
         assert_rgb(style.fg, (40, 50, 60));
     }
 
+    #[test]
+    fn syntax_colors_override_matching_theme_scopes() {
+        let theme = Theme {
+            settings: ThemeSettings::default(),
+            scopes: vec![
+                theme_item_with_foreground("comment", (10, 20, 30)),
+                theme_item_with_foreground("string", (40, 50, 60)),
+            ],
+            ..Theme::default()
+        };
+        let (colors, warnings) = parse_syntax_colors(&BTreeMap::from([
+            ("comment".to_string(), "#0a0b0c".to_string()),
+            ("keyword".to_string(), "blue".to_string()),
+        ]));
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+
+        let theme = apply_syntax_colors(theme, &colors);
+
+        let comment = foreground_style_for_scopes_with_theme(&theme, &["comment.line"])
+            .expect("expected comment foreground style");
+        assert_rgb(comment.fg, (10, 11, 12));
+        let string = foreground_style_for_scopes_with_theme(&theme, &["string.quoted"])
+            .expect("expected string foreground style");
+        assert_rgb(string.fg, (40, 50, 60));
+        let keyword = foreground_style_for_scopes_with_theme(&theme, &["keyword.control"])
+            .expect("expected keyword foreground style");
+        assert_eq!(keyword.fg, Some(RtColor::Blue));
+    }
+
+    #[test]
+    fn invalid_syntax_colors_are_reported_and_skipped() {
+        let (colors, warnings) = parse_syntax_colors(&BTreeMap::from([
+            ("comment".to_string(), "#12345".to_string()),
+            ("string".to_string(), "chartreuse".to_string()),
+            ("keyword".to_string(), "DEFAULT".to_string()),
+        ]));
+
+        assert_eq!(colors.len(), 1);
+        assert_eq!(
+            warnings,
+            vec![
+                "Ignoring tui.syntax_colors entry \"comment\": \"#12345\" is not a #rrggbb color or an ANSI color name.".to_string(),
+                "Ignoring tui.syntax_colors entry \"string\": \"chartreuse\" is not a #rrggbb color or an ANSI color name.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn high_contrast_theme_is_bundled() {
+        assert!(validate_theme_name(Some("high-contrast"), /*codex_home*/ None).is_none());
+        assert!(
+            list_available_themes(/*codex_home*/ None)
+                .iter()
+                .any(|entry| entry.name == "high-contrast" && !entry.is_custom)
+        );
+    }
+
     #[test]
     fn bundled_theme_can_provide_diff_scope_backgrounds() {
         let theme = resolve_theme_by_name("github", /*codex_home*/ None)
@@ -1,8 +1,11 @@
 use ratatui::layout::Rect;
 
+pub(crate) mod background_highlight;
+pub(crate) mod grammar_watcher;
 pub(crate) mod highlight;
 pub(crate) mod line_utils;
 pub(crate) mod renderable;
+pub(crate) mod tabs;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Insets {
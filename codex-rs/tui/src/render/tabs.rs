@@ -0,0 +1,98 @@
+//! Tab expansion for rendered code blocks and diffs.
+//!
+//! A raw tab is expanded by the terminal to its own tab stops, which know nothing about the gutters
+//! and indents drawn in front of the code, so the same file lines up differently from one terminal
+//! to the next. Code is expanded to spaces before rendering instead, using `tui.tab_width` or the
+//! entry in `tui.tab_width_overrides` for the block's language.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use unicode_width::UnicodeWidthChar;
+
+/// Tab width used until config is applied.
+const DEFAULT_TAB_WIDTH: usize = 4;
+
+struct TabWidths {
+    default: usize,
+    /// Keyed by lowercased language name or file extension.
+    overrides: BTreeMap<String, usize>,
+}
+
+impl TabWidths {
+    fn new(default: usize, overrides: &BTreeMap<String, usize>) -> Self {
+        Self {
+            default: default.max(1),
+            overrides: overrides
+                .iter()
+                .map(|(lang, width)| (lang.to_ascii_lowercase(), (*width).max(1)))
+                .collect(),
+        }
+    }
+
+    fn width_for(&self, lang: Option<&str>) -> usize {
+        lang.map(str::to_ascii_lowercase)
+            .and_then(|lang| self.overrides.get(&lang).copied())
+            .unwrap_or(self.default)
+    }
+}
+
+static TAB_WIDTHS: RwLock<TabWidths> = RwLock::new(TabWidths {
+    default: DEFAULT_TAB_WIDTH,
+    overrides: BTreeMap::new(),
+});
+
+/// Applies `tui.tab_width` and `tui.tab_width_overrides` from config.
+pub(crate) fn set_tab_widths(default: usize, overrides: &BTreeMap<String, usize>) {
+    let widths = TabWidths::new(default, overrides);
+    match TAB_WIDTHS.write() {
+        Ok(mut guard) => *guard = widths,
+        Err(poisoned) => *poisoned.into_inner() = widths,
+    }
+}
+
+/// Tab width for code in `lang`, a fence language or file extension.
+pub(crate) fn tab_width_for(lang: Option<&str>) -> usize {
+    match TAB_WIDTHS.read() {
+        Ok(guard) => guard.width_for(lang),
+        Err(poisoned) => poisoned.into_inner().width_for(lang),
+    }
+}
+
+/// Columns from `col` to the next tab stop.
+pub(crate) fn tab_advance(col: usize, tab_width: usize) -> usize {
+    let tab_width = tab_width.max(1);
+    tab_width - col % tab_width
+}
+
+/// Replaces each tab in `line` with spaces up to the next multiple of `tab_width` columns.
+pub(crate) fn expand_tabs(line: &str, tab_width: usize) -> Cow<'_, str> {
+    if !line.contains('\t') {
+        return Cow::Borrowed(line);
+    }
+    let mut expanded = String::with_capacity(line.len() + tab_width);
+    let mut col = 0;
+    for ch in line.chars() {
+        match ch {
+            '\t' => {
+                let advance = tab_advance(col, tab_width);
+                expanded.extend(std::iter::repeat_n(' ', advance));
+                col += advance;
+            }
+            '\n' => {
+                expanded.push(ch);
+                col = 0;
+            }
+            _ => {
+                expanded.push(ch);
+                col += ch.width().unwrap_or(0);
+            }
+        }
+    }
+    Cow::Owned(expanded)
+}
+
+#[cfg(test)]
+#[path = "tabs_tests.rs"]
+mod tests;
@@ -0,0 +1,32 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn tabs_expand_to_the_next_tab_stop() {
+    assert_eq!("a   b", expand_tabs("a\tb", /*tab_width*/ 4));
+    assert_eq!("abcd    e", expand_tabs("abcd\te", /*tab_width*/ 4));
+    assert_eq!("        x", expand_tabs("\t\tx", /*tab_width*/ 4));
+    assert_eq!("界      x", expand_tabs("界\tx", /*tab_width*/ 8));
+    assert_eq!("a\n  b", expand_tabs("a\n\tb", /*tab_width*/ 2));
+}
+
+#[test]
+fn lines_without_tabs_are_borrowed() {
+    assert!(matches!(
+        expand_tabs("no tabs", /*tab_width*/ 4),
+        Cow::Borrowed("no tabs")
+    ));
+}
+
+#[test]
+fn overrides_match_language_case_insensitively() {
+    let widths = TabWidths::new(
+        /*default*/ 2,
+        &BTreeMap::from([("Go".to_string(), 8), ("make".to_string(), 0)]),
+    );
+
+    assert_eq!(8, widths.width_for(Some("go")));
+    assert_eq!(1, widths.width_for(Some("make")));
+    assert_eq!(2, widths.width_for(Some("rs")));
+    assert_eq!(2, widths.width_for(/*lang*/ None));
+}
@@ -1004,6 +1004,7 @@ impl PickerState {
         };
         self.overlay = Some(Overlay::new_transcript(
             cells.clone(),
+            Vec::new(),
             self.pager_keymap.clone(),
         ));
         self.pending_transcript_open = None;
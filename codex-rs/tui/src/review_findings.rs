@@ -0,0 +1,107 @@
+//! Review findings recovered from the text of a finished code review.
+//!
+//! The app server reports a finished review as the text produced by
+//! `codex_protocol::review_format::render_review_output_text`. This module parses the findings
+//! block of that text back into entries so the TUI can list them and offer to apply a fix.
+
+use std::path::PathBuf;
+
+const FINDINGS_HEADERS: [&str; 2] = ["Full review comments:", "Review comment:"];
+const LOCATION_SEPARATOR: &str = " — ";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ReviewFindingEntry {
+    pub(crate) title: String,
+    /// Parsed from the `[P0]`..`[P3]` tag the review rubric puts at the start of each title.
+    pub(crate) priority: Option<u8>,
+    pub(crate) path: PathBuf,
+    pub(crate) start_line: u32,
+    pub(crate) end_line: u32,
+    pub(crate) body: String,
+}
+
+impl ReviewFindingEntry {
+    pub(crate) fn location(&self) -> String {
+        format!(
+            "{}:{}-{}",
+            self.path.display(),
+            self.start_line,
+            self.end_line
+        )
+    }
+
+    /// Prompt asking the agent to apply the fix the finding suggests.
+    pub(crate) fn fix_prompt(&self) -> String {
+        format!(
+            "Apply the fix suggested by this code review finding, then verify it.\n\n{}\nLocation: {}\n\n{}",
+            self.title,
+            self.location(),
+            self.body
+        )
+    }
+}
+
+/// Parses the findings block out of review text, sorted by file and then by priority.
+///
+/// Returns an empty list when the text has no findings block.
+pub(crate) fn parse_review_findings(review: &str) -> Vec<ReviewFindingEntry> {
+    let mut lines = review.lines();
+    if !lines.any(|line| FINDINGS_HEADERS.contains(&line.trim())) {
+        return Vec::new();
+    }
+
+    let mut findings: Vec<ReviewFindingEntry> = Vec::new();
+    for line in lines {
+        if let Some(body_line) = line.strip_prefix("  ")
+            && let Some(finding) = findings.last_mut()
+        {
+            if !finding.body.is_empty() {
+                finding.body.push('\n');
+            }
+            finding.body.push_str(body_line);
+        } else if let Some(item) = line.strip_prefix("- ")
+            && let Some(finding) = parse_finding_line(item)
+        {
+            findings.push(finding);
+        }
+    }
+    findings.sort_by(|left, right| {
+        left.path
+            .cmp(&right.path)
+            .then(
+                left.priority
+                    .unwrap_or(u8::MAX)
+                    .cmp(&right.priority.unwrap_or(u8::MAX)),
+            )
+            .then(left.start_line.cmp(&right.start_line))
+    });
+    findings
+}
+
+fn parse_finding_line(item: &str) -> Option<ReviewFindingEntry> {
+    // Strip the selection checkbox used when findings are rendered for selection.
+    let item = item
+        .strip_prefix("[x] ")
+        .or_else(|| item.strip_prefix("[ ] "))
+        .unwrap_or(item);
+    let (title, location) = item.rsplit_once(LOCATION_SEPARATOR)?;
+    let (path, range) = location.rsplit_once(':')?;
+    let (start, end) = range.split_once('-')?;
+    Some(ReviewFindingEntry {
+        title: title.to_string(),
+        priority: priority_tag(title),
+        path: PathBuf::from(path),
+        start_line: start.parse().ok()?,
+        end_line: end.parse().ok()?,
+        body: String::new(),
+    })
+}
+
+fn priority_tag(title: &str) -> Option<u8> {
+    let tag = title.strip_prefix("[P")?.split_once(']')?.0;
+    tag.parse().ok().filter(|priority| *priority <= 3)
+}
+
+#[cfg(test)]
+#[path = "review_findings_tests.rs"]
+mod tests;
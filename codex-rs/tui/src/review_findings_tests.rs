@@ -0,0 +1,94 @@
+use super::*;
+use codex_protocol::protocol::ReviewCodeLocation;
+use codex_protocol::protocol::ReviewFinding;
+use codex_protocol::protocol::ReviewLineRange;
+use codex_protocol::protocol::ReviewOutputEvent;
+use codex_protocol::review_format::render_review_output_text;
+use pretty_assertions::assert_eq;
+
+fn finding(title: &str, path: &str, start: u32, body: &str) -> ReviewFinding {
+    ReviewFinding {
+        title: title.to_string(),
+        body: body.to_string(),
+        confidence_score: 0.8,
+        priority: 1,
+        code_location: ReviewCodeLocation {
+            absolute_file_path: PathBuf::from(path),
+            line_range: ReviewLineRange {
+                start,
+                end: start + 2,
+            },
+        },
+    }
+}
+
+#[test]
+fn parses_rendered_review_output_sorted_by_file_and_priority() {
+    let review = render_review_output_text(&ReviewOutputEvent {
+        findings: vec![
+            finding(
+                "[P2] Log message is misleading",
+                "/repo/src/lib.rs",
+                40,
+                "Says retrying when it gives up.",
+            ),
+            finding(
+                "[P1] Missing bounds check — panics on empty input",
+                "/repo/src/lib.rs",
+                10,
+                "Index 0 is read before checking length.\nGuard with `first()`.",
+            ),
+            finding("Unused import", "/repo/src/a.rs", 1, "Remove it."),
+        ],
+        overall_correctness: "patch is incorrect".to_string(),
+        overall_explanation: "One crash, two nits.".to_string(),
+        overall_confidence_score: 0.7,
+    });
+
+    let findings = parse_review_findings(&review);
+
+    assert_eq!(
+        findings,
+        vec![
+            ReviewFindingEntry {
+                title: "Unused import".to_string(),
+                priority: None,
+                path: PathBuf::from("/repo/src/a.rs"),
+                start_line: 1,
+                end_line: 3,
+                body: "Remove it.".to_string(),
+            },
+            ReviewFindingEntry {
+                title: "[P1] Missing bounds check — panics on empty input".to_string(),
+                priority: Some(1),
+                path: PathBuf::from("/repo/src/lib.rs"),
+                start_line: 10,
+                end_line: 12,
+                body: "Index 0 is read before checking length.\nGuard with `first()`.".to_string(),
+            },
+            ReviewFindingEntry {
+                title: "[P2] Log message is misleading".to_string(),
+                priority: Some(2),
+                path: PathBuf::from("/repo/src/lib.rs"),
+                start_line: 40,
+                end_line: 42,
+                body: "Says retrying when it gives up.".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        findings[1].fix_prompt(),
+        "Apply the fix suggested by this code review finding, then verify it.\n\n\
+         [P1] Missing bounds check — panics on empty input\n\
+         Location: /repo/src/lib.rs:10-12\n\n\
+         Index 0 is read before checking length.\nGuard with `first()`."
+    );
+}
+
+#[test]
+fn review_without_findings_block_has_no_findings() {
+    assert_eq!(
+        parse_review_findings("Looks good.\n- not a finding — nowhere"),
+        Vec::new()
+    );
+}
@@ -0,0 +1,97 @@
+//! `codex --safe-mode`: start with extensions and customizations turned off.
+//!
+//! Safe mode lets users recover from a config or extension that breaks the TUI without editing
+//! files blind. It is expressed entirely as `-c` style overrides layered over the user's config,
+//! so nothing on disk changes and the next normal start behaves as before:
+//!
+//! - hooks, plugins, and apps are disabled through their feature flags;
+//! - every configured MCP server is disabled by name, since a server table cannot be removed
+//!   through an override;
+//! - approvals fall back to `on-request`.
+//!
+//! Custom themes, syntax colors, and grammars are skipped where the TUI applies them at startup.
+//! A user config that fails to load is ignored for the session instead of stopping startup.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use codex_config::CloudConfigBundleLoader;
+use codex_config::ConfigLoadOptions;
+use codex_config::LoaderOverrides;
+use codex_config::TomlValue;
+use codex_config::types::McpServerConfig;
+
+use crate::legacy_core::config::load_config_toml_with_layer_stack;
+
+/// Overrides applied before config is loaded, in `-c key=value` form.
+pub(crate) const SAFE_MODE_RAW_OVERRIDES: &[&str] = &[
+    "features.hooks=false",
+    "features.plugin_hooks=false",
+    "features.plugins=false",
+    "features.apps=false",
+    "approval_policy=\"on-request\"",
+];
+
+/// Startup notice shown in the transcript so the session is not mistaken for a normal one.
+pub(crate) const SAFE_MODE_WARNING: &str = "Safe mode: MCP servers, hooks, plugins, apps, and custom themes are disabled for this session. Restart without `--safe-mode` to use them again.";
+
+/// Overrides that disable each configured MCP server, sorted by server name.
+pub(crate) fn disable_mcp_server_overrides(
+    mcp_servers: &HashMap<String, McpServerConfig>,
+) -> Vec<(String, TomlValue)> {
+    let mut names = mcp_servers.keys().collect::<Vec<_>>();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| {
+            (
+                format!("mcp_servers.{}.enabled", override_key_segment(name)),
+                TomlValue::Boolean(false),
+            )
+        })
+        .collect()
+}
+
+/// Quotes `name` when it contains a `.` or `"`, which an unquoted override path segment would
+/// split on or misread.
+fn override_key_segment(name: &str) -> String {
+    if name.contains(['.', '"']) {
+        format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        name.to_string()
+    }
+}
+
+/// Loads the user's config as startup will, without the project layers, and describes why it
+/// failed if it did. Safe mode then starts without the user's config rather than exiting.
+pub(crate) async fn user_config_load_error(
+    codex_home: &Path,
+    cli_kv_overrides: &[(String, TomlValue)],
+    loader_overrides: &LoaderOverrides,
+    strict_config: bool,
+) -> Option<String> {
+    load_config_toml_with_layer_stack(
+        codex_home,
+        /*cwd*/ None,
+        cli_kv_overrides.to_vec(),
+        ConfigLoadOptions {
+            loader_overrides: loader_overrides.clone(),
+            strict_config,
+            cloud_config_bundle: CloudConfigBundleLoader::default(),
+        },
+    )
+    .await
+    .err()
+    .map(|err| crate::describe_config_load_error(&err))
+}
+
+/// Startup notice for a session that ignores the user's config because it failed to load.
+pub(crate) fn skipped_user_config_warning(error: &str) -> String {
+    format!(
+        "Safe mode: config.toml could not be loaded, so this session ignores it. Fix it and restart to use it again.\n{error}"
+    )
+}
+
+#[cfg(test)]
+#[path = "safe_mode_tests.rs"]
+mod tests;
@@ -0,0 +1,59 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn stdio_server() -> McpServerConfig {
+    toml::from_str("command = \"server\"").expect("valid MCP server config")
+}
+
+#[test]
+fn every_mcp_server_is_disabled() {
+    let mcp_servers = HashMap::from([
+        ("search".to_string(), stdio_server()),
+        ("docs".to_string(), stdio_server()),
+        ("team.docs".to_string(), stdio_server()),
+    ]);
+
+    assert_eq!(
+        disable_mcp_server_overrides(&mcp_servers),
+        vec![
+            (
+                "mcp_servers.docs.enabled".to_string(),
+                TomlValue::Boolean(false)
+            ),
+            (
+                "mcp_servers.search.enabled".to_string(),
+                TomlValue::Boolean(false)
+            ),
+            (
+                "mcp_servers.\"team.docs\".enabled".to_string(),
+                TomlValue::Boolean(false)
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn broken_user_config_is_reported_instead_of_failing() {
+    let codex_home = tempfile::tempdir().expect("tempdir");
+    let loader_overrides = LoaderOverrides::without_managed_config_for_tests();
+    assert_eq!(
+        user_config_load_error(
+            codex_home.path(),
+            &[],
+            &loader_overrides,
+            /*strict_config*/ false
+        )
+        .await,
+        None
+    );
+
+    std::fs::write(codex_home.path().join("config.toml"), "model = [").expect("write config");
+    let error = user_config_load_error(
+        codex_home.path(),
+        &[],
+        &loader_overrides,
+        /*strict_config*/ false,
+    )
+    .await;
+    assert!(error.is_some_and(|error| error.starts_with("Error loading config.toml")));
+}
@@ -46,10 +46,16 @@ pub enum SlashCommand {
     Copy,
     Raw,
     Diff,
+    Todos,
+    Env,
+    Artifacts,
+    Template,
     Mention,
     Status,
+    Context,
     Usage,
     DebugConfig,
+    Logs,
     Title,
     Statusline,
     Theme,
@@ -68,6 +74,7 @@ pub enum SlashCommand {
     Stop,
     Clear,
     Personality,
+    Language,
     TestApproval,
     #[strum(serialize = "subagents")]
     MultiAgents,
@@ -95,16 +102,24 @@ impl SlashCommand {
             SlashCommand::Fork => "fork the current chat",
             SlashCommand::App => "continue this session in the Desktop app",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
-            SlashCommand::Copy => "copy last response as markdown",
+            SlashCommand::Copy => {
+                "copy the last response, turn diff, command, or code block to the clipboard"
+            }
             SlashCommand::Raw => "toggle raw scrollback mode for copy-friendly terminal selection",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Todos => "list TODO/FIXME/HACK comments and pick one to fix",
+            SlashCommand::Env => "show the OS, toolchain versions, and build env vars",
+            SlashCommand::Artifacts => "list files Codex generated in this session and open one",
+            SlashCommand::Template => "insert a workflow template and Tab between its fields",
             SlashCommand::Mention => "mention a file",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Import => "import setup, this project, and recent chats from Claude Code",
             SlashCommand::Hooks => "view and manage lifecycle hooks",
             SlashCommand::Status => "show current session configuration and token usage",
+            SlashCommand::Context => "show the effective system prompt and project additions",
             SlashCommand::Usage => "view account usage or use a usage limit reset",
             SlashCommand::DebugConfig => "show config layers and requirement sources for debugging",
+            SlashCommand::Logs => "show recent Codex logs; filter with /logs [level] [target]",
             SlashCommand::Title => "configure which items appear in the terminal title",
             SlashCommand::Statusline => "configure which items appear in the status line",
             SlashCommand::Theme => "choose a syntax highlighting theme",
@@ -118,6 +133,7 @@ impl SlashCommand {
                 "include current selection, open files, and other context from your IDE"
             }
             SlashCommand::Personality => "choose a communication style for Codex",
+            SlashCommand::Language => "set the language replies use in this project",
             SlashCommand::Plan => "switch to Plan mode",
             SlashCommand::Goal => "set or view the goal for a long-running task",
             SlashCommand::Agent | SlashCommand::MultiAgents => "switch the active agent thread",
@@ -162,13 +178,17 @@ impl SlashCommand {
                 | SlashCommand::Ide
                 | SlashCommand::Keymap
                 | SlashCommand::Mcp
+                | SlashCommand::Copy
                 | SlashCommand::Raw
                 | SlashCommand::Usage
+                | SlashCommand::Language
+                | SlashCommand::Logs
                 | SlashCommand::Pets
                 | SlashCommand::Side
                 | SlashCommand::Btw
                 | SlashCommand::Resume
                 | SlashCommand::SandboxReadRoot
+                | SlashCommand::Template
         )
     }
 
@@ -209,9 +229,14 @@ impl SlashCommand {
             | SlashCommand::MemoryDrop
             | SlashCommand::MemoryUpdate => false,
             SlashCommand::Diff
+            | SlashCommand::Todos
+            | SlashCommand::Env
+            | SlashCommand::Artifacts
+            | SlashCommand::Template
             | SlashCommand::Resume
             | SlashCommand::Model
             | SlashCommand::Personality
+            | SlashCommand::Language
             | SlashCommand::Permissions
             | SlashCommand::Copy
             | SlashCommand::Raw
@@ -220,8 +245,10 @@ impl SlashCommand {
             | SlashCommand::Skills
             | SlashCommand::Hooks
             | SlashCommand::Status
+            | SlashCommand::Context
             | SlashCommand::Usage
             | SlashCommand::DebugConfig
+            | SlashCommand::Logs
             | SlashCommand::Ps
             | SlashCommand::Stop
             | SlashCommand::App
@@ -277,6 +277,44 @@ fn permissions_text_for(config: &Config) -> Option<String> {
         })
 }
 
+#[tokio::test]
+async fn status_shows_response_language_when_set() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    let usage = TokenUsage::default();
+    let captured_at = chrono::Local
+        .with_ymd_and_hms(2024, 1, 2, 3, 4, 5)
+        .single()
+        .expect("timestamp");
+    let model_slug = get_model_offline_for_tests(config.model.as_deref());
+    let response_language_line = |config: &Config| {
+        let composite = new_status_output(
+            config,
+            test_status_account_display().as_ref(),
+            /*token_info*/ None,
+            &usage,
+            &None,
+            /*thread_name*/ None,
+            /*forked_from*/ None,
+            /*rate_limits*/ None,
+            None,
+            captured_at,
+            &model_slug,
+            /*collaboration_mode*/ None,
+            /*reasoning_effort_override*/ None,
+        );
+        render_lines(&composite.display_lines(/*width*/ 80))
+            .into_iter()
+            .find(|line| line.contains("Response language:"))
+    };
+
+    assert_eq!(response_language_line(&config), None);
+
+    config.response_language = Some("pt-BR".to_string());
+    let line = response_language_line(&config).expect("response language line");
+    assert!(line.contains("pt-BR"), "{line}");
+}
+
 #[tokio::test]
 async fn status_snapshot_includes_reasoning_details() {
     let temp_home = TempDir::new().expect("temp home");
@@ -1728,8 +1766,11 @@ async fn transcript_overlay_remeasures_status_after_rate_limit_refresh() {
         "<none>".to_string(),
         /*refreshing_rate_limits*/ true,
     );
-    let mut overlay =
-        TranscriptOverlay::new(vec![Arc::new(status)], RuntimeKeymap::defaults().pager);
+    let mut overlay = TranscriptOverlay::new(
+        vec![Arc::new(status)],
+        Vec::new(),
+        RuntimeKeymap::defaults().pager,
+    );
     let area = Rect::new(
         /*x*/ 0, /*y*/ 0, /*width*/ 80, /*height*/ 30,
     );
@@ -1756,7 +1797,10 @@ async fn transcript_overlay_remeasures_status_after_rate_limit_refresh() {
         }],
         now,
     );
-    overlay.insert_cell(Arc::new(PlainHistoryCell::new(vec!["next message".into()])));
+    overlay.insert_cell(
+        Arc::new(PlainHistoryCell::new(vec!["next message".into()])),
+        std::time::SystemTime::now(),
+    );
     buffer = Buffer::empty(area);
     overlay.render(area, &mut buffer);
     let after = buffer_to_text(&buffer, area.width);
@@ -19,6 +19,8 @@ use ratatui::widgets::WidgetRef;
 use unicode_width::UnicodeWidthStr;
 
 use crate::app_event_sender::AppEventSender;
+use crate::i18n::Msg;
+use crate::i18n::tr;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::line_truncation::truncate_line_with_ellipsis_if_overflow;
@@ -84,7 +86,7 @@ impl StatusIndicatorWidget {
         animations_enabled: bool,
     ) -> Self {
         Self {
-            header: String::from("Working"),
+            header: tr(Msg::StatusWorking),
             details: None,
             details_max_lines: STATUS_DETAILS_DEFAULT_MAX_LINES,
             inline_message: None,
@@ -240,7 +242,7 @@ impl Renderable for StatusIndicatorWidget {
             return;
         }
 
-        if self.animations_enabled {
+        if self.animations_enabled && !crate::idle_pause::background_work_paused() {
             // Schedule next animation frame.
             self.frame_requester
                 .schedule_frame_in(Duration::from_millis(32));
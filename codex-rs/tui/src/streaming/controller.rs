@@ -19,6 +19,11 @@
 //! agent and proposed-plan streams. Lines in `Outside` and `Markdown` fence
 //! contexts are scanned; lines inside non-markdown fences are skipped.
 //!
+//! A code block longer than `tui.code_block_max_lines` renders its first and
+//! last lines around a hidden-lines row, and the last lines move as it grows.
+//! Once an open fence passes half that limit, it is held back like a table
+//! until it closes.
+//!
 //! ## Resize handling
 //!
 //! On terminal width change, `StreamCore::set_width` re-renders at the new
@@ -40,6 +45,7 @@ use crate::history_cell::HistoryRenderMode;
 use crate::history_cell::{self};
 use crate::inline_visualization::InlineVisualizationContext;
 use crate::markdown::render_markdown_agent_with_links_cwd_and_visualizations;
+use crate::markdown_render::code_block_max_lines;
 use crate::style::proposed_plan_style;
 use crate::terminal_hyperlinks::HyperlinkLine;
 use crate::terminal_hyperlinks::prefix_hyperlink_lines;
@@ -387,8 +393,9 @@ impl StreamCore {
     /// table region is held as tail because adding a row can reshape table
     /// column widths. For `PendingHeader`, only content from the speculative
     /// header line onward is kept mutable so earlier prose can continue
-    /// streaming. When no table is detected, everything flows directly to
-    /// stable. This is the core decision point for the holdback mechanism.
+    /// streaming. An open code fence long enough to collapse is held the same
+    /// way. Otherwise everything flows directly to stable. This is the core
+    /// decision point for the holdback mechanism.
     fn active_tail_budget_lines(&mut self) -> usize {
         if self.render_mode == HistoryRenderMode::Raw {
             return 0;
@@ -402,8 +409,19 @@ impl StreamCore {
             } => self.tail_budget_from_source_start(start),
             TableHoldbackState::None => 0,
         };
+        // A code block long enough to collapse shows its last lines, which change with every
+        // line, so it stays mutable from its opening fence until it closes.
+        let code_fence_start = match code_block_max_lines() {
+            0 => None,
+            max_lines => self.holdback_scanner.long_code_fence_start(max_lines / 2),
+        };
+        let tail_budget = match code_fence_start {
+            Some(start) => tail_budget.max(self.tail_budget_from_source_start(start)),
+            None => tail_budget,
+        };
         tracing::trace!(
             state = ?holdback_state,
+            ?code_fence_start,
             tail_budget,
             elapsed_us = scan_start.elapsed().as_micros(),
             "table holdback decision",
@@ -1889,6 +1907,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn incremental_holdback_tracks_the_open_code_fence() {
+        let mut scanner = TableHoldbackScanner::new();
+        scanner.push_source_chunk("Intro\n\n```rust\nfn a() {}\nfn b() {}\n");
+        assert_eq!(scanner.long_code_fence_start(/*lines*/ 2), None);
+        scanner.push_source_chunk("fn c() {}\n");
+        assert_eq!(scanner.long_code_fence_start(/*lines*/ 2), Some(7));
+        scanner.push_source_chunk("```\n");
+        assert_eq!(scanner.long_code_fence_start(/*lines*/ 0), None);
+    }
+
+    #[test]
+    fn controller_streamed_long_code_block_matches_final_render() {
+        let body: String = (1..=250)
+            .map(|line| format!("let x{line} = {line};\n"))
+            .collect();
+        let source = format!("Code:\n\n```rust\n{body}```\n\nDone.\n");
+        let deltas: Vec<&str> = source.split_inclusive('\n').collect();
+        let streamed = collect_streamed_lines(&deltas, Some(80));
+
+        let mut rendered = Vec::new();
+        crate::markdown::append_markdown_agent(&source, /*width*/ Some(80), &mut rendered);
+        let expected = lines_to_plain_strings(&rendered);
+
+        assert_eq!(streamed, expected);
+        assert!(
+            streamed
+                .iter()
+                .any(|line| line.trim() == "… 50 lines hidden …"),
+            "expected the code block to collapse: {streamed:?}"
+        );
+    }
+
     #[test]
     fn controller_set_width_after_first_line_emit_does_not_requeue_first_line() {
         let mut ctrl = stream_controller(Some(120));
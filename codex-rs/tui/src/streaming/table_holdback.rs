@@ -4,6 +4,9 @@
 //! adding a row can reflow earlier table rows instead of committing a stale
 //! render to scrollback.
 //!
+//! The same scan follows the open non-markdown code fence, so a code block
+//! that grows long enough to collapse can stay mutable until it closes.
+//!
 //! The scanner is intentionally conservative: it only looks for enough
 //! structure to decide where the mutable tail should start. It does not try to
 //! validate an entire table or predict final layout. Rendering remains the job
@@ -31,6 +34,15 @@ pub(super) enum TableHoldbackState {
     Confirmed { table_start: usize },
 }
 
+/// A non-markdown code fence that has not closed yet.
+#[derive(Clone, Copy)]
+struct OpenCodeFence {
+    /// Source offset of the opening fence line.
+    start: usize,
+    /// Lines committed inside the fence so far.
+    lines: usize,
+}
+
 /// Facts remembered about the previous committed source line.
 ///
 /// The scanner only needs one-line lookbehind because a table is confirmed by
@@ -54,6 +66,7 @@ pub(super) struct TableHoldbackScanner {
     previous_line: Option<PreviousLineState>,
     pending_header_start: Option<usize>,
     confirmed_table_start: Option<usize>,
+    open_code_fence: Option<OpenCodeFence>,
 }
 
 impl TableHoldbackScanner {
@@ -64,6 +77,7 @@ impl TableHoldbackScanner {
             previous_line: None,
             pending_header_start: None,
             confirmed_table_start: None,
+            open_code_fence: None,
         }
     }
 
@@ -88,6 +102,14 @@ impl TableHoldbackScanner {
         }
     }
 
+    /// Source offset of the open non-markdown code fence once more than `lines` lines have been
+    /// committed inside it.
+    pub(super) fn long_code_fence_start(&self, lines: usize) -> Option<usize> {
+        self.open_code_fence
+            .filter(|fence| fence.lines > lines)
+            .map(|fence| fence.start)
+    }
+
     /// Advance the scanner with newly committed source.
     ///
     /// Chunks are expected to contain only source that is now safe to commit
@@ -155,6 +177,19 @@ impl TableHoldbackScanner {
         });
 
         self.fence_tracker.advance(line);
+        self.open_code_fence = match (fence_kind, self.fence_tracker.kind()) {
+            (FenceKind::Other, FenceKind::Other) => {
+                self.open_code_fence.map(|fence| OpenCodeFence {
+                    lines: fence.lines + 1,
+                    ..fence
+                })
+            }
+            (_, FenceKind::Other) => Some(OpenCodeFence {
+                start: source_start,
+                lines: 0,
+            }),
+            _ => None,
+        };
         self.source_offset = self.source_offset.saturating_add(source_line.len());
     }
 }
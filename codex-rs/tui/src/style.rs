@@ -4,8 +4,8 @@ use crate::terminal_palette::StdoutColorLevel;
 use crate::terminal_palette::best_color;
 use crate::terminal_palette::default_bg;
 use crate::terminal_palette::default_fg;
+use crate::terminal_palette::effective_stdout_color_level;
 use crate::terminal_palette::rgb_color;
-use crate::terminal_palette::stdout_color_level;
 use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
@@ -24,7 +24,7 @@ pub fn proposed_plan_style() -> Style {
 
 /// Returns a low-contrast rule style for separators within markdown tables.
 pub(crate) fn table_separator_style() -> Style {
-    table_separator_style_for(default_fg(), default_bg(), stdout_color_level())
+    table_separator_style_for(default_fg(), default_bg(), effective_stdout_color_level())
 }
 
 /// Returns the shared accent style for active or selected TUI controls.
@@ -0,0 +1,353 @@
+//! Optional terminal features, detected in one place.
+//!
+//! Widgets that emit escape sequences beyond plain SGR styling (24-bit color, OSC 8 hyperlinks,
+//! OSC 52 clipboard writes, the kitty keyboard protocol, and image protocols) ask
+//! [`terminal_capabilities`] instead of probing the environment themselves. Detection runs once
+//! from the terminal identity in [`codex_terminal_detection`] plus a few emulator-specific
+//! environment variables, and `tui.terminal_capabilities` forces any feature on or off when the
+//! guess is wrong for a less common terminal.
+
+use std::sync::OnceLock;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+use codex_config::types::TerminalCapabilitiesToml;
+use codex_terminal_detection::TerminalInfo;
+use codex_terminal_detection::TerminalName;
+use codex_terminal_detection::terminal_info;
+
+use crate::terminal_palette::StdoutColorLevel;
+use crate::terminal_palette::detected_stdout_color_level;
+
+/// First iTerm2 release that draws images sent with the Kitty graphics protocol.
+const ITERM2_KITTY_MIN_VERSION: (u64, u64, u64) = (3, 6, 0);
+
+/// Variables set inside multiplexers, which do not keep images pane-local.
+const MULTIPLEXER_ENV_VARS: [&str; 6] = [
+    "TMUX",
+    "TMUX_PANE",
+    "ZELLIJ",
+    "ZELLIJ_SESSION_NAME",
+    "ZELLIJ_VERSION",
+    "STY",
+];
+
+/// Variables set by terminals that speak the Kitty graphics protocol, even when `TERM` and
+/// `TERM_PROGRAM` were rewritten on the way (over SSH, for example).
+const KITTY_GRAPHICS_ENV_VARS: [&str; 3] =
+    ["KITTY_WINDOW_ID", "WEZTERM_EXECUTABLE", "WEZTERM_VERSION"];
+
+static OVERRIDES: RwLock<Option<TerminalCapabilitiesToml>> = RwLock::new(None);
+
+/// Features of the terminal the TUI is drawing to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct TerminalCapabilities {
+    /// 24-bit color; without it RGB colors are mapped to the 256-color palette.
+    pub(crate) truecolor: bool,
+    /// OSC 8 hyperlinks.
+    pub(crate) hyperlinks: bool,
+    /// OSC 52 clipboard writes.
+    pub(crate) osc52_clipboard: bool,
+    /// The kitty keyboard protocol. Support is probed at startup, so this is only set when config
+    /// forces it.
+    pub(crate) kitty_keyboard: Option<bool>,
+    /// The Kitty graphics protocol.
+    pub(crate) kitty_graphics: bool,
+    /// Sixel graphics.
+    pub(crate) sixel: bool,
+    /// iTerm2's `OSC 1337 File=` inline images.
+    pub(crate) iterm2_images: bool,
+}
+
+impl TerminalCapabilities {
+    /// Features implied by the terminal's identity and stdout color level.
+    pub(crate) fn detect(info: &TerminalInfo, color_level: StdoutColorLevel) -> Self {
+        let linux_console = info.term.as_deref() == Some("linux");
+        let plain_text = linux_console || info.name == TerminalName::Dumb;
+        // Multiplexers do not keep images pane-local, so they would smear across other panes.
+        let images = info.multiplexer.is_none() && !plain_text;
+        Self {
+            truecolor: color_level == StdoutColorLevel::TrueColor,
+            hyperlinks: !plain_text,
+            // Terminal.app silently drops OSC 52 writes.
+            osc52_clipboard: !plain_text && info.name != TerminalName::AppleTerminal,
+            kitty_keyboard: None,
+            kitty_graphics: images && supports_kitty_graphics(info),
+            sixel: images && supports_sixel(info),
+            iterm2_images: images && supports_iterm2_images(info),
+        }
+    }
+
+    /// Adjusts image support for environment variables that outlive `TERM` rewrites.
+    fn with_environment(mut self, var_is_set: impl Fn(&str) -> bool) -> Self {
+        if MULTIPLEXER_ENV_VARS.into_iter().any(&var_is_set) {
+            self.kitty_graphics = false;
+            self.sixel = false;
+            self.iterm2_images = false;
+        } else if KITTY_GRAPHICS_ENV_VARS.into_iter().any(&var_is_set) {
+            self.kitty_graphics = true;
+        }
+        self
+    }
+
+    /// Applies `tui.terminal_capabilities`; unset keys keep the detected value.
+    pub(crate) fn with_overrides(self, overrides: &TerminalCapabilitiesToml) -> Self {
+        Self {
+            truecolor: overrides.truecolor.unwrap_or(self.truecolor),
+            hyperlinks: overrides.hyperlinks.unwrap_or(self.hyperlinks),
+            osc52_clipboard: overrides.osc52_clipboard.unwrap_or(self.osc52_clipboard),
+            kitty_keyboard: overrides.kitty_keyboard.or(self.kitty_keyboard),
+            kitty_graphics: overrides.kitty_graphics.unwrap_or(self.kitty_graphics),
+            sixel: overrides.sixel.unwrap_or(self.sixel),
+            iterm2_images: overrides.iterm2_images.unwrap_or(self.iterm2_images),
+        }
+    }
+}
+
+/// Applies `tui.terminal_capabilities` from config.
+pub(crate) fn set_terminal_capability_overrides(overrides: TerminalCapabilitiesToml) {
+    *OVERRIDES.write().unwrap_or_else(PoisonError::into_inner) = Some(overrides);
+}
+
+/// Capabilities of the current terminal, with config overrides applied.
+pub(crate) fn terminal_capabilities() -> TerminalCapabilities {
+    static DETECTED: OnceLock<TerminalCapabilities> = OnceLock::new();
+    let detected = *DETECTED.get_or_init(|| {
+        TerminalCapabilities::detect(&terminal_info(), detected_stdout_color_level())
+            .with_environment(|name| std::env::var_os(name).is_some())
+    });
+    let overrides = *OVERRIDES.read().unwrap_or_else(PoisonError::into_inner);
+    detected.with_overrides(&overrides.unwrap_or_default())
+}
+
+/// Whether the terminal is iTerm2, whatever its version.
+pub(crate) fn is_iterm2_terminal(info: &TerminalInfo) -> bool {
+    info.name == TerminalName::Iterm2
+        || terminal_field_contains(info.term_program.as_deref(), "iterm")
+}
+
+fn supports_kitty_graphics(info: &TerminalInfo) -> bool {
+    matches!(
+        info.name,
+        TerminalName::Ghostty | TerminalName::Kitty | TerminalName::WezTerm
+    ) || ["kitty", "ghostty", "wezterm"].into_iter().any(|needle| {
+        terminal_field_contains(info.term.as_deref(), needle)
+            || terminal_field_contains(info.term_program.as_deref(), needle)
+    }) || (is_iterm2_terminal(info)
+        && version_is_at_least(info.version.as_deref(), ITERM2_KITTY_MIN_VERSION))
+}
+
+fn supports_sixel(info: &TerminalInfo) -> bool {
+    info.name == TerminalName::WindowsTerminal
+        || ["sixel", "mlterm", "foot"]
+            .into_iter()
+            .any(|needle| terminal_field_contains(info.term.as_deref(), needle))
+}
+
+fn supports_iterm2_images(info: &TerminalInfo) -> bool {
+    matches!(info.name, TerminalName::Iterm2 | TerminalName::WezTerm)
+        || terminal_field_contains(info.term_program.as_deref(), "iterm")
+        || terminal_field_contains(info.term_program.as_deref(), "wezterm")
+}
+
+fn terminal_field_contains(value: Option<&str>, needle: &str) -> bool {
+    value.is_some_and(|value| value.to_ascii_lowercase().contains(needle))
+}
+
+fn version_is_at_least(version: Option<&str>, minimum: (u64, u64, u64)) -> bool {
+    parse_dotted_version(version).is_some_and(|version| version >= minimum)
+}
+
+fn parse_dotted_version(version: Option<&str>) -> Option<(u64, u64, u64)> {
+    let version = version?;
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().unwrap_or("0").parse().ok()?;
+    let patch = parts.next().unwrap_or("0").parse().ok()?;
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some((major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_terminal_detection::Multiplexer;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn terminal(
+        name: TerminalName,
+        term_program: Option<&str>,
+        term: Option<&str>,
+    ) -> TerminalInfo {
+        TerminalInfo {
+            name,
+            term_program: term_program.map(str::to_string),
+            version: None,
+            term: term.map(str::to_string),
+            multiplexer: None,
+        }
+    }
+
+    #[test]
+    fn truecolor_follows_the_stdout_color_level() {
+        let info = terminal(
+            TerminalName::Unknown,
+            /*term_program*/ None,
+            Some("xterm"),
+        );
+
+        assert!(TerminalCapabilities::detect(&info, StdoutColorLevel::TrueColor).truecolor);
+        assert!(!TerminalCapabilities::detect(&info, StdoutColorLevel::Ansi256).truecolor);
+    }
+
+    #[test]
+    fn plain_text_terminals_get_no_escape_sequence_features() {
+        for info in [
+            terminal(TerminalName::Dumb, /*term_program*/ None, Some("dumb")),
+            terminal(
+                TerminalName::Unknown,
+                /*term_program*/ None,
+                Some("linux"),
+            ),
+        ] {
+            let capabilities = TerminalCapabilities::detect(&info, StdoutColorLevel::Ansi16);
+
+            assert_eq!(
+                (
+                    capabilities.hyperlinks,
+                    capabilities.osc52_clipboard,
+                    capabilities.sixel
+                ),
+                (false, false, false),
+                "{info:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn apple_terminal_keeps_hyperlinks_but_not_osc52() {
+        let info = terminal(
+            TerminalName::AppleTerminal,
+            Some("Apple_Terminal"),
+            Some("xterm-256color"),
+        );
+        let capabilities = TerminalCapabilities::detect(&info, StdoutColorLevel::Ansi256);
+
+        assert!(capabilities.hyperlinks);
+        assert!(!capabilities.osc52_clipboard);
+    }
+
+    #[test]
+    fn image_protocols_follow_the_terminal() {
+        let mut new_iterm2 = terminal(TerminalName::Iterm2, Some("iTerm.app"), /*term*/ None);
+        new_iterm2.version = Some("3.6.10".to_string());
+        let mut old_iterm2 = new_iterm2.clone();
+        old_iterm2.version = Some("3.5.14".to_string());
+
+        for (info, expected) in [
+            (new_iterm2, (true, false, true)),
+            (old_iterm2, (false, false, true)),
+            (
+                terminal(TerminalName::WezTerm, Some("WezTerm"), /*term*/ None),
+                (true, false, true),
+            ),
+            (
+                terminal(TerminalName::Ghostty, Some("ghostty"), /*term*/ None),
+                (true, false, false),
+            ),
+            (
+                terminal(
+                    TerminalName::WindowsTerminal,
+                    /*term_program*/ None,
+                    Some("xterm-256color"),
+                ),
+                (false, true, false),
+            ),
+            (
+                terminal(
+                    TerminalName::Unknown,
+                    /*term_program*/ None,
+                    Some("foot"),
+                ),
+                (false, true, false),
+            ),
+        ] {
+            let capabilities = TerminalCapabilities::detect(&info, StdoutColorLevel::TrueColor);
+
+            assert_eq!(
+                (
+                    capabilities.kitty_graphics,
+                    capabilities.sixel,
+                    capabilities.iterm2_images
+                ),
+                expected,
+                "{info:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn multiplexers_turn_images_off() {
+        let mut info = terminal(TerminalName::Kitty, Some("kitty"), /*term*/ None);
+        info.multiplexer = Some(Multiplexer::Tmux { version: None });
+        assert!(!TerminalCapabilities::detect(&info, StdoutColorLevel::TrueColor).kitty_graphics);
+
+        let info = terminal(TerminalName::Kitty, Some("kitty"), /*term*/ None);
+        let capabilities = TerminalCapabilities::detect(&info, StdoutColorLevel::TrueColor)
+            .with_environment(|name| name == "STY");
+        assert!(!capabilities.kitty_graphics);
+    }
+
+    #[test]
+    fn kitty_and_wezterm_env_vars_imply_kitty_graphics() {
+        let info = terminal(
+            TerminalName::Unknown,
+            /*term_program*/ None,
+            Some("xterm-256color"),
+        );
+
+        for var in ["KITTY_WINDOW_ID", "WEZTERM_VERSION"] {
+            let capabilities = TerminalCapabilities::detect(&info, StdoutColorLevel::TrueColor)
+                .with_environment(|name| name == var);
+            assert!(capabilities.kitty_graphics, "{var}");
+        }
+    }
+
+    #[test]
+    fn overrides_replace_only_the_keys_they_set() {
+        let info = terminal(TerminalName::Ghostty, Some("ghostty"), /*term*/ None);
+        let detected = TerminalCapabilities::detect(&info, StdoutColorLevel::Ansi256);
+
+        let capabilities = detected.with_overrides(&TerminalCapabilitiesToml {
+            truecolor: Some(true),
+            hyperlinks: Some(false),
+            kitty_keyboard: Some(true),
+            ..TerminalCapabilitiesToml::default()
+        });
+
+        assert_eq!(
+            capabilities,
+            TerminalCapabilities {
+                truecolor: true,
+                hyperlinks: false,
+                kitty_keyboard: Some(true),
+                ..detected
+            }
+        );
+    }
+
+    #[test]
+    fn parse_dotted_version_requires_simple_numeric_components() {
+        assert_eq!(parse_dotted_version(Some("3.6.10")), Some((3, 6, 10)));
+        assert_eq!(parse_dotted_version(Some("3.6")), Some((3, 6, 0)));
+        assert_eq!(parse_dotted_version(Some("3")), Some((3, 0, 0)));
+        assert_eq!(parse_dotted_version(Some("3.6.10.1")), None);
+        assert_eq!(parse_dotted_version(Some("3.6beta")), None);
+        assert_eq!(parse_dotted_version(/*version*/ None), None);
+    }
+}
@@ -2,8 +2,14 @@
 //!
 //! Layout code measures and wraps ordinary ratatui lines. Hyperlink annotations are applied only
 //! when text reaches a terminal buffer or scrollback writer so OSC 8 bytes never affect geometry.
+//!
+//! Web URLs always become OSC 8 links. `path:line` references only do when `tui.file_hyperlinks`
+//! is on; they then open in the editor named by `file_opener`, or as plain `file://` URLs.
 
 use std::ops::Range;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
 
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -15,26 +21,63 @@ use ratatui::text::Text;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
 use ratatui::widgets::Wrap;
+use serde::Deserialize;
+use serde::Serialize;
 use unicode_width::UnicodeWidthChar;
 use unicode_width::UnicodeWidthStr;
 use url::Url;
 
+use crate::file_references::FileReference;
+use crate::inline_images::InlineImage;
+use crate::line_truncation::skip_line_columns;
+use crate::line_truncation::truncate_line_to_width;
+use crate::markdown_render::HiddenCode;
 use crate::render::line_utils::line_to_borrowed;
 use crate::render::line_utils::line_to_static;
+use crate::terminal_capabilities::terminal_capabilities;
 use crate::wrapping::RtOptions;
 use crate::wrapping::adaptive_wrap_line;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// Where clicked `path:line` references go, or `None` to leave them as plain text.
+static FILE_LINK_TARGET: RwLock<Option<FileLinkTarget>> = RwLock::new(None);
+
+/// How a `path:line` reference is handed to the terminal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum FileLinkTarget {
+    /// An editor URI such as `vscode://file/<path>:<line>`, with the editor's URI scheme.
+    Editor(&'static str),
+    /// A `file://` URL.  Terminals open it with the system handler, which ignores the line.
+    File,
+}
+
+/// Applies `tui.file_hyperlinks` and `file_opener` from config.
+pub(crate) fn set_file_link_target(target: Option<FileLinkTarget>) {
+    *FILE_LINK_TARGET
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = target;
+}
+
+fn file_link_target() -> Option<FileLinkTarget> {
+    *FILE_LINK_TARGET
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub(crate) struct TerminalHyperlink {
     pub(crate) columns: Range<usize>,
     pub(crate) destination: String,
     destination_kind: DestinationKind,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 enum DestinationKind {
     Web,
     TrustedFile,
+    /// A `path:line` reference opened in the transcript overlay rather than by the terminal.
+    FileReference {
+        line: usize,
+    },
 }
 
 impl TerminalHyperlink {
@@ -46,6 +89,16 @@ impl TerminalHyperlink {
         }
     }
 
+    pub(crate) fn file(columns: Range<usize>, reference: &FileReference) -> Self {
+        Self {
+            columns,
+            destination: reference.path.display().to_string(),
+            destination_kind: DestinationKind::FileReference {
+                line: reference.line,
+            },
+        }
+    }
+
     pub(crate) fn retarget_to_trusted_file(&mut self, destination: &Url) {
         // Keep file URLs out of the general Markdown link path. Only generated visualization links
         // are promoted to this destination kind.
@@ -54,6 +107,17 @@ impl TerminalHyperlink {
         self.destination_kind = DestinationKind::TrustedFile;
     }
 
+    /// Returns the referenced file and line when this span is a file reference.
+    pub(crate) fn file_reference(&self) -> Option<FileReference> {
+        match self.destination_kind {
+            DestinationKind::FileReference { line } => Some(FileReference {
+                path: self.destination.clone().into(),
+                line,
+            }),
+            DestinationKind::Web | DestinationKind::TrustedFile => None,
+        }
+    }
+
     fn with_columns(&self, columns: Range<usize>) -> Self {
         Self {
             columns,
@@ -63,9 +127,15 @@ impl TerminalHyperlink {
     }
 
     fn terminal_destination(&self) -> Option<String> {
+        if !terminal_capabilities().hyperlinks {
+            return None;
+        }
         match self.destination_kind {
             DestinationKind::Web => web_destination(&self.destination),
             DestinationKind::TrustedFile => trusted_file_destination(&self.destination),
+            // File references only become terminal hyperlinks when `tui.file_hyperlinks` is on.
+            DestinationKind::FileReference { line } => file_link_target()
+                .and_then(|target| file_reference_destination(&self.destination, line, target)),
         }
     }
 }
@@ -74,6 +144,20 @@ impl TerminalHyperlink {
 pub(crate) struct HyperlinkLine {
     pub(crate) line: Line<'static>,
     pub(crate) hyperlinks: Vec<TerminalHyperlink>,
+    /// An image drawn over this row and the rows above it once the line reaches scrollback.
+    pub(crate) image: Option<InlineImage>,
+    /// Set on rows of a table kept at full width by `tui.table_overflow = "scroll"`. They are
+    /// never wrapped: scrollback clips them and the transcript overlay scrolls them sideways.
+    pub(crate) scrollable: bool,
+    /// Markdown heading level (1 to 6) on the first row of a heading. The transcript overlay
+    /// folds the rows of the section it opens.
+    pub(crate) heading_level: Option<u8>,
+    /// Set on the `… N lines hidden …` row of a collapsed code block; the transcript overlay
+    /// draws these lines in its place on request.
+    pub(crate) hidden_code: Option<Arc<HiddenCode>>,
+    /// Unstyled source of the code block this row belongs to, shared by all of the block's rows.
+    /// The transcript overlay copies it to the clipboard.
+    pub(crate) code_block: Option<Arc<str>>,
 }
 
 impl HyperlinkLine {
@@ -81,6 +165,11 @@ impl HyperlinkLine {
         Self {
             line,
             hyperlinks: Vec::new(),
+            image: None,
+            scrollable: false,
+            heading_level: None,
+            hidden_code: None,
+            code_block: None,
         }
     }
 
@@ -161,6 +250,9 @@ pub(crate) fn prefix_hyperlink_lines(
             for hyperlink in &mut line.hyperlinks {
                 hyperlink.columns = hyperlink.columns.start + shift..hyperlink.columns.end + shift;
             }
+            if let Some(image) = &mut line.image {
+                image.column += shift;
+            }
             line
         })
         .collect()
@@ -179,6 +271,17 @@ pub(crate) fn adaptive_wrap_hyperlink_lines(
                 .clone()
                 .initial_indent(options.subsequent_indent.clone())
         };
+        if line.scrollable {
+            let mut indented = line.clone();
+            let shift = options.initial_indent.width();
+            indented.line.spans = options.initial_indent.spans.clone();
+            indented.line.spans.extend(line.line.spans.iter().cloned());
+            for hyperlink in &mut indented.hyperlinks {
+                hyperlink.columns = hyperlink.columns.start + shift..hyperlink.columns.end + shift;
+            }
+            out.push(indented);
+            continue;
+        }
         out.extend(remap_wrapped_line(
             line,
             adaptive_wrap_line(&line.line, options)
@@ -190,6 +293,38 @@ pub(crate) fn adaptive_wrap_hyperlink_lines(
     out
 }
 
+/// Shows the `width` columns of each full-width table row that start `column` cells in, leaving
+/// other lines to wrap as usual.
+pub(crate) fn clip_scrollable_lines(
+    lines: Vec<HyperlinkLine>,
+    column: usize,
+    width: usize,
+) -> Vec<HyperlinkLine> {
+    lines
+        .into_iter()
+        .map(|line| {
+            if line.scrollable {
+                window_line(line, column, width)
+            } else {
+                line
+            }
+        })
+        .collect()
+}
+
+/// Keeps the `width` columns of `line` that start `column` cells in, along with the parts of its
+/// hyperlinks that remain visible.
+pub(crate) fn window_line(mut line: HyperlinkLine, column: usize, width: usize) -> HyperlinkLine {
+    line.line = truncate_line_to_width(skip_line_columns(line.line, column), width);
+    line.hyperlinks.retain_mut(|link| {
+        let start = link.columns.start.max(column);
+        let end = link.columns.end.min(column + width);
+        link.columns = start.saturating_sub(column)..end.saturating_sub(column);
+        start < end
+    });
+    line
+}
+
 pub(crate) fn annotate_web_urls(lines: Vec<Line<'static>>) -> Vec<HyperlinkLine> {
     lines.into_iter().map(annotate_web_urls_in_line).collect()
 }
@@ -215,6 +350,15 @@ pub(crate) fn remap_wrapped_line(
     wrapped: Vec<Line<'static>>,
 ) -> Vec<HyperlinkLine> {
     let mut out = plain_hyperlink_lines(wrapped);
+    if let Some(first) = out.first_mut() {
+        first.heading_level = source.heading_level;
+    }
+    if let Some(last) = out.last_mut() {
+        last.image = source.image.clone();
+    }
+    for line in &mut out {
+        line.code_block = source.code_block.clone();
+    }
     if source.hyperlinks.is_empty() {
         return out;
     }
@@ -371,12 +515,24 @@ fn trusted_file_destination(destination: &str) -> Option<String> {
     (parsed.scheme() == "file" && parsed.to_file_path().is_ok()).then_some(safe_destination)
 }
 
+/// Builds the link for an absolute `path` at one-based `line`.  Relative paths, which could not be
+/// resolved against a cwd, get no link.
+fn file_reference_destination(path: &str, line: usize, target: FileLinkTarget) -> Option<String> {
+    let url = Url::from_file_path(sanitized_destination(path)).ok()?;
+    Some(match target {
+        FileLinkTarget::Editor(scheme) => format!("{scheme}://file{}:{line}", url.path()),
+        FileLinkTarget::File => url.to_string(),
+    })
+}
+
 fn sanitized_destination(destination: &str) -> String {
     destination.chars().filter(|ch| !ch.is_control()).collect()
 }
 
 pub(crate) fn osc8_hyperlink(destination: &str, text: &str) -> String {
-    let Some(safe_destination) = web_destination(destination) else {
+    let Some(safe_destination) =
+        web_destination(destination).filter(|_| terminal_capabilities().hyperlinks)
+    else {
         return text.to_string();
     };
     format!("\x1b]8;;{safe_destination}\x07{text}\x1b]8;;\x07")
@@ -563,7 +719,7 @@ fn mark_matching_cells(
     destination: &str,
     matches: impl Fn(&ratatui::buffer::Cell) -> bool,
 ) {
-    if web_destination(destination).is_none() {
+    if web_destination(destination).is_none() || !terminal_capabilities().hyperlinks {
         return;
     }
     for position in area.positions() {
@@ -626,6 +782,11 @@ mod tests {
                 /*columns*/ 0..destination.width(),
                 destination.to_string(),
             )],
+            image: None,
+            scrollable: false,
+            heading_level: None,
+            hidden_code: None,
+            code_block: None,
         };
 
         assert_eq!(
@@ -689,6 +850,11 @@ mod tests {
                         /*columns*/ 10..14,
                         "https://example.com/first".to_string(),
                     )],
+                    image: None,
+                    scrollable: false,
+                    heading_level: None,
+                    hidden_code: None,
+                    code_block: None,
                 },
                 HyperlinkLine {
                     line: Line::from("    middle there end"),
@@ -696,6 +862,11 @@ mod tests {
                         /*columns*/ 11..16,
                         "https://example.com/second".to_string(),
                     )],
+                    image: None,
+                    scrollable: false,
+                    heading_level: None,
+                    hidden_code: None,
+                    code_block: None,
                 },
             ]
         );
@@ -731,6 +902,34 @@ mod tests {
         assert_eq!(linked_text, destination);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn file_references_link_to_the_configured_opener() {
+        let reference = FileReference {
+            path: "/repo/src/my lib.rs".into(),
+            line: 42,
+        };
+        let link = TerminalHyperlink::file(/*columns*/ 0..4, &reference);
+
+        assert_eq!(link.terminal_destination(), None);
+        assert_eq!(
+            file_reference_destination(
+                &link.destination,
+                reference.line,
+                FileLinkTarget::Editor("vscode")
+            ),
+            Some("vscode://file/repo/src/my%20lib.rs:42".to_string())
+        );
+        assert_eq!(
+            file_reference_destination(&link.destination, reference.line, FileLinkTarget::File),
+            Some("file:///repo/src/my%20lib.rs".to_string())
+        );
+        assert_eq!(
+            file_reference_destination("src/lib.rs", /*line*/ 1, FileLinkTarget::File),
+            None
+        );
+    }
+
     #[test]
     fn trusted_file_destination_receives_osc8_without_enabling_plain_file_links() {
         let temp_dir = tempfile::tempdir().expect("temp directory");
@@ -744,6 +943,11 @@ mod tests {
         let line = HyperlinkLine {
             line: Line::from("view"),
             hyperlinks: vec![link],
+            image: None,
+            scrollable: false,
+            heading_level: None,
+            hidden_code: None,
+            code_block: None,
         };
 
         assert_eq!(
@@ -1,4 +1,5 @@
 use crate::color::perceptual_distance;
+use crate::terminal_capabilities::terminal_capabilities;
 use codex_terminal_detection::TerminalName;
 use codex_terminal_detection::terminal_info;
 use ratatui::style::Color;
@@ -40,7 +41,24 @@ pub fn best_color_for_level(target: (u8, u8, u8), color_level: StdoutColorLevel)
     best_color_for_color_level(target, color_level)
 }
 
+/// The color level widgets should draw with, after `tui.terminal_capabilities.truecolor`.
 pub(crate) fn effective_stdout_color_level() -> StdoutColorLevel {
+    color_level_with_truecolor(
+        detected_stdout_color_level(),
+        terminal_capabilities().truecolor,
+    )
+}
+
+fn color_level_with_truecolor(level: StdoutColorLevel, truecolor: bool) -> StdoutColorLevel {
+    match level {
+        _ if truecolor => StdoutColorLevel::TrueColor,
+        StdoutColorLevel::TrueColor => StdoutColorLevel::Ansi256,
+        level => level,
+    }
+}
+
+/// The color level reported for stdout, corrected for terminals that under-report truecolor.
+pub(crate) fn detected_stdout_color_level() -> StdoutColorLevel {
     stdout_color_level_for_terminal(
         stdout_color_level(),
         terminal_info().name,
@@ -72,17 +90,43 @@ fn stdout_color_level_for_terminal(
 fn best_color_for_color_level(target: (u8, u8, u8), color_level: StdoutColorLevel) -> Color {
     match color_level {
         StdoutColorLevel::TrueColor => rgb_color(target),
-        StdoutColorLevel::Ansi256 => xterm_fixed_colors()
-            .min_by(|(_, a), (_, b)| {
-                perceptual_distance(*a, target)
-                    .partial_cmp(&perceptual_distance(*b, target))
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            })
-            .map_or_else(Color::default, |(i, _)| indexed_color(i as u8)),
+        StdoutColorLevel::Ansi256 => nearest_color(xterm_fixed_colors(), target)
+            .map_or_else(Color::default, |i| indexed_color(i as u8)),
         StdoutColorLevel::Ansi16 | StdoutColorLevel::Unknown => Color::default(),
     }
 }
 
+/// Returns the closest foreground color to `target` for a known terminal color level.
+///
+/// Unlike [`best_color_for_level`], ANSI-16 terminals get the nearest named color rather than the
+/// default, and an unknown level keeps the RGB value.  Meant for text such as syntax tokens, where
+/// an approximate hue reads better than none.
+pub(crate) fn foreground_color_for_level(
+    target: (u8, u8, u8),
+    color_level: StdoutColorLevel,
+) -> Color {
+    match color_level {
+        StdoutColorLevel::TrueColor | StdoutColorLevel::Unknown => rgb_color(target),
+        StdoutColorLevel::Ansi256 => best_color_for_color_level(target, color_level),
+        StdoutColorLevel::Ansi16 => {
+            nearest_color(ANSI16_COLORS.into_iter(), target).unwrap_or_default()
+        }
+    }
+}
+
+fn nearest_color<T>(
+    candidates: impl Iterator<Item = (T, (u8, u8, u8))>,
+    target: (u8, u8, u8),
+) -> Option<T> {
+    candidates
+        .min_by(|(_, a), (_, b)| {
+            perceptual_distance(*a, target)
+                .partial_cmp(&perceptual_distance(*b, target))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|(candidate, _)| candidate)
+}
+
 pub fn requery_default_colors() {
     imp::requery_default_colors();
 }
@@ -297,6 +341,26 @@ mod imp {
     pub(super) fn requery_default_colors() {}
 }
 
+/// The named ANSI colors, with the RGB values xterm shows for them by default.
+const ANSI16_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, XTERM_COLORS[0]),
+    (Color::Red, XTERM_COLORS[1]),
+    (Color::Green, XTERM_COLORS[2]),
+    (Color::Yellow, XTERM_COLORS[3]),
+    (Color::Blue, XTERM_COLORS[4]),
+    (Color::Magenta, XTERM_COLORS[5]),
+    (Color::Cyan, XTERM_COLORS[6]),
+    (Color::Gray, XTERM_COLORS[7]),
+    (Color::DarkGray, XTERM_COLORS[8]),
+    (Color::LightRed, XTERM_COLORS[9]),
+    (Color::LightGreen, XTERM_COLORS[10]),
+    (Color::LightYellow, XTERM_COLORS[11]),
+    (Color::LightBlue, XTERM_COLORS[12]),
+    (Color::LightMagenta, XTERM_COLORS[13]),
+    (Color::LightCyan, XTERM_COLORS[14]),
+    (Color::White, XTERM_COLORS[15]),
+];
+
 /// The subset of Xterm colors that are usually consistent across terminals.
 fn xterm_fixed_colors() -> impl Iterator<Item = (usize, (u8, u8, u8))> {
     XTERM_COLORS.into_iter().enumerate().skip(16)
@@ -586,6 +650,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn foreground_colors_fall_back_to_the_terminal_palette() {
+        let orange = (255, 135, 0);
+        assert_eq!(
+            foreground_color_for_level(orange, StdoutColorLevel::TrueColor),
+            rgb_color(orange)
+        );
+        assert_eq!(
+            foreground_color_for_level(orange, StdoutColorLevel::Unknown),
+            rgb_color(orange)
+        );
+        assert_eq!(
+            foreground_color_for_level(orange, StdoutColorLevel::Ansi256),
+            indexed_color(208)
+        );
+        assert_eq!(
+            foreground_color_for_level((250, 20, 20), StdoutColorLevel::Ansi16),
+            Color::LightRed
+        );
+        assert_eq!(
+            foreground_color_for_level((20, 120, 20), StdoutColorLevel::Ansi16),
+            Color::Green
+        );
+    }
+
+    #[test]
+    fn truecolor_capability_overrides_the_detected_level() {
+        assert_eq!(
+            color_level_with_truecolor(StdoutColorLevel::Ansi16, /*truecolor*/ true),
+            StdoutColorLevel::TrueColor
+        );
+        assert_eq!(
+            color_level_with_truecolor(StdoutColorLevel::TrueColor, /*truecolor*/ false),
+            StdoutColorLevel::Ansi256
+        );
+        assert_eq!(
+            color_level_with_truecolor(StdoutColorLevel::Ansi16, /*truecolor*/ false),
+            StdoutColorLevel::Ansi16
+        );
+    }
+
     #[test]
     fn windows_terminal_wt_session_promotes_to_truecolor() {
         assert_eq!(
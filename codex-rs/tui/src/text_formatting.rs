@@ -1,7 +1,8 @@
 use unicode_segmentation::UnicodeSegmentation;
-use unicode_width::UnicodeWidthChar;
 use unicode_width::UnicodeWidthStr;
 
+use crate::display_width::suffix_by_width;
+
 pub(crate) fn capitalize_first(input: &str) -> String {
     let mut chars = input.chars();
     match chars.next() {
@@ -181,22 +182,9 @@ pub(crate) fn center_truncate_path(path: &str, max_width: usize) -> String {
             return "…".to_string();
         }
 
-        let mut kept: Vec<char> = Vec::new();
-        let mut used_width = 1; // reserve space for leading ellipsis
-        for ch in original.chars().rev() {
-            let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if used_width + ch_width > allowed_width {
-                break;
-            }
-            used_width += ch_width;
-            kept.push(ch);
-        }
-        kept.reverse();
-        let mut truncated = String::from("…");
-        for ch in kept {
-            truncated.push(ch);
-        }
-        truncated
+        // Reserve one column for the leading ellipsis.
+        let (start, _) = suffix_by_width(original, allowed_width - 1);
+        format!("…{}", &original[start..])
     };
 
     let mut combos: Vec<(usize, usize)> = Vec::new();
@@ -0,0 +1,112 @@
+//! tmux helpers for the TUI.
+//!
+//! With `tui.tmux_split_views = true` and the TUI running inside tmux, `/diff` and the `/ps`
+//! background-terminal list open in a new tmux split next to Codex instead of a pager overlay or
+//! the transcript. The text is written to a temporary file and paged with `less -R` in the new
+//! pane, which removes the file when the pager exits. The new pane is titled so it is easy to find
+//! in `choose-tree` and the status bar.
+
+use std::ffi::OsString;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+static SPLIT_VIEWS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Applies `tui.tmux_split_views` from config.
+pub(crate) fn set_split_views_enabled(enabled: bool) {
+    SPLIT_VIEWS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether full-screen views should open in a tmux split instead of an overlay.
+pub(crate) fn split_views_enabled() -> bool {
+    SPLIT_VIEWS_ENABLED.load(Ordering::Relaxed) && is_inside_tmux()
+}
+
+fn is_inside_tmux() -> bool {
+    std::env::var_os("TMUX").is_some_and(|value| !value.is_empty())
+}
+
+/// Opens `text` in a pager in a new horizontal tmux split rooted at `cwd`, titled `title`.
+pub(crate) fn open_in_split(title: &str, text: &str, cwd: &Path) -> io::Result<()> {
+    let file = tempfile::Builder::new()
+        .prefix("codex-view-")
+        .suffix(".txt")
+        .tempfile()?;
+    std::fs::write(file.path(), text)?;
+    let (_, path) = file.keep().map_err(|err| err.error)?;
+
+    let output = Command::new("tmux")
+        .args(split_window_args(cwd, &path))
+        .output()?;
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&path);
+        return Err(io::Error::other(format!(
+            "tmux split-window failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let pane_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !pane_id.is_empty() {
+        let _ = Command::new("tmux")
+            .args(["select-pane", "-t", &pane_id, "-T", title])
+            .output();
+    }
+    Ok(())
+}
+
+/// Arguments for `tmux split-window` that page `path` and delete it once the pager exits. The new
+/// pane's id is printed so it can be titled.
+fn split_window_args(cwd: &Path, path: &Path) -> Vec<OsString> {
+    vec![
+        "split-window".into(),
+        "-h".into(),
+        "-P".into(),
+        "-F".into(),
+        "#{pane_id}".into(),
+        "-c".into(),
+        cwd.as_os_str().to_owned(),
+        "--".into(),
+        "sh".into(),
+        "-c".into(),
+        "less -R \"$1\"; rm -f \"$1\"".into(),
+        "sh".into(),
+        path.as_os_str().to_owned(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn split_window_args_page_the_file_in_cwd() {
+        let args = split_window_args(Path::new("/repo"), Path::new("/tmp/codex-view-1.txt"));
+        let args: Vec<String> = args
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            args,
+            vec![
+                "split-window",
+                "-h",
+                "-P",
+                "-F",
+                "#{pane_id}",
+                "-c",
+                "/repo",
+                "--",
+                "sh",
+                "-c",
+                "less -R \"$1\"; rm -f \"$1\"",
+                "sh",
+                "/tmp/codex-view-1.txt",
+            ]
+        );
+    }
+}
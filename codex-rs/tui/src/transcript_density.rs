@@ -0,0 +1,45 @@
+//! How much of each tool call the transcript shows (`tui.transcript_density`).
+//!
+//! Compact keeps one line per call, normal shows the first and last few lines of output, and
+//! verbose shows all of it. Tool cells read the density when they render rather than when they are
+//! created, so switching it and reflowing the transcript re-renders earlier calls from their stored
+//! output.
+
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+
+use codex_config::types::TranscriptDensity;
+
+static DENSITY: AtomicU8 = AtomicU8::new(TranscriptDensity::Normal as u8);
+
+pub(crate) fn transcript_density() -> TranscriptDensity {
+    match DENSITY.load(Ordering::Relaxed) {
+        value if value == TranscriptDensity::Compact as u8 => TranscriptDensity::Compact,
+        value if value == TranscriptDensity::Verbose as u8 => TranscriptDensity::Verbose,
+        _ => TranscriptDensity::Normal,
+    }
+}
+
+/// Applies `tui.transcript_density` from config.
+pub(crate) fn set_transcript_density(density: TranscriptDensity) {
+    DENSITY.store(density as u8, Ordering::Relaxed);
+}
+
+/// Switches to the next density and returns it.
+pub(crate) fn cycle_transcript_density() -> TranscriptDensity {
+    let density = next_density(transcript_density());
+    set_transcript_density(density);
+    density
+}
+
+fn next_density(density: TranscriptDensity) -> TranscriptDensity {
+    match density {
+        TranscriptDensity::Compact => TranscriptDensity::Normal,
+        TranscriptDensity::Normal => TranscriptDensity::Verbose,
+        TranscriptDensity::Verbose => TranscriptDensity::Compact,
+    }
+}
+
+#[cfg(test)]
+#[path = "transcript_density_tests.rs"]
+mod tests;
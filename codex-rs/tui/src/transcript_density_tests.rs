@@ -0,0 +1,20 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn densities_cycle_from_compact_through_verbose() {
+    let mut seen = vec![TranscriptDensity::Compact];
+    for _ in 0..3 {
+        seen.push(next_density(*seen.last().expect("seeded")));
+    }
+
+    assert_eq!(
+        seen,
+        vec![
+            TranscriptDensity::Compact,
+            TranscriptDensity::Normal,
+            TranscriptDensity::Verbose,
+            TranscriptDensity::Compact,
+        ]
+    );
+}
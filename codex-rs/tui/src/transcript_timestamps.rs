@@ -0,0 +1,122 @@
+//! Time labels for the transcript (`tui.transcript_timestamps`).
+//!
+//! `App` records when each committed history cell was added, and the transcript overlay (`Ctrl+T`)
+//! can show that time in a dimmed gutter next to each cell, either as the elapsed time (`5m`) or
+//! as the local wall-clock time (`14:05`). Labels are computed when the overlay draws, so relative
+//! times stay current. Cells replayed from a resumed session carry the start time of their turn.
+//!
+//! Independently of the gutter, `ChatWidget` inserts a "2 hours later" separator wherever the
+//! conversation was idle for longer than [`GAP_THRESHOLD`], so resumed sessions read coherently.
+
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use chrono::DateTime;
+use chrono::Local;
+use codex_config::types::TranscriptTimestamps;
+
+static MODE: AtomicU8 = AtomicU8::new(TranscriptTimestamps::Off as u8);
+
+/// Columns taken by the gutter, including the space before the cell.
+pub(crate) const GUTTER_WIDTH: u16 = 7;
+
+/// Idle time between turns after which the transcript shows a gap separator.
+pub(crate) const GAP_THRESHOLD: Duration = Duration::from_secs(30 * 60);
+
+pub(crate) fn transcript_timestamps() -> TranscriptTimestamps {
+    match MODE.load(Ordering::Relaxed) {
+        value if value == TranscriptTimestamps::Relative as u8 => TranscriptTimestamps::Relative,
+        value if value == TranscriptTimestamps::Absolute as u8 => TranscriptTimestamps::Absolute,
+        _ => TranscriptTimestamps::Off,
+    }
+}
+
+/// Applies `tui.transcript_timestamps` from config.
+pub(crate) fn set_transcript_timestamps(mode: TranscriptTimestamps) {
+    MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+/// Switches to the next mode and returns it.
+pub(crate) fn cycle_transcript_timestamps() -> TranscriptTimestamps {
+    let mode = next_mode(transcript_timestamps());
+    set_transcript_timestamps(mode);
+    mode
+}
+
+fn next_mode(mode: TranscriptTimestamps) -> TranscriptTimestamps {
+    match mode {
+        TranscriptTimestamps::Off => TranscriptTimestamps::Relative,
+        TranscriptTimestamps::Relative => TranscriptTimestamps::Absolute,
+        TranscriptTimestamps::Absolute => TranscriptTimestamps::Off,
+    }
+}
+
+/// Gutter text for a cell added at `at`, or `None` when the gutter is off.
+///
+/// Labels are at most six columns wide so they fit in [`GUTTER_WIDTH`].
+pub(crate) fn gutter_label(
+    mode: TranscriptTimestamps,
+    at: SystemTime,
+    now: SystemTime,
+) -> Option<String> {
+    match mode {
+        TranscriptTimestamps::Off => None,
+        TranscriptTimestamps::Relative => {
+            Some(relative_label(now.duration_since(at).unwrap_or_default()))
+        }
+        TranscriptTimestamps::Absolute => {
+            let at = DateTime::<Local>::from(at);
+            let format = if at.date_naive() == DateTime::<Local>::from(now).date_naive() {
+                "%H:%M"
+            } else {
+                "%b %d"
+            };
+            Some(at.format(format).to_string())
+        }
+    }
+}
+
+fn relative_label(elapsed: Duration) -> String {
+    let minutes = elapsed.as_secs() / 60;
+    let hours = minutes / 60;
+    let days = hours / 24;
+    if minutes == 0 {
+        "now".to_string()
+    } else if hours == 0 {
+        format!("{minutes}m")
+    } else if days == 0 {
+        format!("{hours}h")
+    } else if days < 14 {
+        format!("{days}d")
+    } else {
+        format!("{}w", days / 7)
+    }
+}
+
+/// Text of the separator shown after the conversation was idle for `gap`, e.g. "2 hours later".
+pub(crate) fn gap_label(gap: Duration) -> String {
+    let minutes = gap.as_secs() / 60;
+    let hours = minutes / 60;
+    let days = hours / 24;
+    let (count, unit) = if hours == 0 {
+        (minutes, "minute")
+    } else if days < 2 {
+        (hours, "hour")
+    } else {
+        (days, "day")
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{count} {unit}{plural} later")
+}
+
+/// Converts a protocol Unix timestamp in seconds.
+pub(crate) fn system_time_from_unix_seconds(seconds: i64) -> Option<SystemTime> {
+    let seconds = u64::try_from(seconds).ok()?;
+    SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+#[path = "transcript_timestamps_tests.rs"]
+mod tests;
@@ -0,0 +1,72 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn modes_cycle_from_off_through_absolute() {
+    let mut seen = vec![TranscriptTimestamps::Off];
+    for _ in 0..3 {
+        seen.push(next_mode(*seen.last().expect("seeded")));
+    }
+
+    assert_eq!(
+        seen,
+        vec![
+            TranscriptTimestamps::Off,
+            TranscriptTimestamps::Relative,
+            TranscriptTimestamps::Absolute,
+            TranscriptTimestamps::Off,
+        ]
+    );
+}
+
+#[test]
+fn relative_labels_use_the_largest_whole_unit() {
+    let now = SystemTime::UNIX_EPOCH + Duration::from_secs(100 * 24 * 60 * 60);
+    let label = |seconds: u64| {
+        gutter_label(
+            TranscriptTimestamps::Relative,
+            now - Duration::from_secs(seconds),
+            now,
+        )
+    };
+
+    assert_eq!(label(30), Some("now".to_string()));
+    assert_eq!(label(5 * 60), Some("5m".to_string()));
+    assert_eq!(label(2 * 60 * 60 + 59 * 60), Some("2h".to_string()));
+    assert_eq!(label(3 * 24 * 60 * 60), Some("3d".to_string()));
+    assert_eq!(label(30 * 24 * 60 * 60), Some("4w".to_string()));
+    // A clock that moved backwards reads as "now" rather than failing.
+    assert_eq!(
+        gutter_label(
+            TranscriptTimestamps::Relative,
+            now + Duration::from_secs(60),
+            now
+        ),
+        Some("now".to_string())
+    );
+    assert_eq!(gutter_label(TranscriptTimestamps::Off, now, now), None);
+}
+
+#[test]
+fn absolute_labels_show_the_time_for_today() {
+    let now = SystemTime::now();
+
+    assert_eq!(
+        gutter_label(TranscriptTimestamps::Absolute, now, now),
+        Some(DateTime::<Local>::from(now).format("%H:%M").to_string())
+    );
+}
+
+#[test]
+fn gap_labels_are_pluralized() {
+    assert_eq!(gap_label(Duration::from_secs(45 * 60)), "45 minutes later");
+    assert_eq!(gap_label(Duration::from_secs(60 * 60)), "1 hour later");
+    assert_eq!(
+        gap_label(Duration::from_secs(30 * 60 * 60)),
+        "30 hours later"
+    );
+    assert_eq!(
+        gap_label(Duration::from_secs(3 * 24 * 60 * 60)),
+        "3 days later"
+    );
+}
@@ -470,9 +470,10 @@ pub(crate) fn init() -> Result<InitializedTerminal> {
     };
 
     #[cfg(unix)]
-    let enhanced_keys_supported = startup_probe
-        .keyboard_enhancement_supported
-        .unwrap_or(/*default*/ false);
+    let enhanced_keys_supported = keyboard_modes::keyboard_enhancement_forced()
+        || startup_probe
+            .keyboard_enhancement_supported
+            .unwrap_or(/*default*/ false);
 
     #[cfg(not(unix))]
     let mut backend = CrosstermBackend::new(stdout());
@@ -481,8 +482,9 @@ pub(crate) fn init() -> Result<InitializedTerminal> {
     let cursor_pos = cursor_position_with_crossterm(&mut backend);
 
     #[cfg(not(unix))]
-    let enhanced_keys_supported =
-        !keyboard_modes::keyboard_enhancement_disabled() && detect_keyboard_enhancement_supported();
+    let enhanced_keys_supported = keyboard_modes::keyboard_enhancement_forced()
+        || (!keyboard_modes::keyboard_enhancement_disabled()
+            && detect_keyboard_enhancement_supported());
 
     #[cfg(windows)]
     probe_windows_default_colors();
@@ -13,23 +13,41 @@ use crossterm::event::PopKeyboardEnhancementFlags;
 use crossterm::event::PushKeyboardEnhancementFlags;
 use ratatui::crossterm::execute;
 
+use crate::terminal_capabilities::terminal_capabilities;
+
 const DISABLE_KEYBOARD_ENHANCEMENT_ENV_VAR: &str = "CODEX_TUI_DISABLE_KEYBOARD_ENHANCEMENT";
 
 pub(super) fn keyboard_enhancement_disabled() -> bool {
     let disable_env = std::env::var(DISABLE_KEYBOARD_ENHANCEMENT_ENV_VAR).ok();
+    let kitty_keyboard = terminal_capabilities().kitty_keyboard;
     let is_wsl = running_in_wsl();
     let is_vscode_terminal = is_wsl && running_in_vscode_terminal();
-    keyboard_enhancement_disabled_for(disable_env.as_deref(), is_wsl, is_vscode_terminal)
+    keyboard_enhancement_disabled_for(
+        disable_env.as_deref(),
+        kitty_keyboard,
+        is_wsl,
+        is_vscode_terminal,
+    )
+}
+
+/// Whether `tui.terminal_capabilities.kitty_keyboard` forces enhanced keys on, so the startup
+/// probe's answer is not needed.
+pub(super) fn keyboard_enhancement_forced() -> bool {
+    terminal_capabilities().kitty_keyboard == Some(true) && !keyboard_enhancement_disabled()
 }
 
 fn keyboard_enhancement_disabled_for(
     disable_env: Option<&str>,
+    kitty_keyboard: Option<bool>,
     is_wsl: bool,
     is_vscode_terminal: bool,
 ) -> bool {
     if let Some(disabled) = parse_bool_env(disable_env) {
         return disabled;
     }
+    if let Some(enabled) = kitty_keyboard {
+        return !enabled;
+    }
 
     // VS Code running a WSL shell can hide TERM_PROGRAM from the Linux process
     // environment, so `running_in_vscode_terminal` also probes the Windows-side
@@ -311,17 +329,20 @@ mod tests {
     #[test]
     fn keyboard_enhancement_auto_disables_for_vscode_in_wsl() {
         assert!(keyboard_enhancement_disabled_for(
-            /*disable_env*/ None, /*is_wsl*/ true, /*is_vscode_terminal*/ true
+            /*disable_env*/ None, /*kitty_keyboard*/ None, /*is_wsl*/ true,
+            /*is_vscode_terminal*/ true
         ));
     }
 
     #[test]
     fn keyboard_enhancement_auto_disable_requires_wsl_and_vscode() {
         assert!(!keyboard_enhancement_disabled_for(
-            /*disable_env*/ None, /*is_wsl*/ true, /*is_vscode_terminal*/ false
+            /*disable_env*/ None, /*kitty_keyboard*/ None, /*is_wsl*/ true,
+            /*is_vscode_terminal*/ false
         ));
         assert!(!keyboard_enhancement_disabled_for(
-            /*disable_env*/ None, /*is_wsl*/ false, /*is_vscode_terminal*/ true
+            /*disable_env*/ None, /*kitty_keyboard*/ None, /*is_wsl*/ false,
+            /*is_vscode_terminal*/ true
         ));
     }
 
@@ -329,11 +350,29 @@ mod tests {
     fn keyboard_enhancement_env_flag_overrides_auto_detection() {
         assert!(!keyboard_enhancement_disabled_for(
             Some("0"),
+            /*kitty_keyboard*/ None,
             /*is_wsl*/ true,
             /*is_vscode_terminal*/ true
         ));
         assert!(keyboard_enhancement_disabled_for(
             Some("1"),
+            /*kitty_keyboard*/ Some(true),
+            /*is_wsl*/ false,
+            /*is_vscode_terminal*/ false
+        ));
+    }
+
+    #[test]
+    fn kitty_keyboard_capability_overrides_auto_detection() {
+        assert!(!keyboard_enhancement_disabled_for(
+            /*disable_env*/ None,
+            /*kitty_keyboard*/ Some(true),
+            /*is_wsl*/ true,
+            /*is_vscode_terminal*/ true
+        ));
+        assert!(keyboard_enhancement_disabled_for(
+            /*disable_env*/ None,
+            /*kitty_keyboard*/ Some(false),
             /*is_wsl*/ false,
             /*is_vscode_terminal*/ false
         ));
@@ -0,0 +1,108 @@
+//! Workflow templates for `codex new --template <name>` and the `/template` command.
+//!
+//! A template is markdown with a `{{ task }}` placeholder. It lays out the approach, the context
+//! to gather before editing, and a completion checklist. The rendered template becomes the goal
+//! for the new session, so goal continuation keeps the turn loop running until the model can show
+//! every checklist item is done. `/template <name>` instead inserts the raw template into the
+//! composer so its `{{ ... }}` placeholders can be filled in by hand.
+//!
+//! Built-in templates cover bug fixes, refactors, and reviews. Users add or replace templates by
+//! writing `$CODEX_HOME/workflows/<name>.md`.
+
+use std::io;
+use std::path::Path;
+
+const WORKFLOWS_DIR: &str = "workflows";
+const TASK_PLACEHOLDER: &str = "{{ task }}";
+
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    ("bugfix", include_str!("../templates/workflows/bugfix.md")),
+    (
+        "refactor",
+        include_str!("../templates/workflows/refactor.md"),
+    ),
+    ("review", include_str!("../templates/workflows/review.md")),
+];
+
+/// Renders the named template with `task` as the goal objective for a new session.
+pub(crate) fn render_workflow_goal(
+    codex_home: &Path,
+    name: &str,
+    task: &str,
+) -> io::Result<String> {
+    let task = task.trim();
+    if task.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Workflow template `{name}` needs a prompt describing the task."),
+        ));
+    }
+    let template = load_workflow_template(codex_home, name)?;
+
+    let rendered = if template.contains(TASK_PLACEHOLDER) {
+        template.replace(TASK_PLACEHOLDER, task)
+    } else {
+        // User templates may omit the placeholder; keep the task rather than dropping it.
+        format!("{task}\n\n{template}")
+    };
+    Ok(rendered.trim_end().to_string())
+}
+
+/// Loads the raw markdown of the named template, placeholders included.
+///
+/// A template in `$CODEX_HOME/workflows` takes precedence over a built-in one of the same name.
+pub(crate) fn load_workflow_template(codex_home: &Path, name: &str) -> io::Result<String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid workflow template name `{name}`."),
+        ));
+    }
+
+    let user_template = codex_home.join(WORKFLOWS_DIR).join(format!("{name}.md"));
+    match std::fs::read_to_string(&user_template) {
+        Ok(template) => Ok(template),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => BUILTIN_TEMPLATES
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(_, template)| (*template).to_string())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!(
+                        "Unknown workflow template `{name}`. Available: {}.",
+                        available_templates(codex_home).join(", ")
+                    ),
+                )
+            }),
+        Err(err) => Err(err),
+    }
+}
+
+/// Built-in template names plus any user templates, sorted and deduplicated.
+pub(crate) fn available_templates(codex_home: &Path) -> Vec<String> {
+    let mut names = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect::<Vec<_>>();
+    if let Ok(entries) = std::fs::read_dir(codex_home.join(WORKFLOWS_DIR)) {
+        names.extend(entries.flatten().filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "md" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        }));
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+#[path = "workflow_templates_tests.rs"]
+mod tests;
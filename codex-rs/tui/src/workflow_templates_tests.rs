@@ -0,0 +1,74 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+#[test]
+fn renders_builtin_template_with_task() {
+    let codex_home = TempDir::new().expect("tempdir");
+
+    let goal = render_workflow_goal(codex_home.path(), "bugfix", "  login fails on Safari\n")
+        .expect("render bugfix");
+
+    assert!(goal.starts_with("Fix this bug:\n\nlogin fails on Safari\n\nApproach:"));
+    assert!(goal.ends_with("how the fix addresses it."));
+    assert!(!goal.contains(TASK_PLACEHOLDER));
+}
+
+#[test]
+fn user_template_overrides_builtin() {
+    let codex_home = TempDir::new().expect("tempdir");
+    let workflows = codex_home.path().join(WORKFLOWS_DIR);
+    std::fs::create_dir_all(&workflows).expect("create workflows dir");
+    std::fs::write(
+        workflows.join("review.md"),
+        "Review {{ task }} for security issues.\n",
+    )
+    .expect("write review template");
+    std::fs::write(
+        workflows.join("migration.md"),
+        "Checklist:\n- [ ] Rollback tested\n",
+    )
+    .expect("write migration template");
+
+    assert_eq!(
+        render_workflow_goal(codex_home.path(), "review", "PR 12").expect("render review"),
+        "Review PR 12 for security issues."
+    );
+    assert_eq!(
+        render_workflow_goal(codex_home.path(), "migration", "move to v2").expect("render"),
+        "move to v2\n\nChecklist:\n- [ ] Rollback tested"
+    );
+}
+
+#[test]
+fn unknown_template_lists_available_names() {
+    let codex_home = TempDir::new().expect("tempdir");
+    let workflows = codex_home.path().join(WORKFLOWS_DIR);
+    std::fs::create_dir_all(&workflows).expect("create workflows dir");
+    std::fs::write(workflows.join("release.md"), "{{ task }}").expect("write release template");
+
+    let err = render_workflow_goal(codex_home.path(), "deploy", "ship it").expect_err("unknown");
+
+    assert_eq!(
+        err.to_string(),
+        "Unknown workflow template `deploy`. Available: bugfix, refactor, release, review."
+    );
+}
+
+#[test]
+fn rejects_missing_task_and_path_like_names() {
+    let codex_home = TempDir::new().expect("tempdir");
+
+    assert_eq!(
+        render_workflow_goal(codex_home.path(), "bugfix", " ")
+            .expect_err("empty task")
+            .to_string(),
+        "Workflow template `bugfix` needs a prompt describing the task."
+    );
+    assert_eq!(
+        render_workflow_goal(codex_home.path(), "../secrets", "task")
+            .expect_err("path-like name")
+            .to_string(),
+        "Invalid workflow template name `../secrets`."
+    );
+}
@@ -1065,6 +1065,7 @@ mod tests {
     use super::*;
     use itertools::Itertools as _;
     use pretty_assertions::assert_eq;
+    use proptest::prelude::*;
     use ratatui::style::Color;
     use ratatui::style::Stylize;
     use std::string::ToString;
@@ -1640,4 +1641,54 @@ them."#
         assert_eq!(rebuilt, text);
         assert!(ranges.len() > 1, "expected wrapped ranges, got: {ranges:?}");
     }
+
+    fn wrap_words() -> impl Strategy<Value = String> {
+        prop::collection::vec("[a-z0-9]{1,12}|héllo|界面", 1..40).prop_map(|words| words.join(" "))
+    }
+
+    fn without_whitespace(text: &str) -> String {
+        text.chars().filter(|ch| !ch.is_whitespace()).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn wrapped_lines_fit_width_and_keep_text(text in wrap_words(), width in 2usize..60) {
+            let line = Line::from(text.clone());
+            let wrapped = word_wrap_line(&line, width);
+
+            for wrapped_line in &wrapped {
+                prop_assert!(
+                    wrapped_line.width() <= width,
+                    "{:?} is wider than {}",
+                    concat_line(wrapped_line),
+                    width
+                );
+            }
+            let rebuilt = wrapped.iter().map(concat_line).collect::<String>();
+            prop_assert_eq!(without_whitespace(&rebuilt), without_whitespace(&text));
+        }
+
+        #[test]
+        fn indent_prefixes_are_stable_across_wraps(text in wrap_words(), width in 6usize..60) {
+            let opts = RtOptions::new(width)
+                .initial_indent(Line::from("- "))
+                .subsequent_indent(Line::from("  "));
+            let line = Line::from(text);
+            let wrapped = word_wrap_line(&line, opts);
+
+            for (index, wrapped_line) in wrapped.iter().enumerate() {
+                let text = concat_line(wrapped_line);
+                let prefix = if index == 0 { "- " } else { "  " };
+                let rest = text.strip_prefix(prefix);
+                prop_assert!(rest.is_some(), "line {} lost its indent: {:?}", index, text);
+                prop_assert!(
+                    !rest.is_some_and(|rest| rest.starts_with(' ')),
+                    "line {} starts with extra whitespace: {:?}",
+                    index,
+                    text
+                );
+                prop_assert!(wrapped_line.width() <= width);
+            }
+        }
+    }
 }
@@ -0,0 +1,308 @@
+//! Times a benchmark command against a baseline revision of the repository and against the
+//! working tree, so performance work is judged on repeated runs instead of a single timing.
+//!
+//! The baseline is checked out into a temporary git worktree, so the user's checkout is never
+//! touched. After the warmup runs, runs alternate between the two trees, which spreads thermal and
+//! cache drift evenly across both sides.
+
+mod stats;
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_utils_pty::process_group::KillProcessGroupOnDrop;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::process::Command;
+
+pub use stats::SeriesStats;
+pub use stats::Verdict;
+
+/// Lines of a failing run's stderr included in the error.
+const STDERR_TAIL_LINES: usize = 10;
+
+/// What to time and how often.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchPlan {
+    /// Shell command to time. It runs in the same directory, relative to the repository root, in
+    /// both trees.
+    pub command: String,
+    /// Timed runs on each side. At least two, so the spread can be estimated.
+    pub runs: usize,
+    /// Untimed runs on each side before timing starts, to warm caches and finish builds.
+    pub warmup: usize,
+    /// Each run is abandoned after this long.
+    pub timeout: Duration,
+}
+
+/// Timings of the baseline and the working tree and whether they differ.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BenchComparison {
+    pub command: String,
+    /// Revision the baseline was checked out at.
+    pub baseline_rev: String,
+    pub baseline: SeriesStats,
+    /// The working tree, including uncommitted changes.
+    pub candidate: SeriesStats,
+    /// Change of the mean time relative to the baseline, in percent. Negative is faster.
+    pub change_pct: f64,
+    /// Half-width of the 95% confidence interval of `change_pct`.
+    pub change_margin_pct: f64,
+    /// Welch's t statistic of the difference of the means.
+    pub t_statistic: f64,
+    pub verdict: Verdict,
+}
+
+impl BenchComparison {
+    pub fn new(
+        command: String,
+        baseline_rev: String,
+        baseline: SeriesStats,
+        candidate: SeriesStats,
+    ) -> Self {
+        let test = stats::welch_test(&baseline, &candidate);
+        let percent_of_baseline = |millis: f64| {
+            if baseline.mean_ms > 0.0 {
+                millis / baseline.mean_ms * 100.0
+            } else {
+                0.0
+            }
+        };
+        Self {
+            change_pct: percent_of_baseline(candidate.mean_ms - baseline.mean_ms),
+            change_margin_pct: percent_of_baseline(test.margin_ms),
+            t_statistic: test.t_statistic,
+            verdict: test.verdict,
+            command,
+            baseline_rev,
+            baseline,
+            candidate,
+        }
+    }
+
+    /// A plain-text table of both series and the verdict.
+    pub fn render(&self) -> String {
+        let row = |label: &str, values: [String; 5]| {
+            let [mean, median, stddev, min, max] = values;
+            format!("{label:<14}{mean:>12}{median:>12}{stddev:>12}{min:>12}{max:>12}\n")
+        };
+        let stats_row = |label: &str, stats: &SeriesStats| {
+            row(
+                label,
+                [
+                    stats.mean_ms,
+                    stats.median_ms,
+                    stats.stddev_ms,
+                    stats.min_ms,
+                    stats.max_ms,
+                ]
+                .map(format_millis),
+            )
+        };
+        let verdict = match self.verdict {
+            Verdict::Faster => "faster",
+            Verdict::Slower => "slower",
+            Verdict::NoSignificantChange => "no significant change",
+        };
+
+        let mut out = format!("Benchmark: {}\n", self.command);
+        out.push_str(&format!(
+            "{} vs working tree, {} runs each\n\n",
+            self.baseline_rev, self.candidate.runs
+        ));
+        out.push_str(&row(
+            "",
+            ["mean", "median", "stddev", "min", "max"].map(str::to_string),
+        ));
+        out.push_str(&stats_row("baseline", &self.baseline));
+        out.push_str(&stats_row("working tree", &self.candidate));
+        out.push_str(&format!(
+            "\nChange: {:+.1}% ± {:.1}% ({verdict} at 95% confidence)\n",
+            self.change_pct, self.change_margin_pct
+        ));
+        out
+    }
+}
+
+fn format_millis(millis: f64) -> String {
+    if millis >= 1000.0 {
+        format!("{:.2} s", millis / 1000.0)
+    } else {
+        format!("{millis:.1} ms")
+    }
+}
+
+/// Runs `plan` in a worktree of `baseline_rev` and in `cwd`, which must be inside a git
+/// repository, and compares the timings.
+pub async fn compare(
+    cwd: &Path,
+    baseline_rev: &str,
+    plan: &BenchPlan,
+) -> Result<BenchComparison, String> {
+    if plan.runs < 2 {
+        return Err("a benchmark needs at least 2 runs".to_string());
+    }
+    // The baseline may come from the model, so git must never read it as an option.
+    if baseline_rev.trim().is_empty() || baseline_rev.starts_with('-') {
+        return Err(format!("`{baseline_rev}` is not a valid revision"));
+    }
+    let root = PathBuf::from(git(cwd, &["rev-parse", "--show-toplevel"]).await?);
+    let relative = cwd
+        .canonicalize()
+        .ok()
+        .and_then(|cwd| {
+            let root = root.canonicalize().ok()?;
+            cwd.strip_prefix(&root).ok().map(Path::to_path_buf)
+        })
+        .unwrap_or_default();
+    git(
+        &root,
+        &[
+            "rev-parse",
+            "--verify",
+            "--end-of-options",
+            &format!("{baseline_rev}^{{commit}}"),
+        ],
+    )
+    .await
+    .map_err(|_| format!("`{baseline_rev}` is not a commit in this repository"))?;
+
+    let parent = tempfile::Builder::new()
+        .prefix("codex-bench-")
+        .tempdir()
+        .map_err(|err| format!("failed to create a temporary directory: {err}"))?;
+    let worktree = parent.path().join("baseline");
+    let worktree_arg = worktree.to_string_lossy().into_owned();
+    git(
+        &root,
+        &[
+            "worktree",
+            "add",
+            "--detach",
+            &worktree_arg,
+            "--end-of-options",
+            baseline_rev,
+        ],
+    )
+    .await?;
+    let samples = run_alternating(&worktree.join(&relative), cwd, plan).await;
+    // Remove the worktree even when a run failed; a stale entry would block the next benchmark.
+    let removed = git(&root, &["worktree", "remove", "--force", &worktree_arg]).await;
+    let (baseline, candidate) = samples?;
+    removed?;
+
+    match (
+        SeriesStats::from_samples(&baseline),
+        SeriesStats::from_samples(&candidate),
+    ) {
+        (Some(baseline), Some(candidate)) => Ok(BenchComparison::new(
+            plan.command.clone(),
+            baseline_rev.to_string(),
+            baseline,
+            candidate,
+        )),
+        _ => Err("the benchmark produced no timings".to_string()),
+    }
+}
+
+async fn run_alternating(
+    baseline_dir: &Path,
+    candidate_dir: &Path,
+    plan: &BenchPlan,
+) -> Result<(Vec<Duration>, Vec<Duration>), String> {
+    for _ in 0..plan.warmup {
+        run_once(baseline_dir, plan)
+            .await
+            .map_err(|err| format!("baseline warmup {err}"))?;
+        run_once(candidate_dir, plan)
+            .await
+            .map_err(|err| format!("working tree warmup {err}"))?;
+    }
+    let mut baseline = Vec::with_capacity(plan.runs);
+    let mut candidate = Vec::with_capacity(plan.runs);
+    for _ in 0..plan.runs {
+        baseline.push(
+            run_once(baseline_dir, plan)
+                .await
+                .map_err(|err| format!("baseline run {err}"))?,
+        );
+        candidate.push(
+            run_once(candidate_dir, plan)
+                .await
+                .map_err(|err| format!("working tree run {err}"))?,
+        );
+    }
+    Ok((baseline, candidate))
+}
+
+/// Wall-clock time of one successful run of the command.
+async fn run_once(dir: &Path, plan: &BenchPlan) -> Result<Duration, String> {
+    let mut command = shell_command(&plan.command);
+    command
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    // The shell gets its own process group so that a timeout also stops the processes it started.
+    #[cfg(unix)]
+    command.process_group(0);
+    let started = Instant::now();
+    let child = command
+        .spawn()
+        .map_err(|err| format!("failed to start: {err}"))?;
+    let _process_group = child.id().map(KillProcessGroupOnDrop::new);
+    let output = tokio::time::timeout(plan.timeout, child.wait_with_output())
+        .await
+        .map_err(|_| format!("timed out after {}s", plan.timeout.as_secs()))?
+        .map_err(|err| format!("failed to run: {err}"))?;
+    let elapsed = started.elapsed();
+    if output.status.success() {
+        return Ok(elapsed);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines = stderr.lines().collect::<Vec<_>>();
+    let tail = lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n");
+    Err(format!("failed ({}):\n{tail}", output.status))
+}
+
+/// The argv that runs `command` through the platform shell.
+pub fn shell_argv(command: &str) -> Vec<String> {
+    if cfg!(windows) {
+        vec!["cmd".to_string(), "/C".to_string(), command.to_string()]
+    } else {
+        vec!["sh".to_string(), "-c".to_string(), command.to_string()]
+    }
+}
+
+fn shell_command(command: &str) -> Command {
+    let argv = shell_argv(command);
+    let mut shell = Command::new(&argv[0]);
+    shell.args(&argv[1..]);
+    shell
+}
+
+async fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|err| format!("failed to run git: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,147 @@
+//! Summary statistics for a series of timings and Welch's t-test between two series.
+
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Two-sided 95% critical values of Student's t distribution for 1 to 30 degrees of freedom.
+const T_CRITICAL_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// Timings of one side of a comparison, in milliseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SeriesStats {
+    pub runs: usize,
+    pub mean_ms: f64,
+    pub median_ms: f64,
+    /// Sample standard deviation; zero for a single run.
+    pub stddev_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+}
+
+impl SeriesStats {
+    /// Summarizes `samples`, or returns `None` when there are none.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut millis = samples
+            .iter()
+            .map(|sample| sample.as_nanos() as f64 / 1_000_000.0)
+            .collect::<Vec<_>>();
+        millis.sort_by(f64::total_cmp);
+        let runs = millis.len();
+        let mean = millis.iter().sum::<f64>() / runs as f64;
+        let median = if runs % 2 == 0 {
+            (millis[runs / 2 - 1] + millis[runs / 2]) / 2.0
+        } else {
+            millis[runs / 2]
+        };
+        let variance = if runs > 1 {
+            millis
+                .iter()
+                .map(|value| (value - mean).powi(2))
+                .sum::<f64>()
+                / (runs - 1) as f64
+        } else {
+            0.0
+        };
+        Some(Self {
+            runs,
+            mean_ms: mean,
+            median_ms: median,
+            stddev_ms: variance.sqrt(),
+            min_ms: millis[0],
+            max_ms: millis[runs - 1],
+        })
+    }
+
+    fn variance_of_mean(&self) -> f64 {
+        self.stddev_ms.powi(2) / self.runs as f64
+    }
+}
+
+/// Whether the candidate is faster or slower than the baseline at 95% confidence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Faster,
+    Slower,
+    NoSignificantChange,
+}
+
+/// Welch's t-test on the difference of the means, which does not assume equal variances.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct WelchTest {
+    pub(crate) t_statistic: f64,
+    /// Half-width of the 95% confidence interval of `candidate - baseline`, in milliseconds.
+    pub(crate) margin_ms: f64,
+    pub(crate) verdict: Verdict,
+}
+
+pub(crate) fn welch_test(baseline: &SeriesStats, candidate: &SeriesStats) -> WelchTest {
+    let difference = candidate.mean_ms - baseline.mean_ms;
+    let baseline_variance = baseline.variance_of_mean();
+    let candidate_variance = candidate.variance_of_mean();
+    let standard_error = (baseline_variance + candidate_variance).sqrt();
+    if standard_error == 0.0 {
+        // Both series are perfectly steady, so any difference at all is real.
+        let verdict = match difference.total_cmp(&0.0) {
+            std::cmp::Ordering::Less => Verdict::Faster,
+            std::cmp::Ordering::Greater => Verdict::Slower,
+            std::cmp::Ordering::Equal => Verdict::NoSignificantChange,
+        };
+        return WelchTest {
+            t_statistic: 0.0,
+            margin_ms: 0.0,
+            verdict,
+        };
+    }
+
+    let degrees_of_freedom = (baseline_variance + candidate_variance).powi(2)
+        / (degrees_term(baseline_variance, baseline.runs)
+            + degrees_term(candidate_variance, candidate.runs));
+    let critical = t_critical_95(degrees_of_freedom);
+    let t_statistic = difference / standard_error;
+    let verdict = if t_statistic.abs() <= critical {
+        Verdict::NoSignificantChange
+    } else if t_statistic < 0.0 {
+        Verdict::Faster
+    } else {
+        Verdict::Slower
+    };
+    WelchTest {
+        t_statistic,
+        margin_ms: critical * standard_error,
+        verdict,
+    }
+}
+
+fn degrees_term(variance_of_mean: f64, runs: usize) -> f64 {
+    if runs < 2 {
+        return 0.0;
+    }
+    variance_of_mean.powi(2) / (runs - 1) as f64
+}
+
+/// Critical value for `degrees_of_freedom`, rounded down to the nearest tabulated value so the
+/// test stays conservative.
+fn t_critical_95(degrees_of_freedom: f64) -> f64 {
+    if !degrees_of_freedom.is_finite() {
+        return 1.980;
+    }
+    match degrees_of_freedom.floor() as usize {
+        0 | 1 => T_CRITICAL_95[0],
+        df @ 2..=30 => T_CRITICAL_95[df - 1],
+        31..=39 => T_CRITICAL_95[29],
+        40..=59 => 2.021,
+        60..=119 => 2.000,
+        _ => 1.980,
+    }
+}
@@ -0,0 +1,265 @@
+use std::time::Duration;
+
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+fn millis(values: &[u64]) -> Vec<Duration> {
+    values.iter().copied().map(Duration::from_millis).collect()
+}
+
+fn stats(values: &[u64]) -> SeriesStats {
+    SeriesStats::from_samples(&millis(values)).unwrap()
+}
+
+#[test]
+fn series_stats_summarize_samples() {
+    assert_eq!(
+        stats(&[30, 10, 20, 40]),
+        SeriesStats {
+            runs: 4,
+            mean_ms: 25.0,
+            median_ms: 25.0,
+            stddev_ms: (500.0_f64 / 3.0).sqrt(),
+            min_ms: 10.0,
+            max_ms: 40.0,
+        }
+    );
+    assert_eq!(stats(&[7]).stddev_ms, 0.0);
+    assert_eq!(SeriesStats::from_samples(&[]), None);
+}
+
+#[test]
+fn clearly_slower_candidate_is_significant() {
+    let comparison = BenchComparison::new(
+        "make bench".to_string(),
+        "HEAD".to_string(),
+        stats(&[100, 102, 98, 101, 99]),
+        stats(&[150, 152, 148, 151, 149]),
+    );
+
+    assert_eq!(comparison.verdict, Verdict::Slower);
+    assert_eq!(comparison.change_pct, 50.0);
+    assert!(comparison.t_statistic > 0.0);
+    assert!(comparison.change_margin_pct > 0.0 && comparison.change_margin_pct < 5.0);
+}
+
+#[test]
+fn overlapping_series_are_not_significant() {
+    let comparison = BenchComparison::new(
+        "make bench".to_string(),
+        "HEAD".to_string(),
+        stats(&[100, 130, 80, 120, 90]),
+        stats(&[95, 125, 85, 115, 100]),
+    );
+
+    assert_eq!(comparison.verdict, Verdict::NoSignificantChange);
+    assert!(comparison.change_margin_pct > comparison.change_pct.abs());
+}
+
+#[test]
+fn steady_series_compare_exactly() {
+    let same = BenchComparison::new(
+        "true".to_string(),
+        "HEAD".to_string(),
+        stats(&[10, 10, 10]),
+        stats(&[10, 10, 10]),
+    );
+    let faster = BenchComparison::new(
+        "true".to_string(),
+        "HEAD".to_string(),
+        stats(&[10, 10, 10]),
+        stats(&[9, 9, 9]),
+    );
+
+    assert_eq!(same.verdict, Verdict::NoSignificantChange);
+    assert_eq!(faster.verdict, Verdict::Faster);
+}
+
+#[test]
+fn render_shows_both_series_and_verdict() {
+    let comparison = BenchComparison::new(
+        "make bench".to_string(),
+        "main".to_string(),
+        stats(&[1000, 1000, 1000]),
+        stats(&[500, 500, 500]),
+    );
+
+    assert_eq!(
+        comparison.render(),
+        "\
+Benchmark: make bench
+main vs working tree, 3 runs each
+
+                      mean      median      stddev         min         max
+baseline            1.00 s      1.00 s      0.0 ms      1.00 s      1.00 s
+working tree      500.0 ms    500.0 ms      0.0 ms    500.0 ms    500.0 ms
+
+Change: -50.0% ± 0.0% (faster at 95% confidence)
+"
+    );
+}
+
+#[tokio::test]
+async fn compare_rejects_a_single_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let plan = BenchPlan {
+        command: "true".to_string(),
+        runs: 1,
+        warmup: 0,
+        timeout: Duration::from_secs(10),
+    };
+
+    assert_eq!(
+        compare(dir.path(), "HEAD", &plan).await,
+        Err("a benchmark needs at least 2 runs".to_string())
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn timeout_kills_processes_started_by_the_command() {
+    let dir = tempfile::tempdir().unwrap();
+    let plan = BenchPlan {
+        command: "sleep 30 & echo $! > sleep.pid; wait".to_string(),
+        runs: 2,
+        warmup: 0,
+        timeout: Duration::from_secs(1),
+    };
+
+    assert_eq!(
+        run_once(dir.path(), &plan).await,
+        Err("timed out after 1s".to_string())
+    );
+
+    let pid = std::fs::read_to_string(dir.path().join("sleep.pid")).unwrap();
+    let sleep_is_alive = || {
+        std::process::Command::new("kill")
+            .args(["-0", pid.trim()])
+            .status()
+            .unwrap()
+            .success()
+    };
+    // The orphaned sleep is reaped by init shortly after it is killed.
+    for _ in 0..50 {
+        if !sleep_is_alive() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("the background sleep outlived the timed out run");
+}
+
+#[cfg(unix)]
+mod git_repo {
+    use std::fs;
+    use std::process::Command as StdCommand;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = StdCommand::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed: {output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    fn repo_with_commit() -> tempfile::TempDir {
+        let repo = tempfile::tempdir().unwrap();
+        git(repo.path(), &["init", "--quiet"]);
+        fs::create_dir(repo.path().join("crate")).unwrap();
+        fs::write(repo.path().join("crate/version"), "baseline\n").unwrap();
+        git(repo.path(), &["add", "."]);
+        git(repo.path(), &["commit", "--quiet", "-m", "baseline"]);
+        fs::write(repo.path().join("crate/version"), "candidate\n").unwrap();
+        repo
+    }
+
+    #[tokio::test]
+    async fn compare_runs_both_trees_alternately_and_cleans_up() {
+        let repo = repo_with_commit();
+        let log = tempfile::NamedTempFile::new().unwrap();
+        let plan = BenchPlan {
+            command: format!("cat version >> '{}'", log.path().display()),
+            runs: 3,
+            warmup: 1,
+            timeout: Duration::from_secs(30),
+        };
+
+        let comparison = compare(&repo.path().join("crate"), "HEAD", &plan)
+            .await
+            .unwrap();
+
+        assert_eq!(comparison.baseline.runs, 3);
+        assert_eq!(comparison.candidate.runs, 3);
+        assert_eq!(comparison.baseline_rev, "HEAD");
+        let versions = fs::read_to_string(log.path()).unwrap();
+        assert_eq!(
+            versions.lines().collect::<Vec<_>>(),
+            ["baseline", "candidate"].repeat(4)
+        );
+        assert_eq!(
+            fs::read_to_string(repo.path().join("crate/version")).unwrap(),
+            "candidate\n"
+        );
+        let worktrees = git(repo.path(), &["worktree", "list", "--porcelain"]);
+        assert_eq!(worktrees.matches("worktree ").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn failing_command_reports_stderr_and_cleans_up() {
+        let repo = repo_with_commit();
+        let plan = BenchPlan {
+            command: "echo broken >&2; exit 3".to_string(),
+            runs: 2,
+            warmup: 0,
+            timeout: Duration::from_secs(30),
+        };
+
+        let err = compare(repo.path(), "HEAD", &plan).await.unwrap_err();
+
+        assert!(err.starts_with("baseline run failed"), "{err}");
+        assert!(err.ends_with("\nbroken"), "{err}");
+        let worktrees = git(repo.path(), &["worktree", "list", "--porcelain"]);
+        assert_eq!(worktrees.matches("worktree ").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn unknown_baseline_is_rejected() {
+        let repo = repo_with_commit();
+        let plan = BenchPlan {
+            command: "true".to_string(),
+            runs: 2,
+            warmup: 0,
+            timeout: Duration::from_secs(30),
+        };
+
+        assert_eq!(
+            compare(repo.path(), "no-such-branch", &plan).await,
+            Err("`no-such-branch` is not a commit in this repository".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn option_like_baseline_is_rejected() {
+        let repo = repo_with_commit();
+        let plan = BenchPlan {
+            command: "true".to_string(),
+            runs: 2,
+            warmup: 0,
+            timeout: Duration::from_secs(30),
+        };
+
+        assert_eq!(
+            compare(repo.path(), "--output=stolen", &plan).await,
+            Err("`--output=stolen` is not a valid revision".to_string())
+        );
+        assert!(!repo.path().join("stolen").exists());
+    }
+}
@@ -0,0 +1,201 @@
+//! Captures the OS, toolchain versions, and build-related environment variables of the machine
+//! Codex runs on, for bug reports and for diagnosing "works on my machine" problems.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::process::Command;
+
+/// Each version probe is abandoned after this long, so a hung daemon cannot stall a snapshot.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Environment variables recorded when set. Anything else is left out, since the environment
+/// commonly carries credentials.
+const ENV_VARS: &[&str] = &[
+    "CARGO_HOME",
+    "CARGO_TARGET_DIR",
+    "CC",
+    "CI",
+    "CONDA_DEFAULT_ENV",
+    "CXX",
+    "DOCKER_HOST",
+    "GOFLAGS",
+    "GOPATH",
+    "GOROOT",
+    "JAVA_HOME",
+    "LANG",
+    "LC_ALL",
+    "NODE_ENV",
+    "PATH",
+    "PYTHONPATH",
+    "RUSTFLAGS",
+    "RUSTUP_TOOLCHAIN",
+    "SHELL",
+    "TERM",
+    "VIRTUAL_ENV",
+];
+
+struct ToolchainProbe {
+    name: &'static str,
+    /// Programs tried in order; the first one found on `PATH` is run.
+    programs: &'static [&'static str],
+    args: &'static [&'static str],
+}
+
+const TOOLCHAIN_PROBES: &[ToolchainProbe] = &[
+    ToolchainProbe {
+        name: "rustc",
+        programs: &["rustc"],
+        args: &["--version"],
+    },
+    ToolchainProbe {
+        name: "cargo",
+        programs: &["cargo"],
+        args: &["--version"],
+    },
+    ToolchainProbe {
+        name: "node",
+        programs: &["node"],
+        args: &["--version"],
+    },
+    ToolchainProbe {
+        name: "npm",
+        programs: &["npm"],
+        args: &["--version"],
+    },
+    ToolchainProbe {
+        name: "python",
+        programs: &["python3", "python"],
+        args: &["--version"],
+    },
+    ToolchainProbe {
+        name: "go",
+        programs: &["go"],
+        args: &["version"],
+    },
+    ToolchainProbe {
+        name: "docker",
+        programs: &["docker"],
+        args: &["--version"],
+    },
+    ToolchainProbe {
+        name: "git",
+        programs: &["git"],
+        args: &["--version"],
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EnvSnapshot {
+    pub os: OsSnapshot,
+    pub toolchains: Vec<ToolchainVersion>,
+    /// Build-related environment variables that are set, from a fixed allowlist.
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OsSnapshot {
+    /// OS name and version, such as `Ubuntu 24.04 (noble) [64-bit]`.
+    pub name: String,
+    pub family: String,
+    pub arch: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ToolchainVersion {
+    pub name: String,
+    /// First line of the tool's version output, or `None` when it is not installed or failed.
+    pub version: Option<String>,
+}
+
+impl EnvSnapshot {
+    /// Takes a snapshot of this machine. Version probes run in `cwd`, so per-directory toolchain
+    /// pins such as `rust-toolchain.toml` or `.nvmrc` shims are respected.
+    pub async fn capture(cwd: &Path) -> Self {
+        let info = os_info::get();
+        let toolchains =
+            futures::future::join_all(TOOLCHAIN_PROBES.iter().map(|probe| async move {
+                ToolchainVersion {
+                    name: probe.name.to_string(),
+                    version: probe_version(probe, cwd).await,
+                }
+            }))
+            .await;
+        Self {
+            os: OsSnapshot {
+                name: info.to_string(),
+                family: std::env::consts::OS.to_string(),
+                arch: std::env::consts::ARCH.to_string(),
+            },
+            toolchains,
+            env: env_from_pairs(std::env::vars()),
+        }
+    }
+
+    /// One `label: value` line per fact, for showing the snapshot to a person.
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("os: {} ({})", self.os.name, self.os.arch)];
+        for toolchain in &self.toolchains {
+            lines.push(format!(
+                "{}: {}",
+                toolchain.name,
+                toolchain.version.as_deref().unwrap_or("not found")
+            ));
+        }
+        for (name, value) in &self.env {
+            lines.push(format!("${name}: {value}"));
+        }
+        lines
+    }
+}
+
+async fn probe_version(probe: &ToolchainProbe, cwd: &Path) -> Option<String> {
+    // Resolving through `which` finds `npm.cmd` and friends on Windows.
+    let program = probe
+        .programs
+        .iter()
+        .find_map(|program| which::which(program).ok())?;
+    let mut command = Command::new(program);
+    command
+        .args(probe.args)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    let output = tokio::time::timeout(PROBE_TIMEOUT, command.output())
+        .await
+        .ok()?
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    version_line(
+        &String::from_utf8_lossy(&output.stdout),
+        &String::from_utf8_lossy(&output.stderr),
+    )
+}
+
+/// Returns the first non-empty line of `stdout`, or of `stderr` for tools that print their
+/// version there (older Pythons do).
+fn version_line(stdout: &str, stderr: &str) -> Option<String> {
+    [stdout, stderr]
+        .into_iter()
+        .flat_map(str::lines)
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+fn env_from_pairs(pairs: impl IntoIterator<Item = (String, String)>) -> BTreeMap<String, String> {
+    pairs
+        .into_iter()
+        .filter(|(name, _)| ENV_VARS.contains(&name.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+#[test]
+fn version_line_prefers_stdout_and_skips_blank_lines() {
+    assert_eq!(
+        version_line("\nrustc 1.89.0 (29483883e 2025-08-04)\n", ""),
+        Some("rustc 1.89.0 (29483883e 2025-08-04)".to_string())
+    );
+    assert_eq!(
+        version_line("", "Python 2.7.18\n"),
+        Some("Python 2.7.18".to_string())
+    );
+    assert_eq!(version_line(" \n", ""), None);
+}
+
+#[test]
+fn env_from_pairs_keeps_only_allowlisted_variables() {
+    let env = env_from_pairs([
+        ("PATH".to_string(), "/usr/bin".to_string()),
+        ("OPENAI_API_KEY".to_string(), "sk-secret".to_string()),
+        ("RUSTUP_TOOLCHAIN".to_string(), "nightly".to_string()),
+    ]);
+
+    assert_eq!(
+        env,
+        BTreeMap::from([
+            ("PATH".to_string(), "/usr/bin".to_string()),
+            ("RUSTUP_TOOLCHAIN".to_string(), "nightly".to_string()),
+        ])
+    );
+}
+
+#[test]
+fn summary_lines_mark_missing_toolchains() {
+    let snapshot = EnvSnapshot {
+        os: OsSnapshot {
+            name: "Ubuntu 24.04 (noble) [64-bit]".to_string(),
+            family: "linux".to_string(),
+            arch: "x86_64".to_string(),
+        },
+        toolchains: vec![
+            ToolchainVersion {
+                name: "rustc".to_string(),
+                version: Some("rustc 1.89.0".to_string()),
+            },
+            ToolchainVersion {
+                name: "docker".to_string(),
+                version: None,
+            },
+        ],
+        env: BTreeMap::from([("CI".to_string(), "true".to_string())]),
+    };
+
+    assert_eq!(
+        snapshot.summary_lines(),
+        vec![
+            "os: Ubuntu 24.04 (noble) [64-bit] (x86_64)".to_string(),
+            "rustc: rustc 1.89.0".to_string(),
+            "docker: not found".to_string(),
+            "$CI: true".to_string(),
+        ]
+    );
+}
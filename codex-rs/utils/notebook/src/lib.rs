@@ -0,0 +1,316 @@
+//! Reads and edits Jupyter notebooks (`.ipynb`) as a list of cells.
+//!
+//! The notebook is kept as a JSON value so fields this module does not know about survive an
+//! edit unchanged. [`Notebook::to_ipynb`] writes the same layout Jupyter does (one-space indent,
+//! trailing newline), so an edit only changes the lines of the cells it touches.
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+
+/// Output text kept per cell; longer outputs are cut and marked as truncated.
+pub const MAX_OUTPUT_CHARS: usize = 2_000;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum NotebookError {
+    #[error("not a valid notebook: {0}")]
+    Invalid(String),
+    #[error("cell index {index} is out of range; the notebook has {len} cells")]
+    CellOutOfRange { index: usize, len: usize },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CellType {
+    Code,
+    Markdown,
+    Raw,
+}
+
+impl CellType {
+    fn as_str(self) -> &'static str {
+        match self {
+            CellType::Code => "code",
+            CellType::Markdown => "markdown",
+            CellType::Raw => "raw",
+        }
+    }
+}
+
+/// One cell as presented to the model: its source and a text summary of its outputs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct NotebookCell {
+    pub index: usize,
+    pub id: Option<String>,
+    pub cell_type: String,
+    pub source: String,
+    pub execution_count: Option<i64>,
+    /// Text of each output; images and other rich data are shown as `[image/png]` placeholders.
+    pub outputs: Vec<String>,
+    /// True when the outputs were cut to [`MAX_OUTPUT_CHARS`].
+    pub outputs_truncated: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notebook {
+    value: Value,
+}
+
+impl Notebook {
+    pub fn parse(text: &str) -> Result<Self, NotebookError> {
+        let value: Value =
+            serde_json::from_str(text).map_err(|err| NotebookError::Invalid(err.to_string()))?;
+        if !value.get("cells").is_some_and(Value::is_array) {
+            return Err(NotebookError::Invalid(
+                "missing top-level `cells` array".to_string(),
+            ));
+        }
+        Ok(Self { value })
+    }
+
+    /// Serializes the notebook the way Jupyter writes it.
+    pub fn to_ipynb(&self) -> String {
+        let mut out = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(b" ");
+        let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+        // Serializing a `Value` into memory cannot fail.
+        let _ = self.value.serialize(&mut serializer);
+        let mut text = String::from_utf8_lossy(&out).into_owned();
+        text.push('\n');
+        text
+    }
+
+    /// The kernel language, such as `python`, when the notebook records one.
+    pub fn language(&self) -> Option<&str> {
+        let metadata = self.value.get("metadata")?;
+        metadata
+            .pointer("/language_info/name")
+            .or_else(|| metadata.pointer("/kernelspec/language"))
+            .and_then(Value::as_str)
+    }
+
+    pub fn cells(&self) -> Vec<NotebookCell> {
+        self.raw_cells()
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                let (outputs, outputs_truncated) = cell
+                    .get("outputs")
+                    .and_then(Value::as_array)
+                    .map(|outputs| summarize_outputs(outputs))
+                    .unwrap_or_default();
+                NotebookCell {
+                    index,
+                    id: cell.get("id").and_then(Value::as_str).map(str::to_string),
+                    cell_type: cell
+                        .get("cell_type")
+                        .and_then(Value::as_str)
+                        .unwrap_or("code")
+                        .to_string(),
+                    source: cell.get("source").map(multiline_text).unwrap_or_default(),
+                    execution_count: cell.get("execution_count").and_then(Value::as_i64),
+                    outputs,
+                    outputs_truncated,
+                }
+            })
+            .collect()
+    }
+
+    /// Replaces the source of the cell at `index`, keeping its outputs and metadata.
+    pub fn set_source(&mut self, index: usize, source: &str) -> Result<(), NotebookError> {
+        let cell = self.cell_mut(index)?;
+        if let Value::Object(cell) = cell {
+            cell.insert("source".to_string(), source_lines(source));
+        }
+        Ok(())
+    }
+
+    /// Changes the type of the cell at `index`, adding or dropping code-only fields.
+    pub fn set_cell_type(
+        &mut self,
+        index: usize,
+        cell_type: CellType,
+    ) -> Result<(), NotebookError> {
+        let cell = self.cell_mut(index)?;
+        if let Value::Object(cell) = cell {
+            cell.insert(
+                "cell_type".to_string(),
+                Value::String(cell_type.as_str().to_string()),
+            );
+            if cell_type == CellType::Code {
+                cell.entry("execution_count").or_insert(Value::Null);
+                cell.entry("outputs").or_insert(Value::Array(Vec::new()));
+            } else {
+                cell.remove("execution_count");
+                cell.remove("outputs");
+            }
+        }
+        Ok(())
+    }
+
+    /// Inserts a new cell before `index`; `index` equal to the cell count appends.
+    pub fn insert_cell(
+        &mut self,
+        index: usize,
+        cell_type: CellType,
+        source: &str,
+    ) -> Result<(), NotebookError> {
+        let with_ids = self.uses_cell_ids();
+        let cells = self.raw_cells_mut();
+        if index > cells.len() {
+            return Err(NotebookError::CellOutOfRange {
+                index,
+                len: cells.len(),
+            });
+        }
+        let mut cell = Map::new();
+        cell.insert(
+            "cell_type".to_string(),
+            Value::String(cell_type.as_str().to_string()),
+        );
+        if cell_type == CellType::Code {
+            cell.insert("execution_count".to_string(), Value::Null);
+        }
+        if with_ids {
+            let id = uuid::Uuid::new_v4().simple().to_string();
+            cell.insert("id".to_string(), Value::String(id[..8].to_string()));
+        }
+        cell.insert("metadata".to_string(), Value::Object(Map::new()));
+        if cell_type == CellType::Code {
+            cell.insert("outputs".to_string(), Value::Array(Vec::new()));
+        }
+        cell.insert("source".to_string(), source_lines(source));
+        cells.insert(index, Value::Object(cell));
+        Ok(())
+    }
+
+    pub fn delete_cell(&mut self, index: usize) -> Result<(), NotebookError> {
+        self.cell_mut(index)?;
+        self.raw_cells_mut().remove(index);
+        Ok(())
+    }
+
+    /// Renders the notebook as plain text, one `# %% [type] cell N` section per cell with its
+    /// outputs as `# >` comment lines. Used to show notebook diffs cell by cell.
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        for cell in self.cells() {
+            if cell.index > 0 {
+                out.push('\n');
+            }
+            out.push_str(&format!("# %% [{}] cell {}\n", cell.cell_type, cell.index));
+            out.push_str(&cell.source);
+            if !cell.source.is_empty() && !cell.source.ends_with('\n') {
+                out.push('\n');
+            }
+            for output in &cell.outputs {
+                for line in output.lines() {
+                    out.push_str(&format!("# > {line}\n"));
+                }
+            }
+        }
+        out
+    }
+
+    fn raw_cells(&self) -> &[Value] {
+        self.value
+            .get("cells")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn raw_cells_mut(&mut self) -> &mut Vec<Value> {
+        match self.value.get_mut("cells") {
+            Some(Value::Array(cells)) => cells,
+            // `parse` only accepts notebooks with a `cells` array.
+            _ => unreachable!("notebook without a cells array"),
+        }
+    }
+
+    fn cell_mut(&mut self, index: usize) -> Result<&mut Value, NotebookError> {
+        let cells = self.raw_cells_mut();
+        let len = cells.len();
+        cells
+            .get_mut(index)
+            .ok_or(NotebookError::CellOutOfRange { index, len })
+    }
+
+    /// Cell ids are required from nbformat 4.5 on.
+    fn uses_cell_ids(&self) -> bool {
+        let major = self.value.get("nbformat").and_then(Value::as_u64);
+        let minor = self.value.get("nbformat_minor").and_then(Value::as_u64);
+        major.is_some_and(|major| major > 4)
+            || (major == Some(4) && minor.is_some_and(|minor| minor >= 5))
+            || self.raw_cells().iter().any(|cell| cell.get("id").is_some())
+    }
+}
+
+/// Notebook text fields are either a string or a list of lines.
+fn multiline_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(lines) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+/// Splits `source` into the list-of-lines form Jupyter writes.
+fn source_lines(source: &str) -> Value {
+    Value::Array(
+        source
+            .split_inclusive('\n')
+            .map(|line| Value::String(line.to_string()))
+            .collect(),
+    )
+}
+
+fn summarize_outputs(outputs: &[Value]) -> (Vec<String>, bool) {
+    let mut summaries = Vec::new();
+    let mut remaining = MAX_OUTPUT_CHARS;
+    for output in outputs {
+        let text = match output.get("output_type").and_then(Value::as_str) {
+            Some("stream") => output.get("text").map(multiline_text).unwrap_or_default(),
+            Some("error") => format!(
+                "{}: {}",
+                output
+                    .get("ename")
+                    .and_then(Value::as_str)
+                    .unwrap_or("Error"),
+                output
+                    .get("evalue")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+            ),
+            Some("execute_result" | "display_data") => rich_output_text(output),
+            _ => continue,
+        };
+        let chars = text.chars().count();
+        if chars > remaining {
+            summaries.push(text.chars().take(remaining).collect());
+            return (summaries, true);
+        }
+        remaining -= chars;
+        summaries.push(text);
+    }
+    (summaries, false)
+}
+
+/// Prefers `text/plain`; other mime types are listed as placeholders.
+fn rich_output_text(output: &Value) -> String {
+    let Some(data) = output.get("data").and_then(Value::as_object) else {
+        return String::new();
+    };
+    if let Some(text) = data.get("text/plain") {
+        return multiline_text(text);
+    }
+    data.keys()
+        .map(|mime| format!("[{mime}]"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,144 @@
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+const NOTEBOOK: &str = r##"{
+ "cells": [
+  {
+   "cell_type": "markdown",
+   "id": "intro",
+   "metadata": {},
+   "source": [
+    "# Title"
+   ]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": 1,
+   "id": "load",
+   "metadata": {},
+   "outputs": [
+    {
+     "name": "stdout",
+     "output_type": "stream",
+     "text": [
+      "loaded\n"
+     ]
+    },
+    {
+     "data": {
+      "image/png": "iVBORw0KGgo="
+     },
+     "metadata": {},
+     "output_type": "display_data"
+    }
+   ],
+   "source": [
+    "import pandas as pd\n",
+    "print('loaded')"
+   ]
+  }
+ ],
+ "metadata": {
+  "language_info": {
+   "name": "python"
+  }
+ },
+ "nbformat": 4,
+ "nbformat_minor": 5
+}
+"##;
+
+#[test]
+fn unchanged_notebooks_round_trip_byte_for_byte() {
+    let notebook = Notebook::parse(NOTEBOOK).expect("parse");
+
+    assert_eq!(notebook.to_ipynb(), NOTEBOOK);
+    assert_eq!(notebook.language(), Some("python"));
+}
+
+#[test]
+fn cells_show_source_and_output_summaries() {
+    let notebook = Notebook::parse(NOTEBOOK).expect("parse");
+
+    assert_eq!(
+        notebook.cells()[1],
+        NotebookCell {
+            index: 1,
+            id: Some("load".to_string()),
+            cell_type: "code".to_string(),
+            source: "import pandas as pd\nprint('loaded')".to_string(),
+            execution_count: Some(1),
+            outputs: vec!["loaded\n".to_string(), "[image/png]".to_string()],
+            outputs_truncated: false,
+        }
+    );
+}
+
+#[test]
+fn editing_a_cell_only_changes_its_source_lines() {
+    let mut notebook = Notebook::parse(NOTEBOOK).expect("parse");
+
+    notebook.set_source(0, "# New title").expect("set source");
+
+    assert_eq!(
+        notebook.to_ipynb(),
+        NOTEBOOK.replace("\"# Title\"", "\"# New title\"")
+    );
+}
+
+#[test]
+fn inserted_cells_get_ids_and_code_fields() {
+    let mut notebook = Notebook::parse(NOTEBOOK).expect("parse");
+
+    notebook
+        .insert_cell(2, CellType::Code, "x = 1\ny = 2\n")
+        .expect("insert");
+    notebook.delete_cell(0).expect("delete");
+
+    let cells = notebook.cells();
+    assert_eq!(cells.len(), 2);
+    assert_eq!(cells[1].source, "x = 1\ny = 2\n");
+    assert_eq!(cells[1].id.as_ref().map(String::len), Some(8));
+    assert_eq!(
+        Notebook::parse(&notebook.to_ipynb())
+            .expect("reparse")
+            .cells(),
+        cells
+    );
+}
+
+#[test]
+fn out_of_range_edits_are_rejected() {
+    let mut notebook = Notebook::parse(NOTEBOOK).expect("parse");
+
+    assert_eq!(
+        notebook.delete_cell(5),
+        Err(NotebookError::CellOutOfRange { index: 5, len: 2 })
+    );
+    assert_eq!(
+        notebook.insert_cell(3, CellType::Raw, ""),
+        Err(NotebookError::CellOutOfRange { index: 3, len: 2 })
+    );
+}
+
+#[test]
+fn render_text_lays_out_cells_with_outputs() {
+    let notebook = Notebook::parse(NOTEBOOK).expect("parse");
+
+    assert_eq!(
+        notebook.render_text(),
+        "# %% [markdown] cell 0\n# Title\n\n\
+         # %% [code] cell 1\nimport pandas as pd\nprint('loaded')\n# > loaded\n# > [image/png]\n"
+    );
+}
+
+#[test]
+fn documents_without_cells_are_rejected() {
+    assert_eq!(
+        Notebook::parse("{}"),
+        Err(NotebookError::Invalid(
+            "missing top-level `cells` array".to_string()
+        ))
+    );
+}
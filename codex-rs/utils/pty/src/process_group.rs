@@ -172,6 +172,22 @@ pub fn kill_process_group(_process_group_id: u32) -> io::Result<()> {
     Ok(())
 }
 
+/// Kills a process group when dropped (best-effort), so processes a command
+/// left running in the background do not outlive it.
+pub struct KillProcessGroupOnDrop(u32);
+
+impl KillProcessGroupOnDrop {
+    pub fn new(process_group_id: u32) -> Self {
+        Self(process_group_id)
+    }
+}
+
+impl Drop for KillProcessGroupOnDrop {
+    fn drop(&mut self) {
+        let _ = kill_process_group(self.0);
+    }
+}
+
 #[cfg(unix)]
 /// Kill the process group for a tokio child (best-effort).
 pub fn kill_child_process_group(child: &mut Child) -> io::Result<()> {
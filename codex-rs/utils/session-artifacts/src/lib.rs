@@ -0,0 +1,100 @@
+//! Registry of files the agent generated for a session that are not part of the repository, such
+//! as reports, plots, and generated data.
+//!
+//! Each session's artifacts are listed in `$CODEX_HOME/artifacts/<thread_id>.json`. The agent adds
+//! entries through the `artifacts.register` tool, the TUI lists them in `/artifacts`, and
+//! `codex share` includes them in the exported page.
+
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+const ARTIFACTS_DIR: &str = "artifacts";
+const SCRATCHPAD_DIR: &str = "scratch";
+
+/// A named file produced during a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SessionArtifact {
+    /// Short name that identifies the artifact within its session.
+    pub name: String,
+    /// Absolute path of the file.
+    pub path: PathBuf,
+    /// What the file contains, when the agent described it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Returns the file that lists the artifacts of `thread_id`.
+pub fn registry_path(codex_home: &Path, thread_id: &str) -> PathBuf {
+    codex_home
+        .join(ARTIFACTS_DIR)
+        .join(format!("{thread_id}.json"))
+}
+
+/// Returns the directory where tools save files they generate for `thread_id` before registering
+/// them.
+pub fn artifact_dir(codex_home: &Path, thread_id: &str) -> PathBuf {
+    codex_home.join(ARTIFACTS_DIR).join(thread_id)
+}
+
+/// Returns the scratch directory of `thread_id`, which sandboxed commands may always write to.
+pub fn scratchpad_dir(codex_home: &Path, thread_id: &str) -> PathBuf {
+    codex_home.join(SCRATCHPAD_DIR).join(thread_id)
+}
+
+/// Returns the directories Codex manages for `thread_id`: [`artifact_dir`] and
+/// [`scratchpad_dir`]. Files in them may be registered and shared although they are outside the
+/// workspace.
+pub fn managed_dirs(codex_home: &Path, thread_id: &str) -> [PathBuf; 2] {
+    [
+        artifact_dir(codex_home, thread_id),
+        scratchpad_dir(codex_home, thread_id),
+    ]
+}
+
+/// Returns the artifacts registered for `thread_id`, in registration order.
+pub fn load_artifacts(codex_home: &Path, thread_id: &str) -> io::Result<Vec<SessionArtifact>> {
+    let contents = match std::fs::read_to_string(registry_path(codex_home, thread_id)) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    serde_json::from_str(&contents).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+}
+
+/// Adds `artifact` to the registry of `thread_id`, replacing an earlier artifact with the same
+/// name, and returns the updated list.
+pub fn register_artifact(
+    codex_home: &Path,
+    thread_id: &str,
+    artifact: SessionArtifact,
+) -> io::Result<Vec<SessionArtifact>> {
+    let mut artifacts = load_artifacts(codex_home, thread_id)?;
+    match artifacts
+        .iter_mut()
+        .find(|existing| existing.name == artifact.name)
+    {
+        Some(existing) => *existing = artifact,
+        None => artifacts.push(artifact),
+    }
+
+    let path = registry_path(codex_home, thread_id);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let contents = serde_json::to_string_pretty(&artifacts)
+        .map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+    // Write a sibling file first so a reader never sees a half-written registry.
+    let staging = path.with_extension("json.tmp");
+    std::fs::write(&staging, contents)?;
+    std::fs::rename(&staging, &path)?;
+    Ok(artifacts)
+}
+
+#[cfg(test)]
+mod tests;
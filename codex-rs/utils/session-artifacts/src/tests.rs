@@ -0,0 +1,66 @@
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+fn artifact(name: &str, path: &str) -> SessionArtifact {
+    SessionArtifact {
+        name: name.to_string(),
+        path: PathBuf::from(path),
+        description: None,
+    }
+}
+
+#[test]
+fn load_artifacts_is_empty_before_anything_is_registered() {
+    let codex_home = tempfile::tempdir().expect("tempdir");
+
+    assert_eq!(
+        load_artifacts(codex_home.path(), "thread-1").expect("load artifacts"),
+        Vec::new()
+    );
+}
+
+#[test]
+fn register_artifact_appends_and_replaces_by_name() {
+    let codex_home = tempfile::tempdir().expect("tempdir");
+    register_artifact(
+        codex_home.path(),
+        "thread-1",
+        artifact("report", "/tmp/report.md"),
+    )
+    .expect("register report");
+    register_artifact(
+        codex_home.path(),
+        "thread-1",
+        artifact("plot", "/tmp/plot.png"),
+    )
+    .expect("register plot");
+    let updated = SessionArtifact {
+        description: Some("Latency by endpoint".to_string()),
+        ..artifact("report", "/tmp/report-v2.md")
+    };
+
+    let artifacts =
+        register_artifact(codex_home.path(), "thread-1", updated.clone()).expect("replace report");
+
+    assert_eq!(artifacts, vec![updated, artifact("plot", "/tmp/plot.png")]);
+    assert_eq!(
+        load_artifacts(codex_home.path(), "thread-1").expect("load artifacts"),
+        artifacts
+    );
+    assert_eq!(
+        load_artifacts(codex_home.path(), "thread-2").expect("load artifacts"),
+        Vec::new()
+    );
+}
+
+#[test]
+fn load_artifacts_rejects_a_corrupt_registry() {
+    let codex_home = tempfile::tempdir().expect("tempdir");
+    let path = registry_path(codex_home.path(), "thread-1");
+    std::fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+    std::fs::write(&path, "not json").expect("write registry");
+
+    let err = load_artifacts(codex_home.path(), "thread-1").expect_err("corrupt registry");
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}